@@ -1,5 +1,8 @@
+use std::path::Path;
+
 fn main() {
-    if !cfg!(target_os = "linux") {
+    let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap();
+    if target_os != "linux" && target_os != "macos" && target_os != "windows" {
         return;
     }
     let profile = std::env::var("PROFILE").unwrap();
@@ -10,10 +13,41 @@ fn main() {
         .file("cpp/api.cpp")
         .file("cpp/enums.cpp")
         .file("cpp/callback.cpp")
-        .file("decklink_sdk/include/DeckLinkAPIDispatch.cpp");
+        .file("cpp/platform.cpp");
+
+    if target_os == "windows" {
+        // Files generated with MIDL from "Win/include/DeckLinkAPI.idl" of the DeckLink SDK.
+        // The API is exposed as COM classes registered by Desktop Video.
+        let sdk_dir = Path::new("decklink_sdk/win/include");
+        if !sdk_dir.join("DeckLinkAPI_h.h").exists() {
+            panic!(
+                "DeckLink SDK headers for Windows not found. Generate them with \"midl /h DeckLinkAPI_h.h DeckLinkAPI.idl\" in {}.",
+                sdk_dir.display()
+            );
+        }
+        bridge.file(sdk_dir.join("DeckLinkAPI_i.c"));
+        println!("cargo:rustc-link-lib=ole32");
+        println!("cargo:rustc-link-lib=oleaut32");
+        println!("cargo:rerun-if-changed=decklink_sdk/win/include");
+    } else if target_os == "macos" {
+        // Headers from the "Mac/include" directory of the DeckLink SDK. Dispatch code
+        // loads DeckLinkAPI.framework installed with Desktop Video at runtime.
+        let sdk_dir = Path::new("decklink_sdk/mac/include");
+        if !sdk_dir.join("DeckLinkAPI.h").exists() {
+            panic!(
+                "DeckLink SDK headers for macOS not found. Copy the \"Mac/include\" directory of the DeckLink SDK to {}.",
+                sdk_dir.display()
+            );
+        }
+        bridge.file(sdk_dir.join("DeckLinkAPIDispatch.cpp"));
+        println!("cargo:rustc-link-lib=framework=CoreFoundation");
+        println!("cargo:rerun-if-changed=decklink_sdk/mac/include");
+    } else {
+        bridge.file("decklink_sdk/include/DeckLinkAPIDispatch.cpp");
+    }
 
     if profile == "debug" {
-        bridge.flag("-O");
+        bridge.flag(if target_os == "windows" { "/O2" } else { "-O" });
     }
 
     bridge.std("c++20").compile("decklink-bridge");
@@ -26,4 +60,6 @@ fn main() {
     println!("cargo:rerun-if-changed=cpp/callback.cpp");
     println!("cargo:rerun-if-changed=cpp/enums.h");
     println!("cargo:rerun-if-changed=cpp/enums.cpp");
+    println!("cargo:rerun-if-changed=cpp/platform.h");
+    println!("cargo:rerun-if-changed=cpp/platform.cpp");
 }
@@ -1,16 +1,16 @@
 #include "api.h"
 #include "callback.h"
-#include "decklink/decklink_sdk/include/DeckLinkAPI.h"
-#include "decklink/decklink_sdk/include/DeckLinkAPITypes.h"
 #include "enums.h"
+#include "platform.h"
 
 #include "decklink/src/api.rs.h"
 #include "decklink/src/enums.rs.h"
 #include <cstdint>
+#include <cstring>
 #include <stdexcept>
 
 rust::Vec<IDeckLinkPtr> get_decklinks() {
-  auto deckLinkIterator = CreateDeckLinkIteratorInstance();
+  auto deckLinkIterator = create_decklink_iterator();
   if (deckLinkIterator == nullptr) {
     throw std::runtime_error(
         "This application requires the DeckLink drivers installed.");
@@ -41,6 +41,18 @@ HResult decklink_input(IDeckLink *decklink, IDeckLinkInput *&input) {
   return static_cast<HResult>(result);
 }
 
+HResult decklink_output(IDeckLink *decklink, IDeckLinkOutput *&output) {
+  HRESULT result =
+      decklink->QueryInterface(IID_IDeckLinkOutput, (void **)&output);
+  return static_cast<HResult>(result);
+}
+
+HResult decklink_keyer(IDeckLink *decklink, IDeckLinkKeyer *&keyer) {
+  HRESULT result =
+      decklink->QueryInterface(IID_IDeckLinkKeyer, (void **)&keyer);
+  return static_cast<HResult>(result);
+}
+
 HResult decklink_profile_manager(IDeckLink *decklink,
                                  IDeckLinkProfileManager *&manager) {
   HRESULT result =
@@ -55,6 +67,12 @@ HResult decklink_configuration(IDeckLink *decklink,
   return static_cast<HResult>(result);
 }
 
+HResult decklink_status(IDeckLink *decklink, IDeckLinkStatus *&status) {
+  HRESULT result =
+      decklink->QueryInterface(IID_IDeckLinkStatus, (void **)&status);
+  return static_cast<HResult>(result);
+}
+
 void decklink_release(IDeckLink *decklink) { decklink->Release(); }
 
 //
@@ -63,7 +81,10 @@ void decklink_release(IDeckLink *decklink) { decklink->Release(); }
 
 HResult profile_attributes_flag(IDeckLinkProfileAttributes *attrs,
                                 FlagAttributeId id, bool &out) {
-  return static_cast<HResult>(attrs->GetFlag(flag_attribute_id(id), &out));
+  dlbool_t value = false;
+  auto result = attrs->GetFlag(flag_attribute_id(id), &value);
+  out = value;
+  return static_cast<HResult>(result);
 }
 
 HResult profile_attributes_integer(IDeckLinkProfileAttributes *attrs,
@@ -79,13 +100,18 @@ HResult profile_attributes_float(IDeckLinkProfileAttributes *attrs,
 HResult profile_attributes_string(IDeckLinkProfileAttributes *attrs,
                                   StringAttributeId id, rust::String &out,
                                   bool is_static) {
-  const char *value;
+  dlstring_t value;
   auto result = attrs->GetString(string_attribute_id(id), &value);
   if (result == S_OK) {
-    rust::String(value).swap(out);
+    dlstring_to_string(value).swap(out);
+#if defined(__APPLE__) || defined(_WIN32)
+    // CFStrings and BSTRs have to be released even if the value is static.
+    dlstring_free(value);
+#else
     if (!is_static) {
-      free(const_cast<char *>(value));
+      dlstring_free(value);
     }
+#endif
   }
   return static_cast<HResult>(result);
 }
@@ -106,12 +132,14 @@ HResult input_supports_video_mode(IDeckLinkInput *input, VideoConnection conn,
                                   DisplayModeType &actual_mode,
                                   bool &is_supported) {
   BMDDisplayMode bmd_actual_mode;
+  dlbool_t supported = false;
   auto result = input->DoesSupportVideoMode(
       from_video_connection(conn), from_display_mode_type(mode),
       from_pixel_format(pixel_format),
       from_video_input_conversion_mode(conversion_mode),
       from_supported_video_mode_flags(supported_mode_flags), &bmd_actual_mode,
-      &is_supported);
+      &supported);
+  is_supported = supported;
   if (result == S_OK && is_supported) {
     actual_mode = into_display_mode_type(bmd_actual_mode);
   }
@@ -129,7 +157,7 @@ HResult input_enable_video(IDeckLinkInput *input, DisplayModeType mode,
 HResult input_enable_audio(IDeckLinkInput *input, uint32_t sample_rate,
                            AudioSampleType sample_type, uint32_t channels) {
   auto result = input->EnableAudioInput(
-      sample_rate, static_cast<uint32_t>(sample_type), channels);
+      sample_rate, static_cast<BMDAudioSampleType>(sample_type), channels);
   return static_cast<HResult>(result);
 }
 
@@ -160,17 +188,183 @@ HResult input_flush_streams(IDeckLinkInput *input) {
 void input_release(IDeckLinkInput *input) { input->Release(); }
 
 //
-// IDeckLinkProfileManager
+// IDeckLinkOutput
 //
 
-HResult profile_manager_profiles(IDeckLinkProfileManager *manager,
-                                 rust::Vec<IDeckLinkProfilePtr> &profiles) {
-  IDeckLinkProfileIterator *profile_iterator;
-  auto profile_iter_result = manager->GetProfiles(&profile_iterator);
-  if (profile_iter_result != S_OK) {
-    return static_cast<HResult>(profile_iter_result);
+HResult output_enable_video(IDeckLinkOutput *output, DisplayModeType mode) {
+  auto result = output->EnableVideoOutput(from_display_mode_type(mode),
+                                          bmdVideoOutputFlagDefault);
+  return static_cast<HResult>(result);
+}
+
+HResult output_disable_video(IDeckLinkOutput *output) {
+  return static_cast<HResult>(output->DisableVideoOutput());
+}
+
+HResult output_schedule_video_frame(IDeckLinkOutput *output, int32_t width,
+                                    int32_t height, int32_t row_bytes,
+                                    PixelFormat pixel_format,
+                                    rust::Slice<const uint8_t> data,
+                                    int64_t display_time, int64_t duration,
+                                    int64_t time_scale) {
+  IDeckLinkMutableVideoFrame *frame = nullptr;
+  auto result = output->CreateVideoFrame(width, height, row_bytes,
+                                         from_pixel_format(pixel_format),
+                                         bmdFrameFlagDefault, &frame);
+  if (result != S_OK) {
+    return static_cast<HResult>(result);
+  }
+
+  IDeckLinkVideoBuffer *videoBuffer = nullptr;
+  result =
+      frame->QueryInterface(IID_IDeckLinkVideoBuffer, (void **)&videoBuffer);
+  if (result != S_OK) {
+    frame->Release();
+    return static_cast<HResult>(result);
+  }
+
+  result = videoBuffer->StartAccess(bmdBufferAccessWrite);
+  if (result == S_OK) {
+    void *buffer = nullptr;
+    result = videoBuffer->GetBytes(&buffer);
+    if (result == S_OK) {
+      std::memcpy(buffer, data.data(),
+                  static_cast<size_t>(row_bytes) * static_cast<size_t>(height));
+    }
+    videoBuffer->EndAccess(bmdBufferAccessWrite);
+  }
+  videoBuffer->Release();
+
+  if (result == S_OK) {
+    // The output holds its own reference until the frame is displayed.
+    result = output->ScheduleVideoFrame(frame, display_time, duration,
+                                        time_scale);
+  }
+  frame->Release();
+  return static_cast<HResult>(result);
+}
+
+HResult output_enable_audio(IDeckLinkOutput *output, uint32_t sample_rate,
+                            AudioSampleType sample_type, uint32_t channels) {
+  auto result = output->EnableAudioOutput(
+      sample_rate, static_cast<BMDAudioSampleType>(sample_type), channels,
+      bmdAudioOutputStreamTimestamped);
+  return static_cast<HResult>(result);
+}
+
+HResult output_disable_audio(IDeckLinkOutput *output) {
+  return static_cast<HResult>(output->DisableAudioOutput());
+}
+
+HResult output_begin_audio_preroll(IDeckLinkOutput *output) {
+  return static_cast<HResult>(output->BeginAudioPreroll());
+}
+
+HResult output_end_audio_preroll(IDeckLinkOutput *output) {
+  return static_cast<HResult>(output->EndAudioPreroll());
+}
+
+HResult output_schedule_audio_samples(IDeckLinkOutput *output,
+                                      rust::Slice<const uint8_t> data,
+                                      uint32_t sample_frame_count,
+                                      int64_t stream_time, int64_t time_scale,
+                                      uint32_t &written) {
+  // ScheduleAudioSamples copies the samples and does not modify the buffer.
+  auto buffer = const_cast<uint8_t *>(data.data());
+  auto result = output->ScheduleAudioSamples(
+      buffer, sample_frame_count, stream_time, time_scale, &written);
+  return static_cast<HResult>(result);
+}
+
+HResult output_buffered_audio_sample_frame_count(IDeckLinkOutput *output,
+                                                 uint32_t &out) {
+  return static_cast<HResult>(output->GetBufferedAudioSampleFrameCount(&out));
+}
+
+HResult output_flush_buffered_audio_samples(IDeckLinkOutput *output) {
+  return static_cast<HResult>(output->FlushBufferedAudioSamples());
+}
+
+HResult output_start_scheduled_playback(IDeckLinkOutput *output,
+                                        int64_t start_time,
+                                        int64_t time_scale) {
+  auto result = output->StartScheduledPlayback(start_time, time_scale, 1.0);
+  return static_cast<HResult>(result);
+}
+
+HResult output_stop_scheduled_playback(IDeckLinkOutput *output) {
+  return static_cast<HResult>(output->StopScheduledPlayback(0, nullptr, 0));
+}
+
+HResult output_reference_status(IDeckLinkOutput *output,
+                                ReferenceStatus &out) {
+  BMDReferenceStatus status;
+  auto result = output->GetReferenceStatus(&status);
+  if (result == S_OK) {
+    out = into_reference_status(status);
+  }
+  return static_cast<HResult>(result);
+}
+
+void output_release(IDeckLinkOutput *output) { output->Release(); }
+
+//
+// IDeckLinkKeyer
+//
+
+HResult keyer_enable(IDeckLinkKeyer *keyer, bool is_external) {
+  return static_cast<HResult>(keyer->Enable(is_external));
+}
+
+HResult keyer_disable(IDeckLinkKeyer *keyer) {
+  return static_cast<HResult>(keyer->Disable());
+}
+
+HResult keyer_set_level(IDeckLinkKeyer *keyer, uint8_t level) {
+  return static_cast<HResult>(keyer->SetLevel(level));
+}
+
+HResult keyer_ramp_up(IDeckLinkKeyer *keyer, uint32_t frame_count) {
+  return static_cast<HResult>(keyer->RampUp(frame_count));
+}
+
+HResult keyer_ramp_down(IDeckLinkKeyer *keyer, uint32_t frame_count) {
+  return static_cast<HResult>(keyer->RampDown(frame_count));
+}
+
+void keyer_release(IDeckLinkKeyer *keyer) { keyer->Release(); }
+
+//
+// IDeckLinkStatus
+//
+
+HResult status_reference_signal_locked(IDeckLinkStatus *status, bool &out) {
+  dlbool_t value = false;
+  auto result =
+      status->GetFlag(bmdDeckLinkStatusReferenceSignalLocked, &value);
+  out = value;
+  return static_cast<HResult>(result);
+}
+
+HResult status_reference_signal_mode(IDeckLinkStatus *status,
+                                     DisplayModeType &out) {
+  int64_t value = 0;
+  auto result =
+      status->GetInt(bmdDeckLinkStatusReferenceSignalMode, &value);
+  if (result == S_OK) {
+    out = into_display_mode_type(static_cast<BMDDisplayMode>(value));
   }
+  return static_cast<HResult>(result);
+}
+
+void status_release(IDeckLinkStatus *status) { status->Release(); }
 
+//
+// IDeckLinkProfileManager
+//
+
+static HResult collect_profiles(IDeckLinkProfileIterator *profile_iterator,
+                                rust::Vec<IDeckLinkProfilePtr> &profiles) {
   while (true) {
     IDeckLinkProfile *profile;
     HRESULT result = profile_iterator->Next(&profile);
@@ -182,6 +376,22 @@ HResult profile_manager_profiles(IDeckLinkProfileManager *manager,
   }
 }
 
+HResult profile_manager_profiles(IDeckLinkProfileManager *manager,
+                                 rust::Vec<IDeckLinkProfilePtr> &profiles) {
+  IDeckLinkProfileIterator *profile_iterator;
+  auto profile_iter_result = manager->GetProfiles(&profile_iterator);
+  if (profile_iter_result != S_OK) {
+    return static_cast<HResult>(profile_iter_result);
+  }
+  return collect_profiles(profile_iterator, profiles);
+}
+
+HResult profile_manager_profile(IDeckLinkProfileManager *manager, ProfileId id,
+                                IDeckLinkProfile *&profile) {
+  auto result = manager->GetProfile(from_profile_id(id), &profile);
+  return static_cast<HResult>(result);
+}
+
 void profile_manager_release(IDeckLinkProfileManager *manager) {
   manager->Release();
 }
@@ -198,7 +408,45 @@ HResult profile_profile_attributes(IDeckLinkProfile *profile,
 }
 
 HResult profile_is_active(IDeckLinkProfile *profile, bool &out) {
-  return static_cast<HResult>(profile->IsActive(&out));
+  dlbool_t value = false;
+  auto result = profile->IsActive(&value);
+  out = value;
+  return static_cast<HResult>(result);
+}
+
+HResult profile_profile_id(IDeckLinkProfile *profile, ProfileId &out) {
+  IDeckLinkProfileAttributes *attributes;
+  HRESULT result = profile->QueryInterface(IID_IDeckLinkProfileAttributes,
+                                           (void **)&attributes);
+  if (result != S_OK) {
+    return static_cast<HResult>(result);
+  }
+
+  int64_t value = 0;
+  result = attributes->GetInt(BMDDeckLinkProfileID, &value);
+  attributes->Release();
+  if (result == S_OK) {
+    out = into_profile_id(static_cast<BMDProfileID>(value));
+  }
+  return static_cast<HResult>(result);
+}
+
+HResult profile_set_active(IDeckLinkProfile *profile) {
+  return static_cast<HResult>(profile->SetActive());
+}
+
+HResult profile_peers(IDeckLinkProfile *profile,
+                      rust::Vec<IDeckLinkProfilePtr> &peers) {
+  IDeckLinkProfileIterator *profile_iterator;
+  auto profile_iter_result = profile->GetPeers(&profile_iterator);
+  if (profile_iter_result != S_OK) {
+    return static_cast<HResult>(profile_iter_result);
+  }
+  return collect_profiles(profile_iterator, peers);
+}
+
+HResult profile_device(IDeckLinkProfile *profile, IDeckLink *&device) {
+  return static_cast<HResult>(profile->GetDevice(&device));
 }
 
 void profile_release(IDeckLinkProfile *profile) { profile->Release(); }
@@ -209,7 +457,10 @@ void profile_release(IDeckLinkProfile *profile) { profile->Release(); }
 
 HResult configuration_flag(IDeckLinkConfiguration *conf, FlagConfigurationId id,
                            bool &out) {
-  return static_cast<HResult>(conf->GetFlag(flag_configuration_id(id), &out));
+  dlbool_t value = false;
+  auto result = conf->GetFlag(flag_configuration_id(id), &value);
+  out = value;
+  return static_cast<HResult>(result);
 }
 
 HResult configuration_integer(IDeckLinkConfiguration *conf,
@@ -224,11 +475,11 @@ HResult configuration_float(IDeckLinkConfiguration *conf,
 
 HResult configuration_string(IDeckLinkConfiguration *conf,
                              StringConfigurationId id, rust::String &out) {
-  const char *value;
+  dlstring_t value;
   auto result = conf->GetString(string_configuration_id(id), &value);
   if (result == S_OK) {
-    rust::String(value).swap(out);
-    free(const_cast<char *>(value));
+    dlstring_to_string(value).swap(out);
+    dlstring_free(value);
   }
   return static_cast<HResult>(result);
 }
@@ -252,7 +503,10 @@ HResult configuration_set_float(IDeckLinkConfiguration *conf,
 
 HResult configuration_set_string(IDeckLinkConfiguration *conf,
                                  StringConfigurationId id, rust::String value) {
-  return HResult(conf->SetString(string_configuration_id(id), value.c_str()));
+  auto dl_value = string_to_dlstring(value);
+  auto result = conf->SetString(string_configuration_id(id), dl_value);
+  dlstring_free(dl_value);
+  return static_cast<HResult>(result);
 }
 
 void configuration_release(IDeckLinkConfiguration *conf) { conf->Release(); }
@@ -310,6 +564,46 @@ BMDTimeValue video_input_frame_stream_time(IDeckLinkVideoInputFrame *frame,
   return time;
 }
 
+HResult
+video_input_frame_ancillary_packets(IDeckLinkVideoInputFrame *frame,
+                                    rust::Vec<AncillaryPacket> &packets) {
+  IDeckLinkVideoFrameAncillaryPackets *ancillary_packets;
+  HRESULT result = frame->QueryInterface(
+      IID_IDeckLinkVideoFrameAncillaryPackets, (void **)&ancillary_packets);
+  if (result != S_OK) {
+    return static_cast<HResult>(result);
+  }
+
+  IDeckLinkAncillaryPacketIterator *packet_iterator;
+  result = ancillary_packets->GetPacketIterator(&packet_iterator);
+  ancillary_packets->Release();
+  if (result != S_OK) {
+    return static_cast<HResult>(result);
+  }
+
+  IDeckLinkAncillaryPacket *packet;
+  while (packet_iterator->Next(&packet) == S_OK) {
+    const void *data;
+    uint32_t size;
+    if (packet->GetBytes(bmdAncillaryPacketFormatUInt8, &data, &size) == S_OK) {
+      AncillaryPacket out;
+      out.did = packet->GetDID();
+      out.sdid = packet->GetSDID();
+      out.line_number = packet->GetLineNumber();
+      out.data_stream_index = packet->GetDataStreamIndex();
+      auto bytes = static_cast<const uint8_t *>(data);
+      out.data.reserve(size);
+      for (uint32_t i = 0; i < size; i++) {
+        out.data.push_back(bytes[i]);
+      }
+      packets.push_back(std::move(out));
+    }
+    packet->Release();
+  }
+  packet_iterator->Release();
+  return HResult::Ok;
+}
+
 //
 // IDeckLinkAudioInputPacket
 //
@@ -349,12 +643,12 @@ int64_t display_mode_height(IDeckLinkDisplayMode *mode) {
 }
 
 rust::String display_mode_name(IDeckLinkDisplayMode *mode) {
-  const char *name;
+  dlstring_t name;
   if (mode->GetName(&name) != S_OK) {
     throw std::runtime_error("IDeckLinkDisplayMode::GetName failed.");
   }
-  auto result = rust::String(name);
-  free(const_cast<char *>(name));
+  auto result = dlstring_to_string(name);
+  dlstring_free(name);
   return result;
 }
 
@@ -371,4 +665,8 @@ Ratio display_mode_frame_rate(IDeckLinkDisplayMode *mode) {
   return Ratio{num, den};
 }
 
+FieldDominance display_mode_field_dominance(IDeckLinkDisplayMode *mode) {
+  return into_field_dominance(mode->GetFieldDominance());
+}
+
 void display_mode_release(IDeckLinkDisplayMode *mode) { mode->Release(); }
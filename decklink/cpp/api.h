@@ -1,7 +1,7 @@
 #pragma once
 
-#include "decklink/decklink_sdk/include/DeckLinkAPI.h"
 #include "enums.h"
+#include "platform.h"
 
 #include "decklink/src/api.rs.h"
 #include <cstdint>
@@ -10,8 +10,11 @@ rust::Vec<IDeckLinkPtr> get_decklinks();
 
 HResult decklink_profile_attributes(IDeckLink *, IDeckLinkProfileAttributes *&);
 HResult decklink_input(IDeckLink *, IDeckLinkInput *&);
+HResult decklink_output(IDeckLink *, IDeckLinkOutput *&);
+HResult decklink_keyer(IDeckLink *, IDeckLinkKeyer *&);
 HResult decklink_profile_manager(IDeckLink *, IDeckLinkProfileManager *&);
 HResult decklink_configuration(IDeckLink *, IDeckLinkConfiguration *&);
+HResult decklink_status(IDeckLink *, IDeckLinkStatus *&);
 void decklink_release(IDeckLink *decklink);
 
 // IDeckLinkProfileAttributes
@@ -44,15 +47,63 @@ HResult input_pause_streams(IDeckLinkInput *input);
 HResult input_flush_streams(IDeckLinkInput *input);
 void input_release(IDeckLinkInput *input);
 
+// IDeckLinkOutput
+HResult output_enable_video(IDeckLinkOutput *output, DisplayModeType mode);
+HResult output_disable_video(IDeckLinkOutput *output);
+HResult output_schedule_video_frame(IDeckLinkOutput *output, int32_t width,
+                                    int32_t height, int32_t row_bytes,
+                                    PixelFormat pixel_format,
+                                    rust::Slice<const uint8_t> data,
+                                    int64_t display_time, int64_t duration,
+                                    int64_t time_scale);
+HResult output_enable_audio(IDeckLinkOutput *output, uint32_t sample_rate,
+                            AudioSampleType sample_type, uint32_t channels);
+HResult output_disable_audio(IDeckLinkOutput *output);
+HResult output_begin_audio_preroll(IDeckLinkOutput *output);
+HResult output_end_audio_preroll(IDeckLinkOutput *output);
+HResult output_schedule_audio_samples(IDeckLinkOutput *output,
+                                      rust::Slice<const uint8_t> data,
+                                      uint32_t sample_frame_count,
+                                      int64_t stream_time, int64_t time_scale,
+                                      uint32_t &written);
+HResult output_buffered_audio_sample_frame_count(IDeckLinkOutput *output,
+                                                 uint32_t &out);
+HResult output_flush_buffered_audio_samples(IDeckLinkOutput *output);
+HResult output_start_scheduled_playback(IDeckLinkOutput *output,
+                                        int64_t start_time, int64_t time_scale);
+HResult output_stop_scheduled_playback(IDeckLinkOutput *output);
+HResult output_reference_status(IDeckLinkOutput *output, ReferenceStatus &out);
+void output_release(IDeckLinkOutput *output);
+
+// IDeckLinkKeyer
+HResult keyer_enable(IDeckLinkKeyer *keyer, bool is_external);
+HResult keyer_disable(IDeckLinkKeyer *keyer);
+HResult keyer_set_level(IDeckLinkKeyer *keyer, uint8_t level);
+HResult keyer_ramp_up(IDeckLinkKeyer *keyer, uint32_t frame_count);
+HResult keyer_ramp_down(IDeckLinkKeyer *keyer, uint32_t frame_count);
+void keyer_release(IDeckLinkKeyer *keyer);
+
+// IDeckLinkStatus
+HResult status_reference_signal_locked(IDeckLinkStatus *status, bool &out);
+HResult status_reference_signal_mode(IDeckLinkStatus *status,
+                                     DisplayModeType &out);
+void status_release(IDeckLinkStatus *status);
+
 // IDeckLinkProfileManager
 HResult profile_manager_profiles(IDeckLinkProfileManager *,
                                  rust::Vec<IDeckLinkProfilePtr> &);
+HResult profile_manager_profile(IDeckLinkProfileManager *, ProfileId,
+                                IDeckLinkProfile *&);
 void profile_manager_release(IDeckLinkProfileManager *);
 
 // IDeckLinkProfile
 HResult profile_profile_attributes(IDeckLinkProfile *,
                                    IDeckLinkProfileAttributes *&);
 HResult profile_is_active(IDeckLinkProfile *, bool &);
+HResult profile_profile_id(IDeckLinkProfile *, ProfileId &);
+HResult profile_set_active(IDeckLinkProfile *);
+HResult profile_peers(IDeckLinkProfile *, rust::Vec<IDeckLinkProfilePtr> &);
+HResult profile_device(IDeckLinkProfile *, IDeckLink *&);
 void profile_release(IDeckLinkProfile *);
 
 // IDeckLinkConfiguration
@@ -80,6 +131,9 @@ long video_input_frame_height(IDeckLinkVideoInputFrame *frame);
 long video_input_frame_row_bytes(IDeckLinkVideoInputFrame *frame);
 uint8_t *video_input_frame_bytes(IDeckLinkVideoInputFrame *frame);
 PixelFormat video_input_frame_pixel_format(IDeckLinkVideoInputFrame *frame);
+HResult
+video_input_frame_ancillary_packets(IDeckLinkVideoInputFrame *frame,
+                                    rust::Vec<AncillaryPacket> &packets);
 BMDTimeValue video_input_frame_stream_time(IDeckLinkVideoInputFrame *frame,
                                            BMDTimeScale time_scale);
 
@@ -95,4 +149,5 @@ int64_t display_mode_height(IDeckLinkDisplayMode *mode);
 rust::String display_mode_name(IDeckLinkDisplayMode *mode);
 DisplayModeType display_mode_display_mode_type(IDeckLinkDisplayMode *mode);
 Ratio display_mode_frame_rate(IDeckLinkDisplayMode *mode);
+FieldDominance display_mode_field_dominance(IDeckLinkDisplayMode *mode);
 void display_mode_release(IDeckLinkDisplayMode *mode);
@@ -3,11 +3,11 @@
 #include "enums.h"
 
 ULONG InputCallbackWrapper::AddRef(void) {
-  return __sync_add_and_fetch(&refcount, 1);
+  return ++refcount;
 }
 
 ULONG InputCallbackWrapper::Release(void) {
-  int32_t new_refcount = __sync_sub_and_fetch(&refcount, 1);
+  int32_t new_refcount = --refcount;
   if (new_refcount == 0) {
     delete this;
     return 0;
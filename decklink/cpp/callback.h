@@ -1,10 +1,11 @@
 #include "api.h"
+#include <atomic>
 #include <cstdint>
 
 class InputCallbackWrapper : public IDeckLinkInputCallback {
 private:
   rust::Box<DynInputCallback> cb;
-  int32_t refcount = 1;
+  std::atomic<int32_t> refcount = 1;
 
 public:
   InputCallbackWrapper(rust::Box<DynInputCallback> cb) : cb(std::move(cb)){};
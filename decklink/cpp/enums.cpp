@@ -1,5 +1,5 @@
-#include "decklink/decklink_sdk/include/DeckLinkAPI.h"
 #include "enums.h"
+#include "platform.h"
 #include <format>
 #include <stdexcept>
 
@@ -383,8 +383,8 @@ VideoConnection into_video_connection(BMDVideoConnection conn) {
     CASE(OpticalEthernet)
   }
 #undef CASE
-  throw std::invalid_argument(
-      std::format("Unknown BMDVideoConnection: {:#x}", conn));
+  throw std::invalid_argument(std::format("Unknown BMDVideoConnection: {:#x}",
+                                          static_cast<uint32_t>(conn)));
 }
 
 BMDAudioConnection from_audio_connection(AudioConnection conn) {
@@ -421,8 +421,8 @@ AudioConnection into_audio_connection(BMDAudioConnection conn) {
     CASE(Headphones)
   }
 #undef CASE
-  throw std::invalid_argument(
-      std::format("Unknown BMDAudioConnection: {:#x}", conn));
+  throw std::invalid_argument(std::format("Unknown BMDAudioConnection: {:#x}",
+                                          static_cast<uint32_t>(conn)));
 }
 
 BMDDisplayMode from_display_mode_type(DisplayModeType mode) {
@@ -709,8 +709,8 @@ DisplayModeType into_display_mode_type(BMDDisplayMode mode) {
     CASE(ModeUnknown)
   }
 #undef CASE
-  throw std::invalid_argument(
-      std::format("Unknown BMDDisplayMode: {:#x}", mode));
+  throw std::invalid_argument(std::format("Unknown BMDDisplayMode: {:#x}",
+                                          static_cast<uint32_t>(mode)));
 }
 
 BMDPixelFormat from_pixel_format(PixelFormat format) {
@@ -758,8 +758,8 @@ PixelFormat into_pixel_format(BMDPixelFormat format) {
     CASE(FormatDNxHR)
   }
 #undef CASE
-  throw std::invalid_argument(
-      std::format("Unknown BMDPixelFormat: {:#x}", format));
+  throw std::invalid_argument(std::format("Unknown BMDPixelFormat: {:#x}",
+                                          static_cast<uint32_t>(format)));
 }
 
 BMDVideoInputConversionMode
@@ -799,12 +799,77 @@ into_video_input_conversion_mode(BMDVideoInputConversionMode mode) {
   }
 #undef CASE
   throw std::invalid_argument(
-      std::format("Unknown BMDVideoInputConversionMode: {:#x}", mode));
+      std::format("Unknown BMDVideoInputConversionMode: {:#x}",
+                  static_cast<uint32_t>(mode)));
+}
+
+FieldDominance into_field_dominance(BMDFieldDominance dominance) {
+#define CASE(VALUE)                                                            \
+  case bmd##VALUE:                                                             \
+    return FieldDominance::VALUE;
+
+  switch (dominance) {
+    CASE(UnknownFieldDominance)
+    CASE(LowerFieldFirst)
+    CASE(UpperFieldFirst)
+    CASE(ProgressiveFrame)
+    CASE(ProgressiveSegmentedFrame)
+  }
+#undef CASE
+  throw std::invalid_argument(std::format("Unknown BMDFieldDominance: {:#x}",
+                                          static_cast<uint32_t>(dominance)));
+}
+
+BMDProfileID from_profile_id(ProfileId id) {
+#define CASE(VALUE)                                                            \
+  case ProfileId::VALUE:                                                       \
+    return bmdProfile##VALUE;
+
+  switch (id) {
+    CASE(OneSubDeviceFullDuplex)
+    CASE(OneSubDeviceHalfDuplex)
+    CASE(TwoSubDevicesFullDuplex)
+    CASE(TwoSubDevicesHalfDuplex)
+    CASE(FourSubDevicesHalfDuplex)
+  }
+#undef CASE
+  throw std::invalid_argument(
+      std::format("Unknown ProfileId: {:#x}", static_cast<int64_t>(id)));
+}
+
+ProfileId into_profile_id(BMDProfileID id) {
+#define CASE(VALUE)                                                            \
+  case bmdProfile##VALUE:                                                      \
+    return ProfileId::VALUE;
+
+  switch (id) {
+    CASE(OneSubDeviceFullDuplex)
+    CASE(OneSubDeviceHalfDuplex)
+    CASE(TwoSubDevicesFullDuplex)
+    CASE(TwoSubDevicesHalfDuplex)
+    CASE(FourSubDevicesHalfDuplex)
+  }
+#undef CASE
+  throw std::invalid_argument(std::format("Unknown BMDProfileID: {:#x}",
+                                          static_cast<uint32_t>(id)));
+}
+
+ReferenceStatus into_reference_status(BMDReferenceStatus status) {
+  // Status is a bit field, but the lock state is only meaningful if the
+  // hardware has a reference input.
+  if ((status & bmdReferenceNotSupportedByHardware) != 0) {
+    return ReferenceStatus::NotSupportedByHardware;
+  }
+  if ((status & bmdReferenceLocked) != 0) {
+    return ReferenceStatus::Locked;
+  }
+  return ReferenceStatus::Unlocked;
 }
 
 BMDSupportedVideoModeFlags
 from_supported_video_mode_flags(SupportedVideoModeFlags flags) {
-  BMDSupportedVideoModeFlags bmd_flags = bmdSupportedVideoModeDefault;
+  // Flags are enums on Windows, so they are combined as integers.
+  uint32_t bmd_flags = bmdSupportedVideoModeDefault;
   if (flags.supports_keying) {
     bmd_flags = bmd_flags | bmdSupportedVideoModeKeying;
   }
@@ -827,7 +892,7 @@ from_supported_video_mode_flags(SupportedVideoModeFlags flags) {
     bmd_flags = bmd_flags | bmdSupportedVideoModePsF;
   }
 
-  return bmd_flags;
+  return static_cast<BMDSupportedVideoModeFlags>(bmd_flags);
 }
 
 SupportedVideoModeFlags
@@ -849,7 +914,8 @@ into_supported_video_mode_flags(BMDSupportedVideoModeFlags bmd_flags) {
 }
 
 BMDVideoInputFlags from_video_input_flags(VideoInputFlags flags) {
-  BMDVideoInputFlags bmd_flags = bmdVideoInputFlagDefault;
+  // Flags are enums on Windows, so they are combined as integers.
+  uint32_t bmd_flags = bmdVideoInputFlagDefault;
   if (flags.enable_format_detection) {
     bmd_flags = bmd_flags | bmdVideoInputEnableFormatDetection;
   }
@@ -859,7 +925,7 @@ BMDVideoInputFlags from_video_input_flags(VideoInputFlags flags) {
   if (flags.synchronize_to_capture_group) {
     bmd_flags = bmd_flags | bmdVideoInputSynchronizeToCaptureGroup;
   }
-  return bmd_flags;
+  return static_cast<BMDVideoInputFlags>(bmd_flags);
 }
 
 VideoInputFlags into_video_input_flags(BMDVideoInputFlags bmd_flags) {
@@ -874,7 +940,8 @@ VideoInputFlags into_video_input_flags(BMDVideoInputFlags bmd_flags) {
 
 BMDDetectedVideoInputFormatFlags
 from_detected_video_input_format_flags(DetectedVideoInputFormatFlags flags) {
-  BMDDetectedVideoInputFormatFlags bmd_flags = 0;
+  // Flags are enums on Windows, so they are combined as integers.
+  uint32_t bmd_flags = 0;
   if (flags.format_y_cb_cr_422) {
     bmd_flags = bmd_flags | bmdDetectedVideoInputYCbCr422;
   }
@@ -893,7 +960,7 @@ from_detected_video_input_format_flags(DetectedVideoInputFormatFlags flags) {
   if (flags.bit_depth_8) {
     bmd_flags = bmd_flags | bmdDetectedVideoInput8BitDepth;
   }
-  return bmd_flags;
+  return static_cast<BMDDetectedVideoInputFormatFlags>(bmd_flags);
 }
 
 DetectedVideoInputFormatFlags into_detected_video_input_format_flags(
@@ -910,7 +977,8 @@ DetectedVideoInputFormatFlags into_detected_video_input_format_flags(
 
 BMDVideoInputFormatChangedEvents
 from_video_input_format_changed_events(VideoInputFormatChangedEvents flags) {
-  BMDVideoInputFormatChangedEvents bmd_flags = 0;
+  // Flags are enums on Windows, so they are combined as integers.
+  uint32_t bmd_flags = 0;
   if (flags.display_mode_changed) {
     bmd_flags = bmd_flags | bmdVideoInputDisplayModeChanged;
   }
@@ -920,7 +988,7 @@ from_video_input_format_changed_events(VideoInputFormatChangedEvents flags) {
   if (flags.colorspace_changed) {
     bmd_flags = bmd_flags | bmdVideoInputColorspaceChanged;
   }
-  return bmd_flags;
+  return static_cast<BMDVideoInputFormatChangedEvents>(bmd_flags);
 }
 
 VideoInputFormatChangedEvents into_video_input_format_changed_events(
@@ -1,6 +1,6 @@
 #pragma once
 
-#include "decklink/decklink_sdk/include/DeckLinkAPI.h"
+#include "platform.h"
 #include "decklink/src/enums.rs.h"
 
 REFIID declare_id(DeclarationId id);
@@ -38,6 +38,13 @@ BMDVideoInputConversionMode
 VideoInputConversionMode
     into_video_input_conversion_mode(BMDVideoInputConversionMode);
 
+FieldDominance into_field_dominance(BMDFieldDominance);
+
+BMDProfileID from_profile_id(ProfileId);
+ProfileId into_profile_id(BMDProfileID);
+
+ReferenceStatus into_reference_status(BMDReferenceStatus);
+
 BMDSupportedVideoModeFlags
     from_supported_video_mode_flags(SupportedVideoModeFlags);
 SupportedVideoModeFlags
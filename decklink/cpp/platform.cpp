@@ -0,0 +1,89 @@
+#include "platform.h"
+
+#include <cstdlib>
+#include <cstring>
+#include <vector>
+
+#if defined(_WIN32)
+
+rust::String dlstring_to_string(dlstring_t value) {
+  auto length = static_cast<int>(SysStringLen(value));
+  if (length == 0) {
+    return rust::String();
+  }
+  auto size = WideCharToMultiByte(CP_UTF8, 0, value, length, nullptr, 0,
+                                  nullptr, nullptr);
+  std::vector<char> buffer(size);
+  WideCharToMultiByte(CP_UTF8, 0, value, length, buffer.data(), size, nullptr,
+                      nullptr);
+  return rust::String(buffer.data(), buffer.size());
+}
+
+void dlstring_free(dlstring_t value) { SysFreeString(value); }
+
+dlstring_t string_to_dlstring(const rust::String &value) {
+  auto length = static_cast<int>(value.size());
+  auto size =
+      MultiByteToWideChar(CP_UTF8, 0, value.data(), length, nullptr, 0);
+  auto result = SysAllocStringLen(nullptr, size);
+  MultiByteToWideChar(CP_UTF8, 0, value.data(), length, result, size);
+  return result;
+}
+
+IDeckLinkIterator *create_decklink_iterator() {
+  // S_FALSE and RPC_E_CHANGED_MODE mean that COM was already initialized on
+  // this thread, which is fine for creating the iterator.
+  CoInitializeEx(nullptr, COINIT_MULTITHREADED);
+
+  IDeckLinkIterator *iterator = nullptr;
+  auto result = CoCreateInstance(CLSID_CDeckLinkIterator, nullptr, CLSCTX_ALL,
+                                 IID_IDeckLinkIterator, (void **)&iterator);
+  if (result != S_OK) {
+    return nullptr;
+  }
+  return iterator;
+}
+
+#elif defined(__APPLE__)
+
+rust::String dlstring_to_string(dlstring_t value) {
+  auto length = CFStringGetLength(value);
+  auto max_size =
+      CFStringGetMaximumSizeForEncoding(length, kCFStringEncodingUTF8) + 1;
+  std::vector<char> buffer(max_size);
+  if (!CFStringGetCString(value, buffer.data(), max_size,
+                          kCFStringEncodingUTF8)) {
+    return rust::String();
+  }
+  return rust::String(buffer.data());
+}
+
+void dlstring_free(dlstring_t value) { CFRelease(value); }
+
+dlstring_t string_to_dlstring(const rust::String &value) {
+  return CFStringCreateWithBytes(
+      kCFAllocatorDefault, reinterpret_cast<const UInt8 *>(value.data()),
+      value.size(), kCFStringEncodingUTF8, false);
+}
+
+#else
+
+rust::String dlstring_to_string(dlstring_t value) {
+  return rust::String(value);
+}
+
+void dlstring_free(dlstring_t value) { free(const_cast<char *>(value)); }
+
+dlstring_t string_to_dlstring(const rust::String &value) {
+  return strndup(value.data(), value.size());
+}
+
+#endif
+
+#if !defined(_WIN32)
+
+IDeckLinkIterator *create_decklink_iterator() {
+  return CreateDeckLinkIteratorInstance();
+}
+
+#endif
@@ -0,0 +1,36 @@
+#pragma once
+
+// DeckLink SDK differs between platforms in the headers, in the type used
+// for strings (`const char *` on Linux, `CFStringRef` on macOS and `BSTR` on
+// Windows) and in the type used for booleans (`BOOL` on Windows).
+
+#if defined(_WIN32)
+// Generated with MIDL from DeckLinkAPI.idl, see decklink_sdk/README.md
+#include "decklink/decklink_sdk/win/include/DeckLinkAPI_h.h"
+#include <windows.h>
+
+using dlstring_t = BSTR;
+using dlbool_t = BOOL;
+#elif defined(__APPLE__)
+#include "decklink/decklink_sdk/mac/include/DeckLinkAPI.h"
+#include <CoreFoundation/CoreFoundation.h>
+
+using dlstring_t = CFStringRef;
+using dlbool_t = bool;
+#else
+#include "decklink/decklink_sdk/include/DeckLinkAPI.h"
+
+using dlstring_t = const char *;
+using dlbool_t = bool;
+#endif
+
+#include "rust/cxx.h"
+
+rust::String dlstring_to_string(dlstring_t value);
+// Strings returned by the SDK are owned by the caller.
+void dlstring_free(dlstring_t value);
+// Returned string has to be freed with `dlstring_free`.
+dlstring_t string_to_dlstring(const rust::String &value);
+
+// Returns nullptr if the DeckLink drivers are not installed.
+IDeckLinkIterator *create_decklink_iterator();
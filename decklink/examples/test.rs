@@ -1,4 +1,4 @@
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
 mod example {
     use decklink::{
         AudioSampleType, DeckLinkError, DisplayModeType, PixelFormat, SupportedVideoModeFlags,
@@ -62,14 +62,14 @@ mod example {
     }
 }
 
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
 fn main() {
     if let Err(err) = example::example() {
         println!("error: {}", example::ErrorStack::new(&err).into_string());
     }
 }
 
-#[cfg(not(target_os = "linux"))]
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
 fn main() {
-    println!("Example only available on Linux.");
+    println!("Example only available on Linux, macOS and Windows.");
 }
@@ -5,13 +5,19 @@ use crate::DeckLinkError;
 use self::{
     device::DeckLinkConfiguration,
     input::Input,
+    keyer::Keyer,
+    output::Output,
     profile::{ProfileAttributes, ProfileManager},
+    status::Status,
 };
 use input::DynInputCallback;
 
 pub(super) mod device;
 pub(super) mod input;
+pub(super) mod keyer;
+pub(super) mod output;
 pub(super) mod profile;
+pub(super) mod status;
 
 #[cxx::bridge]
 mod ffi {
@@ -30,6 +36,19 @@ mod ffi {
         pub den: i64,
     }
 
+    /// SMPTE 291M ancillary data packet, e.g. from the vertical blanking interval.
+    #[derive(Debug, Clone)]
+    struct AncillaryPacket {
+        /// Data identifier
+        pub did: u8,
+        /// Secondary data identifier
+        pub sdid: u8,
+        pub line_number: u32,
+        pub data_stream_index: u8,
+        /// User data words without the packet header and checksum.
+        pub data: Vec<u8>,
+    }
+
     // HResult is defined as C++ int, but values are larger than 32-bit integer
     // can hold, so we are using here u32 instead
     #[derive(Debug, Copy)]
@@ -92,6 +111,9 @@ mod ffi {
         type VideoConnection = crate::enums::ffi::VideoConnection;
         type DisplayModeType = crate::enums::ffi::DisplayModeType;
         type PixelFormat = crate::enums::ffi::PixelFormat;
+        type FieldDominance = crate::enums::ffi::FieldDominance;
+        type ProfileId = crate::enums::ffi::ProfileId;
+        type ReferenceStatus = crate::enums::ffi::ReferenceStatus;
         type VideoInputConversionMode = crate::enums::ffi::VideoInputConversionMode;
         type VideoInputFormatChangedEvents = crate::enums::ffi::VideoInputFormatChangedEvents;
         type DetectedVideoInputFormatFlags = crate::enums::ffi::DetectedVideoInputFormatFlags;
@@ -103,10 +125,13 @@ mod ffi {
 
         type IDeckLink;
         type IDeckLinkInput;
+        type IDeckLinkOutput;
+        type IDeckLinkKeyer;
         type IDeckLinkProfile;
         type IDeckLinkProfileManager;
         type IDeckLinkProfileAttributes;
         type IDeckLinkConfiguration;
+        type IDeckLinkStatus;
         type IDeckLinkVideoInputFrame;
         type IDeckLinkAudioInputPacket;
         type IDeckLinkDisplayMode;
@@ -124,6 +149,14 @@ mod ffi {
             decklink: *mut IDeckLink,
             out: &mut *mut IDeckLinkInput,
         ) -> HResult;
+        unsafe fn decklink_output(
+            decklink: *mut IDeckLink,
+            out: &mut *mut IDeckLinkOutput,
+        ) -> HResult;
+        unsafe fn decklink_keyer(
+            decklink: *mut IDeckLink,
+            out: &mut *mut IDeckLinkKeyer,
+        ) -> HResult;
         unsafe fn decklink_profile_manager(
             decklink: *mut IDeckLink,
             out: &mut *mut IDeckLinkProfileManager,
@@ -132,6 +165,10 @@ mod ffi {
             decklink: *mut IDeckLink,
             out: &mut *mut IDeckLinkConfiguration,
         ) -> HResult;
+        unsafe fn decklink_status(
+            decklink: *mut IDeckLink,
+            out: &mut *mut IDeckLinkStatus,
+        ) -> HResult;
         unsafe fn decklink_release(decklink: *mut IDeckLink);
     }
 
@@ -198,12 +235,97 @@ mod ffi {
         unsafe fn input_release(input: *mut IDeckLinkInput);
     }
 
+    // IDeckLinkOutput
+    extern "C++" {
+        unsafe fn output_enable_video(
+            output: *mut IDeckLinkOutput,
+            mode: DisplayModeType,
+        ) -> Result<HResult>;
+        unsafe fn output_disable_video(output: *mut IDeckLinkOutput) -> HResult;
+        #[allow(clippy::too_many_arguments)]
+        unsafe fn output_schedule_video_frame(
+            output: *mut IDeckLinkOutput,
+            width: i32,
+            height: i32,
+            row_bytes: i32,
+            pixel_format: PixelFormat,
+            data: &[u8],
+            display_time: i64,
+            duration: i64,
+            time_scale: i64,
+        ) -> Result<HResult>;
+        unsafe fn output_enable_audio(
+            output: *mut IDeckLinkOutput,
+            sample_rate: u32,
+            sample_type: AudioSampleType,
+            channels: u32,
+        ) -> Result<HResult>;
+        unsafe fn output_disable_audio(output: *mut IDeckLinkOutput) -> HResult;
+        unsafe fn output_begin_audio_preroll(output: *mut IDeckLinkOutput) -> HResult;
+        unsafe fn output_end_audio_preroll(output: *mut IDeckLinkOutput) -> HResult;
+        unsafe fn output_schedule_audio_samples(
+            output: *mut IDeckLinkOutput,
+            data: &[u8],
+            sample_frame_count: u32,
+            stream_time: i64,
+            time_scale: i64,
+            out_written: &mut u32,
+        ) -> HResult;
+        unsafe fn output_buffered_audio_sample_frame_count(
+            output: *mut IDeckLinkOutput,
+            out: &mut u32,
+        ) -> HResult;
+        unsafe fn output_flush_buffered_audio_samples(output: *mut IDeckLinkOutput) -> HResult;
+        unsafe fn output_start_scheduled_playback(
+            output: *mut IDeckLinkOutput,
+            start_time: i64,
+            time_scale: i64,
+        ) -> HResult;
+        unsafe fn output_stop_scheduled_playback(output: *mut IDeckLinkOutput) -> HResult;
+        unsafe fn output_reference_status(
+            output: *mut IDeckLinkOutput,
+            out: &mut ReferenceStatus,
+        ) -> Result<HResult>;
+
+        unsafe fn output_release(output: *mut IDeckLinkOutput);
+    }
+
+    // IDeckLinkKeyer
+    extern "C++" {
+        unsafe fn keyer_enable(keyer: *mut IDeckLinkKeyer, is_external: bool) -> HResult;
+        unsafe fn keyer_disable(keyer: *mut IDeckLinkKeyer) -> HResult;
+        unsafe fn keyer_set_level(keyer: *mut IDeckLinkKeyer, level: u8) -> HResult;
+        unsafe fn keyer_ramp_up(keyer: *mut IDeckLinkKeyer, frame_count: u32) -> HResult;
+        unsafe fn keyer_ramp_down(keyer: *mut IDeckLinkKeyer, frame_count: u32) -> HResult;
+
+        unsafe fn keyer_release(keyer: *mut IDeckLinkKeyer);
+    }
+
+    // IDeckLinkStatus
+    extern "C++" {
+        unsafe fn status_reference_signal_locked(
+            status: *mut IDeckLinkStatus,
+            out: &mut bool,
+        ) -> HResult;
+        unsafe fn status_reference_signal_mode(
+            status: *mut IDeckLinkStatus,
+            out: &mut DisplayModeType,
+        ) -> Result<HResult>;
+
+        unsafe fn status_release(status: *mut IDeckLinkStatus);
+    }
+
     // IDeckLinkProfileManager
     extern "C++" {
         unsafe fn profile_manager_profiles(
             manger: *mut IDeckLinkProfileManager,
             out: &mut Vec<IDeckLinkProfilePtr>,
         ) -> HResult;
+        unsafe fn profile_manager_profile(
+            manager: *mut IDeckLinkProfileManager,
+            id: ProfileId,
+            out: &mut *mut IDeckLinkProfile,
+        ) -> Result<HResult>;
         unsafe fn profile_manager_release(manager: *mut IDeckLinkProfileManager);
     }
 
@@ -214,6 +336,19 @@ mod ffi {
             out: &mut *mut IDeckLinkProfileAttributes,
         ) -> HResult;
         unsafe fn profile_is_active(profile: *mut IDeckLinkProfile, out: &mut bool) -> HResult;
+        unsafe fn profile_profile_id(
+            profile: *mut IDeckLinkProfile,
+            out: &mut ProfileId,
+        ) -> Result<HResult>;
+        unsafe fn profile_set_active(profile: *mut IDeckLinkProfile) -> HResult;
+        unsafe fn profile_peers(
+            profile: *mut IDeckLinkProfile,
+            out: &mut Vec<IDeckLinkProfilePtr>,
+        ) -> HResult;
+        unsafe fn profile_device(
+            profile: *mut IDeckLinkProfile,
+            out: &mut *mut IDeckLink,
+        ) -> HResult;
         unsafe fn profile_release(profile: *mut IDeckLinkProfile);
     }
 
@@ -275,6 +410,10 @@ mod ffi {
             input: *mut IDeckLinkVideoInputFrame,
             time_scale: i64,
         ) -> Result<i64>;
+        unsafe fn video_input_frame_ancillary_packets(
+            input: *mut IDeckLinkVideoInputFrame,
+            out: &mut Vec<AncillaryPacket>,
+        ) -> HResult;
     }
 
     // IDeckLinkAudioInputPacket
@@ -298,11 +437,15 @@ mod ffi {
             mode: *mut IDeckLinkDisplayMode,
         ) -> Result<DisplayModeType>;
         unsafe fn display_mode_frame_rate(mode: *mut IDeckLinkDisplayMode) -> Result<Ratio>;
+        unsafe fn display_mode_field_dominance(
+            mode: *mut IDeckLinkDisplayMode,
+        ) -> Result<FieldDominance>;
 
         unsafe fn display_mode_release(mode: *mut IDeckLinkDisplayMode);
     }
 }
 
+pub use ffi::AncillaryPacket;
 pub use ffi::HResult;
 
 pub struct DeckLink(*mut ffi::IDeckLink);
@@ -322,6 +465,27 @@ impl DeckLink {
         Ok(Input(input))
     }
 
+    pub fn output(&self) -> Result<Output, DeckLinkError> {
+        let mut output = null_mut();
+        unsafe { ffi::decklink_output(self.0, &mut output) }
+            .into_result("IDeckLink::QueryInterface(IID_IDeckLinkOutput, _)")?;
+        Ok(Output(output))
+    }
+
+    pub fn keyer(&self) -> Result<Keyer, DeckLinkError> {
+        let mut keyer = null_mut();
+        unsafe { ffi::decklink_keyer(self.0, &mut keyer) }
+            .into_result("IDeckLink::QueryInterface(IID_IDeckLinkKeyer, _)")?;
+        Ok(Keyer(keyer))
+    }
+
+    pub fn status(&self) -> Result<Status, DeckLinkError> {
+        let mut status = null_mut();
+        unsafe { ffi::decklink_status(self.0, &mut status) }
+            .into_result("IDeckLink::QueryInterface(IID_IDeckLinkStatus, _)")?;
+        Ok(Status(status))
+    }
+
     pub fn profile_manager(&self) -> Result<Option<ProfileManager>, DeckLinkError> {
         let mut manager = null_mut();
         let hresult = unsafe { ffi::decklink_profile_manager(self.0, &mut manager) };
@@ -378,6 +542,10 @@ impl DisplayMode {
     pub fn display_mode_frame_rate(&self) -> Result<ffi::Ratio, DeckLinkError> {
         Ok(unsafe { ffi::display_mode_frame_rate(self.0) }?)
     }
+
+    pub fn field_dominance(&self) -> Result<ffi::FieldDominance, DeckLinkError> {
+        Ok(unsafe { ffi::display_mode_field_dominance(self.0) }?)
+    }
 }
 
 impl Drop for DisplayMode {
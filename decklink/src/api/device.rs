@@ -90,6 +90,22 @@ impl DeckLinkConfiguration {
         let hresult = unsafe { ffi::configuration_set_string(self.0, id, value)? };
         hresult.into_result("IDeckLinkConfiguration::SetString")
     }
+
+    /// Offset of the output timing relative to the reference (genlock) input. Returns `None`
+    /// if the device does not have a reference input.
+    pub fn reference_input_timing_offset(&self) -> Result<Option<i64>, DeckLinkError> {
+        self.get_integer(ffi::IntegerConfigurationId::ConfigReferenceInputTimingOffset)
+    }
+
+    /// Shifts the output timing relative to the reference input to compensate for cable and
+    /// processing delays in a facility. The allowed range depends on the device, see
+    /// [`FlagAttributeId::SupportsFullFrameReferenceInputTimingOffset`](crate::FlagAttributeId::SupportsFullFrameReferenceInputTimingOffset).
+    pub fn set_reference_input_timing_offset(&mut self, offset: i64) -> Result<(), DeckLinkError> {
+        self.set_integer(
+            ffi::IntegerConfigurationId::ConfigReferenceInputTimingOffset,
+            offset,
+        )
+    }
 }
 
 impl Drop for DeckLinkConfiguration {
@@ -149,6 +149,15 @@ impl VideoInputFrame {
         let time_value = unsafe { ffi::video_input_frame_stream_time(self.0, 1_000_000_000)? };
         Ok(Duration::from_nanos(time_value as u64))
     }
+    /// Ancillary data packets (e.g. captions, SCTE-104 messages) captured together with
+    /// the frame.
+    pub fn ancillary_packets(&self) -> Result<Vec<ffi::AncillaryPacket>, DeckLinkError> {
+        let mut packets = vec![];
+        unsafe { ffi::video_input_frame_ancillary_packets(self.0, &mut packets) }.into_result(
+            "IDeckLinkVideoInputFrame::QueryInterface(IID_IDeckLinkVideoFrameAncillaryPackets, _)",
+        )?;
+        Ok(packets)
+    }
 }
 
 pub struct AudioInputPacket(*mut ffi::IDeckLinkAudioInputPacket);
@@ -170,6 +179,11 @@ impl AudioInputPacket {
         Ok(data.freeze())
     }
 
+    /// Number of sample frames in the packet, a sample frame has one sample for every channel.
+    pub fn sample_count(&self) -> usize {
+        unsafe { ffi::audio_input_packet_sample_count(self.0) as usize }
+    }
+
     pub fn as_32_bit_stereo(&self) -> Result<Vec<(i32, i32)>, DeckLinkError> {
         let sample_count = unsafe { ffi::audio_input_packet_sample_count(self.0) as usize };
         let mut result: Vec<(i32, i32)> = Vec::with_capacity(sample_count);
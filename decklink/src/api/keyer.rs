@@ -0,0 +1,49 @@
+use crate::DeckLinkError;
+
+use super::{HResult, ffi};
+
+/// Keys the fill of frames scheduled on [`Output`](crate::Output) over a background, using
+/// their alpha channel as a key. Frames have to be in a pixel format with alpha, e.g.
+/// [`PixelFormat::Format8BitBGRA`](crate::PixelFormat::Format8BitBGRA).
+///
+/// Check [`FlagAttributeId::SupportsInternalKeying`](crate::FlagAttributeId::SupportsInternalKeying)
+/// and [`FlagAttributeId::SupportsExternalKeying`](crate::FlagAttributeId::SupportsExternalKeying)
+/// before enabling the keyer.
+pub struct Keyer(pub(super) *mut ffi::IDeckLinkKeyer);
+
+impl Keyer {
+    /// Internal keying composites the output over the signal of the device's input.
+    /// External keying outputs the fill and key signals on separate outputs, to be
+    /// combined by an external keyer.
+    pub fn enable(&self, external: bool) -> Result<(), DeckLinkError> {
+        unsafe { ffi::keyer_enable(self.0, external) }.into_result("IDeckLinkKeyer::Enable")
+    }
+
+    pub fn disable(&self) -> Result<(), DeckLinkError> {
+        unsafe { ffi::keyer_disable(self.0) }.into_result("IDeckLinkKeyer::Disable")
+    }
+
+    /// Sets the opacity of the key, 0 is fully transparent and 255 is fully opaque.
+    pub fn set_level(&self, level: u8) -> Result<(), DeckLinkError> {
+        unsafe { ffi::keyer_set_level(self.0, level) }.into_result("IDeckLinkKeyer::SetLevel")
+    }
+
+    /// Fades the key from transparent to fully opaque over `frame_count` frames.
+    pub fn ramp_up(&self, frame_count: u32) -> Result<(), DeckLinkError> {
+        unsafe { ffi::keyer_ramp_up(self.0, frame_count) }.into_result("IDeckLinkKeyer::RampUp")
+    }
+
+    /// Fades the key from fully opaque to transparent over `frame_count` frames.
+    pub fn ramp_down(&self, frame_count: u32) -> Result<(), DeckLinkError> {
+        unsafe { ffi::keyer_ramp_down(self.0, frame_count) }.into_result("IDeckLinkKeyer::RampDown")
+    }
+}
+
+impl Drop for Keyer {
+    fn drop(&mut self) {
+        unsafe { ffi::keyer_release(self.0) };
+    }
+}
+
+unsafe impl Send for Keyer {}
+unsafe impl Sync for Keyer {}
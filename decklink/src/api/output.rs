@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use crate::DeckLinkError;
+
+use super::{
+    HResult,
+    ffi::{self, PixelFormat, ReferenceStatus},
+};
+
+/// Time scale used for all timestamps passed to the device, values are in nanoseconds.
+const TIME_SCALE: i64 = 1_000_000_000;
+
+pub struct Output(pub(super) *mut ffi::IDeckLinkOutput);
+
+/// Raw video frame scheduled with [`Output::schedule_video_frame`].
+pub struct VideoOutputFrame<'a> {
+    pub data: &'a [u8],
+    pub width: usize,
+    pub height: usize,
+    pub bytes_per_row: usize,
+    pub pixel_format: PixelFormat,
+}
+
+impl Output {
+    pub fn enable_video(&self, mode: ffi::DisplayModeType) -> Result<(), DeckLinkError> {
+        unsafe { ffi::output_enable_video(self.0, mode)? }
+            .into_result("IDeckLinkOutput::EnableVideoOutput")
+    }
+
+    pub fn disable_video(&self) -> Result<(), DeckLinkError> {
+        unsafe { ffi::output_disable_video(self.0) }
+            .into_result("IDeckLinkOutput::DisableVideoOutput")
+    }
+
+    /// Copies the frame into a buffer owned by the device and schedules it to be displayed at
+    /// `display_time` of the playback started with [`Self::start_scheduled_playback`].
+    pub fn schedule_video_frame(
+        &self,
+        frame: VideoOutputFrame<'_>,
+        display_time: Duration,
+        duration: Duration,
+    ) -> Result<(), DeckLinkError> {
+        if frame.data.len() < frame.height * frame.bytes_per_row {
+            return Err(DeckLinkError::DeckLinkCallFailed(
+                "IDeckLinkOutput::ScheduleVideoFrame",
+                HResult::InvalidArg,
+            ));
+        }
+
+        unsafe {
+            ffi::output_schedule_video_frame(
+                self.0,
+                frame.width as i32,
+                frame.height as i32,
+                frame.bytes_per_row as i32,
+                frame.pixel_format,
+                frame.data,
+                display_time.as_nanos() as i64,
+                duration.as_nanos() as i64,
+                TIME_SCALE,
+            )?
+        }
+        .into_result("IDeckLinkOutput::ScheduleVideoFrame")
+    }
+
+    /// Enables timestamped audio output, samples scheduled with [`Self::schedule_audio_samples`]
+    /// are played in sync with the scheduled video frames.
+    pub fn enable_audio(
+        &self,
+        sample_rate: u32,
+        sample_type: ffi::AudioSampleType,
+        channels: u32,
+    ) -> Result<(), DeckLinkError> {
+        unsafe { ffi::output_enable_audio(self.0, sample_rate, sample_type, channels)? }
+            .into_result("IDeckLinkOutput::EnableAudioOutput")
+    }
+
+    pub fn disable_audio(&self) -> Result<(), DeckLinkError> {
+        unsafe { ffi::output_disable_audio(self.0) }
+            .into_result("IDeckLinkOutput::DisableAudioOutput")
+    }
+
+    /// Samples scheduled between [`Self::begin_audio_preroll`] and [`Self::end_audio_preroll`]
+    /// are buffered before the playback starts.
+    pub fn begin_audio_preroll(&self) -> Result<(), DeckLinkError> {
+        unsafe { ffi::output_begin_audio_preroll(self.0) }
+            .into_result("IDeckLinkOutput::BeginAudioPreroll")
+    }
+
+    pub fn end_audio_preroll(&self) -> Result<(), DeckLinkError> {
+        unsafe { ffi::output_end_audio_preroll(self.0) }
+            .into_result("IDeckLinkOutput::EndAudioPreroll")
+    }
+
+    /// Schedules interleaved samples to be played at `stream_time`. `data` has to contain
+    /// `sample_frame_count` samples for every channel in the format passed to
+    /// [`Self::enable_audio`]. Returns the number of sample frames that were buffered, the rest
+    /// has to be scheduled again later.
+    ///
+    /// Captured audio can be passed through by scheduling
+    /// [`AudioInputPacket::raw_bytes`](crate::AudioInputPacket::raw_bytes) at
+    /// [`AudioInputPacket::packet_time`](crate::AudioInputPacket::packet_time).
+    pub fn schedule_audio_samples(
+        &self,
+        data: &[u8],
+        sample_frame_count: u32,
+        stream_time: Duration,
+    ) -> Result<u32, DeckLinkError> {
+        let mut written = 0;
+        unsafe {
+            ffi::output_schedule_audio_samples(
+                self.0,
+                data,
+                sample_frame_count,
+                stream_time.as_nanos() as i64,
+                TIME_SCALE,
+                &mut written,
+            )
+        }
+        .into_result("IDeckLinkOutput::ScheduleAudioSamples")?;
+        Ok(written)
+    }
+
+    pub fn buffered_audio_sample_frame_count(&self) -> Result<u32, DeckLinkError> {
+        let mut count = 0;
+        unsafe { ffi::output_buffered_audio_sample_frame_count(self.0, &mut count) }
+            .into_result("IDeckLinkOutput::GetBufferedAudioSampleFrameCount")?;
+        Ok(count)
+    }
+
+    pub fn flush_buffered_audio_samples(&self) -> Result<(), DeckLinkError> {
+        unsafe { ffi::output_flush_buffered_audio_samples(self.0) }
+            .into_result("IDeckLinkOutput::FlushBufferedAudioSamples")
+    }
+
+    pub fn start_scheduled_playback(&self, start_time: Duration) -> Result<(), DeckLinkError> {
+        unsafe {
+            ffi::output_start_scheduled_playback(self.0, start_time.as_nanos() as i64, TIME_SCALE)
+        }
+        .into_result("IDeckLinkOutput::StartScheduledPlayback")
+    }
+
+    pub fn stop_scheduled_playback(&self) -> Result<(), DeckLinkError> {
+        unsafe { ffi::output_stop_scheduled_playback(self.0) }
+            .into_result("IDeckLinkOutput::StopScheduledPlayback")
+    }
+
+    /// Whether the output is locked to the reference (genlock) input.
+    pub fn reference_status(&self) -> Result<ReferenceStatus, DeckLinkError> {
+        let mut status = ReferenceStatus::Unlocked;
+        unsafe { ffi::output_reference_status(self.0, &mut status)? }
+            .into_result("IDeckLinkOutput::GetReferenceStatus")?;
+        Ok(status)
+    }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        unsafe { ffi::output_release(self.0) };
+    }
+}
+
+unsafe impl Send for Output {}
+unsafe impl Sync for Output {}
@@ -1,6 +1,6 @@
 use std::ptr::null_mut;
 
-use crate::DeckLinkError;
+use crate::{DeckLink, DeckLinkError, ProfileId};
 
 use super::ffi::{self, HResult};
 
@@ -77,6 +77,18 @@ impl ProfileManager {
             .map(|wrapper| Profile(wrapper.ptr))
             .collect())
     }
+
+    /// Returns `None` if the device does not support the profile.
+    pub fn profile(&self, id: ProfileId) -> Result<Option<Profile>, DeckLinkError> {
+        let mut profile = null_mut();
+        match unsafe { ffi::profile_manager_profile(self.0, id, &mut profile)? } {
+            HResult::InvalidArg => Ok(None),
+            hresult => {
+                hresult.into_result("IDeckLinkProfileManager::GetProfile")?;
+                Ok(Some(Profile(profile)))
+            }
+        }
+    }
 }
 
 impl Drop for ProfileManager {
@@ -103,6 +115,39 @@ impl Profile {
             Ok(is_active)
         }
     }
+
+    pub fn id(&self) -> Result<ProfileId, DeckLinkError> {
+        let mut id = ProfileId::OneSubDeviceFullDuplex;
+        unsafe { ffi::profile_profile_id(self.0, &mut id)? }
+            .into_result("IDeckLinkProfileAttributes::GetInt(BMDDeckLinkProfileID)")?;
+        Ok(id)
+    }
+
+    /// Activates the profile on the device and all its peers. Activation is
+    /// asynchronous, [`Profile::is_active`] returns `true` after it completes.
+    pub fn set_active(&self) -> Result<(), DeckLinkError> {
+        unsafe { ffi::profile_set_active(self.0) }.into_result("IDeckLinkProfile::SetActive")
+    }
+
+    /// Profiles of the other sub-devices that are changed together with
+    /// this profile.
+    pub fn peers(&self) -> Result<Vec<Profile>, DeckLinkError> {
+        let mut peers = vec![];
+        unsafe { ffi::profile_peers(self.0, &mut peers) }
+            .into_result("IDeckLinkProfile::GetPeers")?;
+        Ok(peers
+            .into_iter()
+            .map(|wrapper| Profile(wrapper.ptr))
+            .collect())
+    }
+
+    /// Sub-device affected when this profile becomes active.
+    pub fn device(&self) -> Result<DeckLink, DeckLinkError> {
+        let mut device = null_mut();
+        unsafe { ffi::profile_device(self.0, &mut device) }
+            .into_result("IDeckLinkProfile::GetDevice")?;
+        Ok(DeckLink(device))
+    }
 }
 
 impl Drop for Profile {
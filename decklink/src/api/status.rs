@@ -0,0 +1,45 @@
+use crate::{DeckLinkError, DisplayModeType};
+
+use super::{HResult, ffi};
+
+/// Live status of a device, e.g. whether it is locked to a reference (genlock) signal.
+pub struct Status(pub(super) *mut ffi::IDeckLinkStatus);
+
+impl Status {
+    /// Returns `None` if the device does not have a reference input.
+    pub fn reference_signal_locked(&self) -> Result<Option<bool>, DeckLinkError> {
+        let mut value = false;
+        match unsafe { ffi::status_reference_signal_locked(self.0, &mut value) } {
+            HResult::NotImplementedError => Ok(None),
+            hresult => {
+                hresult.into_result(
+                    "IDeckLinkStatus::GetFlag(bmdDeckLinkStatusReferenceSignalLocked)",
+                )?;
+                Ok(Some(value))
+            }
+        }
+    }
+
+    /// Display mode of the reference signal. Returns `None` if the device does not have
+    /// a reference input or the reference signal is not locked.
+    pub fn reference_signal_mode(&self) -> Result<Option<DisplayModeType>, DeckLinkError> {
+        let mut value = DisplayModeType::ModeUnknown;
+        match unsafe { ffi::status_reference_signal_mode(self.0, &mut value)? } {
+            HResult::NotImplementedError | HResult::Fail => Ok(None),
+            hresult => {
+                hresult
+                    .into_result("IDeckLinkStatus::GetInt(bmdDeckLinkStatusReferenceSignalMode)")?;
+                Ok(Some(value))
+            }
+        }
+    }
+}
+
+impl Drop for Status {
+    fn drop(&mut self) {
+        unsafe { ffi::status_release(self.0) };
+    }
+}
+
+unsafe impl Send for Status {}
+unsafe impl Sync for Status {}
@@ -458,6 +458,31 @@ pub mod ffi {
         VideoInputAnamorphicUpconversion,
     }
 
+    #[derive(Debug, Copy, Clone)]
+    pub enum FieldDominance {
+        UnknownFieldDominance,
+        LowerFieldFirst,
+        UpperFieldFirst,
+        ProgressiveFrame,
+        ProgressiveSegmentedFrame,
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum ProfileId {
+        OneSubDeviceFullDuplex,
+        OneSubDeviceHalfDuplex,
+        TwoSubDevicesFullDuplex,
+        TwoSubDevicesHalfDuplex,
+        FourSubDevicesHalfDuplex,
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum ReferenceStatus {
+        Unlocked,
+        NotSupportedByHardware,
+        Locked,
+    }
+
     #[derive(Debug, Copy, Clone, Default)]
     pub struct SupportedVideoModeFlags {
         pub supports_keying: bool,
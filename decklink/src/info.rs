@@ -112,6 +112,8 @@ pub struct ConfigurationInfo {
     pub audio_input_scale: Option<f64>,
     pub headphone_volume: Option<f64>,
 
+    pub reference_input_timing_offset: Option<i64>,
+
     pub device_label: Option<String>,
     pub device_serial_number: Option<String>,
     pub device_company: Option<String>,
@@ -131,6 +133,8 @@ impl DeckLinkConfiguration {
                 .get_float(FloatConfigurationId::ConfigDigitalAudioInputScale)?,
             headphone_volume: self.get_float(FloatConfigurationId::ConfigHeadphoneVolume)?,
 
+            reference_input_timing_offset: self.reference_input_timing_offset()?,
+
             device_label: self.get_string(StringConfigurationId::ConfigDeviceInformationLabel)?,
             device_serial_number: self
                 .get_string(StringConfigurationId::ConfigDeviceInformationSerialNumber)?,
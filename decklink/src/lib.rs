@@ -1,13 +1,13 @@
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
 mod api;
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
 mod enums;
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
 mod info;
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
 mod input_callback;
 
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
 mod lib {
     use crate::api;
     use crate::enums;
@@ -21,7 +21,10 @@ mod lib {
     pub use enums::ffi::AudioSampleType;
     pub use enums::ffi::DetectedVideoInputFormatFlags;
     pub use enums::ffi::DisplayModeType;
+    pub use enums::ffi::FieldDominance;
     pub use enums::ffi::PixelFormat;
+    pub use enums::ffi::ProfileId;
+    pub use enums::ffi::ReferenceStatus;
     pub use enums::ffi::SupportedVideoModeFlags;
     pub use enums::ffi::VideoConnection;
     pub use enums::ffi::VideoIOSupport;
@@ -29,11 +32,19 @@ mod lib {
     pub use enums::ffi::VideoInputFlags;
     pub use enums::ffi::VideoInputFormatChangedEvents;
 
+    pub use api::AncillaryPacket;
     pub use api::DeckLink;
     pub use api::DisplayMode;
+    pub use api::device::DeckLinkConfiguration;
     pub use api::input::AudioInputPacket;
     pub use api::input::Input;
     pub use api::input::VideoInputFrame;
+    pub use api::keyer::Keyer;
+    pub use api::output::Output;
+    pub use api::output::VideoOutputFrame;
+    pub use api::profile::Profile;
+    pub use api::profile::ProfileManager;
+    pub use api::status::Status;
     pub use input_callback::InputCallback;
     pub use input_callback::InputCallbackResult;
 
@@ -57,5 +68,5 @@ mod lib {
     }
 }
 
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
 pub use lib::*;
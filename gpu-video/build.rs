@@ -1,6 +1,30 @@
 fn main() {
-    #[cfg(feature = "transcoder")]
-    build_transcoding_shader();
+    #[cfg(any(
+        windows,
+        all(
+            unix,
+            not(any(target_os = "macos", target_os = "ios", target_os = "emscripten"))
+        )
+    ))]
+    {
+        build_compute_shader(
+            "src/backends/vulkan/vulkan_decoder/pixel_format_converter.wgsl",
+            "pixel_format_converter.spv",
+        );
+        build_compute_shader(
+            "src/backends/vulkan/vulkan_decoder/deinterlacer.wgsl",
+            "deinterlacer.spv",
+        );
+        build_compute_shader(
+            "src/backends/vulkan/vulkan_encoder/input_converter.wgsl",
+            "input_converter.spv",
+        );
+        #[cfg(feature = "transcoder")]
+        build_compute_shader(
+            "src/backends/vulkan/vulkan_transcoder/shader.wgsl",
+            "transcoding_shader.spv",
+        );
+    }
 
     cfg_aliases::cfg_aliases! {
         vulkan: {
@@ -15,26 +39,20 @@ fn main() {
     }
 }
 
-// cfg vulkan && feature "transcoder"
-#[cfg(all(
-    any(
-        windows,
-        all(
-            unix,
-            not(any(target_os = "macos", target_os = "ios", target_os = "emscripten"))
-        )
-    ),
-    feature = "transcoder"
+// cfg vulkan
+#[cfg(any(
+    windows,
+    all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "emscripten"))
+    )
 ))]
-fn build_transcoding_shader() {
-    println!("cargo:rerun-if-changed=src/backends/vulkan/vulkan_transcoder/shader.wgsl");
+fn build_compute_shader(source_path: &str, output_name: &str) {
+    println!("cargo:rerun-if-changed={source_path}");
 
+    let source = std::fs::read_to_string(source_path).unwrap();
     let mut front = naga::front::wgsl::Frontend::new();
-    let parsed = front
-        .parse(include_str!(
-            "src/backends/vulkan/vulkan_transcoder/shader.wgsl"
-        ))
-        .unwrap();
+    let parsed = front.parse(&source).unwrap();
     let mut validator = naga::valid::Validator::new(
         naga::valid::ValidationFlags::all(),
         naga::valid::Capabilities::all(),
@@ -58,7 +76,7 @@ fn build_transcoding_shader() {
     .unwrap();
 
     let out_dir = std::env::var("OUT_DIR").unwrap();
-    let out_path = std::path::Path::new(&out_dir).join("transcoding_shader.spv");
+    let out_path = std::path::Path::new(&out_dir).join(output_name);
     let bytes: Vec<u8> = compiled
         .iter()
         .flat_map(|word| word.to_le_bytes())
@@ -46,6 +46,7 @@ fn main() {
         let data = EncodedInputChunk {
             data: chunk,
             pts: None,
+            dts: None,
         };
 
         let frames = decoder.decode(data).unwrap();
@@ -44,6 +44,7 @@ fn main() {
         let chunk = EncodedInputChunk {
             data: chunk,
             pts: None,
+            dts: None,
         };
 
         let frames = decoder.decode(chunk).unwrap();
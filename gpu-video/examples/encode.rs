@@ -8,8 +8,9 @@ fn main() {
     use gpu_video::{
         InputFrame, RawFrameData, VideoInstance,
         parameters::{
-            EncoderParametersH264, EncoderParametersH265, RateControl, VideoAdapterDescriptor,
-            VideoDeviceDescriptor, VideoInstanceDescriptor, VideoParameters,
+            EncoderParametersH264, EncoderParametersH265, PixelFormat, RateControl,
+            VideoAdapterDescriptor, VideoDeviceDescriptor, VideoInstanceDescriptor,
+            VideoParameters,
         },
     };
 
@@ -48,6 +49,7 @@ fn main() {
                 width,
                 height,
                 target_framerate: 24.into(),
+                pixel_format: PixelFormat::Nv12,
             },
             output_parameters: video_device
                 .encoder_output_parameters_h264_high_quality(RateControl::VariableBitrate {
@@ -65,6 +67,7 @@ fn main() {
                 width,
                 height,
                 target_framerate: 24.into(),
+                pixel_format: PixelFormat::Nv12,
             },
             output_parameters: video_device
                 .encoder_output_parameters_h265_high_quality(RateControl::VariableBitrate {
@@ -84,15 +87,26 @@ fn main() {
             frame: vec![0; width.get() as usize * height.get() as usize * 3 / 2],
             width: width.get(),
             height: height.get(),
+            planes: None,
         },
         pts: None,
+        qp: None,
     };
 
     while let Ok(()) = nv12.read_exact(&mut frame.data.frame) {
-        let h264 = encoder_h264.encode(&frame, false).expect("encode");
-        output_file_h264.write_all(&h264.data).expect("write");
-        let h265 = encoder_h265.encode(&frame, false).expect("encode");
-        output_file_h265.write_all(&h265.data).expect("write");
+        for chunk in encoder_h264.encode(&frame, false).expect("encode") {
+            output_file_h264.write_all(&chunk.data).expect("write");
+        }
+        for chunk in encoder_h265.encode(&frame, false).expect("encode") {
+            output_file_h265.write_all(&chunk.data).expect("write");
+        }
+    }
+
+    for chunk in encoder_h264.flush().expect("flush") {
+        output_file_h264.write_all(&chunk.data).expect("write");
+    }
+    for chunk in encoder_h265.flush().expect("flush") {
+        output_file_h265.write_all(&chunk.data).expect("write");
     }
 }
 
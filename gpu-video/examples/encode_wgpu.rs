@@ -8,7 +8,7 @@ use gpu_video::{
 fn main() {
     use gpu_video::{
         InputFrame,
-        parameters::{RateControl, VideoDeviceDescriptor, VideoParameters},
+        parameters::{PixelFormat, RateControl, VideoDeviceDescriptor, VideoParameters},
     };
     use std::{io::Write, num::NonZeroU32};
 
@@ -59,6 +59,7 @@ fn main() {
                     width,
                     height,
                     target_framerate: 30.into(),
+                    pixel_format: PixelFormat::Nv12,
                 },
                 output_parameters: video_device
                     .encoder_output_parameters_h264_high_quality(RateControl::VariableBitrate {
@@ -79,6 +80,7 @@ fn main() {
                     width,
                     height,
                     target_framerate: 30.into(),
+                    pixel_format: PixelFormat::Nv12,
                 },
                 output_parameters: video_device
                     .encoder_output_parameters_h265_high_quality(RateControl::VariableBitrate {
@@ -105,22 +107,35 @@ fn main() {
                 InputFrame {
                     data: wgpu_state.nv12_texture.clone(),
                     pts: None,
+                    qp: None,
                 },
                 false,
             )
             .unwrap();
-        output_file_h264.write_all(&h264.data).unwrap();
+        for chunk in h264 {
+            output_file_h264.write_all(&chunk.data).unwrap();
+        }
 
         let h265 = encoder_h265
             .encode(
                 InputFrame {
                     data: wgpu_state.nv12_texture.clone(),
                     pts: None,
+                    qp: None,
                 },
                 false,
             )
             .unwrap();
-        output_file_h265.write_all(&h265.data).unwrap();
+        for chunk in h265 {
+            output_file_h265.write_all(&chunk.data).unwrap();
+        }
+    }
+
+    for chunk in encoder_h264.flush().unwrap() {
+        output_file_h264.write_all(&chunk.data).unwrap();
+    }
+    for chunk in encoder_h265.flush().unwrap() {
+        output_file_h265.write_all(&chunk.data).unwrap();
     }
 }
 
@@ -39,6 +39,7 @@ pub fn run_decoder(
         let frame = EncodedInputChunk {
             data: &buffer[..n],
             pts: None,
+            dts: None,
         };
 
         let decoded = decoder.decode(frame).unwrap();
@@ -108,6 +108,7 @@ fn main() {
         let input = EncodedInputChunk {
             data: &buffer[..n],
             pts: None,
+            dts: None,
         };
         let output = transcoder.transcode(input).unwrap();
 
@@ -82,6 +82,26 @@ pub enum DeviceType {
     Cpu,
 }
 
+/// Video codec supported by the queues of a [`VideoQueueFamilyInfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VideoCodec {
+    H264,
+    H265,
+    Av1,
+}
+
+/// Queue family of an adapter that supports video decoding or encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoQueueFamilyInfo {
+    pub family_index: u32,
+    pub queue_count: u32,
+    /// The family supports neither graphics nor compute operations, which usually means its
+    /// queues run on a dedicated video engine.
+    pub dedicated: bool,
+    pub decode_codecs: Vec<VideoCodec>,
+    pub encode_codecs: Vec<VideoCodec>,
+}
+
 #[derive(Clone)]
 pub struct VideoAdapterInfo {
     pub name: String,
@@ -95,6 +115,47 @@ pub struct VideoAdapterInfo {
     pub supports_encoding: bool,
     pub decode_capabilities: DecodeCapabilities,
     pub encode_capabilities: EncodeCapabilities,
+    pub video_queue_families: Vec<VideoQueueFamilyInfo>,
+}
+
+impl VideoAdapterInfo {
+    /// Estimates how well the adapter is suited for video processing, higher is better. Adapters
+    /// with more supported codecs, more video queues on dedicated video engines and discrete GPUs
+    /// are preferred. Used to sort the adapters returned by
+    /// [`VideoInstance::enumerate_video_adapters`](crate::VideoInstance::enumerate_video_adapters).
+    pub fn suitability_score(&self) -> u32 {
+        let decode = &self.decode_capabilities;
+        let encode = &self.encode_capabilities;
+        let codec_count = [
+            decode.h264.is_some(),
+            decode.h265.is_some(),
+            decode.av1.is_some(),
+            encode.h264.is_some(),
+            encode.h265.is_some(),
+        ]
+        .into_iter()
+        .filter(|supported| *supported)
+        .count() as u32;
+
+        let video_queue_score = self
+            .video_queue_families
+            .iter()
+            .map(|family| match family.dedicated {
+                true => 20 * family.queue_count,
+                false => 5 * family.queue_count,
+            })
+            .sum::<u32>()
+            .min(200);
+
+        let device_type_score = match self.device_type {
+            DeviceType::DiscreteGpu => 300,
+            DeviceType::IntegratedGpu => 100,
+            DeviceType::VirtualGpu => 50,
+            DeviceType::Other | DeviceType::Cpu => 0,
+        };
+
+        100 * codec_count + video_queue_score + device_type_score
+    }
 }
 
 impl Debug for VideoAdapterInfo {
@@ -109,6 +170,7 @@ impl Debug for VideoAdapterInfo {
             .field("device", &self.device)
             .field("supports_decoding", &self.supports_decoding)
             .field("supports_encoding", &self.supports_encoding)
+            .field("video_queue_families", &self.video_queue_families)
             .finish()
     }
 }
@@ -18,11 +18,13 @@ pub(crate) mod vulkan_transcoder;
 pub(crate) mod wrappers;
 
 pub use vulkan_adapter::{VulkanAdapter, VulkanAdapterInfo, VulkanAdapterInitError};
-pub use vulkan_decoder::{VulkanDecoder, VulkanDecoderError};
+pub use vulkan_decoder::VulkanDecoderError;
 pub use vulkan_device::{VulkanDevice, VulkanDeviceInitError};
 #[cfg(feature = "transcoder")]
 pub use vulkan_transcoder::{VulkanTranscoder, VulkanTranscoderError};
 // TODO: expose-backend api
+pub(crate) use vulkan_decoder::VulkanDecoder;
+// TODO: expose-backend api
 pub(crate) use vulkan_encoder::{VulkanEncoder, VulkanEncoderError};
 pub use vulkan_instance::{VulkanInstance, VulkanInstanceInitError};
 
@@ -117,4 +119,13 @@ pub enum VulkanCommonError {
         requested: vk::Extent2D,
         max_extent: vk::Extent2D,
     },
+
+    #[error("The device does not support exporting memory")]
+    ExternalMemoryUnsupported,
+
+    #[error("The device does not support exporting timeline semaphores")]
+    ExternalSemaphoreUnsupported,
+
+    #[error("Cannot find a memory type suitable for the allocation")]
+    NoSuitableMemoryType,
 }
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, num::NonZeroU32};
+use std::{collections::VecDeque, num::NonZeroU32, ops::RangeInclusive};
 
 use ash::vk;
 
@@ -8,15 +8,30 @@ use crate::{
         NativeEncodeCapabilities, NativeEncodeProfileCapabilities,
         NativeEncodeQualityLevelProperties,
     },
-    backends::vulkan::vulkan_encoder::FullEncoderParameters,
+    backends::vulkan::vulkan_encoder::{FullEncoderParameters, PictureType},
     backends::vulkan::wrappers::ProfileInfo,
     parameters::RateControl,
 };
 
+pub(crate) mod av1;
 pub(crate) mod h264;
 pub(crate) mod h265;
 
-pub(crate) trait EncodeCodec: Codec {
+pub(crate) trait EncodeCodec: Codec + EncodeCodecCapabilities {
+    type Profile: Copy + std::fmt::Debug + Send;
+
+    type OwnedParameters;
+
+    type VideoEncodeSessionParametersAddInfo<'a>;
+    type VideoEncodeSessionParametersCreateInfo<'a>: vk::ExtendsVideoSessionParametersCreateInfoKHR;
+
+    fn encode_parameters_add_info<'a: 'b, 'b>(
+        parameters: &'b Self::VkParameters<'a>,
+    ) -> Self::VideoEncodeSessionParametersAddInfo<'b>;
+    fn encode_parameters_create_info<'a: 'b, 'b>(
+        add_info: &'b Self::VideoEncodeSessionParametersAddInfo<'a>,
+    ) -> Self::VideoEncodeSessionParametersCreateInfo<'b>;
+
     fn profile_info<'a>(params: &FullEncoderParameters<Self>) -> ProfileInfo<'a>;
     fn encode_profile_capabilities(
         caps: &Self::NativeEncodeCodecCapabilities,
@@ -40,14 +55,14 @@ pub(crate) trait EncodeCodec: Codec {
     type BitstreamUnitData;
     fn bitstream_unit_data(
         codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'_>,
-        is_idr: bool,
+        picture_type: PictureType,
     ) -> Self::BitstreamUnitData;
-    type BitstreamUnitInfo<'a>;
+    type BitstreamUnitInfo<'a>: Copy;
     fn bitstream_unit_info<'a>(
         data: &'a Self::BitstreamUnitData,
         rate_control: RateControl,
         capabilities: &NativeEncodeQualityLevelProperties<Self>,
-        is_idr: bool,
+        picture_type: PictureType,
     ) -> Self::BitstreamUnitInfo<'a>;
 
     type ReferenceInfo: Copy + Send + 'static;
@@ -55,17 +70,18 @@ pub(crate) trait EncodeCodec: Codec {
     fn reference_list_info(
         counters: &Self::EncodingCounters,
         active_reference_slots: &VecDeque<(usize, Self::ReferenceInfo)>,
+        picture_type: PictureType,
     ) -> Self::ReferenceListInfo;
     fn new_slot_reference_info(
         counters: &Self::EncodingCounters,
-        is_idr: bool,
+        picture_type: PictureType,
     ) -> Self::ReferenceInfo;
 
     type PictureInfoData;
     fn picture_info_data(
         counters: &Self::EncodingCounters,
         codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'_>,
-        is_idr: bool,
+        picture_type: PictureType,
         ref_lists: &Self::ReferenceListInfo,
     ) -> Self::PictureInfoData;
     type PictureInfo<'a>: vk::ExtendsVideoEncodeInfoKHR;
@@ -83,8 +99,10 @@ pub(crate) trait EncodeCodec: Codec {
     }
 
     type EncodingCounters: Default + Clone + Copy + Send;
-    fn advance_counters(counters: &mut Self::EncodingCounters, is_idr: bool);
+    fn advance_counters(counters: &mut Self::EncodingCounters, picture_type: PictureType);
     fn counters_idr(counters: &mut Self::EncodingCounters);
+    /// `display_order` is counted from the last IDR picture
+    fn set_display_order(counters: &mut Self::EncodingCounters, display_order: u32);
 
     type CodecRateControlLayerInfo<'a>: vk::ExtendsVideoEncodeRateControlLayerInfoKHR;
     type CodecRateControlInfo<'a>: vk::ExtendsVideoBeginCodingInfoKHR
@@ -95,6 +113,7 @@ pub(crate) trait EncodeCodec: Codec {
     fn codec_rate_control_info<'a>(
         layers: Option<&'a [vk::VideoEncodeRateControlLayerInfoKHR<'a>]>,
         idr_period: u32,
+        consecutive_b_frames: u32,
     ) -> Option<Self::CodecRateControlInfo<'a>>;
 
     type CodecWriteParametersInfo: Copy;
@@ -109,50 +128,66 @@ pub(crate) trait EncodeCodec: Codec {
         user_provided: Option<NonZeroU32>,
     ) -> NonZeroU32;
 
+    /// QP values which can be used with [`RateControl::ConstantQp`](crate::parameters::RateControl::ConstantQp)
+    fn qp_range<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+    ) -> RangeInclusive<i32>;
+
     fn resolve_max_references<'a>(
         quality_level_properties: &Self::CodecSpecificEncodeQualityLevelProperties<'a>,
         codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
         user_provided: Option<NonZeroU32>,
     ) -> NonZeroU32;
+
+    fn resolve_consecutive_b_frames<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+        profile: Self::Profile,
+        max_references: NonZeroU32,
+        user_provided: Option<u32>,
+    ) -> Result<u32, VulkanEncoderError>;
+
+    /// Number of slices every frame is split into
+    fn resolve_slice_count<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+        height: NonZeroU32,
+        user_provided: Option<NonZeroU32>,
+    ) -> Result<NonZeroU32, VulkanEncoderError>;
+
+    /// Whether slices of one picture can have different types, which intra refresh relies on
+    fn supports_different_slice_types<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+    ) -> bool;
 }
 
 pub(crate) trait Codec: CodecCapabilities + std::fmt::Debug + Clone {
-    type Profile: Copy + std::fmt::Debug + Send;
-
     // Parameters
-    type OwnedParameters;
     type VkParameters<'a>;
 
     type VideoDecodeSessionParametersAddInfo<'a>;
     type VideoDecodeSessionParametersCreateInfo<'a>: vk::ExtendsVideoSessionParametersCreateInfoKHR;
 
-    type VideoEncodeSessionParametersAddInfo<'a>;
-    type VideoEncodeSessionParametersCreateInfo<'a>: vk::ExtendsVideoSessionParametersCreateInfoKHR;
-
     fn decode_parameters_add_info<'a: 'b, 'b>(
         parameters: &'b Self::VkParameters<'a>,
     ) -> Self::VideoDecodeSessionParametersAddInfo<'b>;
     fn decode_parameters_create_info<'a: 'b, 'b>(
         add_info: &'b Self::VideoDecodeSessionParametersAddInfo<'a>,
     ) -> Self::VideoDecodeSessionParametersCreateInfo<'b>;
-
-    fn encode_parameters_add_info<'a: 'b, 'b>(
-        parameters: &'b Self::VkParameters<'a>,
-    ) -> Self::VideoEncodeSessionParametersAddInfo<'b>;
-    fn encode_parameters_create_info<'a: 'b, 'b>(
-        add_info: &'b Self::VideoEncodeSessionParametersAddInfo<'a>,
-    ) -> Self::VideoEncodeSessionParametersCreateInfo<'b>;
 }
 
 pub(crate) trait CodecCapabilities: std::fmt::Debug + Clone {
     type CodecSpecificDecodeCapabilities<'a>: CodecSpecificDecodeCapabilities;
-    type CodecSpecificEncodeCapabilities<'a>: CodecSpecificEncodeCapabilities;
-    type CodecSpecificEncodeQualityLevelProperties<'a>: CodecSpecificEncoderQualityLevelProperties;
-    type NativeEncodeCodecCapabilities;
 
     fn static_decode_capabilities<'a>(
         codec_caps: &Self::CodecSpecificDecodeCapabilities<'a>,
     ) -> Self::CodecSpecificDecodeCapabilities<'static>;
+}
+
+/// Encode part of [`CodecCapabilities`], implemented only by codecs which can be encoded.
+pub(crate) trait EncodeCodecCapabilities: CodecCapabilities {
+    type CodecSpecificEncodeCapabilities<'a>: CodecSpecificEncodeCapabilities;
+    type CodecSpecificEncodeQualityLevelProperties<'a>: CodecSpecificEncoderQualityLevelProperties;
+    type NativeEncodeCodecCapabilities;
+
     fn static_encode_capabilities<'a>(
         codec_caps: &Self::CodecSpecificEncodeCapabilities<'a>,
     ) -> Self::CodecSpecificEncodeCapabilities<'static>;
@@ -0,0 +1,207 @@
+use std::{ptr::null_mut, sync::Arc};
+
+use ash::vk;
+
+use crate::{
+    backends::vulkan::{
+        codec::{Codec, CodecCapabilities, CodecSpecificDecodeCapabilities},
+        vulkan_decoder::VulkanDecoderError,
+        wrappers::ProfileInfo,
+    },
+    parser::av1::SequenceHeader,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Av1Codec;
+
+/// AV1 session parameters contain only the sequence header and cannot be updated, so there is
+/// no add info structure. The sequence header is passed directly to the create info.
+impl Codec for Av1Codec {
+    type VkParameters<'a> = VkAv1SequenceHeader;
+
+    type VideoDecodeSessionParametersAddInfo<'a> = &'a vk::native::StdVideoAV1SequenceHeader;
+    type VideoDecodeSessionParametersCreateInfo<'a> =
+        vk::VideoDecodeAV1SessionParametersCreateInfoKHR<'a>;
+
+    fn decode_parameters_add_info<'a: 'b, 'b>(
+        parameters: &'b Self::VkParameters<'a>,
+    ) -> Self::VideoDecodeSessionParametersAddInfo<'b> {
+        &parameters.sequence_header
+    }
+
+    fn decode_parameters_create_info<'a: 'b, 'b>(
+        add_info: &'b Self::VideoDecodeSessionParametersAddInfo<'a>,
+    ) -> Self::VideoDecodeSessionParametersCreateInfo<'b> {
+        vk::VideoDecodeAV1SessionParametersCreateInfoKHR::default().std_sequence_header(add_info)
+    }
+}
+
+impl CodecCapabilities for Av1Codec {
+    type CodecSpecificDecodeCapabilities<'a> = vk::VideoDecodeAV1CapabilitiesKHR<'a>;
+
+    fn static_decode_capabilities<'a>(
+        codec_caps: &Self::CodecSpecificDecodeCapabilities<'a>,
+    ) -> Self::CodecSpecificDecodeCapabilities<'static> {
+        vk::VideoDecodeAV1CapabilitiesKHR {
+            p_next: null_mut(),
+            _marker: Default::default(),
+            ..*codec_caps
+        }
+    }
+}
+
+impl<'a> CodecSpecificDecodeCapabilities for vk::VideoDecodeAV1CapabilitiesKHR<'a> {}
+
+/// Sequence header together with the structures it points to. The structures are boxed, so the
+/// pointers stay valid when this is moved.
+pub(crate) struct VkAv1SequenceHeader {
+    pub(crate) sequence_header: vk::native::StdVideoAV1SequenceHeader,
+    _color_config: Box<vk::native::StdVideoAV1ColorConfig>,
+    _timing_info: Option<Box<vk::native::StdVideoAV1TimingInfo>>,
+}
+
+impl From<&SequenceHeader> for VkAv1SequenceHeader {
+    fn from(seq: &SequenceHeader) -> Self {
+        let color = &seq.color_config;
+        let color_config = Box::new(vk::native::StdVideoAV1ColorConfig {
+            flags: vk::native::StdVideoAV1ColorConfigFlags {
+                _bitfield_align_1: [],
+                _bitfield_1: vk::native::StdVideoAV1ColorConfigFlags::new_bitfield_1(
+                    color.mono_chrome.into(),
+                    color.color_range.into(),
+                    color.separate_uv_delta_q.into(),
+                    color.color_description_present_flag.into(),
+                    0, // reserved
+                ),
+            },
+            BitDepth: color.bit_depth,
+            subsampling_x: color.subsampling_x.into(),
+            subsampling_y: color.subsampling_y.into(),
+            reserved1: 0,
+            color_primaries: color.color_primaries as u32,
+            transfer_characteristics: color.transfer_characteristics as u32,
+            matrix_coefficients: color.matrix_coefficients as u32,
+            chroma_sample_position: color.chroma_sample_position as u32,
+        });
+
+        let timing_info = seq.timing_info.as_ref().map(|timing_info| {
+            Box::new(vk::native::StdVideoAV1TimingInfo {
+                flags: vk::native::StdVideoAV1TimingInfoFlags {
+                    _bitfield_align_1: [],
+                    _bitfield_1: vk::native::StdVideoAV1TimingInfoFlags::new_bitfield_1(
+                        timing_info.equal_picture_interval.into(),
+                        0, // reserved
+                    ),
+                },
+                num_units_in_display_tick: timing_info.num_units_in_display_tick,
+                time_scale: timing_info.time_scale,
+                num_ticks_per_picture_minus_1: timing_info.num_ticks_per_picture_minus_1,
+            })
+        });
+
+        let sequence_header = vk::native::StdVideoAV1SequenceHeader {
+            flags: vk::native::StdVideoAV1SequenceHeaderFlags {
+                _bitfield_align_1: [],
+                _bitfield_1: vk::native::StdVideoAV1SequenceHeaderFlags::new_bitfield_1(
+                    seq.still_picture.into(),
+                    seq.reduced_still_picture_header.into(),
+                    seq.use_128x128_superblock.into(),
+                    seq.enable_filter_intra.into(),
+                    seq.enable_intra_edge_filter.into(),
+                    seq.enable_interintra_compound.into(),
+                    seq.enable_masked_compound.into(),
+                    seq.enable_warped_motion.into(),
+                    seq.enable_dual_filter.into(),
+                    seq.enable_order_hint.into(),
+                    seq.enable_jnt_comp.into(),
+                    seq.enable_ref_frame_mvs.into(),
+                    seq.frame_id_numbers_present_flag.into(),
+                    seq.enable_superres.into(),
+                    seq.enable_cdef.into(),
+                    seq.enable_restoration.into(),
+                    seq.film_grain_params_present.into(),
+                    seq.timing_info.is_some().into(),
+                    seq.initial_display_delay_present_flag.into(),
+                    0, // reserved
+                ),
+            },
+            seq_profile: seq.seq_profile as u32,
+            frame_width_bits_minus_1: seq.frame_width_bits_minus_1,
+            frame_height_bits_minus_1: seq.frame_height_bits_minus_1,
+            max_frame_width_minus_1: seq.max_frame_width_minus_1,
+            max_frame_height_minus_1: seq.max_frame_height_minus_1,
+            delta_frame_id_length_minus_2: seq.delta_frame_id_length_minus_2,
+            additional_frame_id_length_minus_1: seq.additional_frame_id_length_minus_1,
+            order_hint_bits_minus_1: seq.order_hint_bits_minus_1,
+            seq_force_integer_mv: seq.seq_force_integer_mv,
+            seq_force_screen_content_tools: seq.seq_force_screen_content_tools,
+            reserved1: [0; 5],
+            pColorConfig: &*color_config,
+            pTimingInfo: timing_info
+                .as_deref()
+                .map_or(std::ptr::null(), |timing_info| timing_info),
+        };
+
+        Self {
+            sequence_header,
+            _color_config: color_config,
+            _timing_info: timing_info,
+        }
+    }
+}
+
+pub(crate) struct Av1DecodeProfileInfo<'a> {
+    pub(crate) profile_info: Arc<ProfileInfo<'a>>,
+}
+
+impl<'a> Av1DecodeProfileInfo<'a> {
+    /// Only 8-bit 4:2:0 streams of the Main profile are supported.
+    pub(crate) fn from_sequence_header(
+        seq: &SequenceHeader,
+        decode_usage_info: vk::VideoDecodeUsageInfoKHR<'a>,
+    ) -> Result<Self, VulkanDecoderError> {
+        if seq.seq_profile != 0 {
+            return Err(VulkanDecoderError::InvalidInputData(format!(
+                "unsupported av1 profile: {}",
+                seq.seq_profile
+            )));
+        }
+
+        let color_config = &seq.color_config;
+        if color_config.mono_chrome {
+            return Err(VulkanDecoderError::InvalidInputData(
+                "monochrome av1 streams are not supported".to_string(),
+            ));
+        }
+
+        if color_config.bit_depth != 8 {
+            return Err(VulkanDecoderError::InvalidInputData(format!(
+                "unsupported bit depth: {}",
+                color_config.bit_depth
+            )));
+        }
+
+        // film grain is not applied by the decoder, the output is the same as without it
+        let av1_profile_info = vk::VideoDecodeAV1ProfileInfoKHR::default()
+            .std_profile(vk::native::StdVideoAV1Profile_STD_VIDEO_AV1_PROFILE_MAIN)
+            .film_grain_support(false);
+
+        let av1_profile_info: Box<dyn vk::ExtendsVideoProfileInfoKHR + Send + Sync> =
+            Box::new(av1_profile_info);
+        let decode_usage_info: Box<dyn vk::ExtendsVideoProfileInfoKHR + Send + Sync> =
+            Box::new(decode_usage_info);
+
+        let profile_info = vk::VideoProfileInfoKHR::default()
+            .video_codec_operation(vk::VideoCodecOperationFlagsKHR::DECODE_AV1)
+            .chroma_subsampling(vk::VideoChromaSubsamplingFlagsKHR::TYPE_420)
+            .luma_bit_depth(vk::VideoComponentBitDepthFlagsKHR::TYPE_8)
+            .chroma_bit_depth(vk::VideoComponentBitDepthFlagsKHR::TYPE_8);
+
+        Ok(Self {
+            profile_info: Arc::new(ProfileInfo::new(
+                profile_info,
+                vec![av1_profile_info, decode_usage_info],
+            )),
+        })
+    }
+}
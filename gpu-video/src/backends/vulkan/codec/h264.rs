@@ -5,7 +5,7 @@ use ash::vk;
 use crate::{
     backends::vulkan::codec::{
         Codec, CodecCapabilities, CodecSpecificDecodeCapabilities, CodecSpecificEncodeCapabilities,
-        CodecSpecificEncoderQualityLevelProperties,
+        CodecSpecificEncoderQualityLevelProperties, EncodeCodecCapabilities,
         h264::parameters::{VkH264PictureParameterSet, VkH264SequenceParameterSet},
     },
     backends::vulkan::vulkan_device::caps::{
@@ -31,9 +31,6 @@ pub(crate) struct H264VkParameters {
 }
 
 impl Codec for H264Codec {
-    type Profile = H264Profile;
-
-    type OwnedParameters = H264CodecParameters;
     type VkParameters<'a> = H264VkParameters;
 
     type VideoDecodeSessionParametersAddInfo<'a> =
@@ -41,11 +38,6 @@ impl Codec for H264Codec {
     type VideoDecodeSessionParametersCreateInfo<'a> =
         vk::VideoDecodeH264SessionParametersCreateInfoKHR<'a>;
 
-    type VideoEncodeSessionParametersAddInfo<'a> =
-        vk::VideoEncodeH264SessionParametersAddInfoKHR<'a>;
-    type VideoEncodeSessionParametersCreateInfo<'a> =
-        vk::VideoEncodeH264SessionParametersCreateInfoKHR<'a>;
-
     fn decode_parameters_add_info<'a: 'b, 'b>(
         parameters: &'b Self::VkParameters<'a>,
     ) -> Self::VideoDecodeSessionParametersAddInfo<'b> {
@@ -62,31 +54,10 @@ impl Codec for H264Codec {
             .max_std_pps_count(32)
             .parameters_add_info(add_info)
     }
-
-    fn encode_parameters_add_info<'a: 'b, 'b>(
-        parameters: &'b Self::VkParameters<'a>,
-    ) -> Self::VideoEncodeSessionParametersAddInfo<'b> {
-        vk::VideoEncodeH264SessionParametersAddInfoKHR::default()
-            .std_sp_ss(&parameters.sps)
-            .std_pp_ss(&parameters.pps)
-    }
-
-    fn encode_parameters_create_info<'a: 'b, 'b>(
-        add_info: &'b Self::VideoEncodeSessionParametersAddInfo<'a>,
-    ) -> Self::VideoEncodeSessionParametersCreateInfo<'b> {
-        vk::VideoEncodeH264SessionParametersCreateInfoKHR::default()
-            .max_std_sps_count(32)
-            .max_std_pps_count(32)
-            .parameters_add_info(add_info)
-    }
 }
 
 impl CodecCapabilities for H264Codec {
     type CodecSpecificDecodeCapabilities<'a> = vk::VideoDecodeH264CapabilitiesKHR<'a>;
-    type CodecSpecificEncodeCapabilities<'a> = vk::VideoEncodeH264CapabilitiesKHR<'a>;
-    type CodecSpecificEncodeQualityLevelProperties<'a> =
-        vk::VideoEncodeH264QualityLevelPropertiesKHR<'a>;
-    type NativeEncodeCodecCapabilities = NativeEncodeH264Capabilities;
 
     fn static_decode_capabilities<'a>(
         codec_caps: &Self::CodecSpecificDecodeCapabilities<'a>,
@@ -97,6 +68,13 @@ impl CodecCapabilities for H264Codec {
             ..*codec_caps
         }
     }
+}
+
+impl EncodeCodecCapabilities for H264Codec {
+    type CodecSpecificEncodeCapabilities<'a> = vk::VideoEncodeH264CapabilitiesKHR<'a>;
+    type CodecSpecificEncodeQualityLevelProperties<'a> =
+        vk::VideoEncodeH264QualityLevelPropertiesKHR<'a>;
+    type NativeEncodeCodecCapabilities = NativeEncodeH264Capabilities;
 
     fn static_encode_capabilities<'a>(
         codec_caps: &Self::CodecSpecificEncodeCapabilities<'a>,
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, num::NonZeroU32};
+use std::{collections::VecDeque, num::NonZeroU32, ops::RangeInclusive};
 
 use ash::vk;
 
@@ -6,16 +6,16 @@ use crate::{
     backends::vulkan::codec::{
         EncodeCodec,
         h264::{
-            H264Codec,
+            H264Codec, H264CodecParameters,
             parameters::{VkH264PictureParameterSet, VkH264SequenceParameterSet},
         },
     },
     backends::vulkan::vulkan_device::caps::{
         NativeEncodeProfileCapabilities, NativeEncodeQualityLevelProperties,
     },
-    backends::vulkan::vulkan_encoder::{FullEncoderParameters, VulkanEncoderError},
+    backends::vulkan::vulkan_encoder::{FullEncoderParameters, PictureType, VulkanEncoderError},
     backends::vulkan::wrappers::ProfileInfo,
-    parameters::RateControl,
+    parameters::{H264Profile, RateControl},
 };
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -32,6 +32,32 @@ pub(crate) struct H264WriteParametersInfo {
 }
 
 impl EncodeCodec for H264Codec {
+    type Profile = H264Profile;
+
+    type OwnedParameters = H264CodecParameters;
+
+    type VideoEncodeSessionParametersAddInfo<'a> =
+        vk::VideoEncodeH264SessionParametersAddInfoKHR<'a>;
+    type VideoEncodeSessionParametersCreateInfo<'a> =
+        vk::VideoEncodeH264SessionParametersCreateInfoKHR<'a>;
+
+    fn encode_parameters_add_info<'a: 'b, 'b>(
+        parameters: &'b Self::VkParameters<'a>,
+    ) -> Self::VideoEncodeSessionParametersAddInfo<'b> {
+        vk::VideoEncodeH264SessionParametersAddInfoKHR::default()
+            .std_sp_ss(&parameters.sps)
+            .std_pp_ss(&parameters.pps)
+    }
+
+    fn encode_parameters_create_info<'a: 'b, 'b>(
+        add_info: &'b Self::VideoEncodeSessionParametersAddInfo<'a>,
+    ) -> Self::VideoEncodeSessionParametersCreateInfo<'b> {
+        vk::VideoEncodeH264SessionParametersCreateInfoKHR::default()
+            .max_std_sps_count(32)
+            .max_std_pps_count(32)
+            .parameters_add_info(add_info)
+    }
+
     fn encode_profile_capabilities(
         caps: &Self::NativeEncodeCodecCapabilities,
         profile: Self::Profile,
@@ -67,6 +93,7 @@ impl EncodeCodec for H264Codec {
             parameters.width.get(),
             parameters.height.get(),
             parameters.max_references.get(),
+            parameters.consecutive_b_frames,
             parameters.color_space,
             parameters.color_range,
             parameters.framerate,
@@ -89,23 +116,24 @@ impl EncodeCodec for H264Codec {
     type BitstreamUnitData = vk::native::StdVideoEncodeH264SliceHeader;
     fn bitstream_unit_data(
         _codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'_>,
-        is_idr: bool,
+        picture_type: PictureType,
     ) -> Self::BitstreamUnitData {
         vk::native::StdVideoEncodeH264SliceHeader {
             flags: vk::native::StdVideoEncodeH264SliceHeaderFlags {
                 _bitfield_align_1: [],
                 _bitfield_1: vk::native::StdVideoEncodeH264SliceHeaderFlags::new_bitfield_1(
-                    1, // TODO: b-frames
+                    1, // direct_spatial_mv_pred_flag
                     1, // TODO: don't override always
                     0,
                 ),
             },
+            // Determined by the implementation when the frame is split into multiple slices
             first_mb_in_slice: 0,
-            slice_type: if is_idr {
-                vk::native::StdVideoH264SliceType_STD_VIDEO_H264_SLICE_TYPE_I
-            } else {
-                vk::native::StdVideoH264SliceType_STD_VIDEO_H264_SLICE_TYPE_P
-            }, // TODO: b-frames
+            slice_type: match picture_type {
+                PictureType::Idr => vk::native::StdVideoH264SliceType_STD_VIDEO_H264_SLICE_TYPE_I,
+                PictureType::P => vk::native::StdVideoH264SliceType_STD_VIDEO_H264_SLICE_TYPE_P,
+                PictureType::B => vk::native::StdVideoH264SliceType_STD_VIDEO_H264_SLICE_TYPE_B,
+            },
             slice_alpha_c0_offset_div2: 0,
             slice_beta_offset_div2: 0,
             slice_qp_delta: 0,
@@ -121,22 +149,24 @@ impl EncodeCodec for H264Codec {
         data: &'a Self::BitstreamUnitData,
         rate_control: RateControl,
         capabilities: &NativeEncodeQualityLevelProperties<Self>,
-        is_idr: bool,
+        picture_type: PictureType,
     ) -> Self::BitstreamUnitInfo<'a> {
         let mut slice_info = vk::VideoEncodeH264NaluSliceInfoKHR::default().std_slice_header(data);
 
-        if let RateControl::Disabled = rate_control {
-            if !capabilities.zeroed() {
+        match rate_control {
+            RateControl::Disabled if !capabilities.zeroed() => {
                 let qp = capabilities
                     .codec_quality_level_properties
                     .preferred_constant_qp;
 
-                if is_idr {
-                    slice_info.constant_qp = qp.qp_i;
-                } else {
-                    slice_info.constant_qp = qp.qp_p;
-                }
+                slice_info.constant_qp = match picture_type {
+                    PictureType::Idr => qp.qp_i,
+                    PictureType::P => qp.qp_p,
+                    PictureType::B => qp.qp_b,
+                };
             }
+            RateControl::ConstantQp { qp } => slice_info.constant_qp = qp as i32,
+            _ => {}
         }
 
         slice_info
@@ -147,11 +177,33 @@ impl EncodeCodec for H264Codec {
     fn reference_list_info(
         _counters: &Self::EncodingCounters,
         active_reference_slots: &VecDeque<(usize, Self::ReferenceInfo)>,
+        picture_type: PictureType,
     ) -> Self::ReferenceListInfo {
         let mut ref_list0 = [0xff; 32];
-        for (i, (slot, _)) in active_reference_slots.iter().rev().enumerate() {
-            ref_list0[i] = *slot as u8;
-        }
+        let mut ref_list1 = [0xff; 32];
+        let (num_ref_idx_l0_active, num_ref_idx_l1_active) = match picture_type {
+            PictureType::B => {
+                // B-frames are encoded after the reference that follows them in display order,
+                // so the most recent reference is in the future and the one before it is in
+                // the past.
+                let mut references = active_reference_slots.iter().rev();
+                if let Some((slot, _)) = references.next() {
+                    ref_list1[0] = *slot as u8;
+                }
+                if let Some((slot, _)) = references.next() {
+                    ref_list0[0] = *slot as u8;
+                }
+
+                (1, 1)
+            }
+            PictureType::Idr | PictureType::P => {
+                for (i, (slot, _)) in active_reference_slots.iter().rev().enumerate() {
+                    ref_list0[i] = *slot as u8;
+                }
+
+                (active_reference_slots.len(), 0)
+            }
+        };
 
         vk::native::StdVideoEncodeH264ReferenceListsInfo {
             flags: vk::native::StdVideoEncodeH264ReferenceListsInfoFlags {
@@ -160,10 +212,10 @@ impl EncodeCodec for H264Codec {
                     0, 0, 0,
                 ),
             },
-            num_ref_idx_l0_active_minus1: active_reference_slots.len().saturating_sub(1) as u8,
-            num_ref_idx_l1_active_minus1: 0,
+            num_ref_idx_l0_active_minus1: num_ref_idx_l0_active.saturating_sub(1) as u8,
+            num_ref_idx_l1_active_minus1: num_ref_idx_l1_active.saturating_sub(1) as u8,
             RefPicList0: ref_list0,
-            RefPicList1: [0xff; 32],
+            RefPicList1: ref_list1,
             refList0ModOpCount: 0,
             refList1ModOpCount: 0,
             refPicMarkingOpCount: 0,
@@ -175,14 +227,14 @@ impl EncodeCodec for H264Codec {
     }
     fn new_slot_reference_info(
         counters: &Self::EncodingCounters,
-        is_idr: bool,
+        picture_type: PictureType,
     ) -> Self::ReferenceInfo {
         vk::native::StdVideoEncodeH264ReferenceInfo {
             flags: vk::native::StdVideoEncodeH264ReferenceInfoFlags {
                 _bitfield_align_1: [],
                 _bitfield_1: vk::native::StdVideoEncodeH264ReferenceInfoFlags::new_bitfield_1(0, 0),
             },
-            primary_pic_type: primary_pic_type(is_idr),
+            primary_pic_type: primary_pic_type(picture_type),
             FrameNum: counters.frame_num,
             PicOrderCnt: counters.pic_order_cnt as i32,
             long_term_pic_num: 0,
@@ -195,15 +247,15 @@ impl EncodeCodec for H264Codec {
     fn picture_info_data(
         counters: &Self::EncodingCounters,
         _codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'_>,
-        is_idr: bool,
+        picture_type: PictureType,
         ref_lists: &Self::ReferenceListInfo,
     ) -> Self::PictureInfoData {
         vk::native::StdVideoEncodeH264PictureInfo {
             flags: vk::native::StdVideoEncodeH264PictureInfoFlags {
                 _bitfield_align_1: [],
                 _bitfield_1: vk::native::StdVideoEncodeH264PictureInfoFlags::new_bitfield_1(
-                    is_idr as u32,
-                    1, // TODO: must be the same as nal_ref_idc != 0
+                    (picture_type == PictureType::Idr) as u32,
+                    (picture_type != PictureType::B) as u32, // is_reference
                     0,
                     0, // long term refs
                     0, // adaptive reference control
@@ -213,7 +265,7 @@ impl EncodeCodec for H264Codec {
             seq_parameter_set_id: 0,
             pic_parameter_set_id: 0,
             idr_pic_id: counters.idr_pic_id,
-            primary_pic_type: primary_pic_type(is_idr),
+            primary_pic_type: primary_pic_type(picture_type),
             frame_num: counters.frame_num,
             PicOrderCnt: counters.pic_order_cnt as i32,
             temporal_id: 0,
@@ -239,10 +291,12 @@ impl EncodeCodec for H264Codec {
     }
 
     type EncodingCounters = H264EncodingCounters;
-    fn advance_counters(counters: &mut Self::EncodingCounters, is_idr: bool) {
-        counters.frame_num = counters.frame_num.wrapping_add(1);
-        counters.pic_order_cnt = counters.pic_order_cnt.wrapping_add(2);
-        if is_idr {
+    fn advance_counters(counters: &mut Self::EncodingCounters, picture_type: PictureType) {
+        // frame_num is only incremented after reference pictures
+        if picture_type != PictureType::B {
+            counters.frame_num = counters.frame_num.wrapping_add(1);
+        }
+        if picture_type == PictureType::Idr {
             counters.idr_pic_id = counters.idr_pic_id.wrapping_add(1);
         }
     }
@@ -250,6 +304,9 @@ impl EncodeCodec for H264Codec {
         counters.frame_num = 0;
         counters.pic_order_cnt = 0;
     }
+    fn set_display_order(counters: &mut Self::EncodingCounters, display_order: u32) {
+        counters.pic_order_cnt = display_order.wrapping_mul(2) as u8;
+    }
 
     type CodecRateControlLayerInfo<'a> = vk::VideoEncodeH264RateControlLayerInfoKHR<'a>;
     type CodecRateControlInfo<'a> = vk::VideoEncodeH264RateControlInfoKHR<'a>;
@@ -266,6 +323,7 @@ impl EncodeCodec for H264Codec {
             RateControl::VariableBitrate { .. } => {}
             RateControl::ConstantBitrate { .. } => {}
             RateControl::Disabled => {}
+            RateControl::ConstantQp { .. } => {}
         }
 
         Some(vec![layer_info])
@@ -273,6 +331,7 @@ impl EncodeCodec for H264Codec {
     fn codec_rate_control_info<'a>(
         layers: Option<&'a [vk::VideoEncodeRateControlLayerInfoKHR<'a>]>,
         idr_period: u32,
+        consecutive_b_frames: u32,
     ) -> Option<Self::CodecRateControlInfo<'a>> {
         let layers = layers?;
 
@@ -283,7 +342,7 @@ impl EncodeCodec for H264Codec {
                     vk::VideoEncodeH264RateControlFlagsKHR::REGULAR_GOP
                         | vk::VideoEncodeH264RateControlFlagsKHR::REFERENCE_PATTERN_FLAT,
                 )
-                .consecutive_b_frame_count(0)
+                .consecutive_b_frame_count(consecutive_b_frames)
                 .gop_frame_count(idr_period)
                 .idr_period(idr_period),
         )
@@ -323,6 +382,12 @@ impl EncodeCodec for H264Codec {
         }
     }
 
+    fn qp_range<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+    ) -> RangeInclusive<i32> {
+        codec_capabilities.min_qp..=codec_capabilities.max_qp
+    }
+
     fn resolve_max_references<'a>(
         quality_level_properties: &Self::CodecSpecificEncodeQualityLevelProperties<'a>,
         codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
@@ -339,12 +404,95 @@ impl EncodeCodec for H264Codec {
             max
         }
     }
+
+    fn resolve_consecutive_b_frames<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+        profile: Self::Profile,
+        max_references: NonZeroU32,
+        user_provided: Option<u32>,
+    ) -> Result<u32, VulkanEncoderError> {
+        let consecutive_b_frames = user_provided.unwrap_or(0);
+        if consecutive_b_frames == 0 {
+            return Ok(0);
+        }
+
+        if let H264Profile::Baseline = profile {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "consecutive_b_frames",
+                problem: "B-frames are not allowed in the Baseline profile.".to_string(),
+            });
+        }
+
+        if codec_capabilities.max_b_picture_l0_reference_count == 0
+            || codec_capabilities.max_l1_reference_count == 0
+        {
+            return Err(VulkanEncoderError::UnsupportedDeviceCapabilities(
+                "B-frame encoding",
+            ));
+        }
+
+        // B-frames reference the frames before and after them
+        if max_references.get() < 2 {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "max_references",
+                problem: format!(
+                    "Max references is {max_references}, B-frames require at least 2."
+                ),
+            });
+        }
+
+        Ok(consecutive_b_frames)
+    }
+
+    fn resolve_slice_count<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+        height: NonZeroU32,
+        user_provided: Option<NonZeroU32>,
+    ) -> Result<NonZeroU32, VulkanEncoderError> {
+        let Some(slice_count) = user_provided else {
+            return Ok(NonZeroU32::MIN);
+        };
+
+        if slice_count.get() > codec_capabilities.max_slice_count {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "slice_count",
+                problem: format!(
+                    "Slice count is {slice_count}, the device supports at most {}.",
+                    codec_capabilities.max_slice_count
+                ),
+            });
+        }
+
+        let macroblock_rows = height.get().div_ceil(16);
+        if !codec_capabilities
+            .flags
+            .contains(vk::VideoEncodeH264CapabilityFlagsKHR::ROW_UNALIGNED_SLICE)
+            && slice_count.get() > macroblock_rows
+        {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "slice_count",
+                problem: format!(
+                    "Slice count is {slice_count}, but the device requires slices to consist of whole macroblock rows and the frame has {macroblock_rows} rows."
+                ),
+            });
+        }
+
+        Ok(slice_count)
+    }
+
+    fn supports_different_slice_types<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+    ) -> bool {
+        codec_capabilities
+            .flags
+            .contains(vk::VideoEncodeH264CapabilityFlagsKHR::DIFFERENT_SLICE_TYPE)
+    }
 }
 
-fn primary_pic_type(is_idr: bool) -> vk::native::StdVideoH264PictureType {
-    if is_idr {
-        vk::native::StdVideoH264PictureType_STD_VIDEO_H264_PICTURE_TYPE_IDR
-    } else {
-        vk::native::StdVideoH264PictureType_STD_VIDEO_H264_PICTURE_TYPE_P
+fn primary_pic_type(picture_type: PictureType) -> vk::native::StdVideoH264PictureType {
+    match picture_type {
+        PictureType::Idr => vk::native::StdVideoH264PictureType_STD_VIDEO_H264_PICTURE_TYPE_IDR,
+        PictureType::P => vk::native::StdVideoH264PictureType_STD_VIDEO_H264_PICTURE_TYPE_P,
+        PictureType::B => vk::native::StdVideoH264PictureType_STD_VIDEO_H264_PICTURE_TYPE_B,
     }
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use ash::vk;
 use h264_reader::nal::sps::{FrameMbsFlags, SeqParameterSet};
 
@@ -17,6 +19,12 @@ pub(crate) trait SeqParameterSetExt {
 
     /// Frame size with frame-cropping taken into account
     fn size(&self) -> Result<vk::Extent2D, VulkanDecoderError>;
+
+    /// Position of the top-left corner of the cropped frame
+    fn crop_offset(&self) -> Result<vk::Offset2D, VulkanDecoderError>;
+
+    /// Multipliers of the frame-cropping offsets in luma samples
+    fn crop_units(&self) -> Result<(u32, u32), VulkanDecoderError>;
 }
 
 impl SeqParameterSetExt for SeqParameterSet {
@@ -30,7 +38,7 @@ impl SeqParameterSetExt for SeqParameterSet {
     }
 
     #[allow(non_snake_case)]
-    fn size(&self) -> Result<vk::Extent2D, VulkanDecoderError> {
+    fn crop_units(&self) -> Result<(u32, u32), VulkanDecoderError> {
         let chroma_array_type = if self.chroma_info.separate_colour_plane_flag {
             0
         } else {
@@ -63,6 +71,13 @@ impl SeqParameterSetExt for SeqParameterSet {
             ),
         };
 
+        Ok((CropUnitX, CropUnitY))
+    }
+
+    #[allow(non_snake_case)]
+    fn size(&self) -> Result<vk::Extent2D, VulkanDecoderError> {
+        let (CropUnitX, CropUnitY) = self.crop_units()?;
+
         let (width_offset, height_offset) = match &self.frame_cropping {
             None => (0, 0),
             Some(frame_cropping) => (
@@ -79,6 +94,18 @@ impl SeqParameterSetExt for SeqParameterSet {
 
         Ok(vk::Extent2D { width, height })
     }
+
+    fn crop_offset(&self) -> Result<vk::Offset2D, VulkanDecoderError> {
+        let Some(frame_cropping) = &self.frame_cropping else {
+            return Ok(vk::Offset2D { x: 0, y: 0 });
+        };
+        let (crop_unit_x, crop_unit_y) = self.crop_units()?;
+
+        Ok(vk::Offset2D {
+            x: (frame_cropping.left_offset * crop_unit_x) as i32,
+            y: (frame_cropping.top_offset * crop_unit_y) as i32,
+        })
+    }
 }
 
 pub(crate) struct VkH264SequenceParameterSet {
@@ -254,6 +281,7 @@ impl VkH264SequenceParameterSet {
         width: u32,
         height: u32,
         max_references: u32,
+        consecutive_b_frames: u32,
         color_space: ColorSpace,
         color_range: ColorRange,
         framerate: Rational,
@@ -305,7 +333,8 @@ impl VkH264SequenceParameterSet {
             matrix_coefficients: color_description.matrix_coefficients,
             num_units_in_tick: framerate.denominator.get(),
             time_scale,
-            max_num_reorder_frames: 0, // TODO: B frames
+            // B-frames are never used as references, so at most one frame is waiting for output
+            max_num_reorder_frames: (consecutive_b_frames > 0) as u8,
             max_dec_frame_buffering: max_references as u8,
             chroma_sample_loc_type_top_field: 0,
             chroma_sample_loc_type_bottom_field: 0,
@@ -692,7 +721,7 @@ impl VkH264PictureParameterSet {
 }
 
 pub(crate) struct H264DecodeProfileInfo<'a> {
-    pub(crate) profile_info: ProfileInfo<'a>,
+    pub(crate) profile_info: Arc<ProfileInfo<'a>>,
     pub(crate) profile_idc: vk::native::StdVideoH264ProfileIdc,
     pub(crate) picture_layout: vk::VideoDecodeH264PictureLayoutFlagsKHR,
 }
@@ -717,32 +746,25 @@ impl<'a> H264DecodeProfileInfo<'a> {
         sps: &SeqParameterSet,
         decode_usage_info: vk::VideoDecodeUsageInfoKHR<'a>,
     ) -> Result<Self, VulkanDecoderError> {
-        let profile_idc = h264_profile_idc_to_vk(sps.profile());
-
-        if profile_idc == vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_INVALID {
-            return Err(VulkanDecoderError::InvalidInputData(
-                "unsupported h264 profile".into(),
-            ));
-        }
-
-        let picture_layout = vk::VideoDecodeH264PictureLayoutFlagsKHR::PROGRESSIVE;
-
-        let h264_profile_info = vk::VideoDecodeH264ProfileInfoKHR::default()
-            .std_profile_idc(profile_idc)
-            .picture_layout(picture_layout);
-
-        let h264_profile_info: Box<dyn vk::ExtendsVideoProfileInfoKHR + Send + Sync> =
-            Box::new(h264_profile_info);
-        let decode_usage_info: Box<dyn vk::ExtendsVideoProfileInfoKHR + Send + Sync> =
-            Box::new(decode_usage_info);
-
         let chroma_subsampling = match sps.chroma_info.chroma_format {
             h264_reader::nal::sps::ChromaFormat::YUV420 => {
                 vk::VideoChromaSubsamplingFlagsKHR::TYPE_420
             }
+            h264_reader::nal::sps::ChromaFormat::YUV422 => {
+                vk::VideoChromaSubsamplingFlagsKHR::TYPE_422
+            }
+            // the colour planes coded separately are decoded as monochrome pictures
+            h264_reader::nal::sps::ChromaFormat::YUV444
+                if sps.chroma_info.separate_colour_plane_flag =>
+            {
+                return Err(VulkanDecoderError::InvalidInputData(
+                    "separately coded colour planes are not supported".into(),
+                ));
+            }
+            h264_reader::nal::sps::ChromaFormat::YUV444 => {
+                vk::VideoChromaSubsamplingFlagsKHR::TYPE_444
+            }
             h264_reader::nal::sps::ChromaFormat::Monochrome
-            | h264_reader::nal::sps::ChromaFormat::YUV422
-            | h264_reader::nal::sps::ChromaFormat::YUV444
             | h264_reader::nal::sps::ChromaFormat::Invalid(_) => {
                 return Err(VulkanDecoderError::InvalidInputData(format!(
                     "unsupported chroma format: {:?}",
@@ -751,37 +773,91 @@ impl<'a> H264DecodeProfileInfo<'a> {
             }
         };
 
-        let luma_bit_depth = if sps.chroma_info.bit_depth_luma_minus8 + 8 == 8 {
-            vk::VideoComponentBitDepthFlagsKHR::TYPE_8
-        } else {
+        let luma_bit_depth = sps.chroma_info.bit_depth_luma_minus8 + 8;
+        let chroma_bit_depth = sps.chroma_info.bit_depth_chroma_minus8 + 8;
+        if luma_bit_depth != chroma_bit_depth {
             return Err(VulkanDecoderError::InvalidInputData(format!(
-                "unsupported luma bit length: {}",
-                sps.chroma_info.bit_depth_luma_minus8 + 8
+                "unsupported bit depths: luma {luma_bit_depth}, chroma {chroma_bit_depth}"
             )));
+        }
+
+        let bit_depth = match luma_bit_depth {
+            8 => vk::VideoComponentBitDepthFlagsKHR::TYPE_8,
+            10 => vk::VideoComponentBitDepthFlagsKHR::TYPE_10,
+            _ => {
+                return Err(VulkanDecoderError::InvalidInputData(format!(
+                    "unsupported bit depth: {luma_bit_depth}"
+                )));
+            }
         };
 
-        let chroma_bit_depth = if sps.chroma_info.bit_depth_chroma_minus8 + 8 == 8 {
-            vk::VideoComponentBitDepthFlagsKHR::TYPE_8
-        } else {
-            return Err(VulkanDecoderError::InvalidInputData(format!(
-                "unsupported chroma bit length: {}",
-                sps.chroma_info.bit_depth_chroma_minus8 + 8
-            )));
+        let profile_idc = match (sps.profile(), luma_bit_depth) {
+            // 4:2:2 and 4:4:4 streams, like the ones of the High 4:2:2 profile, can only be
+            // decoded with High 4:4:4 Predictive
+            _ if chroma_subsampling != vk::VideoChromaSubsamplingFlagsKHR::TYPE_420 => {
+                vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_HIGH_444_PREDICTIVE
+            }
+            // 8-bit High 10 streams only use the High profile tools. Vulkan has no profile idc
+            // for High 10, so other streams of this profile are decoded with High 4:4:4
+            // Predictive, which is a superset of it.
+            (h264_reader::nal::sps::Profile::High10, 8) => {
+                vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_HIGH
+            }
+            (h264_reader::nal::sps::Profile::High10, _) => {
+                vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_HIGH_444_PREDICTIVE
+            }
+            (profile, _) => h264_profile_idc_to_vk(profile),
         };
 
+        if profile_idc == vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_INVALID {
+            return Err(VulkanDecoderError::InvalidInputData(
+                "unsupported h264 profile".into(),
+            ));
+        }
+
+        // fields are decoded into alternating lines of the frame they belong to
+        let picture_layout = match sps.frame_mbs_flags {
+            FrameMbsFlags::Frames => vk::VideoDecodeH264PictureLayoutFlagsKHR::PROGRESSIVE,
+            FrameMbsFlags::Fields { .. } => {
+                vk::VideoDecodeH264PictureLayoutFlagsKHR::INTERLACED_INTERLEAVED_LINES
+            }
+        };
+
+        let h264_profile_info = vk::VideoDecodeH264ProfileInfoKHR::default()
+            .std_profile_idc(profile_idc)
+            .picture_layout(picture_layout);
+
+        let h264_profile_info: Box<dyn vk::ExtendsVideoProfileInfoKHR + Send + Sync> =
+            Box::new(h264_profile_info);
+        let decode_usage_info: Box<dyn vk::ExtendsVideoProfileInfoKHR + Send + Sync> =
+            Box::new(decode_usage_info);
+
         let profile_info = vk::VideoProfileInfoKHR::default()
             .video_codec_operation(vk::VideoCodecOperationFlagsKHR::DECODE_H264)
             .chroma_subsampling(chroma_subsampling)
-            .luma_bit_depth(luma_bit_depth)
-            .chroma_bit_depth(chroma_bit_depth);
+            .luma_bit_depth(bit_depth)
+            .chroma_bit_depth(bit_depth);
 
         Ok(Self {
-            profile_info: ProfileInfo::new(
+            profile_info: Arc::new(ProfileInfo::new(
                 profile_info,
                 vec![h264_profile_info, decode_usage_info],
-            ),
+            )),
             profile_idc,
             picture_layout,
         })
     }
+
+    pub(crate) fn bit_depth(&self) -> vk::VideoComponentBitDepthFlagsKHR {
+        self.profile_info.profile_info.luma_bit_depth
+    }
+
+    pub(crate) fn chroma_subsampling(&self) -> vk::VideoChromaSubsamplingFlagsKHR {
+        self.profile_info.profile_info.chroma_subsampling
+    }
+
+    pub(crate) fn is_interlaced(&self) -> bool {
+        self.picture_layout
+            == vk::VideoDecodeH264PictureLayoutFlagsKHR::INTERLACED_INTERLEAVED_LINES
+    }
 }
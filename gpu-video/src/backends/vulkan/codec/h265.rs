@@ -5,7 +5,7 @@ use ash::vk;
 use crate::{
     backends::vulkan::codec::{
         Codec, CodecCapabilities, CodecSpecificDecodeCapabilities, CodecSpecificEncodeCapabilities,
-        CodecSpecificEncoderQualityLevelProperties,
+        CodecSpecificEncoderQualityLevelProperties, EncodeCodecCapabilities,
         h265::parameters::{
             VkH265PictureParameterSet, VkH265SequenceParameterSet, VkH265VideoParameterSet,
         },
@@ -35,10 +35,6 @@ pub(crate) struct H265VkParameters {
 pub(crate) struct H265Codec;
 
 impl Codec for H265Codec {
-    type Profile = H265Profile;
-
-    type OwnedParameters = H265CodecParameters;
-
     type VkParameters<'a> = H265VkParameters;
 
     type VideoDecodeSessionParametersAddInfo<'a> =
@@ -46,11 +42,6 @@ impl Codec for H265Codec {
     type VideoDecodeSessionParametersCreateInfo<'a> =
         vk::VideoDecodeH265SessionParametersCreateInfoKHR<'a>;
 
-    type VideoEncodeSessionParametersAddInfo<'a> =
-        vk::VideoEncodeH265SessionParametersAddInfoKHR<'a>;
-    type VideoEncodeSessionParametersCreateInfo<'a> =
-        vk::VideoEncodeH265SessionParametersCreateInfoKHR<'a>;
-
     fn decode_parameters_add_info<'a: 'b, 'b>(
         parameters: &'b Self::VkParameters<'a>,
     ) -> Self::VideoDecodeSessionParametersAddInfo<'b> {
@@ -69,33 +60,10 @@ impl Codec for H265Codec {
             .max_std_pps_count(32)
             .parameters_add_info(add_info)
     }
-
-    fn encode_parameters_add_info<'a: 'b, 'b>(
-        parameters: &'b Self::VkParameters<'a>,
-    ) -> Self::VideoEncodeSessionParametersAddInfo<'b> {
-        vk::VideoEncodeH265SessionParametersAddInfoKHR::default()
-            .std_vp_ss(&parameters.vps)
-            .std_sp_ss(&parameters.sps)
-            .std_pp_ss(&parameters.pps)
-    }
-
-    fn encode_parameters_create_info<'a: 'b, 'b>(
-        add_info: &'b Self::VideoEncodeSessionParametersAddInfo<'a>,
-    ) -> Self::VideoEncodeSessionParametersCreateInfo<'b> {
-        vk::VideoEncodeH265SessionParametersCreateInfoKHR::default()
-            .max_std_vps_count(32)
-            .max_std_sps_count(32)
-            .max_std_pps_count(32)
-            .parameters_add_info(add_info)
-    }
 }
 
 impl CodecCapabilities for H265Codec {
     type CodecSpecificDecodeCapabilities<'a> = vk::VideoDecodeH265CapabilitiesKHR<'a>;
-    type CodecSpecificEncodeCapabilities<'a> = vk::VideoEncodeH265CapabilitiesKHR<'a>;
-    type CodecSpecificEncodeQualityLevelProperties<'a> =
-        vk::VideoEncodeH265QualityLevelPropertiesKHR<'a>;
-    type NativeEncodeCodecCapabilities = NativeEncodeH265Capabilities;
 
     fn static_decode_capabilities<'a>(
         codec_caps: &Self::CodecSpecificDecodeCapabilities<'a>,
@@ -106,6 +74,13 @@ impl CodecCapabilities for H265Codec {
             ..*codec_caps
         }
     }
+}
+
+impl EncodeCodecCapabilities for H265Codec {
+    type CodecSpecificEncodeCapabilities<'a> = vk::VideoEncodeH265CapabilitiesKHR<'a>;
+    type CodecSpecificEncodeQualityLevelProperties<'a> =
+        vk::VideoEncodeH265QualityLevelPropertiesKHR<'a>;
+    type NativeEncodeCodecCapabilities = NativeEncodeH265Capabilities;
 
     fn static_encode_capabilities<'a>(
         codec_caps: &Self::CodecSpecificEncodeCapabilities<'a>,
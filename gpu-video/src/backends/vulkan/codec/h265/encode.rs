@@ -1,16 +1,17 @@
-use std::num::NonZeroU32;
+use std::{num::NonZeroU32, ops::RangeInclusive};
 
 use ash::vk;
 
 use crate::{
-    backends::vulkan::vulkan_encoder::VulkanEncoderError,
+    backends::vulkan::vulkan_encoder::{PictureType, VulkanEncoderError},
     backends::vulkan::{
         codec::{
             EncodeCodec,
             h265::{
-                H265Codec, H265VkParameters,
+                H265Codec, H265CodecParameters, H265VkParameters,
                 parameters::{
                     VkH265PictureParameterSet, VkH265SequenceParameterSet, VkH265VideoParameterSet,
+                    largest_supported_ctb_log2_size,
                 },
             },
         },
@@ -19,7 +20,7 @@ use crate::{
         },
         wrappers::ProfileInfo,
     },
-    parameters::RateControl,
+    parameters::{H265Profile, RateControl},
 };
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -35,6 +36,34 @@ pub(crate) struct H265WriteParametersInfo {
 }
 
 impl EncodeCodec for H265Codec {
+    type Profile = H265Profile;
+
+    type OwnedParameters = H265CodecParameters;
+
+    type VideoEncodeSessionParametersAddInfo<'a> =
+        vk::VideoEncodeH265SessionParametersAddInfoKHR<'a>;
+    type VideoEncodeSessionParametersCreateInfo<'a> =
+        vk::VideoEncodeH265SessionParametersCreateInfoKHR<'a>;
+
+    fn encode_parameters_add_info<'a: 'b, 'b>(
+        parameters: &'b Self::VkParameters<'a>,
+    ) -> Self::VideoEncodeSessionParametersAddInfo<'b> {
+        vk::VideoEncodeH265SessionParametersAddInfoKHR::default()
+            .std_vp_ss(&parameters.vps)
+            .std_sp_ss(&parameters.sps)
+            .std_pp_ss(&parameters.pps)
+    }
+
+    fn encode_parameters_create_info<'a: 'b, 'b>(
+        add_info: &'b Self::VideoEncodeSessionParametersAddInfo<'a>,
+    ) -> Self::VideoEncodeSessionParametersCreateInfo<'b> {
+        vk::VideoEncodeH265SessionParametersCreateInfoKHR::default()
+            .max_std_vps_count(32)
+            .max_std_sps_count(32)
+            .max_std_pps_count(32)
+            .parameters_add_info(add_info)
+    }
+
     fn profile_info<'a>(
         params: &crate::backends::vulkan::vulkan_encoder::FullEncoderParameters<Self>,
     ) -> ProfileInfo<'a> {
@@ -86,8 +115,9 @@ impl EncodeCodec for H265Codec {
     type BitstreamUnitData = vk::native::StdVideoEncodeH265SliceSegmentHeader;
     fn bitstream_unit_data(
         codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'_>,
-        is_idr: bool,
+        picture_type: PictureType,
     ) -> Self::BitstreamUnitData {
+        let is_idr = picture_type == PictureType::Idr;
         let slice_sao = codec_capabilities
             .std_syntax_flags
             .contains(vk::VideoEncodeH265StdFlagsKHR::SAMPLE_ADAPTIVE_OFFSET_ENABLED_FLAG_SET)
@@ -112,11 +142,13 @@ impl EncodeCodec for H265Codec {
                     0, // reserved
                 ),
             },
-            slice_type: if is_idr {
-                vk::native::StdVideoH265SliceType_STD_VIDEO_H265_SLICE_TYPE_I
-            } else {
-                vk::native::StdVideoH265SliceType_STD_VIDEO_H265_SLICE_TYPE_P
+            slice_type: match picture_type {
+                PictureType::Idr => vk::native::StdVideoH265SliceType_STD_VIDEO_H265_SLICE_TYPE_I,
+                PictureType::P => vk::native::StdVideoH265SliceType_STD_VIDEO_H265_SLICE_TYPE_P,
+                PictureType::B => vk::native::StdVideoH265SliceType_STD_VIDEO_H265_SLICE_TYPE_B,
             },
+            // This and first_slice_segment_in_pic_flag are determined by the implementation when
+            // the frame is split into multiple segments
             slice_segment_address: 0,
             collocated_ref_idx: 0, // collocate with previous ref frame (I hope that's what it means)
             MaxNumMergeCand: 5,    // anything different and amd breaks
@@ -138,23 +170,25 @@ impl EncodeCodec for H265Codec {
         data: &'a Self::BitstreamUnitData,
         rate_control: RateControl,
         capabilities: &NativeEncodeQualityLevelProperties<Self>,
-        is_idr: bool,
+        picture_type: PictureType,
     ) -> Self::BitstreamUnitInfo<'a> {
         let mut slice_info =
             vk::VideoEncodeH265NaluSliceSegmentInfoKHR::default().std_slice_segment_header(data);
 
-        if let RateControl::Disabled = rate_control {
-            if !capabilities.zeroed() {
+        match rate_control {
+            RateControl::Disabled if !capabilities.zeroed() => {
                 let qp = capabilities
                     .codec_quality_level_properties
                     .preferred_constant_qp;
 
-                if is_idr {
-                    slice_info.constant_qp = qp.qp_i;
-                } else {
-                    slice_info.constant_qp = qp.qp_p;
-                }
+                slice_info.constant_qp = match picture_type {
+                    PictureType::Idr => qp.qp_i,
+                    PictureType::P => qp.qp_p,
+                    PictureType::B => qp.qp_b,
+                };
             }
+            RateControl::ConstantQp { qp } => slice_info.constant_qp = qp as i32,
+            _ => {}
         }
 
         slice_info
@@ -165,11 +199,44 @@ impl EncodeCodec for H265Codec {
     fn reference_list_info(
         counters: &Self::EncodingCounters,
         active_reference_slots: &std::collections::VecDeque<(usize, Self::ReferenceInfo)>,
+        picture_type: PictureType,
     ) -> Self::ReferenceListInfo {
+        let current_poc = counters.pic_order_cnt as i32;
+
+        // All references have to be in the reference picture set, otherwise the decoder drops
+        // them. References are kept in display order, B-frames are encoded after the reference
+        // that follows them, so only they have references with a higher POC.
+        let negative_references = active_reference_slots
+            .iter()
+            .rev()
+            .filter(|(_, info)| info.PicOrderCntVal < current_poc)
+            .collect::<Vec<_>>();
+        let positive_references = active_reference_slots
+            .iter()
+            .filter(|(_, info)| info.PicOrderCntVal > current_poc)
+            .collect::<Vec<_>>();
+
         let mut ref_list0 = [0xff; 15];
-        for (i, (slot, _)) in active_reference_slots.iter().rev().enumerate() {
-            ref_list0[i] = *slot as u8;
-        }
+        let mut ref_list1 = [0xff; 15];
+        let (num_ref_idx_l0_active, num_ref_idx_l1_active) = match picture_type {
+            PictureType::B => {
+                if let Some((slot, _)) = negative_references.first() {
+                    ref_list0[0] = *slot as u8;
+                }
+                if let Some((slot, _)) = positive_references.first() {
+                    ref_list1[0] = *slot as u8;
+                }
+
+                (1, 1)
+            }
+            PictureType::Idr | PictureType::P => {
+                for (i, (slot, _)) in negative_references.iter().enumerate() {
+                    ref_list0[i] = *slot as u8;
+                }
+
+                (negative_references.len(), 0)
+            }
+        };
 
         let list_info = vk::native::StdVideoEncodeH265ReferenceListsInfo {
             flags: vk::native::StdVideoEncodeH265ReferenceListsInfoFlags {
@@ -178,19 +245,19 @@ impl EncodeCodec for H265Codec {
                     0, 0, 0,
                 ),
             },
-            num_ref_idx_l0_active_minus1: active_reference_slots.len().saturating_sub(1) as u8,
-            num_ref_idx_l1_active_minus1: 0,
+            num_ref_idx_l0_active_minus1: num_ref_idx_l0_active.saturating_sub(1) as u8,
+            num_ref_idx_l1_active_minus1: num_ref_idx_l1_active.saturating_sub(1) as u8,
             RefPicList0: ref_list0,
-            RefPicList1: [0xff; 15],
+            RefPicList1: ref_list1,
             list_entry_l0: [0; 15],
             list_entry_l1: [0; 15],
         };
 
         let mut delta_poc_s0_minus1 = [0; 16];
-        let mut previous_poc = counters.pic_order_cnt as i32;
+        let mut previous_poc = current_poc;
         let mut used_by_curr_pic_s0_flag = 0;
 
-        for (i, reference) in active_reference_slots.iter().rev().enumerate() {
+        for (i, reference) in negative_references.iter().enumerate() {
             assert!(reference.1.PicOrderCntVal < previous_poc);
 
             delta_poc_s0_minus1[i] = (previous_poc - reference.1.PicOrderCntVal - 1) as u16;
@@ -198,6 +265,18 @@ impl EncodeCodec for H265Codec {
             previous_poc = reference.1.PicOrderCntVal;
         }
 
+        let mut delta_poc_s1_minus1 = [0; 16];
+        let mut previous_poc = current_poc;
+        let mut used_by_curr_pic_s1_flag = 0;
+
+        for (i, reference) in positive_references.iter().enumerate() {
+            assert!(reference.1.PicOrderCntVal > previous_poc);
+
+            delta_poc_s1_minus1[i] = (reference.1.PicOrderCntVal - previous_poc - 1) as u16;
+            used_by_curr_pic_s1_flag |= 1 << i;
+            previous_poc = reference.1.PicOrderCntVal;
+        }
+
         let short_term_ref_pic_set = vk::native::StdVideoH265ShortTermRefPicSet {
             flags: vk::native::StdVideoH265ShortTermRefPicSetFlags {
                 _bitfield_align_1: [],
@@ -210,13 +289,13 @@ impl EncodeCodec for H265Codec {
             abs_delta_rps_minus1: 0,
             used_by_curr_pic_flag: 0,
 
-            num_negative_pics: active_reference_slots.len() as u8,
+            num_negative_pics: negative_references.len() as u8,
             used_by_curr_pic_s0_flag,
             delta_poc_s0_minus1,
 
-            num_positive_pics: 0,
-            used_by_curr_pic_s1_flag: 0,
-            delta_poc_s1_minus1: [0; 16],
+            num_positive_pics: positive_references.len() as u8,
+            used_by_curr_pic_s1_flag,
+            delta_poc_s1_minus1,
 
             reserved1: 0,
             reserved2: 0,
@@ -230,7 +309,7 @@ impl EncodeCodec for H265Codec {
     }
     fn new_slot_reference_info(
         counters: &Self::EncodingCounters,
-        is_idr: bool,
+        picture_type: PictureType,
     ) -> Self::ReferenceInfo {
         vk::native::StdVideoEncodeH265ReferenceInfo {
             flags: vk::native::StdVideoEncodeH265ReferenceInfoFlags {
@@ -239,7 +318,7 @@ impl EncodeCodec for H265Codec {
                     0, 0, 0,
                 ),
             },
-            pic_type: pic_type(is_idr),
+            pic_type: pic_type(picture_type),
             PicOrderCntVal: counters.pic_order_cnt as i32,
             TemporalId: 0,
         }
@@ -249,9 +328,10 @@ impl EncodeCodec for H265Codec {
     fn picture_info_data(
         counters: &Self::EncodingCounters,
         codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'_>,
-        is_idr: bool,
+        picture_type: PictureType,
         ref_lists: &Self::ReferenceListInfo,
     ) -> Self::PictureInfoData {
+        let is_idr = picture_type == PictureType::Idr;
         // Must be 0 when sps_temporal_mvp_enabled_flag is 0 (H.265 7.4.7.1).
         // For IDR slices there are no reference pictures to derive MV candidates from.
         let slice_temporal_mvp_enabled_flag = (codec_capabilities
@@ -263,19 +343,19 @@ impl EncodeCodec for H265Codec {
             flags: vk::native::StdVideoEncodeH265PictureInfoFlags {
                 _bitfield_align_1: [],
                 _bitfield_1: vk::native::StdVideoEncodeH265PictureInfoFlags::new_bitfield_1(
-                    1,             // is_reference
-                    is_idr as u32, // IrapPicFlag
-                    0,             // used_for_long_term_reference
-                    0,             // discardable_flag
-                    0,             // cross_layer_bla_flag
-                    1,             // pic_output_flag
-                    0,             // no_output_of_prior_pics_flag
-                    0,             // short_term_ref_pic_set_sps_flag
+                    (picture_type != PictureType::B) as u32, // is_reference
+                    is_idr as u32,                           // IrapPicFlag
+                    0,                                       // used_for_long_term_reference
+                    0,                                       // discardable_flag
+                    0,                                       // cross_layer_bla_flag
+                    1,                                       // pic_output_flag
+                    0,                                       // no_output_of_prior_pics_flag
+                    0,                                       // short_term_ref_pic_set_sps_flag
                     slice_temporal_mvp_enabled_flag,
                     0, // reserved
                 ),
             },
-            pic_type: pic_type(is_idr),
+            pic_type: pic_type(picture_type),
             sps_video_parameter_set_id: 0,
             pps_seq_parameter_set_id: 0,
             pps_pic_parameter_set_id: 0,
@@ -305,12 +385,13 @@ impl EncodeCodec for H265Codec {
     }
 
     type EncodingCounters = H265EncodingCounters;
-    fn advance_counters(counters: &mut Self::EncodingCounters, _is_idr: bool) {
-        counters.pic_order_cnt = counters.pic_order_cnt.wrapping_add(1);
-    }
+    fn advance_counters(_counters: &mut Self::EncodingCounters, _picture_type: PictureType) {}
     fn counters_idr(counters: &mut Self::EncodingCounters) {
         counters.pic_order_cnt = 0;
     }
+    fn set_display_order(counters: &mut Self::EncodingCounters, display_order: u32) {
+        counters.pic_order_cnt = display_order;
+    }
 
     type CodecRateControlLayerInfo<'a> = vk::VideoEncodeH265RateControlLayerInfoKHR<'a>;
     type CodecRateControlInfo<'a> = vk::VideoEncodeH265RateControlInfoKHR<'a>;
@@ -327,6 +408,7 @@ impl EncodeCodec for H265Codec {
             RateControl::VariableBitrate { .. } => {}
             RateControl::ConstantBitrate { .. } => {}
             RateControl::Disabled => {}
+            RateControl::ConstantQp { .. } => {}
         }
 
         Some(vec![layer_info])
@@ -335,6 +417,7 @@ impl EncodeCodec for H265Codec {
     fn codec_rate_control_info<'a>(
         layers: Option<&'a [ash::vk::VideoEncodeRateControlLayerInfoKHR<'a>]>,
         idr_period: u32,
+        consecutive_b_frames: u32,
     ) -> Option<Self::CodecRateControlInfo<'a>> {
         let layers = layers?;
 
@@ -343,7 +426,7 @@ impl EncodeCodec for H265Codec {
                 .sub_layer_count(layers.len() as u32)
                 .idr_period(idr_period)
                 .gop_frame_count(idr_period)
-                .consecutive_b_frame_count(0)
+                .consecutive_b_frame_count(consecutive_b_frames)
                 .flags(
                     vk::VideoEncodeH265RateControlFlagsKHR::REGULAR_GOP
                         | vk::VideoEncodeH265RateControlFlagsKHR::REFERENCE_PATTERN_FLAT,
@@ -388,6 +471,12 @@ impl EncodeCodec for H265Codec {
         }
     }
 
+    fn qp_range<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+    ) -> RangeInclusive<i32> {
+        codec_capabilities.min_qp..=codec_capabilities.max_qp
+    }
+
     fn resolve_max_references<'a>(
         quality_level_properties: &Self::CodecSpecificEncodeQualityLevelProperties<'a>,
         codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
@@ -404,13 +493,90 @@ impl EncodeCodec for H265Codec {
             max
         }
     }
+
+    fn resolve_consecutive_b_frames<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+        _profile: Self::Profile,
+        max_references: NonZeroU32,
+        user_provided: Option<u32>,
+    ) -> Result<u32, VulkanEncoderError> {
+        let consecutive_b_frames = user_provided.unwrap_or(0);
+        if consecutive_b_frames == 0 {
+            return Ok(0);
+        }
+
+        if codec_capabilities.max_b_picture_l0_reference_count == 0
+            || codec_capabilities.max_l1_reference_count == 0
+        {
+            return Err(VulkanEncoderError::UnsupportedDeviceCapabilities(
+                "B-frame encoding",
+            ));
+        }
+
+        // B-frames reference the frames before and after them
+        if max_references.get() < 2 {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "max_references",
+                problem: format!(
+                    "Max references is {max_references}, B-frames require at least 2."
+                ),
+            });
+        }
+
+        Ok(consecutive_b_frames)
+    }
+
+    fn resolve_slice_count<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+        height: NonZeroU32,
+        user_provided: Option<NonZeroU32>,
+    ) -> Result<NonZeroU32, VulkanEncoderError> {
+        let Some(slice_count) = user_provided else {
+            return Ok(NonZeroU32::MIN);
+        };
+
+        if slice_count.get() > codec_capabilities.max_slice_segment_count {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "slice_count",
+                problem: format!(
+                    "Slice count is {slice_count}, the device supports at most {}.",
+                    codec_capabilities.max_slice_segment_count
+                ),
+            });
+        }
+
+        let ctb_size = 1 << largest_supported_ctb_log2_size(codec_capabilities.ctb_sizes);
+        let ctb_rows = height.get().div_ceil(ctb_size);
+        if !codec_capabilities
+            .flags
+            .contains(vk::VideoEncodeH265CapabilityFlagsKHR::ROW_UNALIGNED_SLICE_SEGMENT)
+            && slice_count.get() > ctb_rows
+        {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "slice_count",
+                problem: format!(
+                    "Slice count is {slice_count}, but the device requires slices to consist of whole CTB rows and the frame has {ctb_rows} rows."
+                ),
+            });
+        }
+
+        Ok(slice_count)
+    }
+
+    fn supports_different_slice_types<'a>(
+        codec_capabilities: &Self::CodecSpecificEncodeCapabilities<'a>,
+    ) -> bool {
+        codec_capabilities
+            .flags
+            .contains(vk::VideoEncodeH265CapabilityFlagsKHR::DIFFERENT_SLICE_SEGMENT_TYPE)
+    }
 }
 
-fn pic_type(is_idr: bool) -> u32 {
-    if is_idr {
-        vk::native::StdVideoH265PictureType_STD_VIDEO_H265_PICTURE_TYPE_IDR
-    } else {
-        vk::native::StdVideoH265PictureType_STD_VIDEO_H265_PICTURE_TYPE_P
+fn pic_type(picture_type: PictureType) -> u32 {
+    match picture_type {
+        PictureType::Idr => vk::native::StdVideoH265PictureType_STD_VIDEO_H265_PICTURE_TYPE_IDR,
+        PictureType::P => vk::native::StdVideoH265PictureType_STD_VIDEO_H265_PICTURE_TYPE_P,
+        PictureType::B => vk::native::StdVideoH265PictureType_STD_VIDEO_H265_PICTURE_TYPE_B,
     }
 }
 
@@ -1,9 +1,17 @@
+use std::sync::Arc;
+
 use ash::vk;
 
 use crate::{
     backends::vulkan::vulkan_encoder::FullEncoderParameters,
-    backends::vulkan::{codec::h265::H265Codec, vulkan_decoder::VulkanDecoderError},
+    backends::vulkan::{
+        codec::h265::H265Codec, vulkan_decoder::VulkanDecoderError, wrappers::ProfileInfo,
+    },
     device::{CodecColorDescription, ColorRange},
+    parser::h265::{
+        PicParameterSet, ProfileTierLevel, ScalingListData, SeqParameterSet, ShortTermRefPicSet,
+        SpsRangeExtension, SubLayerOrderingInfo, VideoParameterSet, VuiParameters,
+    },
 };
 
 pub(crate) struct VkH265VideoParameterSet {
@@ -38,7 +46,8 @@ fn dec_pic_buf_mgr(
     };
     dec_pic_buf_mgr.max_dec_pic_buffering_minus1[0] = params.max_references.get() as u8;
     dec_pic_buf_mgr.max_latency_increase_plus1[0] = 1;
-    dec_pic_buf_mgr.max_num_reorder_pics[0] = 0;
+    // B-frames are never used as references, so at most one frame is waiting for output
+    dec_pic_buf_mgr.max_num_reorder_pics[0] = (params.consecutive_b_frames > 0) as u8;
 
     dec_pic_buf_mgr
 }
@@ -141,6 +150,9 @@ pub(crate) struct VkH265SequenceParameterSet {
     _profile_tier_level: Option<Box<vk::native::StdVideoH265ProfileTierLevel>>,
     _dec_pic_buf_mgr: Option<Box<vk::native::StdVideoH265DecPicBufMgr>>,
     _vui: Option<Box<vk::native::StdVideoH265SequenceParameterSetVui>>,
+    _scaling_lists: Option<Box<vk::native::StdVideoH265ScalingLists>>,
+    _short_term_ref_pic_sets: Option<Box<[vk::native::StdVideoH265ShortTermRefPicSet]>>,
+    _long_term_ref_pics: Option<Box<vk::native::StdVideoH265LongTermRefPicsSps>>,
 }
 
 impl VkH265SequenceParameterSet {
@@ -255,11 +267,16 @@ impl VkH265SequenceParameterSet {
             _profile_tier_level: Some(profile_tier_level),
             _dec_pic_buf_mgr: Some(dec_pic_buf_mgr),
             _vui: Some(vui),
+            _scaling_lists: None,
+            _short_term_ref_pic_sets: None,
+            _long_term_ref_pics: None,
         }
     }
 }
 
-fn largest_supported_ctb_log2_size(ctb_sizes: vk::VideoEncodeH265CtbSizeFlagsKHR) -> u32 {
+pub(crate) fn largest_supported_ctb_log2_size(
+    ctb_sizes: vk::VideoEncodeH265CtbSizeFlagsKHR,
+) -> u32 {
     if ctb_sizes.contains(vk::VideoEncodeH265CtbSizeFlagsKHR::TYPE_64) {
         6
     } else if ctb_sizes.contains(vk::VideoEncodeH265CtbSizeFlagsKHR::TYPE_32) {
@@ -271,6 +288,7 @@ fn largest_supported_ctb_log2_size(ctb_sizes: vk::VideoEncodeH265CtbSizeFlagsKHR
 
 pub(crate) struct VkH265PictureParameterSet {
     pub(crate) pps: vk::native::StdVideoH265PictureParameterSet,
+    _scaling_lists: Option<Box<vk::native::StdVideoH265ScalingLists>>,
 }
 
 impl VkH265PictureParameterSet {
@@ -358,6 +376,7 @@ impl VkH265PictureParameterSet {
                 pScalingLists: std::ptr::null(),
                 pPredictorPaletteEntries: std::ptr::null(),
             },
+            _scaling_lists: None,
         }
     }
 }
@@ -384,3 +403,583 @@ pub(crate) fn vk_to_h265_level_idc(
         ))),
     }
 }
+
+unsafe impl Send for VkH265VideoParameterSet {}
+unsafe impl Sync for VkH265VideoParameterSet {}
+
+impl From<&'_ VideoParameterSet> for VkH265VideoParameterSet {
+    fn from(vps: &VideoParameterSet) -> Self {
+        let profile_tier_level = Box::new(decode_profile_tier_level(&vps.profile_tier_level));
+        let dec_pic_buf_mgr = Box::new(decode_dec_pic_buf_mgr(&vps.sub_layer_ordering_info));
+
+        Self {
+            vps: vk::native::StdVideoH265VideoParameterSet {
+                flags: vk::native::StdVideoH265VpsFlags {
+                    _bitfield_align_1: [],
+                    _bitfield_1: vk::native::StdVideoH265VpsFlags::new_bitfield_1(
+                        vps.vps_temporal_id_nesting_flag.into(),
+                        vps.vps_sub_layer_ordering_info_present_flag.into(),
+                        vps.vps_timing_info_present_flag.into(),
+                        vps.vps_poc_proportional_to_timing_flag.into(),
+                    ),
+                    __bindgen_padding_0: [0; 3],
+                },
+                vps_video_parameter_set_id: vps.vps_video_parameter_set_id,
+                vps_max_sub_layers_minus1: vps.vps_max_sub_layers_minus1,
+                reserved1: 0,
+                reserved2: 0,
+                vps_num_units_in_tick: vps.vps_num_units_in_tick,
+                vps_time_scale: vps.vps_time_scale,
+                vps_num_ticks_poc_diff_one_minus1: vps.vps_num_ticks_poc_diff_one_minus1,
+                reserved3: 0,
+                // HRD parameters are not parsed, they are not needed for decoding
+                pHrdParameters: std::ptr::null(),
+                pDecPicBufMgr: dec_pic_buf_mgr.as_ref(),
+                pProfileTierLevel: profile_tier_level.as_ref(),
+            },
+            _profile_tier_level: profile_tier_level,
+            _dec_pic_buf_mgr: dec_pic_buf_mgr,
+        }
+    }
+}
+
+unsafe impl Send for VkH265SequenceParameterSet {}
+unsafe impl Sync for VkH265SequenceParameterSet {}
+
+impl From<&'_ SeqParameterSet> for VkH265SequenceParameterSet {
+    fn from(sps: &SeqParameterSet) -> Self {
+        let profile_tier_level = Box::new(decode_profile_tier_level(&sps.profile_tier_level));
+        let dec_pic_buf_mgr = Box::new(decode_dec_pic_buf_mgr(&sps.sub_layer_ordering_info));
+        let vui = sps
+            .vui_parameters
+            .as_ref()
+            .map(|vui| Box::new(decode_vui(vui)));
+        let scaling_lists = sps
+            .scaling_list_data
+            .as_ref()
+            .map(|data| Box::new(decode_scaling_lists(data)));
+        let short_term_ref_pic_sets = (!sps.short_term_ref_pic_sets.is_empty()).then(|| {
+            sps.short_term_ref_pic_sets
+                .iter()
+                .map(decode_short_term_ref_pic_set)
+                .collect::<Box<[_]>>()
+        });
+        let long_term_ref_pics = sps.long_term_ref_pics_present_flag.then(|| {
+            let mut lt_ref_pic_poc_lsb_sps = [0; 32];
+            for (dst, src) in lt_ref_pic_poc_lsb_sps
+                .iter_mut()
+                .zip(&sps.lt_ref_pic_poc_lsb_sps)
+            {
+                *dst = *src;
+            }
+
+            Box::new(vk::native::StdVideoH265LongTermRefPicsSps {
+                used_by_curr_pic_lt_sps_flag: bitmask(&sps.used_by_curr_pic_lt_sps_flag),
+                lt_ref_pic_poc_lsb_sps,
+            })
+        });
+
+        let range_extension = sps.sps_range_extension.as_ref();
+        let range_extension_flag =
+            |flag: fn(&SpsRangeExtension) -> bool| range_extension.is_some_and(flag).into();
+        let [
+            conf_win_left_offset,
+            conf_win_right_offset,
+            conf_win_top_offset,
+            conf_win_bottom_offset,
+        ] = sps.conformance_window.unwrap_or_default();
+
+        Self {
+            sps: vk::native::StdVideoH265SequenceParameterSet {
+                flags: vk::native::StdVideoH265SpsFlags {
+                    _bitfield_align_1: [],
+                    _bitfield_1: vk::native::StdVideoH265SpsFlags::new_bitfield_1(
+                        sps.sps_temporal_id_nesting_flag.into(),
+                        sps.separate_colour_plane_flag.into(),
+                        sps.conformance_window.is_some().into(),
+                        sps.sps_sub_layer_ordering_info_present_flag.into(),
+                        sps.scaling_list_enabled_flag.into(),
+                        sps.scaling_list_data.is_some().into(),
+                        sps.amp_enabled_flag.into(),
+                        sps.sample_adaptive_offset_enabled_flag.into(),
+                        sps.pcm_enabled_flag.into(),
+                        sps.pcm_loop_filter_disabled_flag.into(),
+                        sps.long_term_ref_pics_present_flag.into(),
+                        sps.sps_temporal_mvp_enabled_flag.into(),
+                        sps.strong_intra_smoothing_enabled_flag.into(),
+                        sps.vui_parameters.is_some().into(),
+                        sps.sps_extension_present_flag.into(),
+                        range_extension.is_some().into(),
+                        range_extension_flag(|e| e.transform_skip_rotation_enabled_flag),
+                        range_extension_flag(|e| e.transform_skip_context_enabled_flag),
+                        range_extension_flag(|e| e.implicit_rdpcm_enabled_flag),
+                        range_extension_flag(|e| e.explicit_rdpcm_enabled_flag),
+                        range_extension_flag(|e| e.extended_precision_processing_flag),
+                        range_extension_flag(|e| e.intra_smoothing_disabled_flag),
+                        range_extension_flag(|e| e.high_precision_offsets_enabled_flag),
+                        range_extension_flag(|e| e.persistent_rice_adaptation_enabled_flag),
+                        range_extension_flag(|e| e.cabac_bypass_alignment_enabled_flag),
+                        0, // scc extension
+                        0,
+                        0,
+                        0,
+                        0,
+                    ),
+                },
+                chroma_format_idc: sps.chroma_format_idc.into(),
+                pic_width_in_luma_samples: sps.pic_width_in_luma_samples,
+                pic_height_in_luma_samples: sps.pic_height_in_luma_samples,
+                sps_video_parameter_set_id: sps.sps_video_parameter_set_id,
+                sps_max_sub_layers_minus1: sps.sps_max_sub_layers_minus1,
+                sps_seq_parameter_set_id: sps.sps_seq_parameter_set_id,
+                bit_depth_luma_minus8: sps.bit_depth_luma_minus8,
+                bit_depth_chroma_minus8: sps.bit_depth_chroma_minus8,
+                log2_max_pic_order_cnt_lsb_minus4: sps.log2_max_pic_order_cnt_lsb_minus4,
+                log2_min_luma_coding_block_size_minus3: sps.log2_min_luma_coding_block_size_minus3,
+                log2_diff_max_min_luma_coding_block_size: sps
+                    .log2_diff_max_min_luma_coding_block_size,
+                log2_min_luma_transform_block_size_minus2: sps
+                    .log2_min_luma_transform_block_size_minus2,
+                log2_diff_max_min_luma_transform_block_size: sps
+                    .log2_diff_max_min_luma_transform_block_size,
+                max_transform_hierarchy_depth_inter: sps.max_transform_hierarchy_depth_inter,
+                max_transform_hierarchy_depth_intra: sps.max_transform_hierarchy_depth_intra,
+                num_short_term_ref_pic_sets: sps.short_term_ref_pic_sets.len() as u8,
+                num_long_term_ref_pics_sps: sps.lt_ref_pic_poc_lsb_sps.len() as u8,
+                pcm_sample_bit_depth_luma_minus1: sps.pcm_sample_bit_depth_luma_minus1,
+                pcm_sample_bit_depth_chroma_minus1: sps.pcm_sample_bit_depth_chroma_minus1,
+                log2_min_pcm_luma_coding_block_size_minus3: sps
+                    .log2_min_pcm_luma_coding_block_size_minus3,
+                log2_diff_max_min_pcm_luma_coding_block_size: sps
+                    .log2_diff_max_min_pcm_luma_coding_block_size,
+                reserved1: 0,
+                reserved2: 0,
+                palette_max_size: 0,
+                delta_palette_max_predictor_size: 0,
+                motion_vector_resolution_control_idc: 0,
+                sps_num_palette_predictor_initializers_minus1: 0,
+                conf_win_left_offset,
+                conf_win_right_offset,
+                conf_win_top_offset,
+                conf_win_bottom_offset,
+                pProfileTierLevel: profile_tier_level.as_ref(),
+                pDecPicBufMgr: dec_pic_buf_mgr.as_ref(),
+                pScalingLists: scaling_lists
+                    .as_deref()
+                    .map_or(std::ptr::null(), std::ptr::from_ref),
+                pShortTermRefPicSet: short_term_ref_pic_sets
+                    .as_deref()
+                    .map_or(std::ptr::null(), <[_]>::as_ptr),
+                pLongTermRefPicsSps: long_term_ref_pics
+                    .as_deref()
+                    .map_or(std::ptr::null(), std::ptr::from_ref),
+                pSequenceParameterSetVui: vui
+                    .as_deref()
+                    .map_or(std::ptr::null(), std::ptr::from_ref),
+                pPredictorPaletteEntries: std::ptr::null(),
+            },
+            _profile_tier_level: Some(profile_tier_level),
+            _dec_pic_buf_mgr: Some(dec_pic_buf_mgr),
+            _vui: vui,
+            _scaling_lists: scaling_lists,
+            _short_term_ref_pic_sets: short_term_ref_pic_sets,
+            _long_term_ref_pics: long_term_ref_pics,
+        }
+    }
+}
+
+unsafe impl Send for VkH265PictureParameterSet {}
+unsafe impl Sync for VkH265PictureParameterSet {}
+
+impl VkH265PictureParameterSet {
+    /// `sps_video_parameter_set_id` is the id of the VPS referenced by the SPS this PPS refers to.
+    pub(crate) fn new_decode(pps: &PicParameterSet, sps_video_parameter_set_id: u8) -> Self {
+        let scaling_lists = pps
+            .scaling_list_data
+            .as_ref()
+            .map(|data| Box::new(decode_scaling_lists(data)));
+
+        let range_extension = pps.pps_range_extension.clone().unwrap_or_default();
+        let mut cb_qp_offset_list = [0; 6];
+        let mut cr_qp_offset_list = [0; 6];
+        for (dst, src) in cb_qp_offset_list
+            .iter_mut()
+            .zip(&range_extension.cb_qp_offset_list)
+        {
+            *dst = *src;
+        }
+        for (dst, src) in cr_qp_offset_list
+            .iter_mut()
+            .zip(&range_extension.cr_qp_offset_list)
+        {
+            *dst = *src;
+        }
+
+        let mut column_width_minus1 = [0; 19];
+        let mut row_height_minus1 = [0; 21];
+        for (dst, src) in column_width_minus1.iter_mut().zip(&pps.column_width_minus1) {
+            *dst = *src;
+        }
+        for (dst, src) in row_height_minus1.iter_mut().zip(&pps.row_height_minus1) {
+            *dst = *src;
+        }
+
+        Self {
+            pps: vk::native::StdVideoH265PictureParameterSet {
+                flags: vk::native::StdVideoH265PpsFlags {
+                    _bitfield_align_1: [],
+                    _bitfield_1: vk::native::StdVideoH265PpsFlags::new_bitfield_1(
+                        pps.dependent_slice_segments_enabled_flag.into(),
+                        pps.output_flag_present_flag.into(),
+                        pps.sign_data_hiding_enabled_flag.into(),
+                        pps.cabac_init_present_flag.into(),
+                        pps.constrained_intra_pred_flag.into(),
+                        pps.transform_skip_enabled_flag.into(),
+                        pps.cu_qp_delta_enabled_flag.into(),
+                        pps.pps_slice_chroma_qp_offsets_present_flag.into(),
+                        pps.weighted_pred_flag.into(),
+                        pps.weighted_bipred_flag.into(),
+                        pps.transquant_bypass_enabled_flag.into(),
+                        pps.tiles_enabled_flag.into(),
+                        pps.entropy_coding_sync_enabled_flag.into(),
+                        pps.uniform_spacing_flag.into(),
+                        pps.loop_filter_across_tiles_enabled_flag.into(),
+                        pps.pps_loop_filter_across_slices_enabled_flag.into(),
+                        pps.deblocking_filter_control_present_flag.into(),
+                        pps.deblocking_filter_override_enabled_flag.into(),
+                        pps.pps_deblocking_filter_disabled_flag.into(),
+                        pps.scaling_list_data.is_some().into(),
+                        pps.lists_modification_present_flag.into(),
+                        pps.slice_segment_header_extension_present_flag.into(),
+                        pps.pps_extension_present_flag.into(),
+                        range_extension
+                            .cross_component_prediction_enabled_flag
+                            .into(),
+                        range_extension.chroma_qp_offset_list_enabled_flag.into(),
+                        0, // pps_curr_pic_ref_enabled_flag
+                        0, // residual_adaptive_colour_transform_enabled_flag
+                        0, // pps_slice_act_qp_offsets_present_flag
+                        0, // pps_palette_predictor_initializers_present_flag
+                        0, // monochrome_palette_flag
+                        pps.pps_range_extension.is_some().into(),
+                    ),
+                },
+                sps_video_parameter_set_id,
+                pps_seq_parameter_set_id: pps.pps_seq_parameter_set_id,
+                pps_pic_parameter_set_id: pps.pps_pic_parameter_set_id,
+                reserved1: 0,
+                reserved2: 0,
+                num_extra_slice_header_bits: pps.num_extra_slice_header_bits,
+                num_ref_idx_l0_default_active_minus1: pps.num_ref_idx_l0_default_active_minus1,
+                num_ref_idx_l1_default_active_minus1: pps.num_ref_idx_l1_default_active_minus1,
+                init_qp_minus26: pps.init_qp_minus26,
+                diff_cu_qp_delta_depth: pps.diff_cu_qp_delta_depth,
+                pps_cb_qp_offset: pps.pps_cb_qp_offset,
+                pps_cr_qp_offset: pps.pps_cr_qp_offset,
+                pps_beta_offset_div2: pps.pps_beta_offset_div2,
+                pps_tc_offset_div2: pps.pps_tc_offset_div2,
+                log2_parallel_merge_level_minus2: pps.log2_parallel_merge_level_minus2,
+                log2_max_transform_skip_block_size_minus2: range_extension
+                    .log2_max_transform_skip_block_size_minus2,
+                diff_cu_chroma_qp_offset_depth: range_extension.diff_cu_chroma_qp_offset_depth,
+                chroma_qp_offset_list_len_minus1: range_extension.chroma_qp_offset_list_len_minus1,
+                cb_qp_offset_list,
+                cr_qp_offset_list,
+                log2_sao_offset_scale_luma: range_extension.log2_sao_offset_scale_luma,
+                log2_sao_offset_scale_chroma: range_extension.log2_sao_offset_scale_chroma,
+                pps_act_y_qp_offset_plus5: 0,
+                pps_act_cb_qp_offset_plus5: 0,
+                pps_act_cr_qp_offset_plus3: 0,
+                pps_num_palette_predictor_initializers: 0,
+                luma_bit_depth_entry_minus8: 0,
+                chroma_bit_depth_entry_minus8: 0,
+                num_tile_columns_minus1: pps.num_tile_columns_minus1,
+                num_tile_rows_minus1: pps.num_tile_rows_minus1,
+                column_width_minus1,
+                row_height_minus1,
+                reserved3: 0,
+                pScalingLists: scaling_lists
+                    .as_deref()
+                    .map_or(std::ptr::null(), std::ptr::from_ref),
+                pPredictorPaletteEntries: std::ptr::null(),
+            },
+            _scaling_lists: scaling_lists,
+        }
+    }
+}
+
+fn decode_profile_tier_level(
+    profile_tier_level: &ProfileTierLevel,
+) -> vk::native::StdVideoH265ProfileTierLevel {
+    vk::native::StdVideoH265ProfileTierLevel {
+        flags: vk::native::StdVideoH265ProfileTierLevelFlags {
+            _bitfield_align_1: [],
+            _bitfield_1: vk::native::StdVideoH265ProfileTierLevelFlags::new_bitfield_1(
+                profile_tier_level.general_tier_flag.into(),
+                profile_tier_level.general_progressive_source_flag.into(),
+                profile_tier_level.general_interlaced_source_flag.into(),
+                profile_tier_level.general_non_packed_constraint_flag.into(),
+                profile_tier_level.general_frame_only_constraint_flag.into(),
+            ),
+            __bindgen_padding_0: [0; 3],
+        },
+        general_profile_idc: h265_profile_idc_to_vk(profile_tier_level),
+        general_level_idc: h265_level_idc_to_vk(profile_tier_level.general_level_idc),
+    }
+}
+
+fn decode_dec_pic_buf_mgr(
+    sub_layer_ordering_info: &SubLayerOrderingInfo,
+) -> vk::native::StdVideoH265DecPicBufMgr {
+    vk::native::StdVideoH265DecPicBufMgr {
+        max_latency_increase_plus1: sub_layer_ordering_info.max_latency_increase_plus1,
+        max_dec_pic_buffering_minus1: sub_layer_ordering_info.max_dec_pic_buffering_minus1,
+        max_num_reorder_pics: sub_layer_ordering_info.max_num_reorder_pics,
+    }
+}
+
+fn decode_scaling_lists(data: &ScalingListData) -> vk::native::StdVideoH265ScalingLists {
+    vk::native::StdVideoH265ScalingLists {
+        ScalingList4x4: data.scaling_list_4x4,
+        ScalingList8x8: data.scaling_list_8x8,
+        ScalingList16x16: data.scaling_list_16x16,
+        ScalingList32x32: data.scaling_list_32x32,
+        ScalingListDCCoef16x16: data.scaling_list_dc_coef_16x16,
+        ScalingListDCCoef32x32: data.scaling_list_dc_coef_32x32,
+    }
+}
+
+/// The derived `DeltaPocS0` and `DeltaPocS1` values are passed for every set, including the ones
+/// predicted from other sets.
+fn decode_short_term_ref_pic_set(
+    set: &ShortTermRefPicSet,
+) -> vk::native::StdVideoH265ShortTermRefPicSet {
+    let mut delta_poc_s0_minus1 = [0; 16];
+    let mut previous = 0;
+    for (dst, &delta_poc) in delta_poc_s0_minus1.iter_mut().zip(&set.delta_poc_s0) {
+        *dst = (previous - delta_poc - 1) as u16;
+        previous = delta_poc;
+    }
+
+    let mut delta_poc_s1_minus1 = [0; 16];
+    let mut previous = 0;
+    for (dst, &delta_poc) in delta_poc_s1_minus1.iter_mut().zip(&set.delta_poc_s1) {
+        *dst = (delta_poc - previous - 1) as u16;
+        previous = delta_poc;
+    }
+
+    vk::native::StdVideoH265ShortTermRefPicSet {
+        flags: vk::native::StdVideoH265ShortTermRefPicSetFlags {
+            _bitfield_align_1: [],
+            _bitfield_1: vk::native::StdVideoH265ShortTermRefPicSetFlags::new_bitfield_1(
+                set.inter_ref_pic_set_prediction_flag.into(),
+                set.delta_rps_sign.into(),
+            ),
+            __bindgen_padding_0: [0; 3],
+        },
+        delta_idx_minus1: set.delta_idx_minus1,
+        use_delta_flag: bitmask(&set.use_delta_flag) as u16,
+        abs_delta_rps_minus1: set.abs_delta_rps_minus1 as u16,
+        used_by_curr_pic_flag: bitmask(&set.used_by_curr_pic_flag) as u16,
+        used_by_curr_pic_s0_flag: bitmask(&set.used_by_curr_pic_s0) as u16,
+        used_by_curr_pic_s1_flag: bitmask(&set.used_by_curr_pic_s1) as u16,
+        reserved1: 0,
+        reserved2: 0,
+        reserved3: 0,
+        num_negative_pics: set.delta_poc_s0.len() as u8,
+        num_positive_pics: set.delta_poc_s1.len() as u8,
+        delta_poc_s0_minus1,
+        delta_poc_s1_minus1,
+    }
+}
+
+fn decode_vui(vui: &VuiParameters) -> vk::native::StdVideoH265SequenceParameterSetVui {
+    let (aspect_ratio_idc, sar_width, sar_height) = vui.aspect_ratio.unwrap_or_default();
+    let video_signal_type = vui.video_signal_type.as_ref();
+    let colour_description = video_signal_type.and_then(|v| v.colour_description.as_ref());
+    let (chroma_sample_loc_type_top_field, chroma_sample_loc_type_bottom_field) =
+        vui.chroma_loc_info.unwrap_or_default();
+    let [
+        def_disp_win_left_offset,
+        def_disp_win_right_offset,
+        def_disp_win_top_offset,
+        def_disp_win_bottom_offset,
+    ] = vui.default_display_window.unwrap_or_default();
+    let timing_info = vui.timing_info.as_ref();
+    let bitstream_restriction = vui.bitstream_restriction.as_ref();
+
+    vk::native::StdVideoH265SequenceParameterSetVui {
+        flags: vk::native::StdVideoH265SpsVuiFlags {
+            _bitfield_align_1: [],
+            _bitfield_1: vk::native::StdVideoH265SpsVuiFlags::new_bitfield_1(
+                vui.aspect_ratio.is_some().into(),
+                vui.overscan_appropriate.is_some().into(),
+                vui.overscan_appropriate.unwrap_or(false).into(),
+                video_signal_type.is_some().into(),
+                video_signal_type
+                    .is_some_and(|v| v.video_full_range_flag)
+                    .into(),
+                colour_description.is_some().into(),
+                vui.chroma_loc_info.is_some().into(),
+                vui.neutral_chroma_indication_flag.into(),
+                vui.field_seq_flag.into(),
+                vui.frame_field_info_present_flag.into(),
+                vui.default_display_window.is_some().into(),
+                timing_info.is_some().into(),
+                timing_info
+                    .is_some_and(|t| t.num_ticks_poc_diff_one_minus1.is_some())
+                    .into(),
+                0, // vui_hrd_parameters_present_flag, HRD parameters are not parsed
+                bitstream_restriction.is_some().into(),
+                bitstream_restriction
+                    .is_some_and(|b| b.tiles_fixed_structure_flag)
+                    .into(),
+                bitstream_restriction
+                    .is_some_and(|b| b.motion_vectors_over_pic_boundaries_flag)
+                    .into(),
+                bitstream_restriction
+                    .is_some_and(|b| b.restricted_ref_pic_lists_flag)
+                    .into(),
+            ),
+            __bindgen_padding_0: 0,
+        },
+        aspect_ratio_idc: aspect_ratio_idc.into(),
+        sar_width,
+        sar_height,
+        video_format: video_signal_type.map_or(5, |v| v.video_format),
+        colour_primaries: colour_description.map_or(2, |c| c.colour_primaries),
+        transfer_characteristics: colour_description.map_or(2, |c| c.transfer_characteristics),
+        matrix_coeffs: colour_description.map_or(2, |c| c.matrix_coeffs),
+        chroma_sample_loc_type_top_field,
+        chroma_sample_loc_type_bottom_field,
+        reserved1: 0,
+        reserved2: 0,
+        def_disp_win_left_offset: def_disp_win_left_offset as u16,
+        def_disp_win_right_offset: def_disp_win_right_offset as u16,
+        def_disp_win_top_offset: def_disp_win_top_offset as u16,
+        def_disp_win_bottom_offset: def_disp_win_bottom_offset as u16,
+        vui_num_units_in_tick: timing_info.map_or(0, |t| t.num_units_in_tick),
+        vui_time_scale: timing_info.map_or(0, |t| t.time_scale),
+        vui_num_ticks_poc_diff_one_minus1: timing_info
+            .and_then(|t| t.num_ticks_poc_diff_one_minus1)
+            .unwrap_or(0),
+        min_spatial_segmentation_idc: bitstream_restriction
+            .map_or(0, |b| b.min_spatial_segmentation_idc as u16),
+        reserved3: 0,
+        max_bytes_per_pic_denom: bitstream_restriction
+            .map_or(0, |b| b.max_bytes_per_pic_denom as u8),
+        max_bits_per_min_cu_denom: bitstream_restriction
+            .map_or(0, |b| b.max_bits_per_min_cu_denom as u8),
+        log2_max_mv_length_horizontal: bitstream_restriction
+            .map_or(0, |b| b.log2_max_mv_length_horizontal as u8),
+        log2_max_mv_length_vertical: bitstream_restriction
+            .map_or(0, |b| b.log2_max_mv_length_vertical as u8),
+        pHrdParameters: std::ptr::null(),
+    }
+}
+
+/// Bit `i` is set if `flags[i]` is `true`.
+fn bitmask(flags: &[bool]) -> u32 {
+    flags
+        .iter()
+        .take(32)
+        .enumerate()
+        .filter(|(_, flag)| **flag)
+        .fold(0, |mask, (i, _)| mask | (1 << i))
+}
+
+fn h265_profile_idc_to_vk(
+    profile_tier_level: &ProfileTierLevel,
+) -> vk::native::StdVideoH265ProfileIdc {
+    match profile_tier_level.general_profile_idc {
+        1 => vk::native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_MAIN,
+        2 => vk::native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_MAIN_10,
+        3 => vk::native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_MAIN_STILL_PICTURE,
+        4 => vk::native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_FORMAT_RANGE_EXTENSIONS,
+        9 => vk::native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_SCC_EXTENSIONS,
+        _ if profile_tier_level.is_compatible_with(1) => {
+            vk::native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_MAIN
+        }
+        _ if profile_tier_level.is_compatible_with(2) => {
+            vk::native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_MAIN_10
+        }
+        _ => vk::native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_INVALID,
+    }
+}
+
+fn h265_level_idc_to_vk(level_idc: u8) -> vk::native::StdVideoH265LevelIdc {
+    match level_idc {
+        30 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_1_0,
+        60 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_2_0,
+        63 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_2_1,
+        90 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_3_0,
+        93 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_3_1,
+        120 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_4_0,
+        123 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_4_1,
+        150 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_5_0,
+        153 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_5_1,
+        156 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_5_2,
+        180 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_6_0,
+        183 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_6_1,
+        186 => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_6_2,
+        _ => vk::native::StdVideoH265LevelIdc_STD_VIDEO_H265_LEVEL_IDC_INVALID,
+    }
+}
+
+pub(crate) struct H265DecodeProfileInfo<'a> {
+    pub(crate) profile_info: Arc<ProfileInfo<'a>>,
+}
+
+impl<'a> H265DecodeProfileInfo<'a> {
+    /// Only the Main profile is supported. Streams of profiles compatible with it, e.g. Main
+    /// Still Picture, are decoded as Main.
+    pub(crate) fn from_sps_decode(
+        sps: &SeqParameterSet,
+        decode_usage_info: vk::VideoDecodeUsageInfoKHR<'a>,
+    ) -> Result<Self, VulkanDecoderError> {
+        let profile_tier_level = &sps.profile_tier_level;
+        if profile_tier_level.general_profile_idc != 1 && !profile_tier_level.is_compatible_with(1)
+        {
+            return Err(VulkanDecoderError::InvalidInputData(format!(
+                "unsupported h265 profile: {}",
+                profile_tier_level.general_profile_idc
+            )));
+        }
+
+        if sps.chroma_format_idc != 1 {
+            return Err(VulkanDecoderError::InvalidInputData(format!(
+                "unsupported chroma_format_idc: {}",
+                sps.chroma_format_idc
+            )));
+        }
+
+        if sps.bit_depth_luma_minus8 != 0 || sps.bit_depth_chroma_minus8 != 0 {
+            return Err(VulkanDecoderError::InvalidInputData(format!(
+                "unsupported bit depth: luma {}, chroma {}",
+                sps.bit_depth_luma_minus8 + 8,
+                sps.bit_depth_chroma_minus8 + 8
+            )));
+        }
+
+        let h265_profile_info = vk::VideoDecodeH265ProfileInfoKHR::default()
+            .std_profile_idc(vk::native::StdVideoH265ProfileIdc_STD_VIDEO_H265_PROFILE_IDC_MAIN);
+
+        let h265_profile_info: Box<dyn vk::ExtendsVideoProfileInfoKHR + Send + Sync> =
+            Box::new(h265_profile_info);
+        let decode_usage_info: Box<dyn vk::ExtendsVideoProfileInfoKHR + Send + Sync> =
+            Box::new(decode_usage_info);
+
+        let profile_info = vk::VideoProfileInfoKHR::default()
+            .video_codec_operation(vk::VideoCodecOperationFlagsKHR::DECODE_H265)
+            .chroma_subsampling(vk::VideoChromaSubsamplingFlagsKHR::TYPE_420)
+            .luma_bit_depth(vk::VideoComponentBitDepthFlagsKHR::TYPE_8)
+            .chroma_bit_depth(vk::VideoComponentBitDepthFlagsKHR::TYPE_8);
+
+        Ok(Self {
+            profile_info: Arc::new(ProfileInfo::new(
+                profile_info,
+                vec![h265_profile_info, decode_usage_info],
+            )),
+        })
+    }
+}
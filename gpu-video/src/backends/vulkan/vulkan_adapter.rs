@@ -4,7 +4,9 @@ use tracing::{debug, debug_span, warn};
 
 use crate::{
     VideoDeviceInitError,
-    adapter::{DeviceType, VideoAdapterBackend, VideoAdapterInfo},
+    adapter::{
+        DeviceType, VideoAdapterBackend, VideoAdapterInfo, VideoCodec, VideoQueueFamilyInfo,
+    },
     backends::vulkan::{
         VulkanDevice,
         vulkan_device::{
@@ -12,6 +14,7 @@ use crate::{
             queues::{QueueIndex, QueueIndices},
         },
         vulkan_instance::VulkanInstance,
+        wrappers::{EXTERNAL_MEMORY_HANDLE_TYPE, EXTERNAL_SEMAPHORE_HANDLE_TYPE},
     },
     capabilities::{DecodeCapabilities, EncodeCapabilities},
     device::VideoDeviceDescriptor,
@@ -23,6 +26,7 @@ const DECODE_EXTENSIONS: &[&CStr] = &[vk::KHR_VIDEO_DECODE_QUEUE_NAME];
 const DECODE_CODEC_EXTENSIONS: &[&CStr] = &[
     vk::KHR_VIDEO_DECODE_H264_NAME,
     vk::KHR_VIDEO_DECODE_H265_NAME,
+    vk::KHR_VIDEO_DECODE_AV1_NAME,
 ];
 
 const ENCODE_EXTENSIONS: &[&CStr] = &[vk::KHR_VIDEO_ENCODE_QUEUE_NAME];
@@ -31,6 +35,21 @@ const ENCODE_CODEC_EXTENSIONS: &[&CStr] = &[
     vk::KHR_VIDEO_ENCODE_H265_NAME,
 ];
 
+/// Enabled when supported, used for exporting decoded frames.
+#[cfg(unix)]
+const EXTERNAL_MEMORY_EXTENSIONS: &[&CStr] = &[
+    vk::KHR_EXTERNAL_MEMORY_FD_NAME,
+    vk::EXT_EXTERNAL_MEMORY_DMA_BUF_NAME,
+];
+#[cfg(windows)]
+const EXTERNAL_MEMORY_EXTENSIONS: &[&CStr] = &[vk::KHR_EXTERNAL_MEMORY_WIN32_NAME];
+
+/// Enabled when supported, used for synchronizing with consumers of exported frames.
+#[cfg(unix)]
+const EXTERNAL_SEMAPHORE_EXTENSIONS: &[&CStr] = &[vk::KHR_EXTERNAL_SEMAPHORE_FD_NAME];
+#[cfg(windows)]
+const EXTERNAL_SEMAPHORE_EXTENSIONS: &[&CStr] = &[vk::KHR_EXTERNAL_SEMAPHORE_WIN32_NAME];
+
 #[cfg(feature = "wgpu")]
 mod wgpu_api;
 #[cfg(feature = "wgpu")]
@@ -104,6 +123,13 @@ impl<'a> VulkanAdapter<'a> {
             return None;
         }
 
+        let supports_external_memory = check_extensions(EXTERNAL_MEMORY_EXTENSIONS, &extensions)
+            .is_ok()
+            && supports_buffer_export(instance, device);
+        let supports_external_semaphore =
+            check_extensions(EXTERNAL_SEMAPHORE_EXTENSIONS, &extensions).is_ok()
+                && supports_timeline_semaphore_export(instance, device);
+
         let queues_len =
             unsafe { instance.get_physical_device_queue_family_properties2_len(device) };
         let mut queues = vec![vk::QueueFamilyProperties2::default(); queues_len];
@@ -132,6 +158,14 @@ impl<'a> VulkanAdapter<'a> {
             .iter()
             .map(|q| q.queue_family_properties.queue_count)
             .collect::<Vec<_>>();
+        let queue_flags = queues
+            .iter()
+            .map(|q| q.queue_family_properties.queue_flags)
+            .collect::<Vec<_>>();
+        let timestamp_valid_bits = queues
+            .iter()
+            .map(|q| q.queue_family_properties.timestamp_valid_bits)
+            .collect::<Vec<_>>();
 
         let transfer_queue_idx = queues
             .iter()
@@ -195,6 +229,35 @@ impl<'a> VulkanAdapter<'a> {
             return None;
         }
 
+        let video_queue_families = queue_flags
+            .iter()
+            .zip(video_properties.iter())
+            .enumerate()
+            .filter_map(|(i, (&flags, video_properties))| {
+                let codecs = |flag, operations: &[_]| match flags.contains(flag) {
+                    true => codec_operations_to_codecs(
+                        video_properties.video_codec_operations,
+                        operations,
+                    ),
+                    false => Vec::new(),
+                };
+                let decode_codecs = codecs(vk::QueueFlags::VIDEO_DECODE_KHR, DECODE_OPERATIONS);
+                let encode_codecs = codecs(vk::QueueFlags::VIDEO_ENCODE_KHR, ENCODE_OPERATIONS);
+                if decode_codecs.is_empty() && encode_codecs.is_empty() {
+                    return None;
+                }
+
+                Some(VideoQueueFamilyInfo {
+                    family_index: i as u32,
+                    queue_count: queue_counts[i],
+                    dedicated: !flags
+                        .intersects(vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE),
+                    decode_codecs,
+                    encode_codecs,
+                })
+            })
+            .collect::<Vec<_>>();
+
         debug!("decode capabilities: {decode_capabilities:#?}");
         debug!("encode capabilities: {encode_capabilities:#?}");
 
@@ -230,8 +293,11 @@ impl<'a> VulkanAdapter<'a> {
             device_properties: properties,
             supports_decoding: decode_queue_idx.is_some(),
             supports_encoding: encode_queue_idx.is_some(),
+            supports_external_memory,
+            supports_external_semaphore,
             decode_capabilities: decode_capabilities.user_facing(),
             encode_capabilities: encode_capabilities.user_facing(),
+            video_queue_families,
         };
 
         Some(Self {
@@ -244,6 +310,7 @@ impl<'a> VulkanAdapter<'a> {
                     video_properties: video_properties[transfer_queue_idx],
                     query_result_status_properties: query_result_status_properties
                         [transfer_queue_idx],
+                    timestamp_valid_bits: timestamp_valid_bits[transfer_queue_idx],
                 },
                 compute: QueueIndex {
                     family_index: compute_queue_idx,
@@ -251,18 +318,21 @@ impl<'a> VulkanAdapter<'a> {
                     video_properties: video_properties[compute_queue_idx],
                     query_result_status_properties: query_result_status_properties
                         [compute_queue_idx],
+                    timestamp_valid_bits: timestamp_valid_bits[compute_queue_idx],
                 },
-                h264_decode: decode_queue_idx.map(|idx| QueueIndex {
+                decode: decode_queue_idx.map(|idx| QueueIndex {
                     family_index: idx,
                     queue_count: queue_counts[idx] as usize,
                     video_properties: video_properties[idx],
                     query_result_status_properties: query_result_status_properties[idx],
+                    timestamp_valid_bits: timestamp_valid_bits[idx],
                 }),
                 encode: encode_queue_idx.map(|idx| QueueIndex {
                     family_index: idx,
                     queue_count: queue_counts[idx] as usize,
                     video_properties: video_properties[idx],
                     query_result_status_properties: query_result_status_properties[idx],
+                    timestamp_valid_bits: timestamp_valid_bits[idx],
                 }),
                 graphics_transfer_compute: QueueIndex {
                     family_index: graphics_transfer_compute_queue_idx,
@@ -270,6 +340,7 @@ impl<'a> VulkanAdapter<'a> {
                     video_properties: video_properties[graphics_transfer_compute_queue_idx],
                     query_result_status_properties: query_result_status_properties
                         [graphics_transfer_compute_queue_idx],
+                    timestamp_valid_bits: timestamp_valid_bits[graphics_transfer_compute_queue_idx],
                 },
             },
             decode_capabilities: if supports_any_decoding {
@@ -318,9 +389,35 @@ impl<'a> VulkanAdapter<'a> {
                 true => ENCODE_CODEC_EXTENSIONS.iter().copied(),
                 false => [].iter().copied(),
             })
+            .chain(match self.info.supports_external_memory {
+                true => EXTERNAL_MEMORY_EXTENSIONS.iter().copied(),
+                false => [].iter().copied(),
+            })
+            .chain(match self.info.supports_external_semaphore {
+                true => EXTERNAL_SEMAPHORE_EXTENSIONS.iter().copied(),
+                false => [].iter().copied(),
+            })
             .collect::<Vec<_>>()
     }
 
+    /// Extensions that have to be enabled on a device passed to [`VulkanDevice::from_raw`].
+    pub fn required_device_extensions(&self) -> Vec<&'static CStr> {
+        let mut extensions = self.required_extensions();
+        extensions.push(ash::khr::timeline_semaphore::NAME);
+        extensions
+    }
+
+    /// Queue families and the number of queues in each of them that have to be created on a device
+    /// passed to [`VulkanDevice::from_raw`].
+    #[cfg_attr(not(feature = "expose-backends"), allow(dead_code))]
+    pub fn required_queue_families(&self) -> Vec<(u32, u32)> {
+        self.queue_indices
+            .queue_create_infos()
+            .iter()
+            .map(|info| (info.family_idx as u32, info.priorities.len() as u32))
+            .collect()
+    }
+
     pub fn create_device(
         self,
         desc: &VideoDeviceDescriptor,
@@ -341,6 +438,8 @@ impl VideoAdapterBackend for VulkanAdapter<'_> {
             device_properties,
             decode_capabilities,
             encode_capabilities,
+            video_queue_families,
+            ..
         } = self.info().clone();
 
         let api_version = {
@@ -373,6 +472,7 @@ impl VideoAdapterBackend for VulkanAdapter<'_> {
             supports_encoding,
             decode_capabilities,
             encode_capabilities,
+            video_queue_families,
         }
     }
 
@@ -457,6 +557,85 @@ fn supported_extensions<'a>(
         .collect()
 }
 
+/// Checks if buffers that decoded frames are copied into can be exported with
+/// [`EXTERNAL_MEMORY_HANDLE_TYPE`].
+fn supports_buffer_export(instance: &ash::Instance, device: vk::PhysicalDevice) -> bool {
+    let buffer_info = vk::PhysicalDeviceExternalBufferInfo::default()
+        .usage(vk::BufferUsageFlags::TRANSFER_DST)
+        .handle_type(EXTERNAL_MEMORY_HANDLE_TYPE);
+    let mut properties = vk::ExternalBufferProperties::default();
+    unsafe {
+        instance.get_physical_device_external_buffer_properties(
+            device,
+            &buffer_info,
+            &mut properties,
+        )
+    };
+
+    properties
+        .external_memory_properties
+        .external_memory_features
+        .contains(vk::ExternalMemoryFeatureFlags::EXPORTABLE)
+}
+
+/// Checks if timeline semaphores can be exported with [`EXTERNAL_SEMAPHORE_HANDLE_TYPE`].
+fn supports_timeline_semaphore_export(
+    instance: &ash::Instance,
+    device: vk::PhysicalDevice,
+) -> bool {
+    let mut type_info =
+        vk::SemaphoreTypeCreateInfo::default().semaphore_type(vk::SemaphoreType::TIMELINE);
+    let semaphore_info = vk::PhysicalDeviceExternalSemaphoreInfo::default()
+        .handle_type(EXTERNAL_SEMAPHORE_HANDLE_TYPE)
+        .push_next(&mut type_info);
+    let mut properties = vk::ExternalSemaphoreProperties::default();
+    unsafe {
+        instance.get_physical_device_external_semaphore_properties(
+            device,
+            &semaphore_info,
+            &mut properties,
+        )
+    };
+
+    properties
+        .external_semaphore_features
+        .contains(vk::ExternalSemaphoreFeatureFlags::EXPORTABLE)
+}
+
+const DECODE_OPERATIONS: &[(vk::VideoCodecOperationFlagsKHR, VideoCodec)] = &[
+    (
+        vk::VideoCodecOperationFlagsKHR::DECODE_H264,
+        VideoCodec::H264,
+    ),
+    (
+        vk::VideoCodecOperationFlagsKHR::DECODE_H265,
+        VideoCodec::H265,
+    ),
+    (vk::VideoCodecOperationFlagsKHR::DECODE_AV1, VideoCodec::Av1),
+];
+
+const ENCODE_OPERATIONS: &[(vk::VideoCodecOperationFlagsKHR, VideoCodec)] = &[
+    (
+        vk::VideoCodecOperationFlagsKHR::ENCODE_H264,
+        VideoCodec::H264,
+    ),
+    (
+        vk::VideoCodecOperationFlagsKHR::ENCODE_H265,
+        VideoCodec::H265,
+    ),
+];
+
+fn codec_operations_to_codecs(
+    codec_operations: vk::VideoCodecOperationFlagsKHR,
+    operations: &[(vk::VideoCodecOperationFlagsKHR, VideoCodec)],
+) -> Vec<VideoCodec> {
+    operations
+        .iter()
+        .filter(|(operation, _)| codec_operations.contains(*operation))
+        .map(|(_, codec)| *codec)
+        .collect()
+}
+
 fn extensions_to_codec_operations(extensions: &[&CStr]) -> vk::VideoCodecOperationFlagsKHR {
     extensions
         .iter()
@@ -475,6 +654,9 @@ fn extensions_to_codec_operations(extensions: &[&CStr]) -> vk::VideoCodecOperati
                 name if name == vk::KHR_VIDEO_DECODE_H265_NAME => {
                     vk::VideoCodecOperationFlagsKHR::DECODE_H265
                 }
+                name if name == vk::KHR_VIDEO_DECODE_AV1_NAME => {
+                    vk::VideoCodecOperationFlagsKHR::DECODE_AV1
+                }
                 _ => vk::VideoCodecOperationFlagsKHR::empty(),
             }
         })
@@ -517,9 +699,16 @@ pub struct VulkanAdapterInfo {
     pub device_type: vk::PhysicalDeviceType,
     pub supports_decoding: bool,
     pub supports_encoding: bool,
+    /// Decoded frames can be exported with
+    /// [`ExternalMemoryDecoder`](crate::ExternalMemoryDecoder).
+    pub supports_external_memory: bool,
+    /// Decoders can export their timeline semaphore with
+    /// [`ExternalMemoryDecoder::export_semaphore`](crate::ExternalMemoryDecoder::export_semaphore).
+    pub supports_external_semaphore: bool,
     pub device_properties: vk::PhysicalDeviceProperties,
     pub decode_capabilities: DecodeCapabilities,
     pub encode_capabilities: EncodeCapabilities,
+    pub video_queue_families: Vec<VideoQueueFamilyInfo>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -529,4 +718,16 @@ pub enum VulkanAdapterInitError {
 
     #[error("Profile does not support NV12 texture format")]
     NoNV12ProfileSupport,
+
+    #[error("Profile does not support P010 texture format")]
+    NoP010ProfileSupport,
+
+    #[error("Profile does not support {0:?} texture format")]
+    NoPictureFormatSupport(vk::Format),
+
+    #[error("No picture format for {0:?} chroma subsampling and {1:?} bit depth")]
+    UnsupportedProfileFormat(
+        vk::VideoChromaSubsamplingFlagsKHR,
+        vk::VideoComponentBitDepthFlagsKHR,
+    ),
 }
@@ -2,38 +2,114 @@ use ash::vk;
 
 use std::sync::Arc;
 
-use h264_reader::nal::{pps::PicParameterSet, sps::SeqParameterSet};
+use h264_reader::nal::{
+    pps::PicParameterSet,
+    sps::{FrameMbsFlags, SeqParameterSet},
+};
 use rustc_hash::FxHashMap;
-use session_resources::VideoSessionResources;
+use session_resources::{
+    VideoSessionResources, images::DecodingImages, is_max_num_reorder_frames_signaled,
+};
+use tracing::warn;
 
 use crate::{
-    RawFrameData, VideoBackendError,
+    ExternalMemoryFrame, ExternalSemaphoreHandle, PlaneLayout, RawFrameData, VideoBackendError,
     backends::vulkan::{
-        VulkanCommonError, codec::h264::parameters::SeqParameterSetExt as _,
-        vulkan_device::DecodingDevice, wrappers::*,
+        VulkanCommonError,
+        codec::{
+            Codec, CodecCapabilities,
+            h264::{H264Codec, parameters::SeqParameterSetExt as _},
+        },
+        vulkan_device::{
+            DecodingDevice,
+            caps::{picture_format_bytes_per_sample, picture_format_chroma_subsampling},
+            decoding_memory::DecodingMemoryReservation,
+        },
+        wrappers::*,
+    },
+    decoders::{ExternalMemoryVideoDecoderBackend, VideoDecoderBackend, VideoDecoderError},
+    device::{
+        ChromaSubsampling, ColorInfo, ColorRange, ColorSpace, MemoryBudget, SampleAspectRatio,
     },
-    decoders::{VideoDecoderBackend, VideoDecoderError},
-    device::{ColorRange, ColorSpace},
     frame_sorter::{DecodeResult, DecodeResultMetadata},
-    parameters::DecoderUsage,
+    parameters::{DecoderUsage, Deinterlacing, PixelFormat},
     parser::{
         decoder_instructions::DecoderInstruction,
-        reference_manager::{DecodeInformation, ReferenceId},
+        reference_manager::{DecodeInformation, Field, ReferenceId},
     },
 };
 
+mod av1;
+mod deinterlacer;
+mod h265;
+mod pixel_format_converter;
 mod session_resources;
 
-pub struct VulkanDecoder<'a> {
-    video_session_resources: Option<VideoSessionResources<'a>>,
+#[cfg(feature = "wgpu")]
+use crate::decoders::frame_pool::WgpuFramePool;
+use deinterlacer::Deinterlacer;
+use pixel_format_converter::{PixelFormatConversion, PixelFormatConverter};
+
+/// Codec-specific part of [`VulkanDecoder`].
+pub(crate) trait DecodeCodec: Codec {
+    type DecoderInstruction;
+    /// State of the video session, created when the first parameter sets are received.
+    type SessionResources<'a>: Default;
+
+    fn decode<'a, 'b>(
+        decoder: &'b mut VulkanDecoder<'a, Self>,
+        instruction: Self::DecoderInstruction,
+    ) -> Result<Option<DecodeSubmission<'b, 'a, Self>>, VulkanDecoderError>;
+}
+
+impl DecodeCodec for H264Codec {
+    type DecoderInstruction = DecoderInstruction;
+    type SessionResources<'a> = Option<VideoSessionResources<'a>>;
+
+    fn decode<'a, 'b>(
+        decoder: &'b mut VulkanDecoder<'a, Self>,
+        instruction: Self::DecoderInstruction,
+    ) -> Result<Option<DecodeSubmission<'b, 'a, Self>>, VulkanDecoderError> {
+        decoder.decode_instruction(instruction)
+    }
+}
+
+pub(crate) struct VulkanDecoder<'a, C: DecodeCodec = H264Codec> {
+    /// Dropped first, so that the submitted work finishes before the resources it uses are
+    /// destroyed.
     pub(crate) tracker: DecoderTracker,
+    video_session_resources: C::SessionResources<'a>,
     reference_id_to_dpb_slot_index: FxHashMap<ReferenceId, usize>,
-    decoding_device: Arc<DecodingDevice>,
+    decoding_device: Arc<DecodingDevice<C>>,
     usage_info: vk::VideoDecodeUsageInfoKHR<'a>,
     image_modifiers: ImageModifiers,
+    timestamp_queries: Option<Arc<TimestampQueries>>,
+    output_pixel_format: PixelFormat,
+    /// `None` if frames are downloaded in NV12 or P010, which does not require any conversion.
+    pixel_format_converter: Option<PixelFormatConverter>,
+    /// `None` if deinterlacing is disabled.
+    deinterlacer: Option<Deinterlacer>,
+    /// Image that frames are copied through when decoding into a frame pool. It is reused as long
+    /// as the frame size does not change.
+    #[cfg(feature = "wgpu")]
+    frame_pool_staging_image: Option<Arc<Image>>,
+    /// Set after the timeline semaphore is exported. Exported frames are then returned without
+    /// waiting for them to be written.
+    semaphore_exported: bool,
+    pending_exports: Vec<PendingExport>,
+    /// Rows of downloaded frames are padded to the row pitch alignment preferred by the device.
+    aligned_output_rows: bool,
+}
+
+/// Resources of an exported frame, kept until the GPU finishes writing it.
+struct PendingExport {
+    wait_value: SemaphoreWaitValue,
+    _buffer: ExportableBuffer,
+    _conversion: Option<PixelFormatConversion>,
+    _deinterlaced: Option<DecodeSubmissionImageInfo>,
 }
 
-impl VideoDecoderBackend for VulkanDecoder<'_> {
+impl VideoDecoderBackend for VulkanDecoder<'_, H264Codec> {
     fn decode_to_bytes(
         &mut self,
         decoder_instructions: Vec<DecoderInstruction>,
@@ -42,32 +118,53 @@ impl VideoDecoderBackend for VulkanDecoder<'_> {
     }
 }
 
+impl ExternalMemoryVideoDecoderBackend for VulkanDecoder<'_, H264Codec> {
+    fn decode_to_external_memory(
+        &mut self,
+        decoder_instructions: Vec<DecoderInstruction>,
+    ) -> Result<Vec<DecodeResult<ExternalMemoryFrame>>, VideoDecoderError> {
+        VulkanDecoder::decode_to_external_memory(self, decoder_instructions).map_err(Into::into)
+    }
+
+    fn export_semaphore(&mut self) -> Result<ExternalSemaphoreHandle, VideoDecoderError> {
+        VulkanDecoder::export_semaphore(self).map_err(Into::into)
+    }
+}
+
 #[cfg(feature = "wgpu")]
-impl crate::decoders::WgpuVideoDecoderBackend for VulkanDecoder<'_> {
+impl crate::decoders::WgpuVideoDecoderBackend for VulkanDecoder<'_, H264Codec> {
     fn decode_to_wgpu_textures(
         &mut self,
         wgpu_device: &wgpu::Device,
+        frame_pool: Option<&mut WgpuFramePool>,
         decoder_instructions: Vec<DecoderInstruction>,
     ) -> Result<Vec<DecodeResult<wgpu::Texture>>, VideoDecoderError> {
-        VulkanDecoder::decode_to_wgpu_textures(self, wgpu_device, decoder_instructions)
+        VulkanDecoder::decode_to_wgpu_textures(self, wgpu_device, frame_pool, decoder_instructions)
             .map_err(Into::into)
     }
 }
 
-impl VulkanDecoder<'_> {
+impl<'a, C: DecodeCodec> VulkanDecoder<'a, C> {
     pub(crate) fn new(
-        decoding_device: Arc<DecodingDevice>,
+        decoding_device: Arc<DecodingDevice<C>>,
         usage_flags: crate::parameters::DecoderUsage,
+        enable_gpu_timing: bool,
         image_modifiers: ImageModifiers,
+        output_pixel_format: PixelFormat,
+        deinterlacing: Deinterlacing,
     ) -> Result<Self, VulkanDecoderError> {
         let command_buffer_pools = DecoderCommandBufferPools {
             transfer: CommandBufferPool::new(
                 decoding_device.vulkan_device.clone(),
                 decoding_device.vulkan_device.queues.transfer.family_index,
             )?,
+            compute: CommandBufferPool::new(
+                decoding_device.vulkan_device.clone(),
+                decoding_device.vulkan_device.queues.compute.family_index,
+            )?,
             decode: CommandBufferPool::new(
                 decoding_device.vulkan_device.clone(),
-                decoding_device.h264_decode_queues.family_index,
+                decoding_device.decode_queues.family_index,
             )?,
         };
 
@@ -80,21 +177,64 @@ impl VulkanDecoder<'_> {
         let usage_info =
             vk::VideoDecodeUsageInfoKHR::default().video_usage_hints(usage_flags.into());
 
+        let timestamp_valid_bits = decoding_device.decode_queues.timestamp_valid_bits();
+        let timestamp_queries = match (enable_gpu_timing, timestamp_valid_bits) {
+            (false, _) => None,
+            (true, 0) => {
+                warn!("Decode queue does not support timestamp queries. GPU timing is disabled.");
+                None
+            }
+            (true, timestamp_valid_bits) => Some(Arc::new(TimestampQueries::new(
+                decoding_device.vulkan_device.device.clone(),
+                decoding_device
+                    .adapter_info
+                    .device_properties
+                    .limits
+                    .timestamp_period,
+                timestamp_valid_bits,
+            ))),
+        };
+
+        let pixel_format_converter = match output_pixel_format {
+            PixelFormat::Nv12 | PixelFormat::P010 => None,
+            pixel_format => Some(PixelFormatConverter::new(
+                decoding_device.vulkan_device.clone(),
+                pixel_format,
+            )?),
+        };
+
+        let deinterlacer = match deinterlacing {
+            Deinterlacing::Off => None,
+            mode => Some(Deinterlacer::new(
+                decoding_device.vulkan_device.clone(),
+                mode,
+            )?),
+        };
+
         Ok(Self {
             decoding_device,
-            video_session_resources: None,
+            video_session_resources: Default::default(),
             tracker,
             reference_id_to_dpb_slot_index: Default::default(),
             usage_info,
             image_modifiers,
+            timestamp_queries,
+            output_pixel_format,
+            pixel_format_converter,
+            deinterlacer,
+            #[cfg(feature = "wgpu")]
+            frame_pool_staging_image: None,
+            semaphore_exported: false,
+            pending_exports: Vec::new(),
+            aligned_output_rows: false,
         })
     }
 }
 
-impl<'a> VulkanDecoder<'a> {
+impl<'a, C: DecodeCodec> VulkanDecoder<'a, C> {
     pub(crate) fn decode_to_bytes(
         &mut self,
-        decoder_instructions: Vec<DecoderInstruction>,
+        decoder_instructions: Vec<C::DecoderInstruction>,
     ) -> Result<Vec<DecodeResult<RawFrameData>>, VulkanDecoderError> {
         let mut result = Vec::new();
         for instruction in decoder_instructions {
@@ -106,171 +246,60 @@ impl<'a> VulkanDecoder<'a> {
         Ok(result)
     }
 
-    #[cfg(feature = "wgpu")]
-    pub(crate) fn decode_to_wgpu_textures(
+    pub(crate) fn decode_to_external_memory(
         &mut self,
-        wgpu_device: &wgpu::Device,
-        decoder_instructions: Vec<DecoderInstruction>,
-    ) -> Result<Vec<DecodeResult<wgpu::Texture>>, VulkanDecoderError> {
+        decoder_instructions: Vec<C::DecoderInstruction>,
+    ) -> Result<Vec<DecodeResult<ExternalMemoryFrame>>, VulkanDecoderError> {
         let mut result = Vec::new();
         for instruction in decoder_instructions {
             if let Some(output) = self.decode(instruction)? {
-                result.push(output.output_to_wgpu_texture(wgpu_device)?);
+                result.push(output.export_output()?);
             }
         }
 
         Ok(result)
     }
 
-    pub(crate) fn decode<'b>(
-        &'b mut self,
-        instruction: DecoderInstruction,
-    ) -> Result<Option<DecodeSubmission<'b, 'a>>, VulkanDecoderError> {
-        match instruction {
-            DecoderInstruction::Decode {
-                decode_info,
-                reference_id,
-            } => {
-                return self
-                    .process_reference_frame(&decode_info, reference_id)
-                    .map(Option::Some);
-            }
-
-            DecoderInstruction::Idr {
-                decode_info,
-                reference_id,
-            } => {
-                return self
-                    .process_idr(&decode_info, reference_id)
-                    .map(Option::Some);
-            }
-
-            DecoderInstruction::Drop { reference_ids } => {
-                for reference_id in reference_ids {
-                    match self.reference_id_to_dpb_slot_index.remove(&reference_id) {
-                        Some(dpb_idx) => self
-                            .video_session_resources
-                            .as_mut()
-                            .map(|s| s.free_reference_picture(dpb_idx)),
-                        None => return Err(VulkanDecoderError::NonExistentReferenceRequested),
-                    };
-                }
-            }
-
-            DecoderInstruction::Sps(sps) => self.process_sps(sps)?,
-
-            DecoderInstruction::Pps(pps) => self.process_pps(pps)?,
-        }
-
-        Ok(None)
-    }
-
-    fn process_sps(&mut self, sps: SeqParameterSet) -> Result<(), VulkanDecoderError> {
-        match self.video_session_resources.as_mut() {
-            Some(session) => session.process_sps(sps, self.usage_info)?,
-            None => {
-                self.video_session_resources = Some(VideoSessionResources::new_from_sps(
-                    &self.decoding_device,
-                    self.tracker.command_buffer_pools.decode.begin_buffer()?,
-                    sps,
-                    self.usage_info,
-                    &mut self.tracker,
-                    self.image_modifiers,
-                )?)
+    #[cfg(feature = "wgpu")]
+    pub(crate) fn decode_to_wgpu_textures(
+        &mut self,
+        wgpu_device: &wgpu::Device,
+        mut frame_pool: Option<&mut WgpuFramePool>,
+        decoder_instructions: Vec<C::DecoderInstruction>,
+    ) -> Result<Vec<DecodeResult<wgpu::Texture>>, VulkanDecoderError> {
+        let mut result = Vec::new();
+        for instruction in decoder_instructions {
+            if let Some(output) = self.decode(instruction)? {
+                result.push(output.output_to_wgpu_texture(wgpu_device, frame_pool.as_deref_mut())?);
             }
         }
 
-        Ok(())
-    }
-
-    fn process_pps(&mut self, pps: PicParameterSet) -> Result<(), VulkanDecoderError> {
-        self.video_session_resources
-            .as_mut()
-            .ok_or(VulkanDecoderError::NoSession)?
-            .process_pps(pps)?;
-
-        Ok(())
-    }
-
-    fn process_idr<'b>(
-        &'b mut self,
-        decode_information: &DecodeInformation,
-        reference_id: ReferenceId,
-    ) -> Result<DecodeSubmission<'b, 'a>, VulkanDecoderError> {
-        self.do_decode(decode_information, reference_id, true, true)
+        Ok(result)
     }
 
-    fn process_reference_frame<'b>(
+    pub(crate) fn decode<'b>(
         &'b mut self,
-        decode_information: &DecodeInformation,
-        reference_id: ReferenceId,
-    ) -> Result<DecodeSubmission<'b, 'a>, VulkanDecoderError> {
-        self.do_decode(decode_information, reference_id, false, true)
+        instruction: C::DecoderInstruction,
+    ) -> Result<Option<DecodeSubmission<'b, 'a, C>>, VulkanDecoderError> {
+        C::decode(self, instruction)
     }
 
-    fn do_decode<'b>(
-        &'b mut self,
-        decode_information: &'_ DecodeInformation,
-        reference_id: ReferenceId,
-        is_idr: bool,
-        is_reference: bool,
-    ) -> Result<DecodeSubmission<'b, 'a>, VulkanDecoderError> {
-        let video_session_resources = self
-            .video_session_resources
-            .as_mut()
-            .ok_or(VulkanDecoderError::NoSession)?;
-
-        let sps = video_session_resources
-            .sps
-            .get(&decode_information.sps_id)
-            .ok_or(VulkanDecoderError::InvalidInputData(format!(
-                "Unknown SPS id {}",
-                decode_information.sps_id
-            )))?;
-
-        let cropped_extent = sps.size()?;
-        let color_space = ColorSpace::from(sps);
-        let color_range = ColorRange::from(sps);
-
-        if is_idr {
-            video_session_resources.ensure_session(
-                &self.decoding_device,
-                self.tracker.command_buffer_pools.decode.begin_buffer()?,
-                &mut self.tracker,
-                sps.coded_size(),
-            )?;
-        }
-
-        // upload data to a buffer
-        let size = (decode_information.rbsp_bytes.len() as u64).next_multiple_of(
-            self.decoding_device
-                .profile_capabilities
-                .video_capabilities
-                .min_bitstream_buffer_size_alignment,
-        );
-
-        let mut buffer = video_session_resources.decode_buffer_pool.buffer()?;
-        buffer.upload_data(
-            &decode_information.rbsp_bytes,
-            size,
-            &video_session_resources.parameters.profile_info,
-        )?;
-
-        // decode
-        // IDR - remove all reference picures
-        if is_idr {
-            video_session_resources
-                .decoding_images
-                .reset_all_allocations();
-
-            self.reference_id_to_dpb_slot_index = Default::default();
-        }
-
-        // begin video coding
-        let mut cmd_buffer = self.tracker.command_buffer_pools.decode.begin_buffer()?;
-
-        video_session_resources
-            .decoding_images
+    /// Records the commands preceding the decode command: layout transitions of the decoding
+    /// images, query resets and the start of the video coding scope. When `reset` is set, the
+    /// video session is reset, which has to be done before decoding a picture that starts a new
+    /// coded video sequence.
+    fn begin_decode_commands(
+        decoding_device: &DecodingDevice<C>,
+        tracker: &mut DecoderTracker,
+        timestamp_query: Option<&TimestampQueryPool>,
+        decode_query_pool: Option<&DecodingQueryPool>,
+        decoding_images: &DecodingImages,
+        begin_info: &vk::VideoBeginCodingInfoKHR,
+        reset: bool,
+    ) -> Result<OpenCommandBuffer, VulkanDecoderError> {
+        let mut cmd_buffer = tracker.command_buffer_pools.decode.begin_buffer()?;
+
+        decoding_images
             .dpb
             .image
             .image_with_view
@@ -291,7 +320,7 @@ impl<'a> VulkanDecoder<'a> {
                 },
             )?;
 
-        if let Some(dst) = &video_session_resources.decoding_images.dst_image {
+        if let Some(dst) = &decoding_images.dst_image {
             dst.image_with_view.transition_layout(
                 &mut cmd_buffer,
                 vk::PipelineStageFlags2::VIDEO_DECODE_KHR
@@ -319,43 +348,35 @@ impl<'a> VulkanDecoder<'a> {
             );
 
         unsafe {
-            self.decoding_device
-                .vulkan_device
-                .device
-                .cmd_pipeline_barrier2(
-                    cmd_buffer.buffer(),
-                    &vk::DependencyInfo::default().memory_barriers(&[memory_barrier]),
-                )
+            decoding_device.vulkan_device.device.cmd_pipeline_barrier2(
+                cmd_buffer.buffer(),
+                &vk::DependencyInfo::default().memory_barriers(&[memory_barrier]),
+            )
         };
 
-        if let Some(pool) = video_session_resources.decode_query_pool.as_ref() {
+        if let Some(pool) = decode_query_pool {
             pool.reset(cmd_buffer.buffer());
         }
 
-        let reference_slots = video_session_resources
-            .decoding_images
-            .reference_slot_info();
-
-        let begin_info = vk::VideoBeginCodingInfoKHR::default()
-            .video_session(video_session_resources.video_session.session)
-            .video_session_parameters(video_session_resources.parameters_manager.parameters())
-            .reference_slots(&reference_slots);
+        if let Some(pool) = timestamp_query {
+            pool.reset(cmd_buffer.buffer());
+            pool.write_start(cmd_buffer.buffer());
+        }
 
         unsafe {
-            self.decoding_device
+            decoding_device
                 .vulkan_device
                 .device
                 .video_queue_ext
-                .cmd_begin_video_coding_khr(cmd_buffer.buffer(), &begin_info)
+                .cmd_begin_video_coding_khr(cmd_buffer.buffer(), begin_info)
         };
 
-        // IDR - issue the reset command to the video session
-        if is_idr {
+        if reset {
             let control_info = vk::VideoCodingControlInfoKHR::default()
                 .flags(vk::VideoCodingControlFlagsKHR::RESET);
 
             unsafe {
-                self.decoding_device
+                decoding_device
                     .vulkan_device
                     .device
                     .video_queue_ext
@@ -363,208 +384,244 @@ impl<'a> VulkanDecoder<'a> {
             };
         }
 
-        // allocate a new reference picture and fill out the forms to get it set up
-        let new_reference_slot_index = video_session_resources
-            .decoding_images
-            .allocate_reference_picture()?;
-
-        let new_reference_slot_std_reference_info = decode_information.picture_info.into();
-        let mut new_reference_slot_dpb_slot_info = vk::VideoDecodeH264DpbSlotInfoKHR::default()
-            .std_reference_info(&new_reference_slot_std_reference_info);
+        Ok(cmd_buffer)
+    }
 
-        let new_reference_slot_video_picture_resource_info = video_session_resources
-            .decoding_images
-            .video_resource_info(new_reference_slot_index)
-            .unwrap();
+    /// Records the decode command, ends the video coding scope and submits the command buffer
+    /// to the decode queue.
+    fn end_decode_commands(
+        decoding_device: &DecodingDevice<C>,
+        tracker: &mut DecoderTracker,
+        timestamp_query: Option<&TimestampQueryPool>,
+        decode_query_pool: Option<&DecodingQueryPool>,
+        cmd_buffer: OpenCommandBuffer,
+        decode_info: &vk::VideoDecodeInfoKHR,
+    ) -> Result<SemaphoreWaitValue, VulkanDecoderError> {
+        if let Some(pool) = decode_query_pool {
+            pool.begin_query(cmd_buffer.buffer());
+        }
 
-        let setup_reference_slot = vk::VideoReferenceSlotInfoKHR::default()
-            .picture_resource(new_reference_slot_video_picture_resource_info)
-            .slot_index(new_reference_slot_index as i32)
-            .push_next(&mut new_reference_slot_dpb_slot_info);
+        unsafe {
+            decoding_device
+                .vulkan_device
+                .device
+                .video_decode_queue_ext
+                .cmd_decode_video_khr(cmd_buffer.buffer(), decode_info)
+        };
 
-        // prepare the reference list
-        let reference_slots = video_session_resources
-            .decoding_images
-            .reference_slot_info();
+        if let Some(pool) = decode_query_pool {
+            pool.end_query(cmd_buffer.buffer());
+        }
 
-        let references_std_ref_info = Self::prepare_references_std_ref_info(decode_information);
+        unsafe {
+            decoding_device
+                .vulkan_device
+                .device
+                .video_queue_ext
+                .cmd_end_video_coding_khr(
+                    cmd_buffer.buffer(),
+                    &vk::VideoEndCodingInfoKHR::default(),
+                )
+        };
 
-        let mut references_dpb_slot_info =
-            Self::prepare_references_dpb_slot_info(&references_std_ref_info);
+        if let Some(pool) = timestamp_query {
+            pool.write_end(cmd_buffer.buffer());
+        }
 
-        let pic_reference_slots = Self::prepare_reference_list_slot_info(
-            &self.reference_id_to_dpb_slot_index,
-            &reference_slots,
-            &mut references_dpb_slot_info,
-            decode_information,
+        let semaphore_wait_value = decoding_device.decode_queues.submit_chain_semaphore(
+            cmd_buffer.end()?,
+            tracker,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            DecoderTrackerWaitState::Decode,
         )?;
 
-        // prepare the decode target picture
-        let std_picture_info = vk::native::StdVideoDecodeH264PictureInfo {
-            flags: vk::native::StdVideoDecodeH264PictureInfoFlags {
-                _bitfield_align_1: [],
-                __bindgen_padding_0: [0; 3],
-                _bitfield_1: vk::native::StdVideoDecodeH264PictureInfoFlags::new_bitfield_1(
-                    matches!(
-                        decode_information.header.field_pic,
-                        h264_reader::nal::slice::FieldPic::Field(..)
-                    )
-                    .into(),
-                    is_idr.into(),
-                    is_idr.into(),
-                    0,
-                    is_reference.into(),
-                    0,
-                ),
-            },
-            PicOrderCnt: decode_information.picture_info.PicOrderCnt_for_decoding,
-            seq_parameter_set_id: decode_information.sps_id,
-            pic_parameter_set_id: decode_information.pps_id,
-            frame_num: decode_information.header.frame_num,
-            idr_pic_id: decode_information
-                .header
-                .idr_pic_id
-                .map(|a| a as u16)
-                .unwrap_or(0),
-            reserved1: 0,
-            reserved2: 0,
-        };
-
-        let slice_offsets = decode_information
-            .slice_indices
-            .iter()
-            .map(|&x| x as u32)
-            .collect::<Vec<_>>();
-
-        let mut decode_h264_picture_info = vk::VideoDecodeH264PictureInfoKHR::default()
-            .std_picture_info(&std_picture_info)
-            .slice_offsets(&slice_offsets);
-
-        let dst_picture_resource_info = &video_session_resources
-            .decoding_images
-            .target_picture_resource_info(new_reference_slot_index)
-            .unwrap();
-
-        // these 3 variables are for copying the result later
-        let (target_image, target_layer) = video_session_resources
-            .decoding_images
-            .target_info(new_reference_slot_index);
-
-        // fill out the final struct and issue the command
-        let decode_info = vk::VideoDecodeInfoKHR::default()
-            .src_buffer(*buffer.buffer)
-            .src_buffer_offset(0)
-            .src_buffer_range(size)
-            .dst_picture_resource(*dst_picture_resource_info)
-            .setup_reference_slot(&setup_reference_slot)
-            .reference_slots(&pic_reference_slots)
-            .push_next(&mut decode_h264_picture_info);
-
-        if let Some(pool) = video_session_resources.decode_query_pool.as_ref() {
-            pool.begin_query(cmd_buffer.buffer());
-        }
+        Ok(semaphore_wait_value)
+    }
 
-        unsafe {
-            self.decoding_device
-                .vulkan_device
-                .device
-                .video_decode_queue_ext
-                .cmd_decode_video_khr(cmd_buffer.buffer(), &decode_info)
+    #[cfg(feature = "wgpu")]
+    fn output_to_wgpu_texture(
+        &mut self,
+        wgpu_device: &wgpu::Device,
+        frame_pool: Option<&mut WgpuFramePool>,
+        decode_output: &DecodeSubmissionImageInfo,
+        color_space: ColorSpace,
+        color_range: ColorRange,
+    ) -> Result<wgpu::Texture, VulkanDecoderError> {
+        self.check_chroma_subsampling(&decode_output.image, false)?;
+        let deinterlaced = self.deinterlace(decode_output)?;
+        let decode_output = deinterlaced.as_ref().unwrap_or(decode_output);
+
+        let decoded_format = decode_output.image.format;
+        let (format, texture_format) = match (self.output_pixel_format, decoded_format) {
+            (PixelFormat::Rgba, _) => {
+                self.check_output_pixel_format(&decode_output.image)?;
+                (vk::Format::R8G8B8A8_UNORM, wgpu::TextureFormat::Rgba8Unorm)
+            }
+            (_, format @ vk::Format::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16) => {
+                if !wgpu_device
+                    .features()
+                    .contains(wgpu::Features::TEXTURE_FORMAT_P010)
+                {
+                    return Err(VulkanDecoderError::P010TexturesUnsupported);
+                }
+                (format, wgpu::TextureFormat::P010)
+            }
+            (_, format) => (format, wgpu::TextureFormat::NV12),
         };
 
-        if let Some(pool) = video_session_resources.decode_query_pool.as_ref() {
-            pool.end_query(cmd_buffer.buffer());
-        }
+        let copy_extent = vk::Extent3D {
+            width: decode_output.cropped_extent.width,
+            height: decode_output.cropped_extent.height,
+            depth: 1,
+        };
 
-        unsafe {
-            self.decoding_device
-                .vulkan_device
-                .device
-                .video_queue_ext
-                .cmd_end_video_coding_khr(
-                    cmd_buffer.buffer(),
-                    &vk::VideoEndCodingInfoKHR::default(),
-                )
+        let image = match frame_pool {
+            Some(_) => self.frame_pool_staging_image(format, copy_extent)?,
+            None => Arc::new(self.create_wgpu_output_image(format, copy_extent)?),
         };
 
-        let semaphore_wait_value = self
-            .decoding_device
-            .h264_decode_queues
-            .submit_chain_semaphore(
-                cmd_buffer.end()?,
+        match self.pixel_format_converter.as_mut() {
+            Some(converter) => Self::convert_output_to_image(
+                converter,
+                &self.decoding_device,
                 &mut self.tracker,
-                vk::PipelineStageFlags2::ALL_COMMANDS,
-                vk::PipelineStageFlags2::ALL_COMMANDS,
-                DecoderTrackerWaitState::Decode,
-            )?;
+                decode_output,
+                &image,
+                color_space,
+                color_range,
+            )?,
+            None => self.copy_output_to_image(decode_output, &image, copy_extent)?,
+        }
 
-        // after the decode save the new reference picture
-        self.reference_id_to_dpb_slot_index
-            .insert(reference_id, new_reference_slot_index);
+        let texture = Self::wrap_image_in_wgpu_texture(
+            wgpu_device,
+            image.clone(),
+            texture_format,
+            copy_extent,
+        );
 
-        let in_flight_resources = InFlightDecodeResources {
-            _video_session: video_session_resources.video_session.clone(),
-            _video_session_params: video_session_resources
-                .parameters_manager
-                .parameters
-                .clone(),
-            _dpb_image_with_view: video_session_resources
-                .decoding_images
-                .dpb_image_with_view(),
-            _dst_image_with_view: video_session_resources
-                .decoding_images
-                .dst_image_with_view(),
-        };
+        match frame_pool {
+            Some(frame_pool) => {
+                self.copy_to_frame_pool_texture(wgpu_device, frame_pool, &image, &texture)
+            }
+            None => Ok(texture),
+        }
+    }
 
-        Ok(DecodeSubmission {
-            decode_result: DecodeResult {
-                frame: DecodeSubmissionImageInfo {
-                    image: target_image,
-                    layer: target_layer as u32,
-                    cropped_extent,
-                },
-                metadata: DecodeResultMetadata {
-                    pic_order_cnt: decode_information.picture_info.PicOrderCnt_for_decoding[0],
-                    max_num_reorder_frames: video_session_resources
-                        .parameters
-                        .max_num_reorder_frames,
-                    is_idr,
-                    pts: decode_information.pts,
-                    color_space,
-                    color_range,
-                },
-            },
-            semaphore_wait_value,
-            decode_query_pool: video_session_resources.decode_query_pool.clone(),
-            in_flight_resources,
-            input_buffer: buffer,
-            decoder: self,
-        })
+    /// Returns the image that frames are copied through when decoding into a frame pool. The
+    /// pool textures can only be accessed through wgpu, so the frame is first copied into this
+    /// image, which is reused for all frames of the same size.
+    #[cfg(feature = "wgpu")]
+    fn frame_pool_staging_image(
+        &mut self,
+        format: vk::Format,
+        extent: vk::Extent3D,
+    ) -> Result<Arc<Image>, VulkanDecoderError> {
+        if let Some(image) = self
+            .frame_pool_staging_image
+            .as_ref()
+            .filter(|image| image.extent == extent && image.format == format)
+        {
+            return Ok(image.clone());
+        }
+
+        let image = Arc::new(self.create_wgpu_output_image(format, extent)?);
+        self.frame_pool_staging_image = Some(image.clone());
+        Ok(image)
     }
 
+    /// Copies `staging_texture`, which wraps `staging_image`, into a texture from the frame pool.
     #[cfg(feature = "wgpu")]
-    fn output_to_wgpu_texture(
+    fn copy_to_frame_pool_texture(
         &mut self,
         wgpu_device: &wgpu::Device,
-        decode_output: &DecodeSubmissionImageInfo,
+        frame_pool: &mut WgpuFramePool,
+        staging_image: &Image,
+        staging_texture: &wgpu::Texture,
     ) -> Result<wgpu::Texture, VulkanDecoderError> {
-        let hal_device = unsafe { wgpu_device.as_hal::<wgpu::hal::vulkan::Api>().unwrap() };
-        let copy_extent = vk::Extent3D {
-            width: decode_output.cropped_extent.width,
-            height: decode_output.cropped_extent.height,
-            depth: 1,
-        };
+        let (width, height, format) = (
+            staging_texture.width(),
+            staging_texture.height(),
+            staging_texture.format(),
+        );
+        let texture = frame_pool.pool.acquire(width, height, format).ok_or(
+            VulkanDecoderError::FramePoolExhausted {
+                width,
+                height,
+                format,
+            },
+        )?;
+
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_DST) {
+            return Err(VulkanDecoderError::InvalidFramePoolTexture(format!(
+                "texture usage is {:?}, COPY_DST is required",
+                texture.usage()
+            )));
+        }
+        if texture.width() != width || texture.height() != height || texture.format() != format {
+            return Err(VulkanDecoderError::InvalidFramePoolTexture(format!(
+                "texture is a {}x{} {:?} texture, while the frame is {}x{} {:?}",
+                texture.width(),
+                texture.height(),
+                texture.format(),
+                width,
+                height,
+                format,
+            )));
+        }
+
+        // Copy is on the wgpu core queue because it will handle `texture` layout transitions for us
+        let mut encoder = wgpu_device.create_command_encoder(&Default::default());
+        encoder.copy_texture_to_texture(
+            staging_texture.as_image_copy(),
+            texture.as_image_copy(),
+            texture.size(),
+        );
+        frame_pool.wgpu_queue.submit([encoder.finish()]);
+
+        // the staging image is overwritten by the next frame, so the copy has to finish first
+        wgpu_device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .map_err(VulkanDecoderError::WgpuPollError)?;
+
+        self.tracker
+            .image_layout_tracker
+            .lock()
+            .unwrap()
+            .map
+            .insert(
+                staging_image.key(),
+                vec![vk::ImageLayout::TRANSFER_SRC_OPTIMAL].into_boxed_slice(),
+            );
+
+        Ok(texture)
+    }
 
-        let queue_indices = [
+    #[cfg(feature = "wgpu")]
+    fn create_wgpu_output_image(
+        &self,
+        format: vk::Format,
+        extent: vk::Extent3D,
+    ) -> Result<Image, VulkanDecoderError> {
+        // converted frames are copied into the image on the compute queue
+        let mut queue_indices = vec![
             self.decoding_device.queues.transfer.family_index as u32,
+            self.decoding_device.queues.compute.family_index as u32,
             self.decoding_device.queues.wgpu.family_index as u32,
         ];
+        queue_indices.sort_unstable();
+        queue_indices.dedup();
+        let sharing_mode = match queue_indices.len() {
+            1 => vk::SharingMode::EXCLUSIVE,
+            _ => vk::SharingMode::CONCURRENT,
+        };
 
         let create_info = vk::ImageCreateInfo::default()
             .flags(vk::ImageCreateFlags::MUTABLE_FORMAT)
             .image_type(vk::ImageType::TYPE_2D)
-            .format(vk::Format::G8_B8R8_2PLANE_420_UNORM)
-            .extent(copy_extent)
+            .format(format)
+            .extent(extent)
             .mip_levels(1)
             .array_layers(1)
             .samples(vk::SampleCountFlags::TYPE_1)
@@ -574,16 +631,102 @@ impl<'a> VulkanDecoder<'a> {
                     | vk::ImageUsageFlags::TRANSFER_DST
                     | vk::ImageUsageFlags::TRANSFER_SRC,
             )
-            .sharing_mode(vk::SharingMode::CONCURRENT)
+            .sharing_mode(sharing_mode)
             .queue_family_indices(&queue_indices)
             .initial_layout(vk::ImageLayout::UNDEFINED);
 
-        let image = Image::new(
+        Ok(Image::new(
             self.decoding_device.allocator.clone(),
             &create_info,
             self.tracker.image_layout_tracker.clone(),
+        )?)
+    }
+
+    /// Converts the decoded frame to the output pixel format, copies the result into `image`
+    /// and waits for the copy to finish.
+    #[cfg(feature = "wgpu")]
+    fn convert_output_to_image(
+        converter: &mut PixelFormatConverter,
+        decoding_device: &DecodingDevice<C>,
+        tracker: &mut DecoderTracker,
+        decode_output: &DecodeSubmissionImageInfo,
+        image: &Image,
+        color_space: ColorSpace,
+        color_range: ColorRange,
+    ) -> Result<(), VulkanDecoderError> {
+        let device = &decoding_device.vulkan_device.device;
+        let mut cmd_buffer = tracker.command_buffer_pools.compute.begin_buffer()?;
+        let conversion =
+            converter.record(&mut cmd_buffer, decode_output, color_space, color_range)?;
+
+        let buffer_barrier = vk::BufferMemoryBarrier2::default()
+            .src_stage_mask(vk::PipelineStageFlags2::COMPUTE_SHADER)
+            .src_access_mask(vk::AccessFlags2::SHADER_STORAGE_WRITE)
+            .dst_stage_mask(vk::PipelineStageFlags2::COPY)
+            .dst_access_mask(vk::AccessFlags2::TRANSFER_READ)
+            .buffer(*conversion.output)
+            .offset(0)
+            .size(vk::WHOLE_SIZE);
+
+        unsafe {
+            device.cmd_pipeline_barrier2(
+                cmd_buffer.buffer(),
+                &vk::DependencyInfo::default().buffer_memory_barriers(&[buffer_barrier]),
+            )
+        };
+
+        image.transition_layout_single_layer(
+            &mut cmd_buffer,
+            vk::PipelineStageFlags2::NONE..vk::PipelineStageFlags2::COPY,
+            vk::AccessFlags2::NONE..vk::AccessFlags2::TRANSFER_WRITE,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            0,
+        )?;
+
+        let copy_info = vk::BufferImageCopy::default()
+            .image_subresource(vk::ImageSubresourceLayers {
+                mip_level: 0,
+                layer_count: 1,
+                base_array_layer: 0,
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+            })
+            .image_offset(vk::Offset3D::default())
+            .image_extent(image.extent)
+            .buffer_offset(0)
+            .buffer_row_length(0)
+            .buffer_image_height(0);
+
+        unsafe {
+            device.cmd_copy_buffer_to_image(
+                cmd_buffer.buffer(),
+                *conversion.output,
+                **image,
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                &[copy_info],
+            );
+        }
+
+        let wait_value = decoding_device.queues.compute.submit_chain_semaphore(
+            cmd_buffer.end()?,
+            tracker,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            DecoderTrackerWaitState::DownloadImageToBuffer,
         )?;
 
+        tracker.wait_for(wait_value, u64::MAX)?;
+
+        Ok(())
+    }
+
+    /// Copies the decoded frame into `image` and waits for the copy to finish.
+    #[cfg(feature = "wgpu")]
+    fn copy_output_to_image(
+        &mut self,
+        decode_output: &DecodeSubmissionImageInfo,
+        image: &Image,
+        copy_extent: vk::Extent3D,
+    ) -> Result<(), VulkanDecoderError> {
         let mut cmd_buffer = self.tracker.command_buffer_pools.transfer.begin_buffer()?;
 
         decode_output.image.transition_layout_single_layer(
@@ -646,7 +789,7 @@ impl<'a> VulkanDecoder<'a> {
                 cmd_buffer.buffer(),
                 decode_output.image.image,
                 vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
-                *image,
+                **image,
                 vk::ImageLayout::TRANSFER_DST_OPTIMAL,
                 &copy_info,
             );
@@ -664,84 +807,934 @@ impl<'a> VulkanDecoder<'a> {
                 DecoderTrackerWaitState::DownloadImageToBuffer,
             )?;
 
-        self.tracker.wait_for(semaphore_wait_value, u64::MAX)?;
+        self.tracker.wait_for(semaphore_wait_value, u64::MAX)?;
+
+        Ok(())
+    }
+
+    /// Creates a wgpu texture backed by `image`, which has to be in the `TRANSFER_DST_OPTIMAL`
+    /// layout.
+    #[cfg(feature = "wgpu")]
+    fn wrap_image_in_wgpu_texture(
+        wgpu_device: &wgpu::Device,
+        image: Arc<Image>,
+        texture_format: wgpu::TextureFormat,
+        copy_extent: vk::Extent3D,
+    ) -> wgpu::Texture {
+        let hal_device = unsafe { wgpu_device.as_hal::<wgpu::hal::vulkan::Api>().unwrap() };
+        let image_clone = image.clone();
+
+        let hal_texture = unsafe {
+            hal_device.texture_from_raw(
+                **image,
+                &wgpu::hal::TextureDescriptor {
+                    label: Some("vulkan video output texture"),
+                    usage: wgpu::TextureUses::RESOURCE
+                        | wgpu::TextureUses::COPY_DST
+                        | wgpu::TextureUses::COPY_SRC,
+                    memory_flags: wgpu::hal::MemoryFlags::empty(),
+                    size: wgpu::Extent3d {
+                        width: copy_extent.width,
+                        height: copy_extent.height,
+                        depth_or_array_layers: copy_extent.depth,
+                    },
+                    dimension: wgpu::TextureDimension::D2,
+                    sample_count: 1,
+                    view_formats: Vec::new(),
+                    format: texture_format,
+                    mip_level_count: 1,
+                },
+                Some(Box::new(move || {
+                    drop(image_clone);
+                })),
+                wgpu::hal::vulkan::TextureMemory::External,
+            )
+        };
+
+        unsafe {
+            wgpu_device.create_texture_from_hal::<wgpu::hal::vulkan::Api>(
+                hal_texture,
+                &wgpu::TextureDescriptor {
+                    label: Some("vulkan video output texture"),
+                    usage: wgpu::TextureUsages::COPY_DST
+                        | wgpu::TextureUsages::TEXTURE_BINDING
+                        | wgpu::TextureUsages::COPY_SRC,
+                    size: wgpu::Extent3d {
+                        width: copy_extent.width,
+                        height: copy_extent.height,
+                        depth_or_array_layers: copy_extent.depth,
+                    },
+                    dimension: wgpu::TextureDimension::D2,
+                    sample_count: 1,
+                    view_formats: &[],
+                    format: texture_format,
+                    mip_level_count: 1,
+                },
+                wgpu::TextureUses::COPY_DST,
+            )
+        }
+    }
+
+    fn download_output(
+        &mut self,
+        decode_output: &DecodeSubmissionImageInfo,
+        color_space: ColorSpace,
+        color_range: ColorRange,
+    ) -> Result<RawFrameData, VulkanDecoderError> {
+        self.check_output_pixel_format(&decode_output.image)?;
+        self.check_chroma_subsampling(&decode_output.image, self.pixel_format_converter.is_none())?;
+
+        let deinterlaced = self.deinterlace(decode_output)?;
+        let decode_output = deinterlaced.as_ref().unwrap_or(decode_output);
+        let extent = decode_output.cropped_extent;
+
+        // The conversion shaders write tightly packed frames
+        if let Some(converter) = self.pixel_format_converter.as_mut() {
+            let frame = Self::convert_and_download_output(
+                converter,
+                &self.decoding_device,
+                &mut self.tracker,
+                decode_output,
+                color_space,
+                color_range,
+            )?;
+            let planes = self.aligned_output_rows.then(|| {
+                self.output_pixel_format
+                    .plane_layouts(extent.width, extent.height)
+            });
+
+            return Ok(RawFrameData {
+                frame,
+                width: extent.width,
+                height: extent.height,
+                planes,
+            });
+        }
+
+        let chroma_subsampling = picture_format_chroma_subsampling(decode_output.image.format);
+        let planes = self.plane_layouts(extent, decode_output.image.format);
+        let (_, chroma_height) = chroma_subsampling.chroma_size(extent.width, extent.height);
+        let size = planes[1].offset + planes[1].row_pitch * chroma_height as u64;
+        let mut dst_buffer = Buffer::new_transfer(
+            self.decoding_device.allocator.clone(),
+            size,
+            TransferDirection::GpuToMem,
+        )?;
+        let wait_value = self.copy_image_to_buffer(
+            &decode_output.image,
+            extent,
+            decode_output.crop_offset,
+            decode_output.layer,
+            *dst_buffer,
+            &planes,
+        )?;
+
+        self.tracker.wait_for(wait_value, u64::MAX)?;
+
+        let frame = unsafe { dst_buffer.download_data_from_buffer(size as usize)? };
+
+        Ok(RawFrameData {
+            frame,
+            width: extent.width,
+            height: extent.height,
+            planes: (self.aligned_output_rows || chroma_subsampling != ChromaSubsampling::Yuv420)
+                .then_some(planes),
+        })
+    }
+
+    /// Pads the rows of frames downloaded without conversion to the row pitch alignment preferred
+    /// by the device, and returns the layout of the planes with the frames.
+    pub(crate) fn align_output_rows(&mut self) {
+        self.aligned_output_rows = true;
+    }
+
+    /// Layout of a frame downloaded without conversion, with an interleaved chroma plane like in
+    /// NV12 or P010. The rows are padded to the alignment the device prefers for copies from
+    /// images to buffers if [`Self::align_output_rows`] was called, tightly packed otherwise.
+    fn plane_layouts(&self, extent: vk::Extent2D, format: vk::Format) -> Vec<PlaneLayout> {
+        let limits = &self.decoding_device.adapter_info.device_properties.limits;
+        let bytes_per_sample = picture_format_bytes_per_sample(format);
+        let (row_pitch_alignment, offset_alignment) = match self.aligned_output_rows {
+            // The offset of the chroma plane also has to be a multiple of its texel size
+            true => (
+                limits.optimal_buffer_copy_row_pitch_alignment.max(1),
+                limits.optimal_buffer_copy_offset_alignment.max(4),
+            ),
+            false => (1, 1),
+        };
+        let (chroma_width, _) =
+            picture_format_chroma_subsampling(format).chroma_size(extent.width, extent.height);
+
+        let y_row_pitch =
+            (extent.width as u64 * bytes_per_sample).next_multiple_of(row_pitch_alignment);
+        let uv_row_pitch =
+            (2 * chroma_width as u64 * bytes_per_sample).next_multiple_of(row_pitch_alignment);
+        let uv_offset = (y_row_pitch * extent.height as u64).next_multiple_of(offset_alignment);
+
+        vec![
+            PlaneLayout {
+                offset: 0,
+                row_pitch: y_row_pitch,
+            },
+            PlaneLayout {
+                offset: uv_offset,
+                row_pitch: uv_row_pitch,
+            },
+        ]
+    }
+
+    fn convert_and_download_output(
+        converter: &mut PixelFormatConverter,
+        decoding_device: &DecodingDevice<C>,
+        tracker: &mut DecoderTracker,
+        decode_output: &DecodeSubmissionImageInfo,
+        color_space: ColorSpace,
+        color_range: ColorRange,
+    ) -> Result<Vec<u8>, VulkanDecoderError> {
+        let mut cmd_buffer = tracker.command_buffer_pools.compute.begin_buffer()?;
+        let mut conversion =
+            converter.record(&mut cmd_buffer, decode_output, color_space, color_range)?;
+
+        let wait_value = decoding_device.queues.compute.submit_chain_semaphore(
+            cmd_buffer.end()?,
+            tracker,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            DecoderTrackerWaitState::DownloadImageToBuffer,
+        )?;
+
+        tracker.wait_for(wait_value, u64::MAX)?;
+
+        let output = unsafe {
+            conversion
+                .output
+                .download_data_from_buffer(conversion.output_size)?
+        };
+
+        Ok(output)
+    }
+
+    /// Copies the frame into a newly allocated buffer in the output pixel format and exports
+    /// its memory. Waits for the copy to finish, unless the semaphore was exported with
+    /// [`Self::export_semaphore`].
+    fn export_output(
+        &mut self,
+        decode_output: &DecodeSubmissionImageInfo,
+        color_space: ColorSpace,
+        color_range: ColorRange,
+    ) -> Result<ExternalMemoryFrame, VulkanDecoderError> {
+        self.check_output_pixel_format(&decode_output.image)?;
+        self.check_chroma_subsampling(&decode_output.image, false)?;
+        self.release_finished_exports()?;
+
+        let deinterlaced = self.deinterlace(decode_output)?;
+        let decode_output = deinterlaced.as_ref().unwrap_or(decode_output);
+
+        let extent = decode_output.cropped_extent;
+        let planes = self
+            .output_pixel_format
+            .plane_layouts(extent.width, extent.height);
+        let vulkan_device = &self.decoding_device.vulkan_device;
+        let dst_buffer = ExportableBuffer::new(
+            vulkan_device.device.clone(),
+            &vulkan_device.memory_properties,
+            self.output_pixel_format
+                .frame_size(extent.width, extent.height) as u64,
+        )?;
+
+        let (wait_value, conversion) = match self.pixel_format_converter.as_mut() {
+            Some(converter) => {
+                // The descriptor set of the converter cannot be updated while the previous
+                // conversion is executing
+                if let Some(pending) = self.pending_exports.last() {
+                    self.tracker.wait_for(pending.wait_value, u64::MAX)?;
+                }
+                let (wait_value, conversion) = Self::convert_output_to_buffer(
+                    converter,
+                    &self.decoding_device,
+                    &mut self.tracker,
+                    decode_output,
+                    *dst_buffer,
+                    color_space,
+                    color_range,
+                )?;
+                (wait_value, Some(conversion))
+            }
+            None => {
+                let wait_value = self.copy_image_to_buffer(
+                    &decode_output.image,
+                    extent,
+                    decode_output.crop_offset,
+                    decode_output.layer,
+                    *dst_buffer,
+                    &planes,
+                )?;
+                (wait_value, None)
+            }
+        };
+
+        let frame = ExternalMemoryFrame {
+            handle: dst_buffer.export()?,
+            allocation_size: dst_buffer.allocation_size,
+            pixel_format: self.output_pixel_format,
+            width: extent.width,
+            height: extent.height,
+            planes,
+            semaphore_value: self.semaphore_exported.then_some(wait_value.0),
+        };
+
+        if self.semaphore_exported {
+            self.pending_exports.push(PendingExport {
+                wait_value,
+                _buffer: dst_buffer,
+                _conversion: conversion,
+                _deinterlaced: deinterlaced,
+            });
+        } else {
+            self.tracker.wait_for(wait_value, u64::MAX)?;
+        }
+
+        Ok(frame)
+    }
+
+    /// Replaces the timeline semaphore of the decoder with one that can be exported. Has to be
+    /// called before anything is submitted.
+    pub(crate) fn use_exportable_semaphore(&mut self) -> Result<(), VulkanDecoderError> {
+        self.tracker.semaphore_tracker.semaphore = TimelineSemaphore::new_exportable(
+            self.decoding_device.vulkan_device.device.clone(),
+            0,
+            Some("decoder semaphore"),
+        )?;
+        Ok(())
+    }
+
+    /// Returns a new handle to the timeline semaphore of the decoder. After it is exported, the
+    /// decoder does not wait for exported frames to be written, the consumers have to wait for
+    /// [`ExternalMemoryFrame::semaphore_value`] instead.
+    pub(crate) fn export_semaphore(
+        &mut self,
+    ) -> Result<ExternalSemaphoreHandle, VulkanDecoderError> {
+        let handle = self.tracker.semaphore_tracker.semaphore.export()?;
+        self.semaphore_exported = true;
+        Ok(handle)
+    }
+
+    /// Drops the resources of exported frames which the GPU finished writing.
+    fn release_finished_exports(&mut self) -> Result<(), VulkanDecoderError> {
+        if self.pending_exports.is_empty() {
+            return Ok(());
+        }
+
+        let finished = self.tracker.semaphore_tracker.semaphore.counter_value()?;
+        self.pending_exports
+            .retain(|pending| pending.wait_value > finished);
+        self.tracker.mark_waited(finished);
+        Ok(())
+    }
+
+    /// Converts the decoded frame to the output pixel format and records a copy of the result
+    /// into `dst_buffer`. The returned conversion has to be kept alive until the copy finishes.
+    fn convert_output_to_buffer(
+        converter: &mut PixelFormatConverter,
+        decoding_device: &DecodingDevice<C>,
+        tracker: &mut DecoderTracker,
+        decode_output: &DecodeSubmissionImageInfo,
+        dst_buffer: vk::Buffer,
+        color_space: ColorSpace,
+        color_range: ColorRange,
+    ) -> Result<(SemaphoreWaitValue, PixelFormatConversion), VulkanDecoderError> {
+        let device = &decoding_device.vulkan_device.device;
+        let mut cmd_buffer = tracker.command_buffer_pools.compute.begin_buffer()?;
+        let conversion =
+            converter.record(&mut cmd_buffer, decode_output, color_space, color_range)?;
+
+        let buffer_barrier = vk::BufferMemoryBarrier2::default()
+            .src_stage_mask(vk::PipelineStageFlags2::COMPUTE_SHADER)
+            .src_access_mask(vk::AccessFlags2::SHADER_STORAGE_WRITE)
+            .dst_stage_mask(vk::PipelineStageFlags2::COPY)
+            .dst_access_mask(vk::AccessFlags2::TRANSFER_READ)
+            .buffer(*conversion.output)
+            .offset(0)
+            .size(vk::WHOLE_SIZE);
+
+        unsafe {
+            device.cmd_pipeline_barrier2(
+                cmd_buffer.buffer(),
+                &vk::DependencyInfo::default().buffer_memory_barriers(&[buffer_barrier]),
+            );
+            device.cmd_copy_buffer(
+                cmd_buffer.buffer(),
+                *conversion.output,
+                dst_buffer,
+                &[vk::BufferCopy::default().size(conversion.output_size as u64)],
+            );
+        }
+
+        let wait_value = decoding_device.queues.compute.submit_chain_semaphore(
+            cmd_buffer.end()?,
+            tracker,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            DecoderTrackerWaitState::DownloadImageToBuffer,
+        )?;
+
+        Ok((wait_value, conversion))
+    }
+
+    /// Returns the deinterlaced frame, or `None` if deinterlacing is disabled. Waits for the
+    /// deinterlacing to finish, so the result can be read on the transfer or compute queue.
+    fn deinterlace(
+        &mut self,
+        decode_output: &DecodeSubmissionImageInfo,
+    ) -> Result<Option<DecodeSubmissionImageInfo>, VulkanDecoderError> {
+        let Some(deinterlacer) = self.deinterlacer.as_mut() else {
+            return Ok(None);
+        };
+
+        if decode_output.image.format != vk::Format::G8_B8R8_2PLANE_420_UNORM {
+            return Err(VulkanDecoderError::DeinterlacingUnsupported);
+        }
+
+        let mut cmd_buffer = self.tracker.command_buffer_pools.compute.begin_buffer()?;
+        let deinterlaced = deinterlacer.record(&mut cmd_buffer, &self.tracker, decode_output)?;
+
+        let wait_value = self.decoding_device.queues.compute.submit_chain_semaphore(
+            cmd_buffer.end()?,
+            &mut self.tracker,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            vk::PipelineStageFlags2::ALL_COMMANDS,
+            DecoderTrackerWaitState::Deinterlace,
+        )?;
+
+        self.tracker.wait_for(wait_value, u64::MAX)?;
+
+        Ok(Some(deinterlaced.frame))
+    }
+
+    /// 10-bit streams are decoded into P010 images, which can only be downloaded as P010. Images
+    /// of 8-bit streams cannot be downloaded as P010.
+    pub(crate) fn check_output_pixel_format(
+        &self,
+        image: &Image,
+    ) -> Result<(), VulkanDecoderError> {
+        let bit_depth = match picture_format_bytes_per_sample(image.format) {
+            2 => 10,
+            _ => 8,
+        };
+
+        if (bit_depth == 10) != (self.output_pixel_format == PixelFormat::P010) {
+            return Err(VulkanDecoderError::UnsupportedPixelFormat {
+                pixel_format: self.output_pixel_format,
+                bit_depth,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// 4:2:2 and 4:4:4 images are only output as bytes in the layout they were decoded in, the
+    /// conversions, the deinterlacer and the exports expect 4:2:0 frames.
+    pub(crate) fn check_chroma_subsampling(
+        &self,
+        image: &Image,
+        supports_high_chroma: bool,
+    ) -> Result<(), VulkanDecoderError> {
+        match picture_format_chroma_subsampling(image.format) {
+            ChromaSubsampling::Yuv420 => Ok(()),
+            _ if supports_high_chroma => Ok(()),
+            chroma_subsampling => Err(VulkanDecoderError::UnsupportedChromaSubsampling(
+                chroma_subsampling,
+            )),
+        }
+    }
+
+    /// Copies the cropped part of the image into `dst_buffer` with the given layout of the luma
+    /// and interleaved chroma planes. The buffer has to be big enough for the whole frame.
+    fn copy_image_to_buffer(
+        &mut self,
+        image: &Image,
+        dimensions: vk::Extent2D,
+        crop_offset: vk::Offset2D,
+        layer: u32,
+        dst_buffer: vk::Buffer,
+        planes: &[PlaneLayout],
+    ) -> Result<SemaphoreWaitValue, VulkanDecoderError> {
+        let mut cmd_buffer = self.tracker.command_buffer_pools.transfer.begin_buffer()?;
+
+        image.transition_layout_single_layer(
+            &mut cmd_buffer,
+            vk::PipelineStageFlags2::NONE..vk::PipelineStageFlags2::COPY,
+            vk::AccessFlags2::NONE..vk::AccessFlags2::TRANSFER_READ,
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            layer,
+        )?;
+
+        let bytes_per_sample = picture_format_bytes_per_sample(image.format);
+        let chroma_subsampling = picture_format_chroma_subsampling(image.format);
+        let (horizontal_divisor, vertical_divisor) = chroma_subsampling.divisors();
+        let (chroma_width, chroma_height) =
+            chroma_subsampling.chroma_size(dimensions.width, dimensions.height);
+        // Row lengths are specified in texels, a chroma texel holds both the U and V sample
+        let y_row_length = planes[0].row_pitch / bytes_per_sample;
+        let uv_row_length = planes[1].row_pitch / (2 * bytes_per_sample);
+
+        let copy_info = [
+            vk::BufferImageCopy::default()
+                .image_subresource(vk::ImageSubresourceLayers {
+                    mip_level: 0,
+                    layer_count: 1,
+                    base_array_layer: layer,
+                    aspect_mask: vk::ImageAspectFlags::PLANE_0,
+                })
+                .image_offset(vk::Offset3D {
+                    x: crop_offset.x,
+                    y: crop_offset.y,
+                    z: 0,
+                })
+                .image_extent(vk::Extent3D {
+                    width: dimensions.width,
+                    height: dimensions.height,
+                    depth: 1,
+                })
+                .buffer_offset(planes[0].offset)
+                .buffer_row_length(y_row_length as u32)
+                .buffer_image_height(0),
+            vk::BufferImageCopy::default()
+                .image_subresource(vk::ImageSubresourceLayers {
+                    mip_level: 0,
+                    layer_count: 1,
+                    base_array_layer: layer,
+                    aspect_mask: vk::ImageAspectFlags::PLANE_1,
+                })
+                .image_offset(vk::Offset3D {
+                    x: crop_offset.x / horizontal_divisor as i32,
+                    y: crop_offset.y / vertical_divisor as i32,
+                    z: 0,
+                })
+                .image_extent(vk::Extent3D {
+                    width: chroma_width,
+                    height: chroma_height,
+                    depth: 1,
+                })
+                .buffer_offset(planes[1].offset)
+                .buffer_row_length(uv_row_length as u32)
+                .buffer_image_height(0),
+        ];
+
+        unsafe {
+            self.decoding_device
+                .vulkan_device
+                .device
+                .cmd_copy_image_to_buffer(
+                    cmd_buffer.buffer(),
+                    **image,
+                    vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                    dst_buffer,
+                    &copy_info,
+                )
+        };
+
+        let wait_value = self
+            .decoding_device
+            .queues
+            .transfer
+            .submit_chain_semaphore(
+                cmd_buffer.end()?,
+                &mut self.tracker,
+                vk::PipelineStageFlags2::ALL_COMMANDS,
+                vk::PipelineStageFlags2::ALL_COMMANDS,
+                DecoderTrackerWaitState::DownloadImageToBuffer,
+            )?;
+
+        Ok(wait_value)
+    }
+}
+
+impl<'a> VulkanDecoder<'a, H264Codec> {
+    fn decode_instruction<'b>(
+        &'b mut self,
+        instruction: DecoderInstruction,
+    ) -> Result<Option<DecodeSubmission<'b, 'a>>, VulkanDecoderError> {
+        match instruction {
+            DecoderInstruction::Decode {
+                decode_info,
+                reference_id,
+            } => {
+                let submission = self.process_reference_frame(&decode_info, reference_id)?;
+                return Self::output_frame(submission, &decode_info);
+            }
+
+            DecoderInstruction::Idr {
+                decode_info,
+                reference_id,
+            } => {
+                let submission = self.process_idr(&decode_info, reference_id)?;
+                return Self::output_frame(submission, &decode_info);
+            }
+
+            DecoderInstruction::DecodeSecondField {
+                decode_info,
+                reference_id,
+            } => {
+                return self
+                    .process_second_field(&decode_info, reference_id)
+                    .map(Option::Some);
+            }
+
+            DecoderInstruction::Drop { reference_ids } => {
+                for reference_id in reference_ids {
+                    match self.reference_id_to_dpb_slot_index.remove(&reference_id) {
+                        Some(dpb_idx) => self
+                            .video_session_resources
+                            .as_mut()
+                            .map(|s| s.free_reference_picture(dpb_idx)),
+                        None => return Err(VulkanDecoderError::NonExistentReferenceRequested),
+                    };
+                }
+            }
+
+            DecoderInstruction::Sps(sps) => self.process_sps(sps)?,
+
+            DecoderInstruction::Pps(pps) => self.process_pps(pps)?,
+        }
+
+        Ok(None)
+    }
+
+    fn process_sps(&mut self, sps: SeqParameterSet) -> Result<(), VulkanDecoderError> {
+        match self.video_session_resources.as_mut() {
+            Some(session) => session.process_sps(sps, self.usage_info)?,
+            None => {
+                self.video_session_resources = Some(VideoSessionResources::new_from_sps(
+                    &self.decoding_device,
+                    self.tracker.command_buffer_pools.decode.begin_buffer()?,
+                    sps,
+                    self.usage_info,
+                    &mut self.tracker,
+                    self.image_modifiers,
+                )?)
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_pps(&mut self, pps: PicParameterSet) -> Result<(), VulkanDecoderError> {
+        self.video_session_resources
+            .as_mut()
+            .ok_or(VulkanDecoderError::NoSession)?
+            .process_pps(pps)?;
+
+        Ok(())
+    }
+
+    /// The first field of a frame is not output, the frame is output after its second field is
+    /// decoded.
+    fn output_frame<'b>(
+        submission: DecodeSubmission<'b, 'a>,
+        decode_information: &DecodeInformation,
+    ) -> Result<Option<DecodeSubmission<'b, 'a>>, VulkanDecoderError> {
+        if decode_information.picture_info.field.is_some() {
+            submission.discard()?;
+            return Ok(None);
+        }
+
+        Ok(Some(submission))
+    }
+
+    fn process_idr<'b>(
+        &'b mut self,
+        decode_information: &DecodeInformation,
+        reference_id: ReferenceId,
+    ) -> Result<DecodeSubmission<'b, 'a>, VulkanDecoderError> {
+        self.do_decode(decode_information, reference_id, true, true, false)
+    }
+
+    fn process_reference_frame<'b>(
+        &'b mut self,
+        decode_information: &DecodeInformation,
+        reference_id: ReferenceId,
+    ) -> Result<DecodeSubmission<'b, 'a>, VulkanDecoderError> {
+        self.do_decode(decode_information, reference_id, false, true, false)
+    }
+
+    fn process_second_field<'b>(
+        &'b mut self,
+        decode_information: &DecodeInformation,
+        reference_id: ReferenceId,
+    ) -> Result<DecodeSubmission<'b, 'a>, VulkanDecoderError> {
+        self.do_decode(decode_information, reference_id, false, true, true)
+    }
+
+    /// When `second_field` is set, the picture is decoded into the DPB slot of the first field
+    /// of its frame, which was decoded with the same `reference_id`.
+    fn do_decode<'b>(
+        &'b mut self,
+        decode_information: &'_ DecodeInformation,
+        reference_id: ReferenceId,
+        is_idr: bool,
+        is_reference: bool,
+        second_field: bool,
+    ) -> Result<DecodeSubmission<'b, 'a>, VulkanDecoderError> {
+        let video_session_resources = self
+            .video_session_resources
+            .as_mut()
+            .ok_or(VulkanDecoderError::NoSession)?;
+
+        let sps = video_session_resources
+            .sps
+            .get(&decode_information.sps_id)
+            .ok_or(VulkanDecoderError::InvalidInputData(format!(
+                "Unknown SPS id {}",
+                decode_information.sps_id
+            )))?;
+
+        let cropped_extent = sps.size()?;
+        let crop_offset = sps.crop_offset()?;
+        let color_space = ColorSpace::from(sps);
+        let color_range = ColorRange::from(sps);
+        let color_info = ColorInfo::from(sps);
+        let max_num_reorder_frames_signaled = is_max_num_reorder_frames_signaled(sps);
+        let sample_aspect_ratio = SampleAspectRatio::from_h264_sps(sps);
+        let field = decode_information.picture_info.field;
+        // parity of the field which comes first in a frame, used for deinterlacing
+        let first_field = match (field, second_field) {
+            (Some(field), true) => Some(field.opposite()),
+            _ if sps.frame_mbs_flags != FrameMbsFlags::Frames => Some(Field::Top),
+            _ => None,
+        };
+
+        if is_idr {
+            video_session_resources.ensure_session(
+                &self.decoding_device,
+                self.tracker.command_buffer_pools.decode.begin_buffer()?,
+                &mut self.tracker,
+                sps.coded_size(),
+            )?;
+        }
+
+        // upload data to a buffer
+        let size = (decode_information.rbsp_bytes.len() as u64).next_multiple_of(
+            self.decoding_device
+                .profile_capabilities
+                .video_capabilities
+                .min_bitstream_buffer_size_alignment,
+        );
+
+        let mut buffer = video_session_resources.decode_buffer_pool.buffer()?;
+        buffer.upload_data(
+            &decode_information.rbsp_bytes,
+            size,
+            &video_session_resources.parameters.profile_info.profile_info,
+        )?;
+
+        // decode
+        // IDR - remove all reference picures
+        if is_idr {
+            video_session_resources
+                .decoding_images
+                .reset_all_allocations();
+
+            self.reference_id_to_dpb_slot_index = Default::default();
+        }
+
+        // begin video coding
+        let reference_slots = video_session_resources
+            .decoding_images
+            .reference_slot_info();
+
+        let begin_info = vk::VideoBeginCodingInfoKHR::default()
+            .video_session(video_session_resources.video_session.session)
+            .video_session_parameters(video_session_resources.parameters_manager.parameters())
+            .reference_slots(&reference_slots);
+
+        let timestamp_query = self
+            .timestamp_queries
+            .as_ref()
+            .map(|queries| queries.acquire())
+            .transpose()?;
+
+        // IDR - issue the reset command to the video session
+        let cmd_buffer = Self::begin_decode_commands(
+            &self.decoding_device,
+            &mut self.tracker,
+            timestamp_query.as_deref(),
+            video_session_resources.decode_query_pool.as_deref(),
+            &video_session_resources.decoding_images,
+            &begin_info,
+            is_idr,
+        )?;
+
+        // allocate a new reference picture and fill out the forms to get it set up
+        let new_reference_slot_index = match second_field {
+            true => *self
+                .reference_id_to_dpb_slot_index
+                .get(&reference_id)
+                .ok_or(VulkanDecoderError::NonExistentReferenceRequested)?,
+            false => video_session_resources
+                .decoding_images
+                .allocate_reference_picture()?,
+        };
+
+        let new_reference_slot_std_reference_info = decode_information.picture_info.into();
+        let mut new_reference_slot_dpb_slot_info = vk::VideoDecodeH264DpbSlotInfoKHR::default()
+            .std_reference_info(&new_reference_slot_std_reference_info);
+
+        let new_reference_slot_video_picture_resource_info = video_session_resources
+            .decoding_images
+            .video_resource_info(new_reference_slot_index)
+            .unwrap();
+
+        let setup_reference_slot = vk::VideoReferenceSlotInfoKHR::default()
+            .picture_resource(new_reference_slot_video_picture_resource_info)
+            .slot_index(new_reference_slot_index as i32)
+            .push_next(&mut new_reference_slot_dpb_slot_info);
+
+        // prepare the reference list
+        let reference_slots = video_session_resources
+            .decoding_images
+            .reference_slot_info();
+
+        let references_std_ref_info = Self::prepare_references_std_ref_info(decode_information);
+
+        let mut references_dpb_slot_info =
+            Self::prepare_references_dpb_slot_info(&references_std_ref_info);
+
+        let pic_reference_slots = Self::prepare_reference_list_slot_info(
+            &self.reference_id_to_dpb_slot_index,
+            &reference_slots,
+            &mut references_dpb_slot_info,
+            decode_information,
+        )?;
+
+        // prepare the decode target picture
+        let std_picture_info = vk::native::StdVideoDecodeH264PictureInfo {
+            flags: vk::native::StdVideoDecodeH264PictureInfoFlags {
+                _bitfield_align_1: [],
+                __bindgen_padding_0: [0; 3],
+                _bitfield_1: vk::native::StdVideoDecodeH264PictureInfoFlags::new_bitfield_1(
+                    field.is_some().into(),
+                    is_idr.into(),
+                    is_idr.into(),
+                    (field == Some(Field::Bottom)).into(),
+                    is_reference.into(),
+                    second_field.into(),
+                ),
+            },
+            PicOrderCnt: decode_information.picture_info.PicOrderCnt_for_decoding,
+            seq_parameter_set_id: decode_information.sps_id,
+            pic_parameter_set_id: decode_information.pps_id,
+            frame_num: decode_information.header.frame_num,
+            idr_pic_id: decode_information
+                .header
+                .idr_pic_id
+                .map(|a| a as u16)
+                .unwrap_or(0),
+            reserved1: 0,
+            reserved2: 0,
+        };
+
+        let slice_offsets = decode_information
+            .slice_indices
+            .iter()
+            .map(|&x| x as u32)
+            .collect::<Vec<_>>();
 
-        let image = Arc::new(image);
-        let image_clone = image.clone();
+        let mut decode_h264_picture_info = vk::VideoDecodeH264PictureInfoKHR::default()
+            .std_picture_info(&std_picture_info)
+            .slice_offsets(&slice_offsets);
 
-        let hal_texture = unsafe {
-            hal_device.texture_from_raw(
-                **image,
-                &wgpu::hal::TextureDescriptor {
-                    label: Some("vulkan video output texture"),
-                    usage: wgpu::TextureUses::RESOURCE
-                        | wgpu::TextureUses::COPY_DST
-                        | wgpu::TextureUses::COPY_SRC,
-                    memory_flags: wgpu::hal::MemoryFlags::empty(),
-                    size: wgpu::Extent3d {
-                        width: copy_extent.width,
-                        height: copy_extent.height,
-                        depth_or_array_layers: copy_extent.depth,
-                    },
-                    dimension: wgpu::TextureDimension::D2,
-                    sample_count: 1,
-                    view_formats: Vec::new(),
-                    format: wgpu::TextureFormat::NV12,
-                    mip_level_count: 1,
-                },
-                Some(Box::new(move || {
-                    drop(image_clone);
-                })),
-                wgpu::hal::vulkan::TextureMemory::External,
-            )
-        };
+        let dst_picture_resource_info = &video_session_resources
+            .decoding_images
+            .target_picture_resource_info(new_reference_slot_index)
+            .unwrap();
 
-        let wgpu_texture = unsafe {
-            wgpu_device.create_texture_from_hal::<wgpu::hal::vulkan::Api>(
-                hal_texture,
-                &wgpu::TextureDescriptor {
-                    label: Some("vulkan video output texture"),
-                    usage: wgpu::TextureUsages::COPY_DST
-                        | wgpu::TextureUsages::TEXTURE_BINDING
-                        | wgpu::TextureUsages::COPY_SRC,
-                    size: wgpu::Extent3d {
-                        width: copy_extent.width,
-                        height: copy_extent.height,
-                        depth_or_array_layers: copy_extent.depth,
-                    },
-                    dimension: wgpu::TextureDimension::D2,
-                    sample_count: 1,
-                    view_formats: &[],
-                    format: wgpu::TextureFormat::NV12,
-                    mip_level_count: 1,
-                },
-                wgpu::TextureUses::COPY_DST,
-            )
-        };
+        // these 3 variables are for copying the result later
+        let (target_image, target_layer) = video_session_resources
+            .decoding_images
+            .target_info(new_reference_slot_index);
 
-        Ok(wgpu_texture)
-    }
+        // fill out the final struct and issue the command
+        let decode_info = vk::VideoDecodeInfoKHR::default()
+            .src_buffer(*buffer.buffer)
+            .src_buffer_offset(0)
+            .src_buffer_range(size)
+            .dst_picture_resource(*dst_picture_resource_info)
+            .setup_reference_slot(&setup_reference_slot)
+            .reference_slots(&pic_reference_slots)
+            .push_next(&mut decode_h264_picture_info);
 
-    fn download_output(
-        &mut self,
-        decode_output: &DecodeSubmissionImageInfo,
-    ) -> Result<Vec<u8>, VulkanDecoderError> {
-        let extent = vk::Extent3D {
-            width: decode_output.cropped_extent.width,
-            height: decode_output.cropped_extent.height,
-            depth: 1,
-        };
-        let (mut dst_buffer, wait_value) =
-            self.copy_image_to_buffer(&decode_output.image, extent, decode_output.layer)?;
+        let semaphore_wait_value = Self::end_decode_commands(
+            &self.decoding_device,
+            &mut self.tracker,
+            timestamp_query.as_deref(),
+            video_session_resources.decode_query_pool.as_deref(),
+            cmd_buffer,
+            &decode_info,
+        )?;
 
-        self.tracker.wait_for(wait_value, u64::MAX)?;
+        // after the decode save the new reference picture
+        self.reference_id_to_dpb_slot_index
+            .insert(reference_id, new_reference_slot_index);
 
-        let output = unsafe {
-            dst_buffer
-                .download_data_from_buffer(extent.width as usize * extent.height as usize * 3 / 2)?
+        let in_flight_resources = InFlightDecodeResources {
+            _video_session: video_session_resources.video_session.clone(),
+            _video_session_params: video_session_resources
+                .parameters_manager
+                .parameters
+                .clone(),
+            _dpb_image_with_view: video_session_resources
+                .decoding_images
+                .dpb_image_with_view(),
+            _dst_image_with_view: video_session_resources
+                .decoding_images
+                .dst_image_with_view(),
         };
 
-        Ok(output)
+        let chroma_subsampling = picture_format_chroma_subsampling(target_image.format);
+
+        Ok(DecodeSubmission {
+            decode_result: DecodeResult {
+                frame: DecodeSubmissionImageInfo {
+                    image: target_image,
+                    layer: target_layer as u32,
+                    cropped_extent,
+                    crop_offset,
+                    first_field,
+                },
+                metadata: DecodeResultMetadata {
+                    // a frame is ordered by the earlier of its fields
+                    pic_order_cnt: i32::min(
+                        decode_information.picture_info.PicOrderCnt_for_decoding[0],
+                        decode_information.picture_info.PicOrderCnt_for_decoding[1],
+                    ),
+                    max_num_reorder_frames: video_session_resources
+                        .parameters
+                        .max_num_reorder_frames,
+                    max_num_reorder_frames_signaled,
+                    is_idr,
+                    pts: decode_information.pts,
+                    dts: decode_information.dts,
+                    picture_type: decode_information.picture_type,
+                    color_space,
+                    color_range,
+                    color_info,
+                    gpu_decode_duration: None,
+                    corrupted: decode_information.corrupted,
+                    recovering: decode_information.recovering,
+                    sample_aspect_ratio,
+                    chroma_subsampling,
+                    width: cropped_extent.width,
+                    height: cropped_extent.height,
+                },
+            },
+            semaphore_wait_value,
+            decode_query_pool: video_session_resources.decode_query_pool.clone(),
+            timestamp_query,
+            in_flight_resources,
+            input_buffer: Some(buffer),
+            decoder: self,
+        })
     }
 
     fn prepare_references_std_ref_info(
@@ -803,93 +1796,6 @@ impl<'a> VulkanDecoder<'a> {
 
         Ok(pic_reference_slots)
     }
-
-    fn copy_image_to_buffer(
-        &mut self,
-        image: &Image,
-        dimensions: vk::Extent3D,
-        layer: u32,
-    ) -> Result<(Buffer, SemaphoreWaitValue), VulkanDecoderError> {
-        let mut cmd_buffer = self.tracker.command_buffer_pools.transfer.begin_buffer()?;
-
-        image.transition_layout_single_layer(
-            &mut cmd_buffer,
-            vk::PipelineStageFlags2::NONE..vk::PipelineStageFlags2::COPY,
-            vk::AccessFlags2::NONE..vk::AccessFlags2::TRANSFER_READ,
-            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
-            layer,
-        )?;
-
-        let y_plane_size = dimensions.width as u64 * dimensions.height as u64;
-
-        let dst_buffer = Buffer::new_transfer(
-            self.decoding_device.allocator.clone(),
-            y_plane_size * 3 / 2,
-            TransferDirection::GpuToMem,
-        )?;
-
-        let copy_info = [
-            vk::BufferImageCopy::default()
-                .image_subresource(vk::ImageSubresourceLayers {
-                    mip_level: 0,
-                    layer_count: 1,
-                    base_array_layer: layer,
-                    aspect_mask: vk::ImageAspectFlags::PLANE_0,
-                })
-                .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
-                .image_extent(vk::Extent3D {
-                    width: dimensions.width,
-                    height: dimensions.height,
-                    depth: 1,
-                })
-                .buffer_offset(0)
-                .buffer_row_length(0)
-                .buffer_image_height(0),
-            vk::BufferImageCopy::default()
-                .image_subresource(vk::ImageSubresourceLayers {
-                    mip_level: 0,
-                    layer_count: 1,
-                    base_array_layer: layer,
-                    aspect_mask: vk::ImageAspectFlags::PLANE_1,
-                })
-                .image_offset(vk::Offset3D { x: 0, y: 0, z: 0 })
-                .image_extent(vk::Extent3D {
-                    width: dimensions.width / 2,
-                    height: dimensions.height / 2,
-                    depth: 1,
-                })
-                .buffer_offset(y_plane_size)
-                .buffer_row_length(0)
-                .buffer_image_height(0),
-        ];
-
-        unsafe {
-            self.decoding_device
-                .vulkan_device
-                .device
-                .cmd_copy_image_to_buffer(
-                    cmd_buffer.buffer(),
-                    **image,
-                    vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
-                    *dst_buffer,
-                    &copy_info,
-                )
-        };
-
-        let wait_value = self
-            .decoding_device
-            .queues
-            .transfer
-            .submit_chain_semaphore(
-                cmd_buffer.end()?,
-                &mut self.tracker,
-                vk::PipelineStageFlags2::ALL_COMMANDS,
-                vk::PipelineStageFlags2::ALL_COMMANDS,
-                DecoderTrackerWaitState::DownloadImageToBuffer,
-            )?;
-
-        Ok((dst_buffer, wait_value))
-    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -919,6 +1825,56 @@ pub enum VulkanDecoderError {
     #[error("Monochrome video is not supported")]
     MonochromeChromaFormatUnsupported,
 
+    #[error("Frames with {bit_depth}-bit samples cannot be output as {pixel_format:?}")]
+    UnsupportedPixelFormat {
+        pixel_format: PixelFormat,
+        bit_depth: u8,
+    },
+
+    #[error("Decoders that output textures cannot output frames as {0:?}")]
+    UnsupportedTexturePixelFormat(PixelFormat),
+
+    #[error("The wgpu device does not support P010 textures required for 10-bit frames")]
+    P010TexturesUnsupported,
+
+    #[error("Deinterlacing is only supported for 8-bit 4:2:0 frames")]
+    DeinterlacingUnsupported,
+
+    #[error(
+        "The device cannot create decoded pictures with storage usage, which is needed for pixel format conversion and deinterlacing"
+    )]
+    OutputStorageUnsupported,
+
+    #[error("{0:?} frames can only be output as bytes in the pixel format they were decoded in")]
+    UnsupportedChromaSubsampling(ChromaSubsampling),
+
+    #[error("The device does not support exporting decoded frames")]
+    ExternalMemoryUnsupported,
+
+    #[error("The stream needs {required} decoded picture buffer slots, the limit is {limit}")]
+    DpbLimitExceeded { required: u32, limit: u32 },
+
+    #[error(
+        "Decoding images on the device would need {required} bytes, the memory budget is {budget}"
+    )]
+    MemoryBudgetExceeded { required: u64, budget: u64 },
+
+    #[cfg(feature = "wgpu")]
+    #[error("The frame pool has no texture available for a {width}x{height} {format:?} frame")]
+    FramePoolExhausted {
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+    },
+
+    #[cfg(feature = "wgpu")]
+    #[error("Texture from the frame pool cannot be used for the frame: {0}")]
+    InvalidFramePoolTexture(String),
+
+    #[cfg(feature = "wgpu")]
+    #[error("Waiting for the wgpu device failed: {0}")]
+    WgpuPollError(wgpu::PollError),
+
     #[error(transparent)]
     VulkanCommonError(#[from] VulkanCommonError),
 }
@@ -930,11 +1886,56 @@ impl From<VulkanDecoderError> for VideoDecoderError {
             VulkanDecoderError::InvalidInputData(err_msg) => {
                 VideoDecoderError::InvalidInputData(err_msg)
             }
+            VulkanDecoderError::UnsupportedPixelFormat {
+                pixel_format,
+                bit_depth,
+            } => VideoDecoderError::UnsupportedPixelFormat {
+                pixel_format,
+                bit_depth,
+            },
+            VulkanDecoderError::UnsupportedTexturePixelFormat(pixel_format) => {
+                VideoDecoderError::UnsupportedTexturePixelFormat(pixel_format)
+            }
+            VulkanDecoderError::ExternalMemoryUnsupported => {
+                VideoDecoderError::ExternalMemoryUnsupported
+            }
+            VulkanDecoderError::DpbLimitExceeded { required, limit } => {
+                VideoDecoderError::DpbLimitExceeded { required, limit }
+            }
+            VulkanDecoderError::MemoryBudgetExceeded { required, budget } => {
+                VideoDecoderError::MemoryBudgetExceeded { required, budget }
+            }
+            VulkanDecoderError::VulkanCommonError(
+                VulkanCommonError::ExternalSemaphoreUnsupported,
+            ) => VideoDecoderError::ExternalSemaphoreUnsupported,
+            #[cfg(feature = "wgpu")]
+            VulkanDecoderError::FramePoolExhausted {
+                width,
+                height,
+                format,
+            } => VideoDecoderError::FramePoolExhausted {
+                width,
+                height,
+                format,
+            },
+            #[cfg(feature = "wgpu")]
+            VulkanDecoderError::InvalidFramePoolTexture(err_msg) => {
+                VideoDecoderError::InvalidFramePoolTexture(err_msg)
+            }
+            #[cfg(feature = "wgpu")]
+            VulkanDecoderError::WgpuPollError(_) => Self::BackendError(VideoBackendError {
+                message: err.to_string(),
+                source: Box::new(err),
+            }),
             VulkanDecoderError::VkError(_)
             | VulkanDecoderError::NoSession
             | VulkanDecoderError::NonExistentReferenceRequested
             | VulkanDecoderError::DecodeOperationFailed(_)
             | VulkanDecoderError::MonochromeChromaFormatUnsupported
+            | VulkanDecoderError::P010TexturesUnsupported
+            | VulkanDecoderError::DeinterlacingUnsupported
+            | VulkanDecoderError::OutputStorageUnsupported
+            | VulkanDecoderError::UnsupportedChromaSubsampling(_)
             | VulkanDecoderError::VulkanCommonError(_) => Self::BackendError(VideoBackendError {
                 message: err.to_string(),
                 source: Box::new(err),
@@ -948,12 +1949,58 @@ pub(crate) struct ImageModifiers {
     pub(crate) create_flags: vk::ImageCreateFlags,
     pub(crate) usage_flags: vk::ImageUsageFlags,
     pub(crate) additional_queue_index: usize,
+    pub(crate) limits: DecodingImagesLimits,
+}
+
+/// Limits set in [`DecoderParameters`](crate::parameters::DecoderParameters), checked before
+/// the decoding images are allocated.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct DecodingImagesLimits {
+    pub(crate) max_dpb_slots: Option<u32>,
+    pub(crate) memory_budget: Option<MemoryBudget>,
+}
+
+impl DecodingImagesLimits {
+    /// Reserves the memory for the images in the tracker shared by all decoders on the device.
+    /// The reservation is released when the returned guard is dropped together with the images.
+    pub(crate) fn reserve<C: CodecCapabilities>(
+        &self,
+        decoding_device: &DecodingDevice<C>,
+        format: vk::Format,
+        extent: vk::Extent2D,
+        image_count: u32,
+        dpb_slots: u32,
+        replaced: Option<&DecodingMemoryReservation>,
+    ) -> Result<DecodingMemoryReservation, VulkanDecoderError> {
+        if let Some(limit) = self.max_dpb_slots.filter(|limit| dpb_slots > *limit) {
+            return Err(VulkanDecoderError::DpbLimitExceeded {
+                required: dpb_slots,
+                limit,
+            });
+        }
+
+        let bytes_per_sample = picture_format_bytes_per_sample(format);
+        let (chroma_width, chroma_height) =
+            picture_format_chroma_subsampling(format).chroma_size(extent.width, extent.height);
+        // every chroma sample position holds both a U and a V sample
+        let image_size = (extent.width as u64 * extent.height as u64
+            + 2 * chroma_width as u64 * chroma_height as u64)
+            * bytes_per_sample;
+        let required = image_size * image_count as u64;
+
+        decoding_device.vulkan_device.decoding_memory.reserve(
+            required,
+            self.memory_budget,
+            replaced,
+        )
+    }
 }
 
 pub(crate) enum DecoderTrackerWaitState {
     NewDecodingImagesLayoutTransition,
     Decode,
     DownloadImageToBuffer,
+    Deinterlace,
     #[cfg_attr(not(feature = "transcoder"), allow(dead_code))]
     ExternalProcessing,
 }
@@ -969,12 +2016,14 @@ impl TrackerKind for DecoderTrackerKind {
 pub(crate) struct DecoderCommandBufferPools {
     decode: CommandBufferPool,
     transfer: CommandBufferPool,
+    compute: CommandBufferPool,
 }
 
 impl CommandBufferPoolStorage for DecoderCommandBufferPools {
     fn mark_submitted_as_free(&mut self, last_waited_for: SemaphoreWaitValue) {
         self.decode.mark_submitted_as_free(last_waited_for);
         self.transfer.mark_submitted_as_free(last_waited_for);
+        self.compute.mark_submitted_as_free(last_waited_for);
     }
 }
 
@@ -984,6 +2033,10 @@ pub(crate) struct DecodeSubmissionImageInfo {
     pub(crate) image: Arc<Image>,
     pub(crate) layer: u32,
     pub(crate) cropped_extent: vk::Extent2D,
+    /// Position of the top-left corner of the cropped area in the decoded image.
+    pub(crate) crop_offset: vk::Offset2D,
+    /// Parity of the field which comes first in the frame. `None` for progressive streams.
+    pub(crate) first_field: Option<Field>,
 }
 
 /// Vulkan resources that must be kept alive while a decode submission is in flight.
@@ -994,25 +2047,36 @@ pub(crate) struct InFlightDecodeResources {
     _dst_image_with_view: Option<Arc<ImageWithView>>,
 }
 
-pub(crate) struct DecodeSubmission<'borrow, 'decoder> {
+pub(crate) struct DecodeSubmission<'borrow, 'decoder, C: DecodeCodec = H264Codec> {
     pub(crate) decode_result: DecodeResult<DecodeSubmissionImageInfo>,
-    pub(crate) decoder: &'borrow mut VulkanDecoder<'decoder>,
-    pub(crate) input_buffer: DecodeInputBuffer,
+    pub(crate) decoder: &'borrow mut VulkanDecoder<'decoder, C>,
+    /// `None` for frames which are output again without decoding them
+    pub(crate) input_buffer: Option<DecodeInputBuffer>,
     pub(crate) decode_query_pool: Option<Arc<DecodingQueryPool>>,
-    #[cfg_attr(not(feature = "transcoder"), allow(dead_code))]
+    /// Owned by the submission, so that other submissions in flight don't overwrite the result
+    pub(crate) timestamp_query: Option<TimestampQuery>,
     pub(crate) semaphore_wait_value: SemaphoreWaitValue,
     #[cfg_attr(not(feature = "transcoder"), allow(dead_code))]
     pub(crate) in_flight_resources: InFlightDecodeResources,
 }
 
-impl<'a, 'b> DecodeSubmission<'a, 'b> {
+impl<'a, 'b, C: DecodeCodec> DecodeSubmission<'a, 'b, C> {
     fn download_output(self) -> Result<DecodeResult<RawFrameData>, VulkanDecoderError> {
-        let raw_frame_data = self.decoder.download_output(&self.decode_result.frame)?;
-        let frame = RawFrameData {
-            frame: raw_frame_data,
-            width: self.decode_result.frame.cropped_extent.width,
-            height: self.decode_result.frame.cropped_extent.height,
-        };
+        let frame = self.decoder.download_output(
+            &self.decode_result.frame,
+            self.decode_result.metadata.color_space,
+            self.decode_result.metadata.color_range,
+        )?;
+
+        self.finish(frame)
+    }
+
+    fn export_output(self) -> Result<DecodeResult<ExternalMemoryFrame>, VulkanDecoderError> {
+        let frame = self.decoder.export_output(
+            &self.decode_result.frame,
+            self.decode_result.metadata.color_space,
+            self.decode_result.metadata.color_range,
+        )?;
 
         self.finish(frame)
     }
@@ -1021,24 +2085,45 @@ impl<'a, 'b> DecodeSubmission<'a, 'b> {
     fn output_to_wgpu_texture(
         self,
         wgpu_device: &wgpu::Device,
+        frame_pool: Option<&mut WgpuFramePool>,
     ) -> Result<DecodeResult<wgpu::Texture>, VulkanDecoderError> {
-        let wgpu_texture = self
-            .decoder
-            .output_to_wgpu_texture(wgpu_device, &self.decode_result.frame)?;
+        let wgpu_texture = self.decoder.output_to_wgpu_texture(
+            wgpu_device,
+            frame_pool,
+            &self.decode_result.frame,
+            self.decode_result.metadata.color_space,
+            self.decode_result.metadata.color_range,
+        )?;
 
         self.finish(wgpu_texture)
     }
 
+    /// Finishes a submission of a frame which is not output, e.g. an AV1 frame with
+    /// `show_frame` unset.
+    fn discard(self) -> Result<(), VulkanDecoderError> {
+        self.decoder
+            .tracker
+            .wait_for(self.semaphore_wait_value, u64::MAX)?;
+        self.finish(()).map(|_| ())
+    }
+
     fn finish<T>(self, output: T) -> Result<DecodeResult<T>, VulkanDecoderError> {
-        self.input_buffer.release_to_pool();
+        if let Some(input_buffer) = self.input_buffer {
+            input_buffer.release_to_pool();
+        }
 
         if let Some(query_pool) = self.decode_query_pool {
             query_pool.check_results_blocking()?;
         }
 
+        let mut metadata = self.decode_result.metadata;
+        if let Some(query) = self.timestamp_query {
+            metadata.gpu_decode_duration = Some(query.get_duration_blocking()?);
+        }
+
         Ok(DecodeResult {
             frame: output,
-            metadata: self.decode_result.metadata,
+            metadata,
         })
     }
 }
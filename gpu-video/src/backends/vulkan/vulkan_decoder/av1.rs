@@ -0,0 +1,676 @@
+use std::sync::Arc;
+
+use ash::vk;
+
+use crate::{
+    RawFrameData,
+    backends::vulkan::{codec::av1::Av1Codec, wrappers::SemaphoreWaitValue},
+    decoders::{VideoDecoderBackend, VideoDecoderError},
+    device::{ChromaSubsampling, ColorInfo, ColorRange, ColorSpace, PictureType},
+    frame_sorter::{DecodeResult, DecodeResultMetadata},
+    parser::{
+        av1::{
+            FrameHeader, FrameType, SequenceHeader,
+            decoder_instructions::DecoderInstruction,
+            reference_manager::{DecodeInformation, ReferenceFrameInfo},
+        },
+        reference_manager::ReferenceId,
+    },
+};
+
+use super::{
+    DecodeCodec, DecodeSubmission, DecodeSubmissionImageInfo, InFlightDecodeResources,
+    VulkanDecoder, VulkanDecoderError, session_resources::av1::Av1VideoSessionResources,
+};
+
+impl DecodeCodec for Av1Codec {
+    type DecoderInstruction = DecoderInstruction;
+    type SessionResources<'a> = Option<Av1VideoSessionResources<'a>>;
+
+    fn decode<'a, 'b>(
+        decoder: &'b mut VulkanDecoder<'a, Self>,
+        instruction: Self::DecoderInstruction,
+    ) -> Result<Option<DecodeSubmission<'b, 'a, Self>>, VulkanDecoderError> {
+        decoder.decode_instruction(instruction)
+    }
+}
+
+impl VideoDecoderBackend<DecoderInstruction> for VulkanDecoder<'_, Av1Codec> {
+    fn decode_to_bytes(
+        &mut self,
+        decoder_instructions: Vec<DecoderInstruction>,
+    ) -> Result<Vec<DecodeResult<RawFrameData>>, VideoDecoderError> {
+        VulkanDecoder::decode_to_bytes(self, decoder_instructions).map_err(Into::into)
+    }
+}
+
+#[cfg(feature = "wgpu")]
+impl crate::decoders::WgpuVideoDecoderBackend<DecoderInstruction> for VulkanDecoder<'_, Av1Codec> {
+    fn decode_to_wgpu_textures(
+        &mut self,
+        wgpu_device: &wgpu::Device,
+        frame_pool: Option<&mut crate::decoders::frame_pool::WgpuFramePool>,
+        decoder_instructions: Vec<DecoderInstruction>,
+    ) -> Result<Vec<DecodeResult<wgpu::Texture>>, VideoDecoderError> {
+        VulkanDecoder::decode_to_wgpu_textures(self, wgpu_device, frame_pool, decoder_instructions)
+            .map_err(Into::into)
+    }
+}
+
+impl<'a> VulkanDecoder<'a, Av1Codec> {
+    fn decode_instruction<'b>(
+        &'b mut self,
+        instruction: DecoderInstruction,
+    ) -> Result<Option<DecodeSubmission<'b, 'a, Av1Codec>>, VulkanDecoderError> {
+        match instruction {
+            DecoderInstruction::Decode {
+                decode_info,
+                reference_id,
+            } => return self.decode_frame(&decode_info, reference_id, false),
+
+            DecoderInstruction::KeyFrame {
+                decode_info,
+                reference_id,
+            } => return self.decode_frame(&decode_info, reference_id, true),
+
+            DecoderInstruction::ShowExisting {
+                reference_id,
+                header,
+                pts,
+                dts,
+                corrupted,
+            } => {
+                return self
+                    .show_existing(reference_id, &header, pts, dts, corrupted)
+                    .map(Some);
+            }
+
+            DecoderInstruction::Drop { reference_ids } => {
+                for reference_id in reference_ids {
+                    match self.reference_id_to_dpb_slot_index.remove(&reference_id) {
+                        Some(dpb_idx) => self
+                            .video_session_resources
+                            .as_mut()
+                            .map(|s| s.free_reference_picture(dpb_idx)),
+                        None => return Err(VulkanDecoderError::NonExistentReferenceRequested),
+                    };
+                }
+            }
+
+            DecoderInstruction::SequenceHeader(sequence_header) => {
+                self.process_sequence_header(sequence_header)?
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn process_sequence_header(
+        &mut self,
+        sequence_header: Arc<SequenceHeader>,
+    ) -> Result<(), VulkanDecoderError> {
+        match self.video_session_resources.as_mut() {
+            Some(session) => session.process_sequence_header(sequence_header, self.usage_info)?,
+            None => {
+                self.video_session_resources =
+                    Some(Av1VideoSessionResources::new_from_sequence_header(
+                        &self.decoding_device,
+                        self.tracker.command_buffer_pools.decode.begin_buffer()?,
+                        sequence_header,
+                        self.usage_info,
+                        &mut self.tracker,
+                        self.image_modifiers,
+                    )?)
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Frames with `show_frame` unset are only decoded into the DPB. They are output later by
+    /// a `show_existing_frame` header.
+    fn decode_frame<'b>(
+        &'b mut self,
+        decode_information: &DecodeInformation,
+        reference_id: ReferenceId,
+        is_keyframe: bool,
+    ) -> Result<Option<DecodeSubmission<'b, 'a, Av1Codec>>, VulkanDecoderError> {
+        let submission = self.do_decode(decode_information, reference_id, is_keyframe)?;
+        if !decode_information.header.show_frame {
+            submission.discard()?;
+            return Ok(None);
+        }
+
+        Ok(Some(submission))
+    }
+
+    /// `is_keyframe` is set for shown key frames. All previously decoded frames are removed from
+    /// the decoded pictures buffer before decoding them.
+    fn do_decode<'b>(
+        &'b mut self,
+        decode_information: &DecodeInformation,
+        reference_id: ReferenceId,
+        is_keyframe: bool,
+    ) -> Result<DecodeSubmission<'b, 'a, Av1Codec>, VulkanDecoderError> {
+        let video_session_resources = self
+            .video_session_resources
+            .as_mut()
+            .ok_or(VulkanDecoderError::NoSession)?;
+
+        let header = &decode_information.header;
+        let coded_extent = vk::Extent2D {
+            width: header.frame_size.upscaled_width,
+            height: header.frame_size.frame_height,
+        };
+        let color_space = ColorSpace::from(&header.sequence_header.color_config);
+        let color_range = ColorRange::from(&header.sequence_header.color_config);
+
+        if is_keyframe {
+            video_session_resources.ensure_session(
+                &self.decoding_device,
+                self.tracker.command_buffer_pools.decode.begin_buffer()?,
+                &mut self.tracker,
+                coded_extent,
+            )?;
+        }
+
+        // upload data to a buffer
+        let size = (decode_information.bitstream.len() as u64).next_multiple_of(
+            self.decoding_device
+                .profile_capabilities
+                .video_capabilities
+                .min_bitstream_buffer_size_alignment,
+        );
+
+        let mut buffer = video_session_resources.decode_buffer_pool.buffer()?;
+        buffer.upload_data(
+            &decode_information.bitstream,
+            size,
+            &video_session_resources.profile_info.profile_info,
+        )?;
+
+        // a new coded video sequence - remove all reference frames
+        if is_keyframe {
+            video_session_resources
+                .decoding_images
+                .reset_all_allocations();
+
+            self.reference_id_to_dpb_slot_index = Default::default();
+        }
+
+        // begin video coding
+        let reference_slots = video_session_resources
+            .decoding_images
+            .reference_slot_info();
+
+        let begin_info = vk::VideoBeginCodingInfoKHR::default()
+            .video_session(video_session_resources.video_session.session)
+            .video_session_parameters(video_session_resources.parameters.parameters)
+            .reference_slots(&reference_slots);
+
+        let timestamp_query = self
+            .timestamp_queries
+            .as_ref()
+            .map(|queries| queries.acquire())
+            .transpose()?;
+
+        let cmd_buffer = Self::begin_decode_commands(
+            &self.decoding_device,
+            &mut self.tracker,
+            timestamp_query.as_deref(),
+            video_session_resources.decode_query_pool.as_deref(),
+            &video_session_resources.decoding_images,
+            &begin_info,
+            is_keyframe,
+        )?;
+
+        // allocate a new reference picture and fill out the forms to get it set up
+        let new_reference_slot_index = video_session_resources
+            .decoding_images
+            .allocate_reference_picture()?;
+
+        let new_reference_slot_std_reference_info = std_reference_info(header);
+        let mut new_reference_slot_dpb_slot_info = vk::VideoDecodeAV1DpbSlotInfoKHR::default()
+            .std_reference_info(&new_reference_slot_std_reference_info);
+
+        let new_reference_slot_video_picture_resource_info = video_session_resources
+            .decoding_images
+            .video_resource_info(new_reference_slot_index)
+            .unwrap();
+
+        let setup_reference_slot = vk::VideoReferenceSlotInfoKHR::default()
+            .picture_resource(new_reference_slot_video_picture_resource_info)
+            .slot_index(new_reference_slot_index as i32)
+            .push_next(&mut new_reference_slot_dpb_slot_info);
+
+        // prepare the reference list. A frame can be used as more than one reference, but it
+        // can appear in the list of reference slots only once.
+        let reference_slots = video_session_resources
+            .decoding_images
+            .reference_slot_info();
+
+        let mut reference_name_slot_indices = [-1; 7];
+        let mut references: Vec<(usize, &ReferenceFrameInfo)> = Vec::new();
+        for (name_slot_index, reference) in reference_name_slot_indices
+            .iter_mut()
+            .zip(&decode_information.references)
+        {
+            let Some(reference) = reference else {
+                continue;
+            };
+
+            let slot_index = *self
+                .reference_id_to_dpb_slot_index
+                .get(&reference.id)
+                .ok_or(VulkanDecoderError::NonExistentReferenceRequested)?;
+
+            *name_slot_index = slot_index as i32;
+            if references.iter().all(|(index, _)| *index != slot_index) {
+                references.push((slot_index, reference));
+            }
+        }
+
+        let references_std_ref_info = references
+            .iter()
+            .map(|(_, reference)| std_reference_info(&reference.header))
+            .collect::<Vec<_>>();
+
+        let mut references_dpb_slot_info = references_std_ref_info
+            .iter()
+            .map(|info| vk::VideoDecodeAV1DpbSlotInfoKHR::default().std_reference_info(info))
+            .collect::<Vec<_>>();
+
+        let mut pic_reference_slots = Vec::new();
+        for ((slot_index, _), dpb_slot_info) in
+            references.iter().zip(references_dpb_slot_info.iter_mut())
+        {
+            let reference = *reference_slots
+                .get(*slot_index)
+                .ok_or(VulkanDecoderError::NonExistentReferenceRequested)?;
+
+            if reference.slot_index < 0 || reference.p_picture_resource.is_null() {
+                return Err(VulkanDecoderError::NonExistentReferenceRequested);
+            }
+
+            pic_reference_slots.push(reference.push_next(dpb_slot_info));
+        }
+
+        // prepare the decode target picture
+        let std_picture_info_data = StdPictureInfoData::new(header);
+        let std_picture_info = std_picture_info_data.picture_info(header);
+
+        let mut decode_av1_picture_info = vk::VideoDecodeAV1PictureInfoKHR::default()
+            .std_picture_info(&std_picture_info)
+            .reference_name_slot_indices(reference_name_slot_indices)
+            .frame_header_offset(decode_information.frame_header_offset)
+            .tile_offsets(&decode_information.tile_offsets)
+            .tile_sizes(&decode_information.tile_sizes);
+
+        let dst_picture_resource_info = &video_session_resources
+            .decoding_images
+            .target_picture_resource_info(new_reference_slot_index)
+            .unwrap();
+
+        let (target_image, target_layer) = video_session_resources
+            .decoding_images
+            .target_info(new_reference_slot_index);
+
+        let decode_info = vk::VideoDecodeInfoKHR::default()
+            .src_buffer(*buffer.buffer)
+            .src_buffer_offset(0)
+            .src_buffer_range(size)
+            .dst_picture_resource(*dst_picture_resource_info)
+            .setup_reference_slot(&setup_reference_slot)
+            .reference_slots(&pic_reference_slots)
+            .push_next(&mut decode_av1_picture_info);
+
+        let semaphore_wait_value = Self::end_decode_commands(
+            &self.decoding_device,
+            &mut self.tracker,
+            timestamp_query.as_deref(),
+            video_session_resources.decode_query_pool.as_deref(),
+            cmd_buffer,
+            &decode_info,
+        )?;
+
+        // after the decode save the new reference picture
+        self.reference_id_to_dpb_slot_index
+            .insert(reference_id, new_reference_slot_index);
+
+        let in_flight_resources = in_flight_resources(video_session_resources);
+
+        Ok(DecodeSubmission {
+            decode_result: DecodeResult {
+                frame: DecodeSubmissionImageInfo {
+                    image: target_image,
+                    layer: target_layer as u32,
+                    cropped_extent: coded_extent,
+                    crop_offset: vk::Offset2D::default(),
+                    first_field: None,
+                },
+                metadata: frame_metadata(
+                    header,
+                    decode_information.pts,
+                    decode_information.dts,
+                    color_space,
+                    color_range,
+                    decode_information.corrupted,
+                ),
+            },
+            semaphore_wait_value,
+            decode_query_pool: video_session_resources.decode_query_pool.clone(),
+            timestamp_query,
+            in_flight_resources,
+            input_buffer: Some(buffer),
+            decoder: self,
+        })
+    }
+
+    /// Outputs a frame stored in the decoded pictures buffer (`show_existing_frame`). Nothing
+    /// is submitted to the decode queue.
+    fn show_existing<'b>(
+        &'b mut self,
+        reference_id: ReferenceId,
+        header: &FrameHeader,
+        pts: Option<u64>,
+        dts: Option<u64>,
+        corrupted: bool,
+    ) -> Result<DecodeSubmission<'b, 'a, Av1Codec>, VulkanDecoderError> {
+        let video_session_resources = self
+            .video_session_resources
+            .as_mut()
+            .ok_or(VulkanDecoderError::NoSession)?;
+
+        if video_session_resources.decoding_images.dst_image.is_some() {
+            return Err(VulkanDecoderError::InvalidInputData(
+                "show_existing_frame requires a GPU which outputs decoded frames to the DPB"
+                    .to_string(),
+            ));
+        }
+
+        let slot_index = *self
+            .reference_id_to_dpb_slot_index
+            .get(&reference_id)
+            .ok_or(VulkanDecoderError::NonExistentReferenceRequested)?;
+
+        let (image, layer) = video_session_resources
+            .decoding_images
+            .target_info(slot_index);
+
+        let color_space = ColorSpace::from(&header.sequence_header.color_config);
+        let color_range = ColorRange::from(&header.sequence_header.color_config);
+
+        let semaphore_wait_value = self
+            .tracker
+            .semaphore_tracker
+            .wait_for
+            .as_ref()
+            .map(|wait| wait.value)
+            .unwrap_or(SemaphoreWaitValue(0));
+
+        let in_flight_resources = in_flight_resources(video_session_resources);
+
+        Ok(DecodeSubmission {
+            decode_result: DecodeResult {
+                frame: DecodeSubmissionImageInfo {
+                    image,
+                    layer: layer as u32,
+                    cropped_extent: vk::Extent2D {
+                        width: header.frame_size.upscaled_width,
+                        height: header.frame_size.frame_height,
+                    },
+                    crop_offset: vk::Offset2D::default(),
+                    first_field: None,
+                },
+                metadata: frame_metadata(header, pts, dts, color_space, color_range, corrupted),
+            },
+            semaphore_wait_value,
+            decode_query_pool: None,
+            timestamp_query: None,
+            in_flight_resources,
+            input_buffer: None,
+            decoder: self,
+        })
+    }
+}
+
+fn in_flight_resources(
+    video_session_resources: &Av1VideoSessionResources,
+) -> InFlightDecodeResources {
+    InFlightDecodeResources {
+        _video_session: video_session_resources.video_session.clone(),
+        _video_session_params: video_session_resources.parameters.clone(),
+        _dpb_image_with_view: video_session_resources
+            .decoding_images
+            .dpb_image_with_view(),
+        _dst_image_with_view: video_session_resources
+            .decoding_images
+            .dst_image_with_view(),
+    }
+}
+
+/// AV1 frames are output in decode order, so the picture order count is only informational.
+/// AV1 has no bidirectional frames, inter frames are reported as P frames.
+fn frame_metadata(
+    header: &FrameHeader,
+    pts: Option<u64>,
+    dts: Option<u64>,
+    color_space: ColorSpace,
+    color_range: ColorRange,
+    corrupted: bool,
+) -> DecodeResultMetadata {
+    DecodeResultMetadata {
+        pic_order_cnt: header.order_hint as i32,
+        max_num_reorder_frames: 0,
+        max_num_reorder_frames_signaled: true,
+        is_idr: header.frame_type == FrameType::Key,
+        pts,
+        dts,
+        picture_type: match header.is_intra() {
+            true => PictureType::I,
+            false => PictureType::P,
+        },
+        color_space,
+        color_range,
+        chroma_subsampling: ChromaSubsampling::Yuv420,
+        color_info: ColorInfo::from(&header.sequence_header.color_config),
+        gpu_decode_duration: None,
+        corrupted,
+        recovering: false,
+        sample_aspect_ratio: None,
+        width: header.frame_size.upscaled_width,
+        height: header.frame_size.frame_height,
+    }
+}
+
+fn bitmask(values: impl IntoIterator<Item = bool>) -> u8 {
+    values
+        .into_iter()
+        .enumerate()
+        .fold(0, |mask, (i, value)| mask | (u8::from(value) << i))
+}
+
+fn std_reference_info(header: &FrameHeader) -> vk::native::StdVideoDecodeAV1ReferenceInfo {
+    vk::native::StdVideoDecodeAV1ReferenceInfo {
+        flags: vk::native::StdVideoDecodeAV1ReferenceInfoFlags {
+            _bitfield_align_1: [],
+            _bitfield_1: vk::native::StdVideoDecodeAV1ReferenceInfoFlags::new_bitfield_1(
+                header.disable_frame_end_update_cdf.into(),
+                header.segmentation.segmentation_enabled.into(),
+                0, // reserved
+            ),
+        },
+        frame_type: header.frame_type as u8,
+        RefFrameSignBias: bitmask(header.ref_frame_sign_bias),
+        OrderHint: header.order_hint,
+        SavedOrderHints: header.order_hints,
+    }
+}
+
+/// Structures referenced by [`vk::native::StdVideoDecodeAV1PictureInfo`]. The tile info points
+/// to the tile sizes stored in the frame header, so the header has to outlive this.
+struct StdPictureInfoData {
+    tile_info: vk::native::StdVideoAV1TileInfo,
+    quantization: vk::native::StdVideoAV1Quantization,
+    segmentation: vk::native::StdVideoAV1Segmentation,
+    loop_filter: vk::native::StdVideoAV1LoopFilter,
+    cdef: vk::native::StdVideoAV1CDEF,
+    loop_restoration: vk::native::StdVideoAV1LoopRestoration,
+    global_motion: vk::native::StdVideoAV1GlobalMotion,
+}
+
+impl StdPictureInfoData {
+    fn new(header: &FrameHeader) -> Self {
+        let tile_info = &header.tile_info;
+        let quantization = &header.quantization;
+        let segmentation = &header.segmentation;
+        let loop_filter = &header.loop_filter;
+        let cdef = &header.cdef;
+        let loop_restoration = &header.loop_restoration;
+        let global_motion = &header.global_motion;
+
+        Self {
+            tile_info: vk::native::StdVideoAV1TileInfo {
+                flags: vk::native::StdVideoAV1TileInfoFlags {
+                    _bitfield_align_1: [],
+                    _bitfield_1: vk::native::StdVideoAV1TileInfoFlags::new_bitfield_1(
+                        tile_info.uniform_tile_spacing_flag.into(),
+                        0, // reserved
+                    ),
+                },
+                TileCols: tile_info.tile_cols as u8,
+                TileRows: tile_info.tile_rows as u8,
+                context_update_tile_id: tile_info.context_update_tile_id,
+                tile_size_bytes_minus_1: tile_info.tile_size_bytes_minus_1,
+                reserved1: [0; 7],
+                pMiColStarts: tile_info.mi_col_starts.as_ptr(),
+                pMiRowStarts: tile_info.mi_row_starts.as_ptr(),
+                pWidthInSbsMinus1: tile_info.width_in_sbs_minus_1.as_ptr(),
+                pHeightInSbsMinus1: tile_info.height_in_sbs_minus_1.as_ptr(),
+            },
+            quantization: vk::native::StdVideoAV1Quantization {
+                flags: vk::native::StdVideoAV1QuantizationFlags {
+                    _bitfield_align_1: [],
+                    _bitfield_1: vk::native::StdVideoAV1QuantizationFlags::new_bitfield_1(
+                        quantization.using_qmatrix.into(),
+                        quantization.diff_uv_delta.into(),
+                        0, // reserved
+                    ),
+                },
+                base_q_idx: quantization.base_q_idx,
+                DeltaQYDc: quantization.delta_q_y_dc,
+                DeltaQUDc: quantization.delta_q_u_dc,
+                DeltaQUAc: quantization.delta_q_u_ac,
+                DeltaQVDc: quantization.delta_q_v_dc,
+                DeltaQVAc: quantization.delta_q_v_ac,
+                qm_y: quantization.qm_y,
+                qm_u: quantization.qm_u,
+                qm_v: quantization.qm_v,
+            },
+            segmentation: vk::native::StdVideoAV1Segmentation {
+                FeatureEnabled: segmentation.feature_enabled.map(bitmask),
+                FeatureData: segmentation.feature_data,
+            },
+            loop_filter: vk::native::StdVideoAV1LoopFilter {
+                flags: vk::native::StdVideoAV1LoopFilterFlags {
+                    _bitfield_align_1: [],
+                    _bitfield_1: vk::native::StdVideoAV1LoopFilterFlags::new_bitfield_1(
+                        loop_filter.loop_filter_delta_enabled.into(),
+                        loop_filter.loop_filter_delta_update.into(),
+                        0, // reserved
+                    ),
+                },
+                loop_filter_level: loop_filter.loop_filter_level,
+                loop_filter_sharpness: loop_filter.loop_filter_sharpness,
+                update_ref_delta: bitmask(loop_filter.update_ref_delta),
+                loop_filter_ref_deltas: loop_filter.loop_filter_ref_deltas,
+                update_mode_delta: bitmask(loop_filter.update_mode_delta),
+                loop_filter_mode_deltas: loop_filter.loop_filter_mode_deltas,
+            },
+            cdef: vk::native::StdVideoAV1CDEF {
+                cdef_damping_minus_3: cdef.cdef_damping_minus_3,
+                cdef_bits: cdef.cdef_bits,
+                cdef_y_pri_strength: cdef.cdef_y_pri_strength,
+                cdef_y_sec_strength: cdef.cdef_y_sec_strength,
+                cdef_uv_pri_strength: cdef.cdef_uv_pri_strength,
+                cdef_uv_sec_strength: cdef.cdef_uv_sec_strength,
+            },
+            loop_restoration: vk::native::StdVideoAV1LoopRestoration {
+                FrameRestorationType: loop_restoration.frame_restoration_type.map(u32::from),
+                LoopRestorationSize: loop_restoration.loop_restoration_size,
+            },
+            global_motion: vk::native::StdVideoAV1GlobalMotion {
+                GmType: global_motion.gm_type,
+                gm_params: global_motion.gm_params,
+            },
+        }
+    }
+
+    /// Film grain is not applied by the decoder, so `pFilmGrain` is always null.
+    fn picture_info(&self, header: &FrameHeader) -> vk::native::StdVideoDecodeAV1PictureInfo {
+        let frame_size = &header.frame_size;
+        let segmentation = &header.segmentation;
+        let loop_restoration = &header.loop_restoration;
+
+        vk::native::StdVideoDecodeAV1PictureInfo {
+            flags: vk::native::StdVideoDecodeAV1PictureInfoFlags {
+                _bitfield_align_1: [],
+                _bitfield_1: vk::native::StdVideoDecodeAV1PictureInfoFlags::new_bitfield_1(
+                    header.error_resilient_mode.into(),
+                    header.disable_cdf_update.into(),
+                    frame_size.use_superres.into(),
+                    frame_size.render_and_frame_size_different.into(),
+                    header.allow_screen_content_tools.into(),
+                    header.is_filter_switchable.into(),
+                    header.force_integer_mv.into(),
+                    header.frame_size_override_flag.into(),
+                    header.buffer_removal_time_present_flag.into(),
+                    header.allow_intrabc.into(),
+                    header.frame_refs_short_signaling.into(),
+                    header.allow_high_precision_mv.into(),
+                    header.is_motion_mode_switchable.into(),
+                    header.use_ref_frame_mvs.into(),
+                    header.disable_frame_end_update_cdf.into(),
+                    header.allow_warped_motion.into(),
+                    header.reduced_tx_set.into(),
+                    header.reference_select.into(),
+                    header.skip_mode_present.into(),
+                    header.delta_q_present.into(),
+                    header.delta_lf_present.into(),
+                    header.delta_lf_multi.into(),
+                    segmentation.segmentation_enabled.into(),
+                    segmentation.segmentation_update_map.into(),
+                    segmentation.segmentation_temporal_update.into(),
+                    segmentation.segmentation_update_data.into(),
+                    loop_restoration.uses_lr.into(),
+                    loop_restoration.uses_chroma_lr.into(),
+                    0, // apply_grain
+                    0, // reserved
+                ),
+            },
+            frame_type: header.frame_type as u32,
+            current_frame_id: header.current_frame_id,
+            OrderHint: header.order_hint,
+            primary_ref_frame: header.primary_ref_frame,
+            refresh_frame_flags: header.refresh_frame_flags,
+            reserved1: 0,
+            interpolation_filter: header.interpolation_filter.into(),
+            TxMode: header.tx_mode.into(),
+            delta_q_res: header.delta_q_res,
+            delta_lf_res: header.delta_lf_res,
+            SkipModeFrame: header.skip_mode_frame,
+            coded_denom: frame_size.coded_denom,
+            reserved2: [0; 3],
+            OrderHints: header.order_hints,
+            expectedFrameId: header.expected_frame_id,
+            pTileInfo: &self.tile_info,
+            pQuantization: &self.quantization,
+            pSegmentation: &self.segmentation,
+            pLoopFilter: &self.loop_filter,
+            pCDEF: &self.cdef,
+            pLoopRestoration: &self.loop_restoration,
+            pGlobalMotion: &self.global_motion,
+            pFilmGrain: std::ptr::null(),
+        }
+    }
+}
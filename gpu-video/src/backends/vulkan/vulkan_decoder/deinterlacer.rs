@@ -0,0 +1,305 @@
+use std::{io::Cursor, sync::Arc};
+
+use ash::vk;
+
+use crate::{
+    backends::vulkan::{
+        VulkanCommonError, VulkanDevice,
+        vulkan_decoder::{DecodeSubmissionImageInfo, DecoderTracker},
+        wrappers::{
+            ComputePipeline, DescriptorPool, DescriptorSet, DescriptorSetLayout, Image, ImageView,
+            OpenCommandBuffer, PipelineLayout, ShaderModule,
+        },
+    },
+    parameters::Deinterlacing,
+    parser::reference_manager::Field,
+};
+
+const WORKGROUP_SIZE: u32 = 16;
+
+/// Has to match `Immediates` in the shader.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct PushConstants {
+    width: u32,
+    height: u32,
+    mode: u32,
+    kept_field: u32,
+    interlaced: u32,
+}
+
+impl PushConstants {
+    fn to_bytes(self) -> Vec<u8> {
+        [
+            self.width,
+            self.height,
+            self.mode,
+            self.kept_field,
+            self.interlaced,
+        ]
+        .iter()
+        .flat_map(|value| value.to_ne_bytes())
+        .collect()
+    }
+}
+
+/// A deinterlaced frame with the resources that have to be kept alive until the
+/// deinterlacing is finished.
+pub(crate) struct DeinterlacedFrame {
+    pub(crate) frame: DecodeSubmissionImageInfo,
+    _views: [ImageView; 4],
+}
+
+/// Deinterlaces decoded NV12 images with a compute shader. The result is written into a new
+/// image, because the decoded image can still be used as a reference picture.
+///
+/// Frames of progressive streams are copied without changes, so that all output images can be
+/// used by the same queues.
+pub(crate) struct Deinterlacer {
+    mode: Deinterlacing,
+    pipeline: ComputePipeline,
+    // Only one frame is deinterlaced at a time, because the decoder waits for the
+    // deinterlacing to finish before outputting the frame, so a single descriptor set is enough.
+    descriptor_set: DescriptorSet,
+    device: Arc<VulkanDevice>,
+}
+
+impl Deinterlacer {
+    pub(crate) fn new(
+        device: Arc<VulkanDevice>,
+        mode: Deinterlacing,
+    ) -> Result<Self, VulkanCommonError> {
+        let pool_sizes = [vk::DescriptorPoolSize::default()
+            .ty(vk::DescriptorType::STORAGE_IMAGE)
+            .descriptor_count(4)];
+        let descriptor_pool = Arc::new(DescriptorPool::new(
+            device.device.clone(),
+            &vk::DescriptorPoolCreateInfo::default()
+                .max_sets(1)
+                .pool_sizes(&pool_sizes),
+        )?);
+
+        let bindings = [0, 1, 2, 3].map(|binding| {
+            vk::DescriptorSetLayoutBinding::default()
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+                .stage_flags(vk::ShaderStageFlags::COMPUTE)
+                .binding(binding)
+        });
+        let set_layout = Arc::new(DescriptorSetLayout::new(
+            device.device.clone(),
+            &vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings),
+        )?);
+
+        let descriptor_set = DescriptorSet::new(
+            descriptor_pool.clone(),
+            &vk::DescriptorSetAllocateInfo::default()
+                .descriptor_pool(descriptor_pool.pool)
+                .set_layouts(&[set_layout.set_layout]),
+        )?
+        .pop()
+        .unwrap();
+
+        let push_constants = [vk::PushConstantRange::default()
+            .size(std::mem::size_of::<PushConstants>() as u32)
+            .offset(0)
+            .stage_flags(vk::ShaderStageFlags::COMPUTE)];
+        let create_info = vk::PipelineLayoutCreateInfo::default()
+            .set_layouts(std::slice::from_ref(&set_layout.set_layout))
+            .push_constant_ranges(&push_constants);
+        let pipeline_layout = Arc::new(PipelineLayout::new(
+            device.device.clone(),
+            &create_info,
+            vec![set_layout.clone()],
+        )?);
+
+        const SHADER_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/deinterlacer.spv"));
+        let mut shader_bytes_cursor = Cursor::new(SHADER_SPV);
+        let compiled_shader = ash::util::read_spv(&mut shader_bytes_cursor).unwrap();
+
+        let shader_module = Arc::new(ShaderModule::new(
+            device.device.clone(),
+            &vk::ShaderModuleCreateInfo::default().code(&compiled_shader),
+        )?);
+
+        let shader = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .name(c"main")
+            .module(shader_module.module);
+        let create_info = vk::ComputePipelineCreateInfo::default()
+            .stage(shader)
+            .layout(pipeline_layout.layout);
+
+        let pipeline = ComputePipeline::new(
+            device.device.clone(),
+            create_info,
+            pipeline_layout,
+            shader_module,
+        )?;
+
+        Ok(Self {
+            mode,
+            pipeline,
+            descriptor_set,
+            device,
+        })
+    }
+
+    /// Records the deinterlacing into `buffer`. The returned frame has to be kept alive until
+    /// the command buffer finishes executing.
+    pub(crate) fn record(
+        &mut self,
+        buffer: &mut OpenCommandBuffer,
+        tracker: &DecoderTracker,
+        frame: &DecodeSubmissionImageInfo,
+    ) -> Result<DeinterlacedFrame, VulkanCommonError> {
+        // the cropped area is at the same position in the output image
+        let extent = vk::Extent2D {
+            width: frame.crop_offset.x as u32 + frame.cropped_extent.width,
+            height: frame.crop_offset.y as u32 + frame.cropped_extent.height,
+        };
+
+        let mut queue_indices = vec![
+            self.device.queues.compute.family_index as u32,
+            self.device.queues.transfer.family_index as u32,
+        ];
+        queue_indices.dedup();
+        let sharing_mode = match queue_indices.len() {
+            1 => vk::SharingMode::EXCLUSIVE,
+            _ => vk::SharingMode::CONCURRENT,
+        };
+
+        let create_info = vk::ImageCreateInfo::default()
+            .flags(vk::ImageCreateFlags::MUTABLE_FORMAT | vk::ImageCreateFlags::EXTENDED_USAGE)
+            .image_type(vk::ImageType::TYPE_2D)
+            .format(vk::Format::G8_B8R8_2PLANE_420_UNORM)
+            .extent(vk::Extent3D {
+                width: extent.width,
+                height: extent.height,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::TRANSFER_SRC)
+            .sharing_mode(sharing_mode)
+            .queue_family_indices(&queue_indices)
+            .initial_layout(vk::ImageLayout::UNDEFINED);
+
+        let output = Arc::new(Image::new(
+            self.device.allocator.clone(),
+            &create_info,
+            tracker.image_layout_tracker.clone(),
+        )?);
+
+        let views = [
+            frame.image.create_plane_view(
+                frame.layer,
+                vk::ImageAspectFlags::PLANE_0,
+                vk::ImageUsageFlags::STORAGE,
+            )?,
+            frame.image.create_plane_view(
+                frame.layer,
+                vk::ImageAspectFlags::PLANE_1,
+                vk::ImageUsageFlags::STORAGE,
+            )?,
+            output.create_plane_view(
+                0,
+                vk::ImageAspectFlags::PLANE_0,
+                vk::ImageUsageFlags::STORAGE,
+            )?,
+            output.create_plane_view(
+                0,
+                vk::ImageAspectFlags::PLANE_1,
+                vk::ImageUsageFlags::STORAGE,
+            )?,
+        ];
+
+        let image_infos = views.each_ref().map(|view| {
+            vk::DescriptorImageInfo::default()
+                .image_view(view.view)
+                .image_layout(vk::ImageLayout::GENERAL)
+        });
+
+        let writes = [0, 1, 2, 3].map(|binding| {
+            vk::WriteDescriptorSet::default()
+                .dst_set(self.descriptor_set.descriptor_set)
+                .dst_binding(binding as u32)
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+                .image_info(std::slice::from_ref(&image_infos[binding]))
+        });
+        unsafe { self.device.device.update_descriptor_sets(&writes, &[]) };
+
+        frame.image.transition_layout_single_layer(
+            buffer,
+            vk::PipelineStageFlags2::NONE..vk::PipelineStageFlags2::COMPUTE_SHADER,
+            vk::AccessFlags2::NONE..vk::AccessFlags2::SHADER_STORAGE_READ,
+            vk::ImageLayout::GENERAL,
+            frame.layer,
+        )?;
+
+        output.transition_layout_single_layer(
+            buffer,
+            vk::PipelineStageFlags2::NONE..vk::PipelineStageFlags2::COMPUTE_SHADER,
+            vk::AccessFlags2::NONE..vk::AccessFlags2::SHADER_STORAGE_WRITE,
+            vk::ImageLayout::GENERAL,
+            0,
+        )?;
+
+        let push_constants = PushConstants {
+            width: extent.width,
+            height: extent.height,
+            mode: match self.mode {
+                Deinterlacing::Bob | Deinterlacing::Off => 0,
+                Deinterlacing::Blend => 1,
+            },
+            kept_field: match frame.first_field {
+                Some(Field::Bottom) => 1,
+                Some(Field::Top) | None => 0,
+            },
+            interlaced: frame.first_field.is_some() as u32,
+        };
+
+        unsafe {
+            self.device.device.cmd_bind_pipeline(
+                buffer.buffer(),
+                vk::PipelineBindPoint::COMPUTE,
+                self.pipeline.pipeline,
+            );
+            self.device.device.cmd_bind_descriptor_sets(
+                buffer.buffer(),
+                vk::PipelineBindPoint::COMPUTE,
+                self.pipeline.layout.layout,
+                0,
+                &[self.descriptor_set.descriptor_set],
+                &[],
+            );
+            self.device.device.cmd_push_constants(
+                buffer.buffer(),
+                self.pipeline.layout.layout,
+                vk::ShaderStageFlags::COMPUTE,
+                0,
+                &push_constants.to_bytes(),
+            );
+            self.device.device.cmd_dispatch(
+                buffer.buffer(),
+                extent.width.div_ceil(WORKGROUP_SIZE),
+                extent.height.div_ceil(WORKGROUP_SIZE),
+                1,
+            );
+        }
+
+        Ok(DeinterlacedFrame {
+            frame: DecodeSubmissionImageInfo {
+                image: output,
+                layer: 0,
+                cropped_extent: frame.cropped_extent,
+                crop_offset: frame.crop_offset,
+                first_field: None,
+            },
+            _views: views,
+        })
+    }
+}
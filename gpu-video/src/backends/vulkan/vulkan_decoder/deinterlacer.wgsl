@@ -0,0 +1,93 @@
+@group(0) @binding(0) var source_y: texture_storage_2d<r8unorm, read>;
+@group(0) @binding(1) var source_uv: texture_storage_2d<rg8unorm, read>;
+@group(0) @binding(2) var output_y: texture_storage_2d<r8unorm, write>;
+@group(0) @binding(3) var output_uv: texture_storage_2d<rg8unorm, write>;
+
+struct Immediates {
+  width: u32,
+  height: u32,
+  mode: u32,
+  kept_field: u32,
+  interlaced: u32,
+}
+
+var<immediate> imm: Immediates;
+
+// Has to match the mode set in `Deinterlacer::record`
+const MODE_BOB: u32 = 0;
+
+fn load_y(x: u32, y: i32, height: u32) -> vec4<f32> {
+  return textureLoad(source_y, vec2(x, u32(clamp(y, 0, i32(height) - 1))));
+}
+
+fn load_uv(x: u32, y: i32, height: u32) -> vec4<f32> {
+  return textureLoad(source_uv, vec2(x, u32(clamp(y, 0, i32(height) - 1))));
+}
+
+// Lines of the field that is not kept are interpolated from the neighbouring lines, which
+// belong to the kept field. Lines next to the edges of the plane are taken from the only
+// neighbour.
+fn bob_y(x: u32, y: u32, height: u32) -> vec4<f32> {
+  if y % 2 == imm.kept_field {
+    return load_y(x, i32(y), height);
+  }
+  let above = select(i32(y) - 1, i32(y) + 1, y == 0);
+  let below = select(i32(y) + 1, i32(y) - 1, y + 1 >= height);
+  return (load_y(x, above, height) + load_y(x, below, height)) / 2.0;
+}
+
+fn bob_uv(x: u32, y: u32, height: u32) -> vec4<f32> {
+  if y % 2 == imm.kept_field {
+    return load_uv(x, i32(y), height);
+  }
+  let above = select(i32(y) - 1, i32(y) + 1, y == 0);
+  let below = select(i32(y) + 1, i32(y) - 1, y + 1 >= height);
+  return (load_uv(x, above, height) + load_uv(x, below, height)) / 2.0;
+}
+
+fn blend_y(x: u32, y: u32, height: u32) -> vec4<f32> {
+  let row = i32(y);
+  return (load_y(x, row - 1, height) + 2.0 * load_y(x, row, height) + load_y(x, row + 1, height)) / 4.0;
+}
+
+fn blend_uv(x: u32, y: u32, height: u32) -> vec4<f32> {
+  let row = i32(y);
+  return (load_uv(x, row - 1, height) + 2.0 * load_uv(x, row, height) + load_uv(x, row + 1, height)) / 4.0;
+}
+
+// Every invocation writes one luma sample and, if it falls into the chroma plane, one chroma
+// sample.
+@compute
+@workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+  let x = id.x;
+  let y = id.y;
+  if x >= imm.width || y >= imm.height {
+    return;
+  }
+
+  let chroma_width = (imm.width + 1) / 2;
+  let chroma_height = (imm.height + 1) / 2;
+  let write_chroma = x < chroma_width && y < chroma_height;
+
+  if imm.interlaced == 0 {
+    textureStore(output_y, vec2(x, y), load_y(x, i32(y), imm.height));
+    if write_chroma {
+      textureStore(output_uv, vec2(x, y), load_uv(x, i32(y), chroma_height));
+    }
+    return;
+  }
+
+  if imm.mode == MODE_BOB {
+    textureStore(output_y, vec2(x, y), bob_y(x, y, imm.height));
+    if write_chroma {
+      textureStore(output_uv, vec2(x, y), bob_uv(x, y, chroma_height));
+    }
+    return;
+  }
+
+  textureStore(output_y, vec2(x, y), blend_y(x, y, imm.height));
+  if write_chroma {
+    textureStore(output_uv, vec2(x, y), blend_uv(x, y, chroma_height));
+  }
+}
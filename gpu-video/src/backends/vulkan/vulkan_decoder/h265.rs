@@ -0,0 +1,488 @@
+use ash::vk;
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    RawFrameData,
+    backends::vulkan::codec::h265::H265Codec,
+    decoders::{VideoDecoderBackend, VideoDecoderError},
+    device::{ChromaSubsampling, ColorInfo, ColorRange, ColorSpace, SampleAspectRatio},
+    frame_sorter::{DecodeResult, DecodeResultMetadata},
+    parser::{
+        h265::{
+            PicParameterSet, SeqParameterSet, VideoParameterSet,
+            decoder_instructions::DecoderInstruction,
+            reference_manager::{DecodeInformation, ReferencePictureInfo},
+        },
+        reference_manager::ReferenceId,
+    },
+};
+
+use super::{
+    DecodeCodec, DecodeSubmission, DecodeSubmissionImageInfo, InFlightDecodeResources,
+    VulkanDecoder, VulkanDecoderError,
+    session_resources::h265::{H265SessionResources, H265VideoSessionResources},
+};
+
+impl DecodeCodec for H265Codec {
+    type DecoderInstruction = DecoderInstruction;
+    type SessionResources<'a> = H265SessionResources<'a>;
+
+    fn decode<'a, 'b>(
+        decoder: &'b mut VulkanDecoder<'a, Self>,
+        instruction: Self::DecoderInstruction,
+    ) -> Result<Option<DecodeSubmission<'b, 'a, Self>>, VulkanDecoderError> {
+        decoder.decode_instruction(instruction)
+    }
+}
+
+impl VideoDecoderBackend<DecoderInstruction> for VulkanDecoder<'_, H265Codec> {
+    fn decode_to_bytes(
+        &mut self,
+        decoder_instructions: Vec<DecoderInstruction>,
+    ) -> Result<Vec<DecodeResult<RawFrameData>>, VideoDecoderError> {
+        VulkanDecoder::decode_to_bytes(self, decoder_instructions).map_err(Into::into)
+    }
+}
+
+#[cfg(feature = "wgpu")]
+impl crate::decoders::WgpuVideoDecoderBackend<DecoderInstruction> for VulkanDecoder<'_, H265Codec> {
+    fn decode_to_wgpu_textures(
+        &mut self,
+        wgpu_device: &wgpu::Device,
+        frame_pool: Option<&mut crate::decoders::frame_pool::WgpuFramePool>,
+        decoder_instructions: Vec<DecoderInstruction>,
+    ) -> Result<Vec<DecodeResult<wgpu::Texture>>, VideoDecoderError> {
+        VulkanDecoder::decode_to_wgpu_textures(self, wgpu_device, frame_pool, decoder_instructions)
+            .map_err(Into::into)
+    }
+}
+
+impl<'a> VulkanDecoder<'a, H265Codec> {
+    fn decode_instruction<'b>(
+        &'b mut self,
+        instruction: DecoderInstruction,
+    ) -> Result<Option<DecodeSubmission<'b, 'a, H265Codec>>, VulkanDecoderError> {
+        match instruction {
+            DecoderInstruction::Decode {
+                decode_info,
+                reference_id,
+            } => return self.do_decode(&decode_info, reference_id, false).map(Some),
+
+            DecoderInstruction::Irap {
+                decode_info,
+                reference_id,
+            } => return self.do_decode(&decode_info, reference_id, true).map(Some),
+
+            DecoderInstruction::Drop { reference_ids } => {
+                for reference_id in reference_ids {
+                    match self.reference_id_to_dpb_slot_index.remove(&reference_id) {
+                        Some(dpb_idx) => self
+                            .video_session_resources
+                            .session
+                            .as_mut()
+                            .map(|s| s.free_reference_picture(dpb_idx)),
+                        None => return Err(VulkanDecoderError::NonExistentReferenceRequested),
+                    };
+                }
+            }
+
+            DecoderInstruction::Vps(vps) => self.process_vps(vps)?,
+
+            DecoderInstruction::Sps(sps) => self.process_sps(sps)?,
+
+            DecoderInstruction::Pps(pps) => self.process_pps(pps)?,
+        }
+
+        Ok(None)
+    }
+
+    fn process_vps(&mut self, vps: VideoParameterSet) -> Result<(), VulkanDecoderError> {
+        if let Some(session) = self.video_session_resources.session.as_mut() {
+            session.process_vps(&vps)?;
+        }
+
+        self.video_session_resources
+            .vps
+            .insert(vps.vps_video_parameter_set_id, vps);
+
+        Ok(())
+    }
+
+    fn process_sps(&mut self, sps: SeqParameterSet) -> Result<(), VulkanDecoderError> {
+        match self.video_session_resources.session.as_mut() {
+            Some(session) => session.process_sps(sps, self.usage_info)?,
+            None => {
+                self.video_session_resources.session =
+                    Some(H265VideoSessionResources::new_from_sps(
+                        &self.decoding_device,
+                        self.tracker.command_buffer_pools.decode.begin_buffer()?,
+                        &self.video_session_resources.vps,
+                        sps,
+                        self.usage_info,
+                        &mut self.tracker,
+                        self.image_modifiers,
+                    )?)
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_pps(&mut self, pps: PicParameterSet) -> Result<(), VulkanDecoderError> {
+        self.video_session_resources
+            .session
+            .as_mut()
+            .ok_or(VulkanDecoderError::NoSession)?
+            .process_pps(&pps)
+    }
+
+    /// `starts_sequence` is set for IRAP pictures starting a new coded video sequence. All
+    /// previously decoded pictures are removed from the decoded pictures buffer before decoding
+    /// them.
+    fn do_decode<'b>(
+        &'b mut self,
+        decode_information: &DecodeInformation,
+        reference_id: ReferenceId,
+        starts_sequence: bool,
+    ) -> Result<DecodeSubmission<'b, 'a, H265Codec>, VulkanDecoderError> {
+        let video_session_resources = self
+            .video_session_resources
+            .session
+            .as_mut()
+            .ok_or(VulkanDecoderError::NoSession)?;
+
+        let sps = video_session_resources
+            .sps
+            .get(&decode_information.sps_id)
+            .ok_or(VulkanDecoderError::InvalidInputData(format!(
+                "Unknown SPS id {}",
+                decode_information.sps_id
+            )))?;
+
+        let coded_extent = vk::Extent2D {
+            width: sps.pic_width_in_luma_samples,
+            height: sps.pic_height_in_luma_samples,
+        };
+        let (cropped_extent, crop_offset) = conformance_window(sps, coded_extent)?;
+        let color_space = ColorSpace::from(sps);
+        let color_range = ColorRange::from(sps);
+        let color_info = ColorInfo::from(sps);
+        let sample_aspect_ratio = SampleAspectRatio::from_h265_sps(sps);
+
+        if starts_sequence {
+            video_session_resources.ensure_session(
+                &self.decoding_device,
+                self.tracker.command_buffer_pools.decode.begin_buffer()?,
+                &mut self.tracker,
+                coded_extent,
+            )?;
+        }
+
+        // upload data to a buffer
+        let size = (decode_information.rbsp_bytes.len() as u64).next_multiple_of(
+            self.decoding_device
+                .profile_capabilities
+                .video_capabilities
+                .min_bitstream_buffer_size_alignment,
+        );
+
+        let mut buffer = video_session_resources.decode_buffer_pool.buffer()?;
+        buffer.upload_data(
+            &decode_information.rbsp_bytes,
+            size,
+            &video_session_resources.profile_info.profile_info,
+        )?;
+
+        // a new coded video sequence - remove all reference pictures
+        if starts_sequence {
+            video_session_resources
+                .decoding_images
+                .reset_all_allocations();
+
+            self.reference_id_to_dpb_slot_index = Default::default();
+        }
+
+        // begin video coding
+        let reference_slots = video_session_resources
+            .decoding_images
+            .reference_slot_info();
+
+        let begin_info = vk::VideoBeginCodingInfoKHR::default()
+            .video_session(video_session_resources.video_session.session)
+            .video_session_parameters(video_session_resources.parameters_manager.parameters())
+            .reference_slots(&reference_slots);
+
+        let timestamp_query = self
+            .timestamp_queries
+            .as_ref()
+            .map(|queries| queries.acquire())
+            .transpose()?;
+
+        let cmd_buffer = Self::begin_decode_commands(
+            &self.decoding_device,
+            &mut self.tracker,
+            timestamp_query.as_deref(),
+            video_session_resources.decode_query_pool.as_deref(),
+            &video_session_resources.decoding_images,
+            &begin_info,
+            starts_sequence,
+        )?;
+
+        // allocate a new reference picture and fill out the forms to get it set up
+        let new_reference_slot_index = video_session_resources
+            .decoding_images
+            .allocate_reference_picture()?;
+
+        let new_reference_slot_std_reference_info =
+            std_reference_info(decode_information.pic_order_cnt, false);
+        let mut new_reference_slot_dpb_slot_info = vk::VideoDecodeH265DpbSlotInfoKHR::default()
+            .std_reference_info(&new_reference_slot_std_reference_info);
+
+        let new_reference_slot_video_picture_resource_info = video_session_resources
+            .decoding_images
+            .video_resource_info(new_reference_slot_index)
+            .unwrap();
+
+        let setup_reference_slot = vk::VideoReferenceSlotInfoKHR::default()
+            .picture_resource(new_reference_slot_video_picture_resource_info)
+            .slot_index(new_reference_slot_index as i32)
+            .push_next(&mut new_reference_slot_dpb_slot_info);
+
+        // prepare the reference list
+        let reference_slots = video_session_resources
+            .decoding_images
+            .reference_slot_info();
+
+        let references = decode_information
+            .references()
+            .map(|reference| {
+                self.reference_id_to_dpb_slot_index
+                    .get(&reference.id)
+                    .map(|&slot_index| (slot_index, reference))
+                    .ok_or(VulkanDecoderError::NonExistentReferenceRequested)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let references_std_ref_info = references
+            .iter()
+            .map(|(_, reference)| std_reference_info(reference.pic_order_cnt, reference.long_term))
+            .collect::<Vec<_>>();
+
+        let mut references_dpb_slot_info = references_std_ref_info
+            .iter()
+            .map(|info| vk::VideoDecodeH265DpbSlotInfoKHR::default().std_reference_info(info))
+            .collect::<Vec<_>>();
+
+        let mut pic_reference_slots = Vec::new();
+        for ((slot_index, _), dpb_slot_info) in
+            references.iter().zip(references_dpb_slot_info.iter_mut())
+        {
+            let reference = *reference_slots
+                .get(*slot_index)
+                .ok_or(VulkanDecoderError::NonExistentReferenceRequested)?;
+
+            if reference.slot_index < 0 || reference.p_picture_resource.is_null() {
+                return Err(VulkanDecoderError::NonExistentReferenceRequested);
+            }
+
+            pic_reference_slots.push(reference.push_next(dpb_slot_info));
+        }
+
+        // prepare the decode target picture
+        let std_picture_info = vk::native::StdVideoDecodeH265PictureInfo {
+            flags: vk::native::StdVideoDecodeH265PictureInfoFlags {
+                _bitfield_align_1: [],
+                __bindgen_padding_0: [0; 3],
+                _bitfield_1: vk::native::StdVideoDecodeH265PictureInfoFlags::new_bitfield_1(
+                    decode_information.is_irap.into(),
+                    decode_information.is_idr.into(),
+                    // every decoded picture is kept in the DPB until a later RPS drops it
+                    1,
+                    decode_information.short_term_ref_pic_set_sps_flag.into(),
+                ),
+            },
+            sps_video_parameter_set_id: decode_information.vps_id,
+            pps_seq_parameter_set_id: decode_information.sps_id,
+            pps_pic_parameter_set_id: decode_information.pps_id,
+            NumDeltaPocsOfRefRpsIdx: decode_information.num_delta_pocs_of_ref_rps_idx,
+            PicOrderCntVal: decode_information.pic_order_cnt,
+            NumBitsForSTRefPicSetInSlice: decode_information.num_bits_for_st_ref_pic_set_in_slice,
+            reserved: 0,
+            RefPicSetStCurrBefore: dpb_slot_indices(
+                &self.reference_id_to_dpb_slot_index,
+                &decode_information.ref_pic_set_st_curr_before,
+            )?,
+            RefPicSetStCurrAfter: dpb_slot_indices(
+                &self.reference_id_to_dpb_slot_index,
+                &decode_information.ref_pic_set_st_curr_after,
+            )?,
+            RefPicSetLtCurr: dpb_slot_indices(
+                &self.reference_id_to_dpb_slot_index,
+                &decode_information.ref_pic_set_lt_curr,
+            )?,
+        };
+
+        let slice_segment_offsets = decode_information
+            .slice_indices
+            .iter()
+            .map(|&x| x as u32)
+            .collect::<Vec<_>>();
+
+        let mut decode_h265_picture_info = vk::VideoDecodeH265PictureInfoKHR::default()
+            .std_picture_info(&std_picture_info)
+            .slice_segment_offsets(&slice_segment_offsets);
+
+        let dst_picture_resource_info = &video_session_resources
+            .decoding_images
+            .target_picture_resource_info(new_reference_slot_index)
+            .unwrap();
+
+        let (target_image, target_layer) = video_session_resources
+            .decoding_images
+            .target_info(new_reference_slot_index);
+
+        let decode_info = vk::VideoDecodeInfoKHR::default()
+            .src_buffer(*buffer.buffer)
+            .src_buffer_offset(0)
+            .src_buffer_range(size)
+            .dst_picture_resource(*dst_picture_resource_info)
+            .setup_reference_slot(&setup_reference_slot)
+            .reference_slots(&pic_reference_slots)
+            .push_next(&mut decode_h265_picture_info);
+
+        let semaphore_wait_value = Self::end_decode_commands(
+            &self.decoding_device,
+            &mut self.tracker,
+            timestamp_query.as_deref(),
+            video_session_resources.decode_query_pool.as_deref(),
+            cmd_buffer,
+            &decode_info,
+        )?;
+
+        // after the decode save the new reference picture
+        self.reference_id_to_dpb_slot_index
+            .insert(reference_id, new_reference_slot_index);
+
+        let in_flight_resources = InFlightDecodeResources {
+            _video_session: video_session_resources.video_session.clone(),
+            _video_session_params: video_session_resources
+                .parameters_manager
+                .parameters
+                .clone(),
+            _dpb_image_with_view: video_session_resources
+                .decoding_images
+                .dpb_image_with_view(),
+            _dst_image_with_view: video_session_resources
+                .decoding_images
+                .dst_image_with_view(),
+        };
+
+        Ok(DecodeSubmission {
+            decode_result: DecodeResult {
+                frame: DecodeSubmissionImageInfo {
+                    image: target_image,
+                    layer: target_layer as u32,
+                    cropped_extent,
+                    crop_offset,
+                    first_field: None,
+                },
+                metadata: DecodeResultMetadata {
+                    pic_order_cnt: decode_information.pic_order_cnt,
+                    max_num_reorder_frames: video_session_resources
+                        .parameters
+                        .max_num_reorder_frames,
+                    max_num_reorder_frames_signaled: true,
+                    // the picture order count is reset only at the start of a new sequence
+                    is_idr: starts_sequence,
+                    pts: decode_information.pts,
+                    dts: decode_information.dts,
+                    picture_type: decode_information.picture_type,
+                    color_space,
+                    color_range,
+                    chroma_subsampling: ChromaSubsampling::Yuv420,
+                    color_info,
+                    gpu_decode_duration: None,
+                    corrupted: decode_information.corrupted,
+                    recovering: false,
+                    sample_aspect_ratio,
+                    width: cropped_extent.width,
+                    height: cropped_extent.height,
+                },
+            },
+            semaphore_wait_value,
+            decode_query_pool: video_session_resources.decode_query_pool.clone(),
+            timestamp_query,
+            in_flight_resources,
+            input_buffer: Some(buffer),
+            decoder: self,
+        })
+    }
+}
+
+fn std_reference_info(
+    pic_order_cnt: i32,
+    long_term: bool,
+) -> vk::native::StdVideoDecodeH265ReferenceInfo {
+    vk::native::StdVideoDecodeH265ReferenceInfo {
+        flags: vk::native::StdVideoDecodeH265ReferenceInfoFlags {
+            _bitfield_align_1: [],
+            __bindgen_padding_0: [0; 3],
+            _bitfield_1: vk::native::StdVideoDecodeH265ReferenceInfoFlags::new_bitfield_1(
+                long_term.into(),
+                0,
+            ),
+        },
+        PicOrderCntVal: pic_order_cnt,
+    }
+}
+
+/// Maps the pictures of a reference picture set to DPB slot indices. Unused entries are set to
+/// `0xFF`, as required by the Vulkan spec.
+fn dpb_slot_indices(
+    reference_id_to_dpb_slot_index: &FxHashMap<ReferenceId, usize>,
+    references: &[ReferencePictureInfo],
+) -> Result<[u8; 8], VulkanDecoderError> {
+    if references.len() > 8 {
+        return Err(VulkanDecoderError::InvalidInputData(format!(
+            "reference picture set has {} entries, at most 8 are allowed",
+            references.len()
+        )));
+    }
+
+    let mut indices = [0xFF; 8];
+    for (index, reference) in indices.iter_mut().zip(references) {
+        *index = *reference_id_to_dpb_slot_index
+            .get(&reference.id)
+            .ok_or(VulkanDecoderError::NonExistentReferenceRequested)? as u8;
+    }
+
+    Ok(indices)
+}
+
+/// Returns the size and the position of the top-left corner of the conformance cropping window.
+fn conformance_window(
+    sps: &SeqParameterSet,
+    coded_extent: vk::Extent2D,
+) -> Result<(vk::Extent2D, vk::Offset2D), VulkanDecoderError> {
+    let (sub_width_c, sub_height_c) = sps.chroma_subsampling();
+    let [left, right, top, bottom] = sps.conformance_window.unwrap_or_default();
+
+    let width = coded_extent.width.checked_sub(sub_width_c * (left + right));
+    let height = coded_extent
+        .height
+        .checked_sub(sub_height_c * (top + bottom));
+    let (Some(width), Some(height)) = (width, height) else {
+        return Err(VulkanDecoderError::InvalidInputData(
+            "conformance window is larger than the picture".to_string(),
+        ));
+    };
+
+    Ok((
+        vk::Extent2D { width, height },
+        vk::Offset2D {
+            x: (sub_width_c * left) as i32,
+            y: (sub_height_c * top) as i32,
+        },
+    ))
+}
@@ -0,0 +1,287 @@
+use std::{io::Cursor, sync::Arc};
+
+use ash::vk;
+
+use crate::{
+    backends::vulkan::{
+        VulkanCommonError, VulkanDevice,
+        vulkan_decoder::DecodeSubmissionImageInfo,
+        wrappers::{
+            Buffer, ComputePipeline, DescriptorPool, DescriptorSet, DescriptorSetLayout, ImageView,
+            OpenCommandBuffer, PipelineLayout, ShaderModule,
+        },
+    },
+    device::{ColorRange, ColorSpace},
+    parameters::PixelFormat,
+};
+
+const WORKGROUP_SIZE: u32 = 256;
+const MAX_WORKGROUPS_X: u32 = 65535;
+
+/// Has to match `Immediates` in the shader.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct PushConstants {
+    width: u32,
+    height: u32,
+    crop_x: u32,
+    crop_y: u32,
+    pixel_format: u32,
+    color_matrix: u32,
+    full_range: u32,
+}
+
+impl PushConstants {
+    fn to_bytes(self) -> Vec<u8> {
+        [
+            self.width,
+            self.height,
+            self.crop_x,
+            self.crop_y,
+            self.pixel_format,
+            self.color_matrix,
+            self.full_range,
+        ]
+        .iter()
+        .flat_map(|value| value.to_ne_bytes())
+        .collect()
+    }
+}
+
+/// Resources that have to be kept alive until the conversion is finished.
+pub(crate) struct PixelFormatConversion {
+    pub(crate) output: Buffer,
+    pub(crate) output_size: usize,
+    _view_y: ImageView,
+    _view_uv: ImageView,
+}
+
+/// Converts decoded NV12 images into the requested [`PixelFormat`] with a compute shader.
+/// The result is written into a tightly packed host-visible buffer.
+pub(crate) struct PixelFormatConverter {
+    pixel_format: PixelFormat,
+    pipeline: ComputePipeline,
+    // Only one frame is converted at a time, because the decoder waits for every download
+    // or pending export to finish before starting the next one, so a single descriptor set is
+    // enough.
+    descriptor_set: DescriptorSet,
+    device: Arc<VulkanDevice>,
+}
+
+impl PixelFormatConverter {
+    pub(crate) fn new(
+        device: Arc<VulkanDevice>,
+        pixel_format: PixelFormat,
+    ) -> Result<Self, VulkanCommonError> {
+        let pool_sizes = [
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::STORAGE_IMAGE)
+                .descriptor_count(2),
+            vk::DescriptorPoolSize::default()
+                .ty(vk::DescriptorType::STORAGE_BUFFER)
+                .descriptor_count(1),
+        ];
+        let descriptor_pool = Arc::new(DescriptorPool::new(
+            device.device.clone(),
+            &vk::DescriptorPoolCreateInfo::default()
+                .max_sets(1)
+                .pool_sizes(&pool_sizes),
+        )?);
+
+        let bindings = [
+            vk::DescriptorSetLayoutBinding::default()
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+                .stage_flags(vk::ShaderStageFlags::COMPUTE)
+                .binding(0),
+            vk::DescriptorSetLayoutBinding::default()
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+                .stage_flags(vk::ShaderStageFlags::COMPUTE)
+                .binding(1),
+            vk::DescriptorSetLayoutBinding::default()
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .stage_flags(vk::ShaderStageFlags::COMPUTE)
+                .binding(2),
+        ];
+        let set_layout = Arc::new(DescriptorSetLayout::new(
+            device.device.clone(),
+            &vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings),
+        )?);
+
+        let descriptor_set = DescriptorSet::new(
+            descriptor_pool.clone(),
+            &vk::DescriptorSetAllocateInfo::default()
+                .descriptor_pool(descriptor_pool.pool)
+                .set_layouts(&[set_layout.set_layout]),
+        )?
+        .pop()
+        .unwrap();
+
+        let push_constants = [vk::PushConstantRange::default()
+            .size(std::mem::size_of::<PushConstants>() as u32)
+            .offset(0)
+            .stage_flags(vk::ShaderStageFlags::COMPUTE)];
+        let create_info = vk::PipelineLayoutCreateInfo::default()
+            .set_layouts(std::slice::from_ref(&set_layout.set_layout))
+            .push_constant_ranges(&push_constants);
+        let pipeline_layout = Arc::new(PipelineLayout::new(
+            device.device.clone(),
+            &create_info,
+            vec![set_layout.clone()],
+        )?);
+
+        const SHADER_SPV: &[u8] =
+            include_bytes!(concat!(env!("OUT_DIR"), "/pixel_format_converter.spv"));
+        let mut shader_bytes_cursor = Cursor::new(SHADER_SPV);
+        let compiled_shader = ash::util::read_spv(&mut shader_bytes_cursor).unwrap();
+
+        let shader_module = Arc::new(ShaderModule::new(
+            device.device.clone(),
+            &vk::ShaderModuleCreateInfo::default().code(&compiled_shader),
+        )?);
+
+        let shader = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .name(c"main")
+            .module(shader_module.module);
+        let create_info = vk::ComputePipelineCreateInfo::default()
+            .stage(shader)
+            .layout(pipeline_layout.layout);
+
+        let pipeline = ComputePipeline::new(
+            device.device.clone(),
+            create_info,
+            pipeline_layout,
+            shader_module,
+        )?;
+
+        Ok(Self {
+            pixel_format,
+            pipeline,
+            descriptor_set,
+            device,
+        })
+    }
+
+    /// Records the conversion into `buffer`. The returned resources have to be kept alive
+    /// until the command buffer finishes executing.
+    pub(crate) fn record(
+        &mut self,
+        buffer: &mut OpenCommandBuffer,
+        frame: &DecodeSubmissionImageInfo,
+        color_space: ColorSpace,
+        color_range: ColorRange,
+    ) -> Result<PixelFormatConversion, VulkanCommonError> {
+        let extent = frame.cropped_extent;
+        let output_size = self.pixel_format.frame_size(extent.width, extent.height);
+        // the output is written in whole words
+        let output = Buffer::new_storage_readback(
+            self.device.allocator.clone(),
+            output_size.next_multiple_of(4) as u64,
+        )?;
+
+        let view_y = frame.image.create_plane_view(
+            frame.layer,
+            vk::ImageAspectFlags::PLANE_0,
+            vk::ImageUsageFlags::STORAGE,
+        )?;
+        let view_uv = frame.image.create_plane_view(
+            frame.layer,
+            vk::ImageAspectFlags::PLANE_1,
+            vk::ImageUsageFlags::STORAGE,
+        )?;
+
+        let image_info_y = vk::DescriptorImageInfo::default()
+            .image_view(view_y.view)
+            .image_layout(vk::ImageLayout::GENERAL);
+        let image_info_uv = vk::DescriptorImageInfo::default()
+            .image_view(view_uv.view)
+            .image_layout(vk::ImageLayout::GENERAL);
+        let buffer_info = vk::DescriptorBufferInfo::default()
+            .buffer(*output)
+            .offset(0)
+            .range(vk::WHOLE_SIZE);
+
+        let writes = [
+            vk::WriteDescriptorSet::default()
+                .dst_set(self.descriptor_set.descriptor_set)
+                .dst_binding(0)
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+                .image_info(std::slice::from_ref(&image_info_y)),
+            vk::WriteDescriptorSet::default()
+                .dst_set(self.descriptor_set.descriptor_set)
+                .dst_binding(1)
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+                .image_info(std::slice::from_ref(&image_info_uv)),
+            vk::WriteDescriptorSet::default()
+                .dst_set(self.descriptor_set.descriptor_set)
+                .dst_binding(2)
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .buffer_info(std::slice::from_ref(&buffer_info)),
+        ];
+        unsafe { self.device.device.update_descriptor_sets(&writes, &[]) };
+
+        frame.image.transition_layout_single_layer(
+            buffer,
+            vk::PipelineStageFlags2::NONE..vk::PipelineStageFlags2::COMPUTE_SHADER,
+            vk::AccessFlags2::NONE..vk::AccessFlags2::SHADER_STORAGE_READ,
+            vk::ImageLayout::GENERAL,
+            frame.layer,
+        )?;
+
+        let push_constants = PushConstants {
+            width: extent.width,
+            height: extent.height,
+            crop_x: frame.crop_offset.x as u32,
+            crop_y: frame.crop_offset.y as u32,
+            pixel_format: self.pixel_format as u32,
+            color_matrix: match color_space {
+                ColorSpace::BT601Ntsc | ColorSpace::BT601Pal => 1,
+                ColorSpace::BT709 | ColorSpace::Unspecified => 0,
+            },
+            full_range: (color_range == ColorRange::Full) as u32,
+        };
+
+        let workgroups = (output_size.div_ceil(4) as u32).div_ceil(WORKGROUP_SIZE);
+        let workgroups_x = workgroups.clamp(1, MAX_WORKGROUPS_X);
+        let workgroups_y = workgroups.div_ceil(workgroups_x).max(1);
+
+        unsafe {
+            self.device.device.cmd_bind_pipeline(
+                buffer.buffer(),
+                vk::PipelineBindPoint::COMPUTE,
+                self.pipeline.pipeline,
+            );
+            self.device.device.cmd_bind_descriptor_sets(
+                buffer.buffer(),
+                vk::PipelineBindPoint::COMPUTE,
+                self.pipeline.layout.layout,
+                0,
+                &[self.descriptor_set.descriptor_set],
+                &[],
+            );
+            self.device.device.cmd_push_constants(
+                buffer.buffer(),
+                self.pipeline.layout.layout,
+                vk::ShaderStageFlags::COMPUTE,
+                0,
+                &push_constants.to_bytes(),
+            );
+            self.device
+                .device
+                .cmd_dispatch(buffer.buffer(), workgroups_x, workgroups_y, 1);
+        }
+
+        Ok(PixelFormatConversion {
+            output,
+            output_size,
+            _view_y: view_y,
+            _view_uv: view_uv,
+        })
+    }
+}
@@ -0,0 +1,153 @@
+@group(0) @binding(0) var source_y: texture_storage_2d<r8unorm, read>;
+@group(0) @binding(1) var source_uv: texture_storage_2d<rg8unorm, read>;
+@group(0) @binding(2) var<storage, read_write> output: array<u32>;
+
+struct Immediates {
+  width: u32,
+  height: u32,
+  crop_x: u32,
+  crop_y: u32,
+  pixel_format: u32,
+  color_matrix: u32,
+  full_range: u32,
+}
+
+var<immediate> imm: Immediates;
+
+// Has to match `PixelFormat`
+const FORMAT_NV12: u32 = 0;
+const FORMAT_RGBA: u32 = 2;
+const FORMAT_YUYV: u32 = 4;
+
+const MATRIX_BT709: u32 = 0;
+
+const WORKGROUP_SIZE: u32 = 256;
+
+fn to_byte(value: f32) -> u32 {
+  return u32(round(clamp(value, 0.0, 1.0) * 255.0));
+}
+
+fn load_y(x: u32, y: u32) -> f32 {
+  return textureLoad(source_y, vec2(imm.crop_x + x, imm.crop_y + y)).r;
+}
+
+fn load_uv(x: u32, y: u32) -> vec2<f32> {
+  return textureLoad(source_uv, vec2(imm.crop_x / 2 + x, imm.crop_y / 2 + y)).rg;
+}
+
+fn chroma_size() -> vec2<u32> {
+  return vec2((imm.width + 1) / 2, (imm.height + 1) / 2);
+}
+
+fn output_size() -> u32 {
+  let luma = imm.width * imm.height;
+  if imm.pixel_format == FORMAT_RGBA {
+    return 4 * luma;
+  }
+  let chroma = chroma_size();
+  if imm.pixel_format == FORMAT_YUYV {
+    return 4 * chroma.x * imm.height;
+  }
+  return luma + 2 * chroma.x * chroma.y;
+}
+
+// Value of a single byte of an NV12 or I420 frame.
+fn yuv_byte(index: u32) -> u32 {
+  let luma_size = imm.width * imm.height;
+  if index < luma_size {
+    return to_byte(load_y(index % imm.width, index / imm.width));
+  }
+
+  let chroma = chroma_size();
+  let chroma_index = index - luma_size;
+  if imm.pixel_format == FORMAT_NV12 {
+    let sample = chroma_index / 2;
+    let uv = load_uv(sample % chroma.x, sample / chroma.x);
+    return to_byte(uv[chroma_index % 2]);
+  }
+
+  let plane_size = chroma.x * chroma.y;
+  let sample = chroma_index % plane_size;
+  let uv = load_uv(sample % chroma.x, sample / chroma.x);
+  return to_byte(uv[chroma_index / plane_size]);
+}
+
+// Value of a single byte of a YUYV frame. The last pixel of an odd-width row is repeated.
+fn yuyv_byte(index: u32) -> u32 {
+  let row_pitch = 4 * chroma_size().x;
+  let y = index / row_pitch;
+  let pair = (index % row_pitch) / 4;
+  switch index % 4 {
+    case 0u: {
+      return to_byte(load_y(2 * pair, y));
+    }
+    case 2u: {
+      return to_byte(load_y(min(2 * pair + 1, imm.width - 1), y));
+    }
+    default: {
+      let uv = load_uv(pair, y / 2);
+      return to_byte(uv[(index % 4) / 2]);
+    }
+  }
+}
+
+fn rgba_pixel(index: u32) -> u32 {
+  let x = index % imm.width;
+  let y = index / imm.width;
+
+  var luma = load_y(x, y);
+  var chroma = load_uv(x / 2, y / 2) - vec2(128.0 / 255.0);
+  if imm.full_range == 0 {
+    luma = (luma - 16.0 / 255.0) * (255.0 / 219.0);
+    chroma = chroma * (255.0 / 224.0);
+  }
+
+  var rgb: vec3<f32>;
+  if imm.color_matrix == MATRIX_BT709 {
+    rgb = vec3(
+      luma + 1.5748 * chroma.y,
+      luma - 0.1873 * chroma.x - 0.4681 * chroma.y,
+      luma + 1.8556 * chroma.x,
+    );
+  } else {
+    rgb = vec3(
+      luma + 1.402 * chroma.y,
+      luma - 0.344136 * chroma.x - 0.714136 * chroma.y,
+      luma + 1.772 * chroma.x,
+    );
+  }
+
+  return pack4x8unorm(vec4(rgb, 1.0));
+}
+
+// Every invocation writes one word of the tightly packed output buffer.
+@compute
+@workgroup_size(256)
+fn main(
+  @builtin(global_invocation_id) id: vec3<u32>,
+  @builtin(num_workgroups) workgroups: vec3<u32>,
+) {
+  let word_index = id.y * workgroups.x * WORKGROUP_SIZE + id.x;
+  let size = output_size();
+  if word_index * 4 >= size {
+    return;
+  }
+
+  if imm.pixel_format == FORMAT_RGBA {
+    output[word_index] = rgba_pixel(word_index);
+    return;
+  }
+
+  var word: u32 = 0;
+  for (var i: u32 = 0; i < 4; i++) {
+    let byte_index = word_index * 4 + i;
+    if byte_index < size {
+      if imm.pixel_format == FORMAT_YUYV {
+        word |= yuyv_byte(byte_index) << (8 * i);
+      } else {
+        word |= yuv_byte(byte_index) << (8 * i);
+      }
+    }
+  }
+  output[word_index] = word;
+}
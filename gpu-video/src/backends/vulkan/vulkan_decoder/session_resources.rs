@@ -3,26 +3,34 @@ use std::{collections::HashMap, sync::Arc};
 use ash::vk;
 use h264_reader::nal::{
     pps::PicParameterSet,
-    sps::{Profile, SeqParameterSet},
+    sps::{PicOrderCntType, Profile, SeqParameterSet},
 };
 use images::DecodingImages;
 use parameters::{SessionParams, VideoSessionParametersManager};
 use rustc_hash::FxHashMap;
 
 use crate::backends::vulkan::{
-    codec::h264::parameters::{
-        H264DecodeProfileInfo, SeqParameterSetExt as _, h264_level_idc_to_max_dpb_mbs,
-        vk_to_h264_level_idc,
+    codec::{
+        CodecCapabilities,
+        h264::{
+            H264Codec,
+            parameters::{
+                H264DecodeProfileInfo, SeqParameterSetExt as _, h264_level_idc_to_max_dpb_mbs,
+                vk_to_h264_level_idc,
+            },
+        },
     },
     vulkan_decoder::{DecoderTracker, DecoderTrackerWaitState, ImageModifiers, VulkanDecoderError},
-    vulkan_device::DecodingDevice,
+    vulkan_device::{DecodingDevice, caps::NativeDecodeProfileCapabilities},
     wrappers::{DecodeInputBufferPool, DecodingQueryPool, OpenCommandBuffer, VideoSession},
 };
 
-mod images;
+pub(super) mod av1;
+pub(super) mod h265;
+pub(super) mod images;
 mod parameters;
 
-pub(super) struct VideoSessionResources<'a> {
+pub(crate) struct VideoSessionResources<'a> {
     pub(crate) video_session: Arc<VideoSession>,
     pub(crate) parameters: SessionParams<'a>,
     pub(crate) parameters_manager: VideoSessionParametersManager,
@@ -35,9 +43,21 @@ pub(super) struct VideoSessionResources<'a> {
     image_modifiers: ImageModifiers,
 }
 
+/// Whether the reordering limit follows from the stream, either from the bitstream restrictions
+/// in the VUI or from the picture order count type. Otherwise it is estimated from the DPB size.
+pub(crate) fn is_max_num_reorder_frames_signaled(sps: &SeqParameterSet) -> bool {
+    matches!(sps.pic_order_cnt, PicOrderCntType::TypeTwo)
+        || sps
+            .vui_parameters
+            .as_ref()
+            .is_some_and(|v| v.bitstream_restrictions.is_some())
+}
+
 fn calculate_max_num_reorder_frames(sps: &SeqParameterSet) -> Result<u64, VulkanDecoderError> {
-    let fallback_max_num_reorder_frames = if [44u8, 86, 100, 110, 122, 244]
-        .contains(&sps.profile_idc.into())
+    // with picture order count type 2 the output order is the same as the decoding order
+    let fallback_max_num_reorder_frames = if matches!(sps.pic_order_cnt, PicOrderCntType::TypeTwo) {
+        0
+    } else if [44u8, 86, 100, 110, 122, 244].contains(&sps.profile_idc.into())
         && sps.constraint_flags.flag3()
     {
         0
@@ -62,7 +82,7 @@ fn calculate_max_num_reorder_frames(sps: &SeqParameterSet) -> Result<u64, Vulkan
 
 impl<'a> VideoSessionResources<'a> {
     pub(crate) fn new_from_sps(
-        decoding_device: &DecodingDevice,
+        decoding_device: &DecodingDevice<H264Codec>,
         decode_buffer: OpenCommandBuffer,
         sps: SeqParameterSet,
         usage_info: vk::VideoDecodeUsageInfoKHR<'a>,
@@ -70,14 +90,15 @@ impl<'a> VideoSessionResources<'a> {
         image_modifiers: ImageModifiers,
     ) -> Result<Self, VulkanDecoderError> {
         let profile_info = Arc::new(H264DecodeProfileInfo::from_sps_decode(&sps, usage_info)?);
+        let profile_capabilities = decoding_device.profile_capabilities_for(
+            profile_info.chroma_subsampling(),
+            profile_info.bit_depth(),
+        )?;
+        decoding_device.check_interlaced_decoding(profile_info.is_interlaced())?;
 
         let level_idc = sps.level_idc;
-        let max_level_idc = vk_to_h264_level_idc(
-            decoding_device
-                .profile_capabilities
-                .codec_decode_capabilities
-                .max_level_idc,
-        )?;
+        let max_level_idc =
+            vk_to_h264_level_idc(profile_capabilities.codec_decode_capabilities.max_level_idc)?;
 
         if level_idc > max_level_idc {
             return Err(VulkanDecoderError::InvalidInputData(format!(
@@ -93,16 +114,14 @@ impl<'a> VideoSessionResources<'a> {
 
         let video_session = Arc::new(VideoSession::new(
             &decoding_device.vulkan_device,
-            &decoding_device.h264_decode_queues,
+            &decoding_device.decode_queues,
             &profile_info.profile_info.profile_info,
             max_coded_extent,
             max_dpb_slots,
             max_active_references,
             vk::VideoSessionCreateFlagsKHR::empty(),
-            &decoding_device
-                .profile_capabilities
-                .video_capabilities
-                .std_header_version,
+            &profile_capabilities.video_capabilities.std_header_version,
+            profile_capabilities.picture_format,
         )?);
 
         let mut parameters_manager =
@@ -110,19 +129,21 @@ impl<'a> VideoSessionResources<'a> {
 
         parameters_manager.put_sps(&sps)?;
 
-        let decoding_images = Self::new_decoding_images(
+        let decoding_images = new_decoding_images(
             decoding_device,
-            &profile_info,
+            profile_capabilities,
+            &profile_info.profile_info.profile_info,
             max_coded_extent,
             max_dpb_slots,
             decode_buffer,
             tracker,
             image_modifiers,
+            None,
         )?;
 
         let sps = HashMap::from_iter([(sps.id().id(), sps)]);
         let decode_query_pool = if decoding_device
-            .h264_decode_queues
+            .decode_queues
             .supports_result_status_queries()
         {
             Some(Arc::new(DecodingQueryPool::new(
@@ -133,8 +154,10 @@ impl<'a> VideoSessionResources<'a> {
             None
         };
 
-        let decode_buffer_pool =
-            DecodeInputBufferPool::new(decoding_device.allocator.clone(), profile_info.clone());
+        let decode_buffer_pool = DecodeInputBufferPool::new(
+            decoding_device.allocator.clone(),
+            profile_info.profile_info.clone(),
+        );
 
         let parameters = SessionParams {
             max_coded_extent,
@@ -199,7 +222,7 @@ impl<'a> VideoSessionResources<'a> {
 
     pub(crate) fn ensure_session(
         &mut self,
-        decoding_device: &DecodingDevice,
+        decoding_device: &DecodingDevice<H264Codec>,
         decode_buffer: OpenCommandBuffer,
         tracker: &mut DecoderTracker,
         picture_coded_extent: vk::Extent2D,
@@ -223,16 +246,17 @@ impl<'a> VideoSessionResources<'a> {
     fn recreate_session(
         &mut self,
         params: SessionParams<'a>,
-        decoding_device: &DecodingDevice,
+        decoding_device: &DecodingDevice<H264Codec>,
         decode_buffer: OpenCommandBuffer,
         tracker: &mut DecoderTracker,
     ) -> Result<(), VulkanDecoderError> {
-        let max_level_idc = vk_to_h264_level_idc(
-            decoding_device
-                .profile_capabilities
-                .codec_decode_capabilities
-                .max_level_idc,
+        let profile_capabilities = decoding_device.profile_capabilities_for(
+            params.profile_info.chroma_subsampling(),
+            params.profile_info.bit_depth(),
         )?;
+        decoding_device.check_interlaced_decoding(params.profile_info.is_interlaced())?;
+        let max_level_idc =
+            vk_to_h264_level_idc(profile_capabilities.codec_decode_capabilities.max_level_idc)?;
 
         if params.level_idc > max_level_idc {
             return Err(VulkanDecoderError::InvalidInputData(format!(
@@ -243,7 +267,7 @@ impl<'a> VideoSessionResources<'a> {
 
         if self.parameters.profile_info != params.profile_info {
             self.decode_query_pool = match decoding_device
-                .h264_decode_queues
+                .decode_queues
                 .supports_result_status_queries()
             {
                 true => Some(Arc::new(DecodingQueryPool::new(
@@ -254,35 +278,35 @@ impl<'a> VideoSessionResources<'a> {
             };
             self.decode_buffer_pool = DecodeInputBufferPool::new(
                 decoding_device.allocator.clone(),
-                params.profile_info.clone(),
+                params.profile_info.profile_info.clone(),
             );
         }
 
         self.video_session = Arc::new(VideoSession::new(
             &decoding_device.vulkan_device,
-            &decoding_device.h264_decode_queues,
+            &decoding_device.decode_queues,
             &params.profile_info.profile_info.profile_info,
             params.max_coded_extent,
             params.max_dpb_slots,
             params.max_active_references,
             vk::VideoSessionCreateFlagsKHR::empty(),
-            &decoding_device
-                .profile_capabilities
-                .video_capabilities
-                .std_header_version,
+            &profile_capabilities.video_capabilities.std_header_version,
+            profile_capabilities.picture_format,
         )?);
 
         self.parameters_manager
             .change_session(self.video_session.session)?;
 
-        self.decoding_images = Self::new_decoding_images(
+        self.decoding_images = new_decoding_images(
             decoding_device,
-            &params.profile_info,
+            profile_capabilities,
+            &params.profile_info.profile_info.profile_info,
             self.video_session.max_coded_extent,
             self.video_session.max_dpb_slots,
             decode_buffer,
             tracker,
             self.image_modifiers,
+            Some(&self.decoding_images),
         )?;
 
         self.parameters = params;
@@ -290,63 +314,77 @@ impl<'a> VideoSessionResources<'a> {
         Ok(())
     }
 
-    /// Creates a new buffer of reference images used for decoding.
-    ///
-    /// If you're replacing existing decoding images, make sure the old references won't be used,
-    /// e.g. do it right before decoding IDR. Otherwise it may result in error because the decoder
-    /// could try to use references which no longer exist if there's a non IDR frame after SPS.
-    fn new_decoding_images(
-        decoding_device: &DecodingDevice,
-        profile: &H264DecodeProfileInfo,
-        max_coded_extent: vk::Extent2D,
-        max_dpb_slots: u32,
-        mut decode_buffer: OpenCommandBuffer,
-        tracker: &mut DecoderTracker,
-        image_modifiers: ImageModifiers,
-    ) -> Result<DecodingImages<'a>, VulkanDecoderError> {
-        let mut dpb_format = decoding_device.profile_capabilities.dpb_format_properties;
-        // image modifiers are only applied to the output picture, which is the dst_image if it
-        // exists, dpb otherwise
-        if decoding_device
-            .profile_capabilities
-            .dst_format_properties
-            .is_none()
-        {
-            dpb_format.image_create_flags |= image_modifiers.create_flags;
-            dpb_format.image_usage_flags |= image_modifiers.usage_flags;
-        }
-        let dst_format = decoding_device
-            .profile_capabilities
-            .dst_format_properties
-            .map(|p| {
-                p.image_create_flags(p.image_create_flags | image_modifiers.create_flags)
-                    .image_usage_flags(p.image_usage_flags | image_modifiers.usage_flags)
-            });
-
-        let decoding_images = DecodingImages::new(
-            decoding_device,
-            &mut decode_buffer,
-            tracker.image_layout_tracker.clone(),
-            profile,
-            &dpb_format,
-            &dst_format,
-            max_coded_extent,
-            max_dpb_slots,
-            image_modifiers.additional_queue_index as u32,
-        )?;
-
-        decoding_device.h264_decode_queues.submit_chain_semaphore(
-            decode_buffer.end()?,
-            tracker,
-            vk::PipelineStageFlags2::ALL_COMMANDS,
-            vk::PipelineStageFlags2::ALL_COMMANDS,
-            DecoderTrackerWaitState::NewDecodingImagesLayoutTransition,
-        )?;
+    pub(crate) fn free_reference_picture(&mut self, i: usize) {
+        self.decoding_images.free_reference_picture(i);
+    }
+}
 
-        Ok(decoding_images)
+/// Creates a new buffer of reference images used for decoding.
+///
+/// If you're replacing existing decoding images, make sure the old references won't be used,
+/// e.g. do it right before decoding IDR. Otherwise it may result in error because the decoder
+/// could try to use references which no longer exist if there's a non IDR frame after SPS.
+#[allow(clippy::too_many_arguments)]
+fn new_decoding_images<'a, C: CodecCapabilities>(
+    decoding_device: &DecodingDevice<C>,
+    profile_capabilities: &NativeDecodeProfileCapabilities<C>,
+    profile_info: &vk::VideoProfileInfoKHR,
+    max_coded_extent: vk::Extent2D,
+    max_dpb_slots: u32,
+    mut decode_buffer: OpenCommandBuffer,
+    tracker: &mut DecoderTracker,
+    image_modifiers: ImageModifiers,
+    replaced: Option<&DecodingImages<'a>>,
+) -> Result<DecodingImages<'a>, VulkanDecoderError> {
+    if image_modifiers
+        .usage_flags
+        .contains(vk::ImageUsageFlags::STORAGE)
+        && !profile_capabilities.output_storage_supported
+    {
+        return Err(VulkanDecoderError::OutputStorageUnsupported);
     }
 
-    pub(crate) fn free_reference_picture(&mut self, i: usize) {
-        self.decoding_images.free_reference_picture(i);
+    let mut dpb_format = profile_capabilities.dpb_format_properties;
+    // image modifiers are only applied to the output picture, which is the dst_image if it
+    // exists, dpb otherwise
+    if profile_capabilities.dst_format_properties.is_none() {
+        dpb_format.image_create_flags |= image_modifiers.create_flags;
+        dpb_format.image_usage_flags |= image_modifiers.usage_flags;
     }
+    let dst_format = profile_capabilities.dst_format_properties.map(|p| {
+        p.image_create_flags(p.image_create_flags | image_modifiers.create_flags)
+            .image_usage_flags(p.image_usage_flags | image_modifiers.usage_flags)
+    });
+
+    let memory = image_modifiers.limits.reserve(
+        decoding_device,
+        dpb_format.format,
+        max_coded_extent,
+        max_dpb_slots + dst_format.is_some() as u32,
+        max_dpb_slots,
+        replaced.map(|images| &images.memory),
+    )?;
+
+    let decoding_images = DecodingImages::new(
+        decoding_device,
+        &mut decode_buffer,
+        tracker.image_layout_tracker.clone(),
+        profile_info,
+        &dpb_format,
+        &dst_format,
+        max_coded_extent,
+        max_dpb_slots,
+        image_modifiers.additional_queue_index as u32,
+        memory,
+    )?;
+
+    decoding_device.decode_queues.submit_chain_semaphore(
+        decode_buffer.end()?,
+        tracker,
+        vk::PipelineStageFlags2::ALL_COMMANDS,
+        vk::PipelineStageFlags2::ALL_COMMANDS,
+        DecoderTrackerWaitState::NewDecodingImagesLayoutTransition,
+    )?;
+
+    Ok(decoding_images)
 }
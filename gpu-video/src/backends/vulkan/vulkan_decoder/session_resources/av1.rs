@@ -0,0 +1,270 @@
+use std::sync::Arc;
+
+use ash::vk;
+
+use crate::{
+    backends::vulkan::{
+        codec::av1::{Av1Codec, Av1DecodeProfileInfo, VkAv1SequenceHeader},
+        vulkan_decoder::{DecoderTracker, ImageModifiers, VulkanDecoderError},
+        vulkan_device::DecodingDevice,
+        wrappers::{
+            DecodeInputBufferPool, DecodingQueryPool, OpenCommandBuffer, VideoSession,
+            VideoSessionParameters,
+        },
+    },
+    parser::av1::SequenceHeader,
+};
+
+use super::{images::DecodingImages, new_decoding_images};
+
+/// `seq_level_idx` of streams which don't conform to any level
+const SEQ_LEVEL_IDX_MAX_PARAMETERS: u8 = 31;
+
+/// All 8 reference frame slots and the current frame
+const MAX_DPB_SLOTS: u32 = 9;
+const MAX_ACTIVE_REFERENCES: u32 = 7;
+
+/// The video session is created when the first sequence header is received. A new sequence
+/// header is applied when the next key frame is decoded.
+pub(crate) struct Av1VideoSessionResources<'a> {
+    pub(crate) video_session: Arc<VideoSession>,
+    pub(crate) parameters: Arc<VideoSessionParameters>,
+    pub(crate) sequence_header: Arc<SequenceHeader>,
+    pub(crate) profile_info: Arc<Av1DecodeProfileInfo<'a>>,
+    pub(crate) decoding_images: DecodingImages<'a>,
+    pub(crate) decode_query_pool: Option<Arc<DecodingQueryPool>>,
+    pub(crate) decode_buffer_pool: DecodeInputBufferPool<'a>,
+    sequence_header_scheduled_for_reset: Option<Arc<SequenceHeader>>,
+    image_modifiers: ImageModifiers,
+}
+
+impl<'a> Av1VideoSessionResources<'a> {
+    pub(crate) fn new_from_sequence_header(
+        decoding_device: &DecodingDevice<Av1Codec>,
+        decode_buffer: OpenCommandBuffer,
+        sequence_header: Arc<SequenceHeader>,
+        usage_info: vk::VideoDecodeUsageInfoKHR<'a>,
+        tracker: &mut DecoderTracker,
+        image_modifiers: ImageModifiers,
+    ) -> Result<Self, VulkanDecoderError> {
+        let profile_info = Arc::new(Av1DecodeProfileInfo::from_sequence_header(
+            &sequence_header,
+            usage_info,
+        )?);
+        check_level(decoding_device, &sequence_header)?;
+
+        let max_coded_extent = max_coded_extent(&sequence_header);
+        let video_session = Arc::new(VideoSession::new(
+            &decoding_device.vulkan_device,
+            &decoding_device.decode_queues,
+            &profile_info.profile_info.profile_info,
+            max_coded_extent,
+            MAX_DPB_SLOTS,
+            MAX_ACTIVE_REFERENCES,
+            vk::VideoSessionCreateFlagsKHR::empty(),
+            &decoding_device
+                .profile_capabilities
+                .video_capabilities
+                .std_header_version,
+            decoding_device.profile_capabilities.picture_format,
+        )?);
+
+        let parameters = Arc::new(VideoSessionParameters::new::<Av1Codec>(
+            decoding_device.device.clone(),
+            video_session.session,
+            VkAv1SequenceHeader::from(&*sequence_header),
+            None,
+        )?);
+
+        let decoding_images = new_decoding_images(
+            decoding_device,
+            &decoding_device.profile_capabilities,
+            &profile_info.profile_info.profile_info,
+            max_coded_extent,
+            MAX_DPB_SLOTS,
+            decode_buffer,
+            tracker,
+            image_modifiers,
+            None,
+        )?;
+
+        let decode_query_pool = if decoding_device
+            .decode_queues
+            .supports_result_status_queries()
+        {
+            Some(Arc::new(DecodingQueryPool::new(
+                decoding_device.vulkan_device.device.clone(),
+                profile_info.profile_info.profile_info,
+            )?))
+        } else {
+            None
+        };
+
+        let decode_buffer_pool = DecodeInputBufferPool::new(
+            decoding_device.allocator.clone(),
+            profile_info.profile_info.clone(),
+        );
+
+        Ok(Self {
+            video_session,
+            parameters,
+            sequence_header,
+            profile_info,
+            decoding_images,
+            decode_query_pool,
+            decode_buffer_pool,
+            sequence_header_scheduled_for_reset: None,
+            image_modifiers,
+        })
+    }
+
+    pub(crate) fn process_sequence_header(
+        &mut self,
+        sequence_header: Arc<SequenceHeader>,
+        usage_info: vk::VideoDecodeUsageInfoKHR<'a>,
+    ) -> Result<(), VulkanDecoderError> {
+        let current = self
+            .sequence_header_scheduled_for_reset
+            .as_ref()
+            .unwrap_or(&self.sequence_header);
+        if **current == *sequence_header {
+            return Ok(());
+        }
+
+        // only the Main profile is supported, so the profile info stays the same for all streams.
+        // This only validates that the new sequence header can be decoded with it.
+        Av1DecodeProfileInfo::from_sequence_header(&sequence_header, usage_info)?;
+        self.sequence_header_scheduled_for_reset = Some(sequence_header);
+
+        Ok(())
+    }
+
+    /// Applies the sequence header received since the last key frame. Has to be called before
+    /// decoding a key frame.
+    pub(crate) fn ensure_session(
+        &mut self,
+        decoding_device: &DecodingDevice<Av1Codec>,
+        decode_buffer: OpenCommandBuffer,
+        tracker: &mut DecoderTracker,
+        picture_coded_extent: vk::Extent2D,
+    ) -> Result<(), VulkanDecoderError> {
+        if let Some(sequence_header) = self.sequence_header_scheduled_for_reset.take() {
+            self.apply_sequence_header(sequence_header, decoding_device, decode_buffer, tracker)?;
+        }
+
+        // frames can be smaller than the maximum size from the sequence header
+        self.decoding_images
+            .update_coded_extent(picture_coded_extent)?;
+
+        Ok(())
+    }
+
+    fn apply_sequence_header(
+        &mut self,
+        sequence_header: Arc<SequenceHeader>,
+        decoding_device: &DecodingDevice<Av1Codec>,
+        decode_buffer: OpenCommandBuffer,
+        tracker: &mut DecoderTracker,
+    ) -> Result<(), VulkanDecoderError> {
+        check_level(decoding_device, &sequence_header)?;
+
+        let new_max_coded_extent = max_coded_extent(&sequence_header);
+        let max_coded_extent = self.video_session.max_coded_extent;
+        if new_max_coded_extent.width > max_coded_extent.width
+            || new_max_coded_extent.height > max_coded_extent.height
+        {
+            self.recreate_session(
+                vk::Extent2D {
+                    width: u32::max(max_coded_extent.width, new_max_coded_extent.width),
+                    height: u32::max(max_coded_extent.height, new_max_coded_extent.height),
+                },
+                decoding_device,
+                decode_buffer,
+                tracker,
+            )?;
+        }
+
+        // decode submissions that are still in flight keep the previous parameters object alive
+        self.parameters = Arc::new(VideoSessionParameters::new::<Av1Codec>(
+            decoding_device.device.clone(),
+            self.video_session.session,
+            VkAv1SequenceHeader::from(&*sequence_header),
+            None,
+        )?);
+        self.sequence_header = sequence_header;
+
+        Ok(())
+    }
+
+    fn recreate_session(
+        &mut self,
+        max_coded_extent: vk::Extent2D,
+        decoding_device: &DecodingDevice<Av1Codec>,
+        decode_buffer: OpenCommandBuffer,
+        tracker: &mut DecoderTracker,
+    ) -> Result<(), VulkanDecoderError> {
+        self.video_session = Arc::new(VideoSession::new(
+            &decoding_device.vulkan_device,
+            &decoding_device.decode_queues,
+            &self.profile_info.profile_info.profile_info,
+            max_coded_extent,
+            MAX_DPB_SLOTS,
+            MAX_ACTIVE_REFERENCES,
+            vk::VideoSessionCreateFlagsKHR::empty(),
+            &decoding_device
+                .profile_capabilities
+                .video_capabilities
+                .std_header_version,
+            decoding_device.profile_capabilities.picture_format,
+        )?);
+
+        self.decoding_images = new_decoding_images(
+            decoding_device,
+            &decoding_device.profile_capabilities,
+            &self.profile_info.profile_info.profile_info,
+            self.video_session.max_coded_extent,
+            self.video_session.max_dpb_slots,
+            decode_buffer,
+            tracker,
+            self.image_modifiers,
+            Some(&self.decoding_images),
+        )?;
+
+        Ok(())
+    }
+
+    pub(crate) fn free_reference_picture(&mut self, i: usize) {
+        self.decoding_images.free_reference_picture(i);
+    }
+}
+
+fn max_coded_extent(sequence_header: &SequenceHeader) -> vk::Extent2D {
+    vk::Extent2D {
+        width: sequence_header.max_frame_width(),
+        height: sequence_header.max_frame_height(),
+    }
+}
+
+fn check_level(
+    decoding_device: &DecodingDevice<Av1Codec>,
+    sequence_header: &SequenceHeader,
+) -> Result<(), VulkanDecoderError> {
+    // `StdVideoAV1Level` values are equal to `seq_level_idx`
+    let max_level = decoding_device
+        .profile_capabilities
+        .codec_decode_capabilities
+        .max_level;
+    let seq_level_idx = sequence_header
+        .operating_points
+        .first()
+        .map(|op| op.seq_level_idx)
+        .unwrap_or(0);
+
+    if seq_level_idx != SEQ_LEVEL_IDX_MAX_PARAMETERS && seq_level_idx as u32 > max_level {
+        return Err(VulkanDecoderError::InvalidInputData(format!(
+            "stream has seq_level_idx = {seq_level_idx}, while the GPU can decode at most {max_level}"
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,409 @@
+use std::{collections::HashMap, sync::Arc};
+
+use ash::vk;
+use rustc_hash::FxHashMap;
+
+use crate::{
+    backends::vulkan::{
+        codec::h265::{
+            H265Codec, H265VkParameters,
+            parameters::{
+                H265DecodeProfileInfo, VkH265PictureParameterSet, VkH265SequenceParameterSet,
+                VkH265VideoParameterSet, vk_to_h265_level_idc,
+            },
+        },
+        vulkan_decoder::{DecoderTracker, ImageModifiers, VulkanDecoderError},
+        vulkan_device::DecodingDevice,
+        wrappers::{
+            DecodeInputBufferPool, DecodingQueryPool, Device, OpenCommandBuffer, VideoSession,
+            VideoSessionParameters,
+        },
+    },
+    parser::h265::{PicParameterSet, SeqParameterSet, VideoParameterSet},
+};
+
+use super::{images::DecodingImages, new_decoding_images};
+
+/// The video session is created when the first SPS is received. Video parameter sets received
+/// before that are kept, so that they can be added to the session parameters.
+#[derive(Default)]
+pub(crate) struct H265SessionResources<'a> {
+    pub(crate) vps: FxHashMap<u8, VideoParameterSet>,
+    pub(crate) session: Option<H265VideoSessionResources<'a>>,
+}
+
+pub(crate) struct H265VideoSessionResources<'a> {
+    pub(crate) video_session: Arc<VideoSession>,
+    pub(crate) parameters: H265SessionParams,
+    pub(crate) profile_info: Arc<H265DecodeProfileInfo<'a>>,
+    pub(crate) parameters_manager: H265VideoSessionParametersManager,
+    pub(crate) decoding_images: DecodingImages<'a>,
+    pub(crate) sps: FxHashMap<u8, SeqParameterSet>,
+    pub(crate) decode_query_pool: Option<Arc<DecodingQueryPool>>,
+    pub(crate) decode_buffer_pool: DecodeInputBufferPool<'a>,
+    parameters_scheduled_for_reset: Option<H265SessionParams>,
+    image_modifiers: ImageModifiers,
+}
+
+impl<'a> H265VideoSessionResources<'a> {
+    pub(crate) fn new_from_sps(
+        decoding_device: &DecodingDevice<H265Codec>,
+        decode_buffer: OpenCommandBuffer,
+        vps: &FxHashMap<u8, VideoParameterSet>,
+        sps: SeqParameterSet,
+        usage_info: vk::VideoDecodeUsageInfoKHR<'a>,
+        tracker: &mut DecoderTracker,
+        image_modifiers: ImageModifiers,
+    ) -> Result<Self, VulkanDecoderError> {
+        let profile_info = Arc::new(H265DecodeProfileInfo::from_sps_decode(&sps, usage_info)?);
+        let parameters = H265SessionParams::from_sps(&sps);
+        parameters.check_level(decoding_device)?;
+
+        let video_session = Arc::new(VideoSession::new(
+            &decoding_device.vulkan_device,
+            &decoding_device.decode_queues,
+            &profile_info.profile_info.profile_info,
+            parameters.max_coded_extent,
+            parameters.max_dpb_slots,
+            parameters.max_active_references,
+            vk::VideoSessionCreateFlagsKHR::empty(),
+            &decoding_device
+                .profile_capabilities
+                .video_capabilities
+                .std_header_version,
+            decoding_device.profile_capabilities.picture_format,
+        )?);
+
+        let mut parameters_manager =
+            H265VideoSessionParametersManager::new(decoding_device, video_session.session)?;
+        for vps in vps.values() {
+            parameters_manager.put_vps(vps)?;
+        }
+        parameters_manager.put_sps(&sps)?;
+
+        let decoding_images = new_decoding_images(
+            decoding_device,
+            &decoding_device.profile_capabilities,
+            &profile_info.profile_info.profile_info,
+            parameters.max_coded_extent,
+            parameters.max_dpb_slots,
+            decode_buffer,
+            tracker,
+            image_modifiers,
+            None,
+        )?;
+
+        let decode_query_pool = if decoding_device
+            .decode_queues
+            .supports_result_status_queries()
+        {
+            Some(Arc::new(DecodingQueryPool::new(
+                decoding_device.vulkan_device.device.clone(),
+                profile_info.profile_info.profile_info,
+            )?))
+        } else {
+            None
+        };
+
+        let decode_buffer_pool = DecodeInputBufferPool::new(
+            decoding_device.allocator.clone(),
+            profile_info.profile_info.clone(),
+        );
+
+        let sps = HashMap::from_iter([(sps.sps_seq_parameter_set_id, sps)]);
+
+        Ok(Self {
+            video_session,
+            parameters,
+            profile_info,
+            parameters_manager,
+            decoding_images,
+            sps,
+            decode_query_pool,
+            decode_buffer_pool,
+            parameters_scheduled_for_reset: None,
+            image_modifiers,
+        })
+    }
+
+    pub(crate) fn process_vps(
+        &mut self,
+        vps: &VideoParameterSet,
+    ) -> Result<(), VulkanDecoderError> {
+        self.parameters_manager.put_vps(vps)
+    }
+
+    pub(crate) fn process_sps(
+        &mut self,
+        sps: SeqParameterSet,
+        usage_info: vk::VideoDecodeUsageInfoKHR<'a>,
+    ) -> Result<(), VulkanDecoderError> {
+        // only the Main profile is supported, so the profile info stays the same for all streams.
+        // This only validates that the new SPS can be decoded with it.
+        H265DecodeProfileInfo::from_sps_decode(&sps, usage_info)?;
+
+        let new_session_params = H265SessionParams::from_sps(&sps);
+        let current_session_params = self
+            .parameters_scheduled_for_reset
+            .take()
+            .unwrap_or_else(|| self.parameters.clone());
+
+        self.parameters_scheduled_for_reset = Some(H265SessionParams::combine(
+            current_session_params,
+            new_session_params,
+        ));
+
+        self.parameters_manager.put_sps(&sps)?;
+        self.sps.insert(sps.sps_seq_parameter_set_id, sps);
+
+        Ok(())
+    }
+
+    pub(crate) fn process_pps(&mut self, pps: &PicParameterSet) -> Result<(), VulkanDecoderError> {
+        let sps = self.sps.get(&pps.pps_seq_parameter_set_id).ok_or_else(|| {
+            VulkanDecoderError::InvalidInputData(format!(
+                "PPS references an unknown SPS id {}",
+                pps.pps_seq_parameter_set_id
+            ))
+        })?;
+
+        self.parameters_manager
+            .put_pps(pps, sps.sps_video_parameter_set_id)
+    }
+
+    pub(crate) fn ensure_session(
+        &mut self,
+        decoding_device: &DecodingDevice<H265Codec>,
+        decode_buffer: OpenCommandBuffer,
+        tracker: &mut DecoderTracker,
+        picture_coded_extent: vk::Extent2D,
+    ) -> Result<(), VulkanDecoderError> {
+        if let Some(new_params) = self.parameters_scheduled_for_reset.take() {
+            if self.parameters.is_valid(&new_params) {
+                // no need to change the session
+                self.parameters.max_num_reorder_frames = new_params.max_num_reorder_frames;
+            } else {
+                self.recreate_session(new_params, decoding_device, decode_buffer, tracker)?;
+            }
+        };
+
+        // The active SPS can have coded_extent smaller than max_coded_extent
+        self.decoding_images
+            .update_coded_extent(picture_coded_extent)?;
+
+        Ok(())
+    }
+
+    fn recreate_session(
+        &mut self,
+        params: H265SessionParams,
+        decoding_device: &DecodingDevice<H265Codec>,
+        decode_buffer: OpenCommandBuffer,
+        tracker: &mut DecoderTracker,
+    ) -> Result<(), VulkanDecoderError> {
+        params.check_level(decoding_device)?;
+
+        self.video_session = Arc::new(VideoSession::new(
+            &decoding_device.vulkan_device,
+            &decoding_device.decode_queues,
+            &self.profile_info.profile_info.profile_info,
+            params.max_coded_extent,
+            params.max_dpb_slots,
+            params.max_active_references,
+            vk::VideoSessionCreateFlagsKHR::empty(),
+            &decoding_device
+                .profile_capabilities
+                .video_capabilities
+                .std_header_version,
+            decoding_device.profile_capabilities.picture_format,
+        )?);
+
+        self.parameters_manager
+            .change_session(self.video_session.session)?;
+
+        self.decoding_images = new_decoding_images(
+            decoding_device,
+            &decoding_device.profile_capabilities,
+            &self.profile_info.profile_info.profile_info,
+            self.video_session.max_coded_extent,
+            self.video_session.max_dpb_slots,
+            decode_buffer,
+            tracker,
+            self.image_modifiers,
+            Some(&self.decoding_images),
+        )?;
+
+        self.parameters = params;
+
+        Ok(())
+    }
+
+    pub(crate) fn free_reference_picture(&mut self, i: usize) {
+        self.decoding_images.free_reference_picture(i);
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct H265SessionParams {
+    pub(crate) max_coded_extent: vk::Extent2D,
+    pub(crate) max_dpb_slots: u32,
+    pub(crate) max_active_references: u32,
+    pub(crate) max_num_reorder_frames: u64,
+    pub(crate) level_idc: u8,
+}
+
+impl H265SessionParams {
+    fn from_sps(sps: &SeqParameterSet) -> Self {
+        // the decoded picture buffer size includes the current picture
+        let max_dec_pic_buffering = sps.max_dec_pic_buffering();
+
+        Self {
+            max_coded_extent: vk::Extent2D {
+                width: sps.pic_width_in_luma_samples,
+                height: sps.pic_height_in_luma_samples,
+            },
+            max_dpb_slots: max_dec_pic_buffering,
+            max_active_references: max_dec_pic_buffering - 1,
+            max_num_reorder_frames: sps.max_num_reorder_pics() as u64,
+            level_idc: sps.profile_tier_level.general_level_idc,
+        }
+    }
+
+    fn combine(current_params: Self, new_params: Self) -> Self {
+        Self {
+            max_coded_extent: vk::Extent2D {
+                width: u32::max(
+                    current_params.max_coded_extent.width,
+                    new_params.max_coded_extent.width,
+                ),
+                height: u32::max(
+                    current_params.max_coded_extent.height,
+                    new_params.max_coded_extent.height,
+                ),
+            },
+            max_dpb_slots: u32::max(current_params.max_dpb_slots, new_params.max_dpb_slots),
+            max_active_references: u32::max(
+                current_params.max_active_references,
+                new_params.max_active_references,
+            ),
+            // max_num_reorder_frames has to come from the new_params
+            max_num_reorder_frames: new_params.max_num_reorder_frames,
+            level_idc: u8::max(current_params.level_idc, new_params.level_idc),
+        }
+    }
+
+    fn is_valid(&self, new_params: &Self) -> bool {
+        self.max_coded_extent.width >= new_params.max_coded_extent.width
+            && self.max_coded_extent.height >= new_params.max_coded_extent.height
+            && self.max_dpb_slots >= new_params.max_dpb_slots
+            && self.max_active_references >= new_params.max_active_references
+    }
+
+    fn check_level(
+        &self,
+        decoding_device: &DecodingDevice<H265Codec>,
+    ) -> Result<(), VulkanDecoderError> {
+        let max_level_idc = vk_to_h265_level_idc(
+            decoding_device
+                .profile_capabilities
+                .codec_decode_capabilities
+                .max_level_idc,
+        )?;
+
+        if self.level_idc > max_level_idc {
+            return Err(VulkanDecoderError::InvalidInputData(format!(
+                "stream has general_level_idc = {}, while the GPU can decode at most {}",
+                self.level_idc, max_level_idc
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// H265 counterpart of [`super::parameters::VideoSessionParametersManager`]. Parameter sets
+/// change rarely, so instead of adding them to the existing `VideoSessionParameters`, the object
+/// is recreated with all parameter sets every time one of them is received.
+pub(crate) struct H265VideoSessionParametersManager {
+    pub(crate) parameters: Arc<VideoSessionParameters>,
+    vps: HashMap<u8, VkH265VideoParameterSet>,
+    sps: HashMap<u8, VkH265SequenceParameterSet>,
+    pps: HashMap<u8, VkH265PictureParameterSet>,
+    device: Arc<Device>,
+    session: vk::VideoSessionKHR,
+}
+
+impl H265VideoSessionParametersManager {
+    fn new(
+        decoding_device: &DecodingDevice<H265Codec>,
+        session: vk::VideoSessionKHR,
+    ) -> Result<Self, VulkanDecoderError> {
+        Ok(Self {
+            parameters: Arc::new(VideoSessionParameters::new::<H265Codec>(
+                decoding_device.device.clone(),
+                session,
+                H265VkParameters {
+                    vps: Vec::new(),
+                    sps: Vec::new(),
+                    pps: Vec::new(),
+                },
+                None,
+            )?),
+            vps: HashMap::new(),
+            sps: HashMap::new(),
+            pps: HashMap::new(),
+            device: decoding_device.device.clone(),
+            session,
+        })
+    }
+
+    pub(crate) fn parameters(&self) -> vk::VideoSessionParametersKHR {
+        self.parameters.parameters
+    }
+
+    fn change_session(&mut self, session: vk::VideoSessionKHR) -> Result<(), VulkanDecoderError> {
+        if self.session == session {
+            return Ok(());
+        }
+        self.session = session;
+        self.recreate_parameters()
+    }
+
+    fn put_vps(&mut self, vps: &VideoParameterSet) -> Result<(), VulkanDecoderError> {
+        self.vps.insert(vps.vps_video_parameter_set_id, vps.into());
+        self.recreate_parameters()
+    }
+
+    fn put_sps(&mut self, sps: &SeqParameterSet) -> Result<(), VulkanDecoderError> {
+        self.sps.insert(sps.sps_seq_parameter_set_id, sps.into());
+        self.recreate_parameters()
+    }
+
+    fn put_pps(
+        &mut self,
+        pps: &PicParameterSet,
+        sps_video_parameter_set_id: u8,
+    ) -> Result<(), VulkanDecoderError> {
+        self.pps.insert(
+            pps.pps_pic_parameter_set_id,
+            VkH265PictureParameterSet::new_decode(pps, sps_video_parameter_set_id),
+        );
+        self.recreate_parameters()
+    }
+
+    /// Decode submissions that are still in flight keep the previous parameters object alive.
+    fn recreate_parameters(&mut self) -> Result<(), VulkanDecoderError> {
+        self.parameters = Arc::new(VideoSessionParameters::new::<H265Codec>(
+            self.device.clone(),
+            self.session,
+            H265VkParameters {
+                vps: self.vps.values().map(|vps| vps.vps).collect(),
+                sps: self.sps.values().map(|sps| sps.sps).collect(),
+                pps: self.pps.values().map(|pps| pps.pps).collect(),
+            },
+            None,
+        )?);
+
+        Ok(())
+    }
+}
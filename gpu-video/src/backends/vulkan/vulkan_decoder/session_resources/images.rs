@@ -3,9 +3,9 @@ use std::sync::{Arc, Mutex};
 use ash::vk;
 
 use crate::backends::vulkan::{
-    codec::h264::parameters::H264DecodeProfileInfo,
+    codec::CodecCapabilities,
     vulkan_decoder::VulkanDecoderError,
-    vulkan_device::DecodingDevice,
+    vulkan_device::{DecodingDevice, decoding_memory::DecodingMemoryReservation},
     wrappers::{
         CodingImageBundle, DecodedPicturesBuffer, Image, ImageLayoutTracker, ImageWithView,
         OpenCommandBuffer,
@@ -15,6 +15,8 @@ use crate::backends::vulkan::{
 pub(crate) struct DecodingImages<'a> {
     pub(crate) dpb: DecodedPicturesBuffer<'a>,
     pub(crate) dst_image: Option<CodingImageBundle<'a>>,
+    /// Estimated size of the images, counted against the memory budget until they're dropped
+    pub(crate) memory: DecodingMemoryReservation,
 }
 
 impl<'a> DecodingImages<'a> {
@@ -42,16 +44,17 @@ impl<'a> DecodingImages<'a> {
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub(crate) fn new(
-        decoding_device: &DecodingDevice,
+    pub(crate) fn new<C: CodecCapabilities>(
+        decoding_device: &DecodingDevice<C>,
         command_buffer: &mut OpenCommandBuffer,
         image_tracker: Arc<Mutex<ImageLayoutTracker>>,
-        profile: &H264DecodeProfileInfo,
+        profile_info: &vk::VideoProfileInfoKHR,
         dpb_format: &vk::VideoFormatPropertiesKHR<'a>,
         dst_format: &Option<vk::VideoFormatPropertiesKHR<'a>>,
         max_coded_extent: vk::Extent2D,
         max_dpb_slots: u32,
         additional_queue_index: u32,
+        memory: DecodingMemoryReservation,
     ) -> Result<Self, VulkanDecoderError> {
         let dpb_image_usage = if dst_format.is_some() {
             dpb_format.image_usage_flags & vk::ImageUsageFlags::VIDEO_DECODE_DPB_KHR
@@ -65,7 +68,7 @@ impl<'a> DecodingImages<'a> {
 
         let queue_indices = [
             additional_queue_index,
-            decoding_device.h264_decode_queues.family_index as u32,
+            decoding_device.decode_queues.family_index as u32,
         ];
 
         let dpb = DecodedPicturesBuffer::new(
@@ -73,7 +76,7 @@ impl<'a> DecodingImages<'a> {
             command_buffer,
             image_tracker.clone(),
             false,
-            &profile.profile_info.profile_info,
+            profile_info,
             dpb_image_usage,
             dpb_format,
             max_coded_extent,
@@ -100,7 +103,7 @@ impl<'a> DecodingImages<'a> {
                     max_coded_extent,
                     dst_image_usage,
                     false,
-                    &profile.profile_info.profile_info,
+                    profile_info,
                     1,
                     Some(&queue_indices),
                     vk::ImageLayout::VIDEO_DECODE_DST_KHR,
@@ -108,7 +111,11 @@ impl<'a> DecodingImages<'a> {
             })
             .transpose()?;
 
-        Ok(Self { dpb, dst_image })
+        Ok(Self {
+            dpb,
+            dst_image,
+            memory,
+        })
     }
 
     #[allow(dead_code)]
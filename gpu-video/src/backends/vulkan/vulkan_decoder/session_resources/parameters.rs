@@ -41,7 +41,6 @@ impl VideoSessionParametersManager {
                     pps: Vec::new(),
                 },
                 None,
-                None,
             )?),
             sps: HashMap::new(),
             pps: HashMap::new(),
@@ -72,7 +71,6 @@ impl VideoSessionParametersManager {
             session,
             H264VkParameters { sps, pps },
             None,
-            None,
         )?);
 
         Ok(())
@@ -133,7 +131,6 @@ impl VideoSessionParametersManager {
                 pps: initial_pps,
             },
             Some(&self.parameters),
-            None,
         )?);
         Ok(())
     }
@@ -169,11 +166,20 @@ impl SessionParams<'_> {
             ),
             // max_num_reorder_frames has to come from the new_params
             max_num_reorder_frames: new_params.max_num_reorder_frames,
-            profile_info: cmp::max_by(
-                current_params.profile_info,
-                new_params.profile_info,
-                |p1, p2| p1.profile_idc.cmp(&p2.profile_idc),
-            ),
+            // a session decodes pictures of a single bit depth and picture layout, so they have to
+            // match the new stream
+            profile_info: match current_params.profile_info.bit_depth()
+                == new_params.profile_info.bit_depth()
+                && current_params.profile_info.picture_layout
+                    == new_params.profile_info.picture_layout
+            {
+                true => cmp::max_by(
+                    current_params.profile_info,
+                    new_params.profile_info,
+                    |p1, p2| p1.profile_idc.cmp(&p2.profile_idc),
+                ),
+                false => new_params.profile_info,
+            },
             level_idc: u8::max(current_params.level_idc, new_params.level_idc),
         }
     }
@@ -183,5 +189,9 @@ impl SessionParams<'_> {
             && self.max_coded_extent.height >= new_params.max_coded_extent.height
             && self.max_dpb_slots >= new_params.max_dpb_slots
             && self.profile_info.profile_idc >= new_params.profile_info.profile_idc
+            && self.profile_info.bit_depth() == new_params.profile_info.bit_depth()
+            // progressive frames can also be decoded by an interlaced session
+            && (self.profile_info.picture_layout == new_params.profile_info.picture_layout
+                || self.profile_info.is_interlaced())
     }
 }
@@ -5,47 +5,65 @@ use std::sync::Arc;
 
 use ash::vk;
 
-use crate::backends::vulkan::codec::EncodeCodec;
+use crate::backends::vulkan::codec::av1::Av1Codec;
 use crate::backends::vulkan::codec::h264::H264Codec;
-use crate::backends::vulkan::vulkan_decoder::ImageModifiers;
+use crate::backends::vulkan::codec::h265::H265Codec;
+use crate::backends::vulkan::codec::{CodecCapabilities, EncodeCodec};
+use crate::backends::vulkan::vulkan_decoder::{DecodingImagesLimits, ImageModifiers};
 use crate::backends::vulkan::vulkan_encoder::FullEncoderParameters;
 use crate::backends::vulkan::wrappers::*;
 use crate::backends::vulkan::{
     VulkanAdapter, VulkanAdapterInfo, VulkanDecoder, VulkanDecoderError, VulkanEncoder,
     VulkanEncoderError,
 };
-use crate::capabilities::{DecodeCapabilities, EncodeCapabilities};
+use crate::capabilities::{DecodeCapabilities, EncodeCapabilities, VideoCapabilities};
+use crate::decoders::{
+    BytesDecoderInner, HardwareBytesDecoder, sei::SeiTracker, stats::DecoderStatsTracker,
+};
 use crate::device::{
     ColorRange, CoreVideoDeviceBackend, DecoderParameters, EncoderOutputParameters,
     EncoderParametersH264, EncoderParametersH265, Rational, VideoDeviceDescriptor,
 };
 use crate::frame_sorter::FrameSorter;
-use crate::parameters::EncoderPreset;
+use crate::parameters::{Deinterlacing, EncoderPreset, PixelFormat, RateControl};
+use crate::parser::av1::Av1Parser;
 use crate::parser::h264::H264Parser;
+use crate::parser::h265::H265Parser;
 use crate::parser::reference_manager::ReferenceContext;
 use crate::{
-    BytesDecoder, BytesEncoderH264, BytesEncoderH265, RawFrameData, VideoBackendError,
-    VideoDecoderError, VideoDeviceInitError, VideoEncoderError,
+    BytesDecoder, BytesDecoderAv1, BytesDecoderH265, BytesEncoderH264, BytesEncoderH265,
+    ExternalMemoryDecoder, ExternalMemoryFrame, RawFrameData, VideoBackendError, VideoDecoderError,
+    VideoDeviceInitError, VideoEncoderError,
 };
 
 use self::caps::{
     NativeDecodeCapabilities, NativeDecodeProfileCapabilities, NativeEncodeCapabilities,
+    NativeEncodeProfileCapabilities, decode_picture_format,
 };
+use self::decoding_memory::DecodingMemoryTracker;
 use self::queues::{Queue, QueueIndex, Queues, VideoQueues};
 
 #[cfg(feature = "wgpu")]
 mod wgpu_api;
 
 pub(crate) mod caps;
+pub(crate) mod decoding_memory;
 pub(crate) mod queues;
 
 pub struct VulkanDevice {
     pub(crate) _physical_device: vk::PhysicalDevice,
+    pub(crate) memory_properties: vk::PhysicalDeviceMemoryProperties,
     pub(crate) allocator: Arc<Allocator>,
     pub(crate) queues: Queues,
     pub(crate) native_decode_capabilities: Option<NativeDecodeCapabilities>,
     pub(crate) native_encode_capabilities: Option<NativeEncodeCapabilities>,
     pub(crate) adapter_info: Arc<VulkanAdapterInfo>,
+    /// Command pools shared by the coders created on this device. The memory of all coders is
+    /// suballocated from the blocks of one [`Allocator`].
+    pub(crate) command_pool_cache: CommandPoolCache,
+    /// Decoding images of all decoders created on this device, checked against
+    /// [`DecoderParameters::memory_budget`].
+    pub(crate) decoding_memory: Arc<DecodingMemoryTracker>,
     pub(crate) device: Arc<Device>,
 }
 
@@ -57,6 +75,27 @@ impl CoreVideoDeviceBackend for VulkanDevice {
         VulkanDevice::create_bytes_decoder_h264(self, parameters).map_err(Into::into)
     }
 
+    fn create_bytes_decoder_h265(
+        self: Arc<Self>,
+        parameters: DecoderParameters,
+    ) -> Result<BytesDecoderH265, VideoDecoderError> {
+        VulkanDevice::create_bytes_decoder_h265(self, parameters).map_err(Into::into)
+    }
+
+    fn create_bytes_decoder_av1(
+        self: Arc<Self>,
+        parameters: DecoderParameters,
+    ) -> Result<BytesDecoderAv1, VideoDecoderError> {
+        VulkanDevice::create_bytes_decoder_av1(self, parameters).map_err(Into::into)
+    }
+
+    fn create_external_memory_decoder_h264(
+        self: Arc<Self>,
+        parameters: DecoderParameters,
+    ) -> Result<ExternalMemoryDecoder, VideoDecoderError> {
+        VulkanDevice::create_external_memory_decoder_h264(self, parameters).map_err(Into::into)
+    }
+
     fn create_bytes_encoder_h264(
         self: Arc<Self>,
         parameters: EncoderParametersH264,
@@ -86,6 +125,15 @@ impl CoreVideoDeviceBackend for VulkanDevice {
     fn encode_capabilities(&self) -> EncodeCapabilities {
         self.adapter_info.encode_capabilities
     }
+
+    fn video_capabilities(&self) -> VideoCapabilities {
+        VideoCapabilities {
+            supports_decoding: self.adapter_info.supports_decoding,
+            supports_encoding: self.adapter_info.supports_encoding,
+            decode: self.adapter_info.decode_capabilities,
+            encode: self.adapter_info.encode_capabilities,
+        }
+    }
 }
 
 impl VulkanDevice {
@@ -93,8 +141,7 @@ impl VulkanDevice {
         video_adapter: VulkanAdapter<'_>,
         _desc: VideoDeviceDescriptor,
     ) -> Result<crate::VideoDevice, VulkanDeviceInitError> {
-        let mut required_extensions = video_adapter.required_extensions();
-        required_extensions.push(ash::khr::timeline_semaphore::NAME);
+        let required_extensions = video_adapter.required_device_extensions();
 
         let mut timeline_semaphore_feature =
             vk::PhysicalDeviceTimelineSemaphoreFeatures::default().timeline_semaphore(true);
@@ -112,27 +159,43 @@ impl VulkanDevice {
         })
     }
 
+    /// Wraps a device created outside of this crate, e.g. by an engine that already owns the
+    /// vulkan context. The device is not destroyed on drop.
+    ///
+    /// # Safety
+    /// - `device` has to be a valid device created from the physical device of `adapter`.
+    /// - All extensions from [`VulkanAdapter::required_device_extensions`] have to be enabled.
+    /// - The `synchronization2`, `timelineSemaphore`, `videoMaintenance1` and
+    ///   `descriptorBindingPartiallyBound` features have to be enabled.
+    /// - The queues from [`VulkanAdapter::required_queue_families`] have to be created.
+    /// - The queues can be used by this crate at any time, so they cannot be used concurrently
+    ///   by the caller.
+    /// - `device` has to outlive the returned object and all decoders and encoders created with it.
+    #[cfg_attr(not(feature = "expose-backends"), allow(dead_code))]
+    pub unsafe fn from_raw(
+        adapter: VulkanAdapter<'_>,
+        device: ash::Device,
+    ) -> Result<crate::VideoDevice, VulkanDeviceInitError> {
+        let video_device = Self::from_device(adapter, device, false)?;
+
+        Ok(crate::VideoDevice {
+            inner: video_device,
+            #[cfg(feature = "wgpu")]
+            wgpu_device: None,
+        })
+    }
+
     fn new_from_create_info(
         adapter: VulkanAdapter<'_>,
         required_extensions: &[&'static CStr],
         device_create_info: vk::DeviceCreateInfo<'_>,
     ) -> Result<Arc<Self>, VulkanDeviceInitError> {
-        let VulkanAdapter {
-            instance,
-            physical_device,
-            queue_indices,
-            decode_capabilities,
-            encode_capabilities,
-            info,
-            ..
-        } = adapter;
-
         let required_extensions_as_ptrs = required_extensions
             .iter()
             .map(|e| e.as_ptr())
             .collect::<Vec<_>>();
 
-        let queue_create_infos = queue_indices.queue_create_infos();
+        let queue_create_infos = adapter.queue_indices.queue_create_infos();
         let queue_create_infos = queue_create_infos
             .iter()
             .map(|q| q.info())
@@ -153,11 +216,31 @@ impl VulkanDevice {
             .push_next(&mut vk_descriptor_feature);
 
         let device = unsafe {
-            instance
-                .instance
-                .create_device(physical_device, &device_create_info, None)?
+            adapter.instance.instance.create_device(
+                adapter.physical_device,
+                &device_create_info,
+                None,
+            )?
         };
 
+        Self::from_device(adapter, device, true)
+    }
+
+    fn from_device(
+        adapter: VulkanAdapter<'_>,
+        device: ash::Device,
+        destroy_device_on_drop: bool,
+    ) -> Result<Arc<Self>, VulkanDeviceInitError> {
+        let VulkanAdapter {
+            instance,
+            physical_device,
+            queue_indices,
+            decode_capabilities,
+            encode_capabilities,
+            info,
+            ..
+        } = adapter;
+
         let video_queue_ext = ash::khr::video_queue::Device::new(&instance.instance, &device);
         let video_decode_queue_ext =
             ash::khr::video_decode_queue::Device::new(&instance.instance, &device);
@@ -169,6 +252,17 @@ impl VulkanDevice {
             .debug_utils_instance_ext
             .as_ref()
             .map(|_| ash::ext::debug_utils::Device::new(&instance.instance, &device));
+        let external_memory_ext = info
+            .supports_external_memory
+            .then(|| ExternalMemoryDevice::new(&instance.instance, &device));
+        let external_semaphore_ext = info
+            .supports_external_semaphore
+            .then(|| ExternalSemaphoreDevice::new(&instance.instance, &device));
+        let memory_properties = unsafe {
+            instance
+                .instance
+                .get_physical_device_memory_properties(physical_device)
+        };
 
         let device = Arc::new(Device {
             device,
@@ -176,12 +270,15 @@ impl VulkanDevice {
             video_decode_queue_ext,
             video_encode_queue_ext,
             debug_utils_ext,
+            external_memory_ext,
+            external_semaphore_ext,
+            destroy_device_on_drop,
             _instance: instance.instance.clone(),
         });
 
-        let h264_decode_queues =
+        let decode_queues =
             queue_indices
-                .h264_decode
+                .decode
                 .as_ref()
                 .map_or(Vec::new(), |queue_family_index| {
                     (0..queue_family_index.queue_count)
@@ -214,7 +311,7 @@ impl VulkanDevice {
         let queues = Queues {
             transfer: transfer_queue,
             compute: compute_queue,
-            h264_decode: VideoQueues::new(h264_decode_queues.into_boxed_slice()).map(Arc::new),
+            decode: VideoQueues::new(decode_queues.into_boxed_slice()).map(Arc::new),
             encode: VideoQueues::new(h264_encode_queues.into_boxed_slice()).map(Arc::new),
             wgpu: wgpu_queue,
         };
@@ -227,6 +324,9 @@ impl VulkanDevice {
 
         Ok(Arc::new(Self {
             _physical_device: physical_device,
+            memory_properties,
+            command_pool_cache: CommandPoolCache::new(device.clone()),
+            decoding_memory: Default::default(),
             device,
             allocator,
             queues,
@@ -241,31 +341,168 @@ impl VulkanDevice {
         parameters: DecoderParameters,
     ) -> Result<BytesDecoder, VulkanDecoderError> {
         let parser = H264Parser::default();
-        let reference_ctx = ReferenceContext::new(parameters.missed_frame_handling);
+        let reference_ctx =
+            ReferenceContext::new(parameters.missed_frame_handling, parameters.keyframes_only);
 
-        let vulkan_decoder = VulkanDecoder::new(
+        let mut vulkan_decoder = VulkanDecoder::new(
             Arc::new(self.decoding_device()?),
             parameters.usage_flags,
-            ImageModifiers {
-                additional_queue_index: self.queues.transfer.family_index,
-                create_flags: Default::default(),
-                usage_flags: Default::default(),
-            },
+            parameters.enable_gpu_timing,
+            self.decoder_image_modifiers(&parameters, parameters.deinterlacing),
+            parameters.output_pixel_format,
+            parameters.deinterlacing,
         )?;
-        let frame_sorter = FrameSorter::<RawFrameData>::new();
+        if parameters.aligned_output_rows {
+            vulkan_decoder.align_output_rows();
+        }
+        let frame_sorter = FrameSorter::<RawFrameData>::from_parameters(&parameters);
 
         Ok(BytesDecoder {
             parser,
+            inner: BytesDecoderInner::Hardware(HardwareBytesDecoder {
+                decoder: Box::new(vulkan_decoder),
+                reference_ctx,
+                frame_sorter,
+            }),
+            stats: DecoderStatsTracker::new(),
+            sei: SeiTracker::new(parameters.display_orientation),
+        })
+    }
+
+    pub fn create_external_memory_decoder_h264(
+        self: Arc<Self>,
+        parameters: DecoderParameters,
+    ) -> Result<ExternalMemoryDecoder, VulkanDecoderError> {
+        if !self.adapter_info.supports_external_memory {
+            return Err(VulkanDecoderError::ExternalMemoryUnsupported);
+        }
+
+        let parser = H264Parser::default();
+        let reference_ctx =
+            ReferenceContext::new(parameters.missed_frame_handling, parameters.keyframes_only);
+
+        let mut vulkan_decoder = VulkanDecoder::new(
+            Arc::new(self.decoding_device()?),
+            parameters.usage_flags,
+            parameters.enable_gpu_timing,
+            self.decoder_image_modifiers(&parameters, parameters.deinterlacing),
+            parameters.output_pixel_format,
+            parameters.deinterlacing,
+        )?;
+        if self.adapter_info.supports_external_semaphore {
+            vulkan_decoder.use_exportable_semaphore()?;
+        }
+        let frame_sorter = FrameSorter::<ExternalMemoryFrame>::from_parameters(&parameters);
+
+        Ok(ExternalMemoryDecoder {
+            parser,
+            decoder: Box::new(vulkan_decoder),
             reference_ctx,
+            frame_sorter,
+            stats: DecoderStatsTracker::new(),
+            sei: SeiTracker::new(parameters.display_orientation),
+        })
+    }
+
+    pub fn create_bytes_decoder_h265(
+        self: Arc<Self>,
+        parameters: DecoderParameters,
+    ) -> Result<BytesDecoderH265, VulkanDecoderError> {
+        let parser = H265Parser::default();
+        let reference_ctx = crate::parser::h265::reference_manager::ReferenceContext::new(
+            parameters.missed_frame_handling,
+            parameters.keyframes_only,
+        );
+
+        let mut vulkan_decoder = VulkanDecoder::new(
+            Arc::new(self.decoding_device_h265()?),
+            parameters.usage_flags,
+            parameters.enable_gpu_timing,
+            self.decoder_image_modifiers(&parameters, Deinterlacing::Off),
+            parameters.output_pixel_format,
+            Deinterlacing::Off,
+        )?;
+        if parameters.aligned_output_rows {
+            vulkan_decoder.align_output_rows();
+        }
+        let frame_sorter = FrameSorter::<RawFrameData>::from_parameters(&parameters);
+
+        Ok(BytesDecoderH265 {
+            parser,
             decoder: Box::new(vulkan_decoder),
+            reference_ctx,
             frame_sorter,
+            stats: DecoderStatsTracker::new(),
+            sei: SeiTracker::new(parameters.display_orientation),
         })
     }
 
+    pub fn create_bytes_decoder_av1(
+        self: Arc<Self>,
+        parameters: DecoderParameters,
+    ) -> Result<BytesDecoderAv1, VulkanDecoderError> {
+        let parser = Av1Parser::default();
+        let reference_ctx = crate::parser::av1::reference_manager::ReferenceContext::new(
+            parameters.missed_frame_handling,
+            parameters.keyframes_only,
+        );
+
+        let mut vulkan_decoder = VulkanDecoder::new(
+            Arc::new(self.decoding_device_av1()?),
+            parameters.usage_flags,
+            parameters.enable_gpu_timing,
+            self.decoder_image_modifiers(&parameters, Deinterlacing::Off),
+            parameters.output_pixel_format,
+            Deinterlacing::Off,
+        )?;
+        if parameters.aligned_output_rows {
+            vulkan_decoder.align_output_rows();
+        }
+
+        Ok(BytesDecoderAv1 {
+            parser,
+            decoder: Box::new(vulkan_decoder),
+            reference_ctx,
+            frame_sorter: FrameSorter::<RawFrameData>::new_in_decode_order(),
+            stats: DecoderStatsTracker::new(),
+            sei: SeiTracker::new(parameters.display_orientation),
+        })
+    }
+
+    /// Conversion to other pixel formats and deinterlacing are done with compute shaders that
+    /// read the decoded image as a storage image.
+    fn decoder_image_modifiers(
+        &self,
+        parameters: &DecoderParameters,
+        deinterlacing: Deinterlacing,
+    ) -> ImageModifiers {
+        let limits = DecodingImagesLimits {
+            max_dpb_slots: parameters.max_dpb_slots,
+            memory_budget: parameters.memory_budget,
+        };
+
+        match (parameters.output_pixel_format, deinterlacing) {
+            (PixelFormat::Nv12 | PixelFormat::P010, Deinterlacing::Off) => ImageModifiers {
+                additional_queue_index: self.queues.transfer.family_index,
+                create_flags: Default::default(),
+                usage_flags: Default::default(),
+                limits,
+            },
+            _ => ImageModifiers {
+                additional_queue_index: self.queues.compute.family_index,
+                create_flags: vk::ImageCreateFlags::EXTENDED_USAGE
+                    | vk::ImageCreateFlags::MUTABLE_FORMAT,
+                usage_flags: vk::ImageUsageFlags::STORAGE,
+                limits,
+            },
+        }
+    }
+
     pub fn create_bytes_encoder_h264(
         self: Arc<Self>,
         parameters: EncoderParametersH264,
     ) -> Result<BytesEncoderH264, VulkanEncoderError> {
+        let pixel_format = parameters.input_parameters.pixel_format;
         let parameters = self.validate_and_fill_encoder_parameters(
             parameters.output_parameters,
             parameters.input_parameters.width,
@@ -273,11 +510,11 @@ impl VulkanDevice {
             parameters.input_parameters.target_framerate,
         )?;
 
+        let mut encoder = VulkanEncoder::new(Arc::new(self.encoding_device()?), parameters)?;
+        encoder.use_input_pixel_format(pixel_format)?;
+
         Ok(BytesEncoderH264 {
-            encoder: Box::new(VulkanEncoder::new(
-                Arc::new(self.encoding_device()?),
-                parameters,
-            )?),
+            encoder: Box::new(encoder),
         })
     }
 
@@ -285,6 +522,7 @@ impl VulkanDevice {
         self: Arc<Self>,
         parameters: EncoderParametersH265,
     ) -> Result<BytesEncoderH265, VulkanEncoderError> {
+        let pixel_format = parameters.input_parameters.pixel_format;
         let parameters = self.validate_and_fill_encoder_parameters(
             parameters.output_parameters,
             parameters.input_parameters.width,
@@ -292,11 +530,11 @@ impl VulkanDevice {
             parameters.input_parameters.target_framerate,
         )?;
 
+        let mut encoder = VulkanEncoder::new(Arc::new(self.encoding_device()?), parameters)?;
+        encoder.use_input_pixel_format(pixel_format)?;
+
         Ok(BytesEncoderH265 {
-            encoder: Box::new(VulkanEncoder::new(
-                Arc::new(self.encoding_device()?),
-                parameters,
-            )?),
+            encoder: Box::new(encoder),
         })
     }
 
@@ -333,7 +571,9 @@ impl VulkanDevice {
         })
     }
 
-    pub(crate) fn decoding_device(self: &Arc<Self>) -> Result<DecodingDevice, VulkanDecoderError> {
+    pub(crate) fn decoding_device(
+        self: &Arc<Self>,
+    ) -> Result<DecodingDevice<H264Codec>, VulkanDecoderError> {
         let decode_caps = self
             .native_decode_capabilities()
             .ok_or(VulkanDecoderError::VulkanDecoderUnsupported)?
@@ -347,15 +587,68 @@ impl VulkanDevice {
 
         Ok(DecodingDevice {
             vulkan_device: self.clone(),
-            h264_decode_queues: self
+            decode_queues: self
                 .queues
-                .h264_decode
+                .decode
                 .clone()
                 .ok_or(VulkanDecoderError::VulkanDecoderUnsupported)?,
             profile_capabilities: decode_caps
                 .profile(max_profile)
                 .cloned()
                 .ok_or(VulkanDecoderError::VulkanDecoderUnsupported)?,
+            profile_capabilities_10_bit: decode_caps.high10.clone(),
+            high_chroma_profile_capabilities: decode_caps.high_chroma.clone(),
+            interlaced_decoding: decode_caps.interlaced,
+        })
+    }
+
+    pub(crate) fn decoding_device_h265(
+        self: &Arc<Self>,
+    ) -> Result<DecodingDevice<H265Codec>, VulkanDecoderError> {
+        let profile_capabilities = self
+            .native_decode_capabilities()
+            .ok_or(VulkanDecoderError::VulkanDecoderUnsupported)?
+            .h265
+            .as_ref()
+            .and_then(|caps| caps.main.clone())
+            .ok_or(VulkanDecoderError::VulkanDecoderUnsupported)?;
+
+        Ok(DecodingDevice {
+            vulkan_device: self.clone(),
+            decode_queues: self
+                .queues
+                .decode
+                .clone()
+                .ok_or(VulkanDecoderError::VulkanDecoderUnsupported)?,
+            profile_capabilities,
+            profile_capabilities_10_bit: None,
+            high_chroma_profile_capabilities: Vec::new(),
+            interlaced_decoding: false,
+        })
+    }
+
+    pub(crate) fn decoding_device_av1(
+        self: &Arc<Self>,
+    ) -> Result<DecodingDevice<Av1Codec>, VulkanDecoderError> {
+        let profile_capabilities = self
+            .native_decode_capabilities()
+            .ok_or(VulkanDecoderError::VulkanDecoderUnsupported)?
+            .av1
+            .as_ref()
+            .and_then(|caps| caps.main.clone())
+            .ok_or(VulkanDecoderError::VulkanDecoderUnsupported)?;
+
+        Ok(DecodingDevice {
+            vulkan_device: self.clone(),
+            decode_queues: self
+                .queues
+                .decode
+                .clone()
+                .ok_or(VulkanDecoderError::VulkanDecoderUnsupported)?,
+            profile_capabilities,
+            profile_capabilities_10_bit: None,
+            high_chroma_profile_capabilities: Vec::new(),
+            interlaced_decoding: false,
         })
     }
 
@@ -390,10 +683,17 @@ impl VulkanDevice {
         let native_quality_level_properties =
             &native_profile_caps.quality_level_properties[quality_level as usize];
 
-        let idr_period = C::resolve_idr_period(
-            &native_quality_level_properties.codec_quality_level_properties,
+        let idr_period = match (
+            encoder_parameters.intra_refresh_period,
             encoder_parameters.idr_period,
-        );
+        ) {
+            // The picture is refreshed gradually, so IDR frames are only encoded when requested
+            (Some(_), None) => NonZeroU32::MAX,
+            (_, idr_period) => C::resolve_idr_period(
+                &native_quality_level_properties.codec_quality_level_properties,
+                idr_period,
+            ),
+        };
 
         let min_extent = native_profile_caps.video_capabilities.min_coded_extent;
         let max_extent = native_profile_caps.video_capabilities.max_coded_extent;
@@ -418,28 +718,77 @@ impl VulkanDevice {
             });
         }
 
-        let rate_control = encoder_parameters.rate_control;
-        if !native_profile_caps
-            .encode_capabilities
-            .rate_control_modes
-            .contains(rate_control.to_vk())
-        {
+        let max_width = encoder_parameters.max_width.unwrap_or(width);
+        if max_width < width || max_width.get() > max_extent.width {
             return Err(VulkanEncoderError::ParametersError {
-                field: "rate_control",
+                field: "max_width",
                 problem: format!(
-                    "Rate control has mode {:?}. Supported modes are: {:?}.",
-                    rate_control.to_vk(),
-                    native_profile_caps.encode_capabilities.rate_control_modes
+                    "Max width is {}, should be between {} and {}.",
+                    max_width, width, max_extent.width
                 ),
             });
         }
 
+        let max_height = encoder_parameters.max_height.unwrap_or(height);
+        if max_height < height || max_height.get() > max_extent.height {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "max_height",
+                problem: format!(
+                    "Max height is {}, should be between {} and {}.",
+                    max_height, height, max_extent.height
+                ),
+            });
+        }
+
+        let rate_control = encoder_parameters.rate_control;
+        validate_rate_control(native_profile_caps, rate_control)?;
+
         let max_references = C::resolve_max_references(
             &native_quality_level_properties.codec_quality_level_properties,
             &native_profile_caps.codec_encode_capabilities,
             encoder_parameters.max_references,
         );
 
+        let consecutive_b_frames = C::resolve_consecutive_b_frames(
+            &native_profile_caps.codec_encode_capabilities,
+            encoder_parameters.profile,
+            max_references,
+            encoder_parameters.consecutive_b_frames,
+        )?;
+
+        let intra_refresh_period = encoder_parameters.intra_refresh_period;
+        if let Some(period) = intra_refresh_period {
+            if !C::supports_different_slice_types(&native_profile_caps.codec_encode_capabilities) {
+                return Err(VulkanEncoderError::ParametersError {
+                    field: "intra_refresh_period",
+                    problem: "The device does not support intra slices in P-frames.".to_string(),
+                });
+            }
+            if consecutive_b_frames > 0 {
+                return Err(VulkanEncoderError::ParametersError {
+                    field: "intra_refresh_period",
+                    problem: "Intra refresh cannot be used with B-frames.".to_string(),
+                });
+            }
+            if encoder_parameters
+                .slice_count
+                .is_some_and(|slice_count| slice_count != period)
+            {
+                return Err(VulkanEncoderError::ParametersError {
+                    field: "slice_count",
+                    problem: format!(
+                        "Intra refresh splits frames into {period} slices, the slice count has to be unset or equal to it."
+                    ),
+                });
+            }
+        }
+
+        let slice_count = C::resolve_slice_count(
+            &native_profile_caps.codec_encode_capabilities,
+            height,
+            intra_refresh_period.or(encoder_parameters.slice_count),
+        )?;
+
         if framerate.numerator.checked_mul(2).is_none() {
             return Err(VulkanEncoderError::ParametersError {
                 field: "framerate",
@@ -452,6 +801,22 @@ impl VulkanDevice {
                 problem: format!("Framerate is {framerate:?}. The numerator should be != 0.",),
             });
         }
+        let stream_params_interval = encoder_parameters
+            .stream_params_interval
+            .map(|interval| {
+                let frames = interval.as_secs_f64() * framerate.numerator as f64
+                    / framerate.denominator.get() as f64;
+                NonZeroU32::new(frames.ceil() as u32).ok_or_else(|| {
+                    VulkanEncoderError::ParametersError {
+                        field: "stream_params_interval",
+                        problem: format!(
+                            "Interval is {interval:?}, it should be at least one frame long."
+                        ),
+                    }
+                })
+            })
+            .transpose()?;
+
         let usage_flags = encoder_parameters.usage_flags.unwrap_or_default().into();
         let content_flags = vk::VideoEncodeContentFlagsKHR::DEFAULT;
         let color_space = encoder_parameters.color_space.unwrap_or_default();
@@ -465,6 +830,11 @@ impl VulkanDevice {
             height,
             rate_control,
             max_references,
+            consecutive_b_frames,
+            slice_count,
+            intra_refresh_period,
+            max_width,
+            max_height,
             quality_level,
             profile: encoder_parameters.profile,
             framerate,
@@ -472,25 +842,118 @@ impl VulkanDevice {
             tuning_mode,
             content_flags,
             inline_stream_params: encoder_parameters.inline_stream_params.unwrap_or(true),
+            stream_params_interval,
             color_space,
             color_range,
+            enable_gpu_timing: encoder_parameters.enable_gpu_timing,
         })
     }
 }
 
+/// Checks whether the device can encode with `rate_control`.
+pub(crate) fn validate_rate_control<C: EncodeCodec>(
+    native_profile_caps: &NativeEncodeProfileCapabilities<C>,
+    rate_control: RateControl,
+) -> Result<(), VulkanEncoderError> {
+    if !native_profile_caps
+        .encode_capabilities
+        .rate_control_modes
+        .contains(rate_control.to_vk())
+    {
+        return Err(VulkanEncoderError::ParametersError {
+            field: "rate_control",
+            problem: format!(
+                "Rate control has mode {:?}. Supported modes are: {:?}.",
+                rate_control.to_vk(),
+                native_profile_caps.encode_capabilities.rate_control_modes
+            ),
+        });
+    }
+
+    if let RateControl::ConstantQp { qp } = rate_control {
+        let qp_range = C::qp_range(&native_profile_caps.codec_encode_capabilities);
+        if !qp_range.contains(&(qp as i32)) {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "rate_control",
+                problem: format!(
+                    "QP is {qp}, should be between {} and {}.",
+                    qp_range.start(),
+                    qp_range.end()
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 impl std::fmt::Debug for VulkanDevice {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("VulkanDevice").finish()
     }
 }
 
-pub(crate) struct DecodingDevice {
+pub(crate) struct DecodingDevice<C: CodecCapabilities> {
     pub(crate) vulkan_device: Arc<VulkanDevice>,
-    pub(crate) h264_decode_queues: Arc<VideoQueues>,
-    pub(crate) profile_capabilities: NativeDecodeProfileCapabilities<H264Codec>,
+    pub(crate) decode_queues: Arc<VideoQueues>,
+    pub(crate) profile_capabilities: NativeDecodeProfileCapabilities<C>,
+    /// Used instead of `profile_capabilities` for 10-bit streams
+    pub(crate) profile_capabilities_10_bit: Option<NativeDecodeProfileCapabilities<C>>,
+    /// Used for 4:2:2 and 4:4:4 streams, one entry for every supported chroma subsampling and
+    /// bit depth
+    pub(crate) high_chroma_profile_capabilities: Vec<NativeDecodeProfileCapabilities<C>>,
+    /// Whether the device can decode interlaced H264 streams
+    pub(crate) interlaced_decoding: bool,
+}
+
+impl<C: CodecCapabilities> DecodingDevice<C> {
+    /// Returns the capabilities of the profile used to decode streams with the given chroma
+    /// subsampling and bit depth.
+    pub(crate) fn profile_capabilities_for(
+        &self,
+        chroma_subsampling: vk::VideoChromaSubsamplingFlagsKHR,
+        bit_depth: vk::VideoComponentBitDepthFlagsKHR,
+    ) -> Result<&NativeDecodeProfileCapabilities<C>, VulkanDecoderError> {
+        if chroma_subsampling != vk::VideoChromaSubsamplingFlagsKHR::TYPE_420 {
+            let picture_format = decode_picture_format(chroma_subsampling, bit_depth);
+            return self
+                .high_chroma_profile_capabilities
+                .iter()
+                .find(|caps| Some(caps.picture_format) == picture_format)
+                .ok_or_else(|| {
+                    VulkanDecoderError::InvalidInputData(format!(
+                        "the GPU cannot decode {chroma_subsampling:?} streams with {bit_depth:?} bit depth"
+                    ))
+                });
+        }
+
+        match bit_depth {
+            vk::VideoComponentBitDepthFlagsKHR::TYPE_10 => {
+                self.profile_capabilities_10_bit.as_ref().ok_or_else(|| {
+                    VulkanDecoderError::InvalidInputData(
+                        "the GPU cannot decode 10-bit streams".to_string(),
+                    )
+                })
+            }
+            _ => Ok(&self.profile_capabilities),
+        }
+    }
+
+    pub(crate) fn check_interlaced_decoding(
+        &self,
+        interlaced: bool,
+    ) -> Result<(), VulkanDecoderError> {
+        if interlaced && !self.interlaced_decoding {
+            return Err(VulkanDecoderError::InvalidInputData(
+                "the GPU cannot decode interlaced streams".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
-impl Deref for DecodingDevice {
+impl<C: CodecCapabilities> Deref for DecodingDevice<C> {
     type Target = VulkanDevice;
 
     fn deref(&self) -> &Self::Target {
@@ -551,6 +1014,7 @@ fn queue_from_device(
         family_index: queue_family_index.family_index,
         _video_properties: queue_family_index.video_properties,
         query_result_status_properties: queue_family_index.query_result_status_properties,
+        timestamp_valid_bits: queue_family_index.timestamp_valid_bits,
         device,
     }
 }
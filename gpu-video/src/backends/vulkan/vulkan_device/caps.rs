@@ -5,7 +5,9 @@ use ash::vk;
 use crate::backends::vulkan::{
     VulkanAdapterInitError,
     codec::{
-        CodecCapabilities, CodecSpecificEncoderQualityLevelProperties,
+        CodecCapabilities, CodecSpecificEncoderQualityLevelProperties, EncodeCodec,
+        EncodeCodecCapabilities,
+        av1::Av1Codec,
         h264::{H264Codec, parameters::vk_to_h264_level_idc},
         h265::{H265Codec, parameters::vk_to_h265_level_idc},
     },
@@ -13,14 +15,64 @@ use crate::backends::vulkan::{
     wrappers::*,
 };
 use crate::capabilities::{
-    DecodeCapabilities, DecodeH264Capabilities, DecodeH264ProfileCapabilities,
-    DecodeH265Capabilities, DecodeH265ProfileCapabilities, EncodeCapabilities,
-    EncodeH264Capabilities, EncodeH265Capabilities, EncodeProfileCapabilities,
-    RateControlCapabilities,
+    DecodeAv1Capabilities, DecodeAv1ProfileCapabilities, DecodeCapabilities,
+    DecodeH264Capabilities, DecodeH264ProfileCapabilities, DecodeH265Capabilities,
+    DecodeH265ProfileCapabilities, EncodeCapabilities, EncodeH264Capabilities,
+    EncodeH265Capabilities, EncodeProfileCapabilities, RateControlCapabilities,
 };
+use crate::parameters::ChromaSubsampling;
 use crate::parameters::H264Profile;
 use crate::parameters::H265Profile;
 
+/// Format of the pictures decoded in a profile with the given chroma subsampling and bit depth.
+/// The chroma samples are always stored interleaved in the second plane, like in NV12.
+pub(crate) fn decode_picture_format(
+    chroma_subsampling: vk::VideoChromaSubsamplingFlagsKHR,
+    bit_depth: vk::VideoComponentBitDepthFlagsKHR,
+) -> Option<vk::Format> {
+    use vk::VideoChromaSubsamplingFlagsKHR as Chroma;
+    use vk::VideoComponentBitDepthFlagsKHR as BitDepth;
+
+    let format = match (chroma_subsampling, bit_depth) {
+        (Chroma::TYPE_420, BitDepth::TYPE_8) => vk::Format::G8_B8R8_2PLANE_420_UNORM,
+        (Chroma::TYPE_420, BitDepth::TYPE_10) => {
+            vk::Format::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16
+        }
+        (Chroma::TYPE_422, BitDepth::TYPE_8) => vk::Format::G8_B8R8_2PLANE_422_UNORM,
+        (Chroma::TYPE_422, BitDepth::TYPE_10) => {
+            vk::Format::G10X6_B10X6R10X6_2PLANE_422_UNORM_3PACK16
+        }
+        (Chroma::TYPE_444, BitDepth::TYPE_8) => vk::Format::G8_B8R8_2PLANE_444_UNORM,
+        (Chroma::TYPE_444, BitDepth::TYPE_10) => {
+            vk::Format::G10X6_B10X6R10X6_2PLANE_444_UNORM_3PACK16
+        }
+        _ => return None,
+    };
+
+    Some(format)
+}
+
+/// Chroma subsampling of a format returned by [`decode_picture_format`].
+pub(crate) fn picture_format_chroma_subsampling(format: vk::Format) -> ChromaSubsampling {
+    match format {
+        vk::Format::G8_B8R8_2PLANE_422_UNORM
+        | vk::Format::G10X6_B10X6R10X6_2PLANE_422_UNORM_3PACK16 => ChromaSubsampling::Yuv422,
+        vk::Format::G8_B8R8_2PLANE_444_UNORM
+        | vk::Format::G10X6_B10X6R10X6_2PLANE_444_UNORM_3PACK16 => ChromaSubsampling::Yuv444,
+        _ => ChromaSubsampling::Yuv420,
+    }
+}
+
+/// Size of a single sample of a format returned by [`decode_picture_format`].
+pub(crate) fn picture_format_bytes_per_sample(format: vk::Format) -> u64 {
+    match format {
+        vk::Format::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16
+        | vk::Format::G10X6_B10X6R10X6_2PLANE_422_UNORM_3PACK16
+        | vk::Format::G10X6_B10X6R10X6_2PLANE_444_UNORM_3PACK16 => 2,
+        _ => 1,
+    }
+}
+
 pub(crate) fn query_video_format_properties<'a>(
     device: vk::PhysicalDevice,
     video_queue_instance_ext: &ash::khr::video_queue::Instance,
@@ -216,7 +268,7 @@ impl NativeEncodeH264Capabilities {
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
-pub(crate) struct NativeEncodeProfileCapabilities<C: CodecCapabilities> {
+pub(crate) struct NativeEncodeProfileCapabilities<C: EncodeCodecCapabilities> {
     pub(crate) video_capabilities: vk::VideoCapabilitiesKHR<'static>,
     pub(crate) encode_capabilities: vk::VideoEncodeCapabilitiesKHR<'static>,
     pub(crate) encode_dpb_properties: Vec<vk::VideoFormatPropertiesKHR<'static>>,
@@ -225,7 +277,7 @@ pub(crate) struct NativeEncodeProfileCapabilities<C: CodecCapabilities> {
     pub(crate) codec_encode_capabilities: C::CodecSpecificEncodeCapabilities<'static>,
 }
 
-impl<C: CodecCapabilities> NativeEncodeProfileCapabilities<C> {
+impl<C: EncodeCodecCapabilities> NativeEncodeProfileCapabilities<C> {
     fn query(
         instance: &Instance,
         device: vk::PhysicalDevice,
@@ -301,10 +353,20 @@ impl NativeEncodeProfileCapabilities<H264Codec> {
             max_width: self.video_capabilities.max_coded_extent.width,
             min_height: self.video_capabilities.min_coded_extent.height,
             max_height: self.video_capabilities.max_coded_extent.height,
+            max_dpb_slots: self.video_capabilities.max_dpb_slots,
             rate_control: self.encode_capabilities.rate_control_modes.into(),
             max_references: self
                 .codec_encode_capabilities
                 .max_p_picture_l0_reference_count,
+            b_frames_supported: self
+                .codec_encode_capabilities
+                .max_b_picture_l0_reference_count
+                > 0
+                && self.codec_encode_capabilities.max_l1_reference_count > 0,
+            max_slice_count: self.codec_encode_capabilities.max_slice_count,
+            intra_refresh_supported: H264Codec::supports_different_slice_types(
+                &self.codec_encode_capabilities,
+            ),
             quality_levels: self.encode_capabilities.max_quality_levels,
         }
     }
@@ -317,10 +379,20 @@ impl NativeEncodeProfileCapabilities<H265Codec> {
             max_width: self.video_capabilities.max_coded_extent.width,
             min_height: self.video_capabilities.min_coded_extent.height,
             max_height: self.video_capabilities.max_coded_extent.height,
+            max_dpb_slots: self.video_capabilities.max_dpb_slots,
             rate_control: self.encode_capabilities.rate_control_modes.into(),
             max_references: self
                 .codec_encode_capabilities
                 .max_p_picture_l0_reference_count,
+            b_frames_supported: self
+                .codec_encode_capabilities
+                .max_b_picture_l0_reference_count
+                > 0
+                && self.codec_encode_capabilities.max_l1_reference_count > 0,
+            max_slice_count: self.codec_encode_capabilities.max_slice_segment_count,
+            intra_refresh_supported: H265Codec::supports_different_slice_types(
+                &self.codec_encode_capabilities,
+            ),
             quality_levels: self.encode_capabilities.max_quality_levels,
         }
     }
@@ -331,18 +403,19 @@ impl From<vk::VideoEncodeRateControlModeFlagsKHR> for RateControlCapabilities {
         Self {
             vbr_supported: flags.contains(vk::VideoEncodeRateControlModeFlagsKHR::VBR),
             cbr_supported: flags.contains(vk::VideoEncodeRateControlModeFlagsKHR::CBR),
+            constant_qp_supported: flags.contains(vk::VideoEncodeRateControlModeFlagsKHR::DISABLED),
         }
     }
 }
 
 #[derive(Debug, Clone)]
-pub(crate) struct NativeEncodeQualityLevelProperties<C: CodecCapabilities> {
+pub(crate) struct NativeEncodeQualityLevelProperties<C: EncodeCodecCapabilities> {
     pub(crate) quality_level_properties: vk::VideoEncodeQualityLevelPropertiesKHR<'static>,
     pub(crate) codec_quality_level_properties:
         C::CodecSpecificEncodeQualityLevelProperties<'static>,
 }
 
-impl<C: CodecCapabilities> NativeEncodeQualityLevelProperties<C> {
+impl<C: EncodeCodecCapabilities> NativeEncodeQualityLevelProperties<C> {
     fn query(
         instance: &Instance,
         device: vk::PhysicalDevice,
@@ -398,6 +471,7 @@ impl<C: CodecCapabilities> NativeEncodeQualityLevelProperties<C> {
 pub(crate) struct NativeDecodeCapabilities {
     pub(crate) h264: Option<NativeDecodeH264Capabilities>,
     pub(crate) h265: Option<NativeDecodeH265Capabilities>,
+    pub(crate) av1: Option<NativeDecodeAv1Capabilities>,
 }
 
 impl NativeDecodeCapabilities {
@@ -418,7 +492,12 @@ impl NativeDecodeCapabilities {
             false => None,
         };
 
-        Self { h264, h265 }
+        let av1 = match supported_operations.contains(vk::VideoCodecOperationFlagsKHR::DECODE_AV1) {
+            true => Some(NativeDecodeAv1Capabilities::query(instance, device)),
+            false => None,
+        };
+
+        Self { h264, h265, av1 }
     }
 
     pub(crate) fn user_facing(&self) -> DecodeCapabilities {
@@ -431,10 +510,44 @@ impl NativeDecodeCapabilities {
                 .h265
                 .as_ref()
                 .map(NativeDecodeH265Capabilities::user_facing),
+            av1: self
+                .av1
+                .as_ref()
+                .map(NativeDecodeAv1Capabilities::user_facing),
         }
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct NativeDecodeAv1Capabilities {
+    pub(crate) main: Option<NativeDecodeProfileCapabilities<Av1Codec>>,
+}
+
+impl NativeDecodeAv1Capabilities {
+    pub(crate) fn user_facing(&self) -> DecodeAv1Capabilities {
+        DecodeAv1Capabilities {
+            main_profile: self.main.as_ref().map(|profile| profile.user_facing()),
+        }
+    }
+
+    fn query(instance: &Instance, device: vk::PhysicalDevice) -> Self {
+        let profile = vk::VideoProfileInfoKHR::default()
+            .video_codec_operation(vk::VideoCodecOperationFlagsKHR::DECODE_AV1)
+            .chroma_subsampling(vk::VideoChromaSubsamplingFlagsKHR::TYPE_420)
+            .luma_bit_depth(vk::VideoComponentBitDepthFlagsKHR::TYPE_8)
+            .chroma_bit_depth(vk::VideoComponentBitDepthFlagsKHR::TYPE_8);
+
+        let mut av1_profile_info = vk::VideoDecodeAV1ProfileInfoKHR::default()
+            .std_profile(vk::native::StdVideoAV1Profile_STD_VIDEO_AV1_PROFILE_MAIN)
+            .film_grain_support(false);
+
+        let profile = profile.push_next(&mut av1_profile_info);
+        let main = NativeDecodeProfileCapabilities::query(instance, device, &profile).ok();
+
+        Self { main }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct NativeDecodeH265Capabilities {
     pub(crate) main: Option<NativeDecodeProfileCapabilities<H265Codec>>,
@@ -476,6 +589,12 @@ pub(crate) struct NativeDecodeH264Capabilities {
     pub(crate) baseline: Option<NativeDecodeProfileCapabilities<H264Codec>>,
     pub(crate) main: Option<NativeDecodeProfileCapabilities<H264Codec>>,
     pub(crate) high: Option<NativeDecodeProfileCapabilities<H264Codec>>,
+    pub(crate) high10: Option<NativeDecodeProfileCapabilities<H264Codec>>,
+    /// Capabilities for 4:2:2 and 4:4:4 streams with the chroma subsampling and bit depths
+    /// supported by the device, decoded with the High 4:4:4 Predictive profile.
+    pub(crate) high_chroma: Vec<NativeDecodeProfileCapabilities<H264Codec>>,
+    /// Whether interlaced High profile streams can be decoded
+    pub(crate) interlaced: bool,
 }
 
 impl NativeDecodeH264Capabilities {
@@ -493,9 +612,29 @@ impl NativeDecodeH264Capabilities {
                 .high
                 .as_ref()
                 .and_then(|profile| profile.user_facing().ok()),
+            high10_profile: self
+                .high10
+                .as_ref()
+                .and_then(|profile| profile.user_facing().ok()),
+            high422_profile: self.high_chroma_profile(ChromaSubsampling::Yuv422),
+            high444_profile: self.high_chroma_profile(ChromaSubsampling::Yuv444),
+            interlaced: self.interlaced,
         }
     }
 
+    /// Capabilities for the given chroma subsampling with the lowest supported bit depth.
+    fn high_chroma_profile(
+        &self,
+        chroma_subsampling: ChromaSubsampling,
+    ) -> Option<DecodeH264ProfileCapabilities> {
+        self.high_chroma
+            .iter()
+            .find(|profile| {
+                picture_format_chroma_subsampling(profile.picture_format) == chroma_subsampling
+            })
+            .and_then(|profile| profile.user_facing().ok())
+    }
+
     fn query(instance: &Instance, device: vk::PhysicalDevice) -> Self {
         let profile = vk::VideoProfileInfoKHR::default()
             .video_codec_operation(vk::VideoCodecOperationFlagsKHR::DECODE_H264)
@@ -503,24 +642,82 @@ impl NativeDecodeH264Capabilities {
             .luma_bit_depth(vk::VideoComponentBitDepthFlagsKHR::TYPE_8)
             .chroma_bit_depth(vk::VideoComponentBitDepthFlagsKHR::TYPE_8);
 
-        let profile_caps = |profile_idc| {
+        let profile_caps = |profile: vk::VideoProfileInfoKHR, profile_idc, picture_layout| {
             let mut h264_profile_info = vk::VideoDecodeH264ProfileInfoKHR::default()
-                .picture_layout(vk::VideoDecodeH264PictureLayoutFlagsKHR::PROGRESSIVE)
+                .picture_layout(picture_layout)
                 .std_profile_idc(profile_idc);
 
             let profile = profile.push_next(&mut h264_profile_info);
             NativeDecodeProfileCapabilities::query(instance, device, &profile).ok()
         };
 
-        let baseline =
-            profile_caps(vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_BASELINE);
-        let main = profile_caps(vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_MAIN);
-        let high = profile_caps(vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_HIGH);
+        let baseline = profile_caps(
+            profile,
+            vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_BASELINE,
+            vk::VideoDecodeH264PictureLayoutFlagsKHR::PROGRESSIVE,
+        );
+        let main = profile_caps(
+            profile,
+            vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_MAIN,
+            vk::VideoDecodeH264PictureLayoutFlagsKHR::PROGRESSIVE,
+        );
+        let high = profile_caps(
+            profile,
+            vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_HIGH,
+            vk::VideoDecodeH264PictureLayoutFlagsKHR::PROGRESSIVE,
+        );
+
+        // Vulkan has no profile idc for High 10, streams of this profile are decoded with
+        // High 4:4:4 Predictive, which is a superset of it.
+        let high10 = profile_caps(
+            profile
+                .luma_bit_depth(vk::VideoComponentBitDepthFlagsKHR::TYPE_10)
+                .chroma_bit_depth(vk::VideoComponentBitDepthFlagsKHR::TYPE_10),
+            vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_HIGH_444_PREDICTIVE,
+            vk::VideoDecodeH264PictureLayoutFlagsKHR::PROGRESSIVE,
+        );
+
+        // High 4:2:2 streams are also decoded with High 4:4:4 Predictive, which has no dedicated
+        // profile idc in Vulkan either
+        let high_chroma = [
+            vk::VideoChromaSubsamplingFlagsKHR::TYPE_422,
+            vk::VideoChromaSubsamplingFlagsKHR::TYPE_444,
+        ]
+        .into_iter()
+        .flat_map(|chroma_subsampling| {
+            [
+                vk::VideoComponentBitDepthFlagsKHR::TYPE_8,
+                vk::VideoComponentBitDepthFlagsKHR::TYPE_10,
+            ]
+            .map(|bit_depth| (chroma_subsampling, bit_depth))
+        })
+        .filter_map(|(chroma_subsampling, bit_depth)| {
+            profile_caps(
+                profile
+                    .chroma_subsampling(chroma_subsampling)
+                    .luma_bit_depth(bit_depth)
+                    .chroma_bit_depth(bit_depth),
+                vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_HIGH_444_PREDICTIVE,
+                vk::VideoDecodeH264PictureLayoutFlagsKHR::PROGRESSIVE,
+            )
+        })
+        .collect();
+
+        // fields are decoded into alternating lines of their frames
+        let interlaced = profile_caps(
+            profile,
+            vk::native::StdVideoH264ProfileIdc_STD_VIDEO_H264_PROFILE_IDC_HIGH,
+            vk::VideoDecodeH264PictureLayoutFlagsKHR::INTERLACED_INTERLEAVED_LINES,
+        )
+        .is_some();
 
         Self {
             baseline,
             main,
             high,
+            high10,
+            high_chroma,
+            interlaced,
         }
     }
 
@@ -556,6 +753,12 @@ pub(crate) struct NativeDecodeProfileCapabilities<C: CodecCapabilities> {
     pub(crate) codec_decode_capabilities: C::CodecSpecificDecodeCapabilities<'static>,
     pub(crate) dpb_format_properties: vk::VideoFormatPropertiesKHR<'static>,
     pub(crate) dst_format_properties: Option<vk::VideoFormatPropertiesKHR<'static>>,
+    /// Format of the decoded pictures, see [`decode_picture_format`]. NV12 for 8-bit 4:2:0
+    /// profiles and P010 for 10-bit ones.
+    pub(crate) picture_format: vk::Format,
+    /// Whether the output pictures can be created with storage usage, which is needed to read
+    /// them in the pixel format conversion and deinterlacing compute shaders.
+    pub(crate) output_storage_supported: bool,
 }
 
 impl<C: CodecCapabilities> NativeDecodeProfileCapabilities<C> {
@@ -625,35 +828,84 @@ impl<C: CodecCapabilities> NativeDecodeProfileCapabilities<C> {
                 )?)
             };
 
+        let picture_format =
+            decode_picture_format(profile.chroma_subsampling, profile.luma_bit_depth).ok_or(
+                VulkanAdapterInitError::UnsupportedProfileFormat(
+                    profile.chroma_subsampling,
+                    profile.luma_bit_depth,
+                ),
+            )?;
+        let unsupported_format_error = match picture_format {
+            vk::Format::G8_B8R8_2PLANE_420_UNORM => VulkanAdapterInitError::NoNV12ProfileSupport,
+            vk::Format::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16 => {
+                VulkanAdapterInitError::NoP010ProfileSupport
+            }
+            format => VulkanAdapterInitError::NoPictureFormatSupport(format),
+        };
+
         let dpb_format_properties = match dpb_format_properties
             .into_iter()
-            .find(|f| f.format == vk::Format::G8_B8R8_2PLANE_420_UNORM)
+            .find(|f| f.format == picture_format)
         {
             Some(f) => f,
-            None => return Err(VulkanAdapterInitError::NoNV12ProfileSupport),
+            None => return Err(unsupported_format_error),
         };
 
         let dst_format_properties = match dst_format_properties {
             Some(format_properties) => match format_properties
                 .into_iter()
-                .find(|f| f.format == vk::Format::G8_B8R8_2PLANE_420_UNORM)
+                .find(|f| f.format == picture_format)
             {
                 Some(f) => Some(f),
-                None => return Err(VulkanAdapterInitError::NoNV12ProfileSupport),
+                None => return Err(unsupported_format_error),
             },
             None => None,
         };
 
+        let output_usage = match dst_format_properties {
+            Some(_) => {
+                vk::ImageUsageFlags::VIDEO_DECODE_DST_KHR | vk::ImageUsageFlags::TRANSFER_SRC
+            }
+            None => {
+                vk::ImageUsageFlags::VIDEO_DECODE_DST_KHR
+                    | vk::ImageUsageFlags::VIDEO_DECODE_DPB_KHR
+                    | vk::ImageUsageFlags::TRANSFER_SRC
+            }
+        };
+        let output_storage_supported = query_video_format_properties(
+            device,
+            &instance.video_queue_instance_ext,
+            profile,
+            output_usage | vk::ImageUsageFlags::STORAGE,
+        )
+        .is_ok_and(|formats| formats.iter().any(|f| f.format == picture_format));
+
         Ok(Self {
             video_capabilities,
             decode_capabilities,
             codec_decode_capabilities,
             dpb_format_properties,
             dst_format_properties,
+            picture_format,
+            output_storage_supported,
         })
     }
 }
 
+impl NativeDecodeProfileCapabilities<Av1Codec> {
+    pub(crate) fn user_facing(&self) -> DecodeAv1ProfileCapabilities {
+        DecodeAv1ProfileCapabilities {
+            min_width: self.video_capabilities.min_coded_extent.width,
+            max_width: self.video_capabilities.max_coded_extent.width,
+            min_height: self.video_capabilities.min_coded_extent.height,
+            max_height: self.video_capabilities.max_coded_extent.height,
+            max_dpb_slots: self.video_capabilities.max_dpb_slots,
+            // `StdVideoAV1Level` values are equal to `seq_level_idx`
+            max_level: self.codec_decode_capabilities.max_level as u8,
+        }
+    }
+}
+
 impl NativeDecodeProfileCapabilities<H265Codec> {
     pub(crate) fn user_facing(&self) -> Result<DecodeH265ProfileCapabilities, VulkanDecoderError> {
         Ok(DecodeH265ProfileCapabilities {
@@ -661,6 +913,7 @@ impl NativeDecodeProfileCapabilities<H265Codec> {
             max_width: self.video_capabilities.max_coded_extent.width,
             min_height: self.video_capabilities.min_coded_extent.height,
             max_height: self.video_capabilities.max_coded_extent.height,
+            max_dpb_slots: self.video_capabilities.max_dpb_slots,
             max_level_idc: vk_to_h265_level_idc(self.codec_decode_capabilities.max_level_idc)?,
         })
     }
@@ -673,6 +926,7 @@ impl NativeDecodeProfileCapabilities<H264Codec> {
             max_width: self.video_capabilities.max_coded_extent.width,
             min_height: self.video_capabilities.min_coded_extent.height,
             max_height: self.video_capabilities.max_coded_extent.height,
+            max_dpb_slots: self.video_capabilities.max_dpb_slots,
             max_level_idc: vk_to_h264_level_idc(self.codec_decode_capabilities.max_level_idc)?,
         })
     }
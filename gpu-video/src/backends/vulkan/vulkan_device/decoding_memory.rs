@@ -0,0 +1,235 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Instant;
+
+use crate::backends::vulkan::VulkanDecoderError;
+use crate::device::{MemoryBudget, MemoryBudgetExceededStrategy};
+
+/// Sum of the estimated sizes of decoding images of all decoders created on a device, checked
+/// against [`MemoryBudget`] before new images are allocated.
+#[derive(Default)]
+pub(crate) struct DecodingMemoryTracker {
+    usage: Mutex<MemoryUsage>,
+    released: Condvar,
+}
+
+#[derive(Default)]
+struct MemoryUsage {
+    allocated: u64,
+    /// Part of `allocated` held by decoders waiting for the budget. They keep their images
+    /// until they get the memory for the new ones, so this memory can't be released in time
+    /// for another waiting decoder.
+    held_by_waiting: u64,
+}
+
+impl DecodingMemoryTracker {
+    /// `replaced` is the reservation of the images the new ones are going to replace. Its size
+    /// is not counted against the budget, because the old images are dropped right after the
+    /// new ones are created.
+    pub(crate) fn reserve(
+        self: &Arc<Self>,
+        bytes: u64,
+        budget: Option<MemoryBudget>,
+        replaced: Option<&DecodingMemoryReservation>,
+    ) -> Result<DecodingMemoryReservation, VulkanDecoderError> {
+        let replaced = replaced.map(|r| r.bytes).unwrap_or(0);
+        let mut usage = self.usage.lock().unwrap();
+
+        if let Some(budget) = budget {
+            if bytes > budget.bytes {
+                return Err(VulkanDecoderError::MemoryBudgetExceeded {
+                    required: bytes,
+                    budget: budget.bytes,
+                });
+            }
+
+            let fits = |allocated: u64| allocated - replaced + bytes <= budget.bytes;
+            match budget.when_exceeded {
+                MemoryBudgetExceededStrategy::Fail => {}
+                MemoryBudgetExceededStrategy::Wait { timeout } if !fits(usage.allocated) => {
+                    usage.held_by_waiting += replaced;
+                    // the memory other waiting decoders counted on might not be released anymore
+                    self.released.notify_all();
+
+                    let deadline = Instant::now() + timeout;
+                    // Stop waiting when the images would not fit even if all decoders that are
+                    // not waiting released their memory.
+                    while !fits(usage.allocated) && fits(usage.held_by_waiting) {
+                        let now = Instant::now();
+                        if now >= deadline {
+                            break;
+                        }
+                        usage = self.released.wait_timeout(usage, deadline - now).unwrap().0;
+                    }
+
+                    usage.held_by_waiting -= replaced;
+                    self.released.notify_all();
+                }
+                MemoryBudgetExceededStrategy::Wait { .. } => {}
+            }
+
+            if !fits(usage.allocated) {
+                return Err(VulkanDecoderError::MemoryBudgetExceeded {
+                    required: usage.allocated - replaced + bytes,
+                    budget: budget.bytes,
+                });
+            }
+        }
+
+        usage.allocated += bytes;
+        Ok(DecodingMemoryReservation {
+            tracker: self.clone(),
+            bytes,
+        })
+    }
+}
+
+/// Estimated size of decoding images counted in the [`DecodingMemoryTracker`] until dropped.
+pub(crate) struct DecodingMemoryReservation {
+    tracker: Arc<DecodingMemoryTracker>,
+    bytes: u64,
+}
+
+impl Drop for DecodingMemoryReservation {
+    fn drop(&mut self) {
+        self.tracker.usage.lock().unwrap().allocated -= self.bytes;
+        self.tracker.released.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::*;
+
+    fn budget(bytes: u64, when_exceeded: MemoryBudgetExceededStrategy) -> Option<MemoryBudget> {
+        Some(MemoryBudget {
+            bytes,
+            when_exceeded,
+        })
+    }
+
+    fn wait(timeout_ms: u64) -> MemoryBudgetExceededStrategy {
+        MemoryBudgetExceededStrategy::Wait {
+            timeout: Duration::from_millis(timeout_ms),
+        }
+    }
+
+    fn allocated(tracker: &DecodingMemoryTracker) -> u64 {
+        tracker.usage.lock().unwrap().allocated
+    }
+
+    #[test]
+    fn fail_when_budget_exceeded() {
+        let tracker = Arc::new(DecodingMemoryTracker::default());
+        let fail = budget(100, MemoryBudgetExceededStrategy::Fail);
+
+        let _first = tracker.reserve(60, fail, None).unwrap();
+        let result = tracker.reserve(60, fail, None);
+        assert!(matches!(
+            result,
+            Err(VulkanDecoderError::MemoryBudgetExceeded {
+                required: 120,
+                budget: 100
+            })
+        ));
+        // more than the whole budget fails without waiting
+        assert!(
+            tracker
+                .reserve(101, budget(100, wait(10_000)), None)
+                .is_err()
+        );
+
+        // decoders without a budget are counted, but never fail
+        let _unlimited = tracker.reserve(1000, None, None).unwrap();
+        assert_eq!(allocated(&tracker), 1060);
+    }
+
+    #[test]
+    fn release_on_drop() {
+        let tracker = Arc::new(DecodingMemoryTracker::default());
+        let first = tracker.reserve(60, None, None).unwrap();
+        let second = tracker.reserve(30, None, None).unwrap();
+        assert_eq!(allocated(&tracker), 90);
+
+        drop(first);
+        assert_eq!(allocated(&tracker), 30);
+        drop(second);
+        assert_eq!(allocated(&tracker), 0);
+    }
+
+    #[test]
+    fn replaced_images_are_not_counted() {
+        let tracker = Arc::new(DecodingMemoryTracker::default());
+        let fail = budget(100, MemoryBudgetExceededStrategy::Fail);
+
+        let old = tracker.reserve(60, fail, None).unwrap();
+        let new = tracker.reserve(90, fail, Some(&old)).unwrap();
+        assert_eq!(allocated(&tracker), 150);
+        drop(old);
+        assert_eq!(allocated(&tracker), 90);
+
+        assert!(tracker.reserve(20, fail, Some(&new)).is_ok());
+        assert!(tracker.reserve(20, fail, None).is_err());
+    }
+
+    #[test]
+    fn wait_until_memory_is_released() {
+        let tracker = Arc::new(DecodingMemoryTracker::default());
+        let other = tracker.reserve(60, None, None).unwrap();
+
+        let release = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            drop(other);
+        });
+        let start = Instant::now();
+        let reservation = tracker
+            .reserve(60, budget(100, wait(10_000)), None)
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert_eq!(allocated(&tracker), 60);
+
+        release.join().unwrap();
+        drop(reservation);
+    }
+
+    #[test]
+    fn wait_times_out() {
+        let tracker = Arc::new(DecodingMemoryTracker::default());
+        let _other = tracker.reserve(60, None, None).unwrap();
+
+        let start = Instant::now();
+        let result = tracker.reserve(60, budget(100, wait(50)), None);
+        assert!(result.is_err());
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert_eq!(allocated(&tracker), 60);
+    }
+
+    #[test]
+    fn waiting_decoders_do_not_wait_for_each_other() {
+        let tracker = Arc::new(DecodingMemoryTracker::default());
+        let budget = budget(100, wait(10_000));
+        let first_old = tracker.reserve(50, budget, None).unwrap();
+        let second_old = tracker.reserve(50, budget, None).unwrap();
+
+        // Both decoders grow at the same time, each one could only get the memory held by
+        // the other one.
+        let first_tracker = tracker.clone();
+        let first =
+            thread::spawn(move || first_tracker.reserve(70, budget, Some(&first_old)).is_ok());
+        while tracker.usage.lock().unwrap().held_by_waiting == 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        // The second decoder fails right away instead of waiting for the first one
+        let start = Instant::now();
+        assert!(tracker.reserve(70, budget, Some(&second_old)).is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // The first one gets the memory when the second one is dropped after the error
+        drop(second_old);
+        assert!(first.join().unwrap());
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(tracker.usage.lock().unwrap().held_by_waiting, 0);
+    }
+}
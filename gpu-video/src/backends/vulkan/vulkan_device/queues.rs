@@ -13,6 +13,7 @@ pub(crate) struct Queue {
     pub(crate) _video_properties: vk::QueueFamilyVideoPropertiesKHR<'static>,
     pub(crate) query_result_status_properties:
         vk::QueueFamilyQueryResultStatusPropertiesKHR<'static>,
+    pub(crate) timestamp_valid_bits: u32,
     pub(crate) device: Arc<Device>,
 }
 
@@ -62,9 +63,8 @@ impl Queue {
 
 pub(crate) struct Queues {
     pub(crate) transfer: Queue,
-    #[cfg_attr(not(feature = "transcoder"), allow(dead_code))]
     pub(crate) compute: Queue,
-    pub(crate) h264_decode: Option<Arc<VideoQueues>>,
+    pub(crate) decode: Option<Arc<VideoQueues>>,
     pub(crate) encode: Option<Arc<VideoQueues>>,
     pub(crate) wgpu: Queue,
 }
@@ -74,12 +74,13 @@ pub(crate) struct QueueIndex<'a> {
     pub(crate) queue_count: usize,
     pub(crate) video_properties: vk::QueueFamilyVideoPropertiesKHR<'a>,
     pub(crate) query_result_status_properties: vk::QueueFamilyQueryResultStatusPropertiesKHR<'a>,
+    pub(crate) timestamp_valid_bits: u32,
 }
 
 pub(crate) struct QueueIndices<'a> {
     pub(crate) transfer: QueueIndex<'a>,
     pub(crate) compute: QueueIndex<'a>,
-    pub(crate) h264_decode: Option<QueueIndex<'a>>,
+    pub(crate) decode: Option<QueueIndex<'a>>,
     pub(crate) encode: Option<QueueIndex<'a>>,
     pub(crate) graphics_transfer_compute: QueueIndex<'a>,
 }
@@ -87,7 +88,7 @@ pub(crate) struct QueueIndices<'a> {
 impl QueueIndices<'_> {
     pub(crate) fn queue_create_infos(&self) -> Vec<QueueCreateInfo> {
         [
-            self.h264_decode
+            self.decode
                 .as_ref()
                 .map(|q| (q.family_index, q.queue_count)),
             self.encode
@@ -111,8 +112,8 @@ impl QueueIndices<'_> {
 }
 
 pub(crate) struct QueueCreateInfo {
-    family_idx: usize,
-    priorities: Box<[f32]>,
+    pub(crate) family_idx: usize,
+    pub(crate) priorities: Box<[f32]>,
 }
 
 impl QueueCreateInfo {
@@ -161,6 +162,11 @@ impl VideoQueues {
         self.queues[0].supports_result_status_queries()
     }
 
+    pub(crate) fn timestamp_valid_bits(&self) -> u32 {
+        // All queues from the same family share the same properties
+        self.queues[0].timestamp_valid_bits
+    }
+
     pub(crate) fn submit_chain_semaphore<K: TrackerKind>(
         &self,
         buffer: RecordedCommandBuffer,
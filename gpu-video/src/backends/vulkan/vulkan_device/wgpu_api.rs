@@ -5,21 +5,29 @@ use wgpu::hal::vulkan::Api as VkApi;
 
 use crate::{
     VideoDecoderError, VideoEncoderError, WgpuInitError, WgpuTexturesDecoder,
+    WgpuTexturesDecoderAv1, WgpuTexturesDecoderH265,
     backends::vulkan::vulkan_encoder::{VulkanEncoder, VulkanEncoderError},
     backends::{
         WgpuBackend,
         vulkan::{
             VulkanAdapter, VulkanBackend, VulkanDevice, VulkanDeviceInitError,
-            vulkan_decoder::{ImageModifiers, VulkanDecoder, VulkanDecoderError},
+            vulkan_decoder::{VulkanDecoder, VulkanDecoderError},
         },
     },
+    decoders::{sei::SeiTracker, stats::DecoderStatsTracker},
     device::{
         DecoderParameters, EncoderParametersH264, EncoderParametersH265, VideoDeviceDescriptor,
         WgpuVideoDeviceBackend,
     },
     frame_sorter::FrameSorter,
     global_registry::GlobalRegistry,
-    parser::{h264::H264Parser, reference_manager::ReferenceContext},
+    parameters::{Deinterlacing, PixelFormat},
+    parser::{
+        av1::{self, Av1Parser},
+        h264::H264Parser,
+        h265::{self, H265Parser},
+        reference_manager::ReferenceContext,
+    },
 };
 
 impl WgpuVideoDeviceBackend for VulkanDevice {
@@ -32,6 +40,24 @@ impl WgpuVideoDeviceBackend for VulkanDevice {
             .map_err(Into::into)
     }
 
+    fn create_wgpu_textures_decoder_h265(
+        self: Arc<Self>,
+        wgpu_device: wgpu::Device,
+        parameters: DecoderParameters,
+    ) -> Result<crate::WgpuTexturesDecoderH265, VideoDecoderError> {
+        VulkanDevice::create_wgpu_textures_decoder_h265(self, wgpu_device, parameters)
+            .map_err(Into::into)
+    }
+
+    fn create_wgpu_textures_decoder_av1(
+        self: Arc<Self>,
+        wgpu_device: wgpu::Device,
+        parameters: DecoderParameters,
+    ) -> Result<crate::WgpuTexturesDecoderAv1, VideoDecoderError> {
+        VulkanDevice::create_wgpu_textures_decoder_av1(self, wgpu_device, parameters)
+            .map_err(Into::into)
+    }
+
     fn create_wgpu_textures_encoder_h264(
         self: Arc<Self>,
         wgpu_device: wgpu::Device,
@@ -53,6 +79,24 @@ impl WgpuVideoDeviceBackend for VulkanDevice {
     }
 }
 
+/// Decoded frames can be output only in pixel formats which have a matching texture format.
+/// [`PixelFormat::Nv12`] and [`PixelFormat::P010`] both output the decoded frames as they are.
+fn validate_texture_pixel_format(pixel_format: PixelFormat) -> Result<(), VulkanDecoderError> {
+    match pixel_format {
+        PixelFormat::Nv12 | PixelFormat::P010 | PixelFormat::Rgba => Ok(()),
+        PixelFormat::I420 | PixelFormat::Yuyv => Err(
+            VulkanDecoderError::UnsupportedTexturePixelFormat(pixel_format),
+        ),
+    }
+}
+
+/// Features needed for textures with decoded frames. P010 textures are only used for 10-bit
+/// streams, so they are enabled only if the adapter supports them.
+fn video_wgpu_features(wgpu_adapter: &wgpu::Adapter) -> wgpu::Features {
+    wgpu::Features::TEXTURE_FORMAT_NV12
+        | (wgpu_adapter.features() & wgpu::Features::TEXTURE_FORMAT_P010)
+}
+
 impl VulkanDevice {
     pub(crate) fn create_and_register_wgpu(
         wgpu_adapter: &wgpu::Adapter,
@@ -67,7 +111,7 @@ impl VulkanDevice {
             .family_index as u32;
         let mut required_extensions = video_adapter.required_extensions();
 
-        let wgpu_features = desc.wgpu_features | wgpu::Features::TEXTURE_FORMAT_NV12;
+        let wgpu_features = desc.wgpu_features | video_wgpu_features(wgpu_adapter);
         let mut wgpu_extensions = hal_adapter.required_device_extensions(wgpu_features);
         required_extensions.append(&mut wgpu_extensions);
 
@@ -90,7 +134,7 @@ impl VulkanDevice {
             wgpu_limits,
         } = desc;
 
-        let wgpu_features = wgpu_features | wgpu::Features::TEXTURE_FORMAT_NV12;
+        let wgpu_features = wgpu_features | video_wgpu_features(wgpu_adapter);
         let device_key_for_dropping = Arc::new(OnceLock::new());
         let device_key_for_dropping_clone = device_key_for_dropping.clone();
 
@@ -150,19 +194,20 @@ impl VulkanDevice {
         wgpu_device: wgpu::Device,
         parameters: DecoderParameters,
     ) -> Result<WgpuTexturesDecoder, VulkanDecoderError> {
+        validate_texture_pixel_format(parameters.output_pixel_format)?;
         let parser = H264Parser::default();
-        let reference_ctx = ReferenceContext::new(parameters.missed_frame_handling);
+        let reference_ctx =
+            ReferenceContext::new(parameters.missed_frame_handling, parameters.keyframes_only);
 
         let vulkan_decoder = VulkanDecoder::new(
             Arc::new(self.decoding_device()?),
             parameters.usage_flags,
-            ImageModifiers {
-                additional_queue_index: self.queues.transfer.family_index,
-                create_flags: Default::default(),
-                usage_flags: Default::default(),
-            },
+            parameters.enable_gpu_timing,
+            self.decoder_image_modifiers(&parameters, parameters.deinterlacing),
+            parameters.output_pixel_format,
+            parameters.deinterlacing,
         )?;
-        let frame_sorter = FrameSorter::<wgpu::Texture>::new();
+        let frame_sorter = FrameSorter::<wgpu::Texture>::from_parameters(&parameters);
 
         Ok(crate::WgpuTexturesDecoder {
             wgpu_device,
@@ -170,6 +215,76 @@ impl VulkanDevice {
             reference_ctx,
             decoder: Box::new(vulkan_decoder),
             frame_sorter,
+            stats: DecoderStatsTracker::new(),
+            sei: SeiTracker::new(parameters.display_orientation),
+            frame_pool: None,
+        })
+    }
+
+    pub fn create_wgpu_textures_decoder_h265(
+        self: Arc<Self>,
+        wgpu_device: wgpu::Device,
+        parameters: DecoderParameters,
+    ) -> Result<WgpuTexturesDecoderH265, VulkanDecoderError> {
+        validate_texture_pixel_format(parameters.output_pixel_format)?;
+        let parser = H265Parser::default();
+        let reference_ctx = h265::reference_manager::ReferenceContext::new(
+            parameters.missed_frame_handling,
+            parameters.keyframes_only,
+        );
+
+        let vulkan_decoder = VulkanDecoder::new(
+            Arc::new(self.decoding_device_h265()?),
+            parameters.usage_flags,
+            parameters.enable_gpu_timing,
+            self.decoder_image_modifiers(&parameters, Deinterlacing::Off),
+            parameters.output_pixel_format,
+            Deinterlacing::Off,
+        )?;
+        let frame_sorter = FrameSorter::<wgpu::Texture>::from_parameters(&parameters);
+
+        Ok(WgpuTexturesDecoderH265 {
+            wgpu_device,
+            parser,
+            reference_ctx,
+            decoder: Box::new(vulkan_decoder),
+            frame_sorter,
+            stats: DecoderStatsTracker::new(),
+            sei: SeiTracker::new(parameters.display_orientation),
+            frame_pool: None,
+        })
+    }
+
+    pub fn create_wgpu_textures_decoder_av1(
+        self: Arc<Self>,
+        wgpu_device: wgpu::Device,
+        parameters: DecoderParameters,
+    ) -> Result<WgpuTexturesDecoderAv1, VulkanDecoderError> {
+        validate_texture_pixel_format(parameters.output_pixel_format)?;
+        let parser = Av1Parser::default();
+        let reference_ctx = av1::reference_manager::ReferenceContext::new(
+            parameters.missed_frame_handling,
+            parameters.keyframes_only,
+        );
+
+        let vulkan_decoder = VulkanDecoder::new(
+            Arc::new(self.decoding_device_av1()?),
+            parameters.usage_flags,
+            parameters.enable_gpu_timing,
+            self.decoder_image_modifiers(&parameters, Deinterlacing::Off),
+            parameters.output_pixel_format,
+            Deinterlacing::Off,
+        )?;
+
+        Ok(WgpuTexturesDecoderAv1 {
+            wgpu_device,
+            parser,
+            reference_ctx,
+            decoder: Box::new(vulkan_decoder),
+            frame_sorter: FrameSorter::<wgpu::Texture>::new_in_decode_order(),
+            stats: DecoderStatsTracker::new(),
+            sei: SeiTracker::new(parameters.display_orientation),
+            frame_pool: None,
         })
     }
 
@@ -193,6 +308,7 @@ impl VulkanDevice {
                 Arc::new(self.encoding_device()?),
                 parameters,
             )?),
+            rgba_input_converter: None,
         })
     }
 
@@ -216,6 +332,7 @@ impl VulkanDevice {
                 Arc::new(self.encoding_device()?),
                 parameters,
             )?),
+            rgba_input_converter: None,
         })
     }
 }
@@ -1,6 +1,7 @@
 use std::{
     collections::VecDeque,
     num::NonZeroU32,
+    ops::RangeInclusive,
     sync::{Arc, Mutex},
 };
 
@@ -16,22 +17,27 @@ use crate::{
             h264::{H264Codec, encode::H264WriteParametersInfo},
             h265::{H265Codec, encode::H265WriteParametersInfo},
         },
-        vulkan_device::EncodingDevice,
+        vulkan_device::{EncodingDevice, validate_rate_control},
         wrappers::{
             Buffer, CommandBufferPool, CommandBufferPoolStorage, DecodedPicturesBuffer, Image,
             ImageLayoutTracker, ImageView, OpenCommandBuffer, ProfileInfo, QueryPool,
-            SemaphoreWaitValue, Tracker, TrackerKind, VideoEncodeQueueExt, VideoQueueExt,
-            VideoSession, VideoSessionParameters,
+            SemaphoreWaitValue, TimestampQueryPool, Tracker, TrackerKind, VideoEncodeQueueExt,
+            VideoQueueExt, VideoSession, VideoSessionParameters,
         },
     },
     device::{ColorRange, ColorSpace, Rational},
     encoders::{
-        VideoEncoderBackend, VideoEncoderError, VideoEncoderParametersInfoH264,
+        EncoderStats, VideoEncoderBackend, VideoEncoderError, VideoEncoderParametersInfoH264,
         VideoEncoderParametersInfoH265,
+        stats::{EncoderStatsCallback, EncoderStatsTracker},
     },
-    parameters::RateControl,
+    parameters::{PixelFormat, RateControl},
 };
 
+mod input_converter;
+
+use input_converter::{InputConversion, InputConverter};
+
 const MB: u64 = 1024 * 1024;
 
 #[derive(Debug, thiserror::Error)]
@@ -134,9 +140,9 @@ impl VideoSessionResources<'_> {
             parameters.profile,
         )?;
 
-        let extent = vk::Extent2D {
-            width: parameters.width.get(),
-            height: parameters.height.get(),
+        let max_extent = vk::Extent2D {
+            width: parameters.max_width.get(),
+            height: parameters.max_height.get(),
         };
 
         let max_references = parameters.max_references.get();
@@ -146,11 +152,12 @@ impl VideoSessionResources<'_> {
             &encoding_device.vulkan_device,
             &encoding_device.encode_queues,
             profile_info,
-            extent,
+            max_extent,
             max_dpb_slots,
             max_references,
             vk::VideoSessionCreateFlagsKHR::ALLOW_ENCODE_PARAMETER_OPTIMIZATIONS,
             &encode_capabilities.video_capabilities.std_header_version,
+            vk::Format::G8_B8R8_2PLANE_420_UNORM,
         )?;
 
         let use_separate_images = encode_capabilities
@@ -158,7 +165,7 @@ impl VideoSessionResources<'_> {
             .flags
             .contains(vk::VideoCapabilityFlagsKHR::SEPARATE_REFERENCE_IMAGES);
 
-        let dpb = DecodedPicturesBuffer::new(
+        let mut dpb = DecodedPicturesBuffer::new(
             encoding_device,
             command_buffer,
             image_tracker,
@@ -166,21 +173,22 @@ impl VideoSessionResources<'_> {
             profile_info,
             vk::ImageUsageFlags::VIDEO_ENCODE_DPB_KHR,
             &encode_capabilities.encode_dpb_properties[0],
-            extent,
+            max_extent,
             max_dpb_slots,
             None,
             vk::ImageLayout::VIDEO_ENCODE_DPB_KHR,
         )?;
+        dpb.update_coded_extent(parameters.coded_extent())?;
 
         let codec_parameters =
             C::codec_parameters(parameters, &encode_capabilities.codec_encode_capabilities)?;
 
-        let session_parameters = VideoSessionParameters::new::<C>(
+        let session_parameters = VideoSessionParameters::new_encode::<C>(
             encoding_device.vulkan_device.device.clone(),
             video_session.session,
             C::vk_parameters(&codec_parameters),
             None,
-            Some(parameters.quality_level),
+            parameters.quality_level,
         )?;
 
         Ok(Self {
@@ -284,6 +292,7 @@ pub(crate) enum EncoderTrackerWaitState {
 
 pub(crate) struct EncoderCommandBufferPools {
     transfer: CommandBufferPool,
+    compute: CommandBufferPool,
     encode: CommandBufferPool,
 }
 
@@ -293,18 +302,27 @@ impl EncoderCommandBufferPools {
             device.vulkan_device.clone(),
             device.queues.transfer.family_index,
         )?;
+        let compute = CommandBufferPool::new(
+            device.vulkan_device.clone(),
+            device.queues.compute.family_index,
+        )?;
         let encode = CommandBufferPool::new(
             device.vulkan_device.clone(),
             device.encode_queues.family_index,
         )?;
 
-        Ok(Self { transfer, encode })
+        Ok(Self {
+            transfer,
+            compute,
+            encode,
+        })
     }
 }
 
 impl CommandBufferPoolStorage for EncoderCommandBufferPools {
     fn mark_submitted_as_free(&mut self, last_waited_for: SemaphoreWaitValue) {
         self.transfer.mark_submitted_as_free(last_waited_for);
+        self.compute.mark_submitted_as_free(last_waited_for);
         self.encode.mark_submitted_as_free(last_waited_for);
     }
 }
@@ -392,6 +410,29 @@ impl<'a, 'b> WaitedEncodeSubmission<'a, 'b> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PictureType {
+    Idr,
+    P,
+    /// Non-reference picture predicted from the previous and the next reference picture
+    B,
+}
+
+enum StagingResources {
+    Buffer(Buffer),
+    Conversion(InputConversion),
+}
+
+/// A frame waiting to be encoded as a B-frame, in display order.
+struct PendingFrame {
+    image: Arc<Image>,
+    /// Resources the image was uploaded from, they have to live until the frame is encoded
+    _staging: Option<StagingResources>,
+    pts: Option<u64>,
+    qp: Option<u8>,
+    display_order: u32,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct FullEncoderParameters<C: EncodeCodec> {
     pub(crate) idr_period: NonZeroU32,
@@ -399,6 +440,15 @@ pub(crate) struct FullEncoderParameters<C: EncodeCodec> {
     pub(crate) height: NonZeroU32,
     pub(crate) rate_control: RateControl,
     pub(crate) max_references: NonZeroU32,
+    pub(crate) consecutive_b_frames: u32,
+    pub(crate) slice_count: NonZeroU32,
+    /// Number of frames over which the picture is refreshed with intra slices. When set, every
+    /// frame is split into this many slices.
+    pub(crate) intra_refresh_period: Option<NonZeroU32>,
+    /// Size of the video session and the reference pictures. `width` and `height` can be changed
+    /// up to this size without recreating the session.
+    pub(crate) max_width: NonZeroU32,
+    pub(crate) max_height: NonZeroU32,
     pub(crate) profile: C::Profile,
     pub(crate) quality_level: u32,
     pub(crate) framerate: Rational,
@@ -406,8 +456,20 @@ pub(crate) struct FullEncoderParameters<C: EncodeCodec> {
     pub(crate) tuning_mode: vk::VideoEncodeTuningModeKHR,
     pub(crate) content_flags: vk::VideoEncodeContentFlagsKHR,
     pub(crate) inline_stream_params: bool,
+    /// Maximal number of frames between frames preceded by the stream parameters
+    pub(crate) stream_params_interval: Option<NonZeroU32>,
     pub(crate) color_space: ColorSpace,
     pub(crate) color_range: ColorRange,
+    pub(crate) enable_gpu_timing: bool,
+}
+
+impl<C: EncodeCodec> FullEncoderParameters<C> {
+    pub(crate) fn coded_extent(&self) -> vk::Extent2D {
+        vk::Extent2D {
+            width: self.width.get(),
+            height: self.height.get(),
+        }
+    }
 }
 
 impl<C: EncodeCodec> From<&FullEncoderParameters<C>> for vk::VideoEncodeUsageInfoKHR<'_> {
@@ -422,18 +484,42 @@ impl<C: EncodeCodec> From<&FullEncoderParameters<C>> for vk::VideoEncodeUsageInf
 pub(crate) struct VulkanEncoder<'a, C: EncodeCodec> {
     pub(crate) tracker: EncoderTracker,
     query_pool: EncodingQueryPool,
+    /// Measures the GPU time of every encode submission, if GPU timing is enabled.
+    timestamp_query_pool: Option<TimestampQueryPool>,
+    stats: EncoderStatsTracker,
     profile: C::Profile,
     pub(crate) profile_info: ProfileInfo<'a>,
     session_resources: VideoSessionResources<'a>,
     idr_period_counter: u32,
     idr_period: u32,
+    display_order: u32,
+    consecutive_b_frames: u32,
+    pending_frames: VecDeque<PendingFrame>,
+    slice_count: usize,
+    /// Slice which is coded as intra in the next P-frame when intra refresh is enabled
+    intra_refresh_position: usize,
+    /// Images that wgpu textures are copied into. Each buffered B-frame needs its own image.
     #[allow(dead_code)]
-    input_image: Arc<Image>,
+    input_images: Vec<Arc<Image>>,
+    #[allow(dead_code)]
+    next_input_image: usize,
     output_buffer: Buffer,
+    /// Converts frames passed to [`Self::encode_bytes`] to NV12, if they are in another format.
+    input_converter: Option<InputConverter>,
     counters: C::EncodingCounters,
     active_reference_slots: VecDeque<(usize, C::ReferenceInfo)>,
     rate_control: RateControl,
     inline_stream_params: bool,
+    /// The next frame will be encoded as an IDR frame
+    keyframe_requested: bool,
+    /// The stream parameters will be emitted with the next IDR frame, even if
+    /// `inline_stream_params` is off
+    stream_params_requested: bool,
+    /// Frames output since the stream parameters were last emitted
+    frames_since_stream_params: u32,
+    /// Parameters the encoder was created with, `width` and `height` are updated by
+    /// [`Self::set_resolution`]
+    parameters: FullEncoderParameters<C>,
     encoding_device: Arc<EncodingDevice>,
 }
 
@@ -442,9 +528,37 @@ impl<'a, C: EncodeCodec + 'a> VideoEncoderBackend for VulkanEncoder<'a, C> {
         &mut self,
         frame: &InputFrame<RawFrameData>,
         force_idr: bool,
-    ) -> Result<EncodedOutputChunk<Vec<u8>>, VideoEncoderError> {
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
         VulkanEncoder::encode_bytes(self, frame, force_idr)
     }
+
+    fn flush(&mut self) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        VulkanEncoder::flush(self)
+    }
+
+    fn update_rate_control(&mut self, rate_control: RateControl) -> Result<(), VideoEncoderError> {
+        VulkanEncoder::update_rate_control(self, rate_control).map_err(Into::into)
+    }
+
+    fn request_keyframe(&mut self) {
+        VulkanEncoder::request_keyframe(self)
+    }
+
+    fn set_resolution(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        VulkanEncoder::set_resolution(self, width, height).map_err(Into::into)
+    }
+
+    fn stats(&self) -> EncoderStats {
+        self.stats.stats()
+    }
+
+    fn set_stats_callback(&mut self, callback: EncoderStatsCallback) {
+        self.stats.set_callback(callback);
+    }
 }
 
 #[cfg(feature = "wgpu")]
@@ -455,9 +569,37 @@ impl<'a, C: EncodeCodec + 'a> crate::encoders::WgpuVideoEncoderBackend for Vulka
         wgpu_queue: &wgpu::Queue,
         frame: InputFrame<wgpu::Texture>,
         force_idr: bool,
-    ) -> Result<EncodedOutputChunk<Vec<u8>>, VideoEncoderError> {
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
         VulkanEncoder::encode_texture(self, wgpu_device, wgpu_queue, frame, force_idr)
     }
+
+    fn flush(&mut self) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        VulkanEncoder::flush(self)
+    }
+
+    fn update_rate_control(&mut self, rate_control: RateControl) -> Result<(), VideoEncoderError> {
+        VulkanEncoder::update_rate_control(self, rate_control).map_err(Into::into)
+    }
+
+    fn request_keyframe(&mut self) {
+        VulkanEncoder::request_keyframe(self)
+    }
+
+    fn set_resolution(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        VulkanEncoder::set_resolution(self, width, height).map_err(Into::into)
+    }
+
+    fn stats(&self) -> EncoderStats {
+        self.stats.stats()
+    }
+
+    fn set_stats_callback(&mut self, callback: EncoderStatsCallback) {
+        self.stats.set_callback(callback);
+    }
 }
 
 impl<'a> VideoEncoderParametersInfoH264 for VulkanEncoder<'a, H264Codec> {
@@ -529,6 +671,24 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
             profile_info.profile_info,
         )?;
 
+        let timestamp_valid_bits = encoding_device.encode_queues.timestamp_valid_bits();
+        let timestamp_query_pool = match (parameters.enable_gpu_timing, timestamp_valid_bits) {
+            (false, _) => None,
+            (true, 0) => {
+                warn!("Encode queue does not support timestamp queries. GPU timing is disabled.");
+                None
+            }
+            (true, timestamp_valid_bits) => Some(TimestampQueryPool::new(
+                encoding_device.device.clone(),
+                encoding_device
+                    .adapter_info
+                    .device_properties
+                    .limits
+                    .timestamp_period,
+                timestamp_valid_bits,
+            )?),
+        };
+
         // TODO: this buffer should grow when necessary
         let output_buffer = Buffer::new_encode(
             encoding_device.allocator.clone(),
@@ -554,32 +714,75 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
             EncoderTrackerWaitState::InitializeEncoder,
         )?;
 
-        let encode_image = Image::new_encode(
-            &encoding_device,
-            session_resources.video_session.max_coded_extent.into(),
-            &profile_info,
-            encoding_device.queues.wgpu.family_index as u32,
-            tracker.image_layout_tracker.clone(),
-        )?;
+        let input_images = (0..=parameters.consecutive_b_frames)
+            .map(|_| {
+                Image::new_encode(
+                    &encoding_device,
+                    session_resources.video_session.max_coded_extent.into(),
+                    &profile_info,
+                    encoding_device.queues.wgpu.family_index as u32,
+                    tracker.image_layout_tracker.clone(),
+                )
+                .map(Arc::new)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self {
             idr_period_counter: 0,
+            display_order: 0,
+            consecutive_b_frames: parameters.consecutive_b_frames,
+            pending_frames: VecDeque::with_capacity(parameters.consecutive_b_frames as usize),
+            slice_count: parameters.slice_count.get() as usize,
+            intra_refresh_position: 0,
             counters: C::EncodingCounters::default(),
             active_reference_slots: VecDeque::with_capacity(session_resources.dpb.len as usize),
             profile: parameters.profile,
             profile_info,
             encoding_device,
-            input_image: Arc::new(encode_image),
+            input_images,
+            next_input_image: 0,
             tracker,
             query_pool,
+            timestamp_query_pool,
+            stats: EncoderStatsTracker::new(),
             session_resources,
             idr_period: parameters.idr_period.get(),
             output_buffer,
+            input_converter: None,
             rate_control: parameters.rate_control,
             inline_stream_params: parameters.inline_stream_params,
+            keyframe_requested: false,
+            stream_params_requested: false,
+            frames_since_stream_params: 0,
+            parameters,
         })
     }
 
+    /// Frames passed to [`Self::encode_bytes`] will be in `pixel_format` and are converted to
+    /// NV12 before encoding.
+    pub(crate) fn use_input_pixel_format(
+        &mut self,
+        pixel_format: PixelFormat,
+    ) -> Result<(), VulkanEncoderError> {
+        self.input_converter = match pixel_format {
+            PixelFormat::Nv12 => None,
+            PixelFormat::P010 => {
+                return Err(VulkanEncoderError::ParametersError {
+                    field: "pixel_format",
+                    problem: "P010 frames cannot be encoded.".to_string(),
+                });
+            }
+            pixel_format => Some(InputConverter::new(
+                self.encoding_device.vulkan_device.clone(),
+                pixel_format,
+                self.parameters.color_space,
+                self.parameters.color_range,
+            )?),
+        };
+
+        Ok(())
+    }
+
     fn begin_video_coding(&self, buffer: vk::CommandBuffer) {
         let mut codec_layers =
             C::codec_rate_control_layer_info(self.session_resources.rate_control);
@@ -587,8 +790,11 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
             self.session_resources.rate_control,
             codec_layers.as_mut().map(|o| &mut o[..]),
         );
-        let mut codec_rate_control =
-            C::codec_rate_control_info(layers.as_ref().map(|o| &o[..]), self.idr_period);
+        let mut codec_rate_control = C::codec_rate_control_info(
+            layers.as_ref().map(|o| &o[..]),
+            self.idr_period,
+            self.consecutive_b_frames,
+        );
         let mut encode_rate_control = self.encoder_rate_control_for(
             self.session_resources.rate_control,
             layers.as_ref().map(|o| &o[..]),
@@ -639,6 +845,34 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
         &mut self,
         buffer: vk::CommandBuffer,
         rate_control: RateControl,
+    ) {
+        self.issue_coding_control(
+            buffer,
+            vk::VideoCodingControlFlagsKHR::RESET
+                | vk::VideoCodingControlFlagsKHR::ENCODE_QUALITY_LEVEL,
+            rate_control,
+        );
+    }
+
+    /// Changes the rate control state of the session without resetting it, so the references
+    /// stay valid.
+    fn issue_rate_control_update_for(
+        &mut self,
+        buffer: vk::CommandBuffer,
+        rate_control: RateControl,
+    ) {
+        self.issue_coding_control(
+            buffer,
+            vk::VideoCodingControlFlagsKHR::empty(),
+            rate_control,
+        );
+    }
+
+    fn issue_coding_control(
+        &mut self,
+        buffer: vk::CommandBuffer,
+        flags: vk::VideoCodingControlFlagsKHR,
+        rate_control: RateControl,
     ) {
         let mut quality_level = vk::VideoEncodeQualityLevelInfoKHR::default()
             .quality_level(self.session_resources.quality_level);
@@ -646,17 +880,19 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
         let mut codec_layers = C::codec_rate_control_layer_info(rate_control);
         let layers =
             self.rate_control_layers_for(rate_control, codec_layers.as_mut().map(|o| &mut o[..]));
-        let mut codec_rate_control =
-            C::codec_rate_control_info(layers.as_ref().map(|o| &o[..]), self.idr_period);
+        let mut codec_rate_control = C::codec_rate_control_info(
+            layers.as_ref().map(|o| &o[..]),
+            self.idr_period,
+            self.consecutive_b_frames,
+        );
         let mut encode_rate_control =
             self.encoder_rate_control_for(rate_control, layers.as_ref().map(|o| &o[..]));
 
-        let flags = vk::VideoCodingControlFlagsKHR::RESET
-            | vk::VideoCodingControlFlagsKHR::ENCODE_QUALITY_LEVEL;
+        let mut control_info = vk::VideoCodingControlInfoKHR::default().flags(flags);
 
-        let mut control_info = vk::VideoCodingControlInfoKHR::default()
-            .flags(flags)
-            .push_next(&mut quality_level);
+        if flags.contains(vk::VideoCodingControlFlagsKHR::ENCODE_QUALITY_LEVEL) {
+            control_info = control_info.push_next(&mut quality_level);
+        }
 
         if let (Some(encode_rate_control), Some(codec_rate_control)) =
             (encode_rate_control.as_mut(), codec_rate_control.as_mut())
@@ -678,33 +914,47 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
         self.session_resources.rate_control = rate_control;
     }
 
-    // TODO: Maybe we should reuse `input_image` here, instead of creating a new image
+    // TODO: Maybe we should reuse `input_images` here, instead of creating a new image
     fn transfer_buffer_to_image(
         &mut self,
         frame: &InputFrame<RawFrameData>,
-    ) -> Result<(Image, Buffer), VulkanEncoderError> {
+    ) -> Result<(Image, StagingResources), VulkanEncoderError> {
         let extent = vk::Extent3D {
             width: frame.data.width,
             height: frame.data.height,
             depth: 1,
         };
 
-        if frame.data.width as usize * frame.data.height as usize * 3 / 2 != frame.data.frame.len()
-        {
+        if frame.data.planes.is_some() {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "planes",
+                problem: "Only tightly packed frames can be encoded.".to_string(),
+            });
+        }
+
+        let pixel_format = self
+            .input_converter
+            .as_ref()
+            .map(|converter| converter.pixel_format())
+            .unwrap_or(PixelFormat::Nv12);
+        let frame_size = pixel_format.frame_size(frame.data.width, frame.data.height);
+        if frame_size != frame.data.frame.len() {
             return Err(VulkanEncoderError::InconsistentPictureByteSize {
                 bytes: frame.data.frame.len(),
-                size_from_resolution: frame.data.width as usize * frame.data.height as usize * 3
-                    / 2,
+                size_from_resolution: frame_size,
             });
         }
 
         let mut profile_list_info = vk::VideoProfileListInfoKHR::default()
             .profiles(std::slice::from_ref(&self.profile_info.profile_info));
 
-        let queue_family_indices = [
+        let mut queue_family_indices = vec![
             self.encoding_device.queues.transfer.family_index as u32,
+            self.encoding_device.queues.compute.family_index as u32,
             self.encoding_device.encode_queues.family_index as u32,
         ];
+        queue_family_indices.sort();
+        queue_family_indices.dedup();
 
         let image_create_info = vk::ImageCreateInfo::default()
             .flags(vk::ImageCreateFlags::empty())
@@ -727,7 +977,12 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
             self.tracker.image_layout_tracker.clone(),
         )?;
 
-        let mut cmd_buffer = self.tracker.command_buffer_pools.transfer.begin_buffer()?;
+        // Frames in other formats are converted to NV12 on the compute queue, which also
+        // supports transfer operations
+        let mut cmd_buffer = match self.input_converter {
+            Some(_) => self.tracker.command_buffer_pools.compute.begin_buffer()?,
+            None => self.tracker.command_buffer_pools.transfer.begin_buffer()?,
+        };
 
         image.transition_layout_single_layer(
             &mut cmd_buffer,
@@ -737,10 +992,24 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
             0,
         )?;
 
-        let buffer = Buffer::new_transfer_with_data(
-            self.encoding_device.allocator.clone(),
-            &frame.data.frame,
-        )?;
+        let (buffer, staging) = match self.input_converter.as_ref() {
+            Some(converter) => {
+                let conversion = converter.record(
+                    &mut cmd_buffer,
+                    &frame.data.frame,
+                    frame.data.width,
+                    frame.data.height,
+                )?;
+                (*conversion.output, StagingResources::Conversion(conversion))
+            }
+            None => {
+                let buffer = Buffer::new_transfer_with_data(
+                    self.encoding_device.allocator.clone(),
+                    &frame.data.frame,
+                )?;
+                (*buffer, StagingResources::Buffer(buffer))
+            }
+        };
 
         unsafe {
             self.encoding_device
@@ -748,7 +1017,7 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
                 .device
                 .cmd_copy_buffer_to_image(
                     cmd_buffer.buffer(),
-                    *buffer,
+                    buffer,
                     *image,
                     vk::ImageLayout::TRANSFER_DST_OPTIMAL,
                     &[
@@ -788,18 +1057,19 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
                 );
         }
 
-        self.encoding_device
-            .queues
-            .transfer
-            .submit_chain_semaphore(
-                cmd_buffer.end()?,
-                &mut self.tracker,
-                vk::PipelineStageFlags2::COPY,
-                vk::PipelineStageFlags2::COPY,
-                EncoderTrackerWaitState::CopyBufferToImage,
-            )?;
+        let queue = match self.input_converter {
+            Some(_) => &self.encoding_device.queues.compute,
+            None => &self.encoding_device.queues.transfer,
+        };
+        queue.submit_chain_semaphore(
+            cmd_buffer.end()?,
+            &mut self.tracker,
+            vk::PipelineStageFlags2::COMPUTE_SHADER | vk::PipelineStageFlags2::COPY,
+            vk::PipelineStageFlags2::COPY,
+            EncoderTrackerWaitState::CopyBufferToImage,
+        )?;
 
-        Ok((image, buffer))
+        Ok((image, staging))
     }
 
     #[cfg(feature = "wgpu")]
@@ -808,14 +1078,22 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
         wgpu_device: &wgpu::Device,
         wgpu_queue: &wgpu::Queue,
         frame: &InputFrame<wgpu::Texture>,
+        image: &Arc<Image>,
     ) -> Result<wgpu::hal::vulkan::CommandEncoder, VulkanEncoderError> {
         use crate::encoders::WgpuTextureEncoderError;
         use wgpu::hal::{CommandEncoder, Device, Queue, vulkan::Api as VkApi};
 
+        // The input image has the max size of the session, only the part matching the current
+        // resolution is encoded
+        let image_extent = wgpu::Extent3d {
+            width: image.extent.width,
+            height: image.extent.height,
+            depth_or_array_layers: image.extent.depth,
+        };
         let encode_texture_extent = wgpu::Extent3d {
-            width: self.input_image.extent.width,
-            height: self.input_image.extent.height,
-            depth_or_array_layers: self.input_image.extent.depth,
+            width: self.parameters.width.get(),
+            height: self.parameters.height.get(),
+            depth_or_array_layers: 1,
         };
 
         if !frame.data.usage().contains(wgpu::TextureUsages::COPY_SRC) {
@@ -835,13 +1113,13 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
         let hal_device = unsafe { wgpu_device.as_hal::<VkApi>().unwrap() };
         let hal_queue = unsafe { wgpu_queue.as_hal::<VkApi>().unwrap() };
 
-        let input_image_clone = self.input_image.clone();
+        let input_image_clone = image.clone();
         let hal_texture = unsafe {
             hal_device.texture_from_raw(
-                self.input_image.image,
+                image.image,
                 &wgpu::hal::TextureDescriptor {
                     label: None,
-                    size: encode_texture_extent,
+                    size: image_extent,
                     mip_level_count: 1,
                     sample_count: 1,
                     dimension: wgpu::TextureDimension::D2,
@@ -862,7 +1140,7 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
                 hal_texture,
                 &wgpu::TextureDescriptor {
                     label: None,
-                    size: encode_texture_extent,
+                    size: image_extent,
                     mip_level_count: 1,
                     sample_count: 1,
                     dimension: wgpu::TextureDimension::D2,
@@ -892,7 +1170,7 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
             .unwrap()
             .map
             .insert(
-                self.input_image.key(),
+                image.key(),
                 vec![vk::ImageLayout::TRANSFER_DST_OPTIMAL].into_boxed_slice(),
             );
 
@@ -962,13 +1240,17 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
         &mut self,
         frame: &InputFrame<RawFrameData>,
         force_idr: bool,
-    ) -> Result<EncodedOutputChunk<Vec<u8>>, VideoEncoderError> {
-        let (image, _buffer) = self.transfer_buffer_to_image(frame)?;
-        let image = Arc::new(image);
-
-        self.encode(image, force_idr, frame.pts)?
-            .wait_and_download(u64::MAX)
-            .map_err(Into::into)
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        let (image, staging) = self.transfer_buffer_to_image(frame)?;
+
+        self.encode_frame(
+            Arc::new(image),
+            Some(staging),
+            frame.pts,
+            frame.qp,
+            force_idr,
+        )
+        .map_err(Into::into)
     }
 
     #[cfg(feature = "wgpu")]
@@ -978,12 +1260,259 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
         wgpu_queue: &wgpu::Queue,
         frame: InputFrame<wgpu::Texture>,
         force_idr: bool,
-    ) -> Result<EncodedOutputChunk<Vec<u8>>, VideoEncoderError> {
-        let _cmd_encoder = self.copy_wgpu_texture_to_image(wgpu_device, wgpu_queue, &frame)?;
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        let image = self.input_images[self.next_input_image].clone();
+        self.next_input_image = (self.next_input_image + 1) % self.input_images.len();
+
+        let cmd_encoder =
+            self.copy_wgpu_texture_to_image(wgpu_device, wgpu_queue, &frame, &image)?;
+
+        let output = self.encode_frame(image, None, frame.pts, frame.qp, force_idr)?;
+
+        // The frame might have been buffered, so the copy has to finish before the command
+        // encoder is dropped
+        self.tracker.wait_for_all(u64::MAX)?;
+        drop(cmd_encoder);
+
+        Ok(output)
+    }
+
+    /// Encodes all buffered frames. The last buffered frame becomes a P-frame, since there is no
+    /// future reference for it.
+    pub fn flush(&mut self) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        self.flush_pending_frames().map_err(Into::into)
+    }
+
+    /// Changes the rate control of the encoder. The new settings are applied starting with the
+    /// next encoded frame, without recreating the video session or forcing an IDR frame.
+    pub fn update_rate_control(
+        &mut self,
+        rate_control: RateControl,
+    ) -> Result<(), VulkanEncoderError> {
+        if let RateControl::EncoderDefault = rate_control {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "rate_control",
+                problem:
+                    "Rate control cannot be changed to EncoderDefault after the encoder is created."
+                        .to_string(),
+            });
+        }
+
+        let profile_capabilities = C::encode_codec_profile_capabilities(
+            &self.encoding_device.native_encode_capabilities,
+            self.profile,
+        )?;
+        validate_rate_control(profile_capabilities, rate_control)?;
+
+        self.rate_control = rate_control;
+        Ok(())
+    }
+
+    /// Changes the resolution of the encoded video, up to the max resolution the encoder was
+    /// created with. The video session and its reference pictures are reused, only new stream
+    /// parameters are created. A new SPS can only be activated by an IDR frame, so the next frame
+    /// is encoded as an IDR frame preceded by the new stream parameters.
+    ///
+    /// Frames buffered for B-frames are encoded with the previous resolution and returned.
+    pub fn set_resolution(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VulkanEncoderError> {
+        let encode_capabilities = C::encode_codec_profile_capabilities(
+            &self.encoding_device.native_encode_capabilities,
+            self.profile,
+        )?;
+        let min_extent = encode_capabilities.video_capabilities.min_coded_extent;
+
+        if width.get() < min_extent.width || width > self.parameters.max_width {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "width",
+                problem: format!(
+                    "Width is {}, should be between {} and {}.",
+                    width, min_extent.width, self.parameters.max_width
+                ),
+            });
+        }
+
+        if height.get() < min_extent.height || height > self.parameters.max_height {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "height",
+                problem: format!(
+                    "Height is {}, should be between {} and {}.",
+                    height, min_extent.height, self.parameters.max_height
+                ),
+            });
+        }
+
+        let slice_count = C::resolve_slice_count(
+            &encode_capabilities.codec_encode_capabilities,
+            height,
+            Some(self.parameters.slice_count),
+        )?;
+
+        let output = self.flush_pending_frames()?;
+
+        // The old session parameters can be used by submitted work
+        self.tracker.wait_for_all(u64::MAX)?;
+
+        let parameters = FullEncoderParameters {
+            width,
+            height,
+            slice_count,
+            ..self.parameters
+        };
+        let codec_parameters =
+            C::codec_parameters(&parameters, &encode_capabilities.codec_encode_capabilities)?;
+        self.session_resources.parameters = VideoSessionParameters::new_encode::<C>(
+            self.encoding_device.vulkan_device.device.clone(),
+            self.session_resources.video_session.session,
+            C::vk_parameters(&codec_parameters),
+            None,
+            self.session_resources.quality_level,
+        )?;
+        self.session_resources
+            .dpb
+            .update_coded_extent(parameters.coded_extent())?;
+
+        self.parameters = parameters;
+        self.slice_count = slice_count.get() as usize;
+        self.keyframe_requested = true;
+        self.stream_params_requested = true;
+
+        Ok(output)
+    }
+
+    /// Makes the next encoded frame an IDR frame. The stream parameters are emitted with it, even
+    /// if `inline_stream_params` is off.
+    pub fn request_keyframe(&mut self) {
+        self.keyframe_requested = true;
+        self.stream_params_requested = true;
+    }
+
+    /// Decides whether the next frame starts a new IDR period and assigns its display order.
+    fn next_frame_position(&mut self, force_idr: bool) -> (bool, u32) {
+        let is_idr = force_idr
+            || std::mem::take(&mut self.keyframe_requested)
+            || self.idr_period_counter == 0;
+        if is_idr {
+            self.idr_period_counter = 0;
+            self.display_order = 0;
+        }
+
+        let display_order = self.display_order;
+        self.display_order = self.display_order.wrapping_add(1);
+        self.idr_period_counter += 1;
+        self.idr_period_counter %= self.idr_period;
+
+        (is_idr, display_order)
+    }
+
+    /// Index of the slice which is coded as intra in a picture of `picture_type`, if intra
+    /// refresh is enabled. Slices are refreshed from the top of the picture, an IDR frame starts
+    /// a new cycle.
+    fn next_intra_refresh_slice(&mut self, picture_type: PictureType) -> Option<usize> {
+        self.parameters.intra_refresh_period?;
+
+        match picture_type {
+            PictureType::Idr => {
+                self.intra_refresh_position = 0;
+                None
+            }
+            PictureType::P => {
+                let slice = self.intra_refresh_position;
+                self.intra_refresh_position = (slice + 1) % self.slice_count;
+                Some(slice)
+            }
+            PictureType::B => None,
+        }
+    }
+
+    /// Returns encoded chunks in decoding order. Frames which will be encoded as B-frames are
+    /// buffered until the next reference frame arrives.
+    fn encode_frame(
+        &mut self,
+        image: Arc<Image>,
+        staging: Option<StagingResources>,
+        pts: Option<u64>,
+        qp: Option<u8>,
+        force_idr: bool,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VulkanEncoderError> {
+        if let Some(qp) = qp {
+            self.validate_frame_qp(qp)?;
+        }
+
+        let (is_idr, display_order) = self.next_frame_position(force_idr);
+        let frame = PendingFrame {
+            image,
+            _staging: staging,
+            pts,
+            qp,
+            display_order,
+        };
+
+        if is_idr {
+            let mut output = self.flush_pending_frames()?;
+            output.push(self.encode_pending_frame(frame, PictureType::Idr)?);
+            return Ok(output);
+        }
 
-        self.encode(self.input_image.clone(), force_idr, frame.pts)?
-            .wait_and_download(u64::MAX)
-            .map_err(Into::into)
+        if self.pending_frames.len() < self.consecutive_b_frames as usize {
+            self.pending_frames.push_back(frame);
+            return Ok(Vec::new());
+        }
+
+        let mut output = vec![self.encode_pending_frame(frame, PictureType::P)?];
+        output.append(&mut self.encode_pending_b_frames()?);
+        Ok(output)
+    }
+
+    fn flush_pending_frames(
+        &mut self,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VulkanEncoderError> {
+        let Some(last_frame) = self.pending_frames.pop_back() else {
+            return Ok(Vec::new());
+        };
+
+        let mut output = vec![self.encode_pending_frame(last_frame, PictureType::P)?];
+        output.append(&mut self.encode_pending_b_frames()?);
+        Ok(output)
+    }
+
+    fn encode_pending_b_frames(
+        &mut self,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VulkanEncoderError> {
+        let mut output = Vec::with_capacity(self.pending_frames.len());
+        while let Some(frame) = self.pending_frames.pop_front() {
+            output.push(self.encode_pending_frame(frame, PictureType::B)?);
+        }
+
+        Ok(output)
+    }
+
+    fn encode_pending_frame(
+        &mut self,
+        frame: PendingFrame,
+        picture_type: PictureType,
+    ) -> Result<EncodedOutputChunk<Vec<u8>>, VulkanEncoderError> {
+        // `frame` owns the staging buffer, so it has to outlive the encode
+        self.encode_picture(
+            frame.image.clone(),
+            picture_type,
+            frame.display_order,
+            frame.pts,
+            frame.qp,
+        )?
+        .wait_and_download(u64::MAX)
+    }
+
+    fn validate_frame_qp(&self, qp: u8) -> Result<(), VulkanEncoderError> {
+        let profile_capabilities = C::encode_codec_profile_capabilities(
+            &self.encoding_device.native_encode_capabilities,
+            self.profile,
+        )?;
+        let qp_range = C::qp_range(&profile_capabilities.codec_encode_capabilities);
+        validate_frame_qp(self.rate_control, qp_range, qp)
     }
 
     fn encoder_rate_control_for<'b>(
@@ -1018,7 +1547,7 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
                     .initial_virtual_buffer_size_in_ms(0),
             ),
 
-            RateControl::Disabled => {
+            RateControl::Disabled | RateControl::ConstantQp { .. } => {
                 let mut rate_control = vk::VideoEncodeRateControlInfoKHR::default()
                     .rate_control_mode(vk::VideoEncodeRateControlModeFlagsKHR::DISABLED)
                     .layers(layers);
@@ -1071,7 +1600,9 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
                             .push_next(codec_layer_info)
                     }
 
-                    RateControl::Disabled => layer_info = layer_info.push_next(codec_layer_info),
+                    RateControl::Disabled | RateControl::ConstantQp { .. } => {
+                        layer_info = layer_info.push_next(codec_layer_info)
+                    }
                 }
 
                 layer_info
@@ -1080,30 +1611,38 @@ impl<'a, C: EncodeCodec + 'a> VulkanEncoder<'a, C> {
 
         Some(result)
     }
-}
 
-impl<'a, C: EncodeCodec + 'a> DynVulkanEncoder<'a> for VulkanEncoder<'a, C> {
-    fn encode<'b>(
+    fn encode_picture<'b>(
         &'b mut self,
         image: Arc<Image>,
-        force_idr: bool,
+        picture_type: PictureType,
+        display_order: u32,
         pts: Option<u64>,
+        qp: Option<u8>,
     ) -> Result<UnwaitedEncodeSubmission<'b, 'a>, VulkanEncoderError> {
-        let is_idr = force_idr || self.idr_period_counter == 0;
+        let is_idr = picture_type == PictureType::Idr;
 
-        if is_idr {
-            self.idr_period_counter = 0;
-            C::counters_idr(&mut self.counters);
-            self.active_reference_slots.clear();
-            self.session_resources.dpb.reset_all_allocations();
-        } else if self.active_reference_slots.len() == self.session_resources.max_dpb_slots as usize
-        {
-            if let Some((oldest_reference, _)) = self.active_reference_slots.pop_front() {
-                self.session_resources
-                    .dpb
-                    .free_reference_picture(oldest_reference);
+        match picture_type {
+            PictureType::Idr => {
+                C::counters_idr(&mut self.counters);
+                self.active_reference_slots.clear();
+                self.session_resources.dpb.reset_all_allocations();
+            }
+            PictureType::P => {
+                if self.active_reference_slots.len()
+                    == self.session_resources.max_dpb_slots as usize
+                {
+                    if let Some((oldest_reference, _)) = self.active_reference_slots.pop_front() {
+                        self.session_resources
+                            .dpb
+                            .free_reference_picture(oldest_reference);
+                    }
+                }
             }
+            // B-frames are not used as references, so they don't take a DPB slot
+            PictureType::B => {}
         }
+        C::set_display_order(&mut self.counters, display_order);
 
         let mut cmd_buffer = self.tracker.command_buffer_pools.encode.begin_buffer()?;
 
@@ -1141,10 +1680,17 @@ impl<'a, C: EncodeCodec + 'a> DynVulkanEncoder<'a> for VulkanEncoder<'a, C> {
 
         self.query_pool.reset(cmd_buffer.buffer());
 
+        if let Some(pool) = &self.timestamp_query_pool {
+            pool.reset(cmd_buffer.buffer());
+            pool.write_start(cmd_buffer.buffer());
+        }
+
         self.begin_video_coding(cmd_buffer.buffer());
 
         if is_idr {
             self.issue_coding_control_reset_for(cmd_buffer.buffer(), self.rate_control);
+        } else if self.rate_control != self.session_resources.rate_control {
+            self.issue_rate_control_update_for(cmd_buffer.buffer(), self.rate_control);
         }
 
         // bugs in nvidia driver I encountered on this journey:
@@ -1178,38 +1724,71 @@ impl<'a, C: EncodeCodec + 'a> DynVulkanEncoder<'a> for VulkanEncoder<'a, C> {
             self.profile,
         )?;
 
-        let bitstream_unit_data =
-            C::bitstream_unit_data(&profile_capabilities.codec_encode_capabilities, is_idr);
+        let bitstream_unit_data = C::bitstream_unit_data(
+            &profile_capabilities.codec_encode_capabilities,
+            picture_type,
+        );
+        // The session stays in the disabled rate control mode, only the QP of the slices changes
+        let frame_rate_control = match qp {
+            Some(qp) => RateControl::ConstantQp { qp },
+            None => self.rate_control,
+        };
         let bitstream_unit_info = C::bitstream_unit_info(
             &bitstream_unit_data,
-            self.rate_control,
+            frame_rate_control,
             &profile_capabilities.quality_level_properties
                 [self.session_resources.quality_level as usize],
-            is_idr,
+            picture_type,
         );
 
-        let bitstream_unit_infos = [bitstream_unit_info];
+        // Every slice uses the same header, the implementation splits the frame between them.
+        // With intra refresh, one slice of a P-frame is coded as an intra slice instead.
+        let intra_slice = self.next_intra_refresh_slice(picture_type);
+        let intra_bitstream_unit_data = intra_slice.map(|_| {
+            C::bitstream_unit_data(
+                &profile_capabilities.codec_encode_capabilities,
+                PictureType::Idr,
+            )
+        });
+        let mut bitstream_unit_infos = vec![bitstream_unit_info; self.slice_count];
+        if let (Some(slice), Some(data)) = (intra_slice, intra_bitstream_unit_data.as_ref()) {
+            bitstream_unit_infos[slice] = C::bitstream_unit_info(
+                data,
+                frame_rate_control,
+                &profile_capabilities.quality_level_properties
+                    [self.session_resources.quality_level as usize],
+                PictureType::Idr,
+            );
+        }
 
         let reference_list_info =
-            C::reference_list_info(&self.counters, &self.active_reference_slots);
+            C::reference_list_info(&self.counters, &self.active_reference_slots, picture_type);
 
         let picture_info_data = C::picture_info_data(
             &self.counters,
             &profile_capabilities.codec_encode_capabilities,
-            is_idr,
+            picture_type,
             &reference_list_info,
         );
 
         let mut picture_info = C::picture_info(&picture_info_data, &bitstream_unit_infos);
 
-        let setup_reference_slot_idx = self.session_resources.dpb.allocate_reference_picture()?;
+        // B-frames are not used as references, so they are not written to the DPB
+        let setup_reference_slot_idx = match picture_type {
+            PictureType::Idr | PictureType::P => {
+                Some(self.session_resources.dpb.allocate_reference_picture()?)
+            }
+            PictureType::B => None,
+        };
 
         let mut reference_slots = self
             .session_resources
             .dpb
             .reference_slot_info()
             .into_iter()
-            .filter(|i| i.slot_index >= 0 && i.slot_index != setup_reference_slot_idx as i32)
+            .filter(|i| {
+                i.slot_index >= 0 && Some(i.slot_index as usize) != setup_reference_slot_idx
+            })
             .collect::<Vec<_>>();
 
         let mut std_reference_info = self
@@ -1227,28 +1806,22 @@ impl<'a, C: EncodeCodec + 'a> DynVulkanEncoder<'a> for VulkanEncoder<'a, C> {
             *slot = slot.push_next(std_info);
         });
 
-        let new_slot_reference_info = C::new_slot_reference_info(&self.counters, is_idr);
+        let new_slot_reference_info = C::new_slot_reference_info(&self.counters, picture_type);
         let mut new_slot_dpb_info = C::new_slot_dpb_slot_info(&new_slot_reference_info);
 
-        let setup_reference_slot_video_resource_info = self
-            .session_resources
-            .dpb
-            .video_resource_info(setup_reference_slot_idx)
-            .unwrap();
+        let setup_reference_slot = setup_reference_slot_idx.map(|idx| {
+            let setup_reference_slot_video_resource_info =
+                self.session_resources.dpb.video_resource_info(idx).unwrap();
 
-        let setup_reference_slot = vk::VideoReferenceSlotInfoKHR::default()
-            .slot_index(setup_reference_slot_idx as i32)
-            .picture_resource(setup_reference_slot_video_resource_info)
-            .push_next(&mut new_slot_dpb_info);
-
-        let extent = image.extent;
+            vk::VideoReferenceSlotInfoKHR::default()
+                .slot_index(idx as i32)
+                .picture_resource(setup_reference_slot_video_resource_info)
+                .push_next(&mut new_slot_dpb_info)
+        });
 
         let src_picture_resource = vk::VideoPictureResourceInfoKHR::default()
             .coded_offset(vk::Offset2D::default())
-            .coded_extent(vk::Extent2D {
-                width: extent.width,
-                height: extent.height,
-            })
+            .coded_extent(self.parameters.coded_extent())
             .base_array_layer(0)
             .image_view_binding(view.view);
 
@@ -1257,9 +1830,12 @@ impl<'a, C: EncodeCodec + 'a> DynVulkanEncoder<'a> for VulkanEncoder<'a, C> {
             .dst_buffer_range(Self::OUTPUT_BUFFER_LEN)
             .dst_buffer_offset(0)
             .src_picture_resource(src_picture_resource)
-            .setup_reference_slot(&setup_reference_slot)
             .push_next(&mut picture_info);
 
+        if let Some(setup_reference_slot) = setup_reference_slot.as_ref() {
+            encode_info = encode_info.setup_reference_slot(setup_reference_slot);
+        }
+
         if !reference_slots.is_empty() {
             encode_info = encode_info.reference_slots(&reference_slots);
         }
@@ -1287,6 +1863,10 @@ impl<'a, C: EncodeCodec + 'a> DynVulkanEncoder<'a> for VulkanEncoder<'a, C> {
                 );
         }
 
+        if let Some(pool) = &self.timestamp_query_pool {
+            pool.write_end(cmd_buffer.buffer());
+        }
+
         let wait_value = self.encoding_device.encode_queues.submit_chain_semaphore(
             cmd_buffer.end()?,
             &mut self.tracker,
@@ -1295,14 +1875,13 @@ impl<'a, C: EncodeCodec + 'a> DynVulkanEncoder<'a> for VulkanEncoder<'a, C> {
             EncoderTrackerWaitState::Encode,
         )?;
 
-        C::advance_counters(&mut self.counters, is_idr);
+        C::advance_counters(&mut self.counters, picture_type);
         drop(std_reference_info);
 
-        self.active_reference_slots
-            .push_back((setup_reference_slot_idx, new_slot_reference_info));
-
-        self.idr_period_counter += 1;
-        self.idr_period_counter %= self.idr_period;
+        if let Some(setup_reference_slot_idx) = setup_reference_slot_idx {
+            self.active_reference_slots
+                .push_back((setup_reference_slot_idx, new_slot_reference_info));
+        }
 
         Ok(UnwaitedEncodeSubmission(EncodeSubmission {
             is_idr,
@@ -1312,6 +1891,25 @@ impl<'a, C: EncodeCodec + 'a> DynVulkanEncoder<'a> for VulkanEncoder<'a, C> {
             _image: image,
         }))
     }
+}
+
+impl<'a, C: EncodeCodec + 'a> DynVulkanEncoder<'a> for VulkanEncoder<'a, C> {
+    fn encode<'b>(
+        &'b mut self,
+        image: Arc<Image>,
+        force_idr: bool,
+        pts: Option<u64>,
+    ) -> Result<UnwaitedEncodeSubmission<'b, 'a>, VulkanEncoderError> {
+        let (is_idr, display_order) = self.next_frame_position(force_idr);
+        let picture_type = if is_idr {
+            PictureType::Idr
+        } else {
+            PictureType::P
+        };
+
+        self.encode_picture(image, picture_type, display_order, pts, None)
+    }
+
     fn download_output(
         &mut self,
         is_idr: bool,
@@ -1323,11 +1921,27 @@ impl<'a, C: EncodeCodec + 'a> DynVulkanEncoder<'a> for VulkanEncoder<'a, C> {
             return Err(VulkanEncoderError::EncodeOperationFailed(feedback.status));
         }
 
-        let mut output = if is_idr && self.inline_stream_params {
+        let gpu_encode_duration = self
+            .timestamp_query_pool
+            .as_ref()
+            .map(|pool| pool.get_duration_blocking())
+            .transpose()?;
+        self.stats.register_frame(gpu_encode_duration);
+
+        let stream_params_requested = is_idr && std::mem::take(&mut self.stream_params_requested);
+        let interval_elapsed = self
+            .parameters
+            .stream_params_interval
+            .is_some_and(|interval| self.frames_since_stream_params >= interval.get());
+        let mut output = if (is_idr && (self.inline_stream_params || stream_params_requested))
+            || interval_elapsed
+        {
+            self.frames_since_stream_params = 0;
             self.stream_parameters(C::codec_write_parameters_info_all())?
         } else {
             Vec::new()
         };
+        self.frames_since_stream_params = self.frames_since_stream_params.saturating_add(1);
 
         let encoded = unsafe {
             self.output_buffer.download_data_from_buffer_at(
@@ -1356,7 +1970,9 @@ impl RateControl {
             RateControl::EncoderDefault => vk::VideoEncodeRateControlModeFlagsKHR::DEFAULT,
             RateControl::VariableBitrate { .. } => vk::VideoEncodeRateControlModeFlagsKHR::VBR,
             RateControl::ConstantBitrate { .. } => vk::VideoEncodeRateControlModeFlagsKHR::CBR,
-            RateControl::Disabled => vk::VideoEncodeRateControlModeFlagsKHR::DISABLED,
+            RateControl::Disabled | RateControl::ConstantQp { .. } => {
+                vk::VideoEncodeRateControlModeFlagsKHR::DISABLED
+            }
         }
     }
 }
@@ -1373,3 +1989,81 @@ impl From<crate::parameters::EncoderUsage> for vk::VideoEncodeUsageFlagsKHR {
         }
     }
 }
+
+/// The QP of a frame can only be overridden when the rate control is off, otherwise the
+/// device chooses it.
+fn validate_frame_qp(
+    rate_control: RateControl,
+    qp_range: RangeInclusive<i32>,
+    qp: u8,
+) -> Result<(), VulkanEncoderError> {
+    if !matches!(
+        rate_control,
+        RateControl::ConstantQp { .. } | RateControl::Disabled
+    ) {
+        return Err(VulkanEncoderError::ParametersError {
+            field: "qp",
+            problem: format!(
+                "Frame QP can only be set with RateControl::ConstantQp or RateControl::Disabled, the rate control is {rate_control:?}."
+            ),
+        });
+    }
+
+    if !qp_range.contains(&(qp as i32)) {
+        return Err(VulkanEncoderError::ParametersError {
+            field: "qp",
+            problem: format!(
+                "QP is {qp}, should be between {} and {}.",
+                qp_range.start(),
+                qp_range.end()
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn frame_qp_requires_rate_control_off() {
+        for rate_control in [RateControl::Disabled, RateControl::ConstantQp { qp: 30 }] {
+            assert!(validate_frame_qp(rate_control, 0..=51, 20).is_ok());
+        }
+
+        for rate_control in [
+            RateControl::EncoderDefault,
+            RateControl::ConstantBitrate {
+                bitrate: 2_000_000,
+                virtual_buffer_size: Duration::from_secs(1),
+            },
+            RateControl::VariableBitrate {
+                average_bitrate: 2_000_000,
+                max_bitrate: 4_000_000,
+                virtual_buffer_size: Duration::from_secs(1),
+            },
+        ] {
+            assert!(matches!(
+                validate_frame_qp(rate_control, 0..=51, 20),
+                Err(VulkanEncoderError::ParametersError { field: "qp", .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn frame_qp_in_device_range() {
+        let rate_control = RateControl::Disabled;
+        assert!(validate_frame_qp(rate_control, 10..=40, 10).is_ok());
+        assert!(validate_frame_qp(rate_control, 10..=40, 40).is_ok());
+        for qp in [0, 9, 41, 51] {
+            assert!(matches!(
+                validate_frame_qp(rate_control, 10..=40, qp),
+                Err(VulkanEncoderError::ParametersError { field: "qp", .. })
+            ));
+        }
+    }
+}
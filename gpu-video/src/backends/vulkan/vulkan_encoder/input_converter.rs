@@ -0,0 +1,264 @@
+use std::{io::Cursor, sync::Arc};
+
+use ash::vk;
+
+use crate::{
+    backends::vulkan::{
+        VulkanCommonError, VulkanDevice,
+        wrappers::{
+            Buffer, ComputePipeline, DescriptorPool, DescriptorSet, DescriptorSetLayout,
+            OpenCommandBuffer, PipelineLayout, ShaderModule,
+        },
+    },
+    device::{ColorRange, ColorSpace},
+    parameters::PixelFormat,
+};
+
+const WORKGROUP_SIZE: u32 = 256;
+const MAX_WORKGROUPS_X: u32 = 65535;
+
+/// Has to match `Immediates` in the shader.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct PushConstants {
+    width: u32,
+    height: u32,
+    pixel_format: u32,
+    color_matrix: u32,
+    full_range: u32,
+}
+
+impl PushConstants {
+    fn to_bytes(self) -> Vec<u8> {
+        [
+            self.width,
+            self.height,
+            self.pixel_format,
+            self.color_matrix,
+            self.full_range,
+        ]
+        .iter()
+        .flat_map(|value| value.to_ne_bytes())
+        .collect()
+    }
+}
+
+/// Resources that have to be kept alive until the conversion is finished.
+pub(crate) struct InputConversion {
+    /// Tightly packed NV12 frame.
+    pub(crate) output: Buffer,
+    _input: Buffer,
+    _descriptor_set: DescriptorSet,
+}
+
+/// Converts frames passed to bytes encoders from [`PixelFormat::I420`], [`PixelFormat::Rgba`]
+/// or [`PixelFormat::Yuyv`] into NV12 with a compute shader, before they are copied into the
+/// encoder's input image.
+pub(crate) struct InputConverter {
+    pixel_format: PixelFormat,
+    color_space: ColorSpace,
+    color_range: ColorRange,
+    pipeline: ComputePipeline,
+    set_layout: Arc<DescriptorSetLayout>,
+    device: Arc<VulkanDevice>,
+}
+
+impl InputConverter {
+    pub(crate) fn new(
+        device: Arc<VulkanDevice>,
+        pixel_format: PixelFormat,
+        color_space: ColorSpace,
+        color_range: ColorRange,
+    ) -> Result<Self, VulkanCommonError> {
+        let bindings = [
+            vk::DescriptorSetLayoutBinding::default()
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .stage_flags(vk::ShaderStageFlags::COMPUTE)
+                .binding(0),
+            vk::DescriptorSetLayoutBinding::default()
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .stage_flags(vk::ShaderStageFlags::COMPUTE)
+                .binding(1),
+        ];
+        let set_layout = Arc::new(DescriptorSetLayout::new(
+            device.device.clone(),
+            &vk::DescriptorSetLayoutCreateInfo::default().bindings(&bindings),
+        )?);
+
+        let push_constants = [vk::PushConstantRange::default()
+            .size(std::mem::size_of::<PushConstants>() as u32)
+            .offset(0)
+            .stage_flags(vk::ShaderStageFlags::COMPUTE)];
+        let create_info = vk::PipelineLayoutCreateInfo::default()
+            .set_layouts(std::slice::from_ref(&set_layout.set_layout))
+            .push_constant_ranges(&push_constants);
+        let pipeline_layout = Arc::new(PipelineLayout::new(
+            device.device.clone(),
+            &create_info,
+            vec![set_layout.clone()],
+        )?);
+
+        const SHADER_SPV: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/input_converter.spv"));
+        let mut shader_bytes_cursor = Cursor::new(SHADER_SPV);
+        let compiled_shader = ash::util::read_spv(&mut shader_bytes_cursor).unwrap();
+
+        let shader_module = Arc::new(ShaderModule::new(
+            device.device.clone(),
+            &vk::ShaderModuleCreateInfo::default().code(&compiled_shader),
+        )?);
+
+        let shader = vk::PipelineShaderStageCreateInfo::default()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .name(c"main")
+            .module(shader_module.module);
+        let create_info = vk::ComputePipelineCreateInfo::default()
+            .stage(shader)
+            .layout(pipeline_layout.layout);
+
+        let pipeline = ComputePipeline::new(
+            device.device.clone(),
+            create_info,
+            pipeline_layout,
+            shader_module,
+        )?;
+
+        Ok(Self {
+            pixel_format,
+            color_space,
+            color_range,
+            pipeline,
+            set_layout,
+            device,
+        })
+    }
+
+    pub(crate) fn pixel_format(&self) -> PixelFormat {
+        self.pixel_format
+    }
+
+    /// Uploads `frame` and records its conversion into `buffer`, followed by a barrier which
+    /// makes the output available for a copy. The returned resources have to be kept alive until
+    /// the command buffer finishes executing.
+    pub(crate) fn record(
+        &self,
+        buffer: &mut OpenCommandBuffer,
+        frame: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<InputConversion, VulkanCommonError> {
+        let input = Buffer::new_storage_with_data(self.device.allocator.clone(), frame)?;
+        let output_size = PixelFormat::Nv12.frame_size(width, height);
+        // the output is written in whole words
+        let output = Buffer::new_storage_readback(
+            self.device.allocator.clone(),
+            output_size.next_multiple_of(4) as u64,
+        )?;
+
+        // Frames can be buffered by the encoder before their conversion finishes, so every
+        // conversion gets its own descriptor set
+        let pool_sizes = [vk::DescriptorPoolSize::default()
+            .ty(vk::DescriptorType::STORAGE_BUFFER)
+            .descriptor_count(2)];
+        let descriptor_pool = Arc::new(DescriptorPool::new(
+            self.device.device.clone(),
+            &vk::DescriptorPoolCreateInfo::default()
+                .max_sets(1)
+                .pool_sizes(&pool_sizes),
+        )?);
+        let descriptor_set = DescriptorSet::new(
+            descriptor_pool.clone(),
+            &vk::DescriptorSetAllocateInfo::default()
+                .descriptor_pool(descriptor_pool.pool)
+                .set_layouts(&[self.set_layout.set_layout]),
+        )?
+        .pop()
+        .unwrap();
+
+        let input_info = vk::DescriptorBufferInfo::default()
+            .buffer(*input)
+            .offset(0)
+            .range(vk::WHOLE_SIZE);
+        let output_info = vk::DescriptorBufferInfo::default()
+            .buffer(*output)
+            .offset(0)
+            .range(vk::WHOLE_SIZE);
+
+        let writes = [
+            vk::WriteDescriptorSet::default()
+                .dst_set(descriptor_set.descriptor_set)
+                .dst_binding(0)
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .buffer_info(std::slice::from_ref(&input_info)),
+            vk::WriteDescriptorSet::default()
+                .dst_set(descriptor_set.descriptor_set)
+                .dst_binding(1)
+                .descriptor_count(1)
+                .descriptor_type(vk::DescriptorType::STORAGE_BUFFER)
+                .buffer_info(std::slice::from_ref(&output_info)),
+        ];
+        unsafe { self.device.device.update_descriptor_sets(&writes, &[]) };
+
+        let push_constants = PushConstants {
+            width,
+            height,
+            pixel_format: self.pixel_format as u32,
+            color_matrix: match self.color_space {
+                ColorSpace::BT601Ntsc | ColorSpace::BT601Pal => 1,
+                ColorSpace::BT709 | ColorSpace::Unspecified => 0,
+            },
+            full_range: (self.color_range == ColorRange::Full) as u32,
+        };
+
+        let workgroups = (output_size.div_ceil(4) as u32).div_ceil(WORKGROUP_SIZE);
+        let workgroups_x = workgroups.clamp(1, MAX_WORKGROUPS_X);
+        let workgroups_y = workgroups.div_ceil(workgroups_x).max(1);
+
+        let buffer_barrier = vk::BufferMemoryBarrier2::default()
+            .src_stage_mask(vk::PipelineStageFlags2::COMPUTE_SHADER)
+            .src_access_mask(vk::AccessFlags2::SHADER_STORAGE_WRITE)
+            .dst_stage_mask(vk::PipelineStageFlags2::COPY)
+            .dst_access_mask(vk::AccessFlags2::TRANSFER_READ)
+            .buffer(*output)
+            .offset(0)
+            .size(vk::WHOLE_SIZE);
+
+        unsafe {
+            self.device.device.cmd_bind_pipeline(
+                buffer.buffer(),
+                vk::PipelineBindPoint::COMPUTE,
+                self.pipeline.pipeline,
+            );
+            self.device.device.cmd_bind_descriptor_sets(
+                buffer.buffer(),
+                vk::PipelineBindPoint::COMPUTE,
+                self.pipeline.layout.layout,
+                0,
+                &[descriptor_set.descriptor_set],
+                &[],
+            );
+            self.device.device.cmd_push_constants(
+                buffer.buffer(),
+                self.pipeline.layout.layout,
+                vk::ShaderStageFlags::COMPUTE,
+                0,
+                &push_constants.to_bytes(),
+            );
+            self.device
+                .device
+                .cmd_dispatch(buffer.buffer(), workgroups_x, workgroups_y, 1);
+            self.device.device.cmd_pipeline_barrier2(
+                buffer.buffer(),
+                &vk::DependencyInfo::default().buffer_memory_barriers(&[buffer_barrier]),
+            );
+        }
+
+        Ok(InputConversion {
+            output,
+            _input: input,
+            _descriptor_set: descriptor_set,
+        })
+    }
+}
@@ -0,0 +1,148 @@
+@group(0) @binding(0) var<storage, read> input: array<u32>;
+@group(0) @binding(1) var<storage, read_write> output: array<u32>;
+
+struct Immediates {
+  width: u32,
+  height: u32,
+  pixel_format: u32,
+  color_matrix: u32,
+  full_range: u32,
+}
+
+var<immediate> imm: Immediates;
+
+// Has to match `PixelFormat`
+const FORMAT_I420: u32 = 1;
+const FORMAT_RGBA: u32 = 2;
+const FORMAT_YUYV: u32 = 4;
+
+const MATRIX_BT709: u32 = 0;
+
+const WORKGROUP_SIZE: u32 = 256;
+
+fn to_byte(value: f32) -> u32 {
+  return u32(round(clamp(value, 0.0, 1.0) * 255.0));
+}
+
+fn input_byte(index: u32) -> u32 {
+  return (input[index / 4] >> (8 * (index % 4))) & 0xff;
+}
+
+fn chroma_size() -> vec2<u32> {
+  return vec2((imm.width + 1) / 2, (imm.height + 1) / 2);
+}
+
+fn output_size() -> u32 {
+  let chroma = chroma_size();
+  return imm.width * imm.height + 2 * chroma.x * chroma.y;
+}
+
+fn rgb(x: u32, y: u32) -> vec3<f32> {
+  let pixel = unpack4x8unorm(input[min(y, imm.height - 1) * imm.width + min(x, imm.width - 1)]);
+  return pixel.rgb;
+}
+
+// Returns Y, U and V with U and V centered around 0.
+fn rgb_to_yuv(rgb: vec3<f32>) -> vec3<f32> {
+  var kr = 0.299;
+  var kb = 0.114;
+  if imm.color_matrix == MATRIX_BT709 {
+    kr = 0.2126;
+    kb = 0.0722;
+  }
+
+  let luma = kr * rgb.r + (1.0 - kr - kb) * rgb.g + kb * rgb.b;
+  return vec3(luma, (rgb.b - luma) / (2.0 * (1.0 - kb)), (rgb.r - luma) / (2.0 * (1.0 - kr)));
+}
+
+fn luma_to_byte(luma: f32) -> u32 {
+  if imm.full_range == 0 {
+    return to_byte(16.0 / 255.0 + luma * (219.0 / 255.0));
+  }
+  return to_byte(luma);
+}
+
+fn chroma_to_byte(chroma: f32) -> u32 {
+  if imm.full_range == 0 {
+    return to_byte(128.0 / 255.0 + chroma * (224.0 / 255.0));
+  }
+  return to_byte(128.0 / 255.0 + chroma);
+}
+
+fn yuyv_row_pitch() -> u32 {
+  return 4 * chroma_size().x;
+}
+
+fn luma_byte(x: u32, y: u32) -> u32 {
+  switch imm.pixel_format {
+    case FORMAT_I420: {
+      return input_byte(y * imm.width + x);
+    }
+    case FORMAT_YUYV: {
+      return input_byte(y * yuyv_row_pitch() + (x / 2) * 4 + (x % 2) * 2);
+    }
+    default: {
+      return luma_to_byte(rgb_to_yuv(rgb(x, y)).x);
+    }
+  }
+}
+
+// `component` is 0 for U and 1 for V.
+fn chroma_byte(x: u32, y: u32, component: u32) -> u32 {
+  switch imm.pixel_format {
+    case FORMAT_I420: {
+      let chroma = chroma_size();
+      let plane_offset = imm.width * imm.height + component * chroma.x * chroma.y;
+      return input_byte(plane_offset + y * chroma.x + x);
+    }
+    case FORMAT_YUYV: {
+      // YUYV has a chroma sample for every row, the two rows are averaged
+      let offset = x * 4 + 1 + 2 * component;
+      let top = input_byte(2 * y * yuyv_row_pitch() + offset);
+      let bottom = input_byte(min(2 * y + 1, imm.height - 1) * yuyv_row_pitch() + offset);
+      return (top + bottom + 1) / 2;
+    }
+    default: {
+      let average = (
+        rgb(2 * x, 2 * y) + rgb(2 * x + 1, 2 * y) + rgb(2 * x, 2 * y + 1) + rgb(2 * x + 1, 2 * y + 1)
+      ) / 4.0;
+      return chroma_to_byte(rgb_to_yuv(average)[1 + component]);
+    }
+  }
+}
+
+// Value of a single byte of the NV12 frame.
+fn nv12_byte(index: u32) -> u32 {
+  let luma_size = imm.width * imm.height;
+  if index < luma_size {
+    return luma_byte(index % imm.width, index / imm.width);
+  }
+
+  let chroma = chroma_size();
+  let chroma_index = index - luma_size;
+  let sample = chroma_index / 2;
+  return chroma_byte(sample % chroma.x, sample / chroma.x, chroma_index % 2);
+}
+
+// Every invocation writes one word of the tightly packed NV12 output buffer.
+@compute
+@workgroup_size(256)
+fn main(
+  @builtin(global_invocation_id) id: vec3<u32>,
+  @builtin(num_workgroups) workgroups: vec3<u32>,
+) {
+  let word_index = id.y * workgroups.x * WORKGROUP_SIZE + id.x;
+  let size = output_size();
+  if word_index * 4 >= size {
+    return;
+  }
+
+  var word: u32 = 0;
+  for (var i: u32 = 0; i < 4; i++) {
+    let byte_index = word_index * 4 + i;
+    if byte_index < size {
+      word |= nv12_byte(byte_index) << (8 * i);
+    }
+  }
+  output[word_index] = word;
+}
@@ -7,7 +7,7 @@ use crate::{
     adapter::VideoAdapter,
     backends::vulkan::vulkan_adapter::VulkanAdapter,
     backends::vulkan::wrappers::*,
-    instance::{VideoInstanceBackend, VideoInstanceDescriptor},
+    instance::{VideoInstanceBackend, VideoInstanceDescriptor, sort_by_suitability},
 };
 
 pub struct VulkanInstance {
@@ -98,8 +98,22 @@ impl VulkanInstance {
         })
     }
 
+    /// Wraps an instance created outside of this crate, e.g. by an engine that already owns the
+    /// vulkan context. The instance is not destroyed on drop.
+    ///
+    /// # Safety
+    /// - `instance` has to be a valid instance created from `entry` with API version 1.3 or newer.
+    /// - `instance` has to outlive the returned object and all devices created with it.
+    #[cfg_attr(not(feature = "expose-backends"), allow(dead_code))]
+    pub unsafe fn from_raw(
+        entry: Entry,
+        instance: ash::Instance,
+        desc: &VideoInstanceDescriptor,
+    ) -> Self {
+        Self::new_unowned(instance, entry, desc)
+    }
+
     /// Creates an instance that does not own `ash::Instance`. The instance is not destroyed on drop.
-    #[cfg_attr(not(feature = "wgpu"), allow(dead_code))]
     pub(crate) fn new_unowned(
         instance: ash::Instance,
         entry: Entry,
@@ -123,6 +137,14 @@ impl VulkanInstance {
         self.instance.instance.clone()
     }
 
+    /// Returns the adapter for a physical device, e.g. the one already used by the engine that
+    /// owns the instance. Returns `None` if the device doesn't support the features required by
+    /// this crate.
+    #[cfg_attr(not(feature = "expose-backends"), allow(dead_code))]
+    pub fn adapter(&self, physical_device: vk::PhysicalDevice) -> Option<VulkanAdapter<'_>> {
+        VulkanAdapter::new(self, physical_device)
+    }
+
     pub fn iter_adapters<'a>(
         &'a self,
     ) -> Result<Box<dyn Iterator<Item = VideoAdapter<'a>> + 'a>, VideoInstanceInitError> {
@@ -135,6 +157,14 @@ impl VulkanInstance {
             move |device| VulkanAdapter::new(self, device).map(VideoAdapter::from_backend),
         )))
     }
+
+    /// Returns all physical devices that support video decoding or encoding, the most suitable
+    /// first. See [`VideoInstance::enumerate_video_adapters`](crate::VideoInstance::enumerate_video_adapters).
+    pub fn enumerate_video_adapters<'a>(
+        &'a self,
+    ) -> Result<Vec<VideoAdapter<'a>>, VideoInstanceInitError> {
+        Ok(sort_by_suitability(self.iter_adapters()?))
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -13,10 +13,10 @@ use crate::{
             DynVulkanEncoder, FullEncoderParameters, VulkanEncoder, VulkanEncoderError,
         },
         vulkan_transcoder::pipeline::{OutputConfig, ResizeSubmission, ResizingPipeline},
-        wrappers::{DecodeInputBuffer, DecodingQueryPool, SemaphoreWaitValue},
+        wrappers::{DecodeInputBuffer, DecodingQueryPool, SemaphoreWaitValue, TimestampQuery},
     },
     frame_sorter::{DecodeResult, FrameSorter},
-    parameters::DecoderUsage,
+    parameters::{DecoderUsage, Deinterlacing, PixelFormat},
     parser::{
         decoder_instructions::{DecoderInstruction, compile_to_decoder_instructions},
         h264::H264Parser,
@@ -37,7 +37,9 @@ pub(crate) struct ResizedImages {
     images: ResizeSubmission,
     decoder_wait_value: SemaphoreWaitValue,
     decode_query_pool: Option<Arc<DecodingQueryPool>>,
-    input_buffer: DecodeInputBuffer,
+    input_buffer: Option<DecodeInputBuffer>,
+    /// Kept until the frame is encoded, so it is not reused by the next decode submissions
+    _timestamp_query: Option<TimestampQuery>,
     _in_flight_resources: InFlightDecodeResources,
 }
 
@@ -72,12 +74,16 @@ impl VulkanTranscoder {
         let decoder = VulkanDecoder::new(
             Arc::new(device.decoding_device()?),
             DecoderUsage::Transcoding,
+            false,
             ImageModifiers {
                 create_flags: vk::ImageCreateFlags::EXTENDED_USAGE
                     | vk::ImageCreateFlags::MUTABLE_FORMAT,
                 usage_flags: vk::ImageUsageFlags::STORAGE,
                 additional_queue_index: device.queues.compute.family_index,
+                limits: Default::default(),
             },
+            PixelFormat::Nv12,
+            Deinterlacing::Off,
         )?;
 
         let parser = H264Parser::default();
@@ -114,6 +120,20 @@ impl VulkanTranscoder {
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        // Every transcoded frame produces exactly one chunk per output, so encoders can't
+        // reorder frames.
+        let uses_b_frames = parameters.iter().any(|p| match p {
+            AnyFullEncoderParameters::H264(p) => p.consecutive_b_frames > 0,
+            AnyFullEncoderParameters::H265(p) => p.consecutive_b_frames > 0,
+        });
+        if uses_b_frames {
+            return Err(VulkanEncoderError::ParametersError {
+                field: "consecutive_b_frames",
+                problem: "B-frames are not supported by the transcoder.".to_string(),
+            }
+            .into());
+        }
+
         let encoders = parameters
             .iter()
             .copied()
@@ -194,7 +214,9 @@ impl VulkanTranscoder {
         &mut self,
         input: EncodedInputChunk<'_>,
     ) -> Result<Vec<DecoderInstruction>, VulkanTranscoderError> {
-        let access_units = self.parser.parse(input.data, input.pts)?;
+        let access_units = self
+            .parser
+            .parse_with_dts(input.data, input.pts, input.dts)?;
         let instructions = compile_to_decoder_instructions(&mut self.reference_ctx, access_units)?;
 
         Ok(instructions)
@@ -210,6 +232,13 @@ impl VulkanTranscoder {
             let Some(mut frame) = self.decoder.decode(instruction)? else {
                 continue;
             };
+            // the resizing pipeline only reads 8-bit 4:2:0 images
+            frame
+                .decoder
+                .check_output_pixel_format(&frame.decode_result.frame.image)?;
+            frame
+                .decoder
+                .check_chroma_subsampling(&frame.decode_result.frame.image, false)?;
 
             let mut trackers = self
                 .encoders
@@ -227,6 +256,7 @@ impl VulkanTranscoder {
                     decoder_wait_value: frame.semaphore_wait_value,
                     decode_query_pool: frame.decode_query_pool,
                     input_buffer: frame.input_buffer,
+                    _timestamp_query: frame.timestamp_query,
                     _in_flight_resources: frame.in_flight_resources,
                 },
                 metadata: frame.decode_result.metadata,
@@ -285,7 +315,9 @@ impl VulkanTranscoder {
         self.decoder
             .tracker
             .mark_waited(resized_images.data.decoder_wait_value);
-        resized_images.data.input_buffer.release_to_pool();
+        if let Some(input_buffer) = resized_images.data.input_buffer {
+            input_buffer.release_to_pool();
+        }
 
         self.resizing_pipeline
             .mark_command_buffers_completed(resized_images.data.decoder_wait_value);
@@ -5,7 +5,6 @@ use ash::{Entry, vk};
 mod command;
 mod debug;
 mod mem;
-#[cfg(feature = "transcoder")]
 mod pipeline;
 mod sync;
 mod video;
@@ -14,7 +13,6 @@ mod vk_extensions;
 pub(crate) use command::*;
 pub(crate) use debug::*;
 pub(crate) use mem::*;
-#[cfg(feature = "transcoder")]
 pub(crate) use pipeline::*;
 pub(crate) use sync::*;
 pub(crate) use video::*;
@@ -77,6 +75,12 @@ pub(crate) struct Device {
     pub(crate) video_decode_queue_ext: ash::khr::video_decode_queue::Device,
     pub(crate) video_encode_queue_ext: ash::khr::video_encode_queue::Device,
     pub(crate) debug_utils_ext: Option<ash::ext::debug_utils::Device>,
+    /// `None` if the device does not support exporting memory.
+    pub(crate) external_memory_ext: Option<ExternalMemoryDevice>,
+    /// `None` if the device does not support exporting timeline semaphores.
+    pub(crate) external_semaphore_ext: Option<ExternalSemaphoreDevice>,
+    /// `false` for devices created outside of this crate.
+    pub(crate) destroy_device_on_drop: bool,
     pub(crate) _instance: Arc<Instance>,
 }
 
@@ -129,7 +133,9 @@ impl std::ops::Deref for Device {
 
 impl Drop for Device {
     fn drop(&mut self) {
-        unsafe { self.destroy_device(None) };
+        if self.destroy_device_on_drop {
+            unsafe { self.destroy_device(None) };
+        }
     }
 }
 
@@ -8,29 +8,114 @@ use rustc_hash::FxHashMap;
 
 use crate::backends::vulkan::{
     VulkanCommonError, VulkanDevice,
-    wrappers::{ImageKey, ImageLayoutTracker, SemaphoreWaitValue},
+    wrappers::{Device, ImageKey, ImageLayoutTracker, SemaphoreWaitValue},
 };
 
+/// Maximum number of idle command pools kept for every queue family.
+const MAX_IDLE_COMMAND_POOLS: usize = 32;
+
+/// Command pools of coders that were dropped, kept by [`VulkanDevice`] so that new coders can
+/// reuse them together with the command buffers allocated from them. This makes creating coders
+/// cheaper when many streams are started and stopped on one device.
+///
+/// Only idle pools are shared, coders that exist at the same time use separate pools.
+pub(crate) struct CommandPoolCache {
+    device: Arc<Device>,
+    idle: Mutex<FxHashMap<usize, Vec<IdleCommandPool>>>,
+}
+
+struct IdleCommandPool {
+    command_pool: vk::CommandPool,
+    buffers: Vec<vk::CommandBuffer>,
+}
+
+impl CommandPoolCache {
+    pub(crate) fn new(device: Arc<Device>) -> Self {
+        Self {
+            device,
+            idle: Default::default(),
+        }
+    }
+
+    fn take(&self, queue_family_index: usize) -> Option<IdleCommandPool> {
+        self.idle
+            .lock()
+            .unwrap()
+            .get_mut(&queue_family_index)
+            .and_then(Vec::pop)
+    }
+
+    /// The command buffers must not be pending execution.
+    fn recycle(
+        &self,
+        queue_family_index: usize,
+        command_pool: vk::CommandPool,
+        buffers: Vec<vk::CommandBuffer>,
+    ) {
+        let reset_result = unsafe {
+            self.device
+                .reset_command_pool(command_pool, vk::CommandPoolResetFlags::empty())
+        };
+
+        let mut idle = self.idle.lock().unwrap();
+        let pools = idle.entry(queue_family_index).or_default();
+        if let Err(e) = reset_result {
+            tracing::error!("Failed to reset a command pool before reusing it: {e}");
+        } else if pools.len() < MAX_IDLE_COMMAND_POOLS {
+            pools.push(IdleCommandPool {
+                command_pool,
+                buffers,
+            });
+            return;
+        }
+
+        unsafe { self.device.destroy_command_pool(command_pool, None) };
+    }
+}
+
+impl Drop for CommandPoolCache {
+    fn drop(&mut self) {
+        let idle = std::mem::take(self.idle.get_mut().unwrap());
+        for pool in idle.into_values().flatten() {
+            unsafe { self.device.destroy_command_pool(pool.command_pool, None) };
+        }
+    }
+}
+
 struct CommandPool {
     command_pool: vk::CommandPool,
+    queue_family_index: usize,
     device: Arc<VulkanDevice>,
 }
 
 impl CommandPool {
+    /// Takes an idle pool from the [`CommandPoolCache`] of the device if there is one. Returns
+    /// the command buffers that were allocated from the reused pool.
     fn new(
         device: Arc<VulkanDevice>,
         queue_family_index: usize,
-    ) -> Result<Self, VulkanCommonError> {
+    ) -> Result<(Self, Vec<vk::CommandBuffer>), VulkanCommonError> {
+        if let Some(idle) = device.command_pool_cache.take(queue_family_index) {
+            let pool = Self {
+                command_pool: idle.command_pool,
+                queue_family_index,
+                device,
+            };
+            return Ok((pool, idle.buffers));
+        }
+
         let create_info = vk::CommandPoolCreateInfo::default()
             .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER)
             .queue_family_index(queue_family_index as u32);
 
         let command_pool = unsafe { device.device.create_command_pool(&create_info, None)? };
 
-        Ok(Self {
+        let pool = Self {
             device,
+            queue_family_index,
             command_pool,
-        })
+        };
+        Ok((pool, Vec::new()))
     }
 
     fn new_primary(&self) -> Result<vk::CommandBuffer, VulkanCommonError> {
@@ -47,10 +132,22 @@ impl CommandPool {
 
         Ok(buffer)
     }
+
+    /// Gives the pool back to the [`CommandPoolCache`] instead of destroying it on drop.
+    fn recycle(&mut self, buffers: Vec<vk::CommandBuffer>) {
+        let command_pool = std::mem::replace(&mut self.command_pool, vk::CommandPool::null());
+        self.device
+            .command_pool_cache
+            .recycle(self.queue_family_index, command_pool, buffers);
+    }
 }
 
 impl Drop for CommandPool {
     fn drop(&mut self) {
+        if self.command_pool == vk::CommandPool::null() {
+            return;
+        }
+
         unsafe {
             self.device
                 .device
@@ -80,16 +177,27 @@ pub(crate) struct CommandBufferPoolInner {
     submitted: VecDeque<SubmittedCommandBuffer>,
 }
 
+impl Drop for CommandBufferPoolInner {
+    fn drop(&mut self) {
+        // The submitted buffers might still be executing, so a pool that has any of them
+        // cannot be reused
+        if self.submitted.is_empty() {
+            let buffers = std::mem::take(&mut self.free);
+            self.command_pool.recycle(buffers);
+        }
+    }
+}
+
 impl CommandBufferPool {
     pub(crate) fn new(
         device: Arc<VulkanDevice>,
         queue_family_index: usize,
     ) -> Result<Self, VulkanCommonError> {
-        let command_pool = CommandPool::new(device, queue_family_index)?;
+        let (command_pool, free) = CommandPool::new(device, queue_family_index)?;
 
         Ok(Self(Arc::new(Mutex::new(CommandBufferPoolInner {
             command_pool,
-            free: Vec::new(),
+            free,
             submitted: VecDeque::new(),
         }))))
     }
@@ -1,4 +1,8 @@
-use std::{ffi::c_void, sync::Arc};
+use std::{
+    ffi::c_void,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use ash::vk::{self, QueryType};
 use tracing::{error, info, trace, warn};
@@ -171,6 +175,159 @@ impl DecodingQueryPool {
     }
 }
 
+/// Timestamp query pools recycled between GPU operations which can be in flight at the same
+/// time, e.g. decode submissions. Each operation gets its own [`TimestampQuery`], so it can't
+/// overwrite the results of another one before they are read.
+pub(crate) struct TimestampQueries {
+    device: Arc<Device>,
+    timestamp_period: f32,
+    timestamp_valid_bits: u32,
+    free: Mutex<Vec<TimestampQueryPool>>,
+}
+
+impl TimestampQueries {
+    pub(crate) fn new(
+        device: Arc<Device>,
+        timestamp_period: f32,
+        timestamp_valid_bits: u32,
+    ) -> Self {
+        Self {
+            device,
+            timestamp_period,
+            timestamp_valid_bits,
+            free: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Takes a free query pool or creates a new one if all of them are in use.
+    pub(crate) fn acquire(self: &Arc<Self>) -> Result<TimestampQuery, VulkanCommonError> {
+        let pool = match self.free.lock().unwrap().pop() {
+            Some(pool) => pool,
+            None => TimestampQueryPool::new(
+                self.device.clone(),
+                self.timestamp_period,
+                self.timestamp_valid_bits,
+            )?,
+        };
+
+        Ok(TimestampQuery {
+            pool: Some(pool),
+            queries: self.clone(),
+        })
+    }
+}
+
+/// A [`TimestampQueryPool`] used by a single GPU operation. Returned to [`TimestampQueries`]
+/// when dropped, so it must live until the results are read or the operation is finished.
+pub(crate) struct TimestampQuery {
+    pool: Option<TimestampQueryPool>,
+    queries: Arc<TimestampQueries>,
+}
+
+impl std::ops::Deref for TimestampQuery {
+    type Target = TimestampQueryPool;
+
+    fn deref(&self) -> &Self::Target {
+        self.pool.as_ref().unwrap()
+    }
+}
+
+impl Drop for TimestampQuery {
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.take() {
+            self.queries.free.lock().unwrap().push(pool);
+        }
+    }
+}
+
+/// Two timestamp queries written around a single GPU operation.
+pub(crate) struct TimestampQueryPool {
+    pool: QueryPool,
+    /// Number of nanoseconds it takes for the timestamp to be incremented by 1.
+    timestamp_period: f32,
+    timestamp_valid_bits: u32,
+}
+
+impl TimestampQueryPool {
+    const START: u32 = 0;
+    const END: u32 = 1;
+
+    pub(crate) fn new(
+        device: Arc<Device>,
+        timestamp_period: f32,
+        timestamp_valid_bits: u32,
+    ) -> Result<Self, VulkanCommonError> {
+        let pool = QueryPool::new(
+            device,
+            QueryType::TIMESTAMP,
+            2,
+            None,
+            None::<vk::VideoProfileInfoKHR>,
+        )?;
+
+        Ok(Self {
+            pool,
+            timestamp_period,
+            timestamp_valid_bits,
+        })
+    }
+
+    pub(crate) fn reset(&self, buffer: vk::CommandBuffer) {
+        unsafe {
+            self.pool
+                .device
+                .cmd_reset_query_pool(buffer, self.pool.pool, 0, 2)
+        };
+    }
+
+    pub(crate) fn write_start(&self, buffer: vk::CommandBuffer) {
+        unsafe {
+            self.pool.device.cmd_write_timestamp2(
+                buffer,
+                vk::PipelineStageFlags2::TOP_OF_PIPE,
+                self.pool.pool,
+                Self::START,
+            )
+        };
+    }
+
+    pub(crate) fn write_end(&self, buffer: vk::CommandBuffer) {
+        unsafe {
+            self.pool.device.cmd_write_timestamp2(
+                buffer,
+                vk::PipelineStageFlags2::BOTTOM_OF_PIPE,
+                self.pool.pool,
+                Self::END,
+            )
+        };
+    }
+
+    /// Returns the time elapsed on the GPU between [`Self::write_start`] and [`Self::write_end`].
+    pub(crate) fn get_duration_blocking(&self) -> Result<Duration, VulkanCommonError> {
+        let mut result = [0u64; 2];
+        unsafe {
+            self.pool.device.get_query_pool_results(
+                self.pool.pool,
+                0,
+                &mut result,
+                vk::QueryResultFlags::WAIT | vk::QueryResultFlags::TYPE_64,
+            )?
+        };
+
+        let mask = match self.timestamp_valid_bits {
+            64.. => u64::MAX,
+            bits => (1u64 << bits) - 1,
+        };
+        let start = result[Self::START as usize] & mask;
+        let end = result[Self::END as usize] & mask;
+        let ticks = end.wrapping_sub(start) & mask;
+
+        Ok(Duration::from_nanos(
+            (ticks as f64 * self.timestamp_period as f64) as u64,
+        ))
+    }
+}
+
 pub(crate) struct QueryPool {
     pub(crate) pool: vk::QueryPool,
     pub(crate) device: Arc<Device>,
@@ -5,7 +5,6 @@ use vk_mem::Alloc;
 
 use crate::backends::vulkan::{
     VulkanCommonError, VulkanDeviceInitError,
-    codec::h264::parameters::H264DecodeProfileInfo,
     vulkan_decoder::VulkanDecoderError,
     vulkan_device::EncodingDevice,
     wrappers::{ImageLayoutTracker, OpenCommandBuffer, ProfileInfo},
@@ -88,11 +87,11 @@ impl Drop for MemoryAllocation {
 pub(crate) struct DecodeInputBufferPool<'a> {
     freelist: Arc<Mutex<Vec<DecodeInputBuffer>>>,
     allocator: Arc<Allocator>,
-    profile: Arc<H264DecodeProfileInfo<'a>>,
+    profile: Arc<ProfileInfo<'a>>,
 }
 
 impl<'a> DecodeInputBufferPool<'a> {
-    pub(crate) fn new(allocator: Arc<Allocator>, profile: Arc<H264DecodeProfileInfo<'a>>) -> Self {
+    pub(crate) fn new(allocator: Arc<Allocator>, profile: Arc<ProfileInfo<'a>>) -> Self {
         Self {
             allocator,
             freelist: Arc::new(Mutex::new(Vec::new())),
@@ -123,7 +122,7 @@ pub(crate) struct DecodeInputBuffer {
 impl DecodeInputBuffer {
     pub(crate) fn new(
         allocator: Arc<Allocator>,
-        profile: &H264DecodeProfileInfo,
+        profile: &ProfileInfo,
         pool_freelist: Weak<Mutex<Vec<DecodeInputBuffer>>>,
     ) -> Result<Self, VulkanDecoderError> {
         const INITIAL_SIZE: u64 = 1024 * 1024; // 1MiB
@@ -142,7 +141,7 @@ impl DecodeInputBuffer {
         &mut self,
         data: &[u8],
         size: u64,
-        profile: &H264DecodeProfileInfo,
+        profile: &ProfileInfo,
     ) -> Result<(), VulkanDecoderError> {
         debug_assert!(data.len() as u64 <= size);
 
@@ -186,10 +185,10 @@ impl Buffer {
     pub(crate) fn new_decode(
         allocator: Arc<Allocator>,
         size: u64,
-        profile: &H264DecodeProfileInfo,
+        profile: &ProfileInfo,
     ) -> Result<Self, VulkanCommonError> {
         let mut profile_list_info = vk::VideoProfileListInfoKHR::default()
-            .profiles(std::slice::from_ref(&profile.profile_info.profile_info));
+            .profiles(std::slice::from_ref(&profile.profile_info));
 
         let buffer_create_info = vk::BufferCreateInfo::default()
             .size(size)
@@ -235,6 +234,37 @@ impl Buffer {
         Self::new(allocator, buffer_create_info, direction)
     }
 
+    /// Storage buffer that compute shaders write into and the host reads from. It can also be
+    /// the source of a copy to an image.
+    pub(crate) fn new_storage_readback(
+        allocator: Arc<Allocator>,
+        size: u64,
+    ) -> Result<Self, VulkanCommonError> {
+        let buffer_create_info = vk::BufferCreateInfo::default()
+            .size(size)
+            .usage(vk::BufferUsageFlags::STORAGE_BUFFER | vk::BufferUsageFlags::TRANSFER_SRC)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+        Self::new(allocator, buffer_create_info, TransferDirection::GpuToMem)
+    }
+
+    /// Storage buffer that compute shaders read from, filled with `data`. The size is rounded
+    /// up to whole words, so shaders can read it as an array of `u32`.
+    pub(crate) fn new_storage_with_data(
+        allocator: Arc<Allocator>,
+        data: &[u8],
+    ) -> Result<Self, VulkanCommonError> {
+        let buffer_create_info = vk::BufferCreateInfo::default()
+            .size((data.len() as u64).next_multiple_of(4))
+            .usage(vk::BufferUsageFlags::STORAGE_BUFFER)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+        let mut result = Self::new(allocator, buffer_create_info, TransferDirection::MemToGpu)?;
+        result.copy_data_into(data)?;
+
+        Ok(result)
+    }
+
     pub(crate) fn new_transfer_with_data(
         allocator: Arc<Allocator>,
         data: &[u8],
@@ -344,6 +374,7 @@ pub(crate) struct Image {
     tracker: Arc<Mutex<ImageLayoutTracker>>,
     pub(crate) device: Arc<Device>,
     pub(crate) extent: vk::Extent3D,
+    pub(crate) format: vk::Format,
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -356,6 +387,7 @@ impl Image {
         tracker: Arc<Mutex<ImageLayoutTracker>>,
     ) -> Result<Self, VulkanCommonError> {
         let extent = image_create_info.extent;
+        let format = image_create_info.format;
         let alloc_info = vk_mem::AllocationCreateInfo {
             usage: vk_mem::MemoryUsage::Auto,
             ..Default::default()
@@ -377,6 +409,7 @@ impl Image {
             allocator,
             tracker,
             extent,
+            format,
         })
     }
 
@@ -562,7 +595,6 @@ impl Image {
         ImageKey(self.image.as_raw())
     }
 
-    #[cfg_attr(not(feature = "transcoder"), allow(dead_code))]
     pub(crate) fn create_plane_view(
         self: &Arc<Self>,
         layer: u32,
@@ -644,3 +676,162 @@ impl Drop for ImageView {
         unsafe { self.device.destroy_image_view(self.view, None) };
     }
 }
+
+/// Type of the handles that decoded frames are exported as. DMA-BUFs can be imported by VA-API,
+/// GStreamer and EGL, opaque handles on Windows by other Vulkan, D3D12 and OpenGL devices.
+#[cfg(unix)]
+pub(crate) const EXTERNAL_MEMORY_HANDLE_TYPE: vk::ExternalMemoryHandleTypeFlags =
+    vk::ExternalMemoryHandleTypeFlags::DMA_BUF_EXT;
+#[cfg(windows)]
+pub(crate) const EXTERNAL_MEMORY_HANDLE_TYPE: vk::ExternalMemoryHandleTypeFlags =
+    vk::ExternalMemoryHandleTypeFlags::OPAQUE_WIN32;
+
+#[cfg(unix)]
+pub(crate) type ExternalMemoryDevice = ash::khr::external_memory_fd::Device;
+#[cfg(windows)]
+pub(crate) type ExternalMemoryDevice = ash::khr::external_memory_win32::Device;
+
+/// Buffer with its own memory allocation, which can be exported to other APIs and processes.
+/// It is not suballocated, because an exported handle gives access to the whole allocation.
+pub(crate) struct ExportableBuffer {
+    pub(crate) buffer: vk::Buffer,
+    memory: vk::DeviceMemory,
+    /// Size of the memory allocation, which can be bigger than the size of the buffer.
+    pub(crate) allocation_size: u64,
+    device: Arc<Device>,
+}
+
+impl ExportableBuffer {
+    pub(crate) fn new(
+        device: Arc<Device>,
+        memory_properties: &vk::PhysicalDeviceMemoryProperties,
+        size: u64,
+    ) -> Result<Self, VulkanCommonError> {
+        let mut external_info =
+            vk::ExternalMemoryBufferCreateInfo::default().handle_types(EXTERNAL_MEMORY_HANDLE_TYPE);
+        let create_info = vk::BufferCreateInfo::default()
+            .size(size)
+            .usage(vk::BufferUsageFlags::TRANSFER_DST)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .push_next(&mut external_info);
+
+        let buffer = unsafe { device.create_buffer(&create_info, None)? };
+        let mut result = Self {
+            buffer,
+            memory: vk::DeviceMemory::null(),
+            allocation_size: 0,
+            device,
+        };
+
+        let requirements = unsafe { result.device.get_buffer_memory_requirements(buffer) };
+        let memory_type_index = find_memory_type_index(
+            memory_properties,
+            requirements.memory_type_bits,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        )
+        .or_else(|| {
+            find_memory_type_index(
+                memory_properties,
+                requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::empty(),
+            )
+        })
+        .ok_or(VulkanCommonError::NoSuitableMemoryType)?;
+
+        let mut export_info =
+            vk::ExportMemoryAllocateInfo::default().handle_types(EXTERNAL_MEMORY_HANDLE_TYPE);
+        let mut dedicated_info = vk::MemoryDedicatedAllocateInfo::default().buffer(buffer);
+        let allocate_info = vk::MemoryAllocateInfo::default()
+            .allocation_size(requirements.size)
+            .memory_type_index(memory_type_index)
+            .push_next(&mut export_info)
+            .push_next(&mut dedicated_info);
+
+        result.memory = unsafe { result.device.allocate_memory(&allocate_info, None)? };
+        result.allocation_size = requirements.size;
+        unsafe {
+            result
+                .device
+                .bind_buffer_memory(result.buffer, result.memory, 0)?
+        };
+
+        Ok(result)
+    }
+
+    /// Returns a new handle to the memory of the buffer. The memory stays alive as long as the
+    /// handle is open, even after the buffer is dropped.
+    #[cfg(unix)]
+    pub(crate) fn export(&self) -> Result<std::os::fd::OwnedFd, VulkanCommonError> {
+        use std::os::fd::FromRawFd;
+
+        let ext = self
+            .device
+            .external_memory_ext
+            .as_ref()
+            .ok_or(VulkanCommonError::ExternalMemoryUnsupported)?;
+        let fd = unsafe {
+            ext.get_memory_fd(
+                &vk::MemoryGetFdInfoKHR::default()
+                    .memory(self.memory)
+                    .handle_type(EXTERNAL_MEMORY_HANDLE_TYPE),
+            )?
+        };
+
+        Ok(unsafe { std::os::fd::OwnedFd::from_raw_fd(fd) })
+    }
+
+    /// Returns a new handle to the memory of the buffer. The memory stays alive as long as the
+    /// handle is open, even after the buffer is dropped.
+    #[cfg(windows)]
+    pub(crate) fn export(&self) -> Result<std::os::windows::io::OwnedHandle, VulkanCommonError> {
+        use std::os::windows::io::FromRawHandle;
+
+        let ext = self
+            .device
+            .external_memory_ext
+            .as_ref()
+            .ok_or(VulkanCommonError::ExternalMemoryUnsupported)?;
+        let handle = unsafe {
+            ext.get_memory_win32_handle(
+                &vk::MemoryGetWin32HandleInfoKHR::default()
+                    .memory(self.memory)
+                    .handle_type(EXTERNAL_MEMORY_HANDLE_TYPE),
+            )?
+        };
+
+        Ok(unsafe { std::os::windows::io::OwnedHandle::from_raw_handle(handle as _) })
+    }
+}
+
+impl Drop for ExportableBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.destroy_buffer(self.buffer, None);
+            if self.memory != vk::DeviceMemory::null() {
+                self.device.free_memory(self.memory, None);
+            }
+        }
+    }
+}
+
+impl std::ops::Deref for ExportableBuffer {
+    type Target = vk::Buffer;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+fn find_memory_type_index(
+    memory_properties: &vk::PhysicalDeviceMemoryProperties,
+    memory_type_bits: u32,
+    flags: vk::MemoryPropertyFlags,
+) -> Option<u32> {
+    memory_properties.memory_types[..memory_properties.memory_type_count as usize]
+        .iter()
+        .enumerate()
+        .find(|(i, memory_type)| {
+            memory_type_bits & (1 << i) != 0 && memory_type.property_flags.contains(flags)
+        })
+        .map(|(i, _)| i as u32)
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::hash_map::Entry,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use ash::vk;
@@ -10,8 +11,22 @@ use crate::backends::vulkan::{VulkanCommonError, wrappers::ImageKey};
 
 use super::Device;
 
+/// Type of the handles that timeline semaphores are exported as.
+#[cfg(unix)]
+pub(crate) const EXTERNAL_SEMAPHORE_HANDLE_TYPE: vk::ExternalSemaphoreHandleTypeFlags =
+    vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD;
+#[cfg(windows)]
+pub(crate) const EXTERNAL_SEMAPHORE_HANDLE_TYPE: vk::ExternalSemaphoreHandleTypeFlags =
+    vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_WIN32;
+
+#[cfg(unix)]
+pub(crate) type ExternalSemaphoreDevice = ash::khr::external_semaphore_fd::Device;
+#[cfg(windows)]
+pub(crate) type ExternalSemaphoreDevice = ash::khr::external_semaphore_win32::Device;
+
 pub(crate) struct TimelineSemaphore {
     pub(crate) semaphore: vk::Semaphore,
+    exportable: bool,
     device: Arc<Device>,
 }
 
@@ -29,7 +44,93 @@ impl TimelineSemaphore {
 
         device.set_label(semaphore, label)?;
 
-        Ok(Self { semaphore, device })
+        Ok(Self {
+            semaphore,
+            exportable: false,
+            device,
+        })
+    }
+
+    /// Semaphore that can be exported with [`Self::export`] and waited for by other APIs and
+    /// processes.
+    pub(crate) fn new_exportable(
+        device: Arc<Device>,
+        initial_value: u64,
+        label: Option<&str>,
+    ) -> Result<Self, VulkanCommonError> {
+        if device.external_semaphore_ext.is_none() {
+            return Err(VulkanCommonError::ExternalSemaphoreUnsupported);
+        }
+
+        let mut create_type_info = vk::SemaphoreTypeCreateInfo::default()
+            .semaphore_type(vk::SemaphoreType::TIMELINE)
+            .initial_value(initial_value);
+        let mut export_info =
+            vk::ExportSemaphoreCreateInfo::default().handle_types(EXTERNAL_SEMAPHORE_HANDLE_TYPE);
+        let create_info = vk::SemaphoreCreateInfo::default()
+            .push_next(&mut create_type_info)
+            .push_next(&mut export_info);
+        let semaphore = unsafe { device.create_semaphore(&create_info, None)? };
+
+        device.set_label(semaphore, label)?;
+
+        Ok(Self {
+            semaphore,
+            exportable: true,
+            device,
+        })
+    }
+
+    /// Current value of the semaphore, does not block.
+    pub(crate) fn counter_value(&self) -> Result<SemaphoreWaitValue, VulkanCommonError> {
+        let value = unsafe { self.device.get_semaphore_counter_value(self.semaphore)? };
+        Ok(SemaphoreWaitValue(value))
+    }
+
+    /// Returns a new handle to the semaphore.
+    #[cfg(unix)]
+    pub(crate) fn export(&self) -> Result<std::os::fd::OwnedFd, VulkanCommonError> {
+        use std::os::fd::FromRawFd;
+
+        let Some(ext) = self.device.external_semaphore_ext.as_ref() else {
+            return Err(VulkanCommonError::ExternalSemaphoreUnsupported);
+        };
+        if !self.exportable {
+            return Err(VulkanCommonError::ExternalSemaphoreUnsupported);
+        }
+
+        let fd = unsafe {
+            ext.get_semaphore_fd(
+                &vk::SemaphoreGetFdInfoKHR::default()
+                    .semaphore(self.semaphore)
+                    .handle_type(EXTERNAL_SEMAPHORE_HANDLE_TYPE),
+            )?
+        };
+
+        Ok(unsafe { std::os::fd::OwnedFd::from_raw_fd(fd) })
+    }
+
+    /// Returns a new handle to the semaphore.
+    #[cfg(windows)]
+    pub(crate) fn export(&self) -> Result<std::os::windows::io::OwnedHandle, VulkanCommonError> {
+        use std::os::windows::io::FromRawHandle;
+
+        let Some(ext) = self.device.external_semaphore_ext.as_ref() else {
+            return Err(VulkanCommonError::ExternalSemaphoreUnsupported);
+        };
+        if !self.exportable {
+            return Err(VulkanCommonError::ExternalSemaphoreUnsupported);
+        }
+
+        let handle = unsafe {
+            ext.get_semaphore_win32_handle(
+                &vk::SemaphoreGetWin32HandleInfoKHR::default()
+                    .semaphore(self.semaphore)
+                    .handle_type(EXTERNAL_SEMAPHORE_HANDLE_TYPE),
+            )?
+        };
+
+        Ok(unsafe { std::os::windows::io::OwnedHandle::from_raw_handle(handle as _) })
     }
 
     pub(crate) fn wait(
@@ -105,7 +206,6 @@ impl<K: TrackerKind> Tracker<K> {
         })
     }
 
-    #[allow(dead_code)]
     pub(crate) fn wait_for_all(&mut self, timeout: u64) -> Result<(), VulkanCommonError> {
         let waited_for = self.semaphore_tracker.wait_for_all(timeout)?;
 
@@ -132,6 +232,23 @@ impl<K: TrackerKind> Tracker<K> {
     }
 }
 
+/// Upper bound for waiting on the submitted work when a tracker is dropped, so that dropping
+/// a coder does not hang forever e.g. after the device was lost.
+const DROP_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Waits for all submitted work, so that the command buffers are not destroyed while they are
+/// executing and their pools can be reused by other coders. If the wait fails, the command
+/// buffers stay marked as submitted and their pool is not reused.
+impl<K: TrackerKind> Drop for Tracker<K> {
+    fn drop(&mut self) {
+        if let Err(e) = self.wait_for_all(DROP_WAIT_TIMEOUT.as_nanos() as u64) {
+            tracing::error!(
+                "Failed to wait {DROP_WAIT_TIMEOUT:?} for the submitted work before dropping a tracker: {e}"
+            );
+        }
+    }
+}
+
 pub(crate) struct SemaphoreSubmitInfo<'a, S> {
     pub(crate) signal: TrackerWait<S>,
     tracker: &'a mut SemaphoreTracker<S>,
@@ -5,11 +5,11 @@ use ash::vk;
 use crate::{
     backends::vulkan::{
         VulkanCommonError,
-        codec::Codec,
+        codec::{Codec, EncodeCodec},
         vulkan_device::{VulkanDevice, queues::VideoQueues},
         wrappers::{ImageLayoutTracker, OpenCommandBuffer},
     },
-    parser::reference_manager::{PictureInfo, ReferencePictureInfo},
+    parser::reference_manager::{Field, PictureInfo, ReferencePictureInfo},
 };
 
 use super::{Device, Image, ImageView, MemoryAllocation, VideoQueueExt};
@@ -25,46 +25,60 @@ impl VideoSessionParameters {
         session: vk::VideoSessionKHR,
         initial_parameters: C::VkParameters<'_>,
         template: Option<&Self>,
-        encode_quality_level: Option<u32>,
     ) -> Result<Self, VulkanCommonError> {
         let decode_add_info = C::decode_parameters_add_info(&initial_parameters);
+        let mut decode_create_info = C::decode_parameters_create_info(&decode_add_info);
+
+        let create_info = Self::create_info(session, template).push_next(&mut decode_create_info);
+
+        Self::create(device, &create_info)
+    }
 
+    pub(crate) fn new_encode<C: EncodeCodec>(
+        device: Arc<Device>,
+        session: vk::VideoSessionKHR,
+        initial_parameters: C::VkParameters<'_>,
+        template: Option<&Self>,
+        encode_quality_level: u32,
+    ) -> Result<Self, VulkanCommonError> {
         let encode_add_info = C::encode_parameters_add_info(&initial_parameters);
+        let mut encode_create_info = C::encode_parameters_create_info(&encode_add_info);
 
-        let mut quality_level = vk::VideoEncodeQualityLevelInfoKHR::default();
+        let mut quality_level =
+            vk::VideoEncodeQualityLevelInfoKHR::default().quality_level(encode_quality_level);
 
-        let mut create_info = vk::VideoSessionParametersCreateInfoKHR::default()
+        let create_info = Self::create_info(session, template)
+            .push_next(&mut encode_create_info)
+            .push_next(&mut quality_level);
+
+        Self::create(device, &create_info)
+    }
+
+    fn create_info<'a>(
+        session: vk::VideoSessionKHR,
+        template: Option<&Self>,
+    ) -> vk::VideoSessionParametersCreateInfoKHR<'a> {
+        vk::VideoSessionParametersCreateInfoKHR::default()
             .flags(vk::VideoSessionParametersCreateFlagsKHR::empty())
             .video_session_parameters_template(
                 template
                     .map(|t| t.parameters)
                     .unwrap_or_else(vk::VideoSessionParametersKHR::null),
             )
-            .video_session(session);
-
-        let mut decode_create_info = C::decode_parameters_create_info(&decode_add_info);
-
-        let mut encode_create_info = C::encode_parameters_create_info(&encode_add_info);
-
-        if let Some(encode_quality_level) = encode_quality_level {
-            quality_level = quality_level.quality_level(encode_quality_level);
-            create_info = create_info
-                .push_next(&mut encode_create_info)
-                .push_next(&mut quality_level);
-        } else {
-            create_info = create_info.push_next(&mut decode_create_info);
-        }
+            .video_session(session)
+    }
 
+    fn create(
+        device: Arc<Device>,
+        create_info: &vk::VideoSessionParametersCreateInfoKHR,
+    ) -> Result<Self, VulkanCommonError> {
         let parameters = unsafe {
             device
                 .video_queue_ext
-                .create_video_session_parameters_khr(&create_info, None)?
+                .create_video_session_parameters_khr(create_info, None)?
         };
 
-        Ok(Self {
-            parameters,
-            device: device.clone(),
-        })
+        Ok(Self { parameters, device })
     }
 
     pub(crate) fn add(
@@ -120,11 +134,8 @@ impl VideoSession {
         max_active_references: u32,
         flags: vk::VideoSessionCreateFlagsKHR,
         std_header_version: &vk::ExtensionProperties,
+        format: vk::Format,
     ) -> Result<Self, VulkanCommonError> {
-        // TODO: this probably works, but this format needs to be detected and set
-        // based on what the GPU supports
-        let format = vk::Format::G8_B8R8_2PLANE_420_UNORM;
-
         let session_create_info = vk::VideoSessionCreateInfoKHR::default()
             .queue_family_index(queue.family_index as u32)
             .video_profile(profile_info)
@@ -214,8 +225,8 @@ impl From<ReferencePictureInfo> for vk::native::StdVideoDecodeH264ReferenceInfo
                 __bindgen_padding_0: [0; 3],
                 _bitfield_align_1: [],
                 _bitfield_1: vk::native::StdVideoDecodeH264ReferenceInfoFlags::new_bitfield_1(
-                    0,
-                    0,
+                    picture_info.fields.top.into(),
+                    picture_info.fields.bottom.into(),
                     picture_info.is_long_term().into(),
                     picture_info.non_existing.into(),
                 ),
@@ -229,13 +240,22 @@ impl From<ReferencePictureInfo> for vk::native::StdVideoDecodeH264ReferenceInfo
 
 impl From<PictureInfo> for vk::native::StdVideoDecodeH264ReferenceInfo {
     fn from(picture_info: PictureInfo) -> Self {
+        // the second field completes the frame, so both of its fields can be referenced
+        let (top_field_flag, bottom_field_flag) =
+            match (picture_info.field, picture_info.second_field) {
+                (None, _) => (0, 0),
+                (Some(_), true) => (1, 1),
+                (Some(Field::Top), false) => (1, 0),
+                (Some(Field::Bottom), false) => (0, 1),
+            };
+
         vk::native::StdVideoDecodeH264ReferenceInfo {
             flags: vk::native::StdVideoDecodeH264ReferenceInfoFlags {
                 __bindgen_padding_0: [0; 3],
                 _bitfield_align_1: [],
                 _bitfield_1: vk::native::StdVideoDecodeH264ReferenceInfoFlags::new_bitfield_1(
-                    0,
-                    0,
+                    top_field_flag,
+                    bottom_field_flag,
                     picture_info.used_for_long_term_reference.into(),
                     picture_info.non_existing.into(),
                 ),
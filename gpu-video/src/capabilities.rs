@@ -1,7 +1,18 @@
-pub use crate::adapter::{DeviceType, VideoAdapterInfo};
+pub use crate::adapter::{DeviceType, VideoAdapterInfo, VideoCodec, VideoQueueFamilyInfo};
 
 use crate::parameters::{H264Profile, H265Profile};
 
+/// All video capabilities of the device
+#[derive(Debug, Clone, Copy)]
+pub struct VideoCapabilities {
+    /// Whether the device has a video decode queue
+    pub supports_decoding: bool,
+    /// Whether the device has a video encode queue
+    pub supports_encoding: bool,
+    pub decode: DecodeCapabilities,
+    pub encode: EncodeCapabilities,
+}
+
 /// The device capabilities for encoding
 #[derive(Debug, Clone, Copy)]
 pub struct EncodeCapabilities {
@@ -56,6 +67,9 @@ impl EncodeH264Capabilities {
 pub struct RateControlCapabilities {
     pub vbr_supported: bool,
     pub cbr_supported: bool,
+    /// Whether [`RateControl::Disabled`](crate::parameters::RateControl::Disabled) and
+    /// [`RateControl::ConstantQp`](crate::parameters::RateControl::ConstantQp) can be used
+    pub constant_qp_supported: bool,
 }
 
 /// The device capabilities for encoding in a specific codec, at a specific profile
@@ -69,10 +83,20 @@ pub struct EncodeProfileCapabilities {
     pub min_height: u32,
     /// The maximum height of the coded image
     pub max_height: u32,
+    /// The maximum number of pictures in the decoded picture buffer, including the current one
+    pub max_dpb_slots: u32,
     // The supported rate control modes
     pub rate_control: RateControlCapabilities,
     /// Maximum number of back references a P-frame can have
     pub max_references: u32,
+    /// Whether the encoder can produce B-frames
+    pub b_frames_supported: bool,
+    /// Maximum number of slices a frame can be split into, see
+    /// [`EncoderOutputParameters::slice_count`](crate::parameters::EncoderOutputParameters::slice_count)
+    pub max_slice_count: u32,
+    /// Whether the encoder can use intra refresh, see
+    /// [`EncoderOutputParameters::intra_refresh_period`](crate::parameters::EncoderOutputParameters::intra_refresh_period)
+    pub intra_refresh_supported: bool,
     /// The count of [Vulkan Video encode quality levels](https://registry.khronos.org/vulkan/specs/latest/html/vkspec.html#encode-quality-level)
     pub quality_levels: u32,
 }
@@ -82,6 +106,30 @@ pub struct EncodeProfileCapabilities {
 pub struct DecodeCapabilities {
     pub h264: Option<DecodeH264Capabilities>,
     pub h265: Option<DecodeH265Capabilities>,
+    pub av1: Option<DecodeAv1Capabilities>,
+}
+
+/// The device capabilities for AV1 decoding.
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeAv1Capabilities {
+    pub main_profile: Option<DecodeAv1ProfileCapabilities>,
+}
+
+/// The device capabilities for AV1 decoding in a specific profile
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeAv1ProfileCapabilities {
+    /// The minimum width of the coded image
+    pub min_width: u32,
+    /// The maximum width of the coded image
+    pub max_width: u32,
+    /// The minimum height of the coded image
+    pub min_height: u32,
+    /// The maximum height of the coded image
+    pub max_height: u32,
+    /// The maximum number of pictures in the decoded picture buffer, including the current one
+    pub max_dpb_slots: u32,
+    /// The maximum AV1 level, as `seq_level_idx`
+    pub max_level: u8,
 }
 
 /// The device capabilities for H265 decoding.
@@ -103,6 +151,8 @@ pub struct DecodeH265ProfileCapabilities {
     pub min_height: u32,
     /// The maximum height of the coded image
     pub max_height: u32,
+    /// The maximum number of pictures in the decoded picture buffer, including the current one
+    pub max_dpb_slots: u32,
     /// The maximum H265 level
     pub max_level_idc: u8,
 }
@@ -115,6 +165,18 @@ pub struct DecodeH264Capabilities {
     pub baseline_profile: Option<DecodeH264ProfileCapabilities>,
     pub main_profile: Option<DecodeH264ProfileCapabilities>,
     pub high_profile: Option<DecodeH264ProfileCapabilities>,
+    /// Capabilities for 10-bit streams of the High 10 profile. Frames of these streams are
+    /// output as P010.
+    pub high10_profile: Option<DecodeH264ProfileCapabilities>,
+    /// Capabilities for 4:2:2 streams, e.g. of the High 4:2:2 profile, with the lowest bit depth
+    /// supported by the device. Frames of these streams are output with
+    /// [`ChromaSubsampling::Yuv422`](crate::parameters::ChromaSubsampling::Yuv422).
+    pub high422_profile: Option<DecodeH264ProfileCapabilities>,
+    /// Capabilities for 4:4:4 streams of the High 4:4:4 Predictive profile, with the lowest bit
+    /// depth supported by the device.
+    pub high444_profile: Option<DecodeH264ProfileCapabilities>,
+    /// Whether interlaced (PAFF or MBAFF) streams can be decoded
+    pub interlaced: bool,
 }
 
 /// The device capabilities for H264 decoding in a specific profile
@@ -128,6 +190,8 @@ pub struct DecodeH264ProfileCapabilities {
     pub min_height: u32,
     /// The maximum height of the coded image
     pub max_height: u32,
+    /// The maximum number of pictures in the decoded picture buffer, including the current one
+    pub max_dpb_slots: u32,
     /// The maximum H264 level
     pub max_level_idc: u8,
 }
@@ -1,37 +1,102 @@
 use crate::{
-    DecoderEvent, EncodedInputChunk, H264ParserError, OutputFrame, RawFrameData,
-    ReferenceManagementError, VideoBackendError,
+    Av1ParserError, DecoderEvent, DecoderParameters, EncodedInputChunk, H264BitstreamFormat,
+    H264ParserError, H265ParserError, OutputFrame, RawFrameData, ReferenceManagementError,
+    VideoBackendError,
+    decoders::{sei::SeiTracker, stats::DecoderStatsTracker},
     frame_sorter::{DecodeResult, FrameSorter},
+    parameters::PixelFormat,
     parser::{
+        av1::{self, Av1Parser},
         decoder_instructions::{DecoderInstruction, compile_to_decoder_instructions},
         h264::{AccessUnit, H264Parser},
+        h265::{self, H265Parser},
         reference_manager::ReferenceContext,
     },
 };
 
+mod async_api;
+mod external_memory;
+#[cfg(feature = "wgpu")]
+pub(crate) mod frame_pool;
+pub(crate) mod sei;
+#[cfg(feature = "software-fallback")]
+mod software;
+pub(crate) mod stats;
 #[cfg(feature = "wgpu")]
 mod wgpu_api;
+pub use async_api::*;
+pub use external_memory::ExternalMemoryDecoder;
+pub(crate) use external_memory::ExternalMemoryVideoDecoderBackend;
+#[cfg(feature = "wgpu")]
+pub use frame_pool::{FramePool, TexturePool};
+pub use stats::DecoderStats;
 #[cfg(feature = "wgpu")]
 pub use wgpu_api::*;
 
-pub(crate) trait VideoDecoderBackend: Send {
+pub(crate) trait VideoDecoderBackend<I = DecoderInstruction>: Send {
     fn decode_to_bytes(
         &mut self,
-        decoder_instructions: Vec<DecoderInstruction>,
+        decoder_instructions: Vec<I>,
     ) -> Result<Vec<DecodeResult<RawFrameData>>, VideoDecoderError>;
 }
 
+/// Implementation that is decoding the video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecoderBackend {
+    /// Hardware decoding with Vulkan Video.
+    Vulkan,
+    /// Software decoding with OpenH264. Only available with the `software-fallback` feature.
+    Software,
+}
+
 /// A decoder that outputs frames stored as [`Vec<u8>`] with the raw pixel data.
 pub struct BytesDecoder {
-    pub(crate) decoder: Box<dyn VideoDecoderBackend>,
     pub(crate) parser: H264Parser,
+    pub(crate) inner: BytesDecoderInner,
+    pub(crate) stats: DecoderStatsTracker,
+    pub(crate) sei: SeiTracker,
+}
+
+pub(crate) enum BytesDecoderInner {
+    Hardware(HardwareBytesDecoder),
+    #[cfg(feature = "software-fallback")]
+    Software(software::SoftwareH264Decoder),
+}
+
+pub(crate) struct HardwareBytesDecoder {
+    pub(crate) decoder: Box<dyn VideoDecoderBackend>,
     pub(crate) reference_ctx: ReferenceContext,
     pub(crate) frame_sorter: FrameSorter<RawFrameData>,
 }
 
 impl BytesDecoder {
+    /// Create a software H.264 decoder. It does not require a GPU, so it can be used on machines
+    /// without hardware decoding support. Produces frames in the same format as hardware decoders.
+    #[cfg(feature = "software-fallback")]
+    pub fn new_software_h264() -> Result<Self, VideoDecoderError> {
+        Self::new_software_h264_with_parameters(&DecoderParameters::default())
+    }
+
+    /// Software decoder honoring the parameters that apply to it, i.e. the output pixel format,
+    /// the display orientation override and keyframes-only decoding.
+    #[cfg(feature = "software-fallback")]
+    pub(crate) fn new_software_h264_with_parameters(
+        parameters: &DecoderParameters,
+    ) -> Result<Self, VideoDecoderError> {
+        Ok(Self {
+            parser: H264Parser::default(),
+            inner: BytesDecoderInner::Software(software::SoftwareH264Decoder::new(
+                parameters.output_pixel_format,
+                parameters.keyframes_only,
+            )?),
+            stats: DecoderStatsTracker::new(),
+            sei: SeiTracker::new(parameters.display_orientation),
+        })
+    }
+
     /// The result is a sequence of frames. The payload of each [`OutputFrame`] struct is a [`Vec<u8>`]. Each [`Vec<u8>`] contains a single
-    /// decoded frame in the [NV12 format](https://en.wikipedia.org/wiki/YCbCr#4:2:0).
+    /// decoded frame in the [`PixelFormat`](crate::parameters::PixelFormat) selected with
+    /// [`DecoderParameters::output_pixel_format`], [NV12](https://en.wikipedia.org/wiki/YCbCr#4:2:0) by default.
     pub fn decode(
         &mut self,
         frame: EncodedInputChunk<'_>,
@@ -41,22 +106,62 @@ impl BytesDecoder {
 
     /// Flush all frames from the decoder.
     ///
+    /// Returns the frames still waiting in the parser and in the reordering queue, in
+    /// presentation order. Call it at the end of the stream to get the last frames. Decoding can
+    /// continue after a flush.
+    ///
     /// Make sure that this is done when you have the knowledge that no more frames will be coming
     /// that need to be presented before the already decoded frames.
     pub fn flush(&mut self) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
         self.process_event(DecoderEvent::Flush)
     }
 
+    /// Resets the decoder after a discontinuity in the stream without recreating the video
+    /// session. See [`DecoderEvent::Reset`].
+    pub fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Reset).map(|_| ())
+    }
+
+    /// Sets the format of the chunks passed to [`Self::decode`]. Defaults to
+    /// [`H264BitstreamFormat::AnnexB`]. Has to be called before decoding the first chunk, data
+    /// buffered in the parser is dropped.
+    pub fn set_bitstream_format(&mut self, format: H264BitstreamFormat) {
+        self.parser = H264Parser::new(format);
+    }
+
+    /// Returns decoding statistics. See [`DecoderStats`].
+    pub fn stats(&self) -> DecoderStats {
+        self.stats.stats()
+    }
+
+    /// Sets a callback called with the updated [`DecoderStats`] after every decoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(DecoderStats) + Send + 'static) {
+        self.stats.set_callback(Box::new(callback));
+    }
+
+    /// Returns which implementation is decoding the video.
+    pub fn backend(&self) -> DecoderBackend {
+        match self.inner {
+            BytesDecoderInner::Hardware(_) => DecoderBackend::Vulkan,
+            #[cfg(feature = "software-fallback")]
+            BytesDecoderInner::Software(_) => DecoderBackend::Software,
+        }
+    }
+
     /// Process a [`DecoderEvent`]. For most use cases, using [`Self::decode`] and [`Self::flush`] is enough.
     /// Use this only when you need more fine-grained control.
-    /// May return a sequence of decoded frames in the [NV12 format](https://en.wikipedia.org/wiki/YCbCr#4:2:0).
+    /// May return a sequence of decoded frames in the format selected with
+    /// [`DecoderParameters::output_pixel_format`].
     pub fn process_event(
         &mut self,
         event: DecoderEvent<'_, AccessUnit>,
     ) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
-        match event {
+        let mut frames = match event {
             DecoderEvent::DecodeChunk(chunk) => {
-                let nalus = self.parser.parse(chunk.data, chunk.pts)?;
+                let nalus = self
+                    .parser
+                    .parse_with_dts(chunk.data, chunk.pts, chunk.dts)?;
                 self.decode_access_units(nalus)
             }
             DecoderEvent::DecodeParsedFrame(au) => self.decode_access_units(vec![au]),
@@ -64,6 +169,141 @@ impl BytesDecoder {
                 let access_units = self.parser.flush()?;
                 self.decode_access_units(access_units)
             }
+            DecoderEvent::SignalDataLoss => {
+                if let BytesDecoderInner::Hardware(decoder) = &mut self.inner {
+                    decoder.reference_ctx.mark_missed_frames();
+                }
+                Ok(Vec::new())
+            }
+            DecoderEvent::Flush => {
+                let access_units = self.parser.flush()?;
+                let mut frames = self.decode_access_units(access_units)?;
+                match &mut self.inner {
+                    BytesDecoderInner::Hardware(decoder) => {
+                        frames.append(&mut decoder.frame_sorter.flush())
+                    }
+                    #[cfg(feature = "software-fallback")]
+                    BytesDecoderInner::Software(decoder) => {
+                        let mut flushed = decoder.flush()?;
+                        self.stats.register_software_frames(flushed.len());
+                        frames.append(&mut flushed);
+                    }
+                }
+                Ok(frames)
+            }
+            DecoderEvent::Reset => {
+                self.parser.reset();
+                self.sei.reset();
+                match &mut self.inner {
+                    BytesDecoderInner::Hardware(decoder) => {
+                        let instructions = decoder.reference_ctx.reset();
+                        decoder.decoder.decode_to_bytes(instructions)?;
+                        decoder.frame_sorter.clear();
+                    }
+                    #[cfg(feature = "software-fallback")]
+                    BytesDecoderInner::Software(decoder) => decoder.reset()?,
+                }
+                Ok(Vec::new())
+            }
+        }?;
+
+        self.sei.apply(&mut frames);
+        Ok(frames)
+    }
+
+    fn decode_access_units(
+        &mut self,
+        access_units: Vec<AccessUnit>,
+    ) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
+        self.sei.update(&access_units);
+        match &mut self.inner {
+            BytesDecoderInner::Hardware(decoder) => {
+                let instructions =
+                    compile_to_decoder_instructions(&mut decoder.reference_ctx, access_units)?;
+                let unsorted_frames = decoder.decoder.decode_to_bytes(instructions)?;
+                self.stats.register_frames(&unsorted_frames);
+                let sorted_frames = decoder.frame_sorter.put_frames(unsorted_frames);
+                Ok(sorted_frames)
+            }
+            #[cfg(feature = "software-fallback")]
+            BytesDecoderInner::Software(decoder) => {
+                let frames = decoder.decode(access_units)?;
+                self.stats.register_software_frames(frames.len());
+                Ok(frames)
+            }
+        }
+    }
+}
+
+/// A H.265 decoder that outputs frames stored as [`Vec<u8>`] with the raw pixel data.
+pub struct BytesDecoderH265 {
+    pub(crate) parser: H265Parser,
+    pub(crate) decoder:
+        Box<dyn VideoDecoderBackend<h265::decoder_instructions::DecoderInstruction>>,
+    pub(crate) reference_ctx: h265::reference_manager::ReferenceContext,
+    pub(crate) frame_sorter: FrameSorter<RawFrameData>,
+    pub(crate) stats: DecoderStatsTracker,
+    pub(crate) sei: SeiTracker,
+}
+
+impl BytesDecoderH265 {
+    /// The result is a sequence of frames. The payload of each [`OutputFrame`] struct is a [`Vec<u8>`]. Each [`Vec<u8>`] contains a single
+    /// decoded frame in the [`PixelFormat`](crate::parameters::PixelFormat) selected with
+    /// [`DecoderParameters::output_pixel_format`], [NV12](https://en.wikipedia.org/wiki/YCbCr#4:2:0) by default.
+    pub fn decode(
+        &mut self,
+        frame: EncodedInputChunk<'_>,
+    ) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
+        self.process_event(DecoderEvent::DecodeChunk(frame))
+    }
+
+    /// Flush all frames from the decoder.
+    ///
+    /// Works the same as [`BytesDecoder::flush`].
+    ///
+    /// Make sure that this is done when you have the knowledge that no more frames will be coming
+    /// that need to be presented before the already decoded frames.
+    pub fn flush(&mut self) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
+        self.process_event(DecoderEvent::Flush)
+    }
+
+    /// Resets the decoder after a discontinuity in the stream without recreating the video
+    /// session. See [`DecoderEvent::Reset`].
+    pub fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Reset).map(|_| ())
+    }
+
+    /// Returns decoding statistics. See [`DecoderStats`].
+    pub fn stats(&self) -> DecoderStats {
+        self.stats.stats()
+    }
+
+    /// Sets a callback called with the updated [`DecoderStats`] after every decoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(DecoderStats) + Send + 'static) {
+        self.stats.set_callback(Box::new(callback));
+    }
+
+    /// Process a [`DecoderEvent`]. For most use cases, using [`Self::decode`] and [`Self::flush`] is enough.
+    /// Use this only when you need more fine-grained control.
+    /// May return a sequence of decoded frames in the format selected with
+    /// [`DecoderParameters::output_pixel_format`].
+    pub fn process_event(
+        &mut self,
+        event: DecoderEvent<'_, h265::AccessUnit>,
+    ) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
+        let mut frames = match event {
+            DecoderEvent::DecodeChunk(chunk) => {
+                let access_units = self
+                    .parser
+                    .parse_with_dts(chunk.data, chunk.pts, chunk.dts)?;
+                self.decode_access_units(access_units)
+            }
+            DecoderEvent::DecodeParsedFrame(au) => self.decode_access_units(vec![au]),
+            DecoderEvent::SignalFrameEnd => {
+                let access_units = self.parser.flush()?;
+                self.decode_access_units(access_units)
+            }
             DecoderEvent::SignalDataLoss => {
                 self.reference_ctx.mark_missed_frames();
                 Ok(Vec::new())
@@ -74,15 +314,136 @@ impl BytesDecoder {
                 frames.append(&mut self.frame_sorter.flush());
                 Ok(frames)
             }
-        }
+            DecoderEvent::Reset => {
+                self.parser = H265Parser::default();
+                self.sei.reset();
+                let instructions = self.reference_ctx.reset();
+                self.decoder.decode_to_bytes(instructions)?;
+                self.frame_sorter.clear();
+                Ok(Vec::new())
+            }
+        }?;
+
+        self.sei.apply(&mut frames);
+        Ok(frames)
     }
 
     fn decode_access_units(
         &mut self,
-        access_units: Vec<AccessUnit>,
+        access_units: Vec<h265::AccessUnit>,
+    ) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
+        self.sei.update_h265(&access_units);
+        let instructions = h265::decoder_instructions::compile_to_decoder_instructions(
+            &mut self.reference_ctx,
+            access_units,
+        )?;
+        let unsorted_frames = self.decoder.decode_to_bytes(instructions)?;
+        self.stats.register_frames(&unsorted_frames);
+        let sorted_frames = self.frame_sorter.put_frames(unsorted_frames);
+        Ok(sorted_frames)
+    }
+}
+
+/// An AV1 decoder that outputs frames stored as [`Vec<u8>`] with the raw pixel data.
+/// Frames are output in decode order, which is the same as the presentation order in AV1.
+pub struct BytesDecoderAv1 {
+    pub(crate) parser: Av1Parser,
+    pub(crate) decoder: Box<dyn VideoDecoderBackend<av1::decoder_instructions::DecoderInstruction>>,
+    pub(crate) reference_ctx: av1::reference_manager::ReferenceContext,
+    pub(crate) frame_sorter: FrameSorter<RawFrameData>,
+    pub(crate) stats: DecoderStatsTracker,
+    pub(crate) sei: SeiTracker,
+}
+
+impl BytesDecoderAv1 {
+    /// The result is a sequence of frames. The payload of each [`OutputFrame`] struct is a [`Vec<u8>`]. Each [`Vec<u8>`] contains a single
+    /// decoded frame in the [`PixelFormat`](crate::parameters::PixelFormat) selected with
+    /// [`DecoderParameters::output_pixel_format`], [NV12](https://en.wikipedia.org/wiki/YCbCr#4:2:0) by default.
+    pub fn decode(
+        &mut self,
+        frame: EncodedInputChunk<'_>,
+    ) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
+        self.process_event(DecoderEvent::DecodeChunk(frame))
+    }
+
+    /// Flush all frames from the decoder.
+    pub fn flush(&mut self) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
+        self.process_event(DecoderEvent::Flush)
+    }
+
+    /// Resets the decoder after a discontinuity in the stream without recreating the video
+    /// session. See [`DecoderEvent::Reset`].
+    pub fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Reset).map(|_| ())
+    }
+
+    /// Returns decoding statistics. See [`DecoderStats`].
+    pub fn stats(&self) -> DecoderStats {
+        self.stats.stats()
+    }
+
+    /// Sets a callback called with the updated [`DecoderStats`] after every decoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(DecoderStats) + Send + 'static) {
+        self.stats.set_callback(Box::new(callback));
+    }
+
+    /// Process a [`DecoderEvent`]. For most use cases, using [`Self::decode`] and [`Self::flush`] is enough.
+    /// Use this only when you need more fine-grained control.
+    /// May return a sequence of decoded frames in the format selected with
+    /// [`DecoderParameters::output_pixel_format`].
+    pub fn process_event(
+        &mut self,
+        event: DecoderEvent<'_, av1::FrameUnit>,
     ) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
-        let instructions = compile_to_decoder_instructions(&mut self.reference_ctx, access_units)?;
+        let mut frames = match event {
+            DecoderEvent::DecodeChunk(chunk) => {
+                let frame_units = self
+                    .parser
+                    .parse_with_dts(chunk.data, chunk.pts, chunk.dts)?;
+                self.decode_frame_units(frame_units)
+            }
+            DecoderEvent::DecodeParsedFrame(frame_unit) => {
+                self.decode_frame_units(vec![frame_unit])
+            }
+            DecoderEvent::SignalFrameEnd => {
+                let frame_units = self.parser.flush()?;
+                self.decode_frame_units(frame_units)
+            }
+            DecoderEvent::SignalDataLoss => {
+                self.reference_ctx.mark_missed_frames();
+                Ok(Vec::new())
+            }
+            DecoderEvent::Flush => {
+                let frame_units = self.parser.flush()?;
+                let mut frames = self.decode_frame_units(frame_units)?;
+                frames.append(&mut self.frame_sorter.flush());
+                Ok(frames)
+            }
+            DecoderEvent::Reset => {
+                self.parser = Av1Parser::default();
+                self.sei.reset();
+                let instructions = self.reference_ctx.reset();
+                self.decoder.decode_to_bytes(instructions)?;
+                self.frame_sorter.clear();
+                Ok(Vec::new())
+            }
+        }?;
+
+        self.sei.apply(&mut frames);
+        Ok(frames)
+    }
+
+    fn decode_frame_units(
+        &mut self,
+        frame_units: Vec<av1::FrameUnit>,
+    ) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
+        let instructions = av1::decoder_instructions::compile_to_decoder_instructions(
+            &mut self.reference_ctx,
+            frame_units,
+        )?;
         let unsorted_frames = self.decoder.decode_to_bytes(instructions)?;
+        self.stats.register_frames(&unsorted_frames);
         let sorted_frames = self.frame_sorter.put_frames(unsorted_frames);
         Ok(sorted_frames)
     }
@@ -96,9 +457,38 @@ pub enum VideoDecoderError {
     #[error("Invalid input data for the decoder: {0}.")]
     InvalidInputData(String),
 
+    #[error("Frames with {bit_depth}-bit samples cannot be output as {pixel_format:?}")]
+    UnsupportedPixelFormat {
+        pixel_format: PixelFormat,
+        bit_depth: u8,
+    },
+
+    #[error("Decoders that output textures cannot output frames as {0:?}")]
+    UnsupportedTexturePixelFormat(PixelFormat),
+
+    #[error("The device does not support exporting decoded frames")]
+    ExternalMemoryUnsupported,
+
+    #[error("The stream needs {required} decoded picture buffer slots, the limit is {limit}")]
+    DpbLimitExceeded { required: u32, limit: u32 },
+
+    #[error(
+        "Decoding images on the device would need {required} bytes, the memory budget is {budget}"
+    )]
+    MemoryBudgetExceeded { required: u64, budget: u64 },
+
+    #[error("The device does not support exporting the decoder semaphore")]
+    ExternalSemaphoreUnsupported,
+
     #[error("H264 parser error: {0}")]
     ParserError(#[from] H264ParserError),
 
+    #[error("H265 parser error: {0}")]
+    H265ParserError(#[from] H265ParserError),
+
+    #[error("AV1 parser error: {0}")]
+    Av1ParserError(#[from] Av1ParserError),
+
     #[error("Reference management error: {0}")]
     ReferenceManagementError(#[from] ReferenceManagementError),
 
@@ -108,6 +498,24 @@ pub enum VideoDecoderError {
     )]
     VideoDeviceWithoutWgpu,
 
+    #[cfg(feature = "wgpu")]
+    #[error("The frame pool has no texture available for a {width}x{height} {format:?} frame")]
+    FramePoolExhausted {
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+    },
+
+    #[cfg(feature = "wgpu")]
+    #[error("Texture from the frame pool cannot be used for the frame: {0}")]
+    InvalidFramePoolTexture(String),
+
+    #[error("Asynchronous decoding is not supported by the software decoder")]
+    AsyncDecodingUnsupported,
+
+    #[error("Failed to spawn the decoding thread: {0}")]
+    DecodeThreadSpawnFailed(std::io::Error),
+
     #[error("Encoder error: {0}")]
     BackendError(VideoBackendError),
 }
@@ -0,0 +1,513 @@
+use std::{
+    sync::{Arc, Mutex, mpsc},
+    thread::JoinHandle,
+};
+
+use crate::{
+    DecoderEvent, EncodedInputChunk, H264BitstreamFormat, OutputFrame, RawFrameData,
+    decoders::{
+        BytesDecoder, BytesDecoderAv1, BytesDecoderH265, BytesDecoderInner, DecoderStats,
+        VideoDecoderBackend, VideoDecoderError,
+        sei::SeiTracker,
+        stats::{DecoderStatsCallback, DecoderStatsTracker},
+    },
+    frame_sorter::FrameSorter,
+    parser::{
+        av1::{self, Av1Parser},
+        decoder_instructions::{DecoderInstruction, compile_to_decoder_instructions},
+        h264::{AccessUnit, H264Parser},
+        h265::{self, H265Parser},
+        reference_manager::ReferenceContext,
+    },
+};
+
+/// Receives the frames decoded by an asynchronous decoder, in presentation order. The channel is
+/// closed after the decoder is dropped and all frames submitted before that are decoded.
+pub type DecodedFramesReceiver =
+    mpsc::Receiver<Result<OutputFrame<RawFrameData>, VideoDecoderError>>;
+
+enum DecodeThreadMessage<I> {
+    Decode {
+        instructions: Vec<I>,
+        sei: SeiTracker,
+    },
+    Flush {
+        sei: SeiTracker,
+    },
+    Reset {
+        instructions: Vec<I>,
+        sei: SeiTracker,
+    },
+}
+
+/// Submits decoder instructions to the GPU on a separate thread, so that the caller can parse
+/// the next chunks while the previous ones are being decoded.
+struct DecodeThread<I: Send + 'static> {
+    sender: Option<mpsc::Sender<DecodeThreadMessage<I>>>,
+    handle: Option<JoinHandle<()>>,
+    stats: Arc<Mutex<DecoderStatsTracker>>,
+}
+
+impl<I: Send + 'static> DecodeThread<I> {
+    fn spawn(
+        mut decoder: Box<dyn VideoDecoderBackend<I>>,
+        mut frame_sorter: FrameSorter<RawFrameData>,
+        stats: DecoderStatsTracker,
+    ) -> Result<(Self, DecodedFramesReceiver), VideoDecoderError> {
+        let (sender, receiver) = mpsc::channel::<DecodeThreadMessage<I>>();
+        let (frame_sender, frame_receiver) = mpsc::channel();
+        let stats = Arc::new(Mutex::new(stats));
+
+        let thread_stats = stats.clone();
+        let handle = std::thread::Builder::new()
+            .name("gpu-video decoder".to_string())
+            .spawn(move || {
+                for message in receiver {
+                    let (frames, sei) = match message {
+                        DecodeThreadMessage::Decode { instructions, sei } => {
+                            let frames = decoder.decode_to_bytes(instructions).map(|frames| {
+                                thread_stats.lock().unwrap().register_frames(&frames);
+                                frame_sorter.put_frames(frames)
+                            });
+                            (frames, sei)
+                        }
+                        DecodeThreadMessage::Flush { sei } => (Ok(frame_sorter.flush()), sei),
+                        DecodeThreadMessage::Reset { instructions, sei } => {
+                            frame_sorter.clear();
+                            let frames = decoder.decode_to_bytes(instructions).map(|_| Vec::new());
+                            (frames, sei)
+                        }
+                    };
+
+                    let result = match frames {
+                        Ok(mut frames) => {
+                            sei.apply(&mut frames);
+                            frames
+                                .into_iter()
+                                .try_for_each(|frame| frame_sender.send(Ok(frame)))
+                        }
+                        Err(err) => frame_sender.send(Err(err)),
+                    };
+
+                    // the receiver was dropped, so nobody is interested in the frames anymore
+                    if result.is_err() {
+                        return;
+                    }
+                }
+            })
+            .map_err(VideoDecoderError::DecodeThreadSpawnFailed)?;
+
+        Ok((
+            Self {
+                sender: Some(sender),
+                handle: Some(handle),
+                stats,
+            },
+            frame_receiver,
+        ))
+    }
+
+    fn decode(&self, instructions: Vec<I>, sei: &SeiTracker) {
+        if instructions.is_empty() {
+            return;
+        }
+
+        self.send(DecodeThreadMessage::Decode {
+            instructions,
+            sei: sei.clone(),
+        });
+    }
+
+    fn flush(&self, sei: &SeiTracker) {
+        self.send(DecodeThreadMessage::Flush { sei: sei.clone() });
+    }
+
+    /// Unlike [`Self::decode`], sends the message even without instructions, because the frames
+    /// waiting to be output have to be discarded.
+    fn reset(&self, instructions: Vec<I>, sei: &SeiTracker) {
+        self.send(DecodeThreadMessage::Reset {
+            instructions,
+            sei: sei.clone(),
+        });
+    }
+
+    fn send(&self, message: DecodeThreadMessage<I>) {
+        // the thread only exits when the frames receiver is dropped, in which case the frames
+        // would be discarded anyway
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(message);
+        }
+    }
+
+    fn stats(&self) -> DecoderStats {
+        self.stats.lock().unwrap().stats()
+    }
+
+    fn set_stats_callback(&self, callback: DecoderStatsCallback) {
+        self.stats.lock().unwrap().set_callback(callback);
+    }
+}
+
+impl<I: Send + 'static> Drop for DecodeThread<I> {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// An H.264 decoder that decodes on a separate thread. Created with [`BytesDecoder::into_async`].
+///
+/// Chunks are parsed on the calling thread, while decoding and downloading the frames happens on
+/// the decoding thread. The decoded frames are delivered through the [`DecodedFramesReceiver`].
+///
+/// Only parsing overlaps with decoding. The decoding thread still waits for the GPU to finish
+/// each frame before it submits the next one, the same as the synchronous decoder, so the
+/// throughput of the GPU is not higher. There is no asynchronous variant of the wgpu decoders.
+pub struct AsyncBytesDecoder {
+    parser: H264Parser,
+    reference_ctx: ReferenceContext,
+    sei: SeiTracker,
+    thread: DecodeThread<DecoderInstruction>,
+}
+
+impl BytesDecoder {
+    /// Moves decoding to a separate thread. The returned receiver yields the decoded frames.
+    ///
+    /// Only available for hardware decoders.
+    pub fn into_async(
+        self,
+    ) -> Result<(AsyncBytesDecoder, DecodedFramesReceiver), VideoDecoderError> {
+        match self.inner {
+            BytesDecoderInner::Hardware(decoder) => {
+                let (thread, receiver) =
+                    DecodeThread::spawn(decoder.decoder, decoder.frame_sorter, self.stats)?;
+
+                Ok((
+                    AsyncBytesDecoder {
+                        parser: self.parser,
+                        reference_ctx: decoder.reference_ctx,
+                        sei: self.sei,
+                        thread,
+                    },
+                    receiver,
+                ))
+            }
+            #[cfg(feature = "software-fallback")]
+            BytesDecoderInner::Software(_) => Err(VideoDecoderError::AsyncDecodingUnsupported),
+        }
+    }
+}
+
+impl AsyncBytesDecoder {
+    /// Submits a chunk for decoding. Returns after the chunk is parsed, without waiting for the
+    /// GPU.
+    pub fn decode(&mut self, frame: EncodedInputChunk<'_>) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::DecodeChunk(frame))
+    }
+
+    /// Flush all frames from the decoder. The frames are delivered through the receiver after
+    /// all previously submitted chunks.
+    pub fn flush(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Flush)
+    }
+
+    /// Resets the decoder after a discontinuity in the stream without recreating the video
+    /// session. See [`DecoderEvent::Reset`].
+    pub fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Reset)
+    }
+
+    /// Sets the format of the chunks passed to [`Self::decode`]. Defaults to
+    /// [`H264BitstreamFormat::AnnexB`]. Has to be called before decoding the first chunk, data
+    /// buffered in the parser is dropped.
+    pub fn set_bitstream_format(&mut self, format: H264BitstreamFormat) {
+        self.parser = H264Parser::new(format);
+    }
+
+    /// Returns decoding statistics. See [`DecoderStats`].
+    pub fn stats(&self) -> DecoderStats {
+        self.thread.stats()
+    }
+
+    /// Sets a callback called with the updated [`DecoderStats`] after every decoded frame, e.g. to
+    /// collect the GPU time of each frame. The callback is called on the decoding thread.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(DecoderStats) + Send + 'static) {
+        self.thread.set_stats_callback(Box::new(callback));
+    }
+
+    /// Process a [`DecoderEvent`]. For most use cases, using [`Self::decode`] and [`Self::flush`] is enough.
+    /// Use this only when you need more fine-grained control.
+    pub fn process_event(
+        &mut self,
+        event: DecoderEvent<'_, AccessUnit>,
+    ) -> Result<(), VideoDecoderError> {
+        match event {
+            DecoderEvent::DecodeChunk(chunk) => {
+                let access_units = self
+                    .parser
+                    .parse_with_dts(chunk.data, chunk.pts, chunk.dts)?;
+                self.decode_access_units(access_units)
+            }
+            DecoderEvent::DecodeParsedFrame(au) => self.decode_access_units(vec![au]),
+            DecoderEvent::SignalFrameEnd => {
+                let access_units = self.parser.flush()?;
+                self.decode_access_units(access_units)
+            }
+            DecoderEvent::SignalDataLoss => {
+                self.reference_ctx.mark_missed_frames();
+                Ok(())
+            }
+            DecoderEvent::Flush => {
+                let access_units = self.parser.flush()?;
+                self.decode_access_units(access_units)?;
+                self.thread.flush(&self.sei);
+                Ok(())
+            }
+            DecoderEvent::Reset => {
+                self.parser.reset();
+                self.sei.reset();
+                self.thread.reset(self.reference_ctx.reset(), &self.sei);
+                Ok(())
+            }
+        }
+    }
+
+    fn decode_access_units(
+        &mut self,
+        access_units: Vec<AccessUnit>,
+    ) -> Result<(), VideoDecoderError> {
+        self.sei.update(&access_units);
+        let instructions = compile_to_decoder_instructions(&mut self.reference_ctx, access_units)?;
+        self.thread.decode(instructions, &self.sei);
+        Ok(())
+    }
+}
+
+/// An H.265 decoder that decodes on a separate thread. Created with
+/// [`BytesDecoderH265::into_async`].
+///
+/// Chunks are parsed on the calling thread, while decoding and downloading the frames happens on
+/// the decoding thread. The decoded frames are delivered through the [`DecodedFramesReceiver`].
+/// See [`AsyncBytesDecoder`] for what is done asynchronously.
+pub struct AsyncBytesDecoderH265 {
+    parser: H265Parser,
+    reference_ctx: h265::reference_manager::ReferenceContext,
+    sei: SeiTracker,
+    thread: DecodeThread<h265::decoder_instructions::DecoderInstruction>,
+}
+
+impl BytesDecoderH265 {
+    /// Moves decoding to a separate thread. The returned receiver yields the decoded frames.
+    pub fn into_async(
+        self,
+    ) -> Result<(AsyncBytesDecoderH265, DecodedFramesReceiver), VideoDecoderError> {
+        let (thread, receiver) = DecodeThread::spawn(self.decoder, self.frame_sorter, self.stats)?;
+
+        Ok((
+            AsyncBytesDecoderH265 {
+                parser: self.parser,
+                reference_ctx: self.reference_ctx,
+                sei: self.sei,
+                thread,
+            },
+            receiver,
+        ))
+    }
+}
+
+impl AsyncBytesDecoderH265 {
+    /// Submits a chunk for decoding. Returns after the chunk is parsed, without waiting for the
+    /// GPU.
+    pub fn decode(&mut self, frame: EncodedInputChunk<'_>) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::DecodeChunk(frame))
+    }
+
+    /// Flush all frames from the decoder. The frames are delivered through the receiver after
+    /// all previously submitted chunks.
+    pub fn flush(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Flush)
+    }
+
+    /// Resets the decoder after a discontinuity in the stream without recreating the video
+    /// session. See [`DecoderEvent::Reset`].
+    pub fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Reset)
+    }
+
+    /// Returns decoding statistics. See [`DecoderStats`].
+    pub fn stats(&self) -> DecoderStats {
+        self.thread.stats()
+    }
+
+    /// Sets a callback called with the updated [`DecoderStats`] after every decoded frame, e.g. to
+    /// collect the GPU time of each frame. The callback is called on the decoding thread.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(DecoderStats) + Send + 'static) {
+        self.thread.set_stats_callback(Box::new(callback));
+    }
+
+    /// Process a [`DecoderEvent`]. For most use cases, using [`Self::decode`] and [`Self::flush`] is enough.
+    /// Use this only when you need more fine-grained control.
+    pub fn process_event(
+        &mut self,
+        event: DecoderEvent<'_, h265::AccessUnit>,
+    ) -> Result<(), VideoDecoderError> {
+        match event {
+            DecoderEvent::DecodeChunk(chunk) => {
+                let access_units = self
+                    .parser
+                    .parse_with_dts(chunk.data, chunk.pts, chunk.dts)?;
+                self.decode_access_units(access_units)
+            }
+            DecoderEvent::DecodeParsedFrame(au) => self.decode_access_units(vec![au]),
+            DecoderEvent::SignalFrameEnd => {
+                let access_units = self.parser.flush()?;
+                self.decode_access_units(access_units)
+            }
+            DecoderEvent::SignalDataLoss => {
+                self.reference_ctx.mark_missed_frames();
+                Ok(())
+            }
+            DecoderEvent::Flush => {
+                let access_units = self.parser.flush()?;
+                self.decode_access_units(access_units)?;
+                self.thread.flush(&self.sei);
+                Ok(())
+            }
+            DecoderEvent::Reset => {
+                self.parser = H265Parser::default();
+                self.sei.reset();
+                self.thread.reset(self.reference_ctx.reset(), &self.sei);
+                Ok(())
+            }
+        }
+    }
+
+    fn decode_access_units(
+        &mut self,
+        access_units: Vec<h265::AccessUnit>,
+    ) -> Result<(), VideoDecoderError> {
+        self.sei.update_h265(&access_units);
+        let instructions = h265::decoder_instructions::compile_to_decoder_instructions(
+            &mut self.reference_ctx,
+            access_units,
+        )?;
+        self.thread.decode(instructions, &self.sei);
+        Ok(())
+    }
+}
+
+/// An AV1 decoder that decodes on a separate thread. Created with
+/// [`BytesDecoderAv1::into_async`].
+///
+/// Chunks are parsed on the calling thread, while decoding and downloading the frames happens on
+/// the decoding thread. The decoded frames are delivered through the [`DecodedFramesReceiver`].
+/// See [`AsyncBytesDecoder`] for what is done asynchronously.
+pub struct AsyncBytesDecoderAv1 {
+    parser: Av1Parser,
+    reference_ctx: av1::reference_manager::ReferenceContext,
+    sei: SeiTracker,
+    thread: DecodeThread<av1::decoder_instructions::DecoderInstruction>,
+}
+
+impl BytesDecoderAv1 {
+    /// Moves decoding to a separate thread. The returned receiver yields the decoded frames.
+    pub fn into_async(
+        self,
+    ) -> Result<(AsyncBytesDecoderAv1, DecodedFramesReceiver), VideoDecoderError> {
+        let (thread, receiver) = DecodeThread::spawn(self.decoder, self.frame_sorter, self.stats)?;
+
+        Ok((
+            AsyncBytesDecoderAv1 {
+                parser: self.parser,
+                reference_ctx: self.reference_ctx,
+                sei: self.sei,
+                thread,
+            },
+            receiver,
+        ))
+    }
+}
+
+impl AsyncBytesDecoderAv1 {
+    /// Submits a chunk for decoding. Returns after the chunk is parsed, without waiting for the
+    /// GPU.
+    pub fn decode(&mut self, frame: EncodedInputChunk<'_>) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::DecodeChunk(frame))
+    }
+
+    /// Flush all frames from the decoder. The frames are delivered through the receiver after
+    /// all previously submitted chunks.
+    pub fn flush(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Flush)
+    }
+
+    /// Resets the decoder after a discontinuity in the stream without recreating the video
+    /// session. See [`DecoderEvent::Reset`].
+    pub fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Reset)
+    }
+
+    /// Returns decoding statistics. See [`DecoderStats`].
+    pub fn stats(&self) -> DecoderStats {
+        self.thread.stats()
+    }
+
+    /// Sets a callback called with the updated [`DecoderStats`] after every decoded frame, e.g. to
+    /// collect the GPU time of each frame. The callback is called on the decoding thread.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(DecoderStats) + Send + 'static) {
+        self.thread.set_stats_callback(Box::new(callback));
+    }
+
+    /// Process a [`DecoderEvent`]. For most use cases, using [`Self::decode`] and [`Self::flush`] is enough.
+    /// Use this only when you need more fine-grained control.
+    pub fn process_event(
+        &mut self,
+        event: DecoderEvent<'_, av1::FrameUnit>,
+    ) -> Result<(), VideoDecoderError> {
+        match event {
+            DecoderEvent::DecodeChunk(chunk) => {
+                let frame_units = self
+                    .parser
+                    .parse_with_dts(chunk.data, chunk.pts, chunk.dts)?;
+                self.decode_frame_units(frame_units)
+            }
+            DecoderEvent::DecodeParsedFrame(frame_unit) => {
+                self.decode_frame_units(vec![frame_unit])
+            }
+            DecoderEvent::SignalFrameEnd => {
+                let frame_units = self.parser.flush()?;
+                self.decode_frame_units(frame_units)
+            }
+            DecoderEvent::SignalDataLoss => {
+                self.reference_ctx.mark_missed_frames();
+                Ok(())
+            }
+            DecoderEvent::Flush => {
+                let frame_units = self.parser.flush()?;
+                self.decode_frame_units(frame_units)?;
+                self.thread.flush(&self.sei);
+                Ok(())
+            }
+            DecoderEvent::Reset => {
+                self.parser = Av1Parser::default();
+                self.sei.reset();
+                self.thread.reset(self.reference_ctx.reset(), &self.sei);
+                Ok(())
+            }
+        }
+    }
+
+    fn decode_frame_units(
+        &mut self,
+        frame_units: Vec<av1::FrameUnit>,
+    ) -> Result<(), VideoDecoderError> {
+        let instructions = av1::decoder_instructions::compile_to_decoder_instructions(
+            &mut self.reference_ctx,
+            frame_units,
+        )?;
+        self.thread.decode(instructions, &self.sei);
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{
+    DecoderEvent, EncodedInputChunk, ExternalMemoryFrame, ExternalSemaphoreHandle,
+    H264BitstreamFormat, OutputFrame, VideoDecoderError,
+    decoders::{DecoderStats, sei::SeiTracker, stats::DecoderStatsTracker},
+    frame_sorter::{DecodeResult, FrameSorter},
+    parser::{
+        decoder_instructions::{DecoderInstruction, compile_to_decoder_instructions},
+        h264::{AccessUnit, H264Parser},
+        reference_manager::ReferenceContext,
+    },
+};
+
+pub(crate) trait ExternalMemoryVideoDecoderBackend<I = DecoderInstruction>: Send {
+    fn decode_to_external_memory(
+        &mut self,
+        decoder_instructions: Vec<I>,
+    ) -> Result<Vec<DecodeResult<ExternalMemoryFrame>>, VideoDecoderError>;
+
+    fn export_semaphore(&mut self) -> Result<ExternalSemaphoreHandle, VideoDecoderError>;
+}
+
+/// A decoder that outputs frames stored in exported memory, which can be passed without copying
+/// to VA-API, GStreamer, OpenGL or other consumers. See [`ExternalMemoryFrame`].
+pub struct ExternalMemoryDecoder {
+    pub(crate) decoder: Box<dyn ExternalMemoryVideoDecoderBackend>,
+    pub(crate) parser: H264Parser,
+    pub(crate) reference_ctx: ReferenceContext,
+    pub(crate) frame_sorter: FrameSorter<ExternalMemoryFrame>,
+    pub(crate) stats: DecoderStatsTracker,
+    pub(crate) sei: SeiTracker,
+}
+
+impl ExternalMemoryDecoder {
+    /// Every frame is exported in a separate allocation in the
+    /// [`PixelFormat`](crate::parameters::PixelFormat) selected with
+    /// [`DecoderParameters::output_pixel_format`](crate::parameters::DecoderParameters::output_pixel_format).
+    pub fn decode(
+        &mut self,
+        frame: EncodedInputChunk<'_>,
+    ) -> Result<Vec<OutputFrame<ExternalMemoryFrame>>, VideoDecoderError> {
+        self.process_event(DecoderEvent::DecodeChunk(frame))
+    }
+
+    /// Flush all frames from the decoder.
+    ///
+    /// Returns the frames still waiting in the parser and in the reordering queue, in
+    /// presentation order. Call it at the end of the stream to get the last frames. Decoding can
+    /// continue after a flush.
+    ///
+    /// Make sure that this is done when you have the knowledge that no more frames will be coming
+    /// that need to be presented before the already decoded frames.
+    pub fn flush(&mut self) -> Result<Vec<OutputFrame<ExternalMemoryFrame>>, VideoDecoderError> {
+        self.process_event(DecoderEvent::Flush)
+    }
+
+    /// Resets the decoder after a discontinuity in the stream without recreating the video
+    /// session. See [`DecoderEvent::Reset`].
+    pub fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Reset).map(|_| ())
+    }
+
+    /// Sets the format of the chunks passed to [`Self::decode`]. Defaults to
+    /// [`H264BitstreamFormat::AnnexB`]. Has to be called before decoding the first chunk, data
+    /// buffered in the parser is dropped.
+    pub fn set_bitstream_format(&mut self, format: H264BitstreamFormat) {
+        self.parser = H264Parser::new(format);
+    }
+
+    /// Exports the timeline semaphore the decoder signals when its GPU work finishes.
+    ///
+    /// Until this is called, the decoder waits for every frame to be written before returning
+    /// it. Afterwards frames are returned as soon as the copy is submitted, and the consumer has
+    /// to wait for the semaphore to reach [`ExternalMemoryFrame::semaphore_value`] before reading
+    /// the frame, e.g. by importing it into its own API. Every call returns a new handle.
+    pub fn export_semaphore(&mut self) -> Result<ExternalSemaphoreHandle, VideoDecoderError> {
+        self.decoder.export_semaphore()
+    }
+
+    /// Returns decoding statistics. See [`DecoderStats`].
+    pub fn stats(&self) -> DecoderStats {
+        self.stats.stats()
+    }
+
+    /// Sets a callback called with the updated [`DecoderStats`] after every decoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(DecoderStats) + Send + 'static) {
+        self.stats.set_callback(Box::new(callback));
+    }
+
+    /// Process a [`DecoderEvent`]. For most use cases, using [`Self::decode`] and [`Self::flush`] is enough.
+    /// Use this only when you need more fine-grained control.
+    /// May return a sequence of decoded frames in the format described in [`Self::decode`].
+    pub fn process_event(
+        &mut self,
+        event: DecoderEvent<'_, AccessUnit>,
+    ) -> Result<Vec<OutputFrame<ExternalMemoryFrame>>, VideoDecoderError> {
+        let mut frames = match event {
+            DecoderEvent::DecodeChunk(chunk) => {
+                let nalus = self
+                    .parser
+                    .parse_with_dts(chunk.data, chunk.pts, chunk.dts)?;
+                self.decode_access_units(nalus)
+            }
+            DecoderEvent::DecodeParsedFrame(au) => self.decode_access_units(vec![au]),
+            DecoderEvent::SignalFrameEnd => {
+                let access_units = self.parser.flush()?;
+                self.decode_access_units(access_units)
+            }
+            DecoderEvent::SignalDataLoss => {
+                self.reference_ctx.mark_missed_frames();
+                Ok(Vec::new())
+            }
+            DecoderEvent::Flush => {
+                let access_units = self.parser.flush()?;
+                let mut frames = self.decode_access_units(access_units)?;
+                frames.append(&mut self.frame_sorter.flush());
+                Ok(frames)
+            }
+            DecoderEvent::Reset => {
+                self.parser.reset();
+                self.sei.reset();
+                let instructions = self.reference_ctx.reset();
+                self.decoder.decode_to_external_memory(instructions)?;
+                self.frame_sorter.clear();
+                Ok(Vec::new())
+            }
+        }?;
+
+        self.sei.apply(&mut frames);
+        Ok(frames)
+    }
+
+    fn decode_access_units(
+        &mut self,
+        access_units: Vec<AccessUnit>,
+    ) -> Result<Vec<OutputFrame<ExternalMemoryFrame>>, VideoDecoderError> {
+        self.sei.update(&access_units);
+        let instructions = compile_to_decoder_instructions(&mut self.reference_ctx, access_units)?;
+        let unsorted_frames = self.decoder.decode_to_external_memory(instructions)?;
+        self.stats.register_frames(&unsorted_frames);
+        let sorted_frames = self.frame_sorter.put_frames(unsorted_frames);
+        Ok(sorted_frames)
+    }
+}
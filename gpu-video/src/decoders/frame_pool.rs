@@ -0,0 +1,98 @@
+use std::sync::{Arc, Mutex};
+
+/// Provides the textures that the wgpu decoders write decoded frames into. Set it with
+/// `set_frame_pool` on a decoder to avoid allocating a new texture for every frame and to bound
+/// the memory used by the decoded frames.
+pub trait FramePool: Send {
+    /// Returns a texture for the next decoded frame. It has to have the requested size and format
+    /// and the [`wgpu::TextureUsages::COPY_DST`] usage.
+    ///
+    /// Returning `None` makes the decoding fail with
+    /// [`VideoDecoderError::FramePoolExhausted`](crate::VideoDecoderError::FramePoolExhausted).
+    fn acquire(
+        &mut self,
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+    ) -> Option<wgpu::Texture>;
+}
+
+/// A [`FramePool`] with a fixed set of pre-allocated textures. A texture is taken out of the pool
+/// when a frame is decoded into it, and has to be put back with [`TexturePool::release`] once the
+/// frame is no longer needed. Clones of the pool share the same textures.
+#[derive(Debug, Clone, Default)]
+pub struct TexturePool {
+    free_textures: Arc<Mutex<Vec<wgpu::Texture>>>,
+}
+
+impl TexturePool {
+    pub fn new(textures: Vec<wgpu::Texture>) -> Self {
+        Self {
+            free_textures: Arc::new(Mutex::new(textures)),
+        }
+    }
+
+    /// Allocates `count` textures which can hold frames with the given size and format.
+    pub fn with_textures(
+        wgpu_device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+        count: usize,
+    ) -> Self {
+        let textures = (0..count)
+            .map(|_| {
+                wgpu_device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some("gpu-video frame pool texture"),
+                    size: wgpu::Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::COPY_DST
+                        | wgpu::TextureUsages::TEXTURE_BINDING
+                        | wgpu::TextureUsages::COPY_SRC,
+                    view_formats: &[],
+                })
+            })
+            .collect();
+
+        Self::new(textures)
+    }
+
+    /// Puts a texture back into the pool, so that the next frames can be decoded into it.
+    pub fn release(&self, texture: wgpu::Texture) {
+        self.free_textures.lock().unwrap().push(texture);
+    }
+
+    /// Number of textures that are currently available for decoding.
+    pub fn free_count(&self) -> usize {
+        self.free_textures.lock().unwrap().len()
+    }
+}
+
+impl FramePool for TexturePool {
+    fn acquire(
+        &mut self,
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+    ) -> Option<wgpu::Texture> {
+        let mut free_textures = self.free_textures.lock().unwrap();
+        let index = free_textures.iter().position(|texture| {
+            texture.width() == width && texture.height() == height && texture.format() == format
+        })?;
+
+        Some(free_textures.swap_remove(index))
+    }
+}
+
+/// A [`FramePool`] together with the queue used to copy the frames into its textures.
+pub(crate) struct WgpuFramePool {
+    pub(crate) wgpu_queue: wgpu::Queue,
+    pub(crate) pool: Box<dyn FramePool>,
+}
@@ -0,0 +1,138 @@
+use std::collections::VecDeque;
+
+use crate::{
+    OutputFrame,
+    parser::{
+        h264::{self, AccessUnit},
+        h265,
+        sei::{
+            DisplayOrientation, DisplayOrientationSei, FrameSei, MasteringDisplayColorVolume,
+            SeiMessage,
+        },
+    },
+};
+
+/// Maximum number of frames for which the per-frame SEI is kept while waiting for the frame to
+/// be output. It has to cover the reordering delay of the decoder.
+const MAX_PENDING_FRAMES: usize = 64;
+
+/// Keeps track of the SEI messages that apply to all following frames: the display orientation
+/// and the mastering display colour volume. They persist until they are changed or cancelled by
+/// a later message.
+///
+/// Messages that apply only to the frame they are sent with (timecodes, captions and user data)
+/// are stored by the pts of their access unit until a frame with that pts is output.
+#[derive(Clone)]
+pub(crate) struct SeiTracker {
+    override_orientation: Option<DisplayOrientation>,
+    stream_orientation: DisplayOrientation,
+    mastering_display: Option<MasteringDisplayColorVolume>,
+    pending_frame_sei: VecDeque<(u64, FrameSei)>,
+}
+
+impl SeiTracker {
+    pub(crate) fn new(override_orientation: Option<DisplayOrientation>) -> Self {
+        Self {
+            override_orientation,
+            stream_orientation: DisplayOrientation::default(),
+            mastering_display: None,
+            pending_frame_sei: VecDeque::new(),
+        }
+    }
+
+    /// Forgets the state of the stream, keeping only the orientation override.
+    pub(crate) fn reset(&mut self) {
+        *self = Self::new(self.override_orientation);
+    }
+
+    pub(crate) fn update(&mut self, access_units: &[AccessUnit]) {
+        for au in access_units {
+            let pts = au.0.iter().find_map(|nalu| nalu.pts);
+            let messages =
+                au.0.iter()
+                    .filter_map(|nalu| match &nalu.parsed {
+                        h264::ParsedNalu::Sei(messages) => Some(messages),
+                        _ => None,
+                    })
+                    .flatten();
+            self.update_from_messages(pts, messages);
+        }
+    }
+
+    pub(crate) fn update_h265(&mut self, access_units: &[h265::AccessUnit]) {
+        for au in access_units {
+            let pts = au.0.iter().find_map(|nalu| nalu.pts);
+            let messages =
+                au.0.iter()
+                    .filter_map(|nalu| match &nalu.parsed {
+                        h265::ParsedNalu::Sei(messages) => Some(messages),
+                        _ => None,
+                    })
+                    .flatten();
+            self.update_from_messages(pts, messages);
+        }
+    }
+
+    fn update_from_messages<'a>(
+        &mut self,
+        pts: Option<u64>,
+        messages: impl Iterator<Item = &'a SeiMessage>,
+    ) {
+        let mut frame_sei = FrameSei::default();
+        for message in messages {
+            match message {
+                SeiMessage::DisplayOrientation(DisplayOrientationSei::Cancel) => {
+                    self.stream_orientation = DisplayOrientation::default();
+                }
+                SeiMessage::DisplayOrientation(DisplayOrientationSei::Orientation(orientation)) => {
+                    self.stream_orientation = *orientation;
+                }
+                SeiMessage::MasteringDisplayColorVolume(mastering_display) => {
+                    self.mastering_display = Some(*mastering_display);
+                }
+                SeiMessage::Timecodes(timecodes) => {
+                    frame_sei.timecodes.extend_from_slice(timecodes);
+                }
+                SeiMessage::Captions(captions) => {
+                    frame_sei.captions.extend_from_slice(captions);
+                }
+                SeiMessage::UserDataUnregistered(user_data) => {
+                    frame_sei.user_data_unregistered.push(user_data.clone());
+                }
+                // handled by the reference manager
+                SeiMessage::RecoveryPoint(_) => {}
+            }
+        }
+
+        let Some(pts) = pts else {
+            return;
+        };
+        if frame_sei.is_empty() {
+            return;
+        }
+
+        if self.pending_frame_sei.len() == MAX_PENDING_FRAMES {
+            self.pending_frame_sei.pop_front();
+        }
+        self.pending_frame_sei.push_back((pts, frame_sei));
+    }
+
+    /// Doesn't remove the per-frame SEI of the output frames, because the async decoders apply a
+    /// clone of the tracker. The entries are dropped once [`MAX_PENDING_FRAMES`] newer ones arrive.
+    pub(crate) fn apply<T>(&self, frames: &mut [OutputFrame<T>]) {
+        let orientation = self.override_orientation.unwrap_or(self.stream_orientation);
+        for frame in frames {
+            frame.metadata.display_orientation = orientation;
+            frame.metadata.color_info.mastering_display = self.mastering_display;
+            if let Some(pts) = frame.metadata.pts {
+                frame.metadata.sei = self
+                    .pending_frame_sei
+                    .iter()
+                    .rev()
+                    .find(|(frame_pts, _)| *frame_pts == pts)
+                    .map(|(_, sei)| sei.clone())
+                    .unwrap_or_default();
+            }
+        }
+    }
+}
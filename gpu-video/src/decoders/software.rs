@@ -0,0 +1,593 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use openh264::{decoder::Decoder, formats::YUVSource};
+
+use crate::{
+    FrameEvent, FrameMetadata, OutputFrame, RawFrameData, VideoBackendError, VideoDecoderError,
+    device::{ChromaSubsampling, ColorInfo, ColorRange, ColorSpace, MatrixCoefficients},
+    parameters::PixelFormat,
+    parser::h264::{AccessUnit, ParsedNalu},
+};
+
+/// H.264 decoder based on OpenH264, used when Vulkan Video decoding is not available.
+pub(crate) struct SoftwareH264Decoder {
+    decoder: Decoder,
+    pixel_format: PixelFormat,
+    keyframes_only: bool,
+    /// OpenH264 does not pass timestamps through, but it returns frames in presentation order,
+    /// so the frame that comes out always gets the smallest timestamp that is still pending.
+    pending_pts: BinaryHeap<Reverse<u64>>,
+    /// Size of the last output frame, used to report resolution changes.
+    last_output_size: Option<(u32, u32)>,
+    /// Read from the SPS of the last decoded access unit.
+    color: StreamColor,
+}
+
+/// Color description from the VUI of the SPS.
+#[derive(Debug, Clone, Copy)]
+struct StreamColor {
+    space: ColorSpace,
+    range: ColorRange,
+    info: ColorInfo,
+}
+
+impl Default for StreamColor {
+    fn default() -> Self {
+        Self {
+            space: ColorSpace::Unspecified,
+            range: ColorRange::Limited,
+            info: ColorInfo::default(),
+        }
+    }
+}
+
+impl From<&h264_reader::nal::sps::SeqParameterSet> for StreamColor {
+    fn from(sps: &h264_reader::nal::sps::SeqParameterSet) -> Self {
+        Self {
+            space: ColorSpace::from(sps),
+            range: ColorRange::from(sps),
+            info: ColorInfo::from(sps),
+        }
+    }
+}
+
+impl SoftwareH264Decoder {
+    pub(crate) fn new(
+        pixel_format: PixelFormat,
+        keyframes_only: bool,
+    ) -> Result<Self, VideoDecoderError> {
+        // OpenH264 only decodes 8-bit streams
+        if pixel_format == PixelFormat::P010 {
+            return Err(VideoDecoderError::UnsupportedPixelFormat {
+                pixel_format,
+                bit_depth: 8,
+            });
+        }
+
+        let decoder = Decoder::new().map_err(backend_error)?;
+        Ok(Self {
+            decoder,
+            pixel_format,
+            keyframes_only,
+            pending_pts: BinaryHeap::new(),
+            last_output_size: None,
+            color: StreamColor::default(),
+        })
+    }
+
+    pub(crate) fn decode(
+        &mut self,
+        access_units: Vec<AccessUnit>,
+    ) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
+        let mut result = Vec::new();
+        for access_unit in access_units {
+            if self.keyframes_only && !access_unit.is_intra() {
+                continue;
+            }
+
+            let AccessUnit(nalus) = access_unit;
+            let pts = nalus
+                .iter()
+                .filter(|nalu| matches!(nalu.parsed, ParsedNalu::Slice(_)))
+                .find_map(|nalu| nalu.pts);
+            if let Some(pts) = pts {
+                self.pending_pts.push(Reverse(pts));
+            }
+            let sps = nalus.iter().find_map(|nalu| match &nalu.parsed {
+                ParsedNalu::Slice(slice) => Some(&slice.sps),
+                _ => None,
+            });
+            if let Some(sps) = sps {
+                self.color = StreamColor::from(sps);
+            }
+
+            let data = nalus
+                .iter()
+                .flat_map(|nalu| nalu.raw_bytes.iter().copied())
+                .collect::<Vec<u8>>();
+
+            let (pixel_format, color) = (self.pixel_format, self.color);
+            let frame = self
+                .decoder
+                .decode(&data)
+                .map_err(backend_error)?
+                .map(|yuv| convert_frame(&yuv, pixel_format, color));
+            if let Some(frame) = frame {
+                result.push(self.output_frame(frame));
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) fn flush(&mut self) -> Result<Vec<OutputFrame<RawFrameData>>, VideoDecoderError> {
+        let frames = self
+            .decoder
+            .flush_remaining()
+            .map_err(backend_error)?
+            .iter()
+            .map(|yuv| convert_frame(yuv, self.pixel_format, self.color))
+            .collect::<Vec<_>>();
+
+        let frames = frames
+            .into_iter()
+            .map(|frame| self.output_frame(frame))
+            .collect();
+        self.pending_pts.clear();
+        Ok(frames)
+    }
+
+    /// OpenH264 has no way to drop its state, so the decoder is recreated. Frames that were not
+    /// output yet are discarded.
+    pub(crate) fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.decoder = Decoder::new().map_err(backend_error)?;
+        self.pending_pts.clear();
+        Ok(())
+    }
+
+    fn output_frame(&mut self, frame: RawFrameData) -> OutputFrame<RawFrameData> {
+        let size = (frame.width, frame.height);
+        let events = match self.last_output_size.replace(size) {
+            Some(previous_size) if previous_size != size => vec![FrameEvent::ResolutionChanged {
+                width: frame.width,
+                height: frame.height,
+            }],
+            _ => Vec::new(),
+        };
+
+        OutputFrame {
+            data: frame,
+            metadata: FrameMetadata {
+                pts: self.pending_pts.pop().map(|Reverse(pts)| pts),
+                dts: None,
+                picture_type: None,
+                pic_order_cnt: None,
+                color_space: self.color.space,
+                color_range: self.color.range,
+                chroma_subsampling: ChromaSubsampling::Yuv420,
+                color_info: self.color.info,
+                gpu_decode_duration: None,
+                display_orientation: Default::default(),
+                sei: Default::default(),
+                corrupted: false,
+                recovering: false,
+                sample_aspect_ratio: None,
+                events,
+            },
+        }
+    }
+}
+
+/// Converts a planar I420 frame returned by OpenH264 into tightly packed `pixel_format`.
+fn convert_frame(
+    yuv: &impl YUVSource,
+    pixel_format: PixelFormat,
+    color: StreamColor,
+) -> RawFrameData {
+    let (width, height) = yuv.dimensions();
+    let mut frame = Vec::with_capacity(pixel_format.frame_size(width as u32, height as u32));
+    match pixel_format {
+        PixelFormat::Nv12 => write_nv12(yuv, &mut frame),
+        PixelFormat::I420 => write_i420(yuv, &mut frame),
+        PixelFormat::Rgba => {
+            write_rgba(yuv, &mut frame, color.info.matrix_coefficients, color.range)
+        }
+        PixelFormat::Yuyv => write_yuyv(yuv, &mut frame),
+        PixelFormat::P010 => unreachable!("P010 is rejected when the decoder is created"),
+    }
+
+    RawFrameData {
+        frame,
+        width: width as u32,
+        height: height as u32,
+        planes: None,
+    }
+}
+
+fn write_y_plane(yuv: &impl YUVSource, frame: &mut Vec<u8>) {
+    let (width, height) = yuv.dimensions();
+    let (y_stride, _, _) = yuv.strides();
+    for row in yuv.y().chunks(y_stride).take(height) {
+        frame.extend_from_slice(&row[..width]);
+    }
+}
+
+fn write_nv12(yuv: &impl YUVSource, frame: &mut Vec<u8>) {
+    write_y_plane(yuv, frame);
+
+    let (width, height) = yuv.dimensions();
+    let (_, u_stride, v_stride) = yuv.strides();
+    let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+    let u_rows = yuv.u().chunks(u_stride);
+    let v_rows = yuv.v().chunks(v_stride);
+    for (u_row, v_row) in u_rows.zip(v_rows).take(chroma_height) {
+        for (u, v) in u_row[..chroma_width].iter().zip(&v_row[..chroma_width]) {
+            frame.push(*u);
+            frame.push(*v);
+        }
+    }
+}
+
+fn write_i420(yuv: &impl YUVSource, frame: &mut Vec<u8>) {
+    write_y_plane(yuv, frame);
+
+    let (width, height) = yuv.dimensions();
+    let (_, u_stride, v_stride) = yuv.strides();
+    let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+    for (plane, stride) in [(yuv.u(), u_stride), (yuv.v(), v_stride)] {
+        for row in plane.chunks(stride).take(chroma_height) {
+            frame.extend_from_slice(&row[..chroma_width]);
+        }
+    }
+}
+
+fn write_yuyv(yuv: &impl YUVSource, frame: &mut Vec<u8>) {
+    let (width, height) = yuv.dimensions();
+    let (y_stride, u_stride, v_stride) = yuv.strides();
+    let (y_plane, u_plane, v_plane) = (yuv.y(), yuv.u(), yuv.v());
+    for row in 0..height {
+        let y_row = &y_plane[row * y_stride..][..width];
+        let u_row = &u_plane[(row / 2) * u_stride..];
+        let v_row = &v_plane[(row / 2) * v_stride..];
+        for (i, pair) in y_row.chunks(2).enumerate() {
+            // the last pixel of an odd-width row is repeated
+            frame.extend_from_slice(&[pair[0], u_row[i], pair[pair.len() - 1], v_row[i]]);
+        }
+    }
+}
+
+/// Streams with BT.601 matrix coefficients are converted with the BT.601 matrix, all other
+/// ones, including unspecified, with BT.709, which is also what the GPU conversion does.
+fn write_rgba(
+    yuv: &impl YUVSource,
+    frame: &mut Vec<u8>,
+    matrix: MatrixCoefficients,
+    range: ColorRange,
+) {
+    // R = Y + r_v * V, G = Y - g_u * U - g_v * V, B = Y + b_u * U
+    let [r_v, g_u, g_v, b_u] = match matrix {
+        MatrixCoefficients::BT470BG | MatrixCoefficients::SMPTE170M => {
+            [1.402, 0.344136, 0.714136, 1.772]
+        }
+        _ => [1.5748, 0.1873, 0.4681, 1.8556],
+    };
+    let (luma_offset, luma_scale, chroma_scale) = match range {
+        ColorRange::Limited => (16.0, 255.0 / 219.0, 255.0 / 224.0),
+        ColorRange::Full => (0.0, 1.0, 1.0),
+    };
+
+    let (width, height) = yuv.dimensions();
+    let (y_stride, u_stride, v_stride) = yuv.strides();
+    let (y_plane, u_plane, v_plane) = (yuv.y(), yuv.u(), yuv.v());
+    for row in 0..height {
+        for column in 0..width {
+            let luma = (y_plane[row * y_stride + column] as f32 - luma_offset) * luma_scale;
+            let chroma_index = |stride| (row / 2) * stride + column / 2;
+            let u = (u_plane[chroma_index(u_stride)] as f32 - 128.0) * chroma_scale;
+            let v = (v_plane[chroma_index(v_stride)] as f32 - 128.0) * chroma_scale;
+
+            let r = luma + r_v * v;
+            let g = luma - g_u * u - g_v * v;
+            let b = luma + b_u * u;
+            frame.extend(
+                [r, g, b]
+                    .into_iter()
+                    .map(|value| value.round().clamp(0.0, 255.0) as u8),
+            );
+            frame.push(u8::MAX);
+        }
+    }
+}
+
+fn backend_error(err: openh264::Error) -> VideoDecoderError {
+    VideoDecoderError::BackendError(VideoBackendError {
+        message: err.to_string(),
+        source: Box::new(err),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Planar I420 frame with rows padded to the strides, like the frames returned by OpenH264.
+    struct TestYuv {
+        dimensions: (usize, usize),
+        strides: (usize, usize, usize),
+        y: Vec<u8>,
+        u: Vec<u8>,
+        v: Vec<u8>,
+    }
+
+    impl TestYuv {
+        /// Rows of the planes are padded with `0xEE`.
+        fn new(width: usize, height: usize, y: &[u8], u: &[u8], v: &[u8]) -> Self {
+            let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+            let pad = |plane: &[u8], width: usize, height: usize, stride: usize| {
+                assert_eq!(plane.len(), width * height);
+                plane
+                    .chunks(width)
+                    .flat_map(|row| {
+                        row.iter()
+                            .copied()
+                            .chain([0xEE; 3].into_iter().take(stride - width))
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let strides = (width + 3, chroma_width + 2, chroma_width + 1);
+
+            Self {
+                dimensions: (width, height),
+                strides,
+                y: pad(y, width, height, strides.0),
+                u: pad(u, chroma_width, chroma_height, strides.1),
+                v: pad(v, chroma_width, chroma_height, strides.2),
+            }
+        }
+
+        /// Frame with every pixel set to the same color.
+        fn solid(width: usize, height: usize, [y, u, v]: [u8; 3]) -> Self {
+            let chroma_size = width.div_ceil(2) * height.div_ceil(2);
+            Self::new(
+                width,
+                height,
+                &vec![y; width * height],
+                &vec![u; chroma_size],
+                &vec![v; chroma_size],
+            )
+        }
+    }
+
+    impl YUVSource for TestYuv {
+        fn dimensions(&self) -> (usize, usize) {
+            self.dimensions
+        }
+
+        fn strides(&self) -> (usize, usize, usize) {
+            self.strides
+        }
+
+        fn y(&self) -> &[u8] {
+            &self.y
+        }
+
+        fn u(&self) -> &[u8] {
+            &self.u
+        }
+
+        fn v(&self) -> &[u8] {
+            &self.v
+        }
+    }
+
+    /// 3x3 frame, which has 2x2 chroma planes.
+    fn odd_size_frame() -> TestYuv {
+        TestYuv::new(
+            3,
+            3,
+            &[1, 2, 3, 4, 5, 6, 7, 8, 9],
+            &[10, 11, 12, 13],
+            &[20, 21, 22, 23],
+        )
+    }
+
+    fn color(matrix_coefficients: MatrixCoefficients, range: ColorRange) -> StreamColor {
+        StreamColor {
+            range,
+            info: ColorInfo {
+                matrix_coefficients,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn rgba_pixel(yuv: [u8; 3], matrix: MatrixCoefficients, range: ColorRange) -> [u8; 4] {
+        let mut frame = Vec::new();
+        write_rgba(&TestYuv::solid(2, 2, yuv), &mut frame, matrix, range);
+        assert_eq!(frame.len(), 16);
+        assert!(frame.chunks(4).all(|pixel| pixel == &frame[..4]));
+        frame[..4].try_into().unwrap()
+    }
+
+    fn assert_close(actual: [u8; 4], expected: [u8; 4]) {
+        let close = actual
+            .iter()
+            .zip(expected)
+            .all(|(actual, expected)| actual.abs_diff(expected) <= 1);
+        assert!(close, "{actual:?} != {expected:?}");
+    }
+
+    #[test]
+    fn i420_strips_row_padding() {
+        let mut frame = Vec::new();
+        write_i420(&odd_size_frame(), &mut frame);
+        assert_eq!(
+            frame,
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 20, 21, 22, 23]
+        );
+    }
+
+    #[test]
+    fn convert_frame_sizes() {
+        let yuv = odd_size_frame();
+        for pixel_format in [
+            PixelFormat::Nv12,
+            PixelFormat::I420,
+            PixelFormat::Rgba,
+            PixelFormat::Yuyv,
+        ] {
+            let frame = convert_frame(&yuv, pixel_format, StreamColor::default());
+            assert_eq!((frame.width, frame.height), (3, 3));
+            assert_eq!(
+                frame.frame.len(),
+                pixel_format.frame_size(3, 3),
+                "{pixel_format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn convert_frame_nv12_interleaves_chroma() {
+        let frame = convert_frame(&odd_size_frame(), PixelFormat::Nv12, StreamColor::default());
+        assert_eq!(
+            frame.frame,
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20, 11, 21, 12, 22, 13, 23]
+        );
+    }
+
+    #[test]
+    fn nv12_odd_width_even_height() {
+        let yuv = TestYuv::new(3, 2, &[1, 2, 3, 4, 5, 6], &[10, 11], &[20, 21]);
+        let frame = convert_frame(&yuv, PixelFormat::Nv12, StreamColor::default());
+        assert_eq!(frame.frame, [1, 2, 3, 4, 5, 6, 10, 20, 11, 21]);
+    }
+
+    #[test]
+    fn nv12_even_width_odd_height() {
+        let yuv = TestYuv::new(2, 3, &[1, 2, 3, 4, 5, 6], &[10, 11], &[20, 21]);
+        let frame = convert_frame(&yuv, PixelFormat::Nv12, StreamColor::default());
+        assert_eq!(frame.frame, [1, 2, 3, 4, 5, 6, 10, 20, 11, 21]);
+    }
+
+    #[test]
+    fn yuyv_odd_size_repeats_last_pixel() {
+        let frame = convert_frame(&odd_size_frame(), PixelFormat::Yuyv, StreamColor::default());
+        assert_eq!(
+            frame.frame,
+            [
+                1, 10, 2, 20, 3, 11, 3, 21, //
+                4, 10, 5, 20, 6, 11, 6, 21, //
+                7, 12, 8, 22, 9, 13, 9, 23,
+            ]
+        );
+    }
+
+    #[test]
+    fn frames_get_pts_in_presentation_order() {
+        let mut decoder = SoftwareH264Decoder::new(PixelFormat::Nv12, false).unwrap();
+        // I P B B in decode order
+        for pts in [0, 3000, 1000, 2000] {
+            decoder.pending_pts.push(Reverse(pts));
+        }
+        let pts = (0..5)
+            .map(|_| {
+                let frame =
+                    convert_frame(&odd_size_frame(), PixelFormat::Nv12, StreamColor::default());
+                decoder.output_frame(frame).metadata.pts
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(pts, [Some(0), Some(1000), Some(2000), Some(3000), None]);
+    }
+
+    #[test]
+    fn resolution_change_is_reported() {
+        let mut decoder = SoftwareH264Decoder::new(PixelFormat::I420, false).unwrap();
+        let mut output = |yuv: &TestYuv| {
+            let frame = convert_frame(yuv, PixelFormat::I420, StreamColor::default());
+            decoder.output_frame(frame).metadata.events
+        };
+        assert!(output(&odd_size_frame()).is_empty());
+        assert!(output(&odd_size_frame()).is_empty());
+        assert!(matches!(
+            output(&TestYuv::solid(4, 2, [0, 128, 128]))[..],
+            [FrameEvent::ResolutionChanged {
+                width: 4,
+                height: 2
+            }]
+        ));
+    }
+
+    #[test]
+    fn rgba_limited_range_bt709() {
+        let (matrix, range) = (MatrixCoefficients::BT709, ColorRange::Limited);
+        assert_eq!(rgba_pixel([16, 128, 128], matrix, range), [0, 0, 0, 255]);
+        assert_eq!(
+            rgba_pixel([235, 128, 128], matrix, range),
+            [255, 255, 255, 255]
+        );
+        assert_close(rgba_pixel([63, 102, 240], matrix, range), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn rgba_unspecified_matrix_uses_bt709() {
+        assert_eq!(
+            rgba_pixel(
+                [63, 102, 240],
+                MatrixCoefficients::Unspecified,
+                ColorRange::Limited
+            ),
+            rgba_pixel(
+                [63, 102, 240],
+                MatrixCoefficients::BT709,
+                ColorRange::Limited
+            ),
+        );
+    }
+
+    #[test]
+    fn rgba_limited_range_bt601() {
+        for matrix in [MatrixCoefficients::SMPTE170M, MatrixCoefficients::BT470BG] {
+            assert_close(
+                rgba_pixel([81, 90, 240], matrix, ColorRange::Limited),
+                [255, 0, 0, 255],
+            );
+        }
+
+        // the same samples are a different color with the BT.709 matrix
+        let bt709 = rgba_pixel(
+            [81, 90, 240],
+            MatrixCoefficients::BT709,
+            ColorRange::Limited,
+        );
+        assert!(bt709[1] > 10, "{bt709:?}");
+    }
+
+    #[test]
+    fn rgba_full_range() {
+        let (matrix, range) = (MatrixCoefficients::BT709, ColorRange::Full);
+        assert_eq!(rgba_pixel([0, 128, 128], matrix, range), [0, 0, 0, 255]);
+        assert_eq!(
+            rgba_pixel([255, 128, 128], matrix, range),
+            [255, 255, 255, 255]
+        );
+        assert_eq!(rgba_pixel([16, 128, 128], matrix, range), [16, 16, 16, 255]);
+    }
+
+    #[test]
+    fn convert_frame_rgba_uses_stream_color() {
+        let yuv = TestYuv::solid(2, 2, [16, 128, 128]);
+        let limited = convert_frame(
+            &yuv,
+            PixelFormat::Rgba,
+            color(MatrixCoefficients::BT709, ColorRange::Limited),
+        );
+        let full = convert_frame(
+            &yuv,
+            PixelFormat::Rgba,
+            color(MatrixCoefficients::BT709, ColorRange::Full),
+        );
+        assert_eq!(limited.frame[..4], [0, 0, 0, 255]);
+        assert_eq!(full.frame[..4], [16, 16, 16, 255]);
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::frame_sorter::DecodeResult;
+
+/// Window over which [`DecoderStats::gpu_utilization`] is calculated.
+const UTILIZATION_WINDOW: Duration = Duration::from_secs(1);
+
+/// Decoding statistics. GPU timing is only available if the decoder was created with
+/// [`DecoderParameters::enable_gpu_timing`](crate::parameters::DecoderParameters::enable_gpu_timing)
+/// and the device supports timestamp queries on the decode queue.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DecoderStats {
+    /// Number of frames decoded since the decoder was created.
+    pub decoded_frames: u64,
+
+    /// Time the GPU spent decoding the last frame.
+    pub last_gpu_decode_duration: Option<Duration>,
+
+    /// Fraction of the last second the GPU spent decoding frames for this decoder, in the
+    /// range [0, 1].
+    pub gpu_utilization: Option<f32>,
+}
+
+pub(crate) type DecoderStatsCallback = Box<dyn FnMut(DecoderStats) + Send>;
+
+pub(crate) struct DecoderStatsTracker {
+    decoded_frames: u64,
+    last_gpu_decode_duration: Option<Duration>,
+    gpu_decode_durations: VecDeque<(Instant, Duration)>,
+    callback: Option<DecoderStatsCallback>,
+}
+
+impl DecoderStatsTracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            decoded_frames: 0,
+            last_gpu_decode_duration: None,
+            gpu_decode_durations: VecDeque::new(),
+            callback: None,
+        }
+    }
+
+    pub(crate) fn set_callback(&mut self, callback: DecoderStatsCallback) {
+        self.callback = Some(callback);
+    }
+
+    pub(crate) fn register_frames<T>(&mut self, frames: &[DecodeResult<T>]) {
+        let now = Instant::now();
+        for frame in frames {
+            self.decoded_frames += 1;
+            if let Some(duration) = frame.metadata.gpu_decode_duration {
+                self.last_gpu_decode_duration = Some(duration);
+                self.gpu_decode_durations.push_back((now, duration));
+            }
+
+            while let Some((timestamp, _)) = self.gpu_decode_durations.front() {
+                if now.duration_since(*timestamp) <= UTILIZATION_WINDOW {
+                    break;
+                }
+                self.gpu_decode_durations.pop_front();
+            }
+
+            self.notify();
+        }
+    }
+
+    /// Software decoders do not report GPU timing.
+    #[cfg(feature = "software-fallback")]
+    pub(crate) fn register_software_frames(&mut self, count: usize) {
+        for _ in 0..count {
+            self.decoded_frames += 1;
+            self.notify();
+        }
+    }
+
+    /// Calls the stats callback with the stats after the last registered frame.
+    fn notify(&mut self) {
+        let stats = self.stats();
+        if let Some(callback) = self.callback.as_mut() {
+            callback(stats);
+        }
+    }
+
+    pub(crate) fn stats(&self) -> DecoderStats {
+        let gpu_utilization = self.last_gpu_decode_duration.map(|_| {
+            let busy: Duration = self.gpu_decode_durations.iter().map(|(_, d)| *d).sum();
+            (busy.as_secs_f32() / UTILIZATION_WINDOW.as_secs_f32()).min(1.0)
+        });
+
+        DecoderStats {
+            decoded_frames: self.decoded_frames,
+            last_gpu_decode_duration: self.last_gpu_decode_duration,
+            gpu_utilization,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{
+        device::{ColorRange, PictureType},
+        frame_sorter::DecodeResultMetadata,
+    };
+
+    fn frame(gpu_decode_duration: Option<Duration>) -> DecodeResult<()> {
+        DecodeResult {
+            frame: (),
+            metadata: DecodeResultMetadata {
+                pts: None,
+                dts: None,
+                picture_type: PictureType::I,
+                pic_order_cnt: 0,
+                max_num_reorder_frames: 0,
+                max_num_reorder_frames_signaled: true,
+                is_idr: true,
+                color_space: Default::default(),
+                color_range: ColorRange::Limited,
+                chroma_subsampling: Default::default(),
+                color_info: Default::default(),
+                gpu_decode_duration,
+                corrupted: false,
+                recovering: false,
+                sample_aspect_ratio: None,
+                width: 16,
+                height: 16,
+            },
+        }
+    }
+
+    fn assert_utilization(stats: DecoderStats, expected: f32) {
+        let utilization = stats.gpu_utilization.unwrap();
+        assert!(
+            (utilization - expected).abs() < 1e-6,
+            "utilization {utilization}, expected {expected}"
+        );
+    }
+
+    #[test]
+    fn no_gpu_stats_without_timing() {
+        let mut tracker = DecoderStatsTracker::new();
+        tracker.register_frames(&[frame(None), frame(None)]);
+
+        let stats = tracker.stats();
+        assert_eq!(stats.decoded_frames, 2);
+        assert_eq!(stats.last_gpu_decode_duration, None);
+        assert_eq!(stats.gpu_utilization, None);
+    }
+
+    #[test]
+    fn gpu_utilization_in_window() {
+        let mut tracker = DecoderStatsTracker::new();
+        tracker.register_frames(&[
+            frame(Some(Duration::from_millis(100))),
+            frame(Some(Duration::from_millis(150))),
+        ]);
+
+        let stats = tracker.stats();
+        assert_eq!(stats.decoded_frames, 2);
+        assert_eq!(
+            stats.last_gpu_decode_duration,
+            Some(Duration::from_millis(150))
+        );
+        assert_utilization(stats, 0.25);
+
+        // more GPU time than the window is clamped
+        tracker.register_frames(&[frame(Some(Duration::from_millis(900)))]);
+        assert_utilization(tracker.stats(), 1.0);
+    }
+
+    #[test]
+    fn drop_durations_outside_of_window() {
+        let mut tracker = DecoderStatsTracker::new();
+        tracker.register_frames(&[frame(Some(Duration::from_millis(500)))]);
+        tracker.gpu_decode_durations[0].0 -= Duration::from_secs(2);
+
+        tracker.register_frames(&[frame(Some(Duration::from_millis(100))), frame(None)]);
+
+        let stats = tracker.stats();
+        assert_eq!(stats.decoded_frames, 3);
+        assert_eq!(
+            stats.last_gpu_decode_duration,
+            Some(Duration::from_millis(100))
+        );
+        assert_utilization(stats, 0.1);
+    }
+
+    #[test]
+    fn callback_called_for_every_frame() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let mut tracker = DecoderStatsTracker::new();
+        tracker.set_callback({
+            let reported = reported.clone();
+            Box::new(move |stats| reported.lock().unwrap().push(stats.decoded_frames))
+        });
+
+        tracker.register_frames(&[frame(None), frame(None), frame(None)]);
+        assert_eq!(*reported.lock().unwrap(), vec![1, 2, 3]);
+    }
+}
@@ -1,18 +1,25 @@
 use crate::{
-    DecoderEvent, EncodedInputChunk, OutputFrame, VideoDecoderError,
+    DecoderEvent, EncodedInputChunk, H264BitstreamFormat, OutputFrame, VideoDecoderError,
+    decoders::{
+        DecoderStats, FramePool, frame_pool::WgpuFramePool, sei::SeiTracker,
+        stats::DecoderStatsTracker,
+    },
     frame_sorter::{DecodeResult, FrameSorter},
     parser::{
+        av1::{self, Av1Parser},
         decoder_instructions::{DecoderInstruction, compile_to_decoder_instructions},
         h264::{AccessUnit, H264Parser},
+        h265::{self, H265Parser},
         reference_manager::ReferenceContext,
     },
 };
 
-pub(crate) trait WgpuVideoDecoderBackend: Send {
+pub(crate) trait WgpuVideoDecoderBackend<I = DecoderInstruction>: Send {
     fn decode_to_wgpu_textures(
         &mut self,
         wgpu_device: &wgpu::Device,
-        decoder_instructions: Vec<DecoderInstruction>,
+        frame_pool: Option<&mut WgpuFramePool>,
+        decoder_instructions: Vec<I>,
     ) -> Result<Vec<DecodeResult<wgpu::Texture>>, VideoDecoderError>;
 }
 
@@ -23,10 +30,19 @@ pub struct WgpuTexturesDecoder {
     pub(crate) parser: H264Parser,
     pub(crate) reference_ctx: ReferenceContext,
     pub(crate) frame_sorter: FrameSorter<wgpu::Texture>,
+    pub(crate) stats: DecoderStatsTracker,
+    pub(crate) sei: SeiTracker,
+    pub(crate) frame_pool: Option<WgpuFramePool>,
 }
 
 impl WgpuTexturesDecoder {
     /// The produced textures have the [`wgpu::TextureFormat::NV12`] format and can be used as a texture binding.
+    /// Frames of 10-bit streams have the [`wgpu::TextureFormat::P010`] format, which requires
+    /// [`wgpu::Features::TEXTURE_FORMAT_P010`] support in the adapter.
+    /// With [`PixelFormat::Rgba`](crate::parameters::PixelFormat::Rgba) set in
+    /// [`DecoderParameters::output_pixel_format`](crate::parameters::DecoderParameters::output_pixel_format),
+    /// frames are converted on the GPU and the textures have the
+    /// [`wgpu::TextureFormat::Rgba8Unorm`] format.
     pub fn decode(
         &mut self,
         frame: EncodedInputChunk<'_>,
@@ -36,22 +52,63 @@ impl WgpuTexturesDecoder {
 
     /// Flush all frames from the decoder.
     ///
+    /// Works the same as [`BytesDecoder::flush`](crate::BytesDecoder::flush).
+    ///
     /// Make sure that this is done when you have the knowledge that no more frames will be coming
     /// that need to be presented before the already decoded frames.
     pub fn flush(&mut self) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
         self.process_event(DecoderEvent::Flush)
     }
 
+    /// Resets the decoder after a discontinuity in the stream without recreating the video
+    /// session. See [`DecoderEvent::Reset`].
+    pub fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Reset).map(|_| ())
+    }
+
+    /// Sets the format of the chunks passed to [`Self::decode`]. Defaults to
+    /// [`H264BitstreamFormat::AnnexB`]. Has to be called before decoding the first chunk, data
+    /// buffered in the parser is dropped.
+    pub fn set_bitstream_format(&mut self, format: H264BitstreamFormat) {
+        self.parser = H264Parser::new(format);
+    }
+
+    /// Returns decoding statistics. See [`DecoderStats`].
+    pub fn stats(&self) -> DecoderStats {
+        self.stats.stats()
+    }
+
+    /// Sets a callback called with the updated [`DecoderStats`] after every decoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(DecoderStats) + Send + 'static) {
+        self.stats.set_callback(Box::new(callback));
+    }
+
+    /// Makes the decoder write frames into textures from `frame_pool` instead of allocating a
+    /// new texture for every frame. The frames are copied into the textures on `wgpu_queue`.
+    pub fn set_frame_pool(
+        &mut self,
+        wgpu_queue: &wgpu::Queue,
+        frame_pool: impl FramePool + 'static,
+    ) {
+        self.frame_pool = Some(WgpuFramePool {
+            wgpu_queue: wgpu_queue.clone(),
+            pool: Box::new(frame_pool),
+        });
+    }
+
     /// Process a [`DecoderEvent`]. For most use cases, using [`Self::decode`] and [`Self::flush`] is enough.
     /// Use this only when you need more fine-grained control.
-    /// May return a sequence of decoded frames in the [NV12 format](https://en.wikipedia.org/wiki/YCbCr#4:2:0).
+    /// May return a sequence of decoded frames in the format described in [`Self::decode`].
     pub fn process_event(
         &mut self,
         event: DecoderEvent<'_, AccessUnit>,
     ) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
-        match event {
+        let mut frames = match event {
             DecoderEvent::DecodeChunk(chunk) => {
-                let nalus = self.parser.parse(chunk.data, chunk.pts)?;
+                let nalus = self
+                    .parser
+                    .parse_with_dts(chunk.data, chunk.pts, chunk.dts)?;
                 self.decode_access_units(nalus)
             }
             DecoderEvent::DecodeParsedFrame(au) => self.decode_access_units(vec![au]),
@@ -69,17 +126,289 @@ impl WgpuTexturesDecoder {
                 frames.append(&mut self.frame_sorter.flush());
                 Ok(frames)
             }
-        }
+            DecoderEvent::Reset => {
+                self.parser.reset();
+                self.sei.reset();
+                let instructions = self.reference_ctx.reset();
+                self.decoder
+                    .decode_to_wgpu_textures(&self.wgpu_device, None, instructions)?;
+                self.frame_sorter.clear();
+                Ok(Vec::new())
+            }
+        }?;
+
+        self.sei.apply(&mut frames);
+        Ok(frames)
     }
 
     fn decode_access_units(
         &mut self,
         access_units: Vec<AccessUnit>,
     ) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
+        self.sei.update(&access_units);
         let instructions = compile_to_decoder_instructions(&mut self.reference_ctx, access_units)?;
-        let unsorted_frames = self
-            .decoder
-            .decode_to_wgpu_textures(&self.wgpu_device, instructions)?;
+        let unsorted_frames = self.decoder.decode_to_wgpu_textures(
+            &self.wgpu_device,
+            self.frame_pool.as_mut(),
+            instructions,
+        )?;
+        self.stats.register_frames(&unsorted_frames);
+        let sorted_frames = self.frame_sorter.put_frames(unsorted_frames);
+        Ok(sorted_frames)
+    }
+}
+
+/// A H.265 decoder that outputs frames stored as [`wgpu::Texture`]s
+pub struct WgpuTexturesDecoderH265 {
+    pub(crate) wgpu_device: wgpu::Device,
+    pub(crate) decoder:
+        Box<dyn WgpuVideoDecoderBackend<h265::decoder_instructions::DecoderInstruction>>,
+    pub(crate) parser: H265Parser,
+    pub(crate) reference_ctx: h265::reference_manager::ReferenceContext,
+    pub(crate) frame_sorter: FrameSorter<wgpu::Texture>,
+    pub(crate) stats: DecoderStatsTracker,
+    pub(crate) sei: SeiTracker,
+    pub(crate) frame_pool: Option<WgpuFramePool>,
+}
+
+impl WgpuTexturesDecoderH265 {
+    /// The produced textures have the [`wgpu::TextureFormat::NV12`] format and can be used as a texture binding.
+    /// With [`PixelFormat::Rgba`](crate::parameters::PixelFormat::Rgba) set in
+    /// [`DecoderParameters::output_pixel_format`](crate::parameters::DecoderParameters::output_pixel_format),
+    /// frames are converted on the GPU and the textures have the
+    /// [`wgpu::TextureFormat::Rgba8Unorm`] format.
+    pub fn decode(
+        &mut self,
+        frame: EncodedInputChunk<'_>,
+    ) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
+        self.process_event(DecoderEvent::DecodeChunk(frame))
+    }
+
+    /// Flush all frames from the decoder.
+    ///
+    /// Works the same as [`BytesDecoder::flush`](crate::BytesDecoder::flush).
+    ///
+    /// Make sure that this is done when you have the knowledge that no more frames will be coming
+    /// that need to be presented before the already decoded frames.
+    pub fn flush(&mut self) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
+        self.process_event(DecoderEvent::Flush)
+    }
+
+    /// Resets the decoder after a discontinuity in the stream without recreating the video
+    /// session. See [`DecoderEvent::Reset`].
+    pub fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Reset).map(|_| ())
+    }
+
+    /// Returns decoding statistics. See [`DecoderStats`].
+    pub fn stats(&self) -> DecoderStats {
+        self.stats.stats()
+    }
+
+    /// Sets a callback called with the updated [`DecoderStats`] after every decoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(DecoderStats) + Send + 'static) {
+        self.stats.set_callback(Box::new(callback));
+    }
+
+    /// Makes the decoder write frames into textures from `frame_pool` instead of allocating a
+    /// new texture for every frame. The frames are copied into the textures on `wgpu_queue`.
+    pub fn set_frame_pool(
+        &mut self,
+        wgpu_queue: &wgpu::Queue,
+        frame_pool: impl FramePool + 'static,
+    ) {
+        self.frame_pool = Some(WgpuFramePool {
+            wgpu_queue: wgpu_queue.clone(),
+            pool: Box::new(frame_pool),
+        });
+    }
+
+    /// Process a [`DecoderEvent`]. For most use cases, using [`Self::decode`] and [`Self::flush`] is enough.
+    /// Use this only when you need more fine-grained control.
+    /// May return a sequence of decoded frames in the format described in [`Self::decode`].
+    pub fn process_event(
+        &mut self,
+        event: DecoderEvent<'_, h265::AccessUnit>,
+    ) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
+        let mut frames = match event {
+            DecoderEvent::DecodeChunk(chunk) => {
+                let access_units = self
+                    .parser
+                    .parse_with_dts(chunk.data, chunk.pts, chunk.dts)?;
+                self.decode_access_units(access_units)
+            }
+            DecoderEvent::DecodeParsedFrame(au) => self.decode_access_units(vec![au]),
+            DecoderEvent::SignalFrameEnd => {
+                let access_units = self.parser.flush()?;
+                self.decode_access_units(access_units)
+            }
+            DecoderEvent::SignalDataLoss => {
+                self.reference_ctx.mark_missed_frames();
+                Ok(Vec::new())
+            }
+            DecoderEvent::Flush => {
+                let access_units = self.parser.flush()?;
+                let mut frames = self.decode_access_units(access_units)?;
+                frames.append(&mut self.frame_sorter.flush());
+                Ok(frames)
+            }
+            DecoderEvent::Reset => {
+                self.parser = H265Parser::default();
+                self.sei.reset();
+                let instructions = self.reference_ctx.reset();
+                self.decoder
+                    .decode_to_wgpu_textures(&self.wgpu_device, None, instructions)?;
+                self.frame_sorter.clear();
+                Ok(Vec::new())
+            }
+        }?;
+
+        self.sei.apply(&mut frames);
+        Ok(frames)
+    }
+
+    fn decode_access_units(
+        &mut self,
+        access_units: Vec<h265::AccessUnit>,
+    ) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
+        self.sei.update_h265(&access_units);
+        let instructions = h265::decoder_instructions::compile_to_decoder_instructions(
+            &mut self.reference_ctx,
+            access_units,
+        )?;
+        let unsorted_frames = self.decoder.decode_to_wgpu_textures(
+            &self.wgpu_device,
+            self.frame_pool.as_mut(),
+            instructions,
+        )?;
+        self.stats.register_frames(&unsorted_frames);
+        let sorted_frames = self.frame_sorter.put_frames(unsorted_frames);
+        Ok(sorted_frames)
+    }
+}
+
+/// An AV1 decoder that outputs frames stored as [`wgpu::Texture`]s
+pub struct WgpuTexturesDecoderAv1 {
+    pub(crate) wgpu_device: wgpu::Device,
+    pub(crate) decoder:
+        Box<dyn WgpuVideoDecoderBackend<av1::decoder_instructions::DecoderInstruction>>,
+    pub(crate) parser: Av1Parser,
+    pub(crate) reference_ctx: av1::reference_manager::ReferenceContext,
+    pub(crate) frame_sorter: FrameSorter<wgpu::Texture>,
+    pub(crate) stats: DecoderStatsTracker,
+    pub(crate) sei: SeiTracker,
+    pub(crate) frame_pool: Option<WgpuFramePool>,
+}
+
+impl WgpuTexturesDecoderAv1 {
+    /// The produced textures have the [`wgpu::TextureFormat::NV12`] format and can be used as a texture binding.
+    /// With [`PixelFormat::Rgba`](crate::parameters::PixelFormat::Rgba) set in
+    /// [`DecoderParameters::output_pixel_format`](crate::parameters::DecoderParameters::output_pixel_format),
+    /// frames are converted on the GPU and the textures have the
+    /// [`wgpu::TextureFormat::Rgba8Unorm`] format.
+    pub fn decode(
+        &mut self,
+        frame: EncodedInputChunk<'_>,
+    ) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
+        self.process_event(DecoderEvent::DecodeChunk(frame))
+    }
+
+    /// Flush all frames from the decoder.
+    pub fn flush(&mut self) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
+        self.process_event(DecoderEvent::Flush)
+    }
+
+    /// Resets the decoder after a discontinuity in the stream without recreating the video
+    /// session. See [`DecoderEvent::Reset`].
+    pub fn reset(&mut self) -> Result<(), VideoDecoderError> {
+        self.process_event(DecoderEvent::Reset).map(|_| ())
+    }
+
+    /// Returns decoding statistics. See [`DecoderStats`].
+    pub fn stats(&self) -> DecoderStats {
+        self.stats.stats()
+    }
+
+    /// Sets a callback called with the updated [`DecoderStats`] after every decoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(DecoderStats) + Send + 'static) {
+        self.stats.set_callback(Box::new(callback));
+    }
+
+    /// Makes the decoder write frames into textures from `frame_pool` instead of allocating a
+    /// new texture for every frame. The frames are copied into the textures on `wgpu_queue`.
+    pub fn set_frame_pool(
+        &mut self,
+        wgpu_queue: &wgpu::Queue,
+        frame_pool: impl FramePool + 'static,
+    ) {
+        self.frame_pool = Some(WgpuFramePool {
+            wgpu_queue: wgpu_queue.clone(),
+            pool: Box::new(frame_pool),
+        });
+    }
+
+    /// Process a [`DecoderEvent`]. For most use cases, using [`Self::decode`] and [`Self::flush`] is enough.
+    /// Use this only when you need more fine-grained control.
+    /// May return a sequence of decoded frames in the format described in [`Self::decode`].
+    pub fn process_event(
+        &mut self,
+        event: DecoderEvent<'_, av1::FrameUnit>,
+    ) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
+        let mut frames = match event {
+            DecoderEvent::DecodeChunk(chunk) => {
+                let frame_units = self
+                    .parser
+                    .parse_with_dts(chunk.data, chunk.pts, chunk.dts)?;
+                self.decode_frame_units(frame_units)
+            }
+            DecoderEvent::DecodeParsedFrame(frame_unit) => {
+                self.decode_frame_units(vec![frame_unit])
+            }
+            DecoderEvent::SignalFrameEnd => {
+                let frame_units = self.parser.flush()?;
+                self.decode_frame_units(frame_units)
+            }
+            DecoderEvent::SignalDataLoss => {
+                self.reference_ctx.mark_missed_frames();
+                Ok(Vec::new())
+            }
+            DecoderEvent::Flush => {
+                let frame_units = self.parser.flush()?;
+                let mut frames = self.decode_frame_units(frame_units)?;
+                frames.append(&mut self.frame_sorter.flush());
+                Ok(frames)
+            }
+            DecoderEvent::Reset => {
+                self.parser = Av1Parser::default();
+                self.sei.reset();
+                let instructions = self.reference_ctx.reset();
+                self.decoder
+                    .decode_to_wgpu_textures(&self.wgpu_device, None, instructions)?;
+                self.frame_sorter.clear();
+                Ok(Vec::new())
+            }
+        }?;
+
+        self.sei.apply(&mut frames);
+        Ok(frames)
+    }
+
+    fn decode_frame_units(
+        &mut self,
+        frame_units: Vec<av1::FrameUnit>,
+    ) -> Result<Vec<OutputFrame<wgpu::Texture>>, VideoDecoderError> {
+        let instructions = av1::decoder_instructions::compile_to_decoder_instructions(
+            &mut self.reference_ctx,
+            frame_units,
+        )?;
+        let unsorted_frames = self.decoder.decode_to_wgpu_textures(
+            &self.wgpu_device,
+            self.frame_pool.as_mut(),
+            instructions,
+        )?;
+        self.stats.register_frames(&unsorted_frames);
         let sorted_frames = self.frame_sorter.put_frames(unsorted_frames);
         Ok(sorted_frames)
     }
@@ -1,10 +1,11 @@
 use std::num::NonZeroU32;
 use std::sync::Arc;
 
-use crate::capabilities::{DecodeCapabilities, EncodeCapabilities};
+use crate::capabilities::{DecodeCapabilities, EncodeCapabilities, VideoCapabilities};
 use crate::parameters::{EncoderPreset, EncoderUsage, H264Profile, H265Profile, RateControl};
 use crate::{
-    BytesDecoder, BytesEncoderH264, BytesEncoderH265, VideoDecoderError, VideoEncoderError,
+    BytesDecoder, BytesDecoderAv1, BytesDecoderH265, BytesEncoderH264, BytesEncoderH265,
+    ExternalMemoryDecoder, VideoDecoderError, VideoEncoderError,
 };
 
 #[cfg(feature = "wgpu")]
@@ -53,6 +54,62 @@ pub enum MissedFrameHandling {
     /// When missed frames are detected, try to decode later frames that depend on them anyway.
     /// This can produce decoded frames with very visible artifacts.
     Tolerant,
+
+    /// When missed frames are detected, decode later frames that depend on them, using the
+    /// nearest available reference frame in place of every missing one. The frames decoded until
+    /// the next keyframe are marked with [`FrameMetadata::corrupted`](crate::FrameMetadata::corrupted),
+    /// so that live pipelines can keep running and decide how to present them.
+    Conceal,
+}
+
+/// How frames of interlaced streams are output by the decoder
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deinterlacing {
+    /// Output frames with both fields woven together. Motion between the fields is visible
+    /// as combing.
+    #[default]
+    Off,
+
+    /// Output the first field of every frame, with the lines of the second field interpolated
+    /// from it. Removes combing at the cost of half of the vertical resolution.
+    Bob,
+
+    /// Output frames with every line blended with its neighbours from the other field. Keeps
+    /// more detail than [`Deinterlacing::Bob`], but moving objects are blurred.
+    Blend,
+}
+
+/// Limit of memory used for decoding images by all decoders created on a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    /// Maximum size in bytes of the decoding images of all decoders on the device, including
+    /// the decoder which is allocating new images.
+    pub bytes: u64,
+
+    /// What happens when the images a decoder needs don't fit in the budget.
+    pub when_exceeded: MemoryBudgetExceededStrategy,
+}
+
+/// How a decoder behaves when its decoding images don't fit in the [`MemoryBudget`]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryBudgetExceededStrategy {
+    /// Fail with [`VideoDecoderError::MemoryBudgetExceeded`](crate::VideoDecoderError::MemoryBudgetExceeded)
+    /// right away.
+    #[default]
+    Fail,
+
+    /// Block the decoding call until other decoders on the device release enough memory,
+    /// either by being dropped or by switching to a smaller stream. Fails with
+    /// [`VideoDecoderError::MemoryBudgetExceeded`](crate::VideoDecoderError::MemoryBudgetExceeded)
+    /// if the memory is not released before the timeout. Streams that need more than the
+    /// whole budget fail right away.
+    ///
+    /// A waiting decoder keeps its current images until it gets the memory for the new ones,
+    /// so it can't release memory for other waiting decoders. When the images would only fit
+    /// after another waiting decoder released its images, the call fails right away instead of
+    /// waiting, so that two decoders growing at the same time don't block each other until
+    /// the timeout.
+    Wait { timeout: std::time::Duration },
 }
 
 /// Parameters for decoder creation
@@ -65,6 +122,90 @@ pub struct DecoderParameters {
 
     /// A hint indicating what kind of content the decoder is going to be used for.
     pub usage_flags: crate::parameters::DecoderUsage,
+
+    /// Measure how long the GPU spends decoding each frame using timestamp queries. Results
+    /// are available in [`FrameMetadata::gpu_decode_duration`](crate::FrameMetadata::gpu_decode_duration) and in
+    /// decoder stats. Adds a small overhead to every decode submission.
+    ///
+    /// **Defaults to `false`**
+    pub enable_gpu_timing: bool,
+
+    /// Orientation reported for all decoded frames. If not set, the orientation is read from the
+    /// display orientation SEI messages in the stream.
+    ///
+    /// **Defaults to `None`**
+    pub display_orientation: Option<crate::DisplayOrientation>,
+
+    /// Pixel format of frames returned by [`BytesDecoder`](crate::BytesDecoder). Conversion from
+    /// NV12 is done on the GPU before the frame is downloaded.
+    ///
+    /// Decoders that output textures support [`PixelFormat::Nv12`](crate::parameters::PixelFormat::Nv12),
+    /// which returns the decoded NV12 or P010 textures, and
+    /// [`PixelFormat::Rgba`](crate::parameters::PixelFormat::Rgba), which converts the frames into
+    /// `Rgba8Unorm` textures on the GPU.
+    ///
+    /// **Defaults to [`PixelFormat::Nv12`](crate::parameters::PixelFormat::Nv12)**
+    pub output_pixel_format: crate::parameters::PixelFormat,
+
+    /// Decode only frames consisting of intra slices (IDR and I frames) and skip all other
+    /// frames. All references are dropped before every decoded frame and frames are returned
+    /// in decode order. Useful for generating previews of long streams, e.g. thumbnails for
+    /// scrubbing.
+    ///
+    /// **Defaults to `false`**
+    pub keyframes_only: bool,
+
+    /// Deinterlacing applied on the GPU to frames of interlaced streams. Only used by H264
+    /// decoders. Deinterlacing 10-bit frames is not supported.
+    ///
+    /// **Defaults to [`Deinterlacing::Off`]**
+    pub deinterlacing: Deinterlacing,
+
+    /// Output frames of H.264 streams that don't signal their reordering limit (the
+    /// `max_num_reorder_frames` in the VUI) as soon as they are decoded, instead of buffering
+    /// them for the worst case reordering allowed by the DPB size. Useful for conferencing
+    /// inputs, which usually have no B-frames.
+    ///
+    /// If a frame turns out to be reordered, it is output late and the decoder falls back to
+    /// buffering for the rest of the stream. Streams that signal the limit are always reordered
+    /// according to it.
+    ///
+    /// **Defaults to `false`**
+    pub low_latency: bool,
+
+    /// Maximum number of pictures in the decoded picture buffer, including the current one.
+    /// Streams which need more reference pictures fail with
+    /// [`VideoDecoderError::DpbLimitExceeded`](crate::VideoDecoderError::DpbLimitExceeded) when
+    /// their sequence parameters are received, instead of allocating the buffer.
+    ///
+    /// **Defaults to `None`**, the size signaled in the stream is used
+    pub max_dpb_slots: Option<u32>,
+
+    /// Budget for the images decoders allocate for the decoded picture buffer and the decoded
+    /// frame, shared by all decoders created on the device. Sizes are estimated from the
+    /// resolution, the pixel format and the number of pictures. Frames returned by the decoders
+    /// are not included. The budget is checked before the images are allocated, so servers
+    /// decoding many streams can reject or delay the ones that don't fit instead of running out
+    /// of video memory. See [`MemoryBudgetExceededStrategy`] for what happens when a stream
+    /// doesn't fit.
+    ///
+    /// Images of decoders created without a budget are counted as well, but those decoders
+    /// never fail because of it.
+    ///
+    /// **Defaults to `None`**, no limit
+    pub memory_budget: Option<MemoryBudget>,
+
+    /// Pad the rows of frames returned by [`BytesDecoder`](crate::BytesDecoder) to the row
+    /// pitch alignment the device prefers for copies, and return the offsets and row pitches of
+    /// the planes in [`RawFrameData::planes`](crate::RawFrameData::planes). This avoids repacking
+    /// the frame on the GPU, and the planes can be passed with their strides to libyuv or FFmpeg.
+    ///
+    /// Only NV12 and P010 frames are padded, frames converted to other formats stay tightly
+    /// packed, but their layout is returned as well. The software decoder always returns
+    /// tightly packed frames without the layout.
+    ///
+    /// **Defaults to `false`**
+    pub aligned_output_rows: bool,
 }
 
 /// Things the encoder needs to know about the video
@@ -74,6 +215,12 @@ pub struct VideoParameters {
     pub height: NonZeroU32,
     /// The expected/approximate framerate of the encoded video
     pub target_framerate: Rational,
+    /// Format of the frames passed to bytes encoders. Frames in other formats than
+    /// [`PixelFormat::Nv12`](crate::parameters::PixelFormat::Nv12) are converted on the GPU
+    /// before encoding, RGBA frames with the encoder's color space and range.
+    /// [`PixelFormat::P010`](crate::parameters::PixelFormat::P010) is not supported. Texture
+    /// encoders ignore this field.
+    pub pixel_format: crate::parameters::PixelFormat,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -110,6 +257,91 @@ impl From<&h264_reader::nal::sps::SeqParameterSet> for ColorSpace {
     }
 }
 
+impl From<&crate::parser::h265::SeqParameterSet> for ColorSpace {
+    fn from(sps: &crate::parser::h265::SeqParameterSet) -> Self {
+        let Some(cd) = sps
+            .vui_parameters
+            .as_ref()
+            .and_then(|vui| vui.video_signal_type.as_ref())
+            .and_then(|vst| vst.colour_description.as_ref())
+        else {
+            return ColorSpace::Unspecified;
+        };
+
+        match (
+            cd.colour_primaries,
+            cd.transfer_characteristics,
+            cd.matrix_coeffs,
+        ) {
+            (1, 1, 1) => ColorSpace::BT709,
+            (6, 6, 6) => ColorSpace::BT601Ntsc,
+            (5, 6, 5) => ColorSpace::BT601Pal,
+            _ => ColorSpace::Unspecified,
+        }
+    }
+}
+
+impl From<&crate::parser::av1::ColorConfig> for ColorSpace {
+    fn from(color_config: &crate::parser::av1::ColorConfig) -> Self {
+        if !color_config.color_description_present_flag {
+            return ColorSpace::Unspecified;
+        }
+
+        match (
+            color_config.color_primaries,
+            color_config.transfer_characteristics,
+            color_config.matrix_coefficients,
+        ) {
+            (1, 1, 1) => ColorSpace::BT709,
+            (6, 6, 6) => ColorSpace::BT601Ntsc,
+            (5, 6, 5) => ColorSpace::BT601Pal,
+            _ => ColorSpace::Unspecified,
+        }
+    }
+}
+
+/// Resolution of the chroma samples relative to the luma samples.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChromaSubsampling {
+    /// Chroma has half the width and half the height of luma.
+    #[default]
+    Yuv420,
+    /// Chroma has half the width and the full height of luma. Used by professional H.264
+    /// sources, e.g. streams of the High 4:2:2 profile.
+    Yuv422,
+    /// Chroma has the same resolution as luma.
+    Yuv444,
+}
+
+impl ChromaSubsampling {
+    /// Horizontal and vertical ratio between the luma and chroma resolution.
+    pub(crate) fn divisors(&self) -> (u32, u32) {
+        match self {
+            ChromaSubsampling::Yuv420 => (2, 2),
+            ChromaSubsampling::Yuv422 => (2, 1),
+            ChromaSubsampling::Yuv444 => (1, 1),
+        }
+    }
+
+    /// Size of the chroma planes of a frame with the given luma size.
+    pub fn chroma_size(&self, width: u32, height: u32) -> (u32, u32) {
+        let (horizontal, vertical) = self.divisors();
+        (width.div_ceil(horizontal), height.div_ceil(vertical))
+    }
+}
+
+/// Coding type of a decoded picture, which shows what other pictures it was predicted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PictureType {
+    /// Decoded without references to other pictures.
+    I,
+    /// Predicted from at most one reference picture per block.
+    P,
+    /// Predicted from up to two reference pictures per block, which can follow it in
+    /// presentation order.
+    B,
+}
+
 /// Whether the video signal uses the full or limited range of sample values.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColorRange {
@@ -135,6 +367,304 @@ impl From<&h264_reader::nal::sps::SeqParameterSet> for ColorRange {
     }
 }
 
+impl From<&crate::parser::h265::SeqParameterSet> for ColorRange {
+    fn from(sps: &crate::parser::h265::SeqParameterSet) -> Self {
+        sps.vui_parameters
+            .as_ref()
+            .and_then(|v| v.video_signal_type.as_ref())
+            .map(|vst| {
+                if vst.video_full_range_flag {
+                    ColorRange::Full
+                } else {
+                    ColorRange::Limited
+                }
+            })
+            .unwrap_or(ColorRange::Limited)
+    }
+}
+
+impl From<&crate::parser::av1::ColorConfig> for ColorRange {
+    fn from(color_config: &crate::parser::av1::ColorConfig) -> Self {
+        if color_config.color_range {
+            ColorRange::Full
+        } else {
+            ColorRange::Limited
+        }
+    }
+}
+
+/// Color primaries of the video signal, as defined in ITU-T H.273.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorPrimaries {
+    #[default]
+    Unspecified,
+    BT709,
+    /// BT.470 System M
+    BT470M,
+    /// BT.470 System B, G and BT.601 625-line (PAL)
+    BT470BG,
+    /// SMPTE 170M and BT.601 525-line (NTSC)
+    SMPTE170M,
+    SMPTE240M,
+    /// Generic film with Illuminant C
+    Film,
+    BT2020,
+    /// SMPTE ST 428-1 (CIE 1931 XYZ)
+    SMPTE428,
+    /// SMPTE RP 431-2 (DCI-P3)
+    SMPTE431,
+    /// SMPTE EG 432-1 (Display P3)
+    SMPTE432,
+    EBU3213,
+    /// A reserved value or a value not covered by the other variants.
+    Other(u8),
+}
+
+impl From<u8> for ColorPrimaries {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::BT709,
+            2 => Self::Unspecified,
+            4 => Self::BT470M,
+            5 => Self::BT470BG,
+            6 => Self::SMPTE170M,
+            7 => Self::SMPTE240M,
+            8 => Self::Film,
+            9 => Self::BT2020,
+            10 => Self::SMPTE428,
+            11 => Self::SMPTE431,
+            12 => Self::SMPTE432,
+            22 => Self::EBU3213,
+            value => Self::Other(value),
+        }
+    }
+}
+
+/// Transfer characteristics (transfer function) of the video signal, as defined in ITU-T H.273.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransferCharacteristics {
+    #[default]
+    Unspecified,
+    BT709,
+    /// BT.470 System M, gamma 2.2
+    BT470M,
+    /// BT.470 System B and G, gamma 2.8
+    BT470BG,
+    /// SMPTE 170M and BT.601
+    SMPTE170M,
+    SMPTE240M,
+    Linear,
+    /// IEC 61966-2-1 (sRGB)
+    SRGB,
+    /// BT.2020 for 10-bit systems
+    BT2020Ten,
+    /// BT.2020 for 12-bit systems
+    BT2020Twelve,
+    /// SMPTE ST 2084 (PQ), used for HDR10
+    SMPTE2084,
+    /// ARIB STD-B67 (HLG)
+    HLG,
+    /// A reserved value or a value not covered by the other variants.
+    Other(u8),
+}
+
+impl From<u8> for TransferCharacteristics {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::BT709,
+            2 => Self::Unspecified,
+            4 => Self::BT470M,
+            5 => Self::BT470BG,
+            6 => Self::SMPTE170M,
+            7 => Self::SMPTE240M,
+            8 => Self::Linear,
+            13 => Self::SRGB,
+            14 => Self::BT2020Ten,
+            15 => Self::BT2020Twelve,
+            16 => Self::SMPTE2084,
+            18 => Self::HLG,
+            value => Self::Other(value),
+        }
+    }
+}
+
+/// Matrix coefficients used to derive luma and chroma from RGB, as defined in ITU-T H.273.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatrixCoefficients {
+    #[default]
+    Unspecified,
+    /// The samples are RGB (or XYZ) instead of YCbCr.
+    Identity,
+    BT709,
+    /// FCC Title 47
+    FCC,
+    /// BT.470 System B, G and BT.601 625-line (PAL)
+    BT470BG,
+    /// SMPTE 170M and BT.601 525-line (NTSC)
+    SMPTE170M,
+    SMPTE240M,
+    YCgCo,
+    /// BT.2020 non-constant luminance
+    BT2020NCL,
+    /// BT.2020 constant luminance
+    BT2020CL,
+    /// A reserved value or a value not covered by the other variants.
+    Other(u8),
+}
+
+impl From<u8> for MatrixCoefficients {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Identity,
+            1 => Self::BT709,
+            2 => Self::Unspecified,
+            4 => Self::FCC,
+            5 => Self::BT470BG,
+            6 => Self::SMPTE170M,
+            7 => Self::SMPTE240M,
+            8 => Self::YCgCo,
+            9 => Self::BT2020NCL,
+            10 => Self::BT2020CL,
+            value => Self::Other(value),
+        }
+    }
+}
+
+/// Color description of a decoded frame. The primaries, transfer characteristics and matrix
+/// coefficients are read from the VUI of the sequence parameter set (the color config in AV1),
+/// the mastering display from the mastering display colour volume SEI message. Unlike
+/// [`ColorSpace`], it also describes combinations used by HDR and wide gamut streams, e.g.
+/// BT.2020 with the PQ transfer function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColorInfo {
+    pub primaries: ColorPrimaries,
+    pub transfer_characteristics: TransferCharacteristics,
+    pub matrix_coefficients: MatrixCoefficients,
+    /// `None` if the stream does not contain the mastering display colour volume SEI message.
+    pub mastering_display: Option<crate::MasteringDisplayColorVolume>,
+}
+
+impl ColorInfo {
+    fn from_color_description(
+        colour_primaries: u8,
+        transfer_characteristics: u8,
+        matrix_coefficients: u8,
+    ) -> Self {
+        Self {
+            primaries: colour_primaries.into(),
+            transfer_characteristics: transfer_characteristics.into(),
+            matrix_coefficients: matrix_coefficients.into(),
+            mastering_display: None,
+        }
+    }
+}
+
+impl From<&h264_reader::nal::sps::SeqParameterSet> for ColorInfo {
+    fn from(sps: &h264_reader::nal::sps::SeqParameterSet) -> Self {
+        sps.vui_parameters
+            .as_ref()
+            .and_then(|vui| vui.video_signal_type.as_ref())
+            .and_then(|vst| vst.colour_description.as_ref())
+            .map(|cd| {
+                Self::from_color_description(
+                    cd.colour_primaries,
+                    cd.transfer_characteristics,
+                    cd.matrix_coefficients,
+                )
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl From<&crate::parser::h265::SeqParameterSet> for ColorInfo {
+    fn from(sps: &crate::parser::h265::SeqParameterSet) -> Self {
+        sps.vui_parameters
+            .as_ref()
+            .and_then(|vui| vui.video_signal_type.as_ref())
+            .and_then(|vst| vst.colour_description.as_ref())
+            .map(|cd| {
+                Self::from_color_description(
+                    cd.colour_primaries,
+                    cd.transfer_characteristics,
+                    cd.matrix_coeffs,
+                )
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl From<&crate::parser::av1::ColorConfig> for ColorInfo {
+    fn from(color_config: &crate::parser::av1::ColorConfig) -> Self {
+        if !color_config.color_description_present_flag {
+            return Self::default();
+        }
+
+        Self::from_color_description(
+            color_config.color_primaries,
+            color_config.transfer_characteristics,
+            color_config.matrix_coefficients,
+        )
+    }
+}
+
+/// Shape of the samples (pixels) of a frame, as the ratio of their width to their height, read
+/// from the VUI of the sequence parameter set. Frames with a non-square sample aspect ratio have
+/// to be stretched horizontally by `width / height` to be displayed correctly, e.g. 1440x1080
+/// frames with a 4:3 sample aspect ratio are displayed as 1920x1080.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleAspectRatio {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl SampleAspectRatio {
+    pub(crate) fn from_h264_sps(sps: &h264_reader::nal::sps::SeqParameterSet) -> Option<Self> {
+        let (width, height) = sps
+            .vui_parameters
+            .as_ref()?
+            .aspect_ratio_info
+            .as_ref()?
+            .get()?;
+        Self::new(width, height)
+    }
+
+    pub(crate) fn from_h265_sps(sps: &crate::parser::h265::SeqParameterSet) -> Option<Self> {
+        const EXTENDED_SAR: u8 = 255;
+
+        let (aspect_ratio_idc, sar_width, sar_height) = sps.vui_parameters.as_ref()?.aspect_ratio?;
+        // Table E-1 of the H.265 spec
+        let (width, height) = match aspect_ratio_idc {
+            1 => (1, 1),
+            2 => (12, 11),
+            3 => (10, 11),
+            4 => (16, 11),
+            5 => (40, 33),
+            6 => (24, 11),
+            7 => (20, 11),
+            8 => (32, 11),
+            9 => (80, 33),
+            10 => (18, 11),
+            11 => (15, 11),
+            12 => (64, 33),
+            13 => (160, 99),
+            14 => (4, 3),
+            15 => (3, 2),
+            16 => (2, 1),
+            EXTENDED_SAR => (sar_width, sar_height),
+            _ => return None,
+        };
+        Self::new(width, height)
+    }
+
+    /// `None` for the unspecified ratio with a zero component.
+    fn new(width: u16, height: u16) -> Option<Self> {
+        match (width, height) {
+            (0, _) | (_, 0) => None,
+            (width, height) => Some(Self { width, height }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct CodecColorDescription {
     pub colour_primaries: u8,
@@ -181,6 +711,35 @@ pub struct EncoderOutputParameters<P> {
     /// Max number of references a P-frame can have. This value will be clamped to the max number the
     /// GPU supports. If [`None`], this value will be set to the max value supported by the device.
     pub max_references: Option<NonZeroU32>,
+    /// Number of B-frames between consecutive I/P-frames. B-frames improve quality per bitrate,
+    /// but the encoder has to buffer this many frames before it can emit them, so it adds latency.
+    /// Encoded chunks are returned in decoding order. Not allowed in the H.264 Baseline profile,
+    /// and requires `max_references` of at least 2. If [`None`], defaults to 0.
+    pub consecutive_b_frames: Option<u32>,
+    /// Number of slices every frame is split into. Each slice is written as a separate NAL unit,
+    /// so RTP packetizers can split frames on NAL boundaries and a lost packet damages only a part
+    /// of the frame. More slices reduce the quality per bitrate. Has to be at most
+    /// [`EncodeProfileCapabilities::max_slice_count`](crate::capabilities::EncodeProfileCapabilities::max_slice_count).
+    /// If [`None`], defaults to 1.
+    pub slice_count: Option<NonZeroU32>,
+    /// Refresh the picture gradually instead of with periodic IDR frames, which keeps the size of
+    /// the encoded frames even and avoids the latency spikes of large IDR frames on
+    /// bandwidth-limited links. Frames are split into this many slices, stacked from top to
+    /// bottom, and one slice of every P-frame is coded as an intra slice, so the whole picture
+    /// is refreshed every `intra_refresh_period` frames. Only the first frame and frames
+    /// requested with `force_idr` or `request_keyframe` are IDR frames, unless `idr_period` is
+    /// set explicitly. Cannot be used with B-frames, and `slice_count` has to be unset or equal
+    /// to this value. Support is reported in
+    /// [`EncodeProfileCapabilities::intra_refresh_supported`](crate::capabilities::EncodeProfileCapabilities::intra_refresh_supported).
+    /// If [`None`], intra refresh is disabled.
+    pub intra_refresh_period: Option<NonZeroU32>,
+    /// Largest resolution the encoder can be switched to with `set_resolution` on the encoder,
+    /// e.g. to downscale an adaptive output under load. The video session and the reference
+    /// pictures are allocated for this size. If [`None`], defaults to the input resolution, and
+    /// the resolution can only be lowered.
+    pub max_width: Option<NonZeroU32>,
+    /// See [`Self::max_width`]. If [`None`], defaults to the input height.
+    pub max_height: Option<NonZeroU32>,
     /// The profile must be supported by the device
     pub profile: P,
     /// A hint indicating what the encoder should prioritize.
@@ -191,12 +750,22 @@ pub struct EncoderOutputParameters<P> {
     /// If `false`, SPS/PPS can be retrieved separately using methods defined on the encoder.
     /// If [`None`], defaults to `true`.
     pub inline_stream_params: Option<bool>,
+    /// Additionally prepend the stream parameters to the first frame encoded after this much
+    /// time since they were last written, measured with the input framerate. Lets late joiners of
+    /// raw Annex B outputs (e.g. WHIP or RTMP) start decoding without waiting for a parameter
+    /// set sent with an IDR frame, which matters for long IDR periods. Works independently of
+    /// `inline_stream_params`. If [`None`], the stream parameters are only written with IDR
+    /// frames.
+    pub stream_params_interval: Option<std::time::Duration>,
     /// Color space of the encoded output.
     /// If [`None`], defaults to [`ColorSpace::Unspecified`].
     pub color_space: Option<ColorSpace>,
     /// Color range of the encoded output.
     /// If [`None`], defaults to [`ColorRange::Limited`].
     pub color_range: Option<ColorRange>,
+    /// Measure the time the GPU spends encoding every frame with timestamp queries. The results
+    /// are available in the encoder stats. Adds a small overhead to every encode submission.
+    pub enable_gpu_timing: bool,
 }
 
 /// Parameters for H.264 encoder creation
@@ -219,6 +788,21 @@ pub(crate) trait CoreVideoDeviceBackend: Send + Sync {
         parameters: DecoderParameters,
     ) -> Result<BytesDecoder, VideoDecoderError>;
 
+    fn create_bytes_decoder_h265(
+        self: Arc<Self>,
+        parameters: DecoderParameters,
+    ) -> Result<BytesDecoderH265, VideoDecoderError>;
+
+    fn create_bytes_decoder_av1(
+        self: Arc<Self>,
+        parameters: DecoderParameters,
+    ) -> Result<BytesDecoderAv1, VideoDecoderError>;
+
+    fn create_external_memory_decoder_h264(
+        self: Arc<Self>,
+        parameters: DecoderParameters,
+    ) -> Result<ExternalMemoryDecoder, VideoDecoderError>;
+
     fn create_bytes_encoder_h264(
         self: Arc<Self>,
         parameters: EncoderParametersH264,
@@ -238,6 +822,8 @@ pub(crate) trait CoreVideoDeviceBackend: Send + Sync {
     fn decode_capabilities(&self) -> DecodeCapabilities;
 
     fn encode_capabilities(&self) -> EncodeCapabilities;
+
+    fn video_capabilities(&self) -> VideoCapabilities;
 }
 
 #[cfg(feature = "wgpu")]
@@ -34,6 +34,18 @@ pub(crate) trait WgpuVideoDeviceBackend: Send + Sync {
         parameters: DecoderParameters,
     ) -> Result<crate::WgpuTexturesDecoder, VideoDecoderError>;
 
+    fn create_wgpu_textures_decoder_h265(
+        self: Arc<Self>,
+        wgpu_device: wgpu::Device,
+        parameters: DecoderParameters,
+    ) -> Result<crate::WgpuTexturesDecoderH265, VideoDecoderError>;
+
+    fn create_wgpu_textures_decoder_av1(
+        self: Arc<Self>,
+        wgpu_device: wgpu::Device,
+        parameters: DecoderParameters,
+    ) -> Result<crate::WgpuTexturesDecoderAv1, VideoDecoderError>;
+
     fn create_wgpu_textures_encoder_h264(
         self: Arc<Self>,
         wgpu_device: wgpu::Device,
@@ -1,8 +1,16 @@
-use crate::{EncodedOutputChunk, InputFrame, RawFrameData, VideoBackendError};
+use std::num::NonZeroU32;
 
+use crate::{
+    EncodedOutputChunk, InputFrame, RawFrameData, VideoBackendError,
+    encoders::stats::EncoderStatsCallback, parameters::RateControl,
+};
+
+pub(crate) mod stats;
 #[cfg(feature = "wgpu")]
 mod wgpu_api;
 #[cfg(feature = "wgpu")]
+pub use stats::EncoderStats;
+#[cfg(feature = "wgpu")]
 pub use wgpu_api::*;
 
 pub(crate) trait VideoEncoderBackend: Send {
@@ -10,7 +18,23 @@ pub(crate) trait VideoEncoderBackend: Send {
         &mut self,
         frame: &InputFrame<RawFrameData>,
         force_idr: bool,
-    ) -> Result<EncodedOutputChunk<Vec<u8>>, VideoEncoderError>;
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError>;
+
+    fn flush(&mut self) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError>;
+
+    fn update_rate_control(&mut self, rate_control: RateControl) -> Result<(), VideoEncoderError>;
+
+    fn request_keyframe(&mut self);
+
+    fn set_resolution(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError>;
+
+    fn stats(&self) -> EncoderStats;
+
+    fn set_stats_callback(&mut self, callback: EncoderStatsCallback);
 }
 
 pub(crate) trait VideoEncoderParametersInfoH264 {
@@ -36,13 +60,16 @@ pub(crate) trait VideoEncoderBackendH265:
 }
 impl<E: VideoEncoderBackend + VideoEncoderParametersInfoH265> VideoEncoderBackendH265 for E {}
 
-/// An H.264 (AVC) encoder that takes input frames as [`Vec<u8>`] with raw pixel data (in NV12)
+/// An H.264 (AVC) encoder that takes input frames as [`Vec<u8>`] with raw pixel data (in the
+/// [`VideoParameters::pixel_format`](crate::parameters::VideoParameters::pixel_format) format)
 pub struct BytesEncoderH264 {
     pub(crate) encoder: Box<dyn VideoEncoderBackendH264>,
 }
 
 impl BytesEncoderH264 {
-    /// The result is a chunk of H264 bitstream.
+    /// The result is a list of chunks of H264 bitstream, in decoding order. When B-frames are
+    /// enabled, frames are buffered inside the encoder, so a call can return no chunks or
+    /// chunks for multiple frames.
     ///
     /// If the `force_keyframe` option is set to `true`, the encoder will encode this frame as a
     /// [keyframe](https://en.wikipedia.org/wiki/Video_compression_picture_types#Intra-coded_(I)_frames/slices_(key_frames)).
@@ -51,10 +78,61 @@ impl BytesEncoderH264 {
         &mut self,
         frame: &InputFrame<RawFrameData>,
         force_keyframe: bool,
-    ) -> Result<EncodedOutputChunk<Vec<u8>>, VideoEncoderError> {
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
         self.encoder.encode_bytes(frame, force_keyframe)
     }
 
+    /// Encode all frames buffered in the encoder. Call this after the last frame, otherwise
+    /// the buffered frames will be lost.
+    pub fn flush(&mut self) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        self.encoder.flush()
+    }
+
+    /// Change the rate control settings, e.g. in response to bandwidth estimation. They are
+    /// applied starting with the next encoded frame, without interrupting the stream.
+    ///
+    /// Switching to [`RateControl::EncoderDefault`] is not supported.
+    pub fn update_rate_control(
+        &mut self,
+        rate_control: RateControl,
+    ) -> Result<(), VideoEncoderError> {
+        self.encoder.update_rate_control(rate_control)
+    }
+
+    /// Make the next encoded frame a keyframe, e.g. in response to a picture loss indication
+    /// from the receiver. The keyframe is preceded by the stream parameters (SPS, PPS), even
+    /// if `inline_stream_params` is `false`.
+    pub fn request_keyframe(&mut self) {
+        self.encoder.request_keyframe()
+    }
+
+    /// Change the resolution of the encoded video, e.g. to downscale under load, without
+    /// recreating the encoder. The resolution can be at most
+    /// [`EncoderOutputParameters::max_width`](crate::parameters::EncoderOutputParameters::max_width)
+    /// x [`EncoderOutputParameters::max_height`](crate::parameters::EncoderOutputParameters::max_height),
+    /// and the following frames have to be of the new size.
+    ///
+    /// The next frame is encoded as a keyframe preceded by the new stream parameters. Frames
+    /// buffered in the encoder are encoded with the previous resolution and returned.
+    pub fn set_resolution(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        self.encoder.set_resolution(width, height)
+    }
+
+    /// Returns encoding statistics. See [`EncoderStats`].
+    pub fn stats(&self) -> EncoderStats {
+        self.encoder.stats()
+    }
+
+    /// Sets a callback called with the updated [`EncoderStats`] after every encoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(EncoderStats) + Send + 'static) {
+        self.encoder.set_stats_callback(Box::new(callback));
+    }
+
     /// Retrieve encoded SPS NAL units from the video session parameters, in Annex B.
     ///
     /// Useful when `inline_stream_params` is `false` and the parameters need to be
@@ -72,13 +150,16 @@ impl BytesEncoderH264 {
     }
 }
 
-/// An H.265 (HEVC) encoder that takes input frames as [`Vec<u8>`] with raw pixel data (in NV12)
+/// An H.265 (HEVC) encoder that takes input frames as [`Vec<u8>`] with raw pixel data (in the
+/// [`VideoParameters::pixel_format`](crate::parameters::VideoParameters::pixel_format) format)
 pub struct BytesEncoderH265 {
     pub(crate) encoder: Box<dyn VideoEncoderBackendH265>,
 }
 
 impl BytesEncoderH265 {
-    /// The result is a chunk of H265 bitstream.
+    /// The result is a list of chunks of H265 bitstream, in decoding order. When B-frames are
+    /// enabled, frames are buffered inside the encoder, so a call can return no chunks or
+    /// chunks for multiple frames.
     ///
     /// If the `force_keyframe` option is set to `true`, the encoder will encode this frame as a
     /// [keyframe](https://en.wikipedia.org/wiki/Video_compression_picture_types#Intra-coded_(I)_frames/slices_(key_frames)).
@@ -87,10 +168,61 @@ impl BytesEncoderH265 {
         &mut self,
         frame: &InputFrame<RawFrameData>,
         force_keyframe: bool,
-    ) -> Result<EncodedOutputChunk<Vec<u8>>, VideoEncoderError> {
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
         self.encoder.encode_bytes(frame, force_keyframe)
     }
 
+    /// Encode all frames buffered in the encoder. Call this after the last frame, otherwise
+    /// the buffered frames will be lost.
+    pub fn flush(&mut self) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        self.encoder.flush()
+    }
+
+    /// Change the rate control settings, e.g. in response to bandwidth estimation. They are
+    /// applied starting with the next encoded frame, without interrupting the stream.
+    ///
+    /// Switching to [`RateControl::EncoderDefault`] is not supported.
+    pub fn update_rate_control(
+        &mut self,
+        rate_control: RateControl,
+    ) -> Result<(), VideoEncoderError> {
+        self.encoder.update_rate_control(rate_control)
+    }
+
+    /// Make the next encoded frame a keyframe, e.g. in response to a picture loss indication
+    /// from the receiver. The keyframe is preceded by the stream parameters (VPS, SPS, PPS), even
+    /// if `inline_stream_params` is `false`.
+    pub fn request_keyframe(&mut self) {
+        self.encoder.request_keyframe()
+    }
+
+    /// Change the resolution of the encoded video, e.g. to downscale under load, without
+    /// recreating the encoder. The resolution can be at most
+    /// [`EncoderOutputParameters::max_width`](crate::parameters::EncoderOutputParameters::max_width)
+    /// x [`EncoderOutputParameters::max_height`](crate::parameters::EncoderOutputParameters::max_height),
+    /// and the following frames have to be of the new size.
+    ///
+    /// The next frame is encoded as a keyframe preceded by the new stream parameters. Frames
+    /// buffered in the encoder are encoded with the previous resolution and returned.
+    pub fn set_resolution(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        self.encoder.set_resolution(width, height)
+    }
+
+    /// Returns encoding statistics. See [`EncoderStats`].
+    pub fn stats(&self) -> EncoderStats {
+        self.encoder.stats()
+    }
+
+    /// Sets a callback called with the updated [`EncoderStats`] after every encoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(EncoderStats) + Send + 'static) {
+        self.encoder.set_stats_callback(Box::new(callback));
+    }
+
     /// Retrieve encoded VPS NAL units from the video session parameters, in Annex B.
     ///
     /// Useful when `inline_stream_params` is `false` and the parameters need to be
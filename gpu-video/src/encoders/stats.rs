@@ -0,0 +1,81 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Window over which [`EncoderStats::gpu_utilization`] is calculated.
+const UTILIZATION_WINDOW: Duration = Duration::from_secs(1);
+
+/// Encoding statistics. GPU timing is only available if the encoder was created with
+/// [`EncoderOutputParameters::enable_gpu_timing`](crate::parameters::EncoderOutputParameters::enable_gpu_timing)
+/// and the device supports timestamp queries on the encode queue.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EncoderStats {
+    /// Number of frames encoded since the encoder was created.
+    pub encoded_frames: u64,
+
+    /// Time the GPU spent encoding the last frame.
+    pub last_gpu_encode_duration: Option<Duration>,
+
+    /// Fraction of the last second the GPU spent encoding frames for this encoder, in the
+    /// range [0, 1].
+    pub gpu_utilization: Option<f32>,
+}
+
+pub(crate) type EncoderStatsCallback = Box<dyn FnMut(EncoderStats) + Send>;
+
+pub(crate) struct EncoderStatsTracker {
+    encoded_frames: u64,
+    last_gpu_encode_duration: Option<Duration>,
+    gpu_encode_durations: VecDeque<(Instant, Duration)>,
+    callback: Option<EncoderStatsCallback>,
+}
+
+impl EncoderStatsTracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            encoded_frames: 0,
+            last_gpu_encode_duration: None,
+            gpu_encode_durations: VecDeque::new(),
+            callback: None,
+        }
+    }
+
+    pub(crate) fn set_callback(&mut self, callback: EncoderStatsCallback) {
+        self.callback = Some(callback);
+    }
+
+    pub(crate) fn register_frame(&mut self, gpu_encode_duration: Option<Duration>) {
+        let now = Instant::now();
+        self.encoded_frames += 1;
+        if let Some(duration) = gpu_encode_duration {
+            self.last_gpu_encode_duration = Some(duration);
+            self.gpu_encode_durations.push_back((now, duration));
+        }
+
+        while let Some((timestamp, _)) = self.gpu_encode_durations.front() {
+            if now.duration_since(*timestamp) <= UTILIZATION_WINDOW {
+                break;
+            }
+            self.gpu_encode_durations.pop_front();
+        }
+
+        let stats = self.stats();
+        if let Some(callback) = self.callback.as_mut() {
+            callback(stats);
+        }
+    }
+
+    pub(crate) fn stats(&self) -> EncoderStats {
+        let gpu_utilization = self.last_gpu_encode_duration.map(|_| {
+            let busy: Duration = self.gpu_encode_durations.iter().map(|(_, d)| *d).sum();
+            (busy.as_secs_f32() / UTILIZATION_WINDOW.as_secs_f32()).min(1.0)
+        });
+
+        EncoderStats {
+            encoded_frames: self.encoded_frames,
+            last_gpu_encode_duration: self.last_gpu_encode_duration,
+            gpu_utilization,
+        }
+    }
+}
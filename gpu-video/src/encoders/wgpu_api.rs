@@ -1,6 +1,14 @@
+use std::num::NonZeroU32;
+
 use crate::{
-    EncodedOutputChunk, InputFrame, VideoEncoderError,
-    encoders::{VideoEncoderParametersInfoH264, VideoEncoderParametersInfoH265},
+    EncodedOutputChunk, InputFrame, VideoEncoderError, WgpuConverterInitError,
+    WgpuRgbaToNv12Converter,
+    device::{ColorRange, ColorSpace},
+    encoders::{
+        EncoderStats, VideoEncoderParametersInfoH264, VideoEncoderParametersInfoH265,
+        stats::EncoderStatsCallback,
+    },
+    parameters::{RateControl, WgpuConverterParameters},
 };
 
 pub(crate) trait WgpuVideoEncoderBackend: Send {
@@ -10,7 +18,23 @@ pub(crate) trait WgpuVideoEncoderBackend: Send {
         wgpu_queue: &wgpu::Queue,
         frame: InputFrame<wgpu::Texture>,
         force_idr: bool,
-    ) -> Result<EncodedOutputChunk<Vec<u8>>, VideoEncoderError>;
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError>;
+
+    fn flush(&mut self) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError>;
+
+    fn update_rate_control(&mut self, rate_control: RateControl) -> Result<(), VideoEncoderError>;
+
+    fn request_keyframe(&mut self);
+
+    fn set_resolution(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError>;
+
+    fn stats(&self) -> EncoderStats;
+
+    fn set_stats_callback(&mut self, callback: EncoderStatsCallback);
 }
 
 pub(crate) trait WgpuVideoEncoderBackendH264:
@@ -31,15 +55,135 @@ impl<E: WgpuVideoEncoderBackend + VideoEncoderParametersInfoH265> WgpuVideoEncod
 {
 }
 
-/// An H.264 (AVC) encoder that takes input frames as [`wgpu::Texture`]s (in [`wgpu::TextureFormat::NV12`])
+/// Converts RGBA input frames into an NV12 texture, which is then copied into the encoder. The
+/// texture is reused for all frames, the wgpu queue orders the conversion of a frame after the
+/// copy of the previous one.
+pub(crate) struct RgbaInputConverter {
+    converter: WgpuRgbaToNv12Converter,
+    nv12_texture: Option<Nv12Texture>,
+}
+
+struct Nv12Texture {
+    texture: wgpu::Texture,
+    y_plane_view: wgpu::TextureView,
+    uv_plane_view: wgpu::TextureView,
+}
+
+impl Nv12Texture {
+    fn new(wgpu_device: &wgpu::Device, size: wgpu::Extent3d) -> Self {
+        let texture = wgpu_device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("gpu-video encoder rgba input conversion"),
+            format: wgpu::TextureFormat::NV12,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            dimension: wgpu::TextureDimension::D2,
+            sample_count: 1,
+            view_formats: &[],
+            mip_level_count: 1,
+            size,
+        });
+        let y_plane_view = texture.create_view(&wgpu::TextureViewDescriptor {
+            aspect: wgpu::TextureAspect::Plane0,
+            ..Default::default()
+        });
+        let uv_plane_view = texture.create_view(&wgpu::TextureViewDescriptor {
+            aspect: wgpu::TextureAspect::Plane1,
+            ..Default::default()
+        });
+
+        Self {
+            texture,
+            y_plane_view,
+            uv_plane_view,
+        }
+    }
+}
+
+impl RgbaInputConverter {
+    fn new(wgpu_device: &wgpu::Device) -> Result<Self, WgpuConverterInitError> {
+        let converter = WgpuRgbaToNv12Converter::new(
+            wgpu_device,
+            WgpuConverterParameters {
+                color_space: ColorSpace::BT709,
+                color_range: ColorRange::Limited,
+            },
+        )?;
+
+        Ok(Self {
+            converter,
+            nv12_texture: None,
+        })
+    }
+
+    /// Returns NV12 frames unchanged. RGBA frames are converted, other formats are passed on and
+    /// rejected by the encoder.
+    pub(crate) fn convert_input(
+        converter: &mut Option<Self>,
+        wgpu_device: &wgpu::Device,
+        wgpu_queue: &wgpu::Queue,
+        frame: InputFrame<wgpu::Texture>,
+    ) -> Result<InputFrame<wgpu::Texture>, WgpuTextureEncoderError> {
+        let texture = &frame.data;
+        if !matches!(
+            texture.format(),
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb
+        ) {
+            return Ok(frame);
+        }
+        if !texture
+            .usage()
+            .contains(wgpu::TextureUsages::TEXTURE_BINDING)
+        {
+            return Err(WgpuTextureEncoderError::NoTextureBindingUsage(
+                texture.usage(),
+            ));
+        }
+
+        let converter = match converter {
+            Some(converter) => converter,
+            None => converter.insert(Self::new(wgpu_device)?),
+        };
+        let nv12_texture = match &mut converter.nv12_texture {
+            Some(nv12_texture) if nv12_texture.texture.size() == texture.size() => nv12_texture,
+            nv12_texture => nv12_texture.insert(Nv12Texture::new(wgpu_device, texture.size())),
+        };
+
+        let rgba_bind_group = converter.converter.create_input_bind_group(texture);
+        let mut command_encoder = wgpu_device.create_command_encoder(&Default::default());
+        converter.converter.convert(
+            &mut command_encoder,
+            &rgba_bind_group,
+            &nv12_texture.y_plane_view,
+            &nv12_texture.uv_plane_view,
+        );
+        wgpu_queue.submit([command_encoder.finish()]);
+
+        Ok(InputFrame {
+            data: nv12_texture.texture.clone(),
+            pts: frame.pts,
+            qp: frame.qp,
+        })
+    }
+}
+
+/// An H.264 (AVC) encoder that takes input frames as [`wgpu::Texture`]s (in [`wgpu::TextureFormat::NV12`] or RGBA)
 pub struct WgpuTexturesEncoderH264 {
     pub(crate) wgpu_device: wgpu::Device,
     pub(crate) wgpu_queue: wgpu::Queue,
     pub(crate) encoder: Box<dyn WgpuVideoEncoderBackendH264>,
+    /// Created with the first RGBA frame.
+    pub(crate) rgba_input_converter: Option<RgbaInputConverter>,
 }
 
 impl WgpuTexturesEncoderH264 {
-    /// The result is a chunk of H264 bitstream.
+    /// The result is a list of chunks of H264 bitstream, in decoding order. When B-frames are
+    /// enabled, frames are buffered inside the encoder, so a call can return no chunks or
+    /// chunks for multiple frames.
+    ///
+    /// The frame can be an [`wgpu::TextureFormat::NV12`] texture with
+    /// [`wgpu::TextureUsages::COPY_SRC`], or an [`wgpu::TextureFormat::Rgba8Unorm`] or
+    /// [`wgpu::TextureFormat::Rgba8UnormSrgb`] texture with
+    /// [`wgpu::TextureUsages::TEXTURE_BINDING`], which is converted to NV12 (BT.709, limited
+    /// range) on the GPU before encoding.
     ///
     /// If the `force_keyframe` option is set to `true`, the encoder will encode this frame as a
     /// [keyframe](https://en.wikipedia.org/wiki/Video_compression_picture_types#Intra-coded_(I)_frames/slices_(key_frames)).
@@ -48,11 +192,68 @@ impl WgpuTexturesEncoderH264 {
         &mut self,
         frame: InputFrame<wgpu::Texture>,
         force_keyframe: bool,
-    ) -> Result<EncodedOutputChunk<Vec<u8>>, VideoEncoderError> {
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        let frame = RgbaInputConverter::convert_input(
+            &mut self.rgba_input_converter,
+            &self.wgpu_device,
+            &self.wgpu_queue,
+            frame,
+        )?;
         self.encoder
             .encode_texture(&self.wgpu_device, &self.wgpu_queue, frame, force_keyframe)
     }
 
+    /// Encode all frames buffered in the encoder. Call this after the last frame, otherwise
+    /// the buffered frames will be lost.
+    pub fn flush(&mut self) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        self.encoder.flush()
+    }
+
+    /// Change the rate control settings, e.g. in response to bandwidth estimation. They are
+    /// applied starting with the next encoded frame, without interrupting the stream.
+    ///
+    /// Switching to [`RateControl::EncoderDefault`] is not supported.
+    pub fn update_rate_control(
+        &mut self,
+        rate_control: RateControl,
+    ) -> Result<(), VideoEncoderError> {
+        self.encoder.update_rate_control(rate_control)
+    }
+
+    /// Make the next encoded frame a keyframe, e.g. in response to a picture loss indication
+    /// from the receiver. The keyframe is preceded by the stream parameters (SPS, PPS), even
+    /// if `inline_stream_params` is `false`.
+    pub fn request_keyframe(&mut self) {
+        self.encoder.request_keyframe()
+    }
+
+    /// Change the resolution of the encoded video, e.g. to downscale under load, without
+    /// recreating the encoder. The resolution can be at most
+    /// [`EncoderOutputParameters::max_width`](crate::parameters::EncoderOutputParameters::max_width)
+    /// x [`EncoderOutputParameters::max_height`](crate::parameters::EncoderOutputParameters::max_height),
+    /// and the following frames have to be of the new size.
+    ///
+    /// The next frame is encoded as a keyframe preceded by the new stream parameters. Frames
+    /// buffered in the encoder are encoded with the previous resolution and returned.
+    pub fn set_resolution(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        self.encoder.set_resolution(width, height)
+    }
+
+    /// Returns encoding statistics. See [`EncoderStats`].
+    pub fn stats(&self) -> EncoderStats {
+        self.encoder.stats()
+    }
+
+    /// Sets a callback called with the updated [`EncoderStats`] after every encoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(EncoderStats) + Send + 'static) {
+        self.encoder.set_stats_callback(Box::new(callback));
+    }
+
     /// Retrieve encoded SPS NAL units from the video session parameters, in Annex B.
     ///
     /// Useful when `inline_stream_params` is `false` and the parameters need to be
@@ -70,15 +271,25 @@ impl WgpuTexturesEncoderH264 {
     }
 }
 
-/// An H.265 (HEVC) encoder that takes input frames as [`wgpu::Texture`]s (in [`wgpu::TextureFormat::NV12`])
+/// An H.265 (HEVC) encoder that takes input frames as [`wgpu::Texture`]s (in [`wgpu::TextureFormat::NV12`] or RGBA)
 pub struct WgpuTexturesEncoderH265 {
     pub(crate) wgpu_device: wgpu::Device,
     pub(crate) wgpu_queue: wgpu::Queue,
     pub(crate) encoder: Box<dyn WgpuVideoEncoderBackendH265>,
+    /// Created with the first RGBA frame.
+    pub(crate) rgba_input_converter: Option<RgbaInputConverter>,
 }
 
 impl WgpuTexturesEncoderH265 {
-    /// The result is a chunk of H265 bitstream.
+    /// The result is a list of chunks of H265 bitstream, in decoding order. When B-frames are
+    /// enabled, frames are buffered inside the encoder, so a call can return no chunks or
+    /// chunks for multiple frames.
+    ///
+    /// The frame can be an [`wgpu::TextureFormat::NV12`] texture with
+    /// [`wgpu::TextureUsages::COPY_SRC`], or an [`wgpu::TextureFormat::Rgba8Unorm`] or
+    /// [`wgpu::TextureFormat::Rgba8UnormSrgb`] texture with
+    /// [`wgpu::TextureUsages::TEXTURE_BINDING`], which is converted to NV12 (BT.709, limited
+    /// range) on the GPU before encoding.
     ///
     /// If the `force_keyframe` option is set to `true`, the encoder will encode this frame as a
     /// [keyframe](https://en.wikipedia.org/wiki/Video_compression_picture_types#Intra-coded_(I)_frames/slices_(key_frames)).
@@ -87,11 +298,68 @@ impl WgpuTexturesEncoderH265 {
         &mut self,
         frame: InputFrame<wgpu::Texture>,
         force_keyframe: bool,
-    ) -> Result<EncodedOutputChunk<Vec<u8>>, VideoEncoderError> {
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        let frame = RgbaInputConverter::convert_input(
+            &mut self.rgba_input_converter,
+            &self.wgpu_device,
+            &self.wgpu_queue,
+            frame,
+        )?;
         self.encoder
             .encode_texture(&self.wgpu_device, &self.wgpu_queue, frame, force_keyframe)
     }
 
+    /// Encode all frames buffered in the encoder. Call this after the last frame, otherwise
+    /// the buffered frames will be lost.
+    pub fn flush(&mut self) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        self.encoder.flush()
+    }
+
+    /// Change the rate control settings, e.g. in response to bandwidth estimation. They are
+    /// applied starting with the next encoded frame, without interrupting the stream.
+    ///
+    /// Switching to [`RateControl::EncoderDefault`] is not supported.
+    pub fn update_rate_control(
+        &mut self,
+        rate_control: RateControl,
+    ) -> Result<(), VideoEncoderError> {
+        self.encoder.update_rate_control(rate_control)
+    }
+
+    /// Make the next encoded frame a keyframe, e.g. in response to a picture loss indication
+    /// from the receiver. The keyframe is preceded by the stream parameters (VPS, SPS, PPS), even
+    /// if `inline_stream_params` is `false`.
+    pub fn request_keyframe(&mut self) {
+        self.encoder.request_keyframe()
+    }
+
+    /// Change the resolution of the encoded video, e.g. to downscale under load, without
+    /// recreating the encoder. The resolution can be at most
+    /// [`EncoderOutputParameters::max_width`](crate::parameters::EncoderOutputParameters::max_width)
+    /// x [`EncoderOutputParameters::max_height`](crate::parameters::EncoderOutputParameters::max_height),
+    /// and the following frames have to be of the new size.
+    ///
+    /// The next frame is encoded as a keyframe preceded by the new stream parameters. Frames
+    /// buffered in the encoder are encoded with the previous resolution and returned.
+    pub fn set_resolution(
+        &mut self,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Vec<EncodedOutputChunk<Vec<u8>>>, VideoEncoderError> {
+        self.encoder.set_resolution(width, height)
+    }
+
+    /// Returns encoding statistics. See [`EncoderStats`].
+    pub fn stats(&self) -> EncoderStats {
+        self.encoder.stats()
+    }
+
+    /// Sets a callback called with the updated [`EncoderStats`] after every encoded frame, e.g. to
+    /// collect the GPU time of each frame.
+    pub fn set_stats_callback(&mut self, callback: impl FnMut(EncoderStats) + Send + 'static) {
+        self.encoder.set_stats_callback(Box::new(callback));
+    }
+
     /// Retrieve encoded VPS NAL units from the video session parameters, in Annex B.
     ///
     /// Useful when `inline_stream_params` is `false` and the parameters need to be
@@ -119,9 +387,17 @@ impl WgpuTexturesEncoderH265 {
 
 #[derive(Debug, thiserror::Error)]
 pub enum WgpuTextureEncoderError {
-    #[error("The supplied texture's format is {0:?}, when it should be NV12")]
+    #[error("The supplied texture's format is {0:?}, when it should be NV12 or RGBA")]
     NotNV12Texture(wgpu::TextureFormat),
 
+    #[error(
+        "The supplied RGBA texture does not have TEXTURE_BINDING usage. Texture's usages: {0:?}"
+    )]
+    NoTextureBindingUsage(wgpu::TextureUsages),
+
+    #[error("Failed to create the RGBA to NV12 converter: {0}")]
+    RgbaConverterInitError(#[from] WgpuConverterInitError),
+
     #[error("The supplied texture does not have COPY_SRC usage. Texture's usages: {0:?}")]
     NoCopySrcTextureUsage(wgpu::TextureUsages),
 
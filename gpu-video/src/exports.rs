@@ -1,8 +1,10 @@
 pub mod parameters {
     pub use crate::adapter::VideoAdapterDescriptor;
     pub use crate::device::{
-        ColorRange, ColorSpace, DecoderParameters, EncoderOutputParameters, EncoderParametersH264,
-        EncoderParametersH265, MissedFrameHandling, Rational, VideoDeviceDescriptor,
+        ChromaSubsampling, ColorInfo, ColorPrimaries, ColorRange, ColorSpace, DecoderParameters,
+        Deinterlacing, EncoderOutputParameters, EncoderParametersH264, EncoderParametersH265,
+        MatrixCoefficients, MemoryBudget, MemoryBudgetExceededStrategy, MissedFrameHandling,
+        PictureType, Rational, SampleAspectRatio, TransferCharacteristics, VideoDeviceDescriptor,
         VideoParameters,
     };
     pub use crate::instance::VideoInstanceDescriptor;
@@ -15,7 +17,7 @@ pub mod parameters {
     };
 
     #[cfg(feature = "wgpu")]
-    pub use crate::wgpu_helpers::WgpuConverterParameters;
+    pub use crate::wgpu_helpers::{RgbaTextureFormat, WgpuConverterParameters};
 
     /// The rate control algorithm to be used by the encoder.
     ///
@@ -23,7 +25,7 @@ pub mod parameters {
     /// specifying `Disabled`.
     ///
     /// For most use cases, `Vbr` is the correct option
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
     pub enum RateControl {
         /// Use the default setting of the encoder implementation.
         EncoderDefault,
@@ -48,6 +50,11 @@ pub mod parameters {
         /// Rate control is turned off, frames are compressed with a constant rate. A more complicated
         /// frame will just be bigger.
         Disabled,
+
+        /// Rate control is turned off and every frame is compressed with the specified
+        /// quantization parameter. Lower values give better quality and bigger frames. The
+        /// supported range depends on the device, it is at most 0-51 for both H264 and H265.
+        ConstantQp { qp: u8 },
     }
 
     /// A hint indicating what kind of content the decoder is going to be used for.
@@ -89,6 +96,82 @@ pub mod parameters {
         Lanczos3,
     }
 
+    /// Pixel format of frames returned by [`BytesDecoder`](crate::BytesDecoder) and passed to
+    /// [`BytesEncoderH264`](crate::BytesEncoderH264) and
+    /// [`BytesEncoderH265`](crate::BytesEncoderH265).
+    ///
+    /// Frames are cropped to the size signaled in the stream and rows are tightly packed, so the
+    /// stride of every plane is equal to its width. Chroma planes of odd-sized frames are rounded
+    /// up, i.e. they are `width.div_ceil(2)` x `height.div_ceil(2)` samples.
+    ///
+    /// Frames of 4:2:2 and 4:4:4 H.264 streams are output as [`PixelFormat::Nv12`] or
+    /// [`PixelFormat::P010`] with a chroma plane of
+    /// [`ChromaSubsampling::chroma_size`](crate::parameters::ChromaSubsampling::chroma_size)
+    /// samples, see [`FrameMetadata::chroma_subsampling`](crate::FrameMetadata::chroma_subsampling)
+    /// and [`RawFrameData::planes`](crate::RawFrameData::planes).
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    #[repr(u32)]
+    pub enum PixelFormat {
+        /// Y plane (`width` x `height` bytes) followed by a plane of interleaved U and V samples
+        /// (`2 * width.div_ceil(2)` x `height.div_ceil(2)` bytes).
+        #[default]
+        Nv12,
+        /// Y plane (`width` x `height` bytes) followed by U and V planes
+        /// (`width.div_ceil(2)` x `height.div_ceil(2)` bytes each).
+        I420,
+        /// A single plane with 4 bytes per pixel in R, G, B, A order (`4 * width` x `height`
+        /// bytes). Conversion uses the color space and range signaled in the stream, BT.709 is
+        /// assumed if it is not specified.
+        Rgba,
+        /// Same layout as [`PixelFormat::Nv12`], but with 2 byte little-endian samples, which
+        /// store the 10-bit value in the most significant bits. Used for 10-bit streams, which
+        /// cannot be output in other formats. 8-bit streams cannot be output in this format.
+        /// Encoders do not accept it.
+        P010,
+        /// A single plane of packed 4:2:2 samples in Y0, U, Y1, V order, one group of 4 bytes for
+        /// every 2 pixels (`4 * width.div_ceil(2)` x `height` bytes). Common output of capture
+        /// cards and webcams.
+        Yuyv,
+    }
+
+    impl PixelFormat {
+        /// Size in bytes of a frame with the given dimensions.
+        pub fn frame_size(&self, width: u32, height: u32) -> usize {
+            let (width, height) = (width as usize, height as usize);
+            match self {
+                PixelFormat::Nv12 | PixelFormat::I420 => {
+                    width * height + 2 * width.div_ceil(2) * height.div_ceil(2)
+                }
+                PixelFormat::Rgba => 4 * width * height,
+                PixelFormat::P010 => {
+                    2 * (width * height + 2 * width.div_ceil(2) * height.div_ceil(2))
+                }
+                PixelFormat::Yuyv => 4 * width.div_ceil(2) * height,
+            }
+        }
+
+        /// Offsets and row pitches of the planes in a frame with the given dimensions.
+        pub(crate) fn plane_layouts(&self, width: u32, height: u32) -> Vec<crate::PlaneLayout> {
+            let (width, height) = (width as u64, height as u64);
+            let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+            let plane = |offset, row_pitch| crate::PlaneLayout { offset, row_pitch };
+            match self {
+                PixelFormat::Nv12 => vec![plane(0, width), plane(width * height, 2 * chroma_width)],
+                PixelFormat::I420 => vec![
+                    plane(0, width),
+                    plane(width * height, chroma_width),
+                    plane(width * height + chroma_width * chroma_height, chroma_width),
+                ],
+                PixelFormat::Rgba => vec![plane(0, 4 * width)],
+                PixelFormat::P010 => vec![
+                    plane(0, 2 * width),
+                    plane(2 * width * height, 4 * chroma_width),
+                ],
+                PixelFormat::Yuyv => vec![plane(0, 4 * chroma_width)],
+            }
+        }
+    }
+
     /// A profile in H.264 is a set of codec features used while encoding a specific video.
     /// Baseline uses the fewest features, Main can use more and High even more than Main.
     #[derive(Debug, Clone, Copy)]
@@ -111,13 +194,14 @@ mod wgpu_api;
 #[cfg(feature = "wgpu")]
 pub use wgpu_api::*;
 
-use crate::capabilities::{DecodeCapabilities, EncodeCapabilities};
+use crate::capabilities::{DecodeCapabilities, EncodeCapabilities, VideoCapabilities};
 use crate::device::{
-    ColorRange, ColorSpace, DecoderParameters, EncoderOutputParameters, EncoderParametersH264,
-    EncoderParametersH265, VideoDeviceBackend,
+    ChromaSubsampling, ColorInfo, ColorRange, ColorSpace, DecoderParameters,
+    EncoderOutputParameters, EncoderParametersH264, EncoderParametersH265, PictureType,
+    SampleAspectRatio, VideoDeviceBackend,
 };
 use crate::parameters::{H264Profile, H265Profile, RateControl};
-use crate::parser::h264::AccessUnit;
+use crate::parser::{av1, h264::AccessUnit, h265};
 use std::sync::Arc;
 
 #[cfg(feature = "wgpu")]
@@ -125,18 +209,36 @@ pub use crate::{
     adapter::VideoAdapterExt,
     device::VideoDeviceExt,
     global_registry::RegistryError,
-    wgpu_helpers::{WgpuConverterInitError, WgpuNv12ToRgbaConverter, WgpuRgbaToNv12Converter},
+    wgpu_helpers::{
+        WgpuConverterInitError, WgpuNv12ToRgbaConverter, WgpuRgbaToNv12Converter,
+        WgpuYcbcrImageView, WgpuYcbcrSampler, WgpuYcbcrSamplerError,
+    },
 };
 
 pub use crate::adapter::VideoAdapter;
+pub use crate::decoders::{
+    AsyncBytesDecoder, AsyncBytesDecoderAv1, AsyncBytesDecoderH265, BytesDecoder, BytesDecoderAv1,
+    BytesDecoderH265, DecodedFramesReceiver, DecoderBackend, DecoderStats, ExternalMemoryDecoder,
+    VideoDecoderError,
+};
 #[cfg(feature = "wgpu")]
-pub use crate::decoders::WgpuTexturesDecoder;
-pub use crate::decoders::{BytesDecoder, VideoDecoderError};
-pub use crate::encoders::{BytesEncoderH264, BytesEncoderH265, VideoEncoderError};
+pub use crate::decoders::{
+    FramePool, TexturePool, WgpuTexturesDecoder, WgpuTexturesDecoderAv1, WgpuTexturesDecoderH265,
+};
+pub use crate::encoders::{BytesEncoderH264, BytesEncoderH265, EncoderStats, VideoEncoderError};
 #[cfg(feature = "wgpu")]
 pub use crate::encoders::{WgpuTexturesEncoderH264, WgpuTexturesEncoderH265};
 pub use crate::instance::VideoInstance;
-pub use crate::parser::{h264::H264ParserError, reference_manager::ReferenceManagementError};
+pub use crate::parser::{
+    av1::Av1ParserError,
+    h264::{AvcDecoderConfig, H264BitstreamFormat, H264ParserError},
+    h265::H265ParserError,
+    reference_manager::ReferenceManagementError,
+    sei::{
+        CaptionPacket, DisplayOrientation, FrameSei, MasteringDisplayColorVolume, Rotation,
+        Timecode, UserDataUnregistered,
+    },
+};
 #[cfg(feature = "transcoder")]
 pub use crate::transcoder::{VideoTranscoder, VideoTranscoderError};
 
@@ -182,13 +284,58 @@ impl std::fmt::Debug for VideoDevice {
 }
 
 impl VideoDevice {
+    /// With the `software-fallback` feature enabled, a software decoder is created if the device
+    /// does not support H.264 decoding. Use [`BytesDecoder::backend`] to check which one is used.
+    ///
+    /// The fallback only applies to this decoder. Other H.264 decoders, e.g.
+    /// [`Self::create_wgpu_textures_decoder_h264`], return
+    /// [`VideoDecoderError::DecoderUnsupported`] on such devices.
     pub fn create_bytes_decoder_h264(
         &self,
         parameters: DecoderParameters,
     ) -> Result<BytesDecoder, VideoDecoderError> {
-        self.inner.clone().create_bytes_decoder_h264(parameters)
+        let result = self.inner.clone().create_bytes_decoder_h264(parameters);
+
+        #[cfg(feature = "software-fallback")]
+        if let Err(VideoDecoderError::DecoderUnsupported) = result {
+            tracing::warn!(
+                "Device does not support H.264 decoding. Falling back to software decoder."
+            );
+            return BytesDecoder::new_software_h264_with_parameters(&parameters);
+        }
+
+        result
     }
 
+    pub fn create_bytes_decoder_h265(
+        &self,
+        parameters: DecoderParameters,
+    ) -> Result<BytesDecoderH265, VideoDecoderError> {
+        self.inner.clone().create_bytes_decoder_h265(parameters)
+    }
+
+    pub fn create_bytes_decoder_av1(
+        &self,
+        parameters: DecoderParameters,
+    ) -> Result<BytesDecoderAv1, VideoDecoderError> {
+        self.inner.clone().create_bytes_decoder_av1(parameters)
+    }
+
+    /// Creates a H.264 decoder that exports the decoded frames, see [`ExternalMemoryFrame`].
+    /// Returns [`VideoDecoderError::ExternalMemoryUnsupported`] if the device cannot export
+    /// memory.
+    pub fn create_external_memory_decoder_h264(
+        &self,
+        parameters: DecoderParameters,
+    ) -> Result<ExternalMemoryDecoder, VideoDecoderError> {
+        self.inner
+            .clone()
+            .create_external_memory_decoder_h264(parameters)
+    }
+
+    /// Requires Vulkan Video decoding support, the `software-fallback` feature does not apply to
+    /// this decoder. Returns [`VideoDecoderError::DecoderUnsupported`] if the device cannot
+    /// decode H.264, use [`Self::create_bytes_decoder_h264`] and upload the frames instead.
     #[cfg(feature = "wgpu")]
     pub fn create_wgpu_textures_decoder_h264(
         &self,
@@ -203,6 +350,34 @@ impl VideoDevice {
             .create_wgpu_textures_decoder_h264(wgpu_device, parameters)
     }
 
+    #[cfg(feature = "wgpu")]
+    pub fn create_wgpu_textures_decoder_h265(
+        &self,
+        parameters: DecoderParameters,
+    ) -> Result<WgpuTexturesDecoderH265, VideoDecoderError> {
+        let Some(wgpu_device) = self.wgpu_device.clone() else {
+            return Err(VideoDecoderError::VideoDeviceWithoutWgpu);
+        };
+
+        self.inner
+            .clone()
+            .create_wgpu_textures_decoder_h265(wgpu_device, parameters)
+    }
+
+    #[cfg(feature = "wgpu")]
+    pub fn create_wgpu_textures_decoder_av1(
+        &self,
+        parameters: DecoderParameters,
+    ) -> Result<WgpuTexturesDecoderAv1, VideoDecoderError> {
+        let Some(wgpu_device) = self.wgpu_device.clone() else {
+            return Err(VideoDecoderError::VideoDeviceWithoutWgpu);
+        };
+
+        self.inner
+            .clone()
+            .create_wgpu_textures_decoder_av1(wgpu_device, parameters)
+    }
+
     /// Create a single-input multiple-output transcoder.
     /// Each item in `parameters.output_parameters` corresponds to one output.
     #[cfg(feature = "transcoder")]
@@ -269,6 +444,13 @@ impl VideoDevice {
         self.inner.encode_capabilities()
     }
 
+    /// Returns the decoding and encoding capabilities of the device: supported codecs and
+    /// profiles, their size limits and whether encoding is available at all. Useful for picking
+    /// codecs and resolutions before creating decoders or encoders.
+    pub fn video_capabilities(&self) -> VideoCapabilities {
+        self.inner.video_capabilities()
+    }
+
     pub fn encoder_output_parameters_h265_low_latency(
         &self,
         rate_control: RateControl,
@@ -337,12 +519,19 @@ impl VideoDevice {
             profile,
             idr_period: None,
             max_references: None,
+            consecutive_b_frames: None,
+            slice_count: None,
+            intra_refresh_period: None,
+            max_width: None,
+            max_height: None,
             rate_control,
             preset: parameters::EncoderPreset::LowLatency,
             usage_flags: Some(parameters::EncoderUsage::Default),
             inline_stream_params: None,
+            stream_params_interval: None,
             color_space: None,
             color_range: None,
+            enable_gpu_timing: false,
         }
     }
 
@@ -354,12 +543,19 @@ impl VideoDevice {
             profile,
             idr_period: None,
             max_references: None,
+            consecutive_b_frames: None,
+            slice_count: None,
+            intra_refresh_period: None,
+            max_width: None,
+            max_height: None,
             rate_control,
             preset: parameters::EncoderPreset::HighQuality,
             usage_flags: Some(parameters::EncoderUsage::Default),
             inline_stream_params: None,
+            stream_params_interval: None,
             color_space: None,
             color_range: None,
+            enable_gpu_timing: false,
         }
     }
 }
@@ -376,9 +572,14 @@ impl VideoDevice {
 pub struct EncodedInputChunk<'a> {
     pub data: &'a [u8],
     pub pts: Option<u64>,
+    /// Decoding timestamp of the chunk. It is not used for decoding, only passed through to
+    /// [`FrameMetadata::dts`] of the frames decoded from the chunk.
+    pub dts: Option<u64>,
 }
 
 pub type H264DecoderEvent<'a> = DecoderEvent<'a, AccessUnit>;
+pub type H265DecoderEvent<'a> = DecoderEvent<'a, h265::AccessUnit>;
+pub type Av1DecoderEvent<'a> = DecoderEvent<'a, av1::FrameUnit>;
 
 /// Represents all events that can be sent to the decoder
 #[non_exhaustive]
@@ -409,6 +610,14 @@ pub enum DecoderEvent<'a, ParsedFrame> {
     /// Make sure that this is done when you have the knowledge that no more frames will be coming
     /// that need to be presented before the already decoded frames.
     Flush,
+
+    /// Reset the decoder after a discontinuity in the stream, e.g. when the sender reconnects.
+    ///
+    /// Clears the parser, the reference pictures and the frames waiting to be output, but keeps
+    /// the video session and the allocated images. Decoding starts again from the next keyframe.
+    /// Frames which were decoded but not output yet are discarded, send [`DecoderEvent::Flush`]
+    /// first to get them.
+    Reset,
 }
 
 /// Represents a chunk of encoded video data returned by the encoder.
@@ -425,13 +634,79 @@ pub struct EncodedOutputChunk<T> {
 pub struct InputFrame<T> {
     pub data: T,
     pub pts: Option<u64>,
+    /// Quantization parameter used for this frame instead of the one from
+    /// [`RateControl::ConstantQp`](crate::parameters::RateControl::ConstantQp), e.g. to encode
+    /// frames with text or UI in better quality. Can only be set when the rate control is
+    /// `ConstantQp` or `Disabled`. If [`None`], the QP of the rate control is used.
+    ///
+    /// The QP applies to the whole frame, QP maps for regions of a frame are not supported.
+    pub qp: Option<u8>,
 }
 
 /// Additional information about the decoded frame.
 pub struct FrameMetadata {
     pub pts: Option<u64>,
+    /// Decoding timestamp of the chunk the frame was decoded from, see
+    /// [`EncodedInputChunk::dts`]. Always `None` for frames of the software decoder, which
+    /// cannot match its output to the input chunks.
+    pub dts: Option<u64>,
+    /// Coding type of the frame. AV1 inter frames are reported as [`PictureType::P`]. `None`
+    /// for frames of the software decoder.
+    pub picture_type: Option<PictureType>,
+    /// Picture order count of H.264 and H.265 frames, which restarts at every IDR frame, or the
+    /// order hint of AV1 frames. Frames are output in the order of this value, which makes it
+    /// useful for debugging reordering. `None` for frames of the software decoder.
+    pub pic_order_cnt: Option<i32>,
     pub color_space: ColorSpace,
     pub color_range: ColorRange,
+    /// Chroma layout of the frame. Frames with 4:2:2 and 4:4:4 chroma subsampling are only
+    /// returned by bytes decoders with [`PixelFormat::Nv12`](crate::parameters::PixelFormat::Nv12)
+    /// or [`PixelFormat::P010`](crate::parameters::PixelFormat::P010) output, their chroma plane
+    /// has the size given by [`ChromaSubsampling::chroma_size`] and the layout of the planes is
+    /// returned in [`RawFrameData::planes`].
+    pub chroma_subsampling: ChromaSubsampling,
+    /// Full color description of the frame, including the mastering display of HDR streams.
+    pub color_info: ColorInfo,
+    /// Time the GPU spent decoding this frame. Only available if the decoder was created with
+    /// [`DecoderParameters::enable_gpu_timing`].
+    pub gpu_decode_duration: Option<std::time::Duration>,
+    /// Orientation in which the frame should be displayed. Read from the display orientation
+    /// SEI message or overridden with [`DecoderParameters::display_orientation`].
+    /// `WgpuNv12ToRgbaConverter::convert_with_orientation` can apply it during conversion.
+    pub display_orientation: DisplayOrientation,
+    /// Timecodes, closed captions and unregistered user data sent in SEI messages with this
+    /// frame. The messages are matched to the frame by its `pts`, so they are only available if
+    /// the pts was provided in the encoded chunks.
+    pub sei: FrameSei,
+    /// `true` if data needed to decode this frame was lost, so it can contain artifacts. Frames
+    /// are marked until the next keyframe. Only set by decoders created with
+    /// [`MissedFrameHandling::Tolerant`](crate::parameters::MissedFrameHandling::Tolerant) or
+    /// [`MissedFrameHandling::Conceal`](crate::parameters::MissedFrameHandling::Conceal), in the
+    /// strict mode such frames are not decoded.
+    pub corrupted: bool,
+    /// `true` if decoding started at a recovery point SEI message instead of an IDR frame and
+    /// the picture is not fully refreshed yet, so parts of it can be incorrect. Frames are marked
+    /// until the recovery point given in the message is reached. Only set by the H.264 decoders.
+    pub recovering: bool,
+    /// `None` if the stream does not specify it, in which case square samples can be assumed.
+    /// The frames are already cropped to the size from the sequence parameter set, the sample
+    /// aspect ratio applies to the cropped size.
+    pub sample_aspect_ratio: Option<SampleAspectRatio>,
+    /// Changes in the stream which take effect with this frame. Empty for most frames.
+    pub events: Vec<FrameEvent>,
+}
+
+/// Change in the decoded stream reported in [`FrameMetadata::events`] of the first frame it
+/// applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FrameEvent {
+    /// A new sequence header changed the size of the frames. The decoder reallocates its
+    /// buffers and continues decoding without a reset, this frame and the following ones have
+    /// the new size. Consumers which allocate frame-sized resources, e.g. a
+    /// [`FramePool`](crate::FramePool), should resize them. Not reported for the first frame of
+    /// the stream.
+    ResolutionChanged { width: u32, height: u32 },
 }
 
 /// Represents a single decoded frame.
@@ -444,4 +719,78 @@ pub struct RawFrameData {
     pub frame: Vec<u8>,
     pub width: u32,
     pub height: u32,
+    /// Positions and row pitches of the planes in `frame`. `None` if the planes are tightly
+    /// packed as described in [`PixelFormat`](crate::parameters::PixelFormat). Set by decoders
+    /// created with [`DecoderParameters::aligned_output_rows`] and for frames with 4:2:2 or 4:4:4
+    /// chroma subsampling. Encoders only accept tightly packed frames.
+    pub planes: Option<Vec<PlaneLayout>>,
+}
+
+/// Handle to memory exported by [`ExternalMemoryDecoder`]. A DMA-BUF file descriptor on Linux
+/// and an opaque NT handle on Windows. Closing it releases the memory.
+#[cfg(unix)]
+pub type ExternalMemoryHandle = std::os::fd::OwnedFd;
+/// Handle to memory exported by [`ExternalMemoryDecoder`]. A DMA-BUF file descriptor on Linux
+/// and an opaque NT handle on Windows. Closing it releases the memory.
+#[cfg(windows)]
+pub type ExternalMemoryHandle = std::os::windows::io::OwnedHandle;
+
+/// Handle to the timeline semaphore exported with [`ExternalMemoryDecoder::export_semaphore`].
+/// An opaque file descriptor on Linux and an opaque NT handle on Windows.
+#[cfg(unix)]
+pub type ExternalSemaphoreHandle = std::os::fd::OwnedFd;
+/// Handle to the timeline semaphore exported with [`ExternalMemoryDecoder::export_semaphore`].
+/// An opaque file descriptor on Linux and an opaque NT handle on Windows.
+#[cfg(windows)]
+pub type ExternalSemaphoreHandle = std::os::windows::io::OwnedHandle;
+
+/// Decoded frame stored in memory that can be imported by other APIs, e.g. VA-API, GStreamer,
+/// EGL or another Vulkan device, without copying it through the host.
+///
+/// All planes are stored linearly in a single allocation, in the layout of the
+/// [`PixelFormat`](crate::parameters::PixelFormat) selected with
+/// [`DecoderParameters::output_pixel_format`]. The frame is fully written when it is returned,
+/// so no synchronization is needed before reading it, unless the decoder semaphore was exported
+/// with [`ExternalMemoryDecoder::export_semaphore`].
+#[derive(Debug)]
+pub struct ExternalMemoryFrame {
+    pub handle: ExternalMemoryHandle,
+    /// Size of the exported allocation, which can be bigger than the frame. Needed for importing
+    /// the memory into Vulkan or D3D12.
+    pub allocation_size: u64,
+    pub pixel_format: crate::parameters::PixelFormat,
+    pub width: u32,
+    pub height: u32,
+    pub planes: Vec<PlaneLayout>,
+    /// The frame is written when the exported decoder semaphore reaches this value. `None` if
+    /// the semaphore was not exported, in which case the decoder waited before returning the
+    /// frame.
+    pub semaphore_value: Option<u64>,
+}
+
+/// Position of a plane of an [`ExternalMemoryFrame`] in the exported memory, or of a
+/// [`RawFrameData`] in its buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaneLayout {
+    pub offset: u64,
+    /// Distance in bytes between the starts of consecutive rows.
+    pub row_pitch: u64,
+}
+
+impl ExternalMemoryFrame {
+    /// Format modifier of the frame memory for importing it as a DMA-BUF. The planes are
+    /// stored linearly, so this is always `DRM_FORMAT_MOD_LINEAR`.
+    pub const DRM_FORMAT_MODIFIER: u64 = 0;
+
+    /// `DRM_FORMAT_*` fourcc code matching the pixel format of the frame.
+    pub fn drm_fourcc(&self) -> u32 {
+        let code = match self.pixel_format {
+            crate::parameters::PixelFormat::Nv12 => b"NV12",
+            crate::parameters::PixelFormat::I420 => b"YU12",
+            crate::parameters::PixelFormat::Rgba => b"AB24",
+            crate::parameters::PixelFormat::P010 => b"P010",
+        };
+
+        u32::from_le_bytes(*code)
+    }
 }
@@ -1,17 +1,33 @@
-use std::collections::BinaryHeap;
+use std::{collections::BinaryHeap, time::Duration};
 
 use crate::{
-    FrameMetadata, OutputFrame,
-    device::{ColorRange, ColorSpace},
+    FrameEvent, FrameMetadata, OutputFrame,
+    device::{
+        ChromaSubsampling, ColorInfo, ColorRange, ColorSpace, PictureType, SampleAspectRatio,
+    },
+    parameters::DecoderParameters,
 };
 
 pub(crate) struct DecodeResultMetadata {
     pub(crate) pts: Option<u64>,
+    pub(crate) dts: Option<u64>,
+    pub(crate) picture_type: PictureType,
     pub(crate) pic_order_cnt: i32,
     pub(crate) max_num_reorder_frames: u64,
+    /// When `false`, `max_num_reorder_frames` is an upper bound estimated from the DPB size.
+    pub(crate) max_num_reorder_frames_signaled: bool,
     pub(crate) is_idr: bool,
     pub(crate) color_space: ColorSpace,
     pub(crate) color_range: ColorRange,
+    pub(crate) chroma_subsampling: ChromaSubsampling,
+    pub(crate) color_info: ColorInfo,
+    pub(crate) gpu_decode_duration: Option<Duration>,
+    pub(crate) corrupted: bool,
+    pub(crate) recovering: bool,
+    pub(crate) sample_aspect_ratio: Option<SampleAspectRatio>,
+    /// Size of the output frame after cropping.
+    pub(crate) width: u32,
+    pub(crate) height: u32,
 }
 
 pub(crate) struct DecodeResult<T> {
@@ -50,8 +66,20 @@ impl<T> From<DecodeResult<T>> for OutputFrame<T> {
             data: result.frame,
             metadata: FrameMetadata {
                 pts: result.metadata.pts,
+                dts: result.metadata.dts,
+                picture_type: Some(result.metadata.picture_type),
+                pic_order_cnt: Some(result.metadata.pic_order_cnt),
                 color_space: result.metadata.color_space,
                 color_range: result.metadata.color_range,
+                chroma_subsampling: result.metadata.chroma_subsampling,
+                color_info: result.metadata.color_info,
+                gpu_decode_duration: result.metadata.gpu_decode_duration,
+                display_orientation: Default::default(),
+                sei: Default::default(),
+                corrupted: result.metadata.corrupted,
+                recovering: result.metadata.recovering,
+                sample_aspect_ratio: result.metadata.sample_aspect_ratio,
+                events: Vec::new(),
             },
         }
     }
@@ -59,33 +87,98 @@ impl<T> From<DecodeResult<T>> for OutputFrame<T> {
 
 pub(crate) struct FrameSorter<T> {
     frames: BinaryHeap<DecodeResult<T>>,
+    reorder: bool,
+    low_latency: bool,
+    /// Picture order count of the last output frame since the last IDR frame.
+    last_output_pic_order_cnt: Option<i32>,
+    /// Set in low latency mode after a frame arrived too late to be output in order.
+    reordering_detected: bool,
+    /// Size of the last output frame, used to report resolution changes.
+    last_output_size: Option<(u32, u32)>,
 }
 
 impl<T> FrameSorter<T> {
     pub(crate) fn new() -> Self {
         Self {
             frames: BinaryHeap::new(),
+            reorder: true,
+            low_latency: false,
+            last_output_pic_order_cnt: None,
+            reordering_detected: false,
+            last_output_size: None,
+        }
+    }
+
+    /// Frames of streams which don't signal how many frames can be reordered are output without
+    /// waiting, as if the stream had no reordering. Once a frame arrives after a frame that
+    /// follows it in presentation order, the sorter falls back to the limit estimated from the
+    /// DPB size.
+    pub(crate) fn new_low_latency() -> Self {
+        Self {
+            low_latency: true,
+            ..Self::new()
+        }
+    }
+
+    /// Sorter for H.264 and H.265 decoders created with `parameters`.
+    pub(crate) fn from_parameters(parameters: &DecoderParameters) -> Self {
+        match (parameters.keyframes_only, parameters.low_latency) {
+            (true, _) => Self::new_in_decode_order(),
+            (false, true) => Self::new_low_latency(),
+            (false, false) => Self::new(),
+        }
+    }
+
+    /// Frames are returned in decode order. Used when only keyframes are decoded, the picture
+    /// order count is reset on every keyframe, so it cannot be used to sort them. AV1 frames are
+    /// always output in decode order.
+    pub(crate) fn new_in_decode_order() -> Self {
+        Self {
+            reorder: false,
+            ..Self::new()
         }
     }
 
     pub(crate) fn put(&mut self, frame: DecodeResult<T>) -> Vec<OutputFrame<T>> {
-        let max_num_reorder_frames = frame.metadata.max_num_reorder_frames as usize;
+        if !self.reorder {
+            return vec![self.output(frame)];
+        }
+
         let is_idr = frame.metadata.is_idr;
+        let pic_order_cnt = frame.metadata.pic_order_cnt;
+        if self.low_latency
+            && !is_idr
+            && self
+                .last_output_pic_order_cnt
+                .is_some_and(|last| pic_order_cnt < last)
+        {
+            self.reordering_detected = true;
+        }
+
+        let max_num_reorder_frames = match self.low_latency
+            && !self.reordering_detected
+            && !frame.metadata.max_num_reorder_frames_signaled
+        {
+            true => 0,
+            false => frame.metadata.max_num_reorder_frames as usize,
+        };
         let mut result = Vec::new();
 
         if is_idr {
             while !self.frames.is_empty() {
                 let frame = self.frames.pop().unwrap();
-                result.push(frame.into());
+                result.push(self.output(frame));
             }
 
-            result.push(frame.into());
+            self.last_output_pic_order_cnt = Some(pic_order_cnt);
+            result.push(self.output(frame));
         } else {
             self.frames.push(frame);
 
             while self.frames.len() > max_num_reorder_frames {
                 let frame = self.frames.pop().unwrap();
-                result.push(frame.into());
+                self.last_output_pic_order_cnt = Some(frame.metadata.pic_order_cnt);
+                result.push(self.output(frame));
             }
         }
 
@@ -102,14 +195,93 @@ impl<T> FrameSorter<T> {
         result
     }
 
+    /// Discards the frames waiting to be output and forgets the reordering seen so far.
+    pub(crate) fn clear(&mut self) {
+        self.frames.clear();
+        self.last_output_pic_order_cnt = None;
+        self.reordering_detected = false;
+    }
+
     pub(crate) fn flush(&mut self) -> Vec<OutputFrame<T>> {
         let mut result = Vec::with_capacity(self.frames.len());
 
         while !self.frames.is_empty() {
             let frame = self.frames.pop().unwrap();
-            result.push(frame.into());
+            self.last_output_pic_order_cnt = Some(frame.metadata.pic_order_cnt);
+            result.push(self.output(frame));
         }
 
         result
     }
+
+    /// Converts the frame and attaches [`FrameEvent::ResolutionChanged`] if its size differs
+    /// from the previous output frame. The resolution can only change at an IDR frame, which
+    /// flushes the frames of the previous sequence first, so the event lands on the first frame
+    /// of the new sequence in presentation order.
+    fn output(&mut self, frame: DecodeResult<T>) -> OutputFrame<T> {
+        let size = (frame.metadata.width, frame.metadata.height);
+        let previous_size = self.last_output_size.replace(size);
+        let mut frame = OutputFrame::from(frame);
+        if previous_size.is_some_and(|previous_size| previous_size != size) {
+            frame.metadata.events.push(FrameEvent::ResolutionChanged {
+                width: size.0,
+                height: size.1,
+            });
+        }
+
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(pic_order_cnt: i32, picture_type: PictureType, is_idr: bool) -> DecodeResult<i32> {
+        DecodeResult {
+            frame: pic_order_cnt,
+            metadata: DecodeResultMetadata {
+                pts: Some(pic_order_cnt as u64),
+                dts: None,
+                picture_type,
+                pic_order_cnt,
+                max_num_reorder_frames: 2,
+                max_num_reorder_frames_signaled: true,
+                is_idr,
+                color_space: ColorSpace::Unspecified,
+                color_range: ColorRange::Limited,
+                chroma_subsampling: ChromaSubsampling::Yuv420,
+                color_info: ColorInfo::default(),
+                gpu_decode_duration: None,
+                corrupted: false,
+                recovering: false,
+                sample_aspect_ratio: None,
+                width: 16,
+                height: 16,
+            },
+        }
+    }
+
+    fn data(frames: Vec<OutputFrame<i32>>) -> Vec<i32> {
+        frames.into_iter().map(|frame| frame.data).collect()
+    }
+
+    #[test]
+    fn flush_returns_reordered_tail() {
+        let mut sorter = FrameSorter::new();
+        // I P B B in decode order
+        let output = sorter.put_frames(vec![
+            frame(0, PictureType::I, true),
+            frame(6, PictureType::P, false),
+            frame(2, PictureType::B, false),
+            frame(4, PictureType::B, false),
+        ]);
+        assert_eq!(data(output), [0, 2]);
+        assert_eq!(data(sorter.flush()), [4, 6]);
+        assert!(sorter.flush().is_empty());
+
+        // decoding continues after the flush
+        assert!(sorter.put(frame(8, PictureType::P, false)).is_empty());
+        assert_eq!(data(sorter.flush()), [8]);
+    }
 }
@@ -53,6 +53,25 @@ impl VideoInstance {
     ) -> Result<impl Iterator<Item = VideoAdapter<'a>>, VideoInstanceInitError> {
         self.instance.iter_adapters()
     }
+
+    /// Returns all available [`VideoAdapter`]s sorted by
+    /// [`VideoAdapterInfo::suitability_score`](crate::capabilities::VideoAdapterInfo::suitability_score),
+    /// the most suitable first. On machines with multiple GPUs it can be used to pin decoders
+    /// and encoders to the GPU with dedicated video engines. The supported codecs and video
+    /// queue families of every adapter are available in [`VideoAdapter::info`].
+    pub fn enumerate_video_adapters<'a>(
+        &'a self,
+    ) -> Result<Vec<VideoAdapter<'a>>, VideoInstanceInitError> {
+        Ok(sort_by_suitability(self.iter_adapters()?))
+    }
+}
+
+pub(crate) fn sort_by_suitability<'a>(
+    adapters: impl Iterator<Item = VideoAdapter<'a>>,
+) -> Vec<VideoAdapter<'a>> {
+    let mut adapters = adapters.collect::<Vec<_>>();
+    adapters.sort_by_key(|adapter| std::cmp::Reverse(adapter.info().suitability_score()));
+    adapters
 }
 
 impl std::fmt::Debug for VideoInstance {
@@ -1,19 +1,39 @@
 mod au_splitter;
+mod avcc;
 mod nalu_parser;
 mod nalu_splitter;
+mod parameter_sets;
+pub(crate) mod sei;
 
 #[cfg(vulkan)]
 pub(crate) mod decoder_instructions;
 #[cfg(vulkan)]
 pub(crate) mod reference_manager;
 
+pub mod av1;
+pub mod h265;
+
+/// Timestamps of the encoded chunk a bitstream unit was read from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ChunkTimestamps {
+    pub(crate) pts: Option<u64>,
+    pub(crate) dts: Option<u64>,
+}
+
 pub mod h264 {
+    use super::ChunkTimestamps;
     use super::au_splitter::AUSplitter;
     use super::nalu_parser::NalParser;
     use super::nalu_splitter::NALUSplitter;
 
     pub use super::au_splitter::AccessUnit;
+    pub use super::avcc::{AvcDecoderConfig, H264BitstreamFormat};
     pub use super::nalu_parser::{Nalu, ParsedNalu};
+    pub use super::parameter_sets::{PpsInfo, SpsInfo, VuiTimingInfo, parse_pps, parse_sps};
+    pub use super::sei::{
+        CaptionPacket, DisplayOrientationSei, MasteringDisplayColorVolume, RecoveryPoint,
+        SeiMessage, Timecode, UserDataUnregistered,
+    };
     #[cfg(feature = "expose-parsers")]
     pub use h264_reader::nal as nal_types;
 
@@ -33,32 +53,95 @@ pub mod h264 {
 
         #[error("Error while parsing a slice: {0:?}")]
         SliceParseError(h264_reader::nal::slice::SliceHeaderError),
+
+        #[error("Invalid AVC decoder configuration record: {0}")]
+        InvalidAvcDecoderConfig(String),
+
+        #[error("Length-prefixed data ends in the middle of a NAL unit")]
+        TruncatedAvccData,
+
+        #[error("Expected a {expected:?} NAL unit, got {actual:?}")]
+        UnexpectedNalUnitType {
+            expected: h264_reader::nal::UnitType,
+            actual: h264_reader::nal::UnitType,
+        },
     }
 
-    /// H264 parser for Annex B format
+    /// H264 parser for Annex B or AVCC format, see [`H264BitstreamFormat`].
     #[derive(Default)]
     pub struct H264Parser {
         nal_parser: NalParser,
         nalu_splitter: NALUSplitter,
         au_splitter: AUSplitter,
+        format: H264BitstreamFormat,
+        /// The parameter sets of the AVCC format are passed to the parser before the first chunk.
+        parameter_sets_sent: bool,
     }
 
     impl H264Parser {
-        /// Parses nalus in Annex B format.
+        pub fn new(format: H264BitstreamFormat) -> Self {
+            Self {
+                format,
+                ..Default::default()
+            }
+        }
+
+        /// Drops all buffered data and parameter sets, keeping the bitstream format.
+        pub fn reset(&mut self) {
+            *self = Self::new(std::mem::take(&mut self.format));
+        }
+
+        /// Parses nalus in the format the parser was created with.
         /// Returns [`AccessUnit`]s representing whole frame
         pub fn parse(
             &mut self,
             bytes: &[u8],
             pts: Option<u64>,
         ) -> Result<Vec<AccessUnit>, H264ParserError> {
-            let nalus = self.nalu_splitter.push(bytes, pts);
-            let nalus = nalus.into_iter().map(|(nalu_bytes, pts)| {
+            self.parse_with_dts(bytes, pts, None)
+        }
+
+        /// Same as [`Self::parse`], but also passes the decoding timestamp of the chunk to the
+        /// parsed nalus.
+        pub fn parse_with_dts(
+            &mut self,
+            bytes: &[u8],
+            pts: Option<u64>,
+            dts: Option<u64>,
+        ) -> Result<Vec<AccessUnit>, H264ParserError> {
+            let timestamps = ChunkTimestamps { pts, dts };
+            let H264BitstreamFormat::Avcc(config) = &self.format else {
+                return self.parse_annex_b(bytes, timestamps);
+            };
+
+            let mut annex_b = Vec::new();
+            if !self.parameter_sets_sent {
+                annex_b = config.parameter_sets_annex_b();
+                self.parameter_sets_sent = true;
+            }
+            annex_b.append(&mut config.to_annex_b(bytes)?);
+
+            // every chunk contains a whole access unit, so it doesn't have to wait for the next
+            // one to be split
+            let mut access_units = self.parse_annex_b(&annex_b, timestamps)?;
+            access_units.append(&mut self.flush()?);
+            Ok(access_units)
+        }
+
+        fn parse_annex_b(
+            &mut self,
+            bytes: &[u8],
+            timestamps: ChunkTimestamps,
+        ) -> Result<Vec<AccessUnit>, H264ParserError> {
+            let nalus = self.nalu_splitter.push(bytes, timestamps);
+            let nalus = nalus.into_iter().map(|(nalu_bytes, timestamps)| {
                 self.nal_parser
                     .parse_nalu(&nalu_bytes)
                     .map(|parsed_nalu| Nalu {
                         parsed: parsed_nalu,
                         raw_bytes: nalu_bytes.into_boxed_slice(),
-                        pts,
+                        pts: timestamps.pts,
+                        dts: timestamps.dts,
                     })
             });
 
@@ -78,13 +161,14 @@ pub mod h264 {
 
         pub fn flush(&mut self) -> Result<Vec<AccessUnit>, H264ParserError> {
             let nalus = self.nalu_splitter.flush();
-            let nalus = nalus.into_iter().map(|(nalu_bytes, pts)| {
+            let nalus = nalus.into_iter().map(|(nalu_bytes, timestamps)| {
                 self.nal_parser
                     .parse_nalu(&nalu_bytes)
                     .map(|parsed_nalu| Nalu {
                         parsed: parsed_nalu,
                         raw_bytes: nalu_bytes.into_boxed_slice(),
-                        pts,
+                        pts: timestamps.pts,
+                        dts: timestamps.dts,
                     })
             });
 
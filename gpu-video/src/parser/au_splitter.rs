@@ -166,3 +166,19 @@ fn idrs_where_idr_pic_id_differs(last: &Slice, curr: &Slice) -> bool {
 
 /// Group of [`Nalu`]s representing one frame
 pub struct AccessUnit(pub Box<[Nalu]>);
+
+impl AccessUnit {
+    /// Returns `true` if the frame can be decoded without any other frames, i.e. all of its
+    /// slices are intra slices.
+    pub fn is_intra(&self) -> bool {
+        let mut slices = self
+            .0
+            .iter()
+            .filter_map(|nalu| match &nalu.parsed {
+                ParsedNalu::Slice(slice) => Some(slice),
+                _ => None,
+            })
+            .peekable();
+        slices.peek().is_some() && slices.all(|slice| slice.is_intra())
+    }
+}
@@ -0,0 +1,107 @@
+use frame_splitter::FrameSplitter;
+use obu_parser::ObuParser;
+use obu_splitter::ObuSplitter;
+
+use super::ChunkTimestamps;
+
+pub use frame_header::{
+    CdefParams, FilmGrainParams, FrameHeader, FrameSize, FrameType, GlobalMotionParams,
+    LoopFilterParams, LoopRestorationParams, QuantizationParams, SegmentationParams, TileInfo,
+};
+pub use frame_splitter::FrameUnit;
+pub use obu_parser::{Obu, ObuHeader, ObuType, ParsedObu};
+pub use sequence_header::{
+    ColorConfig, DecoderModelInfo, OperatingPoint, SequenceHeader, TimingInfo,
+};
+pub use tile_group::{Tile, TileGroup};
+
+mod bitstream;
+mod frame_header;
+mod frame_splitter;
+mod obu_parser;
+mod obu_splitter;
+mod sequence_header;
+mod tile_group;
+
+#[cfg(vulkan)]
+pub(crate) mod decoder_instructions;
+#[cfg(vulkan)]
+pub(crate) mod reference_manager;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Av1ParserError {
+    #[error("An OBU ended before all of its syntax elements were read")]
+    UnexpectedEndOfObu,
+
+    #[error("Invalid OBU header")]
+    InvalidObuHeader,
+
+    #[error("Invalid value of {name}: {value}")]
+    InvalidValue { name: &'static str, value: i64 },
+
+    #[error("A frame header was received before the sequence header")]
+    MissingSequenceHeader,
+
+    #[error("A tile group was received without a frame header")]
+    MissingFrameHeader,
+
+    #[error("Reference frame slot {0} was used before a frame was stored in it")]
+    MissingReferenceFrame(u8),
+}
+
+/// AV1 parser for the low overhead bitstream format, as used by ISOBMFF, Matroska and RTP.
+/// The last OBU in every chunk of data passed to [`Av1Parser::parse`] may omit the
+/// `obu_size` field.
+#[derive(Default)]
+pub struct Av1Parser {
+    obu_parser: ObuParser,
+    obu_splitter: ObuSplitter,
+    frame_splitter: FrameSplitter,
+}
+
+impl Av1Parser {
+    /// Parses OBUs in the low overhead bitstream format.
+    /// Returns [`FrameUnit`]s representing whole frames
+    pub fn parse(
+        &mut self,
+        bytes: &[u8],
+        pts: Option<u64>,
+    ) -> Result<Vec<FrameUnit>, Av1ParserError> {
+        self.parse_with_dts(bytes, pts, None)
+    }
+
+    /// Same as [`Self::parse`], but also passes the decoding timestamp of the chunk to the
+    /// parsed OBUs.
+    pub fn parse_with_dts(
+        &mut self,
+        bytes: &[u8],
+        pts: Option<u64>,
+        dts: Option<u64>,
+    ) -> Result<Vec<FrameUnit>, Av1ParserError> {
+        let obus = self
+            .obu_splitter
+            .push(bytes, ChunkTimestamps { pts, dts })?;
+
+        let mut frames = Vec::new();
+        for (obu_bytes, timestamps) in obus {
+            let obu = self.obu_parser.parse_obu(obu_bytes, timestamps)?;
+
+            let Some(frame) = self.frame_splitter.put_obu(obu) else {
+                continue;
+            };
+
+            frames.push(frame);
+        }
+
+        Ok(frames)
+    }
+
+    /// Every frame is returned as soon as its last tile group is parsed, so this only discards
+    /// data of an incomplete frame.
+    pub fn flush(&mut self) -> Result<Vec<FrameUnit>, Av1ParserError> {
+        self.obu_splitter.flush();
+        self.frame_splitter.flush();
+
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,134 @@
+use super::Av1ParserError;
+
+/// Reads syntax elements from an OBU, as described in section 4.10 of the AV1 spec.
+pub(crate) struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// Number of bits read so far.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn flag(&mut self) -> Result<bool, Av1ParserError> {
+        let byte = self
+            .data
+            .get(self.position / 8)
+            .ok_or(Av1ParserError::UnexpectedEndOfObu)?;
+        let bit = (byte >> (7 - self.position % 8)) & 1;
+        self.position += 1;
+
+        Ok(bit == 1)
+    }
+
+    /// `f(n)`, at most 32 bits.
+    pub(crate) fn f(&mut self, bits: u32) -> Result<u32, Av1ParserError> {
+        debug_assert!(bits <= 32);
+        let mut value = 0u64;
+        for _ in 0..bits {
+            value = (value << 1) | self.flag()? as u64;
+        }
+
+        Ok(value as u32)
+    }
+
+    /// `su(n)`, signed integer converted from `n` bits.
+    pub(crate) fn su(&mut self, bits: u32) -> Result<i32, Av1ParserError> {
+        let value = self.f(bits)? as i64;
+        let sign_mask = 1i64 << (bits - 1);
+        let value = match value & sign_mask {
+            0 => value,
+            _ => value - 2 * sign_mask,
+        };
+
+        Ok(value as i32)
+    }
+
+    /// `ns(n)`, non-symmetric unsigned encoded integer with maximum number of values `n`.
+    pub(crate) fn ns(&mut self, n: u32) -> Result<u32, Av1ParserError> {
+        let w = floor_log2(n) + 1;
+        let m = (1 << w) - n;
+        let v = self.f(w - 1)?;
+        if v < m {
+            return Ok(v);
+        }
+
+        let extra_bit = self.f(1)?;
+        Ok((v << 1) - m + extra_bit)
+    }
+
+    /// `uvlc()`, variable length unsigned integer.
+    pub(crate) fn uvlc(&mut self) -> Result<u32, Av1ParserError> {
+        let mut leading_zeros = 0;
+        while !self.flag()? {
+            leading_zeros += 1;
+        }
+
+        if leading_zeros >= 32 {
+            return Ok(u32::MAX);
+        }
+
+        let value = self.f(leading_zeros)? as u64;
+        Ok((value + (1 << leading_zeros) - 1) as u32)
+    }
+
+    /// `le(n)`, unsigned little-endian `n`-byte number.
+    pub(crate) fn le(&mut self, bytes: u32) -> Result<u32, Av1ParserError> {
+        let mut value = 0;
+        for i in 0..bytes {
+            value |= self.f(8)? << (i * 8);
+        }
+
+        Ok(value)
+    }
+
+    /// `byte_alignment()`, skips bits up to the next byte boundary.
+    pub(crate) fn byte_alignment(&mut self) -> Result<(), Av1ParserError> {
+        while self.position % 8 != 0 {
+            self.flag()?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn skip(&mut self, bits: usize) -> Result<(), Av1ParserError> {
+        if self.position + bits > self.data.len() * 8 {
+            return Err(Av1ParserError::UnexpectedEndOfObu);
+        }
+        self.position += bits;
+
+        Ok(())
+    }
+}
+
+/// `leb128()`, unsigned little-endian variable length integer. Returns the value and the number
+/// of bytes it occupies, or `None` if `bytes` end before the whole value is read.
+pub(crate) fn leb128(bytes: &[u8]) -> Result<Option<(u64, usize)>, Av1ParserError> {
+    let mut value = 0u64;
+    for i in 0..8 {
+        let Some(&byte) = bytes.get(i) else {
+            return Ok(None);
+        };
+
+        value |= ((byte & 0x7f) as u64) << (i * 7);
+        if byte & 0x80 == 0 {
+            return Ok(Some((value, i + 1)));
+        }
+    }
+
+    Err(Av1ParserError::InvalidValue {
+        name: "leb128 length",
+        value: 8,
+    })
+}
+
+/// `FloorLog2(value)`
+pub(crate) fn floor_log2(value: u32) -> u32 {
+    u32::BITS - 1 - value.max(1).leading_zeros()
+}
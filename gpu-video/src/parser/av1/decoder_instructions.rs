@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use crate::parser::reference_manager::{ReferenceId, ReferenceManagementError};
+
+use super::{
+    FrameHeader, FrameUnit, SequenceHeader,
+    reference_manager::{DecodeInformation, ReferenceContext},
+};
+
+#[derive(Debug, Clone)]
+pub(crate) enum DecoderInstruction {
+    Decode {
+        decode_info: DecodeInformation,
+        reference_id: ReferenceId,
+    },
+
+    /// Shown key frame, which starts a new coded video sequence. All previous references are
+    /// dropped.
+    KeyFrame {
+        decode_info: DecodeInformation,
+        reference_id: ReferenceId,
+    },
+
+    /// Outputs a previously decoded frame (`show_existing_frame`).
+    ShowExisting {
+        reference_id: ReferenceId,
+        header: Arc<FrameHeader>,
+        pts: Option<u64>,
+        dts: Option<u64>,
+        corrupted: bool,
+    },
+
+    Drop {
+        reference_ids: Vec<ReferenceId>,
+    },
+
+    SequenceHeader(Arc<SequenceHeader>),
+}
+
+pub(crate) fn compile_to_decoder_instructions(
+    reference_ctx: &mut ReferenceContext,
+    frames: Vec<FrameUnit>,
+) -> Result<Vec<DecoderInstruction>, ReferenceManagementError> {
+    let mut instructions = Vec::new();
+    for frame in frames {
+        let mut inst = reference_ctx.put_frame(frame)?;
+        instructions.append(&mut inst);
+    }
+
+    Ok(instructions)
+}
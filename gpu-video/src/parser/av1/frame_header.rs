@@ -0,0 +1,1733 @@
+use std::sync::Arc;
+
+use super::{
+    Av1ParserError,
+    bitstream::BitReader,
+    obu_parser::ObuHeader,
+    sequence_header::{SELECT_INTEGER_MV, SELECT_SCREEN_CONTENT_TOOLS, SequenceHeader},
+};
+
+pub(crate) const NUM_REF_FRAMES: usize = 8;
+pub(crate) const REFS_PER_FRAME: usize = 7;
+pub(crate) const PRIMARY_REF_NONE: u8 = 7;
+
+const LAST_FRAME: usize = 1;
+const LAST2_FRAME: usize = 2;
+const LAST3_FRAME: usize = 3;
+const GOLDEN_FRAME: usize = 4;
+const BWDREF_FRAME: usize = 5;
+const ALTREF2_FRAME: usize = 6;
+const ALTREF_FRAME: usize = 7;
+
+const MAX_SEGMENTS: usize = 8;
+const SEG_LVL_MAX: usize = 8;
+const SEG_LVL_ALT_Q: usize = 0;
+const SEGMENTATION_FEATURE_BITS: [u32; SEG_LVL_MAX] = [8, 6, 6, 6, 6, 3, 0, 0];
+const SEGMENTATION_FEATURE_SIGNED: [bool; SEG_LVL_MAX] =
+    [true, true, true, true, true, false, false, false];
+const SEGMENTATION_FEATURE_MAX: [i32; SEG_LVL_MAX] = [255, 63, 63, 63, 63, 7, 0, 0];
+
+const SUPERRES_NUM: u32 = 8;
+const SUPERRES_DENOM_MIN: u32 = 9;
+const SUPERRES_DENOM_BITS: u32 = 3;
+
+const MAX_TILE_WIDTH: u32 = 4096;
+const MAX_TILE_AREA: u32 = 4096 * 2304;
+const MAX_TILE_ROWS: u32 = 64;
+const MAX_TILE_COLS: u32 = 64;
+
+const SWITCHABLE: u8 = 4;
+const RESTORE_SWITCHABLE: u8 = 3;
+const RESTORATION_TILESIZE_MAX: u16 = 256;
+const REMAP_LR_TYPE: [u8; 4] = [0, RESTORE_SWITCHABLE, 1, 2];
+
+const ONLY_4X4: u8 = 0;
+const TX_MODE_LARGEST: u8 = 1;
+const TX_MODE_SELECT: u8 = 2;
+
+const IDENTITY: u8 = 0;
+const TRANSLATION: u8 = 1;
+const ROTZOOM: u8 = 2;
+const AFFINE: u8 = 3;
+const WARPEDMODEL_PREC_BITS: u32 = 16;
+const GM_ABS_TRANS_BITS: u32 = 12;
+const GM_ABS_TRANS_ONLY_BITS: u32 = 9;
+const GM_ABS_ALPHA_BITS: u32 = 12;
+const GM_ALPHA_PREC_BITS: u32 = 15;
+const GM_TRANS_PREC_BITS: u32 = 6;
+const GM_TRANS_ONLY_PREC_BITS: u32 = 3;
+
+const DEFAULT_LOOP_FILTER_REF_DELTAS: [i8; NUM_REF_FRAMES] = [1, 0, 0, 0, -1, 0, -1, -1];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameType {
+    Key,
+    Inter,
+    IntraOnly,
+    Switch,
+}
+
+impl FrameType {
+    fn from_bits(bits: u32) -> Self {
+        match bits {
+            0 => Self::Key,
+            1 => Self::Inter,
+            2 => Self::IntraOnly,
+            _ => Self::Switch,
+        }
+    }
+
+    /// `FrameIsIntra`
+    pub fn is_intra(&self) -> bool {
+        matches!(self, Self::Key | Self::IntraOnly)
+    }
+}
+
+/// Uncompressed frame header, as described in section 5.9 of the AV1 spec. Values which are
+/// loaded from reference frames or derived by the decoding process are stored after
+/// resolving them, e.g. [`SegmentationParams::feature_data`] contains the data used to decode
+/// the frame, even if it was not coded in this header.
+#[derive(Debug, Clone)]
+pub struct FrameHeader {
+    pub sequence_header: Arc<SequenceHeader>,
+    pub temporal_id: u8,
+    pub spatial_id: u8,
+    pub show_existing_frame: bool,
+    pub frame_to_show_map_idx: u8,
+    pub frame_type: FrameType,
+    pub show_frame: bool,
+    pub showable_frame: bool,
+    pub error_resilient_mode: bool,
+    pub disable_cdf_update: bool,
+    pub allow_screen_content_tools: bool,
+    pub force_integer_mv: bool,
+    pub current_frame_id: u32,
+    pub frame_size_override_flag: bool,
+    pub order_hint: u8,
+    pub primary_ref_frame: u8,
+    pub buffer_removal_time_present_flag: bool,
+    pub refresh_frame_flags: u8,
+    pub frame_size: FrameSize,
+    pub allow_intrabc: bool,
+    pub frame_refs_short_signaling: bool,
+    pub ref_frame_idx: [u8; REFS_PER_FRAME],
+    /// `expectedFrameId`, indexed by the reference frame name (`LAST_FRAME` is 1)
+    pub expected_frame_id: [u32; NUM_REF_FRAMES],
+    pub allow_high_precision_mv: bool,
+    pub is_filter_switchable: bool,
+    pub interpolation_filter: u8,
+    pub is_motion_mode_switchable: bool,
+    pub use_ref_frame_mvs: bool,
+    /// `OrderHints`, indexed by the reference frame name (`LAST_FRAME` is 1)
+    pub order_hints: [u8; NUM_REF_FRAMES],
+    /// `RefFrameSignBias`, indexed by the reference frame name (`LAST_FRAME` is 1)
+    pub ref_frame_sign_bias: [bool; NUM_REF_FRAMES],
+    pub disable_frame_end_update_cdf: bool,
+    pub tile_info: TileInfo,
+    pub quantization: QuantizationParams,
+    pub segmentation: SegmentationParams,
+    pub delta_q_present: bool,
+    pub delta_q_res: u8,
+    pub delta_lf_present: bool,
+    pub delta_lf_res: u8,
+    pub delta_lf_multi: bool,
+    pub coded_lossless: bool,
+    pub all_lossless: bool,
+    pub loop_filter: LoopFilterParams,
+    pub cdef: CdefParams,
+    pub loop_restoration: LoopRestorationParams,
+    pub tx_mode: u8,
+    pub reference_select: bool,
+    pub skip_mode_present: bool,
+    /// `SkipModeFrame`, only meaningful if [`FrameHeader::skip_mode_present`] is set
+    pub skip_mode_frame: [u8; 2],
+    pub allow_warped_motion: bool,
+    pub reduced_tx_set: bool,
+    pub global_motion: GlobalMotionParams,
+    pub film_grain: FilmGrainParams,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameSize {
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub upscaled_width: u32,
+    pub render_width: u32,
+    pub render_height: u32,
+    pub render_and_frame_size_different: bool,
+    pub use_superres: bool,
+    pub coded_denom: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileInfo {
+    pub uniform_tile_spacing_flag: bool,
+    pub tile_cols: u16,
+    pub tile_rows: u16,
+    pub tile_cols_log2: u8,
+    pub tile_rows_log2: u8,
+    /// `MiColStarts`, `tile_cols + 1` entries
+    pub mi_col_starts: Vec<u16>,
+    /// `MiRowStarts`, `tile_rows + 1` entries
+    pub mi_row_starts: Vec<u16>,
+    pub width_in_sbs_minus_1: Vec<u16>,
+    pub height_in_sbs_minus_1: Vec<u16>,
+    pub context_update_tile_id: u16,
+    pub tile_size_bytes_minus_1: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantizationParams {
+    pub base_q_idx: u8,
+    pub delta_q_y_dc: i8,
+    pub diff_uv_delta: bool,
+    pub delta_q_u_dc: i8,
+    pub delta_q_u_ac: i8,
+    pub delta_q_v_dc: i8,
+    pub delta_q_v_ac: i8,
+    pub using_qmatrix: bool,
+    pub qm_y: u8,
+    pub qm_u: u8,
+    pub qm_v: u8,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SegmentationParams {
+    pub segmentation_enabled: bool,
+    pub segmentation_update_map: bool,
+    pub segmentation_temporal_update: bool,
+    pub segmentation_update_data: bool,
+    /// `FeatureEnabled[segment][feature]`
+    pub feature_enabled: [[bool; SEG_LVL_MAX]; MAX_SEGMENTS],
+    /// `FeatureData[segment][feature]`
+    pub feature_data: [[i16; SEG_LVL_MAX]; MAX_SEGMENTS],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopFilterParams {
+    pub loop_filter_level: [u8; 4],
+    pub loop_filter_sharpness: u8,
+    pub loop_filter_delta_enabled: bool,
+    pub loop_filter_delta_update: bool,
+    pub update_ref_delta: [bool; NUM_REF_FRAMES],
+    pub loop_filter_ref_deltas: [i8; NUM_REF_FRAMES],
+    pub update_mode_delta: [bool; 2],
+    pub loop_filter_mode_deltas: [i8; 2],
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CdefParams {
+    pub cdef_damping_minus_3: u8,
+    pub cdef_bits: u8,
+    pub cdef_y_pri_strength: [u8; 8],
+    /// As coded in the bitstream, the value 3 means a strength of 4.
+    pub cdef_y_sec_strength: [u8; 8],
+    pub cdef_uv_pri_strength: [u8; 8],
+    /// As coded in the bitstream, the value 3 means a strength of 4.
+    pub cdef_uv_sec_strength: [u8; 8],
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoopRestorationParams {
+    /// `FrameRestorationType`: 0 - none, 1 - wiener, 2 - sgrproj, 3 - switchable
+    pub frame_restoration_type: [u8; 3],
+    /// `LoopRestorationSize` in samples
+    pub loop_restoration_size: [u16; 3],
+    pub uses_lr: bool,
+    pub uses_chroma_lr: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalMotionParams {
+    /// `GmType`, indexed by the reference frame name (`LAST_FRAME` is 1)
+    pub gm_type: [u8; NUM_REF_FRAMES],
+    /// `gm_params`, indexed by the reference frame name (`LAST_FRAME` is 1)
+    pub gm_params: [[i32; 6]; NUM_REF_FRAMES],
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilmGrainParams {
+    pub apply_grain: bool,
+    pub grain_seed: u16,
+    pub update_grain: bool,
+    pub film_grain_params_ref_idx: u8,
+    pub num_y_points: u8,
+    pub point_y_value: [u8; 14],
+    pub point_y_scaling: [u8; 14],
+    pub chroma_scaling_from_luma: bool,
+    pub num_cb_points: u8,
+    pub point_cb_value: [u8; 10],
+    pub point_cb_scaling: [u8; 10],
+    pub num_cr_points: u8,
+    pub point_cr_value: [u8; 10],
+    pub point_cr_scaling: [u8; 10],
+    pub grain_scaling_minus_8: u8,
+    pub ar_coeff_lag: u8,
+    pub ar_coeffs_y_plus_128: [u8; 24],
+    pub ar_coeffs_cb_plus_128: [u8; 25],
+    pub ar_coeffs_cr_plus_128: [u8; 25],
+    pub ar_coeff_shift_minus_6: u8,
+    pub grain_scale_shift: u8,
+    pub cb_mult: u8,
+    pub cb_luma_mult: u8,
+    pub cb_offset: u16,
+    pub cr_mult: u8,
+    pub cr_luma_mult: u8,
+    pub cr_offset: u16,
+    pub overlap_flag: bool,
+    pub clip_to_restricted_range: bool,
+}
+
+/// State of the reference frame slots kept by the decoding process. Frames are stored with
+/// the headers they were decoded with, which contain all values saved by the reference frame
+/// update process (section 7.20 of the AV1 spec).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReferenceFrames {
+    /// `None` if `RefValid` is 0
+    frames: [Option<Arc<FrameHeader>>; NUM_REF_FRAMES],
+    /// `RefOrderHint`
+    order_hints: [u8; NUM_REF_FRAMES],
+}
+
+impl ReferenceFrames {
+    /// Reference frame update process, performed after the whole frame is decoded.
+    pub(crate) fn update(&mut self, header: &Arc<FrameHeader>) {
+        let frame = match header.show_existing_frame {
+            true => match &self.frames[header.frame_to_show_map_idx as usize] {
+                Some(frame) => frame.clone(),
+                None => return,
+            },
+            false => header.clone(),
+        };
+
+        for i in 0..NUM_REF_FRAMES {
+            if (header.refresh_frame_flags >> i) & 1 == 1 {
+                self.order_hints[i] = frame.order_hint;
+                self.frames[i] = Some(frame.clone());
+            }
+        }
+    }
+
+    fn get(&self, idx: u8) -> Result<&Arc<FrameHeader>, Av1ParserError> {
+        self.frames[idx as usize]
+            .as_ref()
+            .ok_or(Av1ParserError::MissingReferenceFrame(idx))
+    }
+}
+
+impl FrameHeader {
+    /// `uncompressed_header()`. Invalidating reference frames, which happens while parsing the
+    /// header, is applied to `refs`.
+    pub(crate) fn parse(
+        r: &mut BitReader,
+        obu_header: &ObuHeader,
+        seq: &Arc<SequenceHeader>,
+        refs: &mut ReferenceFrames,
+    ) -> Result<Self, Av1ParserError> {
+        let id_len = seq.frame_id_length();
+        let all_frames = u8::MAX;
+
+        let show_existing_frame;
+        let frame_type;
+        let show_frame;
+        let showable_frame;
+        let error_resilient_mode;
+        if seq.reduced_still_picture_header {
+            show_existing_frame = false;
+            frame_type = FrameType::Key;
+            show_frame = true;
+            showable_frame = false;
+            error_resilient_mode = true;
+        } else {
+            show_existing_frame = r.flag()?;
+            if show_existing_frame {
+                let frame_to_show_map_idx = r.f(3)? as u8;
+                read_temporal_point_info(r, seq)?;
+
+                if seq.frame_id_numbers_present_flag {
+                    // display_frame_id
+                    r.f(id_len)?;
+                }
+
+                let mut header = (**refs.get(frame_to_show_map_idx)?).clone();
+                header.show_existing_frame = true;
+                header.frame_to_show_map_idx = frame_to_show_map_idx;
+                header.show_frame = true;
+                header.showable_frame = false;
+                header.temporal_id = obu_header.temporal_id;
+                header.spatial_id = obu_header.spatial_id;
+                header.refresh_frame_flags = match header.frame_type {
+                    FrameType::Key => all_frames,
+                    _ => 0,
+                };
+
+                return Ok(header);
+            }
+
+            frame_type = FrameType::from_bits(r.f(2)?);
+            show_frame = r.flag()?;
+            if show_frame {
+                read_temporal_point_info(r, seq)?;
+            }
+
+            showable_frame = match show_frame {
+                true => frame_type != FrameType::Key,
+                false => r.flag()?,
+            };
+
+            error_resilient_mode = match frame_type {
+                FrameType::Switch => true,
+                FrameType::Key if show_frame => true,
+                _ => r.flag()?,
+            };
+        }
+
+        if frame_type == FrameType::Key && show_frame {
+            *refs = ReferenceFrames::default();
+        }
+
+        let frame_is_intra = frame_type.is_intra();
+        let disable_cdf_update = r.flag()?;
+        let allow_screen_content_tools =
+            match seq.seq_force_screen_content_tools == SELECT_SCREEN_CONTENT_TOOLS {
+                true => r.flag()?,
+                false => seq.seq_force_screen_content_tools == 1,
+            };
+
+        let mut force_integer_mv = match allow_screen_content_tools {
+            true => match seq.seq_force_integer_mv == SELECT_INTEGER_MV {
+                true => r.flag()?,
+                false => seq.seq_force_integer_mv == 1,
+            },
+            false => false,
+        };
+        if frame_is_intra {
+            force_integer_mv = true;
+        }
+
+        let mut current_frame_id = 0;
+        if seq.frame_id_numbers_present_flag {
+            current_frame_id = r.f(id_len)?;
+            mark_ref_frames(seq, current_frame_id, refs);
+        }
+
+        let frame_size_override_flag = match frame_type {
+            FrameType::Switch => true,
+            _ if seq.reduced_still_picture_header => false,
+            _ => r.flag()?,
+        };
+
+        let order_hint = r.f(seq.order_hint_bits())? as u8;
+        let primary_ref_frame = match frame_is_intra || error_resilient_mode {
+            true => PRIMARY_REF_NONE,
+            false => r.f(3)? as u8,
+        };
+
+        let mut buffer_removal_time_present_flag = false;
+        if let Some(decoder_model_info) = &seq.decoder_model_info {
+            buffer_removal_time_present_flag = r.flag()?;
+            if buffer_removal_time_present_flag {
+                for op in &seq.operating_points {
+                    if !op.decoder_model_present_for_this_op {
+                        continue;
+                    }
+
+                    let idc = op.operating_point_idc;
+                    let in_temporal_layer = (idc >> obu_header.temporal_id) & 1 == 1;
+                    let in_spatial_layer = (idc >> (obu_header.spatial_id + 8)) & 1 == 1;
+                    if idc == 0 || (in_temporal_layer && in_spatial_layer) {
+                        // buffer_removal_time
+                        r.f(decoder_model_info.buffer_removal_time_length_minus_1 as u32 + 1)?;
+                    }
+                }
+            }
+        }
+
+        let refresh_frame_flags = match frame_type {
+            FrameType::Switch => all_frames,
+            FrameType::Key if show_frame => all_frames,
+            _ => r.f(8)? as u8,
+        };
+
+        if (!frame_is_intra || refresh_frame_flags != all_frames)
+            && error_resilient_mode
+            && seq.enable_order_hint
+        {
+            for i in 0..NUM_REF_FRAMES {
+                let ref_order_hint = r.f(seq.order_hint_bits())? as u8;
+                if ref_order_hint != refs.order_hints[i] {
+                    refs.frames[i] = None;
+                    refs.order_hints[i] = ref_order_hint;
+                }
+            }
+        }
+
+        let mut frame_size;
+        let mut allow_intrabc = false;
+        let mut frame_refs_short_signaling = false;
+        let mut ref_frame_idx = [0; REFS_PER_FRAME];
+        let mut expected_frame_id = [0; NUM_REF_FRAMES];
+        let mut allow_high_precision_mv = false;
+        let mut is_filter_switchable = false;
+        let mut interpolation_filter = 0;
+        let mut is_motion_mode_switchable = false;
+        let mut use_ref_frame_mvs = false;
+        let mut order_hints = [0; NUM_REF_FRAMES];
+        let mut ref_frame_sign_bias = [false; NUM_REF_FRAMES];
+        if frame_is_intra {
+            frame_size = FrameSize::parse(r, seq, frame_size_override_flag)?;
+            frame_size.parse_render_size(r)?;
+            if allow_screen_content_tools && frame_size.upscaled_width == frame_size.frame_width {
+                allow_intrabc = r.flag()?;
+            }
+        } else {
+            if seq.enable_order_hint {
+                frame_refs_short_signaling = r.flag()?;
+                if frame_refs_short_signaling {
+                    let last_frame_idx = r.f(3)? as u8;
+                    let gold_frame_idx = r.f(3)? as u8;
+                    ref_frame_idx =
+                        set_frame_refs(seq, refs, order_hint, last_frame_idx, gold_frame_idx);
+                }
+            }
+
+            for i in 0..REFS_PER_FRAME {
+                if !frame_refs_short_signaling {
+                    ref_frame_idx[i] = r.f(3)? as u8;
+                }
+
+                if seq.frame_id_numbers_present_flag {
+                    let delta_frame_id_minus_1 =
+                        r.f(seq.delta_frame_id_length_minus_2 as u32 + 2)?;
+                    let delta_frame_id = delta_frame_id_minus_1 + 1;
+                    expected_frame_id[LAST_FRAME + i] =
+                        (current_frame_id + (1 << id_len) - delta_frame_id) % (1 << id_len);
+                }
+            }
+
+            for &idx in &ref_frame_idx {
+                refs.get(idx)?;
+            }
+
+            frame_size = match frame_size_override_flag && !error_resilient_mode {
+                true => FrameSize::parse_with_refs(r, seq, refs, &ref_frame_idx)?,
+                false => {
+                    let mut frame_size = FrameSize::parse(r, seq, frame_size_override_flag)?;
+                    frame_size.parse_render_size(r)?;
+                    frame_size
+                }
+            };
+
+            allow_high_precision_mv = match force_integer_mv {
+                true => false,
+                false => r.flag()?,
+            };
+
+            is_filter_switchable = r.flag()?;
+            interpolation_filter = match is_filter_switchable {
+                true => SWITCHABLE,
+                false => r.f(2)? as u8,
+            };
+
+            is_motion_mode_switchable = r.flag()?;
+            use_ref_frame_mvs = match error_resilient_mode || !seq.enable_ref_frame_mvs {
+                true => false,
+                false => r.flag()?,
+            };
+
+            for i in 0..REFS_PER_FRAME {
+                let hint = refs.order_hints[ref_frame_idx[i] as usize];
+                order_hints[LAST_FRAME + i] = hint;
+                ref_frame_sign_bias[LAST_FRAME + i] =
+                    seq.enable_order_hint && get_relative_dist(seq, hint, order_hint) > 0;
+            }
+        }
+
+        let disable_frame_end_update_cdf =
+            match seq.reduced_still_picture_header || disable_cdf_update {
+                true => true,
+                false => r.flag()?,
+            };
+
+        // load_previous()
+        let previous_frame = match primary_ref_frame {
+            PRIMARY_REF_NONE => None,
+            primary_ref_frame => Some(refs.get(ref_frame_idx[primary_ref_frame as usize])?),
+        };
+
+        let tile_info = TileInfo::parse(r, seq, &frame_size)?;
+        let quantization = QuantizationParams::parse(r, seq)?;
+        let segmentation = SegmentationParams::parse(
+            r,
+            primary_ref_frame,
+            previous_frame.map(|frame| &frame.segmentation),
+        )?;
+
+        let mut delta_q_present = false;
+        let mut delta_q_res = 0;
+        if quantization.base_q_idx > 0 {
+            delta_q_present = r.flag()?;
+        }
+        if delta_q_present {
+            delta_q_res = r.f(2)? as u8;
+        }
+
+        let mut delta_lf_present = false;
+        let mut delta_lf_res = 0;
+        let mut delta_lf_multi = false;
+        if delta_q_present {
+            if !allow_intrabc {
+                delta_lf_present = r.flag()?;
+            }
+            if delta_lf_present {
+                delta_lf_res = r.f(2)? as u8;
+                delta_lf_multi = r.flag()?;
+            }
+        }
+
+        let coded_lossless = (0..MAX_SEGMENTS).all(|segment_id| {
+            quantization.qindex(&segmentation, segment_id) == 0
+                && quantization.delta_q_y_dc == 0
+                && quantization.delta_q_u_ac == 0
+                && quantization.delta_q_u_dc == 0
+                && quantization.delta_q_v_ac == 0
+                && quantization.delta_q_v_dc == 0
+        });
+        let all_lossless = coded_lossless && frame_size.frame_width == frame_size.upscaled_width;
+
+        let loop_filter = LoopFilterParams::parse(
+            r,
+            seq,
+            coded_lossless || allow_intrabc,
+            previous_frame.map(|frame| &frame.loop_filter),
+        )?;
+        let cdef = CdefParams::parse(r, seq, coded_lossless || allow_intrabc)?;
+        let loop_restoration = LoopRestorationParams::parse(r, seq, all_lossless || allow_intrabc)?;
+
+        let tx_mode = match coded_lossless {
+            true => ONLY_4X4,
+            false => match r.flag()? {
+                true => TX_MODE_SELECT,
+                false => TX_MODE_LARGEST,
+            },
+        };
+
+        let reference_select = match frame_is_intra {
+            true => false,
+            false => r.flag()?,
+        };
+
+        let skip_mode_frame = match frame_is_intra || !reference_select || !seq.enable_order_hint {
+            true => None,
+            false => skip_mode_frames(seq, refs, order_hint, &ref_frame_idx),
+        };
+        let skip_mode_present = match skip_mode_frame {
+            Some(_) => r.flag()?,
+            None => false,
+        };
+
+        let allow_warped_motion =
+            match frame_is_intra || error_resilient_mode || !seq.enable_warped_motion {
+                true => false,
+                false => r.flag()?,
+            };
+
+        let reduced_tx_set = r.flag()?;
+        let global_motion = GlobalMotionParams::parse(
+            r,
+            frame_is_intra,
+            allow_high_precision_mv,
+            previous_frame.map(|frame| &frame.global_motion),
+        )?;
+
+        let film_grain = match seq.film_grain_params_present && (show_frame || showable_frame) {
+            true => FilmGrainParams::parse(r, seq, frame_type, refs)?,
+            false => FilmGrainParams::default(),
+        };
+
+        Ok(Self {
+            sequence_header: seq.clone(),
+            temporal_id: obu_header.temporal_id,
+            spatial_id: obu_header.spatial_id,
+            show_existing_frame,
+            frame_to_show_map_idx: 0,
+            frame_type,
+            show_frame,
+            showable_frame,
+            error_resilient_mode,
+            disable_cdf_update,
+            allow_screen_content_tools,
+            force_integer_mv,
+            current_frame_id,
+            frame_size_override_flag,
+            order_hint,
+            primary_ref_frame,
+            buffer_removal_time_present_flag,
+            refresh_frame_flags,
+            frame_size,
+            allow_intrabc,
+            frame_refs_short_signaling,
+            ref_frame_idx,
+            expected_frame_id,
+            allow_high_precision_mv,
+            is_filter_switchable,
+            interpolation_filter,
+            is_motion_mode_switchable,
+            use_ref_frame_mvs,
+            order_hints,
+            ref_frame_sign_bias,
+            disable_frame_end_update_cdf,
+            tile_info,
+            quantization,
+            segmentation,
+            delta_q_present,
+            delta_q_res,
+            delta_lf_present,
+            delta_lf_res,
+            delta_lf_multi,
+            coded_lossless,
+            all_lossless,
+            loop_filter,
+            cdef,
+            loop_restoration,
+            tx_mode,
+            reference_select,
+            skip_mode_present,
+            skip_mode_frame: skip_mode_frame.unwrap_or_default(),
+            allow_warped_motion,
+            reduced_tx_set,
+            global_motion,
+            film_grain,
+        })
+    }
+
+    pub fn is_intra(&self) -> bool {
+        self.frame_type.is_intra()
+    }
+}
+
+/// `temporal_point_info()`, skips `frame_presentation_time` if it is present.
+fn read_temporal_point_info(r: &mut BitReader, seq: &SequenceHeader) -> Result<(), Av1ParserError> {
+    let equal_picture_interval = seq
+        .timing_info
+        .as_ref()
+        .is_some_and(|timing_info| timing_info.equal_picture_interval);
+
+    if let Some(decoder_model_info) = &seq.decoder_model_info {
+        if !equal_picture_interval {
+            r.f(decoder_model_info.frame_presentation_time_length_minus_1 as u32 + 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `get_relative_dist()`
+fn get_relative_dist(seq: &SequenceHeader, a: u8, b: u8) -> i32 {
+    if !seq.enable_order_hint {
+        return 0;
+    }
+
+    let bits = seq.order_hint_bits();
+    let diff = a as i32 - b as i32;
+    let m = 1 << (bits - 1);
+    (diff & (m - 1)) - (diff & m)
+}
+
+/// `mark_ref_frames()`
+fn mark_ref_frames(seq: &SequenceHeader, current_frame_id: u32, refs: &mut ReferenceFrames) {
+    let id_len = seq.frame_id_length();
+    let diff_len = seq.delta_frame_id_length_minus_2 as u32 + 2;
+    for frame in refs.frames.iter_mut() {
+        let Some(ref_frame_id) = frame.as_ref().map(|frame| frame.current_frame_id) else {
+            continue;
+        };
+
+        let invalid = match current_frame_id > (1 << diff_len) {
+            true => {
+                ref_frame_id > current_frame_id || ref_frame_id < current_frame_id - (1 << diff_len)
+            }
+            false => {
+                ref_frame_id > current_frame_id
+                    && ref_frame_id < (1 << id_len) + current_frame_id - (1 << diff_len)
+            }
+        };
+
+        if invalid {
+            *frame = None;
+        }
+    }
+}
+
+/// `set_frame_refs()`, the frame reference selection process (section 7.8 of the AV1 spec).
+fn set_frame_refs(
+    seq: &SequenceHeader,
+    refs: &ReferenceFrames,
+    order_hint: u8,
+    last_frame_idx: u8,
+    gold_frame_idx: u8,
+) -> [u8; REFS_PER_FRAME] {
+    let mut ref_frame_idx = [None; REFS_PER_FRAME];
+    ref_frame_idx[LAST_FRAME - LAST_FRAME] = Some(last_frame_idx);
+    ref_frame_idx[GOLDEN_FRAME - LAST_FRAME] = Some(gold_frame_idx);
+
+    let mut used_frame = [false; NUM_REF_FRAMES];
+    used_frame[last_frame_idx as usize] = true;
+    used_frame[gold_frame_idx as usize] = true;
+
+    let cur_frame_hint = 1 << (seq.order_hint_bits() - 1);
+    let shifted_order_hints: [i32; NUM_REF_FRAMES] = std::array::from_fn(|i| {
+        cur_frame_hint + get_relative_dist(seq, refs.order_hints[i], order_hint)
+    });
+
+    // find_latest_backward(), find_earliest_backward() and find_latest_forward()
+    let find = |used_frame: &[bool; NUM_REF_FRAMES], backward: bool, latest: bool| {
+        let mut result: Option<(usize, i32)> = None;
+        for (i, &hint) in shifted_order_hints.iter().enumerate() {
+            if used_frame[i] || (hint >= cur_frame_hint) != backward {
+                continue;
+            }
+
+            let better = match result {
+                None => true,
+                Some((_, best)) if latest => hint >= best,
+                Some((_, best)) => hint < best,
+            };
+            if better {
+                result = Some((i, hint));
+            }
+        }
+
+        result.map(|(i, _)| i as u8)
+    };
+
+    if let Some(i) = find(&used_frame, true, true) {
+        ref_frame_idx[ALTREF_FRAME - LAST_FRAME] = Some(i);
+        used_frame[i as usize] = true;
+    }
+
+    if let Some(i) = find(&used_frame, true, false) {
+        ref_frame_idx[BWDREF_FRAME - LAST_FRAME] = Some(i);
+        used_frame[i as usize] = true;
+    }
+
+    if let Some(i) = find(&used_frame, true, false) {
+        ref_frame_idx[ALTREF2_FRAME - LAST_FRAME] = Some(i);
+        used_frame[i as usize] = true;
+    }
+
+    for ref_frame in [
+        LAST2_FRAME,
+        LAST3_FRAME,
+        BWDREF_FRAME,
+        ALTREF2_FRAME,
+        ALTREF_FRAME,
+    ] {
+        if ref_frame_idx[ref_frame - LAST_FRAME].is_some() {
+            continue;
+        }
+
+        if let Some(i) = find(&used_frame, false, true) {
+            ref_frame_idx[ref_frame - LAST_FRAME] = Some(i);
+            used_frame[i as usize] = true;
+        }
+    }
+
+    let mut earliest: Option<(u8, i32)> = None;
+    for (i, &hint) in shifted_order_hints.iter().enumerate() {
+        if earliest.is_none_or(|(_, earliest_hint)| hint < earliest_hint) {
+            earliest = Some((i as u8, hint));
+        }
+    }
+    let earliest = earliest.map(|(i, _)| i).unwrap_or(0);
+
+    ref_frame_idx.map(|idx| idx.unwrap_or(earliest))
+}
+
+/// Returns `SkipModeFrame` if skip mode is allowed (`skipModeAllowed`).
+fn skip_mode_frames(
+    seq: &SequenceHeader,
+    refs: &ReferenceFrames,
+    order_hint: u8,
+    ref_frame_idx: &[u8; REFS_PER_FRAME],
+) -> Option<[u8; 2]> {
+    let mut forward: Option<(usize, u8)> = None;
+    let mut backward: Option<(usize, u8)> = None;
+    for (i, &idx) in ref_frame_idx.iter().enumerate() {
+        let ref_hint = refs.order_hints[idx as usize];
+        let dist = get_relative_dist(seq, ref_hint, order_hint);
+        if dist < 0 {
+            if forward.is_none_or(|(_, hint)| get_relative_dist(seq, ref_hint, hint) > 0) {
+                forward = Some((i, ref_hint));
+            }
+        } else if dist > 0
+            && backward.is_none_or(|(_, hint)| get_relative_dist(seq, ref_hint, hint) < 0)
+        {
+            backward = Some((i, ref_hint));
+        }
+    }
+
+    let (forward_idx, forward_hint) = forward?;
+    let second_idx = match backward {
+        Some((backward_idx, _)) => backward_idx,
+        None => {
+            let mut second_forward: Option<(usize, u8)> = None;
+            for (i, &idx) in ref_frame_idx.iter().enumerate() {
+                let ref_hint = refs.order_hints[idx as usize];
+                if get_relative_dist(seq, ref_hint, forward_hint) < 0
+                    && second_forward
+                        .is_none_or(|(_, hint)| get_relative_dist(seq, ref_hint, hint) > 0)
+                {
+                    second_forward = Some((i, ref_hint));
+                }
+            }
+
+            second_forward?.0
+        }
+    };
+
+    Some([
+        (LAST_FRAME + forward_idx.min(second_idx)) as u8,
+        (LAST_FRAME + forward_idx.max(second_idx)) as u8,
+    ])
+}
+
+impl FrameSize {
+    /// `frame_size()`
+    fn parse(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        frame_size_override_flag: bool,
+    ) -> Result<Self, Av1ParserError> {
+        let (frame_width, frame_height) = match frame_size_override_flag {
+            true => (
+                r.f(seq.frame_width_bits_minus_1 as u32 + 1)? + 1,
+                r.f(seq.frame_height_bits_minus_1 as u32 + 1)? + 1,
+            ),
+            false => (seq.max_frame_width(), seq.max_frame_height()),
+        };
+
+        let mut frame_size = Self {
+            frame_width,
+            frame_height,
+            upscaled_width: frame_width,
+            render_width: frame_width,
+            render_height: frame_height,
+            render_and_frame_size_different: false,
+            use_superres: false,
+            coded_denom: 0,
+        };
+        frame_size.parse_superres_params(r, seq)?;
+
+        Ok(frame_size)
+    }
+
+    /// `frame_size_with_refs()`
+    fn parse_with_refs(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        refs: &ReferenceFrames,
+        ref_frame_idx: &[u8; REFS_PER_FRAME],
+    ) -> Result<Self, Av1ParserError> {
+        for &idx in ref_frame_idx {
+            let found_ref = r.flag()?;
+            if !found_ref {
+                continue;
+            }
+
+            let ref_size = &refs.get(idx)?.frame_size;
+            let mut frame_size = Self {
+                frame_width: ref_size.upscaled_width,
+                frame_height: ref_size.frame_height,
+                upscaled_width: ref_size.upscaled_width,
+                render_width: ref_size.render_width,
+                render_height: ref_size.render_height,
+                render_and_frame_size_different: ref_size.render_and_frame_size_different,
+                use_superres: false,
+                coded_denom: 0,
+            };
+            frame_size.parse_superres_params(r, seq)?;
+
+            return Ok(frame_size);
+        }
+
+        let mut frame_size = Self::parse(r, seq, true)?;
+        frame_size.parse_render_size(r)?;
+
+        Ok(frame_size)
+    }
+
+    /// `superres_params()`
+    fn parse_superres_params(
+        &mut self,
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+    ) -> Result<(), Av1ParserError> {
+        self.use_superres = match seq.enable_superres {
+            true => r.flag()?,
+            false => false,
+        };
+
+        let superres_denom = match self.use_superres {
+            true => {
+                self.coded_denom = r.f(SUPERRES_DENOM_BITS)? as u8;
+                self.coded_denom as u32 + SUPERRES_DENOM_MIN
+            }
+            false => SUPERRES_NUM,
+        };
+
+        self.upscaled_width = self.frame_width;
+        self.frame_width =
+            (self.upscaled_width * SUPERRES_NUM + (superres_denom / 2)) / superres_denom;
+
+        Ok(())
+    }
+
+    /// `render_size()`
+    fn parse_render_size(&mut self, r: &mut BitReader) -> Result<(), Av1ParserError> {
+        self.render_and_frame_size_different = r.flag()?;
+        match self.render_and_frame_size_different {
+            true => {
+                self.render_width = r.f(16)? + 1;
+                self.render_height = r.f(16)? + 1;
+            }
+            false => {
+                self.render_width = self.upscaled_width;
+                self.render_height = self.frame_height;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `MiCols`
+    pub fn mi_cols(&self) -> u32 {
+        2 * ((self.frame_width + 7) >> 3)
+    }
+
+    /// `MiRows`
+    pub fn mi_rows(&self) -> u32 {
+        2 * ((self.frame_height + 7) >> 3)
+    }
+}
+
+/// `tile_log2()`
+fn tile_log2(blk_size: u32, target: u32) -> u32 {
+    let mut k = 0;
+    while (blk_size << k) < target {
+        k += 1;
+    }
+
+    k
+}
+
+impl TileInfo {
+    /// `tile_info()`
+    fn parse(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        frame_size: &FrameSize,
+    ) -> Result<Self, Av1ParserError> {
+        let mi_cols = frame_size.mi_cols();
+        let mi_rows = frame_size.mi_rows();
+        let (sb_cols, sb_rows, sb_shift) = match seq.use_128x128_superblock {
+            true => ((mi_cols + 31) >> 5, (mi_rows + 31) >> 5, 5),
+            false => ((mi_cols + 15) >> 4, (mi_rows + 15) >> 4, 4),
+        };
+        let sb_size = sb_shift + 2;
+        let max_tile_width_sb = MAX_TILE_WIDTH >> sb_size;
+        let mut max_tile_area_sb = MAX_TILE_AREA >> (2 * sb_size);
+        let min_log2_tile_cols = tile_log2(max_tile_width_sb, sb_cols);
+        let max_log2_tile_cols = tile_log2(1, sb_cols.min(MAX_TILE_COLS));
+        let max_log2_tile_rows = tile_log2(1, sb_rows.min(MAX_TILE_ROWS));
+        let min_log2_tiles = min_log2_tile_cols.max(tile_log2(max_tile_area_sb, sb_rows * sb_cols));
+
+        let mut width_in_sbs = Vec::new();
+        let mut height_in_sbs = Vec::new();
+        let tile_cols_log2;
+        let tile_rows_log2;
+        let uniform_tile_spacing_flag = r.flag()?;
+        if uniform_tile_spacing_flag {
+            let mut cols_log2 = min_log2_tile_cols;
+            while cols_log2 < max_log2_tile_cols && r.flag()? {
+                cols_log2 += 1;
+            }
+
+            let tile_width_sb = (sb_cols + (1 << cols_log2) - 1) >> cols_log2;
+            let mut start_sb = 0;
+            while start_sb < sb_cols {
+                width_in_sbs.push(tile_width_sb.min(sb_cols - start_sb));
+                start_sb += tile_width_sb;
+            }
+
+            let min_log2_tile_rows = min_log2_tiles.saturating_sub(cols_log2);
+            let mut rows_log2 = min_log2_tile_rows;
+            while rows_log2 < max_log2_tile_rows && r.flag()? {
+                rows_log2 += 1;
+            }
+
+            let tile_height_sb = (sb_rows + (1 << rows_log2) - 1) >> rows_log2;
+            let mut start_sb = 0;
+            while start_sb < sb_rows {
+                height_in_sbs.push(tile_height_sb.min(sb_rows - start_sb));
+                start_sb += tile_height_sb;
+            }
+
+            tile_cols_log2 = cols_log2;
+            tile_rows_log2 = rows_log2;
+        } else {
+            let mut widest_tile_sb = 0;
+            let mut start_sb = 0;
+            while start_sb < sb_cols {
+                let max_width = (sb_cols - start_sb).min(max_tile_width_sb);
+                let size_sb = r.ns(max_width)? + 1;
+                widest_tile_sb = widest_tile_sb.max(size_sb);
+                width_in_sbs.push(size_sb);
+                start_sb += size_sb;
+            }
+
+            max_tile_area_sb = match min_log2_tiles {
+                0 => sb_rows * sb_cols,
+                min_log2_tiles => (sb_rows * sb_cols) >> (min_log2_tiles + 1),
+            };
+            let max_tile_height_sb = (max_tile_area_sb / widest_tile_sb).max(1);
+
+            let mut start_sb = 0;
+            while start_sb < sb_rows {
+                let max_height = (sb_rows - start_sb).min(max_tile_height_sb);
+                let size_sb = r.ns(max_height)? + 1;
+                height_in_sbs.push(size_sb);
+                start_sb += size_sb;
+            }
+
+            tile_cols_log2 = tile_log2(1, width_in_sbs.len() as u32);
+            tile_rows_log2 = tile_log2(1, height_in_sbs.len() as u32);
+        }
+
+        let starts = |sizes: &[u32], mi_count: u32| {
+            let mut starts = Vec::with_capacity(sizes.len() + 1);
+            let mut start_sb = 0;
+            for size in sizes {
+                starts.push((start_sb << sb_shift) as u16);
+                start_sb += size;
+            }
+            starts.push(mi_count as u16);
+            starts
+        };
+
+        let mut context_update_tile_id = 0;
+        let mut tile_size_bytes_minus_1 = 0;
+        if tile_cols_log2 > 0 || tile_rows_log2 > 0 {
+            context_update_tile_id = r.f(tile_rows_log2 + tile_cols_log2)? as u16;
+            tile_size_bytes_minus_1 = r.f(2)? as u8;
+        }
+
+        Ok(Self {
+            uniform_tile_spacing_flag,
+            tile_cols: width_in_sbs.len() as u16,
+            tile_rows: height_in_sbs.len() as u16,
+            tile_cols_log2: tile_cols_log2 as u8,
+            tile_rows_log2: tile_rows_log2 as u8,
+            mi_col_starts: starts(&width_in_sbs, mi_cols),
+            mi_row_starts: starts(&height_in_sbs, mi_rows),
+            width_in_sbs_minus_1: width_in_sbs.iter().map(|w| (w - 1) as u16).collect(),
+            height_in_sbs_minus_1: height_in_sbs.iter().map(|h| (h - 1) as u16).collect(),
+            context_update_tile_id,
+            tile_size_bytes_minus_1,
+        })
+    }
+
+    /// `NumTiles`
+    pub fn num_tiles(&self) -> u32 {
+        self.tile_cols as u32 * self.tile_rows as u32
+    }
+
+    /// `TileSizeBytes`
+    pub fn tile_size_bytes(&self) -> u32 {
+        self.tile_size_bytes_minus_1 as u32 + 1
+    }
+}
+
+/// `read_delta_q()`
+fn read_delta_q(r: &mut BitReader) -> Result<i8, Av1ParserError> {
+    let delta_coded = r.flag()?;
+    match delta_coded {
+        true => Ok(r.su(7)? as i8),
+        false => Ok(0),
+    }
+}
+
+impl QuantizationParams {
+    /// `quantization_params()`
+    fn parse(r: &mut BitReader, seq: &SequenceHeader) -> Result<Self, Av1ParserError> {
+        let base_q_idx = r.f(8)? as u8;
+        let delta_q_y_dc = read_delta_q(r)?;
+
+        let mut diff_uv_delta = false;
+        let mut delta_q_u_dc = 0;
+        let mut delta_q_u_ac = 0;
+        let mut delta_q_v_dc = 0;
+        let mut delta_q_v_ac = 0;
+        if seq.color_config.num_planes() > 1 {
+            if seq.color_config.separate_uv_delta_q {
+                diff_uv_delta = r.flag()?;
+            }
+
+            delta_q_u_dc = read_delta_q(r)?;
+            delta_q_u_ac = read_delta_q(r)?;
+            (delta_q_v_dc, delta_q_v_ac) = match diff_uv_delta {
+                true => (read_delta_q(r)?, read_delta_q(r)?),
+                false => (delta_q_u_dc, delta_q_u_ac),
+            };
+        }
+
+        let using_qmatrix = r.flag()?;
+        let mut qm_y = 0;
+        let mut qm_u = 0;
+        let mut qm_v = 0;
+        if using_qmatrix {
+            qm_y = r.f(4)? as u8;
+            qm_u = r.f(4)? as u8;
+            qm_v = match seq.color_config.separate_uv_delta_q {
+                true => r.f(4)? as u8,
+                false => qm_u,
+            };
+        }
+
+        Ok(Self {
+            base_q_idx,
+            delta_q_y_dc,
+            diff_uv_delta,
+            delta_q_u_dc,
+            delta_q_u_ac,
+            delta_q_v_dc,
+            delta_q_v_ac,
+            using_qmatrix,
+            qm_y,
+            qm_u,
+            qm_v,
+        })
+    }
+
+    /// `get_qindex(1, segmentId)`
+    fn qindex(&self, segmentation: &SegmentationParams, segment_id: usize) -> i32 {
+        let base_q_idx = self.base_q_idx as i32;
+        match segmentation.segmentation_enabled
+            && segmentation.feature_enabled[segment_id][SEG_LVL_ALT_Q]
+        {
+            true => (base_q_idx + segmentation.feature_data[segment_id][SEG_LVL_ALT_Q] as i32)
+                .clamp(0, 255),
+            false => base_q_idx,
+        }
+    }
+}
+
+impl SegmentationParams {
+    /// `segmentation_params()`, `previous` are the parameters loaded by `load_previous()`.
+    fn parse(
+        r: &mut BitReader,
+        primary_ref_frame: u8,
+        previous: Option<&SegmentationParams>,
+    ) -> Result<Self, Av1ParserError> {
+        let mut params = Self {
+            segmentation_enabled: r.flag()?,
+            ..Default::default()
+        };
+
+        if !params.segmentation_enabled {
+            return Ok(params);
+        }
+
+        if primary_ref_frame == PRIMARY_REF_NONE {
+            params.segmentation_update_map = true;
+            params.segmentation_temporal_update = false;
+            params.segmentation_update_data = true;
+        } else {
+            params.segmentation_update_map = r.flag()?;
+            if params.segmentation_update_map {
+                params.segmentation_temporal_update = r.flag()?;
+            }
+            params.segmentation_update_data = r.flag()?;
+        }
+
+        if !params.segmentation_update_data {
+            if let Some(previous) = previous {
+                params.feature_enabled = previous.feature_enabled;
+                params.feature_data = previous.feature_data;
+            }
+
+            return Ok(params);
+        }
+
+        for i in 0..MAX_SEGMENTS {
+            for j in 0..SEG_LVL_MAX {
+                let feature_enabled = r.flag()?;
+                params.feature_enabled[i][j] = feature_enabled;
+                if !feature_enabled {
+                    continue;
+                }
+
+                let bits_to_read = SEGMENTATION_FEATURE_BITS[j];
+                let limit = SEGMENTATION_FEATURE_MAX[j];
+                params.feature_data[i][j] = match SEGMENTATION_FEATURE_SIGNED[j] {
+                    true => r.su(1 + bits_to_read)?.clamp(-limit, limit) as i16,
+                    false => (r.f(bits_to_read)? as i32).clamp(0, limit) as i16,
+                };
+            }
+        }
+
+        Ok(params)
+    }
+}
+
+impl LoopFilterParams {
+    /// `loop_filter_params()`, `previous` are the parameters loaded by `load_previous()`.
+    fn parse(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        disabled: bool,
+        previous: Option<&LoopFilterParams>,
+    ) -> Result<Self, Av1ParserError> {
+        let mut params = Self {
+            loop_filter_level: [0; 4],
+            loop_filter_sharpness: 0,
+            loop_filter_delta_enabled: false,
+            loop_filter_delta_update: false,
+            update_ref_delta: [false; NUM_REF_FRAMES],
+            loop_filter_ref_deltas: DEFAULT_LOOP_FILTER_REF_DELTAS,
+            update_mode_delta: [false; 2],
+            loop_filter_mode_deltas: [0; 2],
+        };
+
+        if disabled {
+            return Ok(params);
+        }
+
+        if let Some(previous) = previous {
+            params.loop_filter_ref_deltas = previous.loop_filter_ref_deltas;
+            params.loop_filter_mode_deltas = previous.loop_filter_mode_deltas;
+        }
+
+        params.loop_filter_level[0] = r.f(6)? as u8;
+        params.loop_filter_level[1] = r.f(6)? as u8;
+        if seq.color_config.num_planes() > 1
+            && (params.loop_filter_level[0] != 0 || params.loop_filter_level[1] != 0)
+        {
+            params.loop_filter_level[2] = r.f(6)? as u8;
+            params.loop_filter_level[3] = r.f(6)? as u8;
+        }
+
+        params.loop_filter_sharpness = r.f(3)? as u8;
+        params.loop_filter_delta_enabled = r.flag()?;
+        if !params.loop_filter_delta_enabled {
+            return Ok(params);
+        }
+
+        params.loop_filter_delta_update = r.flag()?;
+        if !params.loop_filter_delta_update {
+            return Ok(params);
+        }
+
+        for i in 0..NUM_REF_FRAMES {
+            params.update_ref_delta[i] = r.flag()?;
+            if params.update_ref_delta[i] {
+                params.loop_filter_ref_deltas[i] = r.su(7)? as i8;
+            }
+        }
+
+        for i in 0..2 {
+            params.update_mode_delta[i] = r.flag()?;
+            if params.update_mode_delta[i] {
+                params.loop_filter_mode_deltas[i] = r.su(7)? as i8;
+            }
+        }
+
+        Ok(params)
+    }
+}
+
+impl CdefParams {
+    /// `cdef_params()`
+    fn parse(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        disabled: bool,
+    ) -> Result<Self, Av1ParserError> {
+        let mut params = Self::default();
+        if disabled || !seq.enable_cdef {
+            return Ok(params);
+        }
+
+        params.cdef_damping_minus_3 = r.f(2)? as u8;
+        params.cdef_bits = r.f(2)? as u8;
+        for i in 0..(1 << params.cdef_bits) {
+            params.cdef_y_pri_strength[i] = r.f(4)? as u8;
+            params.cdef_y_sec_strength[i] = r.f(2)? as u8;
+            if seq.color_config.num_planes() > 1 {
+                params.cdef_uv_pri_strength[i] = r.f(4)? as u8;
+                params.cdef_uv_sec_strength[i] = r.f(2)? as u8;
+            }
+        }
+
+        Ok(params)
+    }
+}
+
+impl LoopRestorationParams {
+    /// `lr_params()`
+    fn parse(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        disabled: bool,
+    ) -> Result<Self, Av1ParserError> {
+        let mut params = Self::default();
+        if disabled || !seq.enable_restoration {
+            return Ok(params);
+        }
+
+        for i in 0..seq.color_config.num_planes() {
+            let lr_type = r.f(2)? as usize;
+            params.frame_restoration_type[i] = REMAP_LR_TYPE[lr_type];
+            if params.frame_restoration_type[i] != 0 {
+                params.uses_lr = true;
+                if i > 0 {
+                    params.uses_chroma_lr = true;
+                }
+            }
+        }
+
+        if !params.uses_lr {
+            return Ok(params);
+        }
+
+        let mut lr_unit_shift = r.f(1)?;
+        if seq.use_128x128_superblock {
+            lr_unit_shift += 1;
+        } else if lr_unit_shift == 1 {
+            lr_unit_shift += r.f(1)?;
+        }
+
+        let color_config = &seq.color_config;
+        let lr_uv_shift =
+            match color_config.subsampling_x && color_config.subsampling_y && params.uses_chroma_lr
+            {
+                true => r.f(1)?,
+                false => 0,
+            };
+
+        let luma_size = RESTORATION_TILESIZE_MAX >> (2 - lr_unit_shift);
+        params.loop_restoration_size = [
+            luma_size,
+            luma_size >> lr_uv_shift,
+            luma_size >> lr_uv_shift,
+        ];
+
+        Ok(params)
+    }
+}
+
+impl Default for GlobalMotionParams {
+    fn default() -> Self {
+        let identity = [
+            0,
+            0,
+            1 << WARPEDMODEL_PREC_BITS,
+            0,
+            0,
+            1 << WARPEDMODEL_PREC_BITS,
+        ];
+        Self {
+            gm_type: [IDENTITY; NUM_REF_FRAMES],
+            gm_params: [identity; NUM_REF_FRAMES],
+        }
+    }
+}
+
+impl GlobalMotionParams {
+    /// `global_motion_params()`, `previous` are the parameters loaded by `load_previous()`.
+    fn parse(
+        r: &mut BitReader,
+        frame_is_intra: bool,
+        allow_high_precision_mv: bool,
+        previous: Option<&GlobalMotionParams>,
+    ) -> Result<Self, Av1ParserError> {
+        let mut params = Self::default();
+        if frame_is_intra {
+            return Ok(params);
+        }
+
+        let defaults = Self::default();
+        let prev_gm_params = previous.unwrap_or(&defaults).gm_params;
+        for ref_frame in LAST_FRAME..=ALTREF_FRAME {
+            let is_global = r.flag()?;
+            let gm_type = match is_global {
+                false => IDENTITY,
+                true => match r.flag()? {
+                    true => ROTZOOM,
+                    false => match r.flag()? {
+                        true => TRANSLATION,
+                        false => AFFINE,
+                    },
+                },
+            };
+            params.gm_type[ref_frame] = gm_type;
+
+            let mut read_param = |idx: usize| {
+                read_global_param(
+                    r,
+                    gm_type,
+                    idx,
+                    allow_high_precision_mv,
+                    prev_gm_params[ref_frame][idx],
+                )
+            };
+
+            let gm_params = &mut params.gm_params[ref_frame];
+            if gm_type >= ROTZOOM {
+                gm_params[2] = read_param(2)?;
+                gm_params[3] = read_param(3)?;
+                if gm_type == AFFINE {
+                    gm_params[4] = read_param(4)?;
+                    gm_params[5] = read_param(5)?;
+                } else {
+                    gm_params[4] = -gm_params[3];
+                    gm_params[5] = gm_params[2];
+                }
+            }
+
+            if gm_type >= TRANSLATION {
+                gm_params[0] = read_param(0)?;
+                gm_params[1] = read_param(1)?;
+            }
+        }
+
+        Ok(params)
+    }
+}
+
+/// `read_global_param()`
+fn read_global_param(
+    r: &mut BitReader,
+    gm_type: u8,
+    idx: usize,
+    allow_high_precision_mv: bool,
+    prev_gm_param: i32,
+) -> Result<i32, Av1ParserError> {
+    let (abs_bits, prec_bits) = match (idx < 2, gm_type) {
+        (true, TRANSLATION) => {
+            let not_high_precision = !allow_high_precision_mv as u32;
+            (
+                GM_ABS_TRANS_ONLY_BITS - not_high_precision,
+                GM_TRANS_ONLY_PREC_BITS - not_high_precision,
+            )
+        }
+        (true, _) => (GM_ABS_TRANS_BITS, GM_TRANS_PREC_BITS),
+        (false, _) => (GM_ABS_ALPHA_BITS, GM_ALPHA_PREC_BITS),
+    };
+
+    let prec_diff = WARPEDMODEL_PREC_BITS - prec_bits;
+    let (round, sub) = match idx % 3 == 2 {
+        true => (1 << WARPEDMODEL_PREC_BITS, 1 << prec_bits),
+        false => (0, 0),
+    };
+    let mx = 1 << abs_bits;
+    let reference = (prev_gm_param >> prec_diff) - sub;
+    let value = decode_signed_subexp_with_ref(r, -mx, mx + 1, reference)?;
+
+    Ok((value << prec_diff) + round)
+}
+
+/// `decode_signed_subexp_with_ref()`
+fn decode_signed_subexp_with_ref(
+    r: &mut BitReader,
+    low: i32,
+    high: i32,
+    reference: i32,
+) -> Result<i32, Av1ParserError> {
+    let x = decode_unsigned_subexp_with_ref(r, high - low, reference - low)?;
+    Ok(x + low)
+}
+
+/// `decode_unsigned_subexp_with_ref()`
+fn decode_unsigned_subexp_with_ref(
+    r: &mut BitReader,
+    mx: i32,
+    reference: i32,
+) -> Result<i32, Av1ParserError> {
+    let v = decode_subexp(r, mx)?;
+    match (reference << 1) <= mx {
+        true => Ok(inverse_recenter(reference, v)),
+        false => Ok(mx - 1 - inverse_recenter(mx - 1 - reference, v)),
+    }
+}
+
+/// `decode_subexp()`
+fn decode_subexp(r: &mut BitReader, num_syms: i32) -> Result<i32, Av1ParserError> {
+    let mut i = 0;
+    let mut mk = 0;
+    let k = 3;
+    loop {
+        let b2 = match i {
+            0 => k,
+            i => k + i - 1,
+        };
+        let a = 1 << b2;
+        if num_syms <= mk + 3 * a {
+            let subexp_final_bits = r.ns((num_syms - mk) as u32)? as i32;
+            return Ok(subexp_final_bits + mk);
+        }
+
+        let subexp_more_bits = r.flag()?;
+        if !subexp_more_bits {
+            let subexp_bits = r.f(b2 as u32)? as i32;
+            return Ok(subexp_bits + mk);
+        }
+
+        i += 1;
+        mk += a;
+    }
+}
+
+/// `inverse_recenter()`
+fn inverse_recenter(r: i32, v: i32) -> i32 {
+    if v > 2 * r {
+        v
+    } else if v & 1 == 1 {
+        r - ((v + 1) >> 1)
+    } else {
+        r + (v >> 1)
+    }
+}
+
+impl FilmGrainParams {
+    /// `film_grain_params()`, called only if film grain params are present for the frame.
+    fn parse(
+        r: &mut BitReader,
+        seq: &SequenceHeader,
+        frame_type: FrameType,
+        refs: &ReferenceFrames,
+    ) -> Result<Self, Av1ParserError> {
+        let mut params = Self {
+            apply_grain: r.flag()?,
+            ..Default::default()
+        };
+
+        if !params.apply_grain {
+            return Ok(params);
+        }
+
+        params.grain_seed = r.f(16)? as u16;
+        params.update_grain = match frame_type {
+            FrameType::Inter => r.flag()?,
+            _ => true,
+        };
+
+        if !params.update_grain {
+            let film_grain_params_ref_idx = r.f(3)? as u8;
+            let grain_seed = params.grain_seed;
+
+            // load_grain_params()
+            params = refs.get(film_grain_params_ref_idx)?.film_grain.clone();
+            params.grain_seed = grain_seed;
+            params.update_grain = false;
+            params.film_grain_params_ref_idx = film_grain_params_ref_idx;
+
+            return Ok(params);
+        }
+
+        params.num_y_points = r.f(4)? as u8;
+        if params.num_y_points > 14 {
+            return Err(Av1ParserError::InvalidValue {
+                name: "num_y_points",
+                value: params.num_y_points as i64,
+            });
+        }
+        for i in 0..params.num_y_points as usize {
+            params.point_y_value[i] = r.f(8)? as u8;
+            params.point_y_scaling[i] = r.f(8)? as u8;
+        }
+
+        let color_config = &seq.color_config;
+        params.chroma_scaling_from_luma = match color_config.mono_chrome {
+            true => false,
+            false => r.flag()?,
+        };
+
+        if !(color_config.mono_chrome
+            || params.chroma_scaling_from_luma
+            || (color_config.subsampling_x
+                && color_config.subsampling_y
+                && params.num_y_points == 0))
+        {
+            params.num_cb_points = r.f(4)? as u8;
+            if params.num_cb_points > 10 {
+                return Err(Av1ParserError::InvalidValue {
+                    name: "num_cb_points",
+                    value: params.num_cb_points as i64,
+                });
+            }
+            for i in 0..params.num_cb_points as usize {
+                params.point_cb_value[i] = r.f(8)? as u8;
+                params.point_cb_scaling[i] = r.f(8)? as u8;
+            }
+
+            params.num_cr_points = r.f(4)? as u8;
+            if params.num_cr_points > 10 {
+                return Err(Av1ParserError::InvalidValue {
+                    name: "num_cr_points",
+                    value: params.num_cr_points as i64,
+                });
+            }
+            for i in 0..params.num_cr_points as usize {
+                params.point_cr_value[i] = r.f(8)? as u8;
+                params.point_cr_scaling[i] = r.f(8)? as u8;
+            }
+        }
+
+        params.grain_scaling_minus_8 = r.f(2)? as u8;
+        params.ar_coeff_lag = r.f(2)? as u8;
+
+        let lag = params.ar_coeff_lag as usize;
+        let num_pos_luma = 2 * lag * (lag + 1);
+        let num_pos_chroma = match params.num_y_points {
+            0 => num_pos_luma,
+            _ => {
+                for i in 0..num_pos_luma {
+                    params.ar_coeffs_y_plus_128[i] = r.f(8)? as u8;
+                }
+                num_pos_luma + 1
+            }
+        };
+
+        if params.chroma_scaling_from_luma || params.num_cb_points > 0 {
+            for i in 0..num_pos_chroma {
+                params.ar_coeffs_cb_plus_128[i] = r.f(8)? as u8;
+            }
+        }
+
+        if params.chroma_scaling_from_luma || params.num_cr_points > 0 {
+            for i in 0..num_pos_chroma {
+                params.ar_coeffs_cr_plus_128[i] = r.f(8)? as u8;
+            }
+        }
+
+        params.ar_coeff_shift_minus_6 = r.f(2)? as u8;
+        params.grain_scale_shift = r.f(2)? as u8;
+        if params.num_cb_points > 0 {
+            params.cb_mult = r.f(8)? as u8;
+            params.cb_luma_mult = r.f(8)? as u8;
+            params.cb_offset = r.f(9)? as u16;
+        }
+
+        if params.num_cr_points > 0 {
+            params.cr_mult = r.f(8)? as u8;
+            params.cr_luma_mult = r.f(8)? as u8;
+            params.cr_offset = r.f(9)? as u16;
+        }
+
+        params.overlap_flag = r.flag()?;
+        params.clip_to_restricted_range = r.flag()?;
+
+        Ok(params)
+    }
+}
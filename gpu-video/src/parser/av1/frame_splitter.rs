@@ -0,0 +1,46 @@
+use super::{
+    frame_header::FrameType,
+    obu_parser::{Obu, ParsedObu},
+};
+
+#[derive(Default)]
+pub(crate) struct FrameSplitter {
+    buffered_obus: Vec<Obu>,
+}
+
+impl FrameSplitter {
+    pub(crate) fn put_obu(&mut self, obu: Obu) -> Option<FrameUnit> {
+        let completes_frame = obu.completes_frame();
+        self.buffered_obus.push(obu);
+
+        match completes_frame {
+            true => Some(FrameUnit(
+                std::mem::take(&mut self.buffered_obus).into_boxed_slice(),
+            )),
+            false => None,
+        }
+    }
+
+    /// Discards OBUs of an incomplete frame.
+    pub(crate) fn flush(&mut self) {
+        self.buffered_obus.clear();
+    }
+}
+
+/// Group of [`Obu`]s representing one frame: its frame header, all of its tile groups and
+/// OBUs preceding them (e.g. the sequence header). Frames with `show_existing_frame` consist
+/// only of the frame header.
+pub struct FrameUnit(pub Box<[Obu]>);
+
+impl FrameUnit {
+    /// Returns `true` if the frame is a shown key frame, which starts a new coded video
+    /// sequence.
+    pub fn is_keyframe(&self) -> bool {
+        self.0.iter().any(|obu| match &obu.parsed {
+            ParsedObu::FrameHeader(header) | ParsedObu::Frame(header, _) => {
+                header.frame_type == FrameType::Key && header.show_frame
+            }
+            _ => false,
+        })
+    }
+}
@@ -0,0 +1,308 @@
+use std::sync::Arc;
+
+use super::{
+    Av1ParserError,
+    bitstream::{BitReader, leb128},
+    frame_header::{FrameHeader, ReferenceFrames},
+    sequence_header::SequenceHeader,
+    tile_group::TileGroup,
+};
+use crate::parser::ChunkTimestamps;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObuType {
+    SequenceHeader,
+    TemporalDelimiter,
+    FrameHeader,
+    TileGroup,
+    Metadata,
+    Frame,
+    RedundantFrameHeader,
+    TileList,
+    Padding,
+    Reserved(u8),
+}
+
+impl ObuType {
+    fn from_bits(bits: u8) -> Self {
+        match bits {
+            1 => Self::SequenceHeader,
+            2 => Self::TemporalDelimiter,
+            3 => Self::FrameHeader,
+            4 => Self::TileGroup,
+            5 => Self::Metadata,
+            6 => Self::Frame,
+            7 => Self::RedundantFrameHeader,
+            8 => Self::TileList,
+            15 => Self::Padding,
+            bits => Self::Reserved(bits),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObuHeader {
+    pub obu_type: ObuType,
+    pub obu_extension_flag: bool,
+    pub obu_has_size_field: bool,
+    pub temporal_id: u8,
+    pub spatial_id: u8,
+}
+
+impl ObuHeader {
+    /// Returns the header and its length in bytes.
+    fn parse(bytes: &[u8]) -> Result<(Self, usize), Av1ParserError> {
+        let Some(&first) = bytes.first() else {
+            return Err(Av1ParserError::InvalidObuHeader);
+        };
+
+        let obu_forbidden_bit = first >> 7;
+        if obu_forbidden_bit != 0 {
+            return Err(Av1ParserError::InvalidObuHeader);
+        }
+
+        let mut header = Self {
+            obu_type: ObuType::from_bits((first >> 3) & 0b1111),
+            obu_extension_flag: (first >> 2) & 1 == 1,
+            obu_has_size_field: (first >> 1) & 1 == 1,
+            temporal_id: 0,
+            spatial_id: 0,
+        };
+
+        if !header.obu_extension_flag {
+            return Ok((header, 1));
+        }
+
+        let Some(&extension) = bytes.get(1) else {
+            return Err(Av1ParserError::InvalidObuHeader);
+        };
+        header.temporal_id = extension >> 5;
+        header.spatial_id = (extension >> 3) & 0b11;
+
+        Ok((header, 2))
+    }
+}
+
+#[derive(Debug)]
+// one variant of this enum is only ever printed out in debug mode, but clippy detects this as it not being
+// used.
+#[allow(dead_code)]
+pub enum ParsedObu {
+    SequenceHeader(Arc<SequenceHeader>),
+    TemporalDelimiter,
+    FrameHeader(Arc<FrameHeader>),
+    TileGroup(TileGroup),
+    /// Frame header followed by the first tile group of the frame
+    Frame(Arc<FrameHeader>, TileGroup),
+    Other(String),
+}
+
+/// AV1 Open Bitstream Unit
+pub struct Obu {
+    pub header: ObuHeader,
+    /// Parsed OBU from [`Obu::bytes`]
+    pub parsed: ParsedObu,
+    /// Whole OBU, including its header
+    pub bytes: Box<[u8]>,
+    pub pts: Option<u64>,
+    /// Decoding timestamp of the chunk the OBU was read from.
+    pub dts: Option<u64>,
+}
+
+impl Obu {
+    /// Returns `true` if this OBU contains the last part of a frame: the last tile group of the
+    /// frame, or a header which shows an existing frame.
+    pub fn completes_frame(&self) -> bool {
+        match &self.parsed {
+            ParsedObu::FrameHeader(header) => header.show_existing_frame,
+            ParsedObu::TileGroup(tile_group) | ParsedObu::Frame(_, tile_group) => {
+                tile_group.is_last()
+            }
+            _ => false,
+        }
+    }
+
+    /// Frame header carried by this OBU
+    pub fn frame_header(&self) -> Option<&Arc<FrameHeader>> {
+        match &self.parsed {
+            ParsedObu::FrameHeader(header) | ParsedObu::Frame(header, _) => Some(header),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ObuParser {
+    sequence_header: Option<Arc<SequenceHeader>>,
+    reference_frames: ReferenceFrames,
+    /// Header of the frame whose tile groups are being received (`SeenFrameHeader`)
+    current_frame: Option<Arc<FrameHeader>>,
+}
+
+impl ObuParser {
+    /// `bytes` contain a single OBU.
+    pub(crate) fn parse_obu(
+        &mut self,
+        bytes: Vec<u8>,
+        timestamps: ChunkTimestamps,
+    ) -> Result<Obu, Av1ParserError> {
+        let (header, header_len) = ObuHeader::parse(&bytes)?;
+        let (payload_offset, payload_len) = match header.obu_has_size_field {
+            true => {
+                let (obu_size, size_len) =
+                    leb128(&bytes[header_len..])?.ok_or(Av1ParserError::UnexpectedEndOfObu)?;
+                (header_len + size_len, obu_size as usize)
+            }
+            false => (header_len, bytes.len() - header_len),
+        };
+
+        let payload = bytes
+            .get(payload_offset..payload_offset + payload_len)
+            .ok_or(Av1ParserError::UnexpectedEndOfObu)?;
+        let parsed = self.parse_payload(&header, payload, payload_offset)?;
+
+        Ok(Obu {
+            header,
+            parsed,
+            bytes: bytes.into_boxed_slice(),
+            pts: timestamps.pts,
+            dts: timestamps.dts,
+        })
+    }
+
+    fn parse_payload(
+        &mut self,
+        header: &ObuHeader,
+        payload: &[u8],
+        payload_offset: usize,
+    ) -> Result<ParsedObu, Av1ParserError> {
+        if self.is_dropped(header) {
+            return Ok(ParsedObu::Other(format!(
+                "OBU type {:?} outside of the operating point (temporal id {}, spatial id {})",
+                header.obu_type, header.temporal_id, header.spatial_id
+            )));
+        }
+
+        match header.obu_type {
+            ObuType::SequenceHeader => {
+                let sequence_header = SequenceHeader::parse(payload)?;
+                let sequence_header = match &self.sequence_header {
+                    Some(current) if **current == sequence_header => current.clone(),
+                    _ => Arc::new(sequence_header),
+                };
+
+                self.sequence_header = Some(sequence_header.clone());
+                Ok(ParsedObu::SequenceHeader(sequence_header))
+            }
+
+            ObuType::TemporalDelimiter => {
+                self.current_frame = None;
+                Ok(ParsedObu::TemporalDelimiter)
+            }
+
+            ObuType::FrameHeader if self.current_frame.is_some() => {
+                Ok(ParsedObu::Other("frame header copy".to_string()))
+            }
+
+            ObuType::FrameHeader => {
+                let mut r = BitReader::new(payload);
+                let frame_header = self.parse_frame_header(&mut r, header)?;
+                if frame_header.show_existing_frame {
+                    self.reference_frames.update(&frame_header);
+                } else {
+                    self.current_frame = Some(frame_header.clone());
+                }
+
+                Ok(ParsedObu::FrameHeader(frame_header))
+            }
+
+            ObuType::Frame => {
+                let mut r = BitReader::new(payload);
+                let frame_header = self.parse_frame_header(&mut r, header)?;
+                if frame_header.show_existing_frame {
+                    return Err(Av1ParserError::InvalidValue {
+                        name: "show_existing_frame in a frame OBU",
+                        value: 1,
+                    });
+                }
+
+                r.byte_alignment()?;
+                let header_bytes = r.position() / 8;
+                let tile_group = TileGroup::parse(
+                    &payload[header_bytes..],
+                    payload_offset + header_bytes,
+                    &frame_header,
+                )?;
+
+                self.current_frame = Some(frame_header.clone());
+                self.finish_tile_group(&tile_group);
+
+                Ok(ParsedObu::Frame(frame_header, tile_group))
+            }
+
+            ObuType::TileGroup => {
+                let frame_header = self
+                    .current_frame
+                    .clone()
+                    .ok_or(Av1ParserError::MissingFrameHeader)?;
+                let tile_group = TileGroup::parse(payload, payload_offset, &frame_header)?;
+                self.finish_tile_group(&tile_group);
+
+                Ok(ParsedObu::TileGroup(tile_group))
+            }
+
+            obu_type => Ok(ParsedObu::Other(format!("OBU type {obu_type:?}"))),
+        }
+    }
+
+    fn parse_frame_header(
+        &mut self,
+        r: &mut BitReader,
+        header: &ObuHeader,
+    ) -> Result<Arc<FrameHeader>, Av1ParserError> {
+        let sequence_header = self
+            .sequence_header
+            .as_ref()
+            .ok_or(Av1ParserError::MissingSequenceHeader)?;
+
+        // the state of reference frames is only modified if the whole header is correct
+        let mut reference_frames = self.reference_frames.clone();
+        let frame_header = FrameHeader::parse(r, header, sequence_header, &mut reference_frames)?;
+        self.reference_frames = reference_frames;
+
+        Ok(Arc::new(frame_header))
+    }
+
+    /// Updates the reference frames after the last tile group of the current frame.
+    fn finish_tile_group(&mut self, tile_group: &TileGroup) {
+        if !tile_group.is_last() {
+            return;
+        }
+
+        if let Some(frame_header) = self.current_frame.take() {
+            self.reference_frames.update(&frame_header);
+        }
+    }
+
+    /// OBUs with extension headers which are not a part of the decoded operating point are
+    /// dropped (section 6.2.1 of the AV1 spec).
+    fn is_dropped(&self, header: &ObuHeader) -> bool {
+        let Some(sequence_header) = &self.sequence_header else {
+            return false;
+        };
+
+        let operating_point_idc = sequence_header.operating_point_idc();
+        if matches!(
+            header.obu_type,
+            ObuType::SequenceHeader | ObuType::TemporalDelimiter
+        ) || operating_point_idc == 0
+            || !header.obu_extension_flag
+        {
+            return false;
+        }
+
+        let in_temporal_layer = (operating_point_idc >> header.temporal_id) & 1 == 1;
+        let in_spatial_layer = (operating_point_idc >> (header.spatial_id + 8)) & 1 == 1;
+        !in_temporal_layer || !in_spatial_layer
+    }
+}
@@ -0,0 +1,97 @@
+use bytes::{BufMut, BytesMut};
+
+use super::{Av1ParserError, bitstream::leb128};
+use crate::parser::ChunkTimestamps;
+
+/// Splits a bitstream in the low overhead bitstream format (section 5.2 of the AV1 spec) into
+/// OBUs. OBUs without the `obu_size` field are assumed to extend to the end of the chunk of
+/// data they were received in, as in the sample format of ISOBMFF and Matroska.
+#[derive(Debug, Default)]
+pub(crate) struct ObuSplitter {
+    buffer: BytesMut,
+    timestamps: ChunkTimestamps,
+}
+
+impl ObuSplitter {
+    pub(crate) fn push(
+        &mut self,
+        bytestream: &[u8],
+        timestamps: ChunkTimestamps,
+    ) -> Result<Vec<(Vec<u8>, ChunkTimestamps)>, Av1ParserError> {
+        let mut output_timestamps = if self.buffer.is_empty() {
+            timestamps
+        } else {
+            self.timestamps
+        };
+
+        self.buffer.put(bytestream);
+        let mut result = Vec::new();
+
+        while !self.buffer.is_empty() {
+            let obu_len = match obu_length(&self.buffer) {
+                Ok(Some(obu_len)) => obu_len,
+                Ok(None) => break,
+                Err(err) => {
+                    self.buffer.clear();
+                    return Err(err);
+                }
+            };
+
+            let obu = match obu_len {
+                ObuLength::Sized(len) if len > self.buffer.len() => break,
+                ObuLength::Sized(len) => self.buffer.split_to(len),
+                ObuLength::UntilEndOfChunk => self.buffer.split(),
+            };
+
+            result.push((obu.to_vec(), output_timestamps));
+            output_timestamps = timestamps;
+        }
+
+        self.timestamps = timestamps;
+
+        Ok(result)
+    }
+
+    /// Discards an incomplete OBU left in the buffer.
+    pub(crate) fn flush(&mut self) {
+        self.buffer.clear();
+    }
+}
+
+enum ObuLength {
+    Sized(usize),
+    UntilEndOfChunk,
+}
+
+/// Returns the length of the OBU at the start of `buf`, including its header, or `None` if
+/// the header is not complete yet.
+fn obu_length(buf: &[u8]) -> Result<Option<ObuLength>, Av1ParserError> {
+    let Some(&first) = buf.first() else {
+        return Ok(None);
+    };
+
+    let obu_extension_flag = (first >> 2) & 1 == 1;
+    let obu_has_size_field = (first >> 1) & 1 == 1;
+    if !obu_has_size_field {
+        return Ok(Some(ObuLength::UntilEndOfChunk));
+    }
+
+    let header_len = 1 + obu_extension_flag as usize;
+    if buf.len() <= header_len {
+        return Ok(None);
+    }
+
+    let Some((obu_size, size_len)) = leb128(&buf[header_len..])? else {
+        return Ok(None);
+    };
+
+    let obu_len = usize::try_from(obu_size)
+        .ok()
+        .and_then(|obu_size| obu_size.checked_add(header_len + size_len))
+        .ok_or(Av1ParserError::InvalidValue {
+            name: "obu_size",
+            value: obu_size as i64,
+        })?;
+
+    Ok(Some(ObuLength::Sized(obu_len)))
+}
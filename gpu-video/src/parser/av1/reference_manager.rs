@@ -0,0 +1,315 @@
+use std::sync::Arc;
+
+use crate::{
+    parameters::MissedFrameHandling,
+    parser::reference_manager::{ReferenceId, ReferenceManagementError},
+};
+
+use super::{
+    FrameUnit,
+    decoder_instructions::DecoderInstruction,
+    frame_header::{FrameHeader, FrameType, NUM_REF_FRAMES, REFS_PER_FRAME},
+    obu_parser::ParsedObu,
+};
+
+/// Tracks which decoded frame is stored in each of the reference frame slots, as described in
+/// section 7.20 of the AV1 spec. A single frame can be stored in multiple slots, it is dropped
+/// when no slot refers to it.
+#[derive(Debug, Default)]
+pub(crate) struct ReferenceContext {
+    slots: [Option<ReferenceFrameInfo>; NUM_REF_FRAMES],
+    next_reference_id: ReferenceId,
+    detected_missed_frames: bool,
+    missed_frame_handling: MissedFrameHandling,
+    keyframes_only: bool,
+}
+
+impl ReferenceContext {
+    pub fn new(missed_frame_handling: MissedFrameHandling, keyframes_only: bool) -> Self {
+        Self {
+            missed_frame_handling,
+            keyframes_only,
+            ..Default::default()
+        }
+    }
+
+    fn next_reference_id(&mut self) -> ReferenceId {
+        let result = self.next_reference_id;
+        self.next_reference_id = result.next();
+        result
+    }
+
+    /// Drops all frames from the reference slots, so that decoding starts again from the next
+    /// key frame.
+    pub(crate) fn reset(&mut self) -> Vec<DecoderInstruction> {
+        let mut reference_ids = self
+            .slots
+            .iter()
+            .flatten()
+            .map(|frame| frame.id)
+            .collect::<Vec<_>>();
+        reference_ids.sort();
+        reference_ids.dedup();
+
+        *self = Self::new(self.missed_frame_handling, self.keyframes_only);
+
+        match reference_ids.is_empty() {
+            true => Vec::new(),
+            false => vec![DecoderInstruction::Drop { reference_ids }],
+        }
+    }
+
+    pub(crate) fn mark_missed_frames(&mut self) {
+        self.detected_missed_frames = true;
+    }
+
+    pub(crate) fn put_frame(
+        &mut self,
+        FrameUnit(obus): FrameUnit,
+    ) -> Result<Vec<DecoderInstruction>, ReferenceManagementError> {
+        let mut instructions = Vec::new();
+        let mut frame_header = None;
+        let mut pts = None;
+        let mut dts = None;
+        let mut bitstream = Vec::new();
+        let mut frame_header_offset = 0;
+        let mut tile_offsets = Vec::new();
+        let mut tile_sizes = Vec::new();
+
+        for obu in obus {
+            let tile_group = match &obu.parsed {
+                ParsedObu::SequenceHeader(sequence_header) => {
+                    instructions.push(DecoderInstruction::SequenceHeader(sequence_header.clone()));
+                    continue;
+                }
+                ParsedObu::FrameHeader(header) => {
+                    frame_header = Some(header.clone());
+                    None
+                }
+                ParsedObu::Frame(header, tile_group) => {
+                    frame_header = Some(header.clone());
+                    Some(tile_group)
+                }
+                ParsedObu::TileGroup(tile_group) => Some(tile_group),
+                ParsedObu::TemporalDelimiter | ParsedObu::Other(_) => continue,
+            };
+
+            if obu.frame_header().is_some() {
+                frame_header_offset = bitstream.len() as u32;
+                pts = obu.pts;
+                dts = obu.dts;
+            }
+
+            for tile in tile_group.iter().flat_map(|tile_group| &tile_group.tiles) {
+                tile_offsets.push((bitstream.len() + tile.offset) as u32);
+                tile_sizes.push(tile.size as u32);
+            }
+
+            bitstream.extend_from_slice(&obu.bytes);
+        }
+
+        let Some(header) = frame_header else {
+            return Ok(instructions);
+        };
+
+        let is_keyframe =
+            header.frame_type == FrameType::Key && header.show_frame && !header.show_existing_frame;
+        if self.keyframes_only && !is_keyframe {
+            return Ok(instructions);
+        }
+
+        if !is_keyframe
+            && self.detected_missed_frames
+            && self.missed_frame_handling == MissedFrameHandling::Strict
+        {
+            return Err(ReferenceManagementError::MissingFrame);
+        }
+
+        if header.show_existing_frame {
+            let Some(frame) = self.slots[header.frame_to_show_map_idx as usize].clone() else {
+                return self.missing_reference(instructions);
+            };
+
+            instructions.push(DecoderInstruction::ShowExisting {
+                reference_id: frame.id,
+                header: header.clone(),
+                pts,
+                dts,
+                corrupted: self.detected_missed_frames,
+            });
+
+            // showing a key frame refreshes all slots with it
+            instructions.extend(self.refresh(header.refresh_frame_flags, frame));
+            return Ok(instructions);
+        }
+
+        let mut references: [Option<ReferenceFrameInfo>; REFS_PER_FRAME] = Default::default();
+        if !header.is_intra() {
+            for (reference, &idx) in references.iter_mut().zip(&header.ref_frame_idx) {
+                match &self.slots[idx as usize] {
+                    Some(frame) => *reference = Some(frame.clone()),
+                    None => match self.concealment_substitute(header.order_hint) {
+                        Some(frame) => *reference = Some(frame),
+                        None => return self.missing_reference(instructions),
+                    },
+                }
+            }
+        }
+
+        if is_keyframe {
+            // all frames are dropped by the decoder when it receives the key frame instruction
+            self.slots = Default::default();
+            self.detected_missed_frames = false;
+        }
+
+        let reference_id = self.next_reference_id();
+        let decode_info = DecodeInformation {
+            header: header.clone(),
+            bitstream,
+            frame_header_offset,
+            tile_offsets,
+            tile_sizes,
+            references,
+            pts,
+            dts,
+            corrupted: self.detected_missed_frames,
+        };
+
+        instructions.push(match is_keyframe {
+            true => DecoderInstruction::KeyFrame {
+                decode_info,
+                reference_id,
+            },
+            false => DecoderInstruction::Decode {
+                decode_info,
+                reference_id,
+            },
+        });
+
+        let frame = ReferenceFrameInfo {
+            id: reference_id,
+            header,
+        };
+        instructions.extend(self.refresh(frame.header.refresh_frame_flags, frame));
+
+        Ok(instructions)
+    }
+
+    /// Stores `frame` in the slots selected by `refresh_frame_flags`. Returns an instruction
+    /// dropping the frames which are no longer stored in any slot, including `frame` itself if
+    /// no slot is refreshed.
+    fn refresh(
+        &mut self,
+        refresh_frame_flags: u8,
+        frame: ReferenceFrameInfo,
+    ) -> Option<DecoderInstruction> {
+        let mut candidates = vec![frame.id];
+        for (i, slot) in self.slots.iter_mut().enumerate() {
+            if (refresh_frame_flags >> i) & 1 == 0 {
+                continue;
+            }
+
+            if let Some(previous) = slot.replace(frame.clone()) {
+                candidates.push(previous.id);
+            }
+        }
+
+        candidates.sort();
+        candidates.dedup();
+        let reference_ids = candidates
+            .into_iter()
+            .filter(|id| self.slots.iter().flatten().all(|frame| frame.id != *id))
+            .collect::<Vec<_>>();
+
+        match reference_ids.is_empty() {
+            true => None,
+            false => Some(DecoderInstruction::Drop { reference_ids }),
+        }
+    }
+
+    /// Frame used in place of a reference frame which was lost when missed frames are concealed,
+    /// the stored frame nearest to the current one in display order. Following frames are
+    /// marked as corrupted.
+    fn concealment_substitute(&mut self, order_hint: u8) -> Option<ReferenceFrameInfo> {
+        if self.missed_frame_handling != MissedFrameHandling::Conceal {
+            return None;
+        }
+
+        let substitute = self
+            .slots
+            .iter()
+            .flatten()
+            .min_by_key(|frame| frame.header.order_hint.abs_diff(order_hint))
+            .cloned()?;
+        self.detected_missed_frames = true;
+        Some(substitute)
+    }
+
+    /// Called when a frame uses a reference frame slot which is empty, e.g. because the frame
+    /// stored in it was lost.
+    fn missing_reference(
+        &mut self,
+        instructions: Vec<DecoderInstruction>,
+    ) -> Result<Vec<DecoderInstruction>, ReferenceManagementError> {
+        match self.missed_frame_handling {
+            MissedFrameHandling::Strict => {
+                self.detected_missed_frames = true;
+                Err(ReferenceManagementError::MissingFrame)
+            }
+            // the frame is skipped
+            MissedFrameHandling::Tolerant | MissedFrameHandling::Conceal => {
+                self.detected_missed_frames = true;
+                Ok(instructions)
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct DecodeInformation {
+    pub(crate) header: Arc<FrameHeader>,
+    /// The frame header OBU followed by all tile group OBUs of the frame
+    pub(crate) bitstream: Vec<u8>,
+    pub(crate) frame_header_offset: u32,
+    /// Offsets of the tiles in [`DecodeInformation::bitstream`]
+    pub(crate) tile_offsets: Vec<u32>,
+    pub(crate) tile_sizes: Vec<u32>,
+    /// Reference frames, indexed by the reference frame name (`LAST_FRAME` is 0). `None` for
+    /// intra frames.
+    pub(crate) references: [Option<ReferenceFrameInfo>; REFS_PER_FRAME],
+    pub(crate) pts: Option<u64>,
+    pub(crate) dts: Option<u64>,
+    /// Decoded after frames were lost, see [`crate::FrameMetadata::corrupted`].
+    pub(crate) corrupted: bool,
+}
+
+impl std::fmt::Debug for DecodeInformation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecodeInformation")
+            .field("frame_type", &self.header.frame_type)
+            .field("order_hint", &self.header.order_hint)
+            .field("show_frame", &self.header.show_frame)
+            .field("references", &self.references)
+            .field("tile_offsets", &self.tile_offsets)
+            .field("pts", &self.pts)
+            .field("dts", &self.dts)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A decoded frame with the header it was decoded with, which contains the values saved by the
+/// reference frame update process.
+#[derive(Clone)]
+pub(crate) struct ReferenceFrameInfo {
+    pub(crate) id: ReferenceId,
+    pub(crate) header: Arc<FrameHeader>,
+}
+
+impl std::fmt::Debug for ReferenceFrameInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReferenceFrameInfo")
+            .field("id", &self.id)
+            .field("order_hint", &self.header.order_hint)
+            .finish_non_exhaustive()
+    }
+}
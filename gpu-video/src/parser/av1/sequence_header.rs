@@ -0,0 +1,447 @@
+use super::{Av1ParserError, bitstream::BitReader};
+
+pub(crate) const SELECT_SCREEN_CONTENT_TOOLS: u8 = 2;
+pub(crate) const SELECT_INTEGER_MV: u8 = 2;
+
+const CP_BT_709: u8 = 1;
+const CP_UNSPECIFIED: u8 = 2;
+const TC_UNSPECIFIED: u8 = 2;
+const TC_SRGB: u8 = 13;
+const MC_IDENTITY: u8 = 0;
+const MC_UNSPECIFIED: u8 = 2;
+const CSP_UNKNOWN: u8 = 0;
+
+/// Sequence header OBU, as described in section 5.5 of the AV1 spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceHeader {
+    pub seq_profile: u8,
+    pub still_picture: bool,
+    pub reduced_still_picture_header: bool,
+    pub timing_info: Option<TimingInfo>,
+    pub decoder_model_info: Option<DecoderModelInfo>,
+    pub initial_display_delay_present_flag: bool,
+    pub operating_points: Vec<OperatingPoint>,
+    pub frame_width_bits_minus_1: u8,
+    pub frame_height_bits_minus_1: u8,
+    pub max_frame_width_minus_1: u16,
+    pub max_frame_height_minus_1: u16,
+    pub frame_id_numbers_present_flag: bool,
+    pub delta_frame_id_length_minus_2: u8,
+    pub additional_frame_id_length_minus_1: u8,
+    pub use_128x128_superblock: bool,
+    pub enable_filter_intra: bool,
+    pub enable_intra_edge_filter: bool,
+    pub enable_interintra_compound: bool,
+    pub enable_masked_compound: bool,
+    pub enable_warped_motion: bool,
+    pub enable_dual_filter: bool,
+    pub enable_order_hint: bool,
+    pub enable_jnt_comp: bool,
+    pub enable_ref_frame_mvs: bool,
+    pub seq_choose_screen_content_tools: bool,
+    pub seq_force_screen_content_tools: u8,
+    pub seq_force_integer_mv: u8,
+    pub order_hint_bits_minus_1: u8,
+    pub enable_superres: bool,
+    pub enable_cdef: bool,
+    pub enable_restoration: bool,
+    pub color_config: ColorConfig,
+    pub film_grain_params_present: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimingInfo {
+    pub num_units_in_display_tick: u32,
+    pub time_scale: u32,
+    pub equal_picture_interval: bool,
+    pub num_ticks_per_picture_minus_1: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecoderModelInfo {
+    pub buffer_delay_length_minus_1: u8,
+    pub num_units_in_decoding_tick: u32,
+    pub buffer_removal_time_length_minus_1: u8,
+    pub frame_presentation_time_length_minus_1: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatingPoint {
+    pub operating_point_idc: u16,
+    pub seq_level_idx: u8,
+    pub seq_tier: u8,
+    pub decoder_model_present_for_this_op: bool,
+    pub decoder_buffer_delay: u32,
+    pub encoder_buffer_delay: u32,
+    pub low_delay_mode_flag: bool,
+    pub initial_display_delay_minus_1: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorConfig {
+    pub bit_depth: u8,
+    pub mono_chrome: bool,
+    pub color_description_present_flag: bool,
+    pub color_primaries: u8,
+    pub transfer_characteristics: u8,
+    pub matrix_coefficients: u8,
+    pub color_range: bool,
+    pub subsampling_x: bool,
+    pub subsampling_y: bool,
+    pub chroma_sample_position: u8,
+    pub separate_uv_delta_q: bool,
+}
+
+impl SequenceHeader {
+    pub(crate) fn parse(data: &[u8]) -> Result<Self, Av1ParserError> {
+        let mut r = BitReader::new(data);
+
+        let seq_profile = r.f(3)? as u8;
+        if seq_profile > 2 {
+            return Err(Av1ParserError::InvalidValue {
+                name: "seq_profile",
+                value: seq_profile as i64,
+            });
+        }
+
+        let still_picture = r.flag()?;
+        let reduced_still_picture_header = r.flag()?;
+
+        let mut timing_info = None;
+        let mut decoder_model_info = None;
+        let mut initial_display_delay_present_flag = false;
+        let mut operating_points = Vec::new();
+        if reduced_still_picture_header {
+            operating_points.push(OperatingPoint {
+                operating_point_idc: 0,
+                seq_level_idx: r.f(5)? as u8,
+                seq_tier: 0,
+                decoder_model_present_for_this_op: false,
+                decoder_buffer_delay: 0,
+                encoder_buffer_delay: 0,
+                low_delay_mode_flag: false,
+                initial_display_delay_minus_1: None,
+            });
+        } else {
+            let timing_info_present_flag = r.flag()?;
+            if timing_info_present_flag {
+                timing_info = Some(TimingInfo::parse(&mut r)?);
+
+                let decoder_model_info_present_flag = r.flag()?;
+                if decoder_model_info_present_flag {
+                    decoder_model_info = Some(DecoderModelInfo::parse(&mut r)?);
+                }
+            }
+
+            initial_display_delay_present_flag = r.flag()?;
+            let operating_points_cnt_minus_1 = r.f(5)?;
+            for _ in 0..=operating_points_cnt_minus_1 {
+                operating_points.push(OperatingPoint::parse(
+                    &mut r,
+                    decoder_model_info.as_ref(),
+                    initial_display_delay_present_flag,
+                )?);
+            }
+        }
+
+        let frame_width_bits_minus_1 = r.f(4)? as u8;
+        let frame_height_bits_minus_1 = r.f(4)? as u8;
+        let max_frame_width_minus_1 = r.f(frame_width_bits_minus_1 as u32 + 1)? as u16;
+        let max_frame_height_minus_1 = r.f(frame_height_bits_minus_1 as u32 + 1)? as u16;
+
+        let frame_id_numbers_present_flag = match reduced_still_picture_header {
+            true => false,
+            false => r.flag()?,
+        };
+
+        let mut delta_frame_id_length_minus_2 = 0;
+        let mut additional_frame_id_length_minus_1 = 0;
+        if frame_id_numbers_present_flag {
+            delta_frame_id_length_minus_2 = r.f(4)? as u8;
+            additional_frame_id_length_minus_1 = r.f(3)? as u8;
+        }
+
+        let use_128x128_superblock = r.flag()?;
+        let enable_filter_intra = r.flag()?;
+        let enable_intra_edge_filter = r.flag()?;
+
+        let mut enable_interintra_compound = false;
+        let mut enable_masked_compound = false;
+        let mut enable_warped_motion = false;
+        let mut enable_dual_filter = false;
+        let mut enable_order_hint = false;
+        let mut enable_jnt_comp = false;
+        let mut enable_ref_frame_mvs = false;
+        let mut seq_choose_screen_content_tools = true;
+        let mut seq_force_screen_content_tools = SELECT_SCREEN_CONTENT_TOOLS;
+        let mut seq_force_integer_mv = SELECT_INTEGER_MV;
+        let mut order_hint_bits_minus_1 = 0;
+        if !reduced_still_picture_header {
+            enable_interintra_compound = r.flag()?;
+            enable_masked_compound = r.flag()?;
+            enable_warped_motion = r.flag()?;
+            enable_dual_filter = r.flag()?;
+            enable_order_hint = r.flag()?;
+            if enable_order_hint {
+                enable_jnt_comp = r.flag()?;
+                enable_ref_frame_mvs = r.flag()?;
+            }
+
+            seq_choose_screen_content_tools = r.flag()?;
+            if !seq_choose_screen_content_tools {
+                seq_force_screen_content_tools = r.f(1)? as u8;
+            }
+
+            if seq_force_screen_content_tools > 0 {
+                let seq_choose_integer_mv = r.flag()?;
+                if !seq_choose_integer_mv {
+                    seq_force_integer_mv = r.f(1)? as u8;
+                }
+            }
+
+            if enable_order_hint {
+                order_hint_bits_minus_1 = r.f(3)? as u8;
+            }
+        }
+
+        let enable_superres = r.flag()?;
+        let enable_cdef = r.flag()?;
+        let enable_restoration = r.flag()?;
+        let color_config = ColorConfig::parse(&mut r, seq_profile)?;
+        let film_grain_params_present = r.flag()?;
+
+        Ok(Self {
+            seq_profile,
+            still_picture,
+            reduced_still_picture_header,
+            timing_info,
+            decoder_model_info,
+            initial_display_delay_present_flag,
+            operating_points,
+            frame_width_bits_minus_1,
+            frame_height_bits_minus_1,
+            max_frame_width_minus_1,
+            max_frame_height_minus_1,
+            frame_id_numbers_present_flag,
+            delta_frame_id_length_minus_2,
+            additional_frame_id_length_minus_1,
+            use_128x128_superblock,
+            enable_filter_intra,
+            enable_intra_edge_filter,
+            enable_interintra_compound,
+            enable_masked_compound,
+            enable_warped_motion,
+            enable_dual_filter,
+            enable_order_hint,
+            enable_jnt_comp,
+            enable_ref_frame_mvs,
+            seq_choose_screen_content_tools,
+            seq_force_screen_content_tools,
+            seq_force_integer_mv,
+            order_hint_bits_minus_1,
+            enable_superres,
+            enable_cdef,
+            enable_restoration,
+            color_config,
+            film_grain_params_present,
+        })
+    }
+
+    /// `OrderHintBits`
+    pub fn order_hint_bits(&self) -> u32 {
+        match self.enable_order_hint {
+            true => self.order_hint_bits_minus_1 as u32 + 1,
+            false => 0,
+        }
+    }
+
+    /// `idLen`, number of bits used to code frame ids.
+    pub(crate) fn frame_id_length(&self) -> u32 {
+        self.additional_frame_id_length_minus_1 as u32
+            + self.delta_frame_id_length_minus_2 as u32
+            + 3
+    }
+
+    /// `OperatingPointIdc` of the decoded operating point. Only the first operating point is
+    /// decoded.
+    pub fn operating_point_idc(&self) -> u16 {
+        self.operating_points
+            .first()
+            .map(|op| op.operating_point_idc)
+            .unwrap_or(0)
+    }
+
+    pub fn max_frame_width(&self) -> u32 {
+        self.max_frame_width_minus_1 as u32 + 1
+    }
+
+    pub fn max_frame_height(&self) -> u32 {
+        self.max_frame_height_minus_1 as u32 + 1
+    }
+}
+
+impl TimingInfo {
+    fn parse(r: &mut BitReader) -> Result<Self, Av1ParserError> {
+        let num_units_in_display_tick = r.f(32)?;
+        let time_scale = r.f(32)?;
+        let equal_picture_interval = r.flag()?;
+        let num_ticks_per_picture_minus_1 = match equal_picture_interval {
+            true => r.uvlc()?,
+            false => 0,
+        };
+
+        Ok(Self {
+            num_units_in_display_tick,
+            time_scale,
+            equal_picture_interval,
+            num_ticks_per_picture_minus_1,
+        })
+    }
+}
+
+impl DecoderModelInfo {
+    fn parse(r: &mut BitReader) -> Result<Self, Av1ParserError> {
+        Ok(Self {
+            buffer_delay_length_minus_1: r.f(5)? as u8,
+            num_units_in_decoding_tick: r.f(32)?,
+            buffer_removal_time_length_minus_1: r.f(5)? as u8,
+            frame_presentation_time_length_minus_1: r.f(5)? as u8,
+        })
+    }
+}
+
+impl OperatingPoint {
+    fn parse(
+        r: &mut BitReader,
+        decoder_model_info: Option<&DecoderModelInfo>,
+        initial_display_delay_present_flag: bool,
+    ) -> Result<Self, Av1ParserError> {
+        let operating_point_idc = r.f(12)? as u16;
+        let seq_level_idx = r.f(5)? as u8;
+        let seq_tier = match seq_level_idx > 7 {
+            true => r.f(1)? as u8,
+            false => 0,
+        };
+
+        let mut decoder_model_present_for_this_op = false;
+        let mut decoder_buffer_delay = 0;
+        let mut encoder_buffer_delay = 0;
+        let mut low_delay_mode_flag = false;
+        if let Some(decoder_model_info) = decoder_model_info {
+            decoder_model_present_for_this_op = r.flag()?;
+            if decoder_model_present_for_this_op {
+                let n = decoder_model_info.buffer_delay_length_minus_1 as u32 + 1;
+                decoder_buffer_delay = r.f(n)?;
+                encoder_buffer_delay = r.f(n)?;
+                low_delay_mode_flag = r.flag()?;
+            }
+        }
+
+        let mut initial_display_delay_minus_1 = None;
+        if initial_display_delay_present_flag {
+            let initial_display_delay_present_for_this_op = r.flag()?;
+            if initial_display_delay_present_for_this_op {
+                initial_display_delay_minus_1 = Some(r.f(4)? as u8);
+            }
+        }
+
+        Ok(Self {
+            operating_point_idc,
+            seq_level_idx,
+            seq_tier,
+            decoder_model_present_for_this_op,
+            decoder_buffer_delay,
+            encoder_buffer_delay,
+            low_delay_mode_flag,
+            initial_display_delay_minus_1,
+        })
+    }
+}
+
+impl ColorConfig {
+    fn parse(r: &mut BitReader, seq_profile: u8) -> Result<Self, Av1ParserError> {
+        let high_bitdepth = r.flag()?;
+        let bit_depth = match (seq_profile, high_bitdepth) {
+            (2, true) => match r.flag()? {
+                true => 12,
+                false => 10,
+            },
+            (_, true) => 10,
+            (_, false) => 8,
+        };
+
+        let mono_chrome = match seq_profile {
+            1 => false,
+            _ => r.flag()?,
+        };
+
+        let color_description_present_flag = r.flag()?;
+        let (color_primaries, transfer_characteristics, matrix_coefficients) =
+            match color_description_present_flag {
+                true => (r.f(8)? as u8, r.f(8)? as u8, r.f(8)? as u8),
+                false => (CP_UNSPECIFIED, TC_UNSPECIFIED, MC_UNSPECIFIED),
+            };
+
+        let mut config = Self {
+            bit_depth,
+            mono_chrome,
+            color_description_present_flag,
+            color_primaries,
+            transfer_characteristics,
+            matrix_coefficients,
+            color_range: false,
+            subsampling_x: true,
+            subsampling_y: true,
+            chroma_sample_position: CSP_UNKNOWN,
+            separate_uv_delta_q: false,
+        };
+
+        if mono_chrome {
+            config.color_range = r.flag()?;
+            return Ok(config);
+        }
+
+        if color_primaries == CP_BT_709
+            && transfer_characteristics == TC_SRGB
+            && matrix_coefficients == MC_IDENTITY
+        {
+            config.color_range = true;
+            config.subsampling_x = false;
+            config.subsampling_y = false;
+        } else {
+            config.color_range = r.flag()?;
+            match seq_profile {
+                0 => {}
+                1 => {
+                    config.subsampling_x = false;
+                    config.subsampling_y = false;
+                }
+                _ if bit_depth == 12 => {
+                    config.subsampling_x = r.flag()?;
+                    config.subsampling_y = match config.subsampling_x {
+                        true => r.flag()?,
+                        false => false,
+                    };
+                }
+                _ => {
+                    config.subsampling_y = false;
+                }
+            }
+
+            if config.subsampling_x && config.subsampling_y {
+                config.chroma_sample_position = r.f(2)? as u8;
+            }
+        }
+
+        config.separate_uv_delta_q = r.flag()?;
+        Ok(config)
+    }
+
+    /// `NumPlanes`
+    pub fn num_planes(&self) -> usize {
+        match self.mono_chrome {
+            true => 1,
+            false => 3,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use super::{Av1ParserError, bitstream::BitReader, frame_header::FrameHeader};
+
+/// Position of the data of one tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    /// Offset of the tile data from the start of the OBU, including the OBU header
+    pub offset: usize,
+    pub size: usize,
+}
+
+/// Tile group OBU, as described in section 5.11 of the AV1 spec
+#[derive(Debug, Clone)]
+pub struct TileGroup {
+    pub tg_start: u32,
+    pub tg_end: u32,
+    /// `NumTiles` of the frame the group belongs to
+    pub num_tiles: u32,
+    pub tiles: Vec<Tile>,
+}
+
+impl TileGroup {
+    /// `tile_group_obu()`. `payload` contains the tile group and `payload_offset` is its
+    /// position in the OBU.
+    pub(crate) fn parse(
+        payload: &[u8],
+        payload_offset: usize,
+        header: &FrameHeader,
+    ) -> Result<Self, Av1ParserError> {
+        let tile_info = &header.tile_info;
+        let num_tiles = tile_info.num_tiles();
+
+        let mut r = BitReader::new(payload);
+        let tile_start_and_end_present_flag = match num_tiles > 1 {
+            true => r.flag()?,
+            false => false,
+        };
+
+        let (tg_start, tg_end) = match tile_start_and_end_present_flag {
+            true => {
+                let tile_bits = tile_info.tile_cols_log2 as u32 + tile_info.tile_rows_log2 as u32;
+                (r.f(tile_bits)?, r.f(tile_bits)?)
+            }
+            false => (0, num_tiles - 1),
+        };
+
+        if tg_start > tg_end || tg_end >= num_tiles {
+            return Err(Av1ParserError::InvalidValue {
+                name: "tg_end",
+                value: tg_end as i64,
+            });
+        }
+
+        r.byte_alignment()?;
+
+        let tile_size_bytes = tile_info.tile_size_bytes();
+        let mut position = r.position() / 8;
+        let mut tiles = Vec::with_capacity((tg_end - tg_start + 1) as usize);
+        for tile_num in tg_start..=tg_end {
+            let size = match tile_num == tg_end {
+                true => payload.len().saturating_sub(position),
+                false => {
+                    let mut r = BitReader::new(&payload[position..]);
+                    let tile_size_minus_1 = r.le(tile_size_bytes)? as usize;
+                    position += tile_size_bytes as usize;
+                    tile_size_minus_1 + 1
+                }
+            };
+
+            if size == 0 || position + size > payload.len() {
+                return Err(Av1ParserError::UnexpectedEndOfObu);
+            }
+
+            tiles.push(Tile {
+                offset: payload_offset + position,
+                size,
+            });
+            position += size;
+        }
+
+        Ok(Self {
+            tg_start,
+            tg_end,
+            num_tiles,
+            tiles,
+        })
+    }
+
+    /// Returns `true` if this is the last tile group of the frame.
+    pub fn is_last(&self) -> bool {
+        self.tg_end + 1 == self.num_tiles
+    }
+}
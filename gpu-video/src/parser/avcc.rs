@@ -0,0 +1,119 @@
+use super::h264::H264ParserError;
+
+const START_CODE: [u8; 4] = [0, 0, 0, 1];
+
+/// Format of the H.264 bitstream passed to the parser and the decoders.
+#[derive(Debug, Clone, Default)]
+pub enum H264BitstreamFormat {
+    /// NAL units separated with start codes, as described in Annex B of the H.264 spec.
+    #[default]
+    AnnexB,
+
+    /// NAL units prefixed with their length, as in MP4 files and RTMP streams. Every chunk has
+    /// to contain whole NAL units of a single access unit. The parameter sets are sent out of
+    /// band in the decoder configuration record.
+    Avcc(AvcDecoderConfig),
+}
+
+/// `AVCDecoderConfigurationRecord` from ISO/IEC 14496-15, stored in the `avcC` box of MP4 files
+/// and sent in the sequence header of RTMP streams.
+#[derive(Debug, Clone)]
+pub struct AvcDecoderConfig {
+    /// Size of the length prefix of every NAL unit, 1, 2 or 4 bytes.
+    pub nalu_length_size: usize,
+    pub sps: Vec<Vec<u8>>,
+    pub pps: Vec<Vec<u8>>,
+}
+
+impl AvcDecoderConfig {
+    /// Parses the record. The extensions for the high profiles at the end of the record are
+    /// ignored.
+    pub fn parse(bytes: &[u8]) -> Result<Self, H264ParserError> {
+        let mut reader = ByteReader(bytes);
+
+        let configuration_version = reader.u8()?;
+        if configuration_version != 1 {
+            return Err(H264ParserError::InvalidAvcDecoderConfig(format!(
+                "unsupported configuration version {configuration_version}"
+            )));
+        }
+
+        // AVCProfileIndication, profile_compatibility, AVCLevelIndication
+        reader.bytes(3)?;
+
+        let nalu_length_size = (reader.u8()? & 0b11) as usize + 1;
+        if nalu_length_size == 3 {
+            return Err(H264ParserError::InvalidAvcDecoderConfig(
+                "NAL unit length size cannot be 3 bytes".to_string(),
+            ));
+        }
+
+        let num_sps = reader.u8()? & 0b1_1111;
+        let sps = (0..num_sps)
+            .map(|_| reader.parameter_set())
+            .collect::<Result<_, _>>()?;
+
+        let num_pps = reader.u8()?;
+        let pps = (0..num_pps)
+            .map(|_| reader.parameter_set())
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            nalu_length_size,
+            sps,
+            pps,
+        })
+    }
+
+    /// The parameter sets from the record in Annex B format.
+    pub(crate) fn parameter_sets_annex_b(&self) -> Vec<u8> {
+        self.sps
+            .iter()
+            .chain(&self.pps)
+            .flat_map(|nalu| START_CODE.iter().chain(nalu))
+            .copied()
+            .collect()
+    }
+
+    /// Replaces the length prefixes of NAL units in `bytes` with start codes.
+    pub(crate) fn to_annex_b(&self, bytes: &[u8]) -> Result<Vec<u8>, H264ParserError> {
+        let mut reader = ByteReader(bytes);
+        let mut result = Vec::with_capacity(bytes.len() + START_CODE.len());
+        while !reader.0.is_empty() {
+            let length = reader
+                .bytes(self.nalu_length_size)?
+                .iter()
+                .fold(0, |length, byte| (length << 8) | *byte as usize);
+            let nalu = reader.bytes(length)?;
+
+            result.extend_from_slice(&START_CODE);
+            result.extend_from_slice(nalu);
+        }
+
+        Ok(result)
+    }
+}
+
+struct ByteReader<'a>(&'a [u8]);
+
+impl<'a> ByteReader<'a> {
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], H264ParserError> {
+        if self.0.len() < count {
+            return Err(H264ParserError::TruncatedAvccData);
+        }
+
+        let (bytes, rest) = self.0.split_at(count);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, H264ParserError> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn parameter_set(&mut self) -> Result<Vec<u8>, H264ParserError> {
+        let length = self.bytes(2)?;
+        let length = u16::from_be_bytes([length[0], length[1]]) as usize;
+        self.bytes(length).map(<[u8]>::to_vec)
+    }
+}
@@ -1,7 +1,8 @@
 use h264_reader::nal::{pps::PicParameterSet, sps::SeqParameterSet};
 
 use crate::parser::{
-    h264::{AccessUnit, ParsedNalu},
+    ChunkTimestamps,
+    h264::{AccessUnit, ParsedNalu, RecoveryPoint, SeiMessage},
     reference_manager::DecodeInformation,
     reference_manager::{ReferenceContext, ReferenceId, ReferenceManagementError},
 };
@@ -18,6 +19,14 @@ pub(crate) enum DecoderInstruction {
         reference_id: ReferenceId,
     },
 
+    /// Second field of a complementary field pair. It is decoded into the same picture as the
+    /// first field, which was decoded with the same `reference_id`. The frame is output after
+    /// both fields are decoded.
+    DecodeSecondField {
+        decode_info: DecodeInformation,
+        reference_id: ReferenceId,
+    },
+
     Drop {
         reference_ids: Vec<ReferenceId>,
     },
@@ -34,6 +43,7 @@ pub(crate) fn compile_to_decoder_instructions(
     let mut instructions = Vec::new();
     for AccessUnit(nalus) in access_units {
         let mut slices = Vec::new();
+        let mut recovery_point = None;
         for nalu in nalus {
             match nalu.parsed {
                 ParsedNalu::Sps(seq_parameter_set) => {
@@ -43,7 +53,15 @@ pub(crate) fn compile_to_decoder_instructions(
                     instructions.push(DecoderInstruction::Pps(pic_parameter_set))
                 }
                 ParsedNalu::Slice(slice) => {
-                    slices.push((slice, nalu.pts));
+                    let timestamps = ChunkTimestamps {
+                        pts: nalu.pts,
+                        dts: nalu.dts,
+                    };
+                    slices.push((slice, timestamps));
+                }
+
+                ParsedNalu::Sei(messages) => {
+                    recovery_point = recovery_point.or_else(|| find_recovery_point(&messages));
                 }
 
                 ParsedNalu::Other(_) => {}
@@ -51,9 +69,16 @@ pub(crate) fn compile_to_decoder_instructions(
         }
 
         // TODO: warn when not all pts are equal here
-        let mut inst = reference_ctx.put_picture(slices)?;
+        let mut inst = reference_ctx.put_picture(slices, recovery_point)?;
         instructions.append(&mut inst);
     }
 
     Ok(instructions)
 }
+
+fn find_recovery_point(messages: &[SeiMessage]) -> Option<RecoveryPoint> {
+    messages.iter().find_map(|message| match message {
+        SeiMessage::RecoveryPoint(recovery_point) => Some(*recovery_point),
+        _ => None,
+    })
+}
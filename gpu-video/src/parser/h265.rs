@@ -0,0 +1,105 @@
+use au_splitter::AUSplitter;
+use nalu_parser::NalParser;
+
+use super::ChunkTimestamps;
+use super::nalu_splitter::NALUSplitter;
+
+pub use super::sei::{
+    CaptionPacket, DisplayOrientationSei, MasteringDisplayColorVolume, SeiMessage, Timecode,
+    UserDataUnregistered,
+};
+pub use au_splitter::AccessUnit;
+pub use nalu_parser::{NalHeader, Nalu, ParsedNalu, Slice};
+pub use parameter_sets::{
+    BitstreamRestriction, ColourDescription, PicParameterSet, PpsRangeExtension, ProfileTierLevel,
+    ScalingListData, SeqParameterSet, ShortTermRefPicSet, SpsRangeExtension, SubLayerOrderingInfo,
+    TimingInfo, VideoParameterSet, VideoSignalType, VuiParameters,
+};
+pub use slice::{LongTermPicture, SliceSegmentHeader, SliceType};
+
+mod au_splitter;
+mod bitstream;
+mod nalu_parser;
+mod parameter_sets;
+mod slice;
+
+#[cfg(vulkan)]
+pub(crate) mod decoder_instructions;
+#[cfg(vulkan)]
+pub(crate) mod reference_manager;
+
+#[derive(Debug, thiserror::Error)]
+pub enum H265ParserError {
+    #[error("A NAL unit ended before all of its syntax elements were read")]
+    UnexpectedEndOfNalu,
+
+    #[error("Invalid NAL unit header")]
+    InvalidNalHeader,
+
+    #[error("Invalid value of {name}: {value}")]
+    InvalidValue { name: &'static str, value: i64 },
+
+    #[error("{kind} with id {id} was referenced before it was received")]
+    MissingParameterSet { kind: &'static str, id: u32 },
+}
+
+/// H265 parser for Annex B format
+#[derive(Default)]
+pub struct H265Parser {
+    nal_parser: NalParser,
+    nalu_splitter: NALUSplitter,
+    au_splitter: AUSplitter,
+}
+
+impl H265Parser {
+    /// Parses nalus in Annex B format.
+    /// Returns [`AccessUnit`]s representing whole frame
+    pub fn parse(
+        &mut self,
+        bytes: &[u8],
+        pts: Option<u64>,
+    ) -> Result<Vec<AccessUnit>, H265ParserError> {
+        self.parse_with_dts(bytes, pts, None)
+    }
+
+    /// Same as [`Self::parse`], but also passes the decoding timestamp of the chunk to the
+    /// parsed nalus.
+    pub fn parse_with_dts(
+        &mut self,
+        bytes: &[u8],
+        pts: Option<u64>,
+        dts: Option<u64>,
+    ) -> Result<Vec<AccessUnit>, H265ParserError> {
+        let nalus = self.nalu_splitter.push(bytes, ChunkTimestamps { pts, dts });
+        self.put_nalus(nalus)
+    }
+
+    pub fn flush(&mut self) -> Result<Vec<AccessUnit>, H265ParserError> {
+        let nalus = self.nalu_splitter.flush();
+        let mut access_units = self.put_nalus(nalus)?;
+
+        if let Some(au) = self.au_splitter.flush() {
+            access_units.push(au);
+        }
+
+        Ok(access_units)
+    }
+
+    fn put_nalus(
+        &mut self,
+        nalus: Vec<(Vec<u8>, ChunkTimestamps)>,
+    ) -> Result<Vec<AccessUnit>, H265ParserError> {
+        let mut access_units = Vec::new();
+        for (nalu_bytes, timestamps) in nalus {
+            let nalu = self.nal_parser.parse_nalu(nalu_bytes, timestamps)?;
+
+            let Some(au) = self.au_splitter.put_nalu(nalu) else {
+                continue;
+            };
+
+            access_units.push(au);
+        }
+
+        Ok(access_units)
+    }
+}
@@ -0,0 +1,56 @@
+use super::nalu_parser::{Nalu, ParsedNalu};
+
+#[derive(Default)]
+pub(crate) struct AUSplitter {
+    buffered_nals: Vec<Nalu>,
+}
+
+impl AUSplitter {
+    pub(crate) fn put_nalu(&mut self, nalu: Nalu) -> Option<AccessUnit> {
+        let result = match is_new_au(&nalu) {
+            true => self.take_until_last_slice(),
+            false => None,
+        };
+
+        self.buffered_nals.push(nalu);
+        result
+    }
+
+    pub(crate) fn flush(&mut self) -> Option<AccessUnit> {
+        self.take_until_last_slice()
+    }
+
+    fn take_until_last_slice(&mut self) -> Option<AccessUnit> {
+        // if there are any slice NALUs, the last one is the end of the AU. Parameter sets and SEI
+        // messages following it belong to the next AU.
+        let i = self
+            .buffered_nals
+            .iter()
+            .rposition(|nalu| matches!(nalu.parsed, ParsedNalu::Slice(_)))?;
+
+        let au = self.buffered_nals.drain(..=i).collect::<Vec<_>>();
+        Some(AccessUnit(au.into_boxed_slice()))
+    }
+}
+
+/// Returns `true` if `nalu` is the first slice segment of a picture. Each access unit contains
+/// exactly one picture, since only the base layer is decoded.
+fn is_new_au(nalu: &Nalu) -> bool {
+    match &nalu.parsed {
+        ParsedNalu::Slice(slice) => slice.header.first_slice_segment_in_pic_flag,
+        _ => false,
+    }
+}
+
+/// Group of [`Nalu`]s representing one frame
+pub struct AccessUnit(pub Box<[Nalu]>);
+
+impl AccessUnit {
+    /// Returns `true` if the frame is an intra random access point (IDR, CRA or BLA picture).
+    pub fn is_irap(&self) -> bool {
+        self.0.iter().any(|nalu| match &nalu.parsed {
+            ParsedNalu::Slice(slice) => slice.nal_header.is_irap(),
+            _ => false,
+        })
+    }
+}
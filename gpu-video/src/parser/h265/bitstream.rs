@@ -0,0 +1,118 @@
+use super::H265ParserError;
+
+/// Removes emulation prevention bytes (`0x03` following two zero bytes) from a NAL unit.
+pub(crate) fn nal_to_rbsp(nal: &[u8]) -> Vec<u8> {
+    let mut rbsp = Vec::with_capacity(nal.len());
+    let mut zeros = 0;
+    for &byte in nal {
+        if zeros >= 2 && byte == 3 {
+            zeros = 0;
+            continue;
+        }
+
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        rbsp.push(byte);
+    }
+
+    rbsp
+}
+
+/// Reads syntax elements from RBSP, as described in section 7.2 of the H.265 spec.
+pub(crate) struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// Number of bits read so far.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn flag(&mut self) -> Result<bool, H265ParserError> {
+        let byte = self
+            .data
+            .get(self.position / 8)
+            .ok_or(H265ParserError::UnexpectedEndOfNalu)?;
+        let bit = (byte >> (7 - self.position % 8)) & 1;
+        self.position += 1;
+
+        Ok(bit == 1)
+    }
+
+    /// `u(n)`, at most 32 bits.
+    pub(crate) fn u(&mut self, bits: u32) -> Result<u32, H265ParserError> {
+        debug_assert!(bits <= 32);
+        let mut value = 0u64;
+        for _ in 0..bits {
+            value = (value << 1) | self.flag()? as u64;
+        }
+
+        Ok(value as u32)
+    }
+
+    pub(crate) fn skip(&mut self, bits: usize) -> Result<(), H265ParserError> {
+        if self.position + bits > self.data.len() * 8 {
+            return Err(H265ParserError::UnexpectedEndOfNalu);
+        }
+        self.position += bits;
+
+        Ok(())
+    }
+
+    /// `ue(v)`, unsigned Exp-Golomb code.
+    pub(crate) fn ue(&mut self) -> Result<u32, H265ParserError> {
+        let mut leading_zeros = 0;
+        while !self.flag()? {
+            leading_zeros += 1;
+            if leading_zeros > 31 {
+                return Err(H265ParserError::InvalidValue {
+                    name: "Exp-Golomb code length",
+                    value: leading_zeros,
+                });
+            }
+        }
+
+        let value = (1u64 << leading_zeros) - 1 + self.u(leading_zeros as u32)? as u64;
+        u32::try_from(value).map_err(|_| H265ParserError::InvalidValue {
+            name: "Exp-Golomb code",
+            value: value as i64,
+        })
+    }
+
+    /// `se(v)`, signed Exp-Golomb code.
+    pub(crate) fn se(&mut self) -> Result<i32, H265ParserError> {
+        let value = self.ue()? as i64;
+        let value = match value % 2 {
+            0 => -(value / 2),
+            _ => (value + 1) / 2,
+        };
+
+        Ok(value as i32)
+    }
+
+    /// `ue(v)` with an inclusive upper bound from the spec.
+    pub(crate) fn ue_max(&mut self, name: &'static str, max: u32) -> Result<u32, H265ParserError> {
+        let value = self.ue()?;
+        if value > max {
+            return Err(H265ParserError::InvalidValue {
+                name,
+                value: value as i64,
+            });
+        }
+
+        Ok(value)
+    }
+}
+
+/// `Ceil(Log2(value))`
+pub(crate) fn ceil_log2(value: u32) -> u32 {
+    match value {
+        0 | 1 => 0,
+        value => u32::BITS - (value - 1).leading_zeros(),
+    }
+}
@@ -0,0 +1,76 @@
+use crate::parser::{
+    ChunkTimestamps,
+    reference_manager::{ReferenceId, ReferenceManagementError},
+};
+
+use super::{
+    AccessUnit, ParsedNalu,
+    parameter_sets::{PicParameterSet, SeqParameterSet, VideoParameterSet},
+    reference_manager::{DecodeInformation, ReferenceContext},
+};
+
+#[derive(Debug, Clone)]
+pub(crate) enum DecoderInstruction {
+    Decode {
+        decode_info: DecodeInformation,
+        reference_id: ReferenceId,
+    },
+
+    /// IRAP picture which starts a new coded video sequence. All previous references are dropped.
+    Irap {
+        decode_info: DecodeInformation,
+        reference_id: ReferenceId,
+    },
+
+    Drop {
+        reference_ids: Vec<ReferenceId>,
+    },
+
+    Vps(VideoParameterSet),
+
+    Sps(SeqParameterSet),
+
+    Pps(PicParameterSet),
+}
+
+pub(crate) fn compile_to_decoder_instructions(
+    reference_ctx: &mut ReferenceContext,
+    access_units: Vec<AccessUnit>,
+) -> Result<Vec<DecoderInstruction>, ReferenceManagementError> {
+    let mut instructions = Vec::new();
+    for AccessUnit(nalus) in access_units {
+        let mut slices = Vec::new();
+        for nalu in nalus {
+            match nalu.parsed {
+                ParsedNalu::Vps(video_parameter_set) => {
+                    instructions.push(DecoderInstruction::Vps(video_parameter_set))
+                }
+                ParsedNalu::Sps(seq_parameter_set) => {
+                    instructions.push(DecoderInstruction::Sps(seq_parameter_set))
+                }
+                ParsedNalu::Pps(pic_parameter_set) => {
+                    instructions.push(DecoderInstruction::Pps(pic_parameter_set))
+                }
+                ParsedNalu::Slice(slice) => {
+                    let timestamps = ChunkTimestamps {
+                        pts: nalu.pts,
+                        dts: nalu.dts,
+                    };
+                    slices.push((slice, timestamps));
+                }
+                ParsedNalu::EndOfSequence => {
+                    let mut inst = reference_ctx.put_picture(std::mem::take(&mut slices))?;
+                    instructions.append(&mut inst);
+                    reference_ctx.mark_end_of_sequence();
+                }
+
+                ParsedNalu::Sei(_) | ParsedNalu::Other(_) => {}
+            }
+        }
+
+        let mut inst = reference_ctx.put_picture(slices)?;
+        instructions.append(&mut inst);
+    }
+
+    Ok(instructions)
+}
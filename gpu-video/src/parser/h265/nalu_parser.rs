@@ -0,0 +1,228 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::{
+    H265ParserError,
+    bitstream::nal_to_rbsp,
+    parameter_sets::{PicParameterSet, SeqParameterSet, VideoParameterSet},
+    slice::SliceSegmentHeader,
+};
+use crate::parser::ChunkTimestamps;
+use crate::parser::sei::{SeiMessage, SeiSyntax, parse_sei_messages};
+
+const VPS_NUT: u8 = 32;
+const SPS_NUT: u8 = 33;
+const PPS_NUT: u8 = 34;
+const EOS_NUT: u8 = 36;
+const PREFIX_SEI_NUT: u8 = 39;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NalHeader {
+    pub nal_unit_type: u8,
+    pub nuh_layer_id: u8,
+    pub nuh_temporal_id_plus1: u8,
+}
+
+impl NalHeader {
+    fn parse(bytes: &[u8]) -> Result<Self, H265ParserError> {
+        let [first, second, ..] = *bytes else {
+            return Err(H265ParserError::InvalidNalHeader);
+        };
+
+        let forbidden_zero_bit = first >> 7;
+        let nuh_temporal_id_plus1 = second & 0b111;
+        if forbidden_zero_bit != 0 || nuh_temporal_id_plus1 == 0 {
+            return Err(H265ParserError::InvalidNalHeader);
+        }
+
+        Ok(Self {
+            nal_unit_type: (first >> 1) & 0b11_1111,
+            nuh_layer_id: ((first & 1) << 5) | (second >> 3),
+            nuh_temporal_id_plus1,
+        })
+    }
+
+    pub fn temporal_id(&self) -> u8 {
+        self.nuh_temporal_id_plus1 - 1
+    }
+
+    pub fn is_vcl(&self) -> bool {
+        self.nal_unit_type < 32
+    }
+
+    /// Intra random access point picture (BLA, IDR or CRA).
+    pub fn is_irap(&self) -> bool {
+        (16..=23).contains(&self.nal_unit_type)
+    }
+
+    pub fn is_idr(&self) -> bool {
+        matches!(self.nal_unit_type, 19 | 20)
+    }
+
+    pub fn is_bla(&self) -> bool {
+        (16..=18).contains(&self.nal_unit_type)
+    }
+
+    pub fn is_cra(&self) -> bool {
+        self.nal_unit_type == 21
+    }
+
+    /// Random access skipped leading picture.
+    pub fn is_rasl(&self) -> bool {
+        matches!(self.nal_unit_type, 8 | 9)
+    }
+
+    /// Random access decodable leading picture.
+    pub fn is_radl(&self) -> bool {
+        matches!(self.nal_unit_type, 6 | 7)
+    }
+
+    /// Picture that is not used for reference by pictures of the same sub-layer.
+    pub fn is_sub_layer_non_reference(&self) -> bool {
+        self.nal_unit_type <= 14 && self.nal_unit_type % 2 == 0
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct NalParser {
+    sps: HashMap<u8, Arc<SeqParameterSet>>,
+    pps: HashMap<u8, Arc<PicParameterSet>>,
+}
+
+impl NalParser {
+    /// `bytes` contain a single NAL unit with the start code.
+    pub(crate) fn parse_nalu(
+        &mut self,
+        bytes: Vec<u8>,
+        timestamps: ChunkTimestamps,
+    ) -> Result<Nalu, H265ParserError> {
+        let start = bytes
+            .windows(3)
+            .position(|w| w == [0, 0, 1])
+            .map(|i| i + 3)
+            .unwrap_or(0);
+        let end = bytes
+            .iter()
+            .rposition(|&b| b != 0)
+            .map(|i| i + 1)
+            .unwrap_or(start)
+            .max(start);
+
+        let parsed = self.parse_nal(&bytes[start..end])?;
+
+        Ok(Nalu {
+            parsed,
+            raw_bytes: bytes.into_boxed_slice(),
+            pts: timestamps.pts,
+            dts: timestamps.dts,
+        })
+    }
+
+    fn parse_nal(&mut self, nal: &[u8]) -> Result<ParsedNalu, H265ParserError> {
+        let header = NalHeader::parse(nal)?;
+        if header.nuh_layer_id > 0 {
+            return Ok(ParsedNalu::Other(format!(
+                "NAL unit type {} with nuh_layer_id {}",
+                header.nal_unit_type, header.nuh_layer_id
+            )));
+        }
+
+        let rbsp = nal_to_rbsp(&nal[2..]);
+        match header.nal_unit_type {
+            VPS_NUT => {
+                let vps = VideoParameterSet::parse(&rbsp)?;
+                Ok(ParsedNalu::Vps(vps))
+            }
+
+            SPS_NUT => {
+                let sps = SeqParameterSet::parse(&rbsp)?;
+                self.sps
+                    .insert(sps.sps_seq_parameter_set_id, Arc::new(sps.clone()));
+                Ok(ParsedNalu::Sps(sps))
+            }
+
+            PPS_NUT => {
+                let pps = PicParameterSet::parse(&rbsp)?;
+                self.pps
+                    .insert(pps.pps_pic_parameter_set_id, Arc::new(pps.clone()));
+                Ok(ParsedNalu::Pps(pps))
+            }
+
+            0..=9 | 16..=21 => {
+                let (slice_header, sps, pps) =
+                    SliceSegmentHeader::parse(&rbsp, &header, &self.sps, &self.pps)?;
+
+                let mut rbsp_bytes = vec![0, 0, 0, 1];
+                rbsp_bytes.extend_from_slice(nal);
+
+                Ok(ParsedNalu::Slice(Slice {
+                    nal_header: header,
+                    header: slice_header,
+                    rbsp_bytes,
+                    sps,
+                    pps,
+                }))
+            }
+
+            EOS_NUT => Ok(ParsedNalu::EndOfSequence),
+
+            PREFIX_SEI_NUT => {
+                let messages = parse_sei_messages(&rbsp, SeiSyntax::H265);
+                match messages.is_empty() {
+                    false => Ok(ParsedNalu::Sei(messages)),
+                    true => Ok(ParsedNalu::Other(format!(
+                        "NAL unit type {}",
+                        header.nal_unit_type
+                    ))),
+                }
+            }
+
+            nal_unit_type => Ok(ParsedNalu::Other(format!("NAL unit type {nal_unit_type}"))),
+        }
+    }
+}
+
+#[derive(Debug)]
+// one variant of this enum is only ever printed out in debug mode, but clippy detects this as it not being
+// used.
+#[allow(dead_code)]
+pub enum ParsedNalu {
+    Vps(VideoParameterSet),
+    Sps(SeqParameterSet),
+    Pps(PicParameterSet),
+    Slice(Slice),
+    /// Prefix SEI NAL unit with at least one supported message.
+    Sei(Vec<SeiMessage>),
+    EndOfSequence,
+    Other(String),
+}
+
+/// H265 Network Abstraction Layer Unit
+pub struct Nalu {
+    /// Parsed nalu from [`Nalu::raw_bytes`]
+    pub parsed: ParsedNalu,
+    // Only used if parsers are exposed
+    #[allow(dead_code)]
+    pub raw_bytes: Box<[u8]>,
+    pub pts: Option<u64>,
+    /// Decoding timestamp of the chunk the nalu was read from.
+    pub dts: Option<u64>,
+}
+
+/// Slice segment NAL unit
+pub struct Slice {
+    pub nal_header: NalHeader,
+    pub header: SliceSegmentHeader,
+    /// Start code followed by the NAL unit, as expected by the decoder.
+    pub rbsp_bytes: Vec<u8>,
+    pub sps: Arc<SeqParameterSet>,
+    pub pps: Arc<PicParameterSet>,
+}
+
+impl std::fmt::Debug for Slice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Slice")
+            .field("nal_header", &self.nal_header)
+            .field("header", &self.header)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,1186 @@
+use super::{H265ParserError, bitstream::BitReader};
+
+/// `profile_tier_level()` syntax with `profilePresentFlag` equal to 1. Only the general
+/// profile and level are kept, sub-layer information is skipped.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileTierLevel {
+    pub general_profile_space: u8,
+    pub general_tier_flag: bool,
+    pub general_profile_idc: u8,
+    pub general_profile_compatibility_flags: u32,
+    pub general_progressive_source_flag: bool,
+    pub general_interlaced_source_flag: bool,
+    pub general_non_packed_constraint_flag: bool,
+    pub general_frame_only_constraint_flag: bool,
+    pub general_level_idc: u8,
+}
+
+impl ProfileTierLevel {
+    fn parse(
+        reader: &mut BitReader<'_>,
+        max_num_sub_layers_minus1: u8,
+    ) -> Result<Self, H265ParserError> {
+        let general_profile_space = reader.u(2)? as u8;
+        let general_tier_flag = reader.flag()?;
+        let general_profile_idc = reader.u(5)? as u8;
+        let general_profile_compatibility_flags = reader.u(32)?;
+        let general_progressive_source_flag = reader.flag()?;
+        let general_interlaced_source_flag = reader.flag()?;
+        let general_non_packed_constraint_flag = reader.flag()?;
+        let general_frame_only_constraint_flag = reader.flag()?;
+        // 43 bits of constraint flags and general_inbld_flag
+        reader.skip(44)?;
+        let general_level_idc = reader.u(8)? as u8;
+
+        let mut sub_layer_profile_present_flag = [false; 8];
+        let mut sub_layer_level_present_flag = [false; 8];
+        for i in 0..max_num_sub_layers_minus1 as usize {
+            sub_layer_profile_present_flag[i] = reader.flag()?;
+            sub_layer_level_present_flag[i] = reader.flag()?;
+        }
+
+        if max_num_sub_layers_minus1 > 0 {
+            // reserved_zero_2bits
+            reader.skip(2 * (8 - max_num_sub_layers_minus1 as usize))?;
+        }
+
+        for i in 0..max_num_sub_layers_minus1 as usize {
+            if sub_layer_profile_present_flag[i] {
+                reader.skip(88)?;
+            }
+            if sub_layer_level_present_flag[i] {
+                reader.skip(8)?;
+            }
+        }
+
+        Ok(Self {
+            general_profile_space,
+            general_tier_flag,
+            general_profile_idc,
+            general_profile_compatibility_flags,
+            general_progressive_source_flag,
+            general_interlaced_source_flag,
+            general_non_packed_constraint_flag,
+            general_frame_only_constraint_flag,
+            general_level_idc,
+        })
+    }
+
+    /// `general_profile_compatibility_flag[j]`
+    pub fn is_compatible_with(&self, profile_idc: u8) -> bool {
+        profile_idc < 32
+            && self.general_profile_compatibility_flags & (1 << (31 - profile_idc)) != 0
+    }
+}
+
+/// DPB size and reordering constraints for every sub-layer. When the values are signaled only for
+/// the highest sub-layer, they are copied to the lower ones.
+#[derive(Debug, Clone, Default)]
+pub struct SubLayerOrderingInfo {
+    pub max_dec_pic_buffering_minus1: [u8; 7],
+    pub max_num_reorder_pics: [u8; 7],
+    pub max_latency_increase_plus1: [u32; 7],
+}
+
+impl SubLayerOrderingInfo {
+    fn parse(
+        reader: &mut BitReader<'_>,
+        info_present_flag: bool,
+        max_sub_layers_minus1: u8,
+    ) -> Result<Self, H265ParserError> {
+        let mut info = Self::default();
+        let first = match info_present_flag {
+            true => 0,
+            false => max_sub_layers_minus1 as usize,
+        };
+
+        for i in first..=max_sub_layers_minus1 as usize {
+            info.max_dec_pic_buffering_minus1[i] =
+                reader.ue_max("max_dec_pic_buffering_minus1", 15)? as u8;
+            info.max_num_reorder_pics[i] = reader.ue_max("max_num_reorder_pics", 15)? as u8;
+            info.max_latency_increase_plus1[i] = reader.ue()?;
+        }
+
+        for i in 0..first {
+            info.max_dec_pic_buffering_minus1[i] = info.max_dec_pic_buffering_minus1[first];
+            info.max_num_reorder_pics[i] = info.max_num_reorder_pics[first];
+            info.max_latency_increase_plus1[i] = info.max_latency_increase_plus1[first];
+        }
+
+        Ok(info)
+    }
+}
+
+/// Video parameter set. Parsing stops before the HRD parameters, which are not needed for decoding.
+#[derive(Debug, Clone)]
+pub struct VideoParameterSet {
+    pub vps_video_parameter_set_id: u8,
+    pub vps_max_layers_minus1: u8,
+    pub vps_max_sub_layers_minus1: u8,
+    pub vps_temporal_id_nesting_flag: bool,
+    pub profile_tier_level: ProfileTierLevel,
+    pub vps_sub_layer_ordering_info_present_flag: bool,
+    pub sub_layer_ordering_info: SubLayerOrderingInfo,
+    pub vps_timing_info_present_flag: bool,
+    pub vps_num_units_in_tick: u32,
+    pub vps_time_scale: u32,
+    pub vps_poc_proportional_to_timing_flag: bool,
+    pub vps_num_ticks_poc_diff_one_minus1: u32,
+}
+
+impl VideoParameterSet {
+    pub(crate) fn parse(rbsp: &[u8]) -> Result<Self, H265ParserError> {
+        let mut reader = BitReader::new(rbsp);
+        let vps_video_parameter_set_id = reader.u(4)? as u8;
+        // vps_base_layer_internal_flag, vps_base_layer_available_flag
+        reader.skip(2)?;
+        let vps_max_layers_minus1 = reader.u(6)? as u8;
+        let vps_max_sub_layers_minus1 = max_sub_layers_minus1(&mut reader)?;
+        let vps_temporal_id_nesting_flag = reader.flag()?;
+        // vps_reserved_0xffff_16bits
+        reader.skip(16)?;
+        let profile_tier_level = ProfileTierLevel::parse(&mut reader, vps_max_sub_layers_minus1)?;
+        let vps_sub_layer_ordering_info_present_flag = reader.flag()?;
+        let sub_layer_ordering_info = SubLayerOrderingInfo::parse(
+            &mut reader,
+            vps_sub_layer_ordering_info_present_flag,
+            vps_max_sub_layers_minus1,
+        )?;
+
+        let vps_max_layer_id = reader.u(6)?;
+        let vps_num_layer_sets_minus1 = reader.ue_max("vps_num_layer_sets_minus1", 1023)?;
+        // layer_id_included_flag
+        reader.skip(vps_num_layer_sets_minus1 as usize * (vps_max_layer_id as usize + 1))?;
+
+        let vps_timing_info_present_flag = reader.flag()?;
+        let mut vps = Self {
+            vps_video_parameter_set_id,
+            vps_max_layers_minus1,
+            vps_max_sub_layers_minus1,
+            vps_temporal_id_nesting_flag,
+            profile_tier_level,
+            vps_sub_layer_ordering_info_present_flag,
+            sub_layer_ordering_info,
+            vps_timing_info_present_flag,
+            vps_num_units_in_tick: 0,
+            vps_time_scale: 0,
+            vps_poc_proportional_to_timing_flag: false,
+            vps_num_ticks_poc_diff_one_minus1: 0,
+        };
+
+        if vps_timing_info_present_flag {
+            vps.vps_num_units_in_tick = reader.u(32)?;
+            vps.vps_time_scale = reader.u(32)?;
+            vps.vps_poc_proportional_to_timing_flag = reader.flag()?;
+            if vps.vps_poc_proportional_to_timing_flag {
+                vps.vps_num_ticks_poc_diff_one_minus1 = reader.ue()?;
+            }
+        }
+
+        Ok(vps)
+    }
+}
+
+/// `st_ref_pic_set()` syntax together with the values derived from it (`DeltaPocS0`,
+/// `UsedByCurrPicS0`, `DeltaPocS1` and `UsedByCurrPicS1`).
+#[derive(Debug, Clone, Default)]
+pub struct ShortTermRefPicSet {
+    pub inter_ref_pic_set_prediction_flag: bool,
+    pub delta_idx_minus1: u32,
+    pub delta_rps_sign: bool,
+    pub abs_delta_rps_minus1: u32,
+    pub used_by_curr_pic_flag: Vec<bool>,
+    pub use_delta_flag: Vec<bool>,
+    /// `NumDeltaPocs[RefRpsIdx]`, only meaningful for predicted sets.
+    pub num_delta_pocs_of_ref_rps: u8,
+
+    pub delta_poc_s0: Vec<i32>,
+    pub used_by_curr_pic_s0: Vec<bool>,
+    pub delta_poc_s1: Vec<i32>,
+    pub used_by_curr_pic_s1: Vec<bool>,
+}
+
+impl ShortTermRefPicSet {
+    /// `st_rps_idx` equal to `num_short_term_ref_pic_sets` means that the set is parsed from
+    /// a slice header. `sets` are the sets preceding it in the SPS.
+    pub(crate) fn parse(
+        reader: &mut BitReader<'_>,
+        st_rps_idx: usize,
+        num_short_term_ref_pic_sets: usize,
+        sets: &[ShortTermRefPicSet],
+    ) -> Result<Self, H265ParserError> {
+        let mut set = Self::default();
+        if st_rps_idx != 0 {
+            set.inter_ref_pic_set_prediction_flag = reader.flag()?;
+        }
+
+        if !set.inter_ref_pic_set_prediction_flag {
+            let num_negative_pics = reader.ue_max("num_negative_pics", 16)?;
+            let num_positive_pics = reader.ue_max("num_positive_pics", 16)?;
+            if num_negative_pics + num_positive_pics > 16 {
+                return Err(H265ParserError::InvalidValue {
+                    name: "num_negative_pics + num_positive_pics",
+                    value: (num_negative_pics + num_positive_pics) as i64,
+                });
+            }
+
+            let mut delta_poc = 0;
+            for _ in 0..num_negative_pics {
+                delta_poc -= reader.ue_max("delta_poc_s0_minus1", 1 << 15)? as i32 + 1;
+                set.delta_poc_s0.push(delta_poc);
+                set.used_by_curr_pic_s0.push(reader.flag()?);
+            }
+
+            let mut delta_poc = 0;
+            for _ in 0..num_positive_pics {
+                delta_poc += reader.ue_max("delta_poc_s1_minus1", 1 << 15)? as i32 + 1;
+                set.delta_poc_s1.push(delta_poc);
+                set.used_by_curr_pic_s1.push(reader.flag()?);
+            }
+
+            return Ok(set);
+        }
+
+        if st_rps_idx == num_short_term_ref_pic_sets {
+            set.delta_idx_minus1 = reader.ue_max("delta_idx_minus1", st_rps_idx as u32 - 1)?;
+        }
+        set.delta_rps_sign = reader.flag()?;
+        set.abs_delta_rps_minus1 = reader.ue_max("abs_delta_rps_minus1", (1 << 15) - 1)?;
+
+        let ref_rps = sets
+            .get(st_rps_idx - (set.delta_idx_minus1 as usize + 1))
+            .ok_or(H265ParserError::InvalidValue {
+                name: "delta_idx_minus1",
+                value: set.delta_idx_minus1 as i64,
+            })?;
+        let num_delta_pocs = ref_rps.num_delta_pocs();
+        set.num_delta_pocs_of_ref_rps = num_delta_pocs as u8;
+        for _ in 0..=num_delta_pocs {
+            let used_by_curr_pic_flag = reader.flag()?;
+            let use_delta_flag = match used_by_curr_pic_flag {
+                true => true,
+                false => reader.flag()?,
+            };
+            set.used_by_curr_pic_flag.push(used_by_curr_pic_flag);
+            set.use_delta_flag.push(use_delta_flag);
+        }
+
+        // (7-61) and (7-62)
+        let delta_rps = match set.delta_rps_sign {
+            true => -(set.abs_delta_rps_minus1 as i32 + 1),
+            false => set.abs_delta_rps_minus1 as i32 + 1,
+        };
+        let num_negative_pics = ref_rps.delta_poc_s0.len();
+
+        for j in (0..ref_rps.delta_poc_s1.len()).rev() {
+            let delta_poc = ref_rps.delta_poc_s1[j] + delta_rps;
+            if delta_poc < 0 && set.use_delta_flag[num_negative_pics + j] {
+                set.delta_poc_s0.push(delta_poc);
+                set.used_by_curr_pic_s0
+                    .push(set.used_by_curr_pic_flag[num_negative_pics + j]);
+            }
+        }
+        if delta_rps < 0 && set.use_delta_flag[num_delta_pocs] {
+            set.delta_poc_s0.push(delta_rps);
+            set.used_by_curr_pic_s0
+                .push(set.used_by_curr_pic_flag[num_delta_pocs]);
+        }
+        for j in 0..num_negative_pics {
+            let delta_poc = ref_rps.delta_poc_s0[j] + delta_rps;
+            if delta_poc < 0 && set.use_delta_flag[j] {
+                set.delta_poc_s0.push(delta_poc);
+                set.used_by_curr_pic_s0.push(set.used_by_curr_pic_flag[j]);
+            }
+        }
+
+        for j in (0..num_negative_pics).rev() {
+            let delta_poc = ref_rps.delta_poc_s0[j] + delta_rps;
+            if delta_poc > 0 && set.use_delta_flag[j] {
+                set.delta_poc_s1.push(delta_poc);
+                set.used_by_curr_pic_s1.push(set.used_by_curr_pic_flag[j]);
+            }
+        }
+        if delta_rps > 0 && set.use_delta_flag[num_delta_pocs] {
+            set.delta_poc_s1.push(delta_rps);
+            set.used_by_curr_pic_s1
+                .push(set.used_by_curr_pic_flag[num_delta_pocs]);
+        }
+        for j in 0..ref_rps.delta_poc_s1.len() {
+            let delta_poc = ref_rps.delta_poc_s1[j] + delta_rps;
+            if delta_poc > 0 && set.use_delta_flag[num_negative_pics + j] {
+                set.delta_poc_s1.push(delta_poc);
+                set.used_by_curr_pic_s1
+                    .push(set.used_by_curr_pic_flag[num_negative_pics + j]);
+            }
+        }
+
+        if set.num_delta_pocs() > 16 {
+            return Err(H265ParserError::InvalidValue {
+                name: "NumDeltaPocs",
+                value: set.num_delta_pocs() as i64,
+            });
+        }
+
+        Ok(set)
+    }
+
+    /// `NumDeltaPocs`
+    pub fn num_delta_pocs(&self) -> usize {
+        self.delta_poc_s0.len() + self.delta_poc_s1.len()
+    }
+}
+
+/// Scaling factors in the coded (up-right diagonal) order. Lists which are not signaled are
+/// filled with the default values.
+#[derive(Debug, Clone)]
+pub struct ScalingListData {
+    pub scaling_list_4x4: [[u8; 16]; 6],
+    pub scaling_list_8x8: [[u8; 64]; 6],
+    pub scaling_list_16x16: [[u8; 64]; 6],
+    pub scaling_list_32x32: [[u8; 64]; 2],
+    pub scaling_list_dc_coef_16x16: [u8; 6],
+    pub scaling_list_dc_coef_32x32: [u8; 2],
+}
+
+/// Table 7-6, `matrixId` 0..=2
+const DEFAULT_SCALING_LIST_INTRA: [u8; 64] = [
+    16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 17, 16, 17, 16, 17, 18, 17, 18, 18, 17, 18, 21, 19, 20,
+    21, 20, 19, 21, 24, 22, 22, 24, 24, 22, 22, 24, 25, 25, 27, 30, 27, 25, 25, 29, 31, 35, 35, 31,
+    29, 36, 41, 44, 41, 36, 47, 54, 54, 47, 65, 70, 65, 88, 88, 115,
+];
+
+/// Table 7-6, `matrixId` 3..=5
+const DEFAULT_SCALING_LIST_INTER: [u8; 64] = [
+    16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 17, 17, 17, 17, 17, 18, 18, 18, 18, 18, 18, 20, 20, 20,
+    20, 20, 20, 20, 24, 24, 24, 24, 24, 24, 24, 24, 25, 25, 25, 25, 25, 25, 25, 28, 28, 28, 28, 28,
+    28, 33, 33, 33, 33, 33, 41, 41, 41, 41, 54, 54, 54, 71, 71, 91,
+];
+
+impl Default for ScalingListData {
+    fn default() -> Self {
+        let default_list = |matrix_id: usize| match matrix_id < 3 {
+            true => DEFAULT_SCALING_LIST_INTRA,
+            false => DEFAULT_SCALING_LIST_INTER,
+        };
+
+        Self {
+            scaling_list_4x4: [[16; 16]; 6],
+            scaling_list_8x8: std::array::from_fn(default_list),
+            scaling_list_16x16: std::array::from_fn(default_list),
+            scaling_list_32x32: [DEFAULT_SCALING_LIST_INTRA, DEFAULT_SCALING_LIST_INTER],
+            scaling_list_dc_coef_16x16: [16; 6],
+            scaling_list_dc_coef_32x32: [16; 2],
+        }
+    }
+}
+
+impl ScalingListData {
+    fn parse(reader: &mut BitReader<'_>) -> Result<Self, H265ParserError> {
+        let mut data = Self::default();
+        for size_id in 0..4 {
+            let matrix_step = if size_id == 3 { 3 } else { 1 };
+            for matrix_id in (0..6).step_by(matrix_step) {
+                let scaling_list_pred_mode_flag = reader.flag()?;
+                if !scaling_list_pred_mode_flag {
+                    let delta = reader.ue_max("scaling_list_pred_matrix_id_delta", 5)? as usize;
+                    if delta == 0 {
+                        // the default list was already set
+                        continue;
+                    }
+
+                    let ref_matrix_id = matrix_id.checked_sub(delta * matrix_step).ok_or(
+                        H265ParserError::InvalidValue {
+                            name: "scaling_list_pred_matrix_id_delta",
+                            value: delta as i64,
+                        },
+                    )?;
+                    data.copy_list(size_id, ref_matrix_id, matrix_id);
+                    continue;
+                }
+
+                let mut next_coef = 8;
+                let coef_num = usize::min(64, 1 << (4 + (size_id << 1)));
+                if size_id > 1 {
+                    let dc_coef = reader.se()? + 8;
+                    if !(1..=255).contains(&dc_coef) {
+                        return Err(H265ParserError::InvalidValue {
+                            name: "scaling_list_dc_coef_minus8",
+                            value: dc_coef as i64 - 8,
+                        });
+                    }
+                    next_coef = dc_coef;
+                    data.set_dc_coef(size_id, matrix_id, dc_coef as u8);
+                }
+
+                let list = data.list_mut(size_id, matrix_id);
+                for coef in list.iter_mut().take(coef_num) {
+                    let delta = reader.se()?;
+                    next_coef = (next_coef + delta + 256).rem_euclid(256);
+                    *coef = next_coef as u8;
+                }
+            }
+        }
+
+        Ok(data)
+    }
+
+    fn list_mut(&mut self, size_id: usize, matrix_id: usize) -> &mut [u8] {
+        match size_id {
+            0 => &mut self.scaling_list_4x4[matrix_id],
+            1 => &mut self.scaling_list_8x8[matrix_id],
+            2 => &mut self.scaling_list_16x16[matrix_id],
+            _ => &mut self.scaling_list_32x32[matrix_id / 3],
+        }
+    }
+
+    fn set_dc_coef(&mut self, size_id: usize, matrix_id: usize, value: u8) {
+        match size_id {
+            2 => self.scaling_list_dc_coef_16x16[matrix_id] = value,
+            3 => self.scaling_list_dc_coef_32x32[matrix_id / 3] = value,
+            _ => {}
+        }
+    }
+
+    fn copy_list(&mut self, size_id: usize, from_matrix_id: usize, to_matrix_id: usize) {
+        match size_id {
+            0 => self.scaling_list_4x4[to_matrix_id] = self.scaling_list_4x4[from_matrix_id],
+            1 => self.scaling_list_8x8[to_matrix_id] = self.scaling_list_8x8[from_matrix_id],
+            2 => {
+                self.scaling_list_16x16[to_matrix_id] = self.scaling_list_16x16[from_matrix_id];
+                self.scaling_list_dc_coef_16x16[to_matrix_id] =
+                    self.scaling_list_dc_coef_16x16[from_matrix_id];
+            }
+            _ => {
+                self.scaling_list_32x32[to_matrix_id / 3] =
+                    self.scaling_list_32x32[from_matrix_id / 3];
+                self.scaling_list_dc_coef_32x32[to_matrix_id / 3] =
+                    self.scaling_list_dc_coef_32x32[from_matrix_id / 3];
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VideoSignalType {
+    pub video_format: u8,
+    pub video_full_range_flag: bool,
+    pub colour_description: Option<ColourDescription>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ColourDescription {
+    pub colour_primaries: u8,
+    pub transfer_characteristics: u8,
+    pub matrix_coeffs: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct TimingInfo {
+    pub num_units_in_tick: u32,
+    pub time_scale: u32,
+    pub num_ticks_poc_diff_one_minus1: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BitstreamRestriction {
+    pub tiles_fixed_structure_flag: bool,
+    pub motion_vectors_over_pic_boundaries_flag: bool,
+    pub restricted_ref_pic_lists_flag: bool,
+    pub min_spatial_segmentation_idc: u32,
+    pub max_bytes_per_pic_denom: u32,
+    pub max_bits_per_min_cu_denom: u32,
+    pub log2_max_mv_length_horizontal: u32,
+    pub log2_max_mv_length_vertical: u32,
+}
+
+/// VUI parameters. HRD parameters are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct VuiParameters {
+    /// `aspect_ratio_idc`, `sar_width` and `sar_height`
+    pub aspect_ratio: Option<(u8, u16, u16)>,
+    pub overscan_appropriate: Option<bool>,
+    pub video_signal_type: Option<VideoSignalType>,
+    /// `chroma_sample_loc_type_top_field` and `chroma_sample_loc_type_bottom_field`
+    pub chroma_loc_info: Option<(u8, u8)>,
+    pub neutral_chroma_indication_flag: bool,
+    pub field_seq_flag: bool,
+    pub frame_field_info_present_flag: bool,
+    /// Left, right, top and bottom offset
+    pub default_display_window: Option<[u32; 4]>,
+    pub timing_info: Option<TimingInfo>,
+    pub bitstream_restriction: Option<BitstreamRestriction>,
+}
+
+impl VuiParameters {
+    fn parse(
+        reader: &mut BitReader<'_>,
+        sps_max_sub_layers_minus1: u8,
+    ) -> Result<Self, H265ParserError> {
+        let mut vui = Self::default();
+        if reader.flag()? {
+            let aspect_ratio_idc = reader.u(8)? as u8;
+            let (sar_width, sar_height) = match aspect_ratio_idc {
+                255 => (reader.u(16)? as u16, reader.u(16)? as u16),
+                _ => (0, 0),
+            };
+            vui.aspect_ratio = Some((aspect_ratio_idc, sar_width, sar_height));
+        }
+
+        if reader.flag()? {
+            vui.overscan_appropriate = Some(reader.flag()?);
+        }
+
+        if reader.flag()? {
+            let video_format = reader.u(3)? as u8;
+            let video_full_range_flag = reader.flag()?;
+            let colour_description = match reader.flag()? {
+                true => Some(ColourDescription {
+                    colour_primaries: reader.u(8)? as u8,
+                    transfer_characteristics: reader.u(8)? as u8,
+                    matrix_coeffs: reader.u(8)? as u8,
+                }),
+                false => None,
+            };
+            vui.video_signal_type = Some(VideoSignalType {
+                video_format,
+                video_full_range_flag,
+                colour_description,
+            });
+        }
+
+        if reader.flag()? {
+            vui.chroma_loc_info = Some((
+                reader.ue_max("chroma_sample_loc_type_top_field", 5)? as u8,
+                reader.ue_max("chroma_sample_loc_type_bottom_field", 5)? as u8,
+            ));
+        }
+
+        vui.neutral_chroma_indication_flag = reader.flag()?;
+        vui.field_seq_flag = reader.flag()?;
+        vui.frame_field_info_present_flag = reader.flag()?;
+
+        if reader.flag()? {
+            vui.default_display_window =
+                Some([reader.ue()?, reader.ue()?, reader.ue()?, reader.ue()?]);
+        }
+
+        if reader.flag()? {
+            let num_units_in_tick = reader.u(32)?;
+            let time_scale = reader.u(32)?;
+            let num_ticks_poc_diff_one_minus1 = match reader.flag()? {
+                true => Some(reader.ue()?),
+                false => None,
+            };
+            vui.timing_info = Some(TimingInfo {
+                num_units_in_tick,
+                time_scale,
+                num_ticks_poc_diff_one_minus1,
+            });
+
+            let vui_hrd_parameters_present_flag = reader.flag()?;
+            if vui_hrd_parameters_present_flag {
+                skip_hrd_parameters(reader, sps_max_sub_layers_minus1)?;
+            }
+        }
+
+        if reader.flag()? {
+            vui.bitstream_restriction = Some(BitstreamRestriction {
+                tiles_fixed_structure_flag: reader.flag()?,
+                motion_vectors_over_pic_boundaries_flag: reader.flag()?,
+                restricted_ref_pic_lists_flag: reader.flag()?,
+                min_spatial_segmentation_idc: reader.ue()?,
+                max_bytes_per_pic_denom: reader.ue()?,
+                max_bits_per_min_cu_denom: reader.ue()?,
+                log2_max_mv_length_horizontal: reader.ue()?,
+                log2_max_mv_length_vertical: reader.ue()?,
+            });
+        }
+
+        Ok(vui)
+    }
+}
+
+/// `hrd_parameters(1, maxNumSubLayersMinus1)`
+fn skip_hrd_parameters(
+    reader: &mut BitReader<'_>,
+    max_num_sub_layers_minus1: u8,
+) -> Result<(), H265ParserError> {
+    let nal_hrd_parameters_present_flag = reader.flag()?;
+    let vcl_hrd_parameters_present_flag = reader.flag()?;
+    let mut sub_pic_hrd_params_present_flag = false;
+    if nal_hrd_parameters_present_flag || vcl_hrd_parameters_present_flag {
+        sub_pic_hrd_params_present_flag = reader.flag()?;
+        if sub_pic_hrd_params_present_flag {
+            // tick_divisor_minus2, du_cpb_removal_delay_increment_length_minus1,
+            // sub_pic_cpb_params_in_pic_timing_sei_flag, dpb_output_delay_du_length_minus1
+            reader.skip(8 + 5 + 1 + 5)?;
+        }
+        // bit_rate_scale, cpb_size_scale
+        reader.skip(4 + 4)?;
+        if sub_pic_hrd_params_present_flag {
+            // cpb_size_du_scale
+            reader.skip(4)?;
+        }
+        // initial_cpb_removal_delay_length_minus1, au_cpb_removal_delay_length_minus1,
+        // dpb_output_delay_length_minus1
+        reader.skip(5 + 5 + 5)?;
+    }
+
+    for _ in 0..=max_num_sub_layers_minus1 {
+        let fixed_pic_rate_general_flag = reader.flag()?;
+        let fixed_pic_rate_within_cvs_flag = match fixed_pic_rate_general_flag {
+            true => true,
+            false => reader.flag()?,
+        };
+
+        let mut low_delay_hrd_flag = false;
+        if fixed_pic_rate_within_cvs_flag {
+            // elemental_duration_in_tc_minus1
+            reader.ue()?;
+        } else {
+            low_delay_hrd_flag = reader.flag()?;
+        }
+
+        let cpb_cnt_minus1 = match low_delay_hrd_flag {
+            true => 0,
+            false => reader.ue_max("cpb_cnt_minus1", 31)?,
+        };
+
+        let sub_layer_count =
+            nal_hrd_parameters_present_flag as u8 + vcl_hrd_parameters_present_flag as u8;
+        for _ in 0..sub_layer_count {
+            for _ in 0..=cpb_cnt_minus1 {
+                // bit_rate_value_minus1, cpb_size_value_minus1
+                reader.ue()?;
+                reader.ue()?;
+                if sub_pic_hrd_params_present_flag {
+                    // cpb_size_du_value_minus1, bit_rate_du_value_minus1
+                    reader.ue()?;
+                    reader.ue()?;
+                }
+                // cbr_flag
+                reader.skip(1)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Sequence parameter set with the range extension. Multilayer, 3D and SCC extensions are not
+/// parsed.
+#[derive(Debug, Clone)]
+pub struct SeqParameterSet {
+    pub sps_video_parameter_set_id: u8,
+    pub sps_max_sub_layers_minus1: u8,
+    pub sps_temporal_id_nesting_flag: bool,
+    pub profile_tier_level: ProfileTierLevel,
+    pub sps_seq_parameter_set_id: u8,
+    pub chroma_format_idc: u8,
+    pub separate_colour_plane_flag: bool,
+    pub pic_width_in_luma_samples: u32,
+    pub pic_height_in_luma_samples: u32,
+    /// Left, right, top and bottom offset
+    pub conformance_window: Option<[u32; 4]>,
+    pub bit_depth_luma_minus8: u8,
+    pub bit_depth_chroma_minus8: u8,
+    pub log2_max_pic_order_cnt_lsb_minus4: u8,
+    pub sps_sub_layer_ordering_info_present_flag: bool,
+    pub sub_layer_ordering_info: SubLayerOrderingInfo,
+    pub log2_min_luma_coding_block_size_minus3: u8,
+    pub log2_diff_max_min_luma_coding_block_size: u8,
+    pub log2_min_luma_transform_block_size_minus2: u8,
+    pub log2_diff_max_min_luma_transform_block_size: u8,
+    pub max_transform_hierarchy_depth_inter: u8,
+    pub max_transform_hierarchy_depth_intra: u8,
+    pub scaling_list_enabled_flag: bool,
+    /// `None` if the scaling list data is not present in the SPS
+    pub scaling_list_data: Option<ScalingListData>,
+    pub amp_enabled_flag: bool,
+    pub sample_adaptive_offset_enabled_flag: bool,
+    pub pcm_enabled_flag: bool,
+    pub pcm_sample_bit_depth_luma_minus1: u8,
+    pub pcm_sample_bit_depth_chroma_minus1: u8,
+    pub log2_min_pcm_luma_coding_block_size_minus3: u8,
+    pub log2_diff_max_min_pcm_luma_coding_block_size: u8,
+    pub pcm_loop_filter_disabled_flag: bool,
+    pub short_term_ref_pic_sets: Vec<ShortTermRefPicSet>,
+    pub long_term_ref_pics_present_flag: bool,
+    pub lt_ref_pic_poc_lsb_sps: Vec<u32>,
+    pub used_by_curr_pic_lt_sps_flag: Vec<bool>,
+    pub sps_temporal_mvp_enabled_flag: bool,
+    pub strong_intra_smoothing_enabled_flag: bool,
+    pub vui_parameters: Option<VuiParameters>,
+    pub sps_extension_present_flag: bool,
+    pub sps_range_extension: Option<SpsRangeExtension>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpsRangeExtension {
+    pub transform_skip_rotation_enabled_flag: bool,
+    pub transform_skip_context_enabled_flag: bool,
+    pub implicit_rdpcm_enabled_flag: bool,
+    pub explicit_rdpcm_enabled_flag: bool,
+    pub extended_precision_processing_flag: bool,
+    pub intra_smoothing_disabled_flag: bool,
+    pub high_precision_offsets_enabled_flag: bool,
+    pub persistent_rice_adaptation_enabled_flag: bool,
+    pub cabac_bypass_alignment_enabled_flag: bool,
+}
+
+impl SeqParameterSet {
+    pub(crate) fn parse(rbsp: &[u8]) -> Result<Self, H265ParserError> {
+        let mut reader = BitReader::new(rbsp);
+        let reader = &mut reader;
+
+        let sps_video_parameter_set_id = reader.u(4)? as u8;
+        let sps_max_sub_layers_minus1 = max_sub_layers_minus1(reader)?;
+        let sps_temporal_id_nesting_flag = reader.flag()?;
+        let profile_tier_level = ProfileTierLevel::parse(reader, sps_max_sub_layers_minus1)?;
+        let sps_seq_parameter_set_id = reader.ue_max("sps_seq_parameter_set_id", 15)? as u8;
+        let chroma_format_idc = reader.ue_max("chroma_format_idc", 3)? as u8;
+        let separate_colour_plane_flag = chroma_format_idc == 3 && reader.flag()?;
+        let pic_width_in_luma_samples = reader.ue()?;
+        let pic_height_in_luma_samples = reader.ue()?;
+        let conformance_window = match reader.flag()? {
+            true => Some([reader.ue()?, reader.ue()?, reader.ue()?, reader.ue()?]),
+            false => None,
+        };
+        let bit_depth_luma_minus8 = reader.ue_max("bit_depth_luma_minus8", 8)? as u8;
+        let bit_depth_chroma_minus8 = reader.ue_max("bit_depth_chroma_minus8", 8)? as u8;
+        let log2_max_pic_order_cnt_lsb_minus4 =
+            reader.ue_max("log2_max_pic_order_cnt_lsb_minus4", 12)? as u8;
+        let sps_sub_layer_ordering_info_present_flag = reader.flag()?;
+        let sub_layer_ordering_info = SubLayerOrderingInfo::parse(
+            reader,
+            sps_sub_layer_ordering_info_present_flag,
+            sps_max_sub_layers_minus1,
+        )?;
+        let log2_min_luma_coding_block_size_minus3 =
+            reader.ue_max("log2_min_luma_coding_block_size_minus3", 3)? as u8;
+        let log2_diff_max_min_luma_coding_block_size =
+            reader.ue_max("log2_diff_max_min_luma_coding_block_size", 3)? as u8;
+        let log2_min_luma_transform_block_size_minus2 =
+            reader.ue_max("log2_min_luma_transform_block_size_minus2", 3)? as u8;
+        let log2_diff_max_min_luma_transform_block_size =
+            reader.ue_max("log2_diff_max_min_luma_transform_block_size", 3)? as u8;
+        let max_transform_hierarchy_depth_inter =
+            reader.ue_max("max_transform_hierarchy_depth_inter", 4)? as u8;
+        let max_transform_hierarchy_depth_intra =
+            reader.ue_max("max_transform_hierarchy_depth_intra", 4)? as u8;
+
+        let scaling_list_enabled_flag = reader.flag()?;
+        let scaling_list_data = match scaling_list_enabled_flag && reader.flag()? {
+            true => Some(ScalingListData::parse(reader)?),
+            false => None,
+        };
+
+        let amp_enabled_flag = reader.flag()?;
+        let sample_adaptive_offset_enabled_flag = reader.flag()?;
+        let pcm_enabled_flag = reader.flag()?;
+        let mut pcm_sample_bit_depth_luma_minus1 = 0;
+        let mut pcm_sample_bit_depth_chroma_minus1 = 0;
+        let mut log2_min_pcm_luma_coding_block_size_minus3 = 0;
+        let mut log2_diff_max_min_pcm_luma_coding_block_size = 0;
+        let mut pcm_loop_filter_disabled_flag = false;
+        if pcm_enabled_flag {
+            pcm_sample_bit_depth_luma_minus1 = reader.u(4)? as u8;
+            pcm_sample_bit_depth_chroma_minus1 = reader.u(4)? as u8;
+            log2_min_pcm_luma_coding_block_size_minus3 =
+                reader.ue_max("log2_min_pcm_luma_coding_block_size_minus3", 2)? as u8;
+            log2_diff_max_min_pcm_luma_coding_block_size =
+                reader.ue_max("log2_diff_max_min_pcm_luma_coding_block_size", 2)? as u8;
+            pcm_loop_filter_disabled_flag = reader.flag()?;
+        }
+
+        let num_short_term_ref_pic_sets = reader.ue_max("num_short_term_ref_pic_sets", 64)?;
+        let mut short_term_ref_pic_sets = Vec::new();
+        for i in 0..num_short_term_ref_pic_sets as usize {
+            let set = ShortTermRefPicSet::parse(
+                reader,
+                i,
+                num_short_term_ref_pic_sets as usize,
+                &short_term_ref_pic_sets,
+            )?;
+            short_term_ref_pic_sets.push(set);
+        }
+
+        let long_term_ref_pics_present_flag = reader.flag()?;
+        let mut lt_ref_pic_poc_lsb_sps = Vec::new();
+        let mut used_by_curr_pic_lt_sps_flag = Vec::new();
+        if long_term_ref_pics_present_flag {
+            let num_long_term_ref_pics_sps = reader.ue_max("num_long_term_ref_pics_sps", 32)?;
+            for _ in 0..num_long_term_ref_pics_sps {
+                lt_ref_pic_poc_lsb_sps
+                    .push(reader.u(log2_max_pic_order_cnt_lsb_minus4 as u32 + 4)?);
+                used_by_curr_pic_lt_sps_flag.push(reader.flag()?);
+            }
+        }
+
+        let sps_temporal_mvp_enabled_flag = reader.flag()?;
+        let strong_intra_smoothing_enabled_flag = reader.flag()?;
+        let vui_parameters = match reader.flag()? {
+            true => Some(VuiParameters::parse(reader, sps_max_sub_layers_minus1)?),
+            false => None,
+        };
+
+        let sps_extension_present_flag = reader.flag()?;
+        let mut sps_range_extension = None;
+        if sps_extension_present_flag {
+            let sps_range_extension_flag = reader.flag()?;
+            // sps_multilayer_extension_flag, sps_3d_extension_flag, sps_scc_extension_flag,
+            // sps_extension_4bits
+            reader.skip(7)?;
+            if sps_range_extension_flag {
+                sps_range_extension = Some(SpsRangeExtension {
+                    transform_skip_rotation_enabled_flag: reader.flag()?,
+                    transform_skip_context_enabled_flag: reader.flag()?,
+                    implicit_rdpcm_enabled_flag: reader.flag()?,
+                    explicit_rdpcm_enabled_flag: reader.flag()?,
+                    extended_precision_processing_flag: reader.flag()?,
+                    intra_smoothing_disabled_flag: reader.flag()?,
+                    high_precision_offsets_enabled_flag: reader.flag()?,
+                    persistent_rice_adaptation_enabled_flag: reader.flag()?,
+                    cabac_bypass_alignment_enabled_flag: reader.flag()?,
+                });
+            }
+        }
+
+        Ok(Self {
+            sps_video_parameter_set_id,
+            sps_max_sub_layers_minus1,
+            sps_temporal_id_nesting_flag,
+            profile_tier_level,
+            sps_seq_parameter_set_id,
+            chroma_format_idc,
+            separate_colour_plane_flag,
+            pic_width_in_luma_samples,
+            pic_height_in_luma_samples,
+            conformance_window,
+            bit_depth_luma_minus8,
+            bit_depth_chroma_minus8,
+            log2_max_pic_order_cnt_lsb_minus4,
+            sps_sub_layer_ordering_info_present_flag,
+            sub_layer_ordering_info,
+            log2_min_luma_coding_block_size_minus3,
+            log2_diff_max_min_luma_coding_block_size,
+            log2_min_luma_transform_block_size_minus2,
+            log2_diff_max_min_luma_transform_block_size,
+            max_transform_hierarchy_depth_inter,
+            max_transform_hierarchy_depth_intra,
+            scaling_list_enabled_flag,
+            scaling_list_data,
+            amp_enabled_flag,
+            sample_adaptive_offset_enabled_flag,
+            pcm_enabled_flag,
+            pcm_sample_bit_depth_luma_minus1,
+            pcm_sample_bit_depth_chroma_minus1,
+            log2_min_pcm_luma_coding_block_size_minus3,
+            log2_diff_max_min_pcm_luma_coding_block_size,
+            pcm_loop_filter_disabled_flag,
+            short_term_ref_pic_sets,
+            long_term_ref_pics_present_flag,
+            lt_ref_pic_poc_lsb_sps,
+            used_by_curr_pic_lt_sps_flag,
+            sps_temporal_mvp_enabled_flag,
+            strong_intra_smoothing_enabled_flag,
+            vui_parameters,
+            sps_extension_present_flag,
+            sps_range_extension,
+        })
+    }
+
+    /// `MaxPicOrderCntLsb`
+    pub fn max_pic_order_cnt_lsb(&self) -> i32 {
+        1 << (self.log2_max_pic_order_cnt_lsb_minus4 + 4)
+    }
+
+    /// `CtbLog2SizeY`
+    pub fn ctb_log2_size(&self) -> u32 {
+        self.log2_min_luma_coding_block_size_minus3 as u32
+            + 3
+            + self.log2_diff_max_min_luma_coding_block_size as u32
+    }
+
+    /// `PicSizeInCtbsY`
+    pub fn pic_size_in_ctbs(&self) -> u32 {
+        let ctb_size = 1 << self.ctb_log2_size();
+        self.pic_width_in_luma_samples.div_ceil(ctb_size)
+            * self.pic_height_in_luma_samples.div_ceil(ctb_size)
+    }
+
+    /// `SubWidthC` and `SubHeightC`
+    pub fn chroma_subsampling(&self) -> (u32, u32) {
+        match (self.chroma_format_idc, self.separate_colour_plane_flag) {
+            (1, _) => (2, 2),
+            (2, _) => (2, 1),
+            _ => (1, 1),
+        }
+    }
+
+    /// Values of the highest sub-layer, which are used for decoding all of them.
+    pub fn max_dec_pic_buffering(&self) -> u32 {
+        self.sub_layer_ordering_info.max_dec_pic_buffering_minus1
+            [self.sps_max_sub_layers_minus1 as usize] as u32
+            + 1
+    }
+
+    pub fn max_num_reorder_pics(&self) -> u32 {
+        self.sub_layer_ordering_info.max_num_reorder_pics[self.sps_max_sub_layers_minus1 as usize]
+            as u32
+    }
+}
+
+/// Picture parameter set with the range extension. Multilayer, 3D and SCC extensions are not
+/// parsed.
+#[derive(Debug, Clone)]
+pub struct PicParameterSet {
+    pub pps_pic_parameter_set_id: u8,
+    pub pps_seq_parameter_set_id: u8,
+    pub dependent_slice_segments_enabled_flag: bool,
+    pub output_flag_present_flag: bool,
+    pub num_extra_slice_header_bits: u8,
+    pub sign_data_hiding_enabled_flag: bool,
+    pub cabac_init_present_flag: bool,
+    pub num_ref_idx_l0_default_active_minus1: u8,
+    pub num_ref_idx_l1_default_active_minus1: u8,
+    pub init_qp_minus26: i8,
+    pub constrained_intra_pred_flag: bool,
+    pub transform_skip_enabled_flag: bool,
+    pub cu_qp_delta_enabled_flag: bool,
+    pub diff_cu_qp_delta_depth: u8,
+    pub pps_cb_qp_offset: i8,
+    pub pps_cr_qp_offset: i8,
+    pub pps_slice_chroma_qp_offsets_present_flag: bool,
+    pub weighted_pred_flag: bool,
+    pub weighted_bipred_flag: bool,
+    pub transquant_bypass_enabled_flag: bool,
+    pub tiles_enabled_flag: bool,
+    pub entropy_coding_sync_enabled_flag: bool,
+    pub num_tile_columns_minus1: u8,
+    pub num_tile_rows_minus1: u8,
+    pub uniform_spacing_flag: bool,
+    pub column_width_minus1: Vec<u16>,
+    pub row_height_minus1: Vec<u16>,
+    pub loop_filter_across_tiles_enabled_flag: bool,
+    pub pps_loop_filter_across_slices_enabled_flag: bool,
+    pub deblocking_filter_control_present_flag: bool,
+    pub deblocking_filter_override_enabled_flag: bool,
+    pub pps_deblocking_filter_disabled_flag: bool,
+    pub pps_beta_offset_div2: i8,
+    pub pps_tc_offset_div2: i8,
+    pub scaling_list_data: Option<ScalingListData>,
+    pub lists_modification_present_flag: bool,
+    pub log2_parallel_merge_level_minus2: u8,
+    pub slice_segment_header_extension_present_flag: bool,
+    pub pps_extension_present_flag: bool,
+    pub pps_range_extension: Option<PpsRangeExtension>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PpsRangeExtension {
+    pub log2_max_transform_skip_block_size_minus2: u8,
+    pub cross_component_prediction_enabled_flag: bool,
+    pub chroma_qp_offset_list_enabled_flag: bool,
+    pub diff_cu_chroma_qp_offset_depth: u8,
+    pub chroma_qp_offset_list_len_minus1: u8,
+    pub cb_qp_offset_list: Vec<i8>,
+    pub cr_qp_offset_list: Vec<i8>,
+    pub log2_sao_offset_scale_luma: u8,
+    pub log2_sao_offset_scale_chroma: u8,
+}
+
+impl PicParameterSet {
+    pub(crate) fn parse(rbsp: &[u8]) -> Result<Self, H265ParserError> {
+        let mut reader = BitReader::new(rbsp);
+        let reader = &mut reader;
+
+        let pps_pic_parameter_set_id = reader.ue_max("pps_pic_parameter_set_id", 63)? as u8;
+        let pps_seq_parameter_set_id = reader.ue_max("pps_seq_parameter_set_id", 15)? as u8;
+        let dependent_slice_segments_enabled_flag = reader.flag()?;
+        let output_flag_present_flag = reader.flag()?;
+        let num_extra_slice_header_bits = reader.u(3)? as u8;
+        let sign_data_hiding_enabled_flag = reader.flag()?;
+        let cabac_init_present_flag = reader.flag()?;
+        let num_ref_idx_l0_default_active_minus1 =
+            reader.ue_max("num_ref_idx_l0_default_active_minus1", 14)? as u8;
+        let num_ref_idx_l1_default_active_minus1 =
+            reader.ue_max("num_ref_idx_l1_default_active_minus1", 14)? as u8;
+        let init_qp_minus26 = signed_in_range(reader, "init_qp_minus26", -(26 + 48), 25)?;
+        let constrained_intra_pred_flag = reader.flag()?;
+        let transform_skip_enabled_flag = reader.flag()?;
+        let cu_qp_delta_enabled_flag = reader.flag()?;
+        let diff_cu_qp_delta_depth = match cu_qp_delta_enabled_flag {
+            true => reader.ue_max("diff_cu_qp_delta_depth", 3)? as u8,
+            false => 0,
+        };
+        let pps_cb_qp_offset = signed_in_range(reader, "pps_cb_qp_offset", -12, 12)?;
+        let pps_cr_qp_offset = signed_in_range(reader, "pps_cr_qp_offset", -12, 12)?;
+        let pps_slice_chroma_qp_offsets_present_flag = reader.flag()?;
+        let weighted_pred_flag = reader.flag()?;
+        let weighted_bipred_flag = reader.flag()?;
+        let transquant_bypass_enabled_flag = reader.flag()?;
+        let tiles_enabled_flag = reader.flag()?;
+        let entropy_coding_sync_enabled_flag = reader.flag()?;
+
+        let mut num_tile_columns_minus1 = 0;
+        let mut num_tile_rows_minus1 = 0;
+        let mut uniform_spacing_flag = true;
+        let mut column_width_minus1 = Vec::new();
+        let mut row_height_minus1 = Vec::new();
+        let mut loop_filter_across_tiles_enabled_flag = true;
+        if tiles_enabled_flag {
+            num_tile_columns_minus1 = reader.ue_max("num_tile_columns_minus1", 19)? as u8;
+            num_tile_rows_minus1 = reader.ue_max("num_tile_rows_minus1", 21)? as u8;
+            uniform_spacing_flag = reader.flag()?;
+            if !uniform_spacing_flag {
+                for _ in 0..num_tile_columns_minus1 {
+                    column_width_minus1.push(reader.ue_max("column_width_minus1", 0xFFFF)? as u16);
+                }
+                for _ in 0..num_tile_rows_minus1 {
+                    row_height_minus1.push(reader.ue_max("row_height_minus1", 0xFFFF)? as u16);
+                }
+            }
+            loop_filter_across_tiles_enabled_flag = reader.flag()?;
+        }
+
+        let pps_loop_filter_across_slices_enabled_flag = reader.flag()?;
+        let deblocking_filter_control_present_flag = reader.flag()?;
+        let mut deblocking_filter_override_enabled_flag = false;
+        let mut pps_deblocking_filter_disabled_flag = false;
+        let mut pps_beta_offset_div2 = 0;
+        let mut pps_tc_offset_div2 = 0;
+        if deblocking_filter_control_present_flag {
+            deblocking_filter_override_enabled_flag = reader.flag()?;
+            pps_deblocking_filter_disabled_flag = reader.flag()?;
+            if !pps_deblocking_filter_disabled_flag {
+                pps_beta_offset_div2 = signed_in_range(reader, "pps_beta_offset_div2", -6, 6)?;
+                pps_tc_offset_div2 = signed_in_range(reader, "pps_tc_offset_div2", -6, 6)?;
+            }
+        }
+
+        let scaling_list_data = match reader.flag()? {
+            true => Some(ScalingListData::parse(reader)?),
+            false => None,
+        };
+        let lists_modification_present_flag = reader.flag()?;
+        let log2_parallel_merge_level_minus2 =
+            reader.ue_max("log2_parallel_merge_level_minus2", 4)? as u8;
+        let slice_segment_header_extension_present_flag = reader.flag()?;
+        let pps_extension_present_flag = reader.flag()?;
+
+        let mut pps_range_extension = None;
+        if pps_extension_present_flag {
+            let pps_range_extension_flag = reader.flag()?;
+            // pps_multilayer_extension_flag, pps_3d_extension_flag, pps_scc_extension_flag,
+            // pps_extension_4bits
+            reader.skip(7)?;
+            if pps_range_extension_flag {
+                let mut extension = PpsRangeExtension::default();
+                if transform_skip_enabled_flag {
+                    extension.log2_max_transform_skip_block_size_minus2 =
+                        reader.ue_max("log2_max_transform_skip_block_size_minus2", 3)? as u8;
+                }
+                extension.cross_component_prediction_enabled_flag = reader.flag()?;
+                extension.chroma_qp_offset_list_enabled_flag = reader.flag()?;
+                if extension.chroma_qp_offset_list_enabled_flag {
+                    extension.diff_cu_chroma_qp_offset_depth =
+                        reader.ue_max("diff_cu_chroma_qp_offset_depth", 3)? as u8;
+                    extension.chroma_qp_offset_list_len_minus1 =
+                        reader.ue_max("chroma_qp_offset_list_len_minus1", 5)? as u8;
+                    for _ in 0..=extension.chroma_qp_offset_list_len_minus1 {
+                        extension.cb_qp_offset_list.push(signed_in_range(
+                            reader,
+                            "cb_qp_offset_list",
+                            -12,
+                            12,
+                        )?);
+                        extension.cr_qp_offset_list.push(signed_in_range(
+                            reader,
+                            "cr_qp_offset_list",
+                            -12,
+                            12,
+                        )?);
+                    }
+                }
+                extension.log2_sao_offset_scale_luma =
+                    reader.ue_max("log2_sao_offset_scale_luma", 6)? as u8;
+                extension.log2_sao_offset_scale_chroma =
+                    reader.ue_max("log2_sao_offset_scale_chroma", 6)? as u8;
+                pps_range_extension = Some(extension);
+            }
+        }
+
+        Ok(Self {
+            pps_pic_parameter_set_id,
+            pps_seq_parameter_set_id,
+            dependent_slice_segments_enabled_flag,
+            output_flag_present_flag,
+            num_extra_slice_header_bits,
+            sign_data_hiding_enabled_flag,
+            cabac_init_present_flag,
+            num_ref_idx_l0_default_active_minus1,
+            num_ref_idx_l1_default_active_minus1,
+            init_qp_minus26,
+            constrained_intra_pred_flag,
+            transform_skip_enabled_flag,
+            cu_qp_delta_enabled_flag,
+            diff_cu_qp_delta_depth,
+            pps_cb_qp_offset,
+            pps_cr_qp_offset,
+            pps_slice_chroma_qp_offsets_present_flag,
+            weighted_pred_flag,
+            weighted_bipred_flag,
+            transquant_bypass_enabled_flag,
+            tiles_enabled_flag,
+            entropy_coding_sync_enabled_flag,
+            num_tile_columns_minus1,
+            num_tile_rows_minus1,
+            uniform_spacing_flag,
+            column_width_minus1,
+            row_height_minus1,
+            loop_filter_across_tiles_enabled_flag,
+            pps_loop_filter_across_slices_enabled_flag,
+            deblocking_filter_control_present_flag,
+            deblocking_filter_override_enabled_flag,
+            pps_deblocking_filter_disabled_flag,
+            pps_beta_offset_div2,
+            pps_tc_offset_div2,
+            scaling_list_data,
+            lists_modification_present_flag,
+            log2_parallel_merge_level_minus2,
+            slice_segment_header_extension_present_flag,
+            pps_extension_present_flag,
+            pps_range_extension,
+        })
+    }
+}
+
+fn max_sub_layers_minus1(reader: &mut BitReader<'_>) -> Result<u8, H265ParserError> {
+    let value = reader.u(3)?;
+    if value > 6 {
+        return Err(H265ParserError::InvalidValue {
+            name: "max_sub_layers_minus1",
+            value: value as i64,
+        });
+    }
+
+    Ok(value as u8)
+}
+
+fn signed_in_range(
+    reader: &mut BitReader<'_>,
+    name: &'static str,
+    min: i32,
+    max: i32,
+) -> Result<i8, H265ParserError> {
+    let value = reader.se()?;
+    if !(min..=max).contains(&value) {
+        return Err(H265ParserError::InvalidValue {
+            name,
+            value: value as i64,
+        });
+    }
+
+    Ok(value as i8)
+}
@@ -0,0 +1,534 @@
+use crate::{
+    parameters::{MissedFrameHandling, PictureType},
+    parser::{
+        ChunkTimestamps,
+        reference_manager::{ReferenceId, ReferenceManagementError},
+    },
+};
+
+use super::{SliceType, decoder_instructions::DecoderInstruction, nalu_parser::Slice};
+
+#[derive(Debug, Clone, Copy)]
+struct ReferencePicture {
+    id: ReferenceId,
+    pic_order_cnt: i32,
+    long_term: bool,
+}
+
+/// Tracks the decoded picture buffer according to the reference picture set (RPS) of every
+/// decoded picture, as described in section 8.3.2 of the H.265 spec.
+#[derive(Debug, Default)]
+pub(crate) struct ReferenceContext {
+    /// Pictures marked as "used for reference"
+    pictures: Vec<ReferencePicture>,
+    next_reference_id: ReferenceId,
+    /// `PicOrderCntVal` of the previous picture with `TemporalId` equal to 0 that is not a RASL,
+    /// RADL or sub-layer non-reference picture.
+    prev_tid0_pic_order_cnt: i32,
+    /// When `false`, the next IRAP picture starts a new coded video sequence, i.e. it has
+    /// `NoRaslOutputFlag` equal to 1.
+    sequence_started: bool,
+    /// RASL pictures associated with an IRAP picture with `NoRaslOutputFlag` equal to 1 reference
+    /// pictures which are not available and are not decoded.
+    skip_rasl_pictures: bool,
+    detected_missed_frames: bool,
+    missed_frame_handling: MissedFrameHandling,
+    keyframes_only: bool,
+}
+
+impl ReferenceContext {
+    pub fn new(missed_frame_handling: MissedFrameHandling, keyframes_only: bool) -> Self {
+        Self {
+            missed_frame_handling,
+            keyframes_only,
+            ..Default::default()
+        }
+    }
+
+    fn next_reference_id(&mut self) -> ReferenceId {
+        let result = self.next_reference_id;
+        self.next_reference_id = result.next();
+        result
+    }
+
+    fn reset_state(&mut self) {
+        *self = Self {
+            sequence_started: true,
+            skip_rasl_pictures: true,
+            ..Self::new(self.missed_frame_handling, self.keyframes_only)
+        };
+    }
+
+    /// Drops all pictures from the DPB and forgets the state of the stream, so that decoding
+    /// starts again from the next IRAP picture.
+    pub(crate) fn reset(&mut self) -> Vec<DecoderInstruction> {
+        let reference_ids = self
+            .pictures
+            .iter()
+            .map(|picture| picture.id)
+            .collect::<Vec<_>>();
+
+        *self = Self::new(self.missed_frame_handling, self.keyframes_only);
+
+        match reference_ids.is_empty() {
+            true => Vec::new(),
+            false => vec![DecoderInstruction::Drop { reference_ids }],
+        }
+    }
+
+    pub(crate) fn mark_missed_frames(&mut self) {
+        self.detected_missed_frames = true;
+        // The next CRA picture is handled as a BLA picture
+        self.sequence_started = false;
+    }
+
+    pub(crate) fn mark_end_of_sequence(&mut self) {
+        self.sequence_started = false;
+    }
+
+    pub(crate) fn put_picture(
+        &mut self,
+        mut slices: Vec<(Slice, ChunkTimestamps)>,
+    ) -> Result<Vec<DecoderInstruction>, ReferenceManagementError> {
+        let Some((first_slice, timestamps)) = slices.first() else {
+            return Ok(Vec::new());
+        };
+        let timestamps = *timestamps;
+        let picture_type = picture_type(&slices);
+        let nal_header = first_slice.nal_header;
+        let header = first_slice.header.clone();
+        let sps = first_slice.sps.clone();
+        let pps = first_slice.pps.clone();
+
+        let is_irap = nal_header.is_irap();
+        if self.keyframes_only && !is_irap {
+            return Ok(Vec::new());
+        }
+
+        let no_rasl_output_flag = is_irap
+            && (nal_header.is_idr()
+                || nal_header.is_bla()
+                || !self.sequence_started
+                || self.keyframes_only);
+
+        if is_irap && !no_rasl_output_flag {
+            self.skip_rasl_pictures = false;
+        }
+
+        if nal_header.is_rasl() && self.skip_rasl_pictures {
+            return Ok(Vec::new());
+        }
+
+        if !is_irap
+            && self.detected_missed_frames
+            && self.missed_frame_handling == MissedFrameHandling::Strict
+        {
+            return Err(ReferenceManagementError::MissingFrame);
+        }
+
+        let pic_order_cnt = self.decode_pic_order_cnt(&slices[0].0, no_rasl_output_flag);
+        let rps = ReferencePictureSet::new(&slices[0].0, pic_order_cnt)?;
+
+        let (kept, references) = match no_rasl_output_flag {
+            true => (Vec::new(), CurrentReferences::default()),
+            false => self.apply_reference_picture_set(&rps)?,
+        };
+
+        let mut rbsp_bytes = Vec::new();
+        let mut slice_indices = Vec::new();
+        for (slice, _) in &mut slices {
+            slice_indices.push(rbsp_bytes.len());
+            rbsp_bytes.append(&mut slice.rbsp_bytes);
+        }
+
+        let num_delta_pocs_of_ref_rps_idx = header
+            .short_term_ref_pic_set
+            .as_ref()
+            .filter(|set| set.inter_ref_pic_set_prediction_flag)
+            .map(|set| set.num_delta_pocs_of_ref_rps)
+            .unwrap_or(0);
+
+        let mut instructions = Vec::new();
+        if no_rasl_output_flag {
+            // all reference pictures are marked as "unused for reference". The decoder drops
+            // them when it receives the IRAP instruction.
+            self.reset_state();
+        } else {
+            let reference_ids = self
+                .pictures
+                .iter()
+                .zip(&kept)
+                .filter(|(_, kept)| !**kept)
+                .map(|(picture, _)| picture.id)
+                .collect::<Vec<_>>();
+            let mut kept = kept.iter();
+            self.pictures.retain(|_| *kept.next().unwrap());
+
+            if !reference_ids.is_empty() {
+                instructions.push(DecoderInstruction::Drop { reference_ids });
+            }
+        }
+
+        if nal_header.temporal_id() == 0
+            && !nal_header.is_rasl()
+            && !nal_header.is_radl()
+            && !nal_header.is_sub_layer_non_reference()
+        {
+            self.prev_tid0_pic_order_cnt = pic_order_cnt;
+        }
+
+        let decode_info = DecodeInformation {
+            vps_id: sps.sps_video_parameter_set_id,
+            sps_id: sps.sps_seq_parameter_set_id,
+            pps_id: pps.pps_pic_parameter_set_id,
+            pic_order_cnt,
+            is_irap,
+            is_idr: nal_header.is_idr(),
+            short_term_ref_pic_set_sps_flag: header.short_term_ref_pic_set_sps_flag,
+            num_delta_pocs_of_ref_rps_idx,
+            num_bits_for_st_ref_pic_set_in_slice: header.num_bits_for_st_ref_pic_set_in_slice,
+            ref_pic_set_st_curr_before: references.st_curr_before,
+            ref_pic_set_st_curr_after: references.st_curr_after,
+            ref_pic_set_lt_curr: references.lt_curr,
+            rbsp_bytes,
+            slice_indices,
+            pts: timestamps.pts,
+            dts: timestamps.dts,
+            picture_type,
+            corrupted: self.detected_missed_frames,
+        };
+
+        // Every decoded picture is marked as "used for short-term reference", it stays in the
+        // decoded picture buffer until the RPS of a later picture doesn't include it.
+        let reference_id = self.next_reference_id();
+        self.pictures.push(ReferencePicture {
+            id: reference_id,
+            pic_order_cnt,
+            long_term: false,
+        });
+
+        instructions.push(match no_rasl_output_flag {
+            true => DecoderInstruction::Irap {
+                decode_info,
+                reference_id,
+            },
+            false => DecoderInstruction::Decode {
+                decode_info,
+                reference_id,
+            },
+        });
+
+        Ok(instructions)
+    }
+
+    /// Section 8.3.1
+    fn decode_pic_order_cnt(&self, slice: &Slice, no_rasl_output_flag: bool) -> i32 {
+        let max_pic_order_cnt_lsb = slice.sps.max_pic_order_cnt_lsb();
+        let pic_order_cnt_lsb = slice.header.slice_pic_order_cnt_lsb as i32;
+
+        let pic_order_cnt_msb = if no_rasl_output_flag {
+            0
+        } else {
+            let prev_pic_order_cnt_lsb = self.prev_tid0_pic_order_cnt & (max_pic_order_cnt_lsb - 1);
+            let prev_pic_order_cnt_msb = self.prev_tid0_pic_order_cnt - prev_pic_order_cnt_lsb;
+
+            if pic_order_cnt_lsb < prev_pic_order_cnt_lsb
+                && prev_pic_order_cnt_lsb - pic_order_cnt_lsb >= max_pic_order_cnt_lsb / 2
+            {
+                prev_pic_order_cnt_msb + max_pic_order_cnt_lsb
+            } else if pic_order_cnt_lsb > prev_pic_order_cnt_lsb
+                && pic_order_cnt_lsb - prev_pic_order_cnt_lsb > max_pic_order_cnt_lsb / 2
+            {
+                prev_pic_order_cnt_msb - max_pic_order_cnt_lsb
+            } else {
+                prev_pic_order_cnt_msb
+            }
+        };
+
+        pic_order_cnt_msb + pic_order_cnt_lsb
+    }
+
+    /// Index of the picture used in place of a missing reference picture when missed frames are
+    /// concealed, the one nearest to it in output order.
+    fn concealment_substitute(&self, entry: &RpsEntry) -> Option<usize> {
+        if !entry.used_by_curr_pic || self.missed_frame_handling != MissedFrameHandling::Conceal {
+            return None;
+        }
+
+        self.pictures
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, picture)| picture.pic_order_cnt.abs_diff(entry.pic_order_cnt))
+            .map(|(i, _)| i)
+    }
+
+    /// Finds the pictures from the RPS in the decoded picture buffer and marks long-term pictures.
+    /// Returns which pictures remain in the DPB and the pictures used by the current picture.
+    fn apply_reference_picture_set(
+        &mut self,
+        rps: &ReferencePictureSet,
+    ) -> Result<(Vec<bool>, CurrentReferences), ReferenceManagementError> {
+        let mut kept = vec![false; self.pictures.len()];
+        let mut references = CurrentReferences::default();
+        let mut missing_reference = false;
+
+        for entry in &rps.lt_entries {
+            let position = self
+                .pictures
+                .iter()
+                .position(|picture| match entry.msb_present {
+                    true => picture.pic_order_cnt == entry.pic_order_cnt,
+                    false => {
+                        picture.pic_order_cnt & (rps.max_pic_order_cnt_lsb - 1)
+                            == entry.pic_order_cnt
+                    }
+                });
+
+            match position {
+                Some(i) => {
+                    kept[i] = true;
+                    self.pictures[i].long_term = true;
+                    references.push(entry, &self.pictures[i]);
+                }
+                None => {
+                    missing_reference |= entry.used_by_curr_pic;
+                    if let Some(i) = self.concealment_substitute(entry) {
+                        kept[i] = true;
+                        references.push(entry, &self.pictures[i]);
+                    }
+                }
+            }
+        }
+
+        for entry in &rps.st_entries {
+            let position = self.pictures.iter().position(|picture| {
+                !picture.long_term && picture.pic_order_cnt == entry.pic_order_cnt
+            });
+
+            match position {
+                Some(i) => {
+                    kept[i] = true;
+                    references.push(entry, &self.pictures[i]);
+                }
+                None => {
+                    missing_reference |= entry.used_by_curr_pic;
+                    if let Some(i) = self.concealment_substitute(entry) {
+                        kept[i] = true;
+                        references.push(entry, &self.pictures[i]);
+                    }
+                }
+            }
+        }
+
+        if missing_reference {
+            match self.missed_frame_handling {
+                MissedFrameHandling::Strict => {
+                    self.detected_missed_frames = true;
+                    return Err(ReferenceManagementError::MissingFrame);
+                }
+                // missing pictures are left out of the reference lists or replaced with
+                // substitutes, the following pictures are marked as corrupted
+                MissedFrameHandling::Tolerant | MissedFrameHandling::Conceal => {
+                    self.detected_missed_frames = true;
+                }
+            }
+        }
+
+        Ok((kept, references))
+    }
+}
+
+struct RpsEntry {
+    pic_order_cnt: i32,
+    used_by_curr_pic: bool,
+    /// Only used for long-term entries, `false` means that only the LSBs of the POC are known.
+    msb_present: bool,
+    kind: RpsEntryKind,
+}
+
+#[derive(Clone, Copy)]
+enum RpsEntryKind {
+    Before,
+    After,
+    LongTerm,
+}
+
+struct ReferencePictureSet {
+    max_pic_order_cnt_lsb: i32,
+    st_entries: Vec<RpsEntry>,
+    lt_entries: Vec<RpsEntry>,
+}
+
+impl ReferencePictureSet {
+    fn new(slice: &Slice, pic_order_cnt: i32) -> Result<Self, ReferenceManagementError> {
+        let max_pic_order_cnt_lsb = slice.sps.max_pic_order_cnt_lsb();
+        let mut rps = Self {
+            max_pic_order_cnt_lsb,
+            st_entries: Vec::new(),
+            lt_entries: Vec::new(),
+        };
+
+        // IDR pictures have an empty RPS
+        if slice.nal_header.is_idr() {
+            return Ok(rps);
+        }
+
+        let short_term_set = slice.header.short_term_ref_pic_set(&slice.sps).ok_or(
+            ReferenceManagementError::IncorrectData(format!(
+                "short_term_ref_pic_set_idx {} is out of range",
+                slice.header.short_term_ref_pic_set_idx
+            )),
+        )?;
+
+        let before = short_term_set
+            .delta_poc_s0
+            .iter()
+            .zip(&short_term_set.used_by_curr_pic_s0)
+            .map(|(delta, used)| (delta, used, RpsEntryKind::Before));
+        let after = short_term_set
+            .delta_poc_s1
+            .iter()
+            .zip(&short_term_set.used_by_curr_pic_s1)
+            .map(|(delta, used)| (delta, used, RpsEntryKind::After));
+        rps.st_entries = before
+            .chain(after)
+            .map(|(delta, &used_by_curr_pic, kind)| RpsEntry {
+                pic_order_cnt: pic_order_cnt + delta,
+                used_by_curr_pic,
+                msb_present: true,
+                kind,
+            })
+            .collect();
+
+        rps.lt_entries = slice
+            .header
+            .long_term_pics
+            .iter()
+            .map(|picture| {
+                let poc_lsb_lt = picture.poc_lsb_lt as i32;
+                let (pic_order_cnt, msb_present) = match picture.delta_poc_msb_cycle_lt {
+                    // (8-5)
+                    Some(delta_poc_msb_cycle_lt) => (
+                        poc_lsb_lt + pic_order_cnt
+                            - delta_poc_msb_cycle_lt as i32 * max_pic_order_cnt_lsb
+                            - (pic_order_cnt & (max_pic_order_cnt_lsb - 1)),
+                        true,
+                    ),
+                    None => (poc_lsb_lt, false),
+                };
+
+                RpsEntry {
+                    pic_order_cnt,
+                    used_by_curr_pic: picture.used_by_curr_pic_lt_flag,
+                    msb_present,
+                    kind: RpsEntryKind::LongTerm,
+                }
+            })
+            .collect();
+
+        Ok(rps)
+    }
+}
+
+/// `RefPicSetStCurrBefore`, `RefPicSetStCurrAfter` and `RefPicSetLtCurr`
+#[derive(Default)]
+struct CurrentReferences {
+    st_curr_before: Vec<ReferencePictureInfo>,
+    st_curr_after: Vec<ReferencePictureInfo>,
+    lt_curr: Vec<ReferencePictureInfo>,
+}
+
+impl CurrentReferences {
+    fn push(&mut self, entry: &RpsEntry, picture: &ReferencePicture) {
+        if !entry.used_by_curr_pic {
+            return;
+        }
+
+        let info = ReferencePictureInfo {
+            id: picture.id,
+            pic_order_cnt: picture.pic_order_cnt,
+            long_term: picture.long_term,
+        };
+        match entry.kind {
+            RpsEntryKind::Before => self.st_curr_before.push(info),
+            RpsEntryKind::After => self.st_curr_after.push(info),
+            RpsEntryKind::LongTerm => self.lt_curr.push(info),
+        }
+    }
+}
+
+/// B if any slice of the picture is a B slice, P if any is a P slice, I otherwise.
+fn picture_type(slices: &[(Slice, ChunkTimestamps)]) -> PictureType {
+    let mut picture_type = PictureType::I;
+    for (slice, _) in slices {
+        match slice.header.slice_type {
+            SliceType::B => return PictureType::B,
+            SliceType::P => picture_type = PictureType::P,
+            SliceType::I => {}
+        }
+    }
+
+    picture_type
+}
+
+#[derive(Clone)]
+pub(crate) struct DecodeInformation {
+    pub(crate) vps_id: u8,
+    pub(crate) sps_id: u8,
+    pub(crate) pps_id: u8,
+    pub(crate) pic_order_cnt: i32,
+    pub(crate) is_irap: bool,
+    pub(crate) is_idr: bool,
+    pub(crate) short_term_ref_pic_set_sps_flag: bool,
+    pub(crate) num_delta_pocs_of_ref_rps_idx: u8,
+    pub(crate) num_bits_for_st_ref_pic_set_in_slice: u16,
+    pub(crate) ref_pic_set_st_curr_before: Vec<ReferencePictureInfo>,
+    pub(crate) ref_pic_set_st_curr_after: Vec<ReferencePictureInfo>,
+    pub(crate) ref_pic_set_lt_curr: Vec<ReferencePictureInfo>,
+    pub(crate) rbsp_bytes: Vec<u8>,
+    pub(crate) slice_indices: Vec<usize>,
+    pub(crate) pts: Option<u64>,
+    pub(crate) dts: Option<u64>,
+    pub(crate) picture_type: PictureType,
+    /// Decoded after pictures were lost, see [`crate::FrameMetadata::corrupted`].
+    pub(crate) corrupted: bool,
+}
+
+impl DecodeInformation {
+    /// All pictures used for inter prediction of this picture.
+    pub(crate) fn references(&self) -> impl Iterator<Item = &ReferencePictureInfo> {
+        self.ref_pic_set_st_curr_before
+            .iter()
+            .chain(&self.ref_pic_set_st_curr_after)
+            .chain(&self.ref_pic_set_lt_curr)
+    }
+}
+
+impl std::fmt::Debug for DecodeInformation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecodeInformation")
+            .field("vps_id", &self.vps_id)
+            .field("sps_id", &self.sps_id)
+            .field("pps_id", &self.pps_id)
+            .field("pic_order_cnt", &self.pic_order_cnt)
+            .field("is_irap", &self.is_irap)
+            .field(
+                "ref_pic_set_st_curr_before",
+                &self.ref_pic_set_st_curr_before,
+            )
+            .field("ref_pic_set_st_curr_after", &self.ref_pic_set_st_curr_after)
+            .field("ref_pic_set_lt_curr", &self.ref_pic_set_lt_curr)
+            .field("slice_indices", &self.slice_indices)
+            .field("pts", &self.pts)
+            .field("dts", &self.dts)
+            .field("picture_type", &self.picture_type)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ReferencePictureInfo {
+    pub(crate) id: ReferenceId,
+    pub(crate) pic_order_cnt: i32,
+    pub(crate) long_term: bool,
+}
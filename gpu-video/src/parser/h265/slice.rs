@@ -0,0 +1,226 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::{
+    H265ParserError,
+    bitstream::{BitReader, ceil_log2},
+    nalu_parser::NalHeader,
+    parameter_sets::{PicParameterSet, SeqParameterSet, ShortTermRefPicSet},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SliceType {
+    B,
+    P,
+    I,
+}
+
+/// Long-term reference picture entry of a slice header, with entries coming from the SPS already
+/// resolved.
+#[derive(Debug, Clone, Copy)]
+pub struct LongTermPicture {
+    pub poc_lsb_lt: u32,
+    pub used_by_curr_pic_lt_flag: bool,
+    /// `DeltaPocMsbCycleLt`, present only if `delta_poc_msb_present_flag` is set
+    pub delta_poc_msb_cycle_lt: Option<u32>,
+}
+
+/// Slice segment header. Parsing stops after `slice_temporal_mvp_enabled_flag`, the remaining
+/// syntax elements are only needed by the slice data decoding process. For dependent slice
+/// segments only the fields preceding `dependent_slice_segment_flag` are filled.
+#[derive(Debug, Clone)]
+pub struct SliceSegmentHeader {
+    pub first_slice_segment_in_pic_flag: bool,
+    pub no_output_of_prior_pics_flag: bool,
+    pub slice_pic_parameter_set_id: u8,
+    pub dependent_slice_segment_flag: bool,
+    pub slice_segment_address: u32,
+    pub slice_type: SliceType,
+    pub pic_output_flag: bool,
+    pub colour_plane_id: u8,
+    pub slice_pic_order_cnt_lsb: u32,
+    pub short_term_ref_pic_set_sps_flag: bool,
+    /// Set coded in the slice header, `None` if `short_term_ref_pic_set_sps_flag` is set.
+    pub short_term_ref_pic_set: Option<ShortTermRefPicSet>,
+    pub short_term_ref_pic_set_idx: u8,
+    /// Size of `st_ref_pic_set()` in the slice header in bits.
+    pub num_bits_for_st_ref_pic_set_in_slice: u16,
+    pub num_long_term_sps: u8,
+    pub long_term_pics: Vec<LongTermPicture>,
+    pub slice_temporal_mvp_enabled_flag: bool,
+}
+
+impl SliceSegmentHeader {
+    pub(crate) fn parse(
+        rbsp: &[u8],
+        nal_header: &NalHeader,
+        sps_map: &HashMap<u8, Arc<SeqParameterSet>>,
+        pps_map: &HashMap<u8, Arc<PicParameterSet>>,
+    ) -> Result<(Self, Arc<SeqParameterSet>, Arc<PicParameterSet>), H265ParserError> {
+        let mut reader = BitReader::new(rbsp);
+        let reader = &mut reader;
+
+        let first_slice_segment_in_pic_flag = reader.flag()?;
+        let no_output_of_prior_pics_flag = nal_header.is_irap() && reader.flag()?;
+        let slice_pic_parameter_set_id = reader.ue_max("slice_pic_parameter_set_id", 63)? as u8;
+
+        let pps = pps_map
+            .get(&slice_pic_parameter_set_id)
+            .ok_or(H265ParserError::MissingParameterSet {
+                kind: "PPS",
+                id: slice_pic_parameter_set_id as u32,
+            })?
+            .clone();
+        let sps = sps_map
+            .get(&pps.pps_seq_parameter_set_id)
+            .ok_or(H265ParserError::MissingParameterSet {
+                kind: "SPS",
+                id: pps.pps_seq_parameter_set_id as u32,
+            })?
+            .clone();
+
+        let mut header = Self {
+            first_slice_segment_in_pic_flag,
+            no_output_of_prior_pics_flag,
+            slice_pic_parameter_set_id,
+            dependent_slice_segment_flag: false,
+            slice_segment_address: 0,
+            slice_type: SliceType::I,
+            pic_output_flag: true,
+            colour_plane_id: 0,
+            slice_pic_order_cnt_lsb: 0,
+            short_term_ref_pic_set_sps_flag: false,
+            short_term_ref_pic_set: None,
+            short_term_ref_pic_set_idx: 0,
+            num_bits_for_st_ref_pic_set_in_slice: 0,
+            num_long_term_sps: 0,
+            long_term_pics: Vec::new(),
+            slice_temporal_mvp_enabled_flag: false,
+        };
+
+        if !first_slice_segment_in_pic_flag {
+            if pps.dependent_slice_segments_enabled_flag {
+                header.dependent_slice_segment_flag = reader.flag()?;
+            }
+            header.slice_segment_address = reader.u(ceil_log2(sps.pic_size_in_ctbs()))?;
+        }
+
+        if header.dependent_slice_segment_flag {
+            return Ok((header, sps, pps));
+        }
+
+        // slice_reserved_flag
+        reader.skip(pps.num_extra_slice_header_bits as usize)?;
+        header.slice_type = match reader.ue()? {
+            0 => SliceType::B,
+            1 => SliceType::P,
+            2 => SliceType::I,
+            slice_type => {
+                return Err(H265ParserError::InvalidValue {
+                    name: "slice_type",
+                    value: slice_type as i64,
+                });
+            }
+        };
+        if pps.output_flag_present_flag {
+            header.pic_output_flag = reader.flag()?;
+        }
+        if sps.separate_colour_plane_flag {
+            header.colour_plane_id = reader.u(2)? as u8;
+        }
+
+        if nal_header.is_idr() {
+            return Ok((header, sps, pps));
+        }
+
+        header.slice_pic_order_cnt_lsb =
+            reader.u(sps.log2_max_pic_order_cnt_lsb_minus4 as u32 + 4)?;
+        header.short_term_ref_pic_set_sps_flag = reader.flag()?;
+        let num_short_term_ref_pic_sets = sps.short_term_ref_pic_sets.len();
+        if !header.short_term_ref_pic_set_sps_flag {
+            let start = reader.position();
+            header.short_term_ref_pic_set = Some(ShortTermRefPicSet::parse(
+                reader,
+                num_short_term_ref_pic_sets,
+                num_short_term_ref_pic_sets,
+                &sps.short_term_ref_pic_sets,
+            )?);
+            header.num_bits_for_st_ref_pic_set_in_slice = (reader.position() - start) as u16;
+        } else if num_short_term_ref_pic_sets > 1 {
+            header.short_term_ref_pic_set_idx =
+                reader.u(ceil_log2(num_short_term_ref_pic_sets as u32))? as u8;
+        } else if num_short_term_ref_pic_sets == 0 {
+            return Err(H265ParserError::InvalidValue {
+                name: "short_term_ref_pic_set_sps_flag",
+                value: 1,
+            });
+        }
+
+        if sps.long_term_ref_pics_present_flag {
+            let num_long_term_ref_pics_sps = sps.lt_ref_pic_poc_lsb_sps.len() as u32;
+            if num_long_term_ref_pics_sps > 0 {
+                header.num_long_term_sps =
+                    reader.ue_max("num_long_term_sps", num_long_term_ref_pics_sps)? as u8;
+            }
+            let num_long_term_pics = reader.ue_max("num_long_term_pics", 32)?;
+
+            let mut delta_poc_msb_cycle_lt = 0;
+            for i in 0..header.num_long_term_sps as u32 + num_long_term_pics {
+                let (poc_lsb_lt, used_by_curr_pic_lt_flag) = if i < header.num_long_term_sps as u32
+                {
+                    let lt_idx_sps = match num_long_term_ref_pics_sps > 1 {
+                        true => reader.u(ceil_log2(num_long_term_ref_pics_sps))? as usize,
+                        false => 0,
+                    };
+                    let poc_lsb_lt = *sps.lt_ref_pic_poc_lsb_sps.get(lt_idx_sps).ok_or(
+                        H265ParserError::InvalidValue {
+                            name: "lt_idx_sps",
+                            value: lt_idx_sps as i64,
+                        },
+                    )?;
+                    (poc_lsb_lt, sps.used_by_curr_pic_lt_sps_flag[lt_idx_sps])
+                } else {
+                    let poc_lsb_lt = reader.u(sps.log2_max_pic_order_cnt_lsb_minus4 as u32 + 4)?;
+                    (poc_lsb_lt, reader.flag()?)
+                };
+
+                let delta_poc_msb_present_flag = reader.flag()?;
+                // (7-52)
+                if i == 0 || i == header.num_long_term_sps as u32 {
+                    delta_poc_msb_cycle_lt = 0;
+                }
+                let delta_poc_msb_cycle_lt = match delta_poc_msb_present_flag {
+                    true => {
+                        delta_poc_msb_cycle_lt += reader.ue()?;
+                        Some(delta_poc_msb_cycle_lt)
+                    }
+                    false => None,
+                };
+
+                header.long_term_pics.push(LongTermPicture {
+                    poc_lsb_lt,
+                    used_by_curr_pic_lt_flag,
+                    delta_poc_msb_cycle_lt,
+                });
+            }
+        }
+
+        if sps.sps_temporal_mvp_enabled_flag {
+            header.slice_temporal_mvp_enabled_flag = reader.flag()?;
+        }
+
+        Ok((header, sps, pps))
+    }
+
+    /// Short-term reference picture set used by the picture.
+    pub fn short_term_ref_pic_set<'a>(
+        &'a self,
+        sps: &'a SeqParameterSet,
+    ) -> Option<&'a ShortTermRefPicSet> {
+        match &self.short_term_ref_pic_set {
+            Some(set) => Some(set),
+            None => sps
+                .short_term_ref_pic_sets
+                .get(self.short_term_ref_pic_set_idx as usize),
+        }
+    }
+}
@@ -6,7 +6,10 @@ use h264_reader::{
     push::{AccumulatedNalHandler, NalAccumulator, NalInterest},
 };
 
-use super::h264::H264ParserError;
+use super::{
+    h264::H264ParserError,
+    sei::{PicTimingParameters, SeiMessage, SeiSyntax, parse_sei_messages},
+};
 
 pub(crate) struct NalParser {
     reader: AnnexBReader<NalAccumulator<NalReceiver>>,
@@ -32,6 +35,9 @@ impl NalParser {
 struct NalReceiver {
     parser_ctx: h264_reader::Context,
     parsed_nalu: Option<Result<ParsedNalu, H264ParserError>>,
+    /// Taken from the last SPS, because the picture timing SEI message comes before the slices
+    /// that reference the active SPS.
+    pic_timing_parameters: Option<PicTimingParameters>,
 }
 
 impl AccumulatedNalHandler for NalReceiver {
@@ -58,6 +64,7 @@ impl NalReceiver {
                 let parsed = h264_reader::nal::sps::SeqParameterSet::from_bits(nal.rbsp_bits())
                     .map_err(H264ParserError::SpsParseError)?;
 
+                self.pic_timing_parameters = Some(PicTimingParameters::from(&parsed));
                 self.parser_ctx.put_seq_param_set(parsed.clone());
                 Ok(ParsedNalu::Sps(parsed.clone()))
             }
@@ -99,11 +106,26 @@ impl NalReceiver {
                 Ok(ParsedNalu::Slice(slice))
             }
 
+            h264_reader::nal::UnitType::SEI => {
+                let mut rbsp = Vec::new();
+                let messages = match nal.rbsp_bytes().read_to_end(&mut rbsp) {
+                    Ok(_) => parse_sei_messages(&rbsp, SeiSyntax::H264(self.pic_timing_parameters)),
+                    Err(_) => Vec::new(),
+                };
+
+                match messages.is_empty() {
+                    false => Ok(ParsedNalu::Sei(messages)),
+                    true => Ok(ParsedNalu::Other(format!(
+                        "{:?}",
+                        nal.header().unwrap().nal_unit_type()
+                    ))),
+                }
+            }
+
             h264_reader::nal::UnitType::Unspecified(_)
             | h264_reader::nal::UnitType::SliceDataPartitionALayer
             | h264_reader::nal::UnitType::SliceDataPartitionBLayer
             | h264_reader::nal::UnitType::SliceDataPartitionCLayer
-            | h264_reader::nal::UnitType::SEI
             | h264_reader::nal::UnitType::AccessUnitDelimiter
             | h264_reader::nal::UnitType::EndOfSeq
             | h264_reader::nal::UnitType::EndOfStream
@@ -143,6 +165,8 @@ pub enum ParsedNalu {
     Sps(SeqParameterSet),
     Pps(PicParameterSet),
     Slice(Slice),
+    /// SEI NAL unit with at least one supported message.
+    Sei(Vec<SeiMessage>),
     Other(String),
 }
 
@@ -154,6 +178,8 @@ pub struct Nalu {
     #[allow(dead_code)]
     pub raw_bytes: Box<[u8]>,
     pub pts: Option<u64>,
+    /// Decoding timestamp of the chunk the nalu was read from.
+    pub dts: Option<u64>,
 }
 
 pub struct Slice {
@@ -165,6 +191,16 @@ pub struct Slice {
     pub pps: h264_reader::nal::pps::PicParameterSet,
 }
 
+impl Slice {
+    /// I and SI slices do not reference other pictures.
+    pub(crate) fn is_intra(&self) -> bool {
+        matches!(
+            self.header.slice_type.family,
+            h264_reader::nal::slice::SliceFamily::I | h264_reader::nal::slice::SliceFamily::SI
+        )
+    }
+}
+
 impl std::fmt::Debug for Slice {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Slice")
@@ -3,10 +3,12 @@ use std::sync::LazyLock;
 use bytes::{BufMut, BytesMut};
 use memchr::memmem::Finder;
 
+use super::ChunkTimestamps;
+
 #[derive(Debug, Default)]
 pub(crate) struct NALUSplitter {
     buffer: BytesMut,
-    pts: Option<u64>,
+    timestamps: ChunkTimestamps,
     previous_search_end: usize,
 }
 
@@ -39,12 +41,12 @@ impl NALUSplitter {
     pub(crate) fn push(
         &mut self,
         bytestream: &[u8],
-        pts: Option<u64>,
-    ) -> Vec<(Vec<u8>, Option<u64>)> {
-        let mut output_pts = if self.buffer.is_empty() {
-            pts
+        timestamps: ChunkTimestamps,
+    ) -> Vec<(Vec<u8>, ChunkTimestamps)> {
+        let mut output_timestamps = if self.buffer.is_empty() {
+            timestamps
         } else {
-            self.pts
+            self.timestamps
         };
 
         self.buffer.put(bytestream);
@@ -53,20 +55,20 @@ impl NALUSplitter {
         while let Some(i) = find_start_of_next_nalu(&self.buffer[self.previous_search_end..]) {
             let nalu = self.buffer.split_to(self.previous_search_end + i);
             self.previous_search_end = 0;
-            result.push((nalu.to_vec(), output_pts));
-            output_pts = pts;
+            result.push((nalu.to_vec(), output_timestamps));
+            output_timestamps = timestamps;
         }
 
         // This will cause the whole start code to be reprocessed when the beginning of the next start code
         // is at the end of current buffer.
         self.previous_search_end = self.buffer.len().saturating_sub(4);
 
-        self.pts = pts;
+        self.timestamps = timestamps;
 
         result
     }
 
-    pub(crate) fn flush(&mut self) -> Vec<(Vec<u8>, Option<u64>)> {
+    pub(crate) fn flush(&mut self) -> Vec<(Vec<u8>, ChunkTimestamps)> {
         if self.buffer.is_empty() {
             return vec![];
         }
@@ -75,10 +77,10 @@ impl NALUSplitter {
         while let Some(i) = find_start_of_next_nalu(&self.buffer[self.previous_search_end..]) {
             let nalu = self.buffer.split_to(self.previous_search_end + i);
             self.previous_search_end = 0;
-            result.push((nalu.to_vec(), self.pts));
+            result.push((nalu.to_vec(), self.timestamps));
         }
 
-        result.push((self.buffer.to_vec(), self.pts));
+        result.push((self.buffer.to_vec(), self.timestamps));
         self.buffer = BytesMut::new();
         self.previous_search_end = 0;
 
@@ -0,0 +1,157 @@
+use h264_reader::nal::{Nal, RefNal, UnitType, pps::PicParameterSet, sps::SeqParameterSet};
+
+use super::h264::H264ParserError;
+
+const MACROBLOCK_SIZE: u32 = 16;
+
+/// Stream parameters read from an H.264 sequence parameter set.
+#[derive(Debug, Clone)]
+pub struct SpsInfo {
+    pub id: u8,
+    pub profile_idc: u8,
+    /// `constraint_set0_flag` to `constraint_set5_flag`, in this order.
+    pub constraint_flags: [bool; 6],
+    pub level_idc: u8,
+    /// Size of the pictures after cropping, i.e. the size of the output frames.
+    pub width: u32,
+    pub height: u32,
+    /// Size of the decoded pictures before cropping, a multiple of the macroblock size.
+    pub coded_width: u32,
+    pub coded_height: u32,
+    pub chroma_format_idc: u32,
+    pub bit_depth_luma: u8,
+    pub bit_depth_chroma: u8,
+    /// `false` if the stream can contain field pictures.
+    pub frame_mbs_only: bool,
+    pub max_num_ref_frames: u32,
+    /// Read from the bitstream restrictions in the VUI, `None` if they are not present.
+    pub max_num_reorder_frames: Option<u32>,
+    pub timing_info: Option<VuiTimingInfo>,
+    sps: SeqParameterSet,
+}
+
+/// Timing information from the VUI of an H.264 sequence parameter set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VuiTimingInfo {
+    pub num_units_in_tick: u32,
+    pub time_scale: u32,
+    pub fixed_frame_rate: bool,
+}
+
+impl VuiTimingInfo {
+    /// Frame rate of a progressive stream. A tick is the duration of a single field, so a
+    /// frame lasts two ticks.
+    pub fn frame_rate(&self) -> Option<f64> {
+        match self.num_units_in_tick {
+            0 => None,
+            ticks => Some(self.time_scale as f64 / (2 * ticks as u64) as f64),
+        }
+    }
+}
+
+/// Parameters read from an H.264 picture parameter set.
+#[derive(Debug, Clone)]
+pub struct PpsInfo {
+    pub id: u8,
+    pub sps_id: u8,
+    /// CABAC entropy coding is used instead of CAVLC.
+    pub entropy_coding_mode: bool,
+    pub num_ref_idx_l0_default_active: u32,
+    pub num_ref_idx_l1_default_active: u32,
+    pub weighted_pred: bool,
+    pub weighted_bipred_idc: u8,
+    pub transform_8x8_mode: bool,
+}
+
+/// Parses a sequence parameter set NAL unit, with or without the Annex B start code.
+pub fn parse_sps(nalu: &[u8]) -> Result<SpsInfo, H264ParserError> {
+    let nal = ref_nal(nalu, UnitType::SeqParameterSet)?;
+    let sps =
+        SeqParameterSet::from_bits(nal.rbsp_bits()).map_err(H264ParserError::SpsParseError)?;
+
+    let (width, height) = sps
+        .pixel_dimensions()
+        .map_err(H264ParserError::SpsParseError)?;
+    let frame_mbs_only = sps.frame_mbs_flags == h264_reader::nal::sps::FrameMbsFlags::Frames;
+    let vui = sps.vui_parameters.as_ref();
+
+    Ok(SpsInfo {
+        id: sps.seq_parameter_set_id.id(),
+        profile_idc: sps.profile_idc.into(),
+        constraint_flags: [
+            sps.constraint_flags.flag0(),
+            sps.constraint_flags.flag1(),
+            sps.constraint_flags.flag2(),
+            sps.constraint_flags.flag3(),
+            sps.constraint_flags.flag4(),
+            sps.constraint_flags.flag5(),
+        ],
+        level_idc: sps.level_idc,
+        width,
+        height,
+        coded_width: (sps.pic_width_in_mbs_minus1 + 1) * MACROBLOCK_SIZE,
+        coded_height: (sps.pic_height_in_map_units_minus1 + 1)
+            * (2 - frame_mbs_only as u32)
+            * MACROBLOCK_SIZE,
+        chroma_format_idc: sps.chroma_info.chroma_format.to_chroma_format_idc(),
+        bit_depth_luma: sps.chroma_info.bit_depth_luma_minus8 + 8,
+        bit_depth_chroma: sps.chroma_info.bit_depth_chroma_minus8 + 8,
+        frame_mbs_only,
+        max_num_ref_frames: sps.max_num_ref_frames,
+        max_num_reorder_frames: vui
+            .and_then(|vui| vui.bitstream_restrictions.as_ref())
+            .map(|restrictions| restrictions.max_num_reorder_frames),
+        timing_info: vui
+            .and_then(|vui| vui.timing_info.as_ref())
+            .map(|timing| VuiTimingInfo {
+                num_units_in_tick: timing.num_units_in_tick,
+                time_scale: timing.time_scale,
+                fixed_frame_rate: timing.fixed_frame_rate_flag,
+            }),
+        sps,
+    })
+}
+
+/// Parses a picture parameter set NAL unit, with or without the Annex B start code. The
+/// syntax of the PPS depends on the SPS it refers to.
+pub fn parse_pps(nalu: &[u8], sps: &SpsInfo) -> Result<PpsInfo, H264ParserError> {
+    let nal = ref_nal(nalu, UnitType::PicParameterSet)?;
+    let mut ctx = h264_reader::Context::new();
+    ctx.put_seq_param_set(sps.sps.clone());
+    let pps = PicParameterSet::from_bits(&ctx, nal.rbsp_bits())
+        .map_err(H264ParserError::PpsParseError)?;
+
+    Ok(PpsInfo {
+        id: pps.pic_parameter_set_id.id(),
+        sps_id: pps.seq_parameter_set_id.id(),
+        entropy_coding_mode: pps.entropy_coding_mode_flag,
+        num_ref_idx_l0_default_active: pps.num_ref_idx_l0_default_active_minus1 + 1,
+        num_ref_idx_l1_default_active: pps.num_ref_idx_l1_default_active_minus1 + 1,
+        weighted_pred: pps.weighted_pred_flag,
+        weighted_bipred_idc: pps.weighted_bipred_idc,
+        transform_8x8_mode: pps
+            .extension
+            .as_ref()
+            .is_some_and(|extension| extension.transform_8x8_mode_flag),
+    })
+}
+
+fn ref_nal(nalu: &[u8], expected: UnitType) -> Result<RefNal<'_>, H264ParserError> {
+    let nalu = nalu
+        .strip_prefix(&[0, 0, 0, 1])
+        .or_else(|| nalu.strip_prefix(&[0, 0, 1]))
+        .unwrap_or(nalu);
+    let nal = RefNal::new(nalu, &[], true);
+    let unit_type = nal
+        .header()
+        .map_err(H264ParserError::NalHeaderParseError)?
+        .nal_unit_type();
+    if unit_type != expected {
+        return Err(H264ParserError::UnexpectedNalUnitType {
+            expected,
+            actual: unit_type,
+        });
+    }
+
+    Ok(nal)
+}
@@ -3,15 +3,22 @@ use std::sync::Arc;
 use h264_reader::nal::{
     pps::PicParameterSet,
     slice::{
-        DecRefPicMarking, MemoryManagementControlOperation, ModificationOfPicNums, NumRefIdxActive,
-        RefPicListModifications, SliceHeader,
+        DecRefPicMarking, FieldParity, FieldPic, MemoryManagementControlOperation,
+        ModificationOfPicNums, NumRefIdxActive, RefPicListModifications, SliceHeader,
     },
     sps::SeqParameterSet,
 };
 
-use crate::{parameters::MissedFrameHandling, parser::decoder_instructions::DecoderInstruction};
+use crate::{
+    parameters::{MissedFrameHandling, PictureType},
+    parser::decoder_instructions::DecoderInstruction,
+};
 
-use super::nalu_parser::{Slice, SpsExt};
+use super::{
+    ChunkTimestamps,
+    nalu_parser::{Slice, SpsExt},
+    sei::RecoveryPoint,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ReferenceManagementError {
@@ -24,7 +31,7 @@ pub enum ReferenceManagementError {
     #[error("PicOrderCntType {0} is not supperted")]
     PicOrderCntTypeNotSupported(u8),
 
-    #[error("The H.264 bytestream is not spec compliant: {0}.")]
+    #[error("The bytestream is not spec compliant: {0}.")]
     IncorrectData(String),
 
     #[error("Missing frame. Decoder is in a corrupted state. Waiting for IDR frame")]
@@ -39,6 +46,47 @@ pub enum ReferenceManagementError {
 #[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct ReferenceId(usize);
 
+impl ReferenceId {
+    pub(crate) fn next(self) -> Self {
+        Self(self.0 + 1)
+    }
+}
+
+/// Parity of a field picture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Field {
+    Top,
+    Bottom,
+}
+
+impl Field {
+    /// `None` if the picture is a frame
+    fn of(header: &SliceHeader) -> Option<Self> {
+        match header.field_pic {
+            FieldPic::Frame => None,
+            FieldPic::Field(FieldParity::Top) => Some(Field::Top),
+            FieldPic::Field(FieldParity::Bottom) => Some(Field::Bottom),
+        }
+    }
+
+    pub(crate) fn opposite(self) -> Self {
+        match self {
+            Field::Top => Field::Bottom,
+            Field::Bottom => Field::Top,
+        }
+    }
+}
+
+/// A field picture whose complementary field was not decoded yet
+#[derive(Debug, Clone, Copy)]
+struct FirstField {
+    reference_id: ReferenceId,
+    field: Field,
+    frame_num: u16,
+    is_reference: bool,
+    pic_order_cnt: i32,
+}
+
 #[derive(Debug, Clone, Copy)]
 enum BFrameReferenceListKind {
     L0,
@@ -57,8 +105,12 @@ pub(crate) struct ReferenceContext {
     MaxLongTermFrameIdx: MaxLongTermFrameIdx,
     prevFrameNumOffset: i64,
     previous_picture_included_mmco_equal_5: bool,
+    first_field: Option<FirstField>,
     detected_missed_frames: bool,
     missed_frame_handling: MissedFrameHandling,
+    keyframes_only: bool,
+    decoding_started: bool,
+    recovery: Option<Recovery>,
 }
 
 #[derive(Debug, Default)]
@@ -68,17 +120,28 @@ enum MaxLongTermFrameIdx {
     Idx(u64),
 }
 
+/// Decoding started at a recovery point SEI message instead of an IDR picture.
+#[derive(Debug, Clone, Copy)]
+struct Recovery {
+    /// `frame_num` of the picture at which the recovery completes.
+    recovery_frame_num: u16,
+    /// Picture order count of the picture at which the recovery completed. The pictures which
+    /// precede it in output order are not fully refreshed.
+    recovered_pic_order_cnt: Option<i32>,
+}
+
 impl ReferenceContext {
-    pub fn new(missed_frame_handling: MissedFrameHandling) -> Self {
+    pub fn new(missed_frame_handling: MissedFrameHandling, keyframes_only: bool) -> Self {
         Self {
             missed_frame_handling,
+            keyframes_only,
             ..Default::default()
         }
     }
 
     fn next_reference_id(&mut self) -> ReferenceId {
         let result = self.next_reference_id;
-        self.next_reference_id = ReferenceId(result.0 + 1);
+        self.next_reference_id = result.next();
         result
     }
 
@@ -93,17 +156,66 @@ impl ReferenceContext {
             MaxLongTermFrameIdx: MaxLongTermFrameIdx::NoLongTermFrameIndices,
             prevFrameNumOffset: 0,
             previous_picture_included_mmco_equal_5: false,
+            first_field: None,
             detected_missed_frames: false,
             missed_frame_handling: self.missed_frame_handling,
+            keyframes_only: self.keyframes_only,
+            decoding_started: false,
+            recovery: None,
         };
     }
 
+    /// Ids of the reference pictures that were decoded into the DPB.
+    fn decoded_reference_ids(&self) -> Vec<ReferenceId> {
+        self.pictures
+            .short_term
+            .iter()
+            .filter(|picture| !picture.non_existing)
+            .map(|picture| picture.id)
+            .chain(self.pictures.long_term.iter().map(|picture| picture.id))
+            .collect()
+    }
+
+    /// Drops all pictures from the DPB and forgets the state of the stream, so that decoding
+    /// starts again from the next IDR picture.
+    pub(crate) fn reset(&mut self) -> Vec<DecoderInstruction> {
+        let mut reference_ids = self.decoded_reference_ids();
+        // a non-reference first field stays in the DPB until its second field is decoded
+        if let Some(first_field) = self.first_field.filter(|field| !field.is_reference) {
+            reference_ids.push(first_field.reference_id);
+        }
+
+        self.reset_state();
+
+        match reference_ids.is_empty() {
+            true => Vec::new(),
+            false => vec![DecoderInstruction::Drop { reference_ids }],
+        }
+    }
+
+    /// Drops all references and starts reference tracking from the current picture, as if it
+    /// was the first picture in the stream.
+    fn reset_for_keyframe(&mut self, frame_num: u16) -> Vec<DecoderInstruction> {
+        let reference_ids = self.decoded_reference_ids();
+
+        self.reset_state();
+        // Skipped frames should not be detected as missing.
+        self.prevFrameNum = frame_num;
+        self.PrevRefFrameNum = frame_num;
+
+        match reference_ids.is_empty() {
+            true => Vec::new(),
+            false => vec![DecoderInstruction::Drop { reference_ids }],
+        }
+    }
+
     #[allow(non_snake_case)]
     fn add_long_term_reference(
         &mut self,
         frame_num: u16,
         LongTermFrameIdx: u64,
         pic_order_cnt: [i32; 2],
+        field: Option<Field>,
     ) -> ReferenceId {
         let id = self.next_reference_id();
         self.pictures.long_term.push(LongTermReferencePicture {
@@ -111,18 +223,25 @@ impl ReferenceContext {
             id,
             LongTermFrameIdx,
             pic_order_cnt,
+            field,
         });
 
         id
     }
 
-    fn add_short_term_reference(&mut self, frame_num: u16, pic_order_cnt: [i32; 2]) -> ReferenceId {
+    fn add_short_term_reference(
+        &mut self,
+        frame_num: u16,
+        pic_order_cnt: [i32; 2],
+        field: Option<Field>,
+    ) -> ReferenceId {
         let id = self.next_reference_id();
         self.pictures.short_term.push(ShortTermReferencePicture {
             frame_num,
             id,
             pic_order_cnt,
             non_existing: false,
+            field,
         });
         id
     }
@@ -138,6 +257,7 @@ impl ReferenceContext {
             id,
             pic_order_cnt,
             non_existing: true,
+            field: None,
         });
         id
     }
@@ -146,20 +266,104 @@ impl ReferenceContext {
         self.detected_missed_frames = true;
     }
 
+    /// Starts decoding at a picture with a recovery point SEI message, which is possible before
+    /// the first IDR picture of the stream or after frames were lost. Intra pictures start with
+    /// an empty DPB, like keyframes. Other pictures refresh the image gradually, so they can only
+    /// be decoded when there are references to use in the meantime. Returns `None` if decoding
+    /// can't start at this picture.
+    fn start_at_recovery_point(
+        &mut self,
+        sps: &SeqParameterSet,
+        frame_num: u16,
+        recovery_point: RecoveryPoint,
+        is_intra: bool,
+    ) -> Option<Vec<DecoderInstruction>> {
+        let instructions = if is_intra {
+            self.reset_for_keyframe(frame_num)
+        } else if !self.decoded_reference_ids().is_empty() {
+            // the frames decoded until the recovery completes are marked separately
+            self.detected_missed_frames = false;
+            self.prevFrameNum = frame_num;
+            self.PrevRefFrameNum = frame_num;
+            Vec::new()
+        } else {
+            return None;
+        };
+
+        let recovery_frame_num =
+            (frame_num as i64 + recovery_point.recovery_frame_cnt as i64) % sps.max_frame_num();
+        self.recovery = Some(Recovery {
+            recovery_frame_num: recovery_frame_num as u16,
+            recovered_pic_order_cnt: None,
+        });
+
+        Some(instructions)
+    }
+
+    /// Returns `true` if the picture is decoded after a recovery point, but precedes the
+    /// recovered picture in output order, so it's not fully refreshed yet.
+    fn is_recovering(&mut self, frame_num: u16, pic_order_cnt: i32) -> bool {
+        let Some(recovery) = &mut self.recovery else {
+            return false;
+        };
+        if recovery.recovered_pic_order_cnt.is_none() && frame_num == recovery.recovery_frame_num {
+            recovery.recovered_pic_order_cnt = Some(pic_order_cnt);
+        }
+
+        recovery
+            .recovered_pic_order_cnt
+            .is_none_or(|recovered| pic_order_cnt < recovered)
+    }
+
     pub(crate) fn put_picture(
         &mut self,
-        mut slices: Vec<(Slice, Option<u64>)>,
+        mut slices: Vec<(Slice, ChunkTimestamps)>,
+        recovery_point: Option<RecoveryPoint>,
     ) -> Result<Vec<DecoderInstruction>, ReferenceManagementError> {
         let header = slices.last().unwrap().0.header.clone();
         let sps = slices.last().unwrap().0.sps.clone();
         let pps = slices.last().unwrap().0.pps.clone();
-        let pts = slices.last().unwrap().1;
+        let timestamps = slices.last().unwrap().1;
+        let picture_type = picture_type(&slices);
 
         let is_ref_frame = header.dec_ref_pic_marking.is_some();
         let is_idr = matches!(
             &header.dec_ref_pic_marking,
             Some(DecRefPicMarking::Idr { .. })
         );
+        let field = Field::of(&header);
+        let (first_field, unpaired_field_instructions) =
+            self.take_first_field(&header, field, is_idr);
+
+        let mut keyframe_reset_instructions = Vec::new();
+        if self.keyframes_only {
+            if !slices.iter().all(|(slice, _)| slice.is_intra()) {
+                let mut instructions = unpaired_field_instructions;
+                if let Some(first_field) = first_field.filter(|first| !first.is_reference) {
+                    instructions.push(DecoderInstruction::Drop {
+                        reference_ids: vec![first_field.reference_id],
+                    });
+                }
+                return Ok(instructions);
+            }
+            // the second field is decoded into the picture of the first one, which has to be kept
+            if first_field.is_none() {
+                keyframe_reset_instructions = self.reset_for_keyframe(header.frame_num);
+            }
+        }
+
+        // Recovery points are only used when there are no valid references, at the start of the
+        // stream or after frames were lost.
+        let awaiting_random_access = !self.decoding_started || self.detected_missed_frames;
+        if let Some(recovery_point) = recovery_point.filter(|_| {
+            awaiting_random_access && !is_idr && !self.keyframes_only && first_field.is_none()
+        }) {
+            let is_intra = slices.iter().all(|(slice, _)| slice.is_intra());
+            keyframe_reset_instructions = self
+                .start_at_recovery_point(&sps, header.frame_num, recovery_point, is_intra)
+                .unwrap_or_default();
+        }
+
         if is_ref_frame && !is_idr && self.missed_frame_handling == MissedFrameHandling::Strict {
             self.verify_frame_num(&sps, &header)?;
         }
@@ -167,8 +371,16 @@ impl ReferenceContext {
         let has_gap = header.frame_num != self.PrevRefFrameNum
             && header.frame_num
                 != ((self.PrevRefFrameNum as u32 + 1) % sps.max_frame_num() as u32) as u16;
+        let frames_lost = has_gap && !is_idr && !sps.gaps_in_frame_num_value_allowed_flag;
+        if frames_lost && self.missed_frame_handling != MissedFrameHandling::Strict {
+            self.detected_missed_frames = true;
+        }
 
-        let gap_instructions = if sps.gaps_in_frame_num_value_allowed_flag && !is_idr && has_gap {
+        // When concealing, the lost frames are handled like a gap in frame_num, so that they
+        // still take their place in the sliding window
+        let fill_gap = sps.gaps_in_frame_num_value_allowed_flag
+            || self.missed_frame_handling == MissedFrameHandling::Conceal;
+        let gap_instructions = if fill_gap && !is_idr && has_gap {
             self.handle_gaps_in_frame_num(&sps, header.frame_num)?
         } else {
             Vec::new()
@@ -186,32 +398,50 @@ impl ReferenceContext {
             rbsp_bytes.append(&mut slice.rbsp_bytes);
         }
 
-        let decode_info = self.decode_information_for_frame(
+        let mut decode_info = self.decode_information_for_frame(
             header.clone(),
             slice_indices,
             rbsp_bytes,
             &sps,
             &pps,
-            pts,
+            timestamps,
+            picture_type,
         )?;
+        let pic_order_cnt = decode_info.picture_info.PicOrderCnt_for_decoding[0];
+
+        let decoder_instructions = match (first_field, &header.clone().dec_ref_pic_marking) {
+            (Some(first_field), dec_ref_pic_marking) => {
+                decode_info
+                    .picture_info
+                    .complete_field_pair(first_field, header.includes_mmco_equal_5());
+                self.reference_picture_marking_process_second_field(
+                    &sps,
+                    header.clone(),
+                    decode_info,
+                    first_field,
+                    dec_ref_pic_marking.as_ref(),
+                )?
+            }
 
-        let decoder_instructions = match &header.clone().dec_ref_pic_marking {
-            Some(DecRefPicMarking::Idr {
-                long_term_reference_flag,
-                ..
-            }) => self.reference_picture_marking_process_idr(
+            (
+                None,
+                Some(DecRefPicMarking::Idr {
+                    long_term_reference_flag,
+                    ..
+                }),
+            ) => self.reference_picture_marking_process_idr(
                 header.clone(),
                 decode_info,
                 *long_term_reference_flag,
             )?,
 
-            Some(DecRefPicMarking::SlidingWindow) => self
+            (None, Some(DecRefPicMarking::SlidingWindow)) => self
                 .reference_picture_marking_process_sliding_window(
                     &sps,
                     header.clone(),
                     decode_info,
                 )?,
-            Some(DecRefPicMarking::Adaptive(memory_management_control_operations)) => self
+            (None, Some(DecRefPicMarking::Adaptive(memory_management_control_operations))) => self
                 .reference_picture_marking_process_adaptive(
                     &sps,
                     header.clone(),
@@ -220,33 +450,89 @@ impl ReferenceContext {
                 )?,
 
             // this picture is not a reference
-            None => {
+            (None, None) => {
                 let reference_id = self.next_reference_id();
-                vec![
-                    DecoderInstruction::Decode {
-                        decode_info,
-                        reference_id,
-                    },
-                    DecoderInstruction::Drop {
+                let mut instructions = vec![DecoderInstruction::Decode {
+                    decode_info,
+                    reference_id,
+                }];
+                // the first field has to stay in the DPB until the second field is decoded into
+                // the same picture
+                if field.is_none() {
+                    instructions.push(DecoderInstruction::Drop {
                         reference_ids: vec![reference_id],
-                    },
-                ]
+                    });
+                }
+                instructions
             }
         };
 
+        if let (Some(field), None) = (field, first_field) {
+            self.first_field = decoder_instructions
+                .iter()
+                .find_map(|instruction| match instruction {
+                    DecoderInstruction::Decode { reference_id, .. }
+                    | DecoderInstruction::Idr { reference_id, .. } => Some(*reference_id),
+                    _ => None,
+                })
+                .map(|reference_id| FirstField {
+                    reference_id,
+                    field,
+                    frame_num: header.frame_num,
+                    is_reference: is_ref_frame,
+                    pic_order_cnt,
+                });
+        }
+
         self.previous_picture_included_mmco_equal_5 = header.includes_mmco_equal_5();
+        if header.includes_mmco_equal_5() {
+            // the picture order count is reset, it can't be compared with the recovered picture
+            self.recovery = None;
+        }
+        self.decoding_started = true;
         self.prevFrameNum = header.frame_num;
         if is_ref_frame {
             self.PrevRefFrameNum = header.frame_num;
         }
 
         let mut instructions = Vec::new();
+        instructions.extend(unpaired_field_instructions);
+        instructions.extend(keyframe_reset_instructions);
         instructions.extend(gap_instructions);
         instructions.extend(decoder_instructions);
 
         Ok(instructions)
     }
 
+    /// Returns the first field of the frame the current picture belongs to, if the current
+    /// picture is its second field. A first field which is not followed by its second field is
+    /// kept as a reference frame with a single field, or dropped if it is not a reference.
+    fn take_first_field(
+        &mut self,
+        header: &SliceHeader,
+        field: Option<Field>,
+        is_idr: bool,
+    ) -> (Option<FirstField>, Vec<DecoderInstruction>) {
+        let Some(first_field) = self.first_field.take() else {
+            return (None, Vec::new());
+        };
+
+        let is_second_field = !is_idr
+            && field == Some(first_field.field.opposite())
+            && header.frame_num == first_field.frame_num;
+
+        match (is_second_field, first_field.is_reference) {
+            (true, _) => (Some(first_field), Vec::new()),
+            (false, true) => (None, Vec::new()),
+            (false, false) => (
+                None,
+                vec![DecoderInstruction::Drop {
+                    reference_ids: vec![first_field.reference_id],
+                }],
+            ),
+        }
+    }
+
     fn remove_long_term_ref(
         &mut self,
         long_term_frame_idx: u64,
@@ -296,6 +582,52 @@ impl ReferenceContext {
     ) -> Result<Vec<DecoderInstruction>, ReferenceManagementError> {
         let mut decoder_instructions = Vec::new();
 
+        let new_long_term_frame_idx = self.apply_memory_management_control_operations(
+            sps,
+            &header,
+            memory_management_control_operations,
+            None,
+            &mut decoder_instructions,
+        )?;
+
+        let reference_id = match new_long_term_frame_idx {
+            Some(long_term_frame_idx) => self.add_long_term_reference(
+                header.frame_num,
+                long_term_frame_idx,
+                decode_info.picture_info.PicOrderCnt_as_reference_pic,
+                Field::of(&header),
+            ),
+            None => self.add_short_term_reference(
+                header.frame_num,
+                decode_info.picture_info.PicOrderCnt_as_reference_pic,
+                Field::of(&header),
+            ),
+        };
+
+        decoder_instructions.insert(
+            0,
+            DecoderInstruction::Decode {
+                decode_info,
+                reference_id,
+            },
+        );
+
+        self.verify_long_term_reference_count()?;
+
+        Ok(decoder_instructions)
+    }
+
+    /// Applies the operations to the stored references. Returns the `LongTermFrameIdx` assigned
+    /// to the current picture by MMCO 6. `current_frame` is the frame containing the first field
+    /// when the current picture is the second field.
+    fn apply_memory_management_control_operations(
+        &mut self,
+        sps: &SeqParameterSet,
+        header: &SliceHeader,
+        memory_management_control_operations: &[MemoryManagementControlOperation],
+        current_frame: Option<ReferenceId>,
+        decoder_instructions: &mut Vec<DecoderInstruction>,
+    ) -> Result<Option<u64>, ReferenceManagementError> {
         let mut new_long_term_frame_idx = None;
 
         for memory_management_control_operation in memory_management_control_operations {
@@ -303,8 +635,7 @@ impl ReferenceContext {
                 MemoryManagementControlOperation::ShortTermUnusedForRef {
                     difference_of_pic_nums_minus1,
                 } => {
-                    let pic_num_to_remove =
-                        header.frame_num as i64 - (*difference_of_pic_nums_minus1 as i64 + 1);
+                    let pic_num_to_remove = frame_pic_num(header, *difference_of_pic_nums_minus1);
 
                     let removed = self.remove_short_term_ref(
                         header.frame_num.into(),
@@ -320,7 +651,10 @@ impl ReferenceContext {
                 }
 
                 MemoryManagementControlOperation::LongTermUnusedForRef { long_term_pic_num } => {
-                    let removed = self.remove_long_term_ref(*long_term_pic_num as u64)?;
+                    let removed = self.remove_long_term_ref(frame_long_term_pic_num(
+                        header,
+                        *long_term_pic_num,
+                    ))?;
 
                     decoder_instructions.push(DecoderInstruction::Drop {
                         reference_ids: vec![removed.id],
@@ -337,8 +671,7 @@ impl ReferenceContext {
                         });
                     }
 
-                    let pic_num_to_remove =
-                        header.frame_num as i64 - (*difference_of_pic_nums_minus1 as i64 + 1);
+                    let pic_num_to_remove = frame_pic_num(header, *difference_of_pic_nums_minus1);
 
                     let picture = self.remove_short_term_ref(
                         header.frame_num.into(),
@@ -357,6 +690,7 @@ impl ReferenceContext {
                         LongTermFrameIdx: *long_term_frame_idx as u64,
                         pic_order_cnt: picture.pic_order_cnt,
                         id: picture.id,
+                        field: picture.field,
                     });
                 }
 
@@ -410,10 +744,18 @@ impl ReferenceContext {
                 MemoryManagementControlOperation::CurrentUsedForLongTerm {
                     long_term_frame_idx,
                 } => {
-                    if let Ok(picture) = self.remove_long_term_ref(*long_term_frame_idx as u64) {
-                        decoder_instructions.push(DecoderInstruction::Drop {
-                            reference_ids: vec![picture.id],
-                        });
+                    // the first field of the current frame keeps its index
+                    let is_current_frame = |picture: &LongTermReferencePicture| {
+                        picture.LongTermFrameIdx == *long_term_frame_idx as u64
+                            && Some(picture.id) == current_frame
+                    };
+                    if !self.pictures.long_term.iter().any(is_current_frame) {
+                        if let Ok(picture) = self.remove_long_term_ref(*long_term_frame_idx as u64)
+                        {
+                            decoder_instructions.push(DecoderInstruction::Drop {
+                                reference_ids: vec![picture.id],
+                            });
+                        }
                     }
 
                     new_long_term_frame_idx = Some(*long_term_frame_idx as u64);
@@ -421,26 +763,10 @@ impl ReferenceContext {
             }
         }
 
-        let reference_id = match new_long_term_frame_idx {
-            Some(long_term_frame_idx) => self.add_long_term_reference(
-                header.frame_num,
-                long_term_frame_idx,
-                decode_info.picture_info.PicOrderCnt_as_reference_pic,
-            ),
-            None => self.add_short_term_reference(
-                header.frame_num,
-                decode_info.picture_info.PicOrderCnt_as_reference_pic,
-            ),
-        };
-
-        decoder_instructions.insert(
-            0,
-            DecoderInstruction::Decode {
-                decode_info,
-                reference_id,
-            },
-        );
+        Ok(new_long_term_frame_idx)
+    }
 
+    fn verify_long_term_reference_count(&self) -> Result<(), ReferenceManagementError> {
         if let MaxLongTermFrameIdx::Idx(max) = self.MaxLongTermFrameIdx {
             if self.pictures.long_term.len() > max as usize + 1 {
                 return Err(ReferenceManagementError::IncorrectData(format!(
@@ -450,7 +776,7 @@ impl ReferenceContext {
             }
         }
 
-        Ok(decoder_instructions)
+        Ok(())
     }
 
     fn reference_picture_marking_process_sliding_window(
@@ -462,6 +788,7 @@ impl ReferenceContext {
         let reference_id = self.add_short_term_reference(
             header.frame_num,
             decode_info.picture_info.PicOrderCnt_as_reference_pic,
+            Field::of(&header),
         );
 
         let mut decoder_instructions = vec![DecoderInstruction::Decode {
@@ -475,6 +802,101 @@ impl ReferenceContext {
         Ok(decoder_instructions)
     }
 
+    /// Fields are marked as parts of their frames. An operation on one field of a reference frame
+    /// is applied to the whole frame.
+    #[allow(non_snake_case)]
+    fn reference_picture_marking_process_second_field(
+        &mut self,
+        sps: &SeqParameterSet,
+        header: Arc<SliceHeader>,
+        decode_info: DecodeInformation,
+        first_field: FirstField,
+        dec_ref_pic_marking: Option<&DecRefPicMarking>,
+    ) -> Result<Vec<DecoderInstruction>, ReferenceManagementError> {
+        let reference_id = first_field.reference_id;
+        let pic_order_cnt = decode_info.picture_info.PicOrderCnt_as_reference_pic;
+        let mut decoder_instructions = vec![DecoderInstruction::DecodeSecondField {
+            decode_info,
+            reference_id,
+        }];
+
+        let new_long_term_frame_idx = match dec_ref_pic_marking {
+            // this picture is not a reference, the frame stays a reference if the first field was
+            None if first_field.is_reference => return Ok(decoder_instructions),
+            None => {
+                decoder_instructions.push(DecoderInstruction::Drop {
+                    reference_ids: vec![reference_id],
+                });
+                return Ok(decoder_instructions);
+            }
+            Some(DecRefPicMarking::Idr { .. }) => {
+                return Err(ReferenceManagementError::IncorrectData(
+                    "the second field of a frame is an IDR picture".into(),
+                ));
+            }
+            Some(DecRefPicMarking::SlidingWindow) => None,
+            Some(DecRefPicMarking::Adaptive(memory_management_control_operations)) => self
+                .apply_memory_management_control_operations(
+                    sps,
+                    &header,
+                    memory_management_control_operations,
+                    Some(reference_id),
+                    &mut decoder_instructions,
+                )?,
+        };
+
+        let short_term = self
+            .pictures
+            .short_term
+            .iter()
+            .position(|picture| picture.id == reference_id)
+            .map(|i| self.pictures.short_term.remove(i));
+        let long_term = self
+            .pictures
+            .long_term
+            .iter()
+            .position(|picture| picture.id == reference_id)
+            .map(|i| self.pictures.long_term.remove(i));
+
+        match (new_long_term_frame_idx, long_term) {
+            (Some(LongTermFrameIdx), _)
+            | (
+                None,
+                Some(LongTermReferencePicture {
+                    LongTermFrameIdx, ..
+                }),
+            ) => {
+                self.pictures.long_term.push(LongTermReferencePicture {
+                    frame_num: header.frame_num,
+                    LongTermFrameIdx,
+                    id: reference_id,
+                    pic_order_cnt,
+                    field: None,
+                });
+            }
+            (None, None) => {
+                self.pictures.short_term.push(ShortTermReferencePicture {
+                    frame_num: header.frame_num,
+                    id: reference_id,
+                    pic_order_cnt,
+                    non_existing: false,
+                    field: None,
+                });
+
+                // the sliding window is only applied to frames which were not references before
+                if short_term.is_none() {
+                    decoder_instructions.extend(
+                        self.evict_oldest_short_term_if_over_capacity(sps, header.frame_num),
+                    );
+                }
+            }
+        }
+
+        self.verify_long_term_reference_count()?;
+
+        Ok(decoder_instructions)
+    }
+
     fn evict_oldest_short_term_if_over_capacity(
         &mut self,
         sps: &SeqParameterSet,
@@ -527,12 +949,14 @@ impl ReferenceContext {
                 header.frame_num,
                 0,
                 decode_info.picture_info.PicOrderCnt_as_reference_pic,
+                Field::of(&header),
             )
         } else {
             self.MaxLongTermFrameIdx = MaxLongTermFrameIdx::NoLongTermFrameIndices;
             self.add_short_term_reference(
                 header.frame_num,
                 decode_info.picture_info.PicOrderCnt_as_reference_pic,
+                Field::of(&header),
             )
         };
 
@@ -550,16 +974,27 @@ impl ReferenceContext {
         rbsp_bytes: Vec<u8>,
         sps: &SeqParameterSet,
         pps: &PicParameterSet,
-        pts: Option<u64>,
+        timestamps: ChunkTimestamps,
+        picture_type: PictureType,
     ) -> Result<DecodeInformation, ReferenceManagementError> {
         let PicOrderCnt_for_decoding = self.decode_pic_order_cnt(&header, sps)?;
+        let is_idr = matches!(
+            &header.dec_ref_pic_marking,
+            Some(DecRefPicMarking::Idr { .. })
+        );
         let PicOrderCnt_as_reference_pic = if header.includes_mmco_equal_5() {
             [0, 0]
         } else {
             PicOrderCnt_for_decoding
         };
 
+        let field = Field::of(&header);
         let (reference_list_l0, reference_list_l1) = match header.slice_type.family {
+            h264_reader::nal::slice::SliceFamily::P | h264_reader::nal::slice::SliceFamily::B
+                if field.is_some() =>
+            {
+                (Some(self.reference_pictures_for_field()), None)
+            }
             h264_reader::nal::slice::SliceFamily::P => {
                 let num_ref_idx_l0_active = header.num_ref_idx_l0_active(pps);
 
@@ -586,10 +1021,7 @@ impl ReferenceContext {
                 }
 
                 reference_list_l0.truncate(num_ref_idx_l0_active as usize);
-
-                if reference_list_l0.iter().any(|p| p.non_existing) {
-                    return Err(ReferenceManagementError::NonExistingReferenceInActiveList);
-                }
+                self.conceal_non_existing_references(&mut reference_list_l0)?;
 
                 (Some(reference_list_l0), None)
             }
@@ -638,12 +1070,8 @@ impl ReferenceContext {
 
                 reference_list_l0.truncate(num_ref_idx_l0_active as usize);
                 reference_list_l1.truncate(num_ref_idx_l1_active as usize);
-
-                if reference_list_l0.iter().any(|p| p.non_existing)
-                    || reference_list_l1.iter().any(|p| p.non_existing)
-                {
-                    return Err(ReferenceManagementError::NonExistingReferenceInActiveList);
-                }
+                self.conceal_non_existing_references(&mut reference_list_l0)?;
+                self.conceal_non_existing_references(&mut reference_list_l1)?;
 
                 (Some(reference_list_l0), Some(reference_list_l1))
             }
@@ -669,11 +1097,53 @@ impl ReferenceContext {
                 PicOrderCnt_for_decoding,
                 PicOrderCnt_as_reference_pic,
                 FrameNum: header.frame_num,
+                field,
+                second_field: false,
             },
-            pts,
+            pts: timestamps.pts,
+            dts: timestamps.dts,
+            picture_type,
+            corrupted: self.detected_missed_frames && !is_idr,
+            recovering: !is_idr
+                && self.is_recovering(header.frame_num, PicOrderCnt_for_decoding[0]),
         })
     }
 
+    /// Non-existing pictures in an active reference list can't be used by the decoder. They are
+    /// an error, unless missed frames are concealed, in which case every one of them is replaced
+    /// with the nearest existing picture in the list.
+    fn conceal_non_existing_references(
+        &mut self,
+        reference_list: &mut [ReferencePictureInfo],
+    ) -> Result<(), ReferenceManagementError> {
+        if reference_list.iter().all(|p| !p.non_existing) {
+            return Ok(());
+        }
+        if self.missed_frame_handling != MissedFrameHandling::Conceal {
+            return Err(ReferenceManagementError::NonExistingReferenceInActiveList);
+        }
+
+        let existing = reference_list
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !p.non_existing)
+            .map(|(i, p)| (i, *p))
+            .collect::<Vec<_>>();
+        for (i, reference) in reference_list.iter_mut().enumerate() {
+            if !reference.non_existing {
+                continue;
+            }
+
+            let Some((_, substitute)) = existing.iter().min_by_key(|(j, _)| i.abs_diff(*j)) else {
+                return Err(ReferenceManagementError::NonExistingReferenceInActiveList);
+            };
+            *reference = *substitute;
+        }
+
+        self.detected_missed_frames = true;
+        Ok(())
+    }
+
     // This is outside of spec, but I think it would not be good if a malicious bitstream could
     // trivially force us to do an arbitrary amount of work
     const MAX_GAP_SIZE: u32 = 512;
@@ -889,10 +1359,44 @@ impl ReferenceContext {
                 FrameNum: numbers.FrameNum as u16,
                 non_existing: reference.non_existing,
                 PicOrderCnt: reference.pic_order_cnt,
+                fields: ReferenceFields::default(),
             })
             .collect()
     }
 
+    /// The decoder builds the reference picture lists of field pictures from the slice headers,
+    /// so it only needs all frames containing reference fields.
+    fn reference_pictures_for_field(&self) -> Vec<ReferencePictureInfo> {
+        let short_term = self
+            .pictures
+            .short_term
+            .iter()
+            .filter(|pic| !pic.non_existing)
+            .map(|pic| ReferencePictureInfo {
+                id: pic.id,
+                LongTermPicNum: None,
+                non_existing: false,
+                FrameNum: pic.frame_num,
+                PicOrderCnt: pic.pic_order_cnt,
+                fields: ReferenceFields::of(pic.field),
+            });
+
+        let long_term = self
+            .pictures
+            .long_term
+            .iter()
+            .map(|pic| ReferencePictureInfo {
+                id: pic.id,
+                LongTermPicNum: Some(pic.LongTermFrameIdx),
+                non_existing: false,
+                FrameNum: pic.frame_num,
+                PicOrderCnt: pic.pic_order_cnt,
+                fields: ReferenceFields::of(pic.field),
+            });
+
+        short_term.chain(long_term).collect()
+    }
+
     fn initialize_long_term_reference_picture_list_for_frame(&self) -> Vec<ReferencePictureInfo> {
         let mut long_term_reference_list = self.pictures.long_term.clone();
 
@@ -906,6 +1410,7 @@ impl ReferenceContext {
                 PicOrderCnt: pic.pic_order_cnt,
                 non_existing: false,
                 FrameNum: pic.frame_num,
+                fields: ReferenceFields::default(),
             })
             .collect()
     }
@@ -1011,6 +1516,7 @@ impl ReferenceContext {
                 non_existing: pic.non_existing,
                 PicOrderCnt: pic.pic_order_cnt,
                 id: pic.id,
+                fields: ReferenceFields::default(),
             })
             .collect();
 
@@ -1163,6 +1669,30 @@ struct ShortTermReferencePicture {
     id: ReferenceId,
     pic_order_cnt: [i32; 2],
     non_existing: bool,
+    /// The only decoded field of the frame, `None` if the whole frame was decoded
+    field: Option<Field>,
+}
+
+/// `picNumX` of a memory management control operation, as `PicNum` of the frame it targets.
+/// Field pictures address single fields, which are approximated by the frames containing them.
+fn frame_pic_num(header: &SliceHeader, difference_of_pic_nums_minus1: u32) -> i64 {
+    let difference_of_pic_nums = difference_of_pic_nums_minus1 as i64 + 1;
+    match header.field_pic {
+        FieldPic::Frame => header.frame_num as i64 - difference_of_pic_nums,
+        // CurrPicNum = 2 * frame_num + 1, PicNum of a field is 2 * FrameNumWrap or
+        // 2 * FrameNumWrap + 1, depending on its parity
+        FieldPic::Field(_) => {
+            (2 * header.frame_num as i64 + 1 - difference_of_pic_nums).div_euclid(2)
+        }
+    }
+}
+
+/// `LongTermFrameIdx` of the frame containing the picture with `long_term_pic_num`
+fn frame_long_term_pic_num(header: &SliceHeader, long_term_pic_num: u32) -> u64 {
+    match header.field_pic {
+        FieldPic::Frame => long_term_pic_num as u64,
+        FieldPic::Field(_) => long_term_pic_num as u64 / 2,
+    }
 }
 
 #[allow(non_snake_case)]
@@ -1198,6 +1728,8 @@ struct LongTermReferencePicture {
     LongTermFrameIdx: u64,
     id: ReferenceId,
     pic_order_cnt: [i32; 2],
+    /// The only decoded field of the frame, `None` if the whole frame was decoded
+    field: Option<Field>,
 }
 
 #[allow(non_snake_case)]
@@ -1268,6 +1800,22 @@ impl SliceHeaderExt for SliceHeader {
     }
 }
 
+/// B if any slice of the picture is a B slice, P if any is a P or SP slice, I otherwise.
+fn picture_type(slices: &[(Slice, ChunkTimestamps)]) -> PictureType {
+    let mut picture_type = PictureType::I;
+    for (slice, _) in slices {
+        match slice.header.slice_type.family {
+            h264_reader::nal::slice::SliceFamily::B => return PictureType::B,
+            h264_reader::nal::slice::SliceFamily::P | h264_reader::nal::slice::SliceFamily::SP => {
+                picture_type = PictureType::P
+            }
+            h264_reader::nal::slice::SliceFamily::I | h264_reader::nal::slice::SliceFamily::SI => {}
+        }
+    }
+
+    picture_type
+}
+
 #[derive(Clone)]
 pub struct DecodeInformation {
     pub(crate) reference_list_l0: Option<Vec<ReferencePictureInfo>>,
@@ -1279,6 +1827,12 @@ pub struct DecodeInformation {
     pub(crate) pps_id: u8,
     pub(crate) picture_info: PictureInfo,
     pub(crate) pts: Option<u64>,
+    pub(crate) dts: Option<u64>,
+    pub(crate) picture_type: PictureType,
+    /// Decoded after frames were lost, see [`crate::FrameMetadata::corrupted`].
+    pub(crate) corrupted: bool,
+    /// See [`crate::FrameMetadata::recovering`].
+    pub(crate) recovering: bool,
 }
 
 impl std::fmt::Debug for DecodeInformation {
@@ -1291,6 +1845,8 @@ impl std::fmt::Debug for DecodeInformation {
             .field("pps_id", &self.pps_id)
             .field("picture_info", &self.picture_info)
             .field("pts", &self.pts)
+            .field("dts", &self.dts)
+            .field("picture_type", &self.picture_type)
             .finish_non_exhaustive()
     }
 }
@@ -1303,6 +1859,7 @@ pub(crate) struct ReferencePictureInfo {
     pub(crate) non_existing: bool,
     pub(crate) FrameNum: u16,
     pub(crate) PicOrderCnt: [i32; 2],
+    pub(crate) fields: ReferenceFields,
 }
 
 impl ReferencePictureInfo {
@@ -1311,6 +1868,33 @@ impl ReferencePictureInfo {
     }
 }
 
+/// Fields of a reference frame which can be used by the current field picture. Both are unset
+/// when the current picture is a frame, which can only reference whole frames.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ReferenceFields {
+    pub(crate) top: bool,
+    pub(crate) bottom: bool,
+}
+
+impl ReferenceFields {
+    fn of(field: Option<Field>) -> Self {
+        match field {
+            None => Self {
+                top: true,
+                bottom: true,
+            },
+            Some(Field::Top) => Self {
+                top: true,
+                bottom: false,
+            },
+            Some(Field::Bottom) => Self {
+                top: false,
+                bottom: true,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 #[allow(non_snake_case)]
 pub(crate) struct PictureInfo {
@@ -1319,4 +1903,257 @@ pub(crate) struct PictureInfo {
     pub(crate) FrameNum: u16,
     pub(crate) PicOrderCnt_for_decoding: [i32; 2],
     pub(crate) PicOrderCnt_as_reference_pic: [i32; 2],
+    /// `None` if the picture is a frame
+    pub(crate) field: Option<Field>,
+    /// Set for the second field of a complementary field pair
+    pub(crate) second_field: bool,
+}
+
+impl PictureInfo {
+    /// Combines the picture order count of the second field with the one of the first field, so
+    /// they describe the whole frame.
+    fn complete_field_pair(&mut self, first_field: FirstField, includes_mmco_equal_5: bool) {
+        let [current, _] = self.PicOrderCnt_for_decoding;
+        let pic_order_cnt = match first_field.field {
+            Field::Top => [first_field.pic_order_cnt, current],
+            Field::Bottom => [current, first_field.pic_order_cnt],
+        };
+
+        self.PicOrderCnt_for_decoding = pic_order_cnt;
+        if !includes_mmco_equal_5 {
+            self.PicOrderCnt_as_reference_pic = pic_order_cnt;
+        }
+        self.second_field = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{
+        decoder_instructions::compile_to_decoder_instructions,
+        h264::{AccessUnit, H264Parser},
+    };
+
+    use super::*;
+
+    /// Writes RBSP of parameter sets and slice headers.
+    #[derive(Default)]
+    struct BitWriter {
+        bytes: Vec<u8>,
+        bit_count: usize,
+    }
+
+    impl BitWriter {
+        fn flag(mut self, value: bool) -> Self {
+            if self.bit_count.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if value {
+                *self.bytes.last_mut().unwrap() |= 0x80 >> (self.bit_count % 8);
+            }
+            self.bit_count += 1;
+            self
+        }
+
+        fn u(mut self, bits: u32, value: u32) -> Self {
+            for i in (0..bits).rev() {
+                self = self.flag((value >> i) & 1 == 1);
+            }
+            self
+        }
+
+        fn ue(self, value: u32) -> Self {
+            let value = value + 1;
+            let length = u32::BITS - value.leading_zeros();
+            self.u(length - 1, 0).u(length, value)
+        }
+
+        fn se(self, value: i32) -> Self {
+            match value > 0 {
+                true => self.ue(2 * value as u32 - 1),
+                false => self.ue(2 * value.unsigned_abs()),
+            }
+        }
+
+        /// Adds `rbsp_trailing_bits` and returns the NAL unit with a start code and emulation
+        /// prevention bytes.
+        fn nalu(mut self, header: u8) -> Vec<u8> {
+            self = self.flag(true);
+            while !self.bit_count.is_multiple_of(8) {
+                self = self.flag(false);
+            }
+
+            let mut nalu = vec![0, 0, 0, 1, header];
+            let mut zeros = 0;
+            for byte in self.bytes {
+                if zeros >= 2 && byte <= 3 {
+                    nalu.push(3);
+                    zeros = 0;
+                }
+                nalu.push(byte);
+                zeros = if byte == 0 { zeros + 1 } else { 0 };
+            }
+            nalu
+        }
+    }
+
+    /// Baseline profile, 16x16, one reference frame, `pic_order_cnt_type` = 2
+    fn sps() -> Vec<u8> {
+        BitWriter::default()
+            .u(8, 66) // profile_idc
+            .u(8, 0) // constraint flags
+            .u(8, 30) // level_idc
+            .ue(0) // seq_parameter_set_id
+            .ue(0) // log2_max_frame_num_minus4
+            .ue(2) // pic_order_cnt_type
+            .ue(1) // max_num_ref_frames
+            .flag(false) // gaps_in_frame_num_value_allowed_flag
+            .ue(0) // pic_width_in_mbs_minus1
+            .ue(0) // pic_height_in_map_units_minus1
+            .flag(true) // frame_mbs_only_flag
+            .flag(true) // direct_8x8_inference_flag
+            .flag(false) // frame_cropping_flag
+            .flag(false) // vui_parameters_present_flag
+            .nalu(0x67)
+    }
+
+    fn pps() -> Vec<u8> {
+        BitWriter::default()
+            .ue(0) // pic_parameter_set_id
+            .ue(0) // seq_parameter_set_id
+            .flag(false) // entropy_coding_mode_flag
+            .flag(false) // bottom_field_pic_order_in_frame_present_flag
+            .ue(0) // num_slice_groups_minus1
+            .ue(0) // num_ref_idx_l0_default_active_minus1
+            .ue(0) // num_ref_idx_l1_default_active_minus1
+            .flag(false) // weighted_pred_flag
+            .u(2, 0) // weighted_bipred_idc
+            .se(0) // pic_init_qp_minus26
+            .se(0) // pic_init_qs_minus26
+            .se(0) // chroma_qp_index_offset
+            .flag(true) // deblocking_filter_control_present_flag
+            .flag(false) // constrained_intra_pred_flag
+            .flag(false) // redundant_pic_cnt_present_flag
+            .nalu(0x68)
+    }
+
+    #[derive(Clone, Copy)]
+    enum SliceKind {
+        Idr,
+        I,
+        P,
+    }
+
+    /// Reference slice of a whole frame, only the header is written.
+    fn slice(kind: SliceKind, frame_num: u32) -> Vec<u8> {
+        let slice_type = match kind {
+            SliceKind::Idr | SliceKind::I => 2,
+            SliceKind::P => 0,
+        };
+        let mut w = BitWriter::default()
+            .ue(0) // first_mb_in_slice
+            .ue(slice_type)
+            .ue(0) // pic_parameter_set_id
+            .u(4, frame_num);
+        w = match kind {
+            SliceKind::Idr => w.ue(0), // idr_pic_id
+            SliceKind::I => w,
+            SliceKind::P => w
+                .flag(false) // num_ref_idx_active_override_flag
+                .flag(false), // ref_pic_list_modification_flag_l0
+        };
+        w = match kind {
+            SliceKind::Idr => w
+                .flag(false) // no_output_of_prior_pics_flag
+                .flag(false), // long_term_reference_flag
+            SliceKind::I | SliceKind::P => w.flag(false), // adaptive_ref_pic_marking_mode_flag
+        };
+        w.se(0) // slice_qp_delta
+            .ue(1) // disable_deblocking_filter_idc
+            .nalu(match kind {
+                SliceKind::Idr => 0x65,
+                SliceKind::I | SliceKind::P => 0x41,
+            })
+    }
+
+    /// Access units of an IDR picture followed by a non-IDR I picture and a P picture.
+    fn access_units() -> [AccessUnit; 3] {
+        let stream = [
+            sps(),
+            pps(),
+            slice(SliceKind::Idr, 0),
+            slice(SliceKind::I, 1),
+            slice(SliceKind::P, 2),
+        ]
+        .concat();
+
+        let mut parser = H264Parser::default();
+        let mut access_units = parser.parse(&stream, None).unwrap();
+        access_units.append(&mut parser.flush().unwrap());
+        let Ok(access_units) = access_units.try_into() else {
+            panic!("expected 3 access units");
+        };
+        access_units
+    }
+
+    fn decoded_reference_ids(instructions: &[DecoderInstruction]) -> Vec<ReferenceId> {
+        instructions
+            .iter()
+            .filter_map(|instruction| match instruction {
+                DecoderInstruction::Decode { reference_id, .. }
+                | DecoderInstruction::Idr { reference_id, .. } => Some(*reference_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn access_unit_is_intra() {
+        let is_intra = access_units()
+            .iter()
+            .map(AccessUnit::is_intra)
+            .collect::<Vec<_>>();
+        assert_eq!(is_intra, [true, true, false]);
+    }
+
+    #[test]
+    fn keyframes_only_resets_references_at_intra_pictures() {
+        let mut ctx = ReferenceContext::new(MissedFrameHandling::Strict, true);
+        let [idr, i, p] = access_units();
+
+        let instructions = compile_to_decoder_instructions(&mut ctx, vec![idr]).unwrap();
+        assert!(matches!(
+            instructions.as_slice(),
+            [
+                DecoderInstruction::Sps(_),
+                DecoderInstruction::Pps(_),
+                DecoderInstruction::Idr { .. },
+            ]
+        ));
+        let idr_reference_ids = decoded_reference_ids(&instructions);
+
+        // the IDR picture is dropped before the I picture is decoded
+        let instructions = compile_to_decoder_instructions(&mut ctx, vec![i]).unwrap();
+        match instructions.as_slice() {
+            [
+                DecoderInstruction::Drop { reference_ids },
+                DecoderInstruction::Decode { .. },
+            ] => assert_eq!(reference_ids, &idr_reference_ids),
+            instructions => panic!("unexpected instructions: {instructions:?}"),
+        }
+        assert_eq!(ctx.decoded_reference_ids().len(), 1);
+
+        // the P picture is skipped and the references are left untouched
+        let instructions = compile_to_decoder_instructions(&mut ctx, vec![p]).unwrap();
+        assert!(instructions.is_empty(), "{instructions:?}");
+        assert_eq!(ctx.decoded_reference_ids().len(), 1);
+    }
+
+    #[test]
+    fn all_pictures_are_decoded_without_keyframes_only() {
+        let mut ctx = ReferenceContext::new(MissedFrameHandling::Strict, false);
+        let instructions =
+            compile_to_decoder_instructions(&mut ctx, access_units().into()).unwrap();
+        assert_eq!(decoded_reference_ids(&instructions).len(), 3);
+    }
 }
@@ -0,0 +1,629 @@
+const PIC_TIMING_PAYLOAD_TYPE: u32 = 1;
+const USER_DATA_REGISTERED_ITU_T_T35_PAYLOAD_TYPE: u32 = 4;
+const USER_DATA_UNREGISTERED_PAYLOAD_TYPE: u32 = 5;
+const RECOVERY_POINT_PAYLOAD_TYPE: u32 = 6;
+const DISPLAY_ORIENTATION_PAYLOAD_TYPE: u32 = 47;
+const TIME_CODE_PAYLOAD_TYPE: u32 = 136;
+const MASTERING_DISPLAY_COLOUR_VOLUME_PAYLOAD_TYPE: u32 = 137;
+
+/// ATSC A/53 caption data: United States country code, ATSC provider code, "GA94" user
+/// identifier and the `cc_data` user data type code.
+const ATSC_CAPTION_DATA_HEADER: [u8; 8] = [0xB5, 0x00, 0x31, b'G', b'A', b'9', b'4', 0x03];
+
+/// Orientation in which a decoded frame should be displayed. Mobile devices usually signal it
+/// with the display orientation SEI message instead of rotating the encoded image.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DisplayOrientation {
+    /// Mirror the frame horizontally.
+    pub horizontal_flip: bool,
+
+    /// Mirror the frame vertically.
+    pub vertical_flip: bool,
+
+    /// Clockwise rotation, applied after flipping.
+    pub rotation: Rotation,
+}
+
+impl DisplayOrientation {
+    /// Returns `true` if the width and height of the frame are swapped after applying the orientation.
+    pub fn swaps_dimensions(&self) -> bool {
+        matches!(self.rotation, Rotation::Degrees90 | Rotation::Degrees270)
+    }
+}
+
+/// Clockwise rotation of a frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Rotation {
+    #[default]
+    Degrees0,
+    Degrees90,
+    Degrees180,
+    Degrees270,
+}
+
+/// Display orientation SEI message
+#[derive(Debug, Clone, Copy)]
+pub enum DisplayOrientationSei {
+    /// Orientation from the previous message no longer applies.
+    Cancel,
+    Orientation(DisplayOrientation),
+}
+
+/// Color volume of the display used to master the content, signaled with the mastering display
+/// colour volume SEI message. Usually present in HDR streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MasteringDisplayColorVolume {
+    /// Chromaticity coordinates of the green, blue and red primaries, in this order, in units
+    /// of 0.00002.
+    pub display_primaries: [(u16, u16); 3],
+
+    /// Chromaticity coordinates of the white point in units of 0.00002.
+    pub white_point: (u16, u16),
+
+    /// Maximum luminance of the display in units of 0.0001 cd/m².
+    pub max_luminance: u32,
+
+    /// Minimum luminance of the display in units of 0.0001 cd/m².
+    pub min_luminance: u32,
+}
+
+/// Timecode of a frame, signaled in the clock timestamps of the picture timing SEI message in
+/// H.264 and of the time code SEI message in H.265.
+///
+/// Seconds, minutes and hours can be omitted from a message, in which case they are `None` and
+/// the values of the previous timecode in decoding order apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timecode {
+    pub hours: Option<u8>,
+    pub minutes: Option<u8>,
+    pub seconds: Option<u8>,
+    /// Number of the frame within the second.
+    pub frames: u16,
+    /// Frame numbers were skipped while counting, e.g. in a drop-frame timecode.
+    pub dropped_frames: bool,
+    /// The timecode is not continuous with the previous one.
+    pub discontinuity: bool,
+    /// Offset of the timestamp in units of the clock tick of the stream.
+    pub time_offset: i32,
+}
+
+/// A single `cc_data` packet of the CEA-708 caption data carried in ATSC A/53 user data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptionPacket {
+    pub cc_valid: bool,
+    /// 0 and 1 for CEA-608 data of the first and second field, 2 and 3 for the data and the
+    /// start of a CEA-708 (DTVCC) packet.
+    pub cc_type: u8,
+    pub cc_data: [u8; 2],
+}
+
+/// Unregistered user data SEI message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserDataUnregistered {
+    pub uuid: [u8; 16],
+    pub payload: Vec<u8>,
+}
+
+/// H.264 recovery point SEI message. Decoding can start at the picture it is sent with, even if
+/// it is not an IDR picture. The decoded pictures are correct starting from the recovery point,
+/// `recovery_frame_cnt` frames later, which allows gradual decoder refresh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryPoint {
+    /// Distance to the recovery point in units of `frame_num`.
+    pub recovery_frame_cnt: u32,
+    /// The pictures at and after the recovery point are identical to the pictures decoded from
+    /// the start of the stream.
+    pub exact_match: bool,
+    /// The stream was spliced at this picture, so the pictures decoded after the preceding
+    /// pictures can contain artifacts.
+    pub broken_link: bool,
+}
+
+/// Data from the SEI messages that are sent with a frame and apply only to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrameSei {
+    pub timecodes: Vec<Timecode>,
+    pub captions: Vec<CaptionPacket>,
+    pub user_data_unregistered: Vec<UserDataUnregistered>,
+}
+
+impl FrameSei {
+    pub fn is_empty(&self) -> bool {
+        self.timecodes.is_empty()
+            && self.captions.is_empty()
+            && self.user_data_unregistered.is_empty()
+    }
+}
+
+/// SEI message supported by the parsers.
+#[derive(Debug, Clone)]
+pub enum SeiMessage {
+    DisplayOrientation(DisplayOrientationSei),
+    MasteringDisplayColorVolume(MasteringDisplayColorVolume),
+    Timecodes(Vec<Timecode>),
+    Captions(Vec<CaptionPacket>),
+    UserDataUnregistered(UserDataUnregistered),
+    RecoveryPoint(RecoveryPoint),
+}
+
+/// Fields of the H.264 SPS needed to parse the picture timing SEI message.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PicTimingParameters {
+    /// `None` if neither NAL nor VCL HRD parameters are present.
+    cpb_dpb_delays: Option<(u32, u32)>,
+    pic_struct_present: bool,
+    time_offset_length: u32,
+}
+
+impl From<&h264_reader::nal::sps::SeqParameterSet> for PicTimingParameters {
+    fn from(sps: &h264_reader::nal::sps::SeqParameterSet) -> Self {
+        let vui = sps.vui_parameters.as_ref();
+        let hrd = vui.and_then(|vui| {
+            vui.nal_hrd_parameters
+                .as_ref()
+                .or(vui.vcl_hrd_parameters.as_ref())
+        });
+
+        Self {
+            cpb_dpb_delays: hrd.map(|hrd| {
+                (
+                    hrd.cpb_removal_delay_length_minus1 as u32 + 1,
+                    hrd.dpb_output_delay_length_minus1 as u32 + 1,
+                )
+            }),
+            pic_struct_present: vui.is_some_and(|vui| vui.pic_struct_present_flag),
+            time_offset_length: hrd.map_or(24, |hrd| hrd.time_offset_length as u32),
+        }
+    }
+}
+
+/// Syntax of the SEI messages, which differs between codecs for some payload types.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SeiSyntax {
+    /// The picture timing message can only be parsed if the active SPS is known.
+    H264(Option<PicTimingParameters>),
+    H265,
+}
+
+/// Parses the supported messages from SEI RBSP (without the NAL header). Other messages are
+/// skipped.
+pub(crate) fn parse_sei_messages(rbsp: &[u8], syntax: SeiSyntax) -> Vec<SeiMessage> {
+    let mut messages = Vec::new();
+    let mut data = rbsp;
+    // the last byte contains rbsp_trailing_bits
+    while data.len() > 1 {
+        let Some(payload_type) = read_sei_value(&mut data) else {
+            break;
+        };
+        let Some(payload_size) = read_sei_value(&mut data) else {
+            break;
+        };
+        let payload_size = payload_size as usize;
+        if payload_size > data.len() {
+            break;
+        }
+
+        let (payload, rest) = data.split_at(payload_size);
+        let message = match payload_type {
+            DISPLAY_ORIENTATION_PAYLOAD_TYPE => {
+                parse_display_orientation(payload).map(SeiMessage::DisplayOrientation)
+            }
+            MASTERING_DISPLAY_COLOUR_VOLUME_PAYLOAD_TYPE => {
+                parse_mastering_display_colour_volume(payload)
+                    .map(SeiMessage::MasteringDisplayColorVolume)
+            }
+            USER_DATA_REGISTERED_ITU_T_T35_PAYLOAD_TYPE => {
+                parse_caption_data(payload).map(SeiMessage::Captions)
+            }
+            USER_DATA_UNREGISTERED_PAYLOAD_TYPE => {
+                parse_user_data_unregistered(payload).map(SeiMessage::UserDataUnregistered)
+            }
+            PIC_TIMING_PAYLOAD_TYPE => match syntax {
+                SeiSyntax::H264(Some(parameters)) => {
+                    parse_pic_timing(payload, &parameters).map(SeiMessage::Timecodes)
+                }
+                SeiSyntax::H264(None) | SeiSyntax::H265 => None,
+            },
+            RECOVERY_POINT_PAYLOAD_TYPE => match syntax {
+                SeiSyntax::H264(_) => parse_recovery_point(payload).map(SeiMessage::RecoveryPoint),
+                SeiSyntax::H265 => None,
+            },
+            TIME_CODE_PAYLOAD_TYPE => match syntax {
+                SeiSyntax::H265 => parse_time_code(payload).map(SeiMessage::Timecodes),
+                SeiSyntax::H264(_) => None,
+            },
+            _ => None,
+        };
+        messages.extend(message);
+        data = rest;
+    }
+
+    messages
+}
+
+/// Payload type and size are coded as a sequence of 0xFF bytes followed by the last byte,
+/// the value is the sum of all of them.
+fn read_sei_value(data: &mut &[u8]) -> Option<u32> {
+    let mut value = 0;
+    loop {
+        let (&byte, rest) = data.split_first()?;
+        *data = rest;
+        value += byte as u32;
+        if byte != 0xFF {
+            return Some(value);
+        }
+    }
+}
+
+fn parse_display_orientation(payload: &[u8]) -> Option<DisplayOrientationSei> {
+    let first_byte = *payload.first()?;
+    let cancel_flag = first_byte & 0b1000_0000 != 0;
+    if cancel_flag {
+        return Some(DisplayOrientationSei::Cancel);
+    }
+    if payload.len() < 3 {
+        return None;
+    }
+
+    // hor_flip u(1), ver_flip u(1), anticlockwise_rotation u(16)
+    let bits = u32::from_be_bytes([0, payload[0], payload[1], payload[2]]);
+    let horizontal_flip = bits & (1 << 22) != 0;
+    let vertical_flip = bits & (1 << 21) != 0;
+    let anticlockwise_rotation = (bits >> 5) & 0xFFFF;
+
+    // The rotation is in units of 360 / 2^16 degrees. Round it to the closest quarter turn.
+    let anticlockwise_quarter_turns = ((anticlockwise_rotation + (1 << 13)) >> 14) % 4;
+    let rotation = match anticlockwise_quarter_turns {
+        0 => Rotation::Degrees0,
+        1 => Rotation::Degrees270,
+        2 => Rotation::Degrees180,
+        _ => Rotation::Degrees90,
+    };
+
+    Some(DisplayOrientationSei::Orientation(DisplayOrientation {
+        horizontal_flip,
+        vertical_flip,
+        rotation,
+    }))
+}
+
+fn parse_mastering_display_colour_volume(payload: &[u8]) -> Option<MasteringDisplayColorVolume> {
+    // display_primaries_x/y[3] u(16), white_point_x/y u(16),
+    // max/min_display_mastering_luminance u(32)
+    if payload.len() < 24 {
+        return None;
+    }
+
+    let u16_at = |offset: usize| u16::from_be_bytes([payload[offset], payload[offset + 1]]);
+    let u32_at = |offset: usize| {
+        u32::from_be_bytes([
+            payload[offset],
+            payload[offset + 1],
+            payload[offset + 2],
+            payload[offset + 3],
+        ])
+    };
+
+    Some(MasteringDisplayColorVolume {
+        display_primaries: [
+            (u16_at(0), u16_at(2)),
+            (u16_at(4), u16_at(6)),
+            (u16_at(8), u16_at(10)),
+        ],
+        white_point: (u16_at(12), u16_at(14)),
+        max_luminance: u32_at(16),
+        min_luminance: u32_at(20),
+    })
+}
+
+fn parse_caption_data(payload: &[u8]) -> Option<Vec<CaptionPacket>> {
+    let cc_data = payload.strip_prefix(&ATSC_CAPTION_DATA_HEADER)?;
+    // process_em_data_flag u(1), process_cc_data_flag u(1), additional_data_flag u(1),
+    // cc_count u(5), em_data u(8)
+    let [flags, _em_data, packets @ ..] = cc_data else {
+        return None;
+    };
+    if flags & 0b0100_0000 == 0 {
+        return None;
+    }
+
+    let cc_count = (flags & 0b1_1111) as usize;
+    let captions = packets
+        .chunks_exact(3)
+        .take(cc_count)
+        .map(|packet| CaptionPacket {
+            cc_valid: packet[0] & 0b100 != 0,
+            cc_type: packet[0] & 0b11,
+            cc_data: [packet[1], packet[2]],
+        })
+        .collect::<Vec<_>>();
+
+    match captions.is_empty() {
+        true => None,
+        false => Some(captions),
+    }
+}
+
+fn parse_user_data_unregistered(payload: &[u8]) -> Option<UserDataUnregistered> {
+    let (uuid, payload) = payload.split_first_chunk::<16>()?;
+
+    Some(UserDataUnregistered {
+        uuid: *uuid,
+        payload: payload.to_vec(),
+    })
+}
+
+fn parse_recovery_point(payload: &[u8]) -> Option<RecoveryPoint> {
+    // recovery_frame_cnt ue(v), exact_match_flag u(1), broken_link_flag u(1),
+    // changing_slice_group_idc u(2)
+    let mut r = BitReader::new(payload);
+    let recovery_frame_cnt = r.ue()?;
+    let exact_match = r.flag()?;
+    let broken_link = r.flag()?;
+
+    Some(RecoveryPoint {
+        recovery_frame_cnt,
+        exact_match,
+        broken_link,
+    })
+}
+
+/// Number of clock timestamps in the picture timing message for each `pic_struct` value.
+const NUM_CLOCK_TS: [usize; 9] = [1, 1, 1, 2, 2, 3, 3, 2, 3];
+
+fn parse_pic_timing(payload: &[u8], parameters: &PicTimingParameters) -> Option<Vec<Timecode>> {
+    if !parameters.pic_struct_present {
+        return None;
+    }
+
+    let mut r = BitReader::new(payload);
+    if let Some((cpb_removal_delay_length, dpb_output_delay_length)) = parameters.cpb_dpb_delays {
+        r.u(cpb_removal_delay_length)?;
+        r.u(dpb_output_delay_length)?;
+    }
+
+    let pic_struct = r.u(4)? as usize;
+    let num_clock_ts = *NUM_CLOCK_TS.get(pic_struct)?;
+    let mut timecodes = Vec::new();
+    for _ in 0..num_clock_ts {
+        if !r.flag()? {
+            continue;
+        }
+
+        // ct_type u(2), nuit_field_based_flag u(1), counting_type u(5)
+        r.u(8)?;
+        let timecode = parse_clock_timestamp(&mut r, 8, |_| Some(parameters.time_offset_length))?;
+        timecodes.push(timecode);
+    }
+
+    match timecodes.is_empty() {
+        true => None,
+        false => Some(timecodes),
+    }
+}
+
+fn parse_time_code(payload: &[u8]) -> Option<Vec<Timecode>> {
+    let mut r = BitReader::new(payload);
+    let num_clock_ts = r.u(2)?;
+    let mut timecodes = Vec::new();
+    for _ in 0..num_clock_ts {
+        if !r.flag()? {
+            continue;
+        }
+
+        // units_field_based_flag u(1), counting_type u(5)
+        r.u(6)?;
+        let timecode = parse_clock_timestamp(&mut r, 9, |r| r.u(5))?;
+        timecodes.push(timecode);
+    }
+
+    match timecodes.is_empty() {
+        true => None,
+        false => Some(timecodes),
+    }
+}
+
+/// Parses the part of a clock timestamp starting with `full_timestamp_flag`, which is shared by
+/// H.264 and H.265. They differ in the size of `n_frames` and in where the length of
+/// `time_offset` comes from.
+fn parse_clock_timestamp(
+    r: &mut BitReader,
+    n_frames_bits: u32,
+    time_offset_length: impl FnOnce(&mut BitReader) -> Option<u32>,
+) -> Option<Timecode> {
+    let full_timestamp = r.flag()?;
+    let discontinuity = r.flag()?;
+    let dropped_frames = r.flag()?;
+    let frames = r.u(n_frames_bits)? as u16;
+
+    let (mut seconds, mut minutes, mut hours) = (None, None, None);
+    if full_timestamp {
+        seconds = Some(r.u(6)? as u8);
+        minutes = Some(r.u(6)? as u8);
+        hours = Some(r.u(5)? as u8);
+    } else if r.flag()? {
+        seconds = Some(r.u(6)? as u8);
+        if r.flag()? {
+            minutes = Some(r.u(6)? as u8);
+            if r.flag()? {
+                hours = Some(r.u(5)? as u8);
+            }
+        }
+    }
+
+    let time_offset_length = time_offset_length(r)?;
+    let time_offset = match time_offset_length {
+        0 => 0,
+        length => r.i(length)?,
+    };
+
+    Some(Timecode {
+        hours,
+        minutes,
+        seconds,
+        frames,
+        dropped_frames,
+        discontinuity,
+        time_offset,
+    })
+}
+
+/// Reads big-endian bit fields from an SEI payload.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn u(&mut self, bits: u32) -> Option<u32> {
+        let mut value = 0;
+        for _ in 0..bits {
+            let byte = *self.data.get(self.position / 8)?;
+            let bit = (byte >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | bit as u32;
+            self.position += 1;
+        }
+
+        Some(value)
+    }
+
+    fn flag(&mut self) -> Option<bool> {
+        self.u(1).map(|bit| bit == 1)
+    }
+
+    /// Unsigned Exp-Golomb code.
+    fn ue(&mut self) -> Option<u32> {
+        let mut leading_zeros = 0;
+        while !self.flag()? {
+            leading_zeros += 1;
+            if leading_zeros > 31 {
+                return None;
+            }
+        }
+
+        Some((1 << leading_zeros) - 1 + self.u(leading_zeros)?)
+    }
+
+    /// Two's complement signed integer, `bits` has to be between 1 and 32.
+    fn i(&mut self, bits: u32) -> Option<i32> {
+        let shift = 32 - bits;
+        Some(((self.u(bits)? << shift) as i32) >> shift)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Display orientation payload with `display_orientation_repetition_period` = 0,
+    /// `display_orientation_extension_flag` = 0 and the payload trailing bits.
+    fn display_orientation_payload(
+        horizontal_flip: bool,
+        vertical_flip: bool,
+        anticlockwise_rotation: u16,
+    ) -> [u8; 3] {
+        let bits = ((horizontal_flip as u32) << 22)
+            | ((vertical_flip as u32) << 21)
+            | ((anticlockwise_rotation as u32) << 5)
+            | 0b1_0100;
+        let [_, a, b, c] = bits.to_be_bytes();
+        [a, b, c]
+    }
+
+    fn orientation(payload: &[u8]) -> Option<DisplayOrientation> {
+        match parse_display_orientation(payload)? {
+            DisplayOrientationSei::Orientation(orientation) => Some(orientation),
+            DisplayOrientationSei::Cancel => panic!("unexpected cancel"),
+        }
+    }
+
+    #[test]
+    fn display_orientation_known_payloads() {
+        assert_eq!(
+            display_orientation_payload(true, false, 0),
+            [0x40, 0x00, 0x14]
+        );
+        assert_eq!(
+            orientation(&[0x40, 0x00, 0x14]),
+            Some(DisplayOrientation {
+                horizontal_flip: true,
+                vertical_flip: false,
+                rotation: Rotation::Degrees0,
+            })
+        );
+
+        // vertical flip, 90 degrees anticlockwise
+        assert_eq!(
+            orientation(&[0x28, 0x00, 0x14]),
+            Some(DisplayOrientation {
+                horizontal_flip: false,
+                vertical_flip: true,
+                rotation: Rotation::Degrees270,
+            })
+        );
+    }
+
+    #[test]
+    fn display_orientation_rotation() {
+        let rotation = |anticlockwise_rotation| {
+            orientation(&display_orientation_payload(
+                false,
+                false,
+                anticlockwise_rotation,
+            ))
+            .unwrap()
+            .rotation
+        };
+
+        assert_eq!(rotation(0x0000), Rotation::Degrees0);
+        assert_eq!(rotation(0x4000), Rotation::Degrees270);
+        assert_eq!(rotation(0x8000), Rotation::Degrees180);
+        assert_eq!(rotation(0xC000), Rotation::Degrees90);
+
+        // rounded to the closest quarter turn
+        assert_eq!(rotation(0x3F00), Rotation::Degrees270);
+        assert_eq!(rotation(0x4100), Rotation::Degrees270);
+        assert_eq!(rotation(0x1F00), Rotation::Degrees0);
+        assert_eq!(rotation(0xFF00), Rotation::Degrees0);
+    }
+
+    #[test]
+    fn display_orientation_cancel() {
+        assert!(matches!(
+            parse_display_orientation(&[0x80]),
+            Some(DisplayOrientationSei::Cancel)
+        ));
+    }
+
+    #[test]
+    fn display_orientation_truncated() {
+        assert!(parse_display_orientation(&[]).is_none());
+        assert!(parse_display_orientation(&[0x40, 0x00]).is_none());
+    }
+
+    #[test]
+    fn display_orientation_in_sei_rbsp() {
+        // buffering period (skipped), display orientation with 180 degree rotation and
+        // rbsp_trailing_bits
+        let rbsp = [0, 1, 0xAA, 47, 3, 0x10, 0x00, 0x14, 0x80];
+
+        for syntax in [SeiSyntax::H264(None), SeiSyntax::H265] {
+            let messages = parse_sei_messages(&rbsp, syntax);
+            assert_eq!(messages.len(), 1);
+            assert!(matches!(
+                messages[0],
+                SeiMessage::DisplayOrientation(DisplayOrientationSei::Orientation(
+                    DisplayOrientation {
+                        horizontal_flip: false,
+                        vertical_flip: false,
+                        rotation: Rotation::Degrees180,
+                    }
+                ))
+            ));
+        }
+    }
+}
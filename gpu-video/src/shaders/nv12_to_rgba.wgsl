@@ -15,16 +15,59 @@ const TEXTURE_COORDS: array<vec2<f32>, 3> = array<vec2<f32>, 3>(
     vec2<f32>(1.0, 0.0),
 );
 
+// Orientation is encoded as:
+// bits 0-1 - clockwise rotation in quarter turns
+// bit 2 - horizontal flip
+// bit 3 - vertical flip
+fn source_tex_coords(tex_coords: vec2<f32>, orientation: u32) -> vec2<f32> {
+    // Undo the rotation first, because it is applied after flipping.
+    var coords = tex_coords;
+    switch orientation & 3u {
+        case 1u: {
+            coords = vec2<f32>(tex_coords.y, 1.0 - tex_coords.x);
+        }
+        case 2u: {
+            coords = vec2<f32>(1.0 - tex_coords.x, 1.0 - tex_coords.y);
+        }
+        case 3u: {
+            coords = vec2<f32>(1.0 - tex_coords.y, tex_coords.x);
+        }
+        default: {}
+    }
+
+    if (orientation & 4u) != 0u {
+        coords.x = 1.0 - coords.x;
+    }
+    if (orientation & 8u) != 0u {
+        coords.y = 1.0 - coords.y;
+    }
+
+    return coords;
+}
+
+@group(2) @binding(0) var<uniform> encoded_orientation: u32;
+
 @vertex
 fn vs_main(@builtin(vertex_index) idx: u32) -> VertexOutput {
     var output: VertexOutput;
 
     output.position = vec4(VERTICES[idx], 1.0);
-    output.tex_coords = TEXTURE_COORDS[idx];
+    output.tex_coords = source_tex_coords(TEXTURE_COORDS[idx], encoded_orientation);
 
     return output;
 }
 
+// Set when the output texture has an sRGB format. The format encodes written values, so they
+// have to be decoded first to store the same bytes as with a non-sRGB format.
+override LINEAR_OUTPUT: bool = false;
+
+fn srgb_to_linear(encoded: vec3<f32>) -> vec3<f32> {
+    let value = clamp(encoded, vec3<f32>(0.0), vec3<f32>(1.0));
+    let low = value / 12.92;
+    let high = pow((value + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, value <= vec3<f32>(0.04045));
+}
+
 @group(0) @binding(0) var y_texture: texture_2d<f32>;
 @group(0) @binding(1) var uv_texture: texture_2d<f32>;
 @group(1) @binding(0) var sampler_: sampler;
@@ -45,5 +88,8 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
         textureSample(uv_texture, sampler_, input.tex_coords).rg,
     );
     let rgb = (yuv - conversion_bias) * conversion_scale * conversion_weights;
+    if LINEAR_OUTPUT {
+        return vec4<f32>(srgb_to_linear(rgb), 1.0);
+    }
     return vec4<f32>(rgb, 1.0);
 }
@@ -1,8 +1,10 @@
 mod nv12_to_rgba;
 mod rgba_to_nv12;
+mod ycbcr_sampler;
 
 pub use nv12_to_rgba::*;
 pub use rgba_to_nv12::*;
+pub use ycbcr_sampler::*;
 
 use crate::device::{ColorRange, ColorSpace};
 
@@ -34,6 +36,35 @@ pub struct WgpuConverterParameters {
     pub color_range: ColorRange,
 }
 
+/// Format of RGBA textures produced by [`WgpuNv12ToRgbaConverter`].
+///
+/// The converter always applies the color matrix and range from [`WgpuConverterParameters`]
+/// first, which results in gamma-encoded (non-linear) RGB values. The format only decides how
+/// those values are stored and what a shader sampling the texture receives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RgbaTextureFormat {
+    /// [`wgpu::TextureFormat::Rgba8Unorm`]. Gamma-encoded values are stored as they are, so
+    /// sampling the texture returns gamma-encoded values. Use it if your renderer works on
+    /// encoded values or does its own color management.
+    #[default]
+    Rgba8Unorm,
+
+    /// [`wgpu::TextureFormat::Rgba8UnormSrgb`]. The texture contains the same bytes as with
+    /// [`RgbaTextureFormat::Rgba8Unorm`], but sampling it decodes them with the sRGB transfer
+    /// function, so shaders receive linear values. Use it if your renderer blends in linear
+    /// space, sampling an `Rgba8Unorm` texture there would apply the gamma twice.
+    Rgba8UnormSrgb,
+}
+
+impl RgbaTextureFormat {
+    pub fn wgpu_format(&self) -> wgpu::TextureFormat {
+        match self {
+            RgbaTextureFormat::Rgba8Unorm => wgpu::TextureFormat::Rgba8Unorm,
+            RgbaTextureFormat::Rgba8UnormSrgb => wgpu::TextureFormat::Rgba8UnormSrgb,
+        }
+    }
+}
+
 struct WgpuSampler {
     bgl: wgpu::BindGroupLayout,
     bg: wgpu::BindGroup,
@@ -1,7 +1,9 @@
+use wgpu::util::DeviceExt;
+
 use crate::{
-    OutputFrame, WgpuConverterInitError,
+    DisplayOrientation, OutputFrame, Rotation, WgpuConverterInitError,
     device::{ColorRange, ColorSpace},
-    parameters::WgpuConverterParameters,
+    parameters::{RgbaTextureFormat, WgpuConverterParameters},
     wgpu_helpers::WgpuSampler,
 };
 
@@ -11,17 +13,33 @@ use crate::{
 pub struct WgpuNv12ToRgbaConverter {
     pipeline: wgpu::RenderPipeline,
     params: WgpuConverterParameters,
+    output_format: RgbaTextureFormat,
 
     nv12_planes_bgl: wgpu::BindGroupLayout,
     sampler: WgpuSampler,
+    /// Uniform with the encoded orientation for every possible [`DisplayOrientation`], indexed
+    /// by the value returned from [`encode_orientation`]. The uniforms are never written after
+    /// creation, so conversions with different orientations can be recorded in one encoder.
+    orientation_bind_groups: Vec<wgpu::BindGroup>,
 
     device: wgpu::Device,
 }
 
 impl WgpuNv12ToRgbaConverter {
+    /// Creates a converter that writes to [`wgpu::TextureFormat::Rgba8Unorm`] textures.
     pub fn new(
         device: &wgpu::Device,
         params: WgpuConverterParameters,
+    ) -> Result<Self, WgpuConverterInitError> {
+        Self::new_with_output_format(device, params, RgbaTextureFormat::default())
+    }
+
+    /// Creates a converter that writes to textures in the selected format.
+    /// See [`RgbaTextureFormat`] for the differences between formats.
+    pub fn new_with_output_format(
+        device: &wgpu::Device,
+        params: WgpuConverterParameters,
+        output_format: RgbaTextureFormat,
     ) -> Result<Self, WgpuConverterInitError> {
         match (params.color_space, params.color_range) {
             (ColorSpace::BT709, ColorRange::Limited) => {}
@@ -54,12 +72,47 @@ impl WgpuNv12ToRgbaConverter {
             ],
         });
 
+        let orientation_bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let orientation_bind_groups = (0..ORIENTATION_COUNT)
+            .map(|orientation| {
+                let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("gpu-video nv12 to rgba converter orientation buffer"),
+                    contents: &orientation.to_ne_bytes(),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                });
+                device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: None,
+                    layout: &orientation_bgl,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    }],
+                })
+            })
+            .collect();
+
         let sampler = WgpuSampler::new(device);
         let shader_module =
             device.create_shader_module(wgpu::include_wgsl!("../shaders/nv12_to_rgba.wgsl"));
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("gpu-video nv12 to rgba converter pipeline layout"),
-            bind_group_layouts: &[Some(&nv12_planes_bgl), Some(&sampler.bgl)],
+            bind_group_layouts: &[
+                Some(&nv12_planes_bgl),
+                Some(&sampler.bgl),
+                Some(&orientation_bgl),
+            ],
             immediate_size: 0,
         });
 
@@ -75,8 +128,14 @@ impl WgpuNv12ToRgbaConverter {
             fragment: Some(wgpu::FragmentState {
                 module: &shader_module,
                 entry_point: Some("fs_main"),
-                compilation_options: Default::default(),
-                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &[(
+                        "LINEAR_OUTPUT",
+                        (output_format == RgbaTextureFormat::Rgba8UnormSrgb).into(),
+                    )],
+                    ..Default::default()
+                },
+                targets: &[Some(output_format.wgpu_format().into())],
             }),
             primitive: Default::default(),
             depth_stencil: None,
@@ -88,12 +147,19 @@ impl WgpuNv12ToRgbaConverter {
         Ok(Self {
             pipeline,
             params,
+            output_format,
             nv12_planes_bgl,
             sampler,
+            orientation_bind_groups,
             device: device.clone(),
         })
     }
 
+    /// Format of the RGBA textures the converter writes to.
+    pub fn output_format(&self) -> RgbaTextureFormat {
+        self.output_format
+    }
+
     /// Creates [`wgpu::BindGroup`] for [`OutputFrame<wgpu::Texture>`].
     pub fn create_input_bind_group(
         &self,
@@ -140,12 +206,32 @@ impl WgpuNv12ToRgbaConverter {
     }
 
     /// Converts NV12 texture into RGBA texture.
-    /// RGBA texture's usage must contain [`wgpu::TextureUsages::RENDER_ATTACHMENT`].
+    /// RGBA texture's usage must contain [`wgpu::TextureUsages::RENDER_ATTACHMENT`] and the view
+    /// has to be in the [output format](Self::output_format) of the converter.
     pub fn convert(
         &self,
         command_encoder: &mut wgpu::CommandEncoder,
         src_nv12_bind_group: &wgpu::BindGroup,
         dst_rgba_view: &wgpu::TextureView,
+    ) {
+        self.convert_with_orientation(
+            command_encoder,
+            src_nv12_bind_group,
+            dst_rgba_view,
+            DisplayOrientation::default(),
+        );
+    }
+
+    /// Converts NV12 texture into RGBA texture, flipping and rotating it according to `orientation`
+    /// (usually [`FrameMetadata::display_orientation`](crate::FrameMetadata::display_orientation)).
+    /// If [`DisplayOrientation::swaps_dimensions`] is `true`, the RGBA texture should have
+    /// width and height swapped compared to the NV12 texture.
+    pub fn convert_with_orientation(
+        &self,
+        command_encoder: &mut wgpu::CommandEncoder,
+        src_nv12_bind_group: &wgpu::BindGroup,
+        dst_rgba_view: &wgpu::TextureView,
+        orientation: DisplayOrientation,
     ) {
         let mut render_pass = command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: None,
@@ -166,7 +252,28 @@ impl WgpuNv12ToRgbaConverter {
 
         render_pass.set_bind_group(0, src_nv12_bind_group, &[]);
         render_pass.set_bind_group(1, &self.sampler.bg, &[]);
+        render_pass.set_bind_group(
+            2,
+            &self.orientation_bind_groups[encode_orientation(orientation) as usize],
+            &[],
+        );
         render_pass.set_pipeline(&self.pipeline);
         render_pass.draw(0..3, 0..1);
     }
 }
+
+/// Number of values returned by [`encode_orientation`]
+const ORIENTATION_COUNT: u32 = 16;
+
+/// Has to match `source_tex_coords` in the shader.
+fn encode_orientation(orientation: DisplayOrientation) -> u32 {
+    let rotation = match orientation.rotation {
+        Rotation::Degrees0 => 0,
+        Rotation::Degrees90 => 1,
+        Rotation::Degrees180 => 2,
+        Rotation::Degrees270 => 3,
+    };
+    rotation
+        | ((orientation.horizontal_flip as u32) << 2)
+        | ((orientation.vertical_flip as u32) << 3)
+}
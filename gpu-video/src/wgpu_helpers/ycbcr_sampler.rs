@@ -0,0 +1,262 @@
+use ash::vk;
+
+use crate::{
+    device::{ColorRange, ColorSpace},
+    parameters::WgpuConverterParameters,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum WgpuYcbcrSamplerError {
+    #[error("Y'CbCr sampling is only available on wgpu devices using the Vulkan backend")]
+    NotVulkanDevice,
+
+    #[error("Y'CbCr sampling is only supported for NV12 and P010 textures, got {0:?}")]
+    UnsupportedTextureFormat(wgpu::TextureFormat),
+
+    #[error("The device does not support Y'CbCr sampling of {0:?} textures")]
+    FormatNotSupported(wgpu::TextureFormat),
+
+    #[error("Texture format does not match the sampler. Expected {expected:?}, actual {actual:?}")]
+    IncompatibleTexture {
+        expected: wgpu::TextureFormat,
+        actual: wgpu::TextureFormat,
+    },
+
+    #[error("Vulkan error: {0}")]
+    VkError(#[from] vk::Result),
+}
+
+/// Sampler which converts NV12 and P010 textures returned by
+/// [`WgpuTexturesDecoder`](crate::WgpuTexturesDecoder) to RGB in the texture unit, using
+/// `VK_KHR_sampler_ycbcr_conversion`. Renderers which use Vulkan directly, e.g. through
+/// wgpu-hal, can sample the decoded frames without the intermediate
+/// [`WgpuNv12ToRgbaConverter`](crate::WgpuNv12ToRgbaConverter) pass.
+///
+/// The sampler has to be used as an immutable sampler in the descriptor set layout, and the
+/// texture has to be bound through a view created with [`Self::create_image_view`]. One sampler
+/// can be used for all frames with the same format, color space and range.
+pub struct WgpuYcbcrSampler {
+    conversion: vk::SamplerYcbcrConversion,
+    sampler: vk::Sampler,
+    vk_format: vk::Format,
+    texture_format: wgpu::TextureFormat,
+    device: wgpu::Device,
+}
+
+impl WgpuYcbcrSampler {
+    /// Creates a sampler for textures in `texture_format` ([`wgpu::TextureFormat::NV12`] or
+    /// [`wgpu::TextureFormat::P010`]) with the color space and range from `params`, usually
+    /// read from [`FrameMetadata`](crate::FrameMetadata). Frames with an unspecified color space
+    /// are converted as BT.709.
+    ///
+    /// The `samplerYcbcrConversion` feature is enabled by wgpu on devices created with
+    /// [`VideoAdapterExt::request_device_with_video_support`](crate::VideoAdapterExt::request_device_with_video_support).
+    pub fn new(
+        device: &wgpu::Device,
+        texture_format: wgpu::TextureFormat,
+        params: WgpuConverterParameters,
+    ) -> Result<Self, WgpuYcbcrSamplerError> {
+        let vk_format = match texture_format {
+            wgpu::TextureFormat::NV12 => vk::Format::G8_B8R8_2PLANE_420_UNORM,
+            wgpu::TextureFormat::P010 => vk::Format::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16,
+            format => return Err(WgpuYcbcrSamplerError::UnsupportedTextureFormat(format)),
+        };
+
+        let hal_device = unsafe { device.as_hal::<wgpu::hal::vulkan::Api>() }
+            .ok_or(WgpuYcbcrSamplerError::NotVulkanDevice)?;
+        let format_features = unsafe {
+            hal_device
+                .shared_instance()
+                .raw_instance()
+                .get_physical_device_format_properties(hal_device.raw_physical_device(), vk_format)
+        }
+        .optimal_tiling_features;
+
+        // H.264 and H.265 streams place the chroma samples next to the first luma sample in a
+        // row and between the rows by default
+        let x_chroma_offset =
+            if format_features.contains(vk::FormatFeatureFlags::COSITED_CHROMA_SAMPLES) {
+                vk::ChromaLocation::COSITED_EVEN
+            } else if format_features.contains(vk::FormatFeatureFlags::MIDPOINT_CHROMA_SAMPLES) {
+                vk::ChromaLocation::MIDPOINT
+            } else {
+                return Err(WgpuYcbcrSamplerError::FormatNotSupported(texture_format));
+            };
+        let y_chroma_offset =
+            if format_features.contains(vk::FormatFeatureFlags::MIDPOINT_CHROMA_SAMPLES) {
+                vk::ChromaLocation::MIDPOINT
+            } else {
+                vk::ChromaLocation::COSITED_EVEN
+            };
+        let filter = match format_features
+            .contains(vk::FormatFeatureFlags::SAMPLED_IMAGE_YCBCR_CONVERSION_LINEAR_FILTER)
+        {
+            true => vk::Filter::LINEAR,
+            false => vk::Filter::NEAREST,
+        };
+
+        let ycbcr_model = match params.color_space {
+            ColorSpace::BT601Ntsc | ColorSpace::BT601Pal => {
+                vk::SamplerYcbcrModelConversion::YCBCR_601
+            }
+            ColorSpace::BT709 | ColorSpace::Unspecified => {
+                vk::SamplerYcbcrModelConversion::YCBCR_709
+            }
+        };
+        let ycbcr_range = match params.color_range {
+            ColorRange::Limited => vk::SamplerYcbcrRange::ITU_NARROW,
+            ColorRange::Full => vk::SamplerYcbcrRange::ITU_FULL,
+        };
+
+        let raw_device = hal_device.raw_device();
+        let conversion_create_info = vk::SamplerYcbcrConversionCreateInfo::default()
+            .format(vk_format)
+            .ycbcr_model(ycbcr_model)
+            .ycbcr_range(ycbcr_range)
+            .components(vk::ComponentMapping::default())
+            .x_chroma_offset(x_chroma_offset)
+            .y_chroma_offset(y_chroma_offset)
+            .chroma_filter(filter)
+            .force_explicit_reconstruction(false);
+        let conversion =
+            unsafe { raw_device.create_sampler_ycbcr_conversion(&conversion_create_info, None)? };
+
+        let mut conversion_info = vk::SamplerYcbcrConversionInfo::default().conversion(conversion);
+        let sampler_create_info = vk::SamplerCreateInfo::default()
+            .mag_filter(filter)
+            .min_filter(filter)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .unnormalized_coordinates(false)
+            .push_next(&mut conversion_info);
+        let sampler = match unsafe { raw_device.create_sampler(&sampler_create_info, None) } {
+            Ok(sampler) => sampler,
+            Err(err) => {
+                unsafe { raw_device.destroy_sampler_ycbcr_conversion(conversion, None) };
+                return Err(err.into());
+            }
+        };
+
+        Ok(Self {
+            conversion,
+            sampler,
+            vk_format,
+            texture_format,
+            device: device.clone(),
+        })
+    }
+
+    /// The sampler, which has to be passed as an immutable sampler when creating the
+    /// descriptor set layout.
+    pub fn sampler(&self) -> vk::Sampler {
+        self.sampler
+    }
+
+    /// The Y'CbCr conversion used by the sampler.
+    pub fn conversion(&self) -> vk::SamplerYcbcrConversion {
+        self.conversion
+    }
+
+    /// Format of the textures this sampler can be used with.
+    pub fn texture_format(&self) -> wgpu::TextureFormat {
+        self.texture_format
+    }
+
+    /// Creates a view of all planes of a decoded `texture` with the sampler's conversion. The
+    /// view keeps the texture alive.
+    ///
+    /// # Safety
+    /// wgpu does not know about accesses through the view. Decoded textures are returned in the
+    /// `TRANSFER_DST_OPTIMAL` layout, so the caller has to transition the image to
+    /// `SHADER_READ_ONLY_OPTIMAL` before sampling it, and cannot use the texture through wgpu
+    /// while it is sampled through the view.
+    pub unsafe fn create_image_view(
+        &self,
+        texture: &wgpu::Texture,
+    ) -> Result<WgpuYcbcrImageView, WgpuYcbcrSamplerError> {
+        if texture.format() != self.texture_format {
+            return Err(WgpuYcbcrSamplerError::IncompatibleTexture {
+                expected: self.texture_format,
+                actual: texture.format(),
+            });
+        }
+
+        let image = unsafe { texture.as_hal::<wgpu::hal::vulkan::Api>() }
+            .ok_or(WgpuYcbcrSamplerError::NotVulkanDevice)?
+            .raw_handle();
+        let hal_device = unsafe { self.device.as_hal::<wgpu::hal::vulkan::Api>() }
+            .ok_or(WgpuYcbcrSamplerError::NotVulkanDevice)?;
+
+        let mut conversion_info =
+            vk::SamplerYcbcrConversionInfo::default().conversion(self.conversion);
+        let view_create_info = vk::ImageViewCreateInfo::default()
+            .image(image)
+            .view_type(vk::ImageViewType::TYPE_2D)
+            .format(self.vk_format)
+            .components(vk::ComponentMapping::default())
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            })
+            .push_next(&mut conversion_info);
+        let view = unsafe {
+            hal_device
+                .raw_device()
+                .create_image_view(&view_create_info, None)?
+        };
+
+        Ok(WgpuYcbcrImageView {
+            view,
+            image,
+            _texture: texture.clone(),
+            device: self.device.clone(),
+        })
+    }
+}
+
+impl Drop for WgpuYcbcrSampler {
+    fn drop(&mut self) {
+        let Some(hal_device) = (unsafe { self.device.as_hal::<wgpu::hal::vulkan::Api>() }) else {
+            return;
+        };
+        unsafe {
+            let raw_device = hal_device.raw_device();
+            raw_device.destroy_sampler(self.sampler, None);
+            raw_device.destroy_sampler_ycbcr_conversion(self.conversion, None);
+        }
+    }
+}
+
+/// View of a decoded texture created with [`WgpuYcbcrSampler::create_image_view`]. The view is
+/// destroyed on drop, so it has to outlive all command buffers using it.
+pub struct WgpuYcbcrImageView {
+    view: vk::ImageView,
+    image: vk::Image,
+    _texture: wgpu::Texture,
+    device: wgpu::Device,
+}
+
+impl WgpuYcbcrImageView {
+    pub fn view(&self) -> vk::ImageView {
+        self.view
+    }
+
+    /// The image backing the texture, e.g. for layout transitions.
+    pub fn image(&self) -> vk::Image {
+        self.image
+    }
+}
+
+impl Drop for WgpuYcbcrImageView {
+    fn drop(&mut self) {
+        let Some(hal_device) = (unsafe { self.device.as_hal::<wgpu::hal::vulkan::Api>() }) else {
+            return;
+        };
+        unsafe { hal_device.raw_device().destroy_image_view(self.view, None) };
+    }
+}
@@ -16,6 +16,7 @@ mod inputs;
 mod outputs;
 mod players;
 mod smelter_state;
+mod stats;
 mod utils;
 
 use crate::smelter_state::SmelterState;
@@ -46,6 +47,9 @@ pub enum Action {
     #[strum(to_string = "Reorder inputs")]
     ReorderInputs,
 
+    #[strum(to_string = "Show stats")]
+    ShowStats,
+
     #[strum(to_string = "Reset")]
     Reset,
 
@@ -93,6 +97,7 @@ fn run_demo() {
             Action::RemoveInput => state.unregister_input(),
             Action::RemoveOutput => state.unregister_output(),
             Action::ReorderInputs => state.reorder_inputs(),
+            Action::ShowStats => stats::show_stats(),
             Action::Reset => match examples::post("reset", &json!({})) {
                 Ok(_) => {
                     state = if should_reread_json_dump(JSON_ENV)
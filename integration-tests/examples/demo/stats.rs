@@ -0,0 +1,173 @@
+use std::{
+    io::{self, Write},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Context, Result, anyhow};
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    terminal::{Clear, ClearType},
+};
+use serde_json::Value;
+use smelter::config::read_config;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const TRACKS: [&str; 2] = ["video", "audio"];
+
+/// Polls the `/stats` endpoint and prints per-input stats until Enter is pressed.
+pub fn show_stats() -> Result<()> {
+    let (stop_sender, stop_receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = io::stdin().read_line(&mut String::new());
+        let _ = stop_sender.send(());
+    });
+
+    loop {
+        let mut stdout = io::stdout();
+        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+        println!("Input stats (press Enter to go back)\n");
+
+        // Errors are displayed instead of returned, the stdin thread has to consume Enter first.
+        match fetch_stats() {
+            Ok(stats) => print_rows(&input_rows(&stats)),
+            Err(err) => println!("Failed to get stats: {err:#}"),
+        }
+        stdout.flush()?;
+
+        match stop_receiver.recv_timeout(POLL_INTERVAL) {
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+    }
+}
+
+fn fetch_stats() -> Result<Value> {
+    let url = format!("http://127.0.0.1:{}/stats", read_config().api_port);
+    let response = reqwest::blocking::Client::new()
+        .get(url)
+        .timeout(POLL_INTERVAL)
+        .send()
+        .context("Couldn't send request. Make sure the server is running.")?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Request failed with status {}.", response.status()));
+    }
+    Ok(response.json()?)
+}
+
+struct TrackRow {
+    input: String,
+    protocol: String,
+    track: &'static str,
+    bitrate: Option<u64>,
+    framerate: Option<u64>,
+    frames_dropped: Option<u64>,
+    frames_dropped_10s: Option<u64>,
+    packets_lost: Option<u64>,
+    packets_lost_10s: Option<u64>,
+    jitter_buffer_seconds: Option<f64>,
+    input_buffer_seconds: Option<f64>,
+}
+
+fn input_rows(stats: &Value) -> Vec<TrackRow> {
+    let Some(inputs) = stats["inputs"].as_object() else {
+        return vec![];
+    };
+
+    let mut rows = vec![];
+    for (input_id, report) in inputs {
+        let protocol = report["type"].as_str().unwrap_or("unknown");
+        for track in TRACKS {
+            // RTP, WHIP and WHEP inputs report stats of the jitter buffer
+            let (track_report, is_rtp) = match &report[format!("{track}_rtp")] {
+                Value::Null => (&report[track], false),
+                track_report => (track_report, true),
+            };
+            if track_report.is_null() {
+                continue;
+            }
+            let window = &track_report["last_10_seconds"];
+            // Decoded frames are only reported for the video track
+            let frames_report = match track {
+                "video" => &report["video_frames"],
+                _ => &Value::Null,
+            };
+            rows.push(TrackRow {
+                input: input_id.clone(),
+                protocol: protocol.to_string(),
+                track,
+                bitrate: track_report["bitrate_1_second"].as_u64(),
+                framerate: frames_report["framerate_1_second"].as_u64(),
+                frames_dropped: frames_report["frames_dropped"].as_u64(),
+                frames_dropped_10s: frames_report["last_10_seconds"]["frames_dropped"].as_u64(),
+                packets_lost: track_report["packets_lost"].as_u64(),
+                packets_lost_10s: window["packets_lost"].as_u64(),
+                jitter_buffer_seconds: match is_rtp {
+                    true => window["effective_buffer_on_pop_avg_seconds"].as_f64(),
+                    false => window["effective_buffer_avg_seconds"].as_f64(),
+                },
+                input_buffer_seconds: window["input_buffer_avg_seconds"].as_f64(),
+            });
+        }
+    }
+    rows
+}
+
+fn print_rows(rows: &[TrackRow]) {
+    if rows.is_empty() {
+        println!("No inputs registered.");
+        return;
+    }
+
+    println!(
+        "{:<20} {:<12} {:<6} {:>12} {:>6} {:>14} {:>14} {:>12} {:>12}",
+        "INPUT",
+        "TYPE",
+        "TRACK",
+        "BITRATE",
+        "FPS",
+        "DROPPED (10s)",
+        "LOST (10s)",
+        "JITTER BUF",
+        "INPUT BUF"
+    );
+    for row in rows {
+        println!(
+            "{:<20} {:<12} {:<6} {:>12} {:>6} {:>14} {:>14} {:>12} {:>12}",
+            row.input,
+            row.protocol,
+            row.track,
+            format_optional(row.bitrate.map(format_bitrate)),
+            format_optional(row.framerate.map(|framerate| framerate.to_string())),
+            format_counter(row.frames_dropped_10s, row.frames_dropped),
+            format_counter(row.packets_lost_10s, row.packets_lost),
+            format_optional(row.jitter_buffer_seconds.map(format_seconds)),
+            format_optional(row.input_buffer_seconds.map(format_seconds)),
+        );
+    }
+}
+
+fn format_bitrate(bitrate: u64) -> String {
+    match bitrate {
+        0..1_000 => format!("{bitrate} b/s"),
+        1_000..1_000_000 => format!("{:.1} kb/s", bitrate as f64 / 1_000.0),
+        _ => format!("{:.1} Mb/s", bitrate as f64 / 1_000_000.0),
+    }
+}
+
+fn format_seconds(seconds: f64) -> String {
+    format!("{:.0} ms", seconds * 1000.0)
+}
+
+fn format_counter(last_10s: Option<u64>, total: Option<u64>) -> String {
+    match (last_10s, total) {
+        (Some(last_10s), Some(total)) => format!("{last_10s} ({total})"),
+        _ => "-".to_string(),
+    }
+}
+
+fn format_optional(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "-".to_string())
+}
@@ -57,8 +57,17 @@ fn main() {
             initial: Component::InputStream(InputStreamComponent {
                 id: None,
                 input_id: input_id.clone(),
+                chroma_key: None,
+                color_correction: None,
+                lut_id: None,
+                opacity: 1.0,
+                crop: None,
+                ken_burns: None,
+                flip_horizontal: false,
+                flip_vertical: false,
             }),
             end_condition: PipelineOutputEndCondition::Never,
+            debug_overlay: false,
         }),
         audio: Some(RegisterOutputAudioOptions {
             initial: AudioMixerConfig {
@@ -90,6 +99,7 @@ fn main() {
             required: true,
             ..Default::default()
         },
+        decryption: None,
     });
 
     Pipeline::register_input(&state.pipeline().unwrap(), input_id.clone(), input_options).unwrap();
@@ -120,8 +120,20 @@ mod main_module {
                     .unwrap(),
             }),
             video: Some(RegisterOutputVideoOptions {
-                initial: Component::InputStream(InputStreamComponent { id: None, input_id }),
+                initial: Component::InputStream(InputStreamComponent {
+                    id: None,
+                    input_id,
+                    chroma_key: None,
+                    color_correction: None,
+                    lut_id: None,
+                    opacity: 1.0,
+                    crop: None,
+                    ken_burns: None,
+                    flip_horizontal: false,
+                    flip_vertical: false,
+                }),
                 end_condition: PipelineOutputEndCondition::Never,
+                debug_overlay: false,
             }),
             audio: None, // TODO: add audio example
         }
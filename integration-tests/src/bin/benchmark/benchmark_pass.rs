@@ -197,6 +197,7 @@ impl SingleBenchmarkPass {
                 video: Some(RegisterOutputVideoOptions {
                     initial: root,
                     end_condition: PipelineOutputEndCondition::Never,
+                    debug_overlay: false,
                 }),
                 audio: Some(audio_output_options(audio_mix)),
                 output_options: EncodedDataOutputOptions {
@@ -233,6 +234,7 @@ impl SingleBenchmarkPass {
                 video: Some(RegisterOutputVideoOptions {
                     initial: root,
                     end_condition: PipelineOutputEndCondition::Never,
+                    debug_overlay: false,
                 }),
                 audio: Some(audio_output_options(audio_mix)),
                 output_options: EncodedDataOutputOptions {
@@ -267,6 +269,7 @@ impl SingleBenchmarkPass {
                 video: Some(RegisterOutputVideoOptions {
                     initial: root,
                     end_condition: PipelineOutputEndCondition::Never,
+                    debug_overlay: false,
                 }),
                 audio: Some(audio_output_options(audio_mix)),
                 output_options: RawDataOutputOptions {
@@ -304,6 +307,7 @@ impl SingleBenchmarkPass {
                     required: true,
                     ..Default::default()
                 },
+                decryption: None,
             }),
         )
     }
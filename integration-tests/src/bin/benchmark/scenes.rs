@@ -68,6 +68,14 @@ pub const SINGLE_VIDEO_N_TO_N: SceneLayout = SceneLayout {
                 children: vec![Component::InputStream(InputStreamComponent {
                     id: None,
                     input_id: input_id.clone(),
+                    chroma_key: None,
+                    color_correction: None,
+                    lut_id: None,
+                    opacity: 1.0,
+                    crop: None,
+                    ken_burns: None,
+                    flip_horizontal: false,
+                    flip_vertical: false,
                 })],
                 background_color: RGBAColor(128, 128, 128, 255),
                 ..Default::default()
@@ -112,10 +120,26 @@ pub const TWO_VIDEO_2N_TO_N: SceneLayout = SceneLayout {
                     Component::InputStream(InputStreamComponent {
                         id: None,
                         input_id: input_1.clone(),
+                        chroma_key: None,
+                        color_correction: None,
+                        lut_id: None,
+                        opacity: 1.0,
+                        crop: None,
+                        ken_burns: None,
+                        flip_horizontal: false,
+                        flip_vertical: false,
                     }),
                     Component::InputStream(InputStreamComponent {
                         id: None,
                         input_id: input_2.clone(),
+                        chroma_key: None,
+                        color_correction: None,
+                        lut_id: None,
+                        opacity: 1.0,
+                        crop: None,
+                        ken_burns: None,
+                        flip_horizontal: false,
+                        flip_vertical: false,
                     }),
                 ],
                 background_color: RGBAColor(128, 128, 128, 255),
@@ -169,18 +193,50 @@ pub const FOUR_VIDEO_4N_TO_N: SceneLayout = SceneLayout {
                     Component::InputStream(InputStreamComponent {
                         id: None,
                         input_id: input_1.clone(),
+                        chroma_key: None,
+                        color_correction: None,
+                        lut_id: None,
+                        opacity: 1.0,
+                        crop: None,
+                        ken_burns: None,
+                        flip_horizontal: false,
+                        flip_vertical: false,
                     }),
                     Component::InputStream(InputStreamComponent {
                         id: None,
                         input_id: input_2.clone(),
+                        chroma_key: None,
+                        color_correction: None,
+                        lut_id: None,
+                        opacity: 1.0,
+                        crop: None,
+                        ken_burns: None,
+                        flip_horizontal: false,
+                        flip_vertical: false,
                     }),
                     Component::InputStream(InputStreamComponent {
                         id: None,
                         input_id: input_3.clone(),
+                        chroma_key: None,
+                        color_correction: None,
+                        lut_id: None,
+                        opacity: 1.0,
+                        crop: None,
+                        ken_burns: None,
+                        flip_horizontal: false,
+                        flip_vertical: false,
                     }),
                     Component::InputStream(InputStreamComponent {
                         id: None,
                         input_id: input_4.clone(),
+                        chroma_key: None,
+                        color_correction: None,
+                        lut_id: None,
+                        opacity: 1.0,
+                        crop: None,
+                        ken_burns: None,
+                        flip_horizontal: false,
+                        flip_vertical: false,
                     }),
                 ],
                 background_color: RGBAColor(128, 128, 128, 255),
@@ -238,6 +294,14 @@ pub const SINGLE_VIDEO_1_TO_N: SceneLayout = SceneLayout {
                 children: vec![Component::InputStream(InputStreamComponent {
                     id: None,
                     input_id: input_id.clone(),
+                    chroma_key: None,
+                    color_correction: None,
+                    lut_id: None,
+                    opacity: 1.0,
+                    crop: None,
+                    ken_burns: None,
+                    flip_horizontal: false,
+                    flip_vertical: false,
                 })],
                 background_color: RGBAColor(128, 128, 128, 255),
                 ..Default::default()
@@ -303,6 +367,14 @@ pub const TILES_1_TO_N: SceneLayout = SceneLayout {
                         Component::InputStream(InputStreamComponent {
                             id: None,
                             input_id: input_id.clone(),
+                            chroma_key: None,
+                            color_correction: None,
+                            lut_id: None,
+                            opacity: 1.0,
+                            crop: None,
+                            ken_burns: None,
+                            flip_horizontal: false,
+                            flip_vertical: false,
                         })
                     })
                     .collect(),
@@ -349,6 +421,14 @@ pub const PASS_THROUGH_1_TO_N: SceneLayout = SceneLayout {
             Component::InputStream(InputStreamComponent {
                 id: None,
                 input_id: input_id.clone(),
+                chroma_key: None,
+                color_correction: None,
+                lut_id: None,
+                opacity: 1.0,
+                crop: None,
+                ken_burns: None,
+                flip_horizontal: false,
+                flip_vertical: false,
             }),
             AudioMixerConfig {
                 inputs: vec![AudioMixerInputConfig {
@@ -374,6 +454,10 @@ pub const STATIC_IMAGE_1_TO_N: SceneLayout = SceneLayout {
                     image_id: RendererId("example_image".into()),
                     width: None,
                     height: None,
+                    nine_slice: None,
+                    ken_burns: None,
+                    flip_horizontal: false,
+                    flip_vertical: false,
                 })
                 .into(),
                 ..Default::default()
@@ -407,6 +491,10 @@ pub const IMAGE_WITH_SHADER_1_TO_N: SceneLayout = SceneLayout {
                     image_id: RendererId("example_image".into()),
                     width: None,
                     height: None,
+                    nine_slice: None,
+                    ken_burns: None,
+                    flip_horizontal: false,
+                    flip_vertical: false,
                 })],
                 id: None,
                 shader_id: RendererId("example_shader".into()),
@@ -2,7 +2,8 @@ use anyhow::Result;
 use integration_tests::paths::integration_tests_root;
 use smelter_core::{
     DEFAULT_BUFFER_DURATION, PipelineMoqServerOptions, PipelineOptions, PipelineRtmpServerOptions,
-    PipelineWgpuOptions, PipelineWhipWhepServerOptions, graphics_context::GraphicsContext,
+    PipelineWgpuOptions, PipelineWhipWhepServerOptions, RtpTimestampEpoch,
+    graphics_context::GraphicsContext,
 };
 use std::{
     fs::{self, File},
@@ -35,6 +36,7 @@ pub fn benchmark_pipeline_options(
         download_root: std::env::temp_dir().into(),
         load_system_fonts: false,
         mixing_sample_rate: 48_000,
+        rtp_timestamp_epoch: RtpTimestampEpoch::PipelineStart,
         stream_fallback_timeout: Duration::from_millis(500),
         tokio_rt: None,
         rendering_mode,
@@ -43,11 +45,14 @@ pub fn benchmark_pipeline_options(
         webrtc_stun_servers: Vec::new().into(),
         webrtc_udp_port_strategy: None,
         webrtc_nat_1to1_ips: Arc::new(vec![]),
+        webrtc_keepalive: Default::default(),
         rtmp_server: PipelineRtmpServerOptions::Disable,
         moq_server: PipelineMoqServerOptions::Disable,
         moq_disable_tls_verification: true,
+        rtp_codec_registry: Default::default(),
         wgpu_options: PipelineWgpuOptions::Context(graphics_context),
         side_channel_socket_dir: None,
+        gpu_decode_timing: false,
     }
 }
 
@@ -0,0 +1,29 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::{Context, Result};
+use integration_tests::tools::api_player;
+
+const DEFAULT_API_URL: &str = "http://127.0.0.1:8081";
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<()> {
+    let mut args = std::env::args_os().skip(1);
+    let path: PathBuf = args
+        .next()
+        .context("Usage: replay_api_requests <recording_file> [api_url]")?
+        .into();
+    let api_url = args
+        .next()
+        .map(|url| url.to_string_lossy().into_owned())
+        .unwrap_or(DEFAULT_API_URL.to_string());
+    api_player::play(&path, &api_url)
+}
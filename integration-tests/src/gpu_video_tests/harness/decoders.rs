@@ -19,7 +19,13 @@ impl Decoder for BytesDecoder {
     }
 
     fn decode_bytes(&mut self, data: &[u8]) -> Vec<Nv12Frame> {
-        let frames = self.decode(EncodedInputChunk { data, pts: None }).unwrap();
+        let frames = self
+            .decode(EncodedInputChunk {
+                data,
+                pts: None,
+                dts: None,
+            })
+            .unwrap();
         frames
             .into_iter()
             .map(|frame| Nv12Frame {
@@ -49,7 +55,13 @@ impl Decoder for WgpuTexturesDecoder {
     }
 
     fn decode_bytes(&mut self, data: &[u8]) -> Vec<Nv12Frame> {
-        let frames = self.decode(EncodedInputChunk { data, pts: None }).unwrap();
+        let frames = self
+            .decode(EncodedInputChunk {
+                data,
+                pts: None,
+                dts: None,
+            })
+            .unwrap();
         let (device, queue) = video_device();
 
         frames
@@ -25,6 +25,7 @@ pub(super) fn frame_to_rgba(frame: &Frame) -> Vec<u8> {
         FrameData::Nv12(_) => panic!("unsupported"),
         FrameData::Bgra(_) => panic!("unsupported"),
         FrameData::Argb(_) => panic!("unsupported"),
+        FrameData::InterleavedV210(_) => panic!("unsupported"),
     }
 }
 
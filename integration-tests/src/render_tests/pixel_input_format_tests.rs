@@ -42,6 +42,14 @@ fn test_bgra_pixel_format_input() {
     let input_component = Component::InputStream(InputStreamComponent {
         id: None,
         input_id: InputId::from(Arc::from(input_id)),
+        chroma_key: None,
+        color_correction: None,
+        lut_id: None,
+        opacity: 1.0,
+        crop: None,
+        ken_burns: None,
+        flip_horizontal: false,
+        flip_vertical: false,
     });
 
     let view_component = Component::View(ViewComponent {
@@ -104,6 +112,14 @@ fn test_argb_pixel_format_input() {
     let input_component = Component::InputStream(InputStreamComponent {
         id: None,
         input_id: InputId::from(Arc::from(input_id)),
+        chroma_key: None,
+        color_correction: None,
+        lut_id: None,
+        opacity: 1.0,
+        crop: None,
+        ken_burns: None,
+        flip_horizontal: false,
+        flip_vertical: false,
     });
 
     let view_component = Component::View(ViewComponent {
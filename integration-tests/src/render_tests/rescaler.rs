@@ -60,6 +60,14 @@ fn input_stream(id: &str) -> Component {
     Component::InputStream(InputStreamComponent {
         id: None,
         input_id: InputId(id.into()),
+        chroma_key: None,
+        color_correction: None,
+        lut_id: None,
+        opacity: 1.0,
+        crop: None,
+        ken_burns: None,
+        flip_horizontal: false,
+        flip_vertical: false,
     })
 }
 
@@ -68,6 +76,7 @@ fn box_shadow_offset_30(color: RGBAColor) -> BoxShadow {
         offset_x: 60.0,
         offset_y: 30.0,
         blur_radius: 30.0,
+        spread: 0.0,
         color,
     }
 }
@@ -92,6 +101,9 @@ fn fit_view_with_known_height() -> Result<()> {
                     position_horizontal: HorizontalPosition::LeftOffset(160.0),
                     position_vertical: VerticalPosition::TopOffset(90.0),
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
                 }),
                 mode: RescaleMode::Fit,
                 child: Box::new(Component::View(ViewComponent {
@@ -131,6 +143,9 @@ fn fit_view_with_known_width() -> Result<()> {
                     position_horizontal: HorizontalPosition::LeftOffset(160.0),
                     position_vertical: VerticalPosition::TopOffset(90.0),
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
                 }),
                 mode: RescaleMode::Fit,
                 child: Box::new(Component::View(ViewComponent {
@@ -170,6 +185,9 @@ fn fit_view_with_unknown_width_and_height() -> Result<()> {
                     position_horizontal: HorizontalPosition::LeftOffset(160.0),
                     position_vertical: VerticalPosition::TopOffset(90.0),
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
                 }),
                 mode: RescaleMode::Fit,
                 child: Box::new(Component::View(ViewComponent {
@@ -462,6 +480,9 @@ fn fill_input_stream_scene(
                     position_horizontal: HorizontalPosition::LeftOffset(160.0),
                     position_vertical: VerticalPosition::TopOffset(90.0),
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
                 }),
                 mode: RescaleMode::Fill,
                 horizontal_align,
@@ -495,6 +516,9 @@ fn fit_input_stream_scene(
                     position_horizontal: HorizontalPosition::LeftOffset(160.0),
                     position_vertical: VerticalPosition::TopOffset(90.0),
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
                 }),
                 mode: RescaleMode::Fit,
                 horizontal_align,
@@ -519,6 +543,9 @@ fn border_radius() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             child: Box::new(Component::View(ViewComponent {
@@ -545,6 +572,9 @@ fn border_width() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_width: 20.0,
             border_color: WHITE,
@@ -572,6 +602,9 @@ fn box_shadow() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             box_shadow: vec![box_shadow_offset_30(GREEN)],
             child: Box::new(Component::View(ViewComponent {
@@ -598,6 +631,9 @@ fn border_radius_border_box_shadow() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             border_width: 20.0,
@@ -627,6 +663,9 @@ fn border_radius_box_shadow() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             box_shadow: vec![box_shadow_offset_30(GREEN)],
@@ -654,6 +693,9 @@ fn border_radius_box_shadow_fit_input_stream() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             border_width: 20.0,
@@ -681,6 +723,9 @@ fn border_radius_box_shadow_fill_input_stream() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             border_width: 20.0,
@@ -708,6 +753,9 @@ fn nested_border_width_radius() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             border_width: 20.0,
@@ -746,6 +794,9 @@ fn nested_border_width_radius_aligned() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(80.0),
             border_width: 20.0,
@@ -797,6 +848,7 @@ fn border_radius_border_box_shadow_rescaled() -> Result<()> {
                     offset_x: 20.0,
                     offset_y: 20.0,
                     blur_radius: 5.0,
+                    spread: 0.0,
                     color: GREEN,
                 }],
                 child: Box::new(input_stream("input_1")),
@@ -74,6 +74,14 @@ fn input_streams(count: usize) -> Vec<Component> {
             Component::InputStream(InputStreamComponent {
                 id: None,
                 input_id: InputId(format!("input_{i}").into()),
+                chroma_key: None,
+                color_correction: None,
+                lut_id: None,
+                opacity: 1.0,
+                crop: None,
+                ken_burns: None,
+                flip_horizontal: false,
+                flip_vertical: false,
             })
         })
         .collect()
@@ -22,6 +22,14 @@ fn simple_input_pass_through() -> Result<()> {
         children: vec![Component::InputStream(InputStreamComponent {
             id: None,
             input_id: InputId("input_1".into()),
+            chroma_key: None,
+            color_correction: None,
+            lut_id: None,
+            opacity: 1.0,
+            crop: None,
+            ken_burns: None,
+            flip_horizontal: false,
+            flip_vertical: false,
         })],
         ..Default::default()
     }));
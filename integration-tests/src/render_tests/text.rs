@@ -46,7 +46,7 @@ fn align_center() -> Result<()> {
         font_size: 100.0,
         line_height: 100.0,
         font_family: Arc::from("Inter"),
-        align: HorizontalAlign::Center,
+        align: Some(HorizontalAlign::Center),
         dimensions: TextDimensions::Fixed {
             width: 1000.0,
             height: 200.0,
@@ -65,7 +65,7 @@ fn align_right() -> Result<()> {
         font_size: 100.0,
         line_height: 100.0,
         font_family: Arc::from("Inter"),
-        align: HorizontalAlign::Right,
+        align: Some(HorizontalAlign::Right),
         dimensions: TextDimensions::Fixed {
             width: 1000.0,
             height: 200.0,
@@ -84,7 +84,7 @@ fn bold_text() -> Result<()> {
         font_size: 100.0,
         line_height: 100.0,
         font_family: Arc::from("Inter"),
-        align: HorizontalAlign::Right,
+        align: Some(HorizontalAlign::Right),
         weight: TextWeight::Bold,
         dimensions: TextDimensions::Fixed {
             width: 1000.0,
@@ -269,7 +269,7 @@ fn remove_text_in_view() -> Result<()> {
         font_size: 100.0,
         line_height: 100.0,
         font_family: Arc::from("Inter"),
-        align: HorizontalAlign::Center,
+        align: Some(HorizontalAlign::Center),
         dimensions: TextDimensions::Fixed {
             width: 1000.0,
             height: 200.0,
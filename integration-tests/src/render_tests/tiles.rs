@@ -65,6 +65,14 @@ fn input_streams(count: usize) -> Vec<Component> {
             Component::InputStream(InputStreamComponent {
                 id: None,
                 input_id: InputId(format!("input_{i}").into()),
+                chroma_key: None,
+                color_correction: None,
+                lut_id: None,
+                opacity: 1.0,
+                crop: None,
+                ken_burns: None,
+                flip_horizontal: false,
+                flip_vertical: false,
             })
         })
         .collect()
@@ -376,6 +384,14 @@ fn video_call_with_labels() -> Result<()> {
                     child: Box::new(Component::InputStream(InputStreamComponent {
                         id: None,
                         input_id: InputId(format!("input_{i}").into()),
+                        chroma_key: None,
+                        color_correction: None,
+                        lut_id: None,
+                        opacity: 1.0,
+                        crop: None,
+                        ken_burns: None,
+                        flip_horizontal: false,
+                        flip_vertical: false,
                     })),
                     ..Default::default()
                 }),
@@ -386,6 +402,9 @@ fn video_call_with_labels() -> Result<()> {
                         position_horizontal: HorizontalPosition::LeftOffset(0.0),
                         position_vertical: VerticalPosition::BottomOffset(0.0),
                         rotation_degrees: 0.0,
+                        rotation_x_degrees: 0.0,
+                        rotation_y_degrees: 0.0,
+                        perspective: None,
                     }),
                     children: vec![
                         Component::View(ViewComponent::default()),
@@ -393,7 +412,7 @@ fn video_call_with_labels() -> Result<()> {
                             text: format!("InputStream {i}").into(),
                             font_size: 25.0,
                             line_height: 25.0,
-                            align: HorizontalAlign::Center,
+                            align: Some(HorizontalAlign::Center),
                             color: RGBAColor(255, 255, 255, 255),
                             background_color: RGBAColor(255, 0, 0, 255),
                             dimensions: TextDimensions::Fitted {
@@ -29,6 +29,7 @@ pub const TESTS: &[RenderTest] = &[
     REPLACE_COMPONENT_BY_CHANGING_ID_ADD_MARGIN,
     REPLACE_COMPONENT_BY_CHANGING_ID_ADD_NEW_COMPONENT_LAST_ROW_CENTER_ALIGNED,
     REPLACE_COMPONENT_BY_CHANGING_ID_ADD_NEW_COMPONENT_LAST_ROW_LEFT_ALIGNED,
+    ADD_AND_REMOVE_INPUT_FADE,
 ];
 
 const DARK_GRAY: RGBAColor = RGBAColor(0x33, 0x33, 0x33, 255);
@@ -48,6 +49,14 @@ fn input(idx: usize, with_id: bool) -> Component {
     Component::InputStream(InputStreamComponent {
         id: with_id.then(|| ComponentId(name.clone().into())),
         input_id: InputId(name.into()),
+        chroma_key: None,
+        color_correction: None,
+        lut_id: None,
+        opacity: 1.0,
+        crop: None,
+        ken_burns: None,
+        flip_horizontal: false,
+        flip_vertical: false,
     })
 }
 
@@ -69,6 +78,9 @@ fn tile_resize_entire_component_with_parent_transition() -> Result<()> {
                 position_horizontal: HorizontalPosition::RightOffset(0.0),
                 position_vertical: VerticalPosition::BottomOffset(0.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             children: vec![Component::Tiles(TilesComponent {
                 id: Some(ComponentId(TILES_ID.into())),
@@ -90,6 +102,9 @@ fn tile_resize_entire_component_with_parent_transition() -> Result<()> {
                 position_horizontal: HorizontalPosition::RightOffset(10.0),
                 position_vertical: VerticalPosition::BottomOffset(10.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             transition: Some(linear_500ms(false)),
             children: vec![Component::Tiles(TilesComponent {
@@ -130,6 +145,9 @@ fn tile_resize_entire_component_without_parent_transition() -> Result<()> {
                 position_horizontal: HorizontalPosition::RightOffset(0.0),
                 position_vertical: VerticalPosition::BottomOffset(0.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             children: vec![Component::Tiles(TilesComponent {
                 id: Some(ComponentId(TILES_ID.into())),
@@ -151,6 +169,9 @@ fn tile_resize_entire_component_without_parent_transition() -> Result<()> {
                 position_horizontal: HorizontalPosition::RightOffset(10.0),
                 position_vertical: VerticalPosition::BottomOffset(10.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             children: vec![Component::Tiles(TilesComponent {
                 id: Some(ComponentId(TILES_ID.into())),
@@ -468,3 +489,32 @@ fn replace_component_by_changing_id_add_new_component_last_row_left_aligned() ->
     runner.snapshot(Duration::from_millis(500));
     runner.finish()
 }
+
+#[render_test(description = "")]
+fn add_and_remove_input_fade() -> Result<()> {
+    let mut runner = TestRunner::new(MODULE, TEST_NAME).with_inputs(vec![
+        TestInput::new(1),
+        TestInput::new(2),
+        TestInput::new(3),
+        TestInput::new(4),
+    ]);
+    runner.update_scene(Component::Tiles(TilesComponent {
+        id: Some(ComponentId(TILES_ID.into())),
+        children: vec![input(1, true), input(2, true), input(3, true)],
+        ..Default::default()
+    }));
+    runner.snapshot(Duration::from_millis(0));
+    // `input_2` is removed (fades out in its old position) and `input_4` is
+    // added (fades in in its final position).
+    runner.update_scene(Component::Tiles(TilesComponent {
+        id: Some(ComponentId(TILES_ID.into())),
+        transition: Some(linear_500ms(false)),
+        children: vec![input(1, true), input(3, true), input(4, true)],
+        ..Default::default()
+    }));
+    runner.snapshot(Duration::from_millis(250));
+    runner.snapshot(Duration::from_millis(500));
+    // `input_2` is no longer rendered after the transition.
+    runner.snapshot(Duration::from_millis(750));
+    runner.finish()
+}
@@ -65,6 +65,9 @@ fn change_rescaler_absolute_and_send_next_update() -> Result<()> {
                         position_horizontal: HorizontalPosition::RightOffset(right),
                         position_vertical: VerticalPosition::TopOffset(top),
                         rotation_degrees: 0.0,
+                        rotation_x_degrees: 0.0,
+                        rotation_y_degrees: 0.0,
+                        perspective: None,
                     }),
                     transition,
                     child: Box::new(Component::View(ViewComponent {
@@ -263,6 +266,9 @@ fn absolute_view(
                 position_horizontal: HorizontalPosition::RightOffset(right),
                 position_vertical: VerticalPosition::TopOffset(top),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             transition,
             ..Default::default()
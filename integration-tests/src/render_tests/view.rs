@@ -72,6 +72,14 @@ fn input_stream(id: &str) -> Component {
     Component::InputStream(InputStreamComponent {
         id: None,
         input_id: InputId(id.into()),
+        chroma_key: None,
+        color_correction: None,
+        lut_id: None,
+        opacity: 1.0,
+        crop: None,
+        ken_burns: None,
+        flip_horizontal: false,
+        flip_vertical: false,
     })
 }
 
@@ -80,6 +88,7 @@ fn box_shadow_offset_30(color: RGBAColor) -> BoxShadow {
         offset_x: 60.0,
         offset_y: 30.0,
         blur_radius: 30.0,
+        spread: 0.0,
         color,
     }
 }
@@ -252,6 +261,9 @@ fn constant_width_views_row_with_overflow_hidden() -> Result<()> {
                         position_horizontal: HorizontalPosition::LeftOffset(-100.0),
                         position_vertical: VerticalPosition::TopOffset(100.0),
                         rotation_degrees: 0.0,
+                        rotation_x_degrees: 0.0,
+                        rotation_y_degrees: 0.0,
+                        perspective: None,
                     }),
                     ..Default::default()
                 })],
@@ -300,6 +312,9 @@ fn constant_width_views_row_with_overflow_visible() -> Result<()> {
                         position_horizontal: HorizontalPosition::LeftOffset(-100.0),
                         position_vertical: VerticalPosition::TopOffset(100.0),
                         rotation_degrees: 0.0,
+                        rotation_x_degrees: 0.0,
+                        rotation_y_degrees: 0.0,
+                        perspective: None,
                     }),
                     ..Default::default()
                 })],
@@ -369,6 +384,9 @@ fn constant_width_views_row_with_overflow_fit() -> Result<()> {
                             position_horizontal: HorizontalPosition::LeftOffset(50.0),
                             position_vertical: VerticalPosition::TopOffset(50.0),
                             rotation_degrees: 0.0,
+                            rotation_x_degrees: 0.0,
+                            rotation_y_degrees: 0.0,
+                            perspective: None,
                         }),
                         ..Default::default()
                     }),
@@ -527,6 +545,9 @@ fn view_with_absolute_positioning_partially_covered_by_sibling() -> Result<()> {
                     position_horizontal: HorizontalPosition::RightOffset(50.0),
                     position_vertical: VerticalPosition::TopOffset(50.0),
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
                 }),
                 ..Default::default()
             }),
@@ -562,6 +583,9 @@ fn view_with_absolute_positioning_render_over_siblings() -> Result<()> {
                     position_horizontal: HorizontalPosition::RightOffset(50.0),
                     position_vertical: VerticalPosition::TopOffset(50.0),
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
                 }),
                 ..Default::default()
             }),
@@ -585,6 +609,9 @@ fn root_view_with_background_color() -> Result<()> {
                 position_horizontal: HorizontalPosition::RightOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             ..Default::default()
         })],
@@ -607,6 +634,9 @@ fn border_radius() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             ..Default::default()
@@ -630,6 +660,9 @@ fn border_radius_clipping() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(500.0),
             ..Default::default()
@@ -653,6 +686,9 @@ fn border_radius_clipping_large_border_width() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(25.0),
                 position_vertical: VerticalPosition::TopOffset(25.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(500.0),
             border_width: 100.0,
@@ -678,6 +714,9 @@ fn border_width() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_width: 20.0,
             border_color: WHITE,
@@ -702,6 +741,9 @@ fn box_shadow() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             box_shadow: vec![box_shadow_offset_30(GREEN_FULL)],
             ..Default::default()
@@ -724,6 +766,9 @@ fn box_shadow_sibling() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(100.0),
                 position_vertical: VerticalPosition::TopOffset(100.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             overflow: Overflow::Visible,
             children: vec![
@@ -734,12 +779,14 @@ fn box_shadow_sibling() -> Result<()> {
                             offset_x: 0.0,
                             offset_y: 60.0,
                             blur_radius: 30.0,
+                            spread: 0.0,
                             color: RED,
                         },
                         BoxShadow {
                             offset_x: -60.0,
                             offset_y: -30.0,
                             blur_radius: 30.0,
+                            spread: 0.0,
                             color: BLUE,
                         },
                     ],
@@ -774,6 +821,9 @@ fn border_radius_border_box_shadow() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             border_width: 20.0,
@@ -800,6 +850,9 @@ fn border_radius_box_shadow() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             box_shadow: vec![box_shadow_offset_30(GREEN_FULL)],
@@ -824,6 +877,9 @@ fn border_radius_box_shadow_overflow_hidden() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             border_width: 20.0,
@@ -851,6 +907,9 @@ fn border_radius_box_shadow_overflow_fit() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             overflow: Overflow::Fit,
             border_radius: BorderRadius::new_with_radius(50.0),
@@ -879,6 +938,9 @@ fn border_radius_box_shadow_rescaler_input_stream() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             border_width: 20.0,
@@ -910,6 +972,9 @@ fn nested_border_width_radius() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             border_width: 20.0,
@@ -940,6 +1005,9 @@ fn nested_border_width_radius_aligned() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(80.0),
             border_width: 20.0,
@@ -971,6 +1039,9 @@ fn nested_border_width_radius_multi_child() -> Result<()> {
                 position_horizontal: HorizontalPosition::LeftOffset(50.0),
                 position_vertical: VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: BorderRadius::new_with_radius(50.0),
             border_width: 10.0,
@@ -1012,6 +1083,7 @@ fn border_radius_border_box_shadow_rescaled() -> Result<()> {
                     offset_x: 20.0,
                     offset_y: 20.0,
                     blur_radius: 5.0,
+                    spread: 0.0,
                     color: GREEN_FULL,
                 }],
                 ..Default::default()
@@ -1069,6 +1141,7 @@ fn border_radius_border_box_shadow_rescaled_and_hidden_by_parent() -> Result<()>
                         offset_x: 20.0,
                         offset_y: 20.0,
                         blur_radius: 5.0,
+                        spread: 0.0,
                         color: GREEN_FULL,
                     }],
                     ..Default::default()
@@ -1324,6 +1397,9 @@ fn padding_absolute_children() -> Result<()> {
                         position_horizontal: HorizontalPosition::LeftOffset(40.0),
                         position_vertical: VerticalPosition::TopOffset(40.0),
                         rotation_degrees: 0.0,
+                        rotation_x_degrees: 0.0,
+                        rotation_y_degrees: 0.0,
+                        perspective: None,
                     }),
                     padding: Padding {
                         top: 20.0,
@@ -4,7 +4,7 @@ use std::{sync::Arc, time::Duration};
 use smelter_render::{
     OutputFrameFormat, RendererId, RendererSpec, Resolution,
     scene::{
-        BorderRadius, Component, Overflow, Position, RGBAColor, ShaderComponent, Size,
+        BlendMode, BorderRadius, Component, Overflow, Position, RGBAColor, ShaderComponent, Size,
         ViewChildrenDirection, ViewComponent,
     },
     shader::ShaderSpec,
@@ -97,13 +97,20 @@ fn yuv_test_uniform_color() {
                     height: None,
                 },
                 transition: None,
+                keyframes: vec![],
                 overflow: Overflow::Hidden,
                 background_color: RGBAColor(50, 0, 0, 255),
+                background_gradient: None,
                 border_radius: BorderRadius::ZERO,
                 border_width: 0.0,
                 border_color: RGBAColor(0, 0, 0, 0),
                 box_shadow: vec![],
                 padding: Default::default(),
+                blur_radius: 0.0,
+                color_correction: None,
+                lut_id: None,
+                opacity: 1.0,
+                blend_mode: BlendMode::Normal,
             })),
             Step::RenderWithSnapshot(Duration::ZERO),
         ],
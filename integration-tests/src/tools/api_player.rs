@@ -0,0 +1,166 @@
+//! Replay API requests recorded with `SMELTER_API_RECORDING_FILE` against
+//! a running Smelter instance.
+//!
+//! Requests are sent in the recorded order, preserving the time offsets
+//! between them (relative to the start of the playback). Failed requests
+//! are reported, but do not stop the playback.
+
+use std::{
+    fs,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use smelter::api_recorder::RecordedRequest;
+
+pub fn play(path: &Path, api_url: &str) -> Result<()> {
+    let recording = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read recording {}", path.display()))?;
+    let requests = parse_recording(&recording)?;
+
+    let client = reqwest::blocking::Client::new();
+    let start = Instant::now();
+    for request in requests {
+        let time = Duration::from_secs_f64(request.time_ms / 1000.0);
+        if let Some(wait) = time.checked_sub(start.elapsed()) {
+            thread::sleep(wait);
+        }
+
+        let url = format!("{}{}", api_url.trim_end_matches('/'), request.path);
+        let builder = client.post(&url).timeout(Duration::from_secs(100));
+        let builder = match &request.body {
+            Some(body) => builder.json(body),
+            None => builder,
+        };
+        match builder.send() {
+            Ok(response) if response.status().is_success() => {
+                println!("[{:>10.1}ms] {} OK", request.time_ms, request.path);
+            }
+            Ok(response) => {
+                let status = response.status();
+                let body = response.text().unwrap_or_default();
+                eprintln!(
+                    "[{:>10.1}ms] {} failed with status {status}: {body}",
+                    request.time_ms, request.path
+                );
+            }
+            Err(err) => {
+                eprintln!(
+                    "[{:>10.1}ms] {} failed: {err}",
+                    request.time_ms, request.path
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_recording(recording: &str) -> Result<Vec<RecordedRequest>> {
+    recording
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str::<RecordedRequest>)
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to parse recording")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+    };
+
+    use serde_json::{Value, json};
+    use smelter::api_recorder::ApiRecorder;
+
+    use super::*;
+
+    /// Accepts `count` requests and returns their paths and bodies.
+    fn serve(listener: TcpListener, count: usize) -> Vec<(String, Option<Value>)> {
+        (0..count)
+            .map(|_| {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split(' ').nth(1).unwrap().to_string();
+
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':')
+                        && name.eq_ignore_ascii_case("content-length")
+                    {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .unwrap();
+                let body = (!body.is_empty()).then(|| serde_json::from_slice(&body).unwrap());
+                (path, body)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn play_recorded_requests() {
+        let path = std::env::temp_dir().join(format!(
+            "smelter_api_recording_{}.jsonl",
+            std::process::id()
+        ));
+        let register = json!({ "type": "mp4", "path": "input.mp4" });
+
+        let recorder = ApiRecorder::new(&path).unwrap();
+        recorder.record(
+            Duration::from_millis(5),
+            "/api/input/input_1/register",
+            register.to_string().as_bytes(),
+        );
+        recorder.record(
+            Duration::from_millis(10),
+            "/api/input/input_2/register",
+            b"{",
+        );
+        recorder.record(Duration::from_millis(20), "/api/start", b"");
+        drop(recorder);
+
+        let recording = parse_recording(&fs::read_to_string(&path).unwrap()).unwrap();
+        let times = recording.iter().map(|r| r.time_ms).collect::<Vec<_>>();
+        assert_eq!(times, [5.0, 20.0]);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let api_url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = thread::spawn(move || serve(listener, 2));
+
+        let start = Instant::now();
+        play(&path, &api_url).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            server.join().unwrap(),
+            [
+                ("/api/input/input_1/register".to_string(), Some(register)),
+                ("/api/start".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_invalid_recording() {
+        assert!(parse_recording("{\"time_ms\": 1.0, \"path\": \"/api/start\"}\n\n").is_ok());
+        assert!(parse_recording("not a request").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod api_player;
 pub mod frame_inspector;
 pub mod mp4_player;
 pub mod mp4_source;
@@ -14,6 +14,51 @@ pub fn decode_amf_values(amf_bytes: Bytes) -> Result<Vec<AmfValue>, AmfDecodingE
     decoder.decode_buf()
 }
 
+/// Decode AMF0 encoded messages without building top-level objects and ECMA arrays.
+///
+/// Properties of top-level objects and ECMA arrays are passed to the visitor one by one,
+/// and the visitor can skip decoding the ones it does not need. All other values are
+/// decoded the same way as in [`decode_amf_values`].
+///
+/// Skipped values are not kept, so references pointing to them resolve to [`AmfValue::Undefined`].
+pub fn decode_amf_values_with_visitor(
+    amf_bytes: Bytes,
+    visitor: &mut impl AmfVisitor,
+) -> Result<(), AmfDecodingError> {
+    let mut decoder = Amf0DecoderState::new(amf_bytes);
+    while decoder.buf.has_remaining() {
+        decoder.visit_value(visitor)?;
+    }
+    Ok(())
+}
+
+/// Kind of a top-level complex value that is passed to [`AmfVisitor`] property by property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmfObjectKind {
+    Object,
+    EcmaArray,
+}
+
+/// Receives values decoded by [`decode_amf_values_with_visitor`].
+pub trait AmfVisitor {
+    /// Called for every top-level value that is not an object or ECMA array.
+    fn visit_value(&mut self, value: AmfValue);
+
+    /// Called at the start of a top-level object or ECMA array, before its properties.
+    fn visit_object_start(&mut self, kind: AmfObjectKind);
+
+    /// Called before decoding a property value. If it returns `true`, the value is skipped
+    /// without allocating and [`Self::visit_property`] is not called.
+    fn skip_property(&mut self, _key: &str) -> bool {
+        false
+    }
+
+    fn visit_property(&mut self, key: String, value: AmfValue);
+
+    /// Called after the last property of a top-level object or ECMA array.
+    fn visit_object_end(&mut self);
+}
+
 #[derive(Default)]
 struct Amf0DecoderState {
     buf: Bytes,
@@ -38,6 +83,37 @@ impl Amf0DecoderState {
         Ok(amf_values)
     }
 
+    fn visit_value(&mut self, visitor: &mut impl AmfVisitor) -> Result<(), AmfDecodingError> {
+        let kind = match self.buf.first() {
+            Some(&OBJECT) => AmfObjectKind::Object,
+            Some(&ECMA_ARRAY) => AmfObjectKind::EcmaArray,
+            _ => {
+                visitor.visit_value(self.decode_value()?);
+                return Ok(());
+            }
+        };
+        self.buf.advance(1);
+
+        if kind == AmfObjectKind::EcmaArray {
+            self.skip_bytes(4)?; // array size
+        }
+
+        visitor.visit_object_start(kind);
+        while let Some(key) = self.decode_object_key()? {
+            if visitor.skip_property(&key) {
+                self.skip_value()?;
+            } else {
+                let value = self.decode_value()?;
+                visitor.visit_property(key, value);
+            }
+        }
+        visitor.visit_object_end();
+
+        // Keep indexes of the following complex values in sync, the content is not stored.
+        self.complexes.push(AmfValue::Undefined);
+        Ok(())
+    }
+
     fn decode_value(&mut self) -> Result<AmfValue, AmfDecodingError> {
         if self.buf.is_empty() {
             return Err(AmfDecodingError::InsufficientData);
@@ -198,25 +274,108 @@ impl Amf0DecoderState {
 
     fn decode_object_pairs(&mut self) -> Result<HashMap<String, AmfValue>, AmfDecodingError> {
         let mut pairs = HashMap::new();
+        while let Some(key) = self.decode_object_key()? {
+            let value = self.decode_value()?;
+            pairs.insert(key, value);
+        }
+        Ok(pairs)
+    }
+
+    /// Returns `None` and consumes the end marker if there are no more properties.
+    fn decode_object_key(&mut self) -> Result<Option<String>, AmfDecodingError> {
+        if self.buf.remaining() < 3 {
+            return Err(AmfDecodingError::InsufficientData);
+        }
+        if self.buf[..3] == OBJECT_END_MARKER {
+            self.buf.advance(3);
+            return Ok(None);
+        }
+        let key_size = self.buf.get_u16() as usize;
+        if self.buf.remaining() < key_size {
+            return Err(AmfDecodingError::InsufficientData);
+        }
+        let key_bytes: Bytes = self.buf.split_to(key_size);
+        let key =
+            String::from_utf8(key_bytes.to_vec()).map_err(|_| AmfDecodingError::InvalidUtf8)?;
+        Ok(Some(key))
+    }
+
+    fn skip_value(&mut self) -> Result<(), AmfDecodingError> {
+        if self.buf.is_empty() {
+            return Err(AmfDecodingError::InsufficientData);
+        }
+
+        let marker = self.buf.get_u8();
+        match marker {
+            NUMBER => self.skip_bytes(8)?,
+            BOOLEAN => self.skip_bytes(1)?,
+            STRING => self.skip_string()?,
+            NULL | UNDEFINED => {}
+            REFERENCE => self.skip_bytes(2)?,
+            DATE => self.skip_bytes(10)?,
+            LONG_STRING => {
+                if self.buf.remaining() < 4 {
+                    return Err(AmfDecodingError::InsufficientData);
+                }
+                let size = self.buf.get_u32() as usize;
+                self.skip_bytes(size)?;
+            }
+            OBJECT => {
+                self.skip_object_pairs()?;
+                self.complexes.push(AmfValue::Undefined);
+            }
+            ECMA_ARRAY => {
+                self.skip_bytes(4)?;
+                self.skip_object_pairs()?;
+                self.complexes.push(AmfValue::Undefined);
+            }
+            STRICT_ARRAY => {
+                if self.buf.remaining() < 4 {
+                    return Err(AmfDecodingError::InsufficientData);
+                }
+                let size = self.buf.get_u32();
+                for _ in 0..size {
+                    self.skip_value()?;
+                }
+                self.complexes.push(AmfValue::Undefined);
+            }
+            TYPED_OBJECT => {
+                self.skip_string()?;
+                self.skip_object_pairs()?;
+                self.complexes.push(AmfValue::Undefined);
+            }
+            _ => return Err(AmfDecodingError::UnknownType(marker)),
+        }
+        Ok(())
+    }
 
+    fn skip_object_pairs(&mut self) -> Result<(), AmfDecodingError> {
         loop {
             if self.buf.remaining() < 3 {
                 return Err(AmfDecodingError::InsufficientData);
             }
             if self.buf[..3] == OBJECT_END_MARKER {
                 self.buf.advance(3);
-                return Ok(pairs);
-            }
-            let key_size = self.buf.get_u16() as usize;
-            if self.buf.remaining() < key_size {
-                return Err(AmfDecodingError::InsufficientData);
+                return Ok(());
             }
-            let key_bytes: Bytes = self.buf.split_to(key_size);
-            let key =
-                String::from_utf8(key_bytes.to_vec()).map_err(|_| AmfDecodingError::InvalidUtf8)?;
+            self.skip_string()?;
+            self.skip_value()?;
+        }
+    }
 
-            let value = self.decode_value()?;
-            pairs.insert(key, value);
+    fn skip_string(&mut self) -> Result<(), AmfDecodingError> {
+        if self.buf.remaining() < 2 {
+            return Err(AmfDecodingError::InsufficientData);
+        }
+        let size = self.buf.get_u16() as usize;
+        self.skip_bytes(size)
+    }
+
+    fn skip_bytes(&mut self, size: usize) -> Result<(), AmfDecodingError> {
+        if self.buf.remaining() < size {
+            return Err(AmfDecodingError::InsufficientData);
         }
+        self.buf.advance(size);
+        Ok(())
     }
 }
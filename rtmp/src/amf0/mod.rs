@@ -3,7 +3,7 @@ use std::collections::HashMap;
 mod decoding;
 mod encoding;
 
-pub use decoding::decode_amf_values;
+pub use decoding::{AmfObjectKind, AmfVisitor, decode_amf_values, decode_amf_values_with_visitor};
 pub use encoding::encode_amf_values;
 
 const NUMBER: u8 = 0x00;
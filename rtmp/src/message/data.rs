@@ -1,6 +1,15 @@
 use std::collections::HashMap;
 
-use crate::amf0::AmfValue;
+use bytes::Bytes;
+
+use crate::{
+    AmfDecodingError,
+    amf0::{AmfObjectKind, AmfValue, AmfVisitor, decode_amf_values_with_visitor},
+};
+
+/// Metadata properties that are not decoded. Some publishers send keyframe index tables
+/// that can be very large, and they are only useful for seeking in files.
+const SKIPPED_METADATA_PROPERTIES: [&str; 1] = ["keyframes"];
 
 #[derive(Debug, Clone)]
 pub(crate) enum DataMessage {
@@ -9,6 +18,12 @@ pub(crate) enum DataMessage {
 }
 
 impl DataMessage {
+    pub fn from_amf_bytes(payload: Bytes) -> Result<Self, AmfDecodingError> {
+        let mut visitor = DataMessageVisitor::default();
+        decode_amf_values_with_visitor(payload, &mut visitor)?;
+        Ok(Self::from_amf_values(visitor.values))
+    }
+
     pub fn from_amf_values(values: Vec<AmfValue>) -> Self {
         // onMetaData can appear as:
         //   ["@setDataFrame", "onMetaData", {properties}]
@@ -51,3 +66,81 @@ impl DataMessage {
         }
     }
 }
+
+#[derive(Default)]
+struct DataMessageVisitor {
+    values: Vec<AmfValue>,
+    object: Option<(AmfObjectKind, HashMap<String, AmfValue>)>,
+}
+
+impl AmfVisitor for DataMessageVisitor {
+    fn visit_value(&mut self, value: AmfValue) {
+        self.values.push(value);
+    }
+
+    fn visit_object_start(&mut self, kind: AmfObjectKind) {
+        self.object = Some((kind, HashMap::new()));
+    }
+
+    fn skip_property(&mut self, key: &str) -> bool {
+        SKIPPED_METADATA_PROPERTIES.contains(&key)
+    }
+
+    fn visit_property(&mut self, key: String, value: AmfValue) {
+        if let Some((_, properties)) = &mut self.object {
+            properties.insert(key, value);
+        }
+    }
+
+    fn visit_object_end(&mut self) {
+        let Some((kind, properties)) = self.object.take() else {
+            return;
+        };
+        self.values.push(match kind {
+            AmfObjectKind::Object => AmfValue::Object(properties),
+            AmfObjectKind::EcmaArray => AmfValue::EcmaArray(properties),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amf0::encode_amf_values;
+
+    #[test]
+    fn on_metadata_skips_keyframes() {
+        let keyframes = HashMap::from([
+            (
+                "times".to_string(),
+                AmfValue::StrictArray(vec![AmfValue::Number(0.0), AmfValue::Number(2.0)]),
+            ),
+            (
+                "filepositions".to_string(),
+                AmfValue::StrictArray(vec![AmfValue::Number(100.0), AmfValue::Number(200.0)]),
+            ),
+        ]);
+        let metadata = HashMap::from([
+            ("width".to_string(), AmfValue::Number(1280.0)),
+            ("keyframes".to_string(), AmfValue::Object(keyframes)),
+            ("encoder".to_string(), AmfValue::String("obs".into())),
+        ]);
+        let payload = encode_amf_values(&[
+            AmfValue::String("@setDataFrame".into()),
+            AmfValue::String("onMetaData".into()),
+            AmfValue::EcmaArray(metadata),
+        ])
+        .unwrap();
+
+        let DataMessage::OnMetaData(decoded) = DataMessage::from_amf_bytes(payload).unwrap() else {
+            panic!("expected onMetaData");
+        };
+        assert_eq!(
+            decoded,
+            HashMap::from([
+                ("width".to_string(), AmfValue::Number(1280.0)),
+                ("encoder".to_string(), AmfValue::String("obs".into())),
+            ])
+        );
+    }
+}
@@ -1,6 +1,5 @@
 use crate::{
     RtmpMessageParseError,
-    amf0::decode_amf_values,
     message::{
         DataMessage, RtmpMessageIncoming, audio::AudioMessage, command::CommandMessage,
         user_control::UserControlMessage, video::VideoMessage,
@@ -21,7 +20,7 @@ impl RtmpMessageIncoming {
             },
 
             MessageType::DataMessageAmf0 => RtmpMessageIncoming::DataMessage {
-                data: DataMessage::from_amf_values(decode_amf_values(msg.payload)?),
+                data: DataMessage::from_amf_bytes(msg.payload)?,
             },
 
             MessageType::SetChunkSize if msg.payload.len() >= 4 => {
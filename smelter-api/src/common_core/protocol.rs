@@ -63,3 +63,87 @@ impl TryFrom<PortOrPortRange> for core::PortOrRange {
         }
     }
 }
+
+/// Options applied to the RTP socket.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RtpSocketOptions {
+    /// Size of the socket receive buffer (`SO_RCVBUF`) in bytes. Larger buffers reduce packet
+    /// loss on high-bitrate streams. OS may clamp this value, actually applied size is logged.
+    /// For UDP inputs defaults to 16MiB, otherwise the OS default is used.
+    pub receive_buffer_size_bytes: Option<u32>,
+    /// Size of the socket send buffer (`SO_SNDBUF`) in bytes. OS may clamp this value,
+    /// actually applied size is logged.
+    pub send_buffer_size_bytes: Option<u32>,
+    /// DSCP value (0-63) used to mark outgoing packets for QoS on managed networks.
+    pub dscp: Option<u8>,
+}
+
+impl TryFrom<RtpSocketOptions> for core::RtpSocketOptions {
+    type Error = TypeError;
+
+    fn try_from(value: RtpSocketOptions) -> Result<Self, Self::Error> {
+        let buffer_size = |field: &str, size: Option<u32>| match size {
+            Some(0) => Err(TypeError::new(format!(
+                "\"{field}\" has to be larger than 0."
+            ))),
+            Some(size) if size > i32::MAX as u32 => Err(TypeError::new(format!(
+                "\"{field}\" can not be larger than {}.",
+                i32::MAX
+            ))),
+            size => Ok(size.map(|size| size as usize)),
+        };
+        if let Some(dscp) = value.dscp
+            && dscp > 63
+        {
+            return Err(TypeError::new(
+                "\"dscp\" has to be a number between 0 and 63.",
+            ));
+        }
+
+        Ok(core::RtpSocketOptions {
+            recv_buffer_size: buffer_size(
+                "receive_buffer_size_bytes",
+                value.receive_buffer_size_bytes,
+            )?,
+            send_buffer_size: buffer_size("send_buffer_size_bytes", value.send_buffer_size_bytes)?,
+            dscp: value.dscp,
+        })
+    }
+}
+
+/// RTP payload types used to identify video and audio packets. Custom codecs registered
+/// in a Smelter instance embedded as a library are selected based on these values.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RtpPayloadTypes {
+    /// (**default=`96`**) Payload type of the video track.
+    pub video: Option<u8>,
+    /// (**default=`97`**) Payload type of the audio track.
+    pub audio: Option<u8>,
+}
+
+impl TryFrom<RtpPayloadTypes> for core::RtpPayloadTypes {
+    type Error = TypeError;
+
+    fn try_from(value: RtpPayloadTypes) -> Result<Self, Self::Error> {
+        let default = core::RtpPayloadTypes::default();
+        let payload_type = |field: &str, payload_type: Option<u8>, default: u8| {
+            match payload_type.unwrap_or(default) {
+                // Values 64-95 are reserved to distinguish RTP from RTCP (RFC 5761).
+                pt @ (0..=63 | 96..=127) => Ok(pt),
+                _ => Err(TypeError::new(format!(
+                    "\"{field}\" has to be a number between 0 and 63 or between 96 and 127."
+                ))),
+            }
+        };
+        let video = payload_type("video", value.video, default.video)?;
+        let audio = payload_type("audio", value.audio, default.audio)?;
+        if video == audio {
+            return Err(TypeError::new(
+                "Video and audio tracks have to use different payload types.",
+            ));
+        }
+        Ok(core::RtpPayloadTypes { video, audio })
+    }
+}
@@ -31,12 +31,34 @@ pub struct DeckLink {
     /// will list available devices.
     pub persistent_id: Option<String>,
 
+    /// Profile that should be activated on the DeckLink card before the input is started.
+    /// Profiles define how many sub-devices are exposed by a card and whether each of them
+    /// can capture and play back at the same time. The sub-device is selected again after
+    /// the profile is changed.
+    ///
+    /// If not specified, the currently active profile is used.
+    pub profile: Option<DeckLinkProfile>,
+
     /// (**default=`true`**) Enable audio support.
     pub enable_audio: Option<bool>,
 
+    /// (**default=`false`**) Emit `INPUT_ANCILLARY_DATA` events for CEA-708 captions and
+    /// SCTE-104 messages carried in the ancillary data of the input signal.
+    pub enable_ancillary_data: Option<bool>,
+
     /// (**default=`false`**) If input is required and frames are not processed
     /// on time, then Smelter will delay producing output frames.
     pub required: Option<bool>,
     /// Enable side channel for video and/or audio track.
     pub side_channel: Option<SideChannel>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeckLinkProfile {
+    OneSubDeviceFullDuplex,
+    OneSubDeviceHalfDuplex,
+    TwoSubDevicesFullDuplex,
+    TwoSubDevicesHalfDuplex,
+    FourSubDevicesHalfDuplex,
+}
@@ -24,7 +24,9 @@ impl TryFrom<DeckLink> for core::RegisterInputOptions {
                 subdevice_index: value.subdevice_index,
                 display_name: value.display_name,
                 persistent_id,
+                profile: value.profile.map(Into::into),
                 enable_audio: value.enable_audio.unwrap_or(true),
+                enable_ancillary_data: value.enable_ancillary_data.unwrap_or(false),
                 pixel_format: Some(core::DeckLinkPixelFormat::Format8BitYUV),
                 queue_options: {
                     let side_channel = value.side_channel.unwrap_or_default();
@@ -47,3 +49,16 @@ impl TryFrom<DeckLink> for core::RegisterInputOptions {
         ))
     }
 }
+
+#[cfg(feature = "decklink")]
+impl From<DeckLinkProfile> for core::DeckLinkProfileId {
+    fn from(value: DeckLinkProfile) -> Self {
+        match value {
+            DeckLinkProfile::OneSubDeviceFullDuplex => Self::OneSubDeviceFullDuplex,
+            DeckLinkProfile::OneSubDeviceHalfDuplex => Self::OneSubDeviceHalfDuplex,
+            DeckLinkProfile::TwoSubDevicesFullDuplex => Self::TwoSubDevicesFullDuplex,
+            DeckLinkProfile::TwoSubDevicesHalfDuplex => Self::TwoSubDevicesHalfDuplex,
+            DeckLinkProfile::FourSubDevicesHalfDuplex => Self::FourSubDevicesHalfDuplex,
+        }
+    }
+}
@@ -33,6 +33,20 @@ pub struct Mp4Input {
     pub decoder_map: Option<HashMap<InputMp4Codec, Mp4VideoDecoderOptions>>,
     /// Enable side channel for video and/or audio track.
     pub side_channel: Option<SideChannel>,
+    /// Decryption key for files protected with Common Encryption (CENC). Supported schemes
+    /// are `cenc`, `cens`, `cbc1` and `cbcs`.
+    pub decryption: Option<Mp4InputDecryption>,
+}
+
+/// Clear key used to decrypt the MP4 file.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Mp4InputDecryption {
+    /// 128-bit content key as a hex string (32 characters).
+    pub key: Arc<str>,
+    /// 128-bit key ID as a hex string (32 characters). If provided, it has to match
+    /// the key ID of encrypted tracks in the file.
+    pub key_id: Option<Arc<str>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq, Eq, Hash)]
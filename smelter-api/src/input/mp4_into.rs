@@ -18,6 +18,7 @@ impl TryFrom<Mp4Input> for core::RegisterInputOptions {
             should_loop,
             decoder_map,
             side_channel,
+            decryption,
         } = value;
 
         const BAD_URL_PATH_SPEC: &str = "Exactly one of `url` or `path` has to be specified in a register request for an mp4 input.";
@@ -50,6 +51,18 @@ impl TryFrom<Mp4Input> for core::RegisterInputOptions {
             .transpose()
             .map_err(|err| TypeError::new(format!("Invalid duration. {err}")))?;
 
+        let decryption = decryption
+            .map(|decryption| {
+                Ok::<_, TypeError>(core::Mp4DecryptionOptions {
+                    key: parse_key("key", &decryption.key)?,
+                    key_id: decryption
+                        .key_id
+                        .map(|key_id| parse_key("key_id", &key_id))
+                        .transpose()?,
+                })
+            })
+            .transpose()?;
+
         Ok(core::RegisterInputOptions::Mp4(core::Mp4InputOptions {
             source,
             should_loop: should_loop.unwrap_or(false),
@@ -62,6 +75,25 @@ impl TryFrom<Mp4Input> for core::RegisterInputOptions {
                 audio_side_channel: side_channel.audio.unwrap_or(false),
                 side_channel_delay,
             },
+            decryption,
         }))
     }
 }
+
+fn parse_key(field: &str, key: &str) -> Result<[u8; 16], TypeError> {
+    let invalid_key = || {
+        TypeError::new(format!(
+            "\"{field}\" has to be a 128-bit value encoded as 32 hex digits."
+        ))
+    };
+    if key.len() != 32 || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid_key());
+    }
+
+    let mut bytes = [0; 16];
+    for (byte, digits) in bytes.iter_mut().zip(key.as_bytes().chunks(2)) {
+        let digits = std::str::from_utf8(digits).map_err(|_| invalid_key())?;
+        *byte = u8::from_str_radix(digits, 16).map_err(|_| invalid_key())?;
+    }
+    Ok(bytes)
+}
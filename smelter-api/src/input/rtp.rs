@@ -32,6 +32,10 @@ pub struct RtpInput {
     pub buffer_size_ms: Option<f64>,
     /// Enable side channel for video and/or audio track.
     pub side_channel: Option<SideChannel>,
+    /// Socket buffer sizes and DSCP marking.
+    pub socket_options: Option<RtpSocketOptions>,
+    /// (**default=`{ "video": 96, "audio": 97 }`**) Payload types of video and audio packets.
+    pub payload_types: Option<RtpPayloadTypes>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema, ToSchema)]
@@ -65,6 +69,12 @@ pub enum InputRtpAudioOptions {
         /// that should be used when depacketizing this stream.
         rtp_mode: Option<AacRtpMode>,
     },
+    /// Decoder registered for the audio payload type in a Smelter instance embedded
+    /// as a library.
+    Custom {
+        /// RTP clock rate of the audio track.
+        clock_rate: u32,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema)]
@@ -88,4 +98,8 @@ pub enum RtpVideoDecoderOptions {
     /// Hardware decoder. Requires GPU that supports Vulkan Video decoding.
     /// Requires gpu-video feature.
     VulkanH264,
+
+    /// Decoder registered for the video payload type in a Smelter instance embedded
+    /// as a library.
+    Custom,
 }
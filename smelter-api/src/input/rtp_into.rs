@@ -22,6 +22,8 @@ impl TryFrom<RtpInput> for core::RegisterInputOptions {
             buffer_size_ms,
             transport_protocol,
             side_channel,
+            socket_options,
+            payload_types,
         } = value;
 
         let (required, offset) = new_queue_options(required, offset_ms)?;
@@ -47,13 +49,14 @@ impl TryFrom<RtpInput> for core::RegisterInputOptions {
             video: video
                 .as_ref()
                 .map(|video| {
-                    let options = match video.decoder {
+                    let decoder = match video.decoder {
                         RtpVideoDecoderOptions::FfmpegH264 => core::VideoDecoderOptions::FfmpegH264,
                         RtpVideoDecoderOptions::FfmpegVp8 => core::VideoDecoderOptions::FfmpegVp8,
                         RtpVideoDecoderOptions::FfmpegVp9 => core::VideoDecoderOptions::FfmpegVp9,
                         RtpVideoDecoderOptions::VulkanH264 => core::VideoDecoderOptions::VulkanH264,
+                        RtpVideoDecoderOptions::Custom => return Ok(core::RtpVideoOptions::Custom),
                     };
-                    Ok(options)
+                    Ok(core::RtpVideoOptions::Decoder(decoder))
                 })
                 .transpose()?,
             audio: audio.map(TryFrom::try_from).transpose()?,
@@ -66,6 +69,14 @@ impl TryFrom<RtpInput> for core::RegisterInputOptions {
                 side_channel_delay,
             },
             offset,
+            socket_options: socket_options
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
+            payload_types: payload_types
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
         }))
     }
 }
@@ -76,6 +87,12 @@ impl TryFrom<InputRtpAudioOptions> for core::RtpAudioOptions {
     fn try_from(audio: InputRtpAudioOptions) -> Result<Self, Self::Error> {
         match audio {
             InputRtpAudioOptions::Opus => Ok(core::RtpAudioOptions::Opus),
+            InputRtpAudioOptions::Custom { clock_rate } => {
+                if clock_rate == 0 {
+                    return Err(TypeError::new("\"clock_rate\" has to be larger than 0."));
+                }
+                Ok(core::RtpAudioOptions::Custom { clock_rate })
+            }
             InputRtpAudioOptions::Aac {
                 audio_specific_config,
                 rtp_mode,
@@ -35,6 +35,8 @@ pub struct OutputHlsVideoOptions {
     pub encoder: HlsVideoEncoderOptions,
     /// Root of a component tree/scene that should be rendered for the output. Use [`update_output` request](../routes.md#update-output) to update this value after registration. [Learn more](../../concept/component.md).
     pub initial: VideoScene,
+    /// (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame. Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+    pub debug_overlay: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema)]
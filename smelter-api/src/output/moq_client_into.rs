@@ -27,11 +27,13 @@ impl TryFrom<MoqClientOutput> for core::RegisterOutputOptions {
                 send_eos_when,
                 encoder,
                 initial,
+                debug_overlay,
             }) => {
                 let encoder_options = encoder.to_pipeline_options(resolution, container)?;
                 let output_options = core::RegisterOutputVideoOptions {
                     initial: initial.try_into()?,
                     end_condition: send_eos_when.unwrap_or_default().try_into()?,
+                    debug_overlay: debug_overlay.unwrap_or(false),
                 };
                 (Some(encoder_options), Some(output_options))
             }
@@ -16,6 +16,13 @@ pub struct Mp4Output {
     pub video: Option<OutputMp4VideoOptions>,
     /// Audio stream configuration.
     pub audio: Option<OutputMp4AudioOptions>,
+    /// (**default=`true`**) Write `moov` atom before media data, so the file can be played progressively over HTTP.
+    /// The file is rewritten when the output ends, which takes additional time and disk space for long recordings.
+    pub fast_start: Option<bool>,
+    /// Interleave audio and video samples in chunks of at most this duration (in milliseconds).
+    /// Samples are written in time order across tracks, which improves compatibility with players
+    /// that do minimal buffering. By default, samples are written in the order they are encoded.
+    pub interleave_duration_ms: Option<f64>,
     /// Raw FFmpeg muxer options. See [docs](https://ffmpeg.org/ffmpeg-formats.html) for more.
     pub ffmpeg_options: Option<HashMap<Arc<str>, Arc<str>>>,
 }
@@ -31,6 +38,8 @@ pub struct OutputMp4VideoOptions {
     pub encoder: Mp4VideoEncoderOptions,
     /// Root of a component tree/scene that should be rendered for the output. Use [`update_output` request](../routes.md#update-output) to update this value after registration. [Learn more](../../concept/component.md).
     pub initial: VideoScene,
+    /// (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame. Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+    pub debug_overlay: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema)]
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::common_core::prelude as core;
 use crate::*;
 
@@ -9,6 +11,8 @@ impl TryFrom<Mp4Output> for core::RegisterOutputOptions {
             path,
             video,
             audio,
+            fast_start,
+            interleave_duration_ms,
             ffmpeg_options,
         } = request;
 
@@ -24,11 +28,13 @@ impl TryFrom<Mp4Output> for core::RegisterOutputOptions {
                 send_eos_when,
                 encoder,
                 initial,
+                debug_overlay,
             }) => {
                 let encoder_options = encoder.to_pipeline_options(resolution)?;
                 let output_options = core::RegisterOutputVideoOptions {
                     initial: initial.try_into()?,
                     end_condition: send_eos_when.unwrap_or_default().try_into()?,
+                    debug_overlay: debug_overlay.unwrap_or(false),
                 };
 
                 (Some(encoder_options), Some(output_options))
@@ -59,10 +65,22 @@ impl TryFrom<Mp4Output> for core::RegisterOutputOptions {
             None => (None, None),
         };
 
+        let interleave_duration = interleave_duration_ms
+            .map(|ms| Duration::try_from_secs_f64(ms / 1000.0))
+            .transpose()
+            .map_err(|err| TypeError::new(format!("Invalid interleave_duration_ms. {err}")))?;
+        if interleave_duration.is_some_and(|duration| duration.is_zero()) {
+            return Err(TypeError::new(
+                "\"interleave_duration_ms\" has to be larger than 0.",
+            ));
+        }
+
         let output_options = core::ProtocolOutputOptions::Mp4(core::Mp4OutputOptions {
             output_path: path,
             video: video_encoder_options,
             audio: audio_encoder_options,
+            fast_start: fast_start.unwrap_or(true),
+            interleave_duration,
             raw_options: ffmpeg_options.unwrap_or_default().into_iter().collect(),
         });
 
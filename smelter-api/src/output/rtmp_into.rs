@@ -19,10 +19,12 @@ impl TryFrom<RtmpOutput> for core::RegisterOutputOptions {
                 send_eos_when,
                 encoder,
                 initial,
+                debug_overlay,
             }) => {
                 let output_options = core::RegisterOutputVideoOptions {
                     initial: initial.try_into()?,
                     end_condition: send_eos_when.unwrap_or_default().try_into()?,
+                    debug_overlay: debug_overlay.unwrap_or(false),
                 };
 
                 (
@@ -21,6 +21,10 @@ pub struct RtpOutput {
     pub video: Option<OutputRtpVideoOptions>,
     /// Parameters of an audio included in the RTP stream.
     pub audio: Option<OutputRtpAudioOptions>,
+    /// Socket buffer sizes and DSCP marking.
+    pub socket_options: Option<RtpSocketOptions>,
+    /// (**default=`{ "video": 96, "audio": 97 }`**) Payload types of video and audio packets.
+    pub payload_types: Option<RtpPayloadTypes>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema)]
@@ -34,6 +38,8 @@ pub struct OutputRtpVideoOptions {
     pub encoder: RtpVideoEncoderOptions,
     /// Root of a component tree/scene that should be rendered for the output. Use [`update_output` request](../routes.md#update-output) to update this value after registration. [Learn more](../../concept/component.md).
     pub initial: VideoScene,
+    /// (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame. Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+    pub debug_overlay: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema)]
@@ -11,6 +11,8 @@ impl TryFrom<RtpOutput> for core::RegisterOutputOptions {
             transport_protocol,
             video,
             audio,
+            socket_options,
+            payload_types,
         } = request;
 
         if video.is_none() && audio.is_none() {
@@ -25,11 +27,13 @@ impl TryFrom<RtpOutput> for core::RegisterOutputOptions {
                 send_eos_when,
                 encoder,
                 initial,
+                debug_overlay,
             }) => {
                 let encoder_options = encoder.to_pipeline_options(resolution)?;
                 let output_options = core::RegisterOutputVideoOptions {
                     initial: initial.try_into()?,
                     end_condition: send_eos_when.unwrap_or_default().try_into()?,
+                    debug_overlay: debug_overlay.unwrap_or(false),
                 };
                 (Some(encoder_options), Some(output_options))
             }
@@ -94,6 +98,14 @@ impl TryFrom<RtpOutput> for core::RegisterOutputOptions {
             connection_options,
             video: video_encoder_options,
             audio: audio_encoder_options,
+            socket_options: socket_options
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
+            payload_types: payload_types
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
         });
 
         Ok(Self {
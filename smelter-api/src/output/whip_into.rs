@@ -24,6 +24,7 @@ impl TryFrom<WhipOutput> for core::RegisterOutputOptions {
                 let output_options = core::RegisterOutputVideoOptions {
                     initial: options.initial.try_into()?,
                     end_condition: options.send_eos_when.unwrap_or_default().try_into()?,
+                    debug_overlay: options.debug_overlay.unwrap_or(false),
                 };
 
                 let encoder_preferences = match options.encoder_preferences.as_deref() {
@@ -1,7 +1,9 @@
 mod image;
+mod lut;
 mod shader;
 mod web_renderer;
 
 pub use image::*;
+pub use lut::*;
 pub use shader::*;
 pub use web_renderer::*;
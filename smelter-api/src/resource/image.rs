@@ -35,6 +35,18 @@ pub enum ImageSpec {
         #[schema(value_type = Option<str>)]
         path: Option<Arc<Path>>,
     },
+    Webp {
+        url: Option<Arc<str>>,
+
+        #[schema(value_type = Option<str>)]
+        path: Option<Arc<Path>>,
+    },
+    Avif {
+        url: Option<Arc<str>>,
+
+        #[schema(value_type = Option<str>)]
+        path: Option<Arc<Path>>,
+    },
     Auto {
         url: Option<Arc<str>>,
 
@@ -80,6 +92,14 @@ impl TryFrom<ImageSpec> for smelter_render::RendererSpec {
                 src: from_url_or_path(url, path)?,
                 image_type: image::ImageType::Gif,
             },
+            ImageSpec::Webp { url, path } => image::ImageSpec {
+                src: from_url_or_path(url, path)?,
+                image_type: image::ImageType::Webp,
+            },
+            ImageSpec::Avif { url, path } => image::ImageSpec {
+                src: from_url_or_path(url, path)?,
+                image_type: image::ImageType::Avif,
+            },
             ImageSpec::Auto { url, path } => image::ImageSpec {
                 src: from_url_or_path(url, path)?,
                 image_type: image::ImageType::Auto,
@@ -0,0 +1,42 @@
+use std::{path::Path, sync::Arc};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use smelter_render::lut;
+use utoipa::ToSchema;
+
+use crate::*;
+
+/// 3D color lookup table in the `.cube` format.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LutSpec {
+    /// URL of the `.cube` file.
+    pub url: Option<Arc<str>>,
+
+    /// Path to the `.cube` file on the local filesystem.
+    #[schema(value_type = Option<str>)]
+    pub path: Option<Arc<Path>>,
+}
+
+impl TryFrom<LutSpec> for smelter_render::RendererSpec {
+    type Error = TypeError;
+
+    fn try_from(spec: LutSpec) -> Result<Self, Self::Error> {
+        let src = match (spec.url, spec.path) {
+            (None, None) => {
+                return Err(TypeError::new(
+                    "\"url\" or \"path\" field is required when registering a LUT.",
+                ));
+            }
+            (None, Some(path)) => lut::LutSource::LocalPath { path },
+            (Some(url), None) => lut::LutSource::Url { url },
+            (Some(_), Some(_)) => {
+                return Err(TypeError::new(
+                    "\"url\" and \"path\" fields are mutually exclusive when registering a LUT.",
+                ));
+            }
+        };
+        Ok(Self::Lut(lut::LutSpec { src }))
+    }
+}
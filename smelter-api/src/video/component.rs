@@ -17,6 +17,7 @@ pub enum Component {
     Text(Text),
     Tiles(Tiles),
     Rescaler(Rescaler),
+    AudioVisualizer(AudioVisualizer),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
@@ -26,6 +27,29 @@ pub struct InputStream {
     pub id: Option<ComponentId>,
     /// Id of an input. It identifies a stream registered using a [`RegisterInputStream`](../routes.md#register-input) request.
     pub input_id: InputId,
+    /// Removes the background of a given color from the input, e.g. a green screen.
+    pub chroma_key: Option<ChromaKey>,
+    /// Adjusts brightness, contrast, saturation and hue of the input.
+    pub color_correction: Option<ColorCorrection>,
+    /// Id of a LUT registered using a [`register LUT`](../routes.md#register-lut) request.
+    /// The LUT is applied after the color correction.
+    pub lut_id: Option<RendererId>,
+    /// (**default=`1.0`**) Opacity of the input in the range from `0.0` (fully transparent) to
+    /// `1.0` (fully opaque).
+    pub opacity: Option<f32>,
+    /// Part of the input that is rendered, e.g. to cut off letterboxing. Size of the component
+    /// is equal to the size of the cropped part.
+    pub crop: Option<SourceCrop>,
+    /// Pan and zoom animation of the input. Applied only if the component is a child of a
+    /// layout component (`View`, `Tiles` or `Rescaler`).
+    pub ken_burns: Option<KenBurns>,
+    /// (**default=`false`**) Mirror the input horizontally, e.g. for front-facing camera feeds.
+    /// Applied only if the component is a child of a layout component (`View`, `Tiles` or
+    /// `Rescaler`).
+    pub flip_horizontal: Option<bool>,
+    /// (**default=`false`**) Mirror the input vertically. Applied only if the component is
+    /// a child of a layout component (`View`, `Tiles` or `Rescaler`).
+    pub flip_vertical: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
@@ -72,19 +96,40 @@ pub struct View {
     /// Rotation of a component in degrees. If this field is defined, this element will be
     /// absolutely positioned, instead of being laid out by its parent.
     pub rotation: Option<f32>,
+    /// Rotation of a component in degrees around its horizontal axis (like CSS `rotateX`).
+    /// Positive values move the top edge away from the viewer. If this field is defined, this
+    /// element will be absolutely positioned, instead of being laid out by its parent.
+    pub rotation_x: Option<f32>,
+    /// Rotation of a component in degrees around its vertical axis (like CSS `rotateY`).
+    /// Positive values move the right edge away from the viewer. If this field is defined, this
+    /// element will be absolutely positioned, instead of being laid out by its parent.
+    pub rotation_y: Option<f32>,
+    /// Distance in pixels between the viewer and the component, used to project components
+    /// rotated with `rotation_x` or `rotation_y`. Smaller values make the effect stronger.
+    /// Child components without their own `perspective` inherit it. If this field is defined,
+    /// this element will be absolutely positioned, instead of being laid out by its parent.
+    pub perspective: Option<f32>,
 
     /// Defines how this component will behave during a scene update. This will only have an
     /// effect if the previous scene already contained a `View` component with the same id.
     pub transition: Option<Transition>,
 
+    /// Keyframe animations of the component properties. They are evaluated on every frame,
+    /// so multi-step animations do not require scene updates.
+    pub keyframes: Option<Vec<KeyframeTrack>>,
+
     /// (**default=`"hidden"`**) Controls what happens to content that is too big to fit into an area.
     pub overflow: Option<Overflow>,
 
     /// (**default=`"#00000000"`**) Background color in a `"#RRGGBBAA"` format.
     pub background_color: Option<RGBAColor>,
 
-    /// (**default=`0.0`**) Radius of a rounded corner.
-    pub border_radius: Option<f32>,
+    /// Background of the component, either a solid color or a gradient. Can't be used together
+    /// with `background_color`.
+    pub background: Option<Background>,
+
+    /// (**default=`0.0`**) Radius of rounded corners. A single number applies to all corners.
+    pub border_radius: Option<BorderRadius>,
 
     /// (**default=`0.0`**) Border width.
     pub border_width: Option<f32>,
@@ -115,6 +160,51 @@ pub struct View {
 
     /// (**default=`0.0`**) Padding on left side in pixels.
     pub padding_left: Option<f32>,
+
+    /// (**default=`0.0`**) Standard deviation in pixels of a Gaussian blur applied to all
+    /// inputs, images and other textures inside this component. Background color and borders
+    /// are not blurred. It can be animated with `transition`.
+    pub blur_radius: Option<f32>,
+
+    /// Adjusts brightness, contrast, saturation and hue of all inputs, images and other textures
+    /// inside this component. It is applied after the color correction of the child components.
+    /// Background color and borders are not modified.
+    pub color_correction: Option<ColorCorrection>,
+
+    /// Id of a LUT registered using a [`register LUT`](../routes.md#register-lut) request. It is
+    /// applied to all inputs, images and other textures inside this component that do not
+    /// use a LUT of their own. Background color and borders are not modified.
+    pub lut_id: Option<RendererId>,
+
+    /// (**default=`1.0`**) Opacity of this component and all of its children in the range from
+    /// `0.0` (fully transparent) to `1.0` (fully opaque). It can be animated with `transition`.
+    pub opacity: Option<f32>,
+
+    /// (**default=`"normal"`**) Defines how this component, including its background, borders
+    /// and children, is composited with the content rendered below it.
+    pub blend_mode: Option<BlendMode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(untagged)]
+pub enum BorderRadius {
+    /// Radius of all corners.
+    Uniform(f32),
+    /// Radius of each corner.
+    Corners(BorderRadiusCorners),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BorderRadiusCorners {
+    /// (**default=`0.0`**) Radius of the top-left corner.
+    pub top_left: Option<f32>,
+    /// (**default=`0.0`**) Radius of the top-right corner.
+    pub top_right: Option<f32>,
+    /// (**default=`0.0`**) Radius of the bottom-right corner.
+    pub bottom_right: Option<f32>,
+    /// (**default=`0.0`**) Radius of the bottom-left corner.
+    pub bottom_left: Option<f32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
@@ -124,6 +214,89 @@ pub struct BoxShadow {
     pub offset_y: Option<f32>,
     pub color: Option<RGBAColor>,
     pub blur_radius: Option<f32>,
+    /// (**default=`0.0`**) Distance in pixels by which the shadow is expanded in all directions
+    /// before it is blurred. Negative values shrink the shadow.
+    pub spread: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ChromaKey {
+    /// (**default=`"#00FF00"`**) Color that should be removed in a `"#RRGGBB"` format.
+    pub key_color: Option<RGBAColor>,
+    /// (**default=`0.4`**) How close to the key color (in the range 0 to 1) a pixel needs to be
+    /// to become fully transparent.
+    pub similarity: Option<f32>,
+    /// (**default=`0.08`**) Width of the range over which pixels fade from fully transparent to
+    /// fully opaque.
+    pub smoothness: Option<f32>,
+    /// (**default=`0.1`**) Reduces the key color reflected onto the foreground. Set to `0.0` to
+    /// disable.
+    pub spill_reduction: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ColorCorrection {
+    /// (**default=`0.0`**) Value in the range -1 to 1 added to all color channels.
+    pub brightness: Option<f32>,
+    /// (**default=`1.0`**) Multiplier of the distance from the middle gray. Values below `1.0`
+    /// decrease and values above `1.0` increase the contrast.
+    pub contrast: Option<f32>,
+    /// (**default=`1.0`**) Multiplier of the color intensity. `0.0` produces a grayscale image.
+    pub saturation: Option<f32>,
+    /// (**default=`0.0`**) Rotation of the hue in degrees.
+    pub hue_shift: Option<f32>,
+}
+
+/// Part of a source that is kept, everything outside of it is cut off.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SourceCrop {
+    /// Rectangle in pixels of the source. Parts outside of the source are ignored.
+    Pixels {
+        top: f32,
+        left: f32,
+        width: f32,
+        height: f32,
+    },
+    /// Rectangle relative to the size of the source, where `0.0` is the top/left edge and
+    /// `1.0` is the bottom/right edge of the source.
+    Normalized {
+        top: f32,
+        left: f32,
+        width: f32,
+        height: f32,
+    },
+}
+
+/// Fill of the area of a component.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Background {
+    /// Solid color in `#RRGGBBAA` format.
+    Color { color: RGBAColor },
+    /// Gradient along a line going through the center of the component, the same as the
+    /// CSS `linear-gradient` function.
+    LinearGradient {
+        /// (**default=`180.0`**) Direction of the gradient line in degrees. `0` goes from the
+        /// bottom to the top, `90` from the left to the right.
+        angle: Option<f32>,
+        /// List of 2 to 8 color stops.
+        stops: Vec<GradientStop>,
+    },
+    /// Elliptical gradient with the same aspect ratio as the component. The last stop is
+    /// placed at the corner of the component that is the farthest from the center.
+    RadialGradient {
+        /// (**default=`0.5`**) Horizontal position of the center relative to the width of
+        /// the component, where `0.0` is the left edge and `1.0` is the right edge.
+        center_x: Option<f32>,
+        /// (**default=`0.5`**) Vertical position of the center relative to the height of
+        /// the component, where `0.0` is the top edge and `1.0` is the bottom edge.
+        center_y: Option<f32>,
+        /// List of 2 to 8 color stops.
+        stops: Vec<GradientStop>,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
@@ -146,6 +319,23 @@ pub enum Overflow {
     Fit,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlendMode {
+    /// Draw the component over the content below it.
+    Normal,
+    /// Multiply colors of the component and the content below it. Result is always darker,
+    /// white pixels leave the content unchanged.
+    Multiply,
+    /// Inverse of `multiply`. Result is always lighter, black pixels leave the content
+    /// unchanged.
+    Screen,
+    /// `multiply` for dark and `screen` for light parts of the content below the component.
+    Overlay,
+    /// Add colors of the component to the content below it, e.g. for light effects.
+    Add,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ViewDirection {
@@ -203,13 +393,30 @@ pub struct Rescaler {
     /// Rotation of a component in degrees. If this field is defined, this element will be
     /// absolutely positioned, instead of being laid out by its parent.
     pub rotation: Option<f32>,
+    /// Rotation of a component in degrees around its horizontal axis (like CSS `rotateX`).
+    /// Positive values move the top edge away from the viewer. If this field is defined, this
+    /// element will be absolutely positioned, instead of being laid out by its parent.
+    pub rotation_x: Option<f32>,
+    /// Rotation of a component in degrees around its vertical axis (like CSS `rotateY`).
+    /// Positive values move the right edge away from the viewer. If this field is defined, this
+    /// element will be absolutely positioned, instead of being laid out by its parent.
+    pub rotation_y: Option<f32>,
+    /// Distance in pixels between the viewer and the component, used to project components
+    /// rotated with `rotation_x` or `rotation_y`. Smaller values make the effect stronger.
+    /// Child components without their own `perspective` inherit it. If this field is defined,
+    /// this element will be absolutely positioned, instead of being laid out by its parent.
+    pub perspective: Option<f32>,
 
     /// Defines how this component will behave during a scene update. This will only have an
     /// effect if the previous scene already contained a `Rescaler` component with the same id.
     pub transition: Option<Transition>,
 
-    /// (**default=`0.0`**) Radius of a rounded corner.
-    pub border_radius: Option<f32>,
+    /// Keyframe animations of the component properties. They are evaluated on every frame,
+    /// so multi-step animations do not require scene updates.
+    pub keyframes: Option<Vec<KeyframeTrack>>,
+
+    /// (**default=`0.0`**) Radius of rounded corners. A single number applies to all corners.
+    pub border_radius: Option<BorderRadius>,
 
     /// (**default=`0.0`**) Border width.
     pub border_width: Option<f32>,
@@ -219,6 +426,34 @@ pub struct Rescaler {
 
     /// List of box shadows.
     pub box_shadow: Option<Vec<BoxShadow>>,
+
+    /// Removes the background of a given color from all inputs inside this component, unless
+    /// they define their own `chroma_key`.
+    pub chroma_key: Option<ChromaKey>,
+
+    /// (**default=`0.0`**) Standard deviation in pixels of a Gaussian blur applied to all
+    /// inputs, images and other textures inside this component. Borders are not blurred. It
+    /// can be animated with `transition`.
+    pub blur_radius: Option<f32>,
+
+    /// (**default=`1.0`**) Opacity of this component and its child in the range from `0.0`
+    /// (fully transparent) to `1.0` (fully opaque). It can be animated with `transition`.
+    pub opacity: Option<f32>,
+
+    /// (**default=`"normal"`**) Defines how this component, including its borders and child,
+    /// is composited with the content rendered below it.
+    pub blend_mode: Option<BlendMode>,
+
+    /// Part of the child that is rescaled, everything outside of it is cut off. Dimensions of
+    /// the child that are not known are equal to the size of the rescaler (without borders).
+    pub crop: Option<SourceCrop>,
+
+    /// (**default=`false`**) Mirror the child horizontally. Position and size of the child
+    /// inside the rescaler are not changed.
+    pub flip_horizontal: Option<bool>,
+    /// (**default=`false`**) Mirror the child vertically. Position and size of the child
+    /// inside the rescaler are not changed.
+    pub flip_vertical: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
@@ -268,6 +503,34 @@ pub struct Image {
     /// Height of the image in pixels.
     /// If `width` is not explicitly provided, the image will automatically adjust its width to maintain its original aspect ratio relative to the height.
     pub height: Option<f32>,
+
+    /// Enables nine-slice scaling. Image is split into 9 parts by the insets, corners are
+    /// rendered without scaling, edges are stretched along one axis and the center along
+    /// both. Not supported for SVG images.
+    pub nine_slice: Option<NineSlice>,
+
+    /// Pan and zoom animation of the image. Applied only if the component is a child of a
+    /// layout component (`View`, `Tiles` or `Rescaler`).
+    pub ken_burns: Option<KenBurns>,
+    /// (**default=`false`**) Mirror the image horizontally. Applied only if the component is
+    /// a child of a layout component (`View`, `Tiles` or `Rescaler`).
+    pub flip_horizontal: Option<bool>,
+    /// (**default=`false`**) Mirror the image vertically. Applied only if the component is
+    /// a child of a layout component (`View`, `Tiles` or `Rescaler`).
+    pub flip_vertical: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct NineSlice {
+    /// (**default=`0.0`**) Distance in pixels of the source image from the top edge.
+    pub top: Option<f32>,
+    /// (**default=`0.0`**) Distance in pixels of the source image from the right edge.
+    pub right: Option<f32>,
+    /// (**default=`0.0`**) Distance in pixels of the source image from the bottom edge.
+    pub bottom: Option<f32>,
+    /// (**default=`0.0`**) Distance in pixels of the source image from the left edge.
+    pub left: Option<f32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
@@ -329,8 +592,12 @@ pub struct Text {
     /// Id of a component.
     pub id: Option<ComponentId>,
 
-    /// Text that will be rendered.
-    pub text: Arc<str>,
+    /// Text that will be rendered. Either `text` or `spans` has to be provided.
+    pub text: Option<Arc<str>>,
+    /// Parts of the text rendered one after another, each with its own styling. Span properties
+    /// that are not defined are inherited from the component. It's an error to provide both
+    /// `text` and `spans`.
+    pub spans: Option<Vec<TextSpan>>,
 
     /// Width of a texture that text will be rendered on. If not provided, the resulting texture
     /// will be sized based on the defined text but limited to `max_width` value.
@@ -352,19 +619,127 @@ pub struct Text {
     pub line_height: Option<f32>,
     /// (**default=`"#FFFFFFFF"`**) Font color in `#RRGGBBAA` format.
     pub color: Option<RGBAColor>,
+    /// Linear gradient that fills the text instead of `color`. The gradient spans the entire
+    /// texture that the text is rendered on.
+    pub gradient: Option<LinearGradient>,
     /// (**default=`"#00000000"`**) Background color in `#RRGGBBAA` format.
     pub background_color: Option<RGBAColor>,
+    /// Outline drawn around the glyphs. If the texture is sized based on the text, it is
+    /// extended to fit the stroke.
+    pub stroke: Option<TextStroke>,
+    /// Shadow of the text and its stroke. If the texture is sized based on the text, it is
+    /// extended to fit the shadow.
+    pub shadow: Option<TextShadow>,
+    /// Continuously scrolls the text horizontally, e.g. for news tickers. Text is not wrapped
+    /// and `wrap` is ignored. It's an error to provide `ticker` if `width` is not defined.
+    ///
+    /// Scrolling continues across scene updates as long as the component keeps the same `id`
+    /// and its text and `ticker` do not change.
+    pub ticker: Option<TextTicker>,
     /// (**default=`"Verdana"`**) Font family. Provide [family-name](https://www.w3.org/TR/2018/REC-css-fonts-3-20180920/#family-name-value)
     /// for a specific font. "generic-family" values like e.g. "sans-serif" will not work.
     pub font_family: Option<Arc<str>>,
     /// (**default=`"normal"`**) Font style. The selected font needs to support the specified style.
     pub style: Option<TextStyle>,
-    /// (**default=`"left"`**) Text align.
+    /// Text align. By default, each paragraph is aligned to its start, i.e. to the left for
+    /// left-to-right and to the right for right-to-left text.
     pub align: Option<HorizontalAlign>,
+    /// (**default=`"auto"`**) Base direction of the text. Right-to-left (e.g. Arabic, Hebrew) and
+    /// mixed-direction text is reordered and shaped according to the Unicode Bidirectional
+    /// Algorithm regardless of this value.
+    pub direction: Option<TextDirection>,
     /// (**default=`"none"`**) Text wrapping options.
     pub wrap: Option<TextWrapMode>,
     /// (**default=`"normal"`**) Font weight. The selected font needs to support the specified weight.
     pub weight: Option<TextWeight>,
+    /// (**default=`"none"`**) Adjusts the font size, so the text fits within `width` and `height`
+    /// (or `max_width` and `max_height` if they are not defined). Line height and font sizes of
+    /// `spans` are scaled proportionally. It's an error to provide `fit` together with `ticker`.
+    pub fit: Option<TextFit>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TextSpan {
+    /// Text of the span. It can contain newlines.
+    pub text: Arc<str>,
+    /// Font family of the span. Defaults to `font_family` of the component.
+    pub font_family: Option<Arc<str>>,
+    /// Font size of the span in pixels. Defaults to `font_size` of the component. Line height
+    /// is scaled proportionally to `line_height` of the component.
+    pub font_size: Option<f32>,
+    /// Font style of the span. Defaults to `style` of the component.
+    pub style: Option<TextStyle>,
+    /// Font weight of the span. Defaults to `weight` of the component.
+    pub weight: Option<TextWeight>,
+    /// Font color of the span in `#RRGGBBAA` format. Defaults to `color` of the component.
+    /// Stroke, shadow and gradient are applied to the whole text regardless of this value.
+    pub color: Option<RGBAColor>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LinearGradient {
+    /// (**default=`180.0`**) Direction of the gradient line in degrees, the same as in the CSS
+    /// `linear-gradient` function. `0` goes from the bottom to the top, `90` from the left to
+    /// the right.
+    pub angle: Option<f32>,
+    /// List of 2 to 8 color stops.
+    pub stops: Vec<GradientStop>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GradientStop {
+    /// Color in `#RRGGBBAA` format.
+    pub color: RGBAColor,
+    /// Position of the stop on the gradient line in the range from 0 to 1. Positions have
+    /// to be in ascending order. If not provided, the first stop is placed at 0, the last at 1,
+    /// and others are evenly spaced between their neighbors.
+    pub position: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TextStroke {
+    /// Width of the outline in pixels.
+    pub width: f32,
+    /// (**default=`"#000000FF"`**) Outline color in `#RRGGBBAA` format.
+    pub color: Option<RGBAColor>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TextShadow {
+    /// (**default=`0.0`**) Horizontal offset of the shadow in pixels.
+    pub offset_x: Option<f32>,
+    /// (**default=`0.0`**) Vertical offset of the shadow in pixels.
+    pub offset_y: Option<f32>,
+    /// (**default=`"#000000FF"`**) Shadow color in `#RRGGBBAA` format.
+    pub color: Option<RGBAColor>,
+    /// (**default=`0.0`**) Standard deviation in pixels of a Gaussian blur applied to the
+    /// shadow.
+    pub blur_radius: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TextTicker {
+    /// Scrolling speed in pixels per second.
+    pub speed: f32,
+    /// (**default=`"left"`**) Direction in which the text moves. The text enters the texture
+    /// from the opposite edge.
+    pub direction: Option<TickerDirection>,
+    /// (**default=`true`**) If `true`, the text enters again after it leaves the texture,
+    /// otherwise it stays hidden.
+    pub repeat: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TickerDirection {
+    Left,
+    Right,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
@@ -386,6 +761,28 @@ pub enum TextWrapMode {
     Word,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextFit {
+    /// Text is rendered with `font_size`.
+    None,
+    /// Font size is reduced if the text does not fit. Text is never larger than `font_size`.
+    Shrink,
+    /// Font size is reduced or increased to the largest size at which the text fits.
+    Fill,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextDirection {
+    /// Direction of each paragraph is detected from its first letter.
+    Auto,
+    /// Left-to-right base direction.
+    Ltr,
+    /// Right-to-left base direction.
+    Rtl,
+}
+
 /// Font weight, based on the [OpenType specification](https://learn.microsoft.com/en-gb/typography/opentype/spec/os2#usweightclass).
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -442,8 +839,17 @@ pub struct Tiles {
 
     /// (**default=`"#00000000"`**) Background color in a `"#RRGGBBAA"` format.
     pub background_color: Option<RGBAColor>,
+    /// (**default=`{ "type": "fit" }`**) Defines how tiles are arranged and sized.
+    pub fill_strategy: Option<TilesFillStrategy>,
     /// (**default=`"16:9"`**) Aspect ratio of a tile in `"W:H"` format, where W and H are integers.
+    /// Ignored by the `fill` strategy.
     pub tile_aspect_ratio: Option<AspectRatio>,
+    /// Maximal width of a tile in pixels (without padding). Tiles are smaller than the
+    /// available space if this limit is reached, e.g. when there are only a few children.
+    pub max_tile_width: Option<f32>,
+    /// Maximal height of a tile in pixels (without padding). Tiles are smaller than the
+    /// available space if this limit is reached, e.g. when there are only a few children.
+    pub max_tile_height: Option<f32>,
     /// (**default=`0`**) Margin of each tile in pixels.
     pub margin: Option<f32>,
     /// (**default=`0`**) Padding on each tile in pixels.
@@ -456,4 +862,102 @@ pub struct Tiles {
     /// Defines how this component will behave during a scene update. This will only have an
     /// effect if the previous scene already contained a `Tiles` component with the same id.
     pub transition: Option<Transition>,
+    /// Animations of tiles that are added, removed or moved during the `transition`. Ignored
+    /// if `transition` is not defined.
+    pub tile_transitions: Option<TileTransitions>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TileTransitions {
+    /// (**default=`{ "effect": "fade" }`**) Animation of a tile of a child added in the
+    /// scene update.
+    pub enter: Option<TileTransition>,
+    /// (**default=`{ "effect": "fade" }`**) Animation of a tile of a child removed in the
+    /// scene update.
+    pub exit: Option<TileTransition>,
+    /// (**default=`{ "effect": "slide" }`**) Animation of a tile of a child that changed its
+    /// position or size in the scene update.
+    pub reorder: Option<TileTransition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TileTransition {
+    /// Animation effect.
+    pub effect: TileTransitionEffect,
+    /// Duration of the animation in milliseconds. Defaults to the duration of the component
+    /// `transition`.
+    pub duration_ms: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TileTransitionEffect {
+    /// Tile is added, removed or moved immediately.
+    None,
+    /// Tile fades in or out. Moved tile fades out from the old position and then fades in
+    /// at the new one.
+    Fade,
+    /// Tile enters or exits through the bottom edge of the component. Moved tile slides to
+    /// the new position.
+    Slide,
+    /// Tile grows from or shrinks to its center. Moved tile shrinks at the old position and
+    /// then grows at the new one.
+    Scale,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TilesFillStrategy {
+    /// Number of rows and columns is selected to maximize the size of tiles. Tiles preserve
+    /// `tile_aspect_ratio`, so they might not cover the entire component.
+    Fit,
+    /// Number of rows and columns is selected the same way as for `fit`, but tiles are
+    /// stretched to cover the entire component.
+    Fill,
+    /// Tiles are arranged in a constant number of columns and preserve `tile_aspect_ratio`.
+    FixedColumns {
+        /// Number of columns.
+        columns: u32,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AudioVisualizer {
+    /// Id of a component.
+    pub id: Option<ComponentId>,
+    /// Id of an input. It identifies a stream registered using a [`RegisterInputStream`](../routes.md#register-input) request.
+    /// Visualization is based on the audio track of that input before volume is applied.
+    pub input_id: InputId,
+
+    /// Width of the visualization in pixels.
+    pub width: f32,
+    /// Height of the visualization in pixels.
+    pub height: f32,
+
+    /// (**default=`{ "type": "bars" }`**) Visualization type.
+    pub mode: Option<AudioVisualizerMode>,
+    /// (**default=`"#FFFFFFFF"`**) Color of the waveform or bars in `#RRGGBBAA` format.
+    pub color: Option<RGBAColor>,
+    /// (**default=`"#00000000"`**) Background color in `#RRGGBBAA` format.
+    pub background_color: Option<RGBAColor>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum AudioVisualizerMode {
+    /// Oscilloscope-like line showing the most recent audio samples.
+    Waveform {
+        /// (**default=`2.0`**) Width of the line in pixels.
+        line_width: Option<f32>,
+    },
+    /// Frequency spectrum split into bars on a logarithmic frequency scale.
+    Bars {
+        /// (**default=`32`**) Number of bars. Has to be in the range from 1 to 256.
+        count: Option<u32>,
+        /// (**default=`2.0`**) Gap between bars in pixels.
+        gap: Option<f32>,
+    },
 }
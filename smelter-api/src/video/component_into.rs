@@ -1,8 +1,7 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use smelter_render::MAX_NODE_RESOLUTION;
 use smelter_render::scene;
-use smelter_render::scene::BorderRadius;
 use smelter_render::scene::Position;
 
 use crate::*;
@@ -12,24 +11,44 @@ impl TryFrom<Component> for scene::Component {
 
     fn try_from(node: Component) -> Result<Self, Self::Error> {
         match node {
-            Component::InputStream(input) => Ok(Self::InputStream(input.into())),
+            Component::InputStream(input) => Ok(Self::InputStream(input.try_into()?)),
             Component::View(view) => Ok(Self::View(view.try_into()?)),
             Component::WebView(web) => Ok(Self::WebView(web.try_into()?)),
             Component::Shader(shader) => Ok(Self::Shader(shader.try_into()?)),
-            Component::Image(image) => Ok(Self::Image(image.into())),
+            Component::Image(image) => Ok(Self::Image(image.try_into()?)),
             Component::Text(text) => Ok(Self::Text(text.try_into()?)),
             Component::Tiles(tiles) => Ok(Self::Tiles(tiles.try_into()?)),
             Component::Rescaler(rescaler) => Ok(Self::Rescaler(rescaler.try_into()?)),
+            Component::AudioVisualizer(visualizer) => {
+                Ok(Self::AudioVisualizer(visualizer.try_into()?))
+            }
         }
     }
 }
 
-impl From<InputStream> for scene::InputStreamComponent {
-    fn from(input: InputStream) -> Self {
-        Self {
+impl TryFrom<InputStream> for scene::InputStreamComponent {
+    type Error = TypeError;
+
+    fn try_from(input: InputStream) -> Result<Self, Self::Error> {
+        let opacity = input.opacity.unwrap_or(1.0);
+        if !(0.0..=1.0).contains(&opacity) {
+            return Err(TypeError::new(
+                "Opacity has to be in the range from 0.0 to 1.0.",
+            ));
+        }
+
+        Ok(Self {
             id: input.id.map(Into::into),
             input_id: input.input_id.into(),
-        }
+            chroma_key: input.chroma_key.map(TryInto::try_into).transpose()?,
+            color_correction: input.color_correction.map(TryInto::try_into).transpose()?,
+            lut_id: input.lut_id.map(Into::into),
+            opacity,
+            crop: input.crop.map(TryInto::try_into).transpose()?,
+            ken_burns: input.ken_burns.map(TryInto::try_into).transpose()?,
+            flip_horizontal: input.flip_horizontal.unwrap_or(false),
+            flip_vertical: input.flip_vertical.unwrap_or(false),
+        })
     }
 }
 
@@ -42,11 +61,20 @@ impl TryFrom<View> for scene::ViewComponent {
         const HORIZONTAL_REQUIRED_MSG: &str =
             "Non-static \"View\" component requires either \"left\" or \"right\" coordinate.";
         const HORIZONTAL_ONLY_ONE_MSG: &str = "Fields \"left\" and \"right\" are mutually exclusive, you can only specify one on a \"View\" component.";
+        const BACKGROUND_ONLY_ONE_MSG: &str = "Fields \"background\" and \"background_color\" are mutually exclusive, you can only specify one on a \"View\" component.";
         let is_absolute_position = view.top.is_some()
             || view.bottom.is_some()
             || view.left.is_some()
             || view.right.is_some()
-            || view.rotation.is_some();
+            || view.rotation.is_some()
+            || view.rotation_x.is_some()
+            || view.rotation_y.is_some()
+            || view.perspective.is_some();
+        if let Some(perspective) = view.perspective
+            && perspective <= 0.0
+        {
+            return Err(TypeError::new("Perspective has to be a positive number."));
+        }
         let position = if is_absolute_position {
             let position_vertical = match (view.top, view.bottom) {
                 (Some(top), None) => scene::VerticalPosition::TopOffset(top),
@@ -66,6 +94,9 @@ impl TryFrom<View> for scene::ViewComponent {
                 position_horizontal,
                 position_vertical,
                 rotation_degrees: view.rotation.unwrap_or(0.0),
+                rotation_x_degrees: view.rotation_x.unwrap_or(0.0),
+                rotation_y_degrees: view.rotation_y.unwrap_or(0.0),
+                perspective: view.perspective,
             })
         } else {
             Position::Static {
@@ -110,6 +141,50 @@ impl TryFrom<View> for scene::ViewComponent {
         if padding.top < 0.0 || padding.right < 0.0 || padding.bottom < 0.0 || padding.left < 0.0 {
             return Err(TypeError::new("Padding values cannot be negative."));
         }
+        let blur_radius = view.blur_radius.unwrap_or(0.0);
+        if blur_radius < 0.0 {
+            return Err(TypeError::new("Blur radius cannot be negative."));
+        }
+        let opacity = view.opacity.unwrap_or(1.0);
+        if !(0.0..=1.0).contains(&opacity) {
+            return Err(TypeError::new(
+                "Opacity has to be in the range from 0.0 to 1.0.",
+            ));
+        }
+        let transparent = scene::RGBAColor(0, 0, 0, 0);
+        let (background_color, background_gradient) = match (view.background, view.background_color)
+        {
+            (Some(_), Some(_)) => return Err(TypeError::new(BACKGROUND_ONLY_ONE_MSG)),
+            (None, None) => (transparent, None),
+            (None, Some(color)) | (Some(Background::Color { color }), None) => {
+                (color.try_into()?, None)
+            }
+            (Some(Background::LinearGradient { angle, stops }), None) => {
+                let gradient = LinearGradient { angle, stops }.try_into()?;
+                (
+                    transparent,
+                    Some(scene::BackgroundGradient::Linear(gradient)),
+                )
+            }
+            (
+                Some(Background::RadialGradient {
+                    center_x,
+                    center_y,
+                    stops,
+                }),
+                None,
+            ) => {
+                let gradient = scene::RadialGradient {
+                    center_x: center_x.unwrap_or(0.5),
+                    center_y: center_y.unwrap_or(0.5),
+                    stops: gradient_stops(stops)?,
+                };
+                (
+                    transparent,
+                    Some(scene::BackgroundGradient::Radial(gradient)),
+                )
+            }
+        };
 
         Ok(Self {
             id: view.id.map(Into::into),
@@ -122,12 +197,14 @@ impl TryFrom<View> for scene::ViewComponent {
             direction,
             position,
             overflow,
-            background_color: view
-                .background_color
-                .map(TryInto::try_into)
-                .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 0)))?,
+            background_color,
+            background_gradient,
             transition: view.transition.map(TryInto::try_into).transpose()?,
-            border_radius: BorderRadius::new_with_radius(view.border_radius.unwrap_or(0.0)),
+            keyframes: keyframe_tracks(view.keyframes, &position)?,
+            border_radius: view
+                .border_radius
+                .map(Into::into)
+                .unwrap_or(scene::BorderRadius::ZERO),
             border_width: view.border_width.unwrap_or(0.0),
             border_color: view
                 .border_color
@@ -140,6 +217,11 @@ impl TryFrom<View> for scene::ViewComponent {
                 .map(TryInto::try_into)
                 .collect::<Result<_, _>>()?,
             padding,
+            blur_radius,
+            color_correction: view.color_correction.map(TryInto::try_into).transpose()?,
+            lut_id: view.lut_id.map(Into::into),
+            opacity,
+            blend_mode: view.blend_mode.map(Into::into).unwrap_or_default(),
         })
     }
 }
@@ -157,7 +239,15 @@ impl TryFrom<Rescaler> for scene::RescalerComponent {
             || rescaler.bottom.is_some()
             || rescaler.left.is_some()
             || rescaler.right.is_some()
-            || rescaler.rotation.is_some();
+            || rescaler.rotation.is_some()
+            || rescaler.rotation_x.is_some()
+            || rescaler.rotation_y.is_some()
+            || rescaler.perspective.is_some();
+        if let Some(perspective) = rescaler.perspective
+            && perspective <= 0.0
+        {
+            return Err(TypeError::new("Perspective has to be a positive number."));
+        }
         let position = if is_absolute_position {
             let position_vertical = match (rescaler.top, rescaler.bottom) {
                 (Some(top), None) => scene::VerticalPosition::TopOffset(top),
@@ -177,6 +267,9 @@ impl TryFrom<Rescaler> for scene::RescalerComponent {
                 position_horizontal,
                 position_vertical,
                 rotation_degrees: rescaler.rotation.unwrap_or(0.0),
+                rotation_x_degrees: rescaler.rotation_x.unwrap_or(0.0),
+                rotation_y_degrees: rescaler.rotation_y.unwrap_or(0.0),
+                perspective: rescaler.perspective,
             })
         } else {
             Position::Static {
@@ -184,6 +277,16 @@ impl TryFrom<Rescaler> for scene::RescalerComponent {
                 height: rescaler.height,
             }
         };
+        let blur_radius = rescaler.blur_radius.unwrap_or(0.0);
+        if blur_radius < 0.0 {
+            return Err(TypeError::new("Blur radius cannot be negative."));
+        }
+        let opacity = rescaler.opacity.unwrap_or(1.0);
+        if !(0.0..=1.0).contains(&opacity) {
+            return Err(TypeError::new(
+                "Opacity has to be in the range from 0.0 to 1.0.",
+            ));
+        }
         let mode = match rescaler.mode {
             Some(RescaleMode::Fit) => scene::RescaleMode::Fit,
             Some(RescaleMode::Fill) => scene::RescaleMode::Fill,
@@ -203,7 +306,11 @@ impl TryFrom<Rescaler> for scene::RescalerComponent {
                 .unwrap_or(VerticalAlign::Center)
                 .into(),
             transition: rescaler.transition.map(TryInto::try_into).transpose()?,
-            border_radius: BorderRadius::new_with_radius(rescaler.border_radius.unwrap_or(0.0)),
+            keyframes: keyframe_tracks(rescaler.keyframes, &position)?,
+            border_radius: rescaler
+                .border_radius
+                .map(Into::into)
+                .unwrap_or(scene::BorderRadius::ZERO),
             border_width: rescaler.border_width.unwrap_or(0.0),
             border_color: rescaler
                 .border_color
@@ -215,10 +322,70 @@ impl TryFrom<Rescaler> for scene::RescalerComponent {
                 .into_iter()
                 .map(TryInto::try_into)
                 .collect::<Result<_, _>>()?,
+            chroma_key: rescaler.chroma_key.map(TryInto::try_into).transpose()?,
+            blur_radius,
+            opacity,
+            blend_mode: rescaler.blend_mode.map(Into::into).unwrap_or_default(),
+            crop: rescaler.crop.map(TryInto::try_into).transpose()?,
+            flip_horizontal: rescaler.flip_horizontal.unwrap_or(false),
+            flip_vertical: rescaler.flip_vertical.unwrap_or(false),
         })
     }
 }
 
+fn keyframe_tracks(
+    tracks: Option<Vec<KeyframeTrack>>,
+    position: &Position,
+) -> Result<Vec<scene::KeyframeTrack>, TypeError> {
+    let tracks = tracks
+        .unwrap_or_default()
+        .into_iter()
+        .map(scene::KeyframeTrack::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    for (index, track) in tracks.iter().enumerate() {
+        if tracks[..index].iter().any(|t| t.property == track.property) {
+            return Err(TypeError::new(
+                "Each property can be animated by only one keyframe track.",
+            ));
+        }
+        let is_defined = match (track.property, position) {
+            (scene::AnimatedProperty::Top, Position::Absolute(position)) => matches!(
+                position.position_vertical,
+                scene::VerticalPosition::TopOffset(_)
+            ),
+            (scene::AnimatedProperty::Bottom, Position::Absolute(position)) => matches!(
+                position.position_vertical,
+                scene::VerticalPosition::BottomOffset(_)
+            ),
+            (scene::AnimatedProperty::Left, Position::Absolute(position)) => matches!(
+                position.position_horizontal,
+                scene::HorizontalPosition::LeftOffset(_)
+            ),
+            (scene::AnimatedProperty::Right, Position::Absolute(position)) => matches!(
+                position.position_horizontal,
+                scene::HorizontalPosition::RightOffset(_)
+            ),
+            (
+                scene::AnimatedProperty::Top
+                | scene::AnimatedProperty::Bottom
+                | scene::AnimatedProperty::Left
+                | scene::AnimatedProperty::Right
+                | scene::AnimatedProperty::Rotation
+                | scene::AnimatedProperty::RotationX
+                | scene::AnimatedProperty::RotationY,
+                Position::Static { .. },
+            ) => false,
+            _ => true,
+        };
+        if !is_defined {
+            return Err(TypeError::new(
+                "Keyframe tracks for \"top\", \"bottom\", \"left\" and \"right\" require the same property to be defined on the component. Keyframe tracks for \"rotation\", \"rotation_x\" and \"rotation_y\" require absolute positioning.",
+            ));
+        }
+    }
+    Ok(tracks)
+}
+
 impl TryFrom<Shader> for scene::ShaderComponent {
     type Error = TypeError;
 
@@ -269,14 +436,43 @@ impl From<ShaderParam> for scene::ShaderParam {
     }
 }
 
-impl From<Image> for scene::ImageComponent {
-    fn from(image: Image) -> Self {
-        Self {
+impl TryFrom<Image> for scene::ImageComponent {
+    type Error = TypeError;
+
+    fn try_from(image: Image) -> Result<Self, Self::Error> {
+        Ok(Self {
             id: image.id.map(Into::into),
             image_id: image.image_id.into(),
             width: image.width,
             height: image.height,
+            nine_slice: image.nine_slice.map(TryInto::try_into).transpose()?,
+            ken_burns: image.ken_burns.map(TryInto::try_into).transpose()?,
+            flip_horizontal: image.flip_horizontal.unwrap_or(false),
+            flip_vertical: image.flip_vertical.unwrap_or(false),
+        })
+    }
+}
+
+impl TryFrom<NineSlice> for scene::NineSlice {
+    type Error = TypeError;
+
+    fn try_from(nine_slice: NineSlice) -> Result<Self, Self::Error> {
+        let nine_slice = Self {
+            top: nine_slice.top.unwrap_or(0.0),
+            right: nine_slice.right.unwrap_or(0.0),
+            bottom: nine_slice.bottom.unwrap_or(0.0),
+            left: nine_slice.left.unwrap_or(0.0),
+        };
+        let insets = [
+            nine_slice.top,
+            nine_slice.right,
+            nine_slice.bottom,
+            nine_slice.left,
+        ];
+        if insets.iter().any(|inset| *inset < 0.0) {
+            return Err(TypeError::new("Nine-slice insets cannot be negative."));
         }
+        Ok(nine_slice)
     }
 }
 
@@ -284,29 +480,30 @@ impl TryFrom<Text> for scene::TextComponent {
     type Error = TypeError;
 
     fn try_from(text: Text) -> Result<Self, Self::Error> {
-        let style = match text.style {
-            Some(TextStyle::Normal) => scene::TextStyle::Normal,
-            Some(TextStyle::Italic) => scene::TextStyle::Italic,
-            Some(TextStyle::Oblique) => scene::TextStyle::Oblique,
-            None => scene::TextStyle::Normal,
-        };
+        let style = text
+            .style
+            .map(Into::into)
+            .unwrap_or(scene::TextStyle::Normal);
         let wrap = match text.wrap {
             Some(TextWrapMode::None) => scene::TextWrap::None,
             Some(TextWrapMode::Word) => scene::TextWrap::Word,
             Some(TextWrapMode::Glyph) => scene::TextWrap::Glyph,
             None => scene::TextWrap::None,
         };
-        let weight = match text.weight {
-            Some(TextWeight::Thin) => scene::TextWeight::Thin,
-            Some(TextWeight::ExtraLight) => scene::TextWeight::ExtraLight,
-            Some(TextWeight::Light) => scene::TextWeight::Light,
-            Some(TextWeight::Normal) => scene::TextWeight::Normal,
-            Some(TextWeight::Medium) => scene::TextWeight::Medium,
-            Some(TextWeight::SemiBold) => scene::TextWeight::SemiBold,
-            Some(TextWeight::Bold) => scene::TextWeight::Bold,
-            Some(TextWeight::ExtraBold) => scene::TextWeight::ExtraBold,
-            Some(TextWeight::Black) => scene::TextWeight::Black,
-            None => scene::TextWeight::Normal,
+        let direction = match text.direction {
+            Some(TextDirection::Auto) => scene::TextDirection::Auto,
+            Some(TextDirection::Ltr) => scene::TextDirection::LeftToRight,
+            Some(TextDirection::Rtl) => scene::TextDirection::RightToLeft,
+            None => scene::TextDirection::Auto,
+        };
+        let weight = text
+            .weight
+            .map(Into::into)
+            .unwrap_or(scene::TextWeight::Normal);
+        let fit = match text.fit {
+            Some(TextFit::None) | None => scene::TextFit::None,
+            Some(TextFit::Shrink) => scene::TextFit::Shrink,
+            Some(TextFit::Fill) => scene::TextFit::Fill,
         };
         let dimensions = match (text.width, text.height, text.max_width, text.max_height) {
             (Some(width), Some(height), _, _) => scene::TextDimensions::Fixed { width, height },
@@ -325,6 +522,17 @@ impl TryFrom<Text> for scene::TextComponent {
             },
         };
 
+        if text.ticker.is_some() && text.width.is_none() {
+            return Err(TypeError::new(
+                "\"ticker\" property on a Text component can only be provided if \"width\" is also defined.",
+            ));
+        }
+        if text.ticker.is_some() && fit != scene::TextFit::None {
+            return Err(TypeError::new(
+                "\"fit\" and \"ticker\" properties on a Text component cannot be provided at the same time.",
+            ));
+        }
+
         if text.font_size <= 0.0 {
             return Err(TypeError::new(
                 "\"font_size\" property has to be larger than 0",
@@ -338,9 +546,31 @@ impl TryFrom<Text> for scene::TextComponent {
             ));
         }
 
+        let spans = text
+            .spans
+            .unwrap_or_default()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<scene::TextSpan>, _>>()?;
+        let content = match (text.text, spans.is_empty()) {
+            (Some(content), true) => content,
+            (None, false) => spans.iter().map(|span| span.text.as_ref()).collect(),
+            (Some(_), false) => {
+                return Err(TypeError::new(
+                    "\"text\" and \"spans\" properties on a Text component cannot be provided at the same time.",
+                ));
+            }
+            (None, true) => {
+                return Err(TypeError::new(
+                    "Text component requires either \"text\" or non-empty \"spans\" property.",
+                ));
+            }
+        };
+
         let text = Self {
             id: text.id.map(Into::into),
-            text: text.text,
+            text: content,
+            spans,
             font_size: text.font_size,
             dimensions,
             line_height,
@@ -348,15 +578,21 @@ impl TryFrom<Text> for scene::TextComponent {
                 .color
                 .map(TryInto::try_into)
                 .unwrap_or(Ok(scene::RGBAColor(255, 255, 255, 255)))?,
+            gradient: text.gradient.map(TryInto::try_into).transpose()?,
             font_family: text.font_family.unwrap_or_else(|| Arc::from("Verdana")),
             style,
-            align: text.align.unwrap_or(HorizontalAlign::Left).into(),
+            align: text.align.map(Into::into),
+            direction,
             wrap,
             weight,
+            fit,
             background_color: text
                 .background_color
                 .map(TryInto::try_into)
                 .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 0)))?,
+            stroke: text.stroke.map(TryInto::try_into).transpose()?,
+            shadow: text.shadow.map(TryInto::try_into).transpose()?,
+            ticker: text.ticker.map(TryInto::try_into).transpose()?,
         };
         Ok(text)
     }
@@ -383,6 +619,12 @@ impl TryFrom<Tiles> for scene::TilesComponent {
     type Error = TypeError;
 
     fn try_from(tiles: Tiles) -> Result<Self, Self::Error> {
+        let max_tile_size = [tiles.max_tile_width, tiles.max_tile_height];
+        if max_tile_size.iter().flatten().any(|size| *size <= 0.0) {
+            return Err(TypeError::new(
+                "Maximal tile width and height have to be positive numbers.",
+            ));
+        }
         let result = Self {
             id: tiles.id.map(Into::into),
             children: tiles
@@ -398,10 +640,24 @@ impl TryFrom<Tiles> for scene::TilesComponent {
                 .background_color
                 .map(TryInto::try_into)
                 .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 0)))?,
+            fill_strategy: match tiles.fill_strategy {
+                Some(TilesFillStrategy::Fit) | None => scene::TilesFillStrategy::Fit,
+                Some(TilesFillStrategy::Fill) => scene::TilesFillStrategy::Fill,
+                Some(TilesFillStrategy::FixedColumns { columns }) => {
+                    if columns == 0 {
+                        return Err(TypeError::new(
+                            "Number of columns in the \"fixed_columns\" fill strategy has to be larger than 0.",
+                        ));
+                    }
+                    scene::TilesFillStrategy::FixedColumns(columns)
+                }
+            },
             tile_aspect_ratio: tiles
                 .tile_aspect_ratio
                 .map(TryInto::try_into)
                 .unwrap_or(Ok((16, 9)))?,
+            max_tile_width: tiles.max_tile_width,
+            max_tile_height: tiles.max_tile_height,
             margin: tiles.margin.unwrap_or(0.0),
             padding: tiles.padding.unwrap_or(0.0),
             horizontal_align: tiles
@@ -410,11 +666,86 @@ impl TryFrom<Tiles> for scene::TilesComponent {
                 .into(),
             vertical_align: tiles.vertical_align.unwrap_or(VerticalAlign::Center).into(),
             transition: tiles.transition.map(TryInto::try_into).transpose()?,
+            tile_transitions: tiles
+                .tile_transitions
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
         };
         Ok(result)
     }
 }
 
+impl TryFrom<TileTransitions> for scene::TileTransitions {
+    type Error = TypeError;
+
+    fn try_from(transitions: TileTransitions) -> Result<Self, Self::Error> {
+        let default = Self::default();
+        Ok(Self {
+            enter: transitions
+                .enter
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or(default.enter),
+            exit: transitions
+                .exit
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or(default.exit),
+            reorder: transitions
+                .reorder
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or(default.reorder),
+        })
+    }
+}
+
+impl TryFrom<TileTransition> for scene::TileTransition {
+    type Error = TypeError;
+
+    fn try_from(transition: TileTransition) -> Result<Self, Self::Error> {
+        let duration = transition
+            .duration_ms
+            .map(|duration_ms| Duration::try_from_secs_f64(duration_ms / 1000.0))
+            .transpose()
+            .map_err(|err| TypeError::new(format!("Invalid duration. {err}")))?;
+        let effect = match transition.effect {
+            TileTransitionEffect::None => scene::TileTransitionEffect::None,
+            TileTransitionEffect::Fade => scene::TileTransitionEffect::Fade,
+            TileTransitionEffect::Slide => scene::TileTransitionEffect::Slide,
+            TileTransitionEffect::Scale => scene::TileTransitionEffect::Scale,
+        };
+        Ok(Self { effect, duration })
+    }
+}
+
+impl From<BorderRadius> for scene::BorderRadius {
+    fn from(value: BorderRadius) -> Self {
+        match value {
+            BorderRadius::Uniform(radius) => Self::new_with_radius(radius),
+            BorderRadius::Corners(corners) => Self {
+                top_left: corners.top_left.unwrap_or(0.0),
+                top_right: corners.top_right.unwrap_or(0.0),
+                bottom_right: corners.bottom_right.unwrap_or(0.0),
+                bottom_left: corners.bottom_left.unwrap_or(0.0),
+            },
+        }
+    }
+}
+
+impl From<BlendMode> for scene::BlendMode {
+    fn from(value: BlendMode) -> Self {
+        match value {
+            BlendMode::Normal => Self::Normal,
+            BlendMode::Multiply => Self::Multiply,
+            BlendMode::Screen => Self::Screen,
+            BlendMode::Overlay => Self::Overlay,
+            BlendMode::Add => Self::Add,
+        }
+    }
+}
+
 impl TryFrom<BoxShadow> for scene::BoxShadow {
     type Error = TypeError;
 
@@ -423,6 +754,7 @@ impl TryFrom<BoxShadow> for scene::BoxShadow {
             offset_x: value.offset_x.unwrap_or(0.0),
             offset_y: value.offset_y.unwrap_or(0.0),
             blur_radius: value.blur_radius.unwrap_or(0.0),
+            spread: value.spread.unwrap_or(0.0),
             color: value
                 .color
                 .map(TryInto::try_into)
@@ -430,3 +762,325 @@ impl TryFrom<BoxShadow> for scene::BoxShadow {
         })
     }
 }
+
+impl TryFrom<LinearGradient> for scene::LinearGradient {
+    type Error = TypeError;
+
+    fn try_from(value: LinearGradient) -> Result<Self, Self::Error> {
+        Ok(Self {
+            angle_degrees: value.angle.unwrap_or(180.0),
+            stops: gradient_stops(value.stops)?,
+        })
+    }
+}
+
+fn gradient_stops(stops: Vec<GradientStop>) -> Result<Vec<scene::GradientStop>, TypeError> {
+    let count = stops.len();
+    if !(2..=scene::MAX_GRADIENT_STOPS).contains(&count) {
+        return Err(TypeError::new(format!(
+            "Gradient has to have from 2 to {} stops.",
+            scene::MAX_GRADIENT_STOPS
+        )));
+    }
+
+    let mut positions: Vec<Option<f32>> = stops.iter().map(|stop| stop.position).collect();
+    positions[0] = Some(positions[0].unwrap_or(0.0));
+    positions[count - 1] = Some(positions[count - 1].unwrap_or(1.0));
+    // Stops without a position are evenly spaced between the closest defined ones.
+    let mut previous = 0;
+    for index in 1..count {
+        let (Some(start), Some(end)) = (positions[previous], positions[index]) else {
+            continue;
+        };
+        let steps = (index - previous) as f32;
+        for (step, position) in positions[previous + 1..index].iter_mut().enumerate() {
+            *position = Some(start + (end - start) * (step + 1) as f32 / steps);
+        }
+        previous = index;
+    }
+
+    let stops = stops
+        .into_iter()
+        .zip(positions)
+        .map(|(stop, position)| {
+            Ok(scene::GradientStop {
+                position: position.unwrap_or(0.0),
+                color: stop.color.try_into()?,
+            })
+        })
+        .collect::<Result<Vec<_>, TypeError>>()?;
+    if stops
+        .iter()
+        .any(|stop| !(0.0..=1.0).contains(&stop.position))
+    {
+        return Err(TypeError::new(
+            "Gradient stop positions have to be in the range from 0.0 to 1.0.",
+        ));
+    }
+    if stops
+        .windows(2)
+        .any(|stops| stops[0].position > stops[1].position)
+    {
+        return Err(TypeError::new(
+            "Gradient stop positions have to be in ascending order.",
+        ));
+    }
+
+    Ok(stops)
+}
+
+impl TryFrom<TextTicker> for scene::TextTicker {
+    type Error = TypeError;
+
+    fn try_from(value: TextTicker) -> Result<Self, Self::Error> {
+        if value.speed <= 0.0 {
+            return Err(TypeError::new("Ticker speed has to be a positive number."));
+        }
+        let direction = match value.direction {
+            Some(TickerDirection::Left) | None => scene::TickerDirection::Left,
+            Some(TickerDirection::Right) => scene::TickerDirection::Right,
+        };
+        Ok(Self {
+            speed: value.speed,
+            direction,
+            repeat: value.repeat.unwrap_or(true),
+        })
+    }
+}
+
+impl TryFrom<TextSpan> for scene::TextSpan {
+    type Error = TypeError;
+
+    fn try_from(span: TextSpan) -> Result<Self, Self::Error> {
+        if span.font_size.is_some_and(|font_size| font_size <= 0.0) {
+            return Err(TypeError::new(
+                "\"font_size\" property of a text span has to be larger than 0",
+            ));
+        }
+        Ok(Self {
+            text: span.text,
+            font_family: span.font_family,
+            font_size: span.font_size,
+            style: span.style.map(Into::into),
+            weight: span.weight.map(Into::into),
+            color: span.color.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<TextStyle> for scene::TextStyle {
+    fn from(style: TextStyle) -> Self {
+        match style {
+            TextStyle::Normal => scene::TextStyle::Normal,
+            TextStyle::Italic => scene::TextStyle::Italic,
+            TextStyle::Oblique => scene::TextStyle::Oblique,
+        }
+    }
+}
+
+impl From<TextWeight> for scene::TextWeight {
+    fn from(weight: TextWeight) -> Self {
+        match weight {
+            TextWeight::Thin => scene::TextWeight::Thin,
+            TextWeight::ExtraLight => scene::TextWeight::ExtraLight,
+            TextWeight::Light => scene::TextWeight::Light,
+            TextWeight::Normal => scene::TextWeight::Normal,
+            TextWeight::Medium => scene::TextWeight::Medium,
+            TextWeight::SemiBold => scene::TextWeight::SemiBold,
+            TextWeight::Bold => scene::TextWeight::Bold,
+            TextWeight::ExtraBold => scene::TextWeight::ExtraBold,
+            TextWeight::Black => scene::TextWeight::Black,
+        }
+    }
+}
+
+impl TryFrom<TextStroke> for scene::TextStroke {
+    type Error = TypeError;
+
+    fn try_from(value: TextStroke) -> Result<Self, Self::Error> {
+        if value.width <= 0.0 {
+            return Err(TypeError::new(
+                "Text stroke width has to be a positive number.",
+            ));
+        }
+        Ok(Self {
+            width: value.width,
+            color: value
+                .color
+                .map(TryInto::try_into)
+                .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 255)))?,
+        })
+    }
+}
+
+impl TryFrom<TextShadow> for scene::TextShadow {
+    type Error = TypeError;
+
+    fn try_from(value: TextShadow) -> Result<Self, Self::Error> {
+        let blur_radius = value.blur_radius.unwrap_or(0.0);
+        if blur_radius < 0.0 {
+            return Err(TypeError::new(
+                "Text shadow blur radius cannot be negative.",
+            ));
+        }
+        Ok(Self {
+            offset_x: value.offset_x.unwrap_or(0.0),
+            offset_y: value.offset_y.unwrap_or(0.0),
+            blur_radius,
+            color: value
+                .color
+                .map(TryInto::try_into)
+                .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 255)))?,
+        })
+    }
+}
+
+impl TryFrom<ChromaKey> for scene::ChromaKey {
+    type Error = TypeError;
+
+    fn try_from(value: ChromaKey) -> Result<Self, Self::Error> {
+        let scene::RGBAColor(r, g, b, _) = value
+            .key_color
+            .map(TryInto::try_into)
+            .unwrap_or(Ok(scene::RGBAColor(0, 255, 0, 255)))?;
+        let chroma_key = Self {
+            key_color: scene::RGBColor(r, g, b),
+            similarity: value.similarity.unwrap_or(0.4),
+            smoothness: value.smoothness.unwrap_or(0.08),
+            spill_reduction: value.spill_reduction.unwrap_or(0.1),
+        };
+
+        if chroma_key.similarity < 0.0
+            || chroma_key.smoothness < 0.0
+            || chroma_key.spill_reduction < 0.0
+        {
+            return Err(TypeError::new("Chroma key values cannot be negative."));
+        }
+        Ok(chroma_key)
+    }
+}
+
+impl TryFrom<ColorCorrection> for scene::ColorCorrection {
+    type Error = TypeError;
+
+    fn try_from(value: ColorCorrection) -> Result<Self, Self::Error> {
+        let color_correction = Self {
+            brightness: value.brightness.unwrap_or(0.0),
+            contrast: value.contrast.unwrap_or(1.0),
+            saturation: value.saturation.unwrap_or(1.0),
+            hue_shift_degrees: value.hue_shift.unwrap_or(0.0),
+        };
+
+        if !(-1.0..=1.0).contains(&color_correction.brightness) {
+            return Err(TypeError::new(
+                "Color correction brightness has to be in the range from -1 to 1.",
+            ));
+        }
+        if color_correction.contrast < 0.0 || color_correction.saturation < 0.0 {
+            return Err(TypeError::new(
+                "Color correction contrast and saturation cannot be negative.",
+            ));
+        }
+        Ok(color_correction)
+    }
+}
+
+impl TryFrom<SourceCrop> for scene::SourceCrop {
+    type Error = TypeError;
+
+    fn try_from(crop: SourceCrop) -> Result<Self, Self::Error> {
+        match crop {
+            SourceCrop::Pixels {
+                top,
+                left,
+                width,
+                height,
+            } => {
+                if top < 0.0 || left < 0.0 || width <= 0.0 || height <= 0.0 {
+                    return Err(TypeError::new(
+                        "Crop \"top\" and \"left\" cannot be negative and \"width\" and \"height\" have to be positive numbers.",
+                    ));
+                }
+                Ok(Self::Pixels {
+                    top,
+                    left,
+                    width,
+                    height,
+                })
+            }
+            SourceCrop::Normalized {
+                top,
+                left,
+                width,
+                height,
+            } => Ok(Self::Normalized(
+                NormalizedRect {
+                    top,
+                    left,
+                    width,
+                    height,
+                }
+                .try_into()?,
+            )),
+        }
+    }
+}
+
+impl TryFrom<AudioVisualizer> for scene::AudioVisualizerComponent {
+    type Error = TypeError;
+
+    fn try_from(visualizer: AudioVisualizer) -> Result<Self, Self::Error> {
+        if visualizer.width <= 0.0 || visualizer.height <= 0.0 {
+            return Err(TypeError::new(
+                "AudioVisualizer width and height have to be positive numbers.",
+            ));
+        }
+        let mode = match visualizer.mode {
+            Some(AudioVisualizerMode::Waveform { line_width }) => {
+                let line_width = line_width.unwrap_or(2.0);
+                if line_width <= 0.0 {
+                    return Err(TypeError::new(
+                        "AudioVisualizer line width has to be a positive number.",
+                    ));
+                }
+                scene::AudioVisualizerMode::Waveform { line_width }
+            }
+            Some(AudioVisualizerMode::Bars { count, gap }) => {
+                let count = count.unwrap_or(32);
+                let gap = gap.unwrap_or(2.0);
+                if !(1..=scene::MAX_AUDIO_VISUALIZER_BARS).contains(&count) {
+                    return Err(TypeError::new(format!(
+                        "AudioVisualizer bars count has to be in the range from 1 to {}.",
+                        scene::MAX_AUDIO_VISUALIZER_BARS
+                    )));
+                }
+                if gap < 0.0 {
+                    return Err(TypeError::new(
+                        "AudioVisualizer bars gap cannot be negative.",
+                    ));
+                }
+                scene::AudioVisualizerMode::Bars { count, gap }
+            }
+            None => scene::AudioVisualizerMode::Bars {
+                count: 32,
+                gap: 2.0,
+            },
+        };
+
+        Ok(Self {
+            id: visualizer.id.map(Into::into),
+            input_id: visualizer.input_id.into(),
+            mode,
+            width: visualizer.width,
+            height: visualizer.height,
+            color: visualizer
+                .color
+                .map(TryInto::try_into)
+                .unwrap_or(Ok(scene::RGBAColor(255, 255, 255, 255)))?,
+            background_color: visualizer
+                .background_color
+                .map(TryInto::try_into)
+                .unwrap_or(Ok(scene::RGBAColor(0, 0, 0, 0)))?,
+        })
+    }
+}
@@ -31,14 +31,80 @@ pub enum EasingFunction {
     CubicBezier { points: [f64; 4] },
 }
 
-impl TryFrom<Transition> for scene::Transition {
+/// Animation of a single property of a component. The animation starts when the track is
+/// added to the scene and continues across scene updates as long as the track does not change
+/// and the component keeps the same `id`.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+pub struct KeyframeTrack {
+    /// Animated property. `top`, `bottom`, `left` and `right` can be animated only if they are
+    /// defined on the component, and rotations only if the component is absolutely positioned.
+    pub property: AnimatedProperty,
+    /// List of keyframes sorted by `time_ms`. At least one keyframe is required.
+    pub keyframes: Vec<Keyframe>,
+    /// (**default=`false`**) Restart the animation after the last keyframe.
+    pub repeat: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+pub struct Keyframe {
+    /// Time in milliseconds since the start of the animation.
+    pub time_ms: f64,
+    /// Value of the property at this keyframe.
+    pub value: f32,
+    /// (**default=`"linear"`**) Easing function used to interpolate between this keyframe
+    /// and the next one.
+    pub easing_function: Option<EasingFunction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimatedProperty {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Width,
+    Height,
+    Rotation,
+    RotationX,
+    RotationY,
+    Opacity,
+    BlurRadius,
+}
+
+/// Pan and zoom animation (Ken Burns effect). The visible part of the component is moved
+/// and resized from the `start` to the `end` rectangle and stretched to the size of the
+/// component. The animation starts when it is added to the scene and continues across scene
+/// updates as long as it does not change and the component keeps the same `id`.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+pub struct KenBurns {
+    /// Part of the component visible at the start of the animation.
+    pub start: NormalizedRect,
+    /// Part of the component visible at the end of the animation and after it finishes.
+    pub end: NormalizedRect,
+    /// Duration of the animation in milliseconds.
+    pub duration_ms: f64,
+    /// (**default=`"linear"`**) Easing function to be used for the animation.
+    pub easing_function: Option<EasingFunction>,
+}
+
+/// Rectangle in coordinates relative to the size of a component, where `0.0` is the top/left
+/// edge and `1.0` is the bottom/right edge of the component.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema, PartialEq)]
+pub struct NormalizedRect {
+    pub top: f32,
+    pub left: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl TryFrom<EasingFunction> for scene::InterpolationKind {
     type Error = TypeError;
 
-    fn try_from(transition: Transition) -> Result<Self, Self::Error> {
-        let interpolation_kind = match transition.easing_function.unwrap_or(EasingFunction::Linear)
-        {
-            EasingFunction::Linear => scene::InterpolationKind::Linear,
-            EasingFunction::Bounce => scene::InterpolationKind::Bounce,
+    fn try_from(easing_function: EasingFunction) -> Result<Self, Self::Error> {
+        match easing_function {
+            EasingFunction::Linear => Ok(Self::Linear),
+            EasingFunction::Bounce => Ok(Self::Bounce),
             EasingFunction::CubicBezier { points } => {
                 if points[0] < 0.0 || points[0] > 1.0 {
                     return Err(TypeError::new(
@@ -51,14 +117,25 @@ impl TryFrom<Transition> for scene::Transition {
                     ));
                 }
 
-                scene::InterpolationKind::CubicBezier {
+                Ok(Self::CubicBezier {
                     x1: points[0],
                     y1: points[1],
                     x2: points[2],
                     y2: points[3],
-                }
+                })
             }
-        };
+        }
+    }
+}
+
+impl TryFrom<Transition> for scene::Transition {
+    type Error = TypeError;
+
+    fn try_from(transition: Transition) -> Result<Self, Self::Error> {
+        let interpolation_kind = transition
+            .easing_function
+            .unwrap_or(EasingFunction::Linear)
+            .try_into()?;
 
         let duration = Duration::try_from_secs_f64(transition.duration_ms / 1000.0)
             .map_err(|err| TypeError::new(format!("Invalid duration. {err}")))?;
@@ -70,3 +147,121 @@ impl TryFrom<Transition> for scene::Transition {
         })
     }
 }
+
+impl TryFrom<KeyframeTrack> for scene::KeyframeTrack {
+    type Error = TypeError;
+
+    fn try_from(track: KeyframeTrack) -> Result<Self, Self::Error> {
+        let property: scene::AnimatedProperty = track.property.into();
+        if track.keyframes.is_empty() {
+            return Err(TypeError::new(
+                "Keyframe track requires at least one keyframe.",
+            ));
+        }
+        let keyframes = track
+            .keyframes
+            .into_iter()
+            .map(|keyframe| {
+                let time = Duration::try_from_secs_f64(keyframe.time_ms / 1000.0)
+                    .map_err(|err| TypeError::new(format!("Invalid keyframe time. {err}")))?;
+                match property {
+                    scene::AnimatedProperty::Opacity if !(0.0..=1.0).contains(&keyframe.value) => {
+                        return Err(TypeError::new(
+                            "Keyframe values of \"opacity\" have to be in the range from 0.0 to 1.0.",
+                        ));
+                    }
+                    scene::AnimatedProperty::Width
+                    | scene::AnimatedProperty::Height
+                    | scene::AnimatedProperty::BlurRadius
+                        if keyframe.value < 0.0 =>
+                    {
+                        return Err(TypeError::new(
+                            "Keyframe values of \"width\", \"height\" and \"blur_radius\" cannot be negative.",
+                        ));
+                    }
+                    _ => (),
+                }
+                Ok(scene::Keyframe {
+                    time,
+                    value: keyframe.value,
+                    interpolation_kind: keyframe
+                        .easing_function
+                        .unwrap_or(EasingFunction::Linear)
+                        .try_into()?,
+                })
+            })
+            .collect::<Result<Vec<_>, TypeError>>()?;
+        if !keyframes.is_sorted_by_key(|keyframe| keyframe.time) {
+            return Err(TypeError::new(
+                "Keyframes have to be sorted by \"time_ms\".",
+            ));
+        }
+
+        Ok(Self {
+            property,
+            keyframes,
+            repeat: track.repeat.unwrap_or(false),
+        })
+    }
+}
+
+impl From<AnimatedProperty> for scene::AnimatedProperty {
+    fn from(property: AnimatedProperty) -> Self {
+        match property {
+            AnimatedProperty::Top => Self::Top,
+            AnimatedProperty::Bottom => Self::Bottom,
+            AnimatedProperty::Left => Self::Left,
+            AnimatedProperty::Right => Self::Right,
+            AnimatedProperty::Width => Self::Width,
+            AnimatedProperty::Height => Self::Height,
+            AnimatedProperty::Rotation => Self::Rotation,
+            AnimatedProperty::RotationX => Self::RotationX,
+            AnimatedProperty::RotationY => Self::RotationY,
+            AnimatedProperty::Opacity => Self::Opacity,
+            AnimatedProperty::BlurRadius => Self::BlurRadius,
+        }
+    }
+}
+
+impl TryFrom<KenBurns> for scene::KenBurns {
+    type Error = TypeError;
+
+    fn try_from(ken_burns: KenBurns) -> Result<Self, Self::Error> {
+        let duration = Duration::try_from_secs_f64(ken_burns.duration_ms / 1000.0)
+            .map_err(|err| TypeError::new(format!("Invalid duration. {err}")))?;
+
+        Ok(Self {
+            start: ken_burns.start.try_into()?,
+            end: ken_burns.end.try_into()?,
+            duration,
+            interpolation_kind: ken_burns
+                .easing_function
+                .unwrap_or(EasingFunction::Linear)
+                .try_into()?,
+        })
+    }
+}
+
+impl TryFrom<NormalizedRect> for scene::NormalizedRect {
+    type Error = TypeError;
+
+    fn try_from(rect: NormalizedRect) -> Result<Self, Self::Error> {
+        let is_valid = rect.top >= 0.0
+            && rect.left >= 0.0
+            && rect.width > 0.0
+            && rect.height > 0.0
+            && rect.top + rect.height <= 1.0
+            && rect.left + rect.width <= 1.0;
+        if !is_valid {
+            return Err(TypeError::new(
+                "Normalized rectangle has to have a positive size and fit in the range from 0.0 to 1.0.",
+            ));
+        }
+        Ok(Self {
+            top: rect.top,
+            left: rect.left,
+            width: rect.width,
+            height: rect.height,
+        })
+    }
+}
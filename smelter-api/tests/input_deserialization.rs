@@ -7,9 +7,10 @@ use smelter_api::*;
 use smelter_core::QueueInputOptions;
 use smelter_core::codecs::VideoDecoderOptions;
 use smelter_core::protocols::{
-    HlsInputOptions, HlsInputVideoDecoders, Mp4InputOptions, Mp4InputSource, Mp4InputVideoDecoders,
-    PortOrRange, RtmpServerInputDecoders, RtmpServerInputOptions, RtpAudioOptions, RtpInputOptions,
-    RtpInputTransportProtocol, WebrtcVideoDecoderOptions, WhepInputOptions, WhipInputOptions,
+    HlsInputOptions, HlsInputVideoDecoders, Mp4DecryptionOptions, Mp4InputOptions, Mp4InputSource,
+    Mp4InputVideoDecoders, PortOrRange, RtmpServerInputDecoders, RtmpServerInputOptions,
+    RtpAudioOptions, RtpInputOptions, RtpInputTransportProtocol, RtpPayloadTypes, RtpSocketOptions,
+    RtpVideoOptions, WebrtcVideoDecoderOptions, WhepInputOptions, WhipInputOptions,
 };
 
 #[cfg(target_os = "linux")]
@@ -238,11 +239,13 @@ fn rtp_video_h264() {
         CoreInput::Rtp(RtpInputOptions {
             port: PortOrRange::Exact(9002),
             transport_protocol: RtpInputTransportProtocol::Udp,
-            video: Some(VideoDecoderOptions::FfmpegH264),
+            video: Some(RtpVideoOptions::Decoder(VideoDecoderOptions::FfmpegH264)),
             audio: None,
             queue_options: default_queue(),
             offset: None,
             buffer_duration: None,
+            socket_options: RtpSocketOptions::default(),
+            payload_types: RtpPayloadTypes::default(),
         }),
     );
 }
@@ -266,6 +269,8 @@ fn rtp_audio_opus() {
             queue_options: default_queue(),
             offset: None,
             buffer_duration: None,
+            socket_options: RtpSocketOptions::default(),
+            payload_types: RtpPayloadTypes::default(),
         }),
     );
 }
@@ -323,7 +328,7 @@ fn rtp_video_and_audio() {
         CoreInput::Rtp(RtpInputOptions {
             port: PortOrRange::Exact(9002),
             transport_protocol: RtpInputTransportProtocol::Udp,
-            video: Some(VideoDecoderOptions::FfmpegH264),
+            video: Some(RtpVideoOptions::Decoder(VideoDecoderOptions::FfmpegH264)),
             audio: Some(RtpAudioOptions::Opus),
             queue_options: QueueInputOptions {
                 required: true,
@@ -333,6 +338,8 @@ fn rtp_video_and_audio() {
             },
             offset: Some(Duration::from_millis(500)),
             buffer_duration: Some(Duration::from_millis(200)),
+            socket_options: RtpSocketOptions::default(),
+            payload_types: RtpPayloadTypes::default(),
         }),
     );
 }
@@ -352,11 +359,13 @@ fn rtp_port_range() {
         CoreInput::Rtp(RtpInputOptions {
             port: PortOrRange::Range((9000, 9010)),
             transport_protocol: RtpInputTransportProtocol::TcpServer,
-            video: Some(VideoDecoderOptions::FfmpegVp8),
+            video: Some(RtpVideoOptions::Decoder(VideoDecoderOptions::FfmpegVp8)),
             audio: None,
             queue_options: default_queue(),
             offset: None,
             buffer_duration: None,
+            socket_options: RtpSocketOptions::default(),
+            payload_types: RtpPayloadTypes::default(),
         }),
     );
 }
@@ -375,11 +384,13 @@ fn rtp_video_vp9() {
         CoreInput::Rtp(RtpInputOptions {
             port: PortOrRange::Exact(9002),
             transport_protocol: RtpInputTransportProtocol::Udp,
-            video: Some(VideoDecoderOptions::FfmpegVp9),
+            video: Some(RtpVideoOptions::Decoder(VideoDecoderOptions::FfmpegVp9)),
             audio: None,
             queue_options: default_queue(),
             offset: None,
             buffer_duration: None,
+            socket_options: RtpSocketOptions::default(),
+            payload_types: RtpPayloadTypes::default(),
         }),
     );
 }
@@ -398,11 +409,82 @@ fn rtp_video_vulkan_h264() {
         CoreInput::Rtp(RtpInputOptions {
             port: PortOrRange::Exact(9002),
             transport_protocol: RtpInputTransportProtocol::Udp,
-            video: Some(VideoDecoderOptions::VulkanH264),
+            video: Some(RtpVideoOptions::Decoder(VideoDecoderOptions::VulkanH264)),
             audio: None,
             queue_options: default_queue(),
             offset: None,
             buffer_duration: None,
+            socket_options: RtpSocketOptions::default(),
+            payload_types: RtpPayloadTypes::default(),
+        }),
+    );
+}
+
+#[test]
+fn rtp_socket_options() {
+    check_rtp(
+        json!({
+            "input": {
+                "port": 9002,
+                "video": {
+                    "decoder": "ffmpeg_h264"
+                },
+                "socket_options": {
+                    "receive_buffer_size_bytes": 33554432,
+                    "dscp": 46
+                }
+            }
+        }),
+        CoreInput::Rtp(RtpInputOptions {
+            port: PortOrRange::Exact(9002),
+            transport_protocol: RtpInputTransportProtocol::Udp,
+            video: Some(RtpVideoOptions::Decoder(VideoDecoderOptions::FfmpegH264)),
+            audio: None,
+            queue_options: default_queue(),
+            offset: None,
+            buffer_duration: None,
+            socket_options: RtpSocketOptions {
+                recv_buffer_size: Some(32 * 1024 * 1024),
+                send_buffer_size: None,
+                dscp: Some(46),
+            },
+            payload_types: RtpPayloadTypes::default(),
+        }),
+    );
+}
+
+#[test]
+fn rtp_custom_decoders() {
+    check_rtp(
+        json!({
+            "input": {
+                "port": 9002,
+                "video": {
+                    "decoder": "custom"
+                },
+                "audio": {
+                    "decoder": "custom",
+                    "clock_rate": 16000
+                },
+                "payload_types": {
+                    "video": 110,
+                    "audio": 111
+                }
+            }
+        }),
+        CoreInput::Rtp(RtpInputOptions {
+            port: PortOrRange::Exact(9002),
+            transport_protocol: RtpInputTransportProtocol::Udp,
+            video: Some(RtpVideoOptions::Custom),
+            audio: Some(RtpAudioOptions::Custom { clock_rate: 16000 }),
+            queue_options: default_queue(),
+            offset: None,
+            buffer_duration: None,
+            socket_options: RtpSocketOptions::default(),
+            payload_types: RtpPayloadTypes {
+                video: 110,
+                audio: 111,
+            },
         }),
     );
 }
@@ -465,6 +547,48 @@ fn err_rtp_aac_non_hex_config() {
     );
 }
 
+#[test]
+fn err_rtp_dscp_out_of_range() {
+    check_rtp_err(
+        json!({
+            "input": {
+                "port": 9002,
+                "video": { "decoder": "ffmpeg_h264" },
+                "socket_options": { "dscp": 64 }
+            }
+        }),
+        "\"dscp\" has to be a number between 0 and 63.",
+    );
+}
+
+#[test]
+fn err_rtp_payload_type_in_rtcp_range() {
+    check_rtp_err(
+        json!({
+            "input": {
+                "port": 9002,
+                "video": { "decoder": "ffmpeg_h264" },
+                "payload_types": { "video": 72 }
+            }
+        }),
+        "\"video\" has to be a number between 0 and 63 or between 96 and 127.",
+    );
+}
+
+#[test]
+fn err_rtp_same_payload_types() {
+    check_rtp_err(
+        json!({
+            "input": {
+                "port": 9002,
+                "video": { "decoder": "ffmpeg_h264" },
+                "payload_types": { "audio": 96 }
+            }
+        }),
+        "Video and audio tracks have to use different payload types.",
+    );
+}
+
 #[test]
 fn err_rtp_port_zero() {
     check_rtp_err(
@@ -521,6 +645,7 @@ fn mp4_with_url() {
             seek: None,
             offset: None,
             queue_options: default_queue(),
+            decryption: None,
         }),
     );
 }
@@ -540,6 +665,7 @@ fn mp4_with_path() {
             seek: None,
             offset: None,
             queue_options: default_queue(),
+            decryption: None,
         }),
     );
 }
@@ -574,6 +700,7 @@ fn mp4_with_all_options() {
                 audio_side_channel: true,
                 side_channel_delay: Duration::ZERO,
             },
+            decryption: None,
         }),
     );
 }
@@ -598,6 +725,40 @@ fn mp4_vulkan_decoder() {
             seek: None,
             offset: None,
             queue_options: default_queue(),
+            decryption: None,
+        }),
+    );
+}
+
+#[test]
+fn mp4_with_decryption() {
+    check_mp4(
+        json!({
+            "input": {
+                "path": "/tmp/video.mp4",
+                "decryption": {
+                    "key": "00112233445566778899AABBCCDDEEFF",
+                    "key_id": "0123456789abcdef0123456789abcdef"
+                }
+            }
+        }),
+        CoreInput::Mp4(Mp4InputOptions {
+            source: Mp4InputSource::File(Arc::from(Path::new("/tmp/video.mp4"))),
+            should_loop: false,
+            video_decoders: Mp4InputVideoDecoders { h264: None },
+            seek: None,
+            offset: None,
+            queue_options: default_queue(),
+            decryption: Some(Mp4DecryptionOptions {
+                key: [
+                    0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc,
+                    0xdd, 0xee, 0xff,
+                ],
+                key_id: Some([
+                    0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01, 0x23, 0x45, 0x67, 0x89,
+                    0xab, 0xcd, 0xef,
+                ]),
+            }),
         }),
     );
 }
@@ -638,6 +799,19 @@ fn err_mp4_negative_seek() {
     );
 }
 
+#[test]
+fn err_mp4_invalid_decryption_key() {
+    check_mp4_err(
+        json!({
+            "input": {
+                "path": "/tmp/video.mp4",
+                "decryption": { "key": "0011223344" }
+            }
+        }),
+        "\"key\" has to be a 128-bit value encoded as 32 hex digits.",
+    );
+}
+
 // ── WHIP Input ───────────────────────────────────────────────────────
 
 #[test]
@@ -1000,7 +1174,9 @@ fn decklink_with_all_options() {
             "subdevice_index": 0,
             "display_name": "DeckLink Mini Recorder",
             "persistent_id": "AABBCCDD",
+            "profile": "two_sub_devices_half_duplex",
             "enable_audio": false,
+            "enable_ancillary_data": true,
             "required": true,
             "side_channel": { "video": true }
         }
@@ -1015,3 +1191,12 @@ fn err_serde_decklink_unknown_field() {
         }
     }));
 }
+
+#[test]
+fn err_serde_decklink_unknown_profile() {
+    check_serde_err::<DeckLink>(json!({
+        "input": {
+            "profile": "eight_sub_devices_half_duplex"
+        }
+    }));
+}
@@ -21,6 +21,7 @@ fn default_video() -> smelter_core::RegisterOutputVideoOptions {
             smelter_render::scene::ViewComponent::default(),
         ),
         end_condition: smelter_core::PipelineOutputEndCondition::Never,
+        debug_overlay: false,
     }
 }
 
@@ -463,6 +464,7 @@ fn rtmp_send_eos_when_any_of() {
                     smelter_render::InputId(Arc::from("input_1")),
                     smelter_render::InputId(Arc::from("input_2")),
                 ]),
+                debug_overlay: false,
             }),
             audio: None,
         },
@@ -517,6 +519,7 @@ fn rtmp_send_eos_when_all_inputs() {
                     smelter_render::scene::ViewComponent::default(),
                 ),
                 end_condition: smelter_core::PipelineOutputEndCondition::AllInputs,
+                debug_overlay: false,
             }),
             audio: None,
         },
@@ -639,6 +642,8 @@ fn rtp_udp_video_only() {
                         },
                     )),
                     audio: None,
+                    socket_options: smelter_core::protocols::RtpSocketOptions::default(),
+                    payload_types: smelter_core::protocols::RtpPayloadTypes::default(),
                 },
             ),
             video: Some(default_video()),
@@ -685,6 +690,8 @@ fn rtp_tcp_server_video() {
                         },
                     )),
                     audio: None,
+                    socket_options: smelter_core::protocols::RtpSocketOptions::default(),
+                    payload_types: smelter_core::protocols::RtpPayloadTypes::default(),
                 },
             ),
             video: Some(default_video()),
@@ -729,6 +736,8 @@ fn rtp_tcp_server_port_range() {
                         },
                     )),
                     audio: None,
+                    socket_options: smelter_core::protocols::RtpSocketOptions::default(),
+                    payload_types: smelter_core::protocols::RtpPayloadTypes::default(),
                 },
             ),
             video: Some(default_video()),
@@ -777,6 +786,8 @@ fn rtp_vp8_encoder() {
                         },
                     )),
                     audio: None,
+                    socket_options: smelter_core::protocols::RtpSocketOptions::default(),
+                    payload_types: smelter_core::protocols::RtpPayloadTypes::default(),
                 },
             ),
             video: Some(default_video()),
@@ -822,6 +833,8 @@ fn rtp_vp9_encoder() {
                         },
                     )),
                     audio: None,
+                    socket_options: smelter_core::protocols::RtpSocketOptions::default(),
+                    payload_types: smelter_core::protocols::RtpPayloadTypes::default(),
                 },
             ),
             video: Some(default_video()),
@@ -867,6 +880,8 @@ fn rtp_audio_opus() {
                             packet_loss: 10,
                         },
                     )),
+                    socket_options: smelter_core::protocols::RtpSocketOptions::default(),
+                    payload_types: smelter_core::protocols::RtpPayloadTypes::default(),
                 },
             ),
             video: None,
@@ -928,6 +943,8 @@ fn rtp_video_and_audio() {
                             packet_loss: 0,
                         },
                     )),
+                    socket_options: smelter_core::protocols::RtpSocketOptions::default(),
+                    payload_types: smelter_core::protocols::RtpPayloadTypes::default(),
                 },
             ),
             video: Some(default_video()),
@@ -1023,6 +1040,44 @@ fn err_rtp_opus_packet_loss_out_of_range() {
     );
 }
 
+#[test]
+fn err_rtp_zero_send_buffer_size() {
+    check_rtp_err(
+        json!({
+            "output": {
+                "port": 9002,
+                "ip": "127.0.0.1",
+                "video": {
+                    "resolution": { "width": 1920, "height": 1080 },
+                    "encoder": { "type": "ffmpeg_h264" },
+                    "initial": video_scene()
+                },
+                "socket_options": { "send_buffer_size_bytes": 0 }
+            }
+        }),
+        "\"send_buffer_size_bytes\" has to be larger than 0.",
+    );
+}
+
+#[test]
+fn err_rtp_payload_type_out_of_range() {
+    check_rtp_err(
+        json!({
+            "output": {
+                "port": 9002,
+                "ip": "127.0.0.1",
+                "video": {
+                    "resolution": { "width": 1920, "height": 1080 },
+                    "encoder": { "type": "ffmpeg_h264" },
+                    "initial": video_scene()
+                },
+                "payload_types": { "video": 128 }
+            }
+        }),
+        "\"video\" has to be a number between 0 and 63 or between 96 and 127.",
+    );
+}
+
 // ── MP4 Output ───────────────────────────────────────────────────────
 
 #[test]
@@ -1060,6 +1115,8 @@ fn mp4_video_only() {
                         },
                     )),
                     audio: None,
+                    fast_start: true,
+                    interleave_duration: None,
                     raw_options: vec![],
                 },
             ),
@@ -1093,6 +1150,44 @@ fn mp4_audio_only() {
                             bitstream_format: smelter_core::codecs::AacBitstreamFormat::Raw,
                         },
                     )),
+                    fast_start: true,
+                    interleave_duration: None,
+                    raw_options: vec![],
+                },
+            ),
+            video: None,
+            audio: Some(default_audio()),
+        },
+    );
+}
+
+#[test]
+fn mp4_without_fast_start() {
+    check_mp4(
+        json!({
+            "output": {
+                "path": "/tmp/output.mp4",
+                "audio": {
+                    "encoder": { "type": "aac", "sample_rate": 48000 },
+                    "initial": audio_scene()
+                },
+                "fast_start": false
+            }
+        }),
+        CoreOutput {
+            output_options: smelter_core::ProtocolOutputOptions::Mp4(
+                smelter_core::protocols::Mp4OutputOptions {
+                    output_path: Arc::from(Path::new("/tmp/output.mp4")),
+                    video: None,
+                    audio: Some(smelter_core::codecs::AudioEncoderOptions::FdkAac(
+                        smelter_core::codecs::FdkAacEncoderOptions {
+                            channels: smelter_core::AudioChannels::Stereo,
+                            sample_rate: 48000,
+                            bitstream_format: smelter_core::codecs::AacBitstreamFormat::Raw,
+                        },
+                    )),
+                    fast_start: false,
+                    interleave_duration: None,
                     raw_options: vec![],
                 },
             ),
@@ -1150,6 +1245,8 @@ fn mp4_video_and_audio_with_ffmpeg_options() {
                             bitstream_format: smelter_core::codecs::AacBitstreamFormat::Raw,
                         },
                     )),
+                    fast_start: true,
+                    interleave_duration: None,
                     raw_options: vec![(Arc::from("movflags"), Arc::from("faststart"))],
                 },
             ),
@@ -1194,6 +1291,8 @@ fn mp4_vulkan_encoder() {
                         },
                     )),
                     audio: None,
+                    fast_start: true,
+                    interleave_duration: None,
                     raw_options: vec![],
                 },
             ),
@@ -1203,6 +1302,42 @@ fn mp4_vulkan_encoder() {
     );
 }
 
+#[test]
+fn mp4_with_interleave_duration() {
+    check_mp4(
+        json!({
+            "output": {
+                "path": "/tmp/output.mp4",
+                "audio": {
+                    "encoder": { "type": "aac", "sample_rate": 48000 },
+                    "initial": audio_scene()
+                },
+                "interleave_duration_ms": 500
+            }
+        }),
+        CoreOutput {
+            output_options: smelter_core::ProtocolOutputOptions::Mp4(
+                smelter_core::protocols::Mp4OutputOptions {
+                    output_path: Arc::from(Path::new("/tmp/output.mp4")),
+                    video: None,
+                    audio: Some(smelter_core::codecs::AudioEncoderOptions::FdkAac(
+                        smelter_core::codecs::FdkAacEncoderOptions {
+                            channels: smelter_core::AudioChannels::Stereo,
+                            sample_rate: 48000,
+                            bitstream_format: smelter_core::codecs::AacBitstreamFormat::Raw,
+                        },
+                    )),
+                    fast_start: true,
+                    interleave_duration: Some(Duration::from_millis(500)),
+                    raw_options: vec![],
+                },
+            ),
+            video: None,
+            audio: Some(default_audio()),
+        },
+    );
+}
+
 #[test]
 fn err_mp4_no_video_no_audio() {
     check_mp4_err(
@@ -1215,6 +1350,23 @@ fn err_mp4_no_video_no_audio() {
     );
 }
 
+#[test]
+fn err_mp4_zero_interleave_duration() {
+    check_mp4_err(
+        json!({
+            "output": {
+                "path": "/tmp/output.mp4",
+                "audio": {
+                    "encoder": { "type": "aac", "sample_rate": 48000 },
+                    "initial": audio_scene()
+                },
+                "interleave_duration_ms": 0
+            }
+        }),
+        "\"interleave_duration_ms\" has to be larger than 0.",
+    );
+}
+
 // ── WHIP Output ──────────────────────────────────────────────────────
 
 #[test]
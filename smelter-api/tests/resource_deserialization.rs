@@ -5,6 +5,7 @@ use serde_json::json;
 use smelter_api::*;
 use smelter_render::Resolution;
 use smelter_render::image::{ImageSource, ImageType};
+use smelter_render::lut::{LutSource, LutSpec as RenderLutSpec};
 use smelter_render::shader;
 use smelter_render::web_renderer::{WebEmbeddingMethod, WebRendererSpec as RenderWebRendererSpec};
 
@@ -34,6 +35,22 @@ fn check_shader(raw: serde_json::Value, expected: RendererSpec) {
     assert_eq!(actual, expected);
 }
 
+#[track_caller]
+fn check_lut(raw: serde_json::Value, expected: RendererSpec) {
+    let resource = raw.get("resource").unwrap().clone();
+    let api: LutSpec = serde_json::from_value(resource).unwrap();
+    let actual = RendererSpec::try_from(api).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[track_caller]
+fn check_lut_err(raw: serde_json::Value, expected_msg: &str) {
+    let resource = raw.get("resource").unwrap().clone();
+    let api: LutSpec = serde_json::from_value(resource).unwrap();
+    let err = RendererSpec::try_from(api).unwrap_err();
+    assert_eq!(err.to_string(), expected_msg);
+}
+
 #[track_caller]
 fn check_web_renderer(raw: serde_json::Value, expected: RendererSpec) {
     let resource = raw.get("resource").unwrap().clone();
@@ -179,6 +196,36 @@ fn image_gif_with_path() {
     );
 }
 
+// ── Image: WebP ──────────────────────────────────────────────────────
+
+#[test]
+fn image_webp_with_url() {
+    check_image(
+        json!({
+            "resource": {
+                "asset_type": "webp",
+                "url": "https://example.com/sticker.webp"
+            }
+        }),
+        image_url("https://example.com/sticker.webp", ImageType::Webp),
+    );
+}
+
+// ── Image: AVIF ──────────────────────────────────────────────────────
+
+#[test]
+fn image_avif_with_path() {
+    check_image(
+        json!({
+            "resource": {
+                "asset_type": "avif",
+                "path": "/tmp/photo.avif"
+            }
+        }),
+        image_path("/tmp/photo.avif", ImageType::Avif),
+    );
+}
+
 // ── Image: Auto ──────────────────────────────────────────────────────
 
 #[test]
@@ -364,6 +411,63 @@ fn err_serde_shader_unknown_field() {
     }));
 }
 
+// ── LUT ──────────────────────────────────────────────────────────────
+
+#[test]
+fn lut_with_url() {
+    check_lut(
+        json!({
+            "resource": {
+                "url": "https://example.com/grade.cube"
+            }
+        }),
+        RendererSpec::Lut(RenderLutSpec {
+            src: LutSource::Url {
+                url: Arc::from("https://example.com/grade.cube"),
+            },
+        }),
+    );
+}
+
+#[test]
+fn lut_with_path() {
+    check_lut(
+        json!({
+            "resource": {
+                "path": "/tmp/grade.cube"
+            }
+        }),
+        RendererSpec::Lut(RenderLutSpec {
+            src: LutSource::LocalPath {
+                path: Arc::from(Path::new("/tmp/grade.cube")),
+            },
+        }),
+    );
+}
+
+#[test]
+fn err_lut_missing_source() {
+    check_lut_err(
+        json!({
+            "resource": {}
+        }),
+        "\"url\" or \"path\" field is required when registering a LUT.",
+    );
+}
+
+#[test]
+fn err_lut_url_and_path() {
+    check_lut_err(
+        json!({
+            "resource": {
+                "url": "https://example.com/grade.cube",
+                "path": "/tmp/grade.cube"
+            }
+        }),
+        "\"url\" and \"path\" fields are mutually exclusive when registering a LUT.",
+    );
+}
+
 // ── WebRenderer ──────────────────────────────────────────────────────
 
 #[test]
@@ -17,6 +17,14 @@ fn input_stream(id: Option<&str>, input_id: &str) -> scene::Component {
     scene::Component::InputStream(scene::InputStreamComponent {
         id: id.map(component_id),
         input_id: smelter_render::InputId(input_id.into()),
+        chroma_key: None,
+        color_correction: None,
+        lut_id: None,
+        opacity: 1.0,
+        crop: None,
+        ken_burns: None,
+        flip_horizontal: false,
+        flip_vertical: false,
     })
 }
 
@@ -39,15 +47,22 @@ fn text_default(text: &str, font_size: f32) -> scene::TextComponent {
     scene::TextComponent {
         id: None,
         text: text.into(),
+        spans: vec![],
         font_size,
         line_height: font_size,
         color: scene::RGBAColor(255, 255, 255, 255),
+        gradient: None,
         font_family: Arc::from("Verdana"),
         style: scene::TextStyle::Normal,
-        align: scene::HorizontalAlign::Left,
+        align: None,
+        direction: scene::TextDirection::Auto,
         weight: scene::TextWeight::Normal,
         wrap: scene::TextWrap::None,
+        fit: scene::TextFit::None,
         background_color: scene::RGBAColor(0, 0, 0, 0),
+        stroke: None,
+        shadow: None,
+        ticker: None,
         dimensions: scene::TextDimensions::Fitted {
             max_width: smelter_render::MAX_NODE_RESOLUTION.width as f32,
             max_height: smelter_render::MAX_NODE_RESOLUTION.height as f32,
@@ -116,6 +131,9 @@ fn view_with_background_color() {
                     position_horizontal: scene::HorizontalPosition::RightOffset(50.0),
                     position_vertical: scene::VerticalPosition::TopOffset(50.0),
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
                 }),
                 background_color: scene::RGBAColor(0, 255, 0, 255),
                 ..view_default()
@@ -125,6 +143,114 @@ fn view_with_background_color() {
     );
 }
 
+#[test]
+fn view_background_linear_and_radial_gradient() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "background": {
+                        "type": "linear_gradient",
+                        "angle": 90,
+                        "stops": [
+                            { "color": "#FF0000FF" },
+                            { "color": "#0000FFFF" }
+                        ]
+                    },
+                    "children": [
+                        {
+                            "type": "view",
+                            "background": {
+                                "type": "radial_gradient",
+                                "center_x": 0.25,
+                                "stops": [
+                                    { "color": "#FFFFFFFF" },
+                                    { "color": "#00000000", "position": 0.5 }
+                                ]
+                            }
+                        },
+                        {
+                            "type": "view",
+                            "background": { "type": "color", "color": "#00FF00FF" }
+                        }
+                    ]
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            background_gradient: Some(scene::BackgroundGradient::Linear(scene::LinearGradient {
+                angle_degrees: 90.0,
+                stops: vec![
+                    scene::GradientStop {
+                        position: 0.0,
+                        color: scene::RGBAColor(255, 0, 0, 255),
+                    },
+                    scene::GradientStop {
+                        position: 1.0,
+                        color: scene::RGBAColor(0, 0, 255, 255),
+                    },
+                ],
+            })),
+            children: vec![
+                scene::Component::View(scene::ViewComponent {
+                    background_gradient: Some(scene::BackgroundGradient::Radial(
+                        scene::RadialGradient {
+                            center_x: 0.25,
+                            center_y: 0.5,
+                            stops: vec![
+                                scene::GradientStop {
+                                    position: 0.0,
+                                    color: scene::RGBAColor(255, 255, 255, 255),
+                                },
+                                scene::GradientStop {
+                                    position: 0.5,
+                                    color: scene::RGBAColor(0, 0, 0, 0),
+                                },
+                            ],
+                        },
+                    )),
+                    ..view_default()
+                }),
+                scene::Component::View(scene::ViewComponent {
+                    background_color: scene::RGBAColor(0, 255, 0, 255),
+                    ..view_default()
+                }),
+            ],
+            ..view_default()
+        }),
+    );
+}
+
+#[test]
+fn view_blur_radius_with_rescaler() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "blur_radius": 8,
+                    "children": [
+                        {
+                            "type": "rescaler",
+                            "blur_radius": 2.5,
+                            "child": { "type": "input_stream", "input_id": "input_1" }
+                        }
+                    ]
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            blur_radius: 8.0,
+            children: vec![scene::Component::Rescaler(scene::RescalerComponent {
+                blur_radius: 2.5,
+                ..rescaler_default(input_stream(None, "input_1"))
+            })],
+            ..view_default()
+        }),
+    );
+}
+
 #[test]
 fn view_border_radius_border_box_shadow() {
     check(
@@ -167,6 +293,9 @@ fn view_border_radius_border_box_shadow() {
                     position_horizontal: scene::HorizontalPosition::LeftOffset(50.0),
                     position_vertical: scene::VerticalPosition::TopOffset(50.0),
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
                 }),
                 border_radius: scene::BorderRadius::new_with_radius(50.0),
                 border_width: 20.0,
@@ -175,6 +304,7 @@ fn view_border_radius_border_box_shadow() {
                     offset_x: 60.0,
                     offset_y: 30.0,
                     blur_radius: 30.0,
+                    spread: 0.0,
                     color: scene::RGBAColor(0, 255, 0, 255),
                 }],
                 ..view_default()
@@ -184,6 +314,266 @@ fn view_border_radius_border_box_shadow() {
     );
 }
 
+#[test]
+fn view_border_radius_per_corner() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "border_radius": {
+                        "top_left": 10,
+                        "top_right": 20,
+                        "bottom_right": 30
+                    }
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            border_radius: scene::BorderRadius {
+                top_left: 10.0,
+                top_right: 20.0,
+                bottom_right: 30.0,
+                bottom_left: 0.0,
+            },
+            ..view_default()
+        }),
+    );
+}
+
+#[test]
+fn view_box_shadow_spread() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "box_shadow": [
+                        {
+                            "offset_y": 10,
+                            "blur_radius": 20,
+                            "spread": -5,
+                            "color": "#00000080"
+                        }
+                    ]
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            box_shadow: vec![scene::BoxShadow {
+                offset_x: 0.0,
+                offset_y: 10.0,
+                blur_radius: 20.0,
+                spread: -5.0,
+                color: scene::RGBAColor(0, 0, 0, 128),
+            }],
+            ..view_default()
+        }),
+    );
+}
+
+#[test]
+fn view_and_rescaler_blend_mode() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "blend_mode": "multiply",
+                    "children": [
+                        {
+                            "type": "rescaler",
+                            "blend_mode": "add",
+                            "child": { "type": "input_stream", "input_id": "input_1" }
+                        }
+                    ]
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            blend_mode: scene::BlendMode::Multiply,
+            children: vec![scene::Component::Rescaler(scene::RescalerComponent {
+                blend_mode: scene::BlendMode::Add,
+                ..rescaler_default(input_stream(None, "input_1"))
+            })],
+            ..view_default()
+        }),
+    );
+}
+
+#[test]
+fn view_rescaler_and_input_stream_opacity() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "opacity": 0.5,
+                    "children": [
+                        {
+                            "type": "rescaler",
+                            "opacity": 0.25,
+                            "child": {
+                                "type": "input_stream",
+                                "input_id": "input_1",
+                                "opacity": 0.75
+                            }
+                        }
+                    ]
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            opacity: 0.5,
+            children: vec![scene::Component::Rescaler(scene::RescalerComponent {
+                opacity: 0.25,
+                ..rescaler_default(scene::Component::InputStream(scene::InputStreamComponent {
+                    id: None,
+                    input_id: smelter_render::InputId("input_1".into()),
+                    chroma_key: None,
+                    color_correction: None,
+                    lut_id: None,
+                    opacity: 0.75,
+                    crop: None,
+                    ken_burns: None,
+                    flip_horizontal: false,
+                    flip_vertical: false,
+                }))
+            })],
+            ..view_default()
+        }),
+    );
+}
+
+#[test]
+fn rescaler_and_input_stream_crop() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "rescaler",
+                    "crop": { "type": "normalized", "top": 0.1, "left": 0, "width": 1, "height": 0.8 },
+                    "child": {
+                        "type": "input_stream",
+                        "input_id": "input_1",
+                        "crop": { "type": "pixels", "top": 140, "left": 0, "width": 1920, "height": 800 }
+                    }
+                }
+            }
+        }),
+        scene::Component::Rescaler(scene::RescalerComponent {
+            crop: Some(scene::SourceCrop::Normalized(scene::NormalizedRect {
+                top: 0.1,
+                left: 0.0,
+                width: 1.0,
+                height: 0.8,
+            })),
+            ..rescaler_default(scene::Component::InputStream(scene::InputStreamComponent {
+                id: None,
+                input_id: smelter_render::InputId("input_1".into()),
+                chroma_key: None,
+                color_correction: None,
+                lut_id: None,
+                opacity: 1.0,
+                crop: Some(scene::SourceCrop::Pixels {
+                    top: 140.0,
+                    left: 0.0,
+                    width: 1920.0,
+                    height: 800.0,
+                }),
+                ken_burns: None,
+                flip_horizontal: false,
+                flip_vertical: false,
+            }))
+        }),
+    );
+}
+
+#[test]
+fn rescaler_and_input_stream_flip() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "rescaler",
+                    "flip_vertical": true,
+                    "child": {
+                        "type": "input_stream",
+                        "input_id": "input_1",
+                        "flip_horizontal": true
+                    }
+                }
+            }
+        }),
+        scene::Component::Rescaler(scene::RescalerComponent {
+            flip_vertical: true,
+            ..rescaler_default(scene::Component::InputStream(scene::InputStreamComponent {
+                id: None,
+                input_id: smelter_render::InputId("input_1".into()),
+                chroma_key: None,
+                color_correction: None,
+                lut_id: None,
+                opacity: 1.0,
+                crop: None,
+                ken_burns: None,
+                flip_horizontal: true,
+                flip_vertical: false,
+            }))
+        }),
+    );
+}
+
+#[test]
+fn view_and_rescaler_3d_rotation_with_perspective() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "top": 0,
+                    "left": 0,
+                    "perspective": 800,
+                    "children": [
+                        {
+                            "type": "rescaler",
+                            "top": 90,
+                            "left": 160,
+                            "rotation_y": 180,
+                            "child": { "type": "input_stream", "input_id": "input_1" }
+                        }
+                    ]
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            position: scene::Position::Absolute(scene::AbsolutePosition {
+                width: None,
+                height: None,
+                position_horizontal: scene::HorizontalPosition::LeftOffset(0.0),
+                position_vertical: scene::VerticalPosition::TopOffset(0.0),
+                rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: Some(800.0),
+            }),
+            children: vec![scene::Component::Rescaler(scene::RescalerComponent {
+                position: scene::Position::Absolute(scene::AbsolutePosition {
+                    width: None,
+                    height: None,
+                    position_horizontal: scene::HorizontalPosition::LeftOffset(160.0),
+                    position_vertical: scene::VerticalPosition::TopOffset(90.0),
+                    rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 180.0,
+                    perspective: None,
+                }),
+                ..rescaler_default(input_stream(None, "input_1"))
+            })],
+            ..view_default()
+        }),
+    );
+}
+
 #[test]
 fn view_overflow_fit() {
     check(
@@ -370,6 +760,9 @@ fn rescaler_fit_input_stream() {
                 position_horizontal: scene::HorizontalPosition::LeftOffset(160.0),
                 position_vertical: scene::VerticalPosition::TopOffset(90.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             ..rescaler_default(input_stream(None, "input_1"))
         }),
@@ -404,6 +797,9 @@ fn rescaler_fill_input_stream_align_top_left() {
                 position_horizontal: scene::HorizontalPosition::LeftOffset(160.0),
                 position_vertical: scene::VerticalPosition::TopOffset(90.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             ..rescaler_default(input_stream(None, "input_1"))
         }),
@@ -441,12 +837,16 @@ fn rescaler_border_radius_box_shadow() {
                 position_horizontal: scene::HorizontalPosition::LeftOffset(50.0),
                 position_vertical: scene::VerticalPosition::TopOffset(50.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             border_radius: scene::BorderRadius::new_with_radius(50.0),
             box_shadow: vec![scene::BoxShadow {
                 offset_x: 60.0,
                 offset_y: 30.0,
                 blur_radius: 30.0,
+                spread: 0.0,
                 color: scene::RGBAColor(0, 255, 0, 255),
             }],
             ..rescaler_default(scene::Component::View(scene::ViewComponent {
@@ -458,55 +858,195 @@ fn rescaler_border_radius_box_shadow() {
 }
 
 #[test]
-fn transition_view_cubic_bezier() {
+fn rescaler_chroma_key_with_input_stream_override() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "rescaler",
+                    "chroma_key": {},
+                    "child": {
+                        "type": "input_stream",
+                        "input_id": "input_1",
+                        "chroma_key": {
+                            "key_color": "#0000FF",
+                            "similarity": 0.3,
+                            "smoothness": 0.1,
+                            "spill_reduction": 0.0
+                        }
+                    }
+                }
+            }
+        }),
+        scene::Component::Rescaler(scene::RescalerComponent {
+            chroma_key: Some(scene::ChromaKey {
+                key_color: scene::RGBColor(0, 255, 0),
+                similarity: 0.4,
+                smoothness: 0.08,
+                spill_reduction: 0.1,
+            }),
+            ..rescaler_default(scene::Component::InputStream(scene::InputStreamComponent {
+                id: None,
+                input_id: smelter_render::InputId("input_1".into()),
+                chroma_key: Some(scene::ChromaKey {
+                    key_color: scene::RGBColor(0, 0, 255),
+                    similarity: 0.3,
+                    smoothness: 0.1,
+                    spill_reduction: 0.0,
+                }),
+                color_correction: None,
+                lut_id: None,
+                opacity: 1.0,
+                crop: None,
+                ken_burns: None,
+                flip_horizontal: false,
+                flip_vertical: false,
+            }))
+        }),
+    );
+}
+
+#[test]
+fn view_color_correction_with_input_stream() {
     check(
         json!({
             "video": {
                 "root": {
                     "type": "view",
+                    "color_correction": { "saturation": 0.5 },
                     "children": [
                         {
-                            "id": "resize_1",
-                            "type": "view",
-                            "width": 200,
-                            "height": 200,
-                            "top": 0,
-                            "right": 440,
-                            "transition": {
-                                "duration_ms": 5000,
-                                "easing_function": {
-                                    "function_name": "cubic_bezier",
-                                    "points": [0.83, 0.4, 0.17, 1]
-                                }
-                            },
-                            "background_color": "#00FF00FF"
+                            "type": "input_stream",
+                            "input_id": "input_1",
+                            "color_correction": {
+                                "brightness": 0.1,
+                                "contrast": 1.2,
+                                "hue_shift": -15
+                            }
                         }
                     ]
                 }
             }
         }),
         scene::Component::View(scene::ViewComponent {
-            children: vec![scene::Component::View(scene::ViewComponent {
-                id: Some(component_id("resize_1")),
-                position: scene::Position::Absolute(scene::AbsolutePosition {
-                    width: Some(200.0),
-                    height: Some(200.0),
-                    position_horizontal: scene::HorizontalPosition::RightOffset(440.0),
-                    position_vertical: scene::VerticalPosition::TopOffset(0.0),
-                    rotation_degrees: 0.0,
-                }),
-                transition: Some(scene::Transition {
-                    duration: Duration::from_millis(5000),
-                    interpolation_kind: scene::InterpolationKind::CubicBezier {
-                        x1: 0.83,
-                        y1: 0.4,
-                        x2: 0.17,
-                        y2: 1.0,
-                    },
-                    should_interrupt: false,
+            color_correction: Some(scene::ColorCorrection {
+                brightness: 0.0,
+                contrast: 1.0,
+                saturation: 0.5,
+                hue_shift_degrees: 0.0,
+            }),
+            children: vec![scene::Component::InputStream(scene::InputStreamComponent {
+                id: None,
+                input_id: smelter_render::InputId("input_1".into()),
+                chroma_key: None,
+                color_correction: Some(scene::ColorCorrection {
+                    brightness: 0.1,
+                    contrast: 1.2,
+                    saturation: 1.0,
+                    hue_shift_degrees: -15.0,
                 }),
-                background_color: scene::RGBAColor(0, 255, 0, 255),
-                ..view_default()
+                lut_id: None,
+                opacity: 1.0,
+                crop: None,
+                ken_burns: None,
+                flip_horizontal: false,
+                flip_vertical: false,
+            })],
+            ..view_default()
+        }),
+    );
+}
+
+#[test]
+fn view_lut_with_input_stream() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "lut_id": "film_look",
+                    "children": [
+                        {
+                            "type": "input_stream",
+                            "input_id": "input_1",
+                            "lut_id": "camera_log"
+                        }
+                    ]
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            lut_id: Some(renderer_id("film_look")),
+            children: vec![scene::Component::InputStream(scene::InputStreamComponent {
+                id: None,
+                input_id: smelter_render::InputId("input_1".into()),
+                chroma_key: None,
+                color_correction: None,
+                lut_id: Some(renderer_id("camera_log")),
+                opacity: 1.0,
+                crop: None,
+                ken_burns: None,
+                flip_horizontal: false,
+                flip_vertical: false,
+            })],
+            ..view_default()
+        }),
+    );
+}
+
+#[test]
+fn transition_view_cubic_bezier() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "children": [
+                        {
+                            "id": "resize_1",
+                            "type": "view",
+                            "width": 200,
+                            "height": 200,
+                            "top": 0,
+                            "right": 440,
+                            "transition": {
+                                "duration_ms": 5000,
+                                "easing_function": {
+                                    "function_name": "cubic_bezier",
+                                    "points": [0.83, 0.4, 0.17, 1]
+                                }
+                            },
+                            "background_color": "#00FF00FF"
+                        }
+                    ]
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            children: vec![scene::Component::View(scene::ViewComponent {
+                id: Some(component_id("resize_1")),
+                position: scene::Position::Absolute(scene::AbsolutePosition {
+                    width: Some(200.0),
+                    height: Some(200.0),
+                    position_horizontal: scene::HorizontalPosition::RightOffset(440.0),
+                    position_vertical: scene::VerticalPosition::TopOffset(0.0),
+                    rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
+                }),
+                transition: Some(scene::Transition {
+                    duration: Duration::from_millis(5000),
+                    interpolation_kind: scene::InterpolationKind::CubicBezier {
+                        x1: 0.83,
+                        y1: 0.4,
+                        x2: 0.17,
+                        y2: 1.0,
+                    },
+                    should_interrupt: false,
+                }),
+                background_color: scene::RGBAColor(0, 255, 0, 255),
+                ..view_default()
             })],
             ..view_default()
         }),
@@ -538,6 +1078,9 @@ fn transition_default_easing() {
                 position_horizontal: scene::HorizontalPosition::RightOffset(0.0),
                 position_vertical: scene::VerticalPosition::TopOffset(0.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             transition: Some(scene::Transition {
                 duration: Duration::from_millis(10000),
@@ -581,6 +1124,9 @@ fn transition_linear_with_should_interrupt() {
                 position_horizontal: scene::HorizontalPosition::LeftOffset(0.0),
                 position_vertical: scene::VerticalPosition::TopOffset(0.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             transition: Some(scene::Transition {
                 duration: Duration::from_millis(1000),
@@ -620,6 +1166,9 @@ fn transition_bounce() {
                 position_horizontal: scene::HorizontalPosition::LeftOffset(0.0),
                 position_vertical: scene::VerticalPosition::TopOffset(0.0),
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
             }),
             transition: Some(scene::Transition {
                 duration: Duration::from_millis(500),
@@ -631,6 +1180,108 @@ fn transition_bounce() {
     );
 }
 
+#[test]
+fn keyframes_view_and_rescaler() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "children": [
+                        {
+                            "id": "slide",
+                            "type": "view",
+                            "top": 0,
+                            "left": 0,
+                            "keyframes": [
+                                {
+                                    "property": "left",
+                                    "keyframes": [
+                                        { "time_ms": 0, "value": 0 },
+                                        {
+                                            "time_ms": 1000,
+                                            "value": 100,
+                                            "easing_function": { "function_name": "bounce" }
+                                        }
+                                    ]
+                                }
+                            ]
+                        },
+                        {
+                            "type": "rescaler",
+                            "keyframes": [
+                                {
+                                    "property": "opacity",
+                                    "keyframes": [
+                                        { "time_ms": 0, "value": 1 },
+                                        { "time_ms": 500, "value": 0 }
+                                    ],
+                                    "repeat": true
+                                }
+                            ],
+                            "child": { "type": "view" }
+                        }
+                    ]
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            children: vec![
+                scene::Component::View(scene::ViewComponent {
+                    id: Some(component_id("slide")),
+                    position: scene::Position::Absolute(scene::AbsolutePosition {
+                        width: None,
+                        height: None,
+                        position_horizontal: scene::HorizontalPosition::LeftOffset(0.0),
+                        position_vertical: scene::VerticalPosition::TopOffset(0.0),
+                        rotation_degrees: 0.0,
+                        rotation_x_degrees: 0.0,
+                        rotation_y_degrees: 0.0,
+                        perspective: None,
+                    }),
+                    keyframes: vec![scene::KeyframeTrack {
+                        property: scene::AnimatedProperty::Left,
+                        keyframes: vec![
+                            scene::Keyframe {
+                                time: Duration::ZERO,
+                                value: 0.0,
+                                interpolation_kind: scene::InterpolationKind::Linear,
+                            },
+                            scene::Keyframe {
+                                time: Duration::from_millis(1000),
+                                value: 100.0,
+                                interpolation_kind: scene::InterpolationKind::Bounce,
+                            },
+                        ],
+                        repeat: false,
+                    }],
+                    ..view_default()
+                }),
+                scene::Component::Rescaler(scene::RescalerComponent {
+                    keyframes: vec![scene::KeyframeTrack {
+                        property: scene::AnimatedProperty::Opacity,
+                        keyframes: vec![
+                            scene::Keyframe {
+                                time: Duration::ZERO,
+                                value: 1.0,
+                                interpolation_kind: scene::InterpolationKind::Linear,
+                            },
+                            scene::Keyframe {
+                                time: Duration::from_millis(500),
+                                value: 0.0,
+                                interpolation_kind: scene::InterpolationKind::Linear,
+                            },
+                        ],
+                        repeat: true,
+                    }],
+                    ..rescaler_default(scene::Component::View(view_default()))
+                }),
+            ],
+            ..view_default()
+        }),
+    );
+}
+
 #[test]
 fn tiles_three_inputs() {
     check(
@@ -711,6 +1362,32 @@ fn tiles_align_top_left() {
     );
 }
 
+#[test]
+fn tiles_fixed_columns_with_max_tile_size() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "tiles",
+                    "fill_strategy": { "type": "fixed_columns", "columns": 3 },
+                    "max_tile_width": 640,
+                    "max_tile_height": 360,
+                    "children": [
+                        { "type": "input_stream", "input_id": "input_1" }
+                    ]
+                }
+            }
+        }),
+        scene::Component::Tiles(scene::TilesComponent {
+            fill_strategy: scene::TilesFillStrategy::FixedColumns(3),
+            max_tile_width: Some(640.0),
+            max_tile_height: Some(360.0),
+            children: vec![input_stream(None, "input_1")],
+            ..tiles_default()
+        }),
+    );
+}
+
 #[test]
 fn tiles_with_transition_nested_inputs() {
     check(
@@ -743,6 +1420,52 @@ fn tiles_with_transition_nested_inputs() {
     );
 }
 
+#[test]
+fn tiles_with_tile_transitions() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "tiles",
+                    "id": "tiles",
+                    "transition": { "duration_ms": 500 },
+                    "tile_transitions": {
+                        "enter": { "effect": "scale", "duration_ms": 300 },
+                        "exit": { "effect": "none" }
+                    },
+                    "children": [
+                        { "type": "input_stream", "input_id": "input_1" }
+                    ]
+                }
+            }
+        }),
+        scene::Component::Tiles(scene::TilesComponent {
+            id: Some(component_id("tiles")),
+            transition: Some(scene::Transition {
+                duration: Duration::from_millis(500),
+                interpolation_kind: scene::InterpolationKind::Linear,
+                should_interrupt: false,
+            }),
+            tile_transitions: scene::TileTransitions {
+                enter: scene::TileTransition {
+                    effect: scene::TileTransitionEffect::Scale,
+                    duration: Some(Duration::from_millis(300)),
+                },
+                exit: scene::TileTransition {
+                    effect: scene::TileTransitionEffect::None,
+                    duration: None,
+                },
+                reorder: scene::TileTransition {
+                    effect: scene::TileTransitionEffect::Slide,
+                    duration: None,
+                },
+            },
+            children: vec![input_stream(None, "input_1")],
+            ..tiles_default()
+        }),
+    );
+}
+
 #[test]
 fn text_align_center() {
     check(
@@ -761,7 +1484,7 @@ fn text_align_center() {
         }),
         scene::Component::Text(scene::TextComponent {
             font_family: Arc::from("Inter"),
-            align: scene::HorizontalAlign::Center,
+            align: Some(scene::HorizontalAlign::Center),
             dimensions: scene::TextDimensions::Fixed {
                 width: 1000.0,
                 height: 200.0,
@@ -790,7 +1513,7 @@ fn text_bold_right_aligned() {
         }),
         scene::Component::Text(scene::TextComponent {
             font_family: Arc::from("Inter"),
-            align: scene::HorizontalAlign::Right,
+            align: Some(scene::HorizontalAlign::Right),
             weight: scene::TextWeight::Bold,
             dimensions: scene::TextDimensions::Fixed {
                 width: 1000.0,
@@ -802,120 +1525,434 @@ fn text_bold_right_aligned() {
 }
 
 #[test]
-fn text_wrap_word_with_style_and_line_height() {
+fn text_right_to_left_direction() {
     check(
         json!({
             "video": {
                 "root": {
                     "type": "text",
-                    "text": "Lorem ipsum",
+                    "text": "שלום world",
                     "font_size": 50,
-                    "line_height": 60,
-                    "style": "italic",
-                    "wrap": "word",
-                    "max_width": 800,
-                    "max_height": 400,
-                    "color": "#FFFFFFFF",
-                    "background_color": "#000000FF"
+                    "direction": "rtl"
                 }
             }
         }),
         scene::Component::Text(scene::TextComponent {
-            line_height: 60.0,
-            style: scene::TextStyle::Italic,
-            wrap: scene::TextWrap::Word,
-            dimensions: scene::TextDimensions::Fitted {
-                max_width: 800.0,
-                max_height: 400.0,
-            },
-            color: scene::RGBAColor(255, 255, 255, 255),
-            background_color: scene::RGBAColor(0, 0, 0, 255),
-            ..text_default("Lorem ipsum", 50.0)
+            direction: scene::TextDirection::RightToLeft,
+            ..text_default("שלום world", 50.0)
         }),
     );
 }
 
 #[test]
-fn image_jpeg_as_root() {
+fn text_stroke_and_shadow() {
     check(
         json!({
-            "video": { "root": { "type": "image", "image_id": "image_jpeg" } }
+            "video": {
+                "root": {
+                    "type": "text",
+                    "text": "Outlined",
+                    "font_size": 50,
+                    "stroke": { "width": 3 },
+                    "shadow": {
+                        "offset_x": 4,
+                        "offset_y": -2,
+                        "blur_radius": 5,
+                        "color": "#FF000080"
+                    }
+                }
+            }
         }),
-        scene::Component::Image(scene::ImageComponent {
-            id: None,
-            image_id: smelter_render::RendererId("image_jpeg".into()),
-            width: None,
-            height: None,
+        scene::Component::Text(scene::TextComponent {
+            stroke: Some(scene::TextStroke {
+                width: 3.0,
+                color: scene::RGBAColor(0, 0, 0, 255),
+            }),
+            shadow: Some(scene::TextShadow {
+                offset_x: 4.0,
+                offset_y: -2.0,
+                blur_radius: 5.0,
+                color: scene::RGBAColor(255, 0, 0, 128),
+            }),
+            ..text_default("Outlined", 50.0)
         }),
     );
 }
 
 #[test]
-fn image_with_id_and_dimensions() {
+fn text_gradient_with_default_stop_positions() {
     check(
         json!({
             "video": {
                 "root": {
-                    "type": "image",
-                    "id": "gif",
-                    "image_id": "image_gif1",
-                    "width": 320,
-                    "height": 240
+                    "type": "text",
+                    "text": "Title",
+                    "font_size": 50,
+                    "gradient": {
+                        "angle": 90,
+                        "stops": [
+                            { "color": "#FF0000FF" },
+                            { "color": "#00FF00FF" },
+                            { "color": "#0000FFFF", "position": 0.8 },
+                            { "color": "#FFFFFFFF" }
+                        ]
+                    }
                 }
             }
         }),
-        scene::Component::Image(scene::ImageComponent {
-            id: Some(component_id("gif")),
-            image_id: smelter_render::RendererId("image_gif1".into()),
-            width: Some(320.0),
-            height: Some(240.0),
+        scene::Component::Text(scene::TextComponent {
+            gradient: Some(scene::LinearGradient {
+                angle_degrees: 90.0,
+                stops: vec![
+                    scene::GradientStop {
+                        position: 0.0,
+                        color: scene::RGBAColor(255, 0, 0, 255),
+                    },
+                    scene::GradientStop {
+                        position: 0.4,
+                        color: scene::RGBAColor(0, 255, 0, 255),
+                    },
+                    scene::GradientStop {
+                        position: 0.8,
+                        color: scene::RGBAColor(0, 0, 255, 255),
+                    },
+                    scene::GradientStop {
+                        position: 1.0,
+                        color: scene::RGBAColor(255, 255, 255, 255),
+                    },
+                ],
+            }),
+            ..text_default("Title", 50.0)
         }),
     );
 }
 
 #[test]
-fn shader_with_inputs_no_params() {
+fn text_ticker() {
     check(
         json!({
             "video": {
                 "root": {
-                    "type": "shader",
-                    "shader_id": "base_params_plane_id",
-                    "resolution": { "width": 640, "height": 360 },
-                    "children": [
-                        { "type": "input_stream", "input_id": "input_1" },
-                        { "type": "input_stream", "input_id": "input_2" }
-                    ]
+                    "type": "text",
+                    "text": "Breaking news",
+                    "font_size": 50,
+                    "width": 1920,
+                    "ticker": { "speed": 120, "direction": "right" }
                 }
             }
         }),
-        scene::Component::Shader(scene::ShaderComponent {
-            id: None,
-            shader_id: smelter_render::RendererId("base_params_plane_id".into()),
-            shader_param: None,
-            size: scene::Size {
-                width: 640.0,
-                height: 360.0,
+        scene::Component::Text(scene::TextComponent {
+            ticker: Some(scene::TextTicker {
+                speed: 120.0,
+                direction: scene::TickerDirection::Right,
+                repeat: true,
+            }),
+            dimensions: scene::TextDimensions::FittedColumn {
+                width: 1920.0,
+                max_height: smelter_render::MAX_NODE_RESOLUTION.height as f32,
             },
-            children: vec![input_stream(None, "input_1"), input_stream(None, "input_2")],
+            ..text_default("Breaking news", 50.0)
         }),
     );
 }
 
 #[test]
-fn shader_param_full_enum_coverage() {
+fn text_fit_fill() {
     check(
         json!({
             "video": {
                 "root": {
-                    "type": "shader",
-                    "shader_id": "custom",
-                    "resolution": { "width": 640, "height": 360 },
-                    "shader_param": {
-                        "type": "struct",
-                        "value": [
-                            { "field_name": "intensity", "type": "f32", "value": 0.5 },
-                            { "field_name": "count", "type": "u32", "value": 7 },
+                    "type": "text",
+                    "text": "username",
+                    "font_size": 40,
+                    "width": 300,
+                    "height": 60,
+                    "fit": "fill"
+                }
+            }
+        }),
+        scene::Component::Text(scene::TextComponent {
+            fit: scene::TextFit::Fill,
+            dimensions: scene::TextDimensions::Fixed {
+                width: 300.0,
+                height: 60.0,
+            },
+            ..text_default("username", 40.0)
+        }),
+    );
+}
+
+#[test]
+fn text_spans() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "text",
+                    "font_size": 40,
+                    "line_height": 50,
+                    "spans": [
+                        { "text": "LIVE", "weight": "bold", "color": "#FF0000FF" },
+                        { "text": " • Match 3", "font_size": 30, "style": "italic" }
+                    ]
+                }
+            }
+        }),
+        scene::Component::Text(scene::TextComponent {
+            line_height: 50.0,
+            spans: vec![
+                scene::TextSpan {
+                    text: Arc::from("LIVE"),
+                    font_family: None,
+                    font_size: None,
+                    style: None,
+                    weight: Some(scene::TextWeight::Bold),
+                    color: Some(scene::RGBAColor(255, 0, 0, 255)),
+                },
+                scene::TextSpan {
+                    text: Arc::from(" • Match 3"),
+                    font_family: None,
+                    font_size: Some(30.0),
+                    style: Some(scene::TextStyle::Italic),
+                    weight: None,
+                    color: None,
+                },
+            ],
+            ..text_default("LIVE • Match 3", 40.0)
+        }),
+    );
+}
+
+#[test]
+fn text_wrap_word_with_style_and_line_height() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "text",
+                    "text": "Lorem ipsum",
+                    "font_size": 50,
+                    "line_height": 60,
+                    "style": "italic",
+                    "wrap": "word",
+                    "max_width": 800,
+                    "max_height": 400,
+                    "color": "#FFFFFFFF",
+                    "background_color": "#000000FF"
+                }
+            }
+        }),
+        scene::Component::Text(scene::TextComponent {
+            line_height: 60.0,
+            style: scene::TextStyle::Italic,
+            wrap: scene::TextWrap::Word,
+            dimensions: scene::TextDimensions::Fitted {
+                max_width: 800.0,
+                max_height: 400.0,
+            },
+            color: scene::RGBAColor(255, 255, 255, 255),
+            background_color: scene::RGBAColor(0, 0, 0, 255),
+            ..text_default("Lorem ipsum", 50.0)
+        }),
+    );
+}
+
+#[test]
+fn image_jpeg_as_root() {
+    check(
+        json!({
+            "video": { "root": { "type": "image", "image_id": "image_jpeg" } }
+        }),
+        scene::Component::Image(scene::ImageComponent {
+            id: None,
+            image_id: smelter_render::RendererId("image_jpeg".into()),
+            width: None,
+            height: None,
+            nine_slice: None,
+            ken_burns: None,
+            flip_horizontal: false,
+            flip_vertical: false,
+        }),
+    );
+}
+
+#[test]
+fn image_with_id_and_dimensions() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "image",
+                    "id": "gif",
+                    "image_id": "image_gif1",
+                    "width": 320,
+                    "height": 240
+                }
+            }
+        }),
+        scene::Component::Image(scene::ImageComponent {
+            id: Some(component_id("gif")),
+            image_id: smelter_render::RendererId("image_gif1".into()),
+            width: Some(320.0),
+            height: Some(240.0),
+            nine_slice: None,
+            ken_burns: None,
+            flip_horizontal: false,
+            flip_vertical: false,
+        }),
+    );
+}
+
+#[test]
+fn image_nine_slice() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "image",
+                    "image_id": "frame_png",
+                    "width": 600,
+                    "height": 200,
+                    "nine_slice": { "top": 16, "right": 24, "bottom": 16, "left": 24 }
+                }
+            }
+        }),
+        scene::Component::Image(scene::ImageComponent {
+            id: None,
+            image_id: smelter_render::RendererId("frame_png".into()),
+            width: Some(600.0),
+            height: Some(200.0),
+            nine_slice: Some(scene::NineSlice {
+                top: 16.0,
+                right: 24.0,
+                bottom: 16.0,
+                left: 24.0,
+            }),
+            ken_burns: None,
+            flip_horizontal: false,
+            flip_vertical: false,
+        }),
+    );
+}
+
+#[test]
+fn image_ken_burns() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "children": [{
+                        "type": "image",
+                        "id": "photo",
+                        "image_id": "frame_png",
+                        "ken_burns": {
+                            "start": { "top": 0, "left": 0, "width": 1, "height": 1 },
+                            "end": { "top": 0.25, "left": 0.5, "width": 0.5, "height": 0.5 },
+                            "duration_ms": 5000,
+                            "easing_function": { "function_name": "bounce" }
+                        }
+                    }]
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            children: vec![scene::Component::Image(scene::ImageComponent {
+                id: Some(component_id("photo")),
+                image_id: smelter_render::RendererId("frame_png".into()),
+                ken_burns: Some(scene::KenBurns {
+                    start: scene::NormalizedRect {
+                        top: 0.0,
+                        left: 0.0,
+                        width: 1.0,
+                        height: 1.0,
+                    },
+                    end: scene::NormalizedRect {
+                        top: 0.25,
+                        left: 0.5,
+                        width: 0.5,
+                        height: 0.5,
+                    },
+                    duration: Duration::from_secs(5),
+                    interpolation_kind: scene::InterpolationKind::Bounce,
+                }),
+                ..Default::default()
+            })],
+            ..view_default()
+        }),
+    );
+}
+
+#[test]
+fn audio_visualizer_waveform() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "audio_visualizer",
+                    "input_id": "input_1",
+                    "width": 400,
+                    "height": 100,
+                    "mode": { "type": "waveform", "line_width": 3 },
+                    "color": "#FF0000FF"
+                }
+            }
+        }),
+        scene::Component::AudioVisualizer(scene::AudioVisualizerComponent {
+            id: None,
+            input_id: smelter_render::InputId("input_1".into()),
+            mode: scene::AudioVisualizerMode::Waveform { line_width: 3.0 },
+            width: 400.0,
+            height: 100.0,
+            color: scene::RGBAColor(255, 0, 0, 255),
+            background_color: scene::RGBAColor(0, 0, 0, 0),
+        }),
+    );
+}
+
+#[test]
+fn shader_with_inputs_no_params() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "shader",
+                    "shader_id": "base_params_plane_id",
+                    "resolution": { "width": 640, "height": 360 },
+                    "children": [
+                        { "type": "input_stream", "input_id": "input_1" },
+                        { "type": "input_stream", "input_id": "input_2" }
+                    ]
+                }
+            }
+        }),
+        scene::Component::Shader(scene::ShaderComponent {
+            id: None,
+            shader_id: smelter_render::RendererId("base_params_plane_id".into()),
+            shader_param: None,
+            size: scene::Size {
+                width: 640.0,
+                height: 360.0,
+            },
+            children: vec![input_stream(None, "input_1"), input_stream(None, "input_2")],
+        }),
+    );
+}
+
+#[test]
+fn shader_param_full_enum_coverage() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "shader",
+                    "shader_id": "custom",
+                    "resolution": { "width": 640, "height": 360 },
+                    "shader_param": {
+                        "type": "struct",
+                        "value": [
+                            { "field_name": "intensity", "type": "f32", "value": 0.5 },
+                            { "field_name": "count", "type": "u32", "value": 7 },
                             { "field_name": "offset", "type": "i32", "value": -3 },
                             {
                                 "field_name": "weights",
@@ -1088,6 +2125,9 @@ fn view_bottom_left_absolute() {
                     position_horizontal: scene::HorizontalPosition::LeftOffset(20.0),
                     position_vertical: scene::VerticalPosition::BottomOffset(10.0),
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: None,
                 }),
                 ..view_default()
             })],
@@ -1099,258 +2139,634 @@ fn view_bottom_left_absolute() {
 // ── View rotation triggers absolute position ─────────────────────────
 
 #[test]
-fn view_rotation_absolute() {
-    check(
+fn view_rotation_absolute() {
+    check(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "top": 0,
+                    "left": 0,
+                    "width": 200,
+                    "height": 200,
+                    "rotation": 45
+                }
+            }
+        }),
+        scene::Component::View(scene::ViewComponent {
+            position: scene::Position::Absolute(scene::AbsolutePosition {
+                width: Some(200.0),
+                height: Some(200.0),
+                position_horizontal: scene::HorizontalPosition::LeftOffset(0.0),
+                position_vertical: scene::VerticalPosition::TopOffset(0.0),
+                rotation_degrees: 45.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: None,
+            }),
+            ..view_default()
+        }),
+    );
+}
+
+// ── Error: View positioning ──────────────────────────────────────────
+
+#[test]
+fn err_view_top_and_bottom() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "top": 10,
+                    "bottom": 10,
+                    "left": 0
+                }
+            }
+        }),
+        "Fields \"top\" and \"bottom\" are mutually exclusive, you can only specify one on a \"View\" component.",
+    );
+}
+
+#[test]
+fn err_input_stream_negative_chroma_key_similarity() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "input_stream",
+                    "input_id": "input_1",
+                    "chroma_key": { "similarity": -0.1 }
+                }
+            }
+        }),
+        "Chroma key values cannot be negative.",
+    );
+}
+
+#[test]
+fn err_view_negative_blur_radius() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "blur_radius": -1
+                }
+            }
+        }),
+        "Blur radius cannot be negative.",
+    );
+}
+
+#[test]
+fn err_image_negative_nine_slice_inset() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "image",
+                    "image_id": "frame_png",
+                    "nine_slice": { "left": -4 }
+                }
+            }
+        }),
+        "Nine-slice insets cannot be negative.",
+    );
+}
+
+#[test]
+fn err_input_stream_ken_burns_rect_out_of_bounds() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "input_stream",
+                    "input_id": "input_1",
+                    "ken_burns": {
+                        "start": { "top": 0, "left": 0, "width": 1, "height": 1 },
+                        "end": { "top": 0.5, "left": 0.5, "width": 0.75, "height": 0.5 },
+                        "duration_ms": 3000
+                    }
+                }
+            }
+        }),
+        "Normalized rectangle has to have a positive size and fit in the range from 0.0 to 1.0.",
+    );
+}
+
+#[test]
+fn err_input_stream_crop_zero_width() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "input_stream",
+                    "input_id": "input_1",
+                    "crop": { "type": "pixels", "top": 0, "left": 0, "width": 0, "height": 720 }
+                }
+            }
+        }),
+        "Crop \"top\" and \"left\" cannot be negative and \"width\" and \"height\" have to be positive numbers.",
+    );
+}
+
+#[test]
+fn err_audio_visualizer_too_many_bars() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "audio_visualizer",
+                    "input_id": "input_1",
+                    "width": 400,
+                    "height": 100,
+                    "mode": { "type": "bars", "count": 1000 }
+                }
+            }
+        }),
+        "AudioVisualizer bars count has to be in the range from 1 to 256.",
+    );
+}
+
+#[test]
+fn err_view_opacity_out_of_range() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "opacity": 1.5
+                }
+            }
+        }),
+        "Opacity has to be in the range from 0.0 to 1.0.",
+    );
+}
+
+#[test]
+fn err_input_stream_color_correction_brightness_out_of_range() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "input_stream",
+                    "input_id": "input_1",
+                    "color_correction": { "brightness": 1.5 }
+                }
+            }
+        }),
+        "Color correction brightness has to be in the range from -1 to 1.",
+    );
+}
+
+#[test]
+fn err_view_negative_color_correction_saturation() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "color_correction": { "saturation": -1 }
+                }
+            }
+        }),
+        "Color correction contrast and saturation cannot be negative.",
+    );
+}
+
+#[test]
+fn err_view_background_and_background_color() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "background_color": "#FF0000FF",
+                    "background": { "type": "color", "color": "#00FF00FF" }
+                }
+            }
+        }),
+        "Fields \"background\" and \"background_color\" are mutually exclusive, you can only specify one on a \"View\" component.",
+    );
+}
+
+#[test]
+fn err_view_radial_gradient_single_stop() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "background": {
+                        "type": "radial_gradient",
+                        "stops": [{ "color": "#FF0000FF" }]
+                    }
+                }
+            }
+        }),
+        "Gradient has to have from 2 to 8 stops.",
+    );
+}
+
+#[test]
+fn err_view_left_and_right() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "top": 10,
+                    "left": 10,
+                    "right": 10
+                }
+            }
+        }),
+        "Fields \"left\" and \"right\" are mutually exclusive, you can only specify one on a \"View\" component.",
+    );
+}
+
+#[test]
+fn err_view_rotation_missing_vertical() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "rotation": 45,
+                    "left": 0
+                }
+            }
+        }),
+        "\"View\" component with absolute positioning requires either \"top\" or \"bottom\" coordinate.",
+    );
+}
+
+#[test]
+fn err_view_rotation_missing_horizontal() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "rotation": 45,
+                    "top": 0
+                }
+            }
+        }),
+        "Non-static \"View\" component requires either \"left\" or \"right\" coordinate.",
+    );
+}
+
+// ── Error: Rescaler positioning ──────────────────────────────────────
+
+#[test]
+fn err_rescaler_top_and_bottom() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "rescaler",
+                    "top": 10,
+                    "bottom": 10,
+                    "left": 0,
+                    "child": { "type": "view" }
+                }
+            }
+        }),
+        "Fields \"top\" and \"bottom\" are mutually exclusive, you can only specify one on a \"Rescaler\" component.",
+    );
+}
+
+#[test]
+fn err_rescaler_left_and_right() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "rescaler",
+                    "top": 10,
+                    "left": 10,
+                    "right": 10,
+                    "child": { "type": "view" }
+                }
+            }
+        }),
+        "Fields \"left\" and \"right\" are mutually exclusive, you can only specify one on a \"Rescaler\" component.",
+    );
+}
+
+#[test]
+fn err_rescaler_rotation_missing_vertical() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "rescaler",
+                    "rotation": 45,
+                    "left": 0,
+                    "child": { "type": "view" }
+                }
+            }
+        }),
+        "\"Rescaler\" component with absolute positioning requires either \"top\" or \"bottom\" coordinate.",
+    );
+}
+
+#[test]
+fn err_rescaler_rotation_missing_horizontal() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "rescaler",
+                    "rotation": 45,
+                    "top": 0,
+                    "child": { "type": "view" }
+                }
+            }
+        }),
+        "Non-static \"Rescaler\" component requires either \"left\" or \"right\" coordinate.",
+    );
+}
+
+#[test]
+fn err_view_perspective_not_positive() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "top": 0,
+                    "left": 0,
+                    "perspective": 0
+                }
+            }
+        }),
+        "Perspective has to be a positive number.",
+    );
+}
+
+// ── Error: View negative padding ─────────────────────────────────────
+
+#[test]
+fn err_view_negative_padding() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "padding": -5
+                }
+            }
+        }),
+        "Padding values cannot be negative.",
+    );
+}
+
+#[test]
+fn err_view_negative_padding_top() {
+    check_err(
         json!({
             "video": {
                 "root": {
                     "type": "view",
-                    "top": 0,
-                    "left": 0,
-                    "width": 200,
-                    "height": 200,
-                    "rotation": 45
+                    "padding_top": -1
                 }
             }
         }),
-        scene::Component::View(scene::ViewComponent {
-            position: scene::Position::Absolute(scene::AbsolutePosition {
-                width: Some(200.0),
-                height: Some(200.0),
-                position_horizontal: scene::HorizontalPosition::LeftOffset(0.0),
-                position_vertical: scene::VerticalPosition::TopOffset(0.0),
-                rotation_degrees: 45.0,
-            }),
-            ..view_default()
-        }),
+        "Padding values cannot be negative.",
     );
 }
 
-// ── Error: View positioning ──────────────────────────────────────────
+// ── Error: Text validation ───────────────────────────────────────────
 
 #[test]
-fn err_view_top_and_bottom() {
+fn err_text_height_without_width() {
     check_err(
         json!({
             "video": {
                 "root": {
-                    "type": "view",
-                    "top": 10,
-                    "bottom": 10,
-                    "left": 0
+                    "type": "text",
+                    "text": "hello",
+                    "font_size": 20,
+                    "height": 100
                 }
             }
         }),
-        "Fields \"top\" and \"bottom\" are mutually exclusive, you can only specify one on a \"View\" component.",
+        "\"height\" property on a Text component can only be provided if \"width\" is also defined.",
     );
 }
 
 #[test]
-fn err_view_left_and_right() {
+fn err_text_font_size_zero() {
     check_err(
         json!({
             "video": {
                 "root": {
-                    "type": "view",
-                    "top": 10,
-                    "left": 10,
-                    "right": 10
+                    "type": "text",
+                    "text": "hello",
+                    "font_size": 0
                 }
             }
         }),
-        "Fields \"left\" and \"right\" are mutually exclusive, you can only specify one on a \"View\" component.",
+        "\"font_size\" property has to be larger than 0",
     );
 }
 
 #[test]
-fn err_view_rotation_missing_vertical() {
+fn err_text_font_size_negative() {
     check_err(
         json!({
             "video": {
                 "root": {
-                    "type": "view",
-                    "rotation": 45,
-                    "left": 0
+                    "type": "text",
+                    "text": "hello",
+                    "font_size": -10
                 }
             }
         }),
-        "\"View\" component with absolute positioning requires either \"top\" or \"bottom\" coordinate.",
+        "\"font_size\" property has to be larger than 0",
     );
 }
 
 #[test]
-fn err_view_rotation_missing_horizontal() {
+fn err_text_line_height_zero() {
     check_err(
         json!({
             "video": {
                 "root": {
-                    "type": "view",
-                    "rotation": 45,
-                    "top": 0
+                    "type": "text",
+                    "text": "hello",
+                    "font_size": 20,
+                    "line_height": 0
                 }
             }
         }),
-        "Non-static \"View\" component requires either \"left\" or \"right\" coordinate.",
+        "\"line_height\" property has to be larger than 0",
     );
 }
 
-// ── Error: Rescaler positioning ──────────────────────────────────────
-
 #[test]
-fn err_rescaler_top_and_bottom() {
+fn err_text_gradient_single_stop() {
     check_err(
         json!({
             "video": {
                 "root": {
-                    "type": "rescaler",
-                    "top": 10,
-                    "bottom": 10,
-                    "left": 0,
-                    "child": { "type": "view" }
+                    "type": "text",
+                    "text": "hello",
+                    "font_size": 20,
+                    "gradient": { "stops": [{ "color": "#FF0000FF" }] }
                 }
             }
         }),
-        "Fields \"top\" and \"bottom\" are mutually exclusive, you can only specify one on a \"Rescaler\" component.",
+        "Gradient has to have from 2 to 8 stops.",
     );
 }
 
 #[test]
-fn err_rescaler_left_and_right() {
+fn err_text_gradient_stops_not_ascending() {
     check_err(
         json!({
             "video": {
                 "root": {
-                    "type": "rescaler",
-                    "top": 10,
-                    "left": 10,
-                    "right": 10,
-                    "child": { "type": "view" }
+                    "type": "text",
+                    "text": "hello",
+                    "font_size": 20,
+                    "gradient": {
+                        "stops": [
+                            { "color": "#FF0000FF", "position": 0.6 },
+                            { "color": "#0000FFFF", "position": 0.2 }
+                        ]
+                    }
                 }
             }
         }),
-        "Fields \"left\" and \"right\" are mutually exclusive, you can only specify one on a \"Rescaler\" component.",
+        "Gradient stop positions have to be in ascending order.",
     );
 }
 
 #[test]
-fn err_rescaler_rotation_missing_vertical() {
+fn err_text_ticker_without_width() {
     check_err(
         json!({
             "video": {
                 "root": {
-                    "type": "rescaler",
-                    "rotation": 45,
-                    "left": 0,
-                    "child": { "type": "view" }
+                    "type": "text",
+                    "text": "hello",
+                    "font_size": 20,
+                    "ticker": { "speed": 100 }
                 }
             }
         }),
-        "\"Rescaler\" component with absolute positioning requires either \"top\" or \"bottom\" coordinate.",
+        "\"ticker\" property on a Text component can only be provided if \"width\" is also defined.",
     );
 }
 
 #[test]
-fn err_rescaler_rotation_missing_horizontal() {
+fn err_text_fit_with_ticker() {
     check_err(
         json!({
             "video": {
                 "root": {
-                    "type": "rescaler",
-                    "rotation": 45,
-                    "top": 0,
-                    "child": { "type": "view" }
+                    "type": "text",
+                    "text": "hello",
+                    "font_size": 20,
+                    "width": 500,
+                    "fit": "shrink",
+                    "ticker": { "speed": 100 }
                 }
             }
         }),
-        "Non-static \"Rescaler\" component requires either \"left\" or \"right\" coordinate.",
+        "\"fit\" and \"ticker\" properties on a Text component cannot be provided at the same time.",
     );
 }
 
-// ── Error: View negative padding ─────────────────────────────────────
-
 #[test]
-fn err_view_negative_padding() {
+fn err_text_missing_text_and_spans() {
     check_err(
         json!({
             "video": {
                 "root": {
-                    "type": "view",
-                    "padding": -5
+                    "type": "text",
+                    "font_size": 20
                 }
             }
         }),
-        "Padding values cannot be negative.",
+        "Text component requires either \"text\" or non-empty \"spans\" property.",
     );
 }
 
 #[test]
-fn err_view_negative_padding_top() {
+fn err_text_with_text_and_spans() {
     check_err(
         json!({
             "video": {
                 "root": {
-                    "type": "view",
-                    "padding_top": -1
+                    "type": "text",
+                    "text": "hello",
+                    "font_size": 20,
+                    "spans": [{ "text": "hello" }]
                 }
             }
         }),
-        "Padding values cannot be negative.",
+        "\"text\" and \"spans\" properties on a Text component cannot be provided at the same time.",
     );
 }
 
-// ── Error: Text validation ───────────────────────────────────────────
-
 #[test]
-fn err_text_height_without_width() {
+fn err_text_span_font_size_zero() {
     check_err(
         json!({
             "video": {
                 "root": {
                     "type": "text",
-                    "text": "hello",
                     "font_size": 20,
-                    "height": 100
+                    "spans": [{ "text": "hello", "font_size": 0 }]
                 }
             }
         }),
-        "\"height\" property on a Text component can only be provided if \"width\" is also defined.",
+        "\"font_size\" property of a text span has to be larger than 0",
     );
 }
 
 #[test]
-fn err_text_font_size_zero() {
+fn err_text_ticker_speed_zero() {
     check_err(
         json!({
             "video": {
                 "root": {
                     "type": "text",
                     "text": "hello",
-                    "font_size": 0
+                    "font_size": 20,
+                    "width": 500,
+                    "ticker": { "speed": 0 }
                 }
             }
         }),
-        "\"font_size\" property has to be larger than 0",
+        "Ticker speed has to be a positive number.",
     );
 }
 
 #[test]
-fn err_text_font_size_negative() {
+fn err_text_stroke_width_zero() {
     check_err(
         json!({
             "video": {
                 "root": {
                     "type": "text",
                     "text": "hello",
-                    "font_size": -10
+                    "font_size": 20,
+                    "stroke": { "width": 0 }
                 }
             }
         }),
-        "\"font_size\" property has to be larger than 0",
+        "Text stroke width has to be a positive number.",
     );
 }
 
 #[test]
-fn err_text_line_height_zero() {
+fn err_text_shadow_negative_blur_radius() {
     check_err(
         json!({
             "video": {
@@ -1358,11 +2774,11 @@ fn err_text_line_height_zero() {
                     "type": "text",
                     "text": "hello",
                     "font_size": 20,
-                    "line_height": 0
+                    "shadow": { "blur_radius": -1 }
                 }
             }
         }),
-        "\"line_height\" property has to be larger than 0",
+        "Text shadow blur radius cannot be negative.",
     );
 }
 
@@ -1490,6 +2906,21 @@ fn err_tiles_invalid_aspect_ratio_non_integer() {
     );
 }
 
+#[test]
+fn err_tiles_fixed_columns_zero() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "tiles",
+                    "fill_strategy": { "type": "fixed_columns", "columns": 0 }
+                }
+            }
+        }),
+        "Number of columns in the \"fixed_columns\" fill strategy has to be larger than 0.",
+    );
+}
+
 // ── Error: Transition cubic bezier control points ────────────────────
 
 #[test]
@@ -1557,6 +2988,116 @@ fn err_transition_negative_duration() {
     );
 }
 
+#[test]
+fn err_tile_transition_negative_duration() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "tiles",
+                    "transition": { "duration_ms": 500 },
+                    "tile_transitions": {
+                        "reorder": { "effect": "fade", "duration_ms": -100 }
+                    }
+                }
+            }
+        }),
+        "Invalid duration. cannot convert float seconds to Duration: value is negative",
+    );
+}
+
+// ── Error: Keyframes ─────────────────────────────────────────────────
+
+#[test]
+fn err_keyframes_not_sorted() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "keyframes": [
+                        {
+                            "property": "width",
+                            "keyframes": [
+                                { "time_ms": 1000, "value": 100 },
+                                { "time_ms": 0, "value": 200 }
+                            ]
+                        }
+                    ]
+                }
+            }
+        }),
+        "Keyframes have to be sorted by \"time_ms\".",
+    );
+}
+
+#[test]
+fn err_keyframes_opacity_out_of_range() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "rescaler",
+                    "keyframes": [
+                        {
+                            "property": "opacity",
+                            "keyframes": [{ "time_ms": 0, "value": 1.5 }]
+                        }
+                    ],
+                    "child": { "type": "view" }
+                }
+            }
+        }),
+        "Keyframe values of \"opacity\" have to be in the range from 0.0 to 1.0.",
+    );
+}
+
+#[test]
+fn err_keyframes_property_not_defined() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "bottom": 0,
+                    "left": 0,
+                    "keyframes": [
+                        {
+                            "property": "top",
+                            "keyframes": [{ "time_ms": 0, "value": 10 }]
+                        }
+                    ]
+                }
+            }
+        }),
+        "Keyframe tracks for \"top\", \"bottom\", \"left\" and \"right\" require the same property to be defined on the component. Keyframe tracks for \"rotation\", \"rotation_x\" and \"rotation_y\" require absolute positioning.",
+    );
+}
+
+#[test]
+fn err_keyframes_duplicated_property() {
+    check_err(
+        json!({
+            "video": {
+                "root": {
+                    "type": "view",
+                    "keyframes": [
+                        {
+                            "property": "opacity",
+                            "keyframes": [{ "time_ms": 0, "value": 1 }]
+                        },
+                        {
+                            "property": "opacity",
+                            "keyframes": [{ "time_ms": 0, "value": 0 }]
+                        }
+                    ]
+                }
+            }
+        }),
+        "Each property can be animated by only one keyframe track.",
+    );
+}
+
 // ── Serde-level errors (malformed JSON for the schema) ───────────────
 
 #[test]
@@ -1583,35 +3124,47 @@ fn err_serde_unknown_field_on_view() {
 }
 
 #[test]
-fn err_serde_rescaler_missing_child() {
+fn err_serde_view_border_radius_unknown_corner() {
     check_serde_err(json!({
         "video": {
             "root": {
-                "type": "rescaler"
+                "type": "view",
+                "border_radius": { "top": 10 }
             }
         }
     }));
 }
 
 #[test]
-fn err_serde_shader_missing_resolution() {
+fn err_serde_view_unknown_blend_mode() {
     check_serde_err(json!({
         "video": {
             "root": {
-                "type": "shader",
-                "shader_id": "s1"
+                "type": "view",
+                "blend_mode": "darken"
             }
         }
     }));
 }
 
 #[test]
-fn err_serde_text_missing_text_field() {
+fn err_serde_rescaler_missing_child() {
     check_serde_err(json!({
         "video": {
             "root": {
-                "type": "text",
-                "font_size": 20
+                "type": "rescaler"
+            }
+        }
+    }));
+}
+
+#[test]
+fn err_serde_shader_missing_resolution() {
+    check_serde_err(json!({
+        "video": {
+            "root": {
+                "type": "shader",
+                "shader_id": "s1"
             }
         }
     }));
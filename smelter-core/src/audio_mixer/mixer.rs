@@ -4,7 +4,7 @@ use std::{
     time::Duration,
 };
 
-use smelter_render::{OutputId, error::UpdateSceneError};
+use smelter_render::{AudioSamplesBuffer, OutputId, error::UpdateSceneError};
 use tracing::{debug, trace};
 
 use crate::{
@@ -39,9 +39,10 @@ use crate::prelude::*;
 pub(crate) struct AudioMixer(Arc<Mutex<InternalAudioMixer>>);
 
 impl AudioMixer {
-    pub fn new(mixing_sample_rate: u32) -> Self {
+    pub fn new(mixing_sample_rate: u32, audio_samples: AudioSamplesBuffer) -> Self {
         Self(Arc::new(Mutex::new(InternalAudioMixer::new(
             mixing_sample_rate,
+            audio_samples,
         ))))
     }
 
@@ -76,7 +77,9 @@ impl AudioMixer {
     }
 
     pub fn unregister_input(&self, input_id: &InputId) {
-        self.0.lock().unwrap().inputs.remove(input_id);
+        let mut guard = self.0.lock().unwrap();
+        guard.inputs.remove(input_id);
+        guard.audio_samples.remove_input(input_id);
     }
 
     pub fn update_output(
@@ -107,10 +110,12 @@ pub(super) struct InternalAudioMixer {
     mixing_sample_rate: u32,
     sample_mixer: SampleMixer,
     last_processed_batch_end: Option<Duration>,
+    /// Resampled input samples are also written there for audio visualizations.
+    audio_samples: AudioSamplesBuffer,
 }
 
 impl InternalAudioMixer {
-    pub fn new(mixing_sample_rate: u32) -> Self {
+    pub fn new(mixing_sample_rate: u32, audio_samples: AudioSamplesBuffer) -> Self {
         Self {
             outputs: HashMap::new(),
             inputs: HashMap::new(),
@@ -122,6 +127,7 @@ impl InternalAudioMixer {
                 VOL_UP_INCREMENT,
             ),
             last_processed_batch_end: None,
+            audio_samples,
         }
     }
 
@@ -170,7 +176,7 @@ impl InternalAudioMixer {
             }
         }
 
-        let input_samples = self
+        let input_samples: HashMap<InputId, Vec<(f64, f64)>> = self
             .inputs
             .iter_mut()
             .filter_map(|(input_id, input)| {
@@ -180,6 +186,12 @@ impl InternalAudioMixer {
             })
             .collect();
 
+        for (input_id, samples) in &input_samples {
+            let mono_samples = samples.iter().map(|(l, r)| ((l + r) / 2.0) as f32);
+            self.audio_samples
+                .push(input_id, mono_samples, self.mixing_sample_rate);
+        }
+
         let samples_count = expected_samples_count(
             samples_set.start_pts,
             samples_set.end_pts,
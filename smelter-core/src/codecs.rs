@@ -32,6 +32,10 @@ pub enum VideoDecoderOptions {
     FfmpegH264,
     FfmpegVp8,
     FfmpegVp9,
+    /// Requires a device that supports Vulkan Video H.264 decoding. The `software-fallback`
+    /// feature of `gpu-video` does not apply to this decoder, because frames are decoded
+    /// straight into wgpu textures. Use `FfmpegH264` on devices without Vulkan Video support,
+    /// inputs that don't specify a decoder already do that.
     VulkanH264,
 }
 
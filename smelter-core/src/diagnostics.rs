@@ -0,0 +1,350 @@
+//! Registry of threads and async tasks started by a pipeline. It is meant for debugging,
+//! e.g. to find out what is still running when shutdown or reset hangs.
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::{runtime::Runtime, task::JoinHandle};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::error::CancelTaskError;
+
+pub type TaskId = u64;
+
+/// Snapshot of a running pipeline task.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct TaskInfo {
+    /// Identifier that can be used to cancel the task.
+    pub id: TaskId,
+
+    /// Human-readable name of the task, e.g. "Video Encoder".
+    pub name: String,
+
+    /// Input, output or session that the task belongs to.
+    pub owner: Option<String>,
+
+    pub kind: TaskKind,
+    pub state: TaskState,
+
+    /// Time since the task was started in milliseconds.
+    pub running_for_ms: u64,
+
+    /// Whether the task can be stopped with a cancel request.
+    pub cancellable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    /// OS thread, e.g. encoder, decoder or protocol reader/writer.
+    Thread,
+    /// Task running on the pipeline's async runtime.
+    AsyncTask,
+    /// WebRTC session of WHIP input or WHEP output.
+    WebrtcSession,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    /// Thread is initializing, e.g. creating an encoder.
+    Initializing,
+    Running,
+    /// Cancel was requested, but the task did not stop yet.
+    Cancelling,
+}
+
+type CancelFn = Box<dyn FnOnce() + Send>;
+
+struct TaskEntry {
+    name: String,
+    owner: Option<String>,
+    kind: TaskKind,
+    state: TaskState,
+    started_at: Instant,
+    cancel: Option<CancelFn>,
+}
+
+#[derive(Default)]
+struct TaskRegistryInner {
+    next_id: AtomicU64,
+    tasks: Mutex<BTreeMap<TaskId, TaskEntry>>,
+}
+
+/// Tasks of a single pipeline, owned by `PipelineCtx`.
+#[derive(Clone, Default)]
+pub(crate) struct TaskRegistry(Arc<TaskRegistryInner>);
+
+impl TaskRegistry {
+    pub fn register(
+        &self,
+        name: impl Into<String>,
+        owner: Option<String>,
+        kind: TaskKind,
+    ) -> TaskGuard {
+        let id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        self.0.tasks.lock().unwrap().insert(
+            id,
+            TaskEntry {
+                name: name.into(),
+                owner,
+                kind,
+                state: TaskState::Running,
+                started_at: Instant::now(),
+                cancel: None,
+            },
+        );
+        TaskGuard {
+            id,
+            registry: self.clone(),
+        }
+    }
+
+    /// Spawns the future on the runtime and lists it until it finishes. Cancelling the task
+    /// aborts it.
+    pub fn spawn_tracked<F>(
+        &self,
+        rt: &Runtime,
+        name: impl Into<String>,
+        owner: Option<String>,
+        future: F,
+    ) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let guard = self.register(name, owner, TaskKind::AsyncTask);
+        let id = guard.id;
+        let handle = rt.spawn(async move {
+            let _guard = guard;
+            future.await
+        });
+
+        let abort_handle = handle.abort_handle();
+        self.set_cancel(id, Box::new(move || abort_handle.abort()));
+        handle
+    }
+
+    pub fn list(&self) -> Vec<TaskInfo> {
+        self.0
+            .tasks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, task)| TaskInfo {
+                id: *id,
+                name: task.name.clone(),
+                owner: task.owner.clone(),
+                kind: task.kind,
+                state: task.state,
+                running_for_ms: task.started_at.elapsed().as_millis() as u64,
+                cancellable: task.cancel.is_some(),
+            })
+            .collect()
+    }
+
+    pub fn cancel(&self, id: TaskId) -> Result<(), CancelTaskError> {
+        let cancel = {
+            let mut tasks = self.0.tasks.lock().unwrap();
+            let task = tasks.get_mut(&id).ok_or(CancelTaskError::NotFound(id))?;
+            let cancel = task
+                .cancel
+                .take()
+                .ok_or_else(|| CancelTaskError::NotCancellable(id, task.name.clone()))?;
+            task.state = TaskState::Cancelling;
+            info!(id, name = %task.name, owner = ?task.owner, "Cancelling task.");
+            cancel
+        };
+        // Called without the lock, cancelling might drop the guard of this or other tasks.
+        cancel();
+        Ok(())
+    }
+
+    fn set_cancel(&self, id: TaskId, cancel: CancelFn) {
+        if let Some(task) = self.0.tasks.lock().unwrap().get_mut(&id) {
+            task.cancel = Some(cancel);
+        }
+    }
+}
+
+impl std::fmt::Debug for TaskRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskRegistry")
+            .field("tasks", &self.0.tasks.lock().unwrap().len())
+            .finish()
+    }
+}
+
+/// Keeps the task listed until it is dropped.
+pub(crate) struct TaskGuard {
+    id: TaskId,
+    registry: TaskRegistry,
+}
+
+impl TaskGuard {
+    pub fn set_state(&self, state: TaskState) {
+        if let Some(task) = self.registry.0.tasks.lock().unwrap().get_mut(&self.id) {
+            task.state = state;
+        }
+    }
+
+    /// Makes the task cancellable. `cancel` is called at most once.
+    pub fn set_cancel(&self, cancel: impl FnOnce() + Send + 'static) {
+        self.registry.set_cancel(self.id, Box::new(cancel));
+    }
+
+    /// Makes the task cancellable, the task has to check the returned token
+    /// and stop on its own.
+    pub fn cancel_token(&self) -> CancelToken {
+        let token = CancelToken::default();
+        let token_clone = token.clone();
+        self.set_cancel(move || token_clone.cancel());
+        token
+    }
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.registry.0.tasks.lock().unwrap().remove(&self.id);
+    }
+}
+
+/// Set when the task is cancelled with [`TaskRegistry::cancel`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    #[test]
+    fn register_and_list() {
+        let registry = TaskRegistry::default();
+        let encoder = registry.register(
+            "Video Encoder",
+            Some("Output out_1".to_string()),
+            TaskKind::Thread,
+        );
+        encoder.set_state(TaskState::Initializing);
+        let _session = registry.register("WHIP session", None, TaskKind::WebrtcSession);
+
+        let tasks = registry.list();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].name, "Video Encoder");
+        assert_eq!(tasks[0].owner.as_deref(), Some("Output out_1"));
+        assert_eq!(tasks[0].kind, TaskKind::Thread);
+        assert_eq!(tasks[0].state, TaskState::Initializing);
+        assert!(!tasks[0].cancellable);
+        assert_eq!(tasks[1].kind, TaskKind::WebrtcSession);
+        assert_eq!(tasks[1].state, TaskState::Running);
+        assert_ne!(tasks[0].id, tasks[1].id);
+    }
+
+    #[test]
+    fn deregister_on_guard_drop() {
+        let registry = TaskRegistry::default();
+        let first = registry.register("first", None, TaskKind::Thread);
+        let second = registry.register("second", None, TaskKind::Thread);
+
+        drop(first);
+        let tasks = registry.list();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].name, "second");
+
+        drop(second);
+        assert!(registry.list().is_empty());
+    }
+
+    #[test]
+    fn cancel_calls_cancel_fn_once() {
+        let registry = TaskRegistry::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let task = registry.register("task", None, TaskKind::AsyncTask);
+        let calls_clone = calls.clone();
+        task.set_cancel(move || {
+            calls_clone.fetch_add(1, Ordering::Relaxed);
+        });
+        let id = registry.list()[0].id;
+        assert!(registry.list()[0].cancellable);
+
+        registry.cancel(id).unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        let tasks = registry.list();
+        assert_eq!(tasks[0].state, TaskState::Cancelling);
+        assert!(!tasks[0].cancellable);
+        assert!(matches!(
+            registry.cancel(id),
+            Err(CancelTaskError::NotCancellable(_, _))
+        ));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn cancel_token() {
+        let registry = TaskRegistry::default();
+        let task = registry.register("thread", None, TaskKind::Thread);
+        let token = task.cancel_token();
+        assert!(!token.is_cancelled());
+
+        registry.cancel(registry.list()[0].id).unwrap();
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn cancel_errors() {
+        let registry = TaskRegistry::default();
+        let task = registry.register("not cancellable", None, TaskKind::Thread);
+        let id = registry.list()[0].id;
+
+        assert!(matches!(
+            registry.cancel(id),
+            Err(CancelTaskError::NotCancellable(_, _))
+        ));
+        assert_eq!(registry.list()[0].state, TaskState::Running);
+
+        drop(task);
+        assert!(matches!(
+            registry.cancel(id),
+            Err(CancelTaskError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn registries_are_isolated() {
+        let first = TaskRegistry::default();
+        let second = TaskRegistry::default();
+        let task = first.register("task", None, TaskKind::Thread);
+        let token = task.cancel_token();
+        let id = first.list()[0].id;
+
+        assert!(second.list().is_empty());
+        assert!(matches!(
+            second.cancel(id),
+            Err(CancelTaskError::NotFound(_))
+        ));
+        assert!(!token.is_cancelled());
+    }
+}
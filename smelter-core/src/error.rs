@@ -9,7 +9,7 @@ use smelter_render::{
 #[cfg(feature = "gpu-video")]
 use gpu_video::VideoEncoderError;
 
-use crate::{graphics_context::CreateGraphicsContextError, prelude::*};
+use crate::{diagnostics::TaskId, graphics_context::CreateGraphicsContextError, prelude::*};
 
 #[derive(Debug, Clone, Copy)]
 pub enum ErrorSeverity {
@@ -99,6 +99,11 @@ pub enum RegisterOutputError {
     )]
     NoVideoAndAudio(OutputId),
 
+    #[error(
+        "Failed to register output stream \"{0}\". Debug overlay is not supported for outputs that receive frames as GPU textures, e.g. with Vulkan encoder."
+    )]
+    DebugOverlayNotSupported(OutputId),
+
     #[error("Unknown error: {0}")]
     UnknownError(String),
 }
@@ -126,6 +131,15 @@ pub enum UnregisterInputError {
     StillInUse(InputId),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum CancelTaskError {
+    #[error("Task {0} does not exist, it might have already finished.")]
+    NotFound(TaskId),
+
+    #[error("Task {0} (\"{1}\") can not be cancelled.")]
+    NotCancellable(TaskId, String),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum UnregisterOutputError {
     #[error("Failed to unregister output stream. Stream \"{0}\" does not exist.")]
@@ -307,6 +321,9 @@ pub enum DecoderInitError {
 
     #[error(transparent)]
     FfmpegError(#[from] ffmpeg_next::Error),
+
+    #[error("No custom RTP decoder is registered for payload type {0}.")]
+    RtpDecoderNotRegistered(u8),
 }
 
 pub enum ErrorType {
@@ -343,6 +360,7 @@ const INPUT_ERROR: &str = "INPUT_STREAM_INPUT_ERROR";
 
 const RESOURCE_DOES_NOT_EXIST: &str = "RESOURCE_DOES_NOT_EXIST";
 const INVALID_MP4_SOURCE: &str = "INVALID_MP4_SOURCE";
+const MP4_DECRYPTION_ERROR: &str = "MP4_DECRYPTION_ERROR";
 const WHEP_INVALID_SERVER_URL: &str = "WHEP_INVALID_SERVER_URL";
 const WHEP_REQUEST_FAILED: &str = "WHEP_REQUEST_FAILED";
 const WHEP_BAD_STATUS: &str = "WHEP_BAD_STATUS";
@@ -389,6 +407,10 @@ impl From<&RegisterInputError> for PipelineErrorInfo {
             RegisterInputError::InputError(_, InputInitError::Mp4(Mp4InputError::IoError(_))) => {
                 PipelineErrorInfo::new(INVALID_MP4_SOURCE, ErrorType::UserError)
             }
+            RegisterInputError::InputError(
+                _,
+                InputInitError::Mp4(Mp4InputError::Decryption(_)),
+            ) => PipelineErrorInfo::new(MP4_DECRYPTION_ERROR, ErrorType::UserError),
 
             // MoQ Server
             RegisterInputError::InputError(
@@ -426,6 +448,7 @@ const OUTPUT_STREAM_ALREADY_REGISTERED: &str = "OUTPUT_STREAM_ALREADY_REGISTERED
 const OUTPUT_ERROR: &str = "OUTPUT_STREAM_OUTPUT_ERROR";
 const UNSUPPORTED_RESOLUTION: &str = "UNSUPPORTED_RESOLUTION";
 const NO_VIDEO_OR_AUDIO_FOR_OUTPUT: &str = "NO_VIDEO_OR_AUDIO_FOR_OUTPUT";
+const DEBUG_OVERLAY_NOT_SUPPORTED: &str = "DEBUG_OVERLAY_NOT_SUPPORTED";
 const UNKNOWN_REGISTER_OUTPUT_ERROR: &str = "UNKNOWN_REGISTER_OUTPUT_ERROR";
 
 const RTMP_CONNECTION_FAILED: &str = "RTMP_CONNECTION_FAILED";
@@ -504,6 +527,9 @@ impl From<&RegisterOutputError> for PipelineErrorInfo {
             RegisterOutputError::NoVideoAndAudio(_) => {
                 PipelineErrorInfo::new(NO_VIDEO_OR_AUDIO_FOR_OUTPUT, ErrorType::UserError)
             }
+            RegisterOutputError::DebugOverlayNotSupported(_) => {
+                PipelineErrorInfo::new(DEBUG_OVERLAY_NOT_SUPPORTED, ErrorType::UserError)
+            }
             RegisterOutputError::UnknownError(_) => {
                 PipelineErrorInfo::new(UNKNOWN_REGISTER_OUTPUT_ERROR, ErrorType::ServerError)
             }
@@ -561,6 +587,22 @@ impl From<&UnregisterOutputError> for PipelineErrorInfo {
     }
 }
 
+const TASK_NOT_FOUND: &str = "TASK_NOT_FOUND";
+const TASK_NOT_CANCELLABLE: &str = "TASK_NOT_CANCELLABLE";
+
+impl From<&CancelTaskError> for PipelineErrorInfo {
+    fn from(err: &CancelTaskError) -> Self {
+        match err {
+            CancelTaskError::NotFound(_) => {
+                PipelineErrorInfo::new(TASK_NOT_FOUND, ErrorType::EntityNotFound)
+            }
+            CancelTaskError::NotCancellable(_, _) => {
+                PipelineErrorInfo::new(TASK_NOT_CANCELLABLE, ErrorType::UserError)
+            }
+        }
+    }
+}
+
 const BUILD_SCENE_ERROR: &str = "BUILD_SCENE_ERROR";
 
 impl From<&UpdateSceneError> for PipelineErrorInfo {
@@ -599,6 +641,7 @@ impl From<&RequestKeyframeError> for PipelineErrorInfo {
 
 const WGPU_INIT_ERROR: &str = "WGPU_INIT_ERROR";
 const LAYOUT_INIT_ERROR: &str = "LAYOUT_INIT_ERROR";
+const TEXT_INIT_ERROR: &str = "TEXT_INIT_ERROR";
 
 impl From<&InitRendererEngineError> for PipelineErrorInfo {
     fn from(err: &InitRendererEngineError) -> Self {
@@ -609,6 +652,9 @@ impl From<&InitRendererEngineError> for PipelineErrorInfo {
             InitRendererEngineError::LayoutTransformationsInitError(_) => {
                 PipelineErrorInfo::new(LAYOUT_INIT_ERROR, ErrorType::ServerError)
             }
+            InitRendererEngineError::TextTransformationsInitError(_) => {
+                PipelineErrorInfo::new(TEXT_INIT_ERROR, ErrorType::ServerError)
+            }
         }
     }
 }
@@ -616,6 +662,7 @@ impl From<&InitRendererEngineError> for PipelineErrorInfo {
 const ENTITY_ALREADY_REGISTERED: &str = "ENTITY_ALREADY_REGISTERED";
 const INVALID_SHADER: &str = "INVALID_SHADER";
 const REGISTER_IMAGE_ERROR: &str = "REGISTER_IMAGE_ERROR";
+const REGISTER_LUT_ERROR: &str = "REGISTER_LUT_ERROR";
 const REGISTER_WEB_RENDERER_ERROR: &str = "REGISTER_WEB_RENDERER_ERROR";
 
 impl From<&RegisterRendererError> for PipelineErrorInfo {
@@ -632,6 +679,9 @@ impl From<&RegisterRendererError> for PipelineErrorInfo {
             RegisterRendererError::Image(_, _) => {
                 PipelineErrorInfo::new(REGISTER_IMAGE_ERROR, ErrorType::UserError)
             }
+            RegisterRendererError::Lut(_, _) => {
+                PipelineErrorInfo::new(REGISTER_LUT_ERROR, ErrorType::UserError)
+            }
             RegisterRendererError::Web(_, _) => {
                 PipelineErrorInfo::new(REGISTER_WEB_RENDERER_ERROR, ErrorType::ServerError)
             }
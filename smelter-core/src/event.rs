@@ -1,8 +1,8 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 
 use crossbeam_channel::Receiver;
 use smelter_render::{
-    InputId, OutputId,
+    Framerate, InputId, OutputId, Resolution,
     error::ErrorStack,
     event_handler::{self, Emitter, emit_event},
 };
@@ -20,7 +20,22 @@ pub enum Event {
     VideoInputStreamPaused(InputId),
     AudioInputStreamEos(InputId),
     VideoInputStreamEos(InputId),
+    /// Remote peer stopped responding and the session was closed.
+    InputDisconnected(InputId),
+    /// Capture device detected a new video format on the input signal and the input was
+    /// reconfigured to use it.
+    VideoInputFormatChanged {
+        input_id: InputId,
+        format: InputVideoFormat,
+    },
+    /// Ancillary data packet (e.g. captions) was captured together with a video frame.
+    InputAncillaryData {
+        input_id: InputId,
+        packet: InputAncillaryPacket,
+    },
     OutputDone(OutputId),
+    /// Remote peer of an output session stopped responding and the session was closed.
+    OutputDisconnected(OutputId),
     OutputError {
         output_id: OutputId,
         severity: ErrorSeverity,
@@ -28,6 +43,87 @@ pub enum Event {
     },
 }
 
+/// Video format of a capture device input signal.
+#[derive(Debug, Clone)]
+pub struct InputVideoFormat {
+    /// Name of the display mode reported by the device, e.g. "1080p59.94".
+    pub display_mode: String,
+    pub resolution: Resolution,
+    pub framerate: Framerate,
+    pub pixel_format: InputVideoPixelFormat,
+    pub field_dominance: InputFieldDominance,
+}
+
+/// Pixel format in which frames are captured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputVideoPixelFormat {
+    /// 8-bit YCbCr 4:2:2
+    Uyvy422,
+    /// 10-bit YCbCr 4:2:2
+    V210,
+    /// 8-bit RGB
+    Bgra,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFieldDominance {
+    Progressive,
+    ProgressiveSegmented,
+    UpperFieldFirst,
+    LowerFieldFirst,
+    Unknown,
+}
+
+/// Ancillary data packet captured from the vertical blanking interval of an input signal.
+#[derive(Debug, Clone)]
+pub struct InputAncillaryPacket {
+    pub kind: InputAncillaryDataKind,
+    /// PTS of the video frame that carried the packet.
+    pub pts: Duration,
+    pub line_number: u32,
+    /// User data words of the packet.
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputAncillaryDataKind {
+    /// CEA-708 closed captions (SMPTE 334, DID 0x61, SDID 0x01)
+    Cea708,
+    /// SCTE-104 messages (DID 0x41, SDID 0x07)
+    Scte104,
+}
+
+impl InputVideoPixelFormat {
+    fn as_str(&self) -> &'static str {
+        match self {
+            InputVideoPixelFormat::Uyvy422 => "uyvy422",
+            InputVideoPixelFormat::V210 => "v210",
+            InputVideoPixelFormat::Bgra => "bgra",
+        }
+    }
+}
+
+impl InputFieldDominance {
+    fn as_str(&self) -> &'static str {
+        match self {
+            InputFieldDominance::Progressive => "progressive",
+            InputFieldDominance::ProgressiveSegmented => "progressive_segmented",
+            InputFieldDominance::UpperFieldFirst => "upper_field_first",
+            InputFieldDominance::LowerFieldFirst => "lower_field_first",
+            InputFieldDominance::Unknown => "unknown",
+        }
+    }
+}
+
+impl InputAncillaryDataKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            InputAncillaryDataKind::Cea708 => "cea_708",
+            InputAncillaryDataKind::Scte104 => "scte_104",
+        }
+    }
+}
+
 fn input_event(kind: &str, input_id: InputId) -> event_handler::Event {
     event_handler::Event {
         kind: kind.to_string(),
@@ -53,7 +149,47 @@ impl From<Event> for event_handler::Event {
             Event::VideoInputStreamPaused(id) => input_event("VIDEO_INPUT_PAUSED", id),
             Event::AudioInputStreamEos(id) => input_event("AUDIO_INPUT_EOS", id),
             Event::VideoInputStreamEos(id) => input_event("VIDEO_INPUT_EOS", id),
+            Event::InputDisconnected(id) => input_event("INPUT_DISCONNECTED", id),
+            Event::VideoInputFormatChanged { input_id, format } => event_handler::Event {
+                kind: "VIDEO_INPUT_FORMAT_CHANGED".to_string(),
+                properties: vec![
+                    ("input_id".to_string(), input_id.to_string()),
+                    ("display_mode".to_string(), format.display_mode),
+                    ("width".to_string(), format.resolution.width.to_string()),
+                    ("height".to_string(), format.resolution.height.to_string()),
+                    (
+                        "framerate_num".to_string(),
+                        format.framerate.num.to_string(),
+                    ),
+                    (
+                        "framerate_den".to_string(),
+                        format.framerate.den.to_string(),
+                    ),
+                    (
+                        "pixel_format".to_string(),
+                        format.pixel_format.as_str().to_string(),
+                    ),
+                    (
+                        "field_dominance".to_string(),
+                        format.field_dominance.as_str().to_string(),
+                    ),
+                ],
+            },
+            Event::InputAncillaryData { input_id, packet } => event_handler::Event {
+                kind: "INPUT_ANCILLARY_DATA".to_string(),
+                properties: vec![
+                    ("input_id".to_string(), input_id.to_string()),
+                    ("kind".to_string(), packet.kind.as_str().to_string()),
+                    ("pts_ms".to_string(), packet.pts.as_millis().to_string()),
+                    ("line_number".to_string(), packet.line_number.to_string()),
+                    (
+                        "data".to_string(),
+                        packet.data.iter().map(|b| format!("{b:02x}")).collect(),
+                    ),
+                ],
+            },
             Event::OutputDone(id) => output_event("OUTPUT_DONE", id),
+            Event::OutputDisconnected(id) => output_event("OUTPUT_DISCONNECTED", id),
             Event::OutputError {
                 output_id,
                 err,
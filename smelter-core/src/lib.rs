@@ -5,6 +5,7 @@ mod queue;
 pub use queue::QueueInputOptions;
 
 pub mod codecs;
+pub mod diagnostics;
 pub mod error;
 pub mod event;
 pub mod graphics_context;
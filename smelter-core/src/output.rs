@@ -40,6 +40,9 @@ pub enum ProtocolOutputOptions {
 pub struct RegisterOutputVideoOptions {
     pub initial: Component,
     pub end_condition: PipelineOutputEndCondition,
+    /// Burn frame index and pts into every frame. Only supported for outputs
+    /// that encode frames on CPU.
+    pub debug_overlay: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1,12 +1,18 @@
-use std::{path::Path, sync::Arc, time::Duration};
+use std::{
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use ::rtmp::TlsConfig;
 use smelter_render::{
     Framerate, RenderingMode, WgpuCtx, WgpuFeatures, web_renderer::ChromiumContext,
 };
 use tokio::runtime::Runtime;
+use tracing::warn;
 
 use crate::{
+    diagnostics::TaskRegistry,
     event::EventEmitter,
     graphics_context::GraphicsContext,
     pipeline::{
@@ -45,11 +51,27 @@ mod output;
 
 pub(crate) mod utils;
 
+pub use decoder::EncodedInputEvent;
 pub use instance::Pipeline;
 pub(crate) use moq::SelfSignedTlsError;
+pub use rtp::{
+    RtpInputEvent, RtpPacket,
+    custom_decoder::{RtpAudioDecoder, RtpVideoDecoder},
+    depayloader::{Depayloader, DepayloadingError},
+    payloader::{
+        H264RtpPayloader, OpusRtpPayloader, PayloadingError, RtpPayloader, Vp8RtpPayloader,
+        Vp9RtpPayloader,
+    },
+    registry::{
+        RtpAudioDecoderFactory, RtpCodecFactory, RtpCodecRegistry, RtpDepayloaderFactory,
+        RtpPayloaderFactory, RtpVideoDecoderFactory,
+    },
+};
 
 #[cfg(target_os = "linux")]
-pub use v4l2::{V4l2DeviceInfo, V4l2FormatInfo, V4l2ResolutionInfo, list_v4l2_devices};
+pub use v4l2::{
+    V4l2DeviceInfo, V4l2FormatInfo, V4l2ResolutionInfo, list_v4l2_devices, read_v4l2_device,
+};
 
 #[derive(Debug)]
 pub struct PipelineOptions {
@@ -61,9 +83,13 @@ pub struct PipelineOptions {
     pub never_drop_output_frames: bool,
     pub ahead_of_time_processing: bool,
     pub side_channel_socket_dir: Option<Arc<Path>>,
+    /// Measure the GPU time of decoding every frame with the Vulkan H264 decoder. The results
+    /// are reported in the input stats. Adds timestamp queries to every decode submission.
+    pub gpu_decode_timing: bool,
 
     pub output_framerate: Framerate,
     pub mixing_sample_rate: u32,
+    pub rtp_timestamp_epoch: RtpTimestampEpoch,
 
     pub download_root: Arc<Path>,
 
@@ -79,13 +105,61 @@ pub struct PipelineOptions {
     pub webrtc_stun_servers: Arc<Vec<String>>,
     pub webrtc_udp_port_strategy: Option<WebrtcUdpPortStrategy>,
     pub webrtc_nat_1to1_ips: Arc<Vec<String>>,
+    pub webrtc_keepalive: WebrtcKeepaliveOptions,
 
     pub rtmp_server: PipelineRtmpServerOptions,
     pub moq_server: PipelineMoqServerOptions,
 
+    /// Custom RTP codecs used by RTP inputs and outputs.
+    pub rtp_codec_registry: RtpCodecRegistry,
+
     pub moq_disable_tls_verification: bool,
 }
 
+/// Reference point for RTP timestamps of RTP, WHIP and WHEP outputs.
+///
+/// Internally, PTS of all outputs are relative to the moment the pipeline was started. Setting an
+/// epoch shifts only RTP timestamps, encoders and muxers still get PTS relative to the pipeline
+/// start. Outputs of independent instances with synchronized clocks (e.g. via NTP or PTP) produce
+/// aligned RTP timestamps for the same wall clock time.
+///
+/// For WebRTC outputs, RTCP Sender Reports map RTP timestamps to NTP time based on the wall
+/// clock of the sending instance. With a shared epoch, frames rendered at the same wall clock time
+/// on different instances get the same RTP timestamp, so a receiver can correlate streams from
+/// multiple instances either directly or through the NTP time in Sender Reports.
+///
+/// MP4, HLS, RTMP and MoQ outputs ignore the epoch and their timestamps always start at zero.
+/// A warning is logged when such output is registered while the epoch is set.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum RtpTimestampEpoch {
+    /// Timestamps are relative to the pipeline start.
+    #[default]
+    PipelineStart,
+    /// Timestamps are relative to the Unix epoch.
+    UnixEpoch,
+    /// Timestamps are relative to the provided point in time. It has to be before the
+    /// pipeline start.
+    Anchor(SystemTime),
+}
+
+impl RtpTimestampEpoch {
+    /// Offset between the epoch and the pipeline sync point.
+    pub(crate) fn pts_offset(&self, sync_point: SystemTime) -> Duration {
+        let anchor = match self {
+            RtpTimestampEpoch::PipelineStart => return Duration::ZERO,
+            RtpTimestampEpoch::UnixEpoch => SystemTime::UNIX_EPOCH,
+            RtpTimestampEpoch::Anchor(anchor) => *anchor,
+        };
+        sync_point.duration_since(anchor).unwrap_or_else(|_| {
+            warn!(
+                ?anchor,
+                "Output PTS epoch is after the pipeline start. Falling back to pipeline start."
+            );
+            Duration::ZERO
+        })
+    }
+}
+
 #[derive(Debug)]
 pub enum PipelineWgpuOptions {
     Context(GraphicsContext),
@@ -130,6 +204,10 @@ pub(crate) struct PipelineCtx {
 
     pub mixing_sample_rate: u32,
     pub output_framerate: Framerate,
+    /// Added to output PTS when calculating RTP timestamps. See [`RtpTimestampEpoch`].
+    pub rtp_timestamp_offset: Duration,
+    /// See [`PipelineOptions::gpu_decode_timing`].
+    pub gpu_decode_timing: bool,
 
     pub download_dir: Arc<Path>,
     pub graphics_context: GraphicsContext,
@@ -138,7 +216,11 @@ pub(crate) struct PipelineCtx {
     pub stats_sender: StatsSender,
     pub webrtc_stun_servers: Arc<Vec<String>>,
     pub webrtc_setting_engine: WebrtcSettingEngineCtx,
+    pub webrtc_keepalive: WebrtcKeepaliveOptions,
     pub moq_disable_tls_verification: bool,
+    pub rtp_codec_registry: RtpCodecRegistry,
+    /// Threads, async tasks and WebRTC sessions started by this pipeline.
+    pub task_registry: TaskRegistry,
 
     tokio_rt: Arc<Runtime>,
     whip_whep_state: Option<Arc<WhipWhepPipelineState>>,
@@ -153,6 +235,42 @@ impl std::fmt::Debug for PipelineCtx {
             .field("output_framerate", &self.output_framerate)
             .field("download_dir", &self.download_dir)
             .field("event_emitter", &self.event_emitter)
+            .field("task_registry", &self.task_registry)
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rtp_timestamp_epoch_offset() {
+        let sync_point = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        assert_eq!(
+            RtpTimestampEpoch::PipelineStart.pts_offset(sync_point),
+            Duration::ZERO
+        );
+        assert_eq!(
+            RtpTimestampEpoch::UnixEpoch.pts_offset(sync_point),
+            Duration::from_secs(1_700_000_000)
+        );
+
+        let anchor = sync_point - Duration::from_millis(1500);
+        assert_eq!(
+            RtpTimestampEpoch::Anchor(anchor).pts_offset(sync_point),
+            Duration::from_millis(1500)
+        );
+    }
+
+    #[test]
+    fn rtp_timestamp_epoch_after_pipeline_start() {
+        let sync_point = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let anchor = sync_point + Duration::from_secs(1);
+        assert_eq!(
+            RtpTimestampEpoch::Anchor(anchor).pts_offset(sync_point),
+            Duration::ZERO
+        );
+    }
+}
@@ -5,13 +5,17 @@ use std::{
 
 use crossbeam_channel::TrySendError;
 use decklink::{
-    AudioInputPacket, DetectedVideoInputFormatFlags, DisplayMode, InputCallback,
+    AudioInputPacket, DetectedVideoInputFormatFlags, DisplayMode, FieldDominance, InputCallback,
     InputCallbackResult, PixelFormat, VideoInputFlags, VideoInputFormatChangedEvents,
     VideoInputFrame,
 };
-use smelter_render::{Frame, FrameData, Resolution, error::ErrorStack};
-use tracing::{Span, debug, info, trace, warn};
+use smelter_render::{Frame, FrameData, Framerate, Resolution, error::ErrorStack};
+use tracing::{Level, Span, debug, info, span, trace, warn};
 
+use crate::event::{
+    Event, EventEmitter, InputAncillaryDataKind, InputAncillaryPacket, InputFieldDominance,
+    InputVideoFormat, InputVideoPixelFormat,
+};
 use crate::pipeline::decklink::format::{BitDepth, Colorspace, Format};
 use crate::queue::QueueSender;
 
@@ -23,6 +27,9 @@ pub(super) struct ChannelCallbackAdapter {
     video_sender: Option<QueueSender<Frame>>,
     audio_sender: Option<QueueSender<InputAudioSamples>>,
     span: Span,
+    input_id: InputId,
+    event_emitter: Arc<EventEmitter>,
+    enable_ancillary_data: bool,
 
     // I'm not sure, but I suspect that holding Arc here would create a circular
     // dependency
@@ -36,16 +43,24 @@ pub(super) struct ChannelCallbackAdapter {
 impl ChannelCallbackAdapter {
     pub(super) fn new(
         ctx: &Arc<PipelineCtx>,
-        span: Span,
+        input_ref: &Ref<InputId>,
         video_sender: Option<QueueSender<Frame>>,
         audio_sender: Option<QueueSender<InputAudioSamples>>,
         input: Weak<decklink::Input>,
         initial_format: Format,
+        enable_ancillary_data: bool,
     ) -> Self {
         Self {
             video_sender,
             audio_sender,
-            span,
+            span: span!(
+                Level::INFO,
+                "DeckLink input",
+                input_id = input_ref.to_string()
+            ),
+            input_id: input_ref.id().clone(),
+            event_emitter: ctx.event_emitter.clone(),
+            enable_ancillary_data,
             input,
             sync_point: ctx.queue_ctx.sync_point,
             audio_offset: Mutex::new(None),
@@ -82,6 +97,9 @@ impl ChannelCallbackAdapter {
             PixelFormat::Format8BitBGRA => {
                 Self::frame_from_bgra(width, height, bytes_per_row, data, pts)
             }
+            PixelFormat::Format10BitYUV => {
+                Self::frame_from_v210(width, height, bytes_per_row, data, pts)
+            }
             pixel_format => {
                 warn!(?pixel_format, "Unsupported pixel format");
                 return Ok(());
@@ -100,6 +118,37 @@ impl ChannelCallbackAdapter {
                 debug!("Failed to send frame from DeckLink. Channel closed.");
             }
         }
+
+        if self.enable_ancillary_data {
+            self.emit_ancillary_data(video_frame, pts)?;
+        }
+        Ok(())
+    }
+
+    fn emit_ancillary_data(
+        &self,
+        video_frame: &VideoInputFrame,
+        pts: Duration,
+    ) -> Result<(), decklink::DeckLinkError> {
+        for packet in video_frame.ancillary_packets()? {
+            let kind = match (packet.did, packet.sdid) {
+                (0x61, 0x01) => InputAncillaryDataKind::Cea708,
+                (0x41, 0x07) => InputAncillaryDataKind::Scte104,
+                (did, sdid) => {
+                    trace!(did, sdid, "Skipping unsupported ancillary data packet");
+                    continue;
+                }
+            };
+            self.event_emitter.emit(Event::InputAncillaryData {
+                input_id: self.input_id.clone(),
+                packet: InputAncillaryPacket {
+                    kind,
+                    pts,
+                    line_number: packet.line_number,
+                    data: packet.data,
+                },
+            });
+        }
         Ok(())
     }
 
@@ -178,6 +227,33 @@ impl ChannelCallbackAdapter {
         }
     }
 
+    fn frame_from_v210(
+        width: usize,
+        height: usize,
+        bytes_per_row: usize,
+        data: bytes::Bytes,
+        pts: Duration,
+    ) -> Frame {
+        // v210 rows are padded to 48 pixels, renderer expects no additional padding
+        let v210_bytes_per_row = width.div_ceil(48) * 128;
+        let data = if v210_bytes_per_row != bytes_per_row {
+            let mut output_buffer = bytes::BytesMut::with_capacity(v210_bytes_per_row * height);
+
+            data.chunks(bytes_per_row)
+                .map(|chunk| &chunk[..v210_bytes_per_row])
+                .for_each(|chunk| output_buffer.extend_from_slice(chunk));
+
+            output_buffer.freeze()
+        } else {
+            data
+        };
+        Frame {
+            data: FrameData::InterleavedV210(data),
+            resolution: Resolution { width, height },
+            pts,
+        }
+    }
+
     fn handle_audio_packet(
         &self,
         audio_packet: &mut AudioInputPacket,
@@ -238,15 +314,14 @@ impl ChannelCallbackAdapter {
         *self.last_format.lock().unwrap() = new_format;
 
         let pixel_format = match new_format.colorspace {
-            Colorspace::YCbCr422 => {
-                if new_format.bit_depth != BitDepth::Depth8Bit {
-                    warn!(
-                        "Format changed to {:?}. Forcing 8-bit.",
-                        new_format.bit_depth
-                    )
+            Colorspace::YCbCr422 => match new_format.bit_depth {
+                BitDepth::Depth8Bit => PixelFormat::Format8BitYUV,
+                BitDepth::Depth10Bit => PixelFormat::Format10BitYUV,
+                bit_depth => {
+                    warn!("Format changed to {bit_depth:?}. Forcing 8-bit.");
+                    PixelFormat::Format8BitYUV
                 }
-                PixelFormat::Format8BitYUV
-            }
+            },
             Colorspace::RGB444 => {
                 if new_format.bit_depth != BitDepth::Depth8Bit {
                     warn!(
@@ -277,8 +352,48 @@ impl ChannelCallbackAdapter {
         *self.video_offset.lock().unwrap() = None;
         *self.audio_offset.lock().unwrap() = None;
 
+        let format = Self::input_video_format(&display_mode, pixel_format)?;
+        self.event_emitter.emit(Event::VideoInputFormatChanged {
+            input_id: self.input_id.clone(),
+            format,
+        });
+
         Ok(())
     }
+
+    fn input_video_format(
+        display_mode: &DisplayMode,
+        pixel_format: PixelFormat,
+    ) -> Result<InputVideoFormat, decklink::DeckLinkError> {
+        // DeckLink returns frame duration and time scale
+        let frame_rate = display_mode.display_mode_frame_rate()?;
+        let pixel_format = match pixel_format {
+            PixelFormat::Format10BitYUV => InputVideoPixelFormat::V210,
+            PixelFormat::Format8BitBGRA => InputVideoPixelFormat::Bgra,
+            _ => InputVideoPixelFormat::Uyvy422,
+        };
+        let field_dominance = match display_mode.field_dominance()? {
+            FieldDominance::ProgressiveFrame => InputFieldDominance::Progressive,
+            FieldDominance::ProgressiveSegmentedFrame => InputFieldDominance::ProgressiveSegmented,
+            FieldDominance::UpperFieldFirst => InputFieldDominance::UpperFieldFirst,
+            FieldDominance::LowerFieldFirst => InputFieldDominance::LowerFieldFirst,
+            _ => InputFieldDominance::Unknown,
+        };
+
+        Ok(InputVideoFormat {
+            display_mode: display_mode.name()?,
+            resolution: Resolution {
+                width: display_mode.width(),
+                height: display_mode.height(),
+            },
+            framerate: Framerate {
+                num: frame_rate.den as u32,
+                den: frame_rate.num as u32,
+            },
+            pixel_format,
+            field_dominance,
+        })
+    }
 }
 
 impl InputCallback for ChannelCallbackAdapter {
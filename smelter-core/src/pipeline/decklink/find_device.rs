@@ -1,12 +1,46 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
 use decklink::{
     FlagAttributeId, IntegerAttributeId, StringAttributeId, VideoIOSupport, get_decklinks,
 };
+use tracing::info;
+
+use super::{DeckLinkDeviceInfo, DeckLinkInputError, DeckLinkInputOptions, DeckLinkProfileId};
 
-use super::{DeckLinkDeviceInfo, DeckLinkInputError, DeckLinkInputOptions};
+const PROFILE_ACTIVATION_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub(super) fn find_decklink(
     opts: &DeckLinkInputOptions,
 ) -> Result<decklink::DeckLink, DeckLinkInputError> {
+    let mut decklink = select_decklink(opts)?;
+
+    if let Some(profile_id) = opts.profile
+        && activate_profile(&decklink, profile_id)?
+    {
+        // Sub-devices are enumerated again after the profile change
+        decklink = select_decklink(opts)?;
+    }
+
+    let attr = decklink.profile_attributes()?;
+    let video_io_support = VideoIOSupport::from(
+        attr.get_integer(IntegerAttributeId::VideoIOSupport)?
+            .ok_or(DeckLinkInputError::NoCaptureSupport)?,
+    );
+    if !video_io_support.capture {
+        return Err(DeckLinkInputError::NoCaptureSupport);
+    }
+
+    if attr.get_flag(FlagAttributeId::SupportsInputFormatDetection)? != Some(true) {
+        return Err(DeckLinkInputError::NoInputFormatDetection);
+    }
+
+    Ok(decklink)
+}
+
+fn select_decklink(opts: &DeckLinkInputOptions) -> Result<decklink::DeckLink, DeckLinkInputError> {
     let decklinks = get_decklinks()?;
 
     let decklinks_info = decklinks
@@ -21,6 +55,7 @@ pub(super) fn find_decklink(
                 subdevice_index: attr
                     .get_integer(IntegerAttributeId::SubDeviceIndex)?
                     .map(|i| i as u32),
+                active_profile: active_profile(decklink)?,
             })
         })
         .collect::<Result<_, DeckLinkInputError>>()?;
@@ -58,17 +93,50 @@ fn is_selected_decklink(
         return Ok(false);
     }
 
-    let video_io_support = VideoIOSupport::from(
-        attr.get_integer(IntegerAttributeId::VideoIOSupport)?
-            .ok_or(DeckLinkInputError::NoCaptureSupport)?,
-    );
-    if !video_io_support.capture {
-        return Err(DeckLinkInputError::NoCaptureSupport);
+    Ok(true)
+}
+
+fn active_profile(
+    decklink: &decklink::DeckLink,
+) -> Result<Option<DeckLinkProfileId>, DeckLinkInputError> {
+    let Some(manager) = decklink.profile_manager()? else {
+        return Ok(None);
+    };
+    for profile in manager.profiles()? {
+        if profile.is_active()? {
+            return Ok(Some(profile.id()?));
+        }
     }
+    Ok(None)
+}
 
-    if attr.get_flag(FlagAttributeId::SupportsInputFormatDetection)? != Some(true) {
-        return Err(DeckLinkInputError::NoInputFormatDetection);
+/// Activates the profile on the card that the device belongs to. Returns `true`
+/// if the active profile was changed.
+fn activate_profile(
+    decklink: &decklink::DeckLink,
+    profile_id: DeckLinkProfileId,
+) -> Result<bool, DeckLinkInputError> {
+    let profile = match decklink.profile_manager()? {
+        Some(manager) => manager.profile(profile_id)?,
+        None => None,
+    };
+    let Some(profile) = profile else {
+        return Err(DeckLinkInputError::ProfileNotSupported(profile_id));
+    };
+    if profile.is_active()? {
+        return Ok(false);
     }
 
+    info!(?profile_id, "Activating DeckLink profile.");
+    profile.set_active()?;
+
+    // Activation completes asynchronously
+    let start = Instant::now();
+    while !profile.is_active()? {
+        if start.elapsed() > PROFILE_ACTIVATION_TIMEOUT {
+            return Err(DeckLinkInputError::ProfileActivationTimeout(profile_id));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
     Ok(true)
 }
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use tracing::{Level, error, span};
+use tracing::error;
 
 use crate::pipeline::input::Input;
 use crate::queue::{QueueTrackOffset, QueueTrackOptions};
@@ -37,6 +37,13 @@ const AUDIO_SAMPLE_RATE: u32 = 48_000;
 /// - On format change, streams are paused, video is re-enabled with the new mode,
 ///   streams are flushed and restarted, and video/audio offsets are reset (recomputed
 ///   on the next packet).
+/// - After the input is reconfigured, `Event::VideoInputFormatChanged` is emitted with the
+///   new display mode, pixel format and field dominance.
+///
+/// ### Ancillary data
+/// - If `enable_ancillary_data` is set, ancillary packets of every captured frame are read
+///   and CEA-708 and SCTE-104 packets are emitted as `Event::InputAncillaryData` with the
+///   PTS of the frame. Other packets are ignored.
 ///
 /// ### Unsupported scenarios
 /// - If ahead of time processing is enabled, initial registration will happen on pts already
@@ -54,11 +61,6 @@ impl DeckLink {
         input_ref: Ref<InputId>,
         opts: DeckLinkInputOptions,
     ) -> Result<(Input, InputInitInfo, QueueInput), InputInitError> {
-        let span = span!(
-            Level::INFO,
-            "DeckLink input",
-            input_id = input_ref.to_string()
-        );
         let input = Arc::new(
             find_decklink(&opts)?
                 .input()
@@ -94,11 +96,12 @@ impl DeckLink {
         });
         let callback = ChannelCallbackAdapter::new(
             &ctx,
-            span,
+            &input_ref,
             video_sender,
             audio_sender,
             Arc::<decklink::Input>::downgrade(&input),
             Format::new(initial_mode, initial_pixel_format),
+            opts.enable_ancillary_data,
         );
         input
             .set_callback(Box::new(callback))
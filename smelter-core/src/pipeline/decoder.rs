@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use smelter_render::Frame;
 
@@ -34,7 +34,7 @@ pub mod fdk_aac;
 pub mod libopus;
 
 #[derive(Debug)]
-pub(crate) enum EncodedInputEvent {
+pub enum EncodedInputEvent {
     Chunk(EncodedInputChunk),
     LostData,
     AuDelimiter,
@@ -57,6 +57,12 @@ pub(crate) trait VideoDecoder: Sized + VideoDecoderInstance {
 pub(crate) trait VideoDecoderInstance {
     fn decode(&mut self, chunk: EncodedInputEvent) -> Vec<Frame>;
     fn flush(&mut self) -> Vec<Frame>;
+
+    /// GPU time spent decoding the frames returned since the last call. Only decoders that
+    /// measure it return anything.
+    fn take_gpu_decode_times(&mut self) -> Vec<Duration> {
+        Vec::new()
+    }
 }
 
 pub(crate) trait BytestreamTransformer: Send + 'static {
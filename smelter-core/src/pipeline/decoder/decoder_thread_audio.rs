@@ -3,6 +3,7 @@ use std::{marker::PhantomData, sync::Arc, time::Duration};
 use tracing::warn;
 
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     pipeline::decoder::{AudioDecoderStream, DecoderThreadHandle, EncodedInputEvent},
     queue::QueueSender,
     utils::{InitializableThread, ThreadMetadata, channel::duration_bounded},
@@ -61,8 +62,8 @@ where
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.samples_sender.send(event).is_err() {
                 warn!("Failed to send decoded audio samples from decoder. Channel closed.");
                 return;
@@ -70,6 +71,10 @@ where
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.ctx.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: format!("Audio Decoder ({})", Decoder::LABEL),
@@ -5,6 +5,7 @@ use tracing::warn;
 
 use crate::{
     PipelineCtx, PipelineEvent,
+    diagnostics::{CancelToken, TaskRegistry},
     error::DecoderInitError,
     pipeline::decoder::{
         BytestreamTransformStream, BytestreamTransformer, DecoderThreadHandle, EncodedInputEvent,
@@ -44,7 +45,7 @@ where
         let VideoDecoderThreadOptions {
             ctx,
             transformer,
-            frame_sender,
+            mut frame_sender,
             input_buffer_size: buffer_size,
         } = options;
         let (chunk_sender, chunk_receiver) = duration_bounded(buffer_size);
@@ -59,7 +60,11 @@ where
                 }
             });
 
-        let decoder_stream = VideoDecoderStream::<Decoder, _>::new(ctx, transformed_bytestream)?;
+        let decoder_stream = VideoDecoderStream::<Decoder, _>::new(
+            ctx,
+            transformed_bytestream,
+            frame_sender.take_stats_callback(),
+        )?;
 
         let state = Self {
             stream: Box::new(decoder_stream.flatten()),
@@ -71,8 +76,8 @@ where
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.frame_sender.send(event).is_err() {
                 warn!("Failed to send decoded video frame from decoder. Channel closed.");
                 return;
@@ -80,6 +85,10 @@ where
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.ctx.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: format!("Video Decoder ({})", Decoder::LABEL),
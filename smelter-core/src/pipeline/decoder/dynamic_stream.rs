@@ -51,6 +51,7 @@ where
     source: Source,
     decoders_info: VideoDecoderMapping,
     keyframe_request_sender: KeyframeRequestSender,
+    on_stats_event: Box<dyn FnMut(VideoFramesStatsEvent) + Send>,
 }
 
 impl<Source> DynamicVideoDecoderStream<Source>
@@ -62,6 +63,7 @@ where
         decoders_info: VideoDecoderMapping,
         source: Source,
         keyframe_request_sender: KeyframeRequestSender,
+        on_stats_event: Box<dyn FnMut(VideoFramesStatsEvent) + Send>,
     ) -> Self {
         Self {
             ctx,
@@ -70,6 +72,16 @@ where
             source,
             decoders_info,
             keyframe_request_sender,
+            on_stats_event,
+        }
+    }
+
+    fn report_gpu_decode_times(&mut self) {
+        let Some(decoder) = self.decoder.as_mut() else {
+            return;
+        };
+        for duration in decoder.take_gpu_decode_times() {
+            (self.on_stats_event)(VideoFramesStatsEvent::GpuDecodeTime(duration));
         }
     }
 
@@ -144,7 +156,9 @@ where
                     self.ensure_decoder(chunk.kind);
                 }
                 let decoder = self.decoder.as_mut()?;
-                Some(decoder.decode(event))
+                let frames = decoder.decode(event);
+                self.report_gpu_decode_times();
+                Some(frames)
             }
             Some(PipelineEvent::EOS) | None => {
                 let chunks = self
@@ -152,6 +166,7 @@ where
                     .as_mut()
                     .map(|decoder| decoder.flush())
                     .unwrap_or_default();
+                self.report_gpu_decode_times();
                 match chunks.is_empty() {
                     false => Some(chunks),
                     true => None,
@@ -14,6 +14,7 @@ where
 {
     decoder: Decoder,
     source: Source,
+    on_stats_event: Box<dyn FnMut(VideoFramesStatsEvent) + Send>,
 }
 
 impl<Decoder, Source> VideoDecoderStream<Decoder, Source>
@@ -21,9 +22,23 @@ where
     Decoder: VideoDecoder,
     Source: Iterator<Item = PipelineEvent<EncodedInputEvent>>,
 {
-    pub fn new(ctx: Arc<PipelineCtx>, source: Source) -> Result<Self, DecoderInitError> {
+    pub fn new(
+        ctx: Arc<PipelineCtx>,
+        source: Source,
+        on_stats_event: Box<dyn FnMut(VideoFramesStatsEvent) + Send>,
+    ) -> Result<Self, DecoderInitError> {
         let decoder = Decoder::new(&ctx, None)?;
-        Ok(Self { decoder, source })
+        Ok(Self {
+            decoder,
+            source,
+            on_stats_event,
+        })
+    }
+
+    fn report_gpu_decode_times(&mut self) {
+        for duration in self.decoder.take_gpu_decode_times() {
+            (self.on_stats_event)(VideoFramesStatsEvent::GpuDecodeTime(duration));
+        }
     }
 }
 
@@ -36,9 +51,14 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.source.next() {
-            Some(PipelineEvent::Data(event)) => Some(self.decoder.decode(event)),
+            Some(PipelineEvent::Data(event)) => {
+                let frames = self.decoder.decode(event);
+                self.report_gpu_decode_times();
+                Some(frames)
+            }
             Some(PipelineEvent::EOS) | None => {
                 let chunks = self.decoder.flush();
+                self.report_gpu_decode_times();
                 match chunks.is_empty() {
                     false => Some(chunks),
                     true => None,
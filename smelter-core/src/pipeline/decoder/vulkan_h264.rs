@@ -3,7 +3,9 @@ use std::{sync::Arc, time::Duration};
 use gpu_video::{
     H264DecoderEvent, ReferenceManagementError, VideoDecoderError, VideoDeviceExt,
     WgpuTexturesDecoder,
-    parameters::{DecoderParameters, DecoderUsage, MissedFrameHandling},
+    parameters::{
+        DecoderParameters, DecoderUsage, Deinterlacing, MissedFrameHandling, PixelFormat,
+    },
 };
 use smelter_render::{Frame, FrameData, Resolution};
 use tracing::{debug, info, trace, warn};
@@ -17,6 +19,7 @@ pub struct VulkanH264Decoder {
     decoder: WgpuTexturesDecoder,
     keyframe_request_sender: Option<KeyframeRequestSender>,
     drop_frames: bool,
+    gpu_decode_times: Vec<Duration>,
 }
 
 impl VideoDecoder for VulkanH264Decoder {
@@ -39,11 +42,21 @@ impl VideoDecoder for VulkanH264Decoder {
         let decoder = device.create_wgpu_textures_decoder_h264(DecoderParameters {
             missed_frame_handling: MissedFrameHandling::Strict,
             usage_flags: DecoderUsage::Default,
+            enable_gpu_timing: ctx.gpu_decode_timing,
+            display_orientation: None,
+            output_pixel_format: PixelFormat::Nv12,
+            keyframes_only: false,
+            deinterlacing: Deinterlacing::Off,
+            low_latency: false,
+            max_dpb_slots: None,
+            memory_budget: None,
+            aligned_output_rows: false,
         })?;
         Ok(Self {
             decoder,
             keyframe_request_sender,
             drop_frames: false,
+            gpu_decode_times: Vec::new(),
         })
     }
 }
@@ -58,6 +71,7 @@ impl VideoDecoderInstance for VulkanH264Decoder {
                 H264DecoderEvent::DecodeChunk(gpu_video::EncodedInputChunk {
                     data: chunk.data.as_ref(),
                     pts: Some(chunk.pts.as_micros() as u64),
+                    dts: chunk.dts.map(|dts| dts.as_micros() as u64),
                 })
             }
             EncodedInputEvent::LostData => H264DecoderEvent::SignalDataLoss,
@@ -83,7 +97,7 @@ impl VideoDecoderInstance for VulkanH264Decoder {
 
         match self.drop_frames {
             true => Vec::new(),
-            false => frames.into_iter().map(from_vk_frame).collect(),
+            false => self.convert_frames(frames),
         }
     }
 
@@ -92,13 +106,28 @@ impl VideoDecoderInstance for VulkanH264Decoder {
             return Vec::new();
         }
         match self.decoder.flush() {
-            Ok(frames) => frames.into_iter().map(from_vk_frame).collect(),
+            Ok(frames) => self.convert_frames(frames),
             Err(err) => {
                 warn!("Failed to flush the decoder: {err}");
                 Vec::new()
             }
         }
     }
+
+    fn take_gpu_decode_times(&mut self) -> Vec<Duration> {
+        std::mem::take(&mut self.gpu_decode_times)
+    }
+}
+
+impl VulkanH264Decoder {
+    fn convert_frames(&mut self, frames: Vec<gpu_video::OutputFrame<wgpu::Texture>>) -> Vec<Frame> {
+        self.gpu_decode_times.extend(
+            frames
+                .iter()
+                .filter_map(|frame| frame.metadata.gpu_decode_duration),
+        );
+        frames.into_iter().map(from_vk_frame).collect()
+    }
 }
 
 fn from_vk_frame(frame: gpu_video::OutputFrame<wgpu::Texture>) -> Frame {
@@ -5,6 +5,7 @@ use smelter_render::Frame;
 use tracing::warn;
 
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     prelude::*,
     utils::{InitializableThread, ThreadMetadata},
 };
@@ -70,8 +71,8 @@ where
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.chunks_sender.send(event).is_err() {
                 warn!("Failed to send encoded video chunk from encoder. Channel closed.");
                 return;
@@ -79,6 +80,10 @@ where
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.ctx.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: format!("Video Encoder ({})", Encoder::LABEL),
@@ -1,8 +1,8 @@
-use std::{num::NonZero, ops::Deref, sync::Arc};
+use std::{num::NonZero, ops::Deref, sync::Arc, time::Duration};
 
 use gpu_video::{
     VideoDeviceExt, WgpuTexturesEncoderH264,
-    parameters::{EncoderParametersH264, RateControl, Rational, VideoParameters},
+    parameters::{EncoderParametersH264, PixelFormat, RateControl, Rational, VideoParameters},
 };
 use smelter_render::{FrameData, OutputFrameFormat};
 use tracing::{error, info};
@@ -73,6 +73,7 @@ impl VideoEncoder for VulkanH264Encoder {
                 numerator: framerate.num,
                 denominator: NonZero::new(u32::max(framerate.den, 1)).unwrap(),
             },
+            pixel_format: PixelFormat::Nv12,
         };
 
         let mut encoder_params = match options.preset {
@@ -132,35 +133,53 @@ impl VideoEncoder for VulkanH264Encoder {
         let result = self.encoder.encode(
             gpu_video::InputFrame {
                 data: texture.deref().clone(),
-                pts: None,
+                pts: Some(frame.pts.as_micros() as u64),
+                qp: None,
             },
             force_keyframe,
         );
 
         match result {
-            Ok(chunk) => {
+            Ok(chunks) => self.to_output_chunks(chunks),
+            Err(err) => {
+                error!("Encoder error: {err}.");
+                Vec::new()
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Vec<EncodedOutputChunk> {
+        match self.encoder.flush() {
+            Ok(chunks) => self.to_output_chunks(chunks),
+            Err(err) => {
+                error!("Encoder error: {err}.");
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl VulkanH264Encoder {
+    fn to_output_chunks(
+        &self,
+        chunks: Vec<gpu_video::EncodedOutputChunk<Vec<u8>>>,
+    ) -> Vec<EncodedOutputChunk> {
+        chunks
+            .into_iter()
+            .map(|chunk| {
                 let data = if self.bitstream_format == H264BitstreamFormat::Avcc {
                     annexb_to_avcc(&chunk.data)
                 } else {
                     chunk.data.into()
                 };
-                vec![EncodedOutputChunk {
+                EncodedOutputChunk {
                     data,
-                    pts: frame.pts,
+                    pts: Duration::from_micros(chunk.pts.unwrap_or(0)),
                     dts: None,
                     is_keyframe: chunk.is_keyframe,
                     kind: MediaKind::Video(VideoCodec::H264),
-                }]
-            }
-            Err(err) => {
-                error!("Encoder error: {err}.");
-                Vec::new()
-            }
-        }
-    }
-
-    fn flush(&mut self) -> Vec<EncodedOutputChunk> {
-        // Encoder does not store frames (this will change with B-frame support)
-        Vec::new()
+                }
+            })
+            .collect()
     }
 }
@@ -6,6 +6,7 @@ use smelter_render::{Framerate, OutputId};
 use tracing::{debug, error};
 
 use crate::{
+    diagnostics::{CancelToken, TaskKind},
     event::Event,
     pipeline::{
         encoder::{
@@ -119,6 +120,12 @@ impl HlsOutput {
             .spawn(move || {
                 let _span =
                     tracing::info_span!("HLS writer", output_id = output_ref.to_string()).entered();
+                let task = ctx.task_registry.register(
+                    "HLS writer",
+                    Some(output_ref.to_string()),
+                    TaskKind::Thread,
+                );
+                let cancel = task.cancel_token();
 
                 let stats_sender = HlsOutputStatsSender {
                     stats_sender: ctx.stats_sender.clone(),
@@ -129,6 +136,7 @@ impl HlsOutput {
                     video_stream,
                     audio_stream,
                     encoded_chunks_receiver,
+                    cancel,
                     ctx.output_framerate,
                     stats_sender,
                 );
@@ -288,6 +296,7 @@ fn run_ffmpeg_output_thread(
     mut video_stream: Option<StreamState>,
     mut audio_stream: Option<StreamState>,
     packets_receiver: Receiver<EncodedOutputEvent>,
+    cancel: CancelToken,
     framerate: Framerate,
     stats_sender: HlsOutputStatsSender,
 ) {
@@ -295,7 +304,10 @@ fn run_ffmpeg_output_thread(
     let mut received_audio_eos = audio_stream.as_ref().map(|_| false);
     let mut timestamp_offset = None;
 
-    for packet in packets_receiver {
+    for packet in packets_receiver
+        .into_iter()
+        .take_while(|_| !cancel.is_cancelled())
+    {
         match packet {
             EncodedOutputEvent::Data(chunk) => {
                 stats_sender.bytes_sent_event(chunk.data.len(), chunk.kind.into());
@@ -23,6 +23,7 @@ use smelter_render::{
 
 use crate::{
     audio_mixer::AudioMixer,
+    diagnostics::{TaskId, TaskInfo, TaskRegistry},
     event::{Event, EventEmitter},
     graphics_context::{GraphicsContext, GraphicsContextOptions},
     pipeline::{
@@ -257,6 +258,18 @@ impl Pipeline {
         }
     }
 
+    /// Lists threads, async tasks and WebRTC sessions of this pipeline that are currently
+    /// running.
+    pub fn tasks(&self) -> Vec<TaskInfo> {
+        self.ctx.task_registry.list()
+    }
+
+    /// Requests the task to stop. It is meant for debugging stuck tasks, the input or output
+    /// that owns the task is not unregistered.
+    pub fn cancel_task(&self, task_id: TaskId) -> Result<(), CancelTaskError> {
+        self.ctx.task_registry.cancel(task_id)
+    }
+
     pub fn register_font(&self, font_source: fontdb::Source) {
         self.renderer.register_font(font_source);
     }
@@ -601,12 +614,18 @@ fn create_pipeline(opts: PipelineOptions) -> Result<Pipeline, InitPipelineError>
     )?;
 
     let queue = Queue::new(queue_options);
+    let queue_ctx = queue.ctx();
+    let rtp_timestamp_offset = opts
+        .rtp_timestamp_epoch
+        .pts_offset(queue_ctx.sync_point_system_time);
     let ctx = Arc::new(PipelineCtx {
-        queue_ctx: queue.ctx(),
+        queue_ctx,
         default_buffer_duration: opts.default_buffer_duration,
 
         mixing_sample_rate: opts.mixing_sample_rate,
         output_framerate: opts.output_framerate,
+        rtp_timestamp_offset,
+        gpu_decode_timing: opts.gpu_decode_timing,
 
         download_dir,
         event_emitter: Arc::new(EventEmitter::new()),
@@ -622,9 +641,12 @@ fn create_pipeline(opts: PipelineOptions) -> Result<Pipeline, InitPipelineError>
         },
         webrtc_stun_servers: opts.webrtc_stun_servers.clone(),
         webrtc_setting_engine,
+        webrtc_keepalive: opts.webrtc_keepalive,
         rtmp_state: rtmp_state.clone(),
         moq_state: moq_state.clone(),
         moq_disable_tls_verification: opts.moq_disable_tls_verification,
+        rtp_codec_registry: opts.rtp_codec_registry.clone(),
+        task_registry: TaskRegistry::default(),
     });
 
     let whip_whep_handle = match &ctx.whip_whep_state {
@@ -645,13 +667,15 @@ fn create_pipeline(opts: PipelineOptions) -> Result<Pipeline, InitPipelineError>
         None => None,
     };
 
+    let audio_mixer = AudioMixer::new(opts.mixing_sample_rate, renderer.audio_samples());
+
     let pipeline = Pipeline {
         outputs: HashMap::new(),
         inputs: HashMap::new(),
         queue,
         renderer,
         stats_monitor,
-        audio_mixer: AudioMixer::new(opts.mixing_sample_rate),
+        audio_mixer,
         is_started: false,
         ctx,
         whip_whep_handle,
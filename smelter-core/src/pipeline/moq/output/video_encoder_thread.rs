@@ -5,6 +5,7 @@ use tokio::sync::mpsc;
 use tracing::warn;
 
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     pipeline::encoder::{VideoEncoder, VideoEncoderConfig, VideoEncoderStream},
     prelude::*,
     utils::{InitializableThread, ThreadMetadata},
@@ -67,8 +68,8 @@ where
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.chunks_sender.blocking_send(event).is_err() {
                 warn!("Failed to send encoded video chunk from encoder. Channel closed.");
                 return;
@@ -76,6 +77,10 @@ where
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.ctx.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: format!("Video Encoder ({})", Encoder::LABEL),
@@ -0,0 +1,35 @@
+//! Common Encryption (ISO/IEC 23001-7) support for MP4 input. Only clear key decryption
+//! is supported, the content key has to be provided in the register request.
+
+use bytes::Bytes;
+
+mod decrypt;
+mod parse;
+
+pub(super) use decrypt::SampleDecryptor;
+pub(super) use parse::{ProtectedTrack, ProtectionInfo, read_protection_info};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum EncryptionScheme {
+    /// AES-CTR, full sample or subsample encryption.
+    Cenc,
+    /// AES-CTR with pattern encryption.
+    Cens,
+    /// AES-CBC, full sample or subsample encryption.
+    Cbc1,
+    /// AES-CBC with pattern encryption and constant IV per subsample.
+    Cbcs,
+}
+
+/// Sample auxiliary information, empty IV means that per-sample IV is not present.
+#[derive(Debug, Clone, Default)]
+pub(super) struct SampleEncryption {
+    pub iv: Bytes,
+    pub subsamples: Vec<Subsample>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Subsample {
+    pub clear_bytes: usize,
+    pub protected_bytes: usize,
+}
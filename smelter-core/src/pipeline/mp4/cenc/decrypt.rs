@@ -0,0 +1,364 @@
+use aes::{
+    Aes128,
+    cipher::{BlockDecrypt, BlockEncrypt, KeyInit, generic_array::GenericArray},
+};
+use bytes::Bytes;
+
+use crate::prelude::*;
+
+use super::{EncryptionScheme, ProtectedTrack, SampleEncryption, Subsample};
+
+const BLOCK_SIZE: usize = 16;
+
+pub(in crate::pipeline::mp4) struct SampleDecryptor {
+    cipher: Aes128,
+    track: ProtectedTrack,
+}
+
+impl SampleDecryptor {
+    pub fn new(key: [u8; 16], track: ProtectedTrack) -> Self {
+        Self {
+            cipher: Aes128::new(&GenericArray::from(key)),
+            track,
+        }
+    }
+
+    /// `sample_index` starts from 1, the same as in the `mp4` crate.
+    pub fn decrypt(&self, sample_index: u32, data: Bytes) -> Result<Bytes, Mp4DecryptionError> {
+        if !self.track.is_protected {
+            return Ok(data);
+        }
+
+        let no_aux_info = SampleEncryption::default();
+        let sample = self
+            .track
+            .samples
+            .get(sample_index.saturating_sub(1) as usize)
+            .unwrap_or(&no_aux_info);
+
+        let iv = match (&self.track.constant_iv, sample.iv.is_empty()) {
+            (_, false) => &sample.iv,
+            (Some(constant_iv), true) => constant_iv,
+            // Protected sample can't be decrypted without an IV, passing it further would
+            // feed ciphertext to the decoder.
+            (None, true) => return Err(Mp4DecryptionError::InvalidSampleInfo(sample_index)),
+        };
+        if iv.len() > BLOCK_SIZE {
+            return Err(Mp4DecryptionError::InvalidSampleInfo(sample_index));
+        }
+        let mut iv_block = [0; BLOCK_SIZE];
+        iv_block[..iv.len()].copy_from_slice(iv);
+
+        let full_sample = [Subsample {
+            clear_bytes: 0,
+            protected_bytes: data.len(),
+        }];
+        let subsamples = match sample.subsamples.is_empty() {
+            true => &full_sample[..],
+            false => &sample.subsamples[..],
+        };
+        let subsamples_size = subsamples
+            .iter()
+            .map(|subsample| subsample.clear_bytes + subsample.protected_bytes)
+            .sum::<usize>();
+        if subsamples_size > data.len() {
+            return Err(Mp4DecryptionError::InvalidSampleInfo(sample_index));
+        }
+
+        let mut data = data.to_vec();
+        match self.track.scheme {
+            EncryptionScheme::Cenc => self.decrypt_ctr(&mut data, iv_block, subsamples, None),
+            EncryptionScheme::Cens => {
+                self.decrypt_ctr(&mut data, iv_block, subsamples, Some(self.pattern()))
+            }
+            EncryptionScheme::Cbc1 => self.decrypt_cbc(&mut data, iv_block, subsamples, None),
+            EncryptionScheme::Cbcs => {
+                self.decrypt_cbc(&mut data, iv_block, subsamples, Some(self.pattern()))
+            }
+        }
+        Ok(Bytes::from(data))
+    }
+
+    fn pattern(&self) -> Pattern {
+        Pattern {
+            crypt_blocks: self.track.crypt_byte_block as usize,
+            skip_blocks: self.track.skip_byte_block as usize,
+        }
+    }
+
+    /// Counter is continuous across all subsamples of the sample.
+    fn decrypt_ctr(
+        &self,
+        data: &mut [u8],
+        iv: [u8; BLOCK_SIZE],
+        subsamples: &[Subsample],
+        pattern: Option<Pattern>,
+    ) {
+        let mut keystream = CtrKeystream::new(&self.cipher, iv);
+        for range in protected_ranges(data, subsamples) {
+            match pattern {
+                None => keystream.apply(range),
+                Some(pattern) => {
+                    for block in pattern.encrypted_blocks(range) {
+                        keystream.apply(block);
+                    }
+                }
+            }
+        }
+    }
+
+    /// With pattern encryption (`cbcs`) the IV is reset for every subsample,
+    /// otherwise (`cbc1`) chaining continues across subsamples.
+    fn decrypt_cbc(
+        &self,
+        data: &mut [u8],
+        iv: [u8; BLOCK_SIZE],
+        subsamples: &[Subsample],
+        pattern: Option<Pattern>,
+    ) {
+        let mut previous_block = iv;
+        for range in protected_ranges(data, subsamples) {
+            if pattern.is_some() {
+                previous_block = iv;
+            }
+            // Partial block at the end of the range is not encrypted.
+            for block in pattern.unwrap_or_default().encrypted_blocks(range) {
+                let ciphertext: [u8; BLOCK_SIZE] = block.try_into().unwrap();
+                let mut decrypted = GenericArray::from(ciphertext);
+                self.cipher.decrypt_block(&mut decrypted);
+                for ((byte, decrypted), previous) in
+                    block.iter_mut().zip(decrypted).zip(previous_block)
+                {
+                    *byte = decrypted ^ previous;
+                }
+                previous_block = ciphertext;
+            }
+        }
+    }
+}
+
+fn protected_ranges<'a>(
+    data: &'a mut [u8],
+    subsamples: &[Subsample],
+) -> impl Iterator<Item = &'a mut [u8]> {
+    let mut remaining = data;
+    subsamples.iter().map(move |subsample| {
+        let data = std::mem::take(&mut remaining);
+        let (_clear, data) = data.split_at_mut(subsample.clear_bytes);
+        let (protected, rest) = data.split_at_mut(subsample.protected_bytes);
+        remaining = rest;
+        protected
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Pattern {
+    crypt_blocks: usize,
+    skip_blocks: usize,
+}
+
+impl Pattern {
+    /// Full 16-byte blocks that are encrypted. Pattern `0:0` (or default) means
+    /// that all blocks are encrypted.
+    fn encrypted_blocks(self, data: &mut [u8]) -> impl Iterator<Item = &mut [u8]> {
+        let (crypt_blocks, skip_blocks) = match self.crypt_blocks {
+            0 => (1, 0),
+            crypt_blocks => (crypt_blocks, self.skip_blocks),
+        };
+        data.chunks_exact_mut(BLOCK_SIZE)
+            .enumerate()
+            .filter(move |(index, _)| index % (crypt_blocks + skip_blocks) < crypt_blocks)
+            .map(|(_, block)| block)
+    }
+}
+
+struct CtrKeystream<'a> {
+    cipher: &'a Aes128,
+    counter: [u8; BLOCK_SIZE],
+    block: [u8; BLOCK_SIZE],
+    position: usize,
+}
+
+impl<'a> CtrKeystream<'a> {
+    fn new(cipher: &'a Aes128, iv: [u8; BLOCK_SIZE]) -> Self {
+        Self {
+            cipher,
+            counter: iv,
+            block: [0; BLOCK_SIZE],
+            position: BLOCK_SIZE,
+        }
+    }
+
+    fn apply(&mut self, data: &mut [u8]) {
+        for byte in data {
+            if self.position == BLOCK_SIZE {
+                self.next_block();
+            }
+            *byte ^= self.block[self.position];
+            self.position += 1;
+        }
+    }
+
+    fn next_block(&mut self) {
+        let mut block = GenericArray::from(self.counter);
+        self.cipher.encrypt_block(&mut block);
+        self.block = block.into();
+        self.position = 0;
+
+        // Only the lower 64 bits are used as the block counter.
+        let block_counter = u64::from_be_bytes(self.counter[8..].try_into().unwrap());
+        self.counter[8..].copy_from_slice(&block_counter.wrapping_add(1).to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::pipeline::mp4::reader::DecoderOptions;
+
+    // Test vectors from NIST SP 800-38A
+    const KEY: [u8; 16] = [
+        0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f,
+        0x3c,
+    ];
+    const PLAINTEXT: [u8; 32] = [
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17,
+        0x2a, 0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf,
+        0x8e, 0x51,
+    ];
+    const CBC_IV: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+    const CBC_CIPHERTEXT: [u8; 32] = [
+        0x76, 0x49, 0xab, 0xac, 0x81, 0x19, 0xb2, 0x46, 0xce, 0xe9, 0x8e, 0x9b, 0x12, 0xe9, 0x19,
+        0x7d, 0x50, 0x86, 0xcb, 0x9b, 0x50, 0x72, 0x19, 0xee, 0x95, 0xdb, 0x11, 0x3a, 0x91, 0x76,
+        0x78, 0xb2,
+    ];
+    const CTR_IV: [u8; 16] = [
+        0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe,
+        0xff,
+    ];
+    const CTR_CIPHERTEXT: [u8; 32] = [
+        0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26, 0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6,
+        0xce, 0x98, 0x06, 0xf6, 0x6b, 0x79, 0x70, 0xfd, 0xff, 0x86, 0x17, 0x18, 0x7b, 0xb9, 0xff,
+        0xfd, 0xff,
+    ];
+
+    fn decryptor(
+        scheme: EncryptionScheme,
+        pattern: (u8, u8),
+        sample: SampleEncryption,
+    ) -> SampleDecryptor {
+        SampleDecryptor::new(
+            KEY,
+            ProtectedTrack {
+                decoder_options: DecoderOptions::Aac(Bytes::new()),
+                scheme,
+                default_key_id: [0; 16],
+                is_protected: true,
+                per_sample_iv_size: sample.iv.len() as u8,
+                constant_iv: None,
+                crypt_byte_block: pattern.0,
+                skip_byte_block: pattern.1,
+                samples: vec![sample],
+            },
+        )
+    }
+
+    fn subsample(clear_bytes: usize, protected_bytes: usize) -> Subsample {
+        Subsample {
+            clear_bytes,
+            protected_bytes,
+        }
+    }
+
+    #[test]
+    fn decrypt_cenc_subsamples() {
+        // Keystream continues in the second subsample
+        let sample = [
+            &[1, 2, 3][..],
+            &CTR_CIPHERTEXT[..10],
+            &[4, 5],
+            &CTR_CIPHERTEXT[10..],
+        ]
+        .concat();
+        let decryptor = decryptor(
+            EncryptionScheme::Cenc,
+            (0, 0),
+            SampleEncryption {
+                iv: Bytes::copy_from_slice(&CTR_IV),
+                subsamples: vec![subsample(3, 10), subsample(2, 22)],
+            },
+        );
+
+        let decrypted = decryptor.decrypt(1, Bytes::from(sample)).unwrap();
+        let expected = [&[1, 2, 3][..], &PLAINTEXT[..10], &[4, 5], &PLAINTEXT[10..]].concat();
+        assert_eq!(decrypted.as_ref(), expected);
+    }
+
+    #[test]
+    fn decrypt_cbc1_full_sample() {
+        let sample = [&CBC_CIPHERTEXT[..], &[7, 7, 7]].concat();
+        let decryptor = decryptor(
+            EncryptionScheme::Cbc1,
+            (0, 0),
+            SampleEncryption {
+                iv: Bytes::copy_from_slice(&CBC_IV),
+                subsamples: vec![],
+            },
+        );
+
+        // Trailing partial block stays in clear
+        let decrypted = decryptor.decrypt(1, Bytes::from(sample)).unwrap();
+        assert_eq!(decrypted.as_ref(), [&PLAINTEXT[..], &[7, 7, 7]].concat());
+    }
+
+    #[test]
+    fn decrypt_cbcs_pattern() {
+        // Pattern 1:1, the second block is skipped and does not affect chaining
+        let skipped_block = [9; 16];
+        let protected = [&CBC_CIPHERTEXT[..16], &skipped_block, &CBC_CIPHERTEXT[16..]].concat();
+        let sample = [&[1, 2][..], &protected].concat();
+        let decryptor = decryptor(
+            EncryptionScheme::Cbcs,
+            (1, 1),
+            SampleEncryption {
+                iv: Bytes::copy_from_slice(&CBC_IV),
+                subsamples: vec![subsample(2, 48)],
+            },
+        );
+
+        let decrypted = decryptor.decrypt(1, Bytes::from(sample)).unwrap();
+        let expected = [
+            &[1, 2][..],
+            &PLAINTEXT[..16],
+            &skipped_block,
+            &PLAINTEXT[16..],
+        ]
+        .concat();
+        assert_eq!(decrypted.as_ref(), expected);
+    }
+
+    #[test]
+    fn reject_subsamples_larger_than_sample() {
+        let decryptor = decryptor(
+            EncryptionScheme::Cenc,
+            (0, 0),
+            SampleEncryption {
+                iv: Bytes::copy_from_slice(&CTR_IV[..8]),
+                subsamples: vec![subsample(4, 100)],
+            },
+        );
+        assert!(decryptor.decrypt(1, Bytes::from(vec![0; 32])).is_err());
+    }
+
+    #[test]
+    fn reject_sample_without_iv() {
+        let decryptor = decryptor(EncryptionScheme::Cenc, (0, 0), SampleEncryption::default());
+        assert!(decryptor.decrypt(1, Bytes::from(vec![0; 32])).is_err());
+        // sample without auxiliary information
+        assert!(decryptor.decrypt(2, Bytes::from(vec![0; 32])).is_err());
+    }
+}
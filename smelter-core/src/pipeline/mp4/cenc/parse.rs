@@ -0,0 +1,766 @@
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek, SeekFrom},
+};
+
+use bytes::{Buf, Bytes};
+use tracing::{debug, warn};
+
+use crate::pipeline::{mp4::reader::DecoderOptions, utils::H264AvcDecoderConfig};
+
+use crate::prelude::*;
+
+use super::{EncryptionScheme, SampleEncryption, Subsample};
+
+type FourCC = [u8; 4];
+
+/// Encryption parameters of a track, read from `sinf` box of `encv`/`enca` sample entry
+/// and from sample auxiliary information (`senc` or `saiz`/`saio` boxes).
+#[derive(Debug, Clone)]
+pub(in crate::pipeline::mp4) struct ProtectedTrack {
+    pub decoder_options: DecoderOptions,
+    pub scheme: EncryptionScheme,
+    pub default_key_id: [u8; 16],
+    pub is_protected: bool,
+    pub per_sample_iv_size: u8,
+    pub constant_iv: Option<Bytes>,
+    pub crypt_byte_block: u8,
+    pub skip_byte_block: u8,
+    /// Sample auxiliary information in the order of samples in the file.
+    pub samples: Vec<SampleEncryption>,
+}
+
+#[derive(Debug, Default)]
+pub(in crate::pipeline::mp4) struct ProtectionInfo {
+    /// Protected tracks by track ID.
+    pub tracks: BTreeMap<u32, ProtectedTrack>,
+    /// System IDs from `pssh` boxes. Only used for logging, keys are always provided by the user.
+    pub pssh_system_ids: Vec<[u8; 16]>,
+}
+
+/// Sizes, counts and offsets read from the boxes can't be trusted, so reads are checked
+/// against the size of the file before anything is allocated.
+struct FileReader<'a, R: Read + Seek> {
+    reader: &'a mut R,
+    size: u64,
+}
+
+struct BoxHeader {
+    kind: FourCC,
+    header_size: u64,
+    size: u64,
+}
+
+/// Scans top-level boxes of the file. `moov` and `moof` boxes are read into memory,
+/// the rest is skipped.
+pub(in crate::pipeline::mp4) fn read_protection_info<R: Read + Seek>(
+    reader: &mut R,
+    size: u64,
+) -> Result<ProtectionInfo, Mp4InputError> {
+    let mut file = FileReader { reader, size };
+    let mut info = ProtectionInfo::default();
+    let mut moofs = vec![];
+
+    let mut offset = 0;
+    while let Some(header) = file.read_box_header(offset)? {
+        match &header.kind {
+            b"moov" => {
+                let body = file.read_box_body(offset, &header, "moov")?;
+                info.parse_moov(&mut file, body)?;
+            }
+            b"moof" => moofs.push((offset, header.header_size, header.size)),
+            _ => {}
+        }
+        offset += header.size;
+    }
+
+    if info.tracks.is_empty() {
+        return Ok(info);
+    }
+
+    for (offset, header_size, size) in moofs {
+        let header = BoxHeader {
+            kind: *b"moof",
+            header_size,
+            size,
+        };
+        let body = file.read_box_body(offset, &header, "moof")?;
+        info.parse_moof(&mut file, offset, body)?;
+    }
+    Ok(info)
+}
+
+impl ProtectionInfo {
+    fn parse_moov<R: Read + Seek>(
+        &mut self,
+        file: &mut FileReader<R>,
+        moov: Bytes,
+    ) -> Result<(), Mp4InputError> {
+        for (kind, body) in children(&moov) {
+            match &kind {
+                b"trak" => self.parse_trak(file, body)?,
+                b"pssh" => self.parse_pssh(body)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_trak<R: Read + Seek>(
+        &mut self,
+        file: &mut FileReader<R>,
+        trak: Bytes,
+    ) -> Result<(), Mp4InputError> {
+        let track_id = child(&trak, b"tkhd")
+            .and_then(parse_tkhd_track_id)
+            .ok_or(Mp4DecryptionError::MalformedBox("tkhd"))?;
+        let Some(stbl) = child_path(&trak, &[b"mdia", b"minf", b"stbl"]) else {
+            return Ok(());
+        };
+        let Some(stsd) = child(&stbl, b"stsd") else {
+            return Ok(());
+        };
+        if stsd.len() < 8 {
+            return Err(Mp4DecryptionError::MalformedBox("stsd").into());
+        }
+        // Skip version, flags and entry count
+        let entries = stsd.slice(8..);
+
+        for (kind, entry) in children(&entries) {
+            let Some(entry_children) = sample_entry_children(&kind, &entry) else {
+                continue;
+            };
+            let Some(sinf) = child(&entry_children, b"sinf") else {
+                continue;
+            };
+            let Some(mut track) = parse_sinf(track_id, &sinf, &entry_children)? else {
+                continue;
+            };
+
+            // Samples of non-fragmented files
+            if let Some(senc) = child(&stbl, b"senc") {
+                track.samples = parse_senc(senc, track.per_sample_iv_size, file.size)?;
+            } else if let (Some(saiz), Some(saio)) = (child(&stbl, b"saiz"), child(&stbl, b"saio"))
+            {
+                track.samples = read_aux_info(file, saiz, saio, 0, track.per_sample_iv_size)?;
+            }
+
+            debug!(track_id, scheme=?track.scheme, "Found encrypted MP4 track.");
+            self.tracks.insert(track_id, track);
+            // Only the first sample entry is supported, same as for clear tracks.
+            break;
+        }
+        Ok(())
+    }
+
+    fn parse_moof<R: Read + Seek>(
+        &mut self,
+        file: &mut FileReader<R>,
+        moof_offset: u64,
+        moof: Bytes,
+    ) -> Result<(), Mp4InputError> {
+        for (kind, body) in children(&moof) {
+            match &kind {
+                b"traf" => self.parse_traf(file, moof_offset, body)?,
+                b"pssh" => self.parse_pssh(body)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_traf<R: Read + Seek>(
+        &mut self,
+        file: &mut FileReader<R>,
+        moof_offset: u64,
+        traf: Bytes,
+    ) -> Result<(), Mp4InputError> {
+        let (track_id, base_data_offset) = child(&traf, b"tfhd")
+            .and_then(parse_tfhd)
+            .ok_or(Mp4DecryptionError::MalformedBox("tfhd"))?;
+        let Some(track) = self.tracks.get_mut(&track_id) else {
+            return Ok(());
+        };
+
+        let samples = if let Some(senc) = child(&traf, b"senc") {
+            parse_senc(senc, track.per_sample_iv_size, file.size)?
+        } else if let (Some(saiz), Some(saio)) = (child(&traf, b"saiz"), child(&traf, b"saio")) {
+            let base_offset = base_data_offset.unwrap_or(moof_offset);
+            read_aux_info(file, saiz, saio, base_offset, track.per_sample_iv_size)?
+        } else {
+            // Fragment without auxiliary information, e.g. constant IV without subsamples. Sample count is needed to keep indices in sync.
+            let sample_count = children(&traf)
+                .filter(|(kind, _)| kind == b"trun")
+                .map(|(_, trun)| parse_trun_sample_count(trun, file.size))
+                .try_fold(0u64, |sum, count| Some(sum + count?))
+                .filter(|sample_count| *sample_count <= file.size)
+                .ok_or(Mp4DecryptionError::MalformedBox("trun"))?;
+            vec![SampleEncryption::default(); sample_count as usize]
+        };
+        track.samples.extend(samples);
+        Ok(())
+    }
+
+    fn parse_pssh(&mut self, mut pssh: Bytes) -> Result<(), Mp4InputError> {
+        let mut system_id = [0; 16];
+        pssh.try_get_u32()
+            .and_then(|_| pssh.try_copy_to_slice(&mut system_id))
+            .map_err(|_| Mp4DecryptionError::MalformedBox("pssh"))?;
+        if !self.pssh_system_ids.contains(&system_id) {
+            self.pssh_system_ids.push(system_id);
+        }
+        Ok(())
+    }
+}
+
+/// Returns `None` for codecs that are not supported by the MP4 input.
+fn parse_sinf(
+    track_id: u32,
+    sinf: &Bytes,
+    entry_children: &Bytes,
+) -> Result<Option<ProtectedTrack>, Mp4InputError> {
+    let original_format = child(sinf, b"frma")
+        .and_then(|frma| frma.get(0..4).map(|f| FourCC::try_from(f).unwrap()))
+        .ok_or(Mp4DecryptionError::MalformedBox("frma"))?;
+    let scheme_type = child(sinf, b"schm")
+        .and_then(|schm| schm.get(4..8).map(|s| FourCC::try_from(s).unwrap()))
+        .ok_or(Mp4DecryptionError::MalformedBox("schm"))?;
+    let scheme = match &scheme_type {
+        b"cenc" => EncryptionScheme::Cenc,
+        b"cens" => EncryptionScheme::Cens,
+        b"cbc1" => EncryptionScheme::Cbc1,
+        b"cbcs" => EncryptionScheme::Cbcs,
+        _ => {
+            return Err(Mp4DecryptionError::UnsupportedScheme(
+                String::from_utf8_lossy(&scheme_type).to_string(),
+            )
+            .into());
+        }
+    };
+
+    let decoder_options = match &original_format {
+        b"avc1" | b"avc3" => child(entry_children, b"avcC")
+            .and_then(|avcc| H264AvcDecoderConfig::parse(avcc).ok())
+            .map(DecoderOptions::H264)
+            .ok_or(Mp4DecryptionError::MalformedBox("avcC"))?,
+        b"mp4a" => child(entry_children, b"esds")
+            .and_then(parse_esds_asc)
+            .map(DecoderOptions::Aac)
+            .ok_or(Mp4DecryptionError::MalformedBox("esds"))?,
+        _ => {
+            warn!(
+                track_id,
+                codec = %String::from_utf8_lossy(&original_format),
+                "Unsupported codec of encrypted MP4 track, ignoring the track."
+            );
+            return Ok(None);
+        }
+    };
+
+    let tenc =
+        child_path(sinf, &[b"schi", b"tenc"]).ok_or(Mp4DecryptionError::MalformedBox("tenc"))?;
+    let track = parse_tenc(tenc, decoder_options, scheme)
+        .ok_or(Mp4DecryptionError::MalformedBox("tenc"))?;
+    Ok(Some(track))
+}
+
+fn parse_tenc(
+    mut tenc: Bytes,
+    decoder_options: DecoderOptions,
+    scheme: EncryptionScheme,
+) -> Option<ProtectedTrack> {
+    let version = tenc.try_get_u32().ok()? >> 24;
+    tenc.try_get_u8().ok()?; // reserved
+    let pattern = tenc.try_get_u8().ok()?;
+    let (crypt_byte_block, skip_byte_block) = match version {
+        0 => (0, 0),
+        _ => (pattern >> 4, pattern & 0x0F),
+    };
+    let is_protected = tenc.try_get_u8().ok()? != 0;
+    let per_sample_iv_size = tenc.try_get_u8().ok()?;
+    let mut default_key_id = [0; 16];
+    tenc.try_copy_to_slice(&mut default_key_id).ok()?;
+
+    let constant_iv = match is_protected && per_sample_iv_size == 0 {
+        true => {
+            let size = tenc.try_get_u8().ok()? as usize;
+            if tenc.len() < size {
+                return None;
+            }
+            Some(tenc.split_to(size))
+        }
+        false => None,
+    };
+
+    Some(ProtectedTrack {
+        decoder_options,
+        scheme,
+        default_key_id,
+        is_protected,
+        per_sample_iv_size,
+        constant_iv,
+        crypt_byte_block,
+        skip_byte_block,
+        samples: vec![],
+    })
+}
+
+/// `senc` box, sample auxiliary information stored directly in the box.
+fn parse_senc(
+    senc: Bytes,
+    iv_size: u8,
+    file_size: u64,
+) -> Result<Vec<SampleEncryption>, Mp4InputError> {
+    fn parse(mut senc: Bytes, iv_size: u8, file_size: u64) -> Option<Vec<SampleEncryption>> {
+        let flags = senc.try_get_u32().ok()? & 0x00FF_FFFF;
+        let has_subsamples = flags & 0x2 != 0;
+        let sample_count = senc.try_get_u32().ok()?;
+        let min_entry_size = iv_size as u64 + if has_subsamples { 2 } else { 0 };
+        if !entries_fit(sample_count, min_entry_size, senc.len(), file_size) {
+            return None;
+        }
+        (0..sample_count)
+            .map(|_| parse_sample_encryption(&mut senc, iv_size, has_subsamples))
+            .collect()
+    }
+    parse(senc, iv_size, file_size).ok_or(Mp4DecryptionError::MalformedBox("senc").into())
+}
+
+/// Sample auxiliary information referenced by `saiz` (sizes) and `saio` (offsets) boxes.
+fn read_aux_info<R: Read + Seek>(
+    file: &mut FileReader<R>,
+    saiz: Bytes,
+    saio: Bytes,
+    base_offset: u64,
+    iv_size: u8,
+) -> Result<Vec<SampleEncryption>, Mp4InputError> {
+    let sizes = parse_saiz(saiz, file.size).ok_or(Mp4DecryptionError::MalformedBox("saiz"))?;
+    let offsets = parse_saio(saio).ok_or(Mp4DecryptionError::MalformedBox("saio"))?;
+
+    let total_size = sizes.iter().map(|size| *size as u64).sum::<u64>();
+    let absolute = |offset: u64| {
+        base_offset
+            .checked_add(offset)
+            .ok_or(Mp4DecryptionError::MalformedBox("saio"))
+    };
+    let data = match offsets.as_slice() {
+        // Aux info of all samples is stored contiguously.
+        [first] => file.read_at(absolute(*first)?, total_size, "saio")?,
+        offsets if offsets.len() == sizes.len() => {
+            let mut data = Vec::with_capacity(total_size as usize);
+            for (&sample_offset, size) in offsets.iter().zip(&sizes) {
+                data.extend(file.read_at(absolute(sample_offset)?, *size as u64, "saio")?);
+            }
+            Bytes::from(data)
+        }
+        _ => return Err(Mp4DecryptionError::MalformedBox("saio").into()),
+    };
+
+    let mut data = data;
+    sizes
+        .iter()
+        .map(|size| {
+            // Size 0 means that there is no auxiliary information for the sample, it fails
+            // to decrypt unless the track has a constant IV.
+            if *size == 0 {
+                return Some(SampleEncryption::default());
+            }
+            let mut info = data.split_to(usize::min(*size as usize, data.len()));
+            let has_subsamples = info.len() > iv_size as usize;
+            parse_sample_encryption(&mut info, iv_size, has_subsamples)
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or(Mp4DecryptionError::MalformedBox("saiz").into())
+}
+
+/// Returns sizes of sample auxiliary information. All of it has to fit in the file.
+fn parse_saiz(mut saiz: Bytes, file_size: u64) -> Option<Vec<u8>> {
+    let flags = saiz.try_get_u32().ok()? & 0x00FF_FFFF;
+    if flags & 0x1 != 0 {
+        saiz.try_get_u64().ok()?; // aux_info_type and aux_info_type_parameter
+    }
+    let default_size = saiz.try_get_u8().ok()?;
+    let sample_count = saiz.try_get_u32().ok()?;
+    let sizes = match default_size {
+        0 if saiz.len() as u64 >= sample_count as u64 => {
+            saiz.slice(..sample_count as usize).to_vec()
+        }
+        0 => return None,
+        size if size as u64 * sample_count as u64 <= file_size => {
+            vec![size; sample_count as usize]
+        }
+        _ => return None,
+    };
+    let total_size = sizes.iter().map(|size| *size as u64).sum::<u64>();
+    (total_size <= file_size).then_some(sizes)
+}
+
+/// Returns offsets of sample auxiliary information.
+fn parse_saio(mut saio: Bytes) -> Option<Vec<u64>> {
+    let version_and_flags = saio.try_get_u32().ok()?;
+    if version_and_flags & 0x1 != 0 {
+        saio.try_get_u64().ok()?; // aux_info_type and aux_info_type_parameter
+    }
+    let entry_count = saio.try_get_u32().ok()?;
+    let entry_size = match version_and_flags >> 24 {
+        0 => 4,
+        _ => 8,
+    };
+    if saio.len() as u64 / entry_size < entry_count as u64 {
+        return None;
+    }
+    (0..entry_count)
+        .map(|_| match entry_size {
+            4 => saio.try_get_u32().ok().map(u64::from),
+            _ => saio.try_get_u64().ok(),
+        })
+        .collect()
+}
+
+fn parse_sample_encryption(
+    data: &mut Bytes,
+    iv_size: u8,
+    has_subsamples: bool,
+) -> Option<SampleEncryption> {
+    let iv_size = iv_size as usize;
+    if data.len() < iv_size {
+        return None;
+    }
+    let iv = data.split_to(iv_size);
+    let subsamples = match has_subsamples {
+        true => {
+            let count = data.try_get_u16().ok()?;
+            (0..count)
+                .map(|_| {
+                    Some(Subsample {
+                        clear_bytes: data.try_get_u16().ok()? as usize,
+                        protected_bytes: data.try_get_u32().ok()? as usize,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?
+        }
+        false => vec![],
+    };
+    Some(SampleEncryption { iv, subsamples })
+}
+
+fn parse_tkhd_track_id(mut tkhd: Bytes) -> Option<u32> {
+    let version = tkhd.try_get_u32().ok()? >> 24;
+    // creation and modification time
+    let skip = match version {
+        1 => 16,
+        _ => 8,
+    };
+    if tkhd.len() < skip {
+        return None;
+    }
+    tkhd.advance(skip);
+    tkhd.try_get_u32().ok()
+}
+
+/// Returns track ID and base data offset.
+fn parse_tfhd(mut tfhd: Bytes) -> Option<(u32, Option<u64>)> {
+    let flags = tfhd.try_get_u32().ok()? & 0x00FF_FFFF;
+    let track_id = tfhd.try_get_u32().ok()?;
+    let base_data_offset = match flags & 0x1 != 0 {
+        true => Some(tfhd.try_get_u64().ok()?),
+        false => None,
+    };
+    Some((track_id, base_data_offset))
+}
+
+fn parse_trun_sample_count(mut trun: Bytes, file_size: u64) -> Option<u64> {
+    let flags = trun.try_get_u32().ok()? & 0x00FF_FFFF;
+    let sample_count = trun.try_get_u32().ok()?;
+    // data_offset and first_sample_flags
+    let header_size = [0x1, 0x4].iter().filter(|flag| flags & *flag != 0).count() * 4;
+    // sample_duration, sample_size, sample_flags and sample_composition_time_offset
+    let entry_size = [0x100, 0x200, 0x400, 0x800]
+        .iter()
+        .filter(|flag| flags & *flag != 0)
+        .count() as u64
+        * 4;
+    let entries_size = trun.len().checked_sub(header_size)?;
+    entries_fit(sample_count, entry_size, entries_size, file_size).then_some(sample_count as u64)
+}
+
+/// Checks that `count` entries of at least `entry_size` bytes fit in the `available` bytes
+/// of the box. Entries without any fields still describe samples, so there can't be more
+/// of them than bytes in the file.
+fn entries_fit(count: u32, entry_size: u64, available: usize, file_size: u64) -> bool {
+    match entry_size {
+        0 => count as u64 <= file_size,
+        entry_size => count as u64 * entry_size <= available as u64,
+    }
+}
+
+/// Returns `DecoderSpecificInfo` (AudioSpecificConfig) from `esds` box.
+fn parse_esds_asc(mut esds: Bytes) -> Option<Bytes> {
+    esds.try_get_u32().ok()?; // version and flags
+    let mut es_descriptor = read_descriptor(&mut esds, 0x03)?;
+    es_descriptor.try_get_u16().ok()?; // ES_ID
+    let flags = es_descriptor.try_get_u8().ok()?;
+    if flags & 0x80 != 0 {
+        es_descriptor.try_get_u16().ok()?; // dependsOn_ES_ID
+    }
+    if flags & 0x40 != 0 {
+        let url_length = es_descriptor.try_get_u8().ok()? as usize;
+        if es_descriptor.len() < url_length {
+            return None;
+        }
+        es_descriptor.advance(url_length);
+    }
+    if flags & 0x20 != 0 {
+        es_descriptor.try_get_u16().ok()?; // OCR_ES_Id
+    }
+
+    let mut decoder_config = read_descriptor(&mut es_descriptor, 0x04)?;
+    // objectTypeIndication, streamType, bufferSizeDB, maxBitrate and avgBitrate
+    if decoder_config.len() < 13 {
+        return None;
+    }
+    decoder_config.advance(13);
+    read_descriptor(&mut decoder_config, 0x05)
+}
+
+fn read_descriptor(data: &mut Bytes, tag: u8) -> Option<Bytes> {
+    if data.try_get_u8().ok()? != tag {
+        return None;
+    }
+    let mut length = 0;
+    for _ in 0..4 {
+        let byte = data.try_get_u8().ok()?;
+        length = (length << 7) | (byte & 0x7F) as usize;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    if data.len() < length {
+        return None;
+    }
+    Some(data.split_to(length))
+}
+
+/// Returns children boxes of the sample entry, skipping fields of
+/// visual or audio sample entry.
+fn sample_entry_children(kind: &FourCC, entry: &Bytes) -> Option<Bytes> {
+    let fields_size = match kind {
+        b"encv" => 78,
+        b"enca" => {
+            // QuickTime sound sample description versions have additional fields
+            let version = u16::from_be_bytes(entry.get(8..10)?.try_into().unwrap());
+            match version {
+                1 => 44,
+                2 => 64,
+                _ => 28,
+            }
+        }
+        _ => return None,
+    };
+    (entry.len() >= fields_size).then(|| entry.slice(fields_size..))
+}
+
+impl<R: Read + Seek> FileReader<'_, R> {
+    fn read_box_header(&mut self, offset: u64) -> Result<Option<BoxHeader>, Mp4InputError> {
+        let remaining = self.size.saturating_sub(offset);
+        if remaining < 8 {
+            return Ok(None);
+        }
+        self.reader.seek(SeekFrom::Start(offset))?;
+        let mut header = [0; 8];
+        self.reader.read_exact(&mut header)?;
+        let kind = FourCC::try_from(&header[4..8]).unwrap();
+        let (header_size, size) = match u32::from_be_bytes(header[0..4].try_into().unwrap()) {
+            0 => (8, remaining),
+            1 => {
+                let mut size = [0; 8];
+                self.reader.read_exact(&mut size)?;
+                (16, u64::from_be_bytes(size))
+            }
+            size => (8, size as u64),
+        };
+        if size < header_size || size > remaining {
+            warn!(
+                kind = %String::from_utf8_lossy(&kind),
+                "Invalid MP4 box size, ignoring the rest of the file."
+            );
+            return Ok(None);
+        }
+        Ok(Some(BoxHeader {
+            kind,
+            header_size,
+            size,
+        }))
+    }
+
+    fn read_box_body(
+        &mut self,
+        offset: u64,
+        header: &BoxHeader,
+        kind: &'static str,
+    ) -> Result<Bytes, Mp4InputError> {
+        self.read_at(
+            offset + header.header_size,
+            header.size - header.header_size,
+            kind,
+        )
+    }
+
+    /// Fails with [`Mp4DecryptionError::MalformedBox`] of `kind` if the data is not
+    /// within the file.
+    fn read_at(
+        &mut self,
+        offset: u64,
+        size: u64,
+        kind: &'static str,
+    ) -> Result<Bytes, Mp4InputError> {
+        if offset.checked_add(size).is_none_or(|end| end > self.size) {
+            return Err(Mp4DecryptionError::MalformedBox(kind).into());
+        }
+        self.reader.seek(SeekFrom::Start(offset))?;
+        let mut data = vec![0; size as usize];
+        self.reader.read_exact(&mut data)?;
+        Ok(Bytes::from(data))
+    }
+}
+
+fn children(data: &Bytes) -> impl Iterator<Item = (FourCC, Bytes)> {
+    let mut data = data.clone();
+    std::iter::from_fn(move || {
+        if data.len() < 8 {
+            return None;
+        }
+        let kind = FourCC::try_from(&data[4..8]).unwrap();
+        let (header_size, size) = match u32::from_be_bytes(data[0..4].try_into().unwrap()) {
+            0 => (8, data.len()),
+            1 if data.len() >= 16 => (
+                16,
+                u64::from_be_bytes(data[8..16].try_into().unwrap()) as usize,
+            ),
+            size => (8, size as usize),
+        };
+        if size < header_size || size > data.len() {
+            return None;
+        }
+        let body = data.slice(header_size..size);
+        data.advance(size);
+        Some((kind, body))
+    })
+}
+
+fn child(data: &Bytes, kind: &FourCC) -> Option<Bytes> {
+    children(data).find_map(|(child_kind, body)| (child_kind == *kind).then_some(body))
+}
+
+fn child_path(data: &Bytes, path: &[&FourCC]) -> Option<Bytes> {
+    path.iter()
+        .try_fold(data.clone(), |data, kind| child(&data, kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(kind: &FourCC, body: &[u8]) -> Vec<u8> {
+        let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.extend_from_slice(body);
+        data
+    }
+
+    #[test]
+    fn parse_tenc_with_pattern_and_constant_iv() {
+        let mut body = vec![1, 0, 0, 0, 0, 0x19, 1, 0];
+        body.extend_from_slice(&[0xAB; 16]);
+        body.push(16);
+        body.extend_from_slice(&[0xCD; 16]);
+
+        let track = parse_tenc(
+            Bytes::from(body),
+            DecoderOptions::Aac(Bytes::new()),
+            EncryptionScheme::Cbcs,
+        )
+        .unwrap();
+        assert!(track.is_protected);
+        assert_eq!(track.default_key_id, [0xAB; 16]);
+        assert_eq!((track.crypt_byte_block, track.skip_byte_block), (1, 9));
+        assert_eq!(track.per_sample_iv_size, 0);
+        assert_eq!(track.constant_iv.as_deref(), Some(&[0xCD; 16][..]));
+    }
+
+    #[test]
+    fn parse_senc_with_subsamples() {
+        let mut body = vec![0, 0, 0, 2, 0, 0, 0, 2];
+        for iv in [1u8, 2] {
+            body.extend_from_slice(&[iv; 8]);
+            body.extend_from_slice(&1u16.to_be_bytes());
+            body.extend_from_slice(&5u16.to_be_bytes());
+            body.extend_from_slice(&32u32.to_be_bytes());
+        }
+
+        let samples = parse_senc(Bytes::from(body), 8).unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[1].iv.as_ref(), &[2; 8]);
+        assert_eq!(
+            samples[0].subsamples,
+            vec![Subsample {
+                clear_bytes: 5,
+                protected_bytes: 32
+            }]
+        );
+    }
+
+    #[test]
+    fn reject_counts_that_do_not_fit() {
+        // saiz with default size and 2^32 - 1 samples in a 1 KB file
+        let saiz = [&[0, 0, 0, 0, 16][..], &u32::MAX.to_be_bytes()].concat();
+        assert!(parse_saiz(Bytes::from(saiz), 1024).is_none());
+
+        // trun with sample sizes, but without the entries
+        let trun = [&[0, 0, 0x02, 0][..], &1000u32.to_be_bytes()].concat();
+        assert!(parse_trun_sample_count(Bytes::from(trun), 1024).is_none());
+
+        // trun without per-sample fields
+        let trun = [&[0, 0, 0, 0][..], &u32::MAX.to_be_bytes()].concat();
+        assert!(parse_trun_sample_count(Bytes::from(trun), 1024).is_none());
+        let trun = [&[0, 0, 0, 0][..], &10u32.to_be_bytes()].concat();
+        assert_eq!(parse_trun_sample_count(Bytes::from(trun), 1024), Some(10));
+
+        // senc without IVs and subsamples
+        let senc = [&[0, 0, 0, 0][..], &u32::MAX.to_be_bytes()].concat();
+        assert!(parse_senc(Bytes::from(senc), 0, 1024).is_err());
+    }
+
+    #[test]
+    fn reject_aux_info_outside_of_file() {
+        let mut data = std::io::Cursor::new(vec![0; 64]);
+        let mut file = FileReader {
+            reader: &mut data,
+            size: 64,
+        };
+        let saiz = Bytes::from([&[0, 0, 0, 0, 8][..], &4u32.to_be_bytes()].concat());
+        let saio = |offset: u32| {
+            Bytes::from([&[0, 0, 0, 0, 0, 0, 0, 1][..], &offset.to_be_bytes()].concat())
+        };
+
+        let samples = read_aux_info(&mut file, saiz.clone(), saio(32), 0, 8).unwrap();
+        assert_eq!(samples.len(), 4);
+        assert!(read_aux_info(&mut file, saiz.clone(), saio(48), 0, 8).is_err());
+        assert!(read_aux_info(&mut file, saiz, saio(0), u64::MAX, 8).is_err());
+    }
+
+    #[test]
+    fn find_nested_box() {
+        let tenc = mp4_box(b"tenc", &[1, 2, 3]);
+        let schi = mp4_box(b"schi", &tenc);
+        let sinf = [mp4_box(b"frma", b"avc1"), schi].concat();
+
+        let sinf = Bytes::from(sinf);
+        assert_eq!(child(&sinf, b"frma").as_deref(), Some(&b"avc1"[..]));
+        assert_eq!(
+            child_path(&sinf, &[b"schi", b"tenc"]).as_deref(),
+            Some(&[1, 2, 3][..])
+        );
+        assert!(child(&sinf, b"schm").is_none());
+    }
+}
@@ -1,3 +1,4 @@
+mod cenc;
 mod mp4_input;
 mod mp4_output;
 mod reader;
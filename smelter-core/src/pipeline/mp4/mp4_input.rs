@@ -19,7 +19,7 @@ use crate::{
             fdk_aac, ffmpeg_h264, vulkan_h264,
         },
         input::Input,
-        mp4::reader::{DecoderOptions, Mp4FileReader, Track},
+        mp4::reader::{DecoderOptions, Mp4FileReader, Mp4Protection, Track},
         utils::H264AvccToAnnexB,
     },
     queue::{QueueInput, QueueSender, QueueTrackOffset, QueueTrackOptions, WeakQueueInput},
@@ -113,9 +113,15 @@ impl Mp4Input {
             kind: InputProtocolKind::Mp4,
         });
 
-        let video_track = Mp4FileReader::from_path(&source_file.path)?.try_new_h264_track();
+        let protection = Arc::new(Mp4Protection::from_path(
+            &source_file.path,
+            options.decryption.as_ref(),
+        )?);
+        let video_track =
+            Mp4FileReader::from_path(&source_file.path, protection.clone())?.try_new_h264_track();
         let video_duration = video_track.as_ref().and_then(|track| track.duration());
-        let audio_track = Mp4FileReader::from_path(&source_file.path)?.try_new_aac_track();
+        let audio_track =
+            Mp4FileReader::from_path(&source_file.path, protection)?.try_new_aac_track();
         let audio_duration = audio_track.as_ref().and_then(|track| track.duration());
 
         if video_track.is_none() && audio_track.is_none() {
@@ -513,7 +519,19 @@ struct TrackThread {
 
 impl TrackThread {
     fn run_video_thread(mut self, decoder_handle: DecoderThreadHandle) -> Track<File> {
-        for (chunk, _duration) in self.track.chunks(self.seek) {
+        let mut failed = false;
+        for chunk in self.track.chunks(self.seek) {
+            let (chunk, _duration) = match chunk {
+                Ok(chunk) => chunk,
+                Err(err) => {
+                    error!(
+                        "Stopping MP4 video track: {}",
+                        ErrorStack::new(&err).into_string()
+                    );
+                    failed = true;
+                    break;
+                }
+            };
             self.ctx.stats_sender.send(
                 Mp4InputTrackStatsEvent::BytesReceived(chunk.data.len())
                     .into_event(&self.ctx.input_ref, StatsTrackKind::Video),
@@ -530,15 +548,30 @@ impl TrackThread {
                 break;
             }
         }
-        let _ = self
-            .ctx
-            .event_sender
-            .send(StateEvent::ThreadFinished(thread::current().id()));
+        // A failed track is not restarted when looping, because it would fail again.
+        if !failed {
+            let _ = self
+                .ctx
+                .event_sender
+                .send(StateEvent::ThreadFinished(thread::current().id()));
+        }
         self.track
     }
 
     fn run_audio_thread(mut self, decoder_handle: DecoderThreadHandle) -> Track<File> {
-        for (chunk, _duration) in self.track.chunks(self.seek) {
+        let mut failed = false;
+        for chunk in self.track.chunks(self.seek) {
+            let (chunk, _duration) = match chunk {
+                Ok(chunk) => chunk,
+                Err(err) => {
+                    error!(
+                        "Stopping MP4 audio track: {}",
+                        ErrorStack::new(&err).into_string()
+                    );
+                    failed = true;
+                    break;
+                }
+            };
             self.ctx.stats_sender.send(
                 Mp4InputTrackStatsEvent::BytesReceived(chunk.data.len())
                     .into_event(&self.ctx.input_ref, StatsTrackKind::Audio),
@@ -555,10 +588,13 @@ impl TrackThread {
                 break;
             }
         }
-        let _ = self
-            .ctx
-            .event_sender
-            .send(StateEvent::ThreadFinished(thread::current().id()));
+        // A failed track is not restarted when looping, because it would fail again.
+        if !failed {
+            let _ = self
+                .ctx
+                .event_sender
+                .send(StateEvent::ThreadFinished(thread::current().id()));
+        }
         self.track
     }
 
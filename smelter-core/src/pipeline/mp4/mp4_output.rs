@@ -6,6 +6,7 @@ use smelter_render::OutputId;
 use tracing::{debug, error, warn};
 
 use crate::{
+    diagnostics::{CancelToken, TaskKind},
     event::Event,
     pipeline::{
         encoder::{
@@ -99,13 +100,24 @@ impl Mp4Output {
             None => None,
         };
 
-        let mut ffmpeg_options = FfmpegOptions::from(&[("movflags", "faststart")]);
+        let mut ffmpeg_options = FfmpegOptions::default();
+        if options.fast_start {
+            // Muxer rewrites the file in the second pass when the trailer is written.
+            ffmpeg_options.append(&[("movflags", "faststart")]);
+        }
+        if let Some(interleave_duration) = options.interleave_duration {
+            // Interleaving muxer queue emits up to `chunk_duration` of a single track at once,
+            // MP4 muxer stores consecutive samples of a track as one chunk.
+            let chunk_duration = interleave_duration.as_micros().to_string();
+            ffmpeg_options.append(&[("chunk_duration", chunk_duration.as_str())]);
+        }
         ffmpeg_options.append(&options.raw_options);
 
         output_ctx
             .write_header_with(ffmpeg_options.into_dictionary())
             .map_err(OutputInitError::FfmpegError)?;
 
+        let interleave = options.interleave_duration.is_some();
         let (video_encoder, video_stream) = match video {
             Some((encoder, index)) => (
                 Some(encoder),
@@ -133,6 +145,12 @@ impl Mp4Output {
             .spawn(move || {
                 let _span =
                     tracing::info_span!("MP4 writer", output_id = output_ref.to_string()).entered();
+                let task = ctx.task_registry.register(
+                    "MP4 writer",
+                    Some(output_ref.to_string()),
+                    TaskKind::Thread,
+                );
+                let cancel = task.cancel_token();
 
                 run_ffmpeg_output_thread(
                     &ctx,
@@ -140,7 +158,9 @@ impl Mp4Output {
                     output_ctx,
                     video_stream,
                     audio_stream,
+                    interleave,
                     encoded_chunks_receiver,
+                    cancel,
                 );
                 ctx.event_emitter
                     .emit(Event::OutputDone(output_ref.id().clone()));
@@ -299,7 +319,9 @@ fn run_ffmpeg_output_thread(
     mut output_ctx: ffmpeg::format::context::Output,
     mut video_stream: Option<StreamState>,
     mut audio_stream: Option<StreamState>,
+    interleave: bool,
     packets_receiver: Receiver<EncodedOutputEvent>,
+    cancel: CancelToken,
 ) {
     let mut eos_state = EosState::new(video_stream.is_some(), audio_stream.is_some());
     let mut timestamp_offset = None;
@@ -309,7 +331,10 @@ fn run_ffmpeg_output_thread(
         output_ref: output_ref.clone(),
     };
 
-    for packet in packets_receiver {
+    for packet in packets_receiver
+        .into_iter()
+        .take_while(|_| !cancel.is_cancelled())
+    {
         match packet {
             EncodedOutputEvent::Data(chunk) => {
                 let timestamp_offset = *timestamp_offset.get_or_insert(chunk.pts);
@@ -331,7 +356,9 @@ fn run_ffmpeg_output_thread(
                 };
 
                 stats_sender.bytes_sent_event(chunk.data.len(), chunk.kind.into());
-                if let Err(err) = write_chunk(chunk, stream, &mut output_ctx, timestamp_offset) {
+                if let Err(err) =
+                    write_chunk(chunk, stream, &mut output_ctx, timestamp_offset, interleave)
+                {
                     let try_write_trailer =
                         !matches!(err, OutputMp4RuntimeError::NoSpaceLeftOnDevice);
                     ctx.event_emitter.emit(Event::OutputError {
@@ -373,6 +400,7 @@ fn write_chunk(
     stream: &StreamState,
     output_ctx: &mut ffmpeg::format::context::Output,
     timestamp_offset: Duration,
+    interleave: bool,
 ) -> Result<(), OutputMp4RuntimeError> {
     let pts = chunk.pts.saturating_sub(timestamp_offset);
     let dts = chunk
@@ -398,7 +426,12 @@ fn write_chunk(
         packet.set_flags(ffmpeg::packet::Flags::KEY)
     }
 
-    packet.write(output_ctx).map_err(|err| match err {
+    let result = match interleave {
+        // Packets are buffered by the muxer until it can write them in DTS order across streams.
+        true => packet.write_interleaved(output_ctx),
+        false => packet.write(output_ctx).map(|_| ()),
+    };
+    result.map_err(|err| match err {
         ffmpeg_next::Error::Other {
             errno: ffmpeg::error::ENOSPC,
         } => OutputMp4RuntimeError::NoSpaceLeftOnDevice,
@@ -1,21 +1,36 @@
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{Read, Seek},
     os::unix::fs::MetadataExt,
     path::Path,
+    sync::Arc,
     time::Duration,
 };
 
 use bytes::Bytes;
+use data_encoding::HEXLOWER;
 use mp4::{Mp4Sample, Mp4Track};
-use tracing::warn;
+use tracing::{info, warn};
 
-use crate::pipeline::utils::H264AvcDecoderConfig;
+use crate::pipeline::{
+    mp4::cenc::{self, ProtectedTrack, SampleDecryptor},
+    utils::H264AvcDecoderConfig,
+};
 
 use crate::prelude::*;
 
 pub(super) struct Mp4FileReader<Reader: Read + Seek + Send + 'static> {
     reader: mp4::Mp4Reader<Reader>,
+    protection: Arc<Mp4Protection>,
+}
+
+/// Encrypted tracks of the file and the key to decrypt them. Read once per input and shared
+/// by the readers of all tracks.
+pub(super) struct Mp4Protection {
+    /// Tracks with `encv`/`enca` sample entries, `mp4` crate does not recognize them.
+    tracks: BTreeMap<u32, ProtectedTrack>,
+    key: Option<[u8; 16]>,
 }
 
 #[derive(Debug, Clone)]
@@ -24,22 +39,112 @@ pub(super) enum DecoderOptions {
     Aac(Bytes),
 }
 
+impl Mp4Protection {
+    pub fn from_path(
+        path: &Path,
+        decryption: Option<&Mp4DecryptionOptions>,
+    ) -> Result<Self, Mp4InputError> {
+        let mut file = std::fs::File::open(path)?;
+        let size = file.metadata()?.size();
+        Self::new(&mut file, size, decryption)
+    }
+
+    fn new<Reader: Read + Seek>(
+        reader: &mut Reader,
+        size: u64,
+        decryption: Option<&Mp4DecryptionOptions>,
+    ) -> Result<Self, Mp4InputError> {
+        let protection = cenc::read_protection_info(reader, size)?;
+
+        let key = match (protection.tracks.is_empty(), decryption) {
+            (true, _) => None,
+            (false, None) => return Err(Mp4DecryptionError::MissingKey.into()),
+            (false, Some(decryption)) => {
+                Self::verify_key_id(&protection.tracks, decryption.key_id)?;
+                Some(decryption.key)
+            }
+        };
+        for system_id in &protection.pssh_system_ids {
+            info!(
+                "MP4 file contains \"pssh\" box for DRM system {}, clear key provided in the request will be used instead.",
+                HEXLOWER.encode(system_id)
+            );
+        }
+
+        Ok(Self {
+            tracks: protection.tracks,
+            key,
+        })
+    }
+
+    fn verify_key_id(
+        tracks: &BTreeMap<u32, ProtectedTrack>,
+        key_id: Option<[u8; 16]>,
+    ) -> Result<(), Mp4DecryptionError> {
+        let Some(key_id) = key_id else {
+            return Ok(());
+        };
+        let mismatch = tracks
+            .values()
+            .find(|track| track.is_protected && track.default_key_id != key_id);
+        match mismatch {
+            Some(track) => Err(Mp4DecryptionError::KeyIdMismatch {
+                provided: key_id,
+                expected: track.default_key_id,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the protected track with the lowest ID, so that the same track is picked
+    /// every time the file is read.
+    fn find_track(&self, matches_codec: impl Fn(&DecoderOptions) -> bool) -> Option<u32> {
+        self.tracks
+            .iter()
+            .find(|(_, protected)| matches_codec(&protected.decoder_options))
+            .map(|(&track_id, _)| track_id)
+    }
+}
+
 impl Mp4FileReader<File> {
-    pub fn from_path(path: &Path) -> Result<Self, Mp4InputError> {
+    pub fn from_path(path: &Path, protection: Arc<Mp4Protection>) -> Result<Self, Mp4InputError> {
         let file = std::fs::File::open(path)?;
         let size = file.metadata()?.size();
-        Self::new(file, size)
+        let reader = mp4::Mp4Reader::read_header(file, size)?;
+        Ok(Mp4FileReader { reader, protection })
     }
 }
 
 impl<Reader: Read + Seek + Send + 'static> Mp4FileReader<Reader> {
-    fn new(reader: Reader, size: u64) -> Result<Self, Mp4InputError> {
-        let reader = mp4::Mp4Reader::read_header(reader, size)?;
+    fn into_protected_track(self, track_id: u32) -> Option<Track<Reader>> {
+        let protected = self.protection.tracks.get(&track_id)?.clone();
+        let key = self.protection.key?;
+        let track = self.reader.tracks().get(&track_id)?;
+
+        let (track_start_offset, presentation_delay) =
+            Self::calculate_elst_edits(track, self.reader.timescale());
 
-        Ok(Mp4FileReader { reader })
+        Some(Track {
+            sample_count: track.sample_count(),
+            timescale: track.timescale(),
+            track_id,
+            duration: track.duration(),
+            decoder_options: protected.decoder_options.clone(),
+            decryptor: Some(SampleDecryptor::new(key, protected)),
+            track_start_offset,
+            presentation_delay,
+            reader: self.reader,
+        })
     }
 
     pub fn try_new_aac_track(self) -> Option<Track<Reader>> {
+        if let Some(track_id) = self
+            .protection
+            .find_track(|options| matches!(options, DecoderOptions::Aac(_)))
+        {
+            return self.into_protected_track(track_id);
+        }
+
         let (&track_id, track, aac) = self.reader.tracks().iter().find_map(|(id, track)| {
             let track_type = track.track_type().ok()?;
             let media_type = track.media_type().ok()?;
@@ -74,6 +179,7 @@ impl<Reader: Read + Seek + Send + 'static> Mp4FileReader<Reader> {
             track_id,
             duration: track.duration(),
             decoder_options: DecoderOptions::Aac(asc),
+            decryptor: None,
             track_start_offset,
             presentation_delay,
             reader: self.reader,
@@ -81,6 +187,13 @@ impl<Reader: Read + Seek + Send + 'static> Mp4FileReader<Reader> {
     }
 
     pub fn try_new_h264_track(self) -> Option<Track<Reader>> {
+        if let Some(track_id) = self
+            .protection
+            .find_track(|options| matches!(options, DecoderOptions::H264(_)))
+        {
+            return self.into_protected_track(track_id);
+        }
+
         let (&track_id, track, avc) = self.reader.tracks().iter().find_map(|(id, track)| {
             let track_type = track.track_type().ok()?;
             let media_type = track.media_type().ok()?;
@@ -120,6 +233,7 @@ impl<Reader: Read + Seek + Send + 'static> Mp4FileReader<Reader> {
             track_id,
             duration: track.duration(),
             decoder_options: DecoderOptions::H264(h264_config),
+            decryptor: None,
             track_start_offset: offset,
             presentation_delay: delay,
             reader: self.reader,
@@ -169,6 +283,7 @@ pub(crate) struct Track<Reader: Read + Seek + Send + 'static> {
     track_id: u32,
     duration: Duration,
     decoder_options: DecoderOptions,
+    decryptor: Option<SampleDecryptor>,
 
     /// How much time should be cut from the beginning of the track, derived from the `media_time`
     /// field of the first non-empty edit in the `elst` box.
@@ -278,6 +393,7 @@ impl<Reader: Read + Seek + Send + 'static> Track<Reader> {
     }
 }
 
+/// Ends after the first sample that fails to decrypt, the error is returned in place of it.
 pub(crate) struct TrackChunks<'a, Reader: Read + Seek + Send + 'static> {
     track: &'a mut Track<Reader>,
 
@@ -290,7 +406,7 @@ pub(crate) struct TrackChunks<'a, Reader: Read + Seek + Send + 'static> {
 }
 
 impl<Reader: Read + Seek + Send + 'static> Iterator for TrackChunks<'_, Reader> {
-    type Item = (EncodedInputChunk, Duration);
+    type Item = Result<(EncodedInputChunk, Duration), Mp4DecryptionError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         while self.next_sample_index <= self.track.sample_count {
@@ -301,8 +417,17 @@ impl<Reader: Read + Seek + Send + 'static> Iterator for TrackChunks<'_, Reader>
                 .read_sample(self.track.track_id, sample_index);
             self.next_sample_index += 1;
             match sample {
-                Ok(Some(sample)) => {
-                    return Some(self.sample_into_chunk(sample, sample_index));
+                Ok(Some(mut sample)) => {
+                    if let Some(decryptor) = &self.track.decryptor {
+                        match decryptor.decrypt(sample_index, sample.bytes) {
+                            Ok(bytes) => sample.bytes = bytes,
+                            Err(err) => {
+                                self.next_sample_index = self.track.sample_count + 1;
+                                return Some(Err(err));
+                            }
+                        }
+                    }
+                    return Some(Ok(self.sample_into_chunk(sample, sample_index)));
                 }
                 Ok(None) => {}
                 Err(err) => {
@@ -7,6 +7,8 @@ use crossbeam_channel::Sender;
 use smelter_render::OutputFrameFormat;
 use tracing::{info, warn};
 
+use debug_overlay::DebugOverlay;
+
 use crate::pipeline::{
     hls::HlsOutput,
     input::PipelineInput,
@@ -18,10 +20,34 @@ use crate::pipeline::{
 };
 use crate::prelude::*;
 
+mod debug_overlay;
+
 pub(crate) struct PipelineOutput {
     pub output: Box<dyn Output>,
     pub video_end_condition: Option<PipelineOutputEndConditionState>,
     pub audio_end_condition: Option<PipelineOutputEndConditionState>,
+    pub debug_overlay: Option<Arc<DebugOverlay>>,
+}
+
+/// Sender for rendered video frames of a single output.
+pub(super) struct OutputVideoSender {
+    sender: Sender<PipelineEvent<Frame>>,
+    debug_overlay: Option<Arc<DebugOverlay>>,
+}
+
+impl OutputVideoSender {
+    pub fn send(
+        &self,
+        event: PipelineEvent<Frame>,
+    ) -> Result<(), crossbeam_channel::SendError<PipelineEvent<Frame>>> {
+        let event = match (event, &self.debug_overlay) {
+            (PipelineEvent::Data(frame), Some(overlay)) => {
+                PipelineEvent::Data(overlay.apply(frame))
+            }
+            (event, _) => event,
+        };
+        self.sender.send(event)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -108,9 +134,42 @@ where
     }
 
     let pipeline_ctx = pipeline.lock().unwrap().ctx.clone();
+    let rtp_timestamp_offset = pipeline_ctx.rtp_timestamp_offset;
     let (output, output_result) = build_output(pipeline_ctx, Ref::new(&output_id))
         .map_err(|e| RegisterOutputError::OutputError(output_id.clone(), e))?;
 
+    let kind = output.kind();
+    if !rtp_timestamp_offset.is_zero()
+        && matches!(
+            kind,
+            OutputProtocolKind::Mp4
+                | OutputProtocolKind::Hls
+                | OutputProtocolKind::Rtmp
+                | OutputProtocolKind::MoqClient
+        )
+    {
+        warn!(
+            ?output_id,
+            ?kind,
+            "RTP timestamp epoch is only applied to RTP, WHIP and WHEP outputs. Timestamps of this output start at zero."
+        );
+    }
+
+    // Frame format is known only after the encoder is created.
+    let debug_overlay = match (&video, output.video()) {
+        (Some(video), Some(video_output)) if video.debug_overlay => {
+            match video_output.frame_format {
+                OutputFrameFormat::PlanarYuv420Bytes
+                | OutputFrameFormat::PlanarYuv422Bytes
+                | OutputFrameFormat::PlanarYuv444Bytes => Some(Arc::new(DebugOverlay::default())),
+                OutputFrameFormat::RgbaWgpuTexture | OutputFrameFormat::Nv12WgpuTexture => {
+                    return Err(RegisterOutputError::DebugOverlayNotSupported(output_id));
+                }
+            }
+        }
+        _ => None,
+    };
+
     let mut guard = pipeline.lock().unwrap();
 
     if guard.outputs.contains_key(&output_id) {
@@ -119,6 +178,7 @@ where
 
     let output = PipelineOutput {
         output,
+        debug_overlay,
         audio_end_condition: audio.as_ref().map(|audio| {
             PipelineOutputEndConditionState::new_audio(audio.end_condition.clone(), &guard.inputs)
         }),
@@ -158,7 +218,7 @@ where
 impl Pipeline {
     pub(super) fn all_output_video_senders_iter(
         pipeline: &Arc<Mutex<Pipeline>>,
-    ) -> impl Iterator<Item = (OutputId, OutputSender<Sender<PipelineEvent<Frame>>>)> {
+    ) -> impl Iterator<Item = (OutputId, OutputSender<OutputVideoSender>)> {
         let outputs: HashMap<_, _> = pipeline
             .lock()
             .unwrap()
@@ -166,7 +226,10 @@ impl Pipeline {
             .iter_mut()
             .filter_map(|(output_id, output)| {
                 let eos_status = output.video_end_condition.as_mut()?.eos_status();
-                let sender = output.output.video()?.frame_sender.clone();
+                let sender = OutputVideoSender {
+                    sender: output.output.video()?.frame_sender.clone(),
+                    debug_overlay: output.debug_overlay.clone(),
+                };
                 Some((output_id.clone(), (sender, eos_status)))
             })
             .collect();
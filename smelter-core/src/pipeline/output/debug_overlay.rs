@@ -0,0 +1,221 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use bytes::{Bytes, BytesMut};
+use smelter_render::{FrameData, YuvPlanes};
+
+use crate::prelude::*;
+
+// Limited range BT.709 values
+const Y_BLACK: u8 = 16;
+const Y_WHITE: u8 = 235;
+const CHROMA_NEUTRAL: u8 = 128;
+
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+
+/// Burns the frame index and pts into the top-left corner of every frame sent to the output.
+///
+/// Text is drawn with a fixed bitmap font as white digits on a black background, so it can be
+/// read back from captured frames.
+#[derive(Debug, Default)]
+pub(crate) struct DebugOverlay {
+    next_frame_index: AtomicU64,
+}
+
+impl DebugOverlay {
+    pub fn apply(&self, frame: Frame) -> Frame {
+        let frame_index = self.next_frame_index.fetch_add(1, Ordering::Relaxed);
+        let label = overlay_label(frame_index, frame.pts);
+
+        let (planes, chroma_subsampling) = match frame.data {
+            FrameData::PlanarYuv420(planes) => (planes, (2, 2)),
+            FrameData::PlanarYuv422(planes) => (planes, (2, 1)),
+            FrameData::PlanarYuv444(planes) => (planes, (1, 1)),
+            // Outputs with other formats are rejected on registration.
+            data => return Frame { data, ..frame },
+        };
+
+        let planes = draw_label(&label, planes, frame.resolution, chroma_subsampling);
+        let data = match chroma_subsampling {
+            (2, 2) => FrameData::PlanarYuv420(planes),
+            (2, 1) => FrameData::PlanarYuv422(planes),
+            _ => FrameData::PlanarYuv444(planes),
+        };
+        Frame { data, ..frame }
+    }
+}
+
+/// Frame index and pts formatted as `000123 00:00:04.100`.
+fn overlay_label(frame_index: u64, pts: Duration) -> String {
+    let millis = pts.as_millis();
+    format!(
+        "{frame_index:06} {:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000,
+    )
+}
+
+struct Rect {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+fn draw_label(
+    label: &str,
+    planes: YuvPlanes,
+    resolution: Resolution,
+    (sub_x, sub_y): (usize, usize),
+) -> YuvPlanes {
+    let scale = (resolution.height / 160).max(1);
+    let margin = 4 * scale;
+    let padding = scale;
+    let advance = (GLYPH_WIDTH + 1) * scale;
+
+    let background = Rect {
+        x: margin,
+        y: margin,
+        width: label.len() * advance - scale + 2 * padding,
+        height: GLYPH_HEIGHT * scale + 2 * padding,
+    };
+
+    let mut y_plane = BytesMut::from(&planes.y_plane[..]);
+    fill_rect(
+        &mut y_plane,
+        resolution.width,
+        resolution.height,
+        &background,
+        Y_BLACK,
+    );
+    for (index, char) in label.chars().enumerate() {
+        let glyph_x = background.x + padding + index * advance;
+        let glyph_y = background.y + padding;
+        for (row, bits) in glyph(char).iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                    continue;
+                }
+                let pixel = Rect {
+                    x: glyph_x + column * scale,
+                    y: glyph_y + row * scale,
+                    width: scale,
+                    height: scale,
+                };
+                fill_rect(
+                    &mut y_plane,
+                    resolution.width,
+                    resolution.height,
+                    &pixel,
+                    Y_WHITE,
+                );
+            }
+        }
+    }
+
+    let chroma_width = resolution.width.div_ceil(sub_x);
+    let chroma_height = resolution.height.div_ceil(sub_y);
+    let chroma_background = Rect {
+        x: background.x / sub_x,
+        y: background.y / sub_y,
+        width: background.width.div_ceil(sub_x) + 1,
+        height: background.height.div_ceil(sub_y) + 1,
+    };
+    let fill_chroma = |plane: &Bytes| {
+        let mut plane = BytesMut::from(&plane[..]);
+        fill_rect(
+            &mut plane,
+            chroma_width,
+            chroma_height,
+            &chroma_background,
+            CHROMA_NEUTRAL,
+        );
+        plane.freeze()
+    };
+
+    YuvPlanes {
+        y_plane: y_plane.freeze(),
+        u_plane: fill_chroma(&planes.u_plane),
+        v_plane: fill_chroma(&planes.v_plane),
+    }
+}
+
+fn fill_rect(plane: &mut [u8], width: usize, height: usize, rect: &Rect, value: u8) {
+    let x_end = usize::min(rect.x + rect.width, width);
+    let y_end = usize::min(rect.y + rect.height, height);
+    if rect.x >= x_end || plane.len() < width * height {
+        return;
+    }
+    for y in rect.y..y_end {
+        plane[y * width + rect.x..y * width + x_end].fill(value);
+    }
+}
+
+/// 3x5 bitmap, every row is stored in the 3 lowest bits.
+fn glyph(char: char) -> [u8; GLYPH_HEIGHT] {
+    match char {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        _ => [0b000; GLYPH_HEIGHT],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_overlay_label() {
+        assert_eq!(overlay_label(0, Duration::ZERO), "000000 00:00:00.000");
+        assert_eq!(
+            overlay_label(123, Duration::from_millis(3_723_456)),
+            "000123 01:02:03.456"
+        );
+    }
+
+    #[test]
+    fn draw_overlay_on_yuv420_frame() {
+        let resolution = Resolution {
+            width: 320,
+            height: 240,
+        };
+        let frame = Frame {
+            data: FrameData::PlanarYuv420(YuvPlanes {
+                y_plane: vec![100; 320 * 240].into(),
+                u_plane: vec![50; 160 * 120].into(),
+                v_plane: vec![50; 160 * 120].into(),
+            }),
+            resolution,
+            pts: Duration::from_millis(40),
+        };
+
+        let overlay = DebugOverlay::default();
+        let frame = overlay.apply(frame);
+        let FrameData::PlanarYuv420(planes) = frame.data else {
+            panic!("Expected YUV420 frame");
+        };
+
+        // background in the corner and unchanged pixels outside of it
+        assert_eq!(planes.y_plane[4 * 320 + 4], Y_BLACK);
+        assert_eq!(planes.y_plane[239 * 320 + 319], 100);
+        assert_eq!(planes.u_plane[2 * 160 + 2], CHROMA_NEUTRAL);
+        assert_eq!(planes.u_plane[119 * 160 + 159], 50);
+        // top-left pixel of the first "0" glyph
+        assert_eq!(planes.y_plane[5 * 320 + 5], Y_WHITE);
+    }
+}
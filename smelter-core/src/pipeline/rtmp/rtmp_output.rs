@@ -12,6 +12,7 @@ use rtmp::{
 use smelter_render::OutputFrameFormat;
 
 use crate::{
+    diagnostics::{CancelToken, TaskKind},
     event::Event,
     pipeline::{
         encoder::{
@@ -86,13 +87,24 @@ impl RtmpClientOutput {
             .spawn(move || {
                 let _span = tracing::info_span!("RTMP sender", output_id = output_ref.to_string())
                     .entered();
+                let task = ctx.task_registry.register(
+                    "RTMP sender",
+                    Some(output_ref.to_string()),
+                    TaskKind::Thread,
+                );
+                let cancel = task.cancel_token();
 
                 let stats_sender = RtmpOutputStatsSender {
                     stats_sender: ctx.stats_sender.clone(),
                     output_ref: output_ref.clone(),
                 };
-                let result =
-                    run_rtmp_output_thread(client, video_config, audio_config, stats_sender);
+                let result = run_rtmp_output_thread(
+                    client,
+                    video_config,
+                    audio_config,
+                    stats_sender,
+                    cancel,
+                );
                 if let Err(err) = result {
                     warn!("{}", ErrorStack::new(&err).into_string())
                 }
@@ -320,6 +332,7 @@ fn run_rtmp_output_thread(
     video_config: Option<VideoConfig>,
     audio_config: Option<AudioConfig>,
     stats_sender: RtmpOutputStatsSender,
+    cancel: CancelToken,
 ) -> Result<(), RtmpStreamError> {
     match (video_config, audio_config) {
         (Some(video), Some(audio)) => run_synced_av(
@@ -329,10 +342,14 @@ fn run_rtmp_output_thread(
             &audio.chunks_receiver,
             audio.codec,
             stats_sender,
+            &cancel,
         ),
         (Some(video), None) => {
             let codec = video.codec;
             while let Ok(EncodedOutputEvent::Data(chunk)) = video.chunks_receiver.recv() {
+                if cancel.is_cancelled() {
+                    break;
+                }
                 stats_sender.bytes_sent_event(chunk.data.len(), StatsTrackKind::Video);
                 client.send(video_chunk_to_event(chunk, codec))?;
             }
@@ -341,6 +358,9 @@ fn run_rtmp_output_thread(
         (None, Some(audio)) => {
             let codec = audio.codec;
             while let Ok(EncodedOutputEvent::Data(chunk)) = audio.chunks_receiver.recv() {
+                if cancel.is_cancelled() {
+                    break;
+                }
                 stats_sender.bytes_sent_event(chunk.data.len(), StatsTrackKind::Audio);
                 client.send(audio_chunk_to_event(chunk, codec))?;
             }
@@ -357,6 +377,7 @@ fn run_synced_av(
     audio_rx: &Receiver<EncodedOutputEvent>,
     audio_codec: RtmpAudioCodec,
     rtmp_stats_sender: RtmpOutputStatsSender,
+    cancel: &CancelToken,
 ) -> Result<(), RtmpStreamError> {
     let mut pending_video: Option<EncodedOutputChunk> = None;
     let mut pending_audio: Option<EncodedOutputChunk> = None;
@@ -366,6 +387,9 @@ fn run_synced_av(
     // Each iteration can either send or receive. It will never do both
     // in the same iteration.
     loop {
+        if cancel.is_cancelled() {
+            return Ok(());
+        }
         let need_video = pending_video.is_none() && !video_eos;
         let need_audio = pending_audio.is_none() && !audio_eos;
 
@@ -0,0 +1,120 @@
+use smelter_render::error::ErrorStack;
+use tracing::debug;
+
+use crate::pipeline::rtp::{RtpInputEvent, depayloader::DepayloadingError};
+
+use crate::prelude::*;
+
+/// Decodes RTP packets of a video codec that is not supported by smelter.
+///
+/// Used instead of both the depayloader and the decoder, packets are passed in sequence
+/// number order after the jitter buffer. [`RtpInputEvent::LostPacket`] is passed when
+/// the jitter buffer gave up on a missing packet. PTS of returned frames should be taken
+/// from `RtpPacket::timestamp`.
+///
+/// Errors are logged and the event is dropped, the decoder is used for the following packets.
+pub trait RtpVideoDecoder {
+    fn decode(&mut self, event: RtpInputEvent) -> Result<Vec<Frame>, DepayloadingError>;
+    fn flush(&mut self) -> Vec<Frame>;
+}
+
+/// Decodes RTP packets of an audio codec that is not supported by smelter.
+///
+/// Follows the same contract as [`RtpVideoDecoder`].
+pub trait RtpAudioDecoder {
+    fn decode(&mut self, event: RtpInputEvent)
+    -> Result<Vec<InputAudioSamples>, DepayloadingError>;
+    fn flush(&mut self) -> Vec<InputAudioSamples>;
+}
+
+pub(crate) struct CustomVideoDecoderStream<Source>
+where
+    Source: Iterator<Item = PipelineEvent<RtpInputEvent>>,
+{
+    decoder: Box<dyn RtpVideoDecoder>,
+    source: Source,
+}
+
+impl<Source> CustomVideoDecoderStream<Source>
+where
+    Source: Iterator<Item = PipelineEvent<RtpInputEvent>>,
+{
+    pub fn new(decoder: Box<dyn RtpVideoDecoder>, source: Source) -> Self {
+        Self { decoder, source }
+    }
+}
+
+impl<Source> Iterator for CustomVideoDecoderStream<Source>
+where
+    Source: Iterator<Item = PipelineEvent<RtpInputEvent>>,
+{
+    type Item = Vec<Frame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.source.next() {
+            Some(PipelineEvent::Data(event)) => match self.decoder.decode(event) {
+                Ok(frames) => Some(frames),
+                Err(err) => {
+                    debug!(
+                        "Custom decoder error: {}",
+                        ErrorStack::new(&err).into_string()
+                    );
+                    Some(vec![])
+                }
+            },
+            Some(PipelineEvent::EOS) | None => {
+                let frames = self.decoder.flush();
+                match frames.is_empty() {
+                    false => Some(frames),
+                    true => None,
+                }
+            }
+        }
+    }
+}
+
+pub(crate) struct CustomAudioDecoderStream<Source>
+where
+    Source: Iterator<Item = PipelineEvent<RtpInputEvent>>,
+{
+    decoder: Box<dyn RtpAudioDecoder>,
+    source: Source,
+}
+
+impl<Source> CustomAudioDecoderStream<Source>
+where
+    Source: Iterator<Item = PipelineEvent<RtpInputEvent>>,
+{
+    pub fn new(decoder: Box<dyn RtpAudioDecoder>, source: Source) -> Self {
+        Self { decoder, source }
+    }
+}
+
+impl<Source> Iterator for CustomAudioDecoderStream<Source>
+where
+    Source: Iterator<Item = PipelineEvent<RtpInputEvent>>,
+{
+    type Item = Vec<InputAudioSamples>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.source.next() {
+            Some(PipelineEvent::Data(event)) => match self.decoder.decode(event) {
+                Ok(samples) => Some(samples),
+                Err(err) => {
+                    debug!(
+                        "Custom decoder error: {}",
+                        ErrorStack::new(&err).into_string()
+                    );
+                    Some(vec![])
+                }
+            },
+            Some(PipelineEvent::EOS) | None => {
+                let samples = self.decoder.flush();
+                match samples.is_empty() {
+                    false => Some(samples),
+                    true => None,
+                }
+            }
+        }
+    }
+}
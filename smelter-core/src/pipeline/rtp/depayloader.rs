@@ -10,7 +10,7 @@ use webrtc::rtp::{
 
 use crate::{
     codecs::{AacAudioSpecificConfig, AudioCodec, VideoCodec},
-    pipeline::rtp::RtpPacket,
+    pipeline::rtp::{RtpPacket, registry::RtpDepayloaderFactory},
     protocols::{AacDepayloadingError, RtpAacDepayloaderMode},
 };
 use crate::{pipeline::decoder::EncodedInputEvent, prelude::*};
@@ -33,6 +33,7 @@ pub enum DepayloaderOptions {
     Vp9,
     Opus,
     Aac(RtpAacDepayloaderMode, AacAudioSpecificConfig),
+    Custom(RtpDepayloaderFactory),
 }
 
 pub fn new_depayloader(options: DepayloaderOptions) -> Box<dyn Depayloader> {
@@ -51,10 +52,28 @@ pub fn new_depayloader(options: DepayloaderOptions) -> Box<dyn Depayloader> {
             SimpleDepayloader::<OpusPacket>::new_boxed(MediaKind::Audio(AudioCodec::Opus))
         }
         DepayloaderOptions::Aac(mode, asc) => Box::new(AacDepayloader::new(mode, asc)),
+        DepayloaderOptions::Custom(factory) => factory.create(),
     }
 }
 
-pub(crate) trait Depayloader {
+/// Converts RTP packets into chunks of encoded media.
+///
+/// Packets are passed in sequence number order after the jitter buffer. Missing packets
+/// are not reported to the depayloader, so an implementation that needs to detect loss
+/// has to track sequence numbers on its own. `RtpPacket::timestamp` is the RTP timestamp
+/// already converted to the PTS of the input.
+///
+/// Returned events are passed to the decoder:
+/// - [`EncodedInputEvent::Chunk`] - encoded data in a format expected by the decoder
+///   (e.g. Annex B for H264), `pts` should be taken from the packet.
+/// - [`EncodedInputEvent::AuDelimiter`] - signals that all chunks of an access unit were
+///   already returned, usually emitted when the marker bit is set.
+/// - [`EncodedInputEvent::LostData`] - signals that the data could not be recovered and the
+///   decoder should wait for the next keyframe.
+///
+/// Returning an empty list is valid if the packet did not complete any chunk yet. Errors are
+/// logged and the packet is dropped, the depayloader is used for the following packets.
+pub trait Depayloader {
     fn depayload(&mut self, packet: RtpPacket)
     -> Result<Vec<EncodedInputEvent>, DepayloadingError>;
 }
@@ -65,6 +84,8 @@ pub enum DepayloadingError {
     Rtp(#[from] rtp::Error),
     #[error("AAC depayloading error")]
     Aac(#[from] AacDepayloadingError),
+    #[error(transparent)]
+    Custom(Box<dyn std::error::Error + Send + Sync>),
 }
 
 struct BufferedDepayloader<T: Depacketizer + Default + 'static> {
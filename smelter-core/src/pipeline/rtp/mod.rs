@@ -1,7 +1,9 @@
 use std::time::Duration;
 
+pub(super) mod custom_decoder;
 pub(super) mod depayloader;
 pub(super) mod payloader;
+pub(super) mod registry;
 
 mod rtp_input;
 mod rtp_output;
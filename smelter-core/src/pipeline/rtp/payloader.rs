@@ -1,15 +1,17 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 use tracing::info;
 
+use bytes::Bytes;
 use rand::Rng;
 use webrtc::rtp::{
     self,
     codecs::{h264::H264Payloader, opus::OpusPayloader, vp8::Vp8Payloader, vp9::Vp9Payloader},
+    packetizer::Payloader as _,
 };
 
 use crate::prelude::*;
 
-use super::RtpPacket;
+use super::{RtpPacket, registry::RtpPayloaderFactory};
 
 #[derive(Debug)]
 pub enum PayloadedCodec {
@@ -17,6 +19,7 @@ pub enum PayloadedCodec {
     Vp8,
     Vp9,
     Opus,
+    Custom(RtpPayloaderFactory),
 }
 
 #[derive(Debug)]
@@ -26,25 +29,103 @@ pub struct PayloaderOptions {
     pub clock_rate: u32,
     pub mtu: usize,
     pub ssrc: u32,
+    /// Added to the PTS of every chunk before it is converted to an RTP timestamp.
+    /// See [`RtpTimestampEpoch`](crate::RtpTimestampEpoch).
+    pub pts_offset: Duration,
+}
+
+/// Splits encoded chunks into RTP payloads.
+///
+/// Each call receives a single chunk produced by the encoder (one access unit for video,
+/// one frame for audio) and returns payloads of consecutive RTP packets, each of them
+/// at most `mtu` bytes long. RTP headers are added by smelter: all packets of a chunk
+/// share a timestamp calculated from the chunk PTS, sequence numbers are assigned in
+/// order, and the marker bit is set on the last packet. Returning an empty list skips
+/// the chunk.
+pub trait RtpPayloader: Send {
+    fn payload(
+        &mut self,
+        mtu: usize,
+        chunk: &EncodedOutputChunk,
+    ) -> Result<Vec<Bytes>, PayloadingError>;
+}
+
+/// H264 payloader ([RFC 6184](https://datatracker.ietf.org/doc/html/rfc6184)), expects
+/// chunks in Annex B format.
+#[derive(Default)]
+pub struct H264RtpPayloader(H264Payloader);
+
+/// VP8 payloader ([RFC 7741](https://datatracker.ietf.org/doc/html/rfc7741)).
+#[derive(Default)]
+pub struct Vp8RtpPayloader(Vp8Payloader);
+
+/// VP9 payloader ([RFC 9628](https://datatracker.ietf.org/doc/html/rfc9628)).
+#[derive(Default)]
+pub struct Vp9RtpPayloader(Vp9Payloader);
+
+/// Opus payloader ([RFC 7587](https://datatracker.ietf.org/doc/html/rfc7587)).
+#[derive(Default)]
+pub struct OpusRtpPayloader(OpusPayloader);
+
+impl RtpPayloader for H264RtpPayloader {
+    fn payload(
+        &mut self,
+        mtu: usize,
+        chunk: &EncodedOutputChunk,
+    ) -> Result<Vec<Bytes>, PayloadingError> {
+        Ok(self.0.payload(mtu, &chunk.data)?)
+    }
+}
+
+impl RtpPayloader for Vp8RtpPayloader {
+    fn payload(
+        &mut self,
+        mtu: usize,
+        chunk: &EncodedOutputChunk,
+    ) -> Result<Vec<Bytes>, PayloadingError> {
+        Ok(self.0.payload(mtu, &chunk.data)?)
+    }
+}
+
+impl RtpPayloader for Vp9RtpPayloader {
+    fn payload(
+        &mut self,
+        mtu: usize,
+        chunk: &EncodedOutputChunk,
+    ) -> Result<Vec<Bytes>, PayloadingError> {
+        Ok(self.0.payload(mtu, &chunk.data)?)
+    }
+}
+
+impl RtpPayloader for OpusRtpPayloader {
+    fn payload(
+        &mut self,
+        mtu: usize,
+        chunk: &EncodedOutputChunk,
+    ) -> Result<Vec<Bytes>, PayloadingError> {
+        Ok(self.0.payload(mtu, &chunk.data)?)
+    }
 }
 
 pub(crate) struct Payloader {
-    payloader: Box<dyn rtp::packetizer::Payloader + Send>,
+    payloader: Box<dyn RtpPayloader>,
     mtu: usize,
     ssrc: u32,
     payload_type: u8,
     clock_rate: u32,
+    pts_offset: Duration,
     next_sequence_number: u16,
 }
 
 impl Payloader {
     pub fn new(options: PayloaderOptions) -> Self {
         info!(?options, "Initialize RTP payloader");
-        let payloader: Box<dyn rtp::packetizer::Payloader + Send> = match options.codec {
-            PayloadedCodec::H264 => Box::new(H264Payloader::default()),
-            PayloadedCodec::Vp8 => Box::new(Vp8Payloader::default()),
-            PayloadedCodec::Vp9 => Box::new(Vp9Payloader::default()),
-            PayloadedCodec::Opus => Box::new(OpusPayloader),
+        let payloader: Box<dyn RtpPayloader> = match options.codec {
+            PayloadedCodec::H264 => Box::new(H264RtpPayloader::default()),
+            PayloadedCodec::Vp8 => Box::new(Vp8RtpPayloader::default()),
+            PayloadedCodec::Vp9 => Box::new(Vp9RtpPayloader::default()),
+            PayloadedCodec::Opus => Box::new(OpusRtpPayloader::default()),
+            PayloadedCodec::Custom(factory) => factory.create(),
         };
         Self {
             ssrc: options.ssrc,
@@ -52,6 +133,7 @@ impl Payloader {
             payloader,
             payload_type: options.payload_type,
             clock_rate: options.clock_rate,
+            pts_offset: options.pts_offset,
             next_sequence_number: rand::rng().random::<u16>(),
         }
     }
@@ -60,10 +142,12 @@ impl Payloader {
         &mut self,
         chunk: EncodedOutputChunk,
     ) -> Result<Vec<RtpPacket>, PayloadingError> {
-        let payloads = self.payloader.payload(self.mtu, &chunk.data)?;
+        let payloads = self.payloader.payload(self.mtu, &chunk)?;
         let packets_amount = payloads.len();
-        let timestamp = (chunk.pts.as_secs_f64() * self.clock_rate as f64).round() as u64;
-        let timestamp = timestamp % u32::MAX as u64;
+        let pts = chunk.pts + self.pts_offset;
+        let timestamp = (pts.as_secs_f64() * self.clock_rate as f64).round() as u64;
+        // RTP timestamp is 32-bit and wraps around modulo 2^32
+        let timestamp = timestamp % (1 << 32);
 
         payloads
             .into_iter()
@@ -148,4 +232,62 @@ pub enum PayloadingError {
 
     #[error(transparent)]
     MarshalError(#[from] webrtc_util::Error),
+
+    #[error(transparent)]
+    Custom(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payloader(pts_offset: Duration) -> Payloader {
+        Payloader::new(PayloaderOptions {
+            codec: PayloadedCodec::Opus,
+            payload_type: 111,
+            clock_rate: 48_000,
+            mtu: 1400,
+            ssrc: 1,
+            pts_offset,
+        })
+    }
+
+    fn chunk(pts: Duration) -> EncodedOutputChunk {
+        EncodedOutputChunk {
+            data: Bytes::from_static(&[1, 2, 3]),
+            pts,
+            dts: None,
+            is_keyframe: true,
+            kind: MediaKind::Audio(AudioCodec::Opus),
+        }
+    }
+
+    fn rtp_timestamp(payloader: &mut Payloader, pts: Duration) -> u32 {
+        let packets = payloader.payload(chunk(pts)).unwrap();
+        assert_eq!(packets.len(), 1);
+        // offset is applied only to the RTP timestamp
+        assert_eq!(packets[0].timestamp, pts);
+        packets[0].packet.header.timestamp
+    }
+
+    #[test]
+    fn pts_offset_shifts_rtp_timestamps() {
+        let pts = Duration::from_millis(20);
+        assert_eq!(rtp_timestamp(&mut payloader(Duration::ZERO), pts), 960);
+        assert_eq!(
+            rtp_timestamp(&mut payloader(Duration::from_secs(10)), pts),
+            480_960
+        );
+    }
+
+    #[test]
+    fn pts_offset_wraps_around() {
+        // Unix time offset overflows 32-bit timestamps, they wrap modulo 2^32
+        let offset = Duration::from_secs(1_700_000_000);
+        let expected = (1_700_000_000u64 * 48_000 + 960) % (1 << 32);
+        assert_eq!(
+            rtp_timestamp(&mut payloader(offset), Duration::from_millis(20)),
+            expected as u32
+        );
+    }
 }
@@ -0,0 +1,234 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use crate::pipeline::rtp::{
+    custom_decoder::{RtpAudioDecoder, RtpVideoDecoder},
+    depayloader::Depayloader,
+    payloader::RtpPayloader,
+};
+
+/// Custom RTP codec implementations keyed by RTP payload type.
+///
+/// Payload types of RTP inputs and outputs are configured per track
+/// ([`RtpPayloadTypes`](crate::protocols::RtpPayloadTypes), 96 for video and 97 for audio by
+/// default), and the registry is queried with the payload type of the track:
+/// - A depayloader replaces the built-in one, chunks it produces are still passed to the
+///   decoder selected in the input options.
+/// - A video or audio decoder handles packets of a codec that smelter does not support. It
+///   is used when the input track is configured with a custom decoder and receives packets
+///   straight from the jitter buffer.
+/// - A payloader replaces the built-in one and receives chunks produced by the encoder
+///   selected in the output options.
+///
+/// Payload types of WHIP/WHEP connections are negotiated, so those protocols always use
+/// built-in implementations.
+#[derive(Debug, Clone, Default)]
+pub struct RtpCodecRegistry {
+    depayloaders: HashMap<u8, RtpDepayloaderFactory>,
+    payloaders: HashMap<u8, RtpPayloaderFactory>,
+    video_decoders: HashMap<u8, RtpVideoDecoderFactory>,
+    audio_decoders: HashMap<u8, RtpAudioDecoderFactory>,
+}
+
+impl RtpCodecRegistry {
+    /// Registers a depayloader for the payload type. A new instance is created for every
+    /// input track.
+    pub fn register_depayloader<F>(&mut self, payload_type: u8, factory: F) -> &mut Self
+    where
+        F: Fn() -> Box<dyn Depayloader> + Send + Sync + 'static,
+    {
+        self.depayloaders
+            .insert(payload_type, RtpCodecFactory(Arc::new(factory)));
+        self
+    }
+
+    /// Registers a payloader for the payload type. A new instance is created for every
+    /// output track.
+    pub fn register_payloader<F>(&mut self, payload_type: u8, factory: F) -> &mut Self
+    where
+        F: Fn() -> Box<dyn RtpPayloader> + Send + Sync + 'static,
+    {
+        self.payloaders
+            .insert(payload_type, RtpCodecFactory(Arc::new(factory)));
+        self
+    }
+
+    /// Registers a video decoder for the payload type. A new instance is created for every
+    /// input track.
+    pub fn register_video_decoder<F>(&mut self, payload_type: u8, factory: F) -> &mut Self
+    where
+        F: Fn() -> Box<dyn RtpVideoDecoder> + Send + Sync + 'static,
+    {
+        self.video_decoders
+            .insert(payload_type, RtpCodecFactory(Arc::new(factory)));
+        self
+    }
+
+    /// Registers an audio decoder for the payload type. A new instance is created for every
+    /// input track.
+    pub fn register_audio_decoder<F>(&mut self, payload_type: u8, factory: F) -> &mut Self
+    where
+        F: Fn() -> Box<dyn RtpAudioDecoder> + Send + Sync + 'static,
+    {
+        self.audio_decoders
+            .insert(payload_type, RtpCodecFactory(Arc::new(factory)));
+        self
+    }
+
+    pub(crate) fn depayloader(&self, payload_type: u8) -> Option<RtpDepayloaderFactory> {
+        self.depayloaders.get(&payload_type).cloned()
+    }
+
+    pub(crate) fn payloader(&self, payload_type: u8) -> Option<RtpPayloaderFactory> {
+        self.payloaders.get(&payload_type).cloned()
+    }
+
+    pub(crate) fn video_decoder(&self, payload_type: u8) -> Option<RtpVideoDecoderFactory> {
+        self.video_decoders.get(&payload_type).cloned()
+    }
+
+    pub(crate) fn audio_decoder(&self, payload_type: u8) -> Option<RtpAudioDecoderFactory> {
+        self.audio_decoders.get(&payload_type).cloned()
+    }
+}
+
+pub type RtpDepayloaderFactory = RtpCodecFactory<dyn Depayloader>;
+pub type RtpPayloaderFactory = RtpCodecFactory<dyn RtpPayloader>;
+pub type RtpVideoDecoderFactory = RtpCodecFactory<dyn RtpVideoDecoder>;
+pub type RtpAudioDecoderFactory = RtpCodecFactory<dyn RtpAudioDecoder>;
+
+pub struct RtpCodecFactory<T: ?Sized>(Arc<dyn Fn() -> Box<T> + Send + Sync>);
+
+impl<T: ?Sized> RtpCodecFactory<T> {
+    pub(crate) fn create(&self) -> Box<T> {
+        (self.0)()
+    }
+}
+
+impl<T: ?Sized> Clone for RtpCodecFactory<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for RtpCodecFactory<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RtpCodecFactory")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use bytes::Bytes;
+
+    use crate::{
+        pipeline::{
+            decoder::EncodedInputEvent,
+            rtp::{RtpInputEvent, RtpPacket, depayloader::DepayloadingError},
+        },
+        prelude::*,
+    };
+
+    use super::*;
+
+    struct TestDepayloader;
+
+    impl Depayloader for TestDepayloader {
+        fn depayload(
+            &mut self,
+            _packet: RtpPacket,
+        ) -> Result<Vec<EncodedInputEvent>, DepayloadingError> {
+            Ok(vec![EncodedInputEvent::AuDelimiter])
+        }
+    }
+
+    struct TestPayloader(usize);
+
+    impl RtpPayloader for TestPayloader {
+        fn payload(
+            &mut self,
+            _mtu: usize,
+            _chunk: &EncodedOutputChunk,
+        ) -> Result<Vec<Bytes>, crate::pipeline::rtp::payloader::PayloadingError> {
+            Ok(vec![Bytes::new(); self.0])
+        }
+    }
+
+    struct TestAudioDecoder;
+
+    impl RtpAudioDecoder for TestAudioDecoder {
+        fn decode(
+            &mut self,
+            _event: RtpInputEvent,
+        ) -> Result<Vec<InputAudioSamples>, DepayloadingError> {
+            Ok(vec![])
+        }
+
+        fn flush(&mut self) -> Vec<InputAudioSamples> {
+            vec![]
+        }
+    }
+
+    fn test_chunk() -> EncodedOutputChunk {
+        EncodedOutputChunk {
+            data: Bytes::new(),
+            pts: Duration::ZERO,
+            dts: None,
+            is_keyframe: true,
+            kind: MediaKind::Audio(AudioCodec::Opus),
+        }
+    }
+
+    #[test]
+    fn lookup_by_payload_type() {
+        let mut registry = RtpCodecRegistry::default();
+        registry
+            .register_depayloader(100, || Box::new(TestDepayloader))
+            .register_payloader(101, || Box::new(TestPayloader(1)))
+            .register_audio_decoder(102, || Box::new(TestAudioDecoder));
+
+        assert!(registry.depayloader(100).is_some());
+        assert!(registry.payloader(101).is_some());
+        assert!(registry.audio_decoder(102).is_some());
+
+        assert!(registry.depayloader(101).is_none());
+        assert!(registry.payloader(100).is_none());
+        assert!(registry.video_decoder(102).is_none());
+        assert!(registry.depayloader(96).is_none());
+        assert!(registry.payloader(97).is_none());
+    }
+
+    #[test]
+    fn later_registration_replaces_previous_one() {
+        let mut registry = RtpCodecRegistry::default();
+        registry
+            .register_payloader(110, || Box::new(TestPayloader(1)))
+            .register_payloader(110, || Box::new(TestPayloader(3)));
+
+        let mut payloader = registry.payloader(110).unwrap().create();
+        let payloads = payloader.payload(1400, &test_chunk()).unwrap();
+        assert_eq!(payloads.len(), 3);
+    }
+
+    #[test]
+    fn factory_creates_instance_per_track() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let created_clone = created.clone();
+
+        let mut registry = RtpCodecRegistry::default();
+        registry.register_depayloader(120, move || {
+            created_clone.fetch_add(1, Ordering::Relaxed);
+            Box::new(TestDepayloader)
+        });
+
+        assert_eq!(created.load(Ordering::Relaxed), 0);
+        let factory = registry.depayloader(120).unwrap();
+        let _first = factory.create();
+        let _second = registry.clone().depayloader(120).unwrap().create();
+        assert_eq!(created.load(Ordering::Relaxed), 2);
+    }
+}
@@ -29,6 +29,7 @@ use crate::{
                 rtp_audio_thread::{
                     RtpAudioThread, RtpAudioThreadOptions, RtpAudioTrackThreadHandle,
                 },
+                rtp_custom_thread::{RtpCustomAudioThread, RtpCustomVideoThread},
                 rtp_video_thread::{RtpVideoThread, RtpVideoTrackThreadHandle},
             },
             util::BindToPortError,
@@ -42,6 +43,7 @@ use crate::prelude::*;
 
 mod rollover_state;
 mod rtp_audio_thread;
+mod rtp_custom_thread;
 mod rtp_video_thread;
 mod tcp_server;
 mod udp;
@@ -127,8 +129,21 @@ impl RtpInput {
             },
         });
 
-        let video_handle = Self::start_video_thread(&ctx, &input_ref, opts.video, video_sender)?;
-        let audio_handle = Self::start_audio_thread(&ctx, &input_ref, opts.audio, audio_sender)?;
+        let payload_types = opts.payload_types;
+        let video_handle = Self::start_video_thread(
+            &ctx,
+            &input_ref,
+            payload_types.video,
+            opts.video,
+            video_sender,
+        )?;
+        let audio_handle = Self::start_audio_thread(
+            &ctx,
+            &input_ref,
+            payload_types.audio,
+            opts.audio,
+            audio_sender,
+        )?;
 
         // TODO: this could ran on the same thread as tcp/udp socket
         RtpDemuxerThread::spawn(
@@ -136,6 +151,7 @@ impl RtpInput {
             &input_ref,
             jitter_buffer_ctx,
             raw_packets_receiver,
+            payload_types,
             video_handle,
             audio_handle,
             opts.offset.is_some(),
@@ -151,25 +167,52 @@ impl RtpInput {
     fn start_video_thread(
         ctx: &Arc<PipelineCtx>,
         input_ref: &Ref<InputId>,
-        options: Option<VideoDecoderOptions>,
+        payload_type: u8,
+        options: Option<RtpVideoOptions>,
         frame_sender: Option<QueueSender<Frame>>,
     ) -> Result<Option<RtpVideoTrackThreadHandle>, DecoderInitError> {
         let (Some(options), Some(frame_sender)) = (options, frame_sender) else {
             return Ok(None);
         };
 
+        let options = match options {
+            RtpVideoOptions::Decoder(options) => options,
+            RtpVideoOptions::Custom => {
+                let Some(factory) = ctx.rtp_codec_registry.video_decoder(payload_type) else {
+                    return Err(DecoderInitError::RtpDecoderNotRegistered(payload_type));
+                };
+                let handle = RtpCustomVideoThread::spawn(
+                    input_ref.clone(),
+                    (ctx.clone(), factory, frame_sender),
+                )?;
+                return Ok(Some(handle));
+            }
+        };
+
         let handle = match options {
             VideoDecoderOptions::FfmpegH264 => RtpVideoThread::<FfmpegH264Decoder>::spawn(
                 input_ref.clone(),
-                (ctx.clone(), DepayloaderOptions::H264, frame_sender),
+                (
+                    ctx.clone(),
+                    depayloader_options(ctx, payload_type, DepayloaderOptions::H264),
+                    frame_sender,
+                ),
             )?,
             VideoDecoderOptions::FfmpegVp8 => RtpVideoThread::<FfmpegVp8Decoder>::spawn(
                 input_ref.clone(),
-                (ctx.clone(), DepayloaderOptions::Vp8, frame_sender),
+                (
+                    ctx.clone(),
+                    depayloader_options(ctx, payload_type, DepayloaderOptions::Vp8),
+                    frame_sender,
+                ),
             )?,
             VideoDecoderOptions::FfmpegVp9 => RtpVideoThread::<FfmpegVp9Decoder>::spawn(
                 input_ref.clone(),
-                (ctx.clone(), DepayloaderOptions::Vp9, frame_sender),
+                (
+                    ctx.clone(),
+                    depayloader_options(ctx, payload_type, DepayloaderOptions::Vp9),
+                    frame_sender,
+                ),
             )?,
             VideoDecoderOptions::VulkanH264 => {
                 if !ctx.graphics_context.has_vulkan_decoder_support() {
@@ -177,7 +220,11 @@ impl RtpInput {
                 }
                 RtpVideoThread::<VulkanH264Decoder>::spawn(
                     input_ref.clone(),
-                    (ctx.clone(), DepayloaderOptions::H264, frame_sender),
+                    (
+                        ctx.clone(),
+                        depayloader_options(ctx, payload_type, DepayloaderOptions::H264),
+                        frame_sender,
+                    ),
                 )?
             }
         };
@@ -187,6 +234,7 @@ impl RtpInput {
     fn start_audio_thread(
         ctx: &Arc<PipelineCtx>,
         input_ref: &Ref<InputId>,
+        payload_type: u8,
         options: Option<RtpAudioOptions>,
         samples_sender: Option<QueueSender<InputAudioSamples>>,
     ) -> Result<Option<RtpAudioTrackThreadHandle>, DecoderInitError> {
@@ -201,7 +249,11 @@ impl RtpInput {
                     ctx: ctx.clone(),
                     sample_rate: 48_000,
                     decoder_options: (),
-                    depayloader_options: DepayloaderOptions::Opus,
+                    depayloader_options: depayloader_options(
+                        ctx,
+                        payload_type,
+                        DepayloaderOptions::Opus,
+                    ),
                     samples_sender,
                 },
             )?,
@@ -215,15 +267,40 @@ impl RtpInput {
                     ctx: ctx.clone(),
                     sample_rate: asc.sample_rate,
                     decoder_options: FdkAacDecoderOptions { asc: Some(raw_asc) },
-                    depayloader_options: DepayloaderOptions::Aac(depayloader_mode, asc),
+                    depayloader_options: depayloader_options(
+                        ctx,
+                        payload_type,
+                        DepayloaderOptions::Aac(depayloader_mode, asc),
+                    ),
                     samples_sender,
                 },
             )?,
+            RtpAudioOptions::Custom { clock_rate } => {
+                let Some(factory) = ctx.rtp_codec_registry.audio_decoder(payload_type) else {
+                    return Err(DecoderInitError::RtpDecoderNotRegistered(payload_type));
+                };
+                RtpCustomAudioThread::spawn(
+                    input_ref,
+                    (ctx.clone(), factory, clock_rate, samples_sender),
+                )?
+            }
         };
         Ok(Some(handle))
     }
 }
 
+/// Custom depayloader registered for the payload type takes precedence over the built-in one.
+fn depayloader_options(
+    ctx: &PipelineCtx,
+    payload_type: u8,
+    default: DepayloaderOptions,
+) -> DepayloaderOptions {
+    match ctx.rtp_codec_registry.depayloader(payload_type) {
+        Some(factory) => DepayloaderOptions::Custom(factory),
+        None => default,
+    }
+}
+
 impl Drop for RtpInput {
     fn drop(&mut self) {
         self.should_close
@@ -252,6 +329,7 @@ impl RtpDemuxerThread {
         input_ref: &Ref<InputId>,
         jitter_buffer_ctx: RtpJitterBufferSharedContext,
         receiver: Receiver<bytes::Bytes>,
+        payload_types: RtpPayloadTypes,
         video_handle: Option<RtpVideoTrackThreadHandle>,
         audio_handle: Option<RtpAudioTrackThreadHandle>,
         has_offset: bool,
@@ -262,7 +340,7 @@ impl RtpDemuxerThread {
             let stats_sender = ctx.stats_sender.clone();
             let ref_clone = input_ref.clone();
             tracks.push(TrackState {
-                payload_type: 96,
+                payload_type: payload_types.video,
                 ssrc: None,
                 jitter_buffer: RtpJitterBuffer::new(
                     jitter_buffer_ctx.clone(),
@@ -282,7 +360,7 @@ impl RtpDemuxerThread {
             let ref_clone = input_ref.clone();
             let sample_rate = handle.sample_rate;
             tracks.push(TrackState {
-                payload_type: 97,
+                payload_type: payload_types.audio,
                 ssrc: None,
                 jitter_buffer: RtpJitterBuffer::new(
                     jitter_buffer_ctx,
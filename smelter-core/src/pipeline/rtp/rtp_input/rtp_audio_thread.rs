@@ -3,6 +3,7 @@ use std::{marker::PhantomData, sync::Arc, time::Duration};
 use tracing::warn;
 
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     pipeline::{
         decoder::{AudioDecoder, AudioDecoderStream},
         rtp::{
@@ -78,8 +79,8 @@ impl<Decoder: AudioDecoder + 'static> InitializableThread for RtpAudioThread<Dec
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.samples_sender.send(event).is_err() {
                 warn!("Failed to send decoded audio samples from decoder. Channel closed.");
                 return;
@@ -87,6 +88,10 @@ impl<Decoder: AudioDecoder + 'static> InitializableThread for RtpAudioThread<Dec
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.ctx.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: format!("Rtp Audio Decoder ({})", Decoder::LABEL),
@@ -0,0 +1,134 @@
+use std::{sync::Arc, time::Duration};
+
+use tracing::warn;
+
+use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
+    pipeline::{
+        PipelineCtx,
+        rtp::{
+            custom_decoder::{CustomAudioDecoderStream, CustomVideoDecoderStream},
+            registry::{RtpAudioDecoderFactory, RtpVideoDecoderFactory},
+            rtp_input::{
+                rtp_audio_thread::RtpAudioTrackThreadHandle,
+                rtp_video_thread::RtpVideoTrackThreadHandle,
+            },
+        },
+    },
+    queue::QueueSender,
+    utils::{InitializableThread, ThreadMetadata, channel::duration_bounded},
+};
+
+use crate::prelude::*;
+
+const RTP_BUFFER: Duration = Duration::from_secs(1);
+
+/// Decodes video track with a decoder registered in `RtpCodecRegistry`, bypassing
+/// the built-in depayloader and decoder.
+pub(super) struct RtpCustomVideoThread {
+    stream: Box<dyn Iterator<Item = Frame>>,
+    frame_sender: QueueSender<Frame>,
+}
+
+impl InitializableThread for RtpCustomVideoThread {
+    type InitOptions = (Arc<PipelineCtx>, RtpVideoDecoderFactory, QueueSender<Frame>);
+
+    type SpawnOutput = RtpVideoTrackThreadHandle;
+    type SpawnError = DecoderInitError;
+
+    fn init(options: Self::InitOptions) -> Result<(Self, Self::SpawnOutput), Self::SpawnError> {
+        let (_ctx, decoder_factory, frame_sender) = options;
+
+        let (rtp_packet_sender, rtp_packet_receiver) = duration_bounded(RTP_BUFFER);
+        let decoder_stream = CustomVideoDecoderStream::new(
+            decoder_factory.create(),
+            rtp_packet_receiver.into_iter(),
+        );
+
+        let state = Self {
+            stream: Box::new(decoder_stream.flatten()),
+            frame_sender,
+        };
+        let output = RtpVideoTrackThreadHandle { rtp_packet_sender };
+        Ok((state, output))
+    }
+
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
+            if self.frame_sender.send(event).is_err() {
+                warn!("Failed to send decoded video frame from decoder. Channel closed.");
+                return;
+            }
+        }
+    }
+
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.0.task_registry
+    }
+
+    fn metadata() -> ThreadMetadata {
+        ThreadMetadata {
+            thread_name: "Rtp Video Decoder (custom)".to_string(),
+            thread_instance_name: "Input".to_string(),
+        }
+    }
+}
+
+/// Decodes audio track with a decoder registered in `RtpCodecRegistry`, bypassing
+/// the built-in depayloader and decoder.
+pub(super) struct RtpCustomAudioThread {
+    stream: Box<dyn Iterator<Item = InputAudioSamples>>,
+    samples_sender: QueueSender<InputAudioSamples>,
+}
+
+impl InitializableThread for RtpCustomAudioThread {
+    type InitOptions = (
+        Arc<PipelineCtx>,
+        RtpAudioDecoderFactory,
+        u32,
+        QueueSender<InputAudioSamples>,
+    );
+
+    type SpawnOutput = RtpAudioTrackThreadHandle;
+    type SpawnError = DecoderInitError;
+
+    fn init(options: Self::InitOptions) -> Result<(Self, Self::SpawnOutput), Self::SpawnError> {
+        let (_ctx, decoder_factory, clock_rate, samples_sender) = options;
+
+        let (rtp_packet_sender, rtp_packet_receiver) = duration_bounded(RTP_BUFFER);
+        let decoder_stream = CustomAudioDecoderStream::new(
+            decoder_factory.create(),
+            rtp_packet_receiver.into_iter(),
+        );
+
+        let state = Self {
+            stream: Box::new(decoder_stream.flatten()),
+            samples_sender,
+        };
+        let output = RtpAudioTrackThreadHandle {
+            rtp_packet_sender,
+            sample_rate: clock_rate,
+        };
+        Ok((state, output))
+    }
+
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
+            if self.samples_sender.send(event).is_err() {
+                warn!("Failed to send decoded audio samples from decoder. Channel closed.");
+                return;
+            }
+        }
+    }
+
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.0.task_registry
+    }
+
+    fn metadata() -> ThreadMetadata {
+        ThreadMetadata {
+            thread_name: "Rtp Audio Decoder (custom)".to_string(),
+            thread_instance_name: "Input".to_string(),
+        }
+    }
+}
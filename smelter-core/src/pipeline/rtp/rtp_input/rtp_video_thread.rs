@@ -3,6 +3,7 @@ use std::{marker::PhantomData, sync::Arc, time::Duration};
 use tracing::warn;
 
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     pipeline::{
         decoder::{VideoDecoder, VideoDecoderStream},
         rtp::{
@@ -38,13 +39,16 @@ impl<Decoder: VideoDecoder> InitializableThread for RtpVideoThread<Decoder> {
     type SpawnError = DecoderInitError;
 
     fn init(options: Self::InitOptions) -> Result<(Self, Self::SpawnOutput), Self::SpawnError> {
-        let (ctx, depayloader_options, frame_sender) = options;
+        let (ctx, depayloader_options, mut frame_sender) = options;
 
         let (rtp_packet_sender, rtp_packet_receiver) = duration_bounded(RTP_BUFFER);
         let depayloader_stream =
             DepayloaderStream::new(depayloader_options, rtp_packet_receiver.into_iter());
-        let decoder_stream =
-            VideoDecoderStream::<Decoder, _>::new(ctx, depayloader_stream.flatten())?;
+        let decoder_stream = VideoDecoderStream::<Decoder, _>::new(
+            ctx,
+            depayloader_stream.flatten(),
+            frame_sender.take_stats_callback(),
+        )?;
 
         let state = Self {
             stream: Box::new(decoder_stream.flatten()),
@@ -55,8 +59,8 @@ impl<Decoder: VideoDecoder> InitializableThread for RtpVideoThread<Decoder> {
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.frame_sender.send(event).is_err() {
                 warn!("Failed to send decoded video frame from decoder. Channel closed.");
                 return;
@@ -64,6 +68,10 @@ impl<Decoder: VideoDecoder> InitializableThread for RtpVideoThread<Decoder> {
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.0.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: format!("Rtp Video Decoder ({})", Decoder::LABEL),
@@ -13,7 +13,10 @@ use smelter_render::{InputId, error::ErrorStack};
 use tracing::error;
 use tracing::{Level, debug, info, span, trace};
 
-use crate::pipeline::{Port, rtp::util::bind_to_requested_port};
+use crate::pipeline::{
+    Port,
+    rtp::util::{apply_socket_options, bind_to_requested_port},
+};
 
 use crate::prelude::*;
 
@@ -34,6 +37,7 @@ pub(super) fn start_tcp_server_thread(
     )
     .map_err(RtpInputError::SocketOptions)?;
 
+    apply_socket_options(&socket, &opts.socket_options).map_err(RtpInputError::SocketOptions)?;
     let port = bind_to_requested_port(opts.port, &socket)?;
 
     socket.listen(1).map_err(RtpInputError::SocketBind)?;
@@ -8,12 +8,17 @@ use crossbeam_channel::{Receiver, Sender, unbounded};
 use smelter_render::InputId;
 use tracing::{Level, debug, span, warn};
 
-use crate::pipeline::{Port, rtp::util::bind_to_requested_port};
+use crate::pipeline::{
+    Port,
+    rtp::util::{apply_socket_options, bind_to_requested_port},
+};
 
 use crate::prelude::*;
 
 use super::{RtpInputError, RtpInputOptions};
 
+const DEFAULT_RECV_BUFFER_SIZE: usize = 16 * 1024 * 1024;
+
 pub(super) fn start_udp_reader_thread(
     input_ref: &Ref<InputId>,
     opts: &RtpInputOptions,
@@ -28,17 +33,14 @@ pub(super) fn start_udp_reader_thread(
     )
     .map_err(RtpInputError::SocketOptions)?;
 
-    match socket
-        .set_recv_buffer_size(16 * 1024 * 1024)
-        .map_err(RtpInputError::SocketOptions)
+    if opts.socket_options.recv_buffer_size.is_none()
+        && let Err(e) = socket.set_recv_buffer_size(DEFAULT_RECV_BUFFER_SIZE)
     {
-        Ok(_) => {}
-        Err(e) => {
-            warn!(
-                "Failed to set socket receive buffer size: {e} This may cause packet loss, especially on high-bitrate streams."
-            );
-        }
+        warn!(
+            "Failed to set socket receive buffer size: {e} This may cause packet loss, especially on high-bitrate streams."
+        );
     }
+    apply_socket_options(&socket, &opts.socket_options).map_err(RtpInputError::SocketOptions)?;
 
     let port = bind_to_requested_port(opts.port, &socket)?;
 
@@ -9,6 +9,7 @@ use tracing::{Level, debug, span};
 use webrtc::rtcp;
 
 use crate::{
+    diagnostics::TaskKind,
     event::Event,
     pipeline::{
         encoder::{
@@ -37,9 +38,9 @@ mod tcp_server;
 mod udp;
 
 pub(crate) struct RtpOutput {
-    /// should_close will be set after output is unregistered,
-    /// but the primary way of controlling the shutdown is a channel
-    /// receiver.
+    /// should_close will be set after output is unregistered or the
+    /// sender task is cancelled, but the primary way of controlling
+    /// the shutdown is a channel receiver.
     ///
     /// RtpSender should be explicitly closed based on this value
     /// only if TCP connection is disconnected or writes hang for a
@@ -72,8 +73,12 @@ impl RtpOutput {
         });
 
         let (socket, port) = match &options.connection_options {
-            RtpOutputConnectionOptions::Udp { port, ip } => udp::udp_socket(ip, *port)?,
-            RtpOutputConnectionOptions::TcpServer { port } => tcp_server::tcp_socket(*port)?,
+            RtpOutputConnectionOptions::Udp { port, ip } => {
+                udp::udp_socket(ip, *port, &options.socket_options)?
+            }
+            RtpOutputConnectionOptions::TcpServer { port } => {
+                tcp_server::tcp_socket(*port, &options.socket_options)?
+            }
         };
 
         let (rtp_sender, rtp_receiver) = bounded(1);
@@ -83,6 +88,7 @@ impl RtpOutput {
                 &ctx,
                 &output_ref,
                 mtu,
+                options.payload_types.video,
                 video,
                 rtp_sender.clone(),
             )?),
@@ -93,19 +99,21 @@ impl RtpOutput {
                 &ctx,
                 &output_ref,
                 mtu,
+                options.payload_types.audio,
                 audio,
                 rtp_sender.clone(),
             )?),
             None => None,
         };
 
+        let should_close = Arc::new(AtomicBool::new(false));
         let rtp_stream = RtpBinaryPacketStream {
             receiver: rtp_receiver,
             waiting_audio_eos: audio.is_some(),
             waiting_video_eos: video.is_some(),
+            should_close: should_close.clone(),
         };
 
-        let should_close = Arc::new(AtomicBool::new(false));
         let connection_options = options.connection_options;
         let should_close2 = should_close.clone();
         std::thread::Builder::new()
@@ -117,6 +125,15 @@ impl RtpOutput {
                     output_id = output_ref.to_string()
                 )
                 .entered();
+                let task = ctx.task_registry.register(
+                    "RTP sender",
+                    Some(output_ref.to_string()),
+                    TaskKind::Thread,
+                );
+                let should_close3 = should_close2.clone();
+                task.set_cancel(move || {
+                    should_close3.store(true, std::sync::atomic::Ordering::Relaxed)
+                });
                 match connection_options {
                     RtpOutputConnectionOptions::Udp { .. } => {
                         udp::run_udp_sender_thread(socket, rtp_stream)
@@ -145,18 +162,20 @@ impl RtpOutput {
         ctx: &Arc<PipelineCtx>,
         output_ref: &Ref<OutputId>,
         mtu: usize,
+        payload_type: u8,
         options: VideoEncoderOptions,
         sender: Sender<RtpOutputEvent>,
     ) -> Result<RtpVideoTrackThreadHandle, OutputInitError> {
-        fn payloader_options(codec: PayloadedCodec, mtu: usize) -> PayloaderOptions {
+        let payloader_options = |codec: PayloadedCodec| -> PayloaderOptions {
             PayloaderOptions {
-                codec,
-                payload_type: 96,
+                codec: payloaded_codec(ctx, payload_type, codec),
+                payload_type,
                 clock_rate: 90000,
                 mtu,
                 ssrc: rand::rng().random::<u32>(),
+                pts_offset: ctx.rtp_timestamp_offset,
             }
-        }
+        };
 
         let thread_handle = match &options {
             VideoEncoderOptions::FfmpegH264(options) => {
@@ -166,7 +185,7 @@ impl RtpOutput {
                         ctx: ctx.clone(),
                         output_ref: output_ref.clone(),
                         encoder_options: options.clone(),
-                        payloader_options: payloader_options(PayloadedCodec::H264, mtu),
+                        payloader_options: payloader_options(PayloadedCodec::H264),
                         chunks_sender: sender,
                     },
                 )?
@@ -183,7 +202,7 @@ impl RtpOutput {
                         ctx: ctx.clone(),
                         output_ref: output_ref.clone(),
                         encoder_options: options.clone(),
-                        payloader_options: payloader_options(PayloadedCodec::H264, mtu),
+                        payloader_options: payloader_options(PayloadedCodec::H264),
                         chunks_sender: sender,
                     },
                 )?
@@ -195,7 +214,7 @@ impl RtpOutput {
                         ctx: ctx.clone(),
                         output_ref: output_ref.clone(),
                         encoder_options: options.clone(),
-                        payloader_options: payloader_options(PayloadedCodec::Vp8, mtu),
+                        payloader_options: payloader_options(PayloadedCodec::Vp8),
                         chunks_sender: sender,
                     },
                 )?
@@ -207,7 +226,7 @@ impl RtpOutput {
                         ctx: ctx.clone(),
                         output_ref: output_ref.clone(),
                         encoder_options: options.clone(),
-                        payloader_options: payloader_options(PayloadedCodec::Vp9, mtu),
+                        payloader_options: payloader_options(PayloadedCodec::Vp9),
                         chunks_sender: sender,
                     },
                 )?
@@ -220,22 +239,20 @@ impl RtpOutput {
         ctx: &Arc<PipelineCtx>,
         output_ref: &Ref<OutputId>,
         mtu: usize,
+        payload_type: u8,
         options: AudioEncoderOptions,
         sender: Sender<RtpOutputEvent>,
     ) -> Result<RtpAudioTrackThreadHandle, OutputInitError> {
-        fn payloader_options(
-            codec: PayloadedCodec,
-            sample_rate: u32,
-            mtu: usize,
-        ) -> PayloaderOptions {
+        let payloader_options = |codec: PayloadedCodec, sample_rate: u32| -> PayloaderOptions {
             PayloaderOptions {
-                codec,
-                payload_type: 97,
+                codec: payloaded_codec(ctx, payload_type, codec),
+                payload_type,
                 clock_rate: sample_rate,
                 mtu,
                 ssrc: rand::rng().random::<u32>(),
+                pts_offset: ctx.rtp_timestamp_offset,
             }
-        }
+        };
 
         let thread_handle = match options {
             AudioEncoderOptions::Opus(options) => RtpAudioTrackThread::<OpusEncoder>::spawn(
@@ -244,7 +261,7 @@ impl RtpOutput {
                     ctx: ctx.clone(),
                     output_ref: output_ref.clone(),
                     encoder_options: options.clone(),
-                    payloader_options: payloader_options(PayloadedCodec::Opus, 48_000, mtu),
+                    payloader_options: payloader_options(PayloadedCodec::Opus, 48_000),
                     chunks_sender: sender,
                 },
             )?,
@@ -256,6 +273,14 @@ impl RtpOutput {
     }
 }
 
+/// Custom payloader registered for the payload type takes precedence over the built-in one.
+fn payloaded_codec(ctx: &PipelineCtx, payload_type: u8, default: PayloadedCodec) -> PayloadedCodec {
+    match ctx.rtp_codec_registry.payloader(payload_type) {
+        Some(factory) => PayloadedCodec::Custom(factory),
+        None => default,
+    }
+}
+
 impl Drop for RtpOutput {
     fn drop(&mut self) {
         self.should_close
@@ -1,3 +1,8 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
 use tracing::error;
 use webrtc_util::Marshal;
 
@@ -9,6 +14,8 @@ pub(super) struct RtpBinaryPacketStream {
     pub receiver: Receiver<RtpOutputEvent>,
     pub waiting_audio_eos: bool,
     pub waiting_video_eos: bool,
+    /// Set when the output is unregistered or the sender task is cancelled.
+    pub should_close: Arc<AtomicBool>,
 }
 
 impl Iterator for RtpBinaryPacketStream {
@@ -18,6 +25,9 @@ impl Iterator for RtpBinaryPacketStream {
         if !self.waiting_video_eos && !self.waiting_audio_eos {
             return None;
         }
+        if self.should_close.load(Ordering::Relaxed) {
+            return None;
+        }
         match self.receiver.recv() {
             Ok(RtpOutputEvent::Data(packet)) => match packet.packet.marshal() {
                 Ok(data) => Some(vec![data]),
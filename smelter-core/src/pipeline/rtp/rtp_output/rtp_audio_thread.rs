@@ -7,6 +7,7 @@ use webrtc::rtcp;
 
 use crate::prelude::*;
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     error::EncoderInitError,
     pipeline::{
         encoder::{AudioEncoder, AudioEncoderStream, resampler::ResampledForEncoderStream},
@@ -97,8 +98,8 @@ where
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.chunks_sender.send(event).is_err() {
                 warn!("Failed to send encoded audio chunk from encoder. Channel closed.");
                 return;
@@ -106,6 +107,10 @@ where
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.ctx.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: format!("Rtp Audio Encoder ({})", Encoder::LABEL),
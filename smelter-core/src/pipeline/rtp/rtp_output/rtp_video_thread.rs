@@ -8,6 +8,7 @@ use webrtc::rtcp;
 
 use crate::prelude::*;
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     pipeline::{
         encoder::{VideoEncoder, VideoEncoderConfig, VideoEncoderStream},
         rtp::payloader::{PayloaderOptions, PayloaderStream},
@@ -95,8 +96,8 @@ where
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.chunks_sender.send(event).is_err() {
                 warn!("Failed to send encoded video chunk from encoder. Channel closed.");
                 return;
@@ -104,6 +105,10 @@ where
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.ctx.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: format!("Rtp Video Encoder ({})", Encoder::LABEL),
@@ -11,14 +11,17 @@ use crate::{
     error::OutputInitError,
     pipeline::{
         Port,
-        rtp::util::{BindToPortError, bind_to_requested_port},
+        rtp::util::{BindToPortError, apply_socket_options, bind_to_requested_port},
     },
-    protocols::PortOrRange,
+    protocols::{PortOrRange, RtpSocketOptions},
 };
 
 use super::RtpBinaryPacketStream;
 
-pub(super) fn tcp_socket(port: PortOrRange) -> Result<(socket2::Socket, Port), OutputInitError> {
+pub(super) fn tcp_socket(
+    port: PortOrRange,
+    socket_options: &RtpSocketOptions,
+) -> Result<(socket2::Socket, Port), OutputInitError> {
     let socket = socket2::Socket::new(
         socket2::Domain::IPV4,
         socket2::Type::STREAM,
@@ -26,6 +29,7 @@ pub(super) fn tcp_socket(port: PortOrRange) -> Result<(socket2::Socket, Port), O
     )
     .map_err(OutputInitError::SocketError)?;
 
+    apply_socket_options(&socket, socket_options).map_err(OutputInitError::SocketError)?;
     let port = bind_to_requested_port(port, &socket)?;
 
     socket.listen(1).map_err(OutputInitError::SocketError)?;
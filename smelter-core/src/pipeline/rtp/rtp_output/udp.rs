@@ -1,17 +1,27 @@
 use tracing::{debug, trace};
 
-use crate::{error::OutputInitError, pipeline::Port};
+use crate::{
+    error::OutputInitError,
+    pipeline::{Port, rtp::util::apply_socket_options},
+    protocols::RtpSocketOptions,
+};
 
 use super::RtpBinaryPacketStream;
 
-pub(super) fn udp_socket(ip: &str, port: Port) -> Result<(socket2::Socket, Port), OutputInitError> {
+pub(super) fn udp_socket(
+    ip: &str,
+    port: Port,
+    socket_options: &RtpSocketOptions,
+) -> Result<(socket2::Socket, Port), OutputInitError> {
     let socket = std::net::UdpSocket::bind(std::net::SocketAddrV4::new(
         std::net::Ipv4Addr::UNSPECIFIED,
         0,
     ))?;
 
     socket.connect((ip, port.0))?;
-    Ok((socket.into(), port))
+    let socket = socket2::Socket::from(socket);
+    apply_socket_options(&socket, socket_options)?;
+    Ok((socket, port))
 }
 
 /// this assumes, that a "packet" contains data about a single frame (access unit)
@@ -1,6 +1,8 @@
-use std::net;
+use std::{io, net};
 
-use crate::protocols::{Port, PortOrRange};
+use tracing::{info, warn};
+
+use crate::protocols::{Port, PortOrRange, RtpSocketOptions};
 
 pub(super) enum BindToPortError {
     SocketBind(std::io::Error),
@@ -48,3 +50,44 @@ pub(super) fn bind_to_requested_port(
     };
     Ok(Port(port))
 }
+
+/// Applies buffer sizes and DSCP marking to the socket. OS may clamp buffer sizes
+/// (e.g. to `net.core.rmem_max`/`net.core.wmem_max` on Linux), so values that were actually
+/// applied are read back and logged.
+///
+/// For TCP it should be called before `listen`, accepted connections inherit those options.
+pub(super) fn apply_socket_options(
+    socket: &socket2::Socket,
+    options: &RtpSocketOptions,
+) -> io::Result<()> {
+    if let Some(size) = options.recv_buffer_size {
+        socket.set_recv_buffer_size(size)?;
+        log_buffer_size("receive", size, socket.recv_buffer_size()?);
+    }
+    if let Some(size) = options.send_buffer_size {
+        socket.set_send_buffer_size(size)?;
+        log_buffer_size("send", size, socket.send_buffer_size()?);
+    }
+    if let Some(dscp) = options.dscp {
+        socket.set_tos((dscp as u32) << 2)?;
+    }
+    Ok(())
+}
+
+fn log_buffer_size(kind: &str, requested: usize, applied: usize) {
+    // Linux doubles the requested value to account for the bookkeeping overhead, and the
+    // doubled value is reported back, so the size was only applied if it's at least double.
+    let expected = match cfg!(target_os = "linux") {
+        true => requested.saturating_mul(2),
+        false => requested,
+    };
+    if applied < expected {
+        warn!(
+            requested,
+            applied,
+            "Socket {kind} buffer size was clamped by the OS. Increase the system limit to use the requested size."
+        );
+    } else {
+        info!(requested, applied, "Socket {kind} buffer size set.");
+    }
+}
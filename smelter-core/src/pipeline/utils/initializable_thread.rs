@@ -1,4 +1,6 @@
-use tracing::{Level, span};
+use tracing::{Level, info, span};
+
+use crate::diagnostics::{CancelToken, TaskKind, TaskRegistry, TaskState};
 
 pub(crate) trait InitializableThread: Sized {
     type InitOptions: Send + 'static;
@@ -10,7 +12,12 @@ pub(crate) trait InitializableThread: Sized {
 
     fn init(options: Self::InitOptions) -> Result<(Self, Self::SpawnOutput), Self::SpawnError>;
 
-    fn run(self);
+    /// Runs until the work is done. Implementations should return when `cancel` is set,
+    /// it is set when the thread is cancelled with the diagnostics API.
+    fn run(self, cancel: CancelToken);
+
+    /// Registry of the pipeline that the thread belongs to.
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry;
 
     fn spawn<Id: ToString>(
         thread_instance_id: Id,
@@ -20,14 +27,22 @@ pub(crate) trait InitializableThread: Sized {
 
         let instance_id = thread_instance_id.to_string();
         let metadata = Self::metadata();
+        let task_registry = Self::task_registry(&opts).clone();
         std::thread::Builder::new()
             .name(metadata.thread_name.to_string())
             .spawn(move || {
+                let instance = format!("{} {}", metadata.thread_instance_name, instance_id);
+                let task = task_registry.register(
+                    metadata.thread_name.clone(),
+                    Some(instance.clone()),
+                    TaskKind::Thread,
+                );
+                task.set_state(TaskState::Initializing);
                 let _span = span!(
                     Level::INFO,
                     "Thread",
                     thread = metadata.thread_name,
-                    instance = format!("{} {}", metadata.thread_instance_name, instance_id),
+                    instance = instance,
                 )
                 .entered();
                 let state = match Self::init(opts) {
@@ -40,7 +55,12 @@ pub(crate) trait InitializableThread: Sized {
                         return;
                     }
                 };
-                Self::run(state);
+                task.set_state(TaskState::Running);
+                let cancel = task.cancel_token();
+                Self::run(state, cancel.clone());
+                if cancel.is_cancelled() {
+                    info!("Thread stopped after it was cancelled.");
+                }
             })
             .unwrap();
 
@@ -1,6 +1,9 @@
 use std::{
     path::Path,
-    sync::{Arc, atomic::AtomicBool},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
     time::Duration,
 };
 
@@ -9,6 +12,7 @@ use smelter_render::{FrameData, Framerate, InputId, NvPlanes, Resolution};
 use tracing::{Level, debug, error, info, span, trace, warn};
 
 use crate::{
+    diagnostics::TaskKind,
     pipeline::input::Input,
     queue::{QueueInput, QueueSender, QueueTrackOffset, QueueTrackOptions},
 };
@@ -105,6 +109,13 @@ impl V4l2Input {
             .name(format!("V4L2 reader thread for input {input_ref}"))
             .spawn(move || {
                 let _span = span!(Level::INFO, "V4L2", input_id = input_ref.to_string()).entered();
+                let task = state.ctx.task_registry.register(
+                    "V4L2 reader",
+                    Some(input_ref.to_string()),
+                    TaskKind::Thread,
+                );
+                let should_close = state.should_close.clone();
+                task.set_cancel(move || should_close.store(true, Ordering::Relaxed));
                 state.run();
                 info!("Stopping input.");
             })
@@ -162,6 +173,11 @@ impl V4l2DeviceConfig {
             return Err(V4l2InputError::CaptureNotSupported);
         }
 
+        match read_formats(&device, &opts.path) {
+            Ok(formats) => Self::validate_options(&device, opts, &formats)?,
+            Err(err) => warn!(%err, "Failed to enumerate supported formats. Skipping validation."),
+        }
+
         let format = Self::try_set_format(&device, opts.format)?;
 
         let resolution = match opts.resolution {
@@ -186,6 +202,75 @@ impl V4l2DeviceConfig {
         })
     }
 
+    /// Checks requested format, resolution and framerate against the values advertised
+    /// by the device, so we can fail with a list of valid options instead of silently
+    /// accepting whatever the driver negotiates.
+    fn validate_options(
+        device: &Device,
+        opts: &V4l2InputOptions,
+        formats: &[V4l2FormatInfo],
+    ) -> Result<(), V4l2InputError> {
+        let Some(format_info) = formats.iter().find(|info| info.format == opts.format) else {
+            return Err(V4l2InputError::FormatNotAvailable {
+                requested: opts.format,
+                available: formats.iter().map(|info| info.format).collect(),
+            });
+        };
+
+        // Some devices (e.g. virtual cameras) do not enumerate frame sizes.
+        if format_info.resolutions.is_empty() {
+            return Ok(());
+        }
+
+        let resolution = match opts.resolution {
+            Some(resolution) => resolution,
+            None => {
+                let format = device.format()?;
+                Resolution {
+                    width: format.width as usize,
+                    height: format.height as usize,
+                }
+            }
+        };
+
+        let resolution_info = format_info
+            .resolutions
+            .iter()
+            .find(|info| info.resolution == resolution);
+        let resolution_info = match resolution_info {
+            Some(info) => info,
+            // Resolution was not requested, so the device will keep the current one.
+            None if opts.resolution.is_none() => return Ok(()),
+            None => {
+                return Err(V4l2InputError::ResolutionNotAvailable {
+                    format: opts.format,
+                    requested: resolution,
+                    available: format_info
+                        .resolutions
+                        .iter()
+                        .map(|info| info.resolution)
+                        .collect(),
+                });
+            }
+        };
+
+        if let Some(framerate) = opts.framerate
+            && !resolution_info.framerates.is_empty()
+            && !resolution_info
+                .framerates
+                .iter()
+                .any(|available| framerates_equal(*available, framerate))
+        {
+            return Err(V4l2InputError::FramerateNotAvailable {
+                resolution,
+                requested: framerate,
+                available: resolution_info.framerates.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
     fn try_set_format(device: &Device, format: V4l2Format) -> Result<V4l2Format, V4l2InputError> {
         let requested_fourcc = format.into();
         let current_format = device.format()?;
@@ -265,7 +350,7 @@ impl V4l2DeviceConfig {
             warn!(
                 requested_framerate = ?framerate,
                 configured_framerate = ?negotiated_framerate,
-                "Failed to configure requested resolution.",
+                "Failed to configure requested framerate.",
             );
         }
 
@@ -400,6 +485,8 @@ pub fn list_v4l2_devices(
     Ok(devices)
 }
 
+/// Returns `None` if the device cannot be opened or queried, or does not have the
+/// `VIDEO_CAPTURE` capability.
 fn read_device(path: Arc<Path>) -> Result<Option<V4l2DeviceInfo>, V4l2InputError> {
     let Ok(device) = v4l::Device::with_path(&path) else {
         return Ok(None);
@@ -415,19 +502,32 @@ fn read_device(path: Arc<Path>) -> Result<Option<V4l2DeviceInfo>, V4l2InputError
         return Ok(None);
     }
 
+    let formats = read_formats(&device, &path)?;
+
+    Ok(Some(V4l2DeviceInfo {
+        path,
+        name: caps.card,
+        formats,
+    }))
+}
+
+/// Query a single Video for Linux 2 device for supported formats, resolutions and framerates.
+/// Returns [`V4l2InputError::CaptureNotSupported`] if the device cannot be opened or does not
+/// have the `VIDEO_CAPTURE` capability.
+pub fn read_v4l2_device(path: impl AsRef<Path>) -> Result<V4l2DeviceInfo, V4l2InputError> {
+    read_device(path.as_ref().into())?.ok_or(V4l2InputError::CaptureNotSupported)
+}
+
+fn read_formats(device: &Device, path: &Path) -> Result<Vec<V4l2FormatInfo>, V4l2InputError> {
     let mut formats = Vec::new();
 
     for format in device.enum_formats()? {
-        if let Some(format) = read_format(&device, &path, format)? {
+        if let Some(format) = read_format(device, path, format)? {
             formats.push(format);
         }
     }
 
-    Ok(Some(V4l2DeviceInfo {
-        path,
-        name: caps.card,
-        formats,
-    }))
+    Ok(formats)
 }
 
 fn read_format(
@@ -508,3 +608,7 @@ fn read_stepwise_frame_interval(
             }),
     )
 }
+
+fn framerates_equal(a: Framerate, b: Framerate) -> bool {
+    a.num as u64 * b.den as u64 == b.num as u64 * a.den as u64
+}
@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use smelter_render::{Framerate, Resolution};
+
+/// Limits advertised by the remote peer with `max-fr` and `max-fs` H264 fmtp parameters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct H264FmtpLimits {
+    /// Max framerate in frames per second (`max-fr`).
+    pub max_framerate: Option<u32>,
+    /// Max frame size in macroblocks (`max-fs`).
+    pub max_frame_size: Option<u32>,
+}
+
+impl H264FmtpLimits {
+    pub fn from_fmtp(fmtp: &str) -> Self {
+        let mut limits = Self::default();
+        for param in fmtp.split(';') {
+            let Some((key, val)) = param.trim().split_once('=') else {
+                continue;
+            };
+            let value = val.trim().parse::<u32>().ok().filter(|value| *value > 0);
+            match key.trim().to_ascii_lowercase().as_str() {
+                "max-fr" => limits.max_framerate = value,
+                "max-fs" => limits.max_frame_size = value,
+                _ => {}
+            }
+        }
+        limits
+    }
+
+    /// Returns `None` if framerate does not exceed `max-fr`.
+    pub fn framerate_limit(&self, framerate: Framerate) -> Option<u32> {
+        let max_framerate = self.max_framerate?;
+        let exceeds = framerate.num as u64 > max_framerate as u64 * framerate.den as u64;
+        exceeds.then_some(max_framerate)
+    }
+
+    /// Scales the resolution down, preserving aspect ratio, until it fits in `max-fs`.
+    pub fn clamp_resolution(&self, resolution: Resolution) -> Resolution {
+        let Some(max_frame_size) = self.max_frame_size else {
+            return resolution;
+        };
+        if macroblocks(resolution) <= max_frame_size as usize {
+            return resolution;
+        }
+
+        let max_pixels = max_frame_size as f64 * 256.0;
+        let mut scale = (max_pixels / (resolution.width * resolution.height) as f64).sqrt();
+        loop {
+            let clamped = Resolution {
+                width: even_at_least_2(resolution.width as f64 * scale),
+                height: even_at_least_2(resolution.height as f64 * scale),
+            };
+            if macroblocks(clamped) <= max_frame_size as usize
+                || clamped.width * clamped.height <= 4
+            {
+                return clamped;
+            }
+            scale *= 0.99;
+        }
+    }
+}
+
+fn macroblocks(resolution: Resolution) -> usize {
+    resolution.width.div_ceil(16) * resolution.height.div_ceil(16)
+}
+
+fn even_at_least_2(value: f64) -> usize {
+    ((value as usize) & !1).max(2)
+}
+
+/// Drops frames so that the remaining ones do not exceed `max_framerate`.
+pub(crate) struct FramerateLimiter {
+    min_interval: Duration,
+    next_pts: Option<Duration>,
+}
+
+impl FramerateLimiter {
+    // Frames slightly before the deadline are still sent to avoid dropping
+    // frames because of pts rounding.
+    const TOLERANCE: Duration = Duration::from_millis(1);
+
+    pub fn new(max_framerate: u32) -> Self {
+        Self {
+            min_interval: Duration::from_secs(1) / max_framerate,
+            next_pts: None,
+        }
+    }
+
+    pub fn should_send(&mut self, pts: Duration) -> bool {
+        match self.next_pts {
+            Some(next_pts) if pts + Self::TOLERANCE < next_pts => false,
+            Some(next_pts) if pts < next_pts + self.min_interval => {
+                self.next_pts = Some(next_pts + self.min_interval);
+                true
+            }
+            // first frame or after a gap
+            _ => {
+                self.next_pts = Some(pts + self.min_interval);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_max_fr_and_max_fs() {
+        let limits = H264FmtpLimits::from_fmtp(
+            "level-asymmetry-allowed=1;packetization-mode=1;profile-level-id=42e01f;max-fs=3600;max-fr=30",
+        );
+        assert_eq!(
+            limits,
+            H264FmtpLimits {
+                max_framerate: Some(30),
+                max_frame_size: Some(3600),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_fmtp_without_limits() {
+        let limits = H264FmtpLimits::from_fmtp("packetization-mode=1;profile-level-id=42e01f");
+        assert_eq!(limits, H264FmtpLimits::default());
+    }
+
+    #[test]
+    fn clamp_resolution_to_max_fs() {
+        let limits = H264FmtpLimits::from_fmtp("max-fs=3600;max-fr=30");
+        let full_hd = Resolution {
+            width: 1920,
+            height: 1080,
+        };
+        let clamped = limits.clamp_resolution(full_hd);
+        assert!(macroblocks(clamped) <= 3600);
+        assert!(clamped.width >= 1200);
+        assert_eq!(clamped.width % 2, 0);
+        assert_eq!(clamped.height % 2, 0);
+
+        let hd = Resolution {
+            width: 1280,
+            height: 720,
+        };
+        assert_eq!(limits.clamp_resolution(hd), hd);
+    }
+
+    #[test]
+    fn limit_framerate() {
+        let limits = H264FmtpLimits::from_fmtp("max-fr=15");
+        assert_eq!(
+            limits.framerate_limit(Framerate { num: 30, den: 1 }),
+            Some(15)
+        );
+        assert_eq!(limits.framerate_limit(Framerate { num: 15, den: 1 }), None);
+
+        let mut limiter = FramerateLimiter::new(15);
+        let sent = (0..30)
+            .map(|i| Duration::from_secs(1) * i / 30)
+            .filter(|pts| limiter.should_send(*pts))
+            .count();
+        assert_eq!(sent, 15);
+    }
+}
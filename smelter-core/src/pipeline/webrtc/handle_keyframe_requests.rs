@@ -6,16 +6,20 @@ use webrtc::{
     rtp_transceiver::rtp_sender::RTCRtpSender,
 };
 
-use crate::PipelineCtx;
+use crate::{PipelineCtx, pipeline::webrtc::session_activity::SessionActivity};
 
 pub(crate) fn handle_keyframe_requests(
     ctx: &Arc<PipelineCtx>,
     sender: Arc<RTCRtpSender>,
     keyframe_sender: crossbeam_channel::Sender<()>,
+    session_activity: Option<SessionActivity>,
 ) {
     ctx.tokio_rt.spawn(async move {
         loop {
             if let Ok((packets, _)) = sender.read_rtcp().await {
+                if let Some(session_activity) = &session_activity {
+                    session_activity.mark();
+                }
                 for packet in packets {
                     if packet
                         .as_any()
@@ -108,6 +108,29 @@ impl WhipWhepHttpClient {
         Ok(())
     }
 
+    /// Sends PATCH request without any ICE candidates to signal that the session
+    /// is still in use.
+    pub async fn refresh_session(&self, session_url: &Url) -> Result<(), WebrtcClientError> {
+        let headers = self.header_map(HeaderValue::from_static("application/trickle-ice-sdpfrag"));
+        let response = self
+            .http_client
+            .patch(session_url.clone())
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|_| WebrtcClientError::RequestFailed(Method::PATCH, session_url.clone()))?;
+
+        let status = response.status();
+        if status.is_server_error() || status.is_client_error() {
+            let body = response
+                .text()
+                .await
+                .map_err(|e| WebrtcClientError::BodyParsingError("session refresh", e))?;
+            return Err(WebrtcClientError::BadStatus(status, body));
+        }
+        Ok(())
+    }
+
     pub async fn delete_session(&self, session_url: Url) {
         // Endpoint is required, but some platforms e.g. Twitch do not implement it
         // so we are silently ignoring
@@ -7,19 +7,26 @@ use webrtc::{
     rtp_transceiver::rtp_receiver::RTCRtpReceiver,
 };
 
-use crate::PipelineCtx;
+use crate::{PipelineCtx, pipeline::webrtc::session_activity::SessionActivity};
 
 pub(super) struct RtcpListeners {
     pub sender_report_receiver: mpsc::UnboundedReceiver<SenderReport>,
 }
 
 impl RtcpListeners {
-    pub(super) fn start(ctx: &Arc<PipelineCtx>, rtc_receiver: Arc<RTCRtpReceiver>) -> Self {
+    pub(super) fn start(
+        ctx: &Arc<PipelineCtx>,
+        rtc_receiver: Arc<RTCRtpReceiver>,
+        session_activity: Option<SessionActivity>,
+    ) -> Self {
         let (sender_report_sender, sender_report_receiver) = mpsc::unbounded_channel();
         ctx.tokio_rt.spawn(async move {
             loop {
                 match rtc_receiver.read_rtcp().await {
                     Ok((packets, _attr)) => {
+                        if let Some(session_activity) = &session_activity {
+                            session_activity.mark();
+                        }
                         for packet in packets {
                             debug!(?packet, "Received RTCP packet");
                             if packet.header().packet_type == rtcp::header::PacketType::SenderReport
@@ -12,7 +12,7 @@ use crate::{
     pipeline::{
         decoder::KeyframeRequestSender,
         rtp::{RtpInputEvent, RtpJitterBuffer},
-        webrtc::input_rtcp_listener::RtcpListeners,
+        webrtc::{input_rtcp_listener::RtcpListeners, session_activity::SessionActivity},
     },
 };
 
@@ -31,8 +31,9 @@ impl WebrtcRtpReader {
         track: Arc<TrackRemote>,
         rtc_receiver: Arc<RTCRtpReceiver>,
         jitter_buffer: RtpJitterBuffer,
+        session_activity: Option<SessionActivity>,
     ) -> Self {
-        let rtcp_listeners = RtcpListeners::start(ctx, rtc_receiver.clone());
+        let rtcp_listeners = RtcpListeners::start(ctx, rtc_receiver.clone(), session_activity);
         let rtp_receiver = Self::start_rtp_reader_task(track.clone());
 
         Self {
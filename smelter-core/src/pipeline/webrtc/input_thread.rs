@@ -3,6 +3,7 @@ use std::sync::Arc;
 use tracing::{trace, warn};
 
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     pipeline::{
         decoder::{
             AudioDecoderStream, DynamicVideoDecoderStream, KeyframeRequestSender,
@@ -45,7 +46,7 @@ impl InitializableThread for VideoTrackThread {
     type SpawnError = DecoderInitError;
 
     fn init(options: Self::InitOptions) -> Result<(Self, Self::SpawnOutput), Self::SpawnError> {
-        let (ctx, decoder_mapping, payload_type_mapping, frame_sender, keyframe_request_sender) =
+        let (ctx, decoder_mapping, payload_type_mapping, mut frame_sender, keyframe_request_sender) =
             options;
         let (rtp_packet_sender, rtp_packet_receiver) = tokio::sync::mpsc::channel(5000);
 
@@ -61,6 +62,7 @@ impl InitializableThread for VideoTrackThread {
             decoder_mapping,
             depayloader_stream,
             keyframe_request_sender,
+            frame_sender.take_stats_callback(),
         )
         .flatten()
         .inspect(|frame| trace!(?frame, "Frame produced"));
@@ -73,8 +75,8 @@ impl InitializableThread for VideoTrackThread {
         Ok((state, output))
     }
 
-    fn run(self) {
-        for frame in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for frame in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.frame_sender.send(frame).is_err() {
                 warn!("Failed to send decoded video frame from decoder. Channel closed.");
                 return;
@@ -82,6 +84,10 @@ impl InitializableThread for VideoTrackThread {
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.0.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: "Video Decoder".to_string(),
@@ -130,8 +136,8 @@ impl InitializableThread for AudioTrackThread {
         Ok((state, output))
     }
 
-    fn run(self) {
-        for samples in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for samples in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.samples_sender.send(samples).is_err() {
                 warn!("Failed to send decoded audio samples from decoder. Channel closed.");
                 return;
@@ -139,6 +145,10 @@ impl InitializableThread for AudioTrackThread {
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.0.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: "Audio Decoder".to_string(),
@@ -5,6 +5,7 @@ use tracing::{error, info};
 
 mod bearer_token;
 mod error;
+mod h264_fmtp_limits;
 mod h264_vulkan_capability_filter;
 mod handle_keyframe_requests;
 mod http_client;
@@ -15,6 +16,7 @@ mod negotiated_codecs;
 mod offer_codec_filter;
 mod peer_connection_recvonly;
 mod server;
+mod session_activity;
 mod setting_engine;
 mod supported_codec_parameters;
 mod trickle_ice_utils;
@@ -54,7 +54,10 @@ impl RecvonlyPeerConnection {
         let api = APIBuilder::new()
             .with_media_engine(media_engine)
             .with_interceptor_registry(registry)
-            .with_setting_engine(ctx.webrtc_setting_engine.create_setting_engine())
+            .with_setting_engine(
+                ctx.webrtc_setting_engine
+                    .create_setting_engine(ctx.webrtc_keepalive.ice_keepalive_interval),
+            )
             .build();
 
         let config = RTCConfiguration {
@@ -202,6 +205,12 @@ impl RecvonlyPeerConnection {
         self.pc.add_ice_candidate(candidate).await
     }
 
+    pub async fn close(&self) {
+        if let Err(err) = self.pc.close().await {
+            warn!(%err, "Failed to close peer connection.");
+        }
+    }
+
     pub fn downgrade(&self) -> WeakRecvonlyPeerConnection {
         WeakRecvonlyPeerConnection {
             pc: Arc::downgrade(&self.pc),
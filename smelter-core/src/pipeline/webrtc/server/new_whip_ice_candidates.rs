@@ -27,7 +27,11 @@ pub async fn handle_new_whip_ice_candidates(
     let session = state
         .inputs
         .get_with(&input_ref, |input| match &input.session {
-            Some(s) => Ok(Some((s.peer_connection.downgrade(), s.session_id.clone()))),
+            Some(s) => {
+                // PATCH requests are also used by clients to refresh the session
+                s.activity.mark();
+                Ok(Some((s.peer_connection.downgrade(), s.session_id.clone())))
+            }
             None => Ok(None),
         })?;
 
@@ -0,0 +1,167 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tokio::{sync::oneshot, time::sleep_until};
+use tracing::debug;
+use webrtc::rtp_transceiver::rtp_sender::RTCRtpSender;
+
+use crate::PipelineCtx;
+
+/// Last time the remote peer showed that the session is still alive, either
+/// by sending RTCP packets or by refreshing the session over HTTP.
+#[derive(Debug, Clone)]
+pub(super) struct SessionActivity(Arc<Mutex<Instant>>);
+
+impl SessionActivity {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(Instant::now())))
+    }
+
+    pub fn mark(&self) {
+        *self.0.lock().unwrap() = Instant::now();
+    }
+
+    /// Resolves when there was no activity for at least `timeout`.
+    pub async fn wait_for_inactivity(&self, timeout: Duration) {
+        loop {
+            let deadline = *self.0.lock().unwrap() + timeout;
+            if Instant::now() >= deadline {
+                return;
+            }
+            sleep_until(deadline.into()).await;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) enum SessionEndReason {
+    /// No activity for the given duration.
+    Inactive(Duration),
+    /// Cancelled with the diagnostics API.
+    Cancelled,
+}
+
+impl std::fmt::Display for SessionEndReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionEndReason::Inactive(timeout) => {
+                write!(f, "No RTCP packets received for {timeout:?}")
+            }
+            SessionEndReason::Cancelled => write!(f, "Session cancelled"),
+        }
+    }
+}
+
+/// Resolves when the session should be closed. Inactivity is only detected if `inactivity_timeout`
+/// is set. Returns `None` when `cancel_receiver` is dropped, i.e. the session already ended.
+pub(super) async fn wait_for_session_end(
+    inactivity_timeout: Option<(SessionActivity, Duration)>,
+    cancel_receiver: oneshot::Receiver<()>,
+) -> Option<SessionEndReason> {
+    let inactivity = async {
+        match inactivity_timeout {
+            Some((activity, timeout)) => {
+                activity.wait_for_inactivity(timeout).await;
+                timeout
+            }
+            None => std::future::pending().await,
+        }
+    };
+
+    tokio::select! {
+        timeout = inactivity => Some(SessionEndReason::Inactive(timeout)),
+        result = cancel_receiver => result.ok().map(|_| SessionEndReason::Cancelled),
+    }
+}
+
+/// Marks activity on every RTCP packet received by the sender. Use it only for senders
+/// that do not have any other RTCP reader.
+pub(super) fn spawn_sender_rtcp_listener(
+    ctx: &Arc<PipelineCtx>,
+    sender: Arc<RTCRtpSender>,
+    session_activity: SessionActivity,
+) {
+    ctx.tokio_rt.spawn(async move {
+        loop {
+            if let Err(err) = sender.read_rtcp().await {
+                debug!(%err, "Error while reading RTCP.");
+                return;
+            }
+            session_activity.mark();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use tokio::{sync::oneshot, time::timeout};
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_millis(50);
+
+    #[tokio::test]
+    async fn inactive_session_ends_after_timeout() {
+        let activity = SessionActivity::new();
+        let (_cancel_sender, cancel_receiver) = oneshot::channel();
+
+        let start = Instant::now();
+        let reason = wait_for_session_end(Some((activity, TIMEOUT)), cancel_receiver).await;
+        assert!(matches!(reason, Some(SessionEndReason::Inactive(t)) if t == TIMEOUT));
+        assert!(start.elapsed() >= TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn activity_postpones_timeout() {
+        let activity = SessionActivity::new();
+        let activity_clone = activity.clone();
+        let active_for = Duration::from_millis(150);
+        tokio::spawn(async move {
+            let start = Instant::now();
+            while start.elapsed() < active_for {
+                activity_clone.mark();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        });
+
+        let start = Instant::now();
+        activity.wait_for_inactivity(TIMEOUT).await;
+        assert!(start.elapsed() >= active_for);
+    }
+
+    #[tokio::test]
+    async fn cancel_ends_session() {
+        let activity = SessionActivity::new();
+        let (cancel_sender, cancel_receiver) = oneshot::channel();
+        cancel_sender.send(()).unwrap();
+
+        let reason =
+            wait_for_session_end(Some((activity, Duration::from_secs(60))), cancel_receiver).await;
+        assert!(matches!(reason, Some(SessionEndReason::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn dropped_cancel_sender_returns_none() {
+        let (cancel_sender, cancel_receiver) = oneshot::channel();
+        drop(cancel_sender);
+
+        let reason = wait_for_session_end(None, cancel_receiver).await;
+        assert!(reason.is_none());
+    }
+
+    #[tokio::test]
+    async fn no_timeout_without_inactivity_timeout() {
+        let (_cancel_sender, cancel_receiver) = oneshot::channel();
+
+        let result = timeout(
+            Duration::from_millis(100),
+            wait_for_session_end(None, cancel_receiver),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+}
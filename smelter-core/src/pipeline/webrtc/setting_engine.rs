@@ -86,8 +86,9 @@ impl WebrtcSettingEngineCtx {
         }
     }
 
-    pub fn create_setting_engine(&self) -> SettingEngine {
+    pub fn create_setting_engine(&self, ice_keepalive_interval: Duration) -> SettingEngine {
         let mut setting_engine = SettingEngine::default();
+        setting_engine.set_ice_timeouts(None, None, Some(ice_keepalive_interval));
 
         if !self.nat_1to1_ips().is_empty() {
             setting_engine
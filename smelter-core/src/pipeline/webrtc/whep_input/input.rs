@@ -5,17 +5,19 @@ use std::{
 };
 
 use tokio::sync::oneshot;
-use tracing::{Instrument, Level, debug, span};
+use tracing::{Instrument, Level, debug, span, warn};
 use url::Url;
 
 use crate::{
     AudioChannels,
+    event::Event,
     pipeline::{
         input::Input,
         rtp::{RtpJitterBufferMode, RtpJitterBufferSharedContext},
         webrtc::{
             http_client::{SdpAnswer, WhipWhepHttpClient},
-            peer_connection_recvonly::RecvonlyPeerConnection,
+            peer_connection_recvonly::{RecvonlyPeerConnection, WeakRecvonlyPeerConnection},
+            session_activity::{SessionActivity, SessionEndReason},
             supported_codec_parameters::opus_codec_params,
             whep_input::{
                 WhepTrackContext, listen_for_trickle_candidates::listen_for_trickle_candidates,
@@ -170,6 +172,15 @@ async fn init_whep_client(
 
     pc.set_remote_description(answer).await?;
 
+    let session_activity = ctx
+        .webrtc_keepalive
+        .rtcp_timeout
+        .map(|_| SessionActivity::new());
+    if let (Some(activity), Some(timeout)) = (&session_activity, ctx.webrtc_keepalive.rtcp_timeout)
+    {
+        spawn_session_watchdog(&ctx, &input_ref, pc.downgrade(), activity.clone(), timeout);
+    }
+
     let queue_input = QueueInput::new(&ctx, &input_ref, options.queue_options);
     {
         let input_ref = input_ref.clone();
@@ -191,7 +202,7 @@ async fn init_whep_client(
         });
 
         pc.on_track(move |track_ctx| {
-            let ctx = WhepTrackContext::new(track_ctx, &ctx, &buffer);
+            let ctx = WhepTrackContext::new(track_ctx, &ctx, &buffer, &session_activity);
             handle_on_track(
                 ctx,
                 input_ref.clone(),
@@ -213,3 +224,28 @@ async fn init_whep_client(
         queue_input,
     ))
 }
+
+/// Closes the peer connection when the WHEP server stops sending RTCP packets, e.g. when
+/// the server dropped the session without closing the peer connection.
+fn spawn_session_watchdog(
+    ctx: &Arc<PipelineCtx>,
+    input_ref: &Ref<InputId>,
+    pc: WeakRecvonlyPeerConnection,
+    session_activity: SessionActivity,
+    timeout: Duration,
+) {
+    let event_emitter = ctx.event_emitter.clone();
+    let input_ref = input_ref.clone();
+    ctx.tokio_rt.spawn(async move {
+        session_activity.wait_for_inactivity(timeout).await;
+        // Input was already unregistered
+        let Some(pc) = pc.upgrade() else {
+            return;
+        };
+
+        let reason = SessionEndReason::Inactive(timeout);
+        warn!(input_id=%input_ref, "{reason}, closing WHEP session.");
+        pc.close().await;
+        event_emitter.emit(Event::InputDisconnected(input_ref.id().clone()));
+    });
+}
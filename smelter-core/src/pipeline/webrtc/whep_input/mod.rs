@@ -3,7 +3,8 @@ use webrtc::{rtp_transceiver::rtp_receiver::RTCRtpReceiver, track::track_remote:
 use crate::{
     PipelineCtx,
     pipeline::{
-        rtp::RtpJitterBufferSharedContext, webrtc::peer_connection_recvonly::OnTrackHdlrContext,
+        rtp::RtpJitterBufferSharedContext,
+        webrtc::{peer_connection_recvonly::OnTrackHdlrContext, session_activity::SessionActivity},
     },
 };
 
@@ -22,6 +23,7 @@ struct WhepTrackContext {
     rtc_receiver: Arc<RTCRtpReceiver>,
     pipeline_ctx: Arc<PipelineCtx>,
     buffer: RtpJitterBufferSharedContext,
+    session_activity: Option<SessionActivity>,
 }
 
 impl WhepTrackContext {
@@ -29,12 +31,14 @@ impl WhepTrackContext {
         track_ctx: OnTrackHdlrContext,
         pipeline_ctx: &Arc<PipelineCtx>,
         buffer: &RtpJitterBufferSharedContext,
+        session_activity: &Option<SessionActivity>,
     ) -> Self {
         Self {
             track: track_ctx.track,
             rtc_receiver: track_ctx.rtc_receiver,
             pipeline_ctx: pipeline_ctx.clone(),
             buffer: buffer.clone(),
+            session_activity: session_activity.clone(),
         }
     }
 }
@@ -96,6 +96,7 @@ async fn process_audio_track(
                 stats_sender.send(WhepInputStatsEvent::AudioRtp(event).into_event(&input_ref));
             }),
         ),
+        ctx.session_activity,
     );
 
     while let Some(packet) = rtp_reader.read_packet().await {
@@ -140,6 +141,7 @@ async fn process_video_track(
         ctx.track,
         ctx.rtc_receiver,
         RtpJitterBuffer::new(ctx.buffer, 90_000, on_stats_event),
+        ctx.session_activity,
     );
     let keyframe_request_sender = rtp_reader.enable_pli().await;
 
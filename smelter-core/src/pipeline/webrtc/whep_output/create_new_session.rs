@@ -6,17 +6,24 @@ use std::{
     time::Duration,
 };
 
-use tokio::{task::JoinHandle, time::sleep};
+use tokio::{sync::oneshot, task::JoinHandle, time::sleep};
 use tracing::{debug, warn};
 use uuid::Uuid;
-use webrtc::peer_connection::{
-    peer_connection_state::RTCPeerConnectionState, sdp::session_description::RTCSessionDescription,
+use webrtc::{
+    api::media_engine::MIME_TYPE_H264,
+    peer_connection::{
+        peer_connection_state::RTCPeerConnectionState,
+        sdp::session_description::RTCSessionDescription,
+    },
+    rtp_transceiver::rtp_sender::RTCRtpSender,
 };
 
 use crate::pipeline::webrtc::{
     WhipWhepServerState,
     error::WhipWhepServerError,
+    h264_fmtp_limits::H264FmtpLimits,
     handle_keyframe_requests::handle_keyframe_requests,
+    session_activity::{SessionActivity, spawn_sender_rtcp_listener, wait_for_session_end},
     whep_output::{
         init_payloaders::{init_audio_payloader, init_video_payloader},
         output::WhepOutputStatsSender,
@@ -24,6 +31,7 @@ use crate::pipeline::webrtc::{
         stream_media_to_peer::{MediaStream, MediaStreamTask},
     },
 };
+use crate::{diagnostics::TaskKind, event::Event};
 
 use crate::prelude::*;
 
@@ -60,7 +68,7 @@ pub async fn create_new_whep_session(
     let (video_stream, video_sender) = match (&video_encoder, video_receiver) {
         (Some(encoder), Some(receiver)) => {
             let (track, sender, ssrc) = pc.new_video_track(encoder).await?;
-            let payloader = init_video_payloader(encoder, ssrc);
+            let payloader = init_video_payloader(encoder, ssrc, state.ctx.rtp_timestamp_offset);
             (
                 Some(MediaStream {
                     receiver,
@@ -76,7 +84,7 @@ pub async fn create_new_whep_session(
     let (audio_stream, audio_sender) = match (&audio_encoder, audio_receiver) {
         (Some(encoder), Some(receiver)) => {
             let (track, sender, ssrc) = pc.new_audio_track(encoder).await?;
-            let payloader = init_audio_payloader(ssrc);
+            let payloader = init_audio_payloader(ssrc, state.ctx.rtp_timestamp_offset);
             (
                 Some(MediaStream {
                     receiver,
@@ -96,13 +104,47 @@ pub async fn create_new_whep_session(
         .await?;
     debug!("SDP answer: {}", sdp_answer.sdp);
 
+    let rtcp_timeout = state.ctx.webrtc_keepalive.rtcp_timeout;
+    let session_activity = rtcp_timeout.map(|_| SessionActivity::new());
+
+    if let (Some(encoder), Some(sender)) = (&video_encoder, &video_sender) {
+        warn_about_h264_fmtp_limits(&state.ctx, &output_ref, encoder, sender).await;
+    }
+
     if let (Some(sender), Some(keyframe_request_sender)) = (video_sender, keyframe_request_sender) {
-        handle_keyframe_requests(&state.ctx.clone(), sender, keyframe_request_sender);
+        handle_keyframe_requests(
+            &state.ctx.clone(),
+            sender,
+            keyframe_request_sender,
+            session_activity.clone(),
+        );
+    }
+    if let (Some(sender), Some(session_activity)) = (audio_sender, &session_activity) {
+        spawn_sender_rtcp_listener(&state.ctx, sender, session_activity.clone());
     }
 
     state.outputs.add_session(&output_ref, &session_id, pc)?;
 
-    MediaStreamTask::new(video_stream, audio_stream, should_close).spawn();
+    let (cancel_sender, cancel_receiver) = oneshot::channel();
+    let task = state.ctx.task_registry.register(
+        "WHEP session",
+        Some(format!("{output_ref} session {session_id}")),
+        TaskKind::WebrtcSession,
+    );
+    task.set_cancel(move || {
+        let _ = cancel_sender.send(());
+    });
+
+    spawn_session_watchdog(
+        &state,
+        &output_ref,
+        &session_id,
+        session_activity.zip(rtcp_timeout),
+        should_close.clone(),
+        cancel_receiver,
+    );
+
+    MediaStreamTask::new(video_stream, audio_stream, should_close, task).spawn();
 
     Ok((session_id, sdp_answer))
 }
@@ -180,3 +222,72 @@ fn register_state_change_handler(
 
     should_close
 }
+
+/// The encoder is shared by all sessions of the output, so `max-fs` and `max-fr`
+/// requested by a single peer can not be applied.
+async fn warn_about_h264_fmtp_limits(
+    ctx: &Arc<PipelineCtx>,
+    output_ref: &Ref<OutputId>,
+    encoder: &VideoEncoderOptions,
+    sender: &Arc<RTCRtpSender>,
+) {
+    let (VideoEncoderOptions::FfmpegH264(_) | VideoEncoderOptions::VulkanH264(_)) = encoder else {
+        return;
+    };
+    let params = sender.get_parameters().await;
+    let Some(codec) = params.rtp_parameters.codecs.iter().find(|codec| {
+        codec
+            .capability
+            .mime_type
+            .eq_ignore_ascii_case(MIME_TYPE_H264)
+    }) else {
+        return;
+    };
+
+    let limits = H264FmtpLimits::from_fmtp(&codec.capability.sdp_fmtp_line);
+    let resolution = encoder.resolution();
+    let exceeds_frame_size = limits.clamp_resolution(resolution) != resolution;
+    let exceeds_framerate = limits.framerate_limit(ctx.output_framerate).is_some();
+    if exceeds_frame_size || exceeds_framerate {
+        warn!(
+            output_id=?output_ref.id(),
+            max_fs=limits.max_frame_size,
+            max_fr=limits.max_framerate,
+            "WHEP client requested lower max-fs or max-fr than output resolution and framerate. \
+            Limits can not be applied to outputs shared by multiple sessions, the client might reject frames."
+        );
+    }
+}
+
+/// Closes the session when it is cancelled or when the peer stops sending RTCP packets,
+/// e.g. when the client disappeared without sending DELETE request.
+fn spawn_session_watchdog(
+    server_state: &WhipWhepServerState,
+    output_ref: &Ref<OutputId>,
+    session_id: &Arc<str>,
+    inactivity_timeout: Option<(SessionActivity, Duration)>,
+    should_close: Arc<AtomicBool>,
+    cancel_receiver: oneshot::Receiver<()>,
+) {
+    let ctx = server_state.ctx.clone();
+    let outputs = server_state.outputs.clone();
+    let output_ref = output_ref.clone();
+    let session_id = session_id.clone();
+    server_state.ctx.tokio_rt.spawn(async move {
+        let Some(reason) = wait_for_session_end(inactivity_timeout, cancel_receiver).await else {
+            return;
+        };
+
+        // Fails if the session was already removed
+        if outputs.remove_session(&output_ref, &session_id).is_ok() {
+            warn!(
+                ?session_id,
+                output_id=?output_ref.id(),
+                "{reason}, closing WHEP session."
+            );
+            ctx.event_emitter
+                .emit(Event::OutputDisconnected(output_ref.id().clone()));
+        }
+        should_close.store(true, Ordering::Relaxed);
+    });
+}
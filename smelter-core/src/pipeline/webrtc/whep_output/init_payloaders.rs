@@ -1,7 +1,13 @@
+use std::time::Duration;
+
 use crate::pipeline::rtp::payloader::{PayloadedCodec, Payloader, PayloaderOptions};
 use crate::prelude::*;
 
-pub(crate) fn init_video_payloader(encoder: &VideoEncoderOptions, ssrc: u32) -> Payloader {
+pub(crate) fn init_video_payloader(
+    encoder: &VideoEncoderOptions,
+    ssrc: u32,
+    pts_offset: Duration,
+) -> Payloader {
     let (codec, payload_type, clock_rate) = match encoder {
         VideoEncoderOptions::FfmpegH264(_) | VideoEncoderOptions::VulkanH264(_) => {
             (PayloadedCodec::H264, 102, 90000)
@@ -16,15 +22,17 @@ pub(crate) fn init_video_payloader(encoder: &VideoEncoderOptions, ssrc: u32) ->
         clock_rate,
         mtu: 1200,
         ssrc,
+        pts_offset,
     })
 }
 
-pub(crate) fn init_audio_payloader(ssrc: u32) -> Payloader {
+pub(crate) fn init_audio_payloader(ssrc: u32, pts_offset: Duration) -> Payloader {
     Payloader::new(PayloaderOptions {
         codec: PayloadedCodec::Opus,
         payload_type: 111,
         clock_rate: 48000,
         mtu: 1200,
         ssrc,
+        pts_offset,
     })
 }
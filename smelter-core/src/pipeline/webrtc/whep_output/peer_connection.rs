@@ -59,7 +59,10 @@ impl PeerConnection {
         let api = APIBuilder::new()
             .with_media_engine(media_engine)
             .with_interceptor_registry(registry)
-            .with_setting_engine(ctx.webrtc_setting_engine.create_setting_engine())
+            .with_setting_engine(
+                ctx.webrtc_setting_engine
+                    .create_setting_engine(ctx.webrtc_keepalive.ice_keepalive_interval),
+            )
             .build();
 
         let config = RTCConfiguration {
@@ -6,13 +6,18 @@ use tokio::sync::broadcast::{self, error::RecvError};
 use tracing::{error, info, trace, warn};
 use webrtc::track::track_local::{TrackLocalWriter, track_local_static_rtp::TrackLocalStaticRTP};
 
-use crate::pipeline::{rtp::payloader::Payloader, webrtc::error::WhepError};
+use crate::{
+    diagnostics::TaskGuard,
+    pipeline::{rtp::payloader::Payloader, webrtc::error::WhepError},
+};
 
 use crate::prelude::*;
 
 pub(super) struct MediaStreamTask {
     sender: InterleavedPacketSender,
     should_close: Arc<AtomicBool>,
+    /// Lists the session in diagnostics while media is sent to the peer.
+    _task: TaskGuard,
 }
 
 impl MediaStreamTask {
@@ -20,6 +25,7 @@ impl MediaStreamTask {
         video_stream: Option<MediaStream>,
         audio_stream: Option<MediaStream>,
         should_close: Arc<AtomicBool>,
+        task: TaskGuard,
     ) -> Self {
         let sender = InterleavedPacketSender {
             video_stream,
@@ -30,6 +36,7 @@ impl MediaStreamTask {
         Self {
             sender,
             should_close,
+            _task: task,
         }
     }
 
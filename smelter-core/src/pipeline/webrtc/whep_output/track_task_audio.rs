@@ -4,6 +4,7 @@ use tokio::sync::broadcast;
 use tracing::warn;
 
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     pipeline::{
         encoder::{AudioEncoder, AudioEncoderStream, resampler::ResampledForEncoderStream},
         webrtc::whep_output::output::WhepOutputStatsSender,
@@ -80,8 +81,8 @@ where
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.chunks_sender.send(event).is_err() {
                 warn!("Failed to send encoded audio chunk from encoder. Channel closed.");
                 return;
@@ -89,6 +90,10 @@ where
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.ctx.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: "Whep Audio Encoder".to_string(),
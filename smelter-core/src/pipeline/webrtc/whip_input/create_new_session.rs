@@ -1,10 +1,13 @@
 use std::{sync::Arc, time::Duration};
 
-use tracing::debug;
+use tokio::sync::oneshot;
+use tracing::{debug, warn};
 use uuid::Uuid;
 use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
 
 use crate::{
+    diagnostics::TaskKind,
+    event::Event,
     pipeline::{
         rtp::{RtpJitterBufferMode, RtpJitterBufferSharedContext},
         webrtc::{
@@ -12,6 +15,7 @@ use crate::{
             error::WhipWhepServerError,
             offer_codec_filter::codecs_from_offer,
             peer_connection_recvonly::RecvonlyPeerConnection,
+            session_activity::{SessionActivity, wait_for_session_end},
             whip_input::{
                 WhipTrackContext, on_track::handle_on_track, state::WhipInputSession,
                 video_preferences::video_params_compliant_with_offer,
@@ -72,14 +76,34 @@ pub(crate) async fn create_new_whip_session(
 
     let weak_pear_connection = peer_connection.downgrade();
     let session_id: Arc<str> = Arc::from(Uuid::new_v4().to_string());
+    let session_activity = SessionActivity::new();
+    let (cancel_sender, cancel_receiver) = oneshot::channel();
+    let task = state.ctx.task_registry.register(
+        "WHIP session",
+        Some(format!("{input_ref} session {session_id}")),
+        TaskKind::WebrtcSession,
+    );
+    task.set_cancel(move || {
+        let _ = cancel_sender.send(());
+    });
     // It will fail if there is already connected peer connection
     inputs.get_mut_with(&input_ref, |input| {
         input.maybe_replace_session(WhipInputSession {
             peer_connection,
             session_id: session_id.clone(),
+            activity: session_activity.clone(),
+            _task: task,
         })
     })?;
 
+    spawn_session_watchdog(
+        &state,
+        &input_ref,
+        &session_id,
+        &session_activity,
+        cancel_receiver,
+    );
+
     if let Some(peer_connection) = weak_pear_connection.upgrade() {
         let input_ref = input_ref.clone();
         let desired_size = match jitter_buffer_size {
@@ -99,7 +123,7 @@ pub(crate) async fn create_new_whip_session(
         });
 
         peer_connection.on_track(move |track_ctx| {
-            let ctx = WhipTrackContext::new(track_ctx, &state, &buffer);
+            let ctx = WhipTrackContext::new(track_ctx, &state, &buffer, &session_activity);
             handle_on_track(
                 ctx,
                 input_ref.clone(),
@@ -112,3 +136,47 @@ pub(crate) async fn create_new_whip_session(
 
     Ok((session_id, answer))
 }
+
+/// Closes the session when it is cancelled or when the peer stops sending RTCP packets
+/// and session refresh requests, e.g. when the client disappeared without sending DELETE request.
+fn spawn_session_watchdog(
+    state: &WhipWhepServerState,
+    input_ref: &Ref<InputId>,
+    session_id: &Arc<str>,
+    session_activity: &SessionActivity,
+    cancel_receiver: oneshot::Receiver<()>,
+) {
+    let ctx = state.ctx.clone();
+    let inputs = state.inputs.clone();
+    let input_ref = input_ref.clone();
+    let session_id = session_id.clone();
+    let inactivity_timeout = state
+        .ctx
+        .webrtc_keepalive
+        .rtcp_timeout
+        .map(|timeout| (session_activity.clone(), timeout));
+    state.ctx.tokio_rt.spawn(async move {
+        let Some(reason) = wait_for_session_end(inactivity_timeout, cancel_receiver).await else {
+            return;
+        };
+
+        let session = inputs.get_mut_with(&input_ref, |input| {
+            Ok(input
+                .session
+                .take_if(|session| session.session_id == session_id))
+        });
+        // Session was already replaced or the input was removed
+        let Ok(Some(session)) = session else {
+            return;
+        };
+
+        warn!(
+            ?session_id,
+            input_id=%input_ref,
+            "{reason}, closing WHIP session."
+        );
+        drop(session);
+        ctx.event_emitter
+            .emit(Event::InputDisconnected(input_ref.id().clone()));
+    });
+}
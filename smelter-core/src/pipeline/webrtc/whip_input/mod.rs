@@ -5,7 +5,10 @@ use crate::{
     PipelineCtx,
     pipeline::{
         rtp::RtpJitterBufferSharedContext,
-        webrtc::{WhipWhepServerState, peer_connection_recvonly::OnTrackHdlrContext},
+        webrtc::{
+            WhipWhepServerState, peer_connection_recvonly::OnTrackHdlrContext,
+            session_activity::SessionActivity,
+        },
     },
 };
 
@@ -24,6 +27,7 @@ struct WhipTrackContext {
     rtc_receiver: Arc<RTCRtpReceiver>,
     pipeline_ctx: Arc<PipelineCtx>,
     jitter_buffer_ctx: RtpJitterBufferSharedContext,
+    session_activity: SessionActivity,
 }
 
 impl WhipTrackContext {
@@ -31,12 +35,14 @@ impl WhipTrackContext {
         track_ctx: OnTrackHdlrContext,
         state: &WhipWhepServerState,
         buffer: &RtpJitterBufferSharedContext,
+        session_activity: &SessionActivity,
     ) -> Self {
         Self {
             track: track_ctx.track,
             rtc_receiver: track_ctx.rtc_receiver,
             pipeline_ctx: state.ctx.clone(),
             jitter_buffer_ctx: buffer.clone(),
+            session_activity: session_activity.clone(),
         }
     }
 }
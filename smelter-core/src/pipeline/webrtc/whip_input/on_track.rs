@@ -99,6 +99,7 @@ async fn process_audio_track(
                 stats_sender.send(WhipInputStatsEvent::AudioRtp(event).into_event(&input_ref));
             }),
         ),
+        Some(ctx.session_activity),
     );
 
     while let Some(packet) = rtp_reader.read_packet().await {
@@ -145,6 +146,7 @@ async fn process_video_track(
         ctx.track,
         ctx.rtc_receiver,
         RtpJitterBuffer::new(ctx.jitter_buffer_ctx, 90_000, on_stats_event),
+        Some(ctx.session_activity),
     );
     let keyframe_request_sender = rtp_reader.enable_pli().await;
 
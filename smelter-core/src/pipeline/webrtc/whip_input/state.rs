@@ -10,9 +10,10 @@ use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
 
 use crate::{
     codecs::VideoDecoderOptions,
+    diagnostics::TaskGuard,
     pipeline::webrtc::{
         bearer_token::validate_token, error::WhipWhepServerError,
-        peer_connection_recvonly::RecvonlyPeerConnection,
+        peer_connection_recvonly::RecvonlyPeerConnection, session_activity::SessionActivity,
     },
     queue::WeakQueueInput,
 };
@@ -147,6 +148,9 @@ pub(crate) struct WhipInputState {
 pub(crate) struct WhipInputSession {
     pub peer_connection: RecvonlyPeerConnection,
     pub session_id: Arc<str>,
+    pub activity: SessionActivity,
+    /// Lists the session in diagnostics until it is dropped.
+    pub _task: TaskGuard,
 }
 
 impl WhipInputState {
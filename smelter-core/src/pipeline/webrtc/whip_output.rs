@@ -7,7 +7,10 @@ use std::{
     time::{Duration, Instant},
 };
 
-use tokio::sync::{mpsc, oneshot};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
 use tracing::{Instrument, Level, debug, span, trace, warn};
 use url::Url;
 use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
@@ -28,6 +31,7 @@ use crate::{
         rtp::RtpPacket,
         webrtc::{
             http_client::WhipWhepHttpClient,
+            session_activity::{SessionActivity, SessionEndReason},
             whip_output::codec_preferences::{
                 codec_params_from_preferences, resolve_audio_preferences, resolve_video_preferences,
             },
@@ -81,7 +85,12 @@ impl WhipOutput {
             output_id = output_ref.to_string()
         );
         let rt = ctx.tokio_rt.clone();
-        rt.spawn(
+        let task_registry = ctx.task_registry.clone();
+        let owner = Some(output_ref.to_string());
+        task_registry.spawn_tracked(
+            &rt,
+            "WHIP client",
+            owner,
             async {
                 let result = WhipClientTask::new(ctx, output_ref, options).await;
                 match result {
@@ -112,6 +121,7 @@ struct WhipClientTask {
     video_track: Option<WhipClientTrack>,
     audio_track: Option<WhipClientTrack>,
     should_close: Arc<AtomicBool>,
+    session_activity: Option<SessionActivity>,
 
     #[allow(dead_code)]
     pc: PeerConnection,
@@ -146,11 +156,21 @@ impl WhipClientTask {
 
         pc.set_remote_description(answer).await?;
 
+        let session_activity = ctx
+            .webrtc_keepalive
+            .rtcp_timeout
+            .map(|_| SessionActivity::new());
+
         let (video_thread_handle, video_track) = match video_preferences {
             Some(encoder_preferences) => {
-                let (video_thread_handle, video) =
-                    setup_video_track(&ctx, &output_ref, video_rtc_sender, encoder_preferences)
-                        .await?;
+                let (video_thread_handle, video) = setup_video_track(
+                    &ctx,
+                    &output_ref,
+                    video_rtc_sender,
+                    encoder_preferences,
+                    session_activity.clone(),
+                )
+                .await?;
                 (Some(video_thread_handle), Some(video))
             }
             None => (None, None),
@@ -164,6 +184,7 @@ impl WhipClientTask {
                     audio_rtc_sender,
                     pc.downgrade(),
                     encoder_preferences,
+                    session_activity.clone(),
                 )
                 .await?;
                 (Some(audio_thread_handle), Some(audio))
@@ -180,6 +201,7 @@ impl WhipClientTask {
                 video_track,
                 audio_track,
                 should_close,
+                session_activity,
                 pc,
             },
             WhipOutput {
@@ -221,6 +243,50 @@ impl WhipClientTask {
         should_close
     }
 
+    /// Periodically refreshes the session on the WHIP server, so servers that expire
+    /// idle sessions keep this one alive.
+    fn spawn_session_refresh(&self, interval: Duration) -> JoinHandle<()> {
+        let client = self.client.clone();
+        let session_url = self.session_url.clone();
+        self.ctx.tokio_rt.spawn(
+            async move {
+                let mut interval = tokio::time::interval(interval);
+                // first tick completes immediately
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    if let Err(err) = client.refresh_session(&session_url).await {
+                        warn!(%err, "Failed to refresh WHIP session.");
+                    }
+                }
+            }
+            .in_current_span(),
+        )
+    }
+
+    /// Closes the output when the WHIP server stops sending RTCP packets, e.g. when
+    /// the server dropped the session without closing the peer connection.
+    fn spawn_session_watchdog(
+        &self,
+        session_activity: SessionActivity,
+        timeout: Duration,
+    ) -> JoinHandle<()> {
+        let ctx = self.ctx.clone();
+        let output_ref = self.output_ref.clone();
+        let should_close = self.should_close.clone();
+        self.ctx.tokio_rt.spawn(
+            async move {
+                session_activity.wait_for_inactivity(timeout).await;
+                let reason = SessionEndReason::Inactive(timeout);
+                warn!("{reason}, closing WHIP output.");
+                should_close.store(true, Ordering::Relaxed);
+                ctx.event_emitter
+                    .emit(Event::OutputDisconnected(output_ref.id().clone()));
+            }
+            .in_current_span(),
+        )
+    }
+
     /// Forward packets from audio/video channels while making sure they
     /// are interleaved according to their timestamps
     async fn run(mut self) {
@@ -236,6 +302,17 @@ impl WhipClientTask {
         let mut next_video_packet = None;
         let mut next_audio_packet = None;
 
+        let refresh_task = self
+            .ctx
+            .webrtc_keepalive
+            .session_refresh_interval
+            .map(|interval| self.spawn_session_refresh(interval));
+        let watchdog_task = self
+            .session_activity
+            .clone()
+            .zip(self.ctx.webrtc_keepalive.rtcp_timeout)
+            .map(|(activity, timeout)| self.spawn_session_watchdog(activity, timeout));
+
         loop {
             match (
                 &next_video_packet,
@@ -354,6 +431,12 @@ impl WhipClientTask {
             };
         }
 
+        if let Some(task) = refresh_task {
+            task.abort();
+        }
+        if let Some(task) = watchdog_task {
+            task.abort();
+        }
         self.client.delete_session(self.session_url).await;
         self.ctx
             .event_emitter
@@ -1,7 +1,7 @@
 use rand::Rng;
 use std::{sync::Arc, time::Duration};
 use tokio::sync::{mpsc, watch};
-use tracing::{Instrument, Level, debug, error, span, trace};
+use tracing::{Instrument, Level, debug, error, info, span, trace};
 use webrtc::{
     api::media_engine::{MIME_TYPE_H264, MIME_TYPE_OPUS, MIME_TYPE_VP8, MIME_TYPE_VP9},
     rtp_transceiver::{rtp_codec::RTCRtpCodecCapability, rtp_sender::RTCRtpSender},
@@ -17,7 +17,9 @@ use crate::{
         },
         rtp::payloader::{PayloadedCodec, PayloaderOptions},
         webrtc::{
+            h264_fmtp_limits::H264FmtpLimits,
             handle_keyframe_requests::handle_keyframe_requests,
+            session_activity::SessionActivity,
             whip_output::{
                 WhipOutputStatsSender,
                 peer_connection::WeakPeerConnection,
@@ -72,6 +74,7 @@ pub async fn setup_video_track(
     output_ref: &Ref<OutputId>,
     rtc_sender: Arc<RTCRtpSender>,
     encoder_preferences: Vec<VideoEncoderOptions>,
+    session_activity: Option<SessionActivity>,
 ) -> Result<(WhipVideoTrackThreadHandle, WhipClientTrack), WebrtcClientError> {
     let rtc_sender_params = rtc_sender.get_parameters().await;
     debug!("RTCRtpSender video params: {:#?}", rtc_sender_params);
@@ -90,6 +93,8 @@ pub async fn setup_video_track(
     }) else {
         return Err(WebrtcClientError::NoVideoCodecNegotiated);
     };
+    let (options, max_framerate) =
+        apply_h264_fmtp_limits(ctx, options, &codec_params.capability.sdp_fmtp_line);
 
     let track = Arc::new(TrackLocalStaticRTP::new(
         codec_params.capability.clone(),
@@ -99,13 +104,19 @@ pub async fn setup_video_track(
 
     rtc_sender.replace_track(Some(track.clone())).await?;
 
-    fn payloader_options(codec: PayloadedCodec, payload_type: u8, ssrc: u32) -> PayloaderOptions {
+    fn payloader_options(
+        codec: PayloadedCodec,
+        payload_type: u8,
+        ssrc: u32,
+        pts_offset: Duration,
+    ) -> PayloaderOptions {
         PayloaderOptions {
             codec,
             payload_type,
             clock_rate: 90_000,
             mtu: 1200,
             ssrc,
+            pts_offset,
         }
     }
 
@@ -126,9 +137,11 @@ pub async fn setup_video_track(
                         PayloadedCodec::H264,
                         codec_params.payload_type,
                         ssrc,
+                        ctx.rtp_timestamp_offset,
                     ),
                     chunks_sender: sender,
                     stats_sender,
+                    max_framerate,
                 },
             )
         }
@@ -142,9 +155,11 @@ pub async fn setup_video_track(
                         PayloadedCodec::H264,
                         codec_params.payload_type,
                         ssrc,
+                        ctx.rtp_timestamp_offset,
                     ),
                     chunks_sender: sender,
                     stats_sender,
+                    max_framerate,
                 },
             )
         }
@@ -157,9 +172,11 @@ pub async fn setup_video_track(
                     PayloadedCodec::Vp8,
                     codec_params.payload_type,
                     ssrc,
+                    ctx.rtp_timestamp_offset,
                 ),
                 chunks_sender: sender,
                 stats_sender,
+                max_framerate: None,
             },
         ),
         VideoEncoderOptions::FfmpegVp9(options) => WhipVideoTrackThread::<FfmpegVp9Encoder>::spawn(
@@ -171,9 +188,11 @@ pub async fn setup_video_track(
                     PayloadedCodec::Vp9,
                     codec_params.payload_type,
                     ssrc,
+                    ctx.rtp_timestamp_offset,
                 ),
                 chunks_sender: sender,
                 stats_sender,
+                max_framerate: None,
             },
         ),
     }?;
@@ -182,17 +201,60 @@ pub async fn setup_video_track(
         ctx,
         rtc_sender.clone(),
         handle.keyframe_request_sender.clone(),
+        session_activity,
     );
 
     Ok((handle, WhipClientTrack { receiver, track }))
 }
 
+/// Clamps H264 encoder resolution and framerate to `max-fs` and `max-fr` advertised
+/// by the WHIP server. Returns the options and framerate limit if it has to be applied.
+fn apply_h264_fmtp_limits(
+    ctx: &Arc<PipelineCtx>,
+    mut options: VideoEncoderOptions,
+    fmtp: &str,
+) -> (VideoEncoderOptions, Option<u32>) {
+    let resolution = match &mut options {
+        VideoEncoderOptions::FfmpegH264(options) => &mut options.resolution,
+        VideoEncoderOptions::VulkanH264(options) => &mut options.resolution,
+        VideoEncoderOptions::FfmpegVp8(_) | VideoEncoderOptions::FfmpegVp9(_) => {
+            return (options, None);
+        }
+    };
+
+    let limits = H264FmtpLimits::from_fmtp(fmtp);
+    let clamped_resolution = limits.clamp_resolution(*resolution);
+    if clamped_resolution != *resolution {
+        info!(
+            max_fs = limits.max_frame_size,
+            ?clamped_resolution,
+            "Output resolution {}x{} exceeds max-fs requested by the WHIP server, encoding in {}x{}.",
+            resolution.width,
+            resolution.height,
+            clamped_resolution.width,
+            clamped_resolution.height,
+        );
+        *resolution = clamped_resolution;
+    }
+
+    let max_framerate = limits.framerate_limit(ctx.output_framerate);
+    if let Some(max_framerate) = max_framerate {
+        info!(
+            max_fr = max_framerate,
+            "Output framerate exceeds max-fr requested by the WHIP server, dropping frames to send at most {max_framerate} fps."
+        );
+    }
+
+    (options, max_framerate)
+}
+
 pub async fn setup_audio_track(
     ctx: &Arc<PipelineCtx>,
     output_id: &Ref<OutputId>,
     rtc_sender: Arc<RTCRtpSender>,
     pc: WeakPeerConnection,
     encoder_preferences: Vec<AudioEncoderOptions>,
+    session_activity: Option<SessionActivity>,
 ) -> Result<(WhipAudioTrackThreadHandle, WhipClientTrack), WebrtcClientError> {
     let rtc_sender_params = rtc_sender.get_parameters().await;
     debug!("RTCRtpSender audio params: {:#?}", rtc_sender_params);
@@ -220,13 +282,19 @@ pub async fn setup_audio_track(
 
     rtc_sender.replace_track(Some(track.clone())).await?;
 
-    fn payloader_options(codec: PayloadedCodec, payload_type: u8, ssrc: u32) -> PayloaderOptions {
+    fn payloader_options(
+        codec: PayloadedCodec,
+        payload_type: u8,
+        ssrc: u32,
+        pts_offset: Duration,
+    ) -> PayloaderOptions {
         PayloaderOptions {
             codec,
             payload_type,
             clock_rate: 48_000,
             mtu: 1200,
             ssrc,
+            pts_offset,
         }
     }
 
@@ -246,6 +314,7 @@ pub async fn setup_audio_track(
                     PayloadedCodec::Opus,
                     codec_params.payload_type,
                     ssrc,
+                    ctx.rtp_timestamp_offset,
                 ),
                 chunks_sender: sender,
                 stats_sender,
@@ -262,6 +331,7 @@ pub async fn setup_audio_track(
         rtc_sender.clone(),
         handle.packet_loss_sender.clone(),
         ssrc,
+        session_activity,
     );
 
     Ok((handle, WhipClientTrack { receiver, track }))
@@ -277,6 +347,7 @@ fn handle_packet_loss_requests(
     rtc_sender: Arc<RTCRtpSender>,
     packet_loss_sender: watch::Sender<i32>,
     ssrc: u32,
+    session_activity: Option<SessionActivity>,
 ) {
     let mut cumulative_packets_sent: u64 = 0;
     let mut cumulative_packets_lost: u64 = 0;
@@ -290,6 +361,9 @@ fn handle_packet_loss_requests(
                     debug!(%e, "Error while reading rtcp.");
                     return;
                 }
+                if let Some(session_activity) = &session_activity {
+                    session_activity.mark();
+                }
             }
         }
         .instrument(span.clone()),
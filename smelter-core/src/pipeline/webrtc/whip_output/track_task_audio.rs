@@ -7,6 +7,7 @@ use tracing::warn;
 use crate::pipeline::webrtc::whip_output::WhipOutputStatsSender;
 use crate::prelude::*;
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     pipeline::{
         encoder::{AudioEncoder, AudioEncoderStream, resampler::ResampledForEncoderStream},
         rtp::{
@@ -99,8 +100,8 @@ where
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.chunks_sender.blocking_send(event).is_err() {
                 warn!("Failed to send encoded audio chunk from encoder. Channel closed.");
                 return;
@@ -108,6 +109,10 @@ where
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.ctx.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: "Whip Audio Encoder".to_string(),
@@ -8,12 +8,14 @@ use tracing::warn;
 use crate::pipeline::webrtc::whip_output::WhipOutputStatsSender;
 use crate::prelude::*;
 use crate::{
+    diagnostics::{CancelToken, TaskRegistry},
     pipeline::{
         encoder::{VideoEncoder, VideoEncoderConfig, VideoEncoderStream},
         rtp::{
             RtpPacket,
             payloader::{PayloaderOptions, PayloaderStream},
         },
+        webrtc::h264_fmtp_limits::FramerateLimiter,
     },
     utils::{InitializableThread, ThreadMetadata},
 };
@@ -31,6 +33,8 @@ pub(super) struct WhipVideoTrackThreadOptions<Encoder: VideoEncoder> {
     pub payloader_options: PayloaderOptions,
     pub chunks_sender: mpsc::Sender<RtpPacket>,
     pub stats_sender: WhipOutputStatsSender,
+    /// Frames are dropped to not exceed `max-fr` requested by the WHIP server.
+    pub max_framerate: Option<u32>,
 }
 
 pub(super) struct WhipVideoTrackThread<Encoder: VideoEncoder> {
@@ -55,14 +59,20 @@ where
             payloader_options,
             chunks_sender,
             stats_sender,
+            max_framerate,
         } = options;
 
         let (frame_sender, frame_receiver) = crossbeam_channel::bounded(5);
-        let (encoded_stream, encoder_ctx) = VideoEncoderStream::<Encoder, _>::new(
-            ctx,
-            encoder_options,
-            frame_receiver.into_iter(),
-        )?;
+        let mut framerate_limiter = max_framerate.map(FramerateLimiter::new);
+        let frames =
+            frame_receiver
+                .into_iter()
+                .filter(move |event| match (event, &mut framerate_limiter) {
+                    (PipelineEvent::Data(frame), Some(limiter)) => limiter.should_send(frame.pts),
+                    _ => true,
+                });
+        let (encoded_stream, encoder_ctx) =
+            VideoEncoderStream::<Encoder, _>::new(ctx, encoder_options, frames)?;
 
         let payloaded_stream = PayloaderStream::new(payloader_options, encoded_stream.flatten());
 
@@ -96,8 +106,8 @@ where
         Ok((state, output))
     }
 
-    fn run(self) {
-        for event in self.stream {
+    fn run(self, cancel: CancelToken) {
+        for event in self.stream.take_while(|_| !cancel.is_cancelled()) {
             if self.chunks_sender.blocking_send(event).is_err() {
                 warn!("Failed to send encoded video chunk from encoder. Channel closed.");
                 return;
@@ -105,6 +115,10 @@ where
         }
     }
 
+    fn task_registry(options: &Self::InitOptions) -> &TaskRegistry {
+        &options.ctx.task_registry
+    }
+
     fn metadata() -> ThreadMetadata {
         ThreadMetadata {
             thread_name: "Whip Video Encoder".to_string(),
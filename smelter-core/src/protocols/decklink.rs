@@ -1,4 +1,5 @@
 pub use decklink::PixelFormat as DeckLinkPixelFormat;
+pub use decklink::ProfileId as DeckLinkProfileId;
 
 use crate::queue::QueueInputOptions;
 
@@ -8,8 +9,15 @@ pub struct DeckLinkInputOptions {
     pub display_name: Option<String>,
     /// Persistent id of a device (different value for each sub-device).
     pub persistent_id: Option<u32>,
+    /// Profile that should be activated on the card before the input is started.
+    /// It changes the number of sub-devices and whether they can capture and
+    /// play back at the same time.
+    pub profile: Option<DeckLinkProfileId>,
 
     pub enable_audio: bool,
+    /// Emit `Event::InputAncillaryData` for captions and SCTE-104 messages
+    /// carried in the ancillary data of the input signal.
+    pub enable_ancillary_data: bool,
     /// Force specified pixel format, value resolved in input format
     /// autodetection will be ignored.
     pub pixel_format: Option<DeckLinkPixelFormat>,
@@ -26,6 +34,10 @@ pub enum DeckLinkInputError {
     NoCaptureSupport,
     #[error("Selected device does not support input format detection.")]
     NoInputFormatDetection,
+    #[error("Selected device does not support profile {0:?}.")]
+    ProfileNotSupported(DeckLinkProfileId),
+    #[error("Activation of profile {0:?} did not complete in time.")]
+    ProfileActivationTimeout(DeckLinkProfileId),
 }
 
 #[derive(Debug)]
@@ -33,4 +45,5 @@ pub struct DeckLinkDeviceInfo {
     pub display_name: Option<String>,
     pub persistent_id: Option<String>,
     pub subdevice_index: Option<u32>,
+    pub active_profile: Option<DeckLinkProfileId>,
 }
@@ -1,5 +1,7 @@
 use std::{path::Path, sync::Arc, time::Duration};
 
+use data_encoding::HEXLOWER;
+
 use crate::codecs::{AudioEncoderOptions, VideoDecoderOptions, VideoEncoderOptions};
 use crate::queue::QueueInputOptions;
 
@@ -11,6 +13,15 @@ pub struct Mp4InputOptions {
     pub seek: Option<Duration>,
     pub offset: Option<Duration>,
     pub queue_options: QueueInputOptions,
+    /// Key used to decrypt tracks protected with Common Encryption (CENC).
+    pub decryption: Option<Mp4DecryptionOptions>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mp4DecryptionOptions {
+    pub key: [u8; 16],
+    /// If provided, it has to match the default key ID (`tenc` box) of all encrypted tracks.
+    pub key_id: Option<[u8; 16]>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -18,6 +29,11 @@ pub struct Mp4OutputOptions {
     pub output_path: Arc<Path>,
     pub video: Option<VideoEncoderOptions>,
     pub audio: Option<AudioEncoderOptions>,
+    /// Move `moov` atom to the beginning of the file when recording is finished.
+    pub fast_start: bool,
+    /// Write audio and video samples in time order, grouped into chunks of at most this
+    /// duration per track. If `None`, samples are written in the order they are encoded.
+    pub interleave_duration: Option<Duration>,
     pub raw_options: Vec<(Arc<str>, Arc<str>)>,
 }
 
@@ -46,6 +62,34 @@ pub enum Mp4InputError {
     #[error("No suitable track in the mp4 file")]
     NoTrack,
 
+    #[error(transparent)]
+    Decryption(#[from] Mp4DecryptionError),
+
     #[error("Unknown error: {0}")]
     Unknown(&'static str),
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum Mp4DecryptionError {
+    #[error("MP4 file contains encrypted tracks, but no decryption key was provided.")]
+    MissingKey,
+
+    #[error(
+        "Provided key ID {} does not match key ID {} of the encrypted track.",
+        HEXLOWER.encode(provided),
+        HEXLOWER.encode(expected)
+    )]
+    KeyIdMismatch {
+        provided: [u8; 16],
+        expected: [u8; 16],
+    },
+
+    #[error("Unsupported encryption scheme \"{0}\".")]
+    UnsupportedScheme(String),
+
+    #[error("Malformed \"{0}\" box.")]
+    MalformedBox(&'static str),
+
+    #[error("Invalid encryption info for sample {0}.")]
+    InvalidSampleInfo(u32),
+}
@@ -16,11 +16,21 @@ use crate::{
 pub struct RtpInputOptions {
     pub port: PortOrRange,
     pub transport_protocol: RtpInputTransportProtocol,
-    pub video: Option<VideoDecoderOptions>,
+    pub video: Option<RtpVideoOptions>,
     pub audio: Option<RtpAudioOptions>,
     pub queue_options: QueueInputOptions,
     pub offset: Option<Duration>,
     pub buffer_duration: Option<Duration>,
+    pub socket_options: RtpSocketOptions,
+    pub payload_types: RtpPayloadTypes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RtpVideoOptions {
+    Decoder(VideoDecoderOptions),
+    /// Packets are decoded by an [`RtpVideoDecoder`](crate::RtpVideoDecoder) registered in
+    /// [`RtpCodecRegistry`](crate::RtpCodecRegistry) for the video payload type.
+    Custom,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -31,6 +41,11 @@ pub enum RtpAudioOptions {
         raw_asc: bytes::Bytes,
         depayloader_mode: RtpAacDepayloaderMode,
     },
+    /// Packets are decoded by an [`RtpAudioDecoder`](crate::RtpAudioDecoder) registered in
+    /// [`RtpCodecRegistry`](crate::RtpCodecRegistry) for the audio payload type.
+    Custom {
+        clock_rate: u32,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,6 +59,38 @@ pub struct RtpOutputOptions {
     pub connection_options: RtpOutputConnectionOptions,
     pub video: Option<VideoEncoderOptions>,
     pub audio: Option<AudioEncoderOptions>,
+    pub socket_options: RtpSocketOptions,
+    pub payload_types: RtpPayloadTypes,
+}
+
+/// RTP payload types of video and audio tracks. Packets of an input are assigned to a track
+/// based on this value, and codecs registered in [`RtpCodecRegistry`](crate::RtpCodecRegistry)
+/// are looked up by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtpPayloadTypes {
+    pub video: u8,
+    pub audio: u8,
+}
+
+impl Default for RtpPayloadTypes {
+    fn default() -> Self {
+        Self {
+            video: 96,
+            audio: 97,
+        }
+    }
+}
+
+/// Options applied to the RTP socket. `None` keeps the OS default, except for the receive
+/// buffer of UDP input which defaults to 16MiB.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RtpSocketOptions {
+    /// `SO_RCVBUF` in bytes.
+    pub recv_buffer_size: Option<usize>,
+    /// `SO_SNDBUF` in bytes.
+    pub send_buffer_size: Option<usize>,
+    /// DSCP value (0-63) written to the upper 6 bits of the IP ToS field.
+    pub dscp: Option<u8>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
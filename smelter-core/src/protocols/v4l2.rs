@@ -32,4 +32,51 @@ pub enum V4l2InputError {
 
     #[error("Device is set to an unsupported format: {0}.")]
     UnsupportedFormat(String),
+
+    #[error(
+        "Device does not support {requested:?} format. Supported formats: {}.",
+        format_list(available, |format| format!("{format:?}"))
+    )]
+    FormatNotAvailable {
+        requested: V4l2Format,
+        available: Vec<V4l2Format>,
+    },
+
+    #[error(
+        "Device does not support {} resolution for {format:?} format. Supported resolutions: {}.",
+        format_resolution(requested),
+        format_list(available, format_resolution)
+    )]
+    ResolutionNotAvailable {
+        format: V4l2Format,
+        requested: Resolution,
+        available: Vec<Resolution>,
+    },
+
+    #[error(
+        "Device does not support {} framerate for {} resolution. Supported framerates: {}.",
+        format_framerate(requested),
+        format_resolution(resolution),
+        format_list(available, format_framerate)
+    )]
+    FramerateNotAvailable {
+        resolution: Resolution,
+        requested: Framerate,
+        available: Vec<Framerate>,
+    },
+}
+
+fn format_list<T>(items: &[T], format_item: impl Fn(&T) -> String) -> String {
+    items.iter().map(format_item).collect::<Vec<_>>().join(", ")
+}
+
+fn format_resolution(resolution: &Resolution) -> String {
+    format!("{}x{}", resolution.width, resolution.height)
+}
+
+fn format_framerate(framerate: &Framerate) -> String {
+    match framerate.den {
+        1 => framerate.num.to_string(),
+        den => format!("{}/{den}", framerate.num),
+    }
 }
@@ -91,6 +91,29 @@ pub enum WebrtcUdpPortStrategy {
     Mux(u16),
 }
 
+/// Keepalive and dead session detection for WebRTC connections.
+#[derive(Debug, Clone, Copy)]
+pub struct WebrtcKeepaliveOptions {
+    /// Interval of STUN binding requests sent to keep NAT bindings open.
+    pub ice_keepalive_interval: Duration,
+    /// WHIP/WHEP input and output sessions that did not receive any RTCP
+    /// packets for this duration are closed. Disabled if `None`.
+    pub rtcp_timeout: Option<Duration>,
+    /// Interval of PATCH requests that WHIP output sends to the session URL
+    /// to signal that the session is still in use. Disabled if `None`.
+    pub session_refresh_interval: Option<Duration>,
+}
+
+impl Default for WebrtcKeepaliveOptions {
+    fn default() -> Self {
+        Self {
+            ice_keepalive_interval: Duration::from_secs(2),
+            rtcp_timeout: None,
+            session_refresh_interval: None,
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum WebrtcServerError {
     #[error("Endpoint ID already in use (input_id: {0})")]
@@ -17,7 +17,7 @@ use std::{
         Arc, Mutex, RwLock,
         atomic::{AtomicBool, Ordering},
     },
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
 use crossbeam_channel::{Sender, bounded};
@@ -148,6 +148,8 @@ pub struct Queue {
 #[derive(Debug, Clone)]
 pub struct QueueContext {
     pub sync_point: Instant,
+    /// Wall clock time of `sync_point`.
+    pub sync_point_system_time: SystemTime,
     /// Duration since sync point, represents time of
     /// the queue start
     start_pts: SharedPts,
@@ -257,6 +259,7 @@ impl Queue {
     pub(crate) fn new(opts: QueueOptions) -> Arc<Self> {
         let queue_ctx = QueueContext {
             sync_point: Instant::now(),
+            sync_point_system_time: SystemTime::now(),
             start_pts: Default::default(),
             last_pts: Default::default(),
             side_channel_socket_dir: opts.side_channel_socket_dir,
@@ -16,6 +16,7 @@ use crate::{
         utils::PauseState,
         video_input::VideoQueueInput,
     },
+    stats::{StatsSender, VideoFramesStatsEvent},
     types::Ref,
 };
 
@@ -31,20 +32,36 @@ struct PendingTrack {
     track_offset: TrackOffset,
 }
 
-pub(crate) struct QueueSender<T>(crossbeam_channel::Sender<T>);
+pub(crate) struct QueueSender<T> {
+    sender: crossbeam_channel::Sender<T>,
+    on_stats_event: Option<Box<dyn FnMut(VideoFramesStatsEvent) + Send>>,
+}
 
 impl<T> QueueSender<T> {
     pub(crate) fn new(sender: crossbeam_channel::Sender<T>) -> Self {
-        Self(sender)
+        Self {
+            sender,
+            on_stats_event: None,
+        }
     }
 
     pub fn send(&self, item: T) -> Result<(), crossbeam_channel::SendError<T>> {
-        self.0.send(item)
+        self.sender.send(item)
     }
 
     #[allow(dead_code)]
     pub fn try_send(&self, item: T) -> Result<(), crossbeam_channel::TrySendError<T>> {
-        self.0.try_send(item)
+        self.sender.try_send(item)
+    }
+}
+
+impl QueueSender<Frame> {
+    /// Takes the callback for stats that are known only to the decoder, e.g. GPU decode time.
+    /// Frames sent to the queue are counted by the queue itself.
+    pub fn take_stats_callback(&mut self) -> Box<dyn FnMut(VideoFramesStatsEvent) + Send> {
+        self.on_stats_event
+            .take()
+            .unwrap_or_else(|| Box::new(|_| {}))
     }
 }
 
@@ -70,6 +87,7 @@ pub(super) struct InnerQueueInput {
     video_side_channel: Option<VideoSideChannel>,
     audio_side_channel: Option<AudioSideChannel>,
     side_channel_delay: Duration,
+    stats_sender: Option<StatsSender>,
 }
 
 impl InnerQueueInput {
@@ -110,6 +128,16 @@ impl InnerQueueInput {
         }
     }
 
+    fn video_stats_callback(&self) -> Box<dyn FnMut(VideoFramesStatsEvent) + Send> {
+        let Some(stats_sender) = self.stats_sender.clone() else {
+            return Box::new(|_| {});
+        };
+        let input_ref = self.input_ref.clone();
+        Box::new(move |event: VideoFramesStatsEvent| {
+            stats_sender.send(event.into_event(&input_ref))
+        })
+    }
+
     fn new_pending_track(
         &self,
         opts: QueueTrackOptions,
@@ -139,8 +167,13 @@ impl InnerQueueInput {
                 track_offset.clone(),
                 side_channel,
                 self.side_channel_delay,
+                self.video_stats_callback(),
             );
-            (Some(video_input), Some(QueueSender::new(video_sender)))
+            let video_sender = QueueSender {
+                on_stats_event: Some(self.video_stats_callback()),
+                ..QueueSender::new(video_sender)
+            };
+            (Some(video_input), Some(video_sender))
         } else {
             (None, None)
         };
@@ -263,6 +296,7 @@ impl QueueInput {
             opts,
             video_side_channel,
             audio_side_channel,
+            Some(ctx.stats_sender.clone()),
         )
     }
 
@@ -273,6 +307,7 @@ impl QueueInput {
         opts: QueueInputOptions,
         video_side_channel: Option<VideoSideChannel>,
         audio_side_channel: Option<AudioSideChannel>,
+        stats_sender: Option<StatsSender>,
     ) -> Self {
         let (pending_sender, pending_receiver) = crossbeam_channel::bounded(MAX_PENDING_TRACKS);
         Self(Arc::new(Mutex::new(InnerQueueInput {
@@ -292,6 +327,7 @@ impl QueueInput {
             video_side_channel,
             audio_side_channel,
             side_channel_delay: opts.side_channel_delay,
+            stats_sender,
         })))
     }
 
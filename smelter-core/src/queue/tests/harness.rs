@@ -361,6 +361,7 @@ impl TestQueue {
             opts,
             None,
             None,
+            None,
         );
         let (video, audio) = queue_input.queue_new_track(track);
         self.queue.add_input(&input_id, queue_input.clone());
@@ -11,6 +11,7 @@ use crate::{
         QueueContext, QueueVideoFrame, queue_input::TrackOffset, side_channel::VideoSideChannel,
         utils::EmitOnceGuard,
     },
+    stats::VideoFramesStatsEvent,
 };
 
 pub(crate) struct VideoQueueInput {
@@ -48,8 +49,10 @@ impl VideoQueueInput {
         track_offset: TrackOffset,
         side_channel: Option<VideoSideChannel>,
         side_channel_delay: Duration,
+        on_stats_event: Box<dyn FnMut(VideoFramesStatsEvent) + Send>,
     ) -> (Self, Sender<Frame>) {
-        let (receiver, sender) = VideoInputReceiver::new(side_channel_delay, side_channel);
+        let (receiver, sender) =
+            VideoInputReceiver::new(side_channel_delay, side_channel, on_stats_event);
         let input = Self {
             queue_ctx: queue_ctx.clone(),
             required,
@@ -254,10 +257,17 @@ pub(crate) struct VideoInputReceiver {
     state: ReceiverState,
     delay: Duration,
     side_channel: Option<VideoSideChannel>,
+    /// True if the front frame of `buffer` was already returned from `get_for_pts`.
+    front_delivered: bool,
+    on_stats_event: Box<dyn FnMut(VideoFramesStatsEvent) + Send>,
 }
 
 impl VideoInputReceiver {
-    pub fn new(delay: Duration, side_channel: Option<VideoSideChannel>) -> (Self, Sender<Frame>) {
+    pub fn new(
+        delay: Duration,
+        side_channel: Option<VideoSideChannel>,
+        on_stats_event: Box<dyn FnMut(VideoFramesStatsEvent) + Send>,
+    ) -> (Self, Sender<Frame>) {
         let (sender, receiver) = bounded(1);
         let track = Self {
             max_size: Duration::from_millis(100),
@@ -267,6 +277,8 @@ impl VideoInputReceiver {
             state: ReceiverState::New,
             delay,
             side_channel,
+            front_delivered: false,
+            on_stats_event,
         };
         (track, sender)
     }
@@ -287,9 +299,11 @@ impl VideoInputReceiver {
         }
         if self.disconnected && self.buffer.len() == 1 {
             let frame = self.buffer.pop_front();
+            self.front_delivered = false;
             self.maybe_transition_to_done();
             frame
         } else {
+            self.front_delivered = true;
             self.buffer.front().cloned()
         }
     }
@@ -325,6 +339,10 @@ impl VideoInputReceiver {
                 && second.pts <= pts
             {
                 self.buffer.pop_front();
+                if !self.front_delivered {
+                    (self.on_stats_event)(VideoFramesStatsEvent::FrameDropped);
+                }
+                self.front_delivered = false;
                 dropped = true;
             }
             // If we dropped any frames, there may be room to enqueue more.
@@ -352,6 +370,7 @@ impl VideoInputReceiver {
             match self.receiver.try_recv() {
                 Ok(mut frame) => {
                     trace!(pts=?frame.pts, pending=self.receiver.len(), "Enqueue frame");
+                    (self.on_stats_event)(VideoFramesStatsEvent::FrameDecoded);
                     frame.pts += self.delay;
                     if let Some(side_channel) = &mut self.side_channel {
                         side_channel.send_frame(&frame);
@@ -13,7 +13,7 @@ use crate::{
     },
 };
 
-use super::InputStatsEvent;
+use super::{InputStatsEvent, video_frames::VideoFramesState};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum HlsInputStatsEvent {
@@ -43,6 +43,7 @@ pub(crate) enum HlsInputTrackStatsEvent {
 pub struct HlsInputState {
     pub video: HlsInputTrackState,
     pub audio: HlsInputTrackState,
+    pub video_frames: VideoFramesState,
 }
 
 #[derive(Debug)]
@@ -65,7 +66,11 @@ impl HlsInputState {
         let video = HlsInputTrackState::new();
         let audio = HlsInputTrackState::new();
 
-        Self { video, audio }
+        Self {
+            video,
+            audio,
+            video_frames: VideoFramesState::new(),
+        }
     }
 
     pub fn report(&mut self) -> HlsInputStatsReport {
@@ -75,6 +80,7 @@ impl HlsInputState {
         HlsInputStatsReport {
             video: video_report,
             audio: audio_report,
+            video_frames: self.video_frames.report(),
         }
     }
 
@@ -6,6 +6,7 @@ pub(super) mod moq_server;
 pub(super) mod mp4;
 pub(super) mod rtmp;
 pub(super) mod rtp;
+pub(super) mod video_frames;
 pub(super) mod whep;
 pub(super) mod whip;
 
@@ -14,7 +15,8 @@ use crate::{
     stats::{
         input::hls::HlsInputState, input::moq_client::MoqClientInputState,
         input::moq_server::MoqServerInputState, input::mp4::Mp4InputState,
-        input::rtmp::RtmpInputState, input::rtp::RtpInputState, input::whep::WhepInputState,
+        input::rtmp::RtmpInputState, input::rtp::RtpInputState,
+        input::video_frames::VideoFramesState, input::whep::WhepInputState,
         input::whip::WhipInputState, input_reports::InputStatsReport,
     },
 };
@@ -25,6 +27,7 @@ pub(crate) use moq_server::{MoqServerInputStatsEvent, MoqServerInputTrackStatsEv
 pub(crate) use mp4::{Mp4InputStatsEvent, Mp4InputTrackStatsEvent};
 pub(crate) use rtmp::{RtmpInputStatsEvent, RtmpInputTrackStatsEvent};
 pub(crate) use rtp::{RtpInputStatsEvent, RtpJitterBufferStatsEvent};
+pub(crate) use video_frames::VideoFramesStatsEvent;
 pub(crate) use whep::WhepInputStatsEvent;
 pub(crate) use whip::WhipInputStatsEvent;
 
@@ -38,19 +41,22 @@ pub(crate) enum InputStatsEvent {
     MoqServer(MoqServerInputStatsEvent),
     MoqClient(MoqClientInputStatsEvent),
     Mp4(Mp4InputStatsEvent),
+    VideoFrames(VideoFramesStatsEvent),
 }
 
-impl From<&InputStatsEvent> for InputProtocolKind {
-    fn from(value: &InputStatsEvent) -> Self {
-        match value {
-            InputStatsEvent::Rtp(_) => InputProtocolKind::Rtp,
-            InputStatsEvent::Whip(_) => InputProtocolKind::Whip,
-            InputStatsEvent::Whep(_) => InputProtocolKind::Whep,
-            InputStatsEvent::Hls(_) => InputProtocolKind::Hls,
-            InputStatsEvent::Rtmp(_) => InputProtocolKind::Rtmp,
-            InputStatsEvent::MoqServer(_) => InputProtocolKind::MoqServer,
-            InputStatsEvent::MoqClient(_) => InputProtocolKind::MoqClient,
-            InputStatsEvent::Mp4(_) => InputProtocolKind::Mp4,
+impl InputStatsEvent {
+    /// Returns `None` for events that are not specific to the protocol.
+    pub fn protocol_kind(&self) -> Option<InputProtocolKind> {
+        match self {
+            InputStatsEvent::Rtp(_) => Some(InputProtocolKind::Rtp),
+            InputStatsEvent::Whip(_) => Some(InputProtocolKind::Whip),
+            InputStatsEvent::Whep(_) => Some(InputProtocolKind::Whep),
+            InputStatsEvent::Hls(_) => Some(InputProtocolKind::Hls),
+            InputStatsEvent::Rtmp(_) => Some(InputProtocolKind::Rtmp),
+            InputStatsEvent::MoqServer(_) => Some(InputProtocolKind::MoqServer),
+            InputStatsEvent::MoqClient(_) => Some(InputProtocolKind::MoqClient),
+            InputStatsEvent::Mp4(_) => Some(InputProtocolKind::Mp4),
+            InputStatsEvent::VideoFrames(_) => None,
         }
     }
 }
@@ -108,12 +114,28 @@ impl InputStatsState {
             (InputStatsState::Mp4(state), InputStatsEvent::Mp4(event)) => {
                 state.handle_event(event);
             }
+            (state, InputStatsEvent::VideoFrames(event)) => {
+                state.video_frames().handle_event(event);
+            }
             (state, event) => {
                 error!(?state, ?event, "Wrong event type for input")
             }
         }
     }
 
+    fn video_frames(&mut self) -> &mut VideoFramesState {
+        match self {
+            InputStatsState::Rtp(state) => &mut state.video_frames,
+            InputStatsState::Whip(state) => &mut state.video_frames,
+            InputStatsState::Whep(state) => &mut state.video_frames,
+            InputStatsState::Hls(state) => &mut state.video_frames,
+            InputStatsState::Rtmp(state) => &mut state.video_frames,
+            InputStatsState::MoqServer(state) => &mut state.video_frames,
+            InputStatsState::MoqClient(state) => &mut state.video_frames,
+            InputStatsState::Mp4(state) => &mut state.video_frames,
+        }
+    }
+
     pub fn report(&mut self) -> InputStatsReport {
         match self {
             InputStatsState::Rtp(state) => InputStatsReport::Rtp(state.report()),
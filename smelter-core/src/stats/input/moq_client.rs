@@ -12,7 +12,7 @@ use crate::{
     },
 };
 
-use super::InputStatsEvent;
+use super::{InputStatsEvent, video_frames::VideoFramesState};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum MoqClientInputStatsEvent {
@@ -51,6 +51,7 @@ impl MoqClientInputTrackStatsEvent {
 pub struct MoqClientInputState {
     pub video: MoqClientInputTrackState,
     pub audio: MoqClientInputTrackState,
+    pub video_frames: VideoFramesState,
 }
 
 #[derive(Debug)]
@@ -64,6 +65,7 @@ impl MoqClientInputState {
         Self {
             video: MoqClientInputTrackState::new(),
             audio: MoqClientInputTrackState::new(),
+            video_frames: VideoFramesState::new(),
         }
     }
 
@@ -71,6 +73,7 @@ impl MoqClientInputState {
         MoqClientInputStatsReport {
             video: self.video.report(),
             audio: self.audio.report(),
+            video_frames: self.video_frames.report(),
         }
     }
 
@@ -12,7 +12,7 @@ use crate::{
     },
 };
 
-use super::InputStatsEvent;
+use super::{InputStatsEvent, video_frames::VideoFramesState};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum MoqServerInputStatsEvent {
@@ -51,6 +51,7 @@ impl MoqServerInputTrackStatsEvent {
 pub struct MoqServerInputState {
     pub video: MoqServerInputTrackState,
     pub audio: MoqServerInputTrackState,
+    pub video_frames: VideoFramesState,
 }
 
 #[derive(Debug)]
@@ -64,6 +65,7 @@ impl MoqServerInputState {
         Self {
             video: MoqServerInputTrackState::new(),
             audio: MoqServerInputTrackState::new(),
+            video_frames: VideoFramesState::new(),
         }
     }
 
@@ -71,6 +73,7 @@ impl MoqServerInputState {
         MoqServerInputStatsReport {
             video: self.video.report(),
             audio: self.audio.report(),
+            video_frames: self.video_frames.report(),
         }
     }
 
@@ -12,7 +12,7 @@ use crate::{
     },
 };
 
-use super::InputStatsEvent;
+use super::{InputStatsEvent, video_frames::VideoFramesState};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum Mp4InputStatsEvent {
@@ -51,6 +51,7 @@ impl Mp4InputTrackStatsEvent {
 pub struct Mp4InputState {
     pub video: Mp4InputTrackState,
     pub audio: Mp4InputTrackState,
+    pub video_frames: VideoFramesState,
 }
 
 #[derive(Debug)]
@@ -64,6 +65,7 @@ impl Mp4InputState {
         Self {
             video: Mp4InputTrackState::new(),
             audio: Mp4InputTrackState::new(),
+            video_frames: VideoFramesState::new(),
         }
     }
 
@@ -71,6 +73,7 @@ impl Mp4InputState {
         Mp4InputStatsReport {
             video: self.video.report(),
             audio: self.audio.report(),
+            video_frames: self.video_frames.report(),
         }
     }
 
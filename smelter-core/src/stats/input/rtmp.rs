@@ -12,7 +12,7 @@ use crate::{
     },
 };
 
-use super::InputStatsEvent;
+use super::{InputStatsEvent, video_frames::VideoFramesState};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum RtmpInputStatsEvent {
@@ -51,6 +51,7 @@ impl RtmpInputTrackStatsEvent {
 pub struct RtmpInputState {
     pub video: RtmpInputTrackState,
     pub audio: RtmpInputTrackState,
+    pub video_frames: VideoFramesState,
 }
 
 #[derive(Debug)]
@@ -64,6 +65,7 @@ impl RtmpInputState {
         Self {
             video: RtmpInputTrackState::new(),
             audio: RtmpInputTrackState::new(),
+            video_frames: VideoFramesState::new(),
         }
     }
 
@@ -71,6 +73,7 @@ impl RtmpInputState {
         RtmpInputStatsReport {
             video: self.video.report(),
             audio: self.audio.report(),
+            video_frames: self.video_frames.report(),
         }
     }
 
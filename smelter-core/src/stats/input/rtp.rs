@@ -14,7 +14,7 @@ use crate::{
     },
 };
 
-use super::InputStatsEvent;
+use super::{InputStatsEvent, video_frames::VideoFramesState};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum RtpInputStatsEvent {
@@ -35,6 +35,7 @@ impl RtpInputStatsEvent {
 pub struct RtpInputState {
     pub video: RtpJitterBufferState,
     pub audio: RtpJitterBufferState,
+    pub video_frames: VideoFramesState,
 }
 
 impl RtpInputState {
@@ -42,6 +43,7 @@ impl RtpInputState {
         Self {
             video: RtpJitterBufferState::new(),
             audio: RtpJitterBufferState::new(),
+            video_frames: VideoFramesState::new(),
         }
     }
 
@@ -56,6 +58,7 @@ impl RtpInputState {
         RtpInputStatsReport {
             video_rtp: self.video.report(),
             audio_rtp: self.audio.report(),
+            video_frames: self.video_frames.report(),
         }
     }
 }
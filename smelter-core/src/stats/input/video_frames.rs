@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use smelter_render::InputId;
+
+use crate::{
+    Ref,
+    stats::{
+        input_reports::{VideoFramesSlidingWindowStatsReport, VideoFramesStatsReport},
+        state::StatsEvent,
+        utils::SlidingWindowValue,
+    },
+};
+
+use super::InputStatsEvent;
+
+/// Emitted by the queue for every input, regardless of the protocol.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum VideoFramesStatsEvent {
+    /// Decoded frame reached the queue.
+    FrameDecoded,
+    /// Frame was replaced by a newer one before it was used by the queue.
+    FrameDropped,
+    /// Time the GPU spent decoding a frame. Only reported by the Vulkan decoder when
+    /// [`PipelineOptions::gpu_decode_timing`](crate::PipelineOptions::gpu_decode_timing) is set.
+    GpuDecodeTime(Duration),
+}
+
+impl VideoFramesStatsEvent {
+    pub fn into_event(self, input_ref: &Ref<InputId>) -> StatsEvent {
+        StatsEvent::Input {
+            input_ref: input_ref.clone(),
+            event: InputStatsEvent::VideoFrames(self),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct VideoFramesState {
+    pub frames_decoded: u64,
+    pub frames_dropped: u64,
+    pub frames_decoded_1_sec: SlidingWindowValue<u64>,
+    pub frames_decoded_10_secs: SlidingWindowValue<u64>,
+    pub frames_dropped_10_secs: SlidingWindowValue<u64>,
+    pub last_gpu_decode_time: Option<Duration>,
+    pub gpu_decode_time_1_sec: SlidingWindowValue<Duration>,
+}
+
+impl VideoFramesState {
+    pub fn new() -> Self {
+        Self {
+            frames_decoded: 0,
+            frames_dropped: 0,
+            frames_decoded_1_sec: SlidingWindowValue::new(Duration::from_secs(1)),
+            frames_decoded_10_secs: SlidingWindowValue::new(Duration::from_secs(10)),
+            frames_dropped_10_secs: SlidingWindowValue::new(Duration::from_secs(10)),
+            last_gpu_decode_time: None,
+            gpu_decode_time_1_sec: SlidingWindowValue::new(Duration::from_secs(1)),
+        }
+    }
+
+    pub fn handle_event(&mut self, event: VideoFramesStatsEvent) {
+        match event {
+            VideoFramesStatsEvent::FrameDecoded => {
+                self.frames_decoded += 1;
+                self.frames_decoded_1_sec.push(1);
+                self.frames_decoded_10_secs.push(1);
+            }
+            VideoFramesStatsEvent::FrameDropped => {
+                self.frames_dropped += 1;
+                self.frames_dropped_10_secs.push(1);
+            }
+            VideoFramesStatsEvent::GpuDecodeTime(duration) => {
+                self.last_gpu_decode_time = Some(duration);
+                self.gpu_decode_time_1_sec.push(duration);
+            }
+        }
+    }
+
+    pub fn report(&mut self) -> VideoFramesStatsReport {
+        let gpu_decode_utilization_1_second = self.last_gpu_decode_time.map(|_| {
+            let busy = self.gpu_decode_time_1_sec.sum();
+            f64::min(
+                busy.as_secs_f64() / self.gpu_decode_time_1_sec.window_size().as_secs_f64(),
+                1.0,
+            )
+        });
+
+        VideoFramesStatsReport {
+            frames_decoded: self.frames_decoded,
+            frames_dropped: self.frames_dropped,
+
+            framerate_1_second: self.frames_decoded_1_sec.sum()
+                / self.frames_decoded_1_sec.window_size().as_secs(),
+
+            last_10_seconds: VideoFramesSlidingWindowStatsReport {
+                frames_decoded: self.frames_decoded_10_secs.sum(),
+                frames_dropped: self.frames_dropped_10_secs.sum(),
+            },
+
+            last_gpu_decode_time_seconds: self.last_gpu_decode_time.map(|d| d.as_secs_f64()),
+            gpu_decode_utilization_1_second,
+        }
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
     },
 };
 
-use super::InputStatsEvent;
+use super::{InputStatsEvent, video_frames::VideoFramesState};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum WhepInputStatsEvent {
@@ -30,6 +30,7 @@ impl WhepInputStatsEvent {
 pub struct WhepInputState {
     pub video: RtpJitterBufferState,
     pub audio: RtpJitterBufferState,
+    pub video_frames: VideoFramesState,
 }
 
 impl WhepInputState {
@@ -37,6 +38,7 @@ impl WhepInputState {
         Self {
             video: RtpJitterBufferState::new(),
             audio: RtpJitterBufferState::new(),
+            video_frames: VideoFramesState::new(),
         }
     }
 
@@ -51,6 +53,7 @@ impl WhepInputState {
         WhepInputStatsReport {
             video_rtp: self.video.report(),
             audio_rtp: self.audio.report(),
+            video_frames: self.video_frames.report(),
         }
     }
 }
@@ -24,12 +24,13 @@ impl WhipInputStatsEvent {
     }
 }
 
-use super::InputStatsEvent;
+use super::{InputStatsEvent, video_frames::VideoFramesState};
 
 #[derive(Debug)]
 pub struct WhipInputState {
     pub video: RtpJitterBufferState,
     pub audio: RtpJitterBufferState,
+    pub video_frames: VideoFramesState,
 }
 
 impl WhipInputState {
@@ -37,6 +38,7 @@ impl WhipInputState {
         Self {
             video: RtpJitterBufferState::new(),
             audio: RtpJitterBufferState::new(),
+            video_frames: VideoFramesState::new(),
         }
     }
 
@@ -51,6 +53,7 @@ impl WhipInputState {
         WhipInputStatsReport {
             video_rtp: self.video.report(),
             audio_rtp: self.audio.report(),
+            video_frames: self.video_frames.report(),
         }
     }
 }
@@ -24,6 +24,9 @@ pub struct RtpInputStatsReport {
 
     /// Stats for the audio track.
     pub audio_rtp: RtpJitterBufferStatsReport,
+
+    /// Stats for decoded frames of the video track.
+    pub video_frames: VideoFramesStatsReport,
 }
 
 /// Stats report for `WHIP` input.
@@ -34,6 +37,9 @@ pub struct WhipInputStatsReport {
 
     /// Stats for the audio track.
     pub audio_rtp: RtpJitterBufferStatsReport,
+
+    /// Stats for decoded frames of the video track.
+    pub video_frames: VideoFramesStatsReport,
 }
 
 /// Stats report for `WHEP` input.
@@ -44,6 +50,9 @@ pub struct WhepInputStatsReport {
 
     /// Stats for the audio track.
     pub audio_rtp: RtpJitterBufferStatsReport,
+
+    /// Stats for decoded frames of the video track.
+    pub video_frames: VideoFramesStatsReport,
 }
 
 /// Stats report for `RTP` jitter buffer used in `RTP`, `WHIP` and `WHEP` inputs.
@@ -110,6 +119,9 @@ pub struct RtmpInputStatsReport {
 
     /// Stats for the audio track.
     pub audio: RtmpInputTrackStatsReport,
+
+    /// Stats for decoded frames of the video track.
+    pub video_frames: VideoFramesStatsReport,
 }
 
 /// Stats report for a track in `RTMP` input.
@@ -130,6 +142,9 @@ pub struct MoqServerInputStatsReport {
 
     /// Stats for the audio track.
     pub audio: MoqServerInputTrackStatsReport,
+
+    /// Stats for decoded frames of the video track.
+    pub video_frames: VideoFramesStatsReport,
 }
 
 /// Stats report for a track in `MoQ` server input.
@@ -150,6 +165,9 @@ pub struct MoqClientInputStatsReport {
 
     /// Stats for the audio track.
     pub audio: MoqClientInputTrackStatsReport,
+
+    /// Stats for decoded frames of the video track.
+    pub video_frames: VideoFramesStatsReport,
 }
 
 /// Stats report for a track in `MoQ` client input.
@@ -170,6 +188,9 @@ pub struct Mp4InputStatsReport {
 
     /// Stats for the audio track.
     pub audio: Mp4InputTrackStatsReport,
+
+    /// Stats for decoded frames of the video track.
+    pub video_frames: VideoFramesStatsReport,
 }
 
 /// Stats report for a track in `MP4` input.
@@ -190,6 +211,9 @@ pub struct HlsInputStatsReport {
 
     /// Stats for the audio track.
     pub audio: HlsInputTrackStatsReport,
+
+    /// Stats for decoded frames of the video track.
+    pub video_frames: VideoFramesStatsReport,
 }
 
 /// Stats report for a track in the `HLS` input.
@@ -236,3 +260,35 @@ pub struct HlsInputTrackSlidingWindowStatsReport {
     /// Size of the input buffer.
     pub input_buffer_min_seconds: f64,
 }
+
+/// Stats report for decoded video frames, counted when they reach the queue.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct VideoFramesStatsReport {
+    /// Total count of frames produced by the decoder.
+    pub frames_decoded: u64,
+    /// Total count of frames dropped because a newer frame was already available
+    /// when the queue processed them.
+    pub frames_dropped: u64,
+
+    /// Count of frames produced by the decoder in the 1-second window.
+    pub framerate_1_second: u64,
+
+    /// Video frames stats in the 10-second window.
+    pub last_10_seconds: VideoFramesSlidingWindowStatsReport,
+
+    /// Time the GPU spent decoding the last frame. Only available for the Vulkan H264
+    /// decoder when GPU decode timing is enabled.
+    pub last_gpu_decode_time_seconds: Option<f64>,
+    /// Fraction of the 1-second window the GPU spent decoding frames of this input,
+    /// in the range [0, 1]. Only available when `last_gpu_decode_time_seconds` is.
+    pub gpu_decode_utilization_1_second: Option<f64>,
+}
+
+/// Stats report for decoded video frames in the given time window.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct VideoFramesSlidingWindowStatsReport {
+    /// Count of frames produced by the decoder during the given time window.
+    pub frames_decoded: u64,
+    /// Count of frames dropped during the given time window.
+    pub frames_dropped: u64,
+}
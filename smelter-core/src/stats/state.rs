@@ -53,8 +53,11 @@ impl StatsState {
         let now = Instant::now();
         match event {
             StatsEvent::Input { input_ref, event } => {
-                if !self.inputs.contains_key(&input_ref) {
-                    let kind = InputProtocolKind::from(&event);
+                // Protocol independent events are ignored until the input is known,
+                // inputs without protocol stats (e.g. V4L2) are never reported.
+                if !self.inputs.contains_key(&input_ref)
+                    && let Some(kind) = event.protocol_kind()
+                {
                     self.inputs
                         .insert(input_ref.clone(), (now, InputStatsState::new(kind)));
                 }
@@ -95,6 +95,8 @@ impl SmelterRenderer {
             ImageSpec::Jpeg { url, .. } => (url, ImageType::Jpeg),
             ImageSpec::Svg { url, .. } => (url, ImageType::Svg),
             ImageSpec::Gif { url, .. } => (url, ImageType::Gif),
+            ImageSpec::Webp { url, .. } => (url, ImageType::Webp),
+            ImageSpec::Avif { url, .. } => (url, ImageType::Avif),
             ImageSpec::Auto { url, .. } => (url, ImageType::Auto),
         };
 
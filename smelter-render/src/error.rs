@@ -2,7 +2,11 @@ use crate::transformations::web_renderer::CreateWebRendererError;
 pub use crate::wgpu::CreateWgpuCtxError;
 use crate::wgpu::common_pipeline::CreateShaderError;
 use crate::{OutputId, RendererId};
-use crate::{registry, scene::SceneError, transformations::image::ImageError};
+use crate::{
+    registry,
+    scene::SceneError,
+    transformations::{image::ImageError, lut::LutError},
+};
 
 pub use crate::registry::RegisterError;
 pub use crate::wgpu::WgpuError;
@@ -14,6 +18,12 @@ pub enum InitRendererEngineError {
 
     #[error("Failed to initialize apply_layout transformation.")]
     LayoutTransformationsInitError(#[source] CreateShaderError),
+
+    #[error("Failed to initialize text transformation.")]
+    TextTransformationsInitError(#[source] CreateShaderError),
+
+    #[error("Failed to initialize audio visualizer transformation.")]
+    AudioVisualizerTransformationsInitError(#[source] CreateShaderError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -27,6 +37,9 @@ pub enum RegisterRendererError {
     #[error("Failed to register image \"{1}\".")]
     Image(#[source] ImageError, RendererId),
 
+    #[error("Failed to register LUT \"{1}\".")]
+    Lut(#[source] LutError, RendererId),
+
     #[error("Failed to register web renderer \"{1}\".")]
     Web(#[source] Box<CreateWebRendererError>, RendererId),
 }
@@ -16,6 +16,7 @@ pub use state::Renderer;
 pub use state::RendererOptions;
 pub use state::RendererSpec;
 pub use state::frame_pre_processor::FramePreProcessor;
+pub use transformations::audio_visualizer::AudioSamplesBuffer;
 pub use transformations::layout::DEFAULT_MAX_LAYOUTS_COUNT;
 
 pub use wgpu::{WgpuCtx, WgpuFeatures, required_wgpu_features, set_required_wgpu_limits};
@@ -24,6 +25,10 @@ pub mod image {
     pub use crate::transformations::image::{ImageSource, ImageSpec, ImageType};
 }
 
+pub mod lut {
+    pub use crate::transformations::lut::{LutSource, LutSpec};
+}
+
 pub mod shader {
     pub use crate::transformations::shader::ShaderSpec;
 }
@@ -25,6 +25,7 @@ pub enum RegistryType {
     Shader,
     WebRenderer,
     Image,
+    Lut,
 }
 
 impl RegistryType {
@@ -33,6 +34,7 @@ impl RegistryType {
             RegistryType::Shader => "shader",
             RegistryType::WebRenderer => "web renderer instance",
             RegistryType::Image => "image",
+            RegistryType::Lut => "LUT",
         }
     }
 }
@@ -3,12 +3,14 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::scene::image_component::ImageRenderParams;
+use crate::transformations::audio_visualizer::AudioVisualizerRenderParams;
 use crate::transformations::shader::Shader;
 use crate::transformations::shader::validation::error::ParametersValidationError;
 use crate::transformations::text_renderer::TextRenderParams;
 use crate::transformations::web_renderer::WebRenderer;
 use crate::{InputId, OutputId, RendererId, Resolution};
 
+use self::audio_visualizer_component::StatefulAudioVisualizerComponent;
 use self::image_component::StatefulImageComponent;
 use self::input_stream_component::StatefulInputStreamComponent;
 use self::layout::StatefulLayoutComponent;
@@ -24,9 +26,12 @@ pub(crate) use shader_component::ShaderComponentParams;
 pub use components::*;
 pub use types::*;
 
+mod audio_visualizer_component;
 mod components;
 pub(super) mod image_component;
 mod input_stream_component;
+mod ken_burns;
+mod keyframes;
 mod layout;
 mod rescaler_component;
 mod scene_state;
@@ -56,6 +61,7 @@ pub enum Component {
     View(ViewComponent),
     Tiles(TilesComponent),
     Rescaler(RescalerComponent),
+    AudioVisualizer(AudioVisualizerComponent),
 }
 
 /// Stateful version of a `Component`. Represents the same element as
@@ -69,6 +75,7 @@ enum StatefulComponent {
     WebView(StatefulWebViewComponent),
     Image(StatefulImageComponent),
     Text(StatefulTextComponent),
+    AudioVisualizer(StatefulAudioVisualizerComponent),
     Layout(Box<StatefulLayoutComponent>),
 }
 
@@ -95,17 +102,19 @@ pub(crate) enum NodeParams {
     Web(Vec<ComponentId>, Arc<WebRenderer>),
     Image(ImageRenderParams),
     Text(TextRenderParams),
+    AudioVisualizer(AudioVisualizerRenderParams),
     Layout(LayoutNode),
 }
 
 impl StatefulComponent {
     fn width(&self, pts: Duration) -> Option<f32> {
         match self {
-            StatefulComponent::InputStream(input) => Some(input.size.width),
+            StatefulComponent::InputStream(input) => Some(input.cropped_size().width),
             StatefulComponent::Shader(shader) => Some(shader.component.size.width),
             StatefulComponent::WebView(web) => Some(web.size().width),
             StatefulComponent::Image(image) => Some(image.width()),
             StatefulComponent::Text(text) => Some(text.width()),
+            StatefulComponent::AudioVisualizer(visualizer) => Some(visualizer.width()),
             StatefulComponent::Layout(layout) => match layout.position(pts) {
                 Position::Static { width, .. } => width,
                 Position::Absolute(position) => position.width,
@@ -115,11 +124,12 @@ impl StatefulComponent {
 
     fn height(&self, pts: Duration) -> Option<f32> {
         match self {
-            StatefulComponent::InputStream(input) => Some(input.size.height),
+            StatefulComponent::InputStream(input) => Some(input.cropped_size().height),
             StatefulComponent::Shader(shader) => Some(shader.component.size.height),
             StatefulComponent::WebView(web) => Some(web.size().height),
             StatefulComponent::Image(image) => Some(image.height()),
             StatefulComponent::Text(text) => Some(text.height()),
+            StatefulComponent::AudioVisualizer(visualizer) => Some(visualizer.height()),
             StatefulComponent::Layout(layout) => match layout.position(pts) {
                 Position::Static { height, .. } => height,
                 Position::Absolute(position) => position.height,
@@ -134,6 +144,7 @@ impl StatefulComponent {
             StatefulComponent::WebView(web) => web.intermediate_node(),
             StatefulComponent::Image(image) => image.intermediate_node(),
             StatefulComponent::Text(text) => text.intermediate_node(),
+            StatefulComponent::AudioVisualizer(visualizer) => visualizer.intermediate_node(),
             StatefulComponent::Layout(layout) => match layout.deref() {
                 StatefulLayoutComponent::View(view) => view.intermediate_node(),
                 StatefulLayoutComponent::Tiles(tiles) => tiles.intermediate_node(),
@@ -149,6 +160,7 @@ impl StatefulComponent {
             StatefulComponent::WebView(web) => web.children.iter_mut().collect(),
             StatefulComponent::Image(_) => vec![],
             StatefulComponent::Text(_) => vec![],
+            StatefulComponent::AudioVisualizer(_) => vec![],
             StatefulComponent::Layout(layout) => layout.children_mut(),
         }
     }
@@ -160,6 +172,7 @@ impl StatefulComponent {
             StatefulComponent::WebView(web) => web.component_id(),
             StatefulComponent::Image(image) => image.component_id(),
             StatefulComponent::Text(text) => text.component_id(),
+            StatefulComponent::AudioVisualizer(visualizer) => visualizer.component_id(),
             StatefulComponent::Layout(layout) => layout.component_id(),
         }
     }
@@ -181,6 +194,7 @@ impl Component {
             Component::View(view) => view.stateful_component(ctx),
             Component::Tiles(tiles) => tiles.stateful_component(ctx),
             Component::Rescaler(rescaler) => rescaler.stateful_component(ctx),
+            Component::AudioVisualizer(visualizer) => visualizer.stateful_component(ctx),
         }
     }
 }
@@ -200,11 +214,24 @@ pub enum SceneError {
     )]
     ImageNotFound(RendererId),
 
+    #[error(
+        "Nine-slice insets do not fit within the image \"{0}\". Sum of the opposite insets has to be smaller than the image size."
+    )]
+    InvalidNineSlice(RendererId),
+
+    #[error("Nine-slice scaling is not supported for SVG image \"{0}\".")]
+    NineSliceNotSupportedForSvg(RendererId),
+
     #[error(
         "Shader \"{0}\" does not exist. You have to register it first before using it in the scene definition."
     )]
     ShaderNotFound(RendererId),
 
+    #[error(
+        "LUT \"{0}\" does not exist. You have to register it first before using it in the scene definition."
+    )]
+    LutNotFound(RendererId),
+
     #[error(
         "Instance of web renderer \"{0}\" does not exist. You have to register it first before using it in the scene definition."
     )]
@@ -0,0 +1,57 @@
+use crate::{Resolution, transformations::audio_visualizer::AudioVisualizerRenderParams};
+
+use super::{
+    AudioVisualizerComponent, ComponentId, IntermediateNode, SceneError, Size, StatefulComponent,
+    scene_state::BuildStateTreeCtx,
+};
+
+#[derive(Debug, Clone)]
+pub(super) struct StatefulAudioVisualizerComponent {
+    id: Option<ComponentId>,
+    pub(super) params: AudioVisualizerRenderParams,
+}
+
+impl StatefulAudioVisualizerComponent {
+    pub(super) fn component_id(&self) -> Option<&ComponentId> {
+        self.id.as_ref()
+    }
+
+    pub(super) fn width(&self) -> f32 {
+        self.params.resolution.width as f32
+    }
+
+    pub(super) fn height(&self) -> f32 {
+        self.params.resolution.height as f32
+    }
+
+    pub(super) fn size(&self) -> Size {
+        self.params.resolution.into()
+    }
+
+    pub(super) fn intermediate_node(&self) -> IntermediateNode {
+        IntermediateNode::AudioVisualizer(self.clone())
+    }
+}
+
+impl AudioVisualizerComponent {
+    pub(super) fn stateful_component(
+        self,
+        _ctx: &BuildStateTreeCtx,
+    ) -> Result<StatefulComponent, SceneError> {
+        Ok(StatefulComponent::AudioVisualizer(
+            StatefulAudioVisualizerComponent {
+                id: self.id,
+                params: AudioVisualizerRenderParams {
+                    input_id: self.input_id,
+                    mode: self.mode,
+                    resolution: Resolution {
+                        width: self.width.round() as usize,
+                        height: self.height.round() as usize,
+                    },
+                    color: self.color,
+                    background_color: self.background_color,
+                },
+            },
+        ))
+    }
+}
@@ -3,11 +3,12 @@ use std::{fmt::Display, sync::Arc, time::Duration};
 use crate::{InputId, RendererId};
 
 use super::{
-    AbsolutePosition, BorderRadius, BoxShadow, Component, HorizontalAlign, InterpolationKind,
-    RGBAColor, Size, VerticalAlign,
+    AbsolutePosition, BlendMode, BorderRadius, BoxShadow, ChromaKey, ColorCorrection, Component,
+    HorizontalAlign, InterpolationKind, RGBAColor, Size, VerticalAlign,
 };
 use crate::MAX_NODE_RESOLUTION;
 
+mod crop;
 mod interpolation;
 mod position;
 
@@ -20,10 +21,24 @@ impl Display for ComponentId {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct InputStreamComponent {
     pub id: Option<ComponentId>,
     pub input_id: InputId,
+    pub chroma_key: Option<ChromaKey>,
+    pub color_correction: Option<ColorCorrection>,
+    /// 3D LUT applied to the input after the color correction.
+    pub lut_id: Option<RendererId>,
+    /// Opacity in the range [0, 1].
+    pub opacity: f32,
+    /// Part of the input that is rendered. Size of the component is equal to the size of
+    /// the cropped part.
+    pub crop: Option<SourceCrop>,
+    pub ken_burns: Option<KenBurns>,
+    /// Mirror the input around the vertical axis.
+    pub flip_horizontal: bool,
+    /// Mirror the input around the horizontal axis.
+    pub flip_vertical: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -66,6 +81,13 @@ pub struct ImageComponent {
     pub image_id: RendererId,
     pub width: Option<f32>,
     pub height: Option<f32>,
+    /// Stretches only the center of the image, corners keep their size.
+    pub nine_slice: Option<NineSlice>,
+    pub ken_burns: Option<KenBurns>,
+    /// Mirror the image around the vertical axis.
+    pub flip_horizontal: bool,
+    /// Mirror the image around the horizontal axis.
+    pub flip_vertical: bool,
 }
 
 impl Default for ImageComponent {
@@ -75,30 +97,211 @@ impl Default for ImageComponent {
             image_id: RendererId("".into()),
             width: None,
             height: None,
+            nine_slice: None,
+            ken_burns: None,
+            flip_horizontal: false,
+            flip_vertical: false,
         }
     }
 }
 
+/// Insets (in pixels of the source image) that split an image into 9 parts. Corners
+/// are rendered without scaling, edges are stretched along one axis and the center
+/// along both.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NineSlice {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+/// Pan and zoom animation. Part of the component defined by `start` is smoothly moved
+/// and resized to `end` and stretched to the size of the component. Animation starts
+/// when it is added to the scene and is not restarted by scene updates that do not
+/// change it. Only applied if the component is a child of a layout component.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KenBurns {
+    pub start: NormalizedRect,
+    pub end: NormalizedRect,
+    pub duration: Duration,
+    pub interpolation_kind: InterpolationKind,
+}
+
+/// Rectangle in coordinates relative to the size of a component, where (0, 0) is
+/// the top-left and (1, 1) the bottom-right corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalizedRect {
+    pub top: f32,
+    pub left: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Part of a source that is kept, everything outside of it is cut off.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceCrop {
+    /// Rectangle in pixels of the source.
+    Pixels {
+        top: f32,
+        left: f32,
+        width: f32,
+        height: f32,
+    },
+    /// Rectangle relative to the size of the source.
+    Normalized(NormalizedRect),
+}
+
+/// Max number of frequency bars of `AudioVisualizerMode::Bars`.
+pub const MAX_AUDIO_VISUALIZER_BARS: u32 = 256;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioVisualizerComponent {
+    pub id: Option<ComponentId>,
+    pub input_id: InputId,
+    pub mode: AudioVisualizerMode,
+    pub width: f32,
+    pub height: f32,
+    pub color: RGBAColor,
+    pub background_color: RGBAColor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioVisualizerMode {
+    /// Shape of the most recent samples of the input.
+    Waveform {
+        /// in pixels
+        line_width: f32,
+    },
+    /// Loudness of frequency bands on a logarithmic scale, from the lowest frequency
+    /// on the left to the highest on the right.
+    Bars {
+        /// From 1 to `MAX_AUDIO_VISUALIZER_BARS`.
+        count: u32,
+        /// in pixels, space between neighboring bars
+        gap: f32,
+    },
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TextComponent {
     pub id: Option<ComponentId>,
+    /// If `spans` are defined, concatenated text of all spans.
     pub text: Arc<str>,
+    /// Parts of the text with their own styling. If empty, whole `text` is rendered
+    /// with styling of the component.
+    pub spans: Vec<TextSpan>,
     /// in pixels
     pub font_size: f32,
     /// in pixels, default: same as font_size
     pub line_height: f32,
     pub color: RGBAColor,
+    /// Fills the text instead of `color`.
+    pub gradient: Option<LinearGradient>,
+    pub stroke: Option<TextStroke>,
+    pub shadow: Option<TextShadow>,
+    /// Scrolls the text horizontally. Text is laid out in a single line.
+    pub ticker: Option<TextTicker>,
     /// https://www.w3.org/TR/2018/REC-css-fonts-3-20180920/#family-name-value
     /// use font family name, not generic family name
     pub font_family: Arc<str>,
     pub style: TextStyle,
-    pub align: HorizontalAlign,
+    /// If not defined, each paragraph is aligned to its start, i.e. to the left for
+    /// left-to-right and to the right for right-to-left paragraphs.
+    pub align: Option<HorizontalAlign>,
+    pub direction: TextDirection,
     pub weight: TextWeight,
     pub wrap: TextWrap,
+    pub fit: TextFit,
     pub background_color: RGBAColor,
     pub dimensions: TextDimensions,
 }
 
+/// Properties that are not defined are inherited from the component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: Arc<str>,
+    pub font_family: Option<Arc<str>>,
+    /// in pixels
+    pub font_size: Option<f32>,
+    pub style: Option<TextStyle>,
+    pub weight: Option<TextWeight>,
+    pub color: Option<RGBAColor>,
+}
+
+/// Outline drawn around the glyphs, below the text fill.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStroke {
+    /// in pixels
+    pub width: f32,
+    pub color: RGBAColor,
+}
+
+/// Shadow of the text and its stroke.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextShadow {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// Standard deviation of a Gaussian blur in pixels.
+    pub blur_radius: f32,
+    pub color: RGBAColor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextTicker {
+    /// in pixels per second
+    pub speed: f32,
+    pub direction: TickerDirection,
+    /// If true, the text enters again from the opposite edge after it leaves the
+    /// texture, otherwise it stays hidden.
+    pub repeat: bool,
+}
+
+/// Direction in which the text moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickerDirection {
+    Left,
+    Right,
+}
+
+pub const MAX_GRADIENT_STOPS: usize = 8;
+
+/// Linear gradient spanning the entire texture of a component. Angle and stops
+/// follow the conventions of the CSS `linear-gradient` function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearGradient {
+    /// Direction of the gradient line, `0` points up and `90` to the right.
+    pub angle_degrees: f32,
+    /// From 2 to `MAX_GRADIENT_STOPS` stops sorted by position.
+    pub stops: Vec<GradientStop>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradientStop {
+    /// Position on the gradient line in the range [0, 1].
+    pub position: f32,
+    pub color: RGBAColor,
+}
+
+/// Gradient filling the background of a component.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackgroundGradient {
+    Linear(LinearGradient),
+    Radial(RadialGradient),
+}
+
+/// Elliptical gradient with the same aspect ratio as the component. The last stop is
+/// placed at the corner of the component that is the farthest from the center.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialGradient {
+    /// Center relative to the size of the component, where (0, 0) is the top-left and
+    /// (1, 1) the bottom-right corner.
+    pub center_x: f32,
+    pub center_y: f32,
+    /// From 2 to `MAX_GRADIENT_STOPS` stops sorted by position.
+    pub stops: Vec<GradientStop>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TextStyle {
     Normal,
@@ -113,6 +316,27 @@ pub enum TextWrap {
     Word,
 }
 
+/// Adjusts the font size, so the text fits within the bounds of the texture. Line height,
+/// font sizes of spans and the margin for the stroke and the shadow are taken into account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextFit {
+    None,
+    /// Font size is only reduced if the text does not fit.
+    Shrink,
+    /// The largest font size at which the text fits.
+    Fill,
+}
+
+/// Base direction of paragraphs. Characters inside a paragraph are reordered with
+/// the Unicode Bidirectional Algorithm regardless of this value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextDirection {
+    /// Detected from the first strong character of each paragraph.
+    Auto,
+    LeftToRight,
+    RightToLeft,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TextWeight {
     Thin,
@@ -153,9 +377,12 @@ pub struct ViewComponent {
     pub direction: ViewChildrenDirection,
     pub position: Position,
     pub transition: Option<Transition>,
+    pub keyframes: Vec<KeyframeTrack>,
     pub overflow: Overflow,
 
     pub background_color: RGBAColor,
+    /// Fills the background instead of `background_color`.
+    pub background_gradient: Option<BackgroundGradient>,
 
     pub border_radius: BorderRadius,
     pub border_width: f32,
@@ -164,6 +391,25 @@ pub struct ViewComponent {
     pub box_shadow: Vec<BoxShadow>,
 
     pub padding: Padding,
+
+    /// Standard deviation in pixels of a Gaussian blur applied to all textures rendered
+    /// inside the view. Background color and borders are not blurred.
+    pub blur_radius: f32,
+
+    /// Color correction applied to all textures rendered inside the view, after their
+    /// own color correction. Background color and borders are not modified.
+    pub color_correction: Option<ColorCorrection>,
+
+    /// 3D LUT applied to all textures rendered inside the view that do not define their
+    /// own LUT. Background color and borders are not modified.
+    pub lut_id: Option<RendererId>,
+
+    /// Opacity in the range [0, 1] applied to the view and all of its children.
+    pub opacity: f32,
+
+    /// Blend mode used to composite the view (including its children) with the content
+    /// rendered below it.
+    pub blend_mode: BlendMode,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -180,6 +426,90 @@ pub struct Transition {
     pub should_interrupt: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileTransitions {
+    /// Tile of a child that was added in the scene update.
+    pub enter: TileTransition,
+    /// Tile of a child that was removed in the scene update.
+    pub exit: TileTransition,
+    /// Tile of a child that is present before and after the scene update, but its
+    /// position or size changed.
+    pub reorder: TileTransition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileTransition {
+    pub effect: TileTransitionEffect,
+    /// Defaults to the duration of the component transition.
+    pub duration: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileTransitionEffect {
+    /// Change is applied immediately.
+    None,
+    Fade,
+    /// Tiles enter and exit through the bottom edge of the component. Reordered tiles
+    /// move to their new position.
+    Slide,
+    /// Tiles grow from and shrink to their center.
+    Scale,
+}
+
+impl Default for TileTransitions {
+    fn default() -> Self {
+        Self {
+            enter: TileTransition {
+                effect: TileTransitionEffect::Fade,
+                duration: None,
+            },
+            exit: TileTransition {
+                effect: TileTransitionEffect::Fade,
+                duration: None,
+            },
+            reorder: TileTransition {
+                effect: TileTransitionEffect::Slide,
+                duration: None,
+            },
+        }
+    }
+}
+
+/// Animation of a single property of a component. It starts when the track is added
+/// to the scene and is not restarted by scene updates that do not change it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyframeTrack {
+    pub property: AnimatedProperty,
+    /// Sorted by `time`, has at least one element.
+    pub keyframes: Vec<Keyframe>,
+    /// Restart the animation after the last keyframe.
+    pub repeat: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    /// Time since the start of the animation.
+    pub time: Duration,
+    pub value: f32,
+    /// Interpolation used between this and the next keyframe.
+    pub interpolation_kind: InterpolationKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimatedProperty {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Width,
+    Height,
+    Rotation,
+    RotationX,
+    RotationY,
+    Opacity,
+    BlurRadius,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Position {
     Static {
@@ -227,6 +557,7 @@ pub struct RescalerComponent {
 
     pub position: Position,
     pub transition: Option<Transition>,
+    pub keyframes: Vec<KeyframeTrack>,
 
     pub mode: RescaleMode,
     pub horizontal_align: HorizontalAlign,
@@ -237,6 +568,28 @@ pub struct RescalerComponent {
     pub border_color: RGBAColor,
 
     pub box_shadow: Vec<BoxShadow>,
+
+    /// Applied to all textures rendered inside the rescaler that do not define their own.
+    pub chroma_key: Option<ChromaKey>,
+
+    /// Standard deviation in pixels of a Gaussian blur applied to all textures rendered
+    /// inside the rescaler. Borders are not blurred.
+    pub blur_radius: f32,
+
+    /// Opacity in the range [0, 1] applied to the rescaler and its child.
+    pub opacity: f32,
+
+    /// Blend mode used to composite the rescaler (including its child) with the content
+    /// rendered below it.
+    pub blend_mode: BlendMode,
+
+    /// Part of the child that is rescaled, everything outside of it is cut off.
+    pub crop: Option<SourceCrop>,
+
+    /// Mirror the child around the vertical axis.
+    pub flip_horizontal: bool,
+    /// Mirror the child around the horizontal axis.
+    pub flip_vertical: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -254,13 +607,31 @@ pub struct TilesComponent {
     pub height: Option<f32>,
 
     pub background_color: RGBAColor,
+    pub fill_strategy: TilesFillStrategy,
     pub tile_aspect_ratio: (u32, u32),
+    /// Limits the size of a tile, e.g. when there are only a few children.
+    pub max_tile_width: Option<f32>,
+    pub max_tile_height: Option<f32>,
     pub margin: f32,
     pub padding: f32,
     pub horizontal_align: HorizontalAlign,
     pub vertical_align: VerticalAlign,
 
     pub transition: Option<Transition>,
+    /// Animations of individual tiles during a `transition`.
+    pub tile_transitions: TileTransitions,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TilesFillStrategy {
+    /// Number of rows and columns is selected to maximize the size of tiles. Tiles
+    /// preserve `tile_aspect_ratio`.
+    Fit,
+    /// Same grid as `Fit`, but tiles are stretched to cover the entire component.
+    /// `tile_aspect_ratio` is ignored.
+    Fill,
+    /// Constant number of columns. Tiles preserve `tile_aspect_ratio`.
+    FixedColumns(u32),
 }
 
 impl Default for ViewComponent {
@@ -274,13 +645,20 @@ impl Default for ViewComponent {
                 height: None,
             },
             transition: None,
+            keyframes: vec![],
             overflow: Overflow::Hidden,
             background_color: RGBAColor(0, 0, 0, 0),
+            background_gradient: None,
             border_radius: BorderRadius::ZERO,
             border_width: 0.0,
             border_color: RGBAColor(0, 0, 0, 0),
             box_shadow: vec![],
             padding: Padding::ZERO,
+            blur_radius: 0.0,
+            color_correction: None,
+            lut_id: None,
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
         }
     }
 }
@@ -295,6 +673,7 @@ impl Default for RescalerComponent {
                 height: None,
             },
             transition: None,
+            keyframes: vec![],
             mode: RescaleMode::Fit,
             horizontal_align: HorizontalAlign::Center,
             vertical_align: VerticalAlign::Center,
@@ -302,6 +681,13 @@ impl Default for RescalerComponent {
             border_width: 0.0,
             border_color: RGBAColor(0, 0, 0, 0),
             box_shadow: vec![],
+            chroma_key: None,
+            blur_radius: 0.0,
+            opacity: 1.0,
+            blend_mode: BlendMode::Normal,
+            crop: None,
+            flip_horizontal: false,
+            flip_vertical: false,
         }
     }
 }
@@ -311,14 +697,21 @@ impl Default for TextComponent {
         Self {
             id: None,
             text: Arc::from(""),
+            spans: vec![],
             font_size: 0.0,
             line_height: 0.0,
             color: RGBAColor(255, 255, 255, 255),
+            gradient: None,
+            stroke: None,
+            shadow: None,
+            ticker: None,
             font_family: Arc::from("Verdana"),
             style: TextStyle::Normal,
-            align: HorizontalAlign::Left,
+            align: None,
+            direction: TextDirection::Auto,
             weight: TextWeight::Normal,
             wrap: TextWrap::None,
+            fit: TextFit::None,
             background_color: RGBAColor(0, 0, 0, 0),
             dimensions: TextDimensions::Fitted {
                 max_width: MAX_NODE_RESOLUTION.width as f32,
@@ -338,10 +731,14 @@ impl Default for TilesComponent {
             padding: 0.0,
             children: vec![],
             transition: None,
+            tile_transitions: TileTransitions::default(),
             vertical_align: VerticalAlign::Center,
             horizontal_align: HorizontalAlign::Center,
             background_color: RGBAColor(0, 0, 0, 0),
+            fill_strategy: TilesFillStrategy::Fit,
             tile_aspect_ratio: (16, 9),
+            max_tile_width: None,
+            max_tile_height: None,
         }
     }
 }
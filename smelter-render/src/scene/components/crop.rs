@@ -0,0 +1,54 @@
+use crate::{scene::Size, transformations::layout::Crop};
+
+use super::{NormalizedRect, SourceCrop};
+
+impl SourceCrop {
+    /// Rectangle in pixels of a source of the provided `size`. It is clipped to the
+    /// bounds of the source.
+    pub(crate) fn rect(&self, size: Size) -> Crop {
+        let Crop {
+            top,
+            left,
+            width,
+            height,
+        } = match self {
+            SourceCrop::Pixels {
+                top,
+                left,
+                width,
+                height,
+            } => Crop {
+                top: *top,
+                left: *left,
+                width: *width,
+                height: *height,
+            },
+            SourceCrop::Normalized(rect) => rect.within(&Crop {
+                top: 0.0,
+                left: 0.0,
+                width: size.width,
+                height: size.height,
+            }),
+        };
+        let top = top.clamp(0.0, size.height);
+        let left = left.clamp(0.0, size.width);
+        Crop {
+            top,
+            left,
+            width: width.clamp(0.0, size.width - left),
+            height: height.clamp(0.0, size.height - top),
+        }
+    }
+}
+
+impl NormalizedRect {
+    /// Part of the `rect` described by this normalized rectangle.
+    pub(crate) fn within(&self, rect: &Crop) -> Crop {
+        Crop {
+            top: rect.top + self.top * rect.height,
+            left: rect.left + self.left * rect.width,
+            width: self.width * rect.width,
+            height: self.height * rect.height,
+        }
+    }
+}
@@ -3,7 +3,7 @@ use crate::scene::{
     types::interpolation::{ContinuousValue, InterpolationState},
 };
 
-use super::{AbsolutePosition, Position};
+use super::{AbsolutePosition, NormalizedRect, Position, SourceCrop};
 
 impl ContinuousValue for Position {
     fn interpolate(start: &Self, end: &Self, state: InterpolationState) -> Self {
@@ -46,6 +46,17 @@ impl ContinuousValue for AbsolutePosition {
                 &end.rotation_degrees,
                 state,
             ),
+            rotation_x_degrees: ContinuousValue::interpolate(
+                &start.rotation_x_degrees,
+                &end.rotation_x_degrees,
+                state,
+            ),
+            rotation_y_degrees: ContinuousValue::interpolate(
+                &start.rotation_y_degrees,
+                &end.rotation_y_degrees,
+                state,
+            ),
+            perspective: ContinuousValue::interpolate(&start.perspective, &end.perspective, state),
         }
     }
 }
@@ -84,7 +95,49 @@ impl ContinuousValue for BoxShadow {
             offset_x: ContinuousValue::interpolate(&start.offset_x, &end.offset_x, state),
             offset_y: ContinuousValue::interpolate(&start.offset_y, &end.offset_y, state),
             blur_radius: ContinuousValue::interpolate(&start.blur_radius, &end.blur_radius, state),
+            spread: ContinuousValue::interpolate(&start.spread, &end.spread, state),
             color: end.color,
         }
     }
 }
+
+impl ContinuousValue for SourceCrop {
+    fn interpolate(start: &Self, end: &Self, state: InterpolationState) -> Self {
+        match (start, end) {
+            (
+                SourceCrop::Pixels {
+                    top,
+                    left,
+                    width,
+                    height,
+                },
+                SourceCrop::Pixels {
+                    top: top_end,
+                    left: left_end,
+                    width: width_end,
+                    height: height_end,
+                },
+            ) => Self::Pixels {
+                top: ContinuousValue::interpolate(top, top_end, state),
+                left: ContinuousValue::interpolate(left, left_end, state),
+                width: ContinuousValue::interpolate(width, width_end, state),
+                height: ContinuousValue::interpolate(height, height_end, state),
+            },
+            (SourceCrop::Normalized(start), SourceCrop::Normalized(end)) => {
+                Self::Normalized(ContinuousValue::interpolate(start, end, state))
+            }
+            (_, end) => *end,
+        }
+    }
+}
+
+impl ContinuousValue for NormalizedRect {
+    fn interpolate(start: &Self, end: &Self, state: InterpolationState) -> Self {
+        Self {
+            top: ContinuousValue::interpolate(&start.top, &end.top, state),
+            left: ContinuousValue::interpolate(&start.left, &end.left, state),
+            width: ContinuousValue::interpolate(&start.width, &end.width, state),
+            height: ContinuousValue::interpolate(&start.height, &end.height, state),
+        }
+    }
+}
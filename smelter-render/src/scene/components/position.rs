@@ -15,12 +15,18 @@ impl Position {
                 position_horizontal,
                 position_vertical,
                 rotation_degrees,
+                rotation_x_degrees,
+                rotation_y_degrees,
+                perspective,
             }) => Self::Absolute(AbsolutePosition {
                 width: width.map(|w| w + 2.0 * border_width),
                 height: height.map(|h| h + 2.0 * border_width),
                 position_horizontal,
                 position_vertical,
                 rotation_degrees,
+                rotation_x_degrees,
+                rotation_y_degrees,
+                perspective,
             }),
         }
     }
@@ -37,12 +43,18 @@ impl Position {
                 position_horizontal,
                 position_vertical,
                 rotation_degrees,
+                rotation_x_degrees,
+                rotation_y_degrees,
+                perspective,
             }) => Self::Absolute(AbsolutePosition {
                 width: width.map(|w| w + padding.horizontal()),
                 height: height.map(|h| h + padding.vertical()),
                 position_horizontal,
                 position_vertical,
                 rotation_degrees,
+                rotation_x_degrees,
+                rotation_y_degrees,
+                perspective,
             }),
         }
     }
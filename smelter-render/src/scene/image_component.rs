@@ -1,10 +1,14 @@
 use std::{sync::Arc, time::Duration};
 
-use crate::{Resolution, scene::Size, transformations::image::Image};
+use crate::{
+    RendererId, Resolution,
+    scene::Size,
+    transformations::{image::Image, layout::Crop},
+};
 
 use super::{
-    ComponentId, ImageComponent, IntermediateNode, SceneError, StatefulComponent,
-    scene_state::BuildStateTreeCtx,
+    ComponentId, ImageComponent, IntermediateNode, NineSlice, SceneError, StatefulComponent,
+    ken_burns::KenBurnsState, scene_state::BuildStateTreeCtx,
 };
 
 #[derive(Debug)]
@@ -12,6 +16,7 @@ pub(crate) struct ImageRenderParams {
     pub(crate) image: Image,
     pub(crate) start_pts: Duration,
     pub(crate) resolution: Resolution,
+    pub(crate) nine_slice: Option<NineSlice>,
 }
 
 #[derive(Debug, Clone)]
@@ -20,6 +25,7 @@ pub(super) struct StatefulImageComponent {
     pub(super) image: Image,
     pub(super) start_pts: Duration,
     pub(super) resolution: Resolution,
+    pub(super) ken_burns: Option<KenBurnsState>,
 }
 
 impl StatefulImageComponent {
@@ -44,11 +50,26 @@ impl StatefulImageComponent {
         IntermediateNode::Image(self.clone())
     }
 
+    /// Part of the image texture that should be rendered at `pts`. `None` if the whole
+    /// image is rendered.
+    pub(super) fn crop(&self, pts: Duration) -> Option<Crop> {
+        let source = Crop {
+            top: 0.0,
+            left: 0.0,
+            width: self.width(),
+            height: self.height(),
+        };
+        self.ken_burns
+            .as_ref()
+            .map(|ken_burns| ken_burns.crop(&source, pts))
+    }
+
     pub(super) fn image_render_params(self) -> ImageRenderParams {
         ImageRenderParams {
             image: self.image,
             start_pts: self.start_pts,
             resolution: self.resolution,
+            nine_slice: self.component.nine_slice,
         }
     }
 }
@@ -64,6 +85,10 @@ impl ImageComponent {
             .get(&self.image_id)
             .ok_or_else(|| SceneError::ImageNotFound(self.image_id.clone()))?;
 
+        if let Some(nine_slice) = &self.nine_slice {
+            Self::validate_nine_slice(&self.image_id, &image, nine_slice)?;
+        }
+
         let original_aspect_ratio = image.resolution().width / image.resolution().height;
 
         let resolution = match (self.width, self.height) {
@@ -109,6 +134,11 @@ impl ImageComponent {
             (_, _) => false,
         };
 
+        let ken_burns = KenBurnsState::new(
+            self.ken_burns,
+            prev_state.and_then(|s| s.ken_burns.as_ref()),
+            ctx.last_render_pts,
+        );
         let component = match prev_state {
             Some(state) if self == state.component && are_images_matching => state.clone(),
             _ => StatefulImageComponent {
@@ -116,9 +146,27 @@ impl ImageComponent {
                 image,
                 start_pts: ctx.last_render_pts,
                 resolution,
+                ken_burns,
             },
         };
 
         Ok(StatefulComponent::Image(component))
     }
+
+    fn validate_nine_slice(
+        image_id: &RendererId,
+        image: &Image,
+        nine_slice: &NineSlice,
+    ) -> Result<(), SceneError> {
+        if let Image::Svg(_) = image {
+            return Err(SceneError::NineSliceNotSupportedForSvg(image_id.clone()));
+        }
+        let resolution = image.resolution();
+        if nine_slice.left + nine_slice.right >= resolution.width as f32
+            || nine_slice.top + nine_slice.bottom >= resolution.height as f32
+        {
+            return Err(SceneError::InvalidNineSlice(image_id.clone()));
+        }
+        Ok(())
+    }
 }
@@ -1,14 +1,21 @@
-use crate::Resolution;
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    Resolution,
+    transformations::{layout::Crop, lut::Lut},
+};
 
 use super::{
     ComponentId, InputStreamComponent, IntermediateNode, SceneError, Size, StatefulComponent,
-    scene_state::BuildStateTreeCtx,
+    ken_burns::KenBurnsState, scene_state::BuildStateTreeCtx,
 };
 
 #[derive(Debug, Clone)]
 pub(super) struct StatefulInputStreamComponent {
     pub(super) component: InputStreamComponent,
     pub(super) size: Size,
+    pub(super) lut: Option<Arc<Lut>>,
+    pub(super) ken_burns: Option<KenBurnsState>,
 }
 
 impl StatefulInputStreamComponent {
@@ -19,6 +26,40 @@ impl StatefulInputStreamComponent {
     pub(super) fn intermediate_node(&self) -> IntermediateNode {
         IntermediateNode::InputStream(self.clone())
     }
+
+    /// Size of the component, i.e. size of the input after cropping.
+    pub(super) fn cropped_size(&self) -> Size {
+        let source = self.source_rect();
+        Size {
+            width: source.width,
+            height: source.height,
+        }
+    }
+
+    /// Part of the input texture that should be rendered at `pts`. `None` if the whole
+    /// input is rendered.
+    pub(super) fn crop(&self, pts: Duration) -> Option<Crop> {
+        if self.component.crop.is_none() && self.ken_burns.is_none() {
+            return None;
+        }
+        let source = self.source_rect();
+        Some(match &self.ken_burns {
+            Some(ken_burns) => ken_burns.crop(&source, pts),
+            None => source,
+        })
+    }
+
+    fn source_rect(&self) -> Crop {
+        match self.component.crop {
+            Some(crop) => crop.rect(self.size),
+            None => Crop {
+                top: 0.0,
+                left: 0.0,
+                width: self.size.width,
+                height: self.size.height,
+            },
+        }
+    }
 }
 
 impl InputStreamComponent {
@@ -34,10 +75,35 @@ impl InputStreamComponent {
                 width: 0,
                 height: 0,
             });
+        let lut = self
+            .lut_id
+            .as_ref()
+            .map(|lut_id| {
+                ctx.renderers
+                    .luts
+                    .get(lut_id)
+                    .ok_or_else(|| SceneError::LutNotFound(lut_id.clone()))
+            })
+            .transpose()?;
+        let previous_state = self
+            .id
+            .as_ref()
+            .and_then(|id| ctx.prev_state.get(id))
+            .and_then(|component| match component {
+                StatefulComponent::InputStream(input) => Some(input),
+                _ => None,
+            });
+        let ken_burns = KenBurnsState::new(
+            self.ken_burns,
+            previous_state.and_then(|s| s.ken_burns.as_ref()),
+            ctx.last_render_pts,
+        );
         Ok(StatefulComponent::InputStream(
             StatefulInputStreamComponent {
                 component: self,
                 size: input.into(),
+                lut,
+                ken_burns,
             },
         ))
     }
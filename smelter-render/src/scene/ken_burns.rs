@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use crate::transformations::layout::Crop;
+
+use super::{KenBurns, NormalizedRect, types::interpolation::ContinuousValue};
+
+/// Ken Burns effect of a component together with the PTS at which it was
+/// added to the scene.
+#[derive(Debug, Clone)]
+pub(super) struct KenBurnsState {
+    ken_burns: KenBurns,
+    start_pts: Duration,
+}
+
+impl KenBurnsState {
+    /// Animation is continued if the effect did not change since the previous scene
+    /// update, otherwise it starts from the beginning at `last_pts`.
+    pub fn new(
+        ken_burns: Option<KenBurns>,
+        previous_state: Option<&KenBurnsState>,
+        last_pts: Duration,
+    ) -> Option<Self> {
+        let ken_burns = ken_burns?;
+        let start_pts = match previous_state {
+            Some(previous_state) if previous_state.ken_burns == ken_burns => {
+                previous_state.start_pts
+            }
+            _ => last_pts,
+        };
+        Some(Self {
+            ken_burns,
+            start_pts,
+        })
+    }
+
+    /// Part of the `source` rectangle that should be visible at `pts`, where `source`
+    /// is the part of the content texture displayed by the component.
+    pub fn crop(&self, source: &Crop, pts: Duration) -> Crop {
+        self.rect(pts).within(source)
+    }
+
+    fn rect(&self, pts: Duration) -> NormalizedRect {
+        let KenBurns {
+            start,
+            end,
+            duration,
+            interpolation_kind,
+        } = self.ken_burns;
+        if duration.is_zero() {
+            return end;
+        }
+        let progress = pts.saturating_sub(self.start_pts).as_secs_f64() / duration.as_secs_f64();
+        let state = interpolation_kind.state(f64::min(progress, 1.0));
+        ContinuousValue::interpolate(&start, &end, state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scene::InterpolationKind;
+
+    use super::*;
+
+    fn ken_burns(duration_ms: u64) -> KenBurns {
+        KenBurns {
+            start: NormalizedRect {
+                top: 0.0,
+                left: 0.0,
+                width: 1.0,
+                height: 1.0,
+            },
+            end: NormalizedRect {
+                top: 0.5,
+                left: 0.25,
+                width: 0.5,
+                height: 0.5,
+            },
+            duration: Duration::from_millis(duration_ms),
+            interpolation_kind: InterpolationKind::Linear,
+        }
+    }
+
+    #[test]
+    fn ken_burns_crop() {
+        let state =
+            KenBurnsState::new(Some(ken_burns(1000)), None, Duration::from_secs(1)).unwrap();
+        let source = Crop {
+            top: 0.0,
+            left: 0.0,
+            width: 100.0,
+            height: 200.0,
+        };
+        let crop = state.crop(&source, Duration::from_millis(1500));
+        assert_eq!(
+            (crop.top, crop.left, crop.width, crop.height),
+            (50.0, 12.5, 75.0, 150.0)
+        );
+        let crop = state.crop(&source, Duration::from_secs(5));
+        assert_eq!(
+            (crop.top, crop.left, crop.width, crop.height),
+            (100.0, 25.0, 50.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn ken_burns_continue_after_scene_update() {
+        let state = KenBurnsState::new(Some(ken_burns(1000)), None, Duration::from_millis(1000));
+        let updated = KenBurnsState::new(
+            Some(ken_burns(1000)),
+            state.as_ref(),
+            Duration::from_millis(1200),
+        );
+        assert_eq!(updated.unwrap().start_pts, Duration::from_millis(1000));
+
+        let changed = KenBurnsState::new(
+            Some(ken_burns(2000)),
+            state.as_ref(),
+            Duration::from_millis(1200),
+        );
+        assert_eq!(changed.unwrap().start_pts, Duration::from_millis(1200));
+    }
+}
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use super::{
+    AbsolutePosition, AnimatedProperty, HorizontalPosition, KeyframeTrack, Position,
+    VerticalPosition, types::interpolation::ContinuousValue,
+};
+
+/// Keyframe tracks of a component together with the PTS at which they were
+/// added to the scene.
+#[derive(Debug, Clone)]
+pub(super) struct KeyframesState {
+    tracks: Vec<KeyframeTrack>,
+    start_pts: Duration,
+}
+
+impl KeyframesState {
+    /// Animation is continued if tracks did not change since the previous scene update,
+    /// otherwise it starts from the beginning at `last_pts`.
+    pub fn new(
+        tracks: Vec<KeyframeTrack>,
+        previous_state: Option<&KeyframesState>,
+        last_pts: Duration,
+    ) -> Option<Self> {
+        if tracks.is_empty() {
+            return None;
+        }
+        let start_pts = match previous_state {
+            Some(previous_state) if previous_state.tracks == tracks => previous_state.start_pts,
+            _ => last_pts,
+        };
+        Some(Self { tracks, start_pts })
+    }
+
+    pub fn values(&self, pts: Duration) -> impl Iterator<Item = (AnimatedProperty, f32)> + '_ {
+        let elapsed = pts.saturating_sub(self.start_pts);
+        self.tracks
+            .iter()
+            .filter_map(move |track| Some((track.property, track.value(elapsed)?)))
+    }
+}
+
+impl KeyframeTrack {
+    fn value(&self, elapsed: Duration) -> Option<f32> {
+        let first = self.keyframes.first()?;
+        let last = self.keyframes.last()?;
+        let time = match self.repeat && !last.time.is_zero() {
+            true => Duration::from_nanos((elapsed.as_nanos() % last.time.as_nanos()) as u64),
+            false => elapsed,
+        };
+
+        // Value of the first and the last keyframe is kept before and after the animation.
+        let Some(next_index) = self.keyframes.iter().position(|k| k.time > time) else {
+            return Some(last.value);
+        };
+        if next_index == 0 {
+            return Some(first.value);
+        }
+        let (previous, next) = (&self.keyframes[next_index - 1], &self.keyframes[next_index]);
+        let progress =
+            (time - previous.time).as_secs_f64() / (next.time - previous.time).as_secs_f64();
+        let state = previous.interpolation_kind.state(progress);
+        Some(ContinuousValue::interpolate(
+            &previous.value,
+            &next.value,
+            state,
+        ))
+    }
+}
+
+impl Position {
+    /// Properties that are not defined for this position (e.g. `top` when
+    /// component is positioned with `bottom`) are ignored.
+    pub(super) fn apply_keyframe(&mut self, property: AnimatedProperty, value: f32) {
+        match (property, self) {
+            (
+                AnimatedProperty::Width,
+                Position::Static { width, .. } | Position::Absolute(AbsolutePosition { width, .. }),
+            ) => *width = Some(value.max(0.0)),
+            (
+                AnimatedProperty::Height,
+                Position::Static { height, .. }
+                | Position::Absolute(AbsolutePosition { height, .. }),
+            ) => *height = Some(value.max(0.0)),
+            (AnimatedProperty::Top, Position::Absolute(position)) => {
+                if let VerticalPosition::TopOffset(top) = &mut position.position_vertical {
+                    *top = value;
+                }
+            }
+            (AnimatedProperty::Bottom, Position::Absolute(position)) => {
+                if let VerticalPosition::BottomOffset(bottom) = &mut position.position_vertical {
+                    *bottom = value;
+                }
+            }
+            (AnimatedProperty::Left, Position::Absolute(position)) => {
+                if let HorizontalPosition::LeftOffset(left) = &mut position.position_horizontal {
+                    *left = value;
+                }
+            }
+            (AnimatedProperty::Right, Position::Absolute(position)) => {
+                if let HorizontalPosition::RightOffset(right) = &mut position.position_horizontal {
+                    *right = value;
+                }
+            }
+            (AnimatedProperty::Rotation, Position::Absolute(position)) => {
+                position.rotation_degrees = value;
+            }
+            (AnimatedProperty::RotationX, Position::Absolute(position)) => {
+                position.rotation_x_degrees = value;
+            }
+            (AnimatedProperty::RotationY, Position::Absolute(position)) => {
+                position.rotation_y_degrees = value;
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scene::{InterpolationKind, Keyframe};
+
+    use super::*;
+
+    fn track(repeat: bool) -> KeyframeTrack {
+        let keyframe = |time_ms, value| Keyframe {
+            time: Duration::from_millis(time_ms),
+            value,
+            interpolation_kind: InterpolationKind::Linear,
+        };
+        KeyframeTrack {
+            property: AnimatedProperty::Opacity,
+            keyframes: vec![keyframe(100, 0.0), keyframe(300, 1.0), keyframe(500, 0.5)],
+            repeat,
+        }
+    }
+
+    #[test]
+    fn keyframe_track_value() {
+        let track = track(false);
+        let value = |ms| track.value(Duration::from_millis(ms)).unwrap();
+        assert_eq!(value(0), 0.0);
+        assert_eq!(value(200), 0.5);
+        assert_eq!(value(300), 1.0);
+        assert_eq!(value(400), 0.75);
+        assert_eq!(value(1000), 0.5);
+    }
+
+    #[test]
+    fn repeated_keyframe_track_value() {
+        let track = track(true);
+        let value = |ms| track.value(Duration::from_millis(ms)).unwrap();
+        assert_eq!(value(200), 0.5);
+        assert_eq!(value(700), 0.5);
+        assert_eq!(value(1400), 0.75);
+    }
+
+    #[test]
+    fn keyframes_continue_after_scene_update() {
+        let state = KeyframesState::new(vec![track(false)], None, Duration::from_millis(1000));
+        let updated = KeyframesState::new(
+            vec![track(false)],
+            state.as_ref(),
+            Duration::from_millis(1200),
+        );
+        assert_eq!(updated.unwrap().start_pts, Duration::from_millis(1000));
+
+        let changed = KeyframesState::new(
+            vec![track(true)],
+            state.as_ref(),
+            Duration::from_millis(1200),
+        );
+        assert_eq!(changed.unwrap().start_pts, Duration::from_millis(1200));
+    }
+}
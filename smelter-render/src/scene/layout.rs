@@ -6,9 +6,10 @@ use crate::{
 };
 
 use super::{
-    AbsolutePosition, BorderRadius, ComponentId, HorizontalPosition, Position, RGBAColor, Size,
-    StatefulComponent, VerticalPosition, rescaler_component::StatefulRescalerComponent,
-    tiles_component::StatefulTilesComponent, view_component::StatefulViewComponent,
+    AbsolutePosition, BlendMode, BorderRadius, ComponentId, HorizontalPosition, Position,
+    RGBAColor, Size, StatefulComponent, VerticalPosition,
+    rescaler_component::StatefulRescalerComponent, tiles_component::StatefulTilesComponent,
+    view_component::StatefulViewComponent,
 };
 
 #[derive(Debug, Clone)]
@@ -117,6 +118,7 @@ impl StatefulLayoutComponent {
                 StatefulComponent::Shader(_)
                 | StatefulComponent::Image(_)
                 | StatefulComponent::Text(_)
+                | StatefulComponent::AudioVisualizer(_)
                 | StatefulComponent::WebView(_) => {
                     child_index_offset += 1; // no state
                 }
@@ -131,28 +133,78 @@ impl StatefulLayoutComponent {
         }
     }
 
-    pub(super) fn layout_content(component: &StatefulComponent, index: usize) -> LayoutContent {
+    pub(super) fn layout_content(
+        component: &StatefulComponent,
+        index: usize,
+        pts: Duration,
+    ) -> LayoutContent {
         match component {
             StatefulComponent::Layout(_layout) => LayoutContent::None,
             StatefulComponent::InputStream(input) => LayoutContent::ChildNode {
                 index,
                 size: input.size,
+                chroma_key: input.component.chroma_key,
+                color_correction: input.component.color_correction,
+                lut: input.lut.clone(),
+                opacity: input.component.opacity,
+                crop: input.crop(pts),
+                flip_horizontal: input.component.flip_horizontal,
+                flip_vertical: input.component.flip_vertical,
             },
             StatefulComponent::Shader(shader) => LayoutContent::ChildNode {
                 index,
                 size: shader.component.size,
+                chroma_key: None,
+                color_correction: None,
+                lut: None,
+                opacity: 1.0,
+                crop: None,
+                flip_horizontal: false,
+                flip_vertical: false,
             },
             StatefulComponent::WebView(web) => LayoutContent::ChildNode {
                 index,
                 size: web.size(),
+                chroma_key: None,
+                color_correction: None,
+                lut: None,
+                opacity: 1.0,
+                crop: None,
+                flip_horizontal: false,
+                flip_vertical: false,
             },
             StatefulComponent::Image(image) => LayoutContent::ChildNode {
                 index,
                 size: image.size(),
+                chroma_key: None,
+                color_correction: None,
+                lut: None,
+                opacity: 1.0,
+                crop: image.crop(pts),
+                flip_horizontal: image.component.flip_horizontal,
+                flip_vertical: image.component.flip_vertical,
             },
             StatefulComponent::Text(text) => LayoutContent::ChildNode {
                 index,
                 size: text.size(),
+                chroma_key: None,
+                color_correction: None,
+                lut: None,
+                opacity: 1.0,
+                crop: None,
+                flip_horizontal: false,
+                flip_vertical: false,
+            },
+            StatefulComponent::AudioVisualizer(visualizer) => LayoutContent::ChildNode {
+                index,
+                size: visualizer.size(),
+                chroma_key: None,
+                color_correction: None,
+                lut: None,
+                opacity: 1.0,
+                crop: None,
+                flip_horizontal: false,
+                flip_vertical: false,
             },
         }
     }
@@ -176,7 +228,10 @@ impl StatefulLayoutComponent {
         };
 
         let rotation_degrees = position.rotation_degrees;
-        let content = Self::layout_content(child, 0);
+        let rotation_x_degrees = position.rotation_x_degrees;
+        let rotation_y_degrees = position.rotation_y_degrees;
+        let perspective = position.perspective.unwrap_or(0.0);
+        let content = Self::layout_content(child, 0, pts);
         let crop = None;
         let mask = None;
 
@@ -193,6 +248,9 @@ impl StatefulLayoutComponent {
                     width,
                     height,
                     rotation_degrees,
+                    rotation_x_degrees,
+                    rotation_y_degrees,
+                    perspective,
                     scale_x: 1.0,
                     scale_y: 1.0,
                     crop,
@@ -205,6 +263,9 @@ impl StatefulLayoutComponent {
                     border_color: RGBAColor(0, 0, 0, 0),
                     border_radius: BorderRadius::ZERO,
                     box_shadow: vec![],
+                    opacity: 1.0,
+                    blur_radius: 0.0,
+                    blend_mode: BlendMode::Normal,
                 }
             }
             _non_layout_components => {
@@ -219,6 +280,9 @@ impl StatefulLayoutComponent {
                     width,
                     height,
                     rotation_degrees,
+                    rotation_x_degrees,
+                    rotation_y_degrees,
+                    perspective,
                     scale_x: 1.0,
                     scale_y: 1.0,
                     crop,
@@ -231,6 +295,9 @@ impl StatefulLayoutComponent {
                     border_color: RGBAColor(0, 0, 0, 0),
                     border_radius: BorderRadius::ZERO,
                     box_shadow: vec![],
+                    opacity: 1.0,
+                    blur_radius: 0.0,
+                    blend_mode: BlendMode::Normal,
                 }
             }
         }
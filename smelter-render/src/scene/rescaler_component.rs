@@ -3,9 +3,11 @@ use std::{ops::Deref, time::Duration};
 use crate::transformations::layout::NestedLayout;
 
 use super::{
-    BorderRadius, BoxShadow, Component, ComponentId, HorizontalAlign, IntermediateNode, Position,
-    RGBAColor, RescaleMode, SceneError, Size, StatefulComponent, VerticalAlign,
+    BlendMode, BorderRadius, BoxShadow, ChromaKey, Component, ComponentId, HorizontalAlign,
+    IntermediateNode, Position, RGBAColor, RescaleMode, SceneError, Size, SourceCrop,
+    StatefulComponent, VerticalAlign,
     components::RescalerComponent,
+    keyframes::KeyframesState,
     layout::StatefulLayoutComponent,
     scene_state::BuildStateTreeCtx,
     transition::{TransitionOptions, TransitionState},
@@ -20,6 +22,7 @@ pub(super) struct StatefulRescalerComponent {
     start: Option<RescalerComponentParam>,
     end: RescalerComponentParam,
     transition: Option<TransitionState>,
+    keyframes: Option<KeyframesState>,
     child: Box<StatefulComponent>,
 }
 
@@ -37,16 +40,32 @@ struct RescalerComponentParam {
     border_color: RGBAColor,
 
     box_shadow: Vec<BoxShadow>,
+
+    chroma_key: Option<ChromaKey>,
+    blur_radius: f32,
+    opacity: f32,
+    blend_mode: BlendMode,
+    crop: Option<SourceCrop>,
+    flip_horizontal: bool,
+    flip_vertical: bool,
 }
 
 impl StatefulRescalerComponent {
     /// Generate state of the component for particular pts value.
     fn transition_snapshot(&self, pts: Duration) -> RescalerComponentParam {
-        let (Some(transition), Some(start)) = (&self.transition, &self.start) else {
-            return self.end.clone();
+        let mut rescaler = match (&self.transition, &self.start) {
+            (Some(transition), Some(start)) => {
+                let interpolation_progress = transition.state(pts);
+                ContinuousValue::interpolate(start, &self.end, interpolation_progress)
+            }
+            _ => self.end.clone(),
         };
-        let interpolation_progress = transition.state(pts);
-        ContinuousValue::interpolate(start, &self.end, interpolation_progress)
+        if let Some(keyframes) = &self.keyframes {
+            for (property, value) in keyframes.values(pts) {
+                rescaler.apply_keyframe(property, value);
+            }
+        }
+        rescaler
     }
 
     pub(super) fn children(&self) -> Vec<&StatefulComponent> {
@@ -117,6 +136,13 @@ impl RescalerComponent {
             border_width: self.border_width,
             border_color: self.border_color,
             box_shadow: self.box_shadow,
+            chroma_key: self.chroma_key,
+            blur_radius: self.blur_radius,
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            crop: self.crop,
+            flip_horizontal: self.flip_horizontal,
+            flip_vertical: self.flip_vertical,
         };
 
         let props_changed = previous_state
@@ -134,10 +160,16 @@ impl RescalerComponent {
             interrupt_previous_transition,
             ctx.last_render_pts,
         );
+        let keyframes = KeyframesState::new(
+            self.keyframes,
+            previous_state.and_then(|s| s.keyframes.as_ref()),
+            ctx.last_render_pts,
+        );
         let rescaler = StatefulRescalerComponent {
             start,
             end,
             transition,
+            keyframes,
             child: Box::new(Component::stateful_component(*self.child, ctx)?),
         };
         Ok(StatefulComponent::Layout(
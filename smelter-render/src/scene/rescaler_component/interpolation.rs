@@ -1,4 +1,7 @@
-use crate::scene::types::interpolation::{ContinuousValue, InterpolationState};
+use crate::scene::{
+    AnimatedProperty,
+    types::interpolation::{ContinuousValue, InterpolationState},
+};
 
 use super::RescalerComponentParam;
 
@@ -22,6 +25,23 @@ impl ContinuousValue for RescalerComponentParam {
             ),
             border_color: end.border_color,
             box_shadow: ContinuousValue::interpolate(&start.box_shadow, &end.box_shadow, state),
+            chroma_key: end.chroma_key,
+            blur_radius: ContinuousValue::interpolate(&start.blur_radius, &end.blur_radius, state),
+            opacity: ContinuousValue::interpolate(&start.opacity, &end.opacity, state),
+            blend_mode: end.blend_mode,
+            crop: ContinuousValue::interpolate(&start.crop, &end.crop, state),
+            flip_horizontal: end.flip_horizontal,
+            flip_vertical: end.flip_vertical,
+        }
+    }
+}
+
+impl RescalerComponentParam {
+    pub(super) fn apply_keyframe(&mut self, property: AnimatedProperty, value: f32) {
+        match property {
+            AnimatedProperty::Opacity => self.opacity = value.clamp(0.0, 1.0),
+            AnimatedProperty::BlurRadius => self.blur_radius = value.max(0.0),
+            property => self.position.apply_keyframe(property, value),
         }
     }
 }
@@ -2,8 +2,8 @@ use std::time::Duration;
 
 use crate::{
     scene::{
-        BorderRadius, HorizontalAlign, RGBAColor, RescaleMode, Size, StatefulComponent,
-        VerticalAlign, layout::StatefulLayoutComponent,
+        BlendMode, BorderRadius, HorizontalAlign, RGBAColor, RescaleMode, Size, SourceCrop,
+        StatefulComponent, VerticalAlign, layout::StatefulLayoutComponent,
     },
     transformations::layout::{LayoutContent, Mask, NestedLayout},
 };
@@ -24,6 +24,9 @@ impl RescalerComponentParam {
         let child_width = child.width(pts);
         let child_height = child.height(pts);
         let border_radius = self.border_radius.clip_to_size(size);
+        if let Some(crop) = self.crop {
+            return self.layout_cropped(content_size, border_radius, child, pts, crop);
+        }
         match (child_width, child_height) {
             (None, None) => self.layout_with_scale(content_size, border_radius, child, pts, 1.0),
             (None, Some(child_height)) => self.layout_with_scale(
@@ -82,30 +85,11 @@ impl RescalerComponentParam {
                     child_nodes_count,
                 )
             }
-            ref _non_layout => (StatefulLayoutComponent::layout_content(child, 0), vec![], 1),
-        };
-
-        let top = match self.vertical_align {
-            VerticalAlign::Top => 0.0,
-            VerticalAlign::Bottom => child
-                .height(pts)
-                .map(|height| max_size.height - (height * scale))
-                .unwrap_or(0.0),
-            VerticalAlign::Center | VerticalAlign::Justified => child
-                .height(pts)
-                .map(|height| (max_size.height - (height * scale)) / 2.0)
-                .unwrap_or(0.0),
-        };
-        let left = match self.horizontal_align {
-            HorizontalAlign::Left => 0.0,
-            HorizontalAlign::Right => child
-                .width(pts)
-                .map(|width| max_size.width - (width * scale))
-                .unwrap_or(0.0),
-            HorizontalAlign::Center | HorizontalAlign::Justified => child
-                .width(pts)
-                .map(|width| (max_size.width - (width * scale)) / (2.0))
-                .unwrap_or(0.0),
+            ref _non_layout => (
+                StatefulLayoutComponent::layout_content(child, 0, pts),
+                vec![],
+                1,
+            ),
         };
 
         let width = child
@@ -116,6 +100,151 @@ impl RescalerComponentParam {
             .height(pts)
             .map(|child_height| child_height * scale)
             .unwrap_or(max_size.height);
+        let (top, left) = self.aligned_position(max_size, Size { width, height });
+
+        let child_layout = NestedLayout {
+            top: top + self.border_width,
+            left: left + self.border_width,
+            width,
+            height,
+            rotation_degrees: 0.0,
+            rotation_x_degrees: 0.0,
+            rotation_y_degrees: 0.0,
+            perspective: 0.0,
+            scale_x: scale,
+            scale_y: scale,
+            crop: None,
+            mask: None,
+            content,
+            child_nodes_count,
+            children,
+            border_width: 0.0,
+            border_color: RGBAColor(0, 0, 0, 0),
+            border_radius: BorderRadius::ZERO,
+            box_shadow: vec![],
+            opacity: 1.0,
+            blur_radius: 0.0,
+            blend_mode: BlendMode::Normal,
+        };
+        self.rescaler_layout(max_size, border_radius, child_layout)
+    }
+
+    /// Child is laid out with its own size (unknown dimensions are taken from the rescaler),
+    /// cropped and then the cropped part is rescaled.
+    fn layout_cropped(
+        &self,
+        max_size: Size, // without borders
+        border_radius: BorderRadius,
+        child: &mut StatefulComponent,
+        pts: Duration,
+        crop: SourceCrop,
+    ) -> NestedLayout {
+        let child_size = Size {
+            width: child.width(pts).unwrap_or(max_size.width),
+            height: child.height(pts).unwrap_or(max_size.height),
+        };
+        let crop = crop.rect(child_size);
+        let scale = match (crop.width > 0.0 && crop.height > 0.0, self.mode) {
+            (false, _) => 0.0,
+            (true, RescaleMode::Fit) => {
+                f32::min(max_size.width / crop.width, max_size.height / crop.height)
+            }
+            (true, RescaleMode::Fill) => {
+                f32::max(max_size.width / crop.width, max_size.height / crop.height)
+            }
+        };
+        let children = match child {
+            StatefulComponent::Layout(layout_component) => {
+                vec![layout_component.layout(child_size, pts)]
+            }
+            ref _non_layout => vec![NestedLayout {
+                top: 0.0,
+                left: 0.0,
+                width: child_size.width,
+                height: child_size.height,
+                rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: 0.0,
+                scale_x: 1.0,
+                scale_y: 1.0,
+                crop: None,
+                mask: None,
+                content: StatefulLayoutComponent::layout_content(child, 0, pts),
+                child_nodes_count: 1,
+                children: vec![],
+                border_width: 0.0,
+                border_color: RGBAColor(0, 0, 0, 0),
+                border_radius: BorderRadius::ZERO,
+                box_shadow: vec![],
+                opacity: 1.0,
+                blur_radius: 0.0,
+                blend_mode: BlendMode::Normal,
+            }],
+        };
+        let child_nodes_count = children.iter().map(|c| c.child_nodes_count).sum();
+
+        let width = crop.width * scale;
+        let height = crop.height * scale;
+        let (top, left) = self.aligned_position(max_size, Size { width, height });
+
+        let child_layout = NestedLayout {
+            top: top + self.border_width,
+            left: left + self.border_width,
+            width,
+            height,
+            rotation_degrees: 0.0,
+            rotation_x_degrees: 0.0,
+            rotation_y_degrees: 0.0,
+            perspective: 0.0,
+            scale_x: scale,
+            scale_y: scale,
+            crop: Some(crop),
+            mask: None,
+            content: LayoutContent::None,
+            child_nodes_count,
+            children,
+            border_width: 0.0,
+            border_color: RGBAColor(0, 0, 0, 0),
+            border_radius: BorderRadius::ZERO,
+            box_shadow: vec![],
+            opacity: 1.0,
+            blur_radius: 0.0,
+            blend_mode: BlendMode::Normal,
+        };
+        self.rescaler_layout(max_size, border_radius, child_layout)
+    }
+
+    /// Position of the rescaled child of `size` inside the rescaler.
+    fn aligned_position(&self, max_size: Size, size: Size) -> (f32, f32) {
+        let top = match self.vertical_align {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Bottom => max_size.height - size.height,
+            VerticalAlign::Center | VerticalAlign::Justified => {
+                (max_size.height - size.height) / 2.0
+            }
+        };
+        let left = match self.horizontal_align {
+            HorizontalAlign::Left => 0.0,
+            HorizontalAlign::Right => max_size.width - size.width,
+            HorizontalAlign::Center | HorizontalAlign::Justified => {
+                (max_size.width - size.width) / 2.0
+            }
+        };
+        (top, left)
+    }
+
+    fn rescaler_layout(
+        &self,
+        max_size: Size, // without borders
+        border_radius: BorderRadius,
+        mut child_layout: NestedLayout,
+    ) -> NestedLayout {
+        if let Some(chroma_key) = self.chroma_key {
+            child_layout.apply_chroma_key(chroma_key);
+        }
+        child_layout.flip(self.flip_horizontal, self.flip_vertical);
+        let child_nodes_count = child_layout.child_nodes_count;
 
         NestedLayout {
             top: 0.0,
@@ -123,6 +252,9 @@ impl RescalerComponentParam {
             width: max_size.width + (self.border_width * 2.0),
             height: max_size.height + (self.border_width * 2.0),
             rotation_degrees: 0.0,
+            rotation_x_degrees: 0.0,
+            rotation_y_degrees: 0.0,
+            perspective: 0.0,
             scale_x: 1.0,
             scale_y: 1.0,
             crop: None,
@@ -134,29 +266,15 @@ impl RescalerComponentParam {
                 height: max_size.height,
             }),
             content: LayoutContent::None,
-            children: vec![NestedLayout {
-                top: top + self.border_width,
-                left: left + self.border_width,
-                width,
-                height,
-                rotation_degrees: 0.0,
-                scale_x: scale,
-                scale_y: scale,
-                crop: None,
-                mask: None,
-                content,
-                child_nodes_count,
-                children,
-                border_width: 0.0,
-                border_color: RGBAColor(0, 0, 0, 0),
-                border_radius: BorderRadius::ZERO,
-                box_shadow: vec![],
-            }],
+            children: vec![child_layout],
             child_nodes_count,
             border_width: self.border_width,
             border_color: self.border_color,
             border_radius,
             box_shadow: self.box_shadow.clone(),
+            opacity: self.opacity,
+            blur_radius: self.blur_radius,
+            blend_mode: self.blend_mode,
         }
     }
 }
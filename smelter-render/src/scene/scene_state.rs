@@ -9,6 +9,7 @@ use crate::{
 
 use super::{
     ComponentId, Node, NodeParams, OutputScene, Position, SceneError, Size, StatefulComponent,
+    audio_visualizer_component::StatefulAudioVisualizerComponent,
     image_component::StatefulImageComponent,
     input_stream_component::StatefulInputStreamComponent,
     layout::{LayoutNode, SizedLayoutComponent, StatefulLayoutComponent},
@@ -124,6 +125,26 @@ impl SceneState {
 
         Ok(output_node_tree)
     }
+
+    /// Drops children of tiles components whose exit transition finished at the last
+    /// render pts. Returns new node trees for the outputs that changed.
+    pub(crate) fn drop_finished_removed_children(&mut self) -> Result<Vec<OutputNode>, SceneError> {
+        let mut output_nodes = vec![];
+        for (output_id, output) in self.output_states.iter_mut() {
+            if !drop_finished_removed_children(&mut output.root, self.last_pts) {
+                continue;
+            }
+            output_nodes.push(OutputNode {
+                output_id: output_id.clone(),
+                node: output
+                    .root
+                    .intermediate_node()
+                    .build_tree(Some(output.resolution), self.last_pts)?,
+                resolution: output.resolution,
+            });
+        }
+        Ok(output_nodes)
+    }
 }
 
 /// Intermediate representation of a node tree while it's being constructed.
@@ -139,6 +160,7 @@ pub(super) enum IntermediateNode {
     },
     Image(StatefulImageComponent),
     Text(StatefulTextComponent),
+    AudioVisualizer(StatefulAudioVisualizerComponent),
     Layout {
         root: Box<StatefulLayoutComponent>,
         children: Vec<IntermediateNode>,
@@ -192,6 +214,10 @@ impl IntermediateNode {
                 params: NodeParams::Text(text.params),
                 children: vec![],
             }),
+            IntermediateNode::AudioVisualizer(visualizer) => Ok(Node {
+                params: NodeParams::AudioVisualizer(visualizer.params),
+                children: vec![],
+            }),
         }
     }
 
@@ -205,6 +231,7 @@ impl IntermediateNode {
             IntermediateNode::WebView { web, children: _ } => Ok(web.size()),
             IntermediateNode::Image(image) => Ok(image.size()),
             IntermediateNode::Text(text) => Ok(text.size()),
+            IntermediateNode::AudioVisualizer(visualizer) => Ok(visualizer.size()),
             IntermediateNode::Layout { root, children: _ } => {
                 let (width, height) = match root.position(pts) {
                     Position::Static { width, height } => (width, height),
@@ -299,6 +326,11 @@ fn gather_components_with_id<'a>(
                 components.insert(id.clone(), component);
             }
         }
+        StatefulComponent::AudioVisualizer(visualizer) => {
+            if let Some(id) = visualizer.component_id() {
+                components.insert(id.clone(), component);
+            }
+        }
         StatefulComponent::Layout(layout) => {
             if let Some(id) = layout.component_id() {
                 components.insert(id.clone(), component);
@@ -309,3 +341,17 @@ fn gather_components_with_id<'a>(
         }
     }
 }
+
+fn drop_finished_removed_children(component: &mut StatefulComponent, pts: Duration) -> bool {
+    let mut dropped = match component {
+        StatefulComponent::Layout(layout) => match layout.as_mut() {
+            StatefulLayoutComponent::Tiles(tiles) => tiles.drop_finished_removed_children(pts),
+            _ => false,
+        },
+        _ => false,
+    };
+    for child in component.children_mut() {
+        dropped |= drop_finished_removed_children(child, pts);
+    }
+    dropped
+}
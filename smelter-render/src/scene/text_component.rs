@@ -1,4 +1,6 @@
-use crate::transformations::text_renderer::TextRenderParams;
+use std::sync::Arc;
+
+use crate::transformations::text_renderer::{TextRenderParams, TickerParams};
 
 use super::{
     ComponentId, IntermediateNode, SceneError, Size, StatefulComponent, TextComponent,
@@ -8,6 +10,7 @@ use super::{
 #[derive(Debug, Clone)]
 pub(super) struct StatefulTextComponent {
     id: Option<ComponentId>,
+    text: Arc<str>,
     pub(super) params: TextRenderParams,
 }
 
@@ -38,15 +41,40 @@ impl TextComponent {
         self,
         ctx: &BuildStateTreeCtx,
     ) -> Result<StatefulComponent, SceneError> {
+        let previous_state = self
+            .id
+            .as_ref()
+            .and_then(|id| ctx.prev_state.get(id))
+            .and_then(|component| match component {
+                StatefulComponent::Text(text_state) => Some(text_state),
+                _ => None,
+            });
+        // Scrolling is continued if the text and the ticker did not change since the
+        // previous scene update, otherwise it starts from the beginning.
+        let ticker = self.ticker.map(|ticker| {
+            let start_pts = previous_state
+                .filter(|state| state.text == self.text)
+                .and_then(|state| state.params.ticker.as_ref())
+                .filter(|previous| previous.ticker == ticker)
+                .map_or(ctx.last_render_pts, |previous| previous.start_pts);
+            TickerParams { ticker, start_pts }
+        });
+
         let (buffer, resolution) = ctx
             .text_renderer_ctx
             .layout_text((&self).into(), self.dimensions);
         Ok(StatefulComponent::Text(StatefulTextComponent {
             id: self.id,
+            text: self.text,
             params: TextRenderParams {
                 buffer,
                 resolution,
                 background_color: self.background_color,
+                color: self.color,
+                gradient: self.gradient,
+                stroke: self.stroke,
+                shadow: self.shadow,
+                ticker,
             },
         }))
     }
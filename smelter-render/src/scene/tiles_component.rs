@@ -3,17 +3,19 @@ use std::{ops::Deref, time::Duration};
 use crate::transformations::layout::NestedLayout;
 
 use self::{
+    interpolation::{TileTransitionStates, exit_tiles, interpolate_tiles},
     layout::{layout_tiles, resize_tiles},
-    tiles::Tile,
+    tiles::{Tile, tile_ids},
 };
 
 use super::{
     Component, ComponentId, HorizontalAlign, IntermediateNode, Position, RGBAColor, SceneError,
-    Size, StatefulComponent, TilesComponent, VerticalAlign,
+    Size, StatefulComponent, TileTransition, TileTransitionEffect, TileTransitions, TilesComponent,
+    TilesFillStrategy, VerticalAlign,
     layout::StatefulLayoutComponent,
     scene_state::BuildStateTreeCtx,
     transition::{TransitionOptions, TransitionState},
-    types::interpolation::ContinuousValue,
+    types::interpolation::InterpolationState,
 };
 
 mod interpolation;
@@ -31,9 +33,18 @@ pub(super) struct StatefulTilesComponent {
     last_layout: Option<(Vec<Option<Tile>>, Size)>,
 
     transition: Option<TransitionState>,
+    /// Duration of the longest tile transition. Tile transitions with that duration follow
+    /// `transition` state directly.
+    transition_duration: Duration,
+    tile_transitions: TileTransitions,
 
     component: TilesComponentParams,
     children: Vec<StatefulComponent>,
+
+    /// Children removed in the last scene update together with their last tile
+    /// (in the same coordinates as `start`). They are faded out during the transition
+    /// and dropped when the exit transition finishes.
+    removed_children: Vec<(Tile, StatefulComponent)>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -44,7 +55,10 @@ struct TilesComponentParams {
     height: Option<f32>,
 
     background_color: RGBAColor,
+    fill_strategy: TilesFillStrategy,
     tile_aspect_ratio: (u32, u32),
+    max_tile_width: Option<f32>,
+    max_tile_height: Option<f32>,
     margin: f32,
     padding: f32,
     horizontal_align: HorizontalAlign,
@@ -62,8 +76,63 @@ impl StatefulTilesComponent {
             return end.clone();
         };
         let start = resize_tiles(start, start_size, &size);
-        let interpolation_progress = transition.state(pts);
-        ContinuousValue::interpolate(&start, &end, interpolation_progress)
+        let states = self.tile_transition_states(transition, pts);
+        interpolate_tiles(&start, &end, &self.tile_transitions, states, size)
+    }
+
+    fn removed_tiles(&self, size: Size, pts: Duration) -> Vec<Option<Tile>> {
+        let (Some((_, start_size)), Some(transition)) = (&self.start, &self.transition) else {
+            return vec![None; self.removed_children.len()];
+        };
+        let states = self.tile_transition_states(transition, pts);
+        let tiles: Vec<Option<Tile>> = self
+            .removed_children
+            .iter()
+            .map(|(tile, _)| Some(tile.clone()))
+            .collect();
+        let tiles = resize_tiles(&tiles, start_size, &size);
+        exit_tiles(&tiles, &self.tile_transitions, states, size)
+    }
+
+    fn tile_transition_states(
+        &self,
+        transition: &TransitionState,
+        pts: Duration,
+    ) -> TileTransitionStates {
+        let state = |tile_transition: &TileTransition| match tile_transition {
+            TileTransition {
+                effect: TileTransitionEffect::None,
+                ..
+            } => InterpolationState(1.0),
+            TileTransition {
+                duration: Some(duration),
+                ..
+            } if !self.transition_duration.is_zero() => transition.state_with_fraction(
+                pts,
+                duration.as_secs_f64() / self.transition_duration.as_secs_f64(),
+            ),
+            _ => transition.state(pts),
+        };
+        TileTransitionStates {
+            enter: state(&self.tile_transitions.enter),
+            exit: state(&self.tile_transitions.exit),
+            reorder: state(&self.tile_transitions.reorder),
+        }
+    }
+
+    /// Drops removed children after their exit transition finished, so they are
+    /// no longer part of the render graph. Returns true if any child was dropped.
+    pub(super) fn drop_finished_removed_children(&mut self, pts: Duration) -> bool {
+        let Some(transition) = &self.transition else {
+            return false;
+        };
+        if self.removed_children.is_empty()
+            || self.tile_transition_states(transition, pts).exit.0 < 1.0
+        {
+            return false;
+        }
+        self.removed_children.clear();
+        true
     }
 
     pub(super) fn position(&self, _pts: Duration) -> Position {
@@ -74,17 +143,23 @@ impl StatefulTilesComponent {
     }
 
     pub(super) fn children(&self) -> Vec<&StatefulComponent> {
-        self.children.iter().collect()
+        self.children
+            .iter()
+            .chain(self.removed_children.iter().map(|(_, child)| child))
+            .collect()
     }
 
     pub(super) fn children_mut(&mut self) -> Vec<&mut StatefulComponent> {
-        self.children.iter_mut().collect()
+        self.children
+            .iter_mut()
+            .chain(self.removed_children.iter_mut().map(|(_, child)| child))
+            .collect()
     }
 
     pub(super) fn intermediate_node(&self) -> IntermediateNode {
         let children = self
-            .children
-            .iter()
+            .children()
+            .into_iter()
             .flat_map(|component| {
                 let node = component.intermediate_node();
                 match node {
@@ -102,12 +177,14 @@ impl StatefulTilesComponent {
 
     pub(super) fn layout(&mut self, size: Size, pts: Duration) -> NestedLayout {
         let tiles = self.tiles(size, pts);
+        let removed_tiles = self.removed_tiles(size, pts);
+        let background_color = self.component.background_color;
         let layout = layout_tiles(
-            &tiles,
+            &[tiles.as_slice(), removed_tiles.as_slice()].concat(),
             size,
-            &mut self.children,
+            self.children_mut(),
             pts,
-            self.component.background_color,
+            background_color,
         );
         self.last_layout = Some((tiles, size));
         layout
@@ -137,7 +214,10 @@ impl TilesComponent {
             width: self.width,
             height: self.height,
             background_color: self.background_color,
+            fill_strategy: self.fill_strategy,
             tile_aspect_ratio: self.tile_aspect_ratio,
+            max_tile_width: self.max_tile_width,
+            max_tile_height: self.max_tile_height,
             margin: self.margin,
             padding: self.padding,
             horizontal_align: self.horizontal_align,
@@ -157,9 +237,30 @@ impl TilesComponent {
             .unwrap_or(false);
         let interrupt_previous_transition =
             self.transition.map(|t| t.should_interrupt).unwrap_or(false);
+        // Tile transitions without a duration use the duration of the component transition.
+        let resolve_duration = |tile_transition: TileTransition| TileTransition {
+            duration: tile_transition
+                .duration
+                .or(self.transition.map(|transition| transition.duration)),
+            ..tile_transition
+        };
+        let tile_transitions = TileTransitions {
+            enter: resolve_duration(self.tile_transitions.enter),
+            exit: resolve_duration(self.tile_transitions.exit),
+            reorder: resolve_duration(self.tile_transitions.reorder),
+        };
+        let transition_duration = [
+            tile_transitions.enter,
+            tile_transitions.exit,
+            tile_transitions.reorder,
+        ]
+        .into_iter()
+        .filter_map(|tile_transition| tile_transition.duration)
+        .max()
+        .unwrap_or_default();
         let transition = TransitionState::new(
             self.transition.map(|transition| TransitionOptions {
-                duration: transition.duration,
+                duration: transition_duration,
                 interpolation_kind: transition.interpolation_kind,
             }),
             previous_state.and_then(|s| s.transition.clone()),
@@ -167,12 +268,19 @@ impl TilesComponent {
             interrupt_previous_transition,
             ctx.last_render_pts,
         );
+        let removed_children = match (previous_state, &transition) {
+            (Some(previous_state), Some(_)) => Self::removed_children(previous_state, &children),
+            _ => vec![],
+        };
         let tiles = StatefulTilesComponent {
             start,
             last_layout: previous_state.and_then(|state| state.last_layout.clone()),
             component,
             transition,
+            transition_duration,
+            tile_transitions,
             children,
+            removed_children,
         };
 
         Ok(StatefulComponent::Layout(
@@ -192,4 +300,38 @@ impl TilesComponent {
             .zip(current_children.iter())
             .any(|(c1, c2)| c1.component_id() != c2.component_id())
     }
+
+    /// Children that were rendered before the scene update, but are not part of the
+    /// new scene. Web views are skipped, because their instance can be already used
+    /// somewhere else in the new scene.
+    fn removed_children(
+        previous_state: &StatefulTilesComponent,
+        current_children: &[StatefulComponent],
+    ) -> Vec<(Tile, StatefulComponent)> {
+        let Some((previous_tiles, _)) = &previous_state.last_layout else {
+            return vec![];
+        };
+        let current_ids = tile_ids(current_children);
+        previous_tiles
+            .iter()
+            .zip(previous_state.children.iter())
+            .filter_map(|(tile, child)| {
+                let tile = tile.as_ref()?;
+                let is_removed = !current_ids.contains(&tile.id);
+                (is_removed && !contains_web_view(child)).then(|| (tile.clone(), child.clone()))
+            })
+            .collect()
+    }
+}
+
+fn contains_web_view(component: &StatefulComponent) -> bool {
+    match component {
+        StatefulComponent::WebView(_) => true,
+        StatefulComponent::Shader(shader) => shader.children.iter().any(contains_web_view),
+        StatefulComponent::Layout(layout) => layout.children().into_iter().any(contains_web_view),
+        StatefulComponent::InputStream(_)
+        | StatefulComponent::Image(_)
+        | StatefulComponent::Text(_)
+        | StatefulComponent::AudioVisualizer(_) => false,
+    }
 }
@@ -1,7 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use crate::scene::{
-    ComponentId,
+    ComponentId, Size, TileTransitionEffect, TileTransitions,
     types::interpolation::{ContinuousValue, InterpolationState},
 };
 
@@ -13,54 +13,135 @@ pub(super) enum TileId {
     Index(usize),
 }
 
-impl ContinuousValue for Vec<Option<Tile>> {
-    fn interpolate(start: &Self, end: &Self, state: InterpolationState) -> Self {
-        let start_id_map: HashMap<&TileId, usize> = start
-            .iter()
-            .enumerate()
-            .filter_map(|(index, tile)| tile.as_ref().map(|tile| (&tile.id, index)))
-            .collect();
-        let end_id_set: HashSet<&TileId> = end
-            .iter()
-            .filter_map(|tile| tile.as_ref().map(|tile| &tile.id))
-            .collect();
-
-        if state.0 >= 1.0 {
-            return end.clone();
-        };
+/// Interpolation states of each kind of tile transitions at the same pts.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct TileTransitionStates {
+    pub enter: InterpolationState,
+    pub exit: InterpolationState,
+    pub reorder: InterpolationState,
+}
+
+pub(super) fn interpolate_tiles(
+    start: &[Option<Tile>],
+    end: &[Option<Tile>],
+    transitions: &TileTransitions,
+    states: TileTransitionStates,
+    layout_size: Size,
+) -> Vec<Option<Tile>> {
+    let start_id_map: HashMap<&TileId, usize> = start
+        .iter()
+        .enumerate()
+        .filter_map(|(index, tile)| tile.as_ref().map(|tile| (&tile.id, index)))
+        .collect();
+
+    end.iter()
+        .map(|tile| {
+            let tile = tile.as_ref()?;
+            let old_tile = start_id_map
+                .get(&tile.id)
+                .and_then(|index| start.get(*index))
+                .and_then(|old_tile| old_tile.as_ref());
+            match old_tile {
+                // For each tile that existed before the last update (exists in both `start` and `end`)
+                // interpolate between those 2 states.
+                Some(old_tile) => reorder_tile(
+                    old_tile,
+                    tile,
+                    transitions.reorder.effect,
+                    states.reorder,
+                    layout_size,
+                ),
+                // New tile (`tile` did not exist in the `start` state) is rendered in its final
+                // position and appears during the transition.
+                None => apply_effect(tile, transitions.enter.effect, states.enter.0, layout_size),
+            }
+        })
+        .collect()
+}
+
+/// Tiles of the removed children. `tiles` should be in the same coordinates as the
+/// tiles of the current layout.
+pub(super) fn exit_tiles(
+    tiles: &[Option<Tile>],
+    transitions: &TileTransitions,
+    states: TileTransitionStates,
+    layout_size: Size,
+) -> Vec<Option<Tile>> {
+    tiles
+        .iter()
+        .map(|tile| {
+            apply_effect(
+                tile.as_ref()?,
+                transitions.exit.effect,
+                1.0 - states.exit.0,
+                layout_size,
+            )
+        })
+        .collect()
+}
+
+fn reorder_tile(
+    start: &Tile,
+    end: &Tile,
+    effect: TileTransitionEffect,
+    state: InterpolationState,
+    layout_size: Size,
+) -> Option<Tile> {
+    let state = f64::clamp(state.0, 0.0, 1.0);
+    let is_moved = start.top != end.top
+        || start.left != end.left
+        || start.width != end.width
+        || start.height != end.height;
+    match effect {
+        TileTransitionEffect::Slide => Some(ContinuousValue::interpolate(
+            start,
+            end,
+            InterpolationState(state),
+        )),
+        TileTransitionEffect::None => Some(end.clone()),
+        // Tile disappears from the old position during the first half of the transition
+        // and appears in the new one during the second half.
+        TileTransitionEffect::Fade | TileTransitionEffect::Scale if is_moved => match state < 0.5 {
+            true => apply_effect(start, effect, 1.0 - 2.0 * state, layout_size),
+            false => apply_effect(end, effect, 2.0 * state - 1.0, layout_size),
+        },
+        TileTransitionEffect::Fade | TileTransitionEffect::Scale => Some(
+            ContinuousValue::interpolate(start, end, InterpolationState(state)),
+        ),
+    }
+}
 
-        end.iter()
-            .map(|tile| {
-                let tile = tile.as_ref()?;
-                start_id_map
-                    .get(&tile.id)
-                    .and_then(|index| start.get(*index))
-                    .and_then(|old_tile| {
-                        // For each tile that existed before the last update (exists in both `start` and `end`)
-                        // interpolate between those 2 states.
-                        old_tile
-                            .as_ref()
-                            .map(|old_tile| ContinuousValue::interpolate(old_tile, tile, state))
-                    })
-                    .or_else(|| {
-                        // Handle a new tile (`tile` did not exist in the `start` state). Tiles that existed before
-                        // last update should be handled in the previous `and_then` clousure.
-                        //
-                        // - If any tile existed in the same position as the new tile before the transition and still exists
-                        //   somewhere in the `end` state then do not show the new tile until the end of the transition.
-                        // - Otherwise, show the new tile from the start of the transition.
-                        start
-                            .iter()
-                            .flatten()
-                            .find(|start_tile| are_positions_equal(start_tile, tile))
-                            .and_then(|start_tile| match end_id_set.contains(&start_tile.id) {
-                                true => None,
-                                false => Some(tile.clone()),
-                            })
-                    })
-            })
-            .collect()
+/// `visibility` is in the range from 0 (tile is hidden) to 1 (tile is rendered in
+/// its target position).
+fn apply_effect(
+    tile: &Tile,
+    effect: TileTransitionEffect,
+    visibility: f64,
+    layout_size: Size,
+) -> Option<Tile> {
+    let visibility = f64::clamp(visibility, 0.0, 1.0) as f32;
+    if visibility <= 0.0 {
+        return None;
     }
+    let tile = match effect {
+        TileTransitionEffect::None => tile.clone(),
+        TileTransitionEffect::Fade => Tile {
+            opacity: tile.opacity * visibility,
+            ..tile.clone()
+        },
+        TileTransitionEffect::Slide => Tile {
+            top: layout_size.height + (tile.top - layout_size.height) * visibility,
+            ..tile.clone()
+        },
+        TileTransitionEffect::Scale => Tile {
+            top: tile.top + tile.height * (1.0 - visibility) / 2.0,
+            left: tile.left + tile.width * (1.0 - visibility) / 2.0,
+            width: tile.width * visibility,
+            height: tile.height * visibility,
+            ..tile.clone()
+        },
+    };
+    Some(tile)
 }
 
 impl ContinuousValue for Tile {
@@ -71,17 +152,128 @@ impl ContinuousValue for Tile {
             left: ContinuousValue::interpolate(&start.left, &end.left, state),
             width: ContinuousValue::interpolate(&start.width, &end.width, state),
             height: ContinuousValue::interpolate(&start.height, &end.height, state),
+            opacity: ContinuousValue::interpolate(&start.opacity, &end.opacity, state),
         }
     }
 }
 
-fn are_positions_equal(lhs: &Tile, rhs: &Tile) -> bool {
-    const TOLERANCE: f32 = 0.001;
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::scene::TileTransition;
+
+    use super::*;
+
+    const LAYOUT_SIZE: Size = Size {
+        width: 200.0,
+        height: 100.0,
+    };
+
+    fn tile(id: &str, top: f32, left: f32) -> Tile {
+        Tile {
+            id: TileId::ComponentId(ComponentId(Arc::from(id))),
+            top,
+            left,
+            width: 100.0,
+            height: 50.0,
+            opacity: 1.0,
+        }
+    }
 
-    let top_eq = f32::abs(lhs.top - rhs.top) <= TOLERANCE;
-    let left_eq = f32::abs(lhs.left - rhs.left) <= TOLERANCE;
-    let width_eq = f32::abs(lhs.width - rhs.width) <= TOLERANCE;
-    let height_eq = f32::abs(lhs.height - rhs.height) <= TOLERANCE;
+    fn transitions(effect: TileTransitionEffect) -> TileTransitions {
+        let transition = TileTransition {
+            effect,
+            duration: None,
+        };
+        TileTransitions {
+            enter: transition,
+            exit: transition,
+            reorder: transition,
+        }
+    }
 
-    top_eq && left_eq && width_eq && height_eq
+    fn states(state: f64) -> TileTransitionStates {
+        TileTransitionStates {
+            enter: InterpolationState(state),
+            exit: InterpolationState(state),
+            reorder: InterpolationState(state),
+        }
+    }
+
+    fn rect(tile: &Option<Tile>) -> Option<(f32, f32, f32, f32, f32)> {
+        tile.as_ref()
+            .map(|t| (t.top, t.left, t.width, t.height, t.opacity))
+    }
+
+    /// Returns (moved tile, entering tile, exiting tile) in the middle of the transition.
+    fn interpolate(effect: TileTransitionEffect, state: f64) -> [Option<Tile>; 3] {
+        let start = [Some(tile("moved", 0.0, 0.0))];
+        let end = [
+            Some(tile("moved", 50.0, 100.0)),
+            Some(tile("new", 0.0, 0.0)),
+        ];
+        let removed = [Some(tile("removed", 0.0, 100.0))];
+        let transitions = transitions(effect);
+        let tiles = interpolate_tiles(&start, &end, &transitions, states(state), LAYOUT_SIZE);
+        let exit = exit_tiles(&removed, &transitions, states(state), LAYOUT_SIZE);
+        [tiles[0].clone(), tiles[1].clone(), exit[0].clone()]
+    }
+
+    #[test]
+    fn none_effect() {
+        let [moved, entering, exiting] = interpolate(TileTransitionEffect::None, 0.5);
+        assert_eq!(rect(&moved), Some((50.0, 100.0, 100.0, 50.0, 1.0)));
+        assert_eq!(rect(&entering), Some((0.0, 0.0, 100.0, 50.0, 1.0)));
+        assert_eq!(rect(&exiting), Some((0.0, 100.0, 100.0, 50.0, 1.0)));
+    }
+
+    #[test]
+    fn fade_effect() {
+        let [moved, entering, exiting] = interpolate(TileTransitionEffect::Fade, 0.25);
+        // Moved tile fades out from the old position in the first half.
+        assert_eq!(rect(&moved), Some((0.0, 0.0, 100.0, 50.0, 0.5)));
+        assert_eq!(rect(&entering), Some((0.0, 0.0, 100.0, 50.0, 0.25)));
+        assert_eq!(rect(&exiting), Some((0.0, 100.0, 100.0, 50.0, 0.75)));
+
+        let [moved, _, _] = interpolate(TileTransitionEffect::Fade, 0.75);
+        // and fades in at the new position in the second half.
+        assert_eq!(rect(&moved), Some((50.0, 100.0, 100.0, 50.0, 0.5)));
+    }
+
+    #[test]
+    fn slide_effect() {
+        let [moved, entering, exiting] = interpolate(TileTransitionEffect::Slide, 0.5);
+        assert_eq!(rect(&moved), Some((25.0, 50.0, 100.0, 50.0, 1.0)));
+        // Tiles slide from/to the bottom edge of the layout.
+        assert_eq!(rect(&entering), Some((50.0, 0.0, 100.0, 50.0, 1.0)));
+        assert_eq!(rect(&exiting), Some((50.0, 100.0, 100.0, 50.0, 1.0)));
+    }
+
+    #[test]
+    fn scale_effect() {
+        let [moved, entering, exiting] = interpolate(TileTransitionEffect::Scale, 0.25);
+        assert_eq!(rect(&moved), Some((12.5, 25.0, 50.0, 25.0, 1.0)));
+        assert_eq!(rect(&entering), Some((18.75, 37.5, 25.0, 12.5, 1.0)));
+        assert_eq!(rect(&exiting), Some((6.25, 112.5, 75.0, 37.5, 1.0)));
+    }
+
+    #[test]
+    fn hidden_at_the_edges_of_transition() {
+        for effect in [
+            TileTransitionEffect::Fade,
+            TileTransitionEffect::Slide,
+            TileTransitionEffect::Scale,
+        ] {
+            let [moved, entering, exiting] = interpolate(effect, 0.0);
+            assert_eq!(rect(&moved), Some((0.0, 0.0, 100.0, 50.0, 1.0)));
+            assert!(entering.is_none(), "{effect:?}");
+            assert_eq!(rect(&exiting), Some((0.0, 100.0, 100.0, 50.0, 1.0)));
+
+            let [moved, entering, exiting] = interpolate(effect, 1.0);
+            assert_eq!(rect(&moved), Some((50.0, 100.0, 100.0, 50.0, 1.0)));
+            assert_eq!(rect(&entering), Some((0.0, 0.0, 100.0, 50.0, 1.0)));
+            assert!(exiting.is_none(), "{effect:?}");
+        }
+    }
 }
@@ -1,7 +1,10 @@
 use std::time::Duration;
 
 use crate::{
-    scene::{BorderRadius, RGBAColor, Size, StatefulComponent, layout::StatefulLayoutComponent},
+    scene::{
+        BlendMode, BorderRadius, RGBAColor, Size, StatefulComponent,
+        layout::StatefulLayoutComponent,
+    },
     transformations::layout::{LayoutContent, NestedLayout},
 };
 
@@ -10,12 +13,12 @@ use super::tiles::Tile;
 pub(super) fn layout_tiles(
     tiles: &[Option<Tile>],
     size: Size,
-    children: &mut [StatefulComponent],
+    children: Vec<&mut StatefulComponent>,
     pts: Duration,
     background_color: RGBAColor,
 ) -> NestedLayout {
     let children = children
-        .iter_mut()
+        .into_iter()
         .zip(tiles)
         .map(|(component, tile)| layout_child(component, tile.clone(), pts))
         .collect::<Vec<_>>();
@@ -26,6 +29,9 @@ pub(super) fn layout_tiles(
         width: size.width,
         height: size.height,
         rotation_degrees: 0.0,
+        rotation_x_degrees: 0.0,
+        rotation_y_degrees: 0.0,
+        perspective: 0.0,
         scale_x: 1.0,
         scale_y: 1.0,
         crop: None,
@@ -37,6 +43,9 @@ pub(super) fn layout_tiles(
         border_color: RGBAColor(0, 0, 0, 0),
         border_radius: BorderRadius::ZERO,
         box_shadow: vec![],
+        opacity: 1.0,
+        blur_radius: 0.0,
+        blend_mode: BlendMode::Normal,
     }
 }
 
@@ -66,6 +75,9 @@ fn layout_child(child: &mut StatefulComponent, tile: Option<Tile>, pts: Duration
                 width: tile.width,
                 height: tile.height,
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: 0.0,
                 scale_x: 1.0,
                 scale_y: 1.0,
                 crop: None,
@@ -77,6 +89,9 @@ fn layout_child(child: &mut StatefulComponent, tile: Option<Tile>, pts: Duration
                 border_color: RGBAColor(0, 0, 0, 0),
                 border_radius: BorderRadius::ZERO,
                 box_shadow: vec![],
+                opacity: tile.opacity,
+                blur_radius: 0.0,
+                blend_mode: BlendMode::Normal,
             }
         }
         _ => {
@@ -88,17 +103,23 @@ fn layout_child(child: &mut StatefulComponent, tile: Option<Tile>, pts: Duration
                 width: fitted.width,
                 height: fitted.height,
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: 0.0,
                 scale_x: 1.0,
                 scale_y: 1.0,
                 crop: None,
                 mask: None,
-                content: StatefulLayoutComponent::layout_content(child, 0),
+                content: StatefulLayoutComponent::layout_content(child, 0, pts),
                 child_nodes_count: 1,
                 children: vec![],
                 border_width: 0.0,
                 border_color: RGBAColor(0, 0, 0, 0),
                 border_radius: BorderRadius::ZERO,
                 box_shadow: vec![],
+                opacity: fitted.opacity,
+                blur_radius: 0.0,
+                blend_mode: BlendMode::Normal,
             }
         }
     }
@@ -123,6 +144,7 @@ fn fit_into_tile(tile: Tile, component: &StatefulComponent, pts: Duration) -> Ti
         left: tile.left + left_offset,
         width: scale_factor * width,
         height: scale_factor * height,
+        opacity: tile.opacity,
         id: tile.id,
     }
 }
@@ -145,6 +167,7 @@ pub(super) fn resize_tiles(
                 left: tile.left * scale,
                 width: tile.width * scale,
                 height: tile.height * scale,
+                opacity: tile.opacity,
             })
         })
         .collect()
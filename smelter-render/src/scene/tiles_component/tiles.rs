@@ -1,4 +1,4 @@
-use crate::scene::{HorizontalAlign, Size, StatefulComponent, VerticalAlign};
+use crate::scene::{HorizontalAlign, Size, StatefulComponent, TilesFillStrategy, VerticalAlign};
 
 use super::{TilesComponentParams, interpolation::TileId};
 
@@ -15,6 +15,7 @@ pub(super) struct Tile {
     pub left: f32,
     pub width: f32,
     pub height: f32,
+    pub opacity: f32,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -28,27 +29,28 @@ struct TilePosition {
 impl TilesComponentParams {
     pub(super) fn tiles(&self, size: Size, children: &[StatefulComponent]) -> Vec<Option<Tile>> {
         let input_count = children.len() as u32;
-        let rows_cols = self.optimal_row_column_count(input_count, size);
+        let rows_cols = match self.fill_strategy {
+            TilesFillStrategy::Fit | TilesFillStrategy::Fill => {
+                self.optimal_row_column_count(input_count, size)
+            }
+            TilesFillStrategy::FixedColumns(columns) => RowsCols {
+                rows: input_count.div_ceil(columns),
+                columns,
+            },
+        };
         let tile_size = self.tile_size(rows_cols, size);
         let tiles = self.tiles_positions(input_count, rows_cols, tile_size, size);
-        let mut index = 0;
         tiles
             .into_iter()
-            .zip(children.iter())
-            .map(|(tile, child)| {
+            .zip(tile_ids(children))
+            .map(|(tile, id)| {
                 Some(Tile {
                     top: tile.top,
                     left: tile.left,
                     width: tile.width,
                     height: tile.height,
-                    id: match child.component_id() {
-                        Some(id) => TileId::ComponentId(id.clone()),
-                        None => {
-                            let id = TileId::Index(index);
-                            index += 1;
-                            id
-                        }
-                    },
+                    opacity: 1.0,
+                    id,
                 })
             })
             .collect()
@@ -67,7 +69,7 @@ impl TilesComponentParams {
         for rows in 1..=inputs_count {
             let rows_cols = from_rows_count(inputs_count, rows);
             // larger width <=> larger tile size, because of const tile aspect ratio
-            let tile_size = self.tile_size(rows_cols, layout_size).width;
+            let tile_size = self.fitted_tile_size(rows_cols, layout_size).width;
             if tile_size > best_tile_width {
                 best_rows_cols = rows_cols;
                 best_tile_width = tile_size;
@@ -77,18 +79,38 @@ impl TilesComponentParams {
         best_rows_cols
     }
 
+    /// Size of a tile limited by `max_tile_width` and `max_tile_height`. Tiles preserve
+    /// the aspect ratio, unless the fill strategy is `Fill`.
     fn tile_size(&self, rows_cols: RowsCols, layout_size: Size) -> Size {
-        let x_padding = rows_cols.columns as f32 * 2.0 * self.padding;
-        let y_padding = rows_cols.rows as f32 * 2.0 * self.padding;
-        let x_margin = (rows_cols.columns as f32 + 1.0) * self.margin;
-        let y_margin = (rows_cols.rows as f32 + 1.0) * self.margin;
+        let max_width = self.max_tile_width.unwrap_or(f32::INFINITY);
+        let max_height = self.max_tile_height.unwrap_or(f32::INFINITY);
+        match self.fill_strategy {
+            TilesFillStrategy::Fill => {
+                let available = self.available_size(rows_cols, layout_size);
+                Size {
+                    width: f32::min(available.width / rows_cols.columns as f32, max_width),
+                    height: f32::min(available.height / rows_cols.rows as f32, max_height),
+                }
+            }
+            TilesFillStrategy::Fit | TilesFillStrategy::FixedColumns(_) => {
+                let size = self.fitted_tile_size(rows_cols, layout_size);
+                let scale = f32::min(
+                    1.0,
+                    f32::min(max_width / size.width, max_height / size.height),
+                );
+                Size {
+                    width: size.width * scale,
+                    height: size.height * scale,
+                }
+            }
+        }
+    }
 
-        let x_scale = (layout_size.width - x_padding - x_margin).max(0.0)
-            / rows_cols.columns as f32
-            / self.tile_aspect_ratio.0 as f32;
-        let y_scale = (layout_size.height - y_padding - y_margin).max(0.0)
-            / rows_cols.rows as f32
-            / self.tile_aspect_ratio.1 as f32;
+    /// The largest tile size that preserves `tile_aspect_ratio`.
+    fn fitted_tile_size(&self, rows_cols: RowsCols, layout_size: Size) -> Size {
+        let available = self.available_size(rows_cols, layout_size);
+        let x_scale = available.width / rows_cols.columns as f32 / self.tile_aspect_ratio.0 as f32;
+        let y_scale = available.height / rows_cols.rows as f32 / self.tile_aspect_ratio.1 as f32;
 
         let scale = if x_scale < y_scale { x_scale } else { y_scale };
 
@@ -98,6 +120,19 @@ impl TilesComponentParams {
         }
     }
 
+    /// Space that can be covered by tiles after subtracting margins and paddings.
+    fn available_size(&self, rows_cols: RowsCols, layout_size: Size) -> Size {
+        let x_padding = rows_cols.columns as f32 * 2.0 * self.padding;
+        let y_padding = rows_cols.rows as f32 * 2.0 * self.padding;
+        let x_margin = (rows_cols.columns as f32 + 1.0) * self.margin;
+        let y_margin = (rows_cols.rows as f32 + 1.0) * self.margin;
+
+        Size {
+            width: (layout_size.width - x_padding - x_margin).max(0.0),
+            height: (layout_size.height - y_padding - y_margin).max(0.0),
+        }
+    }
+
     fn tiles_positions(
         &self,
         inputs_count: u32,
@@ -164,3 +199,172 @@ impl TilesComponentParams {
         layouts
     }
 }
+
+/// Tiles are identified by the component id of a child. Children without an id
+/// are identified by their index among the other children without an id.
+pub(super) fn tile_ids(children: &[StatefulComponent]) -> Vec<TileId> {
+    let mut index = 0;
+    children
+        .iter()
+        .map(|child| match child.component_id() {
+            Some(id) => TileId::ComponentId(id.clone()),
+            None => {
+                let id = TileId::Index(index);
+                index += 1;
+                id
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scene::RGBAColor;
+
+    use super::*;
+
+    fn params(fill_strategy: TilesFillStrategy) -> TilesComponentParams {
+        TilesComponentParams {
+            id: None,
+            width: None,
+            height: None,
+            background_color: RGBAColor(0, 0, 0, 0),
+            fill_strategy,
+            tile_aspect_ratio: (16, 9),
+            max_tile_width: None,
+            max_tile_height: None,
+            margin: 0.0,
+            padding: 0.0,
+            horizontal_align: HorizontalAlign::Center,
+            vertical_align: VerticalAlign::Center,
+        }
+    }
+
+    fn size(width: f32, height: f32) -> Size {
+        Size { width, height }
+    }
+
+    #[test]
+    fn available_size() {
+        let params = TilesComponentParams {
+            margin: 10.0,
+            padding: 5.0,
+            ..params(TilesFillStrategy::Fit)
+        };
+        let rows_cols = RowsCols {
+            rows: 2,
+            columns: 3,
+        };
+        // 3 columns: 6 paddings and 4 margins, 2 rows: 4 paddings and 3 margins
+        assert_eq!(
+            params.available_size(rows_cols, size(1000.0, 500.0)),
+            size(930.0, 450.0)
+        );
+        assert_eq!(
+            params.available_size(rows_cols, size(50.0, 50.0)),
+            size(0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn fitted_tile_size() {
+        let params = params(TilesFillStrategy::Fit);
+        let rows_cols = RowsCols {
+            rows: 2,
+            columns: 2,
+        };
+        // Width limits the size of the tile.
+        assert_eq!(
+            params.fitted_tile_size(rows_cols, size(400.0, 400.0)),
+            size(200.0, 112.5)
+        );
+        // Height limits the size of the tile.
+        assert_eq!(
+            params.fitted_tile_size(rows_cols, size(1000.0, 360.0)),
+            size(320.0, 180.0)
+        );
+    }
+
+    #[test]
+    fn fill_tile_size() {
+        let params = TilesComponentParams {
+            margin: 10.0,
+            padding: 5.0,
+            ..params(TilesFillStrategy::Fill)
+        };
+        let rows_cols = RowsCols {
+            rows: 2,
+            columns: 2,
+        };
+        // Tiles cover the whole available space and ignore the aspect ratio.
+        assert_eq!(
+            params.tile_size(rows_cols, size(1000.0, 500.0)),
+            size(475.0, 225.0)
+        );
+
+        let params = TilesComponentParams {
+            max_tile_width: Some(300.0),
+            ..params
+        };
+        assert_eq!(
+            params.tile_size(rows_cols, size(1000.0, 500.0)),
+            size(300.0, 225.0)
+        );
+    }
+
+    #[test]
+    fn max_tile_size_preserves_aspect_ratio() {
+        let rows_cols = RowsCols {
+            rows: 1,
+            columns: 1,
+        };
+        let params = TilesComponentParams {
+            max_tile_width: Some(640.0),
+            ..params(TilesFillStrategy::Fit)
+        };
+        assert_eq!(
+            params.tile_size(rows_cols, size(1920.0, 1080.0)),
+            size(640.0, 360.0)
+        );
+
+        let params = TilesComponentParams {
+            max_tile_height: Some(180.0),
+            ..params
+        };
+        assert_eq!(
+            params.tile_size(rows_cols, size(1920.0, 1080.0)),
+            size(320.0, 180.0)
+        );
+
+        // Limit larger than the fitted size has no effect.
+        let params = TilesComponentParams {
+            max_tile_width: Some(4000.0),
+            max_tile_height: None,
+            ..params
+        };
+        assert_eq!(
+            params.tile_size(rows_cols, size(1920.0, 1080.0)),
+            size(1920.0, 1080.0)
+        );
+    }
+
+    #[test]
+    fn fixed_columns_with_partial_last_row() {
+        let params = params(TilesFillStrategy::FixedColumns(2));
+        let layout_size = size(400.0, 400.0);
+        let rows_cols = RowsCols {
+            rows: 3_u32.div_ceil(2),
+            columns: 2,
+        };
+        let tile_size = params.tile_size(rows_cols, layout_size);
+        assert_eq!(tile_size, size(200.0, 112.5));
+
+        let positions: Vec<(f32, f32)> = params
+            .tiles_positions(3, rows_cols, tile_size, layout_size)
+            .into_iter()
+            .map(|position| (position.top, position.left))
+            .collect();
+        // The last row has a single tile centered horizontally.
+        assert_eq!(positions, vec![(87.5, 0.0), (87.5, 200.0), (200.0, 100.0)]);
+    }
+}
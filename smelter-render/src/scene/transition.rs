@@ -100,13 +100,40 @@ impl TransitionState {
         InterpolationState((state.0 - self.initial_offset.1.0) / (1.0 - self.initial_offset.1.0))
     }
 
+    /// State of a transition that shares the timeline of this one (the same start and
+    /// the same `initial_offset`), but ends after `fraction` of it. For `fraction` equal
+    /// to 1 the result is the same as [`TransitionState::state`].
+    pub fn state_with_fraction(&self, pts: Duration, fraction: f64) -> InterpolationState {
+        if fraction <= 0.0 {
+            return InterpolationState(1.0);
+        }
+        let progress =
+            (pts.as_secs_f64() - self.start_pts.as_secs_f64()) / self.duration.as_secs_f64();
+        // Progress of the full timeline in range [initial_offset.0, 1].
+        let progress = self.initial_offset.0.0 + progress * (1.0 - self.initial_offset.0.0);
+        let progress = f64::clamp(progress / fraction, 0.0, 1.0);
+        let state = self.interpolation_kind.state(progress);
+        // State at the start of this transition, shorter transition might have already
+        // finished before it.
+        let offset_state = match fraction >= 1.0 {
+            true => self.initial_offset.1,
+            false => self
+                .interpolation_kind
+                .state(f64::min(self.initial_offset.0.0 / fraction, 1.0)),
+        };
+        if offset_state.0 >= 1.0 {
+            return InterpolationState(1.0);
+        }
+        InterpolationState((state.0 - offset_state.0) / (1.0 - offset_state.0))
+    }
+
     fn is_finished(&self, current_pts: Duration) -> bool {
         self.start_pts + self.duration <= current_pts
     }
 }
 
 impl InterpolationKind {
-    fn state(&self, t: f64) -> InterpolationState {
+    pub(super) fn state(&self, t: f64) -> InterpolationState {
         match self {
             InterpolationKind::Linear => InterpolationState(t),
             InterpolationKind::Bounce => InterpolationState(bounce_easing(t)),
@@ -116,3 +143,88 @@ impl InterpolationKind {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn linear(duration_ms: u64) -> TransitionOptions {
+        TransitionOptions {
+            duration: Duration::from_millis(duration_ms),
+            interpolation_kind: InterpolationKind::Linear,
+        }
+    }
+
+    fn assert_state(state: InterpolationState, expected: f64) {
+        assert!(
+            (state.0 - expected).abs() < 1e-9,
+            "expected {expected}, got {}",
+            state.0
+        );
+    }
+
+    #[test]
+    fn state_with_fraction() {
+        let transition =
+            TransitionState::new(Some(linear(1000)), None, true, false, Duration::ZERO).unwrap();
+        assert_state(
+            transition.state_with_fraction(Duration::from_millis(250), 0.5),
+            0.5,
+        );
+        assert_state(
+            transition.state_with_fraction(Duration::from_millis(750), 0.5),
+            1.0,
+        );
+        assert_state(
+            transition.state_with_fraction(Duration::from_millis(750), 1.0),
+            0.75,
+        );
+        assert_state(
+            transition.state_with_fraction(Duration::from_millis(750), 0.0),
+            1.0,
+        );
+    }
+
+    #[test]
+    fn state_with_fraction_after_continuation() {
+        let previous =
+            TransitionState::new(Some(linear(1000)), None, true, false, Duration::ZERO).unwrap();
+        // Update 200ms after the start that does not interrupt the transition.
+        let transition = TransitionState::new(
+            Some(linear(1000)),
+            Some(previous),
+            true,
+            false,
+            Duration::from_millis(200),
+        )
+        .unwrap();
+
+        // The longest transition continues from where it was.
+        assert_state(transition.state(Duration::from_millis(600)), 0.5);
+        assert_state(
+            transition.state_with_fraction(Duration::from_millis(600), 1.0),
+            0.5,
+        );
+
+        // Transition with half of the duration is 40% done at the update, so it ends
+        // together with the original timeline at 500ms, not 500ms after the update.
+        assert_state(
+            transition.state_with_fraction(Duration::from_millis(200), 0.5),
+            0.0,
+        );
+        assert_state(
+            transition.state_with_fraction(Duration::from_millis(350), 0.5),
+            0.5,
+        );
+        assert_state(
+            transition.state_with_fraction(Duration::from_millis(500), 0.5),
+            1.0,
+        );
+
+        // Transition that finished before the update stays finished.
+        assert_state(
+            transition.state_with_fraction(Duration::from_millis(200), 0.1),
+            1.0,
+        );
+    }
+}
@@ -60,6 +60,10 @@ pub struct AbsolutePosition {
     pub position_horizontal: HorizontalPosition,
     pub position_vertical: VerticalPosition,
     pub rotation_degrees: f32,
+    pub rotation_x_degrees: f32,
+    pub rotation_y_degrees: f32,
+    /// Distance in pixels between the viewer and the component.
+    pub perspective: Option<f32>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -115,6 +119,28 @@ impl BorderRadius {
             bottom_left: f32::clamp(self.bottom_left, 0.0, max_radius),
         }
     }
+
+    /// Swaps corners as if the rounded rectangle was mirrored.
+    pub fn flip(self, horizontal: bool, vertical: bool) -> Self {
+        let radius = match horizontal {
+            true => Self {
+                top_left: self.top_right,
+                top_right: self.top_left,
+                bottom_right: self.bottom_left,
+                bottom_left: self.bottom_right,
+            },
+            false => self,
+        };
+        match vertical {
+            true => Self {
+                top_left: radius.bottom_left,
+                top_right: radius.bottom_right,
+                bottom_right: radius.top_right,
+                bottom_left: radius.top_left,
+            },
+            false => radius,
+        }
+    }
 }
 
 impl Mul<f32> for BorderRadius {
@@ -164,5 +190,62 @@ pub struct BoxShadow {
     pub offset_x: f32,
     pub offset_y: f32,
     pub blur_radius: f32,
+    /// Expands (or shrinks if negative) the shadow in all directions before it is blurred.
+    pub spread: f32,
     pub color: RGBAColor,
 }
+
+/// Defines how colors of a layout are combined with the content rendered below it. Formulas
+/// follow the W3C Compositing and Blending specification.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Add,
+}
+
+/// Removes pixels close to the key color from a texture, e.g. to composite a green screen
+/// input over other content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChromaKey {
+    pub key_color: RGBColor,
+    /// Chroma distance from the key color in the range [0, 1] below which pixels are fully
+    /// transparent.
+    pub similarity: f32,
+    /// Width of the chroma distance range over which the transparency fades out.
+    pub smoothness: f32,
+    /// Chroma distance range over which pixels close to the key color are desaturated to
+    /// hide the color bleeding onto the foreground. 0 disables spill reduction.
+    pub spill_reduction: f32,
+}
+
+/// Color adjustments applied to a texture, e.g. to match cameras with different
+/// exposure or white balance. Values are applied to gamma-encoded (sRGB) colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorCorrection {
+    /// Value added to all color channels. 0 leaves the color unchanged.
+    pub brightness: f32,
+    /// Multiplier of the distance from the middle gray. 1 leaves the color unchanged.
+    pub contrast: f32,
+    /// Multiplier of the distance from the gray color of the same luma. 1 leaves the color
+    /// unchanged and 0 produces a grayscale image.
+    pub saturation: f32,
+    /// Rotation of the hue in degrees.
+    pub hue_shift_degrees: f32,
+}
+
+impl ColorCorrection {
+    /// Correction equivalent to applying `self` and then `next`. All adjustments are linear
+    /// (before clamping), so they can be combined into a single pass.
+    pub fn then(self, next: ColorCorrection) -> ColorCorrection {
+        ColorCorrection {
+            brightness: self.brightness * next.contrast + next.brightness,
+            contrast: self.contrast * next.contrast,
+            saturation: self.saturation * next.saturation,
+            hue_shift_degrees: self.hue_shift_degrees + next.hue_shift_degrees,
+        }
+    }
+}
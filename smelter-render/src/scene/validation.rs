@@ -15,6 +15,7 @@ impl Component {
             Component::View(view) => view.id.as_ref(),
             Component::Tiles(tiles) => tiles.id.as_ref(),
             Component::Rescaler(rescaler) => rescaler.id.as_ref(),
+            Component::AudioVisualizer(visualizer) => visualizer.id.as_ref(),
         }
     }
 
@@ -28,6 +29,7 @@ impl Component {
             Component::View(view) => view.children.iter().collect(),
             Component::Tiles(tiles) => tiles.children.iter().collect(),
             Component::Rescaler(rescaler) => vec![rescaler.child.as_ref()],
+            Component::AudioVisualizer(_visualizer) => vec![],
         }
     }
 }
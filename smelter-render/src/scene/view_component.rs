@@ -1,11 +1,16 @@
-use std::{ops::Deref, time::Duration};
+use std::{ops::Deref, sync::Arc, time::Duration};
 
-use crate::{scene::ViewChildrenDirection, transformations::layout::NestedLayout};
+use crate::{
+    scene::ViewChildrenDirection,
+    transformations::{layout::NestedLayout, lut::Lut},
+};
 
 use super::{
-    BorderRadius, BoxShadow, Component, ComponentId, IntermediateNode, Overflow, Padding, Position,
-    RGBAColor, SceneError, Size, StatefulComponent,
+    BackgroundGradient, BlendMode, BorderRadius, BoxShadow, ColorCorrection, Component,
+    ComponentId, IntermediateNode, Overflow, Padding, Position, RGBAColor, SceneError, Size,
+    StatefulComponent,
     components::ViewComponent,
+    keyframes::KeyframesState,
     layout::StatefulLayoutComponent,
     scene_state::BuildStateTreeCtx,
     transition::{TransitionOptions, TransitionState},
@@ -20,7 +25,9 @@ pub(super) struct StatefulViewComponent {
     start: Option<ViewComponentParam>,
     end: ViewComponentParam,
     transition: Option<TransitionState>,
+    keyframes: Option<KeyframesState>,
     children: Vec<StatefulComponent>,
+    lut: Option<Arc<Lut>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -32,6 +39,7 @@ struct ViewComponentParam {
     overflow: Overflow,
 
     background_color: RGBAColor,
+    background_gradient: Option<BackgroundGradient>,
     border_radius: BorderRadius,
     border_width: f32,
     border_color: RGBAColor,
@@ -39,15 +47,30 @@ struct ViewComponentParam {
     box_shadow: Vec<BoxShadow>,
 
     padding: Padding,
+
+    blur_radius: f32,
+
+    color_correction: Option<ColorCorrection>,
+
+    opacity: f32,
+    blend_mode: BlendMode,
 }
 
 impl StatefulViewComponent {
     fn view(&self, pts: Duration) -> ViewComponentParam {
-        let (Some(transition), Some(start)) = (&self.transition, &self.start) else {
-            return self.end.clone();
+        let mut view = match (&self.transition, &self.start) {
+            (Some(transition), Some(start)) => {
+                let interpolation_progress = transition.state(pts);
+                ContinuousValue::interpolate(start, &self.end, interpolation_progress)
+            }
+            _ => self.end.clone(),
         };
-        let interpolation_progress = transition.state(pts);
-        ContinuousValue::interpolate(start, &self.end, interpolation_progress)
+        if let Some(keyframes) = &self.keyframes {
+            for (property, value) in keyframes.values(pts) {
+                view.apply_keyframe(property, value);
+            }
+        }
+        view
     }
 
     pub(super) fn children(&self) -> Vec<&StatefulComponent> {
@@ -91,7 +114,11 @@ impl StatefulViewComponent {
 
     pub(super) fn layout(&mut self, size: Size, pts: Duration) -> NestedLayout {
         let view = self.view(pts);
-        view.layout(size, &mut self.children, pts)
+        let mut layout = view.layout(size, &mut self.children, pts);
+        if let Some(lut) = &self.lut {
+            layout.apply_lut(lut);
+        }
+        layout
     }
 }
 
@@ -120,12 +147,17 @@ impl ViewComponent {
             direction: self.direction,
             position: self.position,
             background_color: self.background_color,
+            background_gradient: self.background_gradient,
             overflow: self.overflow,
             border_radius: self.border_radius,
             border_width: self.border_width,
             border_color: self.border_color,
             box_shadow: self.box_shadow,
             padding: self.padding,
+            blur_radius: self.blur_radius,
+            color_correction: self.color_correction,
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
         };
 
         let props_changed = previous_state
@@ -143,15 +175,32 @@ impl ViewComponent {
             interrupt_previous_transition,
             ctx.last_render_pts,
         );
+        let keyframes = KeyframesState::new(
+            self.keyframes,
+            previous_state.and_then(|s| s.keyframes.as_ref()),
+            ctx.last_render_pts,
+        );
+        let lut = self
+            .lut_id
+            .as_ref()
+            .map(|lut_id| {
+                ctx.renderers
+                    .luts
+                    .get(lut_id)
+                    .ok_or_else(|| SceneError::LutNotFound(lut_id.clone()))
+            })
+            .transpose()?;
         let view = StatefulViewComponent {
             start,
             end,
             transition,
+            keyframes,
             children: self
                 .children
                 .into_iter()
                 .map(|c| Component::stateful_component(c, ctx))
                 .collect::<Result<_, _>>()?,
+            lut,
         };
         Ok(StatefulComponent::Layout(
             StatefulLayoutComponent::View(view).into(),
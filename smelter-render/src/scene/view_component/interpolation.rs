@@ -1,4 +1,7 @@
-use crate::scene::types::interpolation::{ContinuousValue, InterpolationState};
+use crate::scene::{
+    AnimatedProperty,
+    types::interpolation::{ContinuousValue, InterpolationState},
+};
 
 use super::ViewComponentParam;
 
@@ -9,6 +12,7 @@ impl ContinuousValue for ViewComponentParam {
             direction: end.direction.clone(),
             position: ContinuousValue::interpolate(&start.position, &end.position, state),
             background_color: end.background_color,
+            background_gradient: end.background_gradient.clone(),
             overflow: end.overflow,
             border_radius: ContinuousValue::interpolate(
                 &start.border_radius,
@@ -23,6 +27,20 @@ impl ContinuousValue for ViewComponentParam {
             border_color: end.border_color,
             box_shadow: ContinuousValue::interpolate(&start.box_shadow, &end.box_shadow, state),
             padding: ContinuousValue::interpolate(&start.padding, &end.padding, state),
+            blur_radius: ContinuousValue::interpolate(&start.blur_radius, &end.blur_radius, state),
+            color_correction: end.color_correction,
+            opacity: ContinuousValue::interpolate(&start.opacity, &end.opacity, state),
+            blend_mode: end.blend_mode,
+        }
+    }
+}
+
+impl ViewComponentParam {
+    pub(super) fn apply_keyframe(&mut self, property: AnimatedProperty, value: f32) {
+        match property {
+            AnimatedProperty::Opacity => self.opacity = value.clamp(0.0, 1.0),
+            AnimatedProperty::BlurRadius => self.blur_radius = value.max(0.0),
+            property => self.position.apply_keyframe(property, value),
         }
     }
 }
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use crate::{
     scene::{
-        BorderRadius, Overflow, Position, RGBAColor, Size, StatefulComponent,
+        BlendMode, BorderRadius, Overflow, Position, RGBAColor, Size, StatefulComponent,
         ViewChildrenDirection, layout::StatefulLayoutComponent,
     },
     transformations::layout::{LayoutContent, Mask, NestedLayout},
@@ -106,24 +106,37 @@ impl ViewComponentParam {
                 }
             })
             .collect();
-        NestedLayout {
+        let mut layout = NestedLayout {
             top: 0.0,
             left: 0.0,
             width: size.width,
             height: size.height,
             rotation_degrees: 0.0,
+            rotation_x_degrees: 0.0,
+            rotation_y_degrees: 0.0,
+            perspective: 0.0,
             scale_x: scale,
             scale_y: scale,
             crop,
             mask,
-            content: LayoutContent::Color(self.background_color),
+            content: match &self.background_gradient {
+                Some(gradient) => LayoutContent::Gradient(gradient.clone()),
+                None => LayoutContent::Color(self.background_color),
+            },
             child_nodes_count: children.iter().map(|l| l.child_nodes_count).sum(),
             children,
             border_width: self.border_width,
             border_color: self.border_color,
             border_radius,
             box_shadow: self.box_shadow.clone(),
+            opacity: self.opacity,
+            blur_radius: self.blur_radius,
+            blend_mode: self.blend_mode,
+        };
+        if let Some(color_correction) = self.color_correction {
+            layout.apply_color_correction(color_correction);
         }
+        layout
     }
 
     fn layout_static_child(
@@ -165,6 +178,9 @@ impl ViewComponentParam {
                     width,
                     height,
                     rotation_degrees: 0.0,
+                    rotation_x_degrees: 0.0,
+                    rotation_y_degrees: 0.0,
+                    perspective: 0.0,
                     scale_x: 1.0,
                     scale_y: 1.0,
                     crop: None,
@@ -176,6 +192,9 @@ impl ViewComponentParam {
                     border_color: RGBAColor(0, 0, 0, 0),
                     border_radius: BorderRadius::ZERO,
                     box_shadow: vec![],
+                    opacity: 1.0,
+                    blur_radius: 0.0,
+                    blend_mode: BlendMode::Normal,
                 }
             }
             _ => NestedLayout {
@@ -184,17 +203,23 @@ impl ViewComponentParam {
                 width,
                 height,
                 rotation_degrees: 0.0,
+                rotation_x_degrees: 0.0,
+                rotation_y_degrees: 0.0,
+                perspective: 0.0,
                 scale_x: 1.0,
                 scale_y: 1.0,
                 crop: None,
                 mask: None,
-                content: StatefulLayoutComponent::layout_content(child, 0),
+                content: StatefulLayoutComponent::layout_content(child, 0, pts),
                 child_nodes_count: 1,
                 children: vec![],
                 border_width: 0.0,
                 border_color: RGBAColor(0, 0, 0, 0),
                 border_radius: BorderRadius::ZERO,
                 box_shadow: vec![],
+                opacity: 1.0,
+                blur_radius: 0.0,
+                blend_mode: BlendMode::Normal,
             },
         };
         (layout, static_offset)
@@ -2,7 +2,7 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use glyphon::fontdb;
-use tracing::trace;
+use tracing::{error, trace};
 
 use crate::{
     FrameSet, InputId, OutputFrameFormat, OutputId, RegistryType, RendererId, RenderingMode,
@@ -11,11 +11,13 @@ use crate::{
         InitRendererEngineError, RegisterRendererError, RenderSceneError, UnregisterRendererError,
         UpdateSceneError,
     },
-    image,
+    image, lut,
     scene::{Component, OutputScene, SceneState},
     shader,
     transformations::{
+        audio_visualizer::AudioSamplesBuffer,
         image::Image,
+        lut::Lut,
         shader::Shader,
         text_renderer::TextRendererCtx,
         web_renderer::{self, ChromiumContext, WebRenderer},
@@ -66,6 +68,8 @@ struct InnerRenderer {
 
     stream_fallback_timeout: Duration,
 
+    audio_samples: AudioSamplesBuffer,
+
     wgpu_ctx: Arc<WgpuCtx>,
 }
 
@@ -74,6 +78,7 @@ pub(crate) struct RenderCtx<'a> {
     pub(crate) text_renderer_ctx: &'a TextRendererCtx,
     pub(crate) renderers: &'a Renderers,
     pub(crate) stream_fallback_timeout: Duration,
+    pub(crate) audio_samples: &'a AudioSamplesBuffer,
 }
 
 pub(crate) struct RegisterCtx {
@@ -90,6 +95,7 @@ pub enum RendererSpec {
     Shader(shader::ShaderSpec),
     WebRenderer(web_renderer::WebRendererSpec),
     Image(image::ImageSpec),
+    Lut(lut::LutSpec),
 }
 
 impl Renderer {
@@ -148,6 +154,13 @@ impl Renderer {
                 let mut guard = self.0.lock().unwrap();
                 Ok(guard.renderers.images.register(id, asset)?)
             }
+            RendererSpec::Lut(spec) => {
+                let lut = Lut::new(&ctx.wgpu_ctx, spec)
+                    .map_err(|err| RegisterRendererError::Lut(err, id.clone()))?;
+
+                let mut guard = self.0.lock().unwrap();
+                Ok(guard.renderers.luts.register(id, Arc::new(lut))?)
+            }
         }
     }
 
@@ -161,6 +174,7 @@ impl Renderer {
             RegistryType::Shader => guard.renderers.shaders.unregister(renderer_id)?,
             RegistryType::WebRenderer => guard.renderers.web_renderers.unregister(renderer_id)?,
             RegistryType::Image => guard.renderers.images.unregister(renderer_id)?,
+            RegistryType::Lut => guard.renderers.luts.unregister(renderer_id)?,
         }
         Ok(())
     }
@@ -190,6 +204,12 @@ impl Renderer {
     pub fn wgpu_ctx(&self) -> Arc<WgpuCtx> {
         self.0.lock().unwrap().wgpu_ctx.clone()
     }
+
+    /// Buffer that audio samples of inputs should be written to, so they can be
+    /// displayed by `AudioVisualizer` components.
+    pub fn audio_samples(&self) -> AudioSamplesBuffer {
+        self.0.lock().unwrap().audio_samples.clone()
+    }
 }
 
 impl InnerRenderer {
@@ -205,6 +225,7 @@ impl InnerRenderer {
             render_graph: RenderGraph::empty(),
             renderers: Renderers::new(wgpu_ctx, opts.max_layouts_count)?,
             stream_fallback_timeout: opts.stream_fallback_timeout,
+            audio_samples: AudioSamplesBuffer::default(),
             scene: SceneState::new(),
             chromium_context: opts.chromium_context,
         })
@@ -226,6 +247,7 @@ impl InnerRenderer {
             text_renderer_ctx: &self.text_renderer_ctx,
             renderers: &self.renderers,
             stream_fallback_timeout: self.stream_fallback_timeout,
+            audio_samples: &self.audio_samples,
         };
 
         let scope = WgpuErrorScope::push(&ctx.wgpu_ctx.device);
@@ -237,6 +259,7 @@ impl InnerRenderer {
             .collect();
         self.scene
             .register_render_event(inputs.pts, input_resolutions);
+        drop_finished_removed_children(ctx, &mut self.scene, &mut self.render_graph);
 
         let pts = inputs.pts;
         trace!("Upload input textures");
@@ -272,6 +295,7 @@ impl InnerRenderer {
                 text_renderer_ctx: &self.text_renderer_ctx,
                 renderers: &self.renderers,
                 stream_fallback_timeout: self.stream_fallback_timeout,
+                audio_samples: &self.audio_samples,
             },
             output_node,
             output_format,
@@ -279,3 +303,23 @@ impl InnerRenderer {
         Ok(())
     }
 }
+
+/// Removes components of tiles that finished their exit transition from the render graph.
+fn drop_finished_removed_children(
+    ctx: &RenderCtx,
+    scene: &mut SceneState,
+    render_graph: &mut RenderGraph,
+) {
+    let output_nodes = match scene.drop_finished_removed_children() {
+        Ok(output_nodes) => output_nodes,
+        Err(err) => {
+            error!("Failed to remove children after exit transition: {err}");
+            return;
+        }
+    };
+    for output_node in output_nodes {
+        if let Err(err) = render_graph.update_nodes(ctx, output_node) {
+            error!("Failed to remove children after exit transition: {err}");
+        }
+    }
+}
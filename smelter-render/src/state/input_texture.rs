@@ -7,6 +7,7 @@ use crate::{
     Frame, FrameData, Resolution,
     state::input_texture::{
         argb::ArgbInput, bgra::BgraInput, interleaved_yuyv422::InterleavedYuyv422Input,
+        v210::V210Input,
     },
     wgpu::{WgpuCtx, texture::PlanarYuvVariant},
 };
@@ -24,6 +25,7 @@ mod interleaved_yuyv422;
 mod nv12_texture;
 mod planar_yuv;
 mod rgba_texture;
+mod v210;
 
 mod convert_linear_to_srgb;
 
@@ -34,6 +36,7 @@ enum InputTextureState {
     Nv12(NV12Input),
     Bgra(BgraInput),
     Argb(ArgbInput),
+    V210(V210Input),
     /// Depending on rendering mode
     /// - GPU - Rgba8UnormSrgb
     /// - CPU optimized - Rgba8Unorm (but data is in sRGB color space)
@@ -51,6 +54,7 @@ impl InputTextureState {
             InputTextureState::Nv12(input) => input.resolution(),
             InputTextureState::Bgra(input) => input.resolution(),
             InputTextureState::Argb(input) => input.resolution(),
+            InputTextureState::V210(input) => input.resolution(),
         }
     }
 }
@@ -197,6 +201,16 @@ impl InputTexture {
                     }
                 };
             }
+            FrameData::InterleavedV210(data) => match &mut self.0 {
+                Some(InputTextureState::V210(input)) => {
+                    input.upload(ctx, &data, frame.resolution);
+                }
+                state => {
+                    let mut input = V210Input::new(ctx);
+                    input.upload(ctx, &data, frame.resolution);
+                    *state = Some(InputTextureState::V210(input));
+                }
+            },
         }
     }
 
@@ -212,6 +226,7 @@ impl InputTexture {
                     InputTextureState::Nv12(state) => state.convert(ctx, dst_state),
                     InputTextureState::Bgra(state) => state.convert(ctx, dst_state),
                     InputTextureState::Argb(state) => state.convert(ctx, dst_state),
+                    InputTextureState::V210(state) => state.convert(ctx, dst_state),
                 }
             }
             None => dest.clear(),
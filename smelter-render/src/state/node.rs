@@ -4,7 +4,9 @@ use std::time::Duration;
 use std::vec;
 
 use crate::InputId;
+use crate::Resolution;
 use crate::scene::{self, ComponentId, ShaderComponentParams, image_component::ImageRenderParams};
+use crate::transformations::audio_visualizer::{AudioVisualizerNode, AudioVisualizerRenderParams};
 use crate::transformations::layout::LayoutNode;
 use crate::transformations::shader::Shader;
 use crate::transformations::shader::node::ShaderNode;
@@ -24,6 +26,7 @@ pub(super) enum InnerRenderNode {
     Web(WebRendererNode),
     Text(TextRendererNode),
     Image(ImageNode),
+    AudioVisualizer(AudioVisualizerNode),
     Layout(LayoutNode),
     InputStreamRef(InputId),
 }
@@ -42,9 +45,10 @@ impl InnerRenderNode {
             }
             InnerRenderNode::Web(renderer) => renderer.render(ctx, sources, target),
             InnerRenderNode::Text(renderer) => {
-                renderer.render(ctx, target);
+                renderer.render(ctx, target, pts);
             }
             InnerRenderNode::Image(node) => node.render(ctx, target, pts),
+            InnerRenderNode::AudioVisualizer(node) => node.render(ctx, target, pts),
             InnerRenderNode::InputStreamRef(_) => {
                 // Nothing to do, textures on input nodes should be populated
                 // at the start of render loop
@@ -52,6 +56,20 @@ impl InnerRenderNode {
             InnerRenderNode::Layout(node) => node.render(ctx, sources, target, pts),
         }
     }
+
+    /// Resolution at which a child node was displayed in the last rendered frame.
+    pub fn child_display_resolution(&self, index: usize) -> Option<Resolution> {
+        match self {
+            InnerRenderNode::Layout(node) => node.child_display_resolution(index),
+            _ => None,
+        }
+    }
+
+    pub fn set_display_resolution(&mut self, display_resolution: Option<Resolution>) {
+        if let InnerRenderNode::Image(node) = self {
+            node.set_display_resolution(display_resolution)
+        }
+    }
 }
 
 pub(super) struct RenderNode {
@@ -80,6 +98,9 @@ impl RenderNode {
             }
             scene::NodeParams::Image(image_params) => Self::new_image_node(ctx, image_params),
             scene::NodeParams::Text(text_params) => Self::new_text_node(ctx, text_params),
+            scene::NodeParams::AudioVisualizer(params) => {
+                Self::new_audio_visualizer_node(ctx, params)
+            }
             scene::NodeParams::Layout(layout_provider) => {
                 Self::new_layout_node(ctx, children, layout_provider)
             }
@@ -164,6 +185,20 @@ impl RenderNode {
         }
     }
 
+    pub(super) fn new_audio_visualizer_node(
+        ctx: &RenderCtx,
+        params: AudioVisualizerRenderParams,
+    ) -> Self {
+        let node = InnerRenderNode::AudioVisualizer(AudioVisualizerNode::new(ctx, params));
+        let output = NodeTexture::new();
+
+        Self {
+            renderer: node,
+            output,
+            children: vec![],
+        }
+    }
+
     pub(super) fn new_layout_node(
         ctx: &RenderCtx,
         children: Vec<RenderNode>,
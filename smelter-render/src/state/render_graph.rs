@@ -62,6 +62,24 @@ impl RenderGraph {
         Ok(())
     }
 
+    /// Replaces nodes of an already registered output, keeping its output texture.
+    pub(super) fn update_nodes(
+        &mut self,
+        ctx: &RenderCtx,
+        output: OutputNode,
+    ) -> Result<(), UpdateSceneError> {
+        let Some(output_tree) = self.outputs.get_mut(&output.output_id) else {
+            return Err(UpdateSceneError::OutputNotRegistered(output.output_id));
+        };
+
+        let scope = WgpuErrorScope::push(&ctx.wgpu_ctx.device);
+        let root = Self::create_node(ctx, output.node)?;
+        scope.pop()?;
+
+        output_tree.root = root;
+        Ok(())
+    }
+
     fn create_node(ctx: &RenderCtx, node: scene::Node) -> Result<RenderNode, UpdateSceneError> {
         let children: Vec<RenderNode> = node
             .children
@@ -252,4 +252,12 @@ pub(super) fn render_node(
         .collect();
     node.renderer
         .render(ctx, &input_textures, &mut node.output, pts);
+
+    // Applied in the next frame, children are already rendered at this point.
+    for (index, child_node) in node.children.iter_mut().enumerate() {
+        let display_resolution = node.renderer.child_display_resolution(index);
+        child_node
+            .renderer
+            .set_display_resolution(display_resolution);
+    }
 }
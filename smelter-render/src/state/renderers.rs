@@ -4,7 +4,8 @@ use crate::{
     error::InitRendererEngineError,
     registry::{RegistryType, RendererRegistry},
     transformations::{
-        image::Image, layout::LayoutRenderer, shader::Shader, web_renderer::WebRenderer,
+        audio_visualizer::AudioVisualizerShader, image::Image, layout::LayoutRenderer, lut::Lut,
+        shader::Shader, text_renderer::TextGradientShader, web_renderer::WebRenderer,
     },
 };
 
@@ -14,7 +15,10 @@ pub(crate) struct Renderers {
     pub(crate) shaders: RendererRegistry<Arc<Shader>>,
     pub(crate) web_renderers: RendererRegistry<Arc<WebRenderer>>,
     pub(crate) images: RendererRegistry<Image>,
+    pub(crate) luts: RendererRegistry<Arc<Lut>>,
     pub(crate) layout: LayoutRenderer,
+    pub(crate) text_gradient: TextGradientShader,
+    pub(crate) audio_visualizer: AudioVisualizerShader,
 }
 
 impl Renderers {
@@ -26,8 +30,13 @@ impl Renderers {
             shaders: RendererRegistry::new(RegistryType::Shader),
             web_renderers: RendererRegistry::new(RegistryType::WebRenderer),
             images: RendererRegistry::new(RegistryType::Image),
+            luts: RendererRegistry::new(RegistryType::Lut),
             layout: LayoutRenderer::new(&wgpu_ctx, max_layouts_count)
                 .map_err(InitRendererEngineError::LayoutTransformationsInitError)?,
+            text_gradient: TextGradientShader::new(&wgpu_ctx)
+                .map_err(InitRendererEngineError::TextTransformationsInitError)?,
+            audio_visualizer: AudioVisualizerShader::new(&wgpu_ctx)
+                .map_err(InitRendererEngineError::AudioVisualizerTransformationsInitError)?,
         })
     }
 }
@@ -0,0 +1,310 @@
+use std::{f32::consts::PI, time::Duration};
+
+use wgpu::util::DeviceExt;
+
+use crate::{
+    InputId, Resolution,
+    scene::{AudioVisualizerMode, RGBAColor},
+    state::{RenderCtx, node_texture::NodeTexture},
+    wgpu::{WgpuCtx, utils::convert_to_shader_color},
+};
+
+mod samples;
+mod shader;
+
+pub use samples::AudioSamplesBuffer;
+pub(crate) use shader::AudioVisualizerShader;
+
+/// Number of the most recent samples drawn by the waveform.
+const WAVEFORM_SAMPLES: usize = 2048;
+/// Has to match the size of `values` in the shader.
+const MAX_VALUES: usize = 256;
+
+/// Number of the most recent samples used to calculate the frequency spectrum.
+const SPECTRUM_SAMPLES: usize = 2048;
+const MIN_FREQUENCY: f32 = 40.0;
+const MAX_FREQUENCY: f32 = 16_000.0;
+/// Loudness of a frequency band that is displayed as an empty bar.
+const MIN_DECIBELS: f32 = -60.0;
+/// Height of a bar (as a fraction of the component height) by which bars can fall
+/// per second. Without it bars are flickering on every frame.
+const BARS_FALL_SPEED: f32 = 2.0;
+
+/// Has to match `AudioVisualizer` in the shader.
+const UNIFORM_SIZE: usize = 64 + 4 * MAX_VALUES;
+
+#[derive(Debug, Clone)]
+pub(crate) struct AudioVisualizerRenderParams {
+    pub(crate) input_id: InputId,
+    pub(crate) mode: AudioVisualizerMode,
+    pub(crate) resolution: Resolution,
+    pub(crate) color: RGBAColor,
+    pub(crate) background_color: RGBAColor,
+}
+
+pub(crate) struct AudioVisualizerNode {
+    params: AudioVisualizerRenderParams,
+    params_buffer: wgpu::Buffer,
+    params_bind_group: wgpu::BindGroup,
+    /// Values rendered in the previous frame.
+    values: Vec<f32>,
+    last_pts: Option<Duration>,
+}
+
+impl AudioVisualizerNode {
+    pub fn new(ctx: &RenderCtx, params: AudioVisualizerRenderParams) -> Self {
+        let params_buffer =
+            ctx.wgpu_ctx
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("audio visualizer params buffer"),
+                    contents: &[0u8; UNIFORM_SIZE],
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                });
+        let params_bind_group = ctx
+            .wgpu_ctx
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("audio visualizer params bind group"),
+                layout: &ctx.wgpu_ctx.uniform_bgl,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                }],
+            });
+
+        Self {
+            params,
+            params_buffer,
+            params_bind_group,
+            values: Vec::new(),
+            last_pts: None,
+        }
+    }
+
+    pub fn render(&mut self, ctx: &mut RenderCtx, target: &mut NodeTexture, pts: Duration) {
+        self.update_values(ctx, pts);
+        let params = params_to_bytes(ctx.wgpu_ctx, &self.params, &self.values);
+        ctx.wgpu_ctx
+            .queue
+            .write_buffer(&self.params_buffer, 0, &params);
+
+        let target = target.ensure_size(ctx.wgpu_ctx, self.params.resolution);
+        ctx.renderers
+            .audio_visualizer
+            .render(ctx.wgpu_ctx, &self.params_bind_group, target.view());
+    }
+
+    fn update_values(&mut self, ctx: &RenderCtx, pts: Duration) {
+        let elapsed = self
+            .last_pts
+            .map(|last_pts| pts.saturating_sub(last_pts))
+            .unwrap_or_default();
+        self.last_pts = Some(pts);
+
+        match self.params.mode {
+            AudioVisualizerMode::Waveform { .. } => {
+                self.values = match ctx
+                    .audio_samples
+                    .latest(&self.params.input_id, WAVEFORM_SAMPLES)
+                {
+                    Some((samples, _)) => waveform(&samples, MAX_VALUES),
+                    None => vec![0.0; MAX_VALUES],
+                };
+            }
+            AudioVisualizerMode::Bars { count, .. } => {
+                let count = (count as usize).min(MAX_VALUES);
+                let values = match ctx
+                    .audio_samples
+                    .latest(&self.params.input_id, SPECTRUM_SAMPLES)
+                {
+                    Some((samples, sample_rate)) => spectrum(&samples, sample_rate, count),
+                    None => vec![0.0; count],
+                };
+                let max_fall = BARS_FALL_SPEED * elapsed.as_secs_f32();
+                self.values.resize(count, 0.0);
+                for (previous, value) in self.values.iter_mut().zip(values) {
+                    *previous = f32::max(value, *previous - max_fall);
+                }
+            }
+        }
+    }
+}
+
+/// Has to match `AudioVisualizer` in the shader.
+fn params_to_bytes(
+    ctx: &WgpuCtx,
+    params: &AudioVisualizerRenderParams,
+    values: &[f32],
+) -> [u8; UNIFORM_SIZE] {
+    let (mode, line_width, gap) = match params.mode {
+        AudioVisualizerMode::Waveform { line_width } => (0u32, line_width, 0.0),
+        AudioVisualizerMode::Bars { gap, .. } => (1u32, 0.0, gap),
+    };
+    let values = &values[..values.len().min(MAX_VALUES)];
+
+    let mut data = [0u8; UNIFORM_SIZE];
+    let colors = convert_to_shader_color(ctx, &params.color)
+        .into_iter()
+        .chain(convert_to_shader_color(ctx, &params.background_color));
+    for (chunk, value) in data[0..32].chunks_exact_mut(4).zip(colors) {
+        chunk.copy_from_slice(&(value as f32).to_le_bytes());
+    }
+    data[32..36].copy_from_slice(&mode.to_le_bytes());
+    data[36..40].copy_from_slice(&(values.len() as u32).to_le_bytes());
+    data[40..44].copy_from_slice(&line_width.to_le_bytes());
+    data[44..48].copy_from_slice(&gap.to_le_bytes());
+    data[48..52].copy_from_slice(&(params.resolution.width as f32).to_le_bytes());
+    data[52..56].copy_from_slice(&(params.resolution.height as f32).to_le_bytes());
+    for (chunk, value) in data[64..].chunks_exact_mut(4).zip(values) {
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    data
+}
+
+/// Splits samples into `points` equal parts and picks the sample with the largest
+/// amplitude from each of them, so short peaks are not skipped.
+fn waveform(samples: &[f32], points: usize) -> Vec<f32> {
+    let part_len = (samples.len() / points).max(1);
+    (0..points)
+        .map(|point| {
+            samples
+                .iter()
+                .skip(point * part_len)
+                .take(part_len)
+                .fold(0.0f32, |peak, sample| match sample.abs() > peak.abs() {
+                    true => *sample,
+                    false => peak,
+                })
+                .clamp(-1.0, 1.0)
+        })
+        .collect()
+}
+
+/// Loudness of `bands` logarithmically spaced frequency ranges normalized to the range
+/// from 0 (`MIN_DECIBELS` or quieter) to 1 (full scale sine wave). Number of samples
+/// has to be a power of 2.
+fn spectrum(samples: &[f32], sample_rate: u32, bands: usize) -> Vec<f32> {
+    if samples.len() < 2 || !samples.len().is_power_of_two() || sample_rate == 0 {
+        return vec![0.0; bands];
+    }
+
+    // Hann window reduces leakage of loud frequencies to the neighboring bands.
+    let window_len = (samples.len() - 1) as f32;
+    let window = |index: usize| 0.5 - 0.5 * f32::cos(2.0 * PI * index as f32 / window_len);
+    let mut re: Vec<f32> = samples
+        .iter()
+        .enumerate()
+        .map(|(index, sample)| sample * window(index))
+        .collect();
+    let mut im = vec![0.0; samples.len()];
+    let window_sum: f32 = (0..samples.len()).map(window).sum();
+    fft(&mut re, &mut im);
+
+    let bin_width = sample_rate as f32 / samples.len() as f32;
+    let max_bin = samples.len() / 2;
+    let max_frequency = MAX_FREQUENCY.min(sample_rate as f32 / 2.0);
+    let frequency = |position: f32| MIN_FREQUENCY * (max_frequency / MIN_FREQUENCY).powf(position);
+    (0..bands)
+        .map(|band| {
+            let start = frequency(band as f32 / bands as f32) / bin_width;
+            let end = frequency((band + 1) as f32 / bands as f32) / bin_width;
+            // Low frequency bands can be narrower than a single bin.
+            let bins = match start.ceil() <= end.floor() {
+                true => start.ceil() as usize..=end.floor() as usize,
+                false => {
+                    let center = frequency((band as f32 + 0.5) / bands as f32) / bin_width;
+                    center.round() as usize..=center.round() as usize
+                }
+            };
+            let magnitude = bins
+                .filter(|bin| *bin <= max_bin)
+                .map(|bin| f32::hypot(re[bin], im[bin]))
+                .fold(0.0, f32::max);
+            let amplitude = 2.0 * magnitude / window_sum;
+            let decibels = 20.0 * amplitude.max(f32::MIN_POSITIVE).log10();
+            ((decibels - MIN_DECIBELS) / -MIN_DECIBELS).clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+/// In-place radix-2 fast Fourier transform. Length of `re` and `im` has to be
+/// a power of 2.
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let len = re.len();
+    let mut reversed = 0;
+    for index in 1..len {
+        let mut bit = len >> 1;
+        while reversed & bit != 0 {
+            reversed ^= bit;
+            bit >>= 1;
+        }
+        reversed |= bit;
+        if index < reversed {
+            re.swap(index, reversed);
+            im.swap(index, reversed);
+        }
+    }
+
+    let mut size = 2;
+    while size <= len {
+        let angle = -2.0 * PI / size as f32;
+        for start in (0..len).step_by(size) {
+            for offset in 0..size / 2 {
+                let (sin, cos) = (angle * offset as f32).sin_cos();
+                let (a, b) = (start + offset, start + offset + size / 2);
+                let b_re = re[b] * cos - im[b] * sin;
+                let b_im = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - b_re;
+                im[b] = im[a] - b_im;
+                re[a] += b_re;
+                im[a] += b_im;
+            }
+        }
+        size <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f32, sample_rate: u32, count: usize) -> Vec<f32> {
+        (0..count)
+            .map(|index| f32::sin(2.0 * PI * frequency * index as f32 / sample_rate as f32))
+            .collect()
+    }
+
+    #[test]
+    fn waveform_keeps_peaks() {
+        let mut samples = vec![0.1; 16];
+        samples[5] = -0.8;
+        samples[14] = 0.6;
+        assert_eq!(waveform(&samples, 2), vec![-0.8, 0.6]);
+    }
+
+    #[test]
+    fn spectrum_of_sine_has_single_loud_band() {
+        let sample_rate = 48_000;
+        let bands = 32;
+        let values = spectrum(&sine(1000.0, sample_rate, 2048), sample_rate, bands);
+
+        let loudest = values
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .unwrap();
+        let position = f32::ln(1000.0 / MIN_FREQUENCY) / f32::ln(MAX_FREQUENCY / MIN_FREQUENCY);
+        assert_eq!(loudest, (position * bands as f32) as usize);
+        assert!(values[loudest] > 0.9);
+        assert!(values[0] < 0.1);
+        assert!(values[bands - 1] < 0.1);
+    }
+
+    #[test]
+    fn spectrum_of_silence_is_empty() {
+        let values = spectrum(&[0.0; 2048], 48_000, 8);
+        assert_eq!(values, vec![0.0; 8]);
+    }
+}
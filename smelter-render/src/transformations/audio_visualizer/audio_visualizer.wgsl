@@ -0,0 +1,81 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+    output.position = vec4(input.position, 1.0);
+    return output;
+}
+
+struct AudioVisualizer {
+    // Colors with pre-multiplied alpha.
+    color: vec4<f32>,
+    background_color: vec4<f32>,
+    mode: u32,       // 0 = waveform, 1 = bars
+    count: u32,      // number of used values
+    line_width: f32, // waveform only, in pixels
+    gap: f32,        // bars only, in pixels
+    resolution: vec2<f32>,
+    // Waveform samples in the range [-1, 1] or bar heights in the range [0, 1].
+    values: array<vec4<f32>, 64>,
+}
+
+@group(0) @binding(0) var<uniform> params: AudioVisualizer;
+
+fn value(index: u32) -> f32 {
+    return params.values[index / 4u][index % 4u];
+}
+
+// `x` is a position in the range [0, 1], values between the samples are interpolated.
+fn waveform_value(x: f32) -> f32 {
+    let position = clamp(x, 0.0, 1.0) * f32(params.count - 1u);
+    let index = min(u32(floor(position)), params.count - 1u);
+    let next = min(index + 1u, params.count - 1u);
+    return mix(value(index), value(next), position - f32(index));
+}
+
+fn is_waveform(position: vec2<f32>) -> bool {
+    let size = params.resolution;
+    // Line covers all values between the edges of the pixel column, so steep slopes
+    // are drawn without gaps.
+    let left = waveform_value((position.x - 0.5) / size.x);
+    let right = waveform_value((position.x + 0.5) / size.x);
+    let top = (1.0 - max(left, right)) * 0.5 * size.y;
+    let bottom = (1.0 - min(left, right)) * 0.5 * size.y;
+    let half_width = params.line_width * 0.5;
+    return position.y >= top - half_width && position.y <= bottom + half_width;
+}
+
+fn is_bar(position: vec2<f32>) -> bool {
+    let size = params.resolution;
+    let slot = size.x / f32(params.count);
+    let index = min(u32(position.x / slot), params.count - 1u);
+    let offset = position.x - f32(index) * slot;
+    // Bars are at least 1 pixel wide even if the gap is larger than the slot.
+    let half_gap = min(params.gap, slot - 1.0) * 0.5;
+    if (offset < half_gap || offset > slot - half_gap) {
+        return false;
+    }
+    return position.y >= (1.0 - value(index)) * size.y;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    if (params.count == 0u) {
+        return params.background_color;
+    }
+    var is_foreground = false;
+    if (params.mode == 0u) {
+        is_foreground = params.count > 1u && is_waveform(input.position.xy);
+    } else {
+        is_foreground = is_bar(input.position.xy);
+    }
+    return select(params.background_color, params.color, is_foreground);
+}
@@ -0,0 +1,60 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use crate::InputId;
+
+/// Number of the most recent samples stored for each input, around 85ms at 48kHz.
+const MAX_SAMPLES: usize = 4096;
+
+/// Most recent audio samples of each input downmixed to mono. Samples are written
+/// by the audio mixer and read by `AudioVisualizer` components on every render.
+#[derive(Debug, Clone, Default)]
+pub struct AudioSamplesBuffer(Arc<Mutex<HashMap<InputId, InputSamples>>>);
+
+#[derive(Debug)]
+struct InputSamples {
+    samples: VecDeque<f32>,
+    sample_rate: u32,
+}
+
+impl AudioSamplesBuffer {
+    pub fn push(
+        &self,
+        input_id: &InputId,
+        samples: impl IntoIterator<Item = f32>,
+        sample_rate: u32,
+    ) {
+        let mut guard = self.0.lock().unwrap();
+        let input = guard
+            .entry(input_id.clone())
+            .or_insert_with(|| InputSamples {
+                samples: VecDeque::with_capacity(MAX_SAMPLES),
+                sample_rate,
+            });
+        if input.sample_rate != sample_rate {
+            input.samples.clear();
+            input.sample_rate = sample_rate;
+        }
+        input.samples.extend(samples);
+        let overflow = input.samples.len().saturating_sub(MAX_SAMPLES);
+        input.samples.drain(..overflow);
+    }
+
+    pub fn remove_input(&self, input_id: &InputId) {
+        self.0.lock().unwrap().remove(input_id);
+    }
+
+    /// Returns `count` most recent samples and their sample rate. If fewer samples
+    /// were received, the beginning is filled with silence.
+    pub(crate) fn latest(&self, input_id: &InputId, count: usize) -> Option<(Vec<f32>, u32)> {
+        let guard = self.0.lock().unwrap();
+        let input = guard.get(input_id)?;
+        let count = count.min(MAX_SAMPLES);
+        let available = input.samples.len().min(count);
+        let mut samples = vec![0.0; count - available];
+        samples.extend(input.samples.range(input.samples.len() - available..));
+        Some((samples, input.sample_rate))
+    }
+}
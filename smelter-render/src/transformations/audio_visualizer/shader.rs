@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use crate::wgpu::{
+    WgpuCtx, WgpuErrorScope,
+    common_pipeline::{self, CreateShaderError},
+};
+
+const LABEL: Option<&str> = Some("Audio visualizer");
+
+/// Draws a waveform or frequency bars from values passed in a uniform buffer.
+pub(crate) struct AudioVisualizerShader {
+    pipeline: wgpu::RenderPipeline,
+}
+
+impl AudioVisualizerShader {
+    pub fn new(wgpu_ctx: &Arc<WgpuCtx>) -> Result<Self, CreateShaderError> {
+        let scope = WgpuErrorScope::push(&wgpu_ctx.device);
+
+        let layout = wgpu_ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: LABEL,
+                bind_group_layouts: &[Some(&wgpu_ctx.uniform_bgl)],
+                immediate_size: 0,
+            });
+        let shader = wgpu_ctx
+            .device
+            .create_shader_module(wgpu::include_wgsl!("audio_visualizer.wgsl"));
+        let pipeline = common_pipeline::create_render_pipeline_with_blend(
+            "Audio visualizer",
+            &wgpu_ctx.device,
+            &layout,
+            &shader,
+            wgpu_ctx.default_view_format(),
+            None,
+        );
+
+        scope.pop()?;
+        Ok(Self { pipeline })
+    }
+
+    pub fn render(
+        &self,
+        wgpu_ctx: &Arc<WgpuCtx>,
+        params_bind_group: &wgpu::BindGroup,
+        target: &wgpu::TextureView,
+    ) {
+        let mut encoder = wgpu_ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: LABEL });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: LABEL,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    view: target,
+                    resolve_target: None,
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, params_bind_group, &[]);
+            wgpu_ctx.plane.draw(&mut render_pass);
+        }
+
+        wgpu_ctx.queue.submit(Some(encoder.finish()));
+    }
+}
@@ -40,6 +40,9 @@ pub enum ImageType {
     Jpeg,
     Svg,
     Gif,
+    Webp,
+    /// Requires `avif` feature.
+    Avif,
     Auto,
 }
 
@@ -66,17 +69,9 @@ impl Image {
                 let asset = SvgAsset::new(&ctx.wgpu_ctx, file)?;
                 Image::Svg(Arc::new(asset))
             }
-            ImageType::Gif => {
-                let asset = AnimatedAsset::new(&ctx.wgpu_ctx, file.clone(), ImageFormat::Gif);
-                match asset {
-                    Ok(asset) => Image::Animated(Arc::new(asset)),
-                    Err(AnimatedError::SingleFrame) => {
-                        let asset = BitmapAsset::new(&ctx.wgpu_ctx, file, ImageFormat::Gif)?;
-                        Image::Bitmap(Arc::new(asset))
-                    }
-                    Err(err) => return Err(ImageError::from(err)),
-                }
-            }
+            ImageType::Gif => Self::new_animated(ctx, file, ImageFormat::Gif)?,
+            ImageType::Webp => Self::new_animated(ctx, file, ImageFormat::WebP)?,
+            ImageType::Avif => Self::new_avif(ctx, file)?,
             ImageType::Auto => {
                 let format = match image::guess_format(&file) {
                     Ok(format) => format,
@@ -90,19 +85,8 @@ impl Image {
                 };
 
                 match format {
-                    ImageFormat::Gif => {
-                        let asset =
-                            AnimatedAsset::new(&ctx.wgpu_ctx, file.clone(), ImageFormat::Gif);
-                        match asset {
-                            Ok(asset) => Image::Animated(Arc::new(asset)),
-                            Err(AnimatedError::SingleFrame) => {
-                                let asset =
-                                    BitmapAsset::new(&ctx.wgpu_ctx, file, ImageFormat::Gif)?;
-                                Image::Bitmap(Arc::new(asset))
-                            }
-                            Err(err) => return Err(ImageError::from(err)),
-                        }
-                    }
+                    ImageFormat::Gif | ImageFormat::WebP => Self::new_animated(ctx, file, format)?,
+                    ImageFormat::Avif => Self::new_avif(ctx, file)?,
                     other_format => {
                         let asset = BitmapAsset::new(&ctx.wgpu_ctx, file, other_format)?;
                         Image::Bitmap(Arc::new(asset))
@@ -113,6 +97,31 @@ impl Image {
         Ok(renderer)
     }
 
+    /// Images with a single frame are rendered the same way as static images.
+    fn new_animated(
+        ctx: &RegisterCtx,
+        file: Bytes,
+        format: ImageFormat,
+    ) -> Result<Self, ImageError> {
+        match AnimatedAsset::new(&ctx.wgpu_ctx, file.clone(), format) {
+            Ok(asset) => Ok(Image::Animated(Arc::new(asset))),
+            Err(AnimatedError::SingleFrame) => {
+                let asset = BitmapAsset::new(&ctx.wgpu_ctx, file, format)?;
+                Ok(Image::Bitmap(Arc::new(asset)))
+            }
+            Err(err) => Err(ImageError::from(err)),
+        }
+    }
+
+    /// Only the first frame of animated AVIF images is rendered.
+    fn new_avif(ctx: &RegisterCtx, file: Bytes) -> Result<Self, ImageError> {
+        if !cfg!(feature = "avif") {
+            return Err(ImageError::AvifNotSupported);
+        }
+        let asset = BitmapAsset::new(&ctx.wgpu_ctx, file, ImageFormat::Avif)?;
+        Ok(Image::Bitmap(Arc::new(asset)))
+    }
+
     pub fn resolution(&self) -> Resolution {
         match self {
             Image::Bitmap(asset) => asset.resolution(),
@@ -162,11 +171,11 @@ impl ImageNode {
         match image.image {
             Image::Bitmap(asset) => Self::Bitmap {
                 asset,
-                state: BitmapNodeState::new(image.resolution),
+                state: BitmapNodeState::new(image.resolution, image.nine_slice),
             },
             Image::Animated(asset) => Self::Animated {
                 asset,
-                state: AnimatedNodeState::new(image.start_pts, image.resolution),
+                state: AnimatedNodeState::new(image.start_pts, image.resolution, image.nine_slice),
             },
             Image::Svg(asset) => Self::Svg {
                 asset,
@@ -186,6 +195,14 @@ impl ImageNode {
         }
     }
 
+    /// Only SVG images are re-rasterized, other image types are always rendered
+    /// at the resolution of the component.
+    pub fn set_display_resolution(&mut self, display_resolution: Option<Resolution>) {
+        if let ImageNode::Svg { state, .. } = self {
+            state.set_display_resolution(display_resolution)
+        }
+    }
+
     fn resolution(&self) -> Resolution {
         match self {
             ImageNode::Bitmap { state, .. } => state.resolution(),
@@ -217,6 +234,11 @@ pub enum ImageError {
 
     #[error("Unsupported file format")]
     UnsupportedFormat,
+
+    #[error(
+        "Decoding AVIF images is not supported. Smelter needs to be built with \"avif\" feature."
+    )]
+    AvifNotSupported,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -1,9 +1,13 @@
 use std::time::Duration;
 
-use image::{AnimationDecoder, ImageFormat, codecs::gif::GifDecoder};
+use image::{
+    AnimationDecoder, ImageFormat,
+    codecs::{gif::GifDecoder, webp::WebPDecoder},
+};
 
 use crate::{
     RenderingMode, Resolution,
+    scene::NineSlice,
     state::node_texture::NodeTextureState,
     wgpu::{
         WgpuCtx,
@@ -13,9 +17,16 @@ use crate::{
 
 use super::AnimatedError;
 
+/// Browsers display GIF frames with a delay shorter than 20ms for 100ms instead. Many GIFs
+/// are encoded with a zero or 10ms delay and rely on that behavior. Other formats use the
+/// encoded delay as is.
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(20);
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
 pub struct AnimatedNodeState {
     start_pts: Duration,
     resolution: Resolution,
+    nine_slice: Option<NineSlice>,
 }
 
 #[derive(Debug)]
@@ -24,6 +35,7 @@ pub struct AnimatedAsset {
     animation_duration: Duration,
 }
 
+/// Frame is displayed from its `pts` until `pts` of the next frame.
 #[derive(Debug)]
 enum AnimationFrame {
     Srgb {
@@ -46,6 +58,7 @@ impl AnimatedAsset {
     ) -> Result<Self, AnimatedError> {
         let decoded_frames = match format {
             ImageFormat::Gif => GifDecoder::new(&data[..])?.into_frames(),
+            ImageFormat::WebP => WebPDecoder::new(&data[..])?.into_frames(),
             other => return Err(AnimatedError::UnsupportedImageFormat(other)),
         };
 
@@ -82,8 +95,7 @@ impl AnimatedAsset {
                 }
             }
 
-            let delay: Duration = frame.delay().into();
-            animation_duration += delay;
+            animation_duration += frame_delay(frame.delay().into(), format);
 
             if frames.len() > 1000 {
                 return Err(AnimatedError::TooManyFrames);
@@ -106,11 +118,6 @@ impl AnimatedAsset {
 
         ctx.queue.submit([]);
 
-        // In case only one frame, where first delay is zero
-        if animation_duration.is_zero() {
-            animation_duration = Duration::from_nanos(1)
-        }
-
         Ok(Self {
             frames,
             animation_duration,
@@ -124,27 +131,47 @@ impl AnimatedAsset {
         state: &mut AnimatedNodeState,
         pts: Duration,
     ) {
+        let elapsed = pts.saturating_sub(state.start_pts);
+        // Animation with only zero delays (possible outside of GIFs) is not animated.
         let animation_pts = Duration::from_nanos(
-            ((pts.as_nanos() - state.start_pts.as_nanos()) % self.animation_duration.as_nanos())
-                as u64,
+            elapsed
+                .as_nanos()
+                .checked_rem(self.animation_duration.as_nanos())
+                .unwrap_or(0) as u64,
         );
 
-        let closest_frame = self
-            .frames
-            .iter()
-            .min_by_key(|frame| u128::abs_diff(frame.pts().as_nanos(), animation_pts.as_nanos()))
-            .unwrap();
-        match &closest_frame {
-            AnimationFrame::Srgb { bg, .. } => {
+        let frame_index = frame_index(&self.frames, AnimationFrame::pts, animation_pts);
+        match (&self.frames[frame_index], &state.nine_slice) {
+            (AnimationFrame::Srgb { bg, .. }, None) => {
                 ctx.utils
                     .srgb_rgba_add_premult_alpha
                     .render(ctx, bg, target.view());
             }
-            AnimationFrame::Linear { bg, .. } => {
+            (AnimationFrame::Linear { bg, .. }, None) => {
                 ctx.utils
                     .linear_rgba_add_premult_alpha
                     .render(ctx, bg, target.view());
             }
+            (AnimationFrame::Srgb { bg, .. }, Some(nine_slice)) => {
+                ctx.utils.srgb_rgba_nine_slice.render(
+                    ctx,
+                    bg,
+                    self.resolution(),
+                    target.view(),
+                    target.resolution(),
+                    nine_slice,
+                );
+            }
+            (AnimationFrame::Linear { bg, .. }, Some(nine_slice)) => {
+                ctx.utils.linear_rgba_nine_slice.render(
+                    ctx,
+                    bg,
+                    self.resolution(),
+                    target.view(),
+                    target.resolution(),
+                    nine_slice,
+                );
+            }
         }
     }
 
@@ -153,11 +180,31 @@ impl AnimatedAsset {
     }
 }
 
+fn frame_delay(delay: Duration, format: ImageFormat) -> Duration {
+    match format == ImageFormat::Gif && delay < MIN_FRAME_DELAY {
+        true => DEFAULT_FRAME_DELAY,
+        false => delay,
+    }
+}
+
+/// Index of the frame displayed at `animation_pts`. Frames are sorted by pts and the
+/// first one starts at zero.
+fn frame_index<T>(
+    frames: &[T],
+    frame_pts: impl Fn(&T) -> Duration,
+    animation_pts: Duration,
+) -> usize {
+    frames
+        .partition_point(|frame| frame_pts(frame) <= animation_pts)
+        .saturating_sub(1)
+}
+
 impl AnimatedNodeState {
-    pub fn new(start_pts: Duration, resolution: Resolution) -> Self {
+    pub fn new(start_pts: Duration, resolution: Resolution, nine_slice: Option<NineSlice>) -> Self {
         Self {
             start_pts,
             resolution,
+            nine_slice,
         }
     }
     pub fn resolution(&self) -> Resolution {
@@ -180,3 +227,48 @@ impl AnimationFrame {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn short_delays_are_replaced_only_in_gifs() {
+        assert_eq!(frame_delay(ms(0), ImageFormat::Gif), ms(100));
+        assert_eq!(frame_delay(ms(10), ImageFormat::Gif), ms(100));
+        assert_eq!(frame_delay(ms(20), ImageFormat::Gif), ms(20));
+        assert_eq!(frame_delay(ms(50), ImageFormat::Gif), ms(50));
+
+        assert_eq!(frame_delay(ms(0), ImageFormat::WebP), ms(0));
+        assert_eq!(frame_delay(ms(10), ImageFormat::WebP), ms(10));
+        assert_eq!(frame_delay(ms(50), ImageFormat::WebP), ms(50));
+    }
+
+    #[test]
+    fn frame_selection_with_mixed_delays() {
+        // GIF frames with 10ms, 20ms, 50ms and 0ms delays.
+        let frames_pts = [ms(10), ms(20), ms(50), ms(0)]
+            .into_iter()
+            .scan(Duration::ZERO, |pts, delay| {
+                let frame_pts = *pts;
+                *pts += frame_delay(delay, ImageFormat::Gif);
+                Some(frame_pts)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(frames_pts, [ms(0), ms(100), ms(120), ms(170)]);
+
+        let index = |millis| frame_index(&frames_pts, |pts| *pts, ms(millis));
+        assert_eq!(index(0), 0);
+        assert_eq!(index(99), 0);
+        assert_eq!(index(100), 1);
+        assert_eq!(index(119), 1);
+        assert_eq!(index(120), 2);
+        assert_eq!(index(169), 2);
+        assert_eq!(index(170), 3);
+        assert_eq!(index(269), 3);
+    }
+}
@@ -2,6 +2,7 @@ use image::ImageFormat;
 
 use crate::{
     RenderingMode, Resolution,
+    scene::NineSlice,
     state::node_texture::NodeTextureState,
     wgpu::{
         WgpuCtx,
@@ -12,6 +13,7 @@ use crate::{
 pub struct BitmapNodeState {
     was_rendered: bool,
     resolution: Resolution,
+    nine_slice: Option<NineSlice>,
 }
 
 #[derive(Debug)]
@@ -72,17 +74,37 @@ impl BitmapAsset {
             return;
         }
 
-        match &self {
-            BitmapAsset::Srgb { bg, .. } => {
+        match (&self, &state.nine_slice) {
+            (BitmapAsset::Srgb { bg, .. }, None) => {
                 ctx.utils
                     .srgb_rgba_add_premult_alpha
                     .render(ctx, bg, target.view());
             }
-            BitmapAsset::Linear { bg, .. } => {
+            (BitmapAsset::Linear { bg, .. }, None) => {
                 ctx.utils
                     .linear_rgba_add_premult_alpha
                     .render(ctx, bg, target.view());
             }
+            (BitmapAsset::Srgb { bg, .. }, Some(nine_slice)) => {
+                ctx.utils.srgb_rgba_nine_slice.render(
+                    ctx,
+                    bg,
+                    self.resolution(),
+                    target.view(),
+                    target.resolution(),
+                    nine_slice,
+                );
+            }
+            (BitmapAsset::Linear { bg, .. }, Some(nine_slice)) => {
+                ctx.utils.linear_rgba_nine_slice.render(
+                    ctx,
+                    bg,
+                    self.resolution(),
+                    target.view(),
+                    target.resolution(),
+                    nine_slice,
+                );
+            }
         }
         state.was_rendered = true;
     }
@@ -100,10 +122,11 @@ impl BitmapAsset {
 }
 
 impl BitmapNodeState {
-    pub fn new(resolution: Resolution) -> Self {
+    pub fn new(resolution: Resolution, nine_slice: Option<NineSlice>) -> Self {
         Self {
             was_rendered: false,
             resolution,
+            nine_slice,
         }
     }
     pub fn resolution(&self) -> Resolution {
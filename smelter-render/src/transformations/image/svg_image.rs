@@ -24,6 +24,9 @@ pub struct SvgNodeState {
     was_rendered: bool,
     renderer: SvgRenderer,
     resolution: Resolution,
+    /// Resolution at which the image was displayed in the last frame. If present,
+    /// SVG is rasterized at this resolution instead of the component's one.
+    display_resolution: Option<Resolution>,
 }
 
 pub struct SvgAsset {
@@ -104,10 +107,19 @@ impl SvgNodeState {
                 RenderingMode::WebGl => SvgRenderer::WebGl(WebGlSvgRenderer::new(ctx)),
             },
             resolution,
+            display_resolution: None,
         }
     }
+
     pub fn resolution(&self) -> Resolution {
-        self.resolution
+        self.display_resolution.unwrap_or(self.resolution)
+    }
+
+    pub fn set_display_resolution(&mut self, display_resolution: Option<Resolution>) {
+        if self.display_resolution != display_resolution {
+            self.display_resolution = display_resolution;
+            self.was_rendered = false;
+        }
     }
 }
 
@@ -1,11 +1,16 @@
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use crate::{
-    Resolution,
-    scene::{BorderRadius, BoxShadow, RGBAColor, Size},
+    MAX_NODE_RESOLUTION, Resolution,
+    scene::{
+        BackgroundGradient, BlendMode, BorderRadius, BoxShadow, ChromaKey, ColorCorrection,
+        RGBAColor, Size,
+    },
     state::{RenderCtx, node_texture::NodeTexture},
+    transformations::lut::Lut,
 };
 
+mod blur;
 mod flatten;
 mod layout_renderer;
 mod params;
@@ -13,10 +18,12 @@ mod resampler;
 mod shader;
 
 use self::{
+    blur::BlurShader,
     resampler::{ResampledChild, ResamplerShader},
-    shader::LayoutShader,
+    shader::{Backdrop, LayoutShader},
 };
 
+pub(crate) use blur::{BlurredChild, MIN_BLUR_RADIUS};
 pub(crate) use layout_renderer::LayoutRenderer;
 use tracing::error;
 
@@ -32,6 +39,11 @@ pub(crate) struct LayoutNode {
     shader: Arc<LayoutShader>,
     resampler: Option<Arc<ResamplerShader>>,
     resample_cache: HashMap<usize, ResampledChild>,
+    blur: Arc<BlurShader>,
+    blur_cache: HashMap<usize, BlurredChild>,
+    backdrop: Option<Backdrop>,
+    /// Resolution at which child nodes were displayed in the last rendered frame.
+    child_display_resolutions: Vec<Option<Resolution>>,
 }
 
 /// When rendering we cut this fragment from texture and stretch it on
@@ -71,11 +83,22 @@ struct RenderLayout {
 
     // Rotated around the center
     rotation_degrees: f32,
+    // Rotated around the horizontal and vertical axis going through the center
+    rotation_x_degrees: f32,
+    rotation_y_degrees: f32,
+    // Distance from the viewer to the z = 0 plane, 0 means no perspective
+    perspective: f32,
     // border radius needs to applied before cropping, so we can't just make it a part of a parent
     // mask
     border_radius: BorderRadius,
     masks: Vec<Mask>,
     content: RenderLayoutContent,
+    // Already multiplied by opacity of all parent layouts.
+    opacity: f32,
+    // Already combined with blur of all parent layouts. Only applied to child nodes.
+    blur_radius: f32,
+    // Blend mode of the innermost parent layout that defines one.
+    blend_mode: BlendMode,
 }
 
 #[derive(Debug, Clone)]
@@ -84,21 +107,56 @@ enum RenderLayoutContent {
         color: RGBAColor,
         border_color: RGBAColor,
         border_width: f32,
+        /// Rendered instead of `color` if defined.
+        gradient: Option<RenderGradient>,
     },
     ChildNode {
         index: usize,
+        /// Size of the child component. `crop` is defined in this coordinate system until
+        /// it's converted to texels of the child node texture before rendering.
+        size: Size,
         border_color: RGBAColor,
         border_width: f32,
         crop: Crop,
+        chroma_key: Option<ChromaKey>,
+        color_correction: Option<ColorCorrection>,
+        lut: Option<Arc<Lut>>,
+        /// Mirror the cropped part of the texture.
+        flip_horizontal: bool,
+        flip_vertical: bool,
     },
     #[allow(dead_code)]
     BoxShadow { color: RGBAColor, blur_radius: f32 },
 }
 
+#[derive(Debug, Clone)]
+struct RenderGradient {
+    gradient: BackgroundGradient,
+    /// Part of the gradient that is visible, relative to the size of the layout before
+    /// it was cropped by its parents, e.g. (0, 0, 1, 1) if the whole gradient is visible.
+    visible: Crop,
+}
+
 #[derive(Debug, Clone)]
 pub enum LayoutContent {
     Color(RGBAColor),
-    ChildNode { index: usize, size: Size },
+    Gradient(BackgroundGradient),
+    ChildNode {
+        index: usize,
+        size: Size,
+        chroma_key: Option<ChromaKey>,
+        color_correction: Option<ColorCorrection>,
+        lut: Option<Arc<Lut>>,
+        /// Multiplied with the opacity of the layout.
+        opacity: f32,
+        /// Part of the child node (in `size` coordinates) that is stretched to the size
+        /// of the layout. Whole child node is rendered if not defined.
+        crop: Option<Crop>,
+        /// Mirror the child node around the vertical axis after cropping.
+        flip_horizontal: bool,
+        /// Mirror the child node around the horizontal axis after cropping.
+        flip_vertical: bool,
+    },
     None,
 }
 
@@ -117,6 +175,14 @@ pub struct NestedLayout {
     pub height: f32,
 
     pub rotation_degrees: f32,
+    /// Rotation around the horizontal axis going through the center of the layout.
+    pub rotation_x_degrees: f32,
+    /// Rotation around the vertical axis going through the center of the layout.
+    pub rotation_y_degrees: f32,
+    /// Distance in pixels between the viewer and the layout, used to project the layout
+    /// rotated around x or y axis. `0.0` means that there is no perspective. It is also
+    /// used by children that do not define their own.
+    pub perspective: f32,
     /// scale will affect content/children, but not the properties of current layout like
     /// top/left/width/height
     pub scale_x: f32,
@@ -141,6 +207,14 @@ pub struct NestedLayout {
     pub border_color: RGBAColor,
     pub border_radius: BorderRadius,
     pub box_shadow: Vec<BoxShadow>,
+    /// Opacity in the range [0, 1]. It is applied to this layout and all of its children.
+    pub opacity: f32,
+    /// Standard deviation of a Gaussian blur in pixels. It is applied to textures of all child
+    /// nodes in this layout and its children.
+    pub blur_radius: f32,
+    /// Blend mode used to composite this layout and all of its children with the content
+    /// rendered below them. Children that define their own mode override it.
+    pub blend_mode: BlendMode,
 
     pub(crate) children: Vec<NestedLayout>,
     /// Describes how many children of this component are nodes. This value also
@@ -157,15 +231,27 @@ impl LayoutNode {
     pub fn new(ctx: &RenderCtx, layout_provider: Box<dyn LayoutProvider>) -> Self {
         let shader = ctx.renderers.layout.shader.clone();
         let resampler = ctx.renderers.layout.resampler.clone();
+        let blur = ctx.renderers.layout.blur.clone();
 
         Self {
             layout_provider,
             shader,
             resampler,
             resample_cache: HashMap::new(),
+            blur,
+            blur_cache: HashMap::new(),
+            backdrop: None,
+            child_display_resolutions: Vec::new(),
         }
     }
 
+    /// Resolution of the whole child node texture if it was stretched the same way as its
+    /// visible part in the last rendered frame. Rotation is not taken into account. If
+    /// a child is rendered multiple times, the largest resolution is used.
+    pub fn child_display_resolution(&self, index: usize) -> Option<Resolution> {
+        self.child_display_resolutions.get(index).copied().flatten()
+    }
+
     pub fn render(
         &mut self,
         ctx: &RenderCtx,
@@ -180,6 +266,8 @@ impl LayoutNode {
         let output_resolution = self.layout_provider.resolution(pts);
         let layouts = self.layout_provider.layouts(pts, &input_resolutions);
         let mut layouts = layouts.flatten(&input_resolutions, output_resolution);
+        self.update_child_display_resolutions(sources.len(), &layouts);
+        Self::crop_in_texels(sources, &mut layouts);
 
         let mut encoder =
             ctx.wgpu_ctx
@@ -189,12 +277,18 @@ impl LayoutNode {
                 });
 
         self.resample_scaled_children(ctx, sources, &mut layouts, &mut encoder);
+        self.blur_children(ctx, sources, &layouts, &mut encoder);
 
         let resolved_views: Vec<&wgpu::TextureView> = layouts
             .iter()
             .enumerate()
             .map(|(layout_index, layout)| match &layout.content {
                 RenderLayoutContent::ChildNode { index, .. } => {
+                    if let Some(blurred) = self.blur_cache.get(&layout_index)
+                        && let Some(state) = blurred.output_state()
+                    {
+                        return state.view();
+                    }
                     if let Some(resampled) = self.resample_cache.get(&layout_index)
                         && let Some(state) = resampled.output_state()
                     {
@@ -225,12 +319,77 @@ impl LayoutNode {
             layouts,
             &resolved_views,
             target,
+            &mut self.backdrop,
             &mut encoder,
         );
 
         ctx.wgpu_ctx.queue.submit(Some(encoder.finish()));
     }
 
+    fn update_child_display_resolutions(
+        &mut self,
+        children_count: usize,
+        layouts: &[RenderLayout],
+    ) {
+        self.child_display_resolutions = vec![None; children_count];
+        for layout in layouts {
+            let RenderLayoutContent::ChildNode {
+                index, size, crop, ..
+            } = &layout.content
+            else {
+                continue;
+            };
+            if crop.width <= 0.0 || crop.height <= 0.0 {
+                continue;
+            }
+            let width = layout.width * size.width / crop.width;
+            let height = layout.height * size.height / crop.height;
+            let Some(display_resolution) = self.child_display_resolutions.get_mut(*index) else {
+                continue;
+            };
+            let current = display_resolution.unwrap_or(Resolution {
+                width: 0,
+                height: 0,
+            });
+            *display_resolution = Some(Resolution {
+                width: (width.round() as usize)
+                    .max(current.width)
+                    .clamp(1, MAX_NODE_RESOLUTION.width),
+                height: (height.round() as usize)
+                    .max(current.height)
+                    .clamp(1, MAX_NODE_RESOLUTION.height),
+            });
+        }
+    }
+
+    /// Child node textures can have a different resolution than the size of their
+    /// components, e.g. SVG images are rasterized at the resolution at which they are
+    /// displayed.
+    fn crop_in_texels(sources: &[&NodeTexture], layouts: &mut [RenderLayout]) {
+        for layout in layouts {
+            let RenderLayoutContent::ChildNode {
+                index, size, crop, ..
+            } = &mut layout.content
+            else {
+                continue;
+            };
+            let Some(resolution) = sources.get(*index).and_then(|t| t.resolution()) else {
+                continue;
+            };
+            if size.width <= 0.0 || size.height <= 0.0 {
+                continue;
+            }
+            let scale_x = resolution.width as f32 / size.width;
+            let scale_y = resolution.height as f32 / size.height;
+            *crop = Crop {
+                top: crop.top * scale_y,
+                left: crop.left * scale_x,
+                width: crop.width * scale_x,
+                height: crop.height * scale_y,
+            };
+        }
+    }
+
     /// Resample scaled child nodes to their exact on-screen size, so the layout
     /// shader always samples 1:1; each child's crop is consumed whole and
     /// replaced with `output_crop`. CPU-optimized rendering has no resampler
@@ -276,6 +435,52 @@ impl LayoutNode {
         self.resample_cache
             .retain(|layout_index, _| resampled.contains(layout_index));
     }
+
+    /// Blur child nodes with a non-zero blur radius. It runs after resampling,
+    /// so it blurs the same texture the layout shader would sample.
+    fn blur_children(
+        &mut self,
+        ctx: &RenderCtx,
+        sources: &[&NodeTexture],
+        layouts: &[RenderLayout],
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let mut blurred: Vec<usize> = Vec::new();
+        for (layout_index, layout) in layouts.iter().enumerate() {
+            let RenderLayoutContent::ChildNode { index, crop, .. } = &layout.content else {
+                continue;
+            };
+            // blur radius is defined in output pixels, convert it to texels of the source
+            let sigma = [
+                layout.blur_radius * crop.width / layout.width,
+                layout.blur_radius * crop.height / layout.height,
+            ];
+            if sigma.iter().all(|sigma| *sigma < MIN_BLUR_RADIUS) {
+                continue;
+            }
+            let source = match self
+                .resample_cache
+                .get(&layout_index)
+                .and_then(|resampled| resampled.output_state())
+            {
+                Some(resampled) => resampled,
+                None => match sources.get(*index).and_then(|t| t.state()) {
+                    Some(source) => source,
+                    None => continue,
+                },
+            };
+            self.blur_cache.entry(layout_index).or_default().render(
+                ctx.wgpu_ctx,
+                &self.blur,
+                source,
+                sigma,
+                encoder,
+            );
+            blurred.push(layout_index);
+        }
+        self.blur_cache
+            .retain(|layout_index, _| blurred.contains(layout_index));
+    }
 }
 
 impl NestedLayout {
@@ -289,6 +494,9 @@ impl NestedLayout {
             width: 0.0,
             height: 0.0,
             rotation_degrees: 0.0,
+            rotation_x_degrees: 0.0,
+            rotation_y_degrees: 0.0,
+            perspective: 0.0,
             scale_x: 1.0,
             scale_y: 1.0,
             crop: None,
@@ -300,6 +508,129 @@ impl NestedLayout {
             border_color: RGBAColor(0, 0, 0, 0),
             border_radius: BorderRadius::ZERO,
             box_shadow: vec![],
+            opacity: 1.0,
+            blur_radius: 0.0,
+            blend_mode: BlendMode::Normal,
+        }
+    }
+
+    /// Sets `chroma_key` on all child nodes in this sub-tree that do not define their own.
+    pub(crate) fn apply_chroma_key(&mut self, chroma_key: ChromaKey) {
+        if let LayoutContent::ChildNode {
+            chroma_key: child_chroma_key,
+            ..
+        } = &mut self.content
+        {
+            child_chroma_key.get_or_insert(chroma_key);
+        }
+        for child in self.children.iter_mut() {
+            child.apply_chroma_key(chroma_key);
+        }
+    }
+
+    /// Applies `color_correction` to all child nodes in this sub-tree after their own
+    /// color correction.
+    pub(crate) fn apply_color_correction(&mut self, color_correction: ColorCorrection) {
+        if let LayoutContent::ChildNode {
+            color_correction: child_color_correction,
+            ..
+        } = &mut self.content
+        {
+            *child_color_correction = Some(match child_color_correction {
+                Some(child_color_correction) => child_color_correction.then(color_correction),
+                None => color_correction,
+            });
+        }
+        for child in self.children.iter_mut() {
+            child.apply_color_correction(color_correction);
+        }
+    }
+
+    /// Mirrors content of this layout together with all of its children. Position, size,
+    /// and rotation of the layout itself are not changed. Children are mirrored inside
+    /// `crop` if it is defined, or inside the whole layout otherwise.
+    pub(crate) fn flip(&mut self, horizontal: bool, vertical: bool) {
+        if !horizontal && !vertical {
+            return;
+        }
+        if let LayoutContent::ChildNode {
+            flip_horizontal,
+            flip_vertical,
+            ..
+        } = &mut self.content
+        {
+            *flip_horizontal ^= horizontal;
+            *flip_vertical ^= vertical;
+        }
+        if let LayoutContent::Gradient(gradient) = &mut self.content {
+            match gradient {
+                BackgroundGradient::Linear(linear) => {
+                    if horizontal {
+                        linear.angle_degrees = -linear.angle_degrees;
+                    }
+                    if vertical {
+                        linear.angle_degrees = 180.0 - linear.angle_degrees;
+                    }
+                }
+                BackgroundGradient::Radial(radial) => {
+                    if horizontal {
+                        radial.center_x = 1.0 - radial.center_x;
+                    }
+                    if vertical {
+                        radial.center_y = 1.0 - radial.center_y;
+                    }
+                }
+            }
+        }
+        self.border_radius = self.border_radius.flip(horizontal, vertical);
+        for box_shadow in self.box_shadow.iter_mut() {
+            if horizontal {
+                box_shadow.offset_x = -box_shadow.offset_x;
+            }
+            if vertical {
+                box_shadow.offset_y = -box_shadow.offset_y;
+            }
+        }
+        if let Some(mask) = &mut self.mask {
+            if horizontal {
+                mask.left = self.width - mask.left - mask.width;
+            }
+            if vertical {
+                mask.top = self.height - mask.top - mask.height;
+            }
+            mask.radius = mask.radius.flip(horizontal, vertical);
+        }
+
+        // Children are defined in coordinates before scaling.
+        let area = self.crop.clone().unwrap_or(Crop {
+            top: 0.0,
+            left: 0.0,
+            width: self.width / self.scale_x,
+            height: self.height / self.scale_y,
+        });
+        for child in self.children.iter_mut() {
+            if horizontal {
+                child.left = 2.0 * area.left + area.width - child.left - child.width;
+                child.rotation_y_degrees = -child.rotation_y_degrees;
+            }
+            if vertical {
+                child.top = 2.0 * area.top + area.height - child.top - child.height;
+                child.rotation_x_degrees = -child.rotation_x_degrees;
+            }
+            if horizontal != vertical {
+                child.rotation_degrees = -child.rotation_degrees;
+            }
+            child.flip(horizontal, vertical);
+        }
+    }
+
+    /// Sets `lut` on all child nodes in this sub-tree that do not define their own.
+    pub(crate) fn apply_lut(&mut self, lut: &Arc<Lut>) {
+        if let LayoutContent::ChildNode { lut: child_lut, .. } = &mut self.content {
+            child_lut.get_or_insert_with(|| lut.clone());
+        }
+        for child in self.children.iter_mut() {
+            child.apply_lut(lut);
         }
     }
 }
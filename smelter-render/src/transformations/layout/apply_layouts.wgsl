@@ -23,6 +23,11 @@ struct BoxShadowParams {
     height: f32,
     rotation_degrees: f32,
     blur_radius: f32,
+    opacity: f32,
+    rotation_x_degrees: f32,
+    rotation_y_degrees: f32,
+    // 0 -> no perspective
+    perspective: f32,
 }
 
 struct TextureParams {
@@ -42,6 +47,31 @@ struct TextureParams {
     rotation_degrees: f32,
     // border size in pixels
     border_width: f32,
+    opacity: f32,
+    rotation_x_degrees: f32,
+    rotation_y_degrees: f32,
+    // 0 -> no perspective
+    perspective: f32,
+
+    // chroma key
+    key_color: vec4<f32>,
+    key_similarity: f32,
+    key_smoothness: f32,
+    key_spill_reduction: f32,
+    // 0 -> disabled, 1 -> enabled
+    key_enabled: u32,
+
+    // color correction
+    cc_brightness: f32,
+    cc_contrast: f32,
+    cc_saturation: f32,
+    cc_hue_shift_degrees: f32,
+    // 0 -> disabled, 1 -> enabled
+    cc_enabled: u32,
+    // 1 if texture is sampled as linear color and has to be converted to sRGB first
+    is_linear: u32,
+    // 0 -> disabled, 1 -> enabled
+    lut_enabled: u32,
 }
 
 struct ColorParams {
@@ -56,6 +86,29 @@ struct ColorParams {
 
     rotation_degrees: f32,
     border_width: f32,
+    opacity: f32,
+    rotation_x_degrees: f32,
+    rotation_y_degrees: f32,
+    // 0 -> no perspective
+    perspective: f32,
+}
+
+struct GradientParams {
+    // 0 -> no gradient, 1 -> linear, 2 -> radial
+    gradient_type: u32,
+    stops_count: u32,
+    // 1 if the output is written to an sRGB view and colors have to be converted to linear.
+    srgb_output: u32,
+    // linear gradient only
+    angle_degrees: f32,
+    // radial gradient only, relative to the size of the gradient
+    center: vec2<f32>,
+    // Part of the gradient visible after cropping by parents (left, top, width, height),
+    // relative to its size.
+    visible: vec4<f32>,
+    positions: array<vec4<f32>, 2>,
+    // RGBA8 colors packed into u32, red in the lowest byte.
+    colors: array<vec4<u32>, 2>,
 }
 
 struct ParentMask {
@@ -70,7 +123,9 @@ struct LayoutInfo {
     // 0 -> Texture, 1 -> Color, 2 -> BoxShadow
     layout_type: u32,
     index: u32,
-    masks_len: u32
+    masks_len: u32,
+    // 0 -> Normal, 1 -> Multiply, 2 -> Screen, 3 -> Overlay, 4 -> Add
+    blend_mode: u32,
 }
 
 
@@ -80,11 +135,15 @@ const MAX_LAYOUTS_COUNT: u32 = 100;
 const MAX_MASKS_COUNT: u32 = 20;
 
 @group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var lut: texture_3d<f32>;
+// Copy of the output rendered before this layout, only bound if blend_mode is not Normal
+@group(0) @binding(2) var backdrop: texture_2d<f32>;
 
 @group(1) @binding(0) var<uniform> output_resolution: vec4<f32>;
 @group(1) @binding(1) var<uniform> texture_params: array<TextureParams, MAX_LAYOUTS_COUNT>;
 @group(1) @binding(2) var<uniform> color_params: array<ColorParams, MAX_LAYOUTS_COUNT>;
 @group(1) @binding(3) var<uniform> box_shadow_params: array<BoxShadowParams, MAX_LAYOUTS_COUNT>;
+@group(1) @binding(4) var<uniform> gradient_params: array<GradientParams, MAX_LAYOUTS_COUNT>;
 
 @group(2) @binding(0) var<uniform> masks: array<ParentMask, MAX_MASKS_COUNT>;
 
@@ -105,6 +164,46 @@ fn rotation_matrix(rotation: f32) -> mat4x4<f32> {
     );
 }
 
+// Rotations around the x and y axes match CSS `rotateX` and `rotateY`, positive angles move
+// the top and the right edge away from the viewer.
+fn rotation_3d_matrix(rotation_x: f32, rotation_y: f32) -> mat4x4<f32> {
+    let angle_x = radians(rotation_x);
+    let cx = cos(angle_x);
+    let sx = sin(angle_x);
+    let rotation_x_mat = mat4x4<f32>(
+        vec4<f32>(1.0, 0.0, 0.0, 0.0),
+        vec4<f32>(0.0, cx, -sx, 0.0),
+        vec4<f32>(0.0, sx, cx, 0.0),
+        vec4<f32>(0.0, 0.0, 0.0, 1.0)
+    );
+    let angle_y = radians(rotation_y);
+    let cy = cos(angle_y);
+    let sy = sin(angle_y);
+    let rotation_y_mat = mat4x4<f32>(
+        vec4<f32>(cy, 0.0, -sy, 0.0),
+        vec4<f32>(0.0, 1.0, 0.0, 0.0),
+        vec4<f32>(sy, 0.0, cy, 0.0),
+        vec4<f32>(0.0, 0.0, 0.0, 1.0)
+    );
+    return rotation_x_mat * rotation_y_mat;
+}
+
+// Projects pixel coordinates (relative to the center of the rectangle) onto the z = 0 plane,
+// as seen by a viewer at distance `perspective` from it. Depth is discarded, so the vertices
+// are not clipped.
+fn perspective_matrix(perspective: f32) -> mat4x4<f32> {
+    var w = 0.0;
+    if (perspective > 0.0) {
+        w = -1.0 / perspective;
+    }
+    return mat4x4<f32>(
+        vec4<f32>(1.0, 0.0, 0.0, 0.0),
+        vec4<f32>(0.0, 1.0, 0.0, 0.0),
+        vec4<f32>(0.0, 0.0, 0.0, w),
+        vec4<f32>(0.0, 0.0, 0.0, 1.0)
+    );
+}
+
 fn scale_matrix(scale: vec2<f32>) -> mat4x4<f32> {
     return mat4x4<f32>(
         vec4<f32>(scale.x, 0.0, 0.0, 0.0),
@@ -124,7 +223,16 @@ fn translation_matrix(translation: vec2<f32>) -> mat4x4<f32> {
     );
 }
 
-fn vertices_transformation_matrix(left: f32, top: f32, width: f32, height: f32, rotation: f32) -> mat4x4<f32> {    
+fn vertices_transformation_matrix(
+    left: f32,
+    top: f32,
+    width: f32,
+    height: f32,
+    rotation: f32,
+    rotation_x: f32,
+    rotation_y: f32,
+    perspective: f32
+) -> mat4x4<f32> {
     let scale_to_size = vec2<f32>(
         width / output_resolution.x,
         height / output_resolution.y
@@ -152,8 +260,10 @@ fn vertices_transformation_matrix(left: f32, top: f32, width: f32, height: f32,
 
     let translation_mat = translation_matrix(translation);
     let rotation_mat = rotation_matrix(rotation);
+    let rotation_3d_mat = rotation_3d_matrix(rotation_x, rotation_y);
+    let perspective_mat = perspective_matrix(perspective);
 
-    return scale_to_clip_space_mat * translation_mat * rotation_mat * scale_to_pixels_mat;
+    return scale_to_clip_space_mat * translation_mat * perspective_mat * rotation_3d_mat * rotation_mat * scale_to_pixels_mat;
 }
 
 fn texture_coord_transformation_matrix(crop_left: f32, crop_top: f32, crop_width: f32, crop_height: f32) -> mat4x4<f32> {
@@ -183,7 +293,10 @@ fn vs_main(input: VertexInput) -> VertexOutput {
                 texture_params[layout_info.index].top,
                 texture_params[layout_info.index].width,
                 texture_params[layout_info.index].height,
-                texture_params[layout_info.index].rotation_degrees
+                texture_params[layout_info.index].rotation_degrees,
+                texture_params[layout_info.index].rotation_x_degrees,
+                texture_params[layout_info.index].rotation_y_degrees,
+                texture_params[layout_info.index].perspective
             );
             let texture_transformation = texture_coord_transformation_matrix(
                 texture_params[layout_info.index].crop_left,
@@ -204,7 +317,10 @@ fn vs_main(input: VertexInput) -> VertexOutput {
                 color_params[layout_info.index].top,
                 color_params[layout_info.index].width,
                 color_params[layout_info.index].height,
-                color_params[layout_info.index].rotation_degrees
+                color_params[layout_info.index].rotation_degrees,
+                color_params[layout_info.index].rotation_x_degrees,
+                color_params[layout_info.index].rotation_y_degrees,
+                color_params[layout_info.index].perspective
             );
             output.position = vertices_transformation * vec4(input.position, 1.0);
             output.tex_coords = input.tex_coords;
@@ -221,7 +337,10 @@ fn vs_main(input: VertexInput) -> VertexOutput {
                 box_shadow_params[layout_info.index].top - box_shadow_params[layout_info.index].blur_radius,
                 width,
                 height,
-                box_shadow_params[layout_info.index].rotation_degrees
+                box_shadow_params[layout_info.index].rotation_degrees,
+                box_shadow_params[layout_info.index].rotation_x_degrees,
+                box_shadow_params[layout_info.index].rotation_y_degrees,
+                box_shadow_params[layout_info.index].perspective
             );
             output.position = vertices_transformation * vec4(input.position, 1.0);
             output.tex_coords = input.tex_coords;
@@ -255,8 +374,228 @@ fn roundedRectSDF(dist: vec2<f32>, size: vec2<f32>, radius: vec4<f32>, rotation:
     return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0, 0.0))) - r.x;
 }
 
+// Chroma of the color (Cb, Cr) using BT.709 coefficients
+fn chroma(rgb: vec3<f32>) -> vec2<f32> {
+    let luma = dot(rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+    return vec2<f32>((rgb.b - luma) / 1.8556, (rgb.r - luma) / 1.5748);
+}
+
+// sample - premultiplied color
+fn apply_chroma_key(sample: vec4<f32>, params: TextureParams) -> vec4<f32> {
+    if (params.key_enabled == 0u || sample.a <= 0.0) {
+        return sample;
+    }
+
+    let rgb = sample.rgb / sample.a;
+    let key_distance = distance(chroma(rgb), chroma(params.key_color.rgb)) - params.key_similarity;
+    let alpha = sample.a * pow(saturate(key_distance / max(params.key_smoothness, 0.0001)), 1.5);
+
+    // desaturate pixels close to the key color to remove the color bleeding from the background
+    var spill = 1.0;
+    if (params.key_spill_reduction > 0.0) {
+        spill = pow(saturate(key_distance / params.key_spill_reduction), 1.5);
+    }
+    let luma = dot(rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+    let color = mix(vec3<f32>(luma), rgb, spill);
+
+    return vec4<f32>(color * alpha, alpha);
+}
+
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let low = color * 12.92;
+    let high = 1.055 * pow(color, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, color <= vec3<f32>(0.0031308));
+}
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, color <= vec3<f32>(0.04045));
+}
+
+fn gradient_stop_position(index: u32) -> f32 {
+    return gradient_params[layout_info.index].positions[index / 4u][index % 4u];
+}
+
+// Premultiplied sRGB color of the stop.
+fn gradient_stop_color(index: u32) -> vec4<f32> {
+    let color = unpack4x8unorm(gradient_params[layout_info.index].colors[index / 4u][index % 4u]);
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
+
+// Gradient of the current color layout.
+// tex_coords - position on the rendered rectangle in [0, 0] (top-left) X [1, 1] (bottom-right)
+// size - size of the rendered rectangle in pixels
+//
+// Colors are interpolated in premultiplied sRGB, the same way as in CSS. Linear gradient follows
+// CSS `linear-gradient`, radial gradient is an ellipse that reaches the farthest corner.
+fn gradient_color(tex_coords: vec2<f32>, size: vec2<f32>) -> vec4<f32> {
+    let gradient_type = gradient_params[layout_info.index].gradient_type;
+    let stops_count = gradient_params[layout_info.index].stops_count;
+    let visible = gradient_params[layout_info.index].visible;
+    let position = visible.xy + tex_coords * visible.zw;
+    let full_size = size / max(visible.zw, vec2<f32>(0.0001));
+
+    var t = 0.0;
+    if (gradient_type == 1u) {
+        let angle = radians(gradient_params[layout_info.index].angle_degrees);
+        let direction = vec2<f32>(sin(angle), -cos(angle));
+        let line_length = abs(full_size.x * direction.x) + abs(full_size.y * direction.y);
+        t = dot((position - 0.5) * full_size, direction) / max(line_length, 0.0001) + 0.5;
+    } else {
+        let center = gradient_params[layout_info.index].center;
+        let radius = length(max(center, 1.0 - center));
+        t = length(position - center) / max(radius, 0.0001);
+    }
+
+    var color = gradient_stop_color(stops_count - 1u);
+    if (t <= gradient_stop_position(0u)) {
+        color = gradient_stop_color(0u);
+    } else {
+        for (var i = 1u; i < stops_count; i++) {
+            let end = gradient_stop_position(i);
+            if (t <= end) {
+                let start = gradient_stop_position(i - 1u);
+                let progress = select(1.0, (t - start) / (end - start), end > start);
+                color = mix(gradient_stop_color(i - 1u), gradient_stop_color(i), progress);
+                break;
+            }
+        }
+    }
+
+    if (gradient_params[layout_info.index].srgb_output == 1u && color.a > 0.0) {
+        color = vec4<f32>(srgb_to_linear(color.rgb / color.a) * color.a, color.a);
+    }
+    return color;
+}
+
+// rgb - gamma encoded color
+fn apply_color_correction(color: vec3<f32>, params: TextureParams) -> vec3<f32> {
+    var rgb = color;
+
+    // brightness and contrast relative to the middle gray
+    rgb = (rgb - 0.5) * params.cc_contrast + 0.5 + params.cc_brightness;
+
+    // hue rotation around the gray axis in YIQ space, preserves luma
+    let angle = radians(params.cc_hue_shift_degrees);
+    let c = cos(angle);
+    let s = sin(angle);
+    let yiq = mat3x3<f32>(
+        vec3<f32>(0.299, 0.596, 0.211),
+        vec3<f32>(0.587, -0.274, -0.523),
+        vec3<f32>(0.114, -0.322, 0.312),
+    ) * rgb;
+    let chroma = vec2<f32>(
+        yiq.y * c - yiq.z * s,
+        yiq.y * s + yiq.z * c,
+    ) * params.cc_saturation;
+    rgb = mat3x3<f32>(
+        vec3<f32>(1.0, 1.0, 1.0),
+        vec3<f32>(0.956, -0.272, -1.106),
+        vec3<f32>(0.621, -0.647, 1.703),
+    ) * vec3<f32>(yiq.x, chroma);
+
+    return saturate(rgb);
+}
+
+// rgb - gamma encoded color, trilinear interpolation between the LUT entries
+fn apply_lut(rgb: vec3<f32>) -> vec3<f32> {
+    let size = vec3<i32>(textureDimensions(lut));
+    let position = saturate(rgb) * vec3<f32>(size - 1);
+    let low = vec3<i32>(floor(position));
+    let high = min(low + 1, size - 1);
+    let t = position - vec3<f32>(low);
+
+    let c000 = textureLoad(lut, low, 0).rgb;
+    let c100 = textureLoad(lut, vec3<i32>(high.x, low.y, low.z), 0).rgb;
+    let c010 = textureLoad(lut, vec3<i32>(low.x, high.y, low.z), 0).rgb;
+    let c110 = textureLoad(lut, vec3<i32>(high.x, high.y, low.z), 0).rgb;
+    let c001 = textureLoad(lut, vec3<i32>(low.x, low.y, high.z), 0).rgb;
+    let c101 = textureLoad(lut, vec3<i32>(high.x, low.y, high.z), 0).rgb;
+    let c011 = textureLoad(lut, vec3<i32>(low.x, high.y, high.z), 0).rgb;
+    let c111 = textureLoad(lut, high, 0).rgb;
+
+    let c00 = mix(c000, c100, t.x);
+    let c10 = mix(c010, c110, t.x);
+    let c01 = mix(c001, c101, t.x);
+    let c11 = mix(c011, c111, t.x);
+    return saturate(mix(mix(c00, c10, t.y), mix(c01, c11, t.y), t.z));
+}
+
+// sample - premultiplied color
+fn apply_color_grading(sample: vec4<f32>, params: TextureParams) -> vec4<f32> {
+    if ((params.cc_enabled == 0u && params.lut_enabled == 0u) || sample.a <= 0.0) {
+        return sample;
+    }
+
+    var rgb = saturate(sample.rgb / sample.a);
+    if (params.is_linear == 1u) {
+        rgb = linear_to_srgb(rgb);
+    }
+    if (params.cc_enabled == 1u) {
+        rgb = apply_color_correction(rgb, params);
+    }
+    if (params.lut_enabled == 1u) {
+        rgb = apply_lut(rgb);
+    }
+    if (params.is_linear == 1u) {
+        rgb = srgb_to_linear(rgb);
+    }
+    return vec4<f32>(rgb * sample.a, sample.a);
+}
+
+fn unpremultiply(color: vec4<f32>) -> vec3<f32> {
+    if (color.a <= 0.0) {
+        return vec3<f32>(0.0, 0.0, 0.0);
+    }
+    return color.rgb / color.a;
+}
+
+// source, backdrop - colors that are not premultiplied
+fn blend(source: vec3<f32>, backdrop: vec3<f32>) -> vec3<f32> {
+    switch layout_info.blend_mode {
+        case 1u: {
+            return source * backdrop;
+        }
+        case 2u: {
+            return source + backdrop - source * backdrop;
+        }
+        case 3u: {
+            let multiply = 2.0 * source * backdrop;
+            let screen = 1.0 - 2.0 * (1.0 - source) * (1.0 - backdrop);
+            return select(screen, multiply, backdrop <= vec3<f32>(0.5));
+        }
+        case 4u: {
+            return min(source + backdrop, vec3<f32>(1.0));
+        }
+        default {
+            return source;
+        }
+    }
+}
+
+// Compositing of a premultiplied color with the backdrop as defined in the W3C Compositing and
+// Blending specification. Result replaces the output, so it also includes the backdrop.
+fn composite(color: vec4<f32>, position: vec2<f32>) -> vec4<f32> {
+    let backdrop_color = textureLoad(backdrop, vec2<i32>(position), 0);
+    let blended = blend(unpremultiply(color), unpremultiply(backdrop_color));
+    let rgb = (1.0 - backdrop_color.a) * color.rgb
+        + color.a * backdrop_color.a * blended
+        + (1.0 - color.a) * backdrop_color.rgb;
+    let alpha = color.a + backdrop_color.a * (1.0 - color.a);
+    return vec4<f32>(rgb, alpha);
+}
+
 @fragment
 fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let color = layout_color(input);
+    if (layout_info.blend_mode == 0u) {
+        return color;
+    }
+    return composite(color, input.position.xy);
+}
+
+fn layout_color(input: VertexOutput) -> vec4<f32> {
     let transparent = vec4<f32>(0.0, 0.0, 0.0, 0.0);
 
     var mask_alpha = 1.0;
@@ -280,12 +619,19 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
 
     switch layout_info.layout_type {
         case 0u: {
-            let sample = textureSample(texture, sampler_, input.tex_coords);
+            let sample = apply_color_grading(
+                apply_chroma_key(
+                    textureSample(texture, sampler_, input.tex_coords),
+                    texture_params[layout_info.index]
+                ),
+                texture_params[layout_info.index]
+            );
 
             let width = texture_params[layout_info.index].width;
             let height = texture_params[layout_info.index].height;
             let border_radius = texture_params[layout_info.index].border_radius;
             let rotation_degrees = texture_params[layout_info.index].rotation_degrees;
+            mask_alpha = mask_alpha * texture_params[layout_info.index].opacity;
             let border_width = texture_params[layout_info.index].border_width;
             let border_color = texture_params[layout_info.index].border_color;
 
@@ -316,12 +662,16 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
             }
         }
         case 1u: {
-            let color = color_params[layout_info.index].color;
-
             let width = color_params[layout_info.index].width;
             let height = color_params[layout_info.index].height;
+            var color = color_params[layout_info.index].color;
+            if (gradient_params[layout_info.index].gradient_type != 0u) {
+                color = gradient_color(input.tex_coords, vec2<f32>(width, height));
+            }
+
             let border_radius = color_params[layout_info.index].border_radius;
             let rotation_degrees = color_params[layout_info.index].rotation_degrees;
+            mask_alpha = mask_alpha * color_params[layout_info.index].opacity;
             let border_width = color_params[layout_info.index].border_width;
             let border_color = color_params[layout_info.index].border_color;
 
@@ -356,6 +706,7 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
             let height = box_shadow_params[layout_info.index].height;
             let border_radius = box_shadow_params[layout_info.index].border_radius;
             let rotation_degrees = box_shadow_params[layout_info.index].rotation_degrees;
+            mask_alpha = mask_alpha * box_shadow_params[layout_info.index].opacity;
             let blur_radius = box_shadow_params[layout_info.index].blur_radius;
 
             let size = vec2<f32>(width, height);
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use crate::{
+    state::node_texture::{NodeTexture, NodeTextureState},
+    wgpu::{
+        WgpuCtx, WgpuErrorScope,
+        common_pipeline::{self, CreateShaderError},
+    },
+};
+
+use super::resampler::Intermediate;
+
+const LABEL: Option<&str> = Some("Gaussian blur");
+
+/// Blur radius (standard deviation in texels) below which the blur is not
+/// visible and the pass is skipped.
+pub(crate) const MIN_BLUR_RADIUS: f32 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    Horizontal = 0,
+    Vertical = 1,
+}
+
+/// Has to match `Blur` in the shader.
+fn to_immediates(axis: Axis, sigma: f32) -> [u8; 8] {
+    let mut data = [0u8; 8];
+    data[0..4].copy_from_slice(&(axis as u32).to_le_bytes());
+    data[4..8].copy_from_slice(&sigma.to_le_bytes());
+    data
+}
+
+/// Separable Gaussian blur of child node textures. The horizontal pass
+/// writes to a linear Rgba16Float intermediate, so the vertical pass does not
+/// accumulate quantization errors of the first one.
+pub struct BlurShader {
+    pipeline: wgpu::RenderPipeline,
+    pipeline_f16: wgpu::RenderPipeline,
+}
+
+impl BlurShader {
+    pub fn new(wgpu_ctx: &Arc<WgpuCtx>) -> Result<Self, CreateShaderError> {
+        let scope = WgpuErrorScope::push(&wgpu_ctx.device);
+
+        let layout = wgpu_ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: LABEL,
+                bind_group_layouts: &[Some(&wgpu_ctx.format.single_texture_layout)],
+                immediate_size: 8,
+            });
+        let shader = wgpu_ctx
+            .device
+            .create_shader_module(wgpu::include_wgsl!("blur.wgsl"));
+        let build = |format| {
+            common_pipeline::create_render_pipeline(
+                "Gaussian blur",
+                &wgpu_ctx.device,
+                &layout,
+                &shader,
+                format,
+            )
+        };
+        let pipeline = build(wgpu_ctx.default_view_format());
+        let pipeline_f16 = build(wgpu::TextureFormat::Rgba16Float);
+
+        scope.pop()?;
+        Ok(Self {
+            pipeline,
+            pipeline_f16,
+        })
+    }
+
+    fn pass(
+        &self,
+        wgpu_ctx: &Arc<WgpuCtx>,
+        pipeline: &wgpu::RenderPipeline,
+        source_view: &wgpu::TextureView,
+        immediates: &[u8],
+        target: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let source_bg = wgpu_ctx
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: LABEL,
+                layout: &wgpu_ctx.format.single_texture_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(source_view),
+                }],
+            });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: LABEL,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+                view: target,
+                resolve_target: None,
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_immediates(0, immediates);
+        render_pass.set_bind_group(0, &source_bg, &[]);
+        wgpu_ctx.plane.draw(&mut render_pass);
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct BlurredChild {
+    intermediate: Option<Intermediate>,
+    output: NodeTexture,
+}
+
+impl BlurredChild {
+    pub(crate) fn output_state(&self) -> Option<&NodeTextureState> {
+        self.output.state()
+    }
+
+    /// Blurs `source` into a texture of the same resolution. `sigma` is the
+    /// standard deviation in source texels for the horizontal and vertical axis.
+    pub(crate) fn render(
+        &mut self,
+        wgpu_ctx: &Arc<WgpuCtx>,
+        shader: &BlurShader,
+        source: &NodeTextureState,
+        sigma: [f32; 2],
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let resolution = source.resolution();
+        let mid = Intermediate::ensure(&mut self.intermediate, wgpu_ctx, resolution);
+        shader.pass(
+            wgpu_ctx,
+            &shader.pipeline_f16,
+            source.view(),
+            &to_immediates(Axis::Horizontal, sigma[0]),
+            mid,
+            encoder,
+        );
+        let target = self.output.ensure_size(wgpu_ctx, resolution).view();
+        shader.pass(
+            wgpu_ctx,
+            &shader.pipeline,
+            mid,
+            &to_immediates(Axis::Vertical, sigma[1]),
+            target,
+            encoder,
+        );
+    }
+}
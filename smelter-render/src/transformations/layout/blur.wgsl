@@ -0,0 +1,46 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+    output.position = vec4(input.position, 1.0);
+    return output;
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+
+struct Blur {
+    axis: u32,  // 0 = horizontal, 1 = vertical
+    sigma: f32, // standard deviation in source texels
+}
+
+var<immediate> blur: Blur;
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let dim = vec2<i32>(textureDimensions(texture));
+    let pos = vec2<i32>(input.position.xy);
+    let step = select(vec2<i32>(1, 0), vec2<i32>(0, 1), blur.axis == 1u);
+
+    // Weights beyond 3 sigma are negligible.
+    let sigma = max(blur.sigma, 0.01);
+    let radius = i32(ceil(3.0 * sigma));
+
+    var sum = vec4<f32>(0.0, 0.0, 0.0, 0.0);
+    var weight_sum = 0.0;
+    for (var i = -radius; i <= radius; i++) {
+        let weight = exp(-f32(i * i) / (2.0 * sigma * sigma));
+        // Edges are clamped, so the content does not fade out towards transparent borders.
+        let coord = clamp(pos + step * i, vec2<i32>(0, 0), dim - 1);
+        sum += textureLoad(texture, coord, 0) * weight;
+        weight_sum += weight;
+    }
+    return sum / weight_sum;
+}
@@ -1,9 +1,13 @@
 use std::{iter, mem};
 
-use crate::{Resolution, scene::RGBAColor};
+use crate::{
+    Resolution,
+    scene::{BlendMode, BorderRadius, RGBAColor},
+};
 
 use super::{
-    BoxShadow, Crop, LayoutContent, Mask, NestedLayout, RenderLayout, RenderLayoutContent,
+    BoxShadow, Crop, LayoutContent, Mask, NestedLayout, RenderGradient, RenderLayout,
+    RenderLayoutContent,
 };
 
 impl NestedLayout {
@@ -27,11 +31,8 @@ impl NestedLayout {
         parent_masks: Vec<Mask>,
     ) -> (Vec<RenderLayout>, Vec<RenderLayout>) {
         let mut child_index_offset = child_index_offset;
-        if let LayoutContent::ChildNode { index, size } = self.content {
-            self.content = LayoutContent::ChildNode {
-                index: index + child_index_offset,
-                size,
-            };
+        if let LayoutContent::ChildNode { index, .. } = &mut self.content {
+            *index += child_index_offset;
             child_index_offset += 1
         }
         let layout = self.render_layout(&parent_masks);
@@ -125,6 +126,7 @@ impl NestedLayout {
     ) -> bool {
         if layout.width <= 0.0
             || layout.height <= 0.0
+            || layout.opacity <= 0.0
             || layout.top > resolution.height as f32
             || layout.left > resolution.width as f32
         {
@@ -135,13 +137,20 @@ impl NestedLayout {
                 color: RGBAColor(_, _, _, 0),
                 border_color: RGBAColor(_, _, _, border_alpha),
                 border_width,
+                gradient: None,
             } => *border_alpha != 0 || *border_width > 0.0,
             RenderLayoutContent::Color { .. } => true,
             RenderLayoutContent::ChildNode {
                 crop,
                 index,
+                size: _,
                 border_color: RGBAColor(_, _, _, _),
                 border_width: _,
+                chroma_key: _,
+                color_correction: _,
+                lut: _,
+                flip_horizontal: _,
+                flip_vertical: _,
             } => {
                 // TODO: handle a case when only border is visible (currently impossible)
                 let size = input_resolutions.get(*index).copied().flatten();
@@ -176,26 +185,43 @@ impl NestedLayout {
                 width: child.width * self.scale_x,
                 height: child.height * self.scale_y,
                 rotation_degrees: child.rotation_degrees + self.rotation_degrees, // TODO: not exactly correct
+                rotation_x_degrees: child.rotation_x_degrees + self.rotation_x_degrees,
+                rotation_y_degrees: child.rotation_y_degrees + self.rotation_y_degrees,
+                perspective: self.child_perspective(child.perspective),
                 content: match child.content {
                     RenderLayoutContent::Color {
                         color,
                         border_color,
                         border_width,
+                        gradient,
                     } => RenderLayoutContent::Color {
                         color,
                         border_color,
                         border_width: border_width * unified_scale,
+                        gradient,
                     },
                     RenderLayoutContent::ChildNode {
                         index,
+                        size,
                         border_color,
                         border_width,
                         crop,
+                        chroma_key,
+                        color_correction,
+                        lut,
+                        flip_horizontal,
+                        flip_vertical,
                     } => RenderLayoutContent::ChildNode {
                         index,
+                        size,
                         border_color,
                         border_width: border_width * unified_scale,
                         crop,
+                        chroma_key,
+                        color_correction,
+                        lut,
+                        flip_horizontal,
+                        flip_vertical,
                     },
                     RenderLayoutContent::BoxShadow { color, blur_radius } => {
                         RenderLayoutContent::BoxShadow {
@@ -208,6 +234,9 @@ impl NestedLayout {
                 // scaled
                 border_radius: child.border_radius * unified_scale,
                 masks: self.parent_parent_masks(&child.masks),
+                opacity: child.opacity * self.opacity,
+                blur_radius: self.child_blur_radius(child.blur_radius),
+                blend_mode: self.child_blend_mode(child.blend_mode),
             },
             Some(crop) => {
                 // Below values are only correct if `crop` is in the same coordinate
@@ -228,33 +257,76 @@ impl NestedLayout {
                         color,
                         border_color,
                         border_width,
+                        gradient,
                     } => {
+                        // Same as for the child node crop below, but the visible part of
+                        // the gradient is relative to the size of the layout.
+                        let gradient = gradient.map(|gradient| {
+                            let visible = gradient.visible;
+                            let top_diff = f32::max(crop.top - child.top, 0.0);
+                            let left_diff = f32::max(crop.left - child.left, 0.0);
+                            let horizontal_scale_factor = visible.width / child.width;
+                            let vertical_scale_factor = visible.height / child.height;
+                            RenderGradient {
+                                gradient: gradient.gradient,
+                                visible: Crop {
+                                    top: visible.top + (top_diff * vertical_scale_factor),
+                                    left: visible.left + (left_diff * horizontal_scale_factor),
+                                    width: cropped_width * horizontal_scale_factor,
+                                    height: cropped_height * vertical_scale_factor,
+                                },
+                            }
+                        });
                         RenderLayout {
                             top: self.top + (cropped_top * self.scale_y),
                             left: self.left + (cropped_left * self.scale_x),
                             width: cropped_width * self.scale_x,
                             height: cropped_height * self.scale_y,
                             rotation_degrees: child.rotation_degrees + self.rotation_degrees, // TODO: not exactly correct
+                            rotation_x_degrees: child.rotation_x_degrees + self.rotation_x_degrees,
+                            rotation_y_degrees: child.rotation_y_degrees + self.rotation_y_degrees,
+                            perspective: self.child_perspective(child.perspective),
                             content: RenderLayoutContent::Color {
                                 color,
                                 border_color,
                                 border_width: border_width * unified_scale,
+                                gradient,
                             },
                             border_radius: child.border_radius * unified_scale,
                             masks: self.parent_parent_masks(&child.masks),
+                            opacity: child.opacity * self.opacity,
+                            blur_radius: self.child_blur_radius(child.blur_radius),
+                            blend_mode: self.child_blend_mode(child.blend_mode),
                         }
                     }
                     RenderLayoutContent::ChildNode {
                         index,
+                        size,
                         crop: child_crop,
                         border_color,
                         border_width,
+                        chroma_key,
+                        color_correction,
+                        lut,
+                        flip_horizontal,
+                        flip_vertical,
                     } => {
                         // Calculate how much top/left coordinates changed when cropping. It represents
                         // how much was removed in layout coordinates. Ignore the change of a position that
-                        // was a result of a translation after cropping.
-                        let top_diff = f32::max(crop.top - child.top, 0.0);
-                        let left_diff = f32::max(crop.left - child.left, 0.0);
+                        // was a result of a translation after cropping. If the texture is flipped, its
+                        // top/left edge is at the bottom/right edge of the layout.
+                        let top_diff = match flip_vertical {
+                            false => f32::max(crop.top - child.top, 0.0),
+                            true => {
+                                f32::max((child.top + child.height) - (crop.top + crop.height), 0.0)
+                            }
+                        };
+                        let left_diff = match flip_horizontal {
+                            false => f32::max(crop.left - child.left, 0.0),
+                            true => {
+                                f32::max((child.left + child.width) - (crop.left + crop.width), 0.0)
+                            }
+                        };
 
                         // Factor to translate from `layout` coordinates to child node coord.
                         // The same factor holds for translations from `self.layout`.
@@ -274,14 +346,26 @@ impl NestedLayout {
                             width: cropped_width * self.scale_x,
                             height: cropped_height * self.scale_y,
                             rotation_degrees: child.rotation_degrees + self.rotation_degrees, // TODO: not exactly correct
+                            rotation_x_degrees: child.rotation_x_degrees + self.rotation_x_degrees,
+                            rotation_y_degrees: child.rotation_y_degrees + self.rotation_y_degrees,
+                            perspective: self.child_perspective(child.perspective),
                             content: RenderLayoutContent::ChildNode {
                                 index,
+                                size,
                                 crop,
                                 border_color,
                                 border_width,
+                                chroma_key,
+                                color_correction,
+                                lut,
+                                flip_horizontal,
+                                flip_vertical,
                             },
                             border_radius: child.border_radius * unified_scale,
                             masks: self.parent_parent_masks(&child.masks),
+                            opacity: child.opacity * self.opacity,
+                            blur_radius: self.child_blur_radius(child.blur_radius),
+                            blend_mode: self.child_blend_mode(child.blend_mode),
                         }
                     }
                     RenderLayoutContent::BoxShadow { color, blur_radius } => {
@@ -291,12 +375,18 @@ impl NestedLayout {
                             width: cropped_width * self.scale_x,
                             height: cropped_height * self.scale_y,
                             rotation_degrees: child.rotation_degrees + self.rotation_degrees, // TODO: not exactly correct
+                            rotation_x_degrees: child.rotation_x_degrees + self.rotation_x_degrees,
+                            rotation_y_degrees: child.rotation_y_degrees + self.rotation_y_degrees,
+                            perspective: self.child_perspective(child.perspective),
                             content: RenderLayoutContent::BoxShadow {
                                 color,
                                 blur_radius: blur_radius * unified_scale,
                             },
                             border_radius: child.border_radius * unified_scale,
                             masks: self.parent_parent_masks(&child.masks),
+                            opacity: child.opacity * self.opacity,
+                            blur_radius: self.child_blur_radius(child.blur_radius),
+                            blend_mode: self.child_blend_mode(child.blend_mode),
                         }
                     }
                 }
@@ -315,48 +405,125 @@ impl NestedLayout {
             width: self.width,
             height: self.height,
             rotation_degrees: self.rotation_degrees,
+            rotation_x_degrees: self.rotation_x_degrees,
+            rotation_y_degrees: self.rotation_y_degrees,
+            perspective: self.perspective,
             content: match self.content {
                 LayoutContent::Color(color) => RenderLayoutContent::Color {
                     color,
                     border_color: self.border_color,
                     border_width: self.border_width,
+                    gradient: None,
+                },
+                LayoutContent::Gradient(ref gradient) => RenderLayoutContent::Color {
+                    color: RGBAColor(0, 0, 0, 0),
+                    border_color: self.border_color,
+                    border_width: self.border_width,
+                    gradient: Some(RenderGradient {
+                        gradient: gradient.clone(),
+                        visible: Crop {
+                            top: 0.0,
+                            left: 0.0,
+                            width: 1.0,
+                            height: 1.0,
+                        },
+                    }),
                 },
-                LayoutContent::ChildNode { index, size } => RenderLayoutContent::ChildNode {
+                LayoutContent::ChildNode {
+                    index,
+                    size,
+                    chroma_key,
+                    color_correction,
+                    ref lut,
+                    opacity: _,
+                    ref crop,
+                    flip_horizontal,
+                    flip_vertical,
+                } => RenderLayoutContent::ChildNode {
                     index,
-                    crop: Crop {
+                    size,
+                    crop: crop.clone().unwrap_or(Crop {
                         top: 0.0,
                         left: 0.0,
                         width: size.width,
                         height: size.height,
-                    },
+                    }),
                     border_color: self.border_color,
                     border_width: self.border_width,
+                    chroma_key,
+                    color_correction,
+                    lut: lut.clone(),
+                    flip_horizontal,
+                    flip_vertical,
                 },
                 LayoutContent::None => RenderLayoutContent::Color {
                     color: RGBAColor(0, 0, 0, 0),
                     border_color: self.border_color,
                     border_width: self.border_width,
+                    gradient: None,
                 },
             },
             border_radius: self.border_radius,
             masks: parent_masks.to_vec(),
+            opacity: match self.content {
+                LayoutContent::ChildNode { opacity, .. } => self.opacity * opacity,
+                _ => self.opacity,
+            },
+            blur_radius: self.blur_radius,
+            blend_mode: self.blend_mode,
         }
     }
 
     /// calculate RenderLayout for one of self box shadows
     fn box_shadow_layout(&self, box_shadow: &BoxShadow, parent_masks: &[Mask]) -> RenderLayout {
+        // negative spread can not shrink the shadow below zero size
+        let spread_x = f32::max(box_shadow.spread, -self.width / 2.0);
+        let spread_y = f32::max(box_shadow.spread, -self.height / 2.0);
         RenderLayout {
-            top: self.top + box_shadow.offset_y,
-            left: self.left + box_shadow.offset_x,
-            width: self.width,
-            height: self.height,
+            top: self.top + box_shadow.offset_y - spread_y,
+            left: self.left + box_shadow.offset_x - spread_x,
+            width: self.width + 2.0 * spread_x,
+            height: self.height + 2.0 * spread_y,
             rotation_degrees: self.rotation_degrees, // TODO: this is incorrect
-            border_radius: self.border_radius + (box_shadow.blur_radius / 2.0),
+            rotation_x_degrees: self.rotation_x_degrees,
+            rotation_y_degrees: self.rotation_y_degrees,
+            perspective: self.perspective,
+            border_radius: spread_border_radius(self.border_radius, box_shadow.spread)
+                + (box_shadow.blur_radius / 2.0),
             content: RenderLayoutContent::BoxShadow {
                 color: box_shadow.color,
                 blur_radius: box_shadow.blur_radius,
             },
             masks: parent_masks.to_vec(),
+            opacity: self.opacity,
+            blur_radius: 0.0,
+            blend_mode: self.blend_mode,
+        }
+    }
+
+    /// Blur radius of a child layout in coordinates of self's parent. Two Gaussian blurs
+    /// applied one after another are equivalent to a single blur with a standard deviation
+    /// of `sqrt(a^2 + b^2)`.
+    fn child_blur_radius(&self, child_blur_radius: f32) -> f32 {
+        let unified_scale = f32::min(self.scale_x, self.scale_y);
+        f32::hypot(child_blur_radius * unified_scale, self.blur_radius)
+    }
+
+    /// Blend mode of a child layout. The innermost layout that defines a mode other than
+    /// `Normal` decides how the content is composited.
+    fn child_blend_mode(&self, child_blend_mode: BlendMode) -> BlendMode {
+        match child_blend_mode {
+            BlendMode::Normal => self.blend_mode,
+            blend_mode => blend_mode,
+        }
+    }
+
+    /// Perspective of a child layout. Children that define their own perspective override
+    /// the one of the parent.
+    fn child_perspective(&self, child_perspective: f32) -> f32 {
+        match child_perspective > 0.0 {
+            true => child_perspective,
+            false => self.perspective,
         }
     }
 
@@ -388,3 +555,18 @@ impl NestedLayout {
             .collect()
     }
 }
+
+/// Like in CSS, spread changes only rounded corners, so a shadow of a component with sharp
+/// corners stays sharp.
+fn spread_border_radius(radius: BorderRadius, spread: f32) -> BorderRadius {
+    let spread_corner = |corner: f32| match corner > 0.0 {
+        true => f32::max(corner + spread, 0.0),
+        false => 0.0,
+    };
+    BorderRadius {
+        top_left: spread_corner(radius.top_left),
+        top_right: spread_corner(radius.top_right),
+        bottom_right: spread_corner(radius.bottom_right),
+        bottom_left: spread_corner(radius.bottom_left),
+    }
+}
@@ -5,12 +5,13 @@ use crate::{
     wgpu::{WgpuCtx, common_pipeline::CreateShaderError},
 };
 
-use super::{resampler::ResamplerShader, shader::LayoutShader};
+use super::{blur::BlurShader, resampler::ResamplerShader, shader::LayoutShader};
 
 pub struct LayoutRenderer {
     pub(super) shader: Arc<LayoutShader>,
     /// `None` in CPU-optimized rendering, which scales bilinearly instead.
     pub(super) resampler: Option<Arc<ResamplerShader>>,
+    pub(crate) blur: Arc<BlurShader>,
 }
 
 impl LayoutRenderer {
@@ -25,6 +26,11 @@ impl LayoutRenderer {
             }
             RenderingMode::CpuOptimized => None,
         };
-        Ok(Self { shader, resampler })
+        let blur = Arc::new(BlurShader::new(wgpu_ctx)?);
+        Ok(Self {
+            shader,
+            resampler,
+            blur,
+        })
     }
 }
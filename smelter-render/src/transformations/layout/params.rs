@@ -5,12 +5,15 @@ use wgpu::{
 };
 
 use crate::{
-    Resolution,
-    scene::RGBAColor,
+    RenderingMode, Resolution,
+    scene::{
+        BackgroundGradient, BlendMode, ChromaKey, ColorCorrection, GradientStop,
+        MAX_GRADIENT_STOPS, RGBAColor,
+    },
     wgpu::{WgpuCtx, utils::convert_to_shader_color},
 };
 
-use super::{BorderRadius, RenderLayout};
+use super::{BorderRadius, RenderGradient, RenderLayout};
 
 const MAX_MASKS_COUNT: usize = 20;
 
@@ -19,6 +22,7 @@ pub struct LayoutInfo {
     pub layout_type: u32,
     pub index: u32,
     pub masks_len: u32,
+    pub blend_mode: u32,
 }
 
 impl LayoutInfo {
@@ -27,6 +31,7 @@ impl LayoutInfo {
         result[0..4].copy_from_slice(&self.layout_type.to_le_bytes());
         result[4..8].copy_from_slice(&self.index.to_le_bytes());
         result[8..12].copy_from_slice(&self.masks_len.to_le_bytes());
+        result[12..16].copy_from_slice(&self.blend_mode.to_le_bytes());
         result
     }
 }
@@ -40,6 +45,7 @@ pub struct ParamsBindGroups {
     texture_params_buffer: wgpu::Buffer,
     color_params_buffer: wgpu::Buffer,
     box_shadow_params_buffer: wgpu::Buffer,
+    gradient_params_buffer: wgpu::Buffer,
     pub bind_groups_2: Vec<(wgpu::BindGroup, wgpu::Buffer)>,
     pub bind_group_2_layout: wgpu::BindGroupLayout,
 }
@@ -47,9 +53,10 @@ pub struct ParamsBindGroups {
 impl ParamsBindGroups {
     pub fn new(ctx: &WgpuCtx, max_layouts_count: usize) -> ParamsBindGroups {
         let output_resolution_buffer = create_buffer(ctx, 16);
-        let texture_params_buffer = create_buffer(ctx, max_layouts_count * 80);
-        let color_params_buffer = create_buffer(ctx, max_layouts_count * 80);
+        let texture_params_buffer = create_buffer(ctx, max_layouts_count * 160);
+        let color_params_buffer = create_buffer(ctx, max_layouts_count * 96);
         let box_shadow_params_buffer = create_buffer(ctx, max_layouts_count * 80);
+        let gradient_params_buffer = create_buffer(ctx, max_layouts_count * 112);
 
         let bind_group_1_layout = ctx
             .device
@@ -96,6 +103,16 @@ impl ParamsBindGroups {
                             min_binding_size: None,
                         },
                     },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 4,
+                        count: None,
+                        visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                    },
                 ],
             });
 
@@ -119,6 +136,10 @@ impl ParamsBindGroups {
                     binding: 3,
                     resource: box_shadow_params_buffer.as_entire_binding(),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: gradient_params_buffer.as_entire_binding(),
+                },
             ],
         });
 
@@ -160,6 +181,7 @@ impl ParamsBindGroups {
             texture_params_buffer,
             color_params_buffer,
             box_shadow_params_buffer,
+            gradient_params_buffer,
             bind_groups_2,
             bind_group_1_layout,
             bind_group_2_layout,
@@ -195,6 +217,8 @@ impl ParamsBindGroups {
         let mut texture_params = Vec::new();
         let mut color_params = Vec::new();
         let mut box_shadow_params = Vec::new();
+        // Indexed the same way as `color_params`
+        let mut gradient_params = Vec::new();
 
         for (index, layout) in layouts.iter().enumerate().take(max_layouts_count) {
             let RenderLayout {
@@ -203,24 +227,37 @@ impl ParamsBindGroups {
                 width,
                 height,
                 rotation_degrees,
+                rotation_x_degrees,
+                rotation_y_degrees,
+                perspective,
                 border_radius,
                 masks,
                 content,
+                opacity,
+                blur_radius: _,
+                blend_mode,
             } = layout;
             let border_radius_bytes = borders_radius_to_bytes(*border_radius);
+            // There is nothing to blend with below the first layout
+            let blend_mode = match index {
+                0 => 0,
+                _ => blend_mode_to_u32(*blend_mode),
+            };
 
             match content {
                 super::RenderLayoutContent::Color {
                     color,
                     border_color,
                     border_width,
+                    gradient,
                 } => {
                     let layout_info = LayoutInfo {
                         layout_type: 1,
                         index: color_params.len() as u32,
                         masks_len: masks.len() as u32,
+                        blend_mode,
                     };
-                    let mut color_params_bytes = [0u8; 80];
+                    let mut color_params_bytes = [0u8; 96];
                     color_params_bytes[0..16].copy_from_slice(&border_radius_bytes);
                     color_params_bytes[16..32].copy_from_slice(&color_to_bytes(ctx, border_color));
                     color_params_bytes[32..48].copy_from_slice(&color_to_bytes(ctx, color));
@@ -230,21 +267,33 @@ impl ParamsBindGroups {
                     color_params_bytes[60..64].copy_from_slice(&height.to_le_bytes());
                     color_params_bytes[64..68].copy_from_slice(&rotation_degrees.to_le_bytes());
                     color_params_bytes[68..72].copy_from_slice(&border_width.to_le_bytes());
+                    color_params_bytes[72..76].copy_from_slice(&opacity.to_le_bytes());
+                    color_params_bytes[76..80].copy_from_slice(&rotation_x_degrees.to_le_bytes());
+                    color_params_bytes[80..84].copy_from_slice(&rotation_y_degrees.to_le_bytes());
+                    color_params_bytes[84..88].copy_from_slice(&perspective.to_le_bytes());
                     color_params.push(color_params_bytes);
+                    gradient_params.push(gradient_to_bytes(ctx, gradient.as_ref()));
                     layout_infos.push(layout_info);
                 }
                 super::RenderLayoutContent::ChildNode {
                     index: _,
+                    size: _,
                     crop,
                     border_color,
                     border_width,
+                    chroma_key,
+                    color_correction,
+                    lut,
+                    flip_horizontal,
+                    flip_vertical,
                 } => {
                     let layout_info = LayoutInfo {
                         layout_type: 0,
                         index: texture_params.len() as u32,
                         masks_len: masks.len() as u32,
+                        blend_mode,
                     };
-                    let mut texture_params_bytes = [0u8; 80];
+                    let mut texture_params_bytes = [0u8; 160];
                     texture_params_bytes[0..16].copy_from_slice(&border_radius_bytes);
                     texture_params_bytes[16..32]
                         .copy_from_slice(&color_to_bytes(ctx, border_color));
@@ -252,12 +301,34 @@ impl ParamsBindGroups {
                     texture_params_bytes[36..40].copy_from_slice(&left.to_le_bytes());
                     texture_params_bytes[40..44].copy_from_slice(&width.to_le_bytes());
                     texture_params_bytes[44..48].copy_from_slice(&height.to_le_bytes());
-                    texture_params_bytes[48..52].copy_from_slice(&crop.top.to_le_bytes());
-                    texture_params_bytes[52..56].copy_from_slice(&crop.left.to_le_bytes());
-                    texture_params_bytes[56..60].copy_from_slice(&crop.width.to_le_bytes());
-                    texture_params_bytes[60..64].copy_from_slice(&crop.height.to_le_bytes());
+                    // Flipped texture is sampled from the opposite edge of the crop.
+                    let (crop_left, crop_width) = match *flip_horizontal {
+                        true => (crop.left + crop.width, -crop.width),
+                        false => (crop.left, crop.width),
+                    };
+                    let (crop_top, crop_height) = match *flip_vertical {
+                        true => (crop.top + crop.height, -crop.height),
+                        false => (crop.top, crop.height),
+                    };
+                    texture_params_bytes[48..52].copy_from_slice(&crop_top.to_le_bytes());
+                    texture_params_bytes[52..56].copy_from_slice(&crop_left.to_le_bytes());
+                    texture_params_bytes[56..60].copy_from_slice(&crop_width.to_le_bytes());
+                    texture_params_bytes[60..64].copy_from_slice(&crop_height.to_le_bytes());
                     texture_params_bytes[64..68].copy_from_slice(&rotation_degrees.to_le_bytes());
                     texture_params_bytes[68..72].copy_from_slice(&border_width.to_le_bytes());
+                    texture_params_bytes[72..76].copy_from_slice(&opacity.to_le_bytes());
+                    texture_params_bytes[76..80].copy_from_slice(&rotation_x_degrees.to_le_bytes());
+                    texture_params_bytes[80..84].copy_from_slice(&rotation_y_degrees.to_le_bytes());
+                    texture_params_bytes[84..88].copy_from_slice(&perspective.to_le_bytes());
+                    if let Some(chroma_key) = chroma_key {
+                        texture_params_bytes[96..128]
+                            .copy_from_slice(&chroma_key_to_bytes(ctx, chroma_key));
+                    }
+                    texture_params_bytes[128..160].copy_from_slice(&color_grading_to_bytes(
+                        ctx,
+                        color_correction.as_ref(),
+                        lut.is_some(),
+                    ));
                     texture_params.push(texture_params_bytes);
                     layout_infos.push(layout_info);
                 }
@@ -266,8 +337,9 @@ impl ParamsBindGroups {
                         layout_type: 2,
                         index: box_shadow_params.len() as u32,
                         masks_len: masks.len() as u32,
+                        blend_mode,
                     };
-                    let mut box_shadow_params_bytes = [0u8; 64];
+                    let mut box_shadow_params_bytes = [0u8; 80];
                     box_shadow_params_bytes[0..16].copy_from_slice(&border_radius_bytes);
                     box_shadow_params_bytes[16..32].copy_from_slice(&color_to_bytes(ctx, color));
                     box_shadow_params_bytes[32..36].copy_from_slice(&top.to_le_bytes());
@@ -277,6 +349,12 @@ impl ParamsBindGroups {
                     box_shadow_params_bytes[48..52]
                         .copy_from_slice(&rotation_degrees.to_le_bytes());
                     box_shadow_params_bytes[52..56].copy_from_slice(&blur_radius.to_le_bytes());
+                    box_shadow_params_bytes[56..60].copy_from_slice(&opacity.to_le_bytes());
+                    box_shadow_params_bytes[60..64]
+                        .copy_from_slice(&rotation_x_degrees.to_le_bytes());
+                    box_shadow_params_bytes[64..68]
+                        .copy_from_slice(&rotation_y_degrees.to_le_bytes());
+                    box_shadow_params_bytes[68..72].copy_from_slice(&perspective.to_le_bytes());
                     box_shadow_params.push(box_shadow_params_bytes);
                     layout_infos.push(layout_info);
                 }
@@ -315,9 +393,10 @@ impl ParamsBindGroups {
             ctx.queue
                 .write_buffer(&self.bind_groups_2[index].1, 0, &masks_bytes.concat());
         }
-        texture_params.resize_with(max_layouts_count, || [0u8; 80]);
-        color_params.resize_with(max_layouts_count, || [0u8; 80]);
-        box_shadow_params.resize_with(max_layouts_count, || [0u8; 64]);
+        texture_params.resize_with(max_layouts_count, || [0u8; 160]);
+        color_params.resize_with(max_layouts_count, || [0u8; 96]);
+        box_shadow_params.resize_with(max_layouts_count, || [0u8; 80]);
+        gradient_params.resize_with(max_layouts_count, || [0u8; 112]);
 
         ctx.queue
             .write_buffer(&self.texture_params_buffer, 0, &texture_params.concat());
@@ -328,6 +407,8 @@ impl ParamsBindGroups {
             0,
             &box_shadow_params.concat(),
         );
+        ctx.queue
+            .write_buffer(&self.gradient_params_buffer, 0, &gradient_params.concat());
 
         layout_infos
     }
@@ -350,6 +431,103 @@ fn borders_radius_to_bytes(border_radius: BorderRadius) -> [u8; 16] {
     result
 }
 
+/// Has to match `blend_mode` in `LayoutInfo` in the shader.
+fn blend_mode_to_u32(blend_mode: BlendMode) -> u32 {
+    match blend_mode {
+        BlendMode::Normal => 0,
+        BlendMode::Multiply => 1,
+        BlendMode::Screen => 2,
+        BlendMode::Overlay => 3,
+        BlendMode::Add => 4,
+    }
+}
+
+fn chroma_key_to_bytes(ctx: &WgpuCtx, chroma_key: &ChromaKey) -> [u8; 32] {
+    let ChromaKey {
+        key_color,
+        similarity,
+        smoothness,
+        spill_reduction,
+    } = chroma_key;
+    let key_color = RGBAColor(key_color.0, key_color.1, key_color.2, 255);
+    let mut result = [0u8; 32];
+    result[0..16].copy_from_slice(&color_to_bytes(ctx, &key_color));
+    result[16..20].copy_from_slice(&similarity.to_le_bytes());
+    result[20..24].copy_from_slice(&smoothness.to_le_bytes());
+    result[24..28].copy_from_slice(&spill_reduction.to_le_bytes());
+    result[28..32].copy_from_slice(&1u32.to_le_bytes());
+    result
+}
+
+fn color_grading_to_bytes(
+    ctx: &WgpuCtx,
+    color_correction: Option<&ColorCorrection>,
+    lut_enabled: bool,
+) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    if let Some(ColorCorrection {
+        brightness,
+        contrast,
+        saturation,
+        hue_shift_degrees,
+    }) = color_correction
+    {
+        result[0..4].copy_from_slice(&brightness.to_le_bytes());
+        result[4..8].copy_from_slice(&contrast.to_le_bytes());
+        result[8..12].copy_from_slice(&saturation.to_le_bytes());
+        result[12..16].copy_from_slice(&hue_shift_degrees.to_le_bytes());
+        result[16..20].copy_from_slice(&1u32.to_le_bytes());
+    }
+    // sRGB textures are sampled as linear colors
+    let is_linear = match ctx.mode {
+        RenderingMode::GpuOptimized | RenderingMode::WebGl => 1u32,
+        RenderingMode::CpuOptimized => 0u32,
+    };
+    result[20..24].copy_from_slice(&is_linear.to_le_bytes());
+    result[24..28].copy_from_slice(&(lut_enabled as u32).to_le_bytes());
+    result
+}
+
+/// Has to match `GradientParams` in the shader.
+fn gradient_to_bytes(ctx: &WgpuCtx, gradient: Option<&RenderGradient>) -> [u8; 112] {
+    let mut result = [0u8; 112];
+    let Some(RenderGradient { gradient, visible }) = gradient else {
+        return result;
+    };
+    let (gradient_type, angle_degrees, center, stops) = match gradient {
+        BackgroundGradient::Linear(linear) => {
+            (1u32, linear.angle_degrees, [0.0, 0.0], &linear.stops)
+        }
+        BackgroundGradient::Radial(radial) => {
+            (2u32, 0.0, [radial.center_x, radial.center_y], &radial.stops)
+        }
+    };
+    // Stops are interpolated in sRGB and converted to linear in the shader if needed.
+    let srgb_output = match ctx.mode {
+        RenderingMode::GpuOptimized | RenderingMode::WebGl => 1u32,
+        RenderingMode::CpuOptimized => 0u32,
+    };
+    let stops = &stops[..stops.len().min(MAX_GRADIENT_STOPS)];
+    result[0..4].copy_from_slice(&gradient_type.to_le_bytes());
+    result[4..8].copy_from_slice(&(stops.len() as u32).to_le_bytes());
+    result[8..12].copy_from_slice(&srgb_output.to_le_bytes());
+    result[12..16].copy_from_slice(&angle_degrees.to_le_bytes());
+    result[16..20].copy_from_slice(&center[0].to_le_bytes());
+    result[20..24].copy_from_slice(&center[1].to_le_bytes());
+    result[32..36].copy_from_slice(&visible.left.to_le_bytes());
+    result[36..40].copy_from_slice(&visible.top.to_le_bytes());
+    result[40..44].copy_from_slice(&visible.width.to_le_bytes());
+    result[44..48].copy_from_slice(&visible.height.to_le_bytes());
+    for (index, GradientStop { position, color }) in stops.iter().enumerate() {
+        let RGBAColor(r, g, b, a) = *color;
+        let position_offset = 48 + 4 * index;
+        let color_offset = 80 + 4 * index;
+        result[position_offset..position_offset + 4].copy_from_slice(&position.to_le_bytes());
+        result[color_offset..color_offset + 4].copy_from_slice(&[r, g, b, a]);
+    }
+    result
+}
+
 fn color_to_bytes(ctx: &WgpuCtx, color: &RGBAColor) -> [u8; 16] {
     let [r, g, b, a] = convert_to_shader_color(ctx, color);
     let mut result = [0u8; 16];
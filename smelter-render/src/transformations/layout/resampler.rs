@@ -152,14 +152,14 @@ pub(super) struct ResampledChild {
 }
 
 /// Cached linear Rgba16Float scratch texture.
-struct Intermediate {
+pub(super) struct Intermediate {
     resolution: Resolution,
     view: wgpu::TextureView,
     _texture: wgpu::Texture,
 }
 
 impl Intermediate {
-    fn ensure<'a>(
+    pub(super) fn ensure<'a>(
         slot: &'a mut Option<Intermediate>,
         wgpu_ctx: &WgpuCtx,
         resolution: Resolution,
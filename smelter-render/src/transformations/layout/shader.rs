@@ -11,7 +11,7 @@ use crate::{
     },
 };
 
-use super::{RenderLayout, params::ParamsBindGroups};
+use super::{RenderLayout, RenderLayoutContent, params::ParamsBindGroups};
 
 const LABEL: Option<&str> = Some("layout node");
 
@@ -21,8 +21,53 @@ const MAX_LAYOUTS_WGSL_DECLARATION: &str = "const MAX_LAYOUTS_COUNT: u32 = 100;"
 #[derive(Debug)]
 pub struct LayoutShader {
     pipeline: wgpu::RenderPipeline,
+    /// Used for layouts with a blend mode other than `Normal`. The shader composites them
+    /// with a copy of the backdrop, so the result replaces the content of the target.
+    blend_pipeline: wgpu::RenderPipeline,
     sampler: Sampler,
     params_bind_groups: ParamsBindGroups,
+    texture_bind_group_layout: wgpu::BindGroupLayout,
+    /// Bound in place of a LUT for layouts that do not use one.
+    empty_lut_view: wgpu::TextureView,
+}
+
+/// Copy of the target texture made before rendering a blended layout, because a render
+/// pass can not sample the texture it renders to.
+pub struct Backdrop {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+impl Backdrop {
+    fn ensure<'a>(
+        slot: &'a mut Option<Backdrop>,
+        wgpu_ctx: &WgpuCtx,
+        target: &wgpu::Texture,
+    ) -> &'a Backdrop {
+        let is_compatible = |backdrop: &Backdrop| {
+            backdrop.texture.size() == target.size() && backdrop.texture.format() == target.format()
+        };
+        if slot
+            .as_ref()
+            .is_none_or(|backdrop| !is_compatible(backdrop))
+        {
+            let texture = wgpu_ctx.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("layout node backdrop"),
+                size: target.size(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: target.format(),
+                usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            });
+            *slot = Some(Backdrop {
+                view: texture.create_view(&Default::default()),
+                texture,
+            });
+        }
+        slot.as_ref().unwrap()
+    }
 }
 
 impl LayoutShader {
@@ -60,6 +105,8 @@ impl LayoutShader {
     ) -> Result<Self, CreateShaderError> {
         let sampler = Sampler::new(&wgpu_ctx.device);
         let params_bind_groups = ParamsBindGroups::new(wgpu_ctx, max_layouts_count);
+        let texture_bind_group_layout = create_texture_bind_group_layout(&wgpu_ctx.device);
+        let empty_lut_view = create_empty_lut_view(&wgpu_ctx.device);
 
         let pipeline_layout =
             wgpu_ctx
@@ -67,7 +114,7 @@ impl LayoutShader {
                 .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                     label: LABEL,
                     bind_group_layouts: &[
-                        Some(&wgpu_ctx.format.single_texture_layout),
+                        Some(&texture_bind_group_layout),
                         Some(&params_bind_groups.bind_group_1_layout),
                         Some(&params_bind_groups.bind_group_2_layout),
                         Some(&sampler.bind_group_layout),
@@ -82,14 +129,26 @@ impl LayoutShader {
             &shader_module,
             wgpu_ctx.default_view_format(),
         );
+        let blend_pipeline = common_pipeline::create_render_pipeline_with_blend(
+            "Layout node (blend)",
+            &wgpu_ctx.device,
+            &pipeline_layout,
+            &shader_module,
+            wgpu_ctx.default_view_format(),
+            None,
+        );
 
         Ok(Self {
             pipeline,
+            blend_pipeline,
             sampler,
             params_bind_groups,
+            texture_bind_group_layout,
+            empty_lut_view,
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
         &self,
         wgpu_ctx: &Arc<WgpuCtx>,
@@ -97,8 +156,16 @@ impl LayoutShader {
         layouts: Vec<RenderLayout>,
         texture_views: &[&wgpu::TextureView],
         target: &NodeTextureState,
+        backdrop: &mut Option<Backdrop>,
         encoder: &mut wgpu::CommandEncoder,
     ) {
+        let luts: Vec<_> = layouts
+            .iter()
+            .map(|layout| match &layout.content {
+                RenderLayoutContent::ChildNode { lut, .. } => lut.clone(),
+                _ => None,
+            })
+            .collect();
         let layout_infos = self
             .params_bind_groups
             .update(wgpu_ctx, output_resolution, layouts);
@@ -111,58 +178,169 @@ impl LayoutShader {
             );
         }
 
+        let backdrop = match layout_infos.iter().any(|info| info.blend_mode != 0) {
+            true => Some(Backdrop::ensure(backdrop, wgpu_ctx, target.texture())),
+            false => {
+                *backdrop = None;
+                None
+            }
+        };
+
         let input_texture_bgs: Vec<wgpu::BindGroup> = texture_views
             .iter()
-            .map(|view| {
+            .zip(luts.iter())
+            .zip(layout_infos.iter())
+            .map(|((view, lut), layout_info)| {
+                let lut_view = match lut {
+                    Some(lut) => lut.view(),
+                    None => &self.empty_lut_view,
+                };
+                let backdrop_view = match (layout_info.blend_mode, backdrop) {
+                    (1.., Some(backdrop)) => &backdrop.view,
+                    _ => wgpu_ctx.default_empty_view(),
+                };
                 wgpu_ctx
                     .device
                     .create_bind_group(&wgpu::BindGroupDescriptor {
-                        layout: &wgpu_ctx.format.single_texture_layout,
+                        layout: &self.texture_bind_group_layout,
                         label: LABEL,
-                        entries: &[wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::TextureView(view),
-                        }],
+                        entries: &[
+                            wgpu::BindGroupEntry {
+                                binding: 0,
+                                resource: wgpu::BindingResource::TextureView(view),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 1,
+                                resource: wgpu::BindingResource::TextureView(lut_view),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 2,
+                                resource: wgpu::BindingResource::TextureView(backdrop_view),
+                            },
+                        ],
                     })
             })
             .collect();
 
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: LABEL,
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    view: target.view(),
-                    resolve_target: None,
-                    depth_slice: None,
-                })],
-                // TODO: depth stencil attachments
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-                multiview_mask: None,
-            });
+        let mut render_pass = Self::begin_render_pass(
+            encoder,
+            target,
+            wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+        );
 
-            for (index, (texture_bg, layout_info)) in input_texture_bgs
-                .iter()
-                .zip(layout_infos.iter())
-                .take(self.params_bind_groups.max_layouts_count)
-                .enumerate()
-            {
-                render_pass.set_pipeline(&self.pipeline);
+        for (index, (texture_bg, layout_info)) in input_texture_bgs
+            .iter()
+            .zip(layout_infos.iter())
+            .take(self.params_bind_groups.max_layouts_count)
+            .enumerate()
+        {
+            match (layout_info.blend_mode, backdrop) {
+                (1.., Some(backdrop)) => {
+                    // Blended layout needs everything rendered so far as a backdrop, so the
+                    // current pass has to end before the copy.
+                    drop(render_pass);
+                    encoder.copy_texture_to_texture(
+                        target.texture().as_image_copy(),
+                        backdrop.texture.as_image_copy(),
+                        target.texture().size(),
+                    );
+                    render_pass = Self::begin_render_pass(encoder, target, wgpu::LoadOp::Load);
+                    render_pass.set_pipeline(&self.blend_pipeline);
+                }
+                _ => render_pass.set_pipeline(&self.pipeline),
+            }
 
-                render_pass.set_immediates(0, &layout_info.to_bytes());
+            render_pass.set_immediates(0, &layout_info.to_bytes());
 
-                render_pass.set_bind_group(0, texture_bg, &[]);
-                render_pass.set_bind_group(1, &self.params_bind_groups.bind_group_1, &[]);
-                render_pass.set_bind_group(2, &self.params_bind_groups.bind_groups_2[index].0, &[]);
-                render_pass.set_bind_group(3, &self.sampler.bind_group, &[]);
+            render_pass.set_bind_group(0, texture_bg, &[]);
+            render_pass.set_bind_group(1, &self.params_bind_groups.bind_group_1, &[]);
+            render_pass.set_bind_group(2, &self.params_bind_groups.bind_groups_2[index].0, &[]);
+            render_pass.set_bind_group(3, &self.sampler.bind_group, &[]);
 
-                wgpu_ctx.plane.draw(&mut render_pass);
-            }
+            wgpu_ctx.plane.draw(&mut render_pass);
         }
     }
+
+    fn begin_render_pass<'a>(
+        encoder: &'a mut wgpu::CommandEncoder,
+        target: &NodeTextureState,
+        load: wgpu::LoadOp<wgpu::Color>,
+    ) -> wgpu::RenderPass<'a> {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: LABEL,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+                view: target.view(),
+                resolve_target: None,
+                depth_slice: None,
+            })],
+            // TODO: depth stencil attachments
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        })
+    }
+}
+
+fn create_texture_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("layout node texture bind group layout"),
+        entries: &[
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                count: None,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    multisampled: false,
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+            },
+            // 3D LUT, interpolated in the shader because Rgba32Float is not filterable
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                count: None,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    multisampled: false,
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D3,
+                },
+            },
+            // backdrop for blend modes, read with textureLoad
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                count: None,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    multisampled: false,
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+            },
+        ],
+    })
+}
+
+fn create_empty_lut_view(device: &wgpu::Device) -> wgpu::TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("empty LUT"),
+            size: wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D3,
+            format: wgpu::TextureFormat::Rgba32Float,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
 }
@@ -0,0 +1,267 @@
+use std::{fs, io, path::Path, str::Utf8Error, sync::Arc};
+
+use bytes::Bytes;
+
+use crate::wgpu::{WgpuCtx, texture::TextureExt};
+
+/// Largest supported `LUT_3D_SIZE`. Common grading LUTs use 17, 33 or 65 points per axis.
+const MAX_LUT_SIZE: usize = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LutSpec {
+    pub src: LutSource,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LutSource {
+    Url { url: Arc<str> },
+    LocalPath { path: Arc<Path> },
+    Bytes { bytes: Bytes },
+}
+
+/// 3D color lookup table parsed from a `.cube` file and uploaded to an Rgba32Float
+/// 3D texture. Red is mapped to the x, green to the y and blue to the z axis.
+#[derive(Debug)]
+pub struct Lut {
+    size: usize,
+    view: wgpu::TextureView,
+}
+
+impl Lut {
+    pub fn new(ctx: &WgpuCtx, spec: LutSpec) -> Result<Self, LutError> {
+        let file = Self::download_file(&spec.src)?;
+        let content = std::str::from_utf8(&file)?;
+        let CubeFile { size, table } = CubeFile::parse(content)?;
+
+        let data: Vec<u8> = table
+            .iter()
+            .flat_map(|[r, g, b]| [*r, *g, *b, 1.0])
+            .flat_map(f32::to_le_bytes)
+            .collect();
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("3D LUT"),
+            size: wgpu::Extent3d {
+                width: size as u32,
+                height: size as u32,
+                depth_or_array_layers: size as u32,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D3,
+            format: wgpu::TextureFormat::Rgba32Float,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        texture.upload_data(&ctx.queue, &data, 16);
+        ctx.queue.submit([]);
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Ok(Self { size, view })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub(crate) fn view(&self) -> &wgpu::TextureView {
+        &self.view
+    }
+
+    fn download_file(src: &LutSource) -> Result<Bytes, LutError> {
+        match src {
+            #[cfg(target_arch = "wasm32")]
+            LutSource::Url { .. } => Err(LutError::LutSourceUrlNotSupported),
+            #[cfg(not(target_arch = "wasm32"))]
+            LutSource::Url { url } => {
+                let response = reqwest::blocking::get(url.as_ref())?;
+                let response = response.error_for_status()?;
+                Ok(response.bytes()?)
+            }
+            LutSource::LocalPath { path } => {
+                let file = fs::read(path)?;
+                Ok(Bytes::from(file))
+            }
+            LutSource::Bytes { bytes } => Ok(bytes.clone()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct CubeFile {
+    size: usize,
+    /// RGB entries with red changing fastest, then green, then blue.
+    table: Vec<[f32; 3]>,
+}
+
+impl CubeFile {
+    fn parse(content: &str) -> Result<Self, LutError> {
+        let mut size = None;
+        let mut table = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut tokens = line.split_whitespace();
+            let Some(keyword) = tokens.next() else {
+                continue;
+            };
+            match keyword {
+                "TITLE" => {}
+                "LUT_1D_SIZE" => return Err(LutError::Unsupported1DLut),
+                "LUT_3D_SIZE" => {
+                    let value = tokens
+                        .next()
+                        .and_then(|value| value.parse::<usize>().ok())
+                        .ok_or(LutError::InvalidLine(line_number))?;
+                    if !(2..=MAX_LUT_SIZE).contains(&value) {
+                        return Err(LutError::UnsupportedSize(value));
+                    }
+                    size = Some(value);
+                }
+                "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                    let expected = match keyword {
+                        "DOMAIN_MIN" => 0.0,
+                        _ => 1.0,
+                    };
+                    let values = parse_triplet(tokens, line_number)?;
+                    if values.iter().any(|value| *value != expected) {
+                        return Err(LutError::UnsupportedDomain);
+                    }
+                }
+                "LUT_3D_INPUT_RANGE" => {
+                    let range: Vec<_> = tokens.map(|value| value.parse::<f32>().ok()).collect();
+                    match range.as_slice() {
+                        [Some(min), Some(max)] if *min == 0.0 && *max == 1.0 => {}
+                        [Some(_), Some(_)] => return Err(LutError::UnsupportedDomain),
+                        _ => return Err(LutError::InvalidLine(line_number)),
+                    }
+                }
+                // Other keywords do not affect the table.
+                keyword if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {}
+                _ => {
+                    let values = parse_triplet(line.split_whitespace(), line_number)?;
+                    table.push(values);
+                }
+            }
+        }
+
+        let size = size.ok_or(LutError::MissingSize)?;
+        if table.len() != size * size * size {
+            return Err(LutError::InvalidEntriesCount {
+                expected: size * size * size,
+                actual: table.len(),
+            });
+        }
+        Ok(Self { size, table })
+    }
+}
+
+fn parse_triplet<'a>(
+    mut tokens: impl Iterator<Item = &'a str>,
+    line_number: usize,
+) -> Result<[f32; 3], LutError> {
+    let mut next = || {
+        tokens
+            .next()
+            .and_then(|value| value.parse::<f32>().ok())
+            .ok_or(LutError::InvalidLine(line_number))
+    };
+    let values = [next()?, next()?, next()?];
+    if tokens.next().is_some() {
+        return Err(LutError::InvalidLine(line_number));
+    }
+    Ok(values)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LutError {
+    #[error("Failed to download asset: {0}")]
+    AssetDownload(#[from] reqwest::Error),
+
+    #[error("Failed to read LUT from disk: {0}")]
+    AssetDiskReadError(#[from] io::Error),
+
+    #[error("Invalid utf-8 content inside the LUT file: {0}")]
+    InvalidUtf8Content(#[from] Utf8Error),
+
+    #[error("Providing URL as LUT source is not supported on wasm platform")]
+    LutSourceUrlNotSupported,
+
+    #[error("Failed to parse line {0} of the LUT file.")]
+    InvalidLine(usize),
+
+    #[error("LUT file does not define \"LUT_3D_SIZE\".")]
+    MissingSize,
+
+    #[error("Unsupported LUT size {0}. Size has to be between 2 and 128.")]
+    UnsupportedSize(usize),
+
+    #[error("1D LUTs are not supported, only 3D LUTs can be registered.")]
+    Unsupported1DLut,
+
+    #[error("LUTs with input domain other than 0 to 1 are not supported.")]
+    UnsupportedDomain,
+
+    #[error("LUT file should contain {expected} entries, found {actual}.")]
+    InvalidEntriesCount { expected: usize, actual: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cube_file() {
+        let content = "\
+TITLE \"identity\"
+# comment
+LUT_3D_SIZE 2
+DOMAIN_MIN 0.0 0.0 0.0
+DOMAIN_MAX 1.0 1.0 1.0
+
+0.0 0.0 0.0
+1.0 0.0 0.0
+0.0 1.0 0.0
+1.0 1.0 0.0
+0.0 0.0 1.0
+1.0 0.0 1.0
+0.0 1.0 1.0
+1.0 1.0 1.0
+";
+        let cube = CubeFile::parse(content).unwrap();
+        assert_eq!(cube.size, 2);
+        assert_eq!(cube.table[1], [1.0, 0.0, 0.0]);
+        assert_eq!(cube.table[6], [0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn rejects_invalid_cube_files() {
+        assert!(matches!(
+            CubeFile::parse("LUT_3D_SIZE 2\n0 0 0\n"),
+            Err(LutError::InvalidEntriesCount {
+                expected: 8,
+                actual: 1
+            })
+        ));
+        assert!(matches!(
+            CubeFile::parse("0 0 0\n"),
+            Err(LutError::MissingSize)
+        ));
+        assert!(matches!(
+            CubeFile::parse("LUT_1D_SIZE 1024\n"),
+            Err(LutError::Unsupported1DLut)
+        ));
+        assert!(matches!(
+            CubeFile::parse("LUT_3D_SIZE 2\nDOMAIN_MAX 2 2 2\n"),
+            Err(LutError::UnsupportedDomain)
+        ));
+        assert!(matches!(
+            CubeFile::parse("LUT_3D_SIZE 2\n0 0\n"),
+            Err(LutError::InvalidLine(2))
+        ));
+    }
+}
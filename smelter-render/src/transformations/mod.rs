@@ -1,5 +1,7 @@
+pub mod audio_visualizer;
 pub mod image;
 pub mod layout;
+pub mod lut;
 pub mod shader;
 pub mod text_renderer;
 
@@ -1,12 +1,15 @@
 use std::{
+    borrow::Cow,
     cmp::max,
-    fmt,
+    f32::consts::PI,
+    fmt, iter,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use glyphon::{
     AttrsOwned, Buffer, Cache, Color, FontSystem, Metrics, Shaping, SwashCache, TextArea,
-    TextAtlas, TextBounds,
+    TextAtlas, TextBounds, Viewport,
     cosmic_text::FontFeatures,
     fontdb::{Database, Source},
 };
@@ -19,21 +22,69 @@ use wgpu::{
 use crate::{
     Resolution,
     scene::{
-        HorizontalAlign, RGBAColor, TextComponent, TextDimensions, TextStyle, TextWeight, TextWrap,
+        HorizontalAlign, LinearGradient, RGBAColor, TextComponent, TextDimensions, TextDirection,
+        TextFit, TextShadow, TextSpan, TextStroke, TextStyle, TextTicker, TextWeight, TextWrap,
+        TickerDirection,
     },
     state::{RenderCtx, node_texture::NodeTexture},
+    transformations::layout::{BlurredChild, MIN_BLUR_RADIUS},
     wgpu::{WgpuCtx, utils::convert_to_shader_color},
 };
 
+pub(crate) use gradient::TextGradientShader;
+
+mod gradient;
+
 #[derive(Debug, Clone)]
 pub(crate) struct TextRenderParams {
     pub(crate) buffer: TextBuffer,
     pub(crate) resolution: Resolution,
     pub(crate) background_color: RGBAColor,
+    pub(crate) color: RGBAColor,
+    pub(crate) gradient: Option<LinearGradient>,
+    pub(crate) stroke: Option<TextStroke>,
+    pub(crate) shadow: Option<TextShadow>,
+    pub(crate) ticker: Option<TickerParams>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TickerParams {
+    pub(crate) ticker: TextTicker,
+    /// PTS at which the text started scrolling.
+    pub(crate) start_pts: Duration,
+}
+
+impl TickerParams {
+    /// Horizontal offset of the text at `pts`. Text starts just outside of the texture
+    /// and scrolls until it leaves the texture on the opposite side.
+    fn offset(&self, pts: Duration, text_width: f32, texture_width: f32) -> f32 {
+        // f32 is not precise enough for distances after a few hours of scrolling
+        let elapsed = pts.saturating_sub(self.start_pts).as_secs_f64();
+        let cycle = (text_width + texture_width) as f64;
+        let distance = self.ticker.speed as f64 * elapsed;
+        let distance = match self.ticker.repeat && cycle > 0.0 {
+            true => distance % cycle,
+            false => distance.min(cycle),
+        } as f32;
+        match self.ticker.direction {
+            TickerDirection::Left => texture_width - distance,
+            TickerDirection::Right => distance - text_width,
+        }
+    }
 }
 
 #[derive(Clone)]
-pub(crate) struct TextBuffer(Arc<glyphon::Buffer>);
+pub(crate) struct TextBuffer {
+    buffer: Arc<glyphon::Buffer>,
+    /// Layout of the same text, but without colors of the spans. It's used to draw the stroke,
+    /// the shadow and the gradient fill. `None` if it would be the same as `buffer`.
+    single_color_buffer: Option<Arc<glyphon::Buffer>>,
+    /// Position of the text on the texture. It leaves space for the stroke and the shadow.
+    left: f32,
+    top: f32,
+    /// Width of the text together with the space reserved for the stroke and the shadow.
+    width: f32,
+}
 
 impl fmt::Debug for TextBuffer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -41,6 +92,57 @@ impl fmt::Debug for TextBuffer {
     }
 }
 
+impl TextBuffer {
+    /// Copies of the text translated by `offsets`. `color` is used for spans that do
+    /// not define their own color.
+    fn text_areas(
+        &self,
+        offsets: &[(f32, f32)],
+        color: &RGBAColor,
+        resolution: Resolution,
+    ) -> Vec<TextArea<'_>> {
+        self.areas(&self.buffer, offsets, color, resolution)
+    }
+
+    /// Same as [`TextBuffer::text_areas`], but all spans are drawn in `color`.
+    fn single_color_text_areas(
+        &self,
+        offsets: &[(f32, f32)],
+        color: &RGBAColor,
+        resolution: Resolution,
+    ) -> Vec<TextArea<'_>> {
+        let buffer = self.single_color_buffer.as_ref().unwrap_or(&self.buffer);
+        self.areas(buffer, offsets, color, resolution)
+    }
+
+    fn areas<'a>(
+        &self,
+        buffer: &'a glyphon::Buffer,
+        offsets: &[(f32, f32)],
+        color: &RGBAColor,
+        resolution: Resolution,
+    ) -> Vec<TextArea<'a>> {
+        let RGBAColor(r, g, b, a) = *color;
+        offsets
+            .iter()
+            .map(|(x, y)| TextArea {
+                buffer,
+                left: self.left + x,
+                top: self.top + y,
+                scale: 1.0,
+                bounds: TextBounds {
+                    left: 0,
+                    top: 0,
+                    right: resolution.width as i32,
+                    bottom: resolution.height as i32,
+                },
+                default_color: Color::rgba(r, g, b, a),
+                custom_glyphs: &[],
+            })
+            .collect()
+    }
+}
+
 impl From<Resolution> for glyphon::Resolution {
     fn from(value: Resolution) -> Self {
         Self {
@@ -54,6 +156,18 @@ pub(crate) struct TextRendererNode {
     buffer: TextBuffer,
     resolution: Resolution,
     background_color: wgpu::Color,
+    color: RGBAColor,
+    gradient: Option<LinearGradient>,
+    stroke: Option<TextStroke>,
+    shadow: Option<TextShadow>,
+    ticker: Option<TickerParams>,
+    /// Kept between frames only if the text is scrolling.
+    draw_resources: Option<TextDrawResources>,
+    /// Shadow is rendered here before it is blurred.
+    shadow_texture: NodeTexture,
+    /// Text is rendered here in white before it is filled with the gradient.
+    fill_texture: NodeTexture,
+    blurred_shadow: BlurredChild,
     was_rendered: bool,
 }
 
@@ -65,11 +179,25 @@ impl TextRendererNode {
             buffer: params.buffer,
             resolution: params.resolution,
             background_color,
+            color: params.color,
+            gradient: params.gradient,
+            stroke: params.stroke,
+            shadow: params.shadow,
+            ticker: params.ticker,
+            draw_resources: None,
+            shadow_texture: NodeTexture::new(),
+            fill_texture: NodeTexture::new(),
+            blurred_shadow: BlurredChild::default(),
             was_rendered: false,
         }
     }
 
-    pub(crate) fn render(&mut self, renderer_ctx: &mut RenderCtx, target: &mut NodeTexture) {
+    pub(crate) fn render(
+        &mut self,
+        renderer_ctx: &mut RenderCtx,
+        target: &mut NodeTexture,
+        pts: Duration,
+    ) {
         if self.was_rendered {
             return;
         }
@@ -84,111 +212,377 @@ impl TextRendererNode {
             return;
         }
 
+        let wgpu_ctx = renderer_ctx.wgpu_ctx;
         let text_renderer = renderer_ctx.text_renderer_ctx;
         let font_system = &mut text_renderer.font_system.lock().unwrap();
         let swash_cache = &mut text_renderer.swash_cache.lock().unwrap();
-        let cache = &mut text_renderer.cache.lock().unwrap();
+        let mut resources = self.draw_resources.take().unwrap_or_else(|| {
+            let cache = text_renderer.cache.lock().unwrap();
+            TextDrawResources::new(wgpu_ctx, &cache, self.resolution)
+        });
+        let mut draw_ctx = TextDrawCtx {
+            wgpu_ctx,
+            font_system,
+            swash_cache,
+            atlas: &mut resources.atlas,
+            viewport: &resources.viewport,
+        };
 
-        let mut viewport = glyphon::Viewport::new(&renderer_ctx.wgpu_ctx.device, cache);
-        viewport.update(&renderer_ctx.wgpu_ctx.queue, self.resolution.into());
+        let mut encoder = wgpu_ctx
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("Text renderer encoder"),
+            });
 
-        let swapchain_format = renderer_ctx.wgpu_ctx.default_view_format();
-        let mut atlas = TextAtlas::new(
-            &renderer_ctx.wgpu_ctx.device,
-            &renderer_ctx.wgpu_ctx.queue,
-            cache,
-            swapchain_format,
-        );
+        let target_state = target.ensure_size(wgpu_ctx, self.resolution);
+        let origin = match &self.ticker {
+            Some(ticker) => {
+                let offset = ticker.offset(pts, self.buffer.width, self.resolution.width as f32);
+                (offset, 0.0)
+            }
+            None => (0.0, 0.0),
+        };
+        let stroke_offsets: Vec<_> = stroke_offsets(self.stroke.as_ref())
+            .into_iter()
+            .map(|(x, y)| (x + origin.0, y + origin.1))
+            .collect();
+
+        // Shadow is drawn on top of the background, blurring them together gives the same
+        // result as blurring the shadow alone, because the background is uniform.
+        let load = match &self.shadow {
+            Some(shadow) => {
+                let shadow_offsets: Vec<_> = iter::once(origin)
+                    .chain(stroke_offsets.iter().copied())
+                    .map(|(x, y)| (x + shadow.offset_x, y + shadow.offset_y))
+                    .collect();
+                let areas = self.buffer.single_color_text_areas(
+                    &shadow_offsets,
+                    &shadow.color,
+                    self.resolution,
+                );
+                let clear = LoadOp::Clear(self.background_color);
+                if shadow.blur_radius < MIN_BLUR_RADIUS {
+                    draw_ctx.draw(areas, target_state.view(), clear, &mut encoder);
+                } else {
+                    let shadow_state = self.shadow_texture.ensure_size(wgpu_ctx, self.resolution);
+                    draw_ctx.draw(areas, shadow_state.view(), clear, &mut encoder);
+                    self.blurred_shadow.render(
+                        wgpu_ctx,
+                        &renderer_ctx.renderers.layout.blur,
+                        shadow_state,
+                        [shadow.blur_radius, shadow.blur_radius],
+                        &mut encoder,
+                    );
+                    if let Some(blurred) = self.blurred_shadow.output_state() {
+                        encoder.copy_texture_to_texture(
+                            blurred.texture().as_image_copy(),
+                            target_state.texture().as_image_copy(),
+                            blurred.texture().size(),
+                        );
+                    }
+                }
+                LoadOp::Load
+            }
+            None => LoadOp::Clear(self.background_color),
+        };
+
+        let mut areas = match &self.stroke {
+            Some(stroke) => {
+                self.buffer
+                    .single_color_text_areas(&stroke_offsets, &stroke.color, self.resolution)
+            }
+            None => vec![],
+        };
+        match &self.gradient {
+            Some(gradient) => {
+                draw_ctx.draw(areas, target_state.view(), load, &mut encoder);
+
+                let white = RGBAColor(255, 255, 255, 255);
+                let fill_areas =
+                    self.buffer
+                        .single_color_text_areas(&[origin], &white, self.resolution);
+                let fill_state = self.fill_texture.ensure_size(wgpu_ctx, self.resolution);
+                draw_ctx.draw(
+                    fill_areas,
+                    fill_state.view(),
+                    LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    &mut encoder,
+                );
+                renderer_ctx.renderers.text_gradient.render(
+                    wgpu_ctx,
+                    gradient,
+                    fill_state,
+                    target_state.view(),
+                    &mut encoder,
+                );
+            }
+            None => {
+                areas.extend(
+                    self.buffer
+                        .text_areas(&[origin], &self.color, self.resolution),
+                );
+                draw_ctx.draw(areas, target_state.view(), load, &mut encoder);
+            }
+        }
+
+        wgpu_ctx.queue.submit(Some(encoder.finish()));
+
+        // Scrolling text is rendered on every frame.
+        match self.ticker {
+            Some(_) => {
+                resources.atlas.trim();
+                self.draw_resources = Some(resources);
+            }
+            None => self.was_rendered = true,
+        }
+    }
+}
+
+struct TextDrawResources {
+    atlas: TextAtlas,
+    viewport: Viewport,
+}
+
+impl TextDrawResources {
+    fn new(wgpu_ctx: &WgpuCtx, cache: &Cache, resolution: Resolution) -> Self {
+        let mut viewport = Viewport::new(&wgpu_ctx.device, cache);
+        viewport.update(&wgpu_ctx.queue, resolution.into());
+
+        let swapchain_format = wgpu_ctx.default_view_format();
+        let atlas = TextAtlas::new(&wgpu_ctx.device, &wgpu_ctx.queue, cache, swapchain_format);
+        Self { atlas, viewport }
+    }
+}
+
+struct TextDrawCtx<'a> {
+    wgpu_ctx: &'a WgpuCtx,
+    font_system: &'a mut FontSystem,
+    swash_cache: &'a mut SwashCache,
+    atlas: &'a mut TextAtlas,
+    viewport: &'a Viewport,
+}
+
+impl TextDrawCtx<'_> {
+    /// Every call uses a separate text renderer, so multiple passes can be recorded before
+    /// the encoder is submitted.
+    fn draw(
+        &mut self,
+        areas: Vec<TextArea<'_>>,
+        view: &wgpu::TextureView,
+        load: LoadOp<wgpu::Color>,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
         let mut text_renderer = glyphon::TextRenderer::new(
-            &mut atlas,
-            &renderer_ctx.wgpu_ctx.device,
+            self.atlas,
+            &self.wgpu_ctx.device,
             MultisampleState::default(),
             None,
         );
-
         text_renderer
             .prepare(
-                &renderer_ctx.wgpu_ctx.device,
-                &renderer_ctx.wgpu_ctx.queue,
-                font_system,
-                &mut atlas,
-                &viewport,
-                [TextArea {
-                    buffer: &self.buffer.0,
-                    left: 0 as f32,
-                    top: 0 as f32,
-                    scale: 1.0,
-                    bounds: TextBounds {
-                        left: 0,
-                        top: 0,
-                        right: self.resolution.width as i32,
-                        bottom: self.resolution.height as i32,
-                    },
-                    default_color: Color::rgb(255, 255, 255),
-                    custom_glyphs: &[],
-                }],
-                swash_cache,
+                &self.wgpu_ctx.device,
+                &self.wgpu_ctx.queue,
+                self.font_system,
+                self.atlas,
+                self.viewport,
+                areas,
+                self.swash_cache,
             )
             .unwrap();
 
-        let mut encoder =
-            renderer_ctx
-                .wgpu_ctx
-                .device
-                .create_command_encoder(&CommandEncoderDescriptor {
-                    label: Some("Text renderer encoder"),
-                });
-
-        let target_state = target.ensure_size(renderer_ctx.wgpu_ctx, self.resolution);
-        let view = &target_state.view();
-        {
-            let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
-                label: None,
-                color_attachments: &[Some(RenderPassColorAttachment {
-                    view,
-                    resolve_target: None,
-                    ops: Operations {
-                        load: LoadOp::Clear(self.background_color),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-                multiview_mask: None,
-            });
-
-            text_renderer.render(&atlas, &viewport, &mut pass).unwrap();
-        }
-
-        renderer_ctx.wgpu_ctx.queue.submit(Some(encoder.finish()));
-        self.was_rendered = true;
+        let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        text_renderer
+            .render(self.atlas, self.viewport, &mut pass)
+            .unwrap();
     }
 }
 
+/// Offsets at which the text is drawn in the stroke color. Samples on each ring and the
+/// rings themselves are at most 2 pixels apart, so the outline does not have gaps.
+///
+/// Overlapping copies accumulate alpha, so semi-transparent strokes are more opaque
+/// than their color.
+fn stroke_offsets(stroke: Option<&TextStroke>) -> Vec<(f32, f32)> {
+    let Some(stroke) = stroke else {
+        return vec![];
+    };
+    let rings = (stroke.width / 2.0).ceil() as usize;
+    (1..=rings)
+        .flat_map(|ring| {
+            let radius = stroke.width * ring as f32 / rings as f32;
+            let samples = ((PI * radius).ceil() as usize).clamp(8, 64);
+            (0..samples).map(move |sample| {
+                let angle = 2.0 * PI * sample as f32 / samples as f32;
+                (radius * angle.cos(), radius * angle.sin())
+            })
+        })
+        .collect()
+}
+
+/// Number of layouts checked to find the font size at which the text fits.
+const FIT_ITERATIONS: usize = 12;
+
+#[derive(Clone)]
 pub(crate) struct TextParams {
     content: Arc<str>,
     attributes: AttrsOwned,
+    /// If not empty, it's used instead of `content`.
+    spans: Vec<(String, AttrsOwned)>,
     font_size: f32,
     line_height: f32,
-    align: glyphon::cosmic_text::Align,
+    /// `None` aligns each line to the start of its paragraph.
+    align: Option<glyphon::cosmic_text::Align>,
     wrap: glyphon::cosmic_text::Wrap,
+    stroke: Option<TextStroke>,
+    shadow: Option<TextShadow>,
+    /// Scrolling text is laid out in a single line of unlimited width.
+    scrolling: bool,
+    fit: TextFit,
+    /// Text is also drawn in a single color for the stroke, the shadow or the gradient fill.
+    single_color: bool,
 }
 
-impl From<&TextComponent> for TextParams {
-    fn from(text: &TextComponent) -> Self {
-        let RGBAColor(r, g, b, a) = text.color;
-        let color = glyphon::Color::rgba(r, g, b, a);
+/// Space between the text and the edges of the texture reserved for the stroke and
+/// the shadow.
+#[derive(Debug, Clone, Copy)]
+struct TextMargin {
+    top: f32,
+    right: f32,
+    bottom: f32,
+    left: f32,
+}
+
+impl TextMargin {
+    fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+
+    fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+}
+
+impl TextParams {
+    fn new_buffer(&self, font_system: &mut FontSystem, span_colors: bool) -> Buffer {
+        let mut buffer = Buffer::new(font_system, Metrics::new(self.font_size, self.line_height));
+        let attributes = self.attributes.as_attrs();
+        match self.spans.is_empty() {
+            true => buffer.set_text(
+                font_system,
+                &self.content,
+                &attributes,
+                Shaping::Advanced,
+                None,
+            ),
+            false => {
+                let spans = self.spans.iter().map(|(content, attributes)| {
+                    let mut attributes = attributes.as_attrs();
+                    if !span_colors {
+                        attributes.color_opt = None;
+                    }
+                    (content.as_str(), attributes)
+                });
+                buffer.set_rich_text(font_system, spans, &attributes, Shaping::Advanced, None);
+            }
+        }
+        buffer.set_wrap(font_system, self.wrap);
+        buffer
+    }
 
-        let family = glyphon::FamilyOwned::Name(text.font_family.clone().into());
+    /// Lays out the text without span colors the same way as `buffer`.
+    fn single_color_buffer(
+        &self,
+        font_system: &mut FontSystem,
+        buffer: &Buffer,
+    ) -> Option<Arc<Buffer>> {
+        let has_span_colors = self
+            .spans
+            .iter()
+            .any(|(_, attributes)| attributes.color_opt.is_some());
+        if !self.single_color || !has_span_colors {
+            return None;
+        }
+        let mut single_color_buffer = self.new_buffer(font_system, false);
+        let (width, height) = buffer.size();
+        single_color_buffer.set_size(font_system, width, height);
+        for line in &mut single_color_buffer.lines {
+            line.set_align(self.align);
+        }
+        single_color_buffer.shape_until_scroll(font_system, false);
+        Some(single_color_buffer.into())
+    }
+
+    /// Font size, line height and sizes of the spans multiplied by `scale`.
+    fn scaled(&self, scale: f32) -> Self {
+        let spans = self
+            .spans
+            .iter()
+            .map(|(content, attributes)| {
+                let mut attributes = attributes.clone();
+                attributes.metrics_opt = attributes
+                    .metrics_opt
+                    .map(|metrics| Metrics::from(metrics).scale(scale).into());
+                (content.clone(), attributes)
+            })
+            .collect();
+        Self {
+            spans,
+            font_size: self.font_size * scale,
+            line_height: self.line_height * scale,
+            ..self.clone()
+        }
+    }
 
-        let style = match text.style {
+    fn margin(&self) -> TextMargin {
+        let stroke_width = self.stroke.as_ref().map_or(0.0, |stroke| stroke.width);
+        let Some(shadow) = &self.shadow else {
+            return TextMargin {
+                top: stroke_width.ceil(),
+                right: stroke_width.ceil(),
+                bottom: stroke_width.ceil(),
+                left: stroke_width.ceil(),
+            };
+        };
+        // Gaussian blur is not visible beyond 3 standard deviations
+        let shadow_extent = stroke_width + 3.0 * shadow.blur_radius;
+        let margin = |offset: f32| f32::max(stroke_width, shadow_extent + offset).ceil();
+        TextMargin {
+            top: margin(-shadow.offset_y),
+            right: margin(shadow.offset_x),
+            bottom: margin(shadow.offset_y),
+            left: margin(-shadow.offset_x),
+        }
+    }
+}
+
+impl From<&TextStyle> for glyphon::Style {
+    fn from(style: &TextStyle) -> Self {
+        match style {
             TextStyle::Normal => glyphon::Style::Normal,
             TextStyle::Italic => glyphon::Style::Italic,
             TextStyle::Oblique => glyphon::Style::Oblique,
-        };
-        let weight = match text.weight {
+        }
+    }
+}
+
+impl From<&TextWeight> for glyphon::Weight {
+    fn from(weight: &TextWeight) -> Self {
+        match weight {
             TextWeight::Thin => glyphon::Weight::THIN,
             TextWeight::ExtraLight => glyphon::Weight::EXTRA_LIGHT,
             TextWeight::Light => glyphon::Weight::LIGHT,
@@ -198,41 +592,93 @@ impl From<&TextComponent> for TextParams {
             TextWeight::Bold => glyphon::Weight::BOLD,
             TextWeight::ExtraBold => glyphon::Weight::EXTRA_BOLD,
             TextWeight::Black => glyphon::Weight::BLACK,
+        }
+    }
+}
+
+fn text_attributes(
+    font_family: &Arc<str>,
+    style: &TextStyle,
+    weight: &TextWeight,
+    metrics: Option<Metrics>,
+    color: Option<&RGBAColor>,
+) -> AttrsOwned {
+    glyphon::AttrsOwned {
+        color_opt: color.map(|RGBAColor(r, g, b, a)| Color::rgba(*r, *g, *b, *a)),
+        family_owned: glyphon::FamilyOwned::Name(font_family.clone().into()),
+        stretch: Default::default(),
+        style: style.into(),
+        weight: weight.into(),
+        metadata: Default::default(),
+        cache_key_flags: glyphon::cosmic_text::CacheKeyFlags::empty(),
+        metrics_opt: metrics.map(Into::into),
+        font_features: FontFeatures::default(),
+        letter_spacing_opt: None,
+    }
+}
+
+impl From<&TextComponent> for TextParams {
+    fn from(text: &TextComponent) -> Self {
+        let wrap = match (text.ticker, &text.wrap) {
+            (Some(_), _) | (None, TextWrap::None) => glyphon::cosmic_text::Wrap::None,
+            (None, TextWrap::Glyph) => glyphon::cosmic_text::Wrap::Glyph,
+            (None, TextWrap::Word) => glyphon::cosmic_text::Wrap::Word,
         };
-        let wrap = match text.wrap {
-            TextWrap::None => glyphon::cosmic_text::Wrap::None,
-            TextWrap::Glyph => glyphon::cosmic_text::Wrap::Glyph,
-            TextWrap::Word => glyphon::cosmic_text::Wrap::Word,
-        };
-        let align = match text.align {
+        let align = text.align.map(|align| match align {
             HorizontalAlign::Left => glyphon::cosmic_text::Align::Left,
             HorizontalAlign::Right => glyphon::cosmic_text::Align::Right,
             HorizontalAlign::Justified => glyphon::cosmic_text::Align::Justified,
             HorizontalAlign::Center => glyphon::cosmic_text::Align::Center,
-        };
+        });
+
+        // Color of the component is defined per text area, so the same buffer can be used
+        // to render the stroke and the shadow.
+        let attributes = text_attributes(&text.font_family, &text.style, &text.weight, None, None);
+        let spans = text
+            .spans
+            .iter()
+            .enumerate()
+            .map(|(index, span)| {
+                let content = span_with_base_direction(&span.text, text.direction, index == 0);
+                (content, span_attributes(text, span))
+            })
+            .collect();
 
         Self {
-            attributes: glyphon::AttrsOwned {
-                color_opt: Some(color),
-                family_owned: family,
-                stretch: Default::default(),
-                style,
-                weight,
-                metadata: Default::default(),
-                cache_key_flags: glyphon::cosmic_text::CacheKeyFlags::empty(),
-                metrics_opt: None,
-                font_features: FontFeatures::default(),
-                letter_spacing_opt: None,
+            attributes,
+            spans,
+            content: match with_base_direction(&text.text, text.direction) {
+                Cow::Borrowed(_) => text.text.clone(),
+                Cow::Owned(content) => content.into(),
             },
-            content: text.text.clone(),
             font_size: text.font_size,
             line_height: text.line_height,
             align,
             wrap,
+            stroke: text.stroke.clone(),
+            shadow: text.shadow.clone(),
+            scrolling: text.ticker.is_some(),
+            fit: text.fit,
+            single_color: text.stroke.is_some() || text.shadow.is_some() || text.gradient.is_some(),
         }
     }
 }
 
+/// Properties that are not defined on the span are taken from the component. Line
+/// height of the span keeps the same ratio to its font size as in the component.
+fn span_attributes(text: &TextComponent, span: &TextSpan) -> AttrsOwned {
+    let metrics = span
+        .font_size
+        .map(|font_size| Metrics::new(font_size, font_size * text.line_height / text.font_size));
+    text_attributes(
+        span.font_family.as_ref().unwrap_or(&text.font_family),
+        span.style.as_ref().unwrap_or(&text.style),
+        span.weight.as_ref().unwrap_or(&text.weight),
+        metrics,
+        span.color.as_ref(),
+    )
+}
+
 pub struct TextRendererCtx {
     font_system: Mutex<FontSystem>,
     swash_cache: Mutex<SwashCache>,
@@ -285,20 +731,28 @@ impl TextRendererCtx {
         text_resolution: TextDimensions,
     ) -> (TextBuffer, Resolution) {
         let font_system = &mut self.font_system.lock().unwrap();
-        let mut buffer = Buffer::new(
-            font_system,
-            Metrics::new(text_params.font_size, text_params.line_height),
-        );
+        let text_params = match text_params.fit {
+            TextFit::None => text_params,
+            fit => {
+                let scale = Self::fit_scale(font_system, &text_params, fit, text_resolution);
+                text_params.scaled(scale)
+            }
+        };
+        let mut buffer = text_params.new_buffer(font_system, true);
 
-        buffer.set_text(
-            font_system,
-            &text_params.content,
-            &text_params.attributes.as_attrs(),
-            Shaping::Advanced,
-            None,
-        );
-        buffer.set_wrap(font_system, text_params.wrap);
+        let margin = text_params.margin();
+        if text_params.scrolling {
+            return Self::layout_scrolling_text(
+                font_system,
+                buffer,
+                &text_params,
+                margin,
+                text_resolution,
+            );
+        }
 
+        // Fitted textures are extended by the margin, otherwise the text is laid out in
+        // a smaller area.
         let texture_size = match text_resolution {
             TextDimensions::Fixed { width, height } => Resolution {
                 width: width as usize,
@@ -310,14 +764,20 @@ impl TextRendererCtx {
             } => {
                 buffer.set_size(font_system, Some(max_width), Some(max_height));
                 buffer.shape_until_scroll(font_system, false);
-                Self::get_text_resolution(
+                let text_size = Self::get_text_resolution(
                     buffer.lines.iter(),
                     text_params.line_height,
                     text_params.font_size,
-                )
+                );
+
+                Resolution {
+                    width: text_size.width + margin.horizontal() as usize,
+                    height: text_size.height + margin.vertical() as usize,
+                }
             }
             TextDimensions::FittedColumn { width, max_height } => {
-                buffer.set_size(font_system, Some(width), Some(max_height));
+                let text_width = f32::max(width - margin.horizontal(), 0.0);
+                buffer.set_size(font_system, Some(text_width), Some(max_height));
                 buffer.shape_until_scroll(font_system, false);
                 let text_size = Self::get_text_resolution(
                     buffer.lines.iter(),
@@ -327,22 +787,136 @@ impl TextRendererCtx {
 
                 Resolution {
                     width: width as usize,
-                    height: text_size.height,
+                    height: text_size.height + margin.vertical() as usize,
                 }
             }
         };
 
         buffer.set_size(
             font_system,
-            Some(texture_size.width as f32),
-            Some(texture_size.height as f32 + text_params.line_height),
+            Some(f32::max(
+                texture_size.width as f32 - margin.horizontal(),
+                0.0,
+            )),
+            Some(texture_size.height as f32 + text_params.line_height - margin.vertical()),
         );
         for line in &mut buffer.lines {
-            line.set_align(Some(text_params.align));
+            line.set_align(text_params.align);
         }
         buffer.shape_until_scroll(font_system, false);
 
-        (TextBuffer(buffer.into()), texture_size)
+        let buffer = TextBuffer {
+            single_color_buffer: text_params.single_color_buffer(font_system, &buffer),
+            buffer: buffer.into(),
+            left: margin.left,
+            top: margin.top,
+            width: texture_size.width as f32,
+        };
+        (buffer, texture_size)
+    }
+
+    /// Largest scale of the font size at which the text fits within the bounds of the
+    /// texture. It's found with a binary search, so the result is approximate. Font size
+    /// does not go below 1 pixel, even if the text does not fit.
+    fn fit_scale(
+        font_system: &mut FontSystem,
+        text_params: &TextParams,
+        fit: TextFit,
+        text_resolution: TextDimensions,
+    ) -> f32 {
+        let (max_width, max_height) = match text_resolution {
+            TextDimensions::Fixed { width, height } => (width, height),
+            TextDimensions::Fitted {
+                max_width,
+                max_height,
+            } => (max_width, max_height),
+            TextDimensions::FittedColumn { width, max_height } => (width, max_height),
+        };
+        let margin = text_params.margin();
+        let text_width = f32::max(max_width - margin.horizontal(), 0.0);
+        let text_height = max_height - margin.vertical();
+
+        let mut fits = |scale: f32| {
+            let text_params = text_params.scaled(scale);
+            let mut buffer = text_params.new_buffer(font_system, false);
+            buffer.set_size(font_system, Some(text_width), None);
+            buffer.shape_until_scroll(font_system, false);
+            let text_size = Self::get_text_resolution(
+                buffer.lines.iter(),
+                text_params.line_height,
+                text_params.font_size,
+            );
+            // Wrapped lines can still be wider than the texture if a single word does not fit.
+            text_size.width as f32 <= text_width && text_size.height as f32 <= text_height
+        };
+
+        let min_scale = f32::min(1.0 / text_params.font_size, 1.0);
+        let (mut low, mut high) = match fit {
+            TextFit::None | TextFit::Shrink => (min_scale, 1.0),
+            // Single line of text can't be higher than the texture.
+            TextFit::Fill => (
+                min_scale,
+                f32::max(text_height / text_params.line_height, 1.0),
+            ),
+        };
+        if fits(high) {
+            return high;
+        }
+        for _ in 0..FIT_ITERATIONS {
+            let scale = (low + high) / 2.0;
+            match fits(scale) {
+                true => low = scale,
+                false => high = scale,
+            }
+        }
+        low
+    }
+
+    /// Text is not wrapped, so it can be wider than the texture. Width of the texture is
+    /// the same as for not scrolling text, but its height is based only on the text.
+    fn layout_scrolling_text(
+        font_system: &mut FontSystem,
+        mut buffer: Buffer,
+        text_params: &TextParams,
+        margin: TextMargin,
+        text_resolution: TextDimensions,
+    ) -> (TextBuffer, Resolution) {
+        buffer.set_size(font_system, None, None);
+        buffer.shape_until_scroll(font_system, false);
+        let text_size = Self::get_text_resolution(
+            buffer.lines.iter(),
+            text_params.line_height,
+            text_params.font_size,
+        );
+        let text_width = text_size.width as f32 + margin.horizontal();
+        let text_height = text_size.height as f32 + margin.vertical();
+
+        let texture_size = match text_resolution {
+            TextDimensions::Fixed { width, height } => Resolution {
+                width: width as usize,
+                height: height as usize,
+            },
+            TextDimensions::Fitted {
+                max_width,
+                max_height,
+            } => Resolution {
+                width: f32::min(text_width, max_width) as usize,
+                height: f32::min(text_height, max_height) as usize,
+            },
+            TextDimensions::FittedColumn { width, max_height } => Resolution {
+                width: width as usize,
+                height: f32::min(text_height, max_height) as usize,
+            },
+        };
+
+        let buffer = TextBuffer {
+            single_color_buffer: text_params.single_color_buffer(font_system, &buffer),
+            buffer: buffer.into(),
+            left: margin.left,
+            top: margin.top,
+            width: text_width,
+        };
+        (buffer, texture_size)
     }
 
     fn get_text_resolution<'a, I: Iterator<Item = &'a glyphon::BufferLine>>(
@@ -351,24 +925,95 @@ impl TextRendererCtx {
         font_size: f32,
     ) -> Resolution {
         let mut width = 0;
-        let mut lines_count = 0u32;
+        let mut lines_height = 0.0;
 
         for line in lines {
             if let Some(layout) = line.layout_opt() {
                 for layout_line in layout {
-                    lines_count += 1;
+                    // Lines with spans of a different font size have their own line height
+                    lines_height += layout_line.line_height_opt.unwrap_or(line_height).ceil();
                     width = max(width, layout_line.w.ceil() as usize);
                 }
             }
         }
 
         let last_line_padding = font_size / 5.0;
-        let height = (lines_count as f32 * line_height.ceil() + last_line_padding) as usize;
+        let height = (lines_height + last_line_padding) as usize;
         Resolution { width, height }
     }
 }
 
+/// Shaping with `Shaping::Advanced` runs the Unicode Bidirectional Algorithm on every
+/// paragraph, which detects its base direction from the first strong character. To force
+/// the direction, each paragraph is prefixed with an invisible LRM or RLM mark.
+fn with_base_direction(content: &str, direction: TextDirection) -> Cow<'_, str> {
+    match direction {
+        TextDirection::Auto => Cow::Borrowed(content),
+        direction => Cow::Owned(span_with_base_direction(content, direction, true)),
+    }
+}
+
+/// Same as [`with_base_direction`], but only the first span starts a paragraph. Other
+/// paragraphs start after a newline inside of a span.
+fn span_with_base_direction(content: &str, direction: TextDirection, first: bool) -> String {
+    let mark = match direction {
+        TextDirection::Auto => return content.to_string(),
+        TextDirection::LeftToRight => '\u{200E}',
+        TextDirection::RightToLeft => '\u{200F}',
+    };
+    let content = content.replace('\n', &format!("\n{mark}"));
+    match first {
+        true => format!("{mark}{content}"),
+        false => content,
+    }
+}
+
 fn rgba_to_wgpu_color(ctx: &WgpuCtx, rgba_color: &RGBAColor) -> wgpu::Color {
     let [r, g, b, a] = convert_to_shader_color(ctx, rgba_color);
     wgpu::Color { r, g, b, a }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_direction_marks() {
+        assert_eq!(
+            with_base_direction("abc\nשלום", TextDirection::Auto),
+            "abc\nשלום"
+        );
+        assert_eq!(
+            with_base_direction("abc\nשלום", TextDirection::RightToLeft),
+            "\u{200F}abc\n\u{200F}שלום"
+        );
+        assert_eq!(
+            with_base_direction("abc", TextDirection::LeftToRight),
+            "\u{200E}abc"
+        );
+    }
+
+    #[test]
+    fn ticker_offset() {
+        let ticker = |direction, repeat| TickerParams {
+            ticker: TextTicker {
+                speed: 100.0,
+                direction,
+                repeat,
+            },
+            start_pts: Duration::from_secs(1),
+        };
+        let offset =
+            |ticker: &TickerParams, ms| ticker.offset(Duration::from_millis(ms), 300.0, 500.0);
+
+        let left = ticker(TickerDirection::Left, true);
+        assert_eq!(offset(&left, 0), 500.0);
+        assert_eq!(offset(&left, 3000), 300.0);
+        assert_eq!(offset(&left, 9000), 500.0);
+        assert_eq!(offset(&left, 10000), 400.0);
+
+        let right = ticker(TickerDirection::Right, false);
+        assert_eq!(offset(&right, 3000), -100.0);
+        assert_eq!(offset(&right, 20000), 500.0);
+    }
+}
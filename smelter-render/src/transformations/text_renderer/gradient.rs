@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use crate::{
+    Resolution,
+    scene::{LinearGradient, MAX_GRADIENT_STOPS, RGBAColor},
+    state::node_texture::NodeTextureState,
+    wgpu::{
+        WgpuCtx, WgpuErrorScope,
+        common_pipeline::{self, CreateShaderError},
+    },
+};
+
+const LABEL: Option<&str> = Some("Text gradient");
+
+/// Has to match `Gradient` in the shader.
+fn to_immediates(gradient: &LinearGradient, resolution: Resolution, srgb_output: bool) -> [u8; 80] {
+    // Gradient line passes through the center of the texture and is long enough for
+    // its perpendicular lines at the ends to pass through the corners, as in CSS.
+    let (sin, cos) = gradient.angle_degrees.to_radians().sin_cos();
+    let (width, height) = (resolution.width as f32, resolution.height as f32);
+    let length = (width * sin).abs() + (height * cos).abs();
+    let direction = match length > 0.0 {
+        true => [sin / length, -cos / length],
+        false => [0.0, 0.0],
+    };
+
+    let stops = &gradient.stops[..gradient.stops.len().min(MAX_GRADIENT_STOPS)];
+    let mut data = [0u8; 80];
+    data[0..4].copy_from_slice(&direction[0].to_le_bytes());
+    data[4..8].copy_from_slice(&direction[1].to_le_bytes());
+    data[8..12].copy_from_slice(&(stops.len() as u32).to_le_bytes());
+    data[12..16].copy_from_slice(&(srgb_output as u32).to_le_bytes());
+    for (index, stop) in stops.iter().enumerate() {
+        let RGBAColor(r, g, b, a) = stop.color;
+        let position_offset = 16 + 4 * index;
+        let color_offset = 48 + 4 * index;
+        data[position_offset..position_offset + 4].copy_from_slice(&stop.position.to_le_bytes());
+        data[color_offset..color_offset + 4].copy_from_slice(&[r, g, b, a]);
+    }
+    data
+}
+
+/// Fills the text with a linear gradient. Text has to be rendered in white into a
+/// separate texture first, the gradient is blended onto the target where that
+/// texture is covered by glyphs.
+pub(crate) struct TextGradientShader {
+    pipeline: wgpu::RenderPipeline,
+}
+
+impl TextGradientShader {
+    pub fn new(wgpu_ctx: &Arc<WgpuCtx>) -> Result<Self, CreateShaderError> {
+        let scope = WgpuErrorScope::push(&wgpu_ctx.device);
+
+        let layout = wgpu_ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: LABEL,
+                bind_group_layouts: &[Some(&wgpu_ctx.format.single_texture_layout)],
+                immediate_size: 80,
+            });
+        let shader = wgpu_ctx
+            .device
+            .create_shader_module(wgpu::include_wgsl!("gradient.wgsl"));
+        let pipeline = common_pipeline::create_render_pipeline(
+            "Text gradient",
+            &wgpu_ctx.device,
+            &layout,
+            &shader,
+            wgpu_ctx.default_view_format(),
+        );
+
+        scope.pop()?;
+        Ok(Self { pipeline })
+    }
+
+    pub fn render(
+        &self,
+        wgpu_ctx: &Arc<WgpuCtx>,
+        gradient: &LinearGradient,
+        mask: &NodeTextureState,
+        target: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let mask_bg = wgpu_ctx
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: LABEL,
+                layout: &wgpu_ctx.format.single_texture_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(mask.view()),
+                }],
+            });
+        let immediates = to_immediates(
+            gradient,
+            mask.resolution(),
+            wgpu_ctx.default_view_format().is_srgb(),
+        );
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: LABEL,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                view: target,
+                resolve_target: None,
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_immediates(0, &immediates);
+        render_pass.set_bind_group(0, &mask_bg, &[]);
+        wgpu_ctx.plane.draw(&mut render_pass);
+    }
+}
@@ -0,0 +1,76 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+    output.position = vec4(input.position, 1.0);
+    return output;
+}
+
+// Text rendered in white on a transparent background. Only alpha is used.
+@group(0) @binding(0) var mask: texture_2d<f32>;
+
+struct Gradient {
+    // Direction of the gradient line divided by its length in pixels.
+    direction: vec2<f32>,
+    stops_count: u32,
+    // 1 if the output is written to an sRGB view and has to be converted to linear.
+    srgb_output: u32,
+    positions: array<vec4<f32>, 2>,
+    // RGBA8 colors packed into u32, red in the lowest byte.
+    colors: array<vec4<u32>, 2>,
+}
+
+var<immediate> gradient: Gradient;
+
+fn stop_position(index: u32) -> f32 {
+    return gradient.positions[index / 4u][index % 4u];
+}
+
+// Premultiplied sRGB color of the stop.
+fn stop_color(index: u32) -> vec4<f32> {
+    let color = unpack4x8unorm(gradient.colors[index / 4u][index % 4u]);
+    return vec4(color.rgb * color.a, color.a);
+}
+
+// Colors are interpolated in premultiplied sRGB, the same way as in CSS.
+fn gradient_color(t: f32) -> vec4<f32> {
+    if t <= stop_position(0u) {
+        return stop_color(0u);
+    }
+    for (var i = 1u; i < gradient.stops_count; i++) {
+        let end = stop_position(i);
+        if t <= end {
+            let start = stop_position(i - 1u);
+            let progress = select(1.0, (t - start) / (end - start), end > start);
+            return mix(stop_color(i - 1u), stop_color(i), progress);
+        }
+    }
+    return stop_color(gradient.stops_count - 1u);
+}
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3(2.4));
+    return select(high, low, color <= vec3(0.04045));
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let dim = vec2<f32>(textureDimensions(mask));
+    let coverage = textureLoad(mask, vec2<i32>(input.position.xy), 0).a;
+
+    let t = dot(input.position.xy - dim / 2.0, gradient.direction) + 0.5;
+    var color = gradient_color(t);
+    if gradient.srgb_output == 1u && color.a > 0.0 {
+        color = vec4(srgb_to_linear(color.rgb / color.a) * color.a, color.a);
+    }
+    return color * coverage;
+}
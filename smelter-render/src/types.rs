@@ -37,6 +37,9 @@ pub enum FrameData {
     Nv12(NvPlanes),
     Bgra(bytes::Bytes),
     Argb(bytes::Bytes),
+    /// 10-bit 4:2:2 YCbCr packed as v210. Rows are padded to a multiple of 48 pixels
+    /// (128 bytes).
+    InterleavedV210(bytes::Bytes),
 }
 
 #[derive(Clone)]
@@ -97,6 +97,26 @@ pub fn create_render_pipeline(
     pipeline_layout: &wgpu::PipelineLayout,
     shader_module: &wgpu::ShaderModule,
     output_format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    create_render_pipeline_with_blend(
+        label,
+        device,
+        pipeline_layout,
+        shader_module,
+        output_format,
+        Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+    )
+}
+
+/// Same as [`create_render_pipeline`], but with a custom blend state. `None` replaces
+/// the content of the target.
+pub fn create_render_pipeline_with_blend(
+    label: &str,
+    device: &wgpu::Device,
+    pipeline_layout: &wgpu::PipelineLayout,
+    shader_module: &wgpu::ShaderModule,
+    output_format: wgpu::TextureFormat,
+    blend: Option<wgpu::BlendState>,
 ) -> wgpu::RenderPipeline {
     device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
         label: Some(label),
@@ -122,7 +142,7 @@ pub fn create_render_pipeline(
             targets: &[Some(wgpu::ColorTargetState {
                 format: output_format,
                 write_mask: wgpu::ColorWrites::all(),
-                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                blend,
             })],
             compilation_options: wgpu::PipelineCompilationOptions::default(),
         }),
@@ -5,6 +5,7 @@ use nv12_to_rgba::Nv12ToRgbaConverter;
 use crate::wgpu::format::{
     argb_to_rgba::ArgbToRgbaConverter, bgra_to_rgba::BgraToRgbaConverter,
     rgba_rescale::RgbaRescaler, rgba_to_nv12::RgbaToNv12Converter,
+    v210_to_rgba::V210ToRgbaConverter,
 };
 
 use self::{planar_yuv_to_rgba::PlanarYuvToRgbaConverter, rgba_to_yuv::RgbaToYuvConverter};
@@ -23,6 +24,7 @@ mod planar_yuv_to_rgba;
 pub mod rgba_rescale;
 mod rgba_to_nv12;
 mod rgba_to_yuv;
+mod v210_to_rgba;
 
 #[derive(Debug)]
 pub struct TextureFormat {
@@ -32,6 +34,7 @@ pub struct TextureFormat {
     pub nv12_to_rgba_linear: Nv12ToRgbaConverter,
     pub bgra_to_rgba_linear: BgraToRgbaConverter,
     pub argb_to_rgba_linear: ArgbToRgbaConverter,
+    pub v210_to_rgba_linear: V210ToRgbaConverter,
 
     pub rgba_to_yuv: RgbaToYuvConverter,
     pub rgba_to_nv12: RgbaToNv12Converter,
@@ -79,6 +82,12 @@ impl TextureFormat {
             wgpu::TextureFormat::Rgba8Unorm,
         );
 
+        let v210_to_rgba_linear = V210ToRgbaConverter::new(
+            device,
+            &single_texture_layout,
+            wgpu::TextureFormat::Rgba8Unorm,
+        );
+
         let rgba_to_yuv = RgbaToYuvConverter::new(device, &single_texture_layout);
         let rgba_to_nv12 = RgbaToNv12Converter::new(device, &single_texture_layout);
         let rgba_rescale_linear = RgbaRescaler::new(
@@ -99,6 +108,7 @@ impl TextureFormat {
             nv12_to_rgba_linear,
             bgra_to_rgba_linear,
             argb_to_rgba_linear,
+            v210_to_rgba_linear,
 
             rgba_to_yuv,
             rgba_to_nv12,
@@ -0,0 +1,93 @@
+use crate::wgpu::common_pipeline::{PRIMITIVE_STATE, Vertex};
+
+use super::WgpuCtx;
+
+#[derive(Debug)]
+pub struct V210ToRgbaConverter {
+    pipeline: wgpu::RenderPipeline,
+}
+
+impl V210ToRgbaConverter {
+    pub fn new(
+        device: &wgpu::Device,
+        v210_texture_bind_group_layout: &wgpu::BindGroupLayout,
+        dst_view_format: wgpu::TextureFormat,
+    ) -> Self {
+        let shader_module = device.create_shader_module(wgpu::include_wgsl!("v210_to_rgba.wgsl"));
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("v210 to RGBA color converter render pipeline layout"),
+            bind_group_layouts: &[Some(v210_texture_bind_group_layout)],
+            immediate_size: 0,
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("v210 to RGBA color converter render pipeline"),
+            layout: Some(&pipeline_layout),
+            primitive: PRIMITIVE_STATE,
+
+            vertex: wgpu::VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                buffers: &[Some(Vertex::LAYOUT)],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+
+            fragment: Some(wgpu::FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: dst_view_format,
+                    write_mask: wgpu::ColorWrites::all(),
+                    blend: None,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview_mask: None,
+            depth_stencil: None,
+            cache: None,
+        });
+
+        Self { pipeline }
+    }
+
+    pub fn convert(&self, ctx: &WgpuCtx, src_bg: &wgpu::BindGroup, dst_view: &wgpu::TextureView) {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("v210 to RGBA color converter encoder"),
+            });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("v210 to RGBA color converter render pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    view: dst_view,
+                    resolve_target: None,
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_bind_group(0, src_bg, &[]);
+
+            ctx.plane.draw(&mut render_pass);
+        }
+
+        ctx.queue.submit(Some(encoder.finish()));
+    }
+}
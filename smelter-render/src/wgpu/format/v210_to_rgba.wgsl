@@ -0,0 +1,84 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+
+    output.position = vec4(input.position, 1.0);
+    output.tex_coords = input.tex_coords;
+
+    return output;
+}
+
+// Every texel is a single v210 word, components are in r, g and b.
+@group(0) @binding(0) var texture: texture_2d<f32>;
+
+fn component(word_index: u32, row: u32, index: u32) -> f32 {
+    let word = textureLoad(texture, vec2(word_index, row), 0);
+    return word[index];
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let x = u32(input.position.x);
+    let row = u32(input.position.y);
+
+    // Every 6 pixels are packed into 4 words:
+    // - word 0: Cb0, Y0, Cr0
+    // - word 1: Y1, Cb2, Y2
+    // - word 2: Cr2, Y3, Cb4
+    // - word 3: Y4, Cr4, Y5
+    let group = (x / 6) * 4;
+    let pixel = x % 6;
+
+    var y: f32;
+    switch pixel {
+        case 0u: { y = component(group, row, 1); }
+        case 1u: { y = component(group + 1, row, 0); }
+        case 2u: { y = component(group + 1, row, 2); }
+        case 3u: { y = component(group + 2, row, 1); }
+        case 4u: { y = component(group + 3, row, 0); }
+        default: { y = component(group + 3, row, 2); }
+    }
+
+    var u: f32;
+    var v: f32;
+    switch pixel / 2 {
+        case 0u: {
+            u = component(group, row, 0);
+            v = component(group, row, 2);
+        }
+        case 1u: {
+            u = component(group + 1, row, 1);
+            v = component(group + 2, row, 0);
+        }
+        default: {
+            u = component(group + 2, row, 2);
+            v = component(group + 3, row, 1);
+        }
+    }
+
+    // YUV conversion from: https://en.wikipedia.org/w/index.php?title=YCbCr&section=8#ITU-R_BT.709_conversion
+    // YUV values footroom needs to be removed
+    // UV planes are in range (0, 1), but equation expects (-0.5, 0.5)
+
+    // (940 - 64) / 1023 ~= .856
+    y = clamp((y - (64.0/1023.0)) / 0.85630498533, 0.0, 1.0);
+    // (960 - 64) / 1023 ~= .876
+    u = clamp((u - (64.0/1023.0)) / 0.87585532746, 0.0, 1.0);
+    v = clamp((v - (64.0/1023.0)) / 0.87585532746, 0.0, 1.0);
+
+    let r = y + 1.5748 * (v - 0.5);
+    let g = y - 0.1873 * (u - 0.5) - 0.4681 * (v - 0.5);
+    let b = y + 1.8556 * (u - 0.5);
+
+    return vec4<f32>(clamp(r, 0.0, 1.0), clamp(g, 0.0, 1.0), clamp(b, 0.0, 1.0), 1.0);
+}
@@ -7,6 +7,7 @@ mod planar_yuv;
 mod rgba_linear;
 mod rgba_multiview;
 mod rgba_srgb;
+mod v210;
 
 pub mod utils;
 
@@ -26,6 +27,7 @@ pub type PlanarYuvTextures = planar_yuv::PlanarYuvTextures;
 pub type InterleavedUyvy422Texture = interleaved_yuv422::InterleavedYuv422Texture;
 pub type InterleavedYuyv422Texture = interleaved_yuv422::InterleavedYuv422Texture;
 pub type NV12Texture = nv12::NV12Texture;
+pub type V210Texture = v210::V210Texture;
 
 pub type PlanarYuvVariant = planar_yuv::YuvVariant;
 
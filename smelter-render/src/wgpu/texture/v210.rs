@@ -0,0 +1,56 @@
+use crate::{Resolution, wgpu::WgpuCtx};
+
+use super::{TextureExt, base::new_texture};
+
+/// Packed v210 frame (10-bit 4:2:2 YCbCr). Every 32-bit word holds three 10-bit components
+/// in the same bit positions as `Rgb10a2Unorm`, so words are uploaded as texels and unpacked
+/// into pixels by the conversion shader.
+#[derive(Debug)]
+pub struct V210Texture {
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    pub(crate) resolution: Resolution,
+}
+
+impl V210Texture {
+    pub fn new(ctx: &WgpuCtx, resolution: Resolution) -> Self {
+        let texture = new_texture(
+            &ctx.device,
+            None,
+            wgpu::Extent3d {
+                width: Self::words_per_row(resolution.width) as u32,
+                height: resolution.height as u32,
+                depth_or_array_layers: 1,
+            },
+            wgpu::TextureFormat::Rgb10a2Unorm,
+            wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+            &[wgpu::TextureFormat::Rgb10a2Unorm],
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self {
+            texture,
+            view,
+            resolution,
+        }
+    }
+
+    /// Rows of a v210 frame are padded to a multiple of 48 pixels (128 bytes).
+    pub fn words_per_row(width: usize) -> usize {
+        width.div_ceil(48) * 32
+    }
+
+    pub fn new_bind_group(&self, ctx: &WgpuCtx) -> wgpu::BindGroup {
+        ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("v210 texture bind group"),
+            layout: &ctx.format.single_texture_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&self.view),
+            }],
+        })
+    }
+
+    pub fn upload(&self, ctx: &WgpuCtx, data: &[u8]) {
+        self.texture.upload_data(&ctx.queue, data, 4);
+    }
+}
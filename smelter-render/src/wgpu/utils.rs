@@ -1,6 +1,7 @@
 use crate::{RenderingMode, scene::RGBAColor};
 
 use add_premultiplied_alpha::PremultiplyAlphaPipeline;
+use nine_slice::NineSlicePipeline;
 use r8_fill_with_color::R8FillWithValue;
 use remove_premultiplied_alpha::RemovePremultipliedAlphaPipeline;
 use rg8_fill_with_color::Rg8FillWithValue;
@@ -8,6 +9,7 @@ use rg8_fill_with_color::Rg8FillWithValue;
 use super::{WgpuCtx, format::TextureFormat};
 
 mod add_premultiplied_alpha;
+mod nine_slice;
 mod r8_fill_with_color;
 mod reinterpret_input_to_srgb;
 mod remove_premultiplied_alpha;
@@ -22,6 +24,8 @@ pub struct TextureUtils {
     pub linear_rgba_remove_premult_alpha: RemovePremultipliedAlphaPipeline,
     pub srgb_rgba_add_premult_alpha: PremultiplyAlphaPipeline,
     pub linear_rgba_add_premult_alpha: PremultiplyAlphaPipeline,
+    pub srgb_rgba_nine_slice: NineSlicePipeline,
+    pub linear_rgba_nine_slice: NineSlicePipeline,
 }
 
 impl TextureUtils {
@@ -44,6 +48,16 @@ impl TextureUtils {
                 &format.single_texture_layout,
                 wgpu::TextureFormat::Rgba8Unorm,
             ),
+            srgb_rgba_nine_slice: NineSlicePipeline::new(
+                device,
+                &format.single_texture_layout,
+                wgpu::TextureFormat::Rgba8UnormSrgb,
+            ),
+            linear_rgba_nine_slice: NineSlicePipeline::new(
+                device,
+                &format.single_texture_layout,
+                wgpu::TextureFormat::Rgba8Unorm,
+            ),
         }
     }
 }
@@ -0,0 +1,148 @@
+use crate::{
+    Resolution,
+    scene::NineSlice,
+    wgpu::common_pipeline::{PRIMITIVE_STATE, Sampler, Vertex},
+};
+
+use super::WgpuCtx;
+
+/// Same as [`super::add_premultiplied_alpha::PremultiplyAlphaPipeline`], but stretches
+/// the source texture using nine-slice scaling.
+#[derive(Debug)]
+pub struct NineSlicePipeline {
+    pipeline: wgpu::RenderPipeline,
+    sampler: Sampler,
+}
+
+impl NineSlicePipeline {
+    pub fn new(
+        device: &wgpu::Device,
+        rgba_textures_bind_group_layout: &wgpu::BindGroupLayout,
+        dst_view_format: wgpu::TextureFormat,
+    ) -> Self {
+        let shader_module = device.create_shader_module(wgpu::include_wgsl!("nine_slice.wgsl"));
+
+        let sampler = Sampler::new(device);
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Nine-slice pipeline layout"),
+            bind_group_layouts: &[
+                Some(rgba_textures_bind_group_layout),
+                Some(&sampler.bind_group_layout),
+            ],
+            immediate_size: 48,
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Nine-slice render pipeline"),
+            layout: Some(&pipeline_layout),
+            primitive: PRIMITIVE_STATE,
+
+            vertex: wgpu::VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                buffers: &[Some(Vertex::LAYOUT)],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+
+            fragment: Some(wgpu::FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: dst_view_format,
+                    write_mask: wgpu::ColorWrites::all(),
+                    blend: Some(wgpu::BlendState::REPLACE),
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview_mask: None,
+            depth_stencil: None,
+            cache: None,
+        });
+
+        Self { pipeline, sampler }
+    }
+
+    pub fn render(
+        &self,
+        ctx: &WgpuCtx,
+        src_bg: &wgpu::BindGroup,
+        src_resolution: Resolution,
+        dst_view: &wgpu::TextureView,
+        dst_resolution: Resolution,
+        nine_slice: &NineSlice,
+    ) {
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Nine-slice encoder"),
+            });
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Nine-slice render pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    view: dst_view,
+                    resolve_target: None,
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_immediates(
+                0,
+                &to_immediates(src_resolution, dst_resolution, nine_slice),
+            );
+            render_pass.set_bind_group(0, src_bg, &[]);
+            render_pass.set_bind_group(1, &self.sampler.bind_group, &[]);
+
+            ctx.plane.draw(&mut render_pass);
+        }
+
+        ctx.queue.submit(Some(encoder.finish()));
+    }
+}
+
+/// Has to match `NineSlice` in the shader. If the target is smaller than the sum of
+/// the opposite insets, all insets are scaled down proportionally.
+fn to_immediates(source: Resolution, target: Resolution, nine_slice: &NineSlice) -> [u8; 48] {
+    let scale_x = target.width as f32 / (nine_slice.left + nine_slice.right);
+    let scale_y = target.height as f32 / (nine_slice.top + nine_slice.bottom);
+    let scale = f32::min(1.0, f32::min(scale_x, scale_y));
+
+    let source_insets = [
+        nine_slice.top,
+        nine_slice.right,
+        nine_slice.bottom,
+        nine_slice.left,
+    ];
+    let values = [
+        source.width as f32,
+        source.height as f32,
+        target.width as f32,
+        target.height as f32,
+    ]
+    .into_iter()
+    .chain(source_insets)
+    .chain(source_insets.map(|inset| inset * scale));
+
+    let mut data = [0u8; 48];
+    for (chunk, value) in data.chunks_exact_mut(4).zip(values) {
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    data
+}
@@ -0,0 +1,93 @@
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) tex_coords: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+
+    output.position = vec4(input.position, 1.0);
+    output.tex_coords = input.tex_coords;
+
+    return output;
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(1) @binding(0) var sampler_: sampler;
+
+struct NineSlice {
+    source_size: vec2<f32>,
+    target_size: vec2<f32>,
+    // top, right, bottom, left in source texels
+    source_insets: vec4<f32>,
+    // top, right, bottom, left in target pixels
+    target_insets: vec4<f32>,
+}
+
+var<immediate> nine_slice: NineSlice;
+
+// Maps position in the target to the position in the source along a single axis.
+fn map_axis(
+    position: f32,
+    source_size: f32,
+    target_size: f32,
+    source_start: f32,
+    source_end: f32,
+    target_start: f32,
+    target_end: f32,
+) -> f32 {
+    if (position < target_start) {
+        return position * source_start / max(target_start, 0.00001);
+    }
+    if (position > target_size - target_end) {
+        let from_end = target_size - position;
+        return source_size - from_end * source_end / max(target_end, 0.00001);
+    }
+    let source_middle = source_size - source_start - source_end;
+    let target_middle = max(target_size - target_start - target_end, 0.00001);
+    return source_start + (position - target_start) * source_middle / target_middle;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let position = input.tex_coords * nine_slice.target_size;
+    let source = nine_slice.source_insets;
+    let target = nine_slice.target_insets;
+
+    let source_position = vec2<f32>(
+        map_axis(
+            position.x,
+            nine_slice.source_size.x,
+            nine_slice.target_size.x,
+            source.w,
+            source.y,
+            target.w,
+            target.y,
+        ),
+        map_axis(
+            position.y,
+            nine_slice.source_size.y,
+            nine_slice.target_size.y,
+            source.x,
+            source.z,
+            target.x,
+            target.z,
+        ),
+    );
+
+    let color = textureSample(texture, sampler_, source_position / nine_slice.source_size);
+    let a = max(color.a, 0.00001);
+
+    return vec4<f32>(
+        clamp(color.r*a, 0.0, 1.0),
+        clamp(color.g*a, 0.0, 1.0),
+        clamp(color.b*a, 0.0, 1.0),
+        clamp(color.a, 0.0, 1.0)
+    );
+}
@@ -0,0 +1,72 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{error, warn};
+
+/// Single API request stored in a recording file. Recording is a JSONL file
+/// where every line is one request.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecordedRequest {
+    /// Time since the recorder was created.
+    pub time_ms: f64,
+    /// Request path, e.g. `/api/output/output_1/update`.
+    pub path: String,
+    pub body: Option<Value>,
+}
+
+/// Records all successful API requests that mutate the state of the instance,
+/// so the session can be replayed later on a new instance.
+pub struct ApiRecorder {
+    start: Instant,
+    file: Mutex<File>,
+}
+
+impl ApiRecorder {
+    pub fn new(path: &Path) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self {
+            start: Instant::now(),
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    pub fn record(&self, time: Duration, path: &str, body: &[u8]) {
+        let body = match body.is_empty() {
+            true => None,
+            false => match serde_json::from_slice::<Value>(body) {
+                Ok(body) => Some(body),
+                Err(_) => {
+                    warn!(path, "Request body is not a JSON. Skipping recording.");
+                    return;
+                }
+            },
+        };
+        let request = RecordedRequest {
+            time_ms: time.as_secs_f64() * 1000.0,
+            path: path.to_string(),
+            body,
+        };
+        let line = match serde_json::to_string(&request) {
+            Ok(line) => line,
+            Err(err) => {
+                error!(%err, "Failed to serialize recorded request.");
+                return;
+            }
+        };
+        let mut file = self.file.lock().unwrap();
+        if let Err(err) = writeln!(file, "{line}") {
+            error!(%err, "Failed to write recorded request.");
+        }
+    }
+}
@@ -3,12 +3,12 @@ use std::{
     path::{Path, PathBuf},
     str::FromStr,
     sync::Arc,
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 use rand::Rng;
 use rtmp::TlsConfig;
-use smelter_core::DEFAULT_BUFFER_DURATION;
+use smelter_core::{DEFAULT_BUFFER_DURATION, RtpTimestampEpoch, protocols::WebrtcKeepaliveOptions};
 use smelter_render::{DEFAULT_MAX_LAYOUTS_COUNT, Framerate, RenderingMode, WgpuFeatures};
 
 use crate::logger::FfmpegLogLevel;
@@ -23,14 +23,17 @@ pub struct Config {
     pub stream_fallback_timeout: Duration,
     pub default_buffer_duration: Duration,
     pub side_channel_socket_dir: Option<Arc<Path>>,
+    pub api_recording_file: Option<Arc<Path>>,
 
     pub ahead_of_time_processing: bool,
     pub run_late_scheduled_events: bool,
     pub never_drop_output_frames: bool,
     pub load_system_fonts: bool,
+    pub gpu_decode_timing: bool,
 
     pub mixing_sample_rate: u32,
     pub output_framerate: Framerate,
+    pub rtp_timestamp_epoch: RtpTimestampEpoch,
 
     pub rendering_mode: RenderingMode,
     pub render_max_layouts_count: usize,
@@ -47,6 +50,7 @@ pub struct Config {
     pub webrtc_stun_servers: Arc<Vec<String>>,
     pub webrtc_udp_port_strategy: Option<WebrtcUdpPortStrategy>,
     pub webrtc_nat_1to1_ips: Arc<Vec<String>>,
+    pub webrtc_keepalive: WebrtcKeepaliveOptions,
 
     pub rtmp_server_port: u16,
     pub rtmp_enable: bool,
@@ -162,6 +166,16 @@ fn try_read_config() -> Result<Config, String> {
         Err(_) => DEFAULT_STREAM_FALLBACK_TIMEOUT,
     };
 
+    let rtp_timestamp_epoch = match env::var("SMELTER_RTP_TIMESTAMP_EPOCH") {
+        Ok(epoch) => rtp_timestamp_epoch_from_str(&epoch).unwrap_or_else(|| {
+            println!(
+                "CONFIG ERROR: Invalid value provided for \"SMELTER_RTP_TIMESTAMP_EPOCH\". Expected \"pipeline_start\", \"unix\" or Unix time in milliseconds. Falling back to \"pipeline_start\"."
+            );
+            RtpTimestampEpoch::PipelineStart
+        }),
+        Err(_) => RtpTimestampEpoch::PipelineStart,
+    };
+
     let default_logger_level = "info,wgpu_hal=warn,wgpu_core=warn,webrtc_srtp::session=warn";
     let logger_level = match env::var("SMELTER_LOGGER_LEVEL") {
         Ok(level) => format!("{default_logger_level},{level}"),
@@ -254,6 +268,11 @@ fn try_read_config() -> Result<Config, String> {
         Err(_) => true,
     };
 
+    let gpu_decode_timing = match env::var("SMELTER_GPU_DECODE_TIMING_ENABLE") {
+        Ok(enable) => bool_env_from_str(&enable).unwrap_or(false),
+        Err(_) => false,
+    };
+
     let whip_whep_server_port = match env::var("SMELTER_WHIP_WHEP_SERVER_PORT") {
         Ok(whip_whep_port) => whip_whep_port
             .parse::<u16>()
@@ -320,6 +339,8 @@ fn try_read_config() -> Result<Config, String> {
         Err(_) => Arc::new(vec![]),
     };
 
+    let webrtc_keepalive = read_webrtc_keepalive();
+
     let rtmp_server_port = match env::var("SMELTER_RTMP_SERVER_PORT") {
         Ok(rtmp_port) => rtmp_port
             .parse::<u16>()
@@ -374,6 +395,11 @@ fn try_read_config() -> Result<Config, String> {
         Err(_) => None,
     };
 
+    let api_recording_file = match env::var("SMELTER_API_RECORDING_FILE") {
+        Ok(path) => Some(Arc::from(PathBuf::from(path))),
+        Err(_) => None,
+    };
+
     let rendering_mode = match env::var("SMELTER_FORCE_CPU_OPTIMIZED_RENDERING_MODE") {
         Ok(enable) => match bool_env_from_str(&enable) {
             Some(true) => RenderingMode::CpuOptimized,
@@ -411,8 +437,10 @@ fn try_read_config() -> Result<Config, String> {
         },
         default_buffer_duration,
         side_channel_socket_dir,
+        api_recording_file,
         ahead_of_time_processing,
         output_framerate,
+        rtp_timestamp_epoch,
         run_late_scheduled_events,
         never_drop_output_frames,
         stream_fallback_timeout,
@@ -425,11 +453,13 @@ fn try_read_config() -> Result<Config, String> {
         gpu_device_id,
         gpu_driver_name,
         load_system_fonts,
+        gpu_decode_timing,
         whip_whep_enable,
         whip_whep_server_port,
         webrtc_stun_servers,
         webrtc_udp_port_strategy,
         webrtc_nat_1to1_ips,
+        webrtc_keepalive,
         rtmp_server_port,
         rtmp_enable,
         rtmp_tls_config,
@@ -471,6 +501,29 @@ fn moq_server_tls_config() -> Option<moq_native::ServerTlsConfig> {
     }
 }
 
+fn read_webrtc_keepalive() -> WebrtcKeepaliveOptions {
+    fn duration_ms_env(name: &str) -> Option<Duration> {
+        let value = env::var(name).ok()?;
+        match value.parse::<f64>() {
+            Ok(ms) if ms > 0.0 => Some(Duration::from_secs_f64(ms / 1000.0)),
+            _ => {
+                println!(
+                    "CONFIG ERROR: Invalid value provided for \"{name}\". Expected positive number of milliseconds. Falling back to default value."
+                );
+                None
+            }
+        }
+    }
+
+    let defaults = WebrtcKeepaliveOptions::default();
+    WebrtcKeepaliveOptions {
+        ice_keepalive_interval: duration_ms_env("SMELTER_WEBRTC_ICE_KEEPALIVE_INTERVAL_MS")
+            .unwrap_or(defaults.ice_keepalive_interval),
+        rtcp_timeout: duration_ms_env("SMELTER_WEBRTC_RTCP_TIMEOUT_MS"),
+        session_refresh_interval: duration_ms_env("SMELTER_WHIP_SESSION_REFRESH_INTERVAL_MS"),
+    }
+}
+
 fn read_side_channel_socket_dir() -> Arc<Path> {
     let dir = match env::var("SMELTER_SIDE_CHANNEL_SOCKET_DIR") {
         Ok(path) => PathBuf::from(path),
@@ -526,6 +579,19 @@ fn bool_env_from_str(s: &str) -> Option<bool> {
     }
 }
 
+fn rtp_timestamp_epoch_from_str(s: &str) -> Option<RtpTimestampEpoch> {
+    match s {
+        "pipeline_start" => Some(RtpTimestampEpoch::PipelineStart),
+        "unix" => Some(RtpTimestampEpoch::UnixEpoch),
+        anchor_ms => {
+            let anchor_ms = anchor_ms.parse::<u64>().ok()?;
+            Some(RtpTimestampEpoch::Anchor(
+                SystemTime::UNIX_EPOCH + Duration::from_millis(anchor_ms),
+            ))
+        }
+    }
+}
+
 fn wgpu_features_from_str(s: &str) -> Result<WgpuFeatures, String> {
     let mut all_features = WgpuFeatures::default();
     for feature in s.split(',') {
@@ -622,3 +688,34 @@ fn wgpu_features_from_str(s: &str) -> Result<WgpuFeatures, String> {
     }
     Ok(all_features)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rtp_timestamp_epoch() {
+        assert!(matches!(
+            rtp_timestamp_epoch_from_str("pipeline_start"),
+            Some(RtpTimestampEpoch::PipelineStart)
+        ));
+        assert!(matches!(
+            rtp_timestamp_epoch_from_str("unix"),
+            Some(RtpTimestampEpoch::UnixEpoch)
+        ));
+
+        let Some(RtpTimestampEpoch::Anchor(anchor)) = rtp_timestamp_epoch_from_str("1700000000123")
+        else {
+            panic!("expected an anchor");
+        };
+        assert_eq!(
+            anchor.duration_since(SystemTime::UNIX_EPOCH).unwrap(),
+            Duration::from_millis(1_700_000_000_123)
+        );
+
+        assert!(rtp_timestamp_epoch_from_str("").is_none());
+        assert!(rtp_timestamp_epoch_from_str("now").is_none());
+        assert!(rtp_timestamp_epoch_from_str("-1000").is_none());
+        assert!(rtp_timestamp_epoch_from_str("1.5").is_none());
+    }
+}
@@ -4,8 +4,8 @@ use axum::{http::StatusCode, response::IntoResponse};
 use serde::Serialize;
 use smelter_api::TypeError;
 use smelter_core::error::{
-    ErrorType, InitPipelineError, PipelineErrorInfo, RegisterInputError, RegisterOutputError,
-    UnregisterInputError, UnregisterOutputError, UpdateInputError,
+    CancelTaskError, ErrorType, InitPipelineError, PipelineErrorInfo, RegisterInputError,
+    RegisterOutputError, UnregisterInputError, UnregisterOutputError, UpdateInputError,
 };
 use smelter_render::error::{
     ErrorStack, RegisterRendererError, RequestKeyframeError, UnregisterRendererError,
@@ -84,6 +84,7 @@ impl_api_err!(UnregisterOutputError);
 impl_api_err!(UnregisterRendererError);
 impl_api_err!(UpdateSceneError);
 impl_api_err!(InitPipelineError);
+impl_api_err!(CancelTaskError);
 
 impl From<TypeError> for ApiError {
     fn from(err: TypeError) -> Self {
@@ -1,5 +1,6 @@
 #![recursion_limit = "256"]
 
+pub mod api_recorder;
 pub mod config;
 pub mod error;
 pub mod logger;
@@ -1,7 +1,9 @@
+use std::sync::Arc;
+
 use axum::{
     body::Body,
-    extract::Request,
-    http::StatusCode,
+    extract::{Request, State},
+    http::{Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -10,6 +12,8 @@ use http_body_util::BodyExt;
 use serde_json::Value;
 use tracing::{Level, enabled, trace};
 
+use crate::state::ApiState;
+
 pub async fn body_logger_middleware(
     request: Request,
     next: Next,
@@ -24,6 +28,37 @@ pub async fn body_logger_middleware(
     Ok(response)
 }
 
+/// Records requests that modify the state of the instance if `SMELTER_API_RECORDING_FILE`
+/// is set. Only successful requests are recorded.
+pub async fn api_recorder_middleware(
+    State(state): State<Arc<ApiState>>,
+    request: Request,
+    next: Next,
+) -> Result<impl IntoResponse, Response> {
+    let Some(recorder) = state.api_recorder.clone() else {
+        return Ok(next.run(request).await);
+    };
+    if request.method() != Method::POST || !request.uri().path().starts_with("/api/") {
+        return Ok(next.run(request).await);
+    }
+    let time = recorder.elapsed();
+    let path = request.uri().path().to_string();
+    let (parts, body) = request.into_parts();
+    let bytes = body
+        .collect()
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response())?
+        .to_bytes();
+
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes.clone())))
+        .await;
+    if response.status().is_success() {
+        recorder.record(time, &path, &bytes);
+    }
+    Ok(response)
+}
+
 async fn buffer_request_body(request: Request) -> Result<Request, Response> {
     let (parts, body) = request.into_parts();
 
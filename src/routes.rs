@@ -13,6 +13,7 @@ use tower_http::cors::CorsLayer;
 use crate::{
     routes::{
         control_request::{handle_reset, handle_start},
+        diagnostics::{cancel_task_handler, tasks_handler},
         status::{stats_handler, status_handler},
         ws::ws_handler,
     },
@@ -20,9 +21,10 @@ use crate::{
 };
 
 use self::{update_output::handle_keyframe_request, update_output::handle_output_update};
-use crate::middleware::body_logger_middleware;
+use crate::middleware::{api_recorder_middleware, body_logger_middleware};
 
 pub mod control_request;
+pub mod diagnostics;
 pub mod register_request;
 pub mod status;
 pub mod unregister_request;
@@ -46,6 +48,10 @@ pub fn routes(state: Arc<ApiState>) -> Router {
         .route("/:id/register", post(register_request::handle_image))
         .route("/:id/unregister", post(unregister_request::handle_image));
 
+    let lut = Router::new()
+        .route("/:id/register", post(register_request::handle_lut))
+        .route("/:id/unregister", post(unregister_request::handle_lut));
+
     let web = Router::new()
         .route("/:id/register", post(register_request::handle_web_renderer))
         .route(
@@ -63,6 +69,7 @@ pub fn routes(state: Arc<ApiState>) -> Router {
         .nest("/api/input", inputs)
         .nest("/api/output", outputs)
         .nest("/api/image", image)
+        .nest("/api/lut", lut)
         .nest("/api/web-renderer", web)
         .nest("/api/shader", shader)
         .nest("/api/font", font)
@@ -73,8 +80,17 @@ pub fn routes(state: Arc<ApiState>) -> Router {
         .route("/ws", get(ws_handler))
         .route("/status", get(status_handler))
         .route("/stats", get(stats_handler))
+        .route("/diagnostics/tasks", get(tasks_handler))
+        .route(
+            "/diagnostics/tasks/:task_id/cancel",
+            post(cancel_task_handler),
+        )
         .layer(CorsLayer::permissive())
         .layer(middleware::from_fn(body_logger_middleware))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            api_recorder_middleware,
+        ))
         .with_state(state)
 }
 
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+};
+use smelter_core::diagnostics::{TaskId, TaskInfo};
+
+use crate::{
+    error::ApiError,
+    state::{ApiState, Response},
+};
+
+#[utoipa::path(
+    get,
+    path = "/diagnostics/tasks",
+    operation_id = "get_tasks",
+    responses(
+        (status = 200, description = "Running tasks fetched successfully.", body = Vec<TaskInfo>),
+        (status = 500, description = "Internal server error.", body = ApiError),
+    ),
+    tags = ["metadata_request"],
+)]
+pub async fn tasks_handler(
+    State(state): State<Arc<ApiState>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let pipeline = state.pipeline()?;
+    Ok(axum::Json(pipeline.lock().unwrap().tasks()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/diagnostics/tasks/{task_id}/cancel",
+    operation_id = "cancel_task",
+    params(("task_id" = u64, Path, description = "Task ID.")),
+    responses(
+        (status = 200, description = "Task cancel requested.", body = Response),
+        (status = 400, description = "Bad request.", body = ApiError),
+        (status = 404, description = "Task not found.", body = ApiError),
+        (status = 500, description = "Internal server error.", body = ApiError),
+    ),
+    tags = ["metadata_request"],
+)]
+pub async fn cancel_task_handler(
+    State(state): State<Arc<ApiState>>,
+    Path(task_id): Path<TaskId>,
+) -> Result<Response, ApiError> {
+    state.pipeline()?.lock().unwrap().cancel_task(task_id)?;
+    Ok(Response::Ok {})
+}
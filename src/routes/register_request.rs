@@ -13,7 +13,7 @@ use crate::{
     state::Response,
 };
 use smelter_api::{
-    DeckLink, HlsInput, HlsOutput, ImageSpec, InputId, MoqClientInput, MoqClientOutput,
+    DeckLink, HlsInput, HlsOutput, ImageSpec, InputId, LutSpec, MoqClientInput, MoqClientOutput,
     MoqServerInput, Mp4Input, Mp4Output, OutputId, RendererId, RtmpInput, RtmpOutput, RtpInput,
     RtpOutput, ShaderSpec, V4l2Input, WebRendererSpec, WhepInput, WhepOutput, WhipInput,
     WhipOutput,
@@ -257,6 +257,32 @@ pub async fn handle_image(
     .unwrap()
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/lut/{lut_id}/register",
+    operation_id = "register_lut",
+    params(("lut_id" = str, Path, description = "LUT ID.")),
+    responses(
+        (status = 200, description = "LUT registered successfully.", body = Response),
+        (status = 400, description = "Bad request.", body = ApiError),
+        (status = 500, description = "Internal server error.", body = ApiError),
+    ),
+    tags = ["register_request"],
+)]
+pub async fn handle_lut(
+    State(api): State<Arc<ApiState>>,
+    Path(lut_id): Path<RendererId>,
+    Json(request): Json<LutSpec>,
+) -> Result<Response, ApiError> {
+    let api = api.clone();
+    tokio::task::spawn_blocking(move || {
+        Pipeline::register_renderer(&api.pipeline()?, lut_id.into(), request.try_into()?)?;
+        Ok(Response::Ok {})
+    })
+    .await
+    .unwrap()
+}
+
 // This type is currently used only for OpenAPI generation
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, ToSchema)]
 pub struct RegisterFontRequest {
@@ -247,3 +247,44 @@ pub async fn handle_image(
     }
     Ok(Response::Ok {})
 }
+
+#[utoipa::path(
+    post,
+    path = "/api/lut/{lut_id}/unregister",
+    operation_id = "unregister_lut",
+    params(("lut_id" = str, Path, description = "LUT ID.")),
+    responses(
+        (status = 200, description = "LUT unregistered successfully.", body = Response),
+        (status = 400, description = "Bad request.", body = ApiError),
+        (status = 404, description = "LUT not found.", body = ApiError),
+        (status = 500, description = "Internal server error.", body = ApiError),
+    ),
+    tags = ["unregister_request"],
+)]
+pub async fn handle_lut(
+    State(api): State<Arc<ApiState>>,
+    Path(lut_id): Path<RendererId>,
+    Json(request): Json<UnregisterRenderer>,
+) -> Result<Response, ApiError> {
+    match request.schedule_time_ms {
+        Some(schedule_time_ms) => {
+            let schedule_time = Duration::from_secs_f64(schedule_time_ms / 1000.0);
+            Pipeline::schedule_event(&api.pipeline()?, schedule_time, move |pipeline| {
+                if let Err(err) = pipeline.unregister_renderer(&lut_id.into(), RegistryType::Lut) {
+                    error!(
+                        "Error while running scheduled LUT unregister for pts {}ms: {}",
+                        schedule_time.as_millis(),
+                        ErrorStack::new(&err).into_string()
+                    )
+                }
+            });
+        }
+        None => {
+            api.pipeline()?
+                .lock()
+                .unwrap()
+                .unregister_renderer(&lut_id.into(), RegistryType::Lut)?;
+        }
+    }
+    Ok(Response::Ok {})
+}
@@ -1,6 +1,7 @@
 use crossbeam_channel::Receiver;
 use rustls::crypto::aws_lc_rs;
 use signal_hook::{consts, iterator::Signals};
+use smelter_core::RtpCodecRegistry;
 use smelter_render::error::ErrorStack;
 use tokio::runtime::Builder;
 use tracing::{debug, error, info};
@@ -11,6 +12,11 @@ use tokio::runtime::Runtime;
 use crate::{config::read_config, logger::init_logger, routes::routes, state::ApiState};
 
 pub fn run() {
+    run_with_rtp_codec_registry(RtpCodecRegistry::default())
+}
+
+/// Starts the server with custom RTP codecs registered, see [`RtpCodecRegistry`].
+pub fn run_with_rtp_codec_registry(rtp_codec_registry: RtpCodecRegistry) {
     listen_for_parent_termination();
     let config = read_config();
     init_logger(config.logger.clone());
@@ -18,12 +24,13 @@ pub fn run() {
 
     info!("Starting Smelter with config:\n{:#?}", config);
     let runtime = Arc::new(init_runtime());
-    let state = ApiState::new(config, runtime.clone()).unwrap_or_else(|err| {
-        panic!(
-            "Failed to start Smelter instance.\n{}",
-            ErrorStack::new(&err).into_string()
-        )
-    });
+    let state = ApiState::new_with_rtp_codec_registry(config, runtime.clone(), rtp_codec_registry)
+        .unwrap_or_else(|err| {
+            panic!(
+                "Failed to start Smelter instance.\n{}",
+                ErrorStack::new(&err).into_string()
+            )
+        });
     let chromium_context = state.chromium_context.clone();
 
     thread::Builder::new()
@@ -3,7 +3,7 @@ use std::sync::{Arc, Mutex};
 use axum::response::IntoResponse;
 use smelter_core::{
     Pipeline, PipelineMoqServerOptions, PipelineOptions, PipelineRtmpServerOptions,
-    PipelineWgpuOptions, PipelineWhipWhepServerOptions, error::InitPipelineError,
+    PipelineWgpuOptions, PipelineWhipWhepServerOptions, RtpCodecRegistry, error::InitPipelineError,
     protocols::WebrtcUdpPortStrategy,
 };
 use smelter_render::web_renderer::{ChromiumContext, ChromiumContextInitError};
@@ -13,7 +13,7 @@ use serde::Serialize;
 use tokio::runtime::Runtime;
 use utoipa::ToSchema;
 
-use crate::{config::Config, error::ApiError};
+use crate::{api_recorder::ApiRecorder, config::Config, error::ApiError};
 
 #[derive(Serialize, Debug, ToSchema)]
 #[serde(untagged)]
@@ -45,6 +45,9 @@ pub enum ApiStateInitError {
 
     #[error(transparent)]
     ChromiumContextInit(#[from] ChromiumContextInitError),
+
+    #[error("Failed to create API recording file.")]
+    ApiRecorderInit(#[source] std::io::Error),
 }
 
 pub struct ApiState {
@@ -52,10 +55,21 @@ pub struct ApiState {
     pub config: Config,
     pub chromium_context: Option<Arc<ChromiumContext>>,
     pub runtime: Arc<Runtime>,
+    pub api_recorder: Option<Arc<ApiRecorder>>,
+    pub rtp_codec_registry: RtpCodecRegistry,
 }
 
 impl ApiState {
     pub fn new(config: Config, runtime: Arc<Runtime>) -> Result<Arc<ApiState>, ApiStateInitError> {
+        Self::new_with_rtp_codec_registry(config, runtime, RtpCodecRegistry::default())
+    }
+
+    /// Custom RTP codecs are passed to the pipeline, also when it is recreated on reset.
+    pub fn new_with_rtp_codec_registry(
+        config: Config,
+        runtime: Arc<Runtime>,
+        rtp_codec_registry: RtpCodecRegistry,
+    ) -> Result<Arc<ApiState>, ApiStateInitError> {
         let chromium_context = match config.web_renderer_enable && cfg!(feature = "web-renderer") {
             true => Some(ChromiumContext::new(
                 config.output_framerate,
@@ -63,13 +77,24 @@ impl ApiState {
             )?),
             false => None,
         };
-        let options = pipeline_options_from_config(&config, &runtime, &chromium_context);
+        let api_recorder = config
+            .api_recording_file
+            .as_ref()
+            .map(|path| ApiRecorder::new(path).map(Arc::new))
+            .transpose()
+            .map_err(ApiStateInitError::ApiRecorderInit)?;
+        let options = PipelineOptions {
+            rtp_codec_registry: rtp_codec_registry.clone(),
+            ..pipeline_options_from_config(&config, &runtime, &chromium_context)
+        };
         let pipeline = Pipeline::new(options)?;
         Ok(Arc::new(ApiState {
             pipeline: Mutex::new(Some(Arc::new(Mutex::new(pipeline)))),
             config,
             runtime,
             chromium_context,
+            api_recorder,
+            rtp_codec_registry,
         }))
     }
 
@@ -89,8 +114,10 @@ impl ApiState {
         let mut guard = self.pipeline.lock().unwrap();
         guard.take();
 
-        let options =
-            pipeline_options_from_config(&self.config, &self.runtime, &self.chromium_context);
+        let options = PipelineOptions {
+            rtp_codec_registry: self.rtp_codec_registry.clone(),
+            ..pipeline_options_from_config(&self.config, &self.runtime, &self.chromium_context)
+        };
         let pipeline = Arc::new(Mutex::new(Pipeline::new(options)?));
         *guard = Some(pipeline);
         Ok(())
@@ -112,9 +139,11 @@ pub fn pipeline_options_from_config(
         run_late_scheduled_events: opt.run_late_scheduled_events,
         never_drop_output_frames: opt.never_drop_output_frames,
         side_channel_socket_dir: opt.side_channel_socket_dir.clone(),
+        gpu_decode_timing: opt.gpu_decode_timing,
 
         mixing_sample_rate: opt.mixing_sample_rate,
         output_framerate: opt.output_framerate,
+        rtp_timestamp_epoch: opt.rtp_timestamp_epoch,
 
         rendering_mode: opt.rendering_mode,
         max_layouts_count: opt.render_max_layouts_count,
@@ -142,6 +171,7 @@ pub fn pipeline_options_from_config(
             crate::config::WebrtcUdpPortStrategy::Mux(port) => WebrtcUdpPortStrategy::Mux(port),
         }),
         webrtc_nat_1to1_ips: opt.webrtc_nat_1to1_ips.clone(),
+        webrtc_keepalive: opt.webrtc_keepalive,
 
         rtmp_server: match opt.rtmp_enable {
             true => PipelineRtmpServerOptions::Enable {
@@ -160,5 +190,6 @@ pub fn pipeline_options_from_config(
         },
 
         moq_disable_tls_verification: opt.moq_disable_tls_verification,
+        rtp_codec_registry: Default::default(),
     }
 }
@@ -27,6 +27,7 @@ enum ApiTypes {
     RegisterInput(routes::register_request::RegisterInput),
     RegisterOutput(Box<routes::register_request::RegisterOutput>),
     RegisterImage(smelter_api::ImageSpec),
+    RegisterLut(smelter_api::LutSpec),
     RegisterWebRenderer(smelter_api::WebRendererSpec),
     RegisterShader(smelter_api::ShaderSpec),
     UpdateOutput(Box<routes::update_output::UpdateOutputRequest>),
@@ -138,17 +139,21 @@ enum SchemaAction {
         smelter::routes::register_request::handle_shader,
         smelter::routes::register_request::handle_web_renderer,
         smelter::routes::register_request::handle_image,
+        smelter::routes::register_request::handle_lut,
         smelter::routes::register_request::handle_font,
         smelter::routes::unregister_request::handle_input,
         smelter::routes::unregister_request::handle_output,
         smelter::routes::unregister_request::handle_shader,
         smelter::routes::unregister_request::handle_web_renderer,
         smelter::routes::unregister_request::handle_image,
+        smelter::routes::unregister_request::handle_lut,
         smelter::routes::update_input::handle_input_update,
         smelter::routes::update_output::handle_output_update,
         smelter::routes::update_output::handle_keyframe_request,
         smelter::routes::status::status_handler,
         smelter::routes::status::stats_handler,
+        smelter::routes::diagnostics::tasks_handler,
+        smelter::routes::diagnostics::cancel_task_handler,
         smelter::routes::ws::ws_handler,
     )
 )]
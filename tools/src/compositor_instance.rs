@@ -60,6 +60,8 @@ impl CompositorInstance {
             config,
             chromium_context: None,
             runtime: runtime(),
+            api_recorder: None,
+            rtp_codec_registry: Default::default(),
         });
 
         let events = pipeline.lock().unwrap().subscribe_pipeline_events();
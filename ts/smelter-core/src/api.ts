@@ -138,6 +138,22 @@ export class ApiClient {
     });
   }
 
+  public async registerLut(lutId: string, request: Api.LutSpec): Promise<object> {
+    return this.serverManager.sendRequest({
+      method: 'POST',
+      route: `/api/lut/${encodeURIComponent(lutId)}/register`,
+      body: request,
+    });
+  }
+
+  public async unregisterLut(lutId: string): Promise<object> {
+    return this.serverManager.sendRequest({
+      method: 'POST',
+      route: `/api/lut/${encodeURIComponent(lutId)}/unregister`,
+      body: {},
+    });
+  }
+
   public async registerWebRenderer(
     instanceId: string,
     request: Api.WebRendererSpec
@@ -114,6 +114,7 @@ function intoMp4RegisterInput(input: Inputs.RegisterMp4Input): RegisterInputRequ
     seek_ms: input.seekMs,
     decoder_map: input.decoderMap,
     side_channel: intoSideChannel(input.sideChannel),
+    decryption: intoMp4Decryption(input.decryption),
   };
 }
 
@@ -139,6 +140,8 @@ function intoRtpRegisterInput(input: Inputs.RegisterRtpInput): RegisterInputRequ
     offset_ms: input.offsetMs,
     buffer_size_ms: input.bufferSizeMs,
     side_channel: intoSideChannel(input.sideChannel),
+    socket_options: intoRtpSocketOptions(input.socketOptions),
+    payload_types: input.payloadTypes ?? undefined,
   };
 }
 
@@ -219,6 +222,31 @@ function intoSideChannel(sideChannel?: Inputs.SideChannel): Api.SideChannel | un
   };
 }
 
+export function intoRtpSocketOptions(
+  socketOptions?: Inputs.RtpSocketOptions | null
+): Api.RtpSocketOptions | undefined {
+  if (!socketOptions) {
+    return undefined;
+  }
+  return {
+    receive_buffer_size_bytes: socketOptions.receiveBufferSizeBytes,
+    send_buffer_size_bytes: socketOptions.sendBufferSizeBytes,
+    dscp: socketOptions.dscp,
+  };
+}
+
+function intoMp4Decryption(
+  decryption?: Inputs.Mp4InputDecryption | null
+): Api.Mp4InputDecryption | undefined {
+  if (!decryption) {
+    return undefined;
+  }
+  return {
+    key: decryption.key,
+    key_id: decryption.keyId,
+  };
+}
+
 function intoInputAudio(audio: Inputs.InputRtpAudioOptions): Api.InputRtpAudioOptions {
   if (audio.decoder === 'opus') {
     return {
@@ -230,6 +258,11 @@ function intoInputAudio(audio: Inputs.InputRtpAudioOptions): Api.InputRtpAudioOp
       audio_specific_config: audio.audioSpecificConfig,
       rtp_mode: audio.rtpMode,
     };
+  } else if (audio.decoder === 'custom') {
+    return {
+      decoder: 'custom',
+      clock_rate: audio.clockRate,
+    };
   } else {
     throw new Error(`Unknown audio decoder type: ${(audio as any).decoder}`);
   }
@@ -24,6 +24,7 @@ export function intoOutputHlsVideoOptions(
     resolution: video.resolution,
     send_eos_when: video.sendEosWhen && intoOutputEosCondition(video.sendEosWhen),
     encoder: intoHlsVideoEncoderOptions(video.encoder),
+    debug_overlay: video.debugOverlay,
     initial,
   };
 }
@@ -11,6 +11,8 @@ export function intoRegisterMp4Output(
     path: output.serverPath,
     video: output.video && initial.video && intoOutputMp4VideoOptions(output.video, initial.video),
     audio: output.audio && initial.audio && intoOutputMp4AudioOptions(output.audio, initial.audio),
+    fast_start: output.fastStart,
+    interleave_duration_ms: output.interleaveDurationMs,
     ffmpeg_options: output.ffmpegOptions,
   };
 }
@@ -23,6 +25,7 @@ export function intoOutputMp4VideoOptions(
     resolution: video.resolution,
     send_eos_when: video.sendEosWhen && intoOutputEosCondition(video.sendEosWhen),
     encoder: intoMp4VideoEncoderOptions(video.encoder),
+    debug_overlay: video.debugOverlay,
     initial,
   };
 }
@@ -29,6 +29,7 @@ export function intoOutputRtmpClientVideoOptions(
     resolution: video.resolution,
     send_eos_when: video.sendEosWhen && intoOutputEosCondition(video.sendEosWhen),
     encoder: intoRtmpClientVideoEncoderOptions(video.encoder),
+    debug_overlay: video.debugOverlay,
     initial,
   };
 }
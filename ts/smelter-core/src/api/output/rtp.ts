@@ -1,6 +1,7 @@
 import type { Api, Outputs, _smelterInternals } from '@swmansion/smelter';
 import type { RegisterOutputRequest } from '../output';
 import { intoOutputEosCondition, intoVideoEncoderBitrate } from './common';
+import { intoRtpSocketOptions } from '../input';
 
 export function intoRegisterRtpOutput(
   output: Outputs.RegisterRtpOutput,
@@ -13,6 +14,8 @@ export function intoRegisterRtpOutput(
     transport_protocol: output.transportProtocol,
     video: output.video && initial.video && intoOutputRtpVideoOptions(output.video, initial.video),
     audio: output.audio && initial.audio && intoOutputRtpAudioOptions(output.audio, initial.audio),
+    socket_options: intoRtpSocketOptions(output.socketOptions),
+    payload_types: output.payloadTypes,
   };
 }
 
@@ -24,6 +27,7 @@ export function intoOutputRtpVideoOptions(
     resolution: video.resolution,
     send_eos_when: video.sendEosWhen && intoOutputEosCondition(video.sendEosWhen),
     encoder: video.encoder && intoRtpVideoEncoderOptions(video.encoder),
+    debug_overlay: video.debugOverlay,
     initial,
   };
 }
@@ -27,6 +27,7 @@ export function intoOutputWhepVideoOptions(
     resolution: video.resolution,
     send_eos_when: video.sendEosWhen && intoOutputEosCondition(video.sendEosWhen),
     encoder: video.encoder && intoWhepVideoEncoderOptions(video.encoder),
+    debug_overlay: video.debugOverlay,
     initial,
   };
 }
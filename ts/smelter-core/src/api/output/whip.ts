@@ -29,6 +29,7 @@ export function intoOutputWhipVideoOptions(
     send_eos_when: video.sendEosWhen && intoOutputEosCondition(video.sendEosWhen),
     encoder_preferences:
       video.encoderPreferences && intoWhipVideoEncoderPreferences(video.encoderPreferences),
+    debug_overlay: video.debugOverlay,
     initial,
   };
 }
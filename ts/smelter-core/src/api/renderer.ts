@@ -12,6 +12,13 @@ export function intoRegisterImage(image: Renderers.RegisterImage): Api.ImageSpec
   };
 }
 
+export function intoRegisterLut(lut: Renderers.RegisterLut): Api.LutSpec {
+  return {
+    url: lut.url,
+    path: lut.serverPath,
+  };
+}
+
 export function intoRegisterWebRenderer(
   renderer: Renderers.RegisterWebRenderer
 ): Api.WebRendererSpec {
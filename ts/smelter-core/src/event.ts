@@ -19,10 +19,33 @@ export function parseEvent(event: any, logger: Logger): SmelterEvent | null {
       SmelterEventType.AUDIO_INPUT_PAUSED,
       SmelterEventType.VIDEO_INPUT_EOS,
       SmelterEventType.AUDIO_INPUT_EOS,
+      SmelterEventType.INPUT_DISCONNECTED,
     ].includes(event.type)
   ) {
     return { type: event.type, inputRef: parseInputRef(event.input_id) };
-  } else if (SmelterEventType.OUTPUT_DONE === event.type) {
+  } else if (SmelterEventType.VIDEO_INPUT_FORMAT_CHANGED === event.type) {
+    return {
+      type: event.type,
+      inputRef: parseInputRef(event.input_id),
+      displayMode: event.display_mode,
+      width: Number(event.width),
+      height: Number(event.height),
+      framerate: { num: Number(event.framerate_num), den: Number(event.framerate_den) },
+      pixelFormat: event.pixel_format,
+      fieldDominance: event.field_dominance,
+    };
+  } else if (SmelterEventType.INPUT_ANCILLARY_DATA === event.type) {
+    return {
+      type: event.type,
+      inputRef: parseInputRef(event.input_id),
+      kind: event.kind,
+      ptsMs: Number(event.pts_ms),
+      lineNumber: Number(event.line_number),
+      data: event.data,
+    };
+  } else if (
+    [SmelterEventType.OUTPUT_DONE, SmelterEventType.OUTPUT_DISCONNECTED].includes(event.type)
+  ) {
     return { type: event.type, outputId: event.output_id };
   } else if (SmelterEventType.OUTPUT_ERROR === event.type) {
     return {
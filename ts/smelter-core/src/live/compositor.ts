@@ -9,7 +9,7 @@ import { intoRegisterOutput } from '../api/output';
 import type { RegisterInput } from '../api/input';
 import { intoRegisterInput } from '../api/input';
 import { parseEvent } from '../event';
-import { intoRegisterImage, intoRegisterWebRenderer } from '../api/renderer';
+import { intoRegisterImage, intoRegisterLut, intoRegisterWebRenderer } from '../api/renderer';
 import { handleEvent } from './event';
 import type { ReactElement } from 'react';
 import type { Logger } from '../logger';
@@ -143,6 +143,16 @@ export class Smelter {
     return this.api.unregisterImage(imageRef, {});
   }
 
+  public async registerLut(lutId: string, request: Renderers.RegisterLut): Promise<object> {
+    this.logger.info({ lutId }, 'Register LUT');
+    return this.api.registerLut(lutId, intoRegisterLut(request));
+  }
+
+  public async unregisterLut(lutId: string): Promise<object> {
+    this.logger.info({ lutId }, 'Unregister LUT');
+    return this.api.unregisterLut(lutId);
+  }
+
   public async registerWebRenderer(
     instanceId: string,
     request: Renderers.RegisterWebRenderer
@@ -7,7 +7,7 @@ import type { RegisterOutput } from '../api/output';
 import { intoRegisterOutput } from '../api/output';
 import type { RegisterInput } from '../api/input';
 import { intoRegisterInput } from '../api/input';
-import { intoRegisterImage } from '../api/renderer';
+import { intoRegisterImage, intoRegisterLut } from '../api/renderer';
 import OfflineOutput from './output';
 import { SmelterEventType, parseEvent } from '../event';
 import type { ReactElement } from 'react';
@@ -143,6 +143,12 @@ export class OfflineSmelter {
     return this.api.registerImage(imageRef, intoRegisterImage(request));
   }
 
+  public async registerLut(lutId: string, request: Renderers.RegisterLut): Promise<object> {
+    this.checkNotStarted();
+    this.logger.info({ lutId }, 'Register LUT');
+    return this.api.registerLut(lutId, intoRegisterLut(request));
+  }
+
   private checkNotStarted() {
     if (this.renderStarted) {
       throw new Error('Render was already started.');
@@ -104,6 +104,18 @@ export default class Smelter {
     });
   }
 
+  public async registerLut(lutId: string, request: Renderers.RegisterLut): Promise<void> {
+    await this.scheduler.run(async () => {
+      await this.coreSmelter.registerLut(lutId, request);
+    });
+  }
+
+  public async unregisterLut(lutId: string): Promise<void> {
+    await this.scheduler.run(async () => {
+      await this.coreSmelter.unregisterLut(lutId);
+    });
+  }
+
   public async registerShader(shaderId: string, request: Renderers.RegisterShader): Promise<void> {
     await this.scheduler.run(async () => {
       await this.coreSmelter.registerShader(shaderId, request);
@@ -76,6 +76,12 @@ export default class OfflineSmelter {
     });
   }
 
+  public async registerLut(lutId: string, request: Renderers.RegisterLut): Promise<void> {
+    await this.scheduler.run(async () => {
+      await this.coreSmelter.registerLut(lutId, request);
+    });
+  }
+
   public async registerShader(shaderId: string, request: Renderers.RegisterShader): Promise<void> {
     await this.scheduler.run(async () => {
       await this.coreSmelter.registerShader(shaderId, request);
@@ -2,7 +2,7 @@ import type { Input, Output } from '@swmansion/smelter-core';
 import type { Api, Renderers } from '@swmansion/smelter';
 
 export type RegisterImage = {
-  assetType: 'png' | 'jpeg' | 'svg' | 'gif' | 'auto';
+  assetType: 'png' | 'jpeg' | 'svg' | 'gif' | 'webp' | 'avif' | 'auto';
   url: string;
 };
 
@@ -12,6 +12,7 @@ export type ApiTypes =
   | RegisterInput
   | RegisterOutput
   | ImageSpec
+  | LutSpec
   | WebRendererSpec
   | ShaderSpec
   | UpdateOutputRequest
@@ -52,6 +53,14 @@ export type RegisterInput =
        * Enable side channel for video and/or audio track.
        */
       side_channel?: SideChannel | null;
+      /**
+       * Socket buffer sizes and DSCP marking.
+       */
+      socket_options?: RtpSocketOptions | null;
+      /**
+       * (**default=`{ "video": 96, "audio": 97 }`**) Payload types of video and audio packets.
+       */
+      payload_types?: RtpPayloadTypes | null;
     }
   | {
       type: "rtmp_server";
@@ -158,6 +167,10 @@ export type RegisterInput =
        * Enable side channel for video and/or audio track.
        */
       side_channel?: SideChannel | null;
+      /**
+       * Decryption key for files protected with Common Encryption (CENC). Supported schemes are `cenc`, `cens`, `cbc1` and `cbcs`.
+       */
+      decryption?: Mp4InputDecryption | null;
     }
   | {
       type: "whip_server";
@@ -287,10 +300,20 @@ export type RegisterInput =
        * The input device is selected based on fields `subdevice_index`, `persistent_id` **AND** `display_name`. All of them need to match the device if they are specified. If nothing is matched, the error response will list available devices.
        */
       persistent_id?: string | null;
+      /**
+       * Profile that should be activated on the DeckLink card before the input is started. Profiles define how many sub-devices are exposed by a card and whether each of them can capture and play back at the same time. The sub-device is selected again after the profile is changed.
+       *
+       * If not specified, the currently active profile is used.
+       */
+      profile?: DeckLinkProfile | null;
       /**
        * (**default=`true`**) Enable audio support.
        */
       enable_audio?: boolean | null;
+      /**
+       * (**default=`false`**) Emit `INPUT_ANCILLARY_DATA` events for CEA-708 captions and SCTE-104 messages carried in the ancillary data of the input signal.
+       */
+      enable_ancillary_data?: boolean | null;
       /**
        * (**default=`false`**) If input is required and frames are not processed on time, then Smelter will delay producing output frames.
        */
@@ -302,7 +325,7 @@ export type RegisterInput =
     };
 export type PortOrPortRange = string | number;
 export type TransportProtocol = "udp" | "tcp_server";
-export type RtpVideoDecoderOptions = "ffmpeg_h264" | "ffmpeg_vp8" | "ffmpeg_vp9" | "vulkan_h264";
+export type RtpVideoDecoderOptions = "ffmpeg_h264" | "ffmpeg_vp8" | "ffmpeg_vp9" | "vulkan_h264" | "custom";
 export type InputRtpAudioOptions =
   | {
       decoder: "opus";
@@ -323,6 +346,13 @@ export type InputRtpAudioOptions =
        * (**default=`"high_bitrate"`**) Specifies the [RFC 3640 mode](https://datatracker.ietf.org/doc/html/rfc3640#section-3.3.1) that should be used when depacketizing this stream.
        */
       rtp_mode?: AacRtpMode | null;
+    }
+  | {
+      decoder: "custom";
+      /**
+       * RTP clock rate of the audio track.
+       */
+      clock_rate: number;
     };
 export type AacRtpMode = "low_bitrate" | "high_bitrate";
 export type RtmpVideoDecoderOptions = "ffmpeg_h264" | "vulkan_h264";
@@ -333,6 +363,12 @@ export type WhipVideoDecoderOptions = "any" | "ffmpeg_h264" | "ffmpeg_vp8" | "ff
 export type WhepVideoDecoderOptions = "any" | "ffmpeg_h264" | "ffmpeg_vp8" | "ffmpeg_vp9" | "vulkan_h264";
 export type HlsVideoDecoderOptions = "ffmpeg_h264" | "vulkan_h264";
 export type V4L2InputFormat = "yuyv" | "nv12";
+export type DeckLinkProfile =
+  | "one_sub_device_full_duplex"
+  | "one_sub_device_half_duplex"
+  | "two_sub_devices_full_duplex"
+  | "two_sub_devices_half_duplex"
+  | "four_sub_devices_half_duplex";
 export type Framerate = string | number;
 export type RegisterOutput =
   | {
@@ -357,6 +393,14 @@ export type RegisterOutput =
        * Parameters of an audio included in the RTP stream.
        */
       audio?: OutputRtpAudioOptions | null;
+      /**
+       * Socket buffer sizes and DSCP marking.
+       */
+      socket_options?: RtpSocketOptions | null;
+      /**
+       * (**default=`{ "video": 96, "audio": 97 }`**) Payload types of video and audio packets.
+       */
+      payload_types?: RtpPayloadTypes | null;
     }
   | {
       type: "rtmp_client";
@@ -387,6 +431,14 @@ export type RegisterOutput =
        * Audio stream configuration.
        */
       audio?: OutputMp4AudioOptions | null;
+      /**
+       * (**default=`true`**) Write `moov` atom before media data, so the file can be played progressively over HTTP. The file is rewritten when the output ends, which takes additional time and disk space for long recordings.
+       */
+      fast_start?: boolean | null;
+      /**
+       * Interleave audio and video samples in chunks of at most this duration (in milliseconds). Samples are written in time order across tracks, which improves compatibility with players that do minimal buffering. By default, samples are written in the order they are encoded.
+       */
+      interleave_duration_ms?: number | null;
       /**
        * Raw FFmpeg muxer options. See [docs](https://ffmpeg.org/ffmpeg-formats.html) for more.
        */
@@ -561,6 +613,40 @@ export type Component =
        * Id of an input. It identifies a stream registered using a [`RegisterInputStream`](../routes.md#register-input) request.
        */
       input_id: InputId;
+      /**
+       * Removes the background of a given color from the input, e.g. a green screen.
+       */
+      chroma_key?: ChromaKey | null;
+      /**
+       * Adjusts brightness, contrast, saturation and hue of the input.
+       */
+      color_correction?: ColorCorrection | null;
+      /**
+       * Id of a LUT registered using a [`register LUT`](../routes.md#register-lut) request.
+       * The LUT is applied after the color correction.
+       */
+      lut_id?: RendererId | null;
+      /**
+       * (**default=`1.0`**) Opacity of the input in the range from `0.0` (fully transparent) to
+       * `1.0` (fully opaque).
+       */
+      opacity?: number | null;
+      /**
+       * Part of the input that is rendered, e.g. to cut off letterboxing. Size of the component is equal to the size of the cropped part.
+       */
+      crop?: SourceCrop | null;
+      /**
+       * Pan and zoom animation of the input. Applied only if the component is a child of a layout component (`View`, `Tiles` or `Rescaler`).
+       */
+      ken_burns?: KenBurns | null;
+      /**
+       * (**default=`false`**) Mirror the input horizontally, e.g. for front-facing camera feeds. Applied only if the component is a child of a layout component (`View`, `Tiles` or `Rescaler`).
+       */
+      flip_horizontal?: boolean | null;
+      /**
+       * (**default=`false`**) Mirror the input vertically. Applied only if the component is a child of a layout component (`View`, `Tiles` or `Rescaler`).
+       */
+      flip_vertical?: boolean | null;
     }
   | {
       type: "view";
@@ -604,10 +690,26 @@ export type Component =
        * Rotation of a component in degrees. If this field is defined, this element will be absolutely positioned, instead of being laid out by its parent.
        */
       rotation?: number | null;
+      /**
+       * Rotation of a component in degrees around its horizontal axis (like CSS `rotateX`). Positive values move the top edge away from the viewer. If this field is defined, this element will be absolutely positioned, instead of being laid out by its parent.
+       */
+      rotation_x?: number | null;
+      /**
+       * Rotation of a component in degrees around its vertical axis (like CSS `rotateY`). Positive values move the right edge away from the viewer. If this field is defined, this element will be absolutely positioned, instead of being laid out by its parent.
+       */
+      rotation_y?: number | null;
+      /**
+       * Distance in pixels between the viewer and the component, used to project components rotated with `rotation_x` or `rotation_y`. Smaller values make the effect stronger. Child components without their own `perspective` inherit it. If this field is defined, this element will be absolutely positioned, instead of being laid out by its parent.
+       */
+      perspective?: number | null;
       /**
        * Defines how this component will behave during a scene update. This will only have an effect if the previous scene already contained a `View` component with the same id.
        */
       transition?: Transition | null;
+      /**
+       * Keyframe animations of the component properties. They are evaluated on every frame, so multi-step animations do not require scene updates.
+       */
+      keyframes?: KeyframeTrack[] | null;
       /**
        * (**default=`"hidden"`**) Controls what happens to content that is too big to fit into an area.
        */
@@ -617,9 +719,13 @@ export type Component =
        */
       background_color?: RGBAColor | null;
       /**
-       * (**default=`0.0`**) Radius of a rounded corner.
+       * Background of the component, either a solid color or a gradient. Can't be used together with `background_color`.
+       */
+      background?: Background | null;
+      /**
+       * (**default=`0.0`**) Radius of rounded corners. A single number applies to all corners.
        */
-      border_radius?: number | null;
+      border_radius?: BorderRadius | null;
       /**
        * (**default=`0.0`**) Border width.
        */
@@ -660,6 +766,34 @@ export type Component =
        * (**default=`0.0`**) Padding on left side in pixels.
        */
       padding_left?: number | null;
+      /**
+       * (**default=`0.0`**) Standard deviation in pixels of a Gaussian blur applied to all
+       * inputs, images and other textures inside this component. Background color and borders
+       * are not blurred. It can be animated with `transition`.
+       */
+      blur_radius?: number | null;
+      /**
+       * Adjusts brightness, contrast, saturation and hue of all inputs, images and other textures
+       * inside this component. It is applied after the color correction of the child components.
+       * Background color and borders are not modified.
+       */
+      color_correction?: ColorCorrection | null;
+      /**
+       * Id of a LUT registered using a [`register LUT`](../routes.md#register-lut) request. It is
+       * applied to all inputs, images and other textures inside this component that do not
+       * use a LUT of their own. Background color and borders are not modified.
+       */
+      lut_id?: RendererId | null;
+      /**
+       * (**default=`1.0`**) Opacity of this component and all of its children in the range from
+       * `0.0` (fully transparent) to `1.0` (fully opaque). It can be animated with `transition`.
+       */
+      opacity?: number | null;
+      /**
+       * (**default=`"normal"`**) Defines how this component, including its background, borders
+       * and children, is composited with the content rendered below it.
+       */
+      blend_mode?: BlendMode | null;
     }
   | {
       type: "web_view";
@@ -721,6 +855,22 @@ export type Component =
        * Height of the image in pixels. If `width` is not explicitly provided, the image will automatically adjust its width to maintain its original aspect ratio relative to the height.
        */
       height?: number | null;
+      /**
+       * Enables nine-slice scaling. Image is split into 9 parts by the insets, corners are rendered without scaling, edges are stretched along one axis and the center along both. Not supported for SVG images.
+       */
+      nine_slice?: NineSlice | null;
+      /**
+       * Pan and zoom animation of the image. Applied only if the component is a child of a layout component (`View`, `Tiles` or `Rescaler`).
+       */
+      ken_burns?: KenBurns | null;
+      /**
+       * (**default=`false`**) Mirror the image horizontally. Applied only if the component is a child of a layout component (`View`, `Tiles` or `Rescaler`).
+       */
+      flip_horizontal?: boolean | null;
+      /**
+       * (**default=`false`**) Mirror the image vertically. Applied only if the component is a child of a layout component (`View`, `Tiles` or `Rescaler`).
+       */
+      flip_vertical?: boolean | null;
     }
   | {
       type: "text";
@@ -729,9 +879,13 @@ export type Component =
        */
       id?: ComponentId | null;
       /**
-       * Text that will be rendered.
+       * Text that will be rendered. Either `text` or `spans` has to be provided.
        */
-      text: string;
+      text?: string | null;
+      /**
+       * Parts of the text rendered one after another, each with its own styling. Span properties that are not defined are inherited from the component. It's an error to provide both `text` and `spans`.
+       */
+      spans?: TextSpan[] | null;
       /**
        * Width of a texture that text will be rendered on. If not provided, the resulting texture will be sized based on the defined text but limited to `max_width` value.
        */
@@ -760,10 +914,28 @@ export type Component =
        * (**default=`"#FFFFFFFF"`**) Font color in `#RRGGBBAA` format.
        */
       color?: RGBAColor | null;
+      /**
+       * Linear gradient that fills the text instead of `color`. The gradient spans the entire texture that the text is rendered on.
+       */
+      gradient?: LinearGradient | null;
       /**
        * (**default=`"#00000000"`**) Background color in `#RRGGBBAA` format.
        */
       background_color?: RGBAColor | null;
+      /**
+       * Outline drawn around the glyphs. If the texture is sized based on the text, it is extended to fit the stroke.
+       */
+      stroke?: TextStroke | null;
+      /**
+       * Shadow of the text and its stroke. If the texture is sized based on the text, it is extended to fit the shadow.
+       */
+      shadow?: TextShadow | null;
+      /**
+       * Continuously scrolls the text horizontally, e.g. for news tickers. Text is not wrapped and `wrap` is ignored. It's an error to provide `ticker` if `width` is not defined.
+       *
+       * Scrolling continues across scene updates as long as the component keeps the same `id` and its text and `ticker` do not change.
+       */
+      ticker?: TextTicker | null;
       /**
        * (**default=`"Verdana"`**) Font family. Provide [family-name](https://www.w3.org/TR/2018/REC-css-fonts-3-20180920/#family-name-value) for a specific font. "generic-family" values like e.g. "sans-serif" will not work.
        */
@@ -773,9 +945,13 @@ export type Component =
        */
       style?: TextStyle | null;
       /**
-       * (**default=`"left"`**) Text align.
+       * Text align. By default, each paragraph is aligned to its start, i.e. to the left for left-to-right and to the right for right-to-left text.
        */
       align?: HorizontalAlign | null;
+      /**
+       * (**default=`"auto"`**) Base direction of the text. Right-to-left (e.g. Arabic, Hebrew) and mixed-direction text is reordered and shaped according to the Unicode Bidirectional Algorithm regardless of this value.
+       */
+      direction?: TextDirection | null;
       /**
        * (**default=`"none"`**) Text wrapping options.
        */
@@ -784,6 +960,10 @@ export type Component =
        * (**default=`"normal"`**) Font weight. The selected font needs to support the specified weight.
        */
       weight?: TextWeight | null;
+      /**
+       * (**default=`"none"`**) Adjusts the font size, so the text fits within `width` and `height` (or `max_width` and `max_height` if they are not defined). Line height and font sizes of `spans` are scaled proportionally. It's an error to provide `fit` together with `ticker`.
+       */
+      fit?: TextFit | null;
     }
   | {
       type: "tiles";
@@ -808,9 +988,21 @@ export type Component =
        */
       background_color?: RGBAColor | null;
       /**
-       * (**default=`"16:9"`**) Aspect ratio of a tile in `"W:H"` format, where W and H are integers.
+       * (**default=`{ "type": "fit" }`**) Defines how tiles are arranged and sized.
+       */
+      fill_strategy?: TilesFillStrategy | null;
+      /**
+       * (**default=`"16:9"`**) Aspect ratio of a tile in `"W:H"` format, where W and H are integers. Ignored by the `fill` strategy.
        */
       tile_aspect_ratio?: AspectRatio | null;
+      /**
+       * Maximal width of a tile in pixels (without padding). Tiles are smaller than the available space if this limit is reached, e.g. when there are only a few children.
+       */
+      max_tile_width?: number | null;
+      /**
+       * Maximal height of a tile in pixels (without padding). Tiles are smaller than the available space if this limit is reached, e.g. when there are only a few children.
+       */
+      max_tile_height?: number | null;
       /**
        * (**default=`0`**) Margin of each tile in pixels.
        */
@@ -831,6 +1023,10 @@ export type Component =
        * Defines how this component will behave during a scene update. This will only have an effect if the previous scene already contained a `Tiles` component with the same id.
        */
       transition?: Transition | null;
+      /**
+       * Animations of tiles that are added, removed or moved during the `transition`. Ignored if `transition` is not defined.
+       */
+      tile_transitions?: TileTransitions | null;
     }
   | {
       type: "rescaler";
@@ -882,14 +1078,30 @@ export type Component =
        * Rotation of a component in degrees. If this field is defined, this element will be absolutely positioned, instead of being laid out by its parent.
        */
       rotation?: number | null;
+      /**
+       * Rotation of a component in degrees around its horizontal axis (like CSS `rotateX`). Positive values move the top edge away from the viewer. If this field is defined, this element will be absolutely positioned, instead of being laid out by its parent.
+       */
+      rotation_x?: number | null;
+      /**
+       * Rotation of a component in degrees around its vertical axis (like CSS `rotateY`). Positive values move the right edge away from the viewer. If this field is defined, this element will be absolutely positioned, instead of being laid out by its parent.
+       */
+      rotation_y?: number | null;
+      /**
+       * Distance in pixels between the viewer and the component, used to project components rotated with `rotation_x` or `rotation_y`. Smaller values make the effect stronger. Child components without their own `perspective` inherit it. If this field is defined, this element will be absolutely positioned, instead of being laid out by its parent.
+       */
+      perspective?: number | null;
       /**
        * Defines how this component will behave during a scene update. This will only have an effect if the previous scene already contained a `Rescaler` component with the same id.
        */
       transition?: Transition | null;
       /**
-       * (**default=`0.0`**) Radius of a rounded corner.
+       * Keyframe animations of the component properties. They are evaluated on every frame, so multi-step animations do not require scene updates.
+       */
+      keyframes?: KeyframeTrack[] | null;
+      /**
+       * (**default=`0.0`**) Radius of rounded corners. A single number applies to all corners.
        */
-      border_radius?: number | null;
+      border_radius?: BorderRadius | null;
       /**
        * (**default=`0.0`**) Border width.
        */
@@ -902,8 +1114,90 @@ export type Component =
        * List of box shadows.
        */
       box_shadow?: BoxShadow[] | null;
+      /**
+       * Removes the background of a given color from all inputs inside this component, unless
+       * they define their own `chroma_key`.
+       */
+      chroma_key?: ChromaKey | null;
+      /**
+       * (**default=`0.0`**) Standard deviation in pixels of a Gaussian blur applied to all
+       * inputs, images and other textures inside this component. Borders are not blurred. It
+       * can be animated with `transition`.
+       */
+      blur_radius?: number | null;
+      /**
+       * (**default=`1.0`**) Opacity of this component and its child in the range from `0.0`
+       * (fully transparent) to `1.0` (fully opaque). It can be animated with `transition`.
+       */
+      opacity?: number | null;
+      /**
+       * (**default=`"normal"`**) Defines how this component, including its borders and child,
+       * is composited with the content rendered below it.
+       */
+      blend_mode?: BlendMode | null;
+      /**
+       * Part of the child that is rescaled, everything outside of it is cut off. Dimensions of the child that are not known are equal to the size of the rescaler (without borders).
+       */
+      crop?: SourceCrop | null;
+      /**
+       * (**default=`false`**) Mirror the child horizontally. Position and size of the child inside the rescaler are not changed.
+       */
+      flip_horizontal?: boolean | null;
+      /**
+       * (**default=`false`**) Mirror the child vertically. Position and size of the child inside the rescaler are not changed.
+       */
+      flip_vertical?: boolean | null;
+    }
+  | {
+      type: "audio_visualizer";
+      /**
+       * Id of a component.
+       */
+      id?: ComponentId | null;
+      /**
+       * Id of an input. It identifies a stream registered using a [`RegisterInputStream`](../routes.md#register-input) request. Visualization is based on the audio track of that input before volume is applied.
+       */
+      input_id: InputId;
+      /**
+       * Width of the visualization in pixels.
+       */
+      width: number;
+      /**
+       * Height of the visualization in pixels.
+       */
+      height: number;
+      /**
+       * (**default=`{ "type": "bars" }`**) Visualization type.
+       */
+      mode?: AudioVisualizerMode | null;
+      /**
+       * (**default=`"#FFFFFFFF"`**) Color of the waveform or bars in `#RRGGBBAA` format.
+       */
+      color?: RGBAColor | null;
+      /**
+       * (**default=`"#00000000"`**) Background color in `#RRGGBBAA` format.
+       */
+      background_color?: RGBAColor | null;
     };
 export type ComponentId = string;
+/**
+ * Part of a source that is kept, everything outside of it is cut off.
+ */
+export type SourceCrop =
+  | {
+      type: "pixels";
+      top: number;
+      left: number;
+      width: number;
+      height: number;
+    }
+  | {
+      type: "normalized";
+      top: number;
+      left: number;
+      width: number;
+      height: number;
+    };
 export type ViewDirection = "row" | "column";
 /**
  * Easing functions are used to interpolate between two values over time.
@@ -925,9 +1219,57 @@ export type EasingFunction =
        */
       points: [number, number, number, number];
     };
+export type AnimatedProperty =
+  | "top"
+  | "bottom"
+  | "left"
+  | "right"
+  | "width"
+  | "height"
+  | "rotation"
+  | "rotation_x"
+  | "rotation_y"
+  | "opacity"
+  | "blur_radius";
 export type Overflow = "visible" | "hidden" | "fit";
+/**
+ * Fill of the area of a component.
+ */
+export type Background =
+  | {
+      type: "color";
+      color: RGBAColor;
+    }
+  | {
+      type: "linear_gradient";
+      /**
+       * (**default=`180.0`**) Direction of the gradient line in degrees. `0` goes from the bottom to the top, `90` from the left to the right.
+       */
+      angle?: number | null;
+      /**
+       * List of 2 to 8 color stops.
+       */
+      stops: GradientStop[];
+    }
+  | {
+      type: "radial_gradient";
+      /**
+       * (**default=`0.5`**) Horizontal position of the center relative to the width of the component, where `0.0` is the left edge and `1.0` is the right edge.
+       */
+      center_x?: number | null;
+      /**
+       * (**default=`0.5`**) Vertical position of the center relative to the height of the component, where `0.0` is the top edge and `1.0` is the bottom edge.
+       */
+      center_y?: number | null;
+      /**
+       * List of 2 to 8 color stops.
+       */
+      stops: GradientStop[];
+    };
+export type BorderRadius = number | BorderRadiusCorners;
 export type RGBAColor = string;
 export type RendererId = string;
+export type BlendMode = "normal" | "multiply" | "screen" | "overlay" | "add";
 export type ShaderParam =
   | {
       type: "f32";
@@ -979,8 +1321,6 @@ export type ShaderParamStructField1 =
       field_name?: string;
     };
 export type TextStyle = "normal" | "italic" | "oblique";
-export type HorizontalAlign = "left" | "right" | "justified" | "center";
-export type TextWrapMode = "none" | "glyph" | "word";
 /**
  * Font weight, based on the [OpenType specification](https://learn.microsoft.com/en-gb/typography/opentype/spec/os2#usweightclass).
  */
@@ -994,9 +1334,48 @@ export type TextWeight =
   | "bold"
   | "extra_bold"
   | "black";
+export type TickerDirection = "left" | "right";
+export type HorizontalAlign = "left" | "right" | "justified" | "center";
+export type TextDirection = "auto" | "ltr" | "rtl";
+export type TextWrapMode = "none" | "glyph" | "word";
+export type TextFit = "none" | "shrink" | "fill";
+export type TilesFillStrategy =
+  | {
+      type: "fit";
+    }
+  | {
+      type: "fill";
+    }
+  | {
+      type: "fixed_columns";
+      /**
+       * Number of columns.
+       */
+      columns: number;
+    };
 export type AspectRatio = string;
 export type VerticalAlign = "top" | "center" | "bottom" | "justified";
+export type TileTransitionEffect = "none" | "fade" | "slide" | "scale";
 export type RescaleMode = "fit" | "fill";
+export type AudioVisualizerMode =
+  | {
+      type: "waveform";
+      /**
+       * (**default=`2.0`**) Width of the line in pixels.
+       */
+      line_width?: number | null;
+    }
+  | {
+      type: "bars";
+      /**
+       * (**default=`32`**) Number of bars. Has to be in the range from 1 to 256.
+       */
+      count?: number | null;
+      /**
+       * (**default=`2.0`**) Gap between bars in pixels.
+       */
+      gap?: number | null;
+    };
 export type AudioMixingStrategy = "sum_clip" | "sum_scale";
 export type RtpAudioEncoderOptions = {
   type: "opus";
@@ -1414,6 +1793,16 @@ export type ImageSpec =
       url?: string | null;
       path?: string | null;
     }
+  | {
+      asset_type: "webp";
+      url?: string | null;
+      path?: string | null;
+    }
+  | {
+      asset_type: "avif";
+      url?: string | null;
+      path?: string | null;
+    }
   | {
       asset_type: "auto";
       url?: string | null;
@@ -1437,6 +1826,10 @@ export type InputStatsReport =
        * Stats for the audio track.
        */
       audio_rtp: RtpJitterBufferStatsReport;
+      /**
+       * Stats for decoded frames of the video track.
+       */
+      video_frames: VideoFramesStatsReport;
     }
   | {
       type: "whip";
@@ -1448,6 +1841,10 @@ export type InputStatsReport =
        * Stats for the audio track.
        */
       audio_rtp: RtpJitterBufferStatsReport;
+      /**
+       * Stats for decoded frames of the video track.
+       */
+      video_frames: VideoFramesStatsReport;
     }
   | {
       type: "whep";
@@ -1459,6 +1856,10 @@ export type InputStatsReport =
        * Stats for the audio track.
        */
       audio_rtp: RtpJitterBufferStatsReport;
+      /**
+       * Stats for decoded frames of the video track.
+       */
+      video_frames: VideoFramesStatsReport;
     }
   | {
       type: "hls";
@@ -1470,6 +1871,10 @@ export type InputStatsReport =
        * Stats for the audio track.
        */
       audio: HlsInputTrackStatsReport;
+      /**
+       * Stats for decoded frames of the video track.
+       */
+      video_frames: VideoFramesStatsReport;
     }
   | {
       type: "rtmp";
@@ -1481,6 +1886,10 @@ export type InputStatsReport =
        * Stats for the audio track.
        */
       audio: RtmpInputTrackStatsReport;
+      /**
+       * Stats for decoded frames of the video track.
+       */
+      video_frames: VideoFramesStatsReport;
     }
   | {
       type: "moq_server";
@@ -1492,6 +1901,10 @@ export type InputStatsReport =
        * Stats for the audio track.
        */
       audio: MoqServerInputTrackStatsReport;
+      /**
+       * Stats for decoded frames of the video track.
+       */
+      video_frames: VideoFramesStatsReport;
     }
   | {
       type: "moq_client";
@@ -1503,6 +1916,10 @@ export type InputStatsReport =
        * Stats for the audio track.
        */
       audio: MoqClientInputTrackStatsReport;
+      /**
+       * Stats for decoded frames of the video track.
+       */
+      video_frames: VideoFramesStatsReport;
     }
   | {
       type: "mp4";
@@ -1514,6 +1931,10 @@ export type InputStatsReport =
        * Stats for the audio track.
        */
       audio: Mp4InputTrackStatsReport;
+      /**
+       * Stats for decoded frames of the video track.
+       */
+      video_frames: VideoFramesStatsReport;
     };
 /**
  * Stats report for outputs.
@@ -1611,6 +2032,49 @@ export interface SideChannel {
    */
   delay_ms?: number | null;
 }
+/**
+ * Options applied to the RTP socket.
+ */
+export interface RtpSocketOptions {
+  /**
+   * Size of the socket receive buffer (`SO_RCVBUF`) in bytes. Larger buffers reduce packet loss on high-bitrate streams. OS may clamp this value, actually applied size is logged. For UDP inputs defaults to 16MiB, otherwise the OS default is used.
+   */
+  receive_buffer_size_bytes?: number | null;
+  /**
+   * Size of the socket send buffer (`SO_SNDBUF`) in bytes. OS may clamp this value, actually applied size is logged.
+   */
+  send_buffer_size_bytes?: number | null;
+  /**
+   * DSCP value (0-63) used to mark outgoing packets for QoS on managed networks.
+   */
+  dscp?: number | null;
+}
+/**
+ * RTP payload types used to identify video and audio packets. Custom codecs registered in a Smelter instance embedded as a library are selected based on these values.
+ */
+export interface RtpPayloadTypes {
+  /**
+   * (**default=`96`**) Payload type of the video track.
+   */
+  video?: number | null;
+  /**
+   * (**default=`97`**) Payload type of the audio track.
+   */
+  audio?: number | null;
+}
+/**
+ * Clear key used to decrypt the MP4 file.
+ */
+export interface Mp4InputDecryption {
+  /**
+   * 128-bit content key as a hex string (32 characters).
+   */
+  key: string;
+  /**
+   * 128-bit key ID as a hex string (32 characters). If provided, it has to match the key ID of encrypted tracks in the file.
+   */
+  key_id?: string | null;
+}
 export interface InputWhipVideoOptions {
   decoder_preferences?: WhipVideoDecoderOptions[] | null;
 }
@@ -1644,6 +2108,10 @@ export interface OutputRtpVideoOptions {
    * Root of a component tree/scene that should be rendered for the output. Use [`update_output` request](../routes.md#update-output) to update this value after registration. [Learn more](../../concept/component.md).
    */
   initial: VideoScene;
+  /**
+   * (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame. Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+   */
+  debug_overlay?: boolean | null;
 }
 /**
  * This type defines when end of an input stream should trigger end of the output stream. Only one of those fields can be set at the time. Unless specified otherwise the input stream is considered finished/ended when: - TCP connection was dropped/closed. - RTCP Goodbye packet (`BYE`) was received. - Mp4 track has ended. - Input was unregistered already (or never registered).
@@ -1669,6 +2137,76 @@ export interface OutputEndCondition {
 export interface VideoScene {
   root: Component;
 }
+export interface ChromaKey {
+  /**
+   * (**default=`"#00FF00"`**) Color that should be removed in a `"#RRGGBB"` format.
+   */
+  key_color?: RGBAColor | null;
+  /**
+   * (**default=`0.4`**) How close to the key color (in the range 0 to 1) a pixel needs to be
+   * to become fully transparent.
+   */
+  similarity?: number | null;
+  /**
+   * (**default=`0.08`**) Width of the range over which pixels fade from fully transparent to
+   * fully opaque.
+   */
+  smoothness?: number | null;
+  /**
+   * (**default=`0.1`**) Reduces the key color reflected onto the foreground. Set to `0.0` to
+   * disable.
+   */
+  spill_reduction?: number | null;
+}
+export interface ColorCorrection {
+  /**
+   * (**default=`0.0`**) Value in the range -1 to 1 added to all color channels.
+   */
+  brightness?: number | null;
+  /**
+   * (**default=`1.0`**) Multiplier of the distance from the middle gray. Values below `1.0`
+   * decrease and values above `1.0` increase the contrast.
+   */
+  contrast?: number | null;
+  /**
+   * (**default=`1.0`**) Multiplier of the color intensity. `0.0` produces a grayscale image.
+   */
+  saturation?: number | null;
+  /**
+   * (**default=`0.0`**) Rotation of the hue in degrees.
+   */
+  hue_shift?: number | null;
+}
+/**
+ * Pan and zoom animation (Ken Burns effect). The visible part of the component is moved and resized from the `start` to the `end` rectangle and stretched to the size of the component. The animation starts when it is added to the scene and continues across scene updates as long as it does not change and the component keeps the same `id`.
+ */
+export interface KenBurns {
+  /**
+   * Part of the component visible at the start of the animation.
+   */
+  start: NormalizedRect;
+  /**
+   * Part of the component visible at the end of the animation and after it finishes.
+   */
+  end: NormalizedRect;
+  /**
+   * Duration of the animation in milliseconds.
+   */
+  duration_ms: number;
+  /**
+   * (**default=`"linear"`**) Easing function to be used for the animation.
+   */
+  easing_function?: EasingFunction | null;
+}
+/**
+ * Rectangle in coordinates relative to the size of a component, where `0.0` is the top/left edge and `1.0` is the bottom/right edge of the component.
+ */
+export interface NormalizedRect {
+  top: number;
+  left: number;
+  width: number;
+  height: number;
+}
 export interface Transition {
   /**
    * Duration of a transition in milliseconds.
@@ -1683,11 +2221,194 @@ export interface Transition {
    */
   should_interrupt?: boolean | null;
 }
+/**
+ * Animation of a single property of a component. The animation starts when the track is added to the scene and continues across scene updates as long as the track does not change and the component keeps the same `id`.
+ */
+export interface KeyframeTrack {
+  /**
+   * Animated property. `top`, `bottom`, `left` and `right` can be animated only if they are defined on the component, and rotations only if the component is absolutely positioned.
+   */
+  property: AnimatedProperty;
+  /**
+   * List of keyframes sorted by `time_ms`. At least one keyframe is required.
+   */
+  keyframes: Keyframe[];
+  /**
+   * (**default=`false`**) Restart the animation after the last keyframe.
+   */
+  repeat?: boolean | null;
+}
+export interface Keyframe {
+  /**
+   * Time in milliseconds since the start of the animation.
+   */
+  time_ms: number;
+  /**
+   * Value of the property at this keyframe.
+   */
+  value: number;
+  /**
+   * (**default=`"linear"`**) Easing function used to interpolate between this keyframe and the next one.
+   */
+  easing_function?: EasingFunction | null;
+}
+export interface GradientStop {
+  /**
+   * Color in `#RRGGBBAA` format.
+   */
+  color: RGBAColor;
+  /**
+   * Position of the stop on the gradient line in the range from 0 to 1. Positions have to be in ascending order. If not provided, the first stop is placed at 0, the last at 1, and others are evenly spaced between their neighbors.
+   */
+  position?: number | null;
+}
+export interface BorderRadiusCorners {
+  /**
+   * (**default=`0.0`**) Radius of the top-left corner.
+   */
+  top_left?: number | null;
+  /**
+   * (**default=`0.0`**) Radius of the top-right corner.
+   */
+  top_right?: number | null;
+  /**
+   * (**default=`0.0`**) Radius of the bottom-right corner.
+   */
+  bottom_right?: number | null;
+  /**
+   * (**default=`0.0`**) Radius of the bottom-left corner.
+   */
+  bottom_left?: number | null;
+}
 export interface BoxShadow {
   offset_x?: number | null;
   offset_y?: number | null;
   color?: RGBAColor | null;
   blur_radius?: number | null;
+  /**
+   * (**default=`0.0`**) Distance in pixels by which the shadow is expanded in all directions before it is blurred. Negative values shrink the shadow.
+   */
+  spread?: number | null;
+}
+export interface NineSlice {
+  /**
+   * (**default=`0.0`**) Distance in pixels of the source image from the top edge.
+   */
+  top?: number | null;
+  /**
+   * (**default=`0.0`**) Distance in pixels of the source image from the right edge.
+   */
+  right?: number | null;
+  /**
+   * (**default=`0.0`**) Distance in pixels of the source image from the bottom edge.
+   */
+  bottom?: number | null;
+  /**
+   * (**default=`0.0`**) Distance in pixels of the source image from the left edge.
+   */
+  left?: number | null;
+}
+export interface TextSpan {
+  /**
+   * Text of the span. It can contain newlines.
+   */
+  text: string;
+  /**
+   * Font family of the span. Defaults to `font_family` of the component.
+   */
+  font_family?: string | null;
+  /**
+   * Font size of the span in pixels. Defaults to `font_size` of the component. Line height is scaled proportionally to `line_height` of the component.
+   */
+  font_size?: number | null;
+  /**
+   * Font style of the span. Defaults to `style` of the component.
+   */
+  style?: TextStyle | null;
+  /**
+   * Font weight of the span. Defaults to `weight` of the component.
+   */
+  weight?: TextWeight | null;
+  /**
+   * Font color of the span in `#RRGGBBAA` format. Defaults to `color` of the component. Stroke, shadow and gradient are applied to the whole text regardless of this value.
+   */
+  color?: RGBAColor | null;
+}
+export interface LinearGradient {
+  /**
+   * (**default=`180.0`**) Direction of the gradient line in degrees, the same as in the CSS `linear-gradient` function. `0` goes from the bottom to the top, `90` from the left to the right.
+   */
+  angle?: number | null;
+  /**
+   * List of 2 to 8 color stops.
+   */
+  stops: GradientStop[];
+}
+export interface TextStroke {
+  /**
+   * Width of the outline in pixels.
+   */
+  width: number;
+  /**
+   * (**default=`"#000000FF"`**) Outline color in `#RRGGBBAA` format.
+   */
+  color?: RGBAColor | null;
+}
+export interface TextShadow {
+  /**
+   * (**default=`0.0`**) Horizontal offset of the shadow in pixels.
+   */
+  offset_x?: number | null;
+  /**
+   * (**default=`0.0`**) Vertical offset of the shadow in pixels.
+   */
+  offset_y?: number | null;
+  /**
+   * (**default=`"#000000FF"`**) Shadow color in `#RRGGBBAA` format.
+   */
+  color?: RGBAColor | null;
+  /**
+   * (**default=`0.0`**) Standard deviation in pixels of a Gaussian blur applied to the shadow.
+   */
+  blur_radius?: number | null;
+}
+export interface TextTicker {
+  /**
+   * Scrolling speed in pixels per second.
+   */
+  speed: number;
+  /**
+   * (**default=`"left"`**) Direction in which the text moves. The text enters the texture from the opposite edge.
+   */
+  direction?: TickerDirection | null;
+  /**
+   * (**default=`true`**) If `true`, the text enters again after it leaves the texture, otherwise it stays hidden.
+   */
+  repeat?: boolean | null;
+}
+export interface TileTransitions {
+  /**
+   * (**default=`{ "effect": "fade" }`**) Animation of a tile of a child added in the scene update.
+   */
+  enter?: TileTransition | null;
+  /**
+   * (**default=`{ "effect": "fade" }`**) Animation of a tile of a child removed in the scene update.
+   */
+  exit?: TileTransition | null;
+  /**
+   * (**default=`{ "effect": "slide" }`**) Animation of a tile of a child that changed its position or size in the scene update.
+   */
+  reorder?: TileTransition | null;
+}
+export interface TileTransition {
+  /**
+   * Animation effect.
+   */
+  effect: TileTransitionEffect;
+  /**
+   * Duration of the animation in milliseconds. Defaults to the duration of the component `transition`.
+   */
+  duration_ms?: number | null;
 }
 export interface OutputRtpAudioOptions {
   /**
@@ -1738,6 +2459,10 @@ export interface OutputRtmpClientVideoOptions {
    * Root of a component tree/scene that should be rendered for the output. Use [`update_output` request](../routes.md#update-output) to update this value after registration. [Learn more](../../concept/component.md).
    */
   initial: VideoScene;
+  /**
+   * (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame. Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+   */
+  debug_overlay?: boolean | null;
 }
 export interface OutputRtmpClientAudioOptions {
   /**
@@ -1778,6 +2503,10 @@ export interface OutputMp4VideoOptions {
    * Root of a component tree/scene that should be rendered for the output. Use [`update_output` request](../routes.md#update-output) to update this value after registration. [Learn more](../../concept/component.md).
    */
   initial: VideoScene;
+  /**
+   * (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame. Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+   */
+  debug_overlay?: boolean | null;
 }
 export interface OutputMp4AudioOptions {
   /**
@@ -1818,6 +2547,10 @@ export interface OutputWhipVideoOptions {
    * Root of a component tree/scene that should be rendered for the output.
    */
   initial: VideoScene;
+  /**
+   * (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame. Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+   */
+  debug_overlay?: boolean | null;
 }
 export interface OutputWhipAudioOptions {
   /**
@@ -1858,6 +2591,10 @@ export interface OutputWhepVideoOptions {
    * Root of a component tree/scene that should be rendered for the output. Use [`update_output` request](../routes.md#update-output) to update this value after registration. [Learn more](../../concept/component.md).
    */
   initial: VideoScene;
+  /**
+   * (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame. Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+   */
+  debug_overlay?: boolean | null;
 }
 export interface OutputWhepAudioOptions {
   /**
@@ -1898,6 +2635,10 @@ export interface OutputHlsVideoOptions {
    * Root of a component tree/scene that should be rendered for the output. Use [`update_output` request](../routes.md#update-output) to update this value after registration. [Learn more](../../concept/component.md).
    */
   initial: VideoScene;
+  /**
+   * (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame. Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+   */
+  debug_overlay?: boolean | null;
 }
 export interface OutputHlsAudioOptions {
   /**
@@ -1921,6 +2662,19 @@ export interface OutputHlsAudioOptions {
    */
   initial: AudioScene;
 }
+/**
+ * 3D color lookup table in the `.cube` format.
+ */
+export interface LutSpec {
+  /**
+   * URL of the `.cube` file.
+   */
+  url?: string | null;
+  /**
+   * Path to the `.cube` file on the local filesystem.
+   */
+  path?: string | null;
+}
 export interface WebRendererSpec {
   /**
    * Url of a website that you want to render.
@@ -2155,6 +2909,48 @@ export interface Mp4InputTrackStatsReport {
    */
   bitrate_1_minute: number;
 }
+/**
+ * Stats report for decoded video frames, counted when they reach the queue.
+ */
+export interface VideoFramesStatsReport {
+  /**
+   * Total count of frames produced by the decoder.
+   */
+  frames_decoded: number;
+  /**
+   * Total count of frames dropped because a newer frame was already available when the queue processed them.
+   */
+  frames_dropped: number;
+  /**
+   * Count of frames produced by the decoder in the 1-second window.
+   */
+  framerate_1_second: number;
+  /**
+   * Video frames stats in the 10-second window.
+   */
+  last_10_seconds: VideoFramesSlidingWindowStatsReport;
+  /**
+   * Time the GPU spent decoding the last frame. Only available for the Vulkan H264 decoder when GPU decode timing is enabled.
+   */
+  last_gpu_decode_time_seconds?: number | null;
+  /**
+   * Fraction of the 1-second window the GPU spent decoding frames of this input, in the range [0, 1]. Only available when `last_gpu_decode_time_seconds` is.
+   */
+  gpu_decode_utilization_1_second?: number | null;
+}
+/**
+ * Stats report for decoded video frames in the given time window.
+ */
+export interface VideoFramesSlidingWindowStatsReport {
+  /**
+   * Count of frames produced by the decoder during the given time window.
+   */
+  frames_decoded: number;
+  /**
+   * Count of frames dropped during the given time window.
+   */
+  frames_dropped: number;
+}
 /**
  * Stats report for a track in the `WHEP` output.
  */
@@ -0,0 +1,91 @@
+import type * as Api from '../api.js';
+import type { ComponentBaseProps, SceneComponent } from '../component.js';
+import { createSmelterComponent } from '../component.js';
+import { inputRefIntoRawId } from '../internal.js';
+
+export type AudioVisualizerMode =
+  | {
+      /**
+       * Oscilloscope-like line showing the most recent audio samples.
+       */
+      type: 'waveform';
+      /**
+       * (**default=`2.0`**) Width of the line in pixels.
+       */
+      lineWidth?: number;
+    }
+  | {
+      /**
+       * Frequency spectrum split into bars on a logarithmic frequency scale.
+       */
+      type: 'bars';
+      /**
+       * (**default=`32`**) Number of bars. Has to be in the range from 1 to 256.
+       */
+      count?: number;
+      /**
+       * (**default=`2.0`**) Gap between bars in pixels.
+       */
+      gap?: number;
+    };
+
+export type AudioVisualizerStyleProps = {
+  /**
+   * Width of the visualization in pixels.
+   */
+  width: number;
+  /**
+   * Height of the visualization in pixels.
+   */
+  height: number;
+  /**
+   * (**default=`"#FFFFFFFF"`**) Color of the waveform or bars in `RGB` or `RGBA` format.
+   */
+  color?: string;
+  /**
+   * (**default=`"#00000000"`**) Background color in `RGB` or `RGBA` format.
+   */
+  backgroundColor?: string;
+};
+
+export type AudioVisualizerProps = Omit<ComponentBaseProps, 'children'> & {
+  /**
+   * Id of an input. It identifies a stream registered using a `Smelter.registerInput`.
+   * Visualization is based on the audio track of that input before volume is applied.
+   */
+  inputId: Api.InputId;
+  /**
+   * (**default=`{ type: "bars" }`**) Visualization type.
+   */
+  mode?: AudioVisualizerMode;
+  /**
+   * Component styling properties.
+   */
+  style: AudioVisualizerStyleProps;
+};
+
+const AudioVisualizer = createSmelterComponent<AudioVisualizerProps>(sceneBuilder);
+
+function sceneBuilder(props: AudioVisualizerProps, _children: SceneComponent[]): Api.Component {
+  const { id, inputId, mode, style } = props;
+  return {
+    type: 'audio_visualizer',
+    id,
+    input_id: inputRefIntoRawId({ type: 'global', id: inputId }),
+    width: style.width,
+    height: style.height,
+    mode: mode && intoApiAudioVisualizerMode(mode),
+    color: style.color,
+    background_color: style.backgroundColor,
+  };
+}
+
+function intoApiAudioVisualizerMode(mode: AudioVisualizerMode): Api.AudioVisualizerMode {
+  if (mode.type === 'waveform') {
+    return { type: 'waveform', line_width: mode.lineWidth };
+  } else {
+    return { type: 'bars', count: mode.count, gap: mode.gap };
+  }
+}
+
+export default AudioVisualizer;
@@ -7,6 +7,8 @@ import { newInternalImageId } from '../context/internalImageIdManager.js';
 import { newBlockingTask } from '../hooks.js';
 import { SmelterContext } from '../context/index.js';
 import type { RegisterImage } from '../types/resource.js';
+import type { KenBurns } from './common.js';
+import { intoApiKenBurns } from './common.js';
 
 export type ImageProps = Omit<ComponentBaseProps, 'children'> &
   (
@@ -19,6 +21,21 @@ export type ImageProps = Omit<ComponentBaseProps, 'children'> &
         imageId?: never; // Ensuring 'imageId' cannot be used alongside 'source'
       }
   ) & {
+    /**
+     * Pan and zoom animation of the image. Applied only if the component is a child of
+     * a layout component (`View`, `Tiles` or `Rescaler`).
+     */
+    kenBurns?: KenBurns;
+    /**
+     * (**default=`false`**) Mirror the image horizontally. Applied only if the component is
+     * a child of a layout component (`View`, `Tiles` or `Rescaler`).
+     */
+    flipHorizontal?: boolean;
+    /**
+     * (**default=`false`**) Mirror the image vertically. Applied only if the component is
+     * a child of a layout component (`View`, `Tiles` or `Rescaler`).
+     */
+    flipVertical?: boolean;
     /**
      * Component styling properties.
      */
@@ -34,6 +51,29 @@ type ImageStyleProps = {
    * Height of the image in pixels. If `width` is not explicitly provided, the image will automatically adjust its width to maintain its original aspect ratio relative to the height.
    */
   height?: number;
+  /**
+   * Enables nine-slice scaling. Image is split into 9 parts by the insets, corners are rendered without scaling, edges are stretched along one axis and the center along both. Not supported for SVG images.
+   */
+  nineSlice?: NineSlice;
+};
+
+type NineSlice = {
+  /**
+   * (**default=`0.0`**) Distance in pixels of the source image from the top edge.
+   */
+  top?: number;
+  /**
+   * (**default=`0.0`**) Distance in pixels of the source image from the right edge.
+   */
+  right?: number;
+  /**
+   * (**default=`0.0`**) Distance in pixels of the source image from the bottom edge.
+   */
+  bottom?: number;
+  /**
+   * (**default=`0.0`**) Distance in pixels of the source image from the left edge.
+   */
+  left?: number;
 };
 
 type ImageSceneBuliderProps = Omit<ImageProps, 'imageId'> & { imageId: string };
@@ -115,6 +155,10 @@ function sceneBuilder(props: ImageSceneBuliderProps, _children: SceneComponent[]
     image_id: props.imageId,
     width: props.style?.width,
     height: props.style?.height,
+    nine_slice: props.style?.nineSlice,
+    ken_burns: props.kenBurns && intoApiKenBurns(props.kenBurns),
+    flip_horizontal: props.flipHorizontal,
+    flip_vertical: props.flipVertical,
   };
 }
 
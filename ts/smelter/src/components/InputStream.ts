@@ -6,6 +6,8 @@ import { useAudioInput, useInputStreams } from '../hooks.js';
 import { useTimeLimitedComponent } from '../context/childrenLifetimeContext.js';
 import { SmelterContext } from '../context/index.js';
 import { inputRefIntoRawId } from '../internal.js';
+import type { ChromaKey, ColorCorrection, KenBurns, SourceCrop } from './common.js';
+import { intoApiChromaKey, intoApiColorCorrection, intoApiKenBurns } from './common.js';
 
 export type InputStreamProps = Omit<ComponentBaseProps, 'children'> & {
   /**
@@ -20,6 +22,45 @@ export type InputStreamProps = Omit<ComponentBaseProps, 'children'> & {
    * Mute audio.
    */
   muted?: boolean;
+  /**
+   * Removes the background of a given color from the input, e.g. a green screen.
+   */
+  chromaKey?: ChromaKey;
+  /**
+   * Adjusts brightness, contrast, saturation and hue of the input.
+   */
+  colorCorrection?: ColorCorrection;
+  /**
+   * Id of a LUT registered using `Smelter.registerLut`. The LUT is applied after
+   * the color correction.
+   */
+  lutId?: Api.RendererId;
+  /**
+   * (**default=`1.0`**) Opacity of the input in the range from `0.0` (fully transparent) to
+   * `1.0` (fully opaque).
+   */
+  opacity?: number;
+  /**
+   * Part of the input that is rendered, e.g. to cut off letterboxing. Size of the component
+   * is equal to the size of the cropped part.
+   */
+  crop?: SourceCrop;
+  /**
+   * Pan and zoom animation of the input. Applied only if the component is a child of
+   * a layout component (`View`, `Tiles` or `Rescaler`).
+   */
+  kenBurns?: KenBurns;
+  /**
+   * (**default=`false`**) Mirror the input horizontally, e.g. for front-facing camera feeds.
+   * Applied only if the component is a child of a layout component (`View`, `Tiles` or
+   * `Rescaler`).
+   */
+  flipHorizontal?: boolean;
+  /**
+   * (**default=`false`**) Mirror the input vertically. Applied only if the component is
+   * a child of a layout component (`View`, `Tiles` or `Rescaler`).
+   */
+  flipVertical?: boolean;
 };
 
 type AudioPropNames = 'muted' | 'volume';
@@ -62,6 +103,14 @@ function sceneBuilder(props: InputStreamProps, _children: SceneComponent[]): Api
     type: 'input_stream',
     id: props.id,
     input_id: props.inputId,
+    chroma_key: props.chromaKey && intoApiChromaKey(props.chromaKey),
+    color_correction: props.colorCorrection && intoApiColorCorrection(props.colorCorrection),
+    lut_id: props.lutId,
+    opacity: props.opacity,
+    crop: props.crop,
+    ken_burns: props.kenBurns && intoApiKenBurns(props.kenBurns),
+    flip_horizontal: props.flipHorizontal,
+    flip_vertical: props.flipVertical,
   };
 }
 
@@ -1,7 +1,20 @@
 import type React from 'react';
 import type * as Api from '../api.js';
-import type { BoxShadow, Transition } from './common.js';
-import { intoApiBoxShadow, intoApiTransition } from './common.js';
+import type {
+  BorderRadius,
+  BoxShadow,
+  ChromaKey,
+  KeyframeTrack,
+  SourceCrop,
+  Transition,
+} from './common.js';
+import {
+  intoApiBorderRadius,
+  intoApiBoxShadow,
+  intoApiChromaKey,
+  intoApiKeyframeTrack,
+  intoApiTransition,
+} from './common.js';
 import type { ComponentBaseProps, SceneComponent } from '../component.js';
 import { createSmelterComponent, sceneComponentIntoApi } from '../component.js';
 
@@ -63,9 +76,28 @@ export type RescalerStyleProps = {
    */
   rotation?: number;
   /**
-   * (**default=`0.0`**) Radius of a rounded corner.
+   * Rotation of a component in degrees around its horizontal axis (like CSS `rotateX`).
+   * Positive values move the top edge away from the viewer. If this field is defined, this
+   * element will be absolutely positioned, instead of being laid out by its parent.
    */
-  borderRadius?: number;
+  rotationX?: number;
+  /**
+   * Rotation of a component in degrees around its vertical axis (like CSS `rotateY`).
+   * Positive values move the right edge away from the viewer. If this field is defined, this
+   * element will be absolutely positioned, instead of being laid out by its parent.
+   */
+  rotationY?: number;
+  /**
+   * Distance in pixels between the viewer and the component, used to project components
+   * rotated with `rotationX` or `rotationY`. Smaller values make the effect stronger.
+   * Child components without their own `perspective` inherit it. If this field is defined,
+   * this element will be absolutely positioned, instead of being laid out by its parent.
+   */
+  perspective?: number;
+  /**
+   * (**default=`0.0`**) Radius of rounded corners. A single number applies to all corners.
+   */
+  borderRadius?: BorderRadius;
   /**
    * (**default=`0.0`**) Border width.
    */
@@ -78,6 +110,41 @@ export type RescalerStyleProps = {
    * Properties of the BoxShadow applied to the container.
    */
   boxShadow?: BoxShadow[];
+  /**
+   * Removes the background of a given color from all inputs inside this component, unless
+   * they define their own `chromaKey`.
+   */
+  chromaKey?: ChromaKey;
+  /**
+   * (**default=`0.0`**) Standard deviation in pixels of a Gaussian blur applied to all
+   * inputs, images and other textures inside this component. Borders are not blurred.
+   */
+  blurRadius?: number;
+  /**
+   * (**default=`1.0`**) Opacity of this component and its child in the range from `0.0`
+   * (fully transparent) to `1.0` (fully opaque).
+   */
+  opacity?: number;
+  /**
+   * (**default=`"normal"`**) Defines how this component, including its borders and child,
+   * is composited with the content rendered below it.
+   */
+  blendMode?: Api.BlendMode;
+  /**
+   * Part of the child that is rescaled, everything outside of it is cut off. Dimensions of
+   * the child that are not known are equal to the size of the rescaler (without borders).
+   */
+  crop?: SourceCrop;
+  /**
+   * (**default=`false`**) Mirror the child horizontally. Position and size of the child
+   * inside the rescaler are not changed.
+   */
+  flipHorizontal?: boolean;
+  /**
+   * (**default=`false`**) Mirror the child vertically. Position and size of the child
+   * inside the rescaler are not changed.
+   */
+  flipVertical?: boolean;
 };
 
 export type RescalerProps = ComponentBaseProps & {
@@ -94,12 +161,17 @@ export type RescalerProps = ComponentBaseProps & {
    * effect if the previous scene already contained a `Rescaler` component with the same id.
    */
   transition?: Transition;
+  /**
+   * Keyframe animations of the component properties. They are evaluated on every frame,
+   * so multi-step animations do not require scene updates.
+   */
+  keyframes?: KeyframeTrack[];
 };
 
 const Rescaler = createSmelterComponent<RescalerProps>(sceneBuilder);
 
 function sceneBuilder(
-  { id, style, transition }: RescalerProps,
+  { id, style, transition, keyframes }: RescalerProps,
   children: SceneComponent[]
 ): Api.Component {
   if (children?.length !== 1) {
@@ -120,11 +192,22 @@ function sceneBuilder(
     left: style?.left,
     right: style?.right,
     rotation: style?.rotation,
+    rotation_x: style?.rotationX,
+    rotation_y: style?.rotationY,
+    perspective: style?.perspective,
     transition: transition && intoApiTransition(transition),
-    border_radius: style?.borderRadius,
+    keyframes: keyframes?.map(intoApiKeyframeTrack),
+    border_radius: style?.borderRadius && intoApiBorderRadius(style.borderRadius),
     border_width: style?.borderWidth,
     border_color: style?.borderColor,
     box_shadow: style?.boxShadow && intoApiBoxShadow(style.boxShadow),
+    chroma_key: style?.chromaKey && intoApiChromaKey(style.chromaKey),
+    blur_radius: style?.blurRadius,
+    opacity: style?.opacity,
+    blend_mode: style?.blendMode,
+    crop: style?.crop,
+    flip_horizontal: style?.flipHorizontal,
+    flip_vertical: style?.flipVertical,
   };
 }
 
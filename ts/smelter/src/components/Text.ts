@@ -1,6 +1,7 @@
 import type * as Api from '../api.js';
 import type { ComponentBaseProps, SceneComponent } from '../component.js';
 import { createSmelterComponent, DEFAULT_FONT_SIZE } from '../component.js';
+import type { GradientStop } from './common.js';
 
 export type TextStyleProps = {
   /**
@@ -36,10 +37,33 @@ export type TextStyleProps = {
    * (**default=`"#FFFFFFFF"`**) Font color in `RGB` or `RGBA` format.
    */
   color?: string;
+  /**
+   * Linear gradient that fills the text instead of `color`. The gradient spans the entire
+   * texture that the text is rendered on.
+   */
+  gradient?: LinearGradient;
   /**
    * (**default=`"#00000000"`**) Background color in `RGB` or `RGBA` format.
    */
   backgroundColor?: string;
+  /**
+   * Outline drawn around the glyphs. If the texture is sized based on the text, it is
+   * extended to fit the stroke.
+   */
+  stroke?: TextStroke;
+  /**
+   * Shadow of the text and its stroke. If the texture is sized based on the text, it is
+   * extended to fit the shadow.
+   */
+  shadow?: TextShadow;
+  /**
+   * Continuously scrolls the text horizontally, e.g. for news tickers. Text is not wrapped
+   * and `wrap` is ignored. It's an error to provide `ticker` if `width` is not defined.
+   *
+   * Scrolling continues across scene updates as long as the component keeps the same `id`
+   * and its text and `ticker` do not change.
+   */
+  ticker?: TextTicker;
   /**
    * (**default=`"Verdana"`**) Font family. Provide [family-name](https://www.w3.org/TR/2018/REC-css-fonts-3-20180920/#family-name-value)
    * for a specific font. "generic-family" values like e.g. "sans-serif" will not work.
@@ -50,9 +74,16 @@ export type TextStyleProps = {
    */
   fontStyle?: Api.TextStyle;
   /**
-   * (**default=`"left"`**) Text align.
+   * Text align. By default, each paragraph is aligned to its start, i.e. to the left for
+   * left-to-right and to the right for right-to-left text.
    */
   align?: Api.HorizontalAlign;
+  /**
+   * (**default=`"auto"`**) Base direction of the text. Right-to-left (e.g. Arabic, Hebrew) and
+   * mixed-direction text is reordered and shaped according to the Unicode Bidirectional
+   * Algorithm regardless of this value.
+   */
+  direction?: Api.TextDirection;
   /**
    * (**default=`"none"`**) Text wrapping options.
    */
@@ -61,6 +92,102 @@ export type TextStyleProps = {
    * (**default=`"normal"`**) Font weight. The selected font needs to support the specified weight.
    */
   fontWeight?: Api.TextWeight;
+  /**
+   * (**default=`"none"`**) Adjusts the font size, so the text fits within `width` and `height`
+   * (or `maxWidth` and `maxHeight` if they are not defined). Line height and font sizes of
+   * `spans` are scaled proportionally. It's an error to provide `fit` together with `ticker`.
+   */
+  fit?: Api.TextFit;
+};
+
+export type LinearGradient = {
+  /**
+   * (**default=`180.0`**) Direction of the gradient line in degrees, the same as in the CSS
+   * `linear-gradient` function. `0` goes from the bottom to the top, `90` from the left to
+   * the right.
+   */
+  angle?: number;
+  /**
+   * List of 2 to 8 color stops.
+   */
+  stops: GradientStop[];
+};
+
+export type TextStroke = {
+  /**
+   * Width of the outline in pixels.
+   */
+  width: number;
+  /**
+   * (**default=`"#000000FF"`**) Outline color in `RGB` or `RGBA` format.
+   */
+  color?: string;
+};
+
+export type TextShadow = {
+  /**
+   * (**default=`0.0`**) Horizontal offset of the shadow in pixels.
+   */
+  offsetX?: number;
+  /**
+   * (**default=`0.0`**) Vertical offset of the shadow in pixels.
+   */
+  offsetY?: number;
+  /**
+   * (**default=`"#000000FF"`**) Shadow color in `RGB` or `RGBA` format.
+   */
+  color?: string;
+  /**
+   * (**default=`0.0`**) Standard deviation in pixels of a Gaussian blur applied to the
+   * shadow.
+   */
+  blurRadius?: number;
+};
+
+export type TextTicker = {
+  /**
+   * Scrolling speed in pixels per second.
+   */
+  speed: number;
+  /**
+   * (**default=`"left"`**) Direction in which the text moves. The text enters the texture
+   * from the opposite edge.
+   */
+  direction?: Api.TickerDirection;
+  /**
+   * (**default=`true`**) If `true`, the text enters again after it leaves the texture,
+   * otherwise it stays hidden.
+   */
+  repeat?: boolean;
+};
+
+export type TextSpan = {
+  /**
+   * Text of the span. It can contain newlines.
+   */
+  text: string;
+  /**
+   * Font family of the span. Defaults to `fontFamily` of the component.
+   */
+  fontFamily?: string;
+  /**
+   * Font size of the span in pixels. Defaults to `fontSize` of the component. Line height
+   * is scaled proportionally to `lineHeight` of the component.
+   */
+  fontSize?: number;
+  /**
+   * Font style of the span. Defaults to `fontStyle` of the component.
+   */
+  fontStyle?: Api.TextStyle;
+  /**
+   * Font weight of the span. Defaults to `fontWeight` of the component.
+   */
+  fontWeight?: Api.TextWeight;
+  /**
+   * Font color of the span in `RGB` or `RGBA` format. Defaults to `color` of the component.
+   * Stroke, shadow and gradient are applied to the whole text regardless of this value.
+   */
+  color?: string;
 };
 
 export type TextProps = ComponentBaseProps & {
@@ -68,6 +195,11 @@ export type TextProps = ComponentBaseProps & {
    * Text content.
    */
   children?: (string | number)[] | string | number;
+  /**
+   * Parts of the text rendered one after another, each with its own styling. If provided,
+   * `children` are ignored.
+   */
+  spans?: TextSpan[];
   /**
    * Text styling properties
    */
@@ -77,12 +209,15 @@ export type TextProps = ComponentBaseProps & {
 const Text = createSmelterComponent<TextProps>(sceneBuilder);
 
 function sceneBuilder(props: TextProps, children: SceneComponent[]): Api.Component {
-  const { id, style } = props;
+  const { id, style, spans } = props;
 
   return {
     type: 'text',
     id: id,
-    text: children.map(child => (typeof child === 'string' ? child : String(child))).join(''),
+    text: spans
+      ? undefined
+      : children.map(child => (typeof child === 'string' ? child : String(child))).join(''),
+    spans: spans?.map(intoApiTextSpan),
     width: style?.width,
     height: style?.height,
     max_width: style?.maxWidth,
@@ -90,12 +225,38 @@ function sceneBuilder(props: TextProps, children: SceneComponent[]): Api.Compone
     font_size: style?.fontSize ?? DEFAULT_FONT_SIZE,
     line_height: style?.lineHeight,
     color: style?.color,
+    gradient: style?.gradient,
     background_color: style?.backgroundColor,
+    stroke: style?.stroke,
+    shadow: style?.shadow && intoApiTextShadow(style.shadow),
+    ticker: style?.ticker,
     font_family: style?.fontFamily,
     style: style?.fontStyle,
     align: style?.align,
+    direction: style?.direction,
     wrap: style?.wrap,
     weight: style?.fontWeight,
+    fit: style?.fit,
+  };
+}
+
+function intoApiTextSpan(span: TextSpan): Api.TextSpan {
+  return {
+    text: span.text,
+    font_family: span.fontFamily,
+    font_size: span.fontSize,
+    style: span.fontStyle,
+    weight: span.fontWeight,
+    color: span.color,
+  };
+}
+
+function intoApiTextShadow(shadow: TextShadow): Api.TextShadow {
+  return {
+    offset_x: shadow.offsetX,
+    offset_y: shadow.offsetY,
+    color: shadow.color,
+    blur_radius: shadow.blurRadius,
   };
 }
 
@@ -25,10 +25,31 @@ export type TilesStyleProps = {
    * (**default=`"#00000000"`**) Background color in `RGB` or `RGBA` format.
    */
   backgroundColor?: string;
+  /**
+   * (**default=`{ type: "fit" }`**) Defines how tiles are arranged and sized.
+   * - `fit` - Number of rows and columns is selected to maximize the size of tiles. Tiles
+   *   preserve `tileAspectRatio`, so they might not cover the entire component.
+   * - `fill` - Number of rows and columns is selected the same way as for `fit`, but tiles
+   *   are stretched to cover the entire component.
+   * - `fixed_columns` - Tiles are arranged in a constant number of columns and preserve
+   *   `tileAspectRatio`.
+   */
+  fillStrategy?: Api.TilesFillStrategy;
   /**
    * (**default=`"16:9"`**) Aspect ratio of a tile in `"W:H"` format, where W and H are integers.
+   * Ignored by the `fill` strategy.
    */
   tileAspectRatio?: Api.AspectRatio | null;
+  /**
+   * Maximal width of a tile in pixels (without padding). Tiles are smaller than the
+   * available space if this limit is reached, e.g. when there are only a few children.
+   */
+  maxTileWidth?: number;
+  /**
+   * Maximal height of a tile in pixels (without padding). Tiles are smaller than the
+   * available space if this limit is reached, e.g. when there are only a few children.
+   */
+  maxTileHeight?: number;
   /**
    * (**default=`0`**) Margin of each tile in pixels.
    */
@@ -47,6 +68,41 @@ export type TilesStyleProps = {
   verticalAlign?: Api.VerticalAlign;
 };
 
+export type TileTransition = {
+  /**
+   * Animation effect:
+   * - `none` - Tile is added, removed or moved immediately.
+   * - `fade` - Tile fades in or out. Moved tile fades out from the old position and then
+   *   fades in at the new one.
+   * - `slide` - Tile enters or exits through the bottom edge of the component. Moved tile
+   *   slides to the new position.
+   * - `scale` - Tile grows from or shrinks to its center. Moved tile shrinks at the old
+   *   position and then grows at the new one.
+   */
+  effect: Api.TileTransitionEffect;
+  /**
+   * Duration of the animation in milliseconds. Defaults to the duration of the component
+   * `transition`.
+   */
+  durationMs?: number;
+};
+
+export type TileTransitions = {
+  /**
+   * (**default=`{ effect: "fade" }`**) Animation of a tile of a child added in the scene update.
+   */
+  enter?: TileTransition;
+  /**
+   * (**default=`{ effect: "fade" }`**) Animation of a tile of a child removed in the scene update.
+   */
+  exit?: TileTransition;
+  /**
+   * (**default=`{ effect: "slide" }`**) Animation of a tile of a child that changed its
+   * position or size in the scene update.
+   */
+  reorder?: TileTransition;
+};
+
 export type TilesProps = ComponentBaseProps & {
   /**
    * Tiles styling properties
@@ -57,12 +113,17 @@ export type TilesProps = ComponentBaseProps & {
    * effect if the previous scene already contained a `Tiles` component with the same id.
    */
   transition?: Transition;
+  /**
+   * Animations of tiles that are added, removed or moved during the `transition`. Ignored
+   * if `transition` is not defined.
+   */
+  tileTransitions?: TileTransitions;
 };
 
 const Tiles = createSmelterComponent<TilesProps>(sceneBuilder);
 
 function sceneBuilder(
-  { id, style, transition }: TilesProps,
+  { id, style, transition, tileTransitions }: TilesProps,
   children: SceneComponent[]
 ): Api.Component {
   return {
@@ -72,12 +133,31 @@ function sceneBuilder(
     width: style?.width,
     height: style?.height,
     background_color: style?.backgroundColor,
+    fill_strategy: style?.fillStrategy,
     tile_aspect_ratio: style?.tileAspectRatio,
+    max_tile_width: style?.maxTileWidth,
+    max_tile_height: style?.maxTileHeight,
     margin: style?.margin,
     padding: style?.padding,
     horizontal_align: style?.horizontalAlign,
     vertical_align: style?.verticalAlign,
     transition: transition && intoApiTransition(transition),
+    tile_transitions: tileTransitions && intoApiTileTransitions(tileTransitions),
+  };
+}
+
+function intoApiTileTransitions(transitions: TileTransitions): Api.TileTransitions {
+  return {
+    enter: transitions.enter && intoApiTileTransition(transitions.enter),
+    exit: transitions.exit && intoApiTileTransition(transitions.exit),
+    reorder: transitions.reorder && intoApiTileTransition(transitions.reorder),
+  };
+}
+
+function intoApiTileTransition(transition: TileTransition): Api.TileTransition {
+  return {
+    effect: transition.effect,
+    duration_ms: transition.durationMs,
   };
 }
 
@@ -1,8 +1,22 @@
 import type * as Api from '../api.js';
 import type { ComponentBaseProps, SceneComponent } from '../component.js';
 import { createSmelterComponent, sceneComponentIntoApi } from '../component.js';
-import type { BoxShadow, Transition } from './common.js';
-import { intoApiBoxShadow, intoApiTransition } from './common.js';
+import type {
+  Background,
+  BorderRadius,
+  BoxShadow,
+  ColorCorrection,
+  KeyframeTrack,
+  Transition,
+} from './common.js';
+import {
+  intoApiBackground,
+  intoApiBorderRadius,
+  intoApiBoxShadow,
+  intoApiColorCorrection,
+  intoApiKeyframeTrack,
+  intoApiTransition,
+} from './common.js';
 
 export type ViewStyleProps = {
   /**
@@ -53,6 +67,25 @@ export type ViewStyleProps = {
    * absolutely positioned, instead of being laid out by its parent.
    */
   rotation?: number;
+  /**
+   * Rotation of a component in degrees around its horizontal axis (like CSS `rotateX`).
+   * Positive values move the top edge away from the viewer. If this field is defined, this
+   * element will be absolutely positioned, instead of being laid out by its parent.
+   */
+  rotationX?: number;
+  /**
+   * Rotation of a component in degrees around its vertical axis (like CSS `rotateY`).
+   * Positive values move the right edge away from the viewer. If this field is defined, this
+   * element will be absolutely positioned, instead of being laid out by its parent.
+   */
+  rotationY?: number;
+  /**
+   * Distance in pixels between the viewer and the component, used to project components
+   * rotated with `rotationX` or `rotationY`. Smaller values make the effect stronger.
+   * Child components without their own `perspective` inherit it. If this field is defined,
+   * this element will be absolutely positioned, instead of being laid out by its parent.
+   */
+  perspective?: number;
   /**
    * (**default=`"hidden"`**) Controls what happens to content that is too big to fit into an area.
    */
@@ -62,9 +95,14 @@ export type ViewStyleProps = {
    */
   backgroundColor?: string;
   /**
-   * (**default=`0.0`**) Radius of a rounded corner.
+   * Background of the component, either a solid color or a gradient. Can't be used together
+   * with `backgroundColor`.
    */
-  borderRadius?: number;
+  background?: Background;
+  /**
+   * (**default=`0.0`**) Radius of rounded corners. A single number applies to all corners.
+   */
+  borderRadius?: BorderRadius;
   /**
    * (**default=`0.0`**) Border width.
    */
@@ -105,6 +143,33 @@ export type ViewStyleProps = {
    * (**default=`0.0`**) Sets padding for the left of the component.
    */
   paddingLeft?: number;
+  /**
+   * (**default=`0.0`**) Standard deviation in pixels of a Gaussian blur applied to all
+   * inputs, images and other textures inside this component. Background color and borders
+   * are not blurred.
+   */
+  blurRadius?: number;
+  /**
+   * Adjusts brightness, contrast, saturation and hue of all inputs, images and other textures
+   * inside this component. It is applied after the color correction of the child components.
+   * Background color and borders are not modified.
+   */
+  colorCorrection?: ColorCorrection;
+  /**
+   * Id of a LUT registered using `Smelter.registerLut`. It is applied to all inputs, images
+   * and other textures inside this component that do not use a LUT of their own.
+   */
+  lutId?: Api.RendererId;
+  /**
+   * (**default=`1.0`**) Opacity of this component and all of its children in the range from
+   * `0.0` (fully transparent) to `1.0` (fully opaque).
+   */
+  opacity?: number;
+  /**
+   * (**default=`"normal"`**) Defines how this component, including its background, borders
+   * and children, is composited with the content rendered below it.
+   */
+  blendMode?: Api.BlendMode;
 };
 
 export type ViewProps = ComponentBaseProps & {
@@ -117,12 +182,17 @@ export type ViewProps = ComponentBaseProps & {
    * effect if the previous scene already contained a `View` component with the same id.
    */
   transition?: Transition;
+  /**
+   * Keyframe animations of the component properties. They are evaluated on every frame,
+   * so multi-step animations do not require scene updates.
+   */
+  keyframes?: KeyframeTrack[];
 };
 
 const View = createSmelterComponent<ViewProps>(sceneBuilder);
 
 function sceneBuilder(
-  { id, style = {}, transition }: ViewProps,
+  { id, style = {}, transition, keyframes }: ViewProps,
   children: SceneComponent[]
 ): Api.Component {
   return {
@@ -139,11 +209,16 @@ function sceneBuilder(
     left: style.left,
 
     rotation: style.rotation,
+    rotation_x: style.rotationX,
+    rotation_y: style.rotationY,
+    perspective: style.perspective,
     overflow: style.overflow,
     background_color: style.backgroundColor,
+    background: style.background && intoApiBackground(style.background),
     transition: transition && intoApiTransition(transition),
+    keyframes: keyframes?.map(intoApiKeyframeTrack),
 
-    border_radius: style.borderRadius,
+    border_radius: style.borderRadius && intoApiBorderRadius(style.borderRadius),
     border_width: style.borderWidth,
     border_color: style.borderColor,
 
@@ -156,6 +231,12 @@ function sceneBuilder(
     padding_bottom: style.paddingBottom,
     padding_right: style.paddingRight,
     padding_left: style.paddingLeft,
+
+    blur_radius: style.blurRadius,
+    color_correction: style.colorCorrection && intoApiColorCorrection(style.colorCorrection),
+    lut_id: style.lutId,
+    opacity: style.opacity,
+    blend_mode: style.blendMode,
   };
 }
 
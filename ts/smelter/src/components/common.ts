@@ -26,6 +26,206 @@ export function intoApiTransition(transition: Transition): Api.Transition {
   };
 }
 
+export interface KeyframeTrack {
+  /**
+   * Animated property. `top`, `bottom`, `left` and `right` can be animated only if they are
+   * defined on the component, and rotations only if the component is absolutely positioned.
+   */
+  property: AnimatedProperty;
+  /**
+   * List of keyframes sorted by `timeMs`. At least one keyframe is required.
+   */
+  keyframes: Keyframe[];
+  /**
+   * (**default=`false`**) Restart the animation after the last keyframe.
+   */
+  repeat?: boolean;
+}
+
+export interface Keyframe {
+  /**
+   * Time in milliseconds since the start of the animation.
+   */
+  timeMs: number;
+  /**
+   * Value of the property at this keyframe.
+   */
+  value: number;
+  /**
+   * (**default=`"linear"`**) Easing function used to interpolate between this keyframe
+   * and the next one.
+   */
+  easingFunction?: EasingFunction | null;
+}
+
+export type AnimatedProperty =
+  | 'top'
+  | 'bottom'
+  | 'left'
+  | 'right'
+  | 'width'
+  | 'height'
+  | 'rotation'
+  | 'rotationX'
+  | 'rotationY'
+  | 'opacity'
+  | 'blurRadius';
+
+export function intoApiKeyframeTrack(track: KeyframeTrack): Api.KeyframeTrack {
+  return {
+    property: intoApiAnimatedProperty(track.property),
+    keyframes: track.keyframes.map(keyframe => ({
+      time_ms: keyframe.timeMs,
+      value: keyframe.value,
+      easing_function: keyframe.easingFunction
+        ? intoApiEasingFunction(keyframe.easingFunction)
+        : undefined,
+    })),
+    repeat: track.repeat,
+  };
+}
+
+function intoApiAnimatedProperty(property: AnimatedProperty): Api.AnimatedProperty {
+  switch (property) {
+    case 'rotationX':
+      return 'rotation_x';
+    case 'rotationY':
+      return 'rotation_y';
+    case 'blurRadius':
+      return 'blur_radius';
+    default:
+      return property;
+  }
+}
+
+/**
+ * Pan and zoom animation (Ken Burns effect). The visible part of the component is moved
+ * and resized from the `start` to the `end` rectangle and stretched to the size of the
+ * component. The animation starts when the component is mounted and continues across
+ * re-renders as long as it does not change and the component keeps the same `id`.
+ */
+export interface KenBurns {
+  /**
+   * Part of the component visible at the start of the animation.
+   */
+  start: NormalizedRect;
+  /**
+   * Part of the component visible at the end of the animation and after it finishes.
+   */
+  end: NormalizedRect;
+  /**
+   * Duration of the animation in milliseconds.
+   */
+  durationMs: number;
+  /**
+   * (**default=`"linear"`**) Easing function to be used for the animation.
+   */
+  easingFunction?: EasingFunction | null;
+}
+
+/**
+ * Rectangle in coordinates relative to the size of a component, where `0.0` is the top/left
+ * edge and `1.0` is the bottom/right edge of the component.
+ */
+export interface NormalizedRect {
+  top: number;
+  left: number;
+  width: number;
+  height: number;
+}
+
+/**
+ * Part of a source that is kept, everything outside of it is cut off. `pixels` rectangle is
+ * defined in pixels of the source, `normalized` rectangle is relative to the size of the
+ * source, where `0.0` is the top/left edge and `1.0` is the bottom/right edge.
+ */
+export interface SourceCrop {
+  type: 'pixels' | 'normalized';
+  top: number;
+  left: number;
+  width: number;
+  height: number;
+}
+
+/**
+ * Fill of the area of a component. `linear_gradient` follows the CSS `linear-gradient`
+ * function. `radial_gradient` is an ellipse with the same aspect ratio as the component,
+ * its last stop is placed at the corner that is the farthest from the center.
+ */
+export type Background =
+  | {
+      type: 'color';
+      /**
+       * Color in `RGB` or `RGBA` format.
+       */
+      color: string;
+    }
+  | {
+      type: 'linear_gradient';
+      /**
+       * (**default=`180.0`**) Direction of the gradient line in degrees. `0` goes from the
+       * bottom to the top, `90` from the left to the right.
+       */
+      angle?: number;
+      /**
+       * List of 2 to 8 color stops.
+       */
+      stops: GradientStop[];
+    }
+  | {
+      type: 'radial_gradient';
+      /**
+       * (**default=`0.5`**) Horizontal position of the center relative to the width of
+       * the component, where `0.0` is the left edge and `1.0` is the right edge.
+       */
+      centerX?: number;
+      /**
+       * (**default=`0.5`**) Vertical position of the center relative to the height of
+       * the component, where `0.0` is the top edge and `1.0` is the bottom edge.
+       */
+      centerY?: number;
+      /**
+       * List of 2 to 8 color stops.
+       */
+      stops: GradientStop[];
+    };
+
+export type GradientStop = {
+  /**
+   * Color in `RGB` or `RGBA` format.
+   */
+  color: string;
+  /**
+   * Position of the stop on the gradient line in the range from 0 to 1. Positions have
+   * to be in ascending order. If not provided, the first stop is placed at 0, the last at 1,
+   * and others are evenly spaced between their neighbors.
+   */
+  position?: number;
+};
+
+export function intoApiBackground(background: Background): Api.Background {
+  if (background.type === 'radial_gradient') {
+    return {
+      type: 'radial_gradient',
+      center_x: background.centerX,
+      center_y: background.centerY,
+      stops: background.stops,
+    };
+  }
+  return background;
+}
+
+export function intoApiKenBurns(kenBurns: KenBurns): Api.KenBurns {
+  return {
+    start: kenBurns.start,
+    end: kenBurns.end,
+    duration_ms: kenBurns.durationMs,
+    easing_function: kenBurns.easingFunction
+      ? intoApiEasingFunction(kenBurns.easingFunction)
+      : undefined,
+  };
+}
+
 export type EasingFunction =
   | 'linear'
   | 'bounce'
@@ -54,11 +254,37 @@ export function intoApiEasingFunction(easing: EasingFunction): Api.EasingFunctio
   }
 }
 
+export type BorderRadius =
+  | number
+  | {
+      topLeft?: number;
+      topRight?: number;
+      bottomRight?: number;
+      bottomLeft?: number;
+    };
+
+export function intoApiBorderRadius(borderRadius: BorderRadius): Api.BorderRadius {
+  if (typeof borderRadius === 'number') {
+    return borderRadius;
+  }
+  return {
+    top_left: borderRadius.topLeft,
+    top_right: borderRadius.topRight,
+    bottom_right: borderRadius.bottomRight,
+    bottom_left: borderRadius.bottomLeft,
+  };
+}
+
 export interface BoxShadow {
   offsetX?: number | null;
   offsetY?: number | null;
   color?: string | null;
   blurRadius?: number | null;
+  /**
+   * (**default=`0.0`**) Distance in pixels by which the shadow is expanded in all directions
+   * before it is blurred. Negative values shrink the shadow.
+   */
+  spread?: number | null;
 }
 
 export function intoApiBoxShadow(boxShadows: BoxShadow[]): Api.BoxShadow[] {
@@ -67,5 +293,66 @@ export function intoApiBoxShadow(boxShadows: BoxShadow[]): Api.BoxShadow[] {
     offset_y: boxShadow.offsetY,
     color: boxShadow.color,
     blur_radius: boxShadow.blurRadius,
+    spread: boxShadow.spread,
   }));
 }
+
+export interface ChromaKey {
+  /**
+   * (**default=`"#00FF00"`**) Color that should be removed in `RGB` format.
+   */
+  keyColor?: string | null;
+  /**
+   * (**default=`0.4`**) How close to the key color (in the range 0 to 1) a pixel needs to be
+   * to become fully transparent.
+   */
+  similarity?: number | null;
+  /**
+   * (**default=`0.08`**) Width of the range over which pixels fade from fully transparent to
+   * fully opaque.
+   */
+  smoothness?: number | null;
+  /**
+   * (**default=`0.1`**) Reduces the key color reflected onto the foreground. Set to `0` to
+   * disable.
+   */
+  spillReduction?: number | null;
+}
+
+export function intoApiChromaKey(chromaKey: ChromaKey): Api.ChromaKey {
+  return {
+    key_color: chromaKey.keyColor,
+    similarity: chromaKey.similarity,
+    smoothness: chromaKey.smoothness,
+    spill_reduction: chromaKey.spillReduction,
+  };
+}
+
+export interface ColorCorrection {
+  /**
+   * (**default=`0`**) Value in the range -1 to 1 added to all color channels.
+   */
+  brightness?: number | null;
+  /**
+   * (**default=`1`**) Multiplier of the distance from the middle gray. Values below `1`
+   * decrease and values above `1` increase the contrast.
+   */
+  contrast?: number | null;
+  /**
+   * (**default=`1`**) Multiplier of the color intensity. `0` produces a grayscale image.
+   */
+  saturation?: number | null;
+  /**
+   * (**default=`0`**) Rotation of the hue in degrees.
+   */
+  hueShift?: number | null;
+}
+
+export function intoApiColorCorrection(colorCorrection: ColorCorrection): Api.ColorCorrection {
+  return {
+    brightness: colorCorrection.brightness,
+    contrast: colorCorrection.contrast,
+    saturation: colorCorrection.saturation,
+    hue_shift: colorCorrection.hueShift,
+  };
+}
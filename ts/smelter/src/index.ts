@@ -5,8 +5,23 @@ import InputStream, { InputStreamProps } from './components/InputStream.js';
 import Rescaler, { RescalerProps } from './components/Rescaler.js';
 import WebView, { WebViewProps } from './components/WebView.js';
 import Shader, { ShaderParam, ShaderParamStructField, ShaderProps } from './components/Shader.js';
-import Tiles, { TilesProps } from './components/Tiles.js';
-import { EasingFunction, Transition } from './components/common.js';
+import Tiles, { TileTransition, TileTransitions, TilesProps } from './components/Tiles.js';
+import AudioVisualizer, {
+  AudioVisualizerMode,
+  AudioVisualizerProps,
+} from './components/AudioVisualizer.js';
+import {
+  AnimatedProperty,
+  Background,
+  EasingFunction,
+  GradientStop,
+  KenBurns,
+  Keyframe,
+  KeyframeTrack,
+  NormalizedRect,
+  SourceCrop,
+  Transition,
+} from './components/common.js';
 import {
   useAudioInput,
   useInputStreams,
@@ -61,6 +76,11 @@ export {
   ShaderProps,
   Tiles,
   TilesProps,
+  TileTransitions,
+  TileTransition,
+  AudioVisualizer,
+  AudioVisualizerProps,
+  AudioVisualizerMode,
   Show,
   ShowProps,
   Slide,
@@ -73,4 +93,17 @@ export {
 
 export { useInputStreams, useAudioInput, useBlockingTask, useAfterTimestamp, useCurrentTimestamp };
 
-export { ShaderParam, ShaderParamStructField, EasingFunction, Transition };
+export {
+  ShaderParam,
+  ShaderParamStructField,
+  EasingFunction,
+  Transition,
+  KeyframeTrack,
+  Keyframe,
+  AnimatedProperty,
+  KenBurns,
+  NormalizedRect,
+  SourceCrop,
+  Background,
+  GradientStop,
+};
@@ -9,12 +9,27 @@ export enum SmelterEventType {
   VIDEO_INPUT_PAUSED = 'VIDEO_INPUT_PAUSED',
   AUDIO_INPUT_EOS = 'AUDIO_INPUT_EOS',
   VIDEO_INPUT_EOS = 'VIDEO_INPUT_EOS',
+  INPUT_DISCONNECTED = 'INPUT_DISCONNECTED',
+  VIDEO_INPUT_FORMAT_CHANGED = 'VIDEO_INPUT_FORMAT_CHANGED',
+  INPUT_ANCILLARY_DATA = 'INPUT_ANCILLARY_DATA',
   OUTPUT_DONE = 'OUTPUT_DONE',
+  OUTPUT_DISCONNECTED = 'OUTPUT_DISCONNECTED',
   OUTPUT_ERROR = 'OUTPUT_ERROR',
 }
 
 export type SmelterErrorSeverity = 'critical' | 'transient' | 'warning';
 
+export type InputVideoPixelFormat = 'uyvy422' | 'v210' | 'bgra';
+
+export type InputFieldDominance =
+  | 'progressive'
+  | 'progressive_segmented'
+  | 'upper_field_first'
+  | 'lower_field_first'
+  | 'unknown';
+
+export type InputAncillaryDataKind = 'cea_708' | 'scte_104';
+
 export type SmelterEvent =
   | { type: SmelterEventType.AUDIO_INPUT_DELIVERED; inputRef: InputRef }
   | { type: SmelterEventType.VIDEO_INPUT_DELIVERED; inputRef: InputRef }
@@ -24,7 +39,30 @@ export type SmelterEvent =
   | { type: SmelterEventType.VIDEO_INPUT_PAUSED; inputRef: InputRef }
   | { type: SmelterEventType.AUDIO_INPUT_EOS; inputRef: InputRef }
   | { type: SmelterEventType.VIDEO_INPUT_EOS; inputRef: InputRef }
+  | { type: SmelterEventType.INPUT_DISCONNECTED; inputRef: InputRef }
+  | {
+      type: SmelterEventType.VIDEO_INPUT_FORMAT_CHANGED;
+      inputRef: InputRef;
+      displayMode: string;
+      width: number;
+      height: number;
+      framerate: { num: number; den: number };
+      pixelFormat: InputVideoPixelFormat;
+      fieldDominance: InputFieldDominance;
+    }
+  | {
+      type: SmelterEventType.INPUT_ANCILLARY_DATA;
+      inputRef: InputRef;
+      kind: InputAncillaryDataKind;
+      ptsMs: number;
+      lineNumber: number;
+      /**
+       * Hex encoded user data words of the packet.
+       */
+      data: string;
+    }
   | { type: SmelterEventType.OUTPUT_DONE; outputId: string }
+  | { type: SmelterEventType.OUTPUT_DISCONNECTED; outputId: string }
   | {
       type: SmelterEventType.OUTPUT_ERROR;
       outputId: string;
@@ -1,7 +1,12 @@
 import type * as Api from '../api.js';
 import type { InputHlsDecoderMap } from './input/hls.js';
-import type { InputMp4DecoderMap } from './input/mp4.js';
-import type { InputRtpAudioOptions, InputRtpVideoOptions } from './input/rtp.js';
+import type { InputMp4DecoderMap, Mp4InputDecryption } from './input/mp4.js';
+import type {
+  InputRtpAudioOptions,
+  InputRtpVideoOptions,
+  RtpPayloadTypes,
+  RtpSocketOptions,
+} from './input/rtp.js';
 import type { SideChannel } from './input/common.js';
 import type { InputWhipVideoOptions } from './input/whip.js';
 import type { InputWhepVideoOptions } from './input/whep.js';
@@ -55,6 +60,14 @@ export type RegisterRtpInput = {
    * Enable side channel for video and/or audio track.
    */
   sideChannel?: SideChannel;
+  /**
+   * Socket buffer sizes and DSCP marking.
+   */
+  socketOptions?: RtpSocketOptions | null;
+  /**
+   * (**default=`{ video: 96, audio: 97 }`**) Payload types of video and audio packets.
+   */
+  payloadTypes?: RtpPayloadTypes | null;
 };
 
 export type RegisterMp4Input = {
@@ -97,6 +110,11 @@ export type RegisterMp4Input = {
    * Enable side channel for video and/or audio track.
    */
   sideChannel?: SideChannel;
+  /**
+   * Decryption key for files protected with Common Encryption (CENC). Supported schemes
+   * are `cenc`, `cens`, `cbc1` and `cbcs`.
+   */
+  decryption?: Mp4InputDecryption | null;
 };
 
 export type RegisterHlsInput = {
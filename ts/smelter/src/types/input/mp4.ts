@@ -3,3 +3,15 @@ import type { H264Decoder } from './common.js';
 export type InputMp4DecoderMap = {
   h264?: H264Decoder;
 };
+
+export type Mp4InputDecryption = {
+  /**
+   * 128-bit content key as a hex string (32 characters).
+   */
+  key: string;
+  /**
+   * 128-bit key ID as a hex string (32 characters). If provided, it has to match
+   * the key ID of encrypted tracks in the file.
+   */
+  keyId?: string | null;
+};
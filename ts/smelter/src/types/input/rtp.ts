@@ -1,6 +1,14 @@
 import type * as Api from '../../api.js';
 
-export type RtpVideoDecoder = 'ffmpeg_h264' | 'ffmpeg_vp8' | 'ffmpeg_vp9' | 'vulkan_h264';
+export type RtpVideoDecoder =
+  | 'ffmpeg_h264'
+  | 'ffmpeg_vp8'
+  | 'ffmpeg_vp9'
+  | 'vulkan_h264'
+  /**
+   * Decoder registered for the video payload type in a Smelter instance embedded as a library.
+   */
+  | 'custom';
 
 export type InputRtpVideoOptions = {
   decoder: RtpVideoDecoder;
@@ -8,7 +16,17 @@ export type InputRtpVideoOptions = {
 
 export type InputRtpAudioOptions =
   | { decoder: 'opus' }
-  | ({ decoder: 'aac' } & InputRtpAudioAacOptions);
+  | ({ decoder: 'aac' } & InputRtpAudioAacOptions)
+  | {
+      /**
+       * Decoder registered for the audio payload type in a Smelter instance embedded as a library.
+       */
+      decoder: 'custom';
+      /**
+       * RTP clock rate of the audio track.
+       */
+      clockRate: number;
+    };
 
 export type InputRtpAudioAacOptions = {
   /**
@@ -34,3 +52,32 @@ export type InputRtpAudioAacOptions = {
    */
   rtpMode?: Api.AacRtpMode | null;
 };
+
+export type RtpSocketOptions = {
+  /**
+   * Size of the socket receive buffer (`SO_RCVBUF`) in bytes. Larger buffers reduce packet
+   * loss on high-bitrate streams. OS may clamp this value, actually applied size is logged.
+   * For UDP inputs defaults to 16MiB, otherwise the OS default is used.
+   */
+  receiveBufferSizeBytes?: number | null;
+  /**
+   * Size of the socket send buffer (`SO_SNDBUF`) in bytes. OS may clamp this value,
+   * actually applied size is logged.
+   */
+  sendBufferSizeBytes?: number | null;
+  /**
+   * DSCP value (0-63) used to mark outgoing packets for QoS on managed networks.
+   */
+  dscp?: number | null;
+};
+
+export type RtpPayloadTypes = {
+  /**
+   * (**default=`96`**) Payload type of the video track.
+   */
+  video?: number | null;
+  /**
+   * (**default=`97`**) Payload type of the audio track.
+   */
+  audio?: number | null;
+};
@@ -2,7 +2,12 @@ import type * as Api from '../api.js';
 import type { Mp4AudioOptions, Mp4VideoOptions } from './output/mp4.js';
 import type { HlsAudioOptions, HlsVideoOptions } from './output/hls.js';
 import type { RtmpClientAudioOptions, RtmpClientVideoOptions } from './output/rtmp.js';
-import type { RtpAudioOptions, RtpVideoOptions } from './output/rtp.js';
+import type {
+  RtpAudioOptions,
+  RtpPayloadTypes,
+  RtpSocketOptions,
+  RtpVideoOptions,
+} from './output/rtp.js';
 import type { WhipAudioOptions, WhipVideoOptions } from './output/whip.js';
 import type { WhepAudioOptions, WhepVideoOptions } from './output/whep.js';
 
@@ -31,6 +36,14 @@ export type RegisterRtpOutput = {
   transportProtocol?: Api.TransportProtocol;
   video?: RtpVideoOptions;
   audio?: RtpAudioOptions;
+  /**
+   * Socket buffer sizes and DSCP marking.
+   */
+  socketOptions?: RtpSocketOptions;
+  /**
+   * (**default=`{ video: 96, audio: 97 }`**) Payload types of video and audio packets.
+   */
+  payloadTypes?: RtpPayloadTypes;
 };
 
 export type RegisterMp4Output = {
@@ -46,6 +59,17 @@ export type RegisterMp4Output = {
    * Audio track configuration.
    */
   audio?: Mp4AudioOptions;
+  /**
+   * (**default=`true`**) Write `moov` atom before media data, so the file can be played progressively over HTTP.
+   * The file is rewritten when the output ends, which takes additional time and disk space for long recordings.
+   */
+  fastStart?: boolean;
+  /**
+   * Interleave audio and video samples in chunks of at most this duration (in milliseconds).
+   * Samples are written in time order across tracks, which improves compatibility with players
+   * that do minimal buffering. By default, samples are written in the order they are encoded.
+   */
+  interleaveDurationMs?: number;
   /**
    * Raw FFmpeg muxer options. See [docs](https://ffmpeg.org/ffmpeg-formats.html) for more.
    */
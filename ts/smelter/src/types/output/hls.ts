@@ -14,6 +14,11 @@ export type HlsVideoOptions = {
    * Video encoder options.
    */
   encoder: HlsVideoEncoderOptions;
+  /**
+   * (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame.
+   * Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+   */
+  debugOverlay?: boolean;
 };
 
 export type HlsVideoEncoderOptions =
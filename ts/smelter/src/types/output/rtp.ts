@@ -1,6 +1,8 @@
 import type * as Api from '../../api.js';
 import type { OutputEndCondition, VideoEncoderBitrate } from './common.js';
 
+export type { RtpPayloadTypes, RtpSocketOptions } from '../input/rtp.js';
+
 export type RtpVideoOptions = {
   /**
    * Output resolution in pixels.
@@ -14,6 +16,11 @@ export type RtpVideoOptions = {
    * Video encoder options.
    */
   encoder: RtpVideoEncoderOptions;
+  /**
+   * (**default=`false`**) Burn frame index and PTS into the top-left corner of every frame.
+   * Useful for validating outputs frame-by-frame. Registering the output fails if it is combined with `vulkan_h264` encoder.
+   */
+  debugOverlay?: boolean;
 };
 
 export type RtpVideoEncoderOptions =
@@ -3,14 +3,31 @@ import type * as Api from '../api.js';
 export type RegisterShader = Api.ShaderSpec;
 
 export type RegisterImage = {
-  assetType: 'png' | 'jpeg' | 'svg' | 'gif' | 'auto';
+  assetType: 'png' | 'jpeg' | 'svg' | 'gif' | 'webp' | 'avif' | 'auto';
   url?: string;
   serverPath?: string;
 };
 
 export type ImageAssetType = RegisterImage['assetType'];
 
-export const imageAssetTypes: ImageAssetType[] = ['png', 'jpeg', 'svg', 'gif', 'auto'];
+export const imageAssetTypes: ImageAssetType[] = [
+  'png',
+  'jpeg',
+  'svg',
+  'gif',
+  'webp',
+  'avif',
+  'auto',
+];
+
+/**
+ * 3D color lookup table in the `.cube` format.
+ */
+export type RegisterLut = {
+  url?: string;
+  serverPath?: string;
+};
+
 export type RegisterWebRenderer = {
   url: string;
   resolution: Api.Resolution;